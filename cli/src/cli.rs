@@ -264,6 +264,24 @@ impl KaspaCli {
         Ok(())
     }
 
+    async fn exec_commands(self: &Arc<Self>, commands: Vec<String>) -> Result<()> {
+        for command in commands {
+            self.handlers.execute(self, &command).await?;
+
+            // The RPC client reports a connection before the wallet processes it, so wait
+            // for the wallet in order for the following commands to observe the connected state
+            if self.try_rpc_client().is_some_and(|rpc| rpc.is_connected()) {
+                for _ in 0..100 {
+                    if self.is_connected() {
+                        break;
+                    }
+                    sleep(Duration::from_millis(50)).await;
+                }
+            }
+        }
+        Ok(())
+    }
+
     async fn stop_notification_pipe_task(self: &Arc<Self>) -> Result<()> {
         self.notifications_task_ctl.signal(()).await?;
         Ok(())
@@ -998,6 +1016,34 @@ pub async fn kaspa_cli(terminal_options: TerminalOptions, banner: Option<String>
     Ok(())
 }
 
+/// Executes the given commands one after another and exits, stopping at the first command that fails.
+/// This allows command-line front-ends to run a single wallet operation without an interactive session.
+pub async fn kaspa_cli_exec(terminal_options: TerminalOptions, commands: Vec<String>) -> Result<()> {
+    KaspaCli::init();
+
+    let options = Options::new(terminal_options, None);
+    let cli = KaspaCli::try_new_arc(options).await?;
+
+    cli.register_handlers()?;
+    cli.start().await?;
+
+    // the terminal keeps running while the commands execute so that they can prompt
+    // the user (i.e. for wallet secrets), and exits once the last command completes
+    let (sender, receiver) = oneshot();
+    let this = cli.clone();
+    workflow_core::task::spawn(async move {
+        let result = this.exec_commands(commands).await;
+        this.term().exit().await;
+        sender.send(result).await.ok();
+    });
+    cli.run().await?;
+    let result = receiver.recv().await.map_err(|err| Error::ChannelError(err.to_string()))?;
+
+    cli.stop().await?;
+
+    result
+}
+
 mod panic_handler {
     use regex::Regex;
     use wasm_bindgen::prelude::*;
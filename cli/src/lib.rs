@@ -12,5 +12,5 @@ pub mod result;
 pub mod utils;
 mod wizards;
 
-pub use cli::{kaspa_cli, KaspaCli, Options, TerminalOptions, TerminalTarget};
+pub use cli::{kaspa_cli, kaspa_cli_exec, KaspaCli, Options, TerminalOptions, TerminalTarget};
 pub use workflow_terminal::Terminal;
@@ -2,13 +2,47 @@ cfg_if::cfg_if! {
     if #[cfg(target_arch = "wasm32")] {
         fn main() {}
     } else {
-        use kaspa_cli_lib::{kaspa_cli, TerminalOptions};
+        use clap::Parser;
+        use kaspa_cli_lib::{kaspa_cli, kaspa_cli_exec, TerminalOptions};
+
+        /// Kaspa CLI. Starts an interactive session unless commands are given with --exec, in which case
+        /// they are executed in order and the process exits, e.g.:
+        ///
+        ///   kaspa-cli --exec "connect ws://127.0.0.1:17110" --exec "rpc get-info"
+        #[derive(Parser, Debug)]
+        #[command(author, version, about, verbatim_doc_comment)]
+        struct Args {
+            /// Execute <COMMAND>, may be given multiple times
+            #[arg(long = "exec", value_name = "COMMAND")]
+            commands: Vec<String>,
+        }
 
         #[tokio::main]
         async fn main() {
-            let result = kaspa_cli(TerminalOptions::new().with_prompt("$ "), None).await;
+            let Args { commands } = Args::parse();
+
+            let result = if commands.is_empty() {
+                kaspa_cli(TerminalOptions::new().with_prompt("$ "), None).await
+            } else {
+                kaspa_cli_exec(TerminalOptions::new().with_prompt(""), commands).await
+            };
             if let Err(err) = result {
                 println!("{err}");
+                std::process::exit(1);
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn test_exec_commands() {
+                assert!(Args::parse_from(["kaspa-cli"]).commands.is_empty());
+                let args = Args::parse_from(["kaspa-cli", "--exec", "connect ws://127.0.0.1:17110", "--exec", "rpc get-info"]);
+                assert_eq!(args.commands, vec!["connect ws://127.0.0.1:17110", "rpc get-info"]);
+                assert!(Args::try_parse_from(["kaspa-cli", "--exec"]).is_err());
+                assert!(Args::try_parse_from(["kaspa-cli", "rpc"]).is_err());
             }
         }
     }
@@ -0,0 +1,25 @@
+use crate::imports::*;
+
+#[derive(Default, Handler)]
+#[help("Display the balance of the currently selected account")]
+pub struct Balance;
+
+impl Balance {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if !ctx.is_connected() {
+            return Err(Error::custom("balance requires a connection to a node, please use 'connect' first"));
+        }
+
+        let account = ctx.wallet().account()?;
+        let BalanceStrings { mature, pending } = account.balance_as_strings(None)?;
+        tprintln!(ctx, "{}", account.name_with_id());
+        tprintln!(ctx, "  mature:  {}", style(mature).green());
+        if let Some(pending) = pending {
+            tprintln!(ctx, "  pending: {}", style(pending).yellow());
+        }
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use crate::imports::*;
 
 pub mod account;
 pub mod address;
+pub mod balance;
 pub mod broadcast;
 pub mod close;
 pub mod connect;
@@ -57,9 +58,9 @@ pub fn register_handlers(cli: &Arc<KaspaCli>) -> Result<()> {
         cli,
         cli.handlers(),
         [
-            account, address, close, connect, details, disconnect, estimate, exit, export, guide, help, history, rpc, list, miner,
-            message, monitor, mute, network, node, open, ping, pskb, reload, select, send, server, settings, sweep, track, transfer,
-            wallet,
+            account, address, balance, close, connect, details, disconnect, estimate, exit, export, guide, help, history, rpc, list,
+            miner, message, monitor, mute, network, node, open, ping, pskb, reload, select, send, server, settings, sweep, track,
+            transfer, wallet,
             // halt,
             // theme,  start, stop
         ]
@@ -32,28 +32,18 @@ impl Pskb {
                 if argv.len() < 2 || argv.len() > 3 {
                     return self.display_help(ctx, argv).await;
                 }
-                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(None).await?;
-                let _ = ctx.notifier().show(Notification::Processing).await;
-
                 let address = Address::try_from(argv.first().unwrap().as_str())?;
                 let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.get(1))?;
                 let outputs = PaymentOutputs::from((address, amount_sompi));
                 let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(2))?.unwrap_or(0);
                 let abortable = Abortable::default();
 
+                // The transactions are left unsigned, so watch-only accounts can create PSKBs to be signed offline
                 let account: Arc<dyn Account> = ctx.wallet().account()?;
-                let signer = account
-                    .pskb_from_send_generator(
-                        outputs.into(),
-                        priority_fee_sompi.into(),
-                        None,
-                        wallet_secret.clone(),
-                        payment_secret.clone(),
-                        &abortable,
-                    )
-                    .await?;
+                let _ = ctx.notifier().show(Notification::Processing).await;
+                let pskb = account.pskb_create(outputs.into(), priority_fee_sompi.into(), None, &abortable).await?;
 
-                match signer.serialize() {
+                match pskb.serialize() {
                     Ok(encoded) => tprintln!(ctx, "{encoded}"),
                     Err(e) => return Err(e.into()),
                 }
@@ -247,7 +237,7 @@ impl Pskb {
     async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
         ctx.term().help(
             &[
-                ("pskb create <address> <amount> <priority fee>", "Create a PSKB from single send transaction"),
+                ("pskb create <address> <amount> <priority fee>", "Create an unsigned PSKB from single send transaction, also from watch-only accounts"),
                 ("pskb sign <pskb>", "Sign given PSKB"),
                 ("pskb send <pskb>", "Broadcast bundled transactions"),
                 ("pskb debug <payload>", "Print PSKB debug view"),
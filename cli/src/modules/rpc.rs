@@ -1,10 +1,125 @@
 use crate::imports::*;
-use convert_case::{Case, Casing};
+use convert_case::{Boundary, Case, Casing};
 use kaspa_rpc_core::api::ops::RpcApiOps;
+use kaspa_rpc_core::Notification as RpcNotification;
 
-#[derive(Default, Handler)]
-#[help("Execute RPC commands against the connected Kaspa node")]
-pub struct Rpc;
+/// Generates the list of RPC methods callable with a JSON request along with the function dispatching
+/// a JSON request to the corresponding `*_call` method of the RPC API
+macro_rules! json_rpc_methods {
+    ([$($op:ident),* $(,)?]) => {
+        paste::paste! {
+            const JSON_RPC_METHODS: &[RpcApiOps] = &[$(RpcApiOps::$op),*];
+
+            /// Deserializes `request` into the request type of `op`, executes it and returns the
+            /// response serialized to JSON, or `None` if `op` is not an RPC method
+            async fn call_json(rpc: &Arc<DynRpcApi>, op: RpcApiOps, request: &str) -> Result<Option<String>> {
+                let request = if request.is_empty() { "{}" } else { request };
+                let response = match op {
+                    $(RpcApiOps::$op => {
+                        let request: kaspa_rpc_core::[<$op Request>] = serde_json::from_str(request)?;
+                        serde_json::to_string_pretty(&rpc.[<$op:snake _call>](None, request).await?)?
+                    })*
+                    _ => return Ok(None),
+                };
+                Ok(Some(response))
+            }
+        }
+    };
+}
+
+json_rpc_methods!([
+    Ping,
+    AddPeer,
+    Ban,
+    DisconnectPeer,
+    EstimateNetworkHashesPerSecond,
+    GetBalanceByAddress,
+    GetBalancesByAddresses,
+    GetBlock,
+    GetBlockCount,
+    GetBlockDagInfo,
+    GetBlocks,
+    GetBlockTemplate,
+    GetChainBlockByScore,
+    GetBlockSerialized,
+    SubmitBlockSerialized,
+    GetTransactionSerialized,
+    SubmitTransactionSerialized,
+    GetLocalTransactions,
+    GetCurrentBlockColor,
+    GetCoinSupply,
+    GetConnectedPeerInfo,
+    GetConnections,
+    GetP2pStats,
+    GetProcessingStats,
+    GetCurrentNetwork,
+    GetDaaScoreTimestampEstimate,
+    GetFeeEstimate,
+    GetFeeEstimateExperimental,
+    GetHeaders,
+    GetInfo,
+    GetMempoolEntries,
+    GetMempoolEntriesByAddresses,
+    GetMempoolEntry,
+    GetMetrics,
+    GetPeerAddresses,
+    GetPeerFlowStates,
+    GetServerInfo,
+    GetSink,
+    GetSinkBlueScore,
+    GetSubnetwork,
+    GetSyncStatus,
+    GetSystemInfo,
+    GetTransactionAcceptanceData,
+    GetUtxosByAddresses,
+    GetVirtualChainFromBlock,
+    ResolveFinalityConflict,
+    SetPeerProtected,
+    Shutdown,
+    SubmitBlock,
+    SubmitTransaction,
+    SubmitTransactionReplacement,
+    Unban,
+]);
+
+#[derive(Default)]
+pub struct Rpc {
+    /// Listener receiving the notifications subscribed to with `rpc subscribe`
+    listener_id: Mutex<Option<ListenerId>>,
+}
+
+#[async_trait]
+impl Handler for Rpc {
+    fn verb(&self, _ctx: &Arc<dyn Context>) -> Option<&'static str> {
+        Some("rpc")
+    }
+
+    fn help(&self, _ctx: &Arc<dyn Context>) -> &'static str {
+        "Execute RPC commands against the connected Kaspa node"
+    }
+
+    async fn complete(&self, _ctx: &Arc<dyn Context>, cmd: &str) -> cli::Result<Option<Vec<String>>> {
+        let argv = cmd.split_whitespace().collect::<Vec<_>>();
+        let partial = if cmd.ends_with(char::is_whitespace) { "" } else { argv.last().copied().unwrap_or_default() };
+        let candidates = match (argv.len(), argv.get(1).copied()) {
+            (1, _) if partial.is_empty() => method_names(),
+            (2, _) if !partial.is_empty() => method_names(),
+            (2, Some("subscribe" | "unsubscribe")) if partial.is_empty() => event_names(),
+            (3, Some("subscribe" | "unsubscribe")) if !partial.is_empty() => event_names(),
+            _ => return Ok(None),
+        };
+        Ok(Some(candidates.into_iter().filter(|candidate| candidate.starts_with(partial)).collect()))
+    }
+
+    async fn stop(self: Arc<Self>, ctx: &Arc<dyn Context>) -> cli::Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        self.unsubscribe_all(&ctx).await.map_err(|e| e.into())
+    }
+
+    async fn handle(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, cmd: &str) -> cli::Result<()> {
+        self.main(ctx, argv, cmd).await.map_err(|e| e.into())
+    }
+}
 
 impl Rpc {
     fn println<T>(&self, ctx: &Arc<KaspaCli>, v: T)
@@ -25,20 +140,46 @@ impl Rpc {
 
         let op_str = argv.remove(0);
 
-        let sanitize = regex::Regex::new(r"\s*rpc\s+\S+\s+").unwrap();
-        let _args = sanitize.replace(cmd, "").trim().to_string();
-        let op_str_uc = op_str.to_case(Case::UpperCamel).to_string();
+        // the raw request is taken from the command line so that JSON arguments are kept intact
+        let sanitize = regex::Regex::new(r"(?i)^\s*rpc\s+\S+\s+").unwrap();
+        let request = if argv.is_empty() { String::new() } else { sanitize.replace(cmd, "").trim().to_string() };
+
+        match op_str.as_str() {
+            "subscribe" => return self.subscribe(&ctx, argv, &request).await,
+            "unsubscribe" => return self.unsubscribe(&ctx, argv).await,
+            _ => {}
+        }
+
+        // digits are kept within their word so that `get-p2p-stats` maps to `GetP2pStats`
+        let op_str_uc = to_upper_camel(&op_str);
         // tprintln!(ctx, "uc: '{op_str_uc}'");
 
         let op = RpcApiOps::from_str(op_str_uc.as_str()).ok_or(Error::custom(format!("No such rpc method: '{op_str}'")))?;
 
+        if request.starts_with('{') {
+            return self.call_json(&ctx, &rpc, op, &op_str, &request).await;
+        }
+
         match op {
             RpcApiOps::Ping => {
                 rpc.ping().await?;
                 tprintln!(ctx, "ok");
             }
             RpcApiOps::GetMetrics => {
-                let result = rpc.get_metrics(true, true, true, true, true, true).await?;
+                let result = rpc
+                    .get_metrics_call(
+                        None,
+                        GetMetricsRequest {
+                            process_metrics: true,
+                            connection_metrics: true,
+                            bandwidth_metrics: true,
+                            consensus_metrics: true,
+                            storage_metrics: true,
+                            custom_metrics: true,
+                            allocation_metrics: true,
+                        },
+                    )
+                    .await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetSystemInfo => {
@@ -70,7 +211,8 @@ impl Rpc {
             //     self.println(&ctx, result);
             // }
             RpcApiOps::GetPeerAddresses => {
-                let result = rpc.get_peer_addresses_call(None, GetPeerAddressesRequest {}).await?;
+                let verbose = argv.first().and_then(|x| x.parse::<bool>().ok()).unwrap_or_default();
+                let result = rpc.get_peer_addresses_call(None, GetPeerAddressesRequest { verbose }).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetSink => {
@@ -86,7 +228,7 @@ impl Rpc {
                 let result = rpc
                     .get_mempool_entries_call(
                         None,
-                        GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: true },
+                        GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: true, subnetwork_id: None },
                     )
                     .await?;
                 self.println(&ctx, result);
@@ -95,6 +237,14 @@ impl Rpc {
                 let result = rpc.get_connected_peer_info_call(None, GetConnectedPeerInfoRequest {}).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetP2pStats => {
+                let result = rpc.get_p2p_stats_call(None, GetP2pStatsRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetProcessingStats => {
+                let result = rpc.get_processing_stats_call(None, GetProcessingStatsRequest {}).await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::AddPeer => {
                 if argv.is_empty() {
                     return Err(Error::custom("Usage: rpc addpeer <ip:port> [true|false for 'is_permanent']"));
@@ -191,29 +341,121 @@ impl Rpc {
                 self.println(&ctx, result);
             }
             RpcApiOps::Ban => {
-                if argv.is_empty() {
-                    return Err(Error::custom("Please specify peer IP address"));
-                }
-                let ip: RpcIpAddress = argv.remove(0).parse()?;
-                let result = rpc.ban_call(None, BanRequest { ip }).await?;
+                let (ip, prefix_length) = argv
+                    .first()
+                    .and_then(|arg| parse_ip_range(arg))
+                    .ok_or_else(|| Error::custom("Usage: rpc ban <ip>[/<prefix length>] [duration in seconds | permanent]"))?;
+                let (duration, is_permanent) = match argv.get(1).map(String::as_str) {
+                    None => (None, false),
+                    Some("permanent") => (None, true),
+                    Some(duration) => (Some(duration.parse::<u64>()?), false),
+                };
+                let result = rpc.ban_call(None, BanRequest { ip, prefix_length, duration, is_permanent }).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::Unban => {
+                let (ip, prefix_length) = argv
+                    .first()
+                    .and_then(|arg| parse_ip_range(arg))
+                    .ok_or_else(|| Error::custom("Usage: rpc unban <ip>[/<prefix length>]"))?;
+                let result = rpc.unban_call(None, UnbanRequest { ip, prefix_length }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetPeerFlowStates => {
+                let peer_id = argv
+                    .first()
+                    .map(|arg| arg.parse::<RpcNodeId>().map_err(|_| Error::custom("Usage: rpc get-peer-flow-states [peer id]")))
+                    .transpose()?;
+                let result = rpc.get_peer_flow_states_call(None, GetPeerFlowStatesRequest { peer_id }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::DisconnectPeer => {
+                let peer_id = argv
+                    .first()
+                    .and_then(|arg| arg.parse::<RpcNodeId>().ok())
+                    .ok_or_else(|| Error::custom("Usage: rpc disconnect-peer <peer id>"))?;
+                let result = rpc.disconnect_peer_call(None, DisconnectPeerRequest { peer_id }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::SetPeerProtected => {
+                let peer_id = argv
+                    .first()
+                    .and_then(|arg| arg.parse::<RpcNodeId>().ok())
+                    .ok_or_else(|| Error::custom("Usage: rpc set-peer-protected <peer id> [true|false]"))?;
+                let is_protected = argv.get(1).and_then(|arg| arg.parse::<bool>().ok()).unwrap_or(true);
+                let result = rpc.set_peer_protected_call(None, SetPeerProtectedRequest { peer_id, is_protected }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionAcceptanceData => {
                 if argv.is_empty() {
-                    return Err(Error::custom("Please specify peer IP address"));
+                    return Err(Error::custom("Please specify at least one transaction id"));
                 }
-                let ip: RpcIpAddress = argv.remove(0).parse()?;
-                let result = rpc.unban_call(None, UnbanRequest { ip }).await?;
+                let transaction_ids =
+                    argv.iter().map(|s| RpcHash::from_hex(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result =
+                    rpc.get_transaction_acceptance_data_call(None, GetTransactionAcceptanceDataRequest { transaction_ids }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetChainBlockByScore => {
+                let score = argv
+                    .first()
+                    .and_then(|arg| arg.parse::<u64>().ok())
+                    .ok_or_else(|| Error::custom("Usage: rpc get-chain-block-by-score <score> [daa|blue]"))?;
+                let by_blue_score = matches!(argv.get(1).map(String::as_str), Some("blue"));
+                let result = rpc.get_chain_block_by_score_call(None, GetChainBlockByScoreRequest { score, by_blue_score }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetBlockSerialized => {
+                let hash = argv.first().ok_or_else(|| Error::custom("Usage: rpc get-block-serialized <block hash>"))?;
+                let hash = RpcHash::from_hex(hash.as_str())?;
+                let result = rpc.get_block_serialized_call(None, GetBlockSerializedRequest { hash }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionSerialized => {
+                let transaction_id = argv
+                    .first()
+                    .ok_or_else(|| Error::custom("Usage: rpc get-transaction-serialized <transaction id> [<block hash>]"))?;
+                let transaction_id = RpcHash::from_hex(transaction_id.as_str())?;
+                let block_hash = argv.get(1).map(|hash| RpcHash::from_hex(hash.as_str())).transpose()?;
+                let result =
+                    rpc.get_transaction_serialized_call(None, GetTransactionSerializedRequest { transaction_id, block_hash }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::SubmitBlockSerialized => {
+                let block_hex = argv.first().ok_or_else(|| Error::custom("Usage: rpc submit-block-serialized <block hex>"))?.clone();
+                let result = rpc
+                    .submit_block_serialized_call(None, SubmitBlockSerializedRequest { block_hex, allow_non_daa_blocks: false })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::SubmitTransactionSerialized => {
+                let transaction_hex =
+                    argv.first().ok_or_else(|| Error::custom("Usage: rpc submit-transaction-serialized <transaction hex>"))?.clone();
+                let result = rpc
+                    .submit_transaction_serialized_call(
+                        None,
+                        SubmitTransactionSerializedRequest { transaction_hex, allow_orphan: false },
+                    )
+                    .await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetInfo => {
                 let result = rpc.get_info_call(None, GetInfoRequest {}).await?;
                 self.println(&ctx, result);
             }
-            // RpcApiOps::EstimateNetworkHashesPerSecond => {
-            //     let result = rpc.estimate_network_hashes_per_second_call(EstimateNetworkHashesPerSecondRequest {  }).await?;
-            //     self.println(&ctx, result);
-            // }
+            RpcApiOps::EstimateNetworkHashesPerSecond => {
+                let window_size = match argv.first() {
+                    Some(window_size) => window_size
+                        .parse::<u32>()
+                        .map_err(|_| Error::custom("Usage: rpc estimate-network-hashes-per-second [window size] [start hash]"))?,
+                    None => 1000,
+                };
+                let start_hash = argv.get(1).map(|hash| RpcHash::from_hex(hash.as_str())).transpose()?;
+                let result = rpc
+                    .estimate_network_hashes_per_second_call(None, EstimateNetworkHashesPerSecondRequest { window_size, start_hash })
+                    .await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetMempoolEntriesByAddresses => {
                 if argv.is_empty() {
                     return Err(Error::custom("Please specify at least one address"));
@@ -251,6 +493,10 @@ impl Rpc {
                     }
                 }
             }
+            RpcApiOps::GetLocalTransactions => {
+                let result = rpc.get_local_transactions_call(None, GetLocalTransactionsRequest {}).await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetFeeEstimate => {
                 let result = rpc.get_fee_estimate_call(None, GetFeeEstimateRequest {}).await?;
                 self.println(&ctx, result);
@@ -270,29 +516,138 @@ impl Rpc {
                 self.println(&ctx, result);
             }
             _ => {
-                tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n");
-                return Ok(());
+                return self.call_json(&ctx, &rpc, op, &op_str, &request).await;
             }
         }
 
-        let prefix = Regex::new(r"(?i)^\s*rpc\s+\S+\s+").unwrap();
-        let _req = prefix.replace(cmd, "").trim().to_string();
+        Ok(())
+    }
+
+    async fn call_json(&self, ctx: &Arc<KaspaCli>, rpc: &Arc<DynRpcApi>, op: RpcApiOps, op_str: &str, request: &str) -> Result<()> {
+        match call_json(rpc, op, request).await? {
+            Some(response) => tprintln!(ctx, "{}", response.crlf()),
+            None => tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n"),
+        }
+        Ok(())
+    }
+
+    async fn subscribe(&self, ctx: &Arc<KaspaCli>, mut argv: Vec<String>, request: &str) -> Result<()> {
+        if argv.is_empty() {
+            return Err(Error::custom("Usage: rpc subscribe <event> [JSON scope]"));
+        }
+        let event = argv.remove(0);
+        let scope = request.strip_prefix(event.as_str()).unwrap_or_default().trim();
+        let scope = parse_scope(&event, if scope.is_empty() { "{}" } else { scope })?;
+
+        let rpc = ctx.wallet().rpc_api();
+        let listener_id = self.listener_id(ctx, &rpc);
+        rpc.start_notify(listener_id, scope).await?;
+        tprintln!(ctx, "subscribed to '{event}'");
+        Ok(())
+    }
+
+    async fn unsubscribe(&self, ctx: &Arc<KaspaCli>, argv: Vec<String>) -> Result<()> {
+        match argv.first().map(String::as_str) {
+            None | Some("all") => self.unsubscribe_all(ctx).await?,
+            Some(event) => {
+                let listener_id = *self.listener_id.lock().unwrap();
+                if let Some(listener_id) = listener_id {
+                    ctx.wallet().rpc_api().stop_notify(listener_id, parse_scope(event, "{}")?).await?;
+                }
+                tprintln!(ctx, "unsubscribed from '{event}'");
+            }
+        }
+        Ok(())
+    }
 
+    async fn unsubscribe_all(&self, ctx: &Arc<KaspaCli>) -> Result<()> {
+        let listener_id = self.listener_id.lock().unwrap().take();
+        if let Some(listener_id) = listener_id {
+            ctx.wallet().rpc_api().unregister_listener(listener_id).await?;
+        }
         Ok(())
     }
 
+    /// Returns the id of the listener printing notifications to the terminal, registering it on first use
+    fn listener_id(&self, ctx: &Arc<KaspaCli>, rpc: &Arc<DynRpcApi>) -> ListenerId {
+        let mut listener_id = self.listener_id.lock().unwrap();
+        if let Some(listener_id) = *listener_id {
+            return listener_id;
+        }
+
+        let (sender, receiver) = workflow_core::channel::unbounded::<RpcNotification>();
+        let id = rpc.register_new_listener(ChannelConnection::new("cli rpc console", sender, ChannelType::Closable));
+        listener_id.replace(id);
+
+        // the channel gets closed when the listener is unregistered, ending the task
+        let term = ctx.term();
+        spawn(async move {
+            while let Ok(notification) = receiver.recv().await {
+                match serde_json::to_string(&notification) {
+                    Ok(json) => term.writeln(json),
+                    Err(err) => term.writeln(format!("unable to serialize notification {notification}: {err}")),
+                }
+            }
+        });
+
+        id
+    }
+
     async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
         // RpcApiOps that do not contain docs are not displayed
         let help = RpcApiOps::into_iter()
-            .filter_map(|op| op.rustdoc().is_not_empty().then_some((op.as_str().to_case(Case::Kebab).to_string(), op.rustdoc())))
+            .filter_map(|op| op.rustdoc().is_not_empty().then_some((to_kebab(op.as_str()), op.rustdoc())))
+            .chain([
+                ("subscribe".to_string(), "Subscribe to an event, printing its notifications (i.e. rpc subscribe block-added)"),
+                ("unsubscribe".to_string(), "Unsubscribe from an event or from all events if none is specified"),
+            ])
             .collect::<Vec<(_, _)>>();
 
         ctx.term().help(&help, None)?;
 
         tprintln!(ctx);
-        tprintln!(ctx, "Please note that not all listed RPC methods are currently implemented");
+        tprintln!(ctx, "Every RPC method accepts its request as a JSON object, for example:");
+        tprintln!(ctx, "  rpc get-block {{\"hash\": \"<hash>\", \"includeTransactions\": false}}");
+        tprintln!(ctx, "  rpc subscribe utxos-changed {{\"addresses\": [\"<address>\"]}}");
+        tprintln!(ctx);
+        tprintln!(ctx, "Available events: {}", event_names().join(", "));
         tprintln!(ctx);
 
         Ok(())
     }
 }
+
+fn to_upper_camel(kebab: &str) -> String {
+    kebab.with_boundaries(&Boundary::defaults()).without_boundaries(&Boundary::digits()).to_case(Case::UpperCamel)
+}
+
+fn to_kebab(pascal: &str) -> String {
+    pascal.from_case(Case::Pascal).without_boundaries(&Boundary::digits()).to_case(Case::Kebab)
+}
+
+/// Kebab-case names of the RPC methods accepting JSON requests
+fn method_names() -> Vec<String> {
+    JSON_RPC_METHODS.iter().map(|op| to_kebab(op.as_str())).chain(["subscribe".to_string(), "unsubscribe".to_string()]).collect()
+}
+
+/// Kebab-case names of the events a subscription can be made to
+fn event_names() -> Vec<String> {
+    RpcApiOps::into_iter().filter_map(|op| op.as_str().strip_suffix("Notification").map(to_kebab)).collect()
+}
+
+/// Builds the subscription scope of a kebab-case event name, along with its JSON parameters if the scope has any
+fn parse_scope(event: &str, scope: &str) -> Result<Scope> {
+    if !event_names().iter().any(|name| name == event) {
+        return Err(Error::custom(format!("No such event: '{event}'")));
+    }
+    let scope: Value = serde_json::from_str(scope)?;
+    Ok(serde_json::from_value(serde_json::json!({ to_upper_camel(event): scope }))?)
+}
+
+/// Parses an `<ip>[/<prefix length>]` argument
+fn parse_ip_range(arg: &str) -> Option<(RpcIpAddress, Option<u8>)> {
+    match arg.split_once('/') {
+        Some((ip, prefix_length)) => Some((ip.parse().ok()?, Some(prefix_length.parse().ok()?))),
+        None => Some((arg.parse().ok()?, None)),
+    }
+}
@@ -1,19 +1,37 @@
 use crate::imports::*;
 
 #[derive(Default, Handler)]
-#[help("Reduces account UTXO size by re-sending all funds to the account's default address")]
+#[help("Reduces account UTXO size by re-sending all funds (or funds of the given addresses) to the account's change address")]
 pub struct Sweep;
 
 impl Sweep {
-    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
 
         let account = ctx.wallet().account()?;
-        let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+
+        let dry_run = argv.first().is_some_and(|arg| arg == "--dry-run");
+        if dry_run {
+            argv.remove(0);
+        }
+        let addresses = if argv.is_empty() {
+            None
+        } else {
+            Some(argv.iter().map(|s| Address::try_from(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?)
+        };
         let abortable = Abortable::default();
+
+        if dry_run {
+            let summary = account.estimate_sweep(addresses, &abortable).await?;
+            tprintln!(ctx, "Sweep estimate - Transactions: {}  {summary}", summary.number_of_generated_transactions());
+            return Ok(());
+        }
+
+        let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
         // let ctx_ = ctx.clone();
         let (summary, _ids) = account
-            .sweep(
+            .sweep_addresses(
+                addresses,
                 wallet_secret,
                 payment_secret,
                 &abortable,
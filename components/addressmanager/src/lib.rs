@@ -2,31 +2,76 @@ mod port_mapping_extender;
 mod stores;
 extern crate self as address_manager;
 
-use std::{collections::HashSet, iter, net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
 
 use address_manager::port_mapping_extender::Extender;
 use igd_next::{
     self as igd, aio::tokio::Tokio, AddAnyPortError, AddPortError, Gateway, GetExternalIpError, GetGenericPortMappingEntryError,
     SearchError,
 };
+use ipnet::IpNet;
 use itertools::{
     Either::{Left, Right},
     Itertools,
 };
 use kaspa_consensus_core::config::Config;
 use kaspa_core::{debug, info, task::tick::TickService, time::unix_now, warn};
-use kaspa_database::prelude::{CachePolicy, StoreResultExtensions, DB};
+use kaspa_database::{
+    migration::{Migration, MigrationResult, MigrationRunner},
+    prelude::{CachePolicy, StoreError, StoreResultExtensions, DB},
+};
 use kaspa_utils::networking::IpAddress;
 use local_ip_address::list_afinet_netifas;
 use parking_lot::Mutex;
-use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, ConnectionBanTimestamp, DbBannedAddressesStore};
+use stores::{
+    address_store::{AddressesSchemaV1Migration, Entry},
+    banned_address_store::{canonical_range, BannedAddressesSchemaV1Migration, BannedAddressesStore, DbBannedAddressesStore},
+    peer_stats_store::{DbPeerStatsStore, PeerStatsStore, PeerStatsStoreReader},
+};
 use thiserror::Error;
 
-pub use stores::NetAddress;
+pub use stores::{banned_address_store::BanEntry, peer_stats_store::PeerStats, NetAddress};
+
+/// Returns the schema migrations of the address manager stores
+pub fn migrations() -> Vec<Box<dyn Migration>> {
+    vec![Box::new(AddressesSchemaV1Migration { now: unix_now() }), Box::new(BannedAddressesSchemaV1Migration)]
+}
 
 const MAX_ADDRESSES: usize = 4096;
+/// The maximum number of peer IPs whose statistics are persisted
+const MAX_PEER_STATS: usize = 2 * MAX_ADDRESSES;
 const MAX_CONNECTION_FAILED_COUNT: u64 = 3;
 
+/// Addresses which were neither advertised to us nor successfully connected to during this period are expired,
+/// as are the statistics of peers we were not connected to during this period
+const ADDRESS_EXPIRY_PERIOD: u64 = 30 * 24 * 60 * 60 * 1000; // 30 days
+/// Addresses we successfully connected to during this period are preferred when selecting outbound peers
+const RECENT_SUCCESS_PERIOD: u64 = 24 * 60 * 60 * 1000; // 24 hours
+/// The selection weight multiplier of addresses recently connected to successfully
+const RECENT_SUCCESS_WEIGHT_FACTOR: f64 = 8.0;
+/// The number of connection attempts per IP family beyond which older outcomes are progressively forgotten
+const FAMILY_CONNECTIVITY_WINDOW: u64 = 64;
+
+/// The ban period applied when no explicit duration is requested
+pub const DEFAULT_BAN_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+/// The minimal prefix lengths of banned IPv4 and IPv6 ranges, so that a single ban cannot isolate the node
+const MIN_BAN_PREFIX_LEN_IPV4: u8 = 8;
+const MIN_BAN_PREFIX_LEN_IPV6: u8 = 16;
+
+/// Returns whether `range` is broader than what may be banned
+pub fn is_ban_range_too_broad(range: IpNet) -> bool {
+    match canonical_range(range) {
+        IpNet::V4(range) => range.prefix_len() < MIN_BAN_PREFIX_LEN_IPV4,
+        IpNet::V6(range) => range.prefix_len() < MIN_BAN_PREFIX_LEN_IPV6,
+    }
+}
+
 const UPNP_DEADLINE_SEC: u64 = 2 * 60;
 const UPNP_EXTEND_PERIOD: u64 = UPNP_DEADLINE_SEC / 2;
 
@@ -51,29 +96,112 @@ pub enum UpnpError {
     GetExternalIpError(#[from] GetExternalIpError),
 }
 
+/// Outcomes of outbound connection attempts to addresses of a single IP family
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConnectivityStats {
+    pub attempts: u64,
+    pub successes: u64,
+}
+
+impl ConnectivityStats {
+    fn record(&mut self, success: bool) {
+        // Halving both counts keeps the rate while letting recent outcomes prevail, e.g. once an IPv6 route is added
+        if self.attempts >= FAMILY_CONNECTIVITY_WINDOW {
+            self.attempts /= 2;
+            self.successes /= 2;
+        }
+        self.attempts += 1;
+        self.successes += success as u64;
+    }
+
+    /// The smoothed rate of successful attempts, which is 1 as long as nothing was attempted
+    pub fn success_rate(&self) -> f64 {
+        (self.successes + 1) as f64 / (self.attempts + 1) as f64
+    }
+}
+
+/// Outbound connectivity per IP family, letting the address selection prefer the families this node can actually reach.
+/// It is kept in memory only since connectivity may well change across restarts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FamilyConnectivity {
+    pub ipv4: ConnectivityStats,
+    pub ipv6: ConnectivityStats,
+}
+
+impl FamilyConnectivity {
+    /// Returns the statistics of the family of `ip`, IPv4-mapped IPv6 addresses counting as IPv4
+    pub fn stats(&self, ip: IpAddress) -> ConnectivityStats {
+        if ip.0.to_canonical().is_ipv4() {
+            self.ipv4
+        } else {
+            self.ipv6
+        }
+    }
+
+    fn record(&mut self, ip: IpAddress, success: bool) {
+        if ip.0.to_canonical().is_ipv4() {
+            self.ipv4.record(success)
+        } else {
+            self.ipv6.record(success)
+        }
+    }
+
+    /// The selection weight multiplier of addresses of the family of `ip`: the success rate of the family relative to the
+    /// best one
+    fn weight_factor(&self, ip: IpAddress) -> f64 {
+        self.stats(ip).success_rate() / self.ipv4.success_rate().max(self.ipv6.success_rate())
+    }
+}
+
 pub struct AddressManager {
     banned_address_store: DbBannedAddressesStore,
+    /// An in-memory copy of the persisted bans, so that ban checks never access the DB
+    bans: HashMap<IpNet, BanEntry>,
     address_store: address_store_with_cache::Store,
+    peer_stats_store: DbPeerStatsStore,
     config: Arc<Config>,
     local_net_addresses: Vec<NetAddress>,
+    family_connectivity: FamilyConnectivity,
 }
 
 impl AddressManager {
-    pub fn new(config: Arc<Config>, db: Arc<DB>, tick_service: Arc<TickService>) -> (Arc<Mutex<Self>>, Option<Extender>) {
+    /// Creates the manager over `db`, applying its pending store migrations first. Fails if the migrations cannot be
+    /// applied since the stores cannot be used with an outdated schema
+    pub fn new(
+        config: Arc<Config>,
+        db: Arc<DB>,
+        tick_service: Arc<TickService>,
+    ) -> MigrationResult<(Arc<Mutex<Self>>, Option<Extender>)> {
+        // The node applies all migrations on startup, so this is a no-op unless the manager is used standalone
+        let report = MigrationRunner::new(db.clone()).with_migrations(migrations()).run()?;
+        for record in report.records {
+            info!("[Address manager] applied store migration {}", record);
+        }
+        // We manage the ban cache ourselves on this level, so we disable the inner builtin cache
+        let banned_address_store = DbBannedAddressesStore::new(db.clone(), CachePolicy::Empty);
+        let bans =
+            banned_address_store.iterator().collect::<Result<_, _>>().map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
         let mut instance = Self {
-            banned_address_store: DbBannedAddressesStore::new(db.clone(), CachePolicy::Count(MAX_ADDRESSES)),
-            address_store: address_store_with_cache::new(db),
+            banned_address_store,
+            bans,
+            address_store: address_store_with_cache::new(db.clone()),
+            peer_stats_store: DbPeerStatsStore::new(db, CachePolicy::Count(MAX_ADDRESSES)),
             local_net_addresses: Vec::new(),
             config,
+            family_connectivity: Default::default(),
         };
+        instance.expire_stale_peer_stats();
+        instance.expire_bans();
 
         let extender = instance.init_local_addresses(tick_service);
 
-        (Arc::new(Mutex::new(instance)), extender)
+        Ok((Arc::new(Mutex::new(instance)), extender))
     }
 
     fn init_local_addresses(&mut self, tick_service: Arc<TickService>) -> Option<Extender> {
         self.local_net_addresses = self.local_addresses().collect();
+        // IPv6 addresses come first, so they are preferred when advertising to peers of unknown family
+        self.local_net_addresses.sort_by_key(|address| address.ip.0.to_canonical().is_ipv4());
 
         let extender = if self.local_net_addresses.is_empty() && !self.config.disable_upnp {
             let (net_address, ExtendHelper { gateway, local_addr, external_port }) = match self.upnp() {
@@ -141,7 +269,7 @@ impl AddressManager {
                 warn!("Error getting network interfaces: {:?}", network_interfaces);
                 return Left(Right(iter::empty()));
             };
-            // TODO: Add Check IPv4 or IPv6 match from Go code
+            // Addresses of both families are kept since an unspecified listen address stands for dual-stack listening
             Right(network_interfaces.into_iter().map(|(_, ip)| IpAddress::from(ip)).filter(|&ip| ip.is_publicly_routable()).map(
                 |ip| {
                     info!("Publicly routable local address found: {}", ip);
@@ -241,17 +369,27 @@ impl AddressManager {
         }
     }
 
-    pub fn best_local_address(&mut self) -> Option<NetAddress> {
-        if self.local_net_addresses.is_empty() {
-            None
-        } else {
-            // TODO: Add logic for finding the best as a function of a peer remote address.
-            // for now, returning the first one
-            Some(self.local_net_addresses[0])
-        }
+    /// Returns the local address to advertise to the peer at `peer_ip`, preferring one of the same IP family since
+    /// the peer evidently reaches it
+    pub fn best_local_address(&self, peer_ip: IpAddress) -> Option<NetAddress> {
+        let peer_is_ipv4 = peer_ip.0.to_canonical().is_ipv4();
+        self.local_net_addresses
+            .iter()
+            .find(|address| address.ip.0.to_canonical().is_ipv4() == peer_is_ipv4)
+            .or(self.local_net_addresses.first())
+            .copied()
+    }
+
+    /// Returns the publicly routable local addresses of all IP families
+    pub fn get_local_addresses(&self) -> Vec<NetAddress> {
+        self.local_net_addresses.clone()
     }
 
-    pub fn add_address(&mut self, address: NetAddress) {
+    /// Adds an address to the store if not already known. `source` is the peer which advertised the address, if any.
+    ///
+    /// The last-seen time of a known address is refreshed only by a successful connection, so that advertisements
+    /// alone cannot keep an unreachable address from expiring
+    pub fn add_address(&mut self, address: NetAddress, source: Option<IpAddress>) {
         if address.ip.is_loopback() || address.ip.is_unspecified() {
             debug!("[Address manager] skipping local address {}", address.ip);
             return;
@@ -262,28 +400,94 @@ impl AddressManager {
         }
 
         // We mark `connection_failed_count` as 0 only after first success
-        self.address_store.set(address, 1);
+        self.address_store.set(Entry::new(address, 1, source, unix_now()));
     }
 
     pub fn mark_connection_failure(&mut self, address: NetAddress) {
+        self.family_connectivity.record(address.ip, false);
         if !self.address_store.has(address) {
             return;
         }
 
-        let new_count = self.address_store.get(address).connection_failed_count + 1;
+        let entry = self.address_store.get(address);
+        let new_count = entry.connection_failed_count + 1;
         if new_count > MAX_CONNECTION_FAILED_COUNT {
             self.address_store.remove(address);
         } else {
-            self.address_store.set(address, new_count);
+            self.address_store.set(Entry { connection_failed_count: new_count, last_attempt: unix_now(), ..entry });
         }
     }
 
     pub fn mark_connection_success(&mut self, address: NetAddress) {
+        self.family_connectivity.record(address.ip, true);
         if !self.address_store.has(address) {
             return;
         }
 
-        self.address_store.set(address, 0);
+        let now = unix_now();
+        let entry = self.address_store.get(address);
+        self.address_store.set(Entry { connection_failed_count: 0, last_seen: now, last_attempt: now, last_success: now, ..entry });
+    }
+
+    /// Removes addresses which were not seen for longer than [`ADDRESS_EXPIRY_PERIOD`], along with peer statistics as such
+    pub fn expire_stale_addresses(&mut self) {
+        let expired = self.address_store.remove_stale(unix_now());
+        if expired > 0 {
+            debug!("[Address manager] expired {} stale addresses", expired);
+        }
+        self.expire_stale_peer_stats();
+        self.expire_bans();
+    }
+
+    /// Removes expired bans. Ban checks already ignore such bans, so this only reclaims their memory and storage
+    fn expire_bans(&mut self) {
+        let now = unix_now();
+        let expired = self.bans.iter().filter(|(_, entry)| entry.is_expired(now)).map(|(range, _)| *range).collect_vec();
+        for range in expired.iter() {
+            self.bans.remove(range);
+            self.banned_address_store.remove(*range).unwrap();
+        }
+        if !expired.is_empty() {
+            debug!("[Address manager] expired {} bans", expired.len());
+        }
+    }
+
+    /// Removes the statistics of peers which were not seen for longer than [`ADDRESS_EXPIRY_PERIOD`], and the least
+    /// recently seen ones beyond [`MAX_PEER_STATS`]
+    fn expire_stale_peer_stats(&mut self) {
+        let now = unix_now();
+        let mut all_stats = self.get_all_peer_stats();
+        all_stats.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.last_seen));
+        let stale = all_stats
+            .into_iter()
+            .enumerate()
+            .filter(|(i, (_, stats))| *i >= MAX_PEER_STATS || now.saturating_sub(stats.last_seen) > ADDRESS_EXPIRY_PERIOD)
+            .map(|(_, (ip, _))| ip)
+            .collect_vec();
+        for ip in stale.iter() {
+            self.peer_stats_store.remove(ip.0).unwrap();
+        }
+        if !stale.is_empty() {
+            debug!("[Address manager] expired the statistics of {} peers", stale.len());
+        }
+    }
+
+    /// Merges the statistics of a connection to `ip` which just ended into the persisted statistics of `ip`
+    pub fn record_peer_session(&mut self, ip: IpAddress, session: PeerStats) {
+        let ip = ip.0.to_canonical();
+        let mut stats = self.peer_stats_store.get(ip).unwrap_option().unwrap_or_default();
+        stats.merge(&PeerStats { last_seen: unix_now(), sessions: 1, ..session });
+        self.peer_stats_store.set(ip, stats).unwrap();
+    }
+
+    /// Returns the persisted statistics of `ip`, if it was ever connected to
+    pub fn get_peer_stats(&self, ip: IpAddress) -> Option<PeerStats> {
+        self.peer_stats_store.get(ip.0.to_canonical()).unwrap_option()
+    }
+
+    /// Returns the persisted statistics of all peers
+    pub fn get_all_peer_stats(&self) -> Vec<(IpAddress, PeerStats)> {
+        self.peer_stats_store.iterator().map(|res| res.unwrap()).map(|(ip, stats)| (IpAddress::from(ip), stats)).collect_vec()
     }
 
     pub fn iterate_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
@@ -291,39 +495,81 @@ impl AddressManager {
     }
 
     pub fn iterate_prioritized_random_addresses(&self, exceptions: HashSet<NetAddress>) -> impl ExactSizeIterator<Item = NetAddress> {
-        self.address_store.iterate_prioritized_random_addresses(exceptions)
+        self.address_store.iterate_prioritized_random_addresses(exceptions, &self.family_connectivity)
+    }
+
+    /// Returns the outcomes of outbound connection attempts per IP family
+    pub fn family_connectivity(&self) -> FamilyConnectivity {
+        self.family_connectivity
     }
 
+    /// Bans `ip` for [`DEFAULT_BAN_DURATION`]
     pub fn ban(&mut self, ip: IpAddress) {
-        self.banned_address_store.set(ip.into(), ConnectionBanTimestamp(unix_now())).unwrap();
-        self.address_store.remove_by_ip(ip.into());
+        self.ban_range(IpNet::from(ip.0), Some(DEFAULT_BAN_DURATION));
     }
 
-    pub fn unban(&mut self, ip: IpAddress) {
-        self.banned_address_store.remove(ip.into()).unwrap();
+    /// Bans all addresses within `range` for `duration`, or permanently if `duration` is `None`.
+    /// Banning a range which is already banned replaces the previous ban. Returns `false`, banning nothing,
+    /// if `range` is too broad (see [`is_ban_range_too_broad`]).
+    pub fn ban_range(&mut self, range: IpNet, duration: Option<Duration>) -> bool {
+        if is_ban_range_too_broad(range) {
+            warn!("[Address manager] refusing to ban the overly broad range {}", range);
+            return false;
+        }
+        let range = canonical_range(range);
+        let now = unix_now();
+        let entry = BanEntry { banned_at: now, expires_at: duration.map(|duration| now.saturating_add(duration.as_millis() as u64)) };
+        self.banned_address_store.set(range, entry).unwrap();
+        self.bans.insert(range, entry);
+        self.address_store.remove_by_range(&range);
+        true
     }
 
-    pub fn is_banned(&mut self, ip: IpAddress) -> bool {
-        const MAX_BANNED_TIME: u64 = 24 * 60 * 60 * 1000;
-        match self.banned_address_store.get(ip.into()).unwrap_option() {
-            Some(timestamp) => {
-                if unix_now() - timestamp.0 > MAX_BANNED_TIME {
-                    self.unban(ip);
-                    false
-                } else {
-                    true
-                }
-            }
-            None => false,
+    /// Removes all the bans overlapping `range`, i.e. bans of ranges containing it or contained within it, so that
+    /// no address of `range` remains banned. Returns `false` if no such active ban exists.
+    pub fn unban_range(&mut self, range: IpNet) -> bool {
+        let range = canonical_range(range);
+        let now = unix_now();
+        let overlapping = self
+            .bans
+            .iter()
+            .filter(|(banned, _)| banned.contains(&range) || range.contains(*banned))
+            .map(|(banned, _)| *banned)
+            .collect_vec();
+        let mut was_banned = false;
+        for banned in overlapping {
+            let entry = self.bans.remove(&banned).unwrap();
+            self.banned_address_store.remove(banned).unwrap();
+            was_banned |= !entry.is_expired(now);
         }
+        was_banned
+    }
+
+    /// Returns whether `ip` is within any active ban
+    pub fn is_banned(&self, ip: IpAddress) -> bool {
+        let ip = ip.0.to_canonical();
+        let now = unix_now();
+        self.bans.iter().any(|(range, entry)| !entry.is_expired(now) && range.contains(&ip))
     }
 
     pub fn get_all_addresses(&self) -> Vec<NetAddress> {
         self.address_store.iterate_addresses().collect_vec()
     }
 
+    /// Returns the network addresses of all active bans
     pub fn get_all_banned_addresses(&self) -> Vec<IpAddress> {
-        self.banned_address_store.iterator().map(|x| IpAddress::from(x.unwrap().0)).collect_vec()
+        self.get_all_bans().into_iter().map(|(range, _)| IpAddress::from(range.network())).collect_vec()
+    }
+
+    /// Returns all active bans along with their ban entries, ordered by range
+    pub fn get_all_bans(&self) -> Vec<(IpNet, BanEntry)> {
+        let now = unix_now();
+        self.bans
+            .iter()
+            .filter(|(_, entry)| !entry.is_expired(now))
+            .map(|(range, entry)| (*range, *entry))
+            .sorted_by_key(|(range, _)| *range)
+            .collect_vec()
     }
 }
 
@@ -331,12 +577,14 @@ mod address_store_with_cache {
     // Since we need operations such as iterating all addresses, count, etc, we keep an easy to use copy of the database addresses.
     // We don't expect it to be expensive since we limit the number of saved addresses.
     use std::{
+        cmp::Reverse,
         collections::{HashMap, HashSet},
-        net::IpAddr,
         sync::Arc,
     };
 
+    use ipnet::IpNet;
     use itertools::Itertools;
+    use kaspa_core::time::unix_now;
     use kaspa_database::prelude::{CachePolicy, DB};
     use kaspa_utils::networking::PrefixBucket;
     use rand::{
@@ -349,7 +597,8 @@ mod address_store_with_cache {
             address_store::{AddressesStore, DbAddressesStore, Entry},
             AddressKey,
         },
-        NetAddress, MAX_ADDRESSES, MAX_CONNECTION_FAILED_COUNT,
+        FamilyConnectivity, NetAddress, ADDRESS_EXPIRY_PERIOD, MAX_ADDRESSES, MAX_CONNECTION_FAILED_COUNT, RECENT_SUCCESS_PERIOD,
+        RECENT_SUCCESS_WEIGHT_FACTOR,
     };
 
     pub struct Store {
@@ -366,31 +615,49 @@ mod address_store_with_cache {
                 addresses.insert(key, entry);
             }
 
-            Self { db_store, addresses }
+            let mut store = Self { db_store, addresses };
+            store.remove_stale(unix_now());
+            store
         }
 
         pub fn has(&mut self, address: NetAddress) -> bool {
             self.addresses.contains_key(&address.into())
         }
 
-        pub fn set(&mut self, address: NetAddress, connection_failed_count: u64) {
-            let entry = match self.addresses.get(&address.into()) {
-                Some(entry) => Entry { connection_failed_count, address: entry.address },
-                None => Entry { connection_failed_count, address },
-            };
-            self.db_store.set(address.into(), entry).unwrap();
-            self.addresses.insert(address.into(), entry);
+        pub fn set(&mut self, entry: Entry) {
+            let key: AddressKey = entry.address.into();
+            self.db_store.set(key, entry).unwrap();
+            self.addresses.insert(key, entry);
             self.keep_limit();
         }
 
         fn keep_limit(&mut self) {
             while self.addresses.len() > MAX_ADDRESSES {
-                let to_remove =
-                    self.addresses.iter().max_by(|a, b| (a.1).connection_failed_count.cmp(&(b.1).connection_failed_count)).unwrap();
-                self.remove_by_key(*to_remove.0);
+                // Evict the address with most connection failures, preferring the least recently seen among those
+                let to_remove = self
+                    .addresses
+                    .iter()
+                    .max_by_key(|(_, entry)| (entry.connection_failed_count, Reverse(entry.last_seen)))
+                    .map(|(key, _)| *key)
+                    .unwrap();
+                self.remove_by_key(to_remove);
             }
         }
 
+        /// Removes all addresses which were not seen for longer than [`ADDRESS_EXPIRY_PERIOD`]. Returns the number of removed addresses
+        pub fn remove_stale(&mut self, now: u64) -> usize {
+            let stale = self
+                .addresses
+                .iter()
+                .filter(|(_, entry)| now.saturating_sub(entry.last_seen) > ADDRESS_EXPIRY_PERIOD)
+                .map(|(key, _)| *key)
+                .collect_vec();
+            for key in stale.iter().copied() {
+                self.remove_by_key(key);
+            }
+            stale.len()
+        }
+
         pub fn get(&self, address: NetAddress) -> Entry {
             *self.addresses.get(&address.into()).unwrap()
         }
@@ -411,47 +678,71 @@ mod address_store_with_cache {
         /// This iterator functions as the node's ip routing selection algo.
         /// It first adjusts in respect to the number of connection failures of each ip address,
         /// whereby each connection failure (up to [`MAX_CONNECTION_FAILED_COUNT`]) reduces an ip's selection weight by a factor of 64,
-        /// Afterwards the weights are normalized uniformly over the ip's [`PrefixBucket`] size.
+        /// and ips successfully connected to during the last [`RECENT_SUCCESS_PERIOD`] have their weight multiplied by
+        /// [`RECENT_SUCCESS_WEIGHT_FACTOR`], and ips of an IP family reached less successfully than the other have their
+        /// weight scaled down accordingly (see [`FamilyConnectivity`]). Afterwards the weights are normalized uniformly over the ip's [`PrefixBucket`] size,
+        /// and over the number of ips advertised by sources of the same [`PrefixBucket`] as the ip's source.
         ///
         /// This ensures a distributed selection across the global network, while respecting
-        /// weight reductions due to ip connection failures.
+        /// weight reductions due to ip connection failures. Normalizing over sources keeps a peer flooding us with
+        /// addresses spread over many prefix buckets from biasing the selection towards its own addresses.
         ///
         /// The exact weight formula for any given ip, is as follows:
         ///```ignore
-        ///         ip_weight = (64 ^ (x - y)) / n
+        ///         ip_weight = (64 ^ (x - y)) * r * f / (n * s)
         ///
         ///             whereby:
         ///                 x: max allowed connection failures.
         ///                 y: connection failures of the ip.
+        ///                 r: the recent success factor if the ip was recently connected to, 1 otherwise.
+        ///                 f: the connection success rate of the ip's family relative to the best family.
         ///                 n: number of ips with the same prefix bytes.
+        ///                 s: number of ips whose source has the same prefix bytes as the ip's source
+        ///                    (ips without a known source are counted together).
         ///```
         pub fn iterate_prioritized_random_addresses(
             &self,
             exceptions: HashSet<NetAddress>,
+            family_connectivity: &FamilyConnectivity,
         ) -> impl ExactSizeIterator<Item = NetAddress> {
             let exceptions: HashSet<AddressKey> = exceptions.into_iter().map(|addr| addr.into()).collect();
+            let now = unix_now();
             let mut prefix_counter: HashMap<PrefixBucket, usize> = HashMap::new();
-            let (mut weights, filtered_addresses): (Vec<f64>, Vec<NetAddress>) = self
+            let mut source_counter: HashMap<Option<PrefixBucket>, usize> = HashMap::new();
+            let (mut weights, filtered_entries): (Vec<f64>, Vec<&Entry>) = self
                 .addresses
                 .iter()
                 .filter(|(addr_key, _)| !exceptions.contains(addr_key))
                 .map(|(_, e)| {
-                    let count = prefix_counter.entry(e.address.prefix_bucket()).or_insert(0);
-                    *count += 1;
-                    (64f64.powf((MAX_CONNECTION_FAILED_COUNT + 1 - e.connection_failed_count) as f64), e.address)
+                    *prefix_counter.entry(e.address.prefix_bucket()).or_insert(0) += 1;
+                    *source_counter.entry(e.source.map(|source| source.prefix_bucket())).or_insert(0) += 1;
+                    let recent_success_factor = if e.last_success > 0 && now.saturating_sub(e.last_success) <= RECENT_SUCCESS_PERIOD {
+                        RECENT_SUCCESS_WEIGHT_FACTOR
+                    } else {
+                        1.0
+                    };
+                    let family_factor = family_connectivity.weight_factor(e.address.ip);
+                    (
+                        64f64.powf((MAX_CONNECTION_FAILED_COUNT + 1 - e.connection_failed_count) as f64)
+                            * recent_success_factor
+                            * family_factor,
+                        e,
+                    )
                 })
                 .unzip();
 
-            // Divide weights by size of bucket of the prefix bytes, to partially uniform the distribution over prefix buckets.
-            for (i, address) in filtered_addresses.iter().enumerate() {
-                *weights.get_mut(i).unwrap() /= *prefix_counter.get(&address.prefix_bucket()).unwrap() as f64;
+            // Divide weights by size of bucket of the prefix bytes, to partially uniform the distribution over prefix buckets,
+            // and by the number of addresses advertised by the source bucket, to partially uniform the distribution over sources.
+            for (weight, e) in weights.iter_mut().zip(filtered_entries.iter()) {
+                *weight /= (prefix_counter[&e.address.prefix_bucket()]
+                    * source_counter[&e.source.map(|source| source.prefix_bucket())]) as f64;
             }
 
-            RandomWeightedIterator::new(weights, filtered_addresses)
+            RandomWeightedIterator::new(weights, filtered_entries.into_iter().map(|e| e.address).collect())
         }
 
-        pub fn remove_by_ip(&mut self, ip: IpAddr) {
-            for key in self.addresses.keys().filter(|key| key.is_ip(ip)).copied().collect_vec() {
+        pub fn remove_by_range(&mut self, range: &IpNet) {
+            for key in self.addresses.keys().filter(|key| key.is_in_range(range)).copied().collect_vec() {
                 self.remove_by_key(key);
             }
         }
@@ -514,7 +805,8 @@ mod address_store_with_cache {
         use std::str::FromStr;
 
         use super::*;
-        use address_manager::AddressManager;
+        use crate::stores::peer_stats_store::PeerStatsStore;
+        use address_manager::{AddressManager, ConnectivityStats, PeerStats, DEFAULT_BAN_DURATION, FAMILY_CONNECTIVITY_WINDOW};
         use kaspa_consensus_core::config::{params::SIMNET_PARAMS, Config};
         use kaspa_core::task::tick::TickService;
         use kaspa_database::create_temp_db;
@@ -522,6 +814,7 @@ mod address_store_with_cache {
         use kaspa_utils::networking::IpAddress;
         use rv::{dist::Uniform, misc::ks_test as one_way_ks_test, traits::Cdf};
         use std::net::{IpAddr, Ipv6Addr};
+        use std::time::Duration;
 
         #[test]
         fn test_weighted_iterator() {
@@ -535,6 +828,172 @@ mod address_store_with_cache {
             assert_eq!(iter.count(), 0);
         }
 
+        #[test]
+        fn test_address_timestamps_and_expiry() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Config::new(SIMNET_PARAMS);
+            let (am, _) = AddressManager::new(Arc::new(config), db.1, Arc::new(TickService::default())).unwrap();
+            let mut am = am.lock();
+
+            let source = IpAddress::from_str("5.6.7.8").unwrap();
+            let connected = NetAddress::new(IpAddress::from_str("1.2.3.4").unwrap(), 16111);
+            let stale = NetAddress::new(IpAddress::from_str("1.2.3.5").unwrap(), 16111);
+            am.add_address(connected, Some(source));
+            am.add_address(stale, None);
+            am.mark_connection_success(connected);
+
+            let entry = am.address_store.get(connected);
+            assert_eq!(entry.source, Some(source));
+            assert_eq!(entry.connection_failed_count, 0);
+            assert!(entry.last_success > 0 && entry.last_success == entry.last_attempt);
+
+            // Simulate an address which was last seen before the expiry period
+            let entry = am.address_store.get(stale);
+            assert_eq!((entry.last_attempt, entry.last_success), (0, 0));
+            am.address_store.set(Entry { last_seen: unix_now() - ADDRESS_EXPIRY_PERIOD - 1, ..entry });
+            // Advertising the stale address again does not refresh it
+            am.add_address(stale, Some(source));
+            am.expire_stale_addresses();
+            assert_eq!(am.get_all_addresses(), vec![connected]);
+        }
+
+        #[test]
+        fn test_ban_ranges() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Config::new(SIMNET_PARAMS);
+            let (am, _) = AddressManager::new(Arc::new(config), db.1.clone(), Arc::new(TickService::default())).unwrap();
+            let mut am = am.lock();
+
+            let inside = NetAddress::new(IpAddress::from_str("10.0.1.2").unwrap(), 16111);
+            let outside = NetAddress::new(IpAddress::from_str("10.1.0.1").unwrap(), 16111);
+            am.add_address(inside, None);
+            am.add_address(outside, None);
+
+            // Host bits of the range are ignored and addresses within the range are dropped
+            am.ban_range(IpNet::from_str("10.0.9.9/16").unwrap(), None);
+            assert!(am.is_banned(inside.ip));
+            assert!(am.is_banned(IpAddress::from_str("::ffff:10.0.200.1").unwrap()));
+            assert!(!am.is_banned(outside.ip));
+            assert_eq!(am.get_all_addresses(), vec![outside]);
+            let bans = am.get_all_bans().into_iter().map(|(range, entry)| (range, entry.expires_at)).collect_vec();
+            assert_eq!(bans, vec![(IpNet::from_str("10.0.0.0/16").unwrap(), None)]);
+
+            // Unbanning an address lifts the bans of the ranges containing it, and unbanning a range lifts the bans within it
+            assert!(am.unban_range(IpNet::from_str("10.0.1.2/32").unwrap()));
+            assert!(!am.is_banned(inside.ip));
+            assert!(!am.unban_range(IpNet::from_str("10.0.0.0/16").unwrap()));
+            am.ban_range(IpNet::from_str("10.0.1.0/24").unwrap(), None);
+            am.ban_range(IpNet::from_str("10.0.2.0/24").unwrap(), None);
+            assert!(am.unban_range(IpNet::from_str("10.0.0.0/16").unwrap()));
+            assert!(am.get_all_bans().is_empty());
+
+            // Overly broad ranges cannot be banned
+            for range in ["0.0.0.0/0", "::/0", "10.0.0.0/7", "::ffff:0.0.0.0/96"] {
+                assert!(!am.ban_range(IpNet::from_str(range).unwrap(), None), "{range} should not be banned");
+            }
+            assert!(am.get_all_bans().is_empty());
+
+            // Expired bans are lifted
+            am.ban_range(IpNet::from_str("2001:db8::/32").unwrap(), Some(Duration::ZERO));
+            assert!(!am.is_banned(IpAddress::from_str("2001:db8::1").unwrap()));
+            assert!(am.get_all_bans().is_empty());
+
+            am.ban(outside.ip);
+            let (range, entry) = am.get_all_bans()[0];
+            assert_eq!(range, IpNet::from_str("10.1.0.1/32").unwrap());
+            assert_eq!(entry.expires_at, Some(entry.banned_at + DEFAULT_BAN_DURATION.as_millis() as u64));
+
+            // Bans are persisted
+            drop(am);
+            let (am, _) = AddressManager::new(Arc::new(Config::new(SIMNET_PARAMS)), db.1, Arc::new(TickService::default())).unwrap();
+            assert!(am.lock().is_banned(outside.ip));
+        }
+
+        #[test]
+        fn test_peer_stats_persistence() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Arc::new(Config::new(SIMNET_PARAMS));
+            let ip = IpAddress::from_str("1.2.3.4").unwrap();
+            let stale = IpAddress::from_str("1.2.3.5").unwrap();
+            {
+                let (am, _) = AddressManager::new(config.clone(), db.1.clone(), Arc::new(TickService::default())).unwrap();
+                let mut am = am.lock();
+                let session = PeerStats { bytes_sent: 10, bytes_received: 20, protocol_version: 6, ..Default::default() };
+                am.record_peer_session(ip, session);
+                am.record_peer_session(IpAddress::from_str("::ffff:1.2.3.4").unwrap(), session);
+                am.peer_stats_store.set(stale.0, PeerStats { last_seen: unix_now() - ADDRESS_EXPIRY_PERIOD - 1, ..session }).unwrap();
+            }
+
+            // Statistics survive a restart while stale ones are expired
+            let (am, _) = AddressManager::new(config, db.1, Arc::new(TickService::default())).unwrap();
+            let am = am.lock();
+            let stats = am.get_peer_stats(ip).unwrap();
+            assert_eq!((stats.bytes_sent, stats.bytes_received, stats.protocol_version, stats.sessions), (20, 40, 6, 2));
+            assert!(am.get_peer_stats(stale).is_none());
+            assert_eq!(am.get_all_peer_stats().len(), 1);
+        }
+
+        #[test]
+        fn test_family_connectivity_weighting() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Config::new(SIMNET_PARAMS);
+            let (am, _) = AddressManager::new(Arc::new(config), db.1, Arc::new(TickService::default())).unwrap();
+            let mut am = am.lock();
+
+            let ipv4 = NetAddress::new(IpAddress::from_str("1.2.3.4").unwrap(), 16111);
+            let ipv6 = NetAddress::new(IpAddress::from_str("2a01:4f8::1").unwrap(), 16111);
+            am.add_address(ipv4, None);
+            am.add_address(ipv6, None);
+
+            // Connecting over IPv6 keeps failing while IPv4 works, as on a host without an IPv6 route
+            for i in 0..32u8 {
+                am.mark_connection_failure(NetAddress::new(IpAddress::from_str(&format!("2a01:4f8::{i:x}:2")).unwrap(), 16111));
+                am.mark_connection_success(NetAddress::new(IpAddress::from_str(&format!("::ffff:1.2.{i}.5")).unwrap(), 16111));
+            }
+            let connectivity = am.family_connectivity();
+            assert_eq!(connectivity.ipv6, ConnectivityStats { attempts: 32, successes: 0 });
+            assert_eq!(connectivity.stats(ipv4.ip), ConnectivityStats { attempts: 32, successes: 32 });
+
+            let num_of_trials = 1000;
+            let ipv6_first =
+                (0..num_of_trials).filter(|_| am.iterate_prioritized_random_addresses(HashSet::new()).next() == Some(ipv6)).count();
+            assert!(ipv6_first < num_of_trials / 10, "the IPv6 address came first in {ipv6_first}/{num_of_trials} trials");
+
+            // Old outcomes fade away as IPv6 connectivity gets restored
+            for i in 0..2 * FAMILY_CONNECTIVITY_WINDOW as u8 {
+                am.mark_connection_success(NetAddress::new(IpAddress::from_str(&format!("2a01:4f8::{i:x}:3")).unwrap(), 16111));
+            }
+            assert!(am.family_connectivity().ipv6.success_rate() > 0.9);
+        }
+
+        #[test]
+        fn test_source_distribution_weighting() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Config::new(SIMNET_PARAMS);
+            let (am, _) = AddressManager::new(Arc::new(config), db.1, Arc::new(TickService::default())).unwrap();
+            let mut am = am.lock();
+
+            // A single source floods 64 addresses spread over distinct prefix buckets, while 64 honest addresses
+            // are each advertised by a distinct source
+            let flooding_source = IpAddress::from_str("9.9.9.9").unwrap();
+            let mut flooded = HashSet::new();
+            for i in 0..64u8 {
+                let address = NetAddress::new(IpAddress::from_str(&format!("10.{i}.0.1")).unwrap(), 16111);
+                am.add_address(address, Some(flooding_source));
+                flooded.insert(address);
+                let source = IpAddress::from_str(&format!("30.{i}.0.1")).unwrap();
+                am.add_address(NetAddress::new(IpAddress::from_str(&format!("20.{i}.0.1")).unwrap(), 16111), Some(source));
+            }
+
+            // The flooding source holds 1/65 of the total weight, hence its addresses should rarely come first
+            // (they would come first in about half of the trials without source normalization)
+            let num_of_trials = 1000;
+            let flooded_first = (0..num_of_trials)
+                .filter(|_| flooded.contains(&am.iterate_prioritized_random_addresses(HashSet::new()).next().unwrap()))
+                .count();
+            assert!(flooded_first < num_of_trials / 10, "flooded addresses came first in {flooded_first}/{num_of_trials} trials");
+        }
+
         #[test]
         fn test_network_distribution_weighting() {
             kaspa_core::log::try_init_logger("info");
@@ -548,7 +1007,7 @@ mod address_store_with_cache {
 
             let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
             let config = Config::new(SIMNET_PARAMS);
-            let (am, _) = AddressManager::new(Arc::new(config), db.1, Arc::new(TickService::default()));
+            let (am, _) = AddressManager::new(Arc::new(config), db.1, Arc::new(TickService::default())).unwrap();
 
             let mut am_guard = am.lock();
 
@@ -561,14 +1020,17 @@ mod address_store_with_cache {
                 for current_suffix_bytes in 0..current_bucket_size {
                     let current_ip_bytes =
                         [current_prefix_bytes.to_be_bytes(), current_suffix_bytes.to_be_bytes()].concat().to_owned();
-                    am_guard.add_address(NetAddress::new(
-                        IpAddress::from_str(&format!(
-                            "{0}.{1}.{2}.{3}",
-                            current_ip_bytes[0], current_ip_bytes[1], current_ip_bytes[2], current_ip_bytes[3]
-                        ))
-                        .unwrap(),
-                        16111,
-                    ));
+                    am_guard.add_address(
+                        NetAddress::new(
+                            IpAddress::from_str(&format!(
+                                "{0}.{1}.{2}.{3}",
+                                current_ip_bytes[0], current_ip_bytes[1], current_ip_bytes[2], current_ip_bytes[3]
+                            ))
+                            .unwrap(),
+                            16111,
+                        ),
+                        None,
+                    );
                     num_of_addresses += 1;
                 }
 
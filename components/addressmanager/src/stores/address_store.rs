@@ -1,10 +1,12 @@
 use kaspa_database::{
+    migration::Migration,
     prelude::DB,
+    prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter},
     prelude::{CachePolicy, StoreError, StoreResult},
-    prelude::{CachedDbAccess, DirectDbWriter},
     registry::DatabaseStorePrefixes,
 };
-use kaspa_utils::mem_size::MemSizeEstimator;
+use kaspa_utils::{mem_size::MemSizeEstimator, networking::IpAddress};
+use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
 use std::net::Ipv6Addr;
 use std::{error::Error, fmt::Display, sync::Arc};
@@ -16,10 +18,33 @@ use crate::NetAddress;
 pub struct Entry {
     pub connection_failed_count: u64,
     pub address: NetAddress,
+    /// Unix time (milliseconds) at which the address was last advertised to us or successfully connected to
+    pub last_seen: u64,
+    /// Unix time (milliseconds) of the last outbound connection attempt, 0 if never attempted
+    pub last_attempt: u64,
+    /// Unix time (milliseconds) of the last successful outbound connection, 0 if never connected
+    pub last_success: u64,
+    /// The peer which first advertised this address to us, if any
+    pub source: Option<IpAddress>,
+}
+
+impl Entry {
+    pub fn new(address: NetAddress, connection_failed_count: u64, source: Option<IpAddress>, now: u64) -> Self {
+        Self { connection_failed_count, address, last_seen: now, last_attempt: 0, last_success: 0, source }
+    }
 }
 
 impl MemSizeEstimator for Entry {}
 
+/// The address entry layout of schema version 0
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct EntryV0 {
+    connection_failed_count: u64,
+    address: NetAddress,
+}
+
+impl MemSizeEstimator for EntryV0 {}
+
 pub trait AddressesStoreReader {
     #[allow(dead_code)]
     fn get(&self, key: AddressKey) -> Result<Entry, StoreError>;
@@ -72,6 +97,43 @@ impl From<DbAddressKey> for AddressKey {
     }
 }
 
+/// Upgrades schema version 0 entries (see [`EntryV0`]) to [`Entry`]. Migrated entries are considered
+/// as seen at `now` since their history is unknown.
+pub struct AddressesSchemaV1Migration {
+    pub now: u64,
+}
+
+impl Migration for AddressesSchemaV1Migration {
+    fn store(&self) -> DatabaseStorePrefixes {
+        DatabaseStorePrefixes::Addresses
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn description(&self) -> &'static str {
+        "add last-seen, attempt and success times and the source peer to address entries"
+    }
+
+    fn transform(&self, db: &Arc<DB>, batch: &mut WriteBatch) -> StoreResult<usize> {
+        let legacy_access: CachedDbAccess<DbAddressKey, EntryV0> =
+            CachedDbAccess::new(db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::Addresses.into());
+        let access: CachedDbAccess<DbAddressKey, Entry> =
+            CachedDbAccess::new(db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::Addresses.into());
+        let mut migrated = 0;
+        for (key_bytes, legacy) in legacy_access.iterator().filter_map(|res| res.ok()) {
+            let Ok(key) = <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) else {
+                continue;
+            };
+            let entry = Entry::new(legacy.address, legacy.connection_failed_count, None, self.now);
+            access.write(BatchDbWriter::new(batch), DbAddressKey(key), entry)?;
+            migrated += 1;
+        }
+        Ok(migrated)
+    }
+}
+
 #[derive(Clone)]
 pub struct DbAddressesStore {
     db: Arc<DB>,
@@ -115,6 +177,38 @@ impl AddressesStore for DbAddressesStore {
 
     fn set_failed_count(&mut self, key: AddressKey, connection_failed_count: u64) -> StoreResult<()> {
         let entry = self.get(key)?;
-        self.set(key, Entry { connection_failed_count, address: entry.address })
+        self.set(key, Entry { connection_failed_count, ..entry })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, migration::MigrationRunner, prelude::ConnBuilder};
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn test_migrate_from_schema_v0() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let address = NetAddress::new(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)).into(), 16111);
+        let key: AddressKey = address.into();
+        let legacy_access: CachedDbAccess<DbAddressKey, EntryV0> =
+            CachedDbAccess::new(db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::Addresses.into());
+        legacy_access.write(DirectDbWriter::new(&db), key.into(), EntryV0 { connection_failed_count: 2, address }).unwrap();
+
+        let report =
+            MigrationRunner::new(db.clone()).with_migration(Box::new(AddressesSchemaV1Migration { now: 1000 })).run().unwrap();
+        assert_eq!(report.records[0].entries, 1);
+        let store = DbAddressesStore::new(db.clone(), CachePolicy::Empty);
+        let entry = store.get(key).unwrap();
+        assert_eq!(entry.address, address);
+        assert_eq!((entry.connection_failed_count, entry.last_seen, entry.last_attempt, entry.last_success), (2, 1000, 0, 0));
+        assert_eq!(entry.source, None);
+
+        // Migration is applied only once
+        let report =
+            MigrationRunner::new(db.clone()).with_migration(Box::new(AddressesSchemaV1Migration { now: 2000 })).run().unwrap();
+        assert!(report.is_empty());
+        assert_eq!(store.get(key).unwrap().last_seen, 1000);
     }
 }
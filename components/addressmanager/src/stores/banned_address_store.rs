@@ -1,75 +1,156 @@
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
 use kaspa_database::{
-    prelude::{CachePolicy, StoreError, StoreResult},
-    prelude::{CachedDbAccess, DirectDbWriter, DB},
+    migration::Migration,
+    prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter, DB},
+    prelude::{CachePolicy, StoreResult},
     registry::DatabaseStorePrefixes,
 };
 use kaspa_utils::mem_size::MemSizeEstimator;
+use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
 use std::net::{IpAddr, Ipv6Addr};
 use std::{error::Error, fmt::Display, sync::Arc};
 
-#[derive(Clone, Copy, Serialize, Deserialize)]
-pub struct ConnectionBanTimestamp(pub u64);
+/// The fixed ban period of bans persisted with schema version 0
+const SCHEMA_V0_BAN_DURATION: u64 = 24 * 60 * 60 * 1000;
 
-impl MemSizeEstimator for ConnectionBanTimestamp {}
+/// A ban of an IP range
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanEntry {
+    /// Unix time (milliseconds) at which the ban was issued
+    pub banned_at: u64,
+    /// Unix time (milliseconds) at which the ban expires, `None` for permanent bans
+    pub expires_at: Option<u64>,
+}
 
-pub trait BannedAddressesStoreReader {
-    fn get(&self, address: IpAddr) -> Result<ConnectionBanTimestamp, StoreError>;
+impl BanEntry {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
 }
 
-pub trait BannedAddressesStore: BannedAddressesStoreReader {
-    fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()>;
-    fn remove(&mut self, ip: IpAddr) -> StoreResult<()>;
+impl MemSizeEstimator for BanEntry {}
+
+/// The ban timestamp persisted with schema version 0
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct ConnectionBanTimestamp(u64);
+
+impl MemSizeEstimator for ConnectionBanTimestamp {}
+
+pub trait BannedAddressesStore {
+    fn set(&mut self, range: IpNet, entry: BanEntry) -> StoreResult<()>;
+    fn remove(&mut self, range: IpNet) -> StoreResult<()>;
 }
 
 const IPV6_LEN: usize = 16;
-const ADDRESS_KEY_SIZE: usize = IPV6_LEN;
+const PREFIX_LEN_LEN: usize = 1;
+const LEGACY_ADDRESS_KEY_SIZE: usize = IPV6_LEN;
+const BAN_KEY_SIZE: usize = IPV6_LEN + PREFIX_LEN_LEN;
+
+/// Number of leading bits of an IPv4-mapped IPv6 address which are fixed by the mapping
+const IPV4_MAPPED_PREFIX_LEN: u8 = 96;
 
+/// The key of schema version 0, a single IP
 #[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
-struct AddressKey([u8; ADDRESS_KEY_SIZE]);
+struct LegacyAddressKey([u8; LEGACY_ADDRESS_KEY_SIZE]);
 
-impl AsRef<[u8]> for AddressKey {
+impl AsRef<[u8]> for LegacyAddressKey {
     fn as_ref(&self) -> &[u8] {
         &self.0
     }
 }
 
-impl Display for AddressKey {
+/// An IP range key. IPv4 ranges are stored in their IPv4-mapped IPv6 form so that all ranges share a single key space
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+struct BanKey([u8; BAN_KEY_SIZE]);
+
+impl AsRef<[u8]> for BanKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for BanKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ip: Ipv6Addr = (*self).into();
-        write!(f, "{ip}")
+        let range: IpNet = (*self).into();
+        write!(f, "{range}")
     }
 }
 
-impl From<IpAddr> for AddressKey {
-    fn from(ip: IpAddr) -> Self {
-        Self(match ip {
-            IpAddr::V4(ip) => ip.to_ipv6_mapped().octets(),
-            IpAddr::V6(ip) => ip.octets(),
-        })
+impl From<IpNet> for BanKey {
+    fn from(range: IpNet) -> Self {
+        let range = range.trunc();
+        let (network, prefix_len) = match range {
+            IpNet::V4(range) => (range.network().to_ipv6_mapped(), range.prefix_len() + IPV4_MAPPED_PREFIX_LEN),
+            IpNet::V6(range) => (range.network(), range.prefix_len()),
+        };
+        let mut bytes = [0; BAN_KEY_SIZE];
+        bytes[..IPV6_LEN].copy_from_slice(&network.octets());
+        bytes[IPV6_LEN] = prefix_len;
+        Self(bytes)
     }
 }
 
-impl From<AddressKey> for Ipv6Addr {
-    fn from(k: AddressKey) -> Self {
-        k.0.into()
+impl From<BanKey> for IpNet {
+    fn from(k: BanKey) -> Self {
+        let network_bytes: [u8; IPV6_LEN] = k.0[..IPV6_LEN].try_into().unwrap();
+        let network: Ipv6Addr = network_bytes.into();
+        let prefix_len = k.0[IPV6_LEN];
+        match network.to_ipv4_mapped() {
+            Some(network) if prefix_len >= IPV4_MAPPED_PREFIX_LEN => {
+                IpNet::V4(Ipv4Net::new(network, prefix_len - IPV4_MAPPED_PREFIX_LEN).unwrap())
+            }
+            _ => IpNet::V6(Ipv6Net::new(network, prefix_len).unwrap()),
+        }
     }
 }
 
-impl From<AddressKey> for IpAddr {
-    fn from(k: AddressKey) -> Self {
-        let ipv6: Ipv6Addr = k.0.into();
-        match ipv6.to_ipv4_mapped() {
-            Some(ipv4) => IpAddr::V4(ipv4),
-            None => IpAddr::V6(ipv6),
+/// Returns `range` in the form it is persisted in, i.e. with its host bits cleared and IPv4-mapped IPv6 ranges converted to IPv4
+pub fn canonical_range(range: IpNet) -> IpNet {
+    BanKey::from(range).into()
+}
+
+/// Converts schema version 0 bans, each a single IP mapped to its ban timestamp, to single address ranges
+/// expiring 24 hours after they were issued, as was the fixed policy back then
+pub struct BannedAddressesSchemaV1Migration;
+
+impl Migration for BannedAddressesSchemaV1Migration {
+    fn store(&self) -> DatabaseStorePrefixes {
+        DatabaseStorePrefixes::BannedAddresses
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn description(&self) -> &'static str {
+        "convert single IP bans to IP range bans with an expiry time"
+    }
+
+    fn transform(&self, db: &Arc<DB>, batch: &mut WriteBatch) -> StoreResult<usize> {
+        let legacy_access: CachedDbAccess<LegacyAddressKey, ConnectionBanTimestamp> =
+            CachedDbAccess::new(db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::BannedAddresses.into());
+        let access: CachedDbAccess<BanKey, BanEntry> =
+            CachedDbAccess::new(db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::BannedAddresses.into());
+        let mut migrated = 0;
+        for (key_bytes, timestamp) in legacy_access.iterator().filter_map(|res| res.ok()) {
+            let Ok(key) = <[u8; LEGACY_ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) else {
+                continue;
+            };
+            legacy_access.delete(BatchDbWriter::new(batch), LegacyAddressKey(key))?;
+            let ip = IpAddr::V6(Ipv6Addr::from(key)).to_canonical();
+            let entry = BanEntry { banned_at: timestamp.0, expires_at: Some(timestamp.0 + SCHEMA_V0_BAN_DURATION) };
+            access.write(BatchDbWriter::new(batch), IpNet::from(ip).into(), entry)?;
+            migrated += 1;
         }
+        Ok(migrated)
     }
 }
 
 #[derive(Clone)]
 pub struct DbBannedAddressesStore {
     db: Arc<DB>,
-    access: CachedDbAccess<AddressKey, ConnectionBanTimestamp>,
+    access: CachedDbAccess<BanKey, BanEntry>,
 }
 
 impl DbBannedAddressesStore {
@@ -77,14 +158,10 @@ impl DbBannedAddressesStore {
         Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BannedAddresses.into()) }
     }
 
-    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpAddr, ConnectionBanTimestamp), Box<dyn Error>>> + '_ {
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpNet, BanEntry), Box<dyn Error>>> + '_ {
         self.access.iterator().map(|iter_result| match iter_result {
-            Ok((key_bytes, connection_ban_timestamp)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
-                Ok(address_key_slice) => {
-                    let addr_key = AddressKey(address_key_slice);
-                    let address: IpAddr = addr_key.into();
-                    Ok((address, connection_ban_timestamp))
-                }
+            Ok((key_bytes, entry)) => match <[u8; BAN_KEY_SIZE]>::try_from(&key_bytes[..]) {
+                Ok(ban_key_slice) => Ok((BanKey(ban_key_slice).into(), entry)),
                 Err(e) => Err(e.into()),
             },
             Err(e) => Err(e),
@@ -92,18 +169,52 @@ impl DbBannedAddressesStore {
     }
 }
 
-impl BannedAddressesStoreReader for DbBannedAddressesStore {
-    fn get(&self, ip: IpAddr) -> Result<ConnectionBanTimestamp, StoreError> {
-        self.access.read(ip.into())
+impl BannedAddressesStore for DbBannedAddressesStore {
+    fn set(&mut self, range: IpNet, entry: BanEntry) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), range.into(), entry)
+    }
+
+    fn remove(&mut self, range: IpNet) -> StoreResult<()> {
+        self.access.delete(DirectDbWriter::new(&self.db), range.into())
     }
 }
 
-impl BannedAddressesStore for DbBannedAddressesStore {
-    fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()> {
-        self.access.write(DirectDbWriter::new(&self.db), ip.into(), timestamp)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, migration::MigrationRunner, prelude::ConnBuilder};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_ban_key_roundtrip() {
+        for range in ["1.2.3.4/32", "10.0.0.0/8", "0.0.0.0/0", "2001:db8::/32", "::1/128", "::/0"] {
+            let range = IpNet::from_str(range).unwrap();
+            assert_eq!(IpNet::from(BanKey::from(range)), range);
+        }
+        assert_eq!(canonical_range(IpNet::from_str("10.1.2.3/8").unwrap()), IpNet::from_str("10.0.0.0/8").unwrap());
+        assert_eq!(canonical_range(IpNet::from_str("::ffff:10.1.2.3/120").unwrap()), IpNet::from_str("10.1.2.0/24").unwrap());
     }
 
-    fn remove(&mut self, ip: IpAddr) -> StoreResult<()> {
-        self.access.delete(DirectDbWriter::new(&self.db), ip.into())
+    #[test]
+    fn test_migrate_from_schema_v0() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let ip = IpAddr::from_str("1.2.3.4").unwrap();
+        let legacy_access: CachedDbAccess<LegacyAddressKey, ConnectionBanTimestamp> =
+            CachedDbAccess::new(db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::BannedAddresses.into());
+        let key = match ip {
+            IpAddr::V4(ip) => ip.to_ipv6_mapped().octets(),
+            IpAddr::V6(ip) => ip.octets(),
+        };
+        legacy_access.write(DirectDbWriter::new(&db), LegacyAddressKey(key), ConnectionBanTimestamp(1000)).unwrap();
+
+        let runner = MigrationRunner::new(db.clone()).with_migration(Box::new(BannedAddressesSchemaV1Migration));
+        assert_eq!(runner.run().unwrap().records[0].entries, 1);
+        let store = DbBannedAddressesStore::new(db, CachePolicy::Empty);
+        let bans = store.iterator().map(|res| res.unwrap()).collect::<Vec<_>>();
+        assert_eq!(bans, vec![(IpNet::from(ip), BanEntry { banned_at: 1000, expires_at: Some(1000 + SCHEMA_V0_BAN_DURATION) })]);
+
+        // Migration is applied only once
+        assert!(runner.run().unwrap().is_empty());
+        assert_eq!(store.iterator().count(), 1);
     }
 }
@@ -0,0 +1,190 @@
+use kaspa_database::{
+    prelude::{CachePolicy, StoreError, StoreResult},
+    prelude::{CachedDbAccess, DirectDbWriter, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv6Addr};
+use std::{error::Error, fmt::Display, sync::Arc};
+
+/// Statistics of a peer IP accumulated over all of its connections
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerStats {
+    /// Total bytes sent to the peer
+    pub bytes_sent: u64,
+    /// Total bytes received from the peer
+    pub bytes_received: u64,
+    /// Unix time (milliseconds) at which a connection to the peer last ended
+    pub last_seen: u64,
+    /// The protocol version applied in the last connection, 0 if unknown
+    pub protocol_version: u32,
+    /// Average ping latency in milliseconds
+    pub avg_latency: u64,
+    /// The number of ping samples averaged into `avg_latency`
+    pub latency_samples: u64,
+    /// Accumulated misbehavior score
+    pub ban_score: u32,
+    /// The number of connections to the peer
+    pub sessions: u64,
+}
+
+impl PeerStats {
+    /// Merges the statistics of a single connection (or any later period) into these statistics
+    pub fn merge(&mut self, session: &PeerStats) {
+        self.bytes_sent = self.bytes_sent.saturating_add(session.bytes_sent);
+        self.bytes_received = self.bytes_received.saturating_add(session.bytes_received);
+        self.last_seen = self.last_seen.max(session.last_seen);
+        if session.protocol_version != 0 {
+            self.protocol_version = session.protocol_version;
+        }
+        let latency_samples = self.latency_samples.saturating_add(session.latency_samples);
+        if latency_samples > 0 {
+            let weighted = self.avg_latency as u128 * self.latency_samples as u128
+                + session.avg_latency as u128 * session.latency_samples as u128;
+            self.avg_latency = (weighted / latency_samples as u128) as u64;
+        }
+        self.latency_samples = latency_samples;
+        self.ban_score = self.ban_score.saturating_add(session.ban_score);
+        self.sessions = self.sessions.saturating_add(session.sessions);
+    }
+}
+
+impl MemSizeEstimator for PeerStats {}
+
+pub trait PeerStatsStoreReader {
+    fn get(&self, ip: IpAddr) -> Result<PeerStats, StoreError>;
+}
+
+pub trait PeerStatsStore: PeerStatsStoreReader {
+    fn set(&mut self, ip: IpAddr, stats: PeerStats) -> StoreResult<()>;
+    fn remove(&mut self, ip: IpAddr) -> StoreResult<()>;
+}
+
+const PEER_KEY_SIZE: usize = 16;
+
+/// An IP key. IPv4 addresses are stored in their IPv4-mapped IPv6 form
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+struct PeerKey([u8; PEER_KEY_SIZE]);
+
+impl AsRef<[u8]> for PeerKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for PeerKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ip: IpAddr = (*self).into();
+        write!(f, "{ip}")
+    }
+}
+
+impl From<IpAddr> for PeerKey {
+    fn from(ip: IpAddr) -> Self {
+        Self(match ip {
+            IpAddr::V4(ip) => ip.to_ipv6_mapped().octets(),
+            IpAddr::V6(ip) => ip.octets(),
+        })
+    }
+}
+
+impl From<PeerKey> for IpAddr {
+    fn from(k: PeerKey) -> Self {
+        IpAddr::V6(Ipv6Addr::from(k.0)).to_canonical()
+    }
+}
+
+#[derive(Clone)]
+pub struct DbPeerStatsStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<PeerKey, PeerStats>,
+}
+
+impl DbPeerStatsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::PeerStats.into()) }
+    }
+
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpAddr, PeerStats), Box<dyn Error>>> + '_ {
+        self.access.iterator().map(|iter_result| match iter_result {
+            Ok((key_bytes, stats)) => match <[u8; PEER_KEY_SIZE]>::try_from(&key_bytes[..]) {
+                Ok(peer_key_slice) => Ok((PeerKey(peer_key_slice).into(), stats)),
+                Err(e) => Err(e.into()),
+            },
+            Err(e) => Err(e),
+        })
+    }
+}
+
+impl PeerStatsStoreReader for DbPeerStatsStore {
+    fn get(&self, ip: IpAddr) -> Result<PeerStats, StoreError> {
+        self.access.read(ip.into())
+    }
+}
+
+impl PeerStatsStore for DbPeerStatsStore {
+    fn set(&mut self, ip: IpAddr, stats: PeerStats) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), ip.into(), stats)
+    }
+
+    fn remove(&mut self, ip: IpAddr) -> StoreResult<()> {
+        self.access.delete(DirectDbWriter::new(&self.db), ip.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_peer_key_roundtrip() {
+        for ip in ["1.2.3.4", "2001:db8::1", "::1"] {
+            let ip = IpAddr::from_str(ip).unwrap();
+            assert_eq!(IpAddr::from(PeerKey::from(ip)), ip);
+        }
+        assert_eq!(IpAddr::from(PeerKey::from(IpAddr::from_str("::ffff:1.2.3.4").unwrap())), IpAddr::from_str("1.2.3.4").unwrap());
+    }
+
+    #[test]
+    fn test_merge_stats() {
+        let mut stats = PeerStats {
+            bytes_sent: 100,
+            bytes_received: 200,
+            last_seen: 1000,
+            protocol_version: 5,
+            avg_latency: 100,
+            latency_samples: 3,
+            ban_score: 10,
+            sessions: 1,
+        };
+        stats.merge(&PeerStats {
+            bytes_sent: 1,
+            bytes_received: 2,
+            last_seen: 2000,
+            protocol_version: 6,
+            avg_latency: 20,
+            latency_samples: 1,
+            ban_score: 0,
+            sessions: 1,
+        });
+        assert_eq!(
+            stats,
+            PeerStats {
+                bytes_sent: 101,
+                bytes_received: 202,
+                last_seen: 2000,
+                protocol_version: 6,
+                avg_latency: 80,
+                latency_samples: 4,
+                ban_score: 10,
+                sessions: 2
+            }
+        );
+
+        // A session without pings or a known protocol version keeps the previous figures
+        stats.merge(&PeerStats { sessions: 1, ..Default::default() });
+        assert_eq!((stats.protocol_version, stats.avg_latency, stats.last_seen, stats.sessions), (6, 80, 2000, 3));
+    }
+}
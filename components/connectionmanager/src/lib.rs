@@ -1,6 +1,6 @@
 use std::{
     cmp::min,
-    collections::{HashMap, HashSet},
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
     net::{IpAddr, SocketAddr, ToSocketAddrs},
     sync::Arc,
     time::{Duration, SystemTime},
@@ -8,11 +8,15 @@ use std::{
 
 use duration_string::DurationString;
 use futures_util::future::{join_all, try_join_all};
+use ipnet::IpNet;
 use itertools::Itertools;
-use kaspa_addressmanager::{AddressManager, NetAddress};
+use kaspa_addressmanager::{is_ban_range_too_broad, AddressManager, NetAddress};
 use kaspa_core::{debug, info, warn};
 use kaspa_p2p_lib::{common::ProtocolError, ConnectionError, Peer};
-use kaspa_utils::triggers::SingleTrigger;
+use kaspa_utils::{
+    networking::{PeerId, PrefixBucket},
+    triggers::SingleTrigger,
+};
 use parking_lot::Mutex as ParkingLotMutex;
 use rand::{seq::SliceRandom, thread_rng};
 use tokio::{
@@ -27,11 +31,15 @@ use tokio::{
 pub struct ConnectionManager {
     p2p_adaptor: Arc<kaspa_p2p_lib::Adaptor>,
     outbound_target: usize,
+    /// The minimal number of distinct network groups ([`PrefixBucket`]s) the outbound peers should span
+    min_outbound_groups: usize,
     inbound_limit: usize,
     dns_seeders: &'static [&'static str],
     default_port: u16,
     address_manager: Arc<ParkingLotMutex<AddressManager>>,
     connection_requests: TokioMutex<HashMap<SocketAddr, ConnectionRequest>>,
+    /// Peers marked by the operator as never to be evicted when above the inbound limit
+    protected_peers: ParkingLotMutex<HashSet<PeerId>>,
     force_next_iteration: UnboundedSender<()>,
     shutdown_signal: SingleTrigger,
 }
@@ -57,14 +65,21 @@ impl ConnectionManager {
         dns_seeders: &'static [&'static str],
         default_port: u16,
         address_manager: Arc<ParkingLotMutex<AddressManager>>,
+        anchor_peers: Vec<SocketAddr>,
+        min_outbound_groups: usize,
     ) -> Arc<Self> {
         let (tx, rx) = unbounded_channel::<()>();
+        // Anchors are registered as permanent requests before the first iteration, so they are reconnected
+        // ahead of any outbound peer selected from the address manager
+        let connection_requests = anchor_peers.into_iter().map(|address| (address, ConnectionRequest::new(true))).collect();
         let manager = Arc::new(Self {
             p2p_adaptor,
             outbound_target,
+            min_outbound_groups: min(min_outbound_groups, outbound_target),
             inbound_limit,
             address_manager,
-            connection_requests: Default::default(),
+            connection_requests: TokioMutex::new(connection_requests),
+            protected_peers: Default::default(),
             force_next_iteration: tx,
             shutdown_signal: SingleTrigger::new(),
             dns_seeders,
@@ -95,14 +110,17 @@ impl ConnectionManager {
 
     async fn handle_event(self: Arc<Self>) {
         debug!("Starting connection loop iteration");
-        let peers = self.p2p_adaptor.active_peers();
-        let peer_by_address: HashMap<SocketAddr, Peer> = peers.into_iter().map(|peer| (peer.net_address(), peer)).collect();
-
-        self.handle_connection_requests(&peer_by_address).await;
+        self.handle_connection_requests(&self.peer_by_address()).await;
+        // Refresh the peers so that connections just made by requests count towards the outbound target
+        let peer_by_address = self.peer_by_address();
         self.handle_outbound_connections(&peer_by_address).await;
         self.handle_inbound_connections(&peer_by_address).await;
     }
 
+    fn peer_by_address(&self) -> HashMap<SocketAddr, Peer> {
+        self.p2p_adaptor.active_peers().into_iter().map(|peer| (peer.net_address(), peer)).collect()
+    }
+
     pub async fn add_connection_request(&self, address: SocketAddr, is_permanent: bool) {
         // If the request already exists, it resets the attempts count and overrides the `is_permanent` setting.
         self.connection_requests.lock().await.insert(address, ConnectionRequest::new(is_permanent));
@@ -163,11 +181,21 @@ impl ConnectionManager {
         let active_outbound: HashSet<kaspa_addressmanager::NetAddress> =
             peer_by_address.values().filter(|peer| peer.is_outbound()).map(|peer| peer.net_address().into()).collect();
         if active_outbound.len() >= self.outbound_target {
+            self.rotate_outbound_for_diversity(peer_by_address).await;
             return;
         }
 
         let mut missing_connections = self.outbound_target - active_outbound.len();
-        let mut addr_iter = self.address_manager.lock().iterate_prioritized_random_addresses(active_outbound);
+        let mut addr_iter = {
+            let mut amgr_lock = self.address_manager.lock();
+            amgr_lock.expire_stale_addresses();
+            let connected = active_outbound.iter().copied().collect_vec();
+            GroupDiverseIterator::new(
+                amgr_lock.iterate_prioritized_random_addresses(active_outbound),
+                connected,
+                self.min_outbound_groups,
+            )
+        };
 
         let mut progressing = true;
         let mut connecting = true;
@@ -221,6 +249,7 @@ impl ConnectionManager {
                     Err(err) => {
                         debug!("Failed connecting to {:?}, err: {}", net_addr, err);
                         self.address_manager.lock().mark_connection_failure(net_addr);
+                        addr_iter.release(net_addr);
                     }
                 }
             }
@@ -238,6 +267,42 @@ impl ConnectionManager {
         }
     }
 
+    /// When the outbound peers are at the target but span fewer than `min_outbound_groups` network groups, disconnects
+    /// a peer of the most crowded group, provided the address manager knows an address of an uncovered group. The freed
+    /// slot is then refilled with a diverse candidate. Permanent peers (including anchors) and protected peers are never
+    /// disconnected
+    async fn rotate_outbound_for_diversity(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
+        let mut groups: HashMap<PrefixBucket, Vec<&Peer>> = HashMap::new();
+        for peer in peer_by_address.values().filter(|peer| peer.is_outbound()) {
+            groups.entry(NetAddress::from(peer.net_address()).prefix_bucket()).or_default().push(peer);
+        }
+        if groups.len() >= self.min_outbound_groups {
+            return;
+        }
+        let has_uncovered_candidate =
+            self.address_manager.lock().get_all_addresses().iter().any(|address| !groups.contains_key(&address.prefix_bucket()));
+        if !has_uncovered_candidate {
+            return;
+        }
+
+        let requests = self.connection_requests.lock().await;
+        let protected_peers = self.protected_peers.lock().clone();
+        let evictable = groups
+            .into_values()
+            .filter(|peers| peers.len() > 1)
+            .max_by_key(|peers| peers.len())
+            .into_iter()
+            .flatten()
+            .filter(|peer| !requests.contains_key(&peer.net_address()) && !protected_peers.contains(&peer.identity()))
+            .collect_vec();
+        drop(requests);
+        if let Some(peer) = evictable.choose(&mut thread_rng()) {
+            debug!("Disconnecting from {} to diversify the network groups of outbound peers", peer.net_address());
+            self.p2p_adaptor.terminate(peer.key()).await;
+            self.force_next_iteration.send(()).unwrap();
+        }
+    }
+
     async fn handle_inbound_connections(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
         let active_inbound = peer_by_address.values().filter(|peer| !peer.is_outbound()).collect_vec();
         let active_inbound_len = active_inbound.len();
@@ -245,8 +310,14 @@ impl ConnectionManager {
             return;
         }
 
-        let mut futures = Vec::with_capacity(active_inbound_len - self.inbound_limit);
-        for peer in active_inbound.choose_multiple(&mut thread_rng(), active_inbound_len - self.inbound_limit) {
+        // Protected peers count towards the limit but are never chosen for eviction
+        let evictable = {
+            let protected_peers = self.protected_peers.lock();
+            active_inbound.into_iter().filter(|peer| !protected_peers.contains(&peer.identity())).collect_vec()
+        };
+        let evict_count = min(active_inbound_len - self.inbound_limit, evictable.len());
+        let mut futures = Vec::with_capacity(evict_count);
+        for peer in evictable.choose_multiple(&mut thread_rng(), evict_count) {
             debug!("Disconnecting from {} because we're above the inbound limit", peer.net_address());
             futures.push(self.p2p_adaptor.terminate(peer.key()));
         }
@@ -302,12 +373,40 @@ impl ConnectionManager {
         info!("Retrieved {} addresses from DNS seeder {}", addrs_len, seeder);
         let mut amgr_lock = self.address_manager.lock();
         for addr in addrs {
-            amgr_lock.add_address(NetAddress::new(addr.ip().into(), addr.port()));
+            amgr_lock.add_address(NetAddress::new(addr.ip().into(), addr.port()), None);
         }
 
         addrs_len
     }
 
+    /// Marks the peer with the given identity as protected from eviction, or removes its protection.
+    /// Protection applies to any connection with that peer identity, including future ones.
+    pub fn set_protected(&self, peer_id: PeerId, is_protected: bool) {
+        let mut protected_peers = self.protected_peers.lock();
+        if is_protected {
+            protected_peers.insert(peer_id);
+        } else {
+            protected_peers.remove(&peer_id);
+        }
+    }
+
+    /// Returns whether the peer with the given identity is protected from eviction.
+    pub fn is_protected(&self, peer_id: PeerId) -> bool {
+        self.protected_peers.lock().contains(&peer_id)
+    }
+
+    /// Disconnects from the active peer with the given identity, regardless of its protection.
+    /// Returns `false` if no such peer is connected.
+    pub async fn disconnect(&self, peer_id: PeerId) -> bool {
+        match self.p2p_adaptor.find_peer(peer_id) {
+            Some(router) => {
+                router.close().await;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Bans the given IP and disconnects from all the peers with that IP.
     ///
     /// _GO-KASPAD: BanByIP_
@@ -323,6 +422,20 @@ impl ConnectionManager {
         self.address_manager.lock().ban(ip.into());
     }
 
+    /// Bans all IPs within `range` for `duration` (permanently if `None`) and disconnects from all the peers within it.
+    /// Ranges containing a permanent connection, as well as overly broad ranges, are not banned.
+    pub async fn ban_range(&self, range: IpNet, duration: Option<Duration>) {
+        if is_ban_range_too_broad(range) || self.range_has_permanent_connection(range).await {
+            return;
+        }
+        for peer in self.p2p_adaptor.active_peers() {
+            if range.contains(&peer.net_address().ip().to_canonical()) {
+                self.p2p_adaptor.terminate(peer.key()).await;
+            }
+        }
+        self.address_manager.lock().ban_range(range, duration);
+    }
+
     /// Returns whether the given address is banned.
     pub async fn is_banned(&self, address: &SocketAddr) -> bool {
         !self.is_permanent(address).await && self.address_manager.lock().is_banned(address.ip().into())
@@ -337,4 +450,77 @@ impl ConnectionManager {
     pub async fn ip_has_permanent_connection(&self, ip: IpAddr) -> bool {
         self.connection_requests.lock().await.iter().any(|(address, request)| request.is_permanent && address.ip() == ip)
     }
+
+    /// Returns whether some permanent request has an IP within `range`.
+    pub async fn range_has_permanent_connection(&self, range: IpNet) -> bool {
+        self.connection_requests
+            .lock()
+            .await
+            .iter()
+            .any(|(address, request)| request.is_permanent && range.contains(&address.ip().to_canonical()))
+    }
+}
+
+/// Wraps the prioritized outbound candidates such that while the outbound peers span fewer than `min_groups` network
+/// groups ([`PrefixBucket`]s), candidates from already covered groups are deferred. Deferred candidates are returned
+/// only once no candidate from an uncovered group remains
+struct GroupDiverseIterator<I> {
+    candidates: I,
+    deferred: VecDeque<NetAddress>,
+    /// The number of connected or pending outbound peers per group
+    groups: HashMap<PrefixBucket, usize>,
+    min_groups: usize,
+}
+
+impl<I: ExactSizeIterator<Item = NetAddress>> GroupDiverseIterator<I> {
+    fn new(candidates: I, connected: Vec<NetAddress>, min_groups: usize) -> Self {
+        let mut groups = HashMap::new();
+        for address in connected {
+            *groups.entry(address.prefix_bucket()).or_default() += 1;
+        }
+        Self { candidates, deferred: VecDeque::new(), groups, min_groups }
+    }
+
+    /// Releases the group slot taken by a returned candidate which failed to connect
+    fn release(&mut self, address: NetAddress) {
+        if let Entry::Occupied(mut entry) = self.groups.entry(address.prefix_bucket()) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+    }
+
+    fn next_candidate(&mut self) -> Option<NetAddress> {
+        if self.groups.len() < self.min_groups {
+            // A released group may turn deferred candidates eligible again
+            if let Some(i) = self.deferred.iter().position(|address| !self.groups.contains_key(&address.prefix_bucket())) {
+                return self.deferred.remove(i);
+            }
+            for candidate in self.candidates.by_ref() {
+                if !self.groups.contains_key(&candidate.prefix_bucket()) {
+                    return Some(candidate);
+                }
+                self.deferred.push_back(candidate);
+            }
+        }
+        self.candidates.next().or_else(|| self.deferred.pop_front())
+    }
 }
+
+impl<I: ExactSizeIterator<Item = NetAddress>> Iterator for GroupDiverseIterator<I> {
+    type Item = NetAddress;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let address = self.next_candidate()?;
+        *self.groups.entry(address.prefix_bucket()).or_default() += 1;
+        Some(address)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.candidates.len() + self.deferred.len();
+        (len, Some(len))
+    }
+}
+
+impl<I: ExactSizeIterator<Item = NetAddress>> ExactSizeIterator for GroupDiverseIterator<I> {}
@@ -13,10 +13,16 @@ use kaspa_consensus_core::{
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
-    BlockHashSet, BlueWorkType, ChainPath, Hash,
+    BlockHashSet, BlueWorkType, ChainPath, ChainScore, Hash,
+};
+use kaspa_utils::{networking::PeerId, sync::rwlock::*};
+use parking_lot::{Condvar, Mutex};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use kaspa_utils::sync::rwlock::*;
-use std::{ops::Deref, sync::Arc};
 
 pub use tokio::task::spawn_blocking;
 
@@ -39,8 +45,67 @@ impl SessionWriteGuard<'_> {
     }
 }
 
+/// The maximal number of [`PruningPointGuard`]s held at once across all holders
+pub const MAX_PRUNING_POINT_GUARDS: usize = 16;
+
+/// The maximal number of [`PruningPointGuard`]s held at once by a single holder (i.e., peer)
+pub const MAX_PRUNING_POINT_GUARDS_PER_HOLDER: usize = 1;
+
+#[derive(Default)]
+struct PruningPointHoldersState {
+    count: usize,
+    per_holder: HashMap<PeerId, usize>,
+    /// The time pruning point movements were deferred for since holders were last found released
+    deferred: Duration,
+}
+
+/// The active [`PruningPointGuard`]s along with a condition variable signaled when a guard is dropped
+#[derive(Default)]
+struct PruningPointHolders {
+    state: Mutex<PruningPointHoldersState>,
+    released: Condvar,
+}
+
+/// A guard delaying pruning point movements for as long as it is held (up to a bounded deferral applied by the pruning
+/// processor). Unlike a consensus session, the guard does not block pruning itself and can thus be held across
+/// multiple sessions and through long waits for peers (e.g., while serving the pruning point UTXO set in chunks).
+pub struct PruningPointGuard {
+    holders: Arc<PruningPointHolders>,
+    holder: PeerId,
+}
+
+impl Drop for PruningPointGuard {
+    fn drop(&mut self) {
+        let mut state = self.holders.state.lock();
+        state.count -= 1;
+        if let Entry::Occupied(mut entry) = state.per_holder.entry(self.holder) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+        if state.count == 0 {
+            self.holders.released.notify_all();
+        }
+    }
+}
+
+/// The outcome of [`SessionLock::defer_pruning_point_movement`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PruningPointDeferral {
+    /// No guard is held
+    Released,
+    /// The deferral budget is exhausted while guards are still held
+    Expired,
+    /// The wait was interrupted by the exit signal
+    Exiting,
+}
+
 #[derive(Clone)]
-pub struct SessionLock(Arc<RfRwLock>);
+pub struct SessionLock {
+    lock: Arc<RfRwLock>,
+    pruning_point_holders: Arc<PruningPointHolders>,
+}
 
 impl Default for SessionLock {
     fn default() -> Self {
@@ -50,23 +115,76 @@ impl Default for SessionLock {
 
 impl SessionLock {
     pub fn new() -> SessionLock {
-        SessionLock(Arc::new(RfRwLock::new()))
+        SessionLock { lock: Arc::new(RfRwLock::new()), pruning_point_holders: Default::default() }
     }
 
     pub async fn read_owned(&self) -> SessionOwnedReadGuard {
-        SessionOwnedReadGuard(Arc::new(self.0.clone().read_owned().await))
+        SessionOwnedReadGuard(Arc::new(self.lock.clone().read_owned().await))
     }
 
     pub async fn read(&self) -> SessionReadGuard {
-        SessionReadGuard(self.0.read().await)
+        SessionReadGuard(self.lock.read().await)
     }
 
     pub fn blocking_read(&self) -> SessionReadGuard {
-        SessionReadGuard(self.0.blocking_read())
+        SessionReadGuard(self.lock.blocking_read())
     }
 
     pub fn blocking_write(&self) -> SessionWriteGuard<'_> {
-        SessionWriteGuard(self.0.blocking_write())
+        SessionWriteGuard(self.lock.blocking_write())
+    }
+
+    /// Acquires a guard delaying pruning point movements until it is dropped, see [`PruningPointGuard`]. Returns `None`
+    /// if `holder` or all holders together already hold the maximal number of guards
+    pub fn try_hold_pruning_point(&self, holder: PeerId) -> Option<PruningPointGuard> {
+        let mut state = self.pruning_point_holders.state.lock();
+        let held = state.per_holder.get(&holder).copied().unwrap_or_default();
+        if state.count >= MAX_PRUNING_POINT_GUARDS || held >= MAX_PRUNING_POINT_GUARDS_PER_HOLDER {
+            return None;
+        }
+        state.count += 1;
+        *state.per_holder.entry(holder).or_default() += 1;
+        Some(PruningPointGuard { holders: self.pruning_point_holders.clone(), holder })
+    }
+
+    /// Returns the number of currently held [`PruningPointGuard`]s
+    pub fn pruning_point_holders(&self) -> usize {
+        self.pruning_point_holders.state.lock().count
+    }
+
+    /// Blocks until all [`PruningPointGuard`]s are dropped, polling `is_exiting` every `poll_interval`.
+    ///
+    /// The total time pruning point movements are deferred for is capped by `max_deferral`, which is only replenished
+    /// once all guards are found released. Hence, a steady stream of new guards cannot defer pruning point movements
+    /// indefinitely: once the budget is exhausted, subsequent calls return immediately until holders release.
+    pub fn defer_pruning_point_movement(
+        &self,
+        max_deferral: Duration,
+        poll_interval: Duration,
+        is_exiting: impl Fn() -> bool,
+    ) -> PruningPointDeferral {
+        let holders = &self.pruning_point_holders;
+        let mut state = holders.state.lock();
+        let start = Instant::now();
+        let deadline = start + max_deferral.saturating_sub(state.deferred);
+        let outcome = loop {
+            if state.count == 0 {
+                break PruningPointDeferral::Released;
+            }
+            if is_exiting() {
+                break PruningPointDeferral::Exiting;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break PruningPointDeferral::Expired;
+            }
+            holders.released.wait_until(&mut state, deadline.min(now + poll_interval));
+        };
+        match outcome {
+            PruningPointDeferral::Released => state.deferred = Duration::ZERO,
+            _ => state.deferred += start.elapsed(),
+        }
+        outcome
     }
 }
 
@@ -113,6 +231,15 @@ impl ConsensusInstance {
     pub fn unguarded_session(&self) -> ConsensusSessionOwned {
         ConsensusSessionOwned::new_without_session_guard(self.consensus.clone())
     }
+
+    /// Returns a guard delaying pruning point movements for as long as it is held, or `None` if the guard limits are
+    /// reached (see [`SessionLock::try_hold_pruning_point`]). Long read operations spanning several sessions (which must
+    /// remain short-lived) should hold this guard so that the pruning point does not move between sessions. Pruning point
+    /// movements are only deferred for a bounded time, after which they proceed regardless, so readers must still handle
+    /// a moved pruning point.
+    pub fn try_hold_pruning_point(&self, holder: PeerId) -> Option<PruningPointGuard> {
+        self.session_lock.try_hold_pruning_point(holder)
+    }
 }
 
 pub struct ConsensusSessionBlocking<'a> {
@@ -200,6 +327,11 @@ impl ConsensusSessionOwned {
         self.consensus.calculate_transaction_storage_mass(transaction)
     }
 
+    pub fn calc_block_subsidy(&self, daa_score: u64) -> u64 {
+        // This method performs pure calculations so no need for an async wrapper
+        self.consensus.calc_block_subsidy(daa_score)
+    }
+
     pub fn get_virtual_daa_score(&self) -> u64 {
         // Accessing cached virtual fields is lock-free and does not require spawn_blocking
         self.consensus.get_virtual_daa_score()
@@ -313,6 +445,10 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(|c| c.get_chain_block_samples()).await
     }
 
+    pub async fn async_get_chain_block_by_score(&self, score: ChainScore, value: u64) -> ConsensusResult<Hash> {
+        self.clone().spawn_blocking(move |c| c.get_chain_block_by_score(score, value)).await
+    }
+
     /// Returns the antipast of block `hash` from the POV of `context`, i.e. `antipast(hash) ∩ past(context)`.
     /// Since this might be an expensive operation for deep blocks, we allow the caller to specify a limit
     /// `max_traversal_allowed` on the maximum amount of blocks to traverse for obtaining the answer
@@ -451,3 +587,70 @@ impl ConsensusSessionOwned {
 }
 
 pub type ConsensusProxy = ConsensusSessionOwned;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_pruning_point_guard_limits() {
+        let lock = SessionLock::new();
+        let peer = PeerId::new(uuid_from(0));
+
+        // A single guard per holder
+        let guard = lock.try_hold_pruning_point(peer).unwrap();
+        assert!(lock.try_hold_pruning_point(peer).is_none());
+        drop(guard);
+        let guard = lock.try_hold_pruning_point(peer).unwrap();
+
+        // A bounded number of guards across holders
+        let guards =
+            (1..MAX_PRUNING_POINT_GUARDS).map(|i| lock.try_hold_pruning_point(PeerId::new(uuid_from(i))).unwrap()).collect::<Vec<_>>();
+        assert_eq!(lock.pruning_point_holders(), MAX_PRUNING_POINT_GUARDS);
+        assert!(lock.try_hold_pruning_point(PeerId::new(uuid_from(MAX_PRUNING_POINT_GUARDS))).is_none());
+        drop(guards);
+        drop(guard);
+        assert_eq!(lock.pruning_point_holders(), 0);
+    }
+
+    #[test]
+    fn test_pruning_point_deferral() {
+        let lock = SessionLock::new();
+        let poll = Duration::from_millis(5);
+        let max_deferral = Duration::from_millis(100);
+        assert_eq!(lock.defer_pruning_point_movement(max_deferral, poll, || false), PruningPointDeferral::Released);
+
+        // Waits for a guard released in time
+        let guard = lock.try_hold_pruning_point(PeerId::new(uuid_from(1))).unwrap();
+        let releaser = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            drop(guard);
+        });
+        assert_eq!(lock.defer_pruning_point_movement(max_deferral, poll, || false), PruningPointDeferral::Released);
+        releaser.join().unwrap();
+
+        // Guards which are never released defer movements up to the budget, which is shared across movements
+        let _guard = lock.try_hold_pruning_point(PeerId::new(uuid_from(2))).unwrap();
+        let start = Instant::now();
+        assert_eq!(lock.defer_pruning_point_movement(max_deferral, poll, || false), PruningPointDeferral::Expired);
+        assert!(start.elapsed() >= max_deferral);
+        let start = Instant::now();
+        assert_eq!(lock.defer_pruning_point_movement(max_deferral, poll, || false), PruningPointDeferral::Expired);
+        assert!(start.elapsed() < max_deferral);
+        assert_eq!(lock.defer_pruning_point_movement(max_deferral, poll, || true), PruningPointDeferral::Exiting);
+
+        // The budget is replenished once holders are found released
+        drop(_guard);
+        assert_eq!(lock.defer_pruning_point_movement(max_deferral, poll, || false), PruningPointDeferral::Released);
+        let _guard = lock.try_hold_pruning_point(PeerId::new(uuid_from(3))).unwrap();
+        let start = Instant::now();
+        assert_eq!(lock.defer_pruning_point_movement(max_deferral, poll, || false), PruningPointDeferral::Expired);
+        assert!(start.elapsed() >= max_deferral);
+    }
+
+    fn uuid_from(i: usize) -> Uuid {
+        Uuid::from_u128(i as u128)
+    }
+}
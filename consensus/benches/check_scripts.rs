@@ -84,7 +84,7 @@ fn benchmark_check_scripts(c: &mut Criterion) {
                 let cache = Cache::new(inputs_count as u64);
                 b.iter(|| {
                     cache.clear();
-                    check_scripts_sequential(black_box(&cache), black_box(&tx.as_verifiable()), false).unwrap();
+                    check_scripts_sequential(black_box(&cache), black_box(&tx.as_verifiable()), false, None).unwrap();
                 })
             });
 
@@ -93,7 +93,7 @@ fn benchmark_check_scripts(c: &mut Criterion) {
                 let cache = Cache::new(inputs_count as u64);
                 b.iter(|| {
                     cache.clear();
-                    check_scripts_par_iter(black_box(&cache), black_box(&tx.as_verifiable()), false).unwrap();
+                    check_scripts_par_iter(black_box(&cache), black_box(&tx.as_verifiable()), false, None).unwrap();
                 })
             });
 
@@ -107,8 +107,14 @@ fn benchmark_check_scripts(c: &mut Criterion) {
                         let cache = Cache::new(inputs_count as u64);
                         b.iter(|| {
                             cache.clear();
-                            check_scripts_par_iter_pool(black_box(&cache), black_box(&tx.as_verifiable()), black_box(&pool), false)
-                                .unwrap();
+                            check_scripts_par_iter_pool(
+                                black_box(&cache),
+                                black_box(&tx.as_verifiable()),
+                                black_box(&pool),
+                                false,
+                                None,
+                            )
+                            .unwrap();
                         })
                     });
                 }
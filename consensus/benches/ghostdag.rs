@@ -0,0 +1,45 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use kaspa_consensus::consensus::test_consensus::TestConsensus;
+use kaspa_consensus_core::config::{params::TESTNET11_PARAMS, ConfigBuilder};
+use kaspa_hashes::Hash;
+use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+
+const LAYERS: u64 = 40;
+
+/// Builds a DAG of `LAYERS` layers of `width` blocks each, where every block points at a few random blocks of the
+/// previous layer, so that blocks have wide anticones. Returns the blocks of the last layer
+fn build_wide_dag(consensus: &TestConsensus, genesis: Hash, width: u64) -> Vec<Hash> {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let mut rng = SmallRng::seed_from_u64(42);
+    let mut layer = vec![genesis];
+    for i in 0..LAYERS {
+        layer = (0..width)
+            .map(|j| {
+                let hash = Hash::from_u64_word(1 + i * width + j);
+                let count = rng.gen_range(2..=4).min(layer.len());
+                let parents = layer.choose_multiple(&mut rng, count).copied().collect();
+                runtime.block_on(consensus.add_block_with_parents(hash, parents)).unwrap();
+                hash
+            })
+            .collect();
+    }
+    layer
+}
+
+/// Measures running GHOSTDAG for a block merging all tips of wide DAGs at the high K of 10 BPS networks, which is
+/// dominated by the k-cluster checks of the mergeset candidates
+fn ghostdag_benchmark(c: &mut Criterion) {
+    let config = ConfigBuilder::new(TESTNET11_PARAMS).skip_proof_of_work().build();
+    for width in [8, 24] {
+        let consensus = TestConsensus::new(&config);
+        let wait_handles = consensus.init();
+        let tips = build_wide_dag(&consensus, config.genesis.hash, width);
+        c.bench_function(&format!("GHOSTDAG of a block merging a DAG of width {width} (K={})", config.ghostdag_k), |b| {
+            b.iter(|| black_box(consensus.ghostdag_manager().ghostdag(&tips)))
+        });
+        consensus.shutdown(wait_handles);
+    }
+}
+
+criterion_group!(benches, ghostdag_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use itertools::Itertools;
+use kaspa_consensus::{
+    params::MAINNET_PARAMS,
+    processes::{mass::TxComputeMassCache, merkle::hash_merkle_leaves},
+};
+use kaspa_consensus_core::{
+    hashing,
+    mass::MassCalculator,
+    merkle::calc_hash_merkle_root,
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{ScriptPublicKey, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput},
+};
+use kaspa_hashes::TransactionID;
+use kaspa_merkle::calc_merkle_root;
+use kaspa_utils::iter::parallelism_in_power_steps;
+
+const BLOCK_TXS: u64 = 1000;
+
+fn generate_transaction(ins: usize, outs: usize, randomness: u64) -> Transaction {
+    let mut tx = Transaction::new(0, vec![], vec![], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+    for i in 0..ins {
+        let mut hasher = TransactionID::new();
+        hasher.write(i.to_le_bytes());
+        hasher.write(randomness.to_le_bytes());
+        tx.inputs.push(TransactionInput::new(TransactionOutpoint::new(hasher.finalize(), 0), vec![10; 66], 0, 1));
+    }
+    for _ in 0..outs {
+        tx.outputs.push(TransactionOutput::new(23456, ScriptPublicKey::from_vec(0, vec![101; 34])));
+    }
+    tx.finalize();
+    tx.set_mass(5000);
+    tx
+}
+
+/// Benchmarks the hash merkle root check of a 1000-tx block. Tx hashes are cached within transactions, so each
+/// iteration hashes a fresh clone of transactions which were never hashed
+pub fn parallel_merkle_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash merkle root 1000 txs");
+    let txs = (0..BLOCK_TXS).map(|i| generate_transaction(2, 2, i)).collect_vec();
+    group.bench_function("seq", |b| {
+        b.iter_batched(|| txs.clone(), |txs| black_box(calc_hash_merkle_root(txs.iter(), true)), BatchSize::SmallInput)
+    });
+
+    for threads in parallelism_in_power_steps() {
+        group.bench_function(format!("par {threads}"), |b| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            b.iter_batched(
+                || txs.clone(),
+                |txs| pool.install(|| black_box(calc_merkle_root(hash_merkle_leaves(&txs, true).into_iter()))),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmarks computing the compute masses of a 1000-tx block with and without the tx compute mass cache
+pub fn mass_cache_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compute mass 1000 txs");
+    let txs = (0..BLOCK_TXS).map(|i| generate_transaction(2, 2, i)).collect_vec();
+    let leaves = txs.iter().map(|tx| hashing::tx::hash(tx, true)).collect_vec();
+    let mass_calculator = MassCalculator::new_with_consensus_params(&MAINNET_PARAMS);
+    group.bench_function("calc", |b| b.iter(|| black_box(txs.iter().map(|tx| mass_calculator.calc_tx_compute_mass(tx)).sum::<u64>())));
+
+    let cache = TxComputeMassCache::new(BLOCK_TXS as usize);
+    txs.iter().for_each(|tx| cache.insert(tx, true, mass_calculator.calc_tx_compute_mass(tx)));
+    group.bench_function("cached", |b| {
+        b.iter(|| {
+            black_box(txs.iter().zip(leaves.iter()).map(|(tx, &hash)| cache.get_or_calc(&mass_calculator, tx, hash)).sum::<u64>())
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parallel_merkle_benchmark, mass_cache_benchmark);
+criterion_main!(benches);
@@ -52,6 +52,7 @@ export interface ITransactionVerboseData {
     computeMass : bigint;
     blockHash : HexString;
     blockTime : bigint;
+    payloadHex : HexString;
 }
 "#;
 
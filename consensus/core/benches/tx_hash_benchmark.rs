@@ -0,0 +1,73 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use kaspa_consensus_core::hashing;
+use kaspa_consensus_core::merkle::calc_hash_merkle_root;
+use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+use kaspa_consensus_core::tx::{
+    ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput,
+};
+use kaspa_merkle::calc_merkle_root;
+use smallvec::smallvec;
+
+/// Builds transactions resembling typical P2PK spends, 2 inputs and 2 outputs each
+fn block_transactions(count: u64) -> Vec<Transaction> {
+    let script_public_key = ScriptPublicKey::new(0, smallvec![0x20; 34]);
+    (0..count)
+        .map(|i| {
+            let inputs = (0..2)
+                .map(|j| TransactionInput::new(TransactionOutpoint::new(TransactionId::from_u64_word(i), j), vec![0x41; 66], 0, 1))
+                .collect();
+            let outputs = vec![
+                TransactionOutput::new(1000 + i, script_public_key.clone()),
+                TransactionOutput::new(2000 + i, script_public_key.clone()),
+            ];
+            Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]).with_mass(2000)
+        })
+        .collect()
+}
+
+/// Measures computing the hash merkle root of a block, as done on every block template update, over transactions
+/// which were never hashed (uncached) or whose hashes are already cached
+fn merkle_root_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash merkle root 300 txs");
+    let txs = block_transactions(300);
+    group.bench_function("uncached", |b| {
+        b.iter_batched(|| txs.clone(), |txs| black_box(calc_hash_merkle_root(txs.iter(), true)), BatchSize::SmallInput)
+    });
+
+    let hashed_txs = txs.clone();
+    calc_hash_merkle_root(hashed_txs.iter(), true);
+    group.bench_function("cached", |b| b.iter(|| black_box(calc_hash_merkle_root(hashed_txs.iter(), true))));
+    group.finish();
+}
+
+/// Measures the transaction hashing done along mempool insertion: validating a transaction for the mempool records its
+/// compute mass keyed by the transaction hash, and the block template then commits to the same hash in its merkle root.
+/// Without caching each transaction is hashed twice, whereas with caching the template reuses the hash of the insertion
+fn mempool_insert_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mempool insert 300 txs");
+    let txs = block_transactions(300);
+    group.bench_function("uncached", |b| {
+        b.iter_batched(
+            || txs.clone(),
+            |txs| {
+                let keys = txs.iter().map(|tx| hashing::tx::hash(tx, true)).collect::<Vec<_>>();
+                black_box((keys, calc_merkle_root(txs.iter().map(|tx| hashing::tx::hash(tx, true)))))
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("cached", |b| {
+        b.iter_batched(
+            || txs.clone(),
+            |txs| {
+                let keys = txs.iter().map(|tx| tx.hash(true)).collect::<Vec<_>>();
+                black_box((keys, calc_hash_merkle_root(txs.iter(), true)))
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, merkle_root_benchmark, mempool_insert_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,67 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionId, TransactionOutpoint, UtxoEntry};
+use kaspa_consensus_core::utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff};
+use smallvec::smallvec;
+use std::ops::Range;
+
+const DIFF_SIZE: u64 = 100_000;
+
+fn collection(outpoints: Range<u64>, daa_score: u64) -> UtxoCollection {
+    let script_public_key = ScriptPublicKey::new(0, smallvec![0x20; 34]);
+    outpoints
+        .map(|i| {
+            (
+                TransactionOutpoint::new(TransactionId::from_u64_word(i), 0),
+                UtxoEntry::new(1000 + i, script_public_key.clone(), daa_score, false),
+            )
+        })
+        .collect()
+}
+
+/// Builds two synthetic diffs of 100k added and 100k removed entries each, where the second spends half of the outputs
+/// created by the first, so that half of its removals cancel out while composing
+fn synthetic_diffs() -> (UtxoDiff, UtxoDiff) {
+    let first = UtxoDiff::new(collection(0..DIFF_SIZE, 1), collection(10 * DIFF_SIZE..11 * DIFF_SIZE, 0));
+    let second = UtxoDiff::new(
+        collection(DIFF_SIZE..2 * DIFF_SIZE, 2),
+        collection(0..DIFF_SIZE / 2, 1).into_iter().chain(collection(11 * DIFF_SIZE..11 * DIFF_SIZE + DIFF_SIZE / 2, 0)).collect(),
+    );
+    (first, second)
+}
+
+fn composition_benchmark(c: &mut Criterion) {
+    let (first, second) = synthetic_diffs();
+    c.bench_function("UTXO diff with_diff (100k entries)", |b| b.iter(|| black_box(first.with_diff(&second).unwrap())));
+    c.bench_function("UTXO diff with_diff_in_place (100k entries)", |b| {
+        b.iter_batched(
+            || first.clone(),
+            |mut diff| {
+                diff.with_diff_in_place(&second).unwrap();
+                diff
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+/// Measures accumulating a chain of smaller diffs, as done when resolving virtual over a deep reorg, followed by reverting
+/// it and releasing the vacant capacity
+fn accumulation_benchmark(c: &mut Criterion) {
+    const CHAIN_LENGTH: u64 = 100;
+    const BLOCK_DIFF_SIZE: u64 = DIFF_SIZE / CHAIN_LENGTH;
+    let diffs = (0..CHAIN_LENGTH)
+        .map(|i| UtxoDiff::new(collection(i * BLOCK_DIFF_SIZE..(i + 1) * BLOCK_DIFF_SIZE, i), UtxoCollection::new()))
+        .collect::<Vec<_>>();
+    c.bench_function("UTXO diff accumulate, revert and shrink (100 x 1k entries)", |b| {
+        b.iter(|| {
+            let mut accumulated = UtxoDiff::default();
+            diffs.iter().for_each(|diff| accumulated.with_diff_in_place(diff).unwrap());
+            diffs.iter().rev().for_each(|diff| accumulated.with_diff_in_place(&diff.as_reversed()).unwrap());
+            accumulated.shrink_if_sparse();
+            black_box(accumulated)
+        })
+    });
+}
+
+criterion_group!(benches, composition_benchmark, accumulation_benchmark);
+criterion_main!(benches);
@@ -1,4 +1,94 @@
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// The number of buckets of a [`LatencyHistogram`]. Bucket `i > 0` holds durations of `[2^(i-1), 2^i)` microseconds
+/// and the last bucket also holds all longer durations
+pub const LATENCY_BUCKETS: usize = 32;
+
+/// Block processing stages whose latencies are sampled by the processors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessingStage {
+    /// Full validation of an ordinary header, including GHOSTDAG
+    HeaderValidation = 0,
+    /// Body validation in isolation and in context
+    BodyValidation,
+    /// UTXO state calculation and verification of a chain block
+    UtxoResolution,
+    /// Database writes of header, body, UTXO and virtual state commits
+    Commit,
+}
+
+pub const PROCESSING_STAGES: usize = 4;
+
+impl ProcessingStage {
+    pub const ALL: [ProcessingStage; PROCESSING_STAGES] =
+        [ProcessingStage::HeaderValidation, ProcessingStage::BodyValidation, ProcessingStage::UtxoResolution, ProcessingStage::Commit];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ProcessingStage::HeaderValidation => "header validation",
+            ProcessingStage::BodyValidation => "body validation",
+            ProcessingStage::UtxoResolution => "UTXO resolution",
+            ProcessingStage::Commit => "commit",
+        }
+    }
+}
+
+/// A lock-free histogram of durations with power-of-two microsecond buckets
+#[derive(Default)]
+pub struct LatencyHistogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS],
+}
+
+impl LatencyHistogram {
+    pub fn record(&self, duration: Duration) {
+        let micros = duration.as_micros().min(u64::MAX as u128) as u64;
+        let bucket = ((u64::BITS - micros.leading_zeros()) as usize).min(LATENCY_BUCKETS - 1);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> LatencyHistogramSnapshot {
+        LatencyHistogramSnapshot { buckets: std::array::from_fn(|i| self.buckets[i].load(Ordering::Relaxed)) }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LatencyHistogramSnapshot {
+    pub buckets: [u64; LATENCY_BUCKETS],
+}
+
+impl LatencyHistogramSnapshot {
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+
+    /// Returns the upper bound in microseconds of the bucket holding the `q` quantile, or zero if the histogram is empty
+    pub fn quantile_micros(&self, q: f64) -> u64 {
+        let count = self.count();
+        if count == 0 {
+            return 0;
+        }
+        let rank = ((q * count as f64).ceil() as u64).clamp(1, count);
+        let mut seen = 0;
+        for (bucket, &bucket_count) in self.buckets.iter().enumerate() {
+            seen += bucket_count;
+            if seen >= rank {
+                return 1 << bucket;
+            }
+        }
+        unreachable!()
+    }
+}
+
+impl core::ops::Sub for &LatencyHistogramSnapshot {
+    type Output = LatencyHistogramSnapshot;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        LatencyHistogramSnapshot { buckets: std::array::from_fn(|i| self.buckets[i].saturating_sub(rhs.buckets[i])) }
+    }
+}
 
 #[derive(Default)]
 pub struct ProcessingCounters {
@@ -11,9 +101,27 @@ pub struct ProcessingCounters {
     pub chain_block_counts: AtomicU64,
     pub chain_disqualified_counts: AtomicU64,
     pub mass_counts: AtomicU64,
+    /// Number of reachability reindex operations
+    pub reindex_counts: AtomicU64,
+    /// Overall number of blocks traversed by reachability reindex operations
+    pub reindex_subtree_sizes: AtomicU64,
+    /// Overall duration of reachability reindex operations in microseconds
+    pub reindex_micros: AtomicU64,
+    /// Number of header tasks currently queued on the header processor pool (a gauge)
+    pub header_queue_len: AtomicU64,
+    /// Number of block tasks currently queued on the body processor pool (a gauge)
+    pub body_queue_len: AtomicU64,
+    /// Number of blocks currently awaiting virtual processing (a gauge)
+    pub virtual_queue_len: AtomicU64,
+    /// Sampled latencies per processing stage, indexed by [`ProcessingStage`]
+    pub stage_latencies: [LatencyHistogram; PROCESSING_STAGES],
 }
 
 impl ProcessingCounters {
+    pub fn record_latency(&self, stage: ProcessingStage, duration: Duration) {
+        self.stage_latencies[stage as usize].record(duration);
+    }
+
     pub fn snapshot(&self) -> ProcessingCountersSnapshot {
         ProcessingCountersSnapshot {
             blocks_submitted: self.blocks_submitted.load(Ordering::Relaxed),
@@ -25,6 +133,13 @@ impl ProcessingCounters {
             chain_block_counts: self.chain_block_counts.load(Ordering::Relaxed),
             chain_disqualified_counts: self.chain_disqualified_counts.load(Ordering::Relaxed),
             mass_counts: self.mass_counts.load(Ordering::Relaxed),
+            reindex_counts: self.reindex_counts.load(Ordering::Relaxed),
+            reindex_subtree_sizes: self.reindex_subtree_sizes.load(Ordering::Relaxed),
+            reindex_micros: self.reindex_micros.load(Ordering::Relaxed),
+            header_queue_len: self.header_queue_len.load(Ordering::Relaxed),
+            body_queue_len: self.body_queue_len.load(Ordering::Relaxed),
+            virtual_queue_len: self.virtual_queue_len.load(Ordering::Relaxed),
+            stage_latencies: std::array::from_fn(|i| self.stage_latencies[i].snapshot()),
         }
     }
 }
@@ -40,6 +155,19 @@ pub struct ProcessingCountersSnapshot {
     pub chain_block_counts: u64,
     pub chain_disqualified_counts: u64,
     pub mass_counts: u64,
+    pub reindex_counts: u64,
+    pub reindex_subtree_sizes: u64,
+    pub reindex_micros: u64,
+    pub header_queue_len: u64,
+    pub body_queue_len: u64,
+    pub virtual_queue_len: u64,
+    pub stage_latencies: [LatencyHistogramSnapshot; PROCESSING_STAGES],
+}
+
+impl ProcessingCountersSnapshot {
+    pub fn stage_latency(&self, stage: ProcessingStage) -> &LatencyHistogramSnapshot {
+        &self.stage_latencies[stage as usize]
+    }
 }
 
 impl core::ops::Sub for &ProcessingCountersSnapshot {
@@ -56,6 +184,41 @@ impl core::ops::Sub for &ProcessingCountersSnapshot {
             chain_block_counts: self.chain_block_counts.saturating_sub(rhs.chain_block_counts),
             chain_disqualified_counts: self.chain_disqualified_counts.saturating_sub(rhs.chain_disqualified_counts),
             mass_counts: self.mass_counts.saturating_sub(rhs.mass_counts),
+            reindex_counts: self.reindex_counts.saturating_sub(rhs.reindex_counts),
+            reindex_subtree_sizes: self.reindex_subtree_sizes.saturating_sub(rhs.reindex_subtree_sizes),
+            reindex_micros: self.reindex_micros.saturating_sub(rhs.reindex_micros),
+            // Queue lengths are gauges, so the delta holds the most recent value
+            header_queue_len: self.header_queue_len,
+            body_queue_len: self.body_queue_len,
+            virtual_queue_len: self.virtual_queue_len,
+            stage_latencies: std::array::from_fn(|i| &self.stage_latencies[i] - &rhs.stage_latencies[i]),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_histogram() {
+        let histogram = LatencyHistogram::default();
+        assert_eq!(histogram.snapshot().quantile_micros(0.5), 0);
+
+        // 90 samples of 3µs (bucket [2, 4)) and 10 samples of 1ms (bucket [512, 1024))
+        (0..90).for_each(|_| histogram.record(Duration::from_micros(3)));
+        (0..10).for_each(|_| histogram.record(Duration::from_millis(1)));
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.count(), 100);
+        assert_eq!(snapshot.quantile_micros(0.5), 4);
+        assert_eq!(snapshot.quantile_micros(0.9), 4);
+        assert_eq!(snapshot.quantile_micros(0.95), 1024);
+        assert_eq!(snapshot.quantile_micros(0.99), 1024);
+
+        // Deltas only account for the samples recorded in between
+        histogram.record(Duration::from_secs(3600));
+        let delta = &histogram.snapshot() - &snapshot;
+        assert_eq!(delta.count(), 1);
+        assert_eq!(delta.quantile_micros(0.5), 1 << (LATENCY_BUCKETS - 1));
+    }
+}
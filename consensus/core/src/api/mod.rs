@@ -20,7 +20,7 @@ use crate::{
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
-    BlockHashSet, BlueWorkType, ChainPath,
+    BlockHashSet, BlueWorkType, ChainPath, ChainScore,
 };
 use kaspa_hashes::Hash;
 
@@ -97,6 +97,11 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the subsidy awarded to a block with the given DAA score.
+    fn calc_block_subsidy(&self, daa_score: u64) -> u64 {
+        unimplemented!()
+    }
+
     /// Returns an aggregation of consensus stats. Designed to be a fast call.
     fn get_stats(&self) -> ConsensusStats {
         unimplemented!()
@@ -170,6 +175,12 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the selected chain block with the highest `score` not exceeding `value`, i.e., the chain block which
+    /// was the sink when the chain reached `value`. Only chain blocks from the pruning point up to the sink are retained
+    fn get_chain_block_by_score(&self, score: ChainScore, value: u64) -> ConsensusResult<Hash> {
+        unimplemented!()
+    }
+
     fn get_virtual_parents(&self) -> BlockHashSet {
         unimplemented!()
     }
@@ -362,6 +373,16 @@ pub trait ConsensusApi: Send + Sync {
     fn finality_point(&self) -> Hash {
         unimplemented!()
     }
+
+    /// Flushes the DB write-ahead log and memtables to disk
+    fn flush_storage(&self) -> ConsensusResult<()> {
+        unimplemented!()
+    }
+
+    /// Manually compacts the whole DB. Blocks until the compaction completes, which might take a while on large DBs
+    fn compact_storage(&self) -> ConsensusResult<()> {
+        unimplemented!()
+    }
 }
 
 pub type DynConsensus = Arc<dyn ConsensusApi>;
@@ -0,0 +1,402 @@
+//!
+//! Binary encoding of headers, transactions and blocks used by the serialized RPC methods.
+//!
+//! Headers and transactions are encoded as their hashing preimage: hashing an encoded header with the block hasher
+//! yields the header hash, and hashing an encoded transaction with the transaction hasher yields the transaction hash.
+//! The format is specific to this node and is not verified against other implementations. Integers are little endian
+//! and lengths are encoded as `u64`.
+//! The transaction mass is not part of the transaction encoding, hence decoded transactions have no mass set. Since the
+//! hash merkle root of blocks for which storage mass is active commits to the masses of their transactions, the block
+//! encoding always follows each transaction with its mass, which is zero for blocks preceding the activation.
+//!
+
+use crate::{
+    block::Block,
+    errors::codec::{CodecError, CodecResult},
+    hashing::{self, tx::TX_ENCODING_FULL, HasherExtensions},
+    header::Header,
+    subnets::{SubnetworkId, SUBNETWORK_ID_SIZE},
+    tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput},
+    BlueWorkType,
+};
+use kaspa_hashes::{Hash, HasherBase, HASH_SIZE};
+
+/// Collects written data into a byte vector, allowing to reuse the hashing serialization code for encoding
+struct Writer(Vec<u8>);
+
+impl HasherBase for Writer {
+    fn update<A: AsRef<[u8]>>(&mut self, data: A) -> &mut Self {
+        self.0.extend_from_slice(data.as_ref());
+        self
+    }
+}
+
+pub fn encode_header(header: &Header) -> Vec<u8> {
+    let mut writer = Writer(Vec::new());
+    hashing::header::write_header(&mut writer, header, header.nonce, header.timestamp);
+    writer.0
+}
+
+pub fn encode_transaction(tx: &Transaction) -> Vec<u8> {
+    let mut writer = Writer(Vec::new());
+    hashing::tx::write_transaction(&mut writer, tx, TX_ENCODING_FULL, 0);
+    writer.0
+}
+
+/// Encodes the block header followed by the number of transactions and the transactions themselves, each followed by
+/// its mass as a `u64`
+pub fn encode_block(block: &Block) -> Vec<u8> {
+    let mut writer = Writer(encode_header(&block.header));
+    writer.write_len(block.transactions.len());
+    for tx in block.transactions.iter() {
+        hashing::tx::write_transaction(&mut writer, tx, TX_ENCODING_FULL, 0);
+        writer.update(tx.mass().to_le_bytes());
+    }
+    writer.0
+}
+
+/// Decodes a header, rejecting trailing data. The header hash is recomputed
+pub fn decode_header(bytes: &[u8]) -> CodecResult<Header> {
+    let mut reader = Reader::new(bytes);
+    let header = reader.read_header()?;
+    reader.finish()?;
+    Ok(header)
+}
+
+/// Decodes a transaction, rejecting trailing data. The transaction ID is recomputed
+pub fn decode_transaction(bytes: &[u8]) -> CodecResult<Transaction> {
+    let mut reader = Reader::new(bytes);
+    let tx = reader.read_transaction()?;
+    reader.finish()?;
+    Ok(tx)
+}
+
+/// Decodes a block, rejecting trailing data
+pub fn decode_block(bytes: &[u8]) -> CodecResult<Block> {
+    let mut reader = Reader::new(bytes);
+    let header = reader.read_header()?;
+    // Every transaction is encoded with more than one byte, which bounds the count by the remaining data
+    let count = reader.read_len(1)?;
+    let transactions = (0..count)
+        .map(|_| {
+            let tx = reader.read_transaction()?;
+            Ok(tx.with_mass(reader.read_u64()?))
+        })
+        .collect::<CodecResult<Vec<_>>>()?;
+    reader.finish()?;
+    Ok(Block::new(header, transactions))
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take(&mut self, n: usize) -> CodecResult<&'a [u8]> {
+        if n > self.data.len() {
+            return Err(CodecError::UnexpectedEnd(n - self.data.len()));
+        }
+        let (taken, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> CodecResult<[u8; N]> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn finish(self) -> CodecResult<()> {
+        match self.data.len() {
+            0 => Ok(()),
+            n => Err(CodecError::TrailingBytes(n)),
+        }
+    }
+
+    fn read_u8(&mut self) -> CodecResult<u8> {
+        Ok(self.take_array::<1>()?[0])
+    }
+
+    fn read_u16(&mut self) -> CodecResult<u16> {
+        Ok(u16::from_le_bytes(self.take_array()?))
+    }
+
+    fn read_u32(&mut self) -> CodecResult<u32> {
+        Ok(u32::from_le_bytes(self.take_array()?))
+    }
+
+    fn read_u64(&mut self) -> CodecResult<u64> {
+        Ok(u64::from_le_bytes(self.take_array()?))
+    }
+
+    fn read_hash(&mut self) -> CodecResult<Hash> {
+        Ok(Hash::from_bytes(self.take_array::<HASH_SIZE>()?))
+    }
+
+    /// Reads a length prefix of elements encoded with at least `min_element_size` bytes each. Lengths which cannot
+    /// fit the remaining data are rejected before allocating for them
+    fn read_len(&mut self, min_element_size: usize) -> CodecResult<usize> {
+        let len = self.read_u64()?;
+        if len.saturating_mul(min_element_size as u64) > self.data.len() as u64 {
+            return Err(CodecError::LengthTooLarge(len));
+        }
+        Ok(len as usize)
+    }
+
+    fn read_var_bytes(&mut self) -> CodecResult<&'a [u8]> {
+        let len = self.read_len(1)?;
+        self.take(len)
+    }
+
+    fn read_blue_work(&mut self) -> CodecResult<BlueWorkType> {
+        let bytes = self.read_var_bytes()?;
+        if bytes.len() > BlueWorkType::BYTES {
+            return Err(CodecError::BlueWorkTooLong(bytes.len(), BlueWorkType::BYTES));
+        }
+        // Blue work is written without leading zeros, so any other encoding is not canonical
+        if bytes.first() == Some(&0) {
+            return Err(CodecError::NonCanonicalBlueWork);
+        }
+        Ok(BlueWorkType::from_be_bytes_var(bytes).unwrap())
+    }
+
+    fn read_header(&mut self) -> CodecResult<Header> {
+        let version = self.read_u16()?;
+        let levels = self.read_len(size_of::<u64>())?;
+        let parents_by_level = (0..levels)
+            .map(|_| {
+                let len = self.read_len(HASH_SIZE)?;
+                (0..len).map(|_| self.read_hash()).collect::<CodecResult<Vec<_>>>()
+            })
+            .collect::<CodecResult<Vec<_>>>()?;
+        let hash_merkle_root = self.read_hash()?;
+        let accepted_id_merkle_root = self.read_hash()?;
+        let utxo_commitment = self.read_hash()?;
+        let timestamp = self.read_u64()?;
+        let bits = self.read_u32()?;
+        let nonce = self.read_u64()?;
+        let daa_score = self.read_u64()?;
+        let blue_score = self.read_u64()?;
+        let blue_work = self.read_blue_work()?;
+        let pruning_point = self.read_hash()?;
+        Ok(Header::new_finalized(
+            version,
+            parents_by_level,
+            hash_merkle_root,
+            accepted_id_merkle_root,
+            utxo_commitment,
+            timestamp,
+            bits,
+            nonce,
+            daa_score,
+            blue_work,
+            blue_score,
+            pruning_point,
+        ))
+    }
+
+    fn read_transaction(&mut self) -> CodecResult<Transaction> {
+        let version = self.read_u16()?;
+        // An input is encoded with at least an outpoint, two length/integer fields and a sig op count
+        let inputs_len = self.read_len(HASH_SIZE + size_of::<u32>() + 2 * size_of::<u64>() + 1)?;
+        let inputs = (0..inputs_len).map(|_| self.read_input()).collect::<CodecResult<Vec<_>>>()?;
+        // An output is encoded with at least a value, a script version and a script length
+        let outputs_len = self.read_len(2 * size_of::<u64>() + size_of::<u16>())?;
+        let outputs = (0..outputs_len).map(|_| self.read_output()).collect::<CodecResult<Vec<_>>>()?;
+        let lock_time = self.read_u64()?;
+        let subnetwork_id = SubnetworkId::from_bytes(self.take_array::<SUBNETWORK_ID_SIZE>()?);
+        let gas = self.read_u64()?;
+        let payload = self.read_var_bytes()?.to_vec();
+        Ok(Transaction::new(version, inputs, outputs, lock_time, subnetwork_id, gas, payload))
+    }
+
+    fn read_input(&mut self) -> CodecResult<TransactionInput> {
+        let previous_outpoint = TransactionOutpoint::new(self.read_hash()?, self.read_u32()?);
+        let signature_script = self.read_var_bytes()?.to_vec();
+        let sig_op_count = self.read_u8()?;
+        let sequence = self.read_u64()?;
+        Ok(TransactionInput::new(previous_outpoint, signature_script, sequence, sig_op_count))
+    }
+
+    fn read_output(&mut self) -> CodecResult<TransactionOutput> {
+        let value = self.read_u64()?;
+        let version = self.read_u16()?;
+        let script = ScriptVec::from_slice(self.read_var_bytes()?);
+        Ok(TransactionOutput::new(value, ScriptPublicKey::new(version, script)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{merkle::calc_hash_merkle_root, subnets::SUBNETWORK_ID_NATIVE};
+    use kaspa_hashes::{BlockHash, Hasher, TransactionHash};
+    use std::str::FromStr;
+
+    fn test_header() -> Header {
+        Header::new_finalized(
+            1,
+            vec![vec![Hash::from_u64_word(1), Hash::from_u64_word(2)], vec![Hash::from_u64_word(3)]],
+            Hash::from_u64_word(4),
+            Hash::from_u64_word(5),
+            Hash::from_u64_word(6),
+            0x0102030405060708,
+            0x1d00ffff,
+            0xdeadbeef,
+            1000,
+            123456.into(),
+            900,
+            Hash::from_u64_word(7),
+        )
+    }
+
+    fn test_transaction() -> Transaction {
+        Transaction::new(
+            0,
+            vec![TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(8), 2), vec![0xaa, 0xbb], u64::MAX, 1)],
+            vec![TransactionOutput::new(1564, ScriptPublicKey::new(0, ScriptVec::from_slice(&[0x51])))],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![0xcc],
+        )
+    }
+
+    #[test]
+    fn test_header_golden_encoding() {
+        // The consensus serialization written field by field
+        let expected = [
+            "0100",                                                             // version
+            "0200000000000000",                                                 // number of parent levels
+            "0200000000000000",                                                 // level 0 parents
+            "0000000000000000000000000000000000000000000000000100000000000000", //
+            "0000000000000000000000000000000000000000000000000200000000000000", //
+            "0100000000000000",                                                 // level 1 parents
+            "0000000000000000000000000000000000000000000000000300000000000000", //
+            "0000000000000000000000000000000000000000000000000400000000000000", // hash merkle root
+            "0000000000000000000000000000000000000000000000000500000000000000", // accepted id merkle root
+            "0000000000000000000000000000000000000000000000000600000000000000", // utxo commitment
+            "0807060504030201",                                                 // timestamp
+            "ffff001d",                                                         // bits
+            "efbeadde00000000",                                                 // nonce
+            "e803000000000000",                                                 // daa score
+            "8403000000000000",                                                 // blue score
+            "030000000000000001e240",                                           // blue work
+            "0000000000000000000000000000000000000000000000000700000000000000", // pruning point
+        ]
+        .concat();
+        let header = test_header();
+        let encoded = encode_header(&header);
+        assert_eq!(faster_hex::hex_string(&encoded), expected);
+        assert_eq!(BlockHash::hash(&encoded), header.hash);
+
+        let decoded = decode_header(&encoded).unwrap();
+        assert_eq!(decoded.hash, header.hash);
+        assert_eq!(encode_header(&decoded), encoded);
+    }
+
+    #[test]
+    fn test_transaction_golden_encoding() {
+        let expected = [
+            "0000",                                                             // version
+            "0100000000000000",                                                 // number of inputs
+            "0000000000000000000000000000000000000000000000000800000000000000", // outpoint transaction id
+            "02000000",                                                         // outpoint index
+            "0200000000000000aabb",                                             // signature script
+            "01",                                                               // sig op count
+            "ffffffffffffffff",                                                 // sequence
+            "0100000000000000",                                                 // number of outputs
+            "1c06000000000000",                                                 // value
+            "0000",                                                             // script public key version
+            "010000000000000051",                                               // script public key
+            "0000000000000000",                                                 // lock time
+            "0000000000000000000000000000000000000000",                         // subnetwork id
+            "0000000000000000",                                                 // gas
+            "0100000000000000cc",                                               // payload
+        ]
+        .concat();
+        let tx = test_transaction();
+        let encoded = encode_transaction(&tx);
+        assert_eq!(faster_hex::hex_string(&encoded), expected);
+        assert_eq!(TransactionHash::hash(&encoded), hashing::tx::hash(&tx, false));
+
+        let decoded = decode_transaction(&encoded).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(decoded.id(), tx.id());
+    }
+
+    #[test]
+    fn test_transaction_hash_vectors() {
+        // Test #5 of the golang reference transaction hashing vectors (see `hashing::tx::tests`)
+        let tx = Transaction::new(
+            2,
+            vec![TransactionInput::new(
+                TransactionOutpoint::new(
+                    Hash::from_str("59b3d6dc6cdc660c389c3fdb5704c48c598d279cdf1bab54182db586a4c95dd5").unwrap(),
+                    2,
+                ),
+                vec![1, 2],
+                7,
+                5,
+            )],
+            vec![TransactionOutput::new(1564, ScriptPublicKey::new(7, ScriptVec::from_slice(&[1, 2, 3, 4, 5])))],
+            54,
+            SubnetworkId::from_byte(0),
+            3,
+            Vec::new(),
+        );
+        let encoded = encode_transaction(&tx);
+        assert_eq!(
+            TransactionHash::hash(&encoded),
+            Hash::from_str("cd575e69fbf5f97fbfd4afb414feb56f8463b3948d6ac30f0ecdd9622672fab9").unwrap()
+        );
+        assert_eq!(decode_transaction(&encoded).unwrap().id(), tx.id());
+    }
+
+    #[test]
+    fn test_block_encoding() {
+        let block = Block::new(test_header(), vec![test_transaction(), test_transaction()]);
+        let encoded = encode_block(&block);
+        let decoded = decode_block(&encoded).unwrap();
+        assert_eq!(decoded.header.hash, block.header.hash);
+        assert_eq!(decoded.transactions, block.transactions);
+        assert_eq!(encode_block(&decoded), encoded);
+    }
+
+    #[test]
+    fn test_block_encoding_with_mass() {
+        let block = Block::new(test_header(), vec![test_transaction().with_mass(1000), test_transaction().with_mass(2000)]);
+        let encoded = encode_block(&block);
+
+        // The masses survive the round trip, so the hash merkle root committing to them can be recomputed
+        let decoded = decode_block(&encoded).unwrap();
+        assert_eq!(decoded.transactions.iter().map(|tx| tx.mass()).collect::<Vec<_>>(), vec![1000, 2000]);
+        assert_eq!(calc_hash_merkle_root(decoded.transactions.iter(), true), calc_hash_merkle_root(block.transactions.iter(), true));
+        assert_eq!(encode_block(&decoded), encoded);
+
+        // A truncated mass is rejected
+        assert!(decode_block(&encoded[..encoded.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_decoding_errors() {
+        let encoded = encode_header(&test_header());
+        assert_eq!(decode_header(&encoded[..encoded.len() - 1]).unwrap_err(), CodecError::UnexpectedEnd(1));
+        assert_eq!(decode_header(&[encoded.as_slice(), &[0]].concat()).unwrap_err(), CodecError::TrailingBytes(1));
+
+        // A parent level count which cannot fit the data is rejected before allocating
+        let mut huge_levels = encoded.clone();
+        huge_levels[2..10].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(decode_header(&huge_levels).unwrap_err(), CodecError::LengthTooLarge(u64::MAX));
+
+        // Blue work with a leading zero byte
+        let blue_work_offset = encoded.len() - HASH_SIZE - 11;
+        let mut non_canonical = encoded[..blue_work_offset].to_vec();
+        non_canonical.extend([4, 0, 0, 0, 0, 0, 0, 0, 0, 1, 226, 64]);
+        non_canonical.extend_from_slice(&encoded[encoded.len() - HASH_SIZE..]);
+        assert_eq!(decode_header(&non_canonical).unwrap_err(), CodecError::NonCanonicalBlueWork);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::tx::{ScriptPublicKey, Transaction};
+use crate::{
+    sompi::Sompi,
+    tx::{ScriptPublicKey, Transaction},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -31,6 +34,12 @@ impl BlockRewardData {
     pub fn new(subsidy: u64, total_fees: u64, script_public_key: ScriptPublicKey) -> Self {
         Self { subsidy, total_fees, script_public_key }
     }
+
+    /// The total reward paid to the block, i.e. its subsidy plus the fees of the transactions it accepted.
+    /// Returns `None` on overflow
+    pub fn total_reward(&self) -> Option<Sompi> {
+        Sompi::from(self.subsidy).checked_add(self.total_fees)
+    }
 }
 
 /// Holds a coinbase transaction along with meta-data obtained during creation
@@ -111,6 +111,7 @@ pub mod perf {
     //!
 
     use crate::config::params::Params;
+    use std::fmt::{Display, Formatter};
 
     /// The default target depth for reachability reindexes.
     pub const DEFAULT_REINDEX_DEPTH: u64 = 100;
@@ -124,6 +125,89 @@ pub mod perf {
     const BASELINE_BLOCK_WINDOW_CACHE_SIZE: usize = 2_000;
     const BASELINE_UTXOSET_CACHE_SIZE: usize = 10_000;
 
+    /// The default number of block hashes remembered per P2P peer as known to it
+    const BASELINE_P2P_KNOWN_BLOCKS_CACHE_SIZE: usize = 1_024;
+
+    /// The default number of transaction IDs remembered per P2P peer as known to it
+    pub const DEFAULT_P2P_KNOWN_TRANSACTIONS_CACHE_SIZE: usize = 8_192;
+
+    /// The default number of chain blocks virtual is advanced by before flushing an intermediate state
+    const BASELINE_VIRTUAL_RESOLVE_CHUNK_SIZE: usize = 1_000;
+
+    /// The default number of header tasks allowed to run concurrently while virtual is being resolved
+    pub const DEFAULT_VIRTUAL_PRIORITY_HEADER_CONCURRENCY: usize = 2;
+
+    /// The overall memory budget (in bytes) of consensus caches which are bounded by byte size
+    pub const BASELINE_CACHE_MEMORY_BUDGET: usize = 1_000_000_000;
+
+    /// A named sizing policy of the consensus caches
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum CacheSizePolicy {
+        /// Half the default cache sizes and memory budget, for nodes with limited RAM
+        Small,
+        #[default]
+        Default,
+        /// Twice the default cache sizes and memory budget
+        Large,
+        /// The default cache sizes with explicit per-store memory budgets (see [`CacheBudgetOverrides`])
+        Custom,
+    }
+
+    pub const CACHE_SIZE_POLICIES: &[CacheSizePolicy] =
+        &[CacheSizePolicy::Small, CacheSizePolicy::Default, CacheSizePolicy::Large, CacheSizePolicy::Custom];
+
+    impl CacheSizePolicy {
+        pub fn name(&self) -> &'static str {
+            match self {
+                CacheSizePolicy::Small => "small",
+                CacheSizePolicy::Default => "default",
+                CacheSizePolicy::Large => "large",
+                CacheSizePolicy::Custom => "custom",
+            }
+        }
+
+        pub fn from_name(name: &str) -> Option<Self> {
+            CACHE_SIZE_POLICIES.iter().find(|policy| policy.name() == name).copied()
+        }
+
+        /// The factor applied to the cache sizes and to the cache memory budget
+        pub fn scale(&self) -> f64 {
+            match self {
+                CacheSizePolicy::Small => 0.5,
+                CacheSizePolicy::Default | CacheSizePolicy::Custom => 1.0,
+                CacheSizePolicy::Large => 2.0,
+            }
+        }
+    }
+
+    impl Display for CacheSizePolicy {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.name())
+        }
+    }
+
+    /// Memory budgets in bytes overriding the cache memory budget or the share of specific stores in it.
+    /// Unlike the shares, which are derived from the (possibly overridden) total, store overrides are not scaled by the RAM scale
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct CacheBudgetOverrides {
+        /// Overall cache memory budget, replacing [`PerfParams::cache_memory_budget`] the shares of the stores are derived from
+        pub total: Option<usize>,
+        /// Budget of the GHOSTDAG data stores
+        pub ghostdag: Option<usize>,
+        /// Budget of the block relations stores, split between parents and children
+        pub relations: Option<usize>,
+        /// Budget of the block headers store
+        pub headers: Option<usize>,
+    }
+
+    impl CacheBudgetOverrides {
+        pub const NONE: CacheBudgetOverrides = CacheBudgetOverrides { total: None, ghostdag: None, relations: None, headers: None };
+
+        pub fn is_empty(&self) -> bool {
+            self.total.is_none() && self.ghostdag.is_none() && self.relations.is_none() && self.headers.is_none()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct PerfParams {
         //
@@ -143,16 +227,59 @@ pub mod perf {
         /// Preferred cache size for block-window-related data
         pub block_window_cache_size: usize,
 
+        /// Memory budget in bytes which is split across all byte-bounded caches
+        /// in fixed proportions (and is further scaled by the configured RAM scale)
+        pub cache_memory_budget: usize,
+
+        /// The sizing policy the cache sizes and budget were derived with
+        pub cache_size_policy: CacheSizePolicy,
+
+        /// Per-store memory budgets overriding their share of the cache memory budget
+        pub cache_budget_overrides: CacheBudgetOverrides,
+
+        /// Number of block hashes remembered per P2P peer as announced by it or sent to it, so that
+        /// blocks are not relayed to peers already having them
+        pub p2p_known_blocks_cache_size: usize,
+
+        /// Number of transaction IDs remembered per P2P peer as announced by it or sent to it, so that
+        /// transactions are not relayed to peers already having them
+        pub p2p_known_transactions_cache_size: usize,
+
+        //
+        // Virtual processing
+        //
+        /// Maximal number of chain blocks whose UTXO diffs are accumulated in memory while resolving
+        /// virtual, before an intermediate virtual state is committed to the DB. A value of 0 disables chunking
+        pub virtual_resolve_chunk_size: usize,
+
+        //
+        // Reachability
+        //
+        /// The depth below the headers selected tip at which the reachability reindex root is maintained.
+        /// Larger values make reindexes rarer but more expensive
+        pub reindex_depth: u64,
+
+        /// The height difference required for the reindex root to switch to a competing chain
+        pub reindex_slack: u64,
+
         //
         // Thread-pools
         //
-        /// Defaults to 0 which indicates using system default
-        /// which is typically the number of logical CPU cores
-        pub block_processors_num_threads: usize,
+        /// Number of threads of the header processor pool. Defaults to 0 which
+        /// indicates using system default which is typically the number of logical CPU cores
+        pub header_processor_num_threads: usize,
+
+        /// Number of threads of the body processor pool. Defaults to 0 which
+        /// indicates using system default which is typically the number of logical CPU cores
+        pub body_processor_num_threads: usize,
 
-        /// Defaults to 0 which indicates using system default
-        /// which is typically the number of logical CPU cores
+        /// Number of threads of the virtual processor pool. Defaults to 0 which
+        /// indicates using system default which is typically the number of logical CPU cores
         pub virtual_processor_num_threads: usize,
+
+        /// Maximal number of header tasks allowed to run concurrently while virtual is being resolved,
+        /// so that virtual resolution is not starved during header floods. A value of 0 disables this limit
+        pub virtual_priority_header_concurrency: usize,
     }
 
     pub const PERF_PARAMS: PerfParams = PerfParams {
@@ -160,14 +287,50 @@ pub mod perf {
         block_data_cache_size: BASELINE_BLOCK_DATA_CACHE_SIZE,
         utxo_set_cache_size: BASELINE_UTXOSET_CACHE_SIZE,
         block_window_cache_size: BASELINE_BLOCK_WINDOW_CACHE_SIZE,
-        block_processors_num_threads: 0,
+        cache_memory_budget: BASELINE_CACHE_MEMORY_BUDGET,
+        cache_size_policy: CacheSizePolicy::Default,
+        cache_budget_overrides: CacheBudgetOverrides::NONE,
+        p2p_known_blocks_cache_size: BASELINE_P2P_KNOWN_BLOCKS_CACHE_SIZE,
+        p2p_known_transactions_cache_size: DEFAULT_P2P_KNOWN_TRANSACTIONS_CACHE_SIZE,
+        virtual_resolve_chunk_size: BASELINE_VIRTUAL_RESOLVE_CHUNK_SIZE,
+        reindex_depth: DEFAULT_REINDEX_DEPTH,
+        reindex_slack: DEFAULT_REINDEX_SLACK,
+        header_processor_num_threads: 0,
+        body_processor_num_threads: 0,
         virtual_processor_num_threads: 0,
+        virtual_priority_header_concurrency: DEFAULT_VIRTUAL_PRIORITY_HEADER_CONCURRENCY,
     };
 
     impl PerfParams {
         pub fn adjust_to_consensus_params(&mut self, consensus_params: &Params) {
-            // Allow caching up to 10x over the baseline
-            self.block_data_cache_size *= consensus_params.bps().clamp(1, 10) as usize;
+            let bps = consensus_params.bps().clamp(1, 10) as usize;
+
+            // The working sets of per-block data grow linearly with BPS, so allow caching up to 10x over the baseline
+            self.block_data_cache_size *= bps;
+            self.header_data_cache_size *= bps;
+            self.utxo_set_cache_size *= bps;
+            self.block_window_cache_size *= bps;
+            self.p2p_known_blocks_cache_size *= bps;
+
+            // The mergeset of a chain block (and hence its UTXO diff) grows with BPS as well, so the chunk size
+            // is scaled down in order to keep the memory held while resolving virtual roughly the same
+            if self.virtual_resolve_chunk_size > 0 {
+                self.virtual_resolve_chunk_size = (self.virtual_resolve_chunk_size / bps).max(1);
+            }
+        }
+
+        /// Scales the cache sizes and memory budget by the sizing policy and sets the per-store budget overrides.
+        /// Expected to be called (at most once) after adjusting to the consensus params
+        pub fn apply_cache_size_policy(&mut self, policy: CacheSizePolicy, overrides: CacheBudgetOverrides) {
+            let scale = policy.scale();
+            let scaled = |size: usize| ((size as f64 * scale) as usize).max(1);
+            self.header_data_cache_size = scaled(self.header_data_cache_size);
+            self.block_data_cache_size = scaled(self.block_data_cache_size);
+            self.utxo_set_cache_size = scaled(self.utxo_set_cache_size);
+            self.block_window_cache_size = scaled(self.block_window_cache_size);
+            self.cache_memory_budget = overrides.total.unwrap_or_else(|| scaled(self.cache_memory_budget));
+            self.cache_size_policy = policy;
+            self.cache_budget_overrides = overrides;
         }
     }
 }
@@ -177,6 +340,59 @@ mod tests {
     use super::consensus::{MAX_DIFFICULTY_TARGET, MAX_DIFFICULTY_TARGET_AS_F64};
     use kaspa_math::Uint256;
 
+    #[test]
+    fn test_adjust_perf_params() {
+        use super::perf::PERF_PARAMS;
+        use crate::config::params::{MAINNET_PARAMS, TESTNET11_PARAMS};
+
+        let mut perf = PERF_PARAMS;
+        perf.adjust_to_consensus_params(&MAINNET_PARAMS);
+        assert_eq!(perf.header_data_cache_size, PERF_PARAMS.header_data_cache_size);
+        assert_eq!(perf.virtual_resolve_chunk_size, PERF_PARAMS.virtual_resolve_chunk_size);
+
+        let mut perf = PERF_PARAMS;
+        perf.adjust_to_consensus_params(&TESTNET11_PARAMS);
+        assert_eq!(perf.block_data_cache_size, 10 * PERF_PARAMS.block_data_cache_size);
+        assert_eq!(perf.block_window_cache_size, 10 * PERF_PARAMS.block_window_cache_size);
+        assert_eq!(perf.p2p_known_blocks_cache_size, 10 * PERF_PARAMS.p2p_known_blocks_cache_size);
+        assert_eq!(perf.p2p_known_transactions_cache_size, PERF_PARAMS.p2p_known_transactions_cache_size);
+        assert_eq!(perf.virtual_resolve_chunk_size, PERF_PARAMS.virtual_resolve_chunk_size / 10);
+    }
+
+    #[test]
+    fn test_cache_size_policy() {
+        use super::perf::{CacheBudgetOverrides, CacheSizePolicy, CACHE_SIZE_POLICIES, PERF_PARAMS};
+
+        for policy in CACHE_SIZE_POLICIES {
+            assert_eq!(CacheSizePolicy::from_name(policy.name()), Some(*policy));
+        }
+        assert_eq!(CacheSizePolicy::from_name("huge"), None);
+
+        let mut perf = PERF_PARAMS;
+        perf.apply_cache_size_policy(CacheSizePolicy::Small, CacheBudgetOverrides::NONE);
+        assert_eq!(perf.header_data_cache_size, PERF_PARAMS.header_data_cache_size / 2);
+        assert_eq!(perf.cache_memory_budget, PERF_PARAMS.cache_memory_budget / 2);
+
+        let mut perf = PERF_PARAMS;
+        perf.apply_cache_size_policy(CacheSizePolicy::Large, CacheBudgetOverrides::NONE);
+        assert_eq!(perf.block_data_cache_size, 2 * PERF_PARAMS.block_data_cache_size);
+        assert_eq!(perf.cache_memory_budget, 2 * PERF_PARAMS.cache_memory_budget);
+        // Non-cache perf params are unaffected
+        assert_eq!(perf.p2p_known_blocks_cache_size, PERF_PARAMS.p2p_known_blocks_cache_size);
+
+        let overrides = CacheBudgetOverrides { ghostdag: Some(200_000_000), ..Default::default() };
+        let mut perf = PERF_PARAMS;
+        perf.apply_cache_size_policy(CacheSizePolicy::Custom, overrides);
+        assert_eq!(perf.utxo_set_cache_size, PERF_PARAMS.utxo_set_cache_size);
+        assert_eq!(perf.cache_memory_budget, PERF_PARAMS.cache_memory_budget);
+        assert_eq!(perf.cache_size_policy, CacheSizePolicy::Custom);
+        assert_eq!(perf.cache_budget_overrides, overrides);
+
+        let mut perf = PERF_PARAMS;
+        perf.apply_cache_size_policy(CacheSizePolicy::Custom, CacheBudgetOverrides { total: Some(300_000_000), ..Default::default() });
+        assert_eq!(perf.cache_memory_budget, 300_000_000);
+    }
+
     #[test]
     fn test_difficulty_max_consts() {
         assert_eq!(MAX_DIFFICULTY_TARGET, Uint256::from_u64(1).wrapping_shl(255) - 1.into());
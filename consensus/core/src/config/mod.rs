@@ -3,6 +3,7 @@ pub mod constants;
 pub mod genesis;
 pub mod params;
 
+use kaspa_addresses::Address;
 use kaspa_utils::networking::{ContextualNetAddress, NetAddress};
 
 #[cfg(feature = "devnet-prealloc")]
@@ -14,7 +15,7 @@ use std::ops::Deref;
 
 use {
     constants::perf::{PerfParams, PERF_PARAMS},
-    params::Params,
+    params::{Params, ParamsPreset},
 };
 
 /// Various consensus configurations all bundled up under a single struct. Use `Config::new` for directly building from
@@ -61,6 +62,12 @@ pub struct Config {
 
     pub block_template_cache_lifetime: Option<u64>,
 
+    /// Default payout address used for block templates requested without an explicit pay address
+    pub mining_address: Option<Address>,
+
+    /// Extra data appended by the block template builder to the coinbase payload of every block template
+    pub coinbase_extra_data: Vec<u8>,
+
     #[cfg(feature = "devnet-prealloc")]
     pub initial_utxo_set: Arc<UtxoCollection>,
 
@@ -90,6 +97,8 @@ impl Config {
             externalip: None,
             p2p_listen_address: ContextualNetAddress::unspecified(),
             block_template_cache_lifetime: None,
+            mining_address: None,
+            coinbase_extra_data: Default::default(),
 
             #[cfg(feature = "devnet-prealloc")]
             initial_utxo_set: Default::default(),
@@ -136,6 +145,12 @@ impl ConfigBuilder {
         self
     }
 
+    /// Applies a params preset, followed by adjusting perf params to the resulting consensus params
+    pub fn apply_params_preset(mut self, preset: ParamsPreset) -> Self {
+        preset.apply(&mut self.config.params);
+        self.adjust_perf_params_to_consensus_params()
+    }
+
     pub fn edit_consensus_params<F>(mut self, edit_func: F) -> Self
     where
         F: Fn(&mut Params),
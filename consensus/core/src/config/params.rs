@@ -5,13 +5,16 @@ pub use super::{
 };
 use crate::{
     constants::STORAGE_MASS_PARAMETER,
+    errors::config::ConfigError,
     network::{NetworkId, NetworkType},
     BlockLevel, KType,
 };
 use kaspa_addresses::Prefix;
+use kaspa_hashes::Hash;
 use kaspa_math::Uint256;
 use std::{
     cmp::min,
+    str::FromStr,
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -42,6 +45,36 @@ impl ForkActivation {
     }
 }
 
+/// A checkpoint block assumed to be valid. Transaction scripts of blocks in the selected chain past of the
+/// checkpoint are not verified during sync, which saves most of the CPU spent on validating historical blocks.
+/// All other validation rules (PoW, UTXO commitments, mass etc.) are still enforced and all stores are built
+/// as usual. Hence, for an honest checkpoint the node ends up with the same state as with full validation, whereas
+/// a dishonest one might lead the node to accept invalid spends in its past.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AssumeValid {
+    pub hash: Hash,
+    /// The DAA score of the checkpoint block. Used as a sanity check, i.e., a block with the checkpoint hash
+    /// but with a different DAA score is not considered a checkpoint
+    pub daa_score: u64,
+}
+
+impl AssumeValid {
+    pub const fn new(hash: Hash, daa_score: u64) -> Self {
+        Self { hash, daa_score }
+    }
+}
+
+impl FromStr for AssumeValid {
+    type Err = ConfigError;
+
+    /// Parses a checkpoint of the form `<block hash>:<DAA score>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ConfigError::InvalidAssumeValid(s.to_owned());
+        let (hash, daa_score) = s.split_once(':').ok_or_else(err)?;
+        Ok(Self::new(Hash::from_str(hash).map_err(|_| err())?, daa_score.parse().map_err(|_| err())?))
+    }
+}
+
 /// Consensus parameters. Contains settings and configurations which are consensus-sensitive.
 /// Changing one of these on a network node would exclude and prevent it from reaching consensus
 /// with the other unmodified nodes.
@@ -122,6 +155,18 @@ pub struct Params {
     ///    - OpTxOutputSpk (0xc3): Get output script public key
     pub kip10_activation: ForkActivation,
 
+    /// DAA score from which native non-coinbase transactions may carry a non-empty payload. The payload is bounded by the
+    /// transaction mass, and is committed to by the signature hash of the inputs
+    pub payload_activation: ForkActivation,
+
+    /// Optional fork id folded into the signature hash of all transaction inputs, providing replay protection between
+    /// (devnet) networks forked with distinct fork ids. Must remain `None` on mainnet
+    pub sighash_fork_id: Option<u8>,
+
+    /// DAA score from which [`Params::sighash_fork_id`] is folded into signature hashes. Transactions preceding the fork,
+    /// which were signed without the fork id, thus remain valid
+    pub sighash_fork_activation: ForkActivation,
+
     /// DAA score after which the pre-deflationary period switches to the deflationary period
     pub deflationary_phase_daa_score: u64,
 
@@ -129,6 +174,13 @@ pub struct Params {
     pub coinbase_maturity: u64,
     pub skip_proof_of_work: bool,
     pub max_block_level: BlockLevel,
+    /// Checkpoint below which transaction scripts are not verified during sync, see [`AssumeValid`]. This is
+    /// not a consensus rule, hence nodes with different (or disabled) checkpoints still reach consensus.
+    ///
+    /// Note that no network ships a checkpoint yet (this is `None` in all presets) since a checkpoint must be audited
+    /// against the canonical chain before being trusted by default. Until then, the checkpoint is only set by operators
+    /// explicitly (see kaspad's `--assume-valid`) and sync fully validates all blocks otherwise
+    pub assume_valid: Option<AssumeValid>,
     pub pruning_proof_m: u64,
 }
 
@@ -246,18 +298,57 @@ impl Params {
     /// Based on the analysis at <https://github.com/kaspanet/docs/blob/main/Reference/prunality/Prunality.pdf>
     /// and on the decomposition of merge depth (rule R-I therein) from finality depth (φ)
     pub fn anticone_finalization_depth(&self) -> u64 {
-        let anticone_finalization_depth = self.finality_depth
-            + self.merge_depth
-            + 4 * self.mergeset_size_limit * self.ghostdag_k as u64
-            + 2 * self.ghostdag_k as u64
-            + 2;
-
         // In mainnet it's guaranteed that `self.pruning_depth` is greater
         // than `anticone_finalization_depth`, but for some tests we use
         // a smaller (unsafe) pruning depth, so we return the minimum of
         // the two to avoid a situation where a block can be pruned and
         // not finalized.
-        min(self.pruning_depth, anticone_finalization_depth)
+        min(self.pruning_depth, self.min_safe_pruning_depth())
+    }
+
+    /// Returns the minimal pruning depth at which the anticone of the pruning point is guaranteed to be final
+    fn min_safe_pruning_depth(&self) -> u64 {
+        self.finality_depth + self.merge_depth + 4 * self.mergeset_size_limit * self.ghostdag_k as u64 + 2 * self.ghostdag_k as u64 + 2
+    }
+
+    /// Overrides the pruning depth such that `finality_windows` finality windows of history are kept below the virtual,
+    /// e.g., for explorer devnets which want a longer history without the storage of a full archival node.
+    /// Fails on mainnet and if the resulting depth is below the minimal safe pruning depth, hence the override can
+    /// only lengthen the history to a safe extent.
+    ///
+    /// Note that the pruning depth is a consensus parameter rather than a local retention setting: it determines the
+    /// expected pruning point of every header (see `expected_header_pruning_point`), so all nodes of the network must
+    /// share the same value. A node with a different value rejects the headers of the rest of the network
+    pub fn set_pruning_depth_in_finality_windows(&mut self, finality_windows: u64) -> Result<(), ConfigError> {
+        if self.net.is_mainnet() {
+            return Err(ConfigError::ConsensusOverridesOnMainnet);
+        }
+        let min_finality_windows = self.min_safe_pruning_depth().div_ceil(self.finality_depth);
+        if finality_windows < min_finality_windows {
+            return Err(ConfigError::OverridePruningFinalityWindowsTooLow(min_finality_windows));
+        }
+        // Leaves room for adding other depths to the pruning depth without overflowing
+        let max_finality_windows = u64::MAX / 2 / self.finality_depth;
+        if finality_windows > max_finality_windows {
+            return Err(ConfigError::OverridePruningFinalityWindowsTooHigh(max_finality_windows));
+        }
+        self.pruning_depth = finality_windows * self.finality_depth;
+        Ok(())
+    }
+
+    /// Validates the bounds on the DAG width. A block must be able to merge all of its parents, hence the mergeset
+    /// size limit must not be below the max block parents, and a mergeset must be able to hold at least K + 1 blues
+    pub fn validate_dag_width(&self) -> Result<(), ConfigError> {
+        if self.max_block_parents == 0 {
+            return Err(ConfigError::OverrideMaxBlockParentsTooLow);
+        }
+        if self.mergeset_size_limit <= self.ghostdag_k as u64 {
+            return Err(ConfigError::OverrideMergesetSizeLimitTooLow(self.ghostdag_k as u64));
+        }
+        if self.max_block_parents as u64 > self.mergeset_size_limit {
+            return Err(ConfigError::OverrideMaxBlockParentsAboveMergesetSizeLimit(self.mergeset_size_limit));
+        }
+        Ok(())
     }
 
     /// Returns whether the sink timestamp is recent enough and the node is considered synced or nearly synced.
@@ -299,6 +390,97 @@ impl Params {
     pub fn finality_duration(&self) -> u64 {
         self.target_time_per_block * self.finality_depth
     }
+
+    /// Overrides all BPS-dependent params with the values derived for `bps` (following the TN11 setup,
+    /// hence sampling is activated from inception). Returns `false` and leaves params untouched if
+    /// `bps` is not supported, i.e., if it does not divide 1000 or exceeds the precomputed GHOSTDAG K table.
+    pub fn set_bps(&mut self, bps: u64) -> bool {
+        match bps {
+            1 => self.set_bps_dependent_params::<1>(),
+            2 => self.set_bps_dependent_params::<2>(),
+            4 => self.set_bps_dependent_params::<4>(),
+            5 => self.set_bps_dependent_params::<5>(),
+            8 => self.set_bps_dependent_params::<8>(),
+            10 => self.set_bps_dependent_params::<10>(),
+            20 => self.set_bps_dependent_params::<20>(),
+            25 => self.set_bps_dependent_params::<25>(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn set_bps_dependent_params<const BPS: u64>(&mut self) {
+        self.sampling_activation = ForkActivation::always();
+        self.ghostdag_k = Bps::<BPS>::ghostdag_k();
+        self.target_time_per_block = Bps::<BPS>::target_time_per_block();
+        self.past_median_time_sample_rate = Bps::<BPS>::past_median_time_sample_rate();
+        self.difficulty_sample_rate = Bps::<BPS>::difficulty_adjustment_sample_rate();
+        self.max_block_parents = Bps::<BPS>::max_block_parents();
+        self.mergeset_size_limit = Bps::<BPS>::mergeset_size_limit();
+        self.merge_depth = Bps::<BPS>::merge_depth_bound();
+        self.finality_depth = Bps::<BPS>::finality_depth();
+        self.pruning_depth = Bps::<BPS>::pruning_depth();
+        self.pruning_proof_m = Bps::<BPS>::pruning_proof_m();
+        self.deflationary_phase_daa_score = Bps::<BPS>::deflationary_phase_daa_score();
+        self.pre_deflationary_phase_base_subsidy = Bps::<BPS>::pre_deflationary_phase_base_subsidy();
+        self.coinbase_maturity = Bps::<BPS>::coinbase_maturity();
+    }
+
+    /// Cuts down coinbase maturity, the DAA and median time windows and the merge, finality and pruning depths
+    /// to a few dozen blocks, so that integration tests exercise the related logic within seconds of mining.
+    /// Expected to be applied over 1 BPS params
+    pub fn apply_fast_test_profile(&mut self) {
+        self.coinbase_maturity = 10;
+        self.past_median_time_sample_rate = 1;
+        self.past_median_time_sampled_window_size = 11;
+        self.difficulty_sample_rate = 1;
+        self.sampled_difficulty_window_size = 32;
+        self.mergeset_size_limit = 32;
+        self.merge_depth = 64;
+        self.finality_depth = 64;
+        self.pruning_proof_m = 16;
+        self.pruning_depth = self.anticone_finalization_depth();
+    }
+}
+
+/// A named set of consensus params which can be applied over the params of any non-mainnet network.
+/// Perf params are derived from the resulting consensus params (see `PerfParams::adjust_to_consensus_params`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParamsPreset {
+    pub name: &'static str,
+    /// Blocks per second. All BPS-dependent params follow the TN11 setup (see [`Params::set_bps`])
+    pub bps: u64,
+    /// Whether the fast test profile is applied over the BPS-dependent params (see [`Params::apply_fast_test_profile`]).
+    /// Such presets are restricted to simnet
+    pub fast_test: bool,
+}
+
+/// A 1 BPS simnet preset for fast integration tests (see [`Params::apply_fast_test_profile`])
+pub const SIMNET_FAST_TEST_PRESET: ParamsPreset = ParamsPreset { name: "simnet-fast-test", bps: 1, fast_test: true };
+
+pub const PARAMS_PRESETS: &[ParamsPreset] = &[
+    ParamsPreset { name: "1bps", bps: 1, fast_test: false },
+    ParamsPreset { name: "2bps", bps: 2, fast_test: false },
+    ParamsPreset { name: "4bps", bps: 4, fast_test: false },
+    ParamsPreset { name: "5bps", bps: 5, fast_test: false },
+    ParamsPreset { name: "8bps", bps: 8, fast_test: false },
+    ParamsPreset { name: "10bps", bps: 10, fast_test: false },
+    ParamsPreset { name: "20bps", bps: 20, fast_test: false },
+    ParamsPreset { name: "25bps", bps: 25, fast_test: false },
+    SIMNET_FAST_TEST_PRESET,
+];
+
+impl ParamsPreset {
+    pub fn from_name(name: &str) -> Option<Self> {
+        PARAMS_PRESETS.iter().find(|preset| preset.name == name).copied()
+    }
+
+    pub fn apply(&self, params: &mut Params) {
+        assert!(params.set_bps(self.bps), "presets are expected to use supported BPS values");
+        if self.fast_test {
+            params.apply_fast_test_profile();
+        }
+    }
 }
 
 impl From<NetworkType> for Params {
@@ -393,6 +575,9 @@ pub const MAINNET_PARAMS: Params = Params {
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation: ForkActivation::never(),
     kip10_activation: ForkActivation::never(),
+    payload_activation: ForkActivation::never(),
+    sighash_fork_id: None,
+    sighash_fork_activation: ForkActivation::never(),
 
     // deflationary_phase_daa_score is the DAA score after which the pre-deflationary period
     // switches to the deflationary period. This number is calculated as follows:
@@ -405,6 +590,7 @@ pub const MAINNET_PARAMS: Params = Params {
     coinbase_maturity: 100,
     skip_proof_of_work: false,
     max_block_level: 225,
+    assume_valid: None,
     pruning_proof_m: 1000,
 };
 
@@ -457,6 +643,9 @@ pub const TESTNET_PARAMS: Params = Params {
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation: ForkActivation::never(),
     kip10_activation: ForkActivation::never(),
+    payload_activation: ForkActivation::never(),
+    sighash_fork_id: None,
+    sighash_fork_activation: ForkActivation::never(),
     // deflationary_phase_daa_score is the DAA score after which the pre-deflationary period
     // switches to the deflationary period. This number is calculated as follows:
     // We define a year as 365.25 days
@@ -468,6 +657,7 @@ pub const TESTNET_PARAMS: Params = Params {
     coinbase_maturity: 100,
     skip_proof_of_work: false,
     max_block_level: 250,
+    assume_valid: None,
     pruning_proof_m: 1000,
 };
 
@@ -527,9 +717,13 @@ pub const TESTNET11_PARAMS: Params = Params {
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation: ForkActivation::always(),
     kip10_activation: ForkActivation::never(),
+    payload_activation: ForkActivation::never(),
+    sighash_fork_id: None,
+    sighash_fork_activation: ForkActivation::never(),
 
     skip_proof_of_work: false,
     max_block_level: 250,
+    assume_valid: None,
 };
 
 pub const SIMNET_PARAMS: Params = Params {
@@ -581,9 +775,13 @@ pub const SIMNET_PARAMS: Params = Params {
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation: ForkActivation::always(),
     kip10_activation: ForkActivation::never(),
+    payload_activation: ForkActivation::never(),
+    sighash_fork_id: None,
+    sighash_fork_activation: ForkActivation::never(),
 
     skip_proof_of_work: true, // For simnet only, PoW can be simulated by default
     max_block_level: 250,
+    assume_valid: None,
 };
 
 pub const DEVNET_PARAMS: Params = Params {
@@ -628,6 +826,9 @@ pub const DEVNET_PARAMS: Params = Params {
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation: ForkActivation::never(),
     kip10_activation: ForkActivation::never(),
+    payload_activation: ForkActivation::never(),
+    sighash_fork_id: None,
+    sighash_fork_activation: ForkActivation::never(),
 
     // deflationary_phase_daa_score is the DAA score after which the pre-deflationary period
     // switches to the deflationary period. This number is calculated as follows:
@@ -640,5 +841,110 @@ pub const DEVNET_PARAMS: Params = Params {
     coinbase_maturity: 100,
     skip_proof_of_work: false,
     max_block_level: 250,
+    assume_valid: None,
     pruning_proof_m: 1000,
 };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_bps() {
+        let mut params = DEVNET_PARAMS;
+        assert!(params.set_bps(10));
+        assert_eq!(params.bps(), 10);
+        assert_eq!(params.ghostdag_k, TESTNET11_PARAMS.ghostdag_k);
+        assert_eq!(params.max_block_parents, TESTNET11_PARAMS.max_block_parents);
+        assert_eq!(params.mergeset_size_limit, TESTNET11_PARAMS.mergeset_size_limit);
+        assert_eq!(params.pruning_depth, TESTNET11_PARAMS.pruning_depth);
+        assert_eq!(params.coinbase_maturity, TESTNET11_PARAMS.coinbase_maturity);
+        assert_eq!(params.sampling_activation, ForkActivation::always());
+
+        let mut params = DEVNET_PARAMS;
+        assert!(!params.set_bps(3));
+        assert_eq!(params.bps(), DEVNET_PARAMS.bps());
+        assert_eq!(params.ghostdag_k, DEVNET_PARAMS.ghostdag_k);
+    }
+
+    #[test]
+    fn test_params_presets() {
+        for preset in PARAMS_PRESETS {
+            let mut params = DEVNET_PARAMS;
+            preset.apply(&mut params);
+            assert_eq!(params.bps(), preset.bps);
+            assert_eq!(ParamsPreset::from_name(preset.name), Some(*preset));
+        }
+        assert_eq!(ParamsPreset::from_name("3bps"), None);
+    }
+
+    #[test]
+    fn test_fast_test_profile() {
+        let mut params = SIMNET_PARAMS;
+        SIMNET_FAST_TEST_PRESET.apply(&mut params);
+        assert_eq!(params.bps(), 1);
+        assert_eq!(params.coinbase_maturity, 10);
+        assert!(params.ghostdag_k as u64 <= params.mergeset_size_limit);
+        assert!(params.difficulty_window_size(0) >= params.min_difficulty_window_len);
+        assert!(params.merge_depth <= params.finality_depth);
+        // The pruning depth is kept at the minimal safe value
+        assert_eq!(params.pruning_depth, params.anticone_finalization_depth());
+    }
+
+    #[test]
+    fn test_dag_width_validation() {
+        for params in [MAINNET_PARAMS, TESTNET_PARAMS, TESTNET11_PARAMS, SIMNET_PARAMS, DEVNET_PARAMS] {
+            params.validate_dag_width().unwrap();
+        }
+
+        let mut params = TESTNET11_PARAMS;
+        params.max_block_parents = 0;
+        assert!(matches!(params.validate_dag_width(), Err(ConfigError::OverrideMaxBlockParentsTooLow)));
+
+        let mut params = TESTNET11_PARAMS;
+        params.mergeset_size_limit = params.ghostdag_k as u64;
+        assert!(matches!(params.validate_dag_width(), Err(ConfigError::OverrideMergesetSizeLimitTooLow(_))));
+
+        let mut params = TESTNET11_PARAMS;
+        params.mergeset_size_limit = params.ghostdag_k as u64 + 1;
+        params.max_block_parents = u8::MAX;
+        assert!(matches!(
+            params.validate_dag_width(),
+            Err(ConfigError::OverrideMaxBlockParentsAboveMergesetSizeLimit(limit)) if limit == params.mergeset_size_limit
+        ));
+    }
+
+    #[test]
+    fn test_pruning_depth_override() {
+        let mut params = TESTNET11_PARAMS;
+        let min_finality_windows = params.pruning_depth.div_ceil(params.finality_depth);
+        assert!(matches!(
+            params.set_pruning_depth_in_finality_windows(1),
+            Err(ConfigError::OverridePruningFinalityWindowsTooLow(windows)) if windows <= min_finality_windows
+        ));
+        assert_eq!(params.pruning_depth, TESTNET11_PARAMS.pruning_depth);
+
+        params.set_pruning_depth_in_finality_windows(10).unwrap();
+        assert_eq!(params.pruning_depth, 10 * params.finality_depth);
+        assert!(params.anticone_finalization_depth() < params.pruning_depth);
+        assert!(matches!(
+            params.set_pruning_depth_in_finality_windows(u64::MAX),
+            Err(ConfigError::OverridePruningFinalityWindowsTooHigh(_))
+        ));
+
+        // Mainnet cannot be weakened nor otherwise modified
+        let mut params = MAINNET_PARAMS;
+        assert!(matches!(params.set_pruning_depth_in_finality_windows(10), Err(ConfigError::ConsensusOverridesOnMainnet)));
+        assert_eq!(params.pruning_depth, MAINNET_PARAMS.pruning_depth);
+    }
+
+    #[test]
+    fn test_parse_assume_valid() {
+        let hash = "0fca37ca667c2d550a6c4416dad9717e50927128c424fa4edbebc436ab13aeef";
+        let assume_valid: AssumeValid = format!("{hash}:1312860").parse().unwrap();
+        assert_eq!(assume_valid, AssumeValid::new(Hash::from_str(hash).unwrap(), 1312860));
+        for invalid in [hash.to_owned(), format!("{hash}:"), format!("{hash}:-1"), "00:1".to_owned()] {
+            assert!(matches!(invalid.parse::<AssumeValid>(), Err(ConfigError::InvalidAssumeValid(_))));
+        }
+    }
+}
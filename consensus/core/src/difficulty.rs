@@ -0,0 +1,175 @@
+//!
+//! Pure implementation of the difficulty adjustment algorithm, allowing
+//! difficulty to be simulated over synthetic block timestamps.
+//!
+
+use crate::config::params::Params;
+use itertools::Itertools;
+use kaspa_math::{Uint256, Uint320};
+use std::cmp::max;
+
+/// Timestamp and difficulty bits of a block in a difficulty window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DifficultySample {
+    pub timestamp: u64,
+    pub bits: u32,
+}
+
+/// Parameters of the difficulty adjustment algorithm.
+#[derive(Clone, Debug)]
+pub struct DifficultyParams {
+    /// Target time per block (in milliseconds)
+    pub target_time_per_block: u64,
+    /// One block out of every `sample_rate` blocks is sampled into the difficulty window (`1` for full windows)
+    pub sample_rate: u64,
+    /// Number of samples in a full difficulty window
+    pub window_size: usize,
+    /// Minimum number of samples required for an adjustment; below it the genesis difficulty applies.
+    /// Expected to be at least [`MIN_DIFFICULTY_WINDOW_LEN`](crate::config::constants::consensus::MIN_DIFFICULTY_WINDOW_LEN)
+    pub min_window_len: usize,
+    pub max_difficulty_target: Uint256,
+    pub genesis_bits: u32,
+}
+
+impl DifficultyParams {
+    /// Difficulty parameters of `params` for blocks whose selected parent has the given DAA score.
+    pub fn new(params: &Params, selected_parent_daa_score: u64) -> Self {
+        Self {
+            target_time_per_block: params.target_time_per_block(selected_parent_daa_score),
+            sample_rate: params.difficulty_sample_rate(selected_parent_daa_score),
+            window_size: params.difficulty_window_size(selected_parent_daa_score),
+            min_window_len: params.min_difficulty_window_len,
+            max_difficulty_target: params.max_difficulty_target,
+            genesis_bits: params.genesis.bits,
+        }
+    }
+
+    /// Computes the difficulty bits of a block from the samples of its difficulty window.
+    ///
+    /// The sample with the lowest timestamp only contributes to the measured duration, the average target is computed
+    /// over the remaining samples. In case of a tie the first such sample in `window` is the excluded one, so callers
+    /// requiring a specific tie-break should place the lowest sample first.
+    pub fn calc_difficulty_bits(&self, window: &[DifficultySample]) -> u32 {
+        // Until there are enough blocks for a valid calculation the difficulty should remain constant.
+        if window.len() < self.min_window_len {
+            return self.genesis_bits;
+        }
+
+        let (min_ts_index, max_ts_index) = window.iter().map(|sample| sample.timestamp).position_minmax().into_option().unwrap();
+        let min_ts = window[min_ts_index].timestamp;
+        let max_ts = window[max_ts_index].timestamp;
+
+        // We need Uint320 to avoid overflow when summing and multiplying by the window size.
+        let samples_len = window.len() as u64 - 1;
+        let targets_sum: Uint320 = window
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| index != min_ts_index)
+            .map(|(_, sample)| Uint320::from(Uint256::from_compact_target_bits(sample.bits)))
+            .sum();
+        let average_target = targets_sum / samples_len;
+        let measured_duration = max(max_ts - min_ts, 1);
+        let expected_duration = self.target_time_per_block * self.sample_rate * samples_len;
+        let new_target = average_target * measured_duration / expected_duration;
+        Uint256::try_from(new_target.min(Uint320::from(self.max_difficulty_target)))
+            .expect("max target < Uint256::MAX")
+            .compact_target_bits()
+    }
+}
+
+/// Simulates the difficulty of a chain of blocks with one block per DAA score
+/// (i.e. no parallel blocks), given the timestamp of each block.
+#[derive(Clone, Debug)]
+pub struct DifficultySimulation {
+    params: DifficultyParams,
+    window: Vec<DifficultySample>,
+    daa_score: u64,
+}
+
+impl DifficultySimulation {
+    pub fn new(params: DifficultyParams) -> Self {
+        Self { window: Vec::with_capacity(params.window_size), params, daa_score: 0 }
+    }
+
+    /// Starts the simulation from an existing difficulty window (oldest sample first).
+    pub fn with_window(params: DifficultyParams, window: impl IntoIterator<Item = DifficultySample>, daa_score: u64) -> Self {
+        let mut simulation = Self::new(params);
+        window.into_iter().for_each(|sample| simulation.push_sample(sample));
+        simulation.daa_score = daa_score;
+        simulation
+    }
+
+    pub fn params(&self) -> &DifficultyParams {
+        &self.params
+    }
+
+    /// DAA score of the next block.
+    pub fn daa_score(&self) -> u64 {
+        self.daa_score
+    }
+
+    /// Difficulty bits required from the next block.
+    pub fn next_bits(&self) -> u32 {
+        self.params.calc_difficulty_bits(&self.window)
+    }
+
+    /// Adds the next block with the given timestamp and returns its difficulty bits.
+    pub fn add_block(&mut self, timestamp: u64) -> u32 {
+        let bits = self.next_bits();
+        if self.daa_score % self.params.sample_rate == 0 {
+            self.push_sample(DifficultySample { timestamp, bits });
+        }
+        self.daa_score += 1;
+        bits
+    }
+
+    /// Adds blocks with the given timestamps and returns their difficulty bits.
+    pub fn add_blocks(&mut self, timestamps: impl IntoIterator<Item = u64>) -> Vec<u32> {
+        timestamps.into_iter().map(|timestamp| self.add_block(timestamp)).collect()
+    }
+
+    fn push_sample(&mut self, sample: DifficultySample) {
+        if self.window.len() == self.params.window_size {
+            self.window.remove(0);
+        }
+        self.window.push(sample);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::params::{MAINNET_PARAMS, TESTNET11_PARAMS};
+
+    #[test]
+    fn test_difficulty_simulation() {
+        for params in [&MAINNET_PARAMS, &TESTNET11_PARAMS] {
+            let difficulty_params = DifficultyParams::new(params, u64::MAX);
+            let target_time = difficulty_params.target_time_per_block;
+            let initial_bits = Uint256::from_compact_target_bits(params.genesis.bits).compact_target_bits();
+            let window = (0..difficulty_params.window_size as u64)
+                .map(|i| DifficultySample { timestamp: i * target_time * difficulty_params.sample_rate, bits: initial_bits });
+            let start = difficulty_params.window_size as u64 * difficulty_params.sample_rate;
+            let mut simulation = DifficultySimulation::with_window(difficulty_params.clone(), window, start);
+
+            // blocks produced on target keep the difficulty stable
+            let on_target = simulation.add_blocks((start..start + 100).map(|i| i * target_time));
+            let target = |bits: u32| Uint256::from_compact_target_bits(bits);
+            assert!(on_target.iter().all(|bits| target(*bits) <= target(initial_bits) + target(initial_bits) / 100));
+
+            // blocks produced twice as fast raise the difficulty (i.e. lower the target)
+            let mut fast = simulation.clone();
+            let last = *fast.add_blocks((start + 100..start + 2_000).map(|i| i * target_time / 2)).last().unwrap();
+            assert!(target(last) < target(initial_bits));
+
+            // blocks produced twice as slow lower the difficulty, up to the max target
+            let mut slow = simulation;
+            let last = *slow.add_blocks((start + 100..start + 2_000).map(|i| i * target_time * 2)).last().unwrap();
+            assert!(target(last) > target(initial_bits));
+            assert!(target(last) <= params.max_difficulty_target);
+        }
+
+        let difficulty_params = DifficultyParams::new(&MAINNET_PARAMS, u64::MAX);
+        assert_eq!(DifficultySimulation::new(difficulty_params.clone()).next_bits(), difficulty_params.genesis_bits);
+    }
+}
@@ -0,0 +1,200 @@
+//!
+//! Emission schedule of the Kaspa block subsidy.
+//!
+//! The subsidy is constant during the pre-deflationary phase and then follows
+//! a monthly table which halves the subsidy every year (smoothly, month by month)
+//! until it reaches zero. The functions here are pure and are used by consensus
+//! when building coinbase transactions, so they can be relied upon by tools which
+//! need to project the emission (block explorers, supply reports, etc).
+//!
+
+use crate::config::params::Params;
+
+// We define a year as 365.25 days and a month as 365.25 / 12 = 30.4375
+// SECONDS_PER_MONTH = 30.4375 * 24 * 60 * 60
+pub const SECONDS_PER_MONTH: u64 = 2629800;
+
+pub const SUBSIDY_BY_MONTH_TABLE_SIZE: usize = 426;
+pub type SubsidyByMonthTable = [u64; SUBSIDY_BY_MONTH_TABLE_SIZE];
+
+/// A DAA score range `[start_daa_score, end_daa_score)` during which every block is awarded the same subsidy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmissionPeriod {
+    pub start_daa_score: u64,
+    pub end_daa_score: u64,
+    pub subsidy: u64,
+}
+
+impl EmissionPeriod {
+    /// Total subsidy emitted during the period, assuming one rewarded block per DAA score.
+    pub fn emission(&self) -> u64 {
+        (self.end_daa_score - self.start_daa_score) * self.subsidy
+    }
+}
+
+/// Block subsidy schedule of a network.
+#[derive(Clone, Debug)]
+pub struct EmissionSchedule {
+    deflationary_phase_daa_score: u64,
+    pre_deflationary_phase_base_subsidy: u64,
+
+    /// Precomputed number of blocks per month
+    blocks_per_month: u64,
+
+    /// Precomputed subsidy by month table
+    subsidy_by_month_table: SubsidyByMonthTable,
+}
+
+impl EmissionSchedule {
+    pub fn new(deflationary_phase_daa_score: u64, pre_deflationary_phase_base_subsidy: u64, target_time_per_block: u64) -> Self {
+        assert!(1000 % target_time_per_block == 0);
+        let bps = 1000 / target_time_per_block;
+        let blocks_per_month = SECONDS_PER_MONTH * bps;
+
+        // Precomputed subsidy by month table for the actual block per second rate
+        // Here values are rounded up so that we keep the same number of rewarding months as in the original 1 BPS table.
+        // In a 10 BPS network, the induced increase in total rewards is 51 KAS (see tests::calc_high_bps_total_rewards_delta()
+        // in the consensus coinbase module)
+        let subsidy_by_month_table: SubsidyByMonthTable = core::array::from_fn(|i| SUBSIDY_BY_MONTH_TABLE[i].div_ceil(bps));
+        Self { deflationary_phase_daa_score, pre_deflationary_phase_base_subsidy, blocks_per_month, subsidy_by_month_table }
+    }
+
+    pub fn deflationary_phase_daa_score(&self) -> u64 {
+        self.deflationary_phase_daa_score
+    }
+
+    pub fn pre_deflationary_phase_base_subsidy(&self) -> u64 {
+        self.pre_deflationary_phase_base_subsidy
+    }
+
+    pub fn blocks_per_month(&self) -> u64 {
+        self.blocks_per_month
+    }
+
+    pub fn subsidy_by_month_table(&self) -> &SubsidyByMonthTable {
+        &self.subsidy_by_month_table
+    }
+
+    /// Subsidy awarded to a block with the given DAA score.
+    pub fn block_subsidy(&self, daa_score: u64) -> u64 {
+        if daa_score < self.deflationary_phase_daa_score {
+            return self.pre_deflationary_phase_base_subsidy;
+        }
+
+        let months_since_deflationary_phase_started =
+            ((daa_score - self.deflationary_phase_daa_score) / self.blocks_per_month) as usize;
+        if months_since_deflationary_phase_started >= self.subsidy_by_month_table.len() {
+            *(self.subsidy_by_month_table).last().unwrap()
+        } else {
+            self.subsidy_by_month_table[months_since_deflationary_phase_started]
+        }
+    }
+
+    /// The schedule as a sequence of constant subsidy periods: the pre-deflationary
+    /// phase followed by every month of the deflationary phase with a non-zero subsidy.
+    pub fn periods(&self) -> impl Iterator<Item = EmissionPeriod> + '_ {
+        let pre_deflationary = EmissionPeriod {
+            start_daa_score: 0,
+            end_daa_score: self.deflationary_phase_daa_score,
+            subsidy: self.pre_deflationary_phase_base_subsidy,
+        };
+        let deflationary =
+            self.subsidy_by_month_table.iter().take_while(|subsidy| **subsidy > 0).enumerate().map(|(month, subsidy)| {
+                let start_daa_score = self.deflationary_phase_daa_score + month as u64 * self.blocks_per_month;
+                EmissionPeriod { start_daa_score, end_daa_score: start_daa_score + self.blocks_per_month, subsidy: *subsidy }
+            });
+        std::iter::once(pre_deflationary).chain(deflationary)
+    }
+
+    /// Total subsidy emitted by blocks with a DAA score lower than `daa_score`,
+    /// assuming one rewarded block per DAA score.
+    pub fn emission_until(&self, daa_score: u64) -> u64 {
+        self.periods()
+            .take_while(|period| period.start_daa_score < daa_score)
+            .map(|period| EmissionPeriod { end_daa_score: period.end_daa_score.min(daa_score), ..period }.emission())
+            .sum()
+    }
+
+    /// Total subsidy emitted over the whole schedule.
+    pub fn total_emission(&self) -> u64 {
+        self.periods().map(|period| period.emission()).sum()
+    }
+
+    /// DAA score from which blocks are no longer awarded any subsidy.
+    pub fn emission_end_daa_score(&self) -> u64 {
+        self.periods().last().map(|period| period.end_daa_score).unwrap_or_default()
+    }
+}
+
+impl From<&Params> for EmissionSchedule {
+    fn from(params: &Params) -> Self {
+        Self::new(params.deflationary_phase_daa_score, params.pre_deflationary_phase_base_subsidy, params.target_time_per_block)
+    }
+}
+
+/*
+    This table was pre-calculated by calling `calcDeflationaryPeriodBlockSubsidyFloatCalc` (in kaspad-go) for all months until reaching 0 subsidy.
+    To regenerate this table, run `TestBuildSubsidyTable` in coinbasemanager_test.go (note the `deflationaryPhaseBaseSubsidy` therein).
+    These values apply to 1 block per second.
+*/
+#[rustfmt::skip]
+pub const SUBSIDY_BY_MONTH_TABLE: SubsidyByMonthTable = [
+	44000000000, 41530469757, 39199543598, 36999442271, 34922823143, 32962755691, 31112698372, 29366476791, 27718263097, 26162556530, 24694165062, 23308188075, 22000000000, 20765234878, 19599771799, 18499721135, 17461411571, 16481377845, 15556349186, 14683238395, 13859131548, 13081278265, 12347082531, 11654094037, 11000000000,
+	10382617439, 9799885899, 9249860567, 8730705785, 8240688922, 7778174593, 7341619197, 6929565774, 6540639132, 6173541265, 5827047018, 5500000000, 5191308719, 4899942949, 4624930283, 4365352892, 4120344461, 3889087296, 3670809598, 3464782887, 3270319566, 3086770632, 2913523509, 2750000000, 2595654359,
+	2449971474, 2312465141, 2182676446, 2060172230, 1944543648, 1835404799, 1732391443, 1635159783, 1543385316, 1456761754, 1375000000, 1297827179, 1224985737, 1156232570, 1091338223, 1030086115, 972271824, 917702399, 866195721, 817579891, 771692658, 728380877, 687500000, 648913589, 612492868,
+	578116285, 545669111, 515043057, 486135912, 458851199, 433097860, 408789945, 385846329, 364190438, 343750000, 324456794, 306246434, 289058142, 272834555, 257521528, 243067956, 229425599, 216548930, 204394972, 192923164, 182095219, 171875000, 162228397, 153123217, 144529071,
+	136417277, 128760764, 121533978, 114712799, 108274465, 102197486, 96461582, 91047609, 85937500, 81114198, 76561608, 72264535, 68208638, 64380382, 60766989, 57356399, 54137232, 51098743, 48230791, 45523804, 42968750, 40557099, 38280804, 36132267, 34104319,
+	32190191, 30383494, 28678199, 27068616, 25549371, 24115395, 22761902, 21484375, 20278549, 19140402, 18066133, 17052159, 16095095, 15191747, 14339099, 13534308, 12774685, 12057697, 11380951, 10742187, 10139274, 9570201, 9033066, 8526079, 8047547,
+	7595873, 7169549, 6767154, 6387342, 6028848, 5690475, 5371093, 5069637, 4785100, 4516533, 4263039, 4023773, 3797936, 3584774, 3383577, 3193671, 3014424, 2845237, 2685546, 2534818, 2392550, 2258266, 2131519, 2011886, 1898968,
+	1792387, 1691788, 1596835, 1507212, 1422618, 1342773, 1267409, 1196275, 1129133, 1065759, 1005943, 949484, 896193, 845894, 798417, 753606, 711309, 671386, 633704, 598137, 564566, 532879, 502971, 474742, 448096,
+	422947, 399208, 376803, 355654, 335693, 316852, 299068, 282283, 266439, 251485, 237371, 224048, 211473, 199604, 188401, 177827, 167846, 158426, 149534, 141141, 133219, 125742, 118685, 112024, 105736,
+	99802, 94200, 88913, 83923, 79213, 74767, 70570, 66609, 62871, 59342, 56012, 52868, 49901, 47100, 44456, 41961, 39606, 37383, 35285, 33304, 31435, 29671, 28006, 26434, 24950,
+	23550, 22228, 20980, 19803, 18691, 17642, 16652, 15717, 14835, 14003, 13217, 12475, 11775, 11114, 10490, 9901, 9345, 8821, 8326, 7858, 7417, 7001, 6608, 6237, 5887,
+	5557, 5245, 4950, 4672, 4410, 4163, 3929, 3708, 3500, 3304, 3118, 2943, 2778, 2622, 2475, 2336, 2205, 2081, 1964, 1854, 1750, 1652, 1559, 1471, 1389,
+	1311, 1237, 1168, 1102, 1040, 982, 927, 875, 826, 779, 735, 694, 655, 618, 584, 551, 520, 491, 463, 437, 413, 389, 367, 347, 327,
+	309, 292, 275, 260, 245, 231, 218, 206, 194, 183, 173, 163, 154, 146, 137, 130, 122, 115, 109, 103, 97, 91, 86, 81, 77,
+	73, 68, 65, 61, 57, 54, 51, 48, 45, 43, 40, 38, 36, 34, 32, 30, 28, 27, 25, 24, 22, 21, 20, 19, 18,
+	17, 16, 15, 14, 13, 12, 12, 11, 10, 10, 9, 9, 8, 8, 7, 7, 6, 6, 6, 5, 5, 5, 4, 4, 4,
+	4, 3, 3, 3, 3, 3, 2, 2, 2, 2, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+	0,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::params::MAINNET_PARAMS, constants::SOMPI_PER_KASPA, network::NetworkId};
+
+    #[test]
+    fn test_emission_schedule() {
+        for network_id in NetworkId::iter() {
+            let params: Params = network_id.into();
+            let schedule = EmissionSchedule::from(&params);
+
+            let periods = schedule.periods().collect::<Vec<_>>();
+            assert!(
+                periods.windows(2).all(|w| w[0].end_daa_score == w[1].start_daa_score),
+                "{network_id}: periods must be contiguous"
+            );
+            for period in periods.iter() {
+                assert_eq!(schedule.block_subsidy(period.start_daa_score), period.subsidy, "{network_id}");
+                assert_eq!(schedule.block_subsidy(period.end_daa_score - 1), period.subsidy, "{network_id}");
+            }
+            assert_eq!(schedule.block_subsidy(schedule.emission_end_daa_score()), 0, "{network_id}");
+
+            let total = schedule.total_emission();
+            assert_eq!(schedule.emission_until(u64::MAX), total, "{network_id}");
+            assert_eq!(schedule.emission_until(schedule.emission_end_daa_score()), total, "{network_id}");
+            assert_eq!(schedule.emission_until(1), params.pre_deflationary_phase_base_subsidy, "{network_id}");
+            let first_month = params.deflationary_phase_daa_score + schedule.blocks_per_month();
+            assert_eq!(
+                schedule.emission_until(first_month + 1),
+                schedule.emission_until(first_month) + schedule.subsidy_by_month_table()[1],
+                "{network_id}"
+            );
+        }
+
+        // The mainnet supply cap is a bit below 29 billion KAS
+        let total = EmissionSchedule::from(&MAINNET_PARAMS).total_emission() / SOMPI_PER_KASPA;
+        assert!((28_000_000_000..29_000_000_000).contains(&total), "unexpected mainnet total emission {total}");
+    }
+}
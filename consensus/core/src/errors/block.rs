@@ -76,8 +76,8 @@ pub enum RuleError {
     #[error("block merges {0} blocks > {1} merge set size limit")]
     MergeSetTooBig(u64, u64),
 
-    #[error("block is violating bounded merge depth")]
-    ViolatingBoundedMergeDepth,
+    #[error("block is violating bounded merge depth: red {0} is neither in the future of merge depth root {1} nor kosherized")]
+    ViolatingBoundedMergeDepth(Hash, Hash),
 
     #[error("invalid merkle root: header indicates {0} but calculated value is {1}")]
     BadMerkleRoot(Hash, Hash),
@@ -100,8 +100,8 @@ pub enum RuleError {
     #[error("transaction in isolation validation failed for tx {0}: {1}")]
     TxInIsolationValidationFailed(TransactionId, TxRuleError),
 
-    #[error("block exceeded mass limit of {0}")]
-    ExceedsMassLimit(u64),
+    #[error("block exceeded mass limit of {0} with a mass of at least {1}")]
+    ExceedsMassLimit(u64, u64),
 
     #[error("transaction {0} has mass field of {1} but mass should be at least {2}")]
     MassFieldTooLow(TransactionId, u64, u64),
@@ -121,8 +121,8 @@ pub enum RuleError {
     #[error("transaction {0} is found more than once in the block")]
     DuplicateTransactions(TransactionId),
 
-    #[error("block has invalid proof-of-work")]
-    InvalidPoW,
+    #[error("block {0} has invalid proof-of-work for target bits {1}")]
+    InvalidPoW(Hash, u32),
 
     #[error("expected header pruning point is {0} but got {1}")]
     WrongHeaderPruningPoint(Hash, Hash),
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CodecError {
+    #[error("unexpected end of data: {0} more bytes are required")]
+    UnexpectedEnd(usize),
+
+    #[error("{0} trailing bytes remain after decoding")]
+    TrailingBytes(usize),
+
+    #[error("encoded length {0} exceeds the remaining data")]
+    LengthTooLarge(u64),
+
+    #[error("blue work is encoded with {0} bytes while at most {1} are allowed")]
+    BlueWorkTooLong(usize, usize),
+
+    #[error("blue work is not minimally encoded")]
+    NonCanonicalBlueWork,
+}
+
+pub type CodecResult<T> = std::result::Result<T, CodecError>;
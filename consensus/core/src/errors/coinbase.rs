@@ -13,6 +13,9 @@ pub enum CoinbaseError {
 
     #[error("coinbase payload length is {0} bytes but it needs to be at least {1} bytes long in order to accommodate the script public key")]
     PayloadCantContainScriptPublicKey(usize, usize),
+
+    #[error("coinbase rewards of the mergeset overflow")]
+    RewardOverflow,
 }
 
 pub type CoinbaseResult<T> = std::result::Result<T, CoinbaseError>;
@@ -5,6 +5,9 @@ pub enum ConfigError {
     #[error("Configuration: --addpeer and --connect cannot be used together")]
     MixedConnectAndAddPeers,
 
+    #[error("Configuration: --anchorpeer and --connect cannot be used together")]
+    MixedConnectAndAnchorPeers,
+
     #[error("Configuration: --logdir and --nologfiles cannot be used together")]
     MixedLogDirAndNoLogFiles,
 
@@ -14,9 +17,72 @@ pub enum ConfigError {
     #[error("Configuration: --ram-scale cannot be set above 10.0")]
     RamScaleTooHigh,
 
+    #[error("Configuration: --cache-policy={0} is not a known cache policy")]
+    UnknownCachePolicy(String),
+
+    #[error("Configuration: per-store cache budgets (--cache-*-mb) require --cache-policy=custom")]
+    CacheBudgetsRequireCustomPolicy,
+
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: consensus param presets and overrides (--preset, --override-*) cannot be used on mainnet")]
+    ConsensusOverridesOnMainnet,
+
+    #[error("Configuration: --override-bps={0} is not supported (BPS must be one of 1, 2, 4, 5, 8, 10, 20, 25)")]
+    UnsupportedOverrideBps(u64),
+
+    #[error("Configuration: --preset={0} is not a known params preset")]
+    UnknownPreset(String),
+
+    #[error("Configuration: --preset={0} can only be used on simnet")]
+    PresetRequiresSimnet(String),
+
+    #[error("Configuration: --override-max-block-parents must be at least 1")]
+    OverrideMaxBlockParentsTooLow,
+
+    #[error("Configuration: --override-mergeset-size-limit must be greater than GHOSTDAG K ({0})")]
+    OverrideMergesetSizeLimitTooLow(u64),
+
+    #[error("Configuration: --override-max-block-parents cannot be set above the mergeset size limit ({0})")]
+    OverrideMaxBlockParentsAboveMergesetSizeLimit(u64),
+
+    #[error("Configuration: --override-pruning-finality-windows must be at least {0} for the pruning point to be final")]
+    OverridePruningFinalityWindowsTooLow(u64),
+
+    #[error("Configuration: --override-pruning-finality-windows cannot be set above {0}")]
+    OverridePruningFinalityWindowsTooHigh(u64),
+
+    #[error("Configuration: --miningaddr {0} is not a valid address for the {1} network")]
+    InvalidMiningAddress(String, String),
+
+    #[error("Configuration: --coinbase-extra-data cannot be longer than {0} bytes")]
+    CoinbaseExtraDataTooLong(usize),
+
+    #[error("Configuration: --assume-valid {0} is invalid (expected <block hash>:<DAA score>)")]
+    InvalidAssumeValid(String),
+
+    #[error("Configuration: --assume-valid and --no-assume-valid cannot be used together")]
+    MixedAssumeValidAndNoAssumeValid,
+
+    #[error("Configuration: --{0} must be a positive number of megabits per second")]
+    InvalidUploadLimit(&'static str),
+
+    #[error("Configuration: --rpc-whitelist entry {0} is neither a profile nor an RPC method")]
+    InvalidRpcWhitelistEntry(String),
+
+    #[error("Configuration: --rpc-rate-limit={0} is invalid (expected <method>:<positive calls per second>)")]
+    InvalidRpcRateLimit(String),
+
+    #[error("Configuration: --rpc-max-concurrent-requests must be at least 1")]
+    RpcMaxConcurrentRequestsTooLow,
+
+    #[error("Configuration: --wrpc-max-connections and --wrpc-max-connections-per-ip must be at least 1")]
+    WrpcMaxConnectionsTooLow,
+
+    #[error("Configuration: --proxy is invalid: {0}")]
+    InvalidProxy(String),
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
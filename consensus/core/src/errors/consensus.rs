@@ -32,6 +32,15 @@ pub enum ConsensusError {
     #[error("difficulty error: {0}")]
     DifficultyError(#[from] DifficultyError),
 
+    #[error("score {0} is below the score of the lowest retained chain block")]
+    ChainScoreBelowRetention(u64),
+
+    #[error("score {0} is above the score of the sink")]
+    ChainScoreAboveSink(u64),
+
+    #[error("storage error: {0}")]
+    StorageError(String),
+
     #[error("{0}")]
     General(&'static str),
 }
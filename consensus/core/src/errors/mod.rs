@@ -1,9 +1,11 @@
 pub mod block;
+pub mod codec;
 pub mod coinbase;
 pub mod config;
 pub mod consensus;
 pub mod difficulty;
 pub mod pruning;
+pub mod snapshot;
 pub mod sync;
 pub mod traversal;
 pub mod tx;
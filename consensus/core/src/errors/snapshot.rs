@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("snapshot io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("not a UTXO set snapshot file")]
+    InvalidMagic,
+
+    #[error("unsupported snapshot version {0} (expected {1})")]
+    UnsupportedVersion(u32, u32),
+
+    #[error("snapshot was created for network {0} but the node is running on {1}")]
+    NetworkMismatch(String, String),
+
+    #[error("snapshot contains {0} UTXO entries but its trailer declares {1}")]
+    UtxoCountMismatch(u64, u64),
+
+    #[error("snapshot is malformed: {0}")]
+    Malformed(&'static str),
+}
+
+pub type SnapshotResult<T> = std::result::Result<T, SnapshotError>;
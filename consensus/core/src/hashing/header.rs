@@ -6,6 +6,12 @@ use kaspa_hashes::{Hash, HasherBase};
 #[inline]
 pub fn hash_override_nonce_time(header: &Header, nonce: u64, timestamp: u64) -> Hash {
     let mut hasher = kaspa_hashes::BlockHash::new();
+    write_header(&mut hasher, header, nonce, timestamp);
+    hasher.finalize()
+}
+
+/// Writes the header fields in consensus serialization order, using the provided nonce+timestamp instead of those in the header.
+pub(crate) fn write_header<T: HasherBase>(hasher: &mut T, header: &Header, nonce: u64, timestamp: u64) {
     hasher.update(header.version.to_le_bytes()).write_len(header.parents_by_level.len()); // Write the number of parent levels
 
     // Write parents at each level
@@ -25,8 +31,6 @@ pub fn hash_override_nonce_time(header: &Header, nonce: u64, timestamp: u64) ->
         .update(header.blue_score.to_le_bytes())
         .write_blue_work(header.blue_work)
         .update(header.pruning_point);
-
-    hasher.finalize()
 }
 
 /// Returns the header hash.
@@ -183,13 +183,13 @@ pub fn sig_op_counts_hash(tx: &Transaction, hash_type: SigHashType, reused_value
 }
 
 pub fn payload_hash(tx: &Transaction) -> Hash {
-    if tx.subnetwork_id == SUBNETWORK_ID_NATIVE {
+    // Native transactions commit to their payload only if they have one, which retains the hashes
+    // of all native transactions preceding the payload activation
+    if tx.subnetwork_id == SUBNETWORK_ID_NATIVE && tx.payload.is_empty() {
         return ZERO_HASH;
     }
 
-    // TODO: Right now this branch will never be executed, since payload is disabled
-    // for all non coinbase transactions. Once payload is enabled, the payload hash
-    // should be cached to make it cost O(1) instead of O(tx.inputs.len()).
+    // TODO: The payload hash should be cached to make it cost O(1) instead of O(tx.inputs.len()).
     let mut hasher = TransactionSigningHash::new();
     hasher.write_var_bytes(&tx.payload);
     hasher.finalize()
@@ -241,6 +241,19 @@ pub fn calc_schnorr_signature_hash(
     input_index: usize,
     hash_type: SigHashType,
     reused_values: &impl SigHashReusedValues,
+) -> Hash {
+    calc_schnorr_signature_hash_with_fork_id(verifiable_tx, input_index, hash_type, reused_values, None)
+}
+
+/// Calculates the schnorr signature hash, folding in `fork_id` if set. Networks forked with distinct fork ids
+/// (see [`Params::sighash_fork_id`](crate::config::params::Params::sighash_fork_id)) thus have replay protection
+/// between them. Without a fork id the hash equals [`calc_schnorr_signature_hash`]
+pub fn calc_schnorr_signature_hash_with_fork_id(
+    verifiable_tx: &impl VerifiableTransaction,
+    input_index: usize,
+    hash_type: SigHashType,
+    reused_values: &impl SigHashReusedValues,
+    fork_id: Option<u8>,
 ) -> Hash {
     let input = verifiable_tx.populated_input(input_index);
     let tx = verifiable_tx.tx();
@@ -262,6 +275,9 @@ pub fn calc_schnorr_signature_hash(
         .write_u64(tx.gas)
         .update(payload_hash(tx))
         .write_u8(hash_type.to_u8());
+    if let Some(fork_id) = fork_id {
+        hasher.write_u8(fork_id);
+    }
     hasher.finalize()
 }
 
@@ -271,7 +287,18 @@ pub fn calc_ecdsa_signature_hash(
     hash_type: SigHashType,
     reused_values: &impl SigHashReusedValues,
 ) -> Hash {
-    let hash = calc_schnorr_signature_hash(tx, input_index, hash_type, reused_values);
+    calc_ecdsa_signature_hash_with_fork_id(tx, input_index, hash_type, reused_values, None)
+}
+
+/// The ECDSA counterpart of [`calc_schnorr_signature_hash_with_fork_id`]
+pub fn calc_ecdsa_signature_hash_with_fork_id(
+    tx: &impl VerifiableTransaction,
+    input_index: usize,
+    hash_type: SigHashType,
+    reused_values: &impl SigHashReusedValues,
+    fork_id: Option<u8>,
+) -> Hash {
+    let hash = calc_schnorr_signature_hash_with_fork_id(tx, input_index, hash_type, reused_values, fork_id);
     let mut hasher = TransactionSigningHashECDSA::new();
     hasher.update(hash);
     hasher.finalize()
@@ -680,5 +707,21 @@ mod tests {
                 test.name
             );
         }
+
+        // A fork id is folded into the hash, while no fork id retains the original hash
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let hash = |fork_id| calc_schnorr_signature_hash_with_fork_id(&native_populated_tx, 0, SIG_HASH_ALL, &reused_values, fork_id);
+        assert_eq!(hash(None), calc_schnorr_signature_hash(&native_populated_tx, 0, SIG_HASH_ALL, &reused_values));
+        assert_ne!(hash(Some(0)), hash(None));
+        assert_ne!(hash(Some(0)), hash(Some(1)));
+
+        // A native transaction commits to its payload once it has one
+        let mut payload_tx = native_tx.clone();
+        payload_tx.payload = vec![1, 2, 3];
+        let payload_populated_tx = PopulatedTransaction::new(&payload_tx, native_populated_tx.entries.clone());
+        assert_ne!(
+            calc_schnorr_signature_hash(&payload_populated_tx, 0, SIG_HASH_ALL, &SigHashReusedValuesUnsync::new()),
+            calc_schnorr_signature_hash(&native_populated_tx, 0, SIG_HASH_ALL, &SigHashReusedValuesUnsync::new())
+        );
     }
 }
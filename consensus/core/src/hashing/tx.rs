@@ -1,6 +1,6 @@
 use super::HasherExtensions;
 use crate::tx::{Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput};
-use kaspa_hashes::{Hash, Hasher};
+use kaspa_hashes::{Hash, HasherBase};
 
 /// A bitmask defining which transaction fields we
 /// want to encode and which to ignore.
@@ -10,9 +10,17 @@ pub const TX_ENCODING_FULL: TxEncodingFlags = 0;
 pub const TX_ENCODING_EXCLUDE_SIGNATURE_SCRIPT: TxEncodingFlags = 1;
 
 /// Returns the transaction hash. Note that this is different than the transaction ID.
+///
+/// The hash commits to the mass field only if `include_mass_field` is set, i.e., once the storage mass hardfork is
+/// active. Computed anew on every call, use `tx.hash()` for the cached hash
 pub fn hash(tx: &Transaction, include_mass_field: bool) -> Hash {
+    hash_with_mass(tx, if include_mass_field { tx.mass() } else { 0 })
+}
+
+/// Returns the transaction hash committing to `mass`, see [`hash`]
+pub(crate) fn hash_with_mass(tx: &Transaction, mass: u64) -> Hash {
     let mut hasher = kaspa_hashes::TransactionHash::new();
-    write_transaction(&mut hasher, tx, TX_ENCODING_FULL, include_mass_field);
+    write_transaction(&mut hasher, tx, TX_ENCODING_FULL, mass);
     hasher.finalize()
 }
 
@@ -23,12 +31,12 @@ pub(crate) fn id(tx: &Transaction) -> TransactionId {
 
     let encoding_flags = if tx.is_coinbase() { TX_ENCODING_FULL } else { TX_ENCODING_EXCLUDE_SIGNATURE_SCRIPT };
     let mut hasher = kaspa_hashes::TransactionID::new();
-    write_transaction(&mut hasher, tx, encoding_flags, false);
+    write_transaction(&mut hasher, tx, encoding_flags, 0);
     hasher.finalize()
 }
 
 /// Write the transaction into the provided hasher according to the encoding flags
-fn write_transaction<T: Hasher>(hasher: &mut T, tx: &Transaction, encoding_flags: TxEncodingFlags, include_mass_field: bool) {
+pub(crate) fn write_transaction<T: HasherBase>(hasher: &mut T, tx: &Transaction, encoding_flags: TxEncodingFlags, mass: u64) {
     hasher.update(tx.version.to_le_bytes()).write_len(tx.inputs.len());
     for input in tx.inputs.iter() {
         // Write the tx input
@@ -43,19 +51,15 @@ fn write_transaction<T: Hasher>(hasher: &mut T, tx: &Transaction, encoding_flags
 
     hasher.update(tx.lock_time.to_le_bytes()).update(&tx.subnetwork_id).update(tx.gas.to_le_bytes()).write_var_bytes(&tx.payload);
 
-    // TODO:
-    //      1. Avoid passing a boolean and hash the mass only if > 0 (requires setting the mass to 0 on BBT).
-    //      2. Use TxEncodingFlags to avoid including the mass for tx ID
-    if include_mass_field {
-        let mass = tx.mass();
-        if mass > 0 {
-            hasher.update(mass.to_le_bytes());
-        }
+    // A zero mass is not written, so the tx ID (which passes zero) and the hash of txs with an unset mass do not
+    // commit to the mass field
+    if mass > 0 {
+        hasher.update(mass.to_le_bytes());
     }
 }
 
 #[inline(always)]
-fn write_input<T: Hasher>(hasher: &mut T, input: &TransactionInput, encoding_flags: TxEncodingFlags) {
+fn write_input<T: HasherBase>(hasher: &mut T, input: &TransactionInput, encoding_flags: TxEncodingFlags) {
     write_outpoint(hasher, &input.previous_outpoint);
     if encoding_flags & TX_ENCODING_EXCLUDE_SIGNATURE_SCRIPT != TX_ENCODING_EXCLUDE_SIGNATURE_SCRIPT {
         hasher.write_var_bytes(input.signature_script.as_slice()).update([input.sig_op_count]);
@@ -66,12 +70,12 @@ fn write_input<T: Hasher>(hasher: &mut T, input: &TransactionInput, encoding_fla
 }
 
 #[inline(always)]
-fn write_outpoint<T: Hasher>(hasher: &mut T, outpoint: &TransactionOutpoint) {
+fn write_outpoint<T: HasherBase>(hasher: &mut T, outpoint: &TransactionOutpoint) {
     hasher.update(outpoint.transaction_id).update(outpoint.index.to_le_bytes());
 }
 
 #[inline(always)]
-fn write_output<T: Hasher>(hasher: &mut T, output: &TransactionOutput) {
+fn write_output<T: HasherBase>(hasher: &mut T, output: &TransactionOutput) {
     hasher
         .update(output.value.to_le_bytes())
         .update(output.script_public_key.version().to_le_bytes())
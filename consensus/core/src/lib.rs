@@ -18,19 +18,25 @@ pub mod api;
 pub mod block;
 pub mod blockhash;
 pub mod blockstatus;
+pub mod codec;
 pub mod coinbase;
 pub mod config;
 pub mod constants;
 pub mod daa_score_timestamp;
+pub mod difficulty;
+pub mod emission;
 pub mod errors;
 pub mod hashing;
 pub mod header;
+pub mod limits;
 pub mod mass;
 pub mod merkle;
 pub mod muhash;
 pub mod network;
 pub mod pruning;
 pub mod sign;
+pub mod snapshot;
+pub mod sompi;
 pub mod subnets;
 pub mod trusted;
 pub mod tx;
@@ -94,6 +100,13 @@ pub struct ChainPath {
     pub removed: Vec<Hash>,
 }
 
+/// A score by which selected chain blocks can be looked up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChainScore {
+    DaaScore,
+    BlueScore,
+}
+
 /// `hashes::Hash` writes 4 u64s so we just use the last one as the hash here
 #[derive(Default, Clone, Copy)]
 pub struct BlockHasher(u64);
@@ -0,0 +1,106 @@
+//!
+//! Upper bounds on the counts and sizes of consensus objects received from untrusted sources, i.e. p2p peers and RPC clients.
+//!
+//! The bounds do not depend on the network and are well above what consensus rules allow, so they never reject a valid
+//! object. The p2p and RPC conversion layers check them before allocating the converted objects, so that a peer cannot
+//! force huge allocations with malformed messages.
+//!
+
+use std::fmt::Display;
+use thiserror::Error;
+
+/// Maximum number of parent levels of a header. Block levels are represented by a `u8`
+pub const MAX_PARENT_LEVELS: usize = 256;
+
+/// Maximum number of parents at a single header level. Direct parents are limited to `u8::MAX` by
+/// `Params::max_block_parents` while higher levels may have more parents
+pub const MAX_PARENTS_PER_LEVEL: usize = 2048;
+
+/// Maximum number of transactions of a block. Every transaction has a mass of dozens of grams at least, so the
+/// block mass limit allows for far fewer transactions
+pub const MAX_BLOCK_TRANSACTIONS: usize = 100_000;
+
+/// Maximum number of inputs of a transaction. Every input has a mass of dozens of grams at least
+pub const MAX_TRANSACTION_INPUTS: usize = 100_000;
+
+/// Maximum number of outputs of a transaction. Every output has a mass of dozens of grams at least
+pub const MAX_TRANSACTION_OUTPUTS: usize = 100_000;
+
+/// Maximum length of a signature script or a script public key. Every byte has a mass of one gram at least, hence a
+/// longer script exceeds the block mass limit
+pub const MAX_SCRIPT_LEN: usize = 1_000_000;
+
+/// Maximum length of a transaction payload. Every byte has a mass of one gram at least, hence a longer payload exceeds
+/// the block mass limit
+pub const MAX_PAYLOAD_LEN: usize = 1_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    ParentLevels,
+    ParentsPerLevel,
+    BlockTransactions,
+    TransactionInputs,
+    TransactionOutputs,
+    SignatureScriptLen,
+    ScriptPublicKeyLen,
+    PayloadLen,
+}
+
+impl Limit {
+    pub const fn max(self) -> usize {
+        match self {
+            Limit::ParentLevels => MAX_PARENT_LEVELS,
+            Limit::ParentsPerLevel => MAX_PARENTS_PER_LEVEL,
+            Limit::BlockTransactions => MAX_BLOCK_TRANSACTIONS,
+            Limit::TransactionInputs => MAX_TRANSACTION_INPUTS,
+            Limit::TransactionOutputs => MAX_TRANSACTION_OUTPUTS,
+            Limit::SignatureScriptLen | Limit::ScriptPublicKeyLen => MAX_SCRIPT_LEN,
+            Limit::PayloadLen => MAX_PAYLOAD_LEN,
+        }
+    }
+
+    /// Checks that `size` does not exceed the limit
+    pub fn check(self, size: usize) -> Result<(), LimitExceeded> {
+        if size > self.max() {
+            Err(LimitExceeded { limit: self, size })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Limit::ParentLevels => "number of parent levels",
+            Limit::ParentsPerLevel => "number of parents per level",
+            Limit::BlockTransactions => "number of block transactions",
+            Limit::TransactionInputs => "number of transaction inputs",
+            Limit::TransactionOutputs => "number of transaction outputs",
+            Limit::SignatureScriptLen => "signature script length",
+            Limit::ScriptPublicKeyLen => "script public key length",
+            Limit::PayloadLen => "payload length",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{limit} {size} exceeds the maximum of {}", limit.max())]
+pub struct LimitExceeded {
+    pub limit: Limit,
+    pub size: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_check() {
+        assert!(Limit::ParentsPerLevel.check(MAX_PARENTS_PER_LEVEL).is_ok());
+        let err = Limit::ParentsPerLevel.check(MAX_PARENTS_PER_LEVEL + 1).unwrap_err();
+        assert_eq!(err, LimitExceeded { limit: Limit::ParentsPerLevel, size: MAX_PARENTS_PER_LEVEL + 1 });
+        assert_eq!(err.to_string(), "number of parents per level 2049 exceeds the maximum of 2048");
+    }
+}
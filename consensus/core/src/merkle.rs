@@ -1,14 +1,44 @@
-use crate::{hashing, tx::Transaction};
+use crate::tx::Transaction;
 use kaspa_hashes::Hash;
-use kaspa_merkle::calc_merkle_root;
+use kaspa_merkle::{build_merkle_proof, calc_merkle_root, verify_merkle_proof};
+pub use kaspa_merkle::{MerkleProof, MerkleRootBuilder};
 
 pub fn calc_hash_merkle_root<'a>(txs: impl ExactSizeIterator<Item = &'a Transaction>, include_mass_field: bool) -> Hash {
-    calc_merkle_root(txs.map(|tx| hashing::tx::hash(tx, include_mass_field)))
+    calc_merkle_root(txs.map(|tx| tx.hash(include_mass_field)))
+}
+
+/// Appends the hashes of `txs` to `builder`, allowing to maintain the hash merkle root of a transaction list which is
+/// being appended to, such as a block template under construction
+pub fn extend_hash_merkle_root<'a>(
+    builder: &mut MerkleRootBuilder,
+    txs: impl IntoIterator<Item = &'a Transaction>,
+    include_mass_field: bool,
+) {
+    builder.extend(txs.into_iter().map(|tx| tx.hash(include_mass_field)))
+}
+
+/// Builds a proof of inclusion of the `index`'th transaction in the hash merkle root computed by [`calc_hash_merkle_root`]
+/// over `txs`, or returns `None` if there is no such transaction. This allows SPV clients to verify transaction inclusion
+/// against a block header
+pub fn build_hash_merkle_proof<'a>(
+    txs: impl ExactSizeIterator<Item = &'a Transaction>,
+    index: usize,
+    include_mass_field: bool,
+) -> Option<MerkleProof> {
+    build_merkle_proof(txs.map(|tx| tx.hash(include_mass_field)), index)
+}
+
+/// Verifies that `proof` proves the inclusion of `tx` in a block with the given hash merkle root. Note that the proven leaf
+/// is the transaction hash, which unlike the transaction ID also commits to signature scripts
+pub fn verify_hash_merkle_proof(hash_merkle_root: Hash, tx: &Transaction, proof: &MerkleProof, include_mass_field: bool) -> bool {
+    verify_merkle_proof(hash_merkle_root, tx.hash(include_mass_field), proof)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::merkle::calc_hash_merkle_root;
+    use crate::merkle::{
+        build_hash_merkle_proof, calc_hash_merkle_root, extend_hash_merkle_root, verify_hash_merkle_proof, MerkleRootBuilder,
+    };
     use crate::{
         subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
         tx::{scriptvec, ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput},
@@ -244,5 +274,18 @@ mod tests {
                 0x38, 0x97, 0x4d, 0xa5, 0x0f, 0xd6, 0xef, 0xb4, 0xd2, 0x66, 0xbc, 0x8d, 0x21,
             ])
         );
+
+        // Appending the transactions incrementally yields the same root
+        let mut builder = MerkleRootBuilder::new();
+        extend_hash_merkle_root(&mut builder, &txs[..2], false);
+        extend_hash_merkle_root(&mut builder, &txs[2..], false);
+        assert_eq!(builder.root(), calc_hash_merkle_root(txs.iter(), false));
+
+        let root = builder.root();
+        for (index, tx) in txs.iter().enumerate() {
+            let proof = build_hash_merkle_proof(txs.iter(), index, false).unwrap();
+            assert!(verify_hash_merkle_proof(root, tx, &proof, false));
+            assert!(!verify_hash_merkle_proof(root, &txs[(index + 1) % txs.len()], &proof, false));
+        }
     }
 }
@@ -1,11 +1,13 @@
 use crate::{
     hashing::{
-        sighash::{calc_schnorr_signature_hash, SigHashReusedValuesUnsync},
+        sighash::{calc_schnorr_signature_hash, calc_schnorr_signature_hash_with_fork_id, SigHashReusedValuesUnsync},
         sighash_type::{SigHashType, SIG_HASH_ALL},
     },
     tx::{SignableTransaction, VerifiableTransaction},
 };
+use async_trait::async_trait;
 use itertools::Itertools;
+use kaspa_hashes::Hash;
 use std::collections::BTreeMap;
 use std::iter::once;
 use thiserror::Error;
@@ -78,20 +80,171 @@ impl Signed {
     }
 }
 
-/// Sign a transaction using schnorr
-pub fn sign(mut signable_tx: SignableTransaction, schnorr_key: secp256k1::Keypair) -> SignableTransaction {
-    for i in 0..signable_tx.tx.inputs.len() {
-        signable_tx.tx.inputs[i].sig_op_count = 1;
+/// A request for a Schnorr signature over the sighash of a single transaction input.
+#[derive(Clone, Debug)]
+pub struct SignatureRequest {
+    pub input_index: usize,
+    /// The key expected to produce the signature.
+    pub public_key: secp256k1::PublicKey,
+    pub sig_hash: Hash,
+    pub hash_type: SigHashType,
+}
+
+/// A source of Schnorr signatures over transaction sighashes.
+///
+/// Signing is split from sighash computation so that keys do not have to be present
+/// in process memory: besides [`KeypairSigner`], implementations can forward requests
+/// to a hardware wallet transport or an external HSM. All requests for a transaction are
+/// passed in a single call, allowing a device to ask for one confirmation per transaction.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Public keys of all keys this signer can sign with.
+    fn public_keys(&self) -> Vec<secp256k1::PublicKey>;
+
+    /// Returns one signature per request, in request order.
+    async fn sign(&self, requests: Vec<SignatureRequest>) -> Result<Vec<secp256k1::schnorr::Signature>, Error>;
+}
+
+/// [`Signer`] backed by in-memory key pairs.
+pub struct KeypairSigner {
+    keypairs: Vec<secp256k1::Keypair>,
+}
+
+impl KeypairSigner {
+    pub fn new(keypairs: Vec<secp256k1::Keypair>) -> Self {
+        Self { keypairs }
+    }
+
+    pub fn try_from_private_keys(privkeys: &[[u8; 32]]) -> Result<Self, Error> {
+        let keypairs = privkeys
+            .iter()
+            .map(|privkey| secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, privkey))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(keypairs))
     }
 
+    /// Synchronous counterpart of [`Signer::sign`] for callers outside an async context.
+    pub fn sign_sync(&self, requests: &[SignatureRequest]) -> Result<Vec<secp256k1::schnorr::Signature>, Error> {
+        requests
+            .iter()
+            .map(|request| {
+                let keypair = self
+                    .keypairs
+                    .iter()
+                    .find(|keypair| keypair.public_key() == request.public_key)
+                    .ok_or_else(|| Error::Message(format!("No key available for input {}", request.input_index)))?;
+                let msg = secp256k1::Message::from_digest_slice(request.sig_hash.as_bytes().as_slice())?;
+                Ok(keypair.sign_schnorr(msg))
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Signer for KeypairSigner {
+    fn public_keys(&self) -> Vec<secp256k1::PublicKey> {
+        self.keypairs.iter().map(|keypair| keypair.public_key()).collect()
+    }
+
+    async fn sign(&self, requests: Vec<SignatureRequest>) -> Result<Vec<secp256k1::schnorr::Signature>, Error> {
+        self.sign_sync(&requests)
+    }
+}
+
+/// Builds signature requests for all inputs spending a P2PK script of one of `public_keys`, with signature hashes committing
+/// to the given sighash fork id (see `Params::sighash_fork_id`). The returned flag is set if some inputs can not be signed
+/// with these keys.
+pub fn signature_requests(
+    signable_tx: &SignableTransaction,
+    public_keys: &[secp256k1::PublicKey],
+    fork_id: Option<u8>,
+) -> (Vec<SignatureRequest>, bool) {
+    let scripts = public_keys
+        .iter()
+        .map(|public_key| {
+            let script = once(0x20).chain(public_key.x_only_public_key().0.serialize()).chain(once(0xac)).collect_vec();
+            (script, *public_key)
+        })
+        .collect::<BTreeMap<_, _>>();
+
     let reused_values = SigHashReusedValuesUnsync::new();
+    let mut additional_signatures_required = false;
+    let mut requests = Vec::with_capacity(signable_tx.tx.inputs.len());
     for i in 0..signable_tx.tx.inputs.len() {
-        let sig_hash = calc_schnorr_signature_hash(&signable_tx.as_verifiable(), i, SIG_HASH_ALL, &reused_values);
-        let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
-        let sig: [u8; 64] = *schnorr_key.sign_schnorr(msg).as_ref();
+        let script = signable_tx.entries[i].as_ref().unwrap().script_public_key.script();
+        if let Some(public_key) = scripts.get(script) {
+            let sig_hash =
+                calc_schnorr_signature_hash_with_fork_id(&signable_tx.as_verifiable(), i, SIG_HASH_ALL, &reused_values, fork_id);
+            requests.push(SignatureRequest { input_index: i, public_key: *public_key, sig_hash, hash_type: SIG_HASH_ALL });
+        } else {
+            additional_signatures_required = true;
+        }
+    }
+    (requests, additional_signatures_required)
+}
+
+/// Writes the signatures produced for `requests` into the signature scripts of the transaction inputs.
+pub fn apply_signatures(
+    signable_tx: &mut SignableTransaction,
+    requests: &[SignatureRequest],
+    signatures: &[secp256k1::schnorr::Signature],
+) -> Result<(), Error> {
+    if requests.len() != signatures.len() {
+        return Err(Error::Message(format!("Expected {} signatures but the signer returned {}", requests.len(), signatures.len())));
+    }
+    for (request, signature) in requests.iter().zip(signatures) {
+        let sig: [u8; 64] = *signature.as_ref();
         // This represents OP_DATA_65 <SIGNATURE+SIGHASH_TYPE> (since signature length is 64 bytes and SIGHASH_TYPE is one byte)
-        signable_tx.tx.inputs[i].signature_script = std::iter::once(65u8).chain(sig).chain([SIG_HASH_ALL.to_u8()]).collect();
+        signable_tx
+            .tx
+            .set_signature_script(request.input_index, std::iter::once(65u8).chain(sig).chain([request.hash_type.to_u8()]).collect());
+    }
+    Ok(())
+}
+
+/// Sign all P2PK inputs spendable by `signer` for a network with the given sighash fork id. Inputs belonging to other keys
+/// are left untouched.
+pub async fn sign_with_signer(
+    mut signable_tx: SignableTransaction,
+    signer: &dyn Signer,
+    fork_id: Option<u8>,
+) -> Result<Signed, Error> {
+    let (requests, additional_signatures_required) = signature_requests(&signable_tx, &signer.public_keys(), fork_id);
+    let signatures = signer.sign(requests.clone()).await?;
+    apply_signatures(&mut signable_tx, &requests, &signatures)?;
+    if additional_signatures_required {
+        Ok(Signed::Partially(signable_tx))
+    } else {
+        Ok(Signed::Fully(signable_tx))
     }
+}
+
+/// Sign a transaction using schnorr
+pub fn sign(signable_tx: SignableTransaction, schnorr_key: secp256k1::Keypair) -> SignableTransaction {
+    sign_with_fork_id(signable_tx, schnorr_key, None)
+}
+
+/// Sign a transaction using schnorr for a network with the given sighash fork id (see `Params::sighash_fork_id`)
+pub fn sign_with_fork_id(
+    mut signable_tx: SignableTransaction,
+    schnorr_key: secp256k1::Keypair,
+    fork_id: Option<u8>,
+) -> SignableTransaction {
+    for i in 0..signable_tx.tx.inputs.len() {
+        signable_tx.tx.inputs[i].sig_op_count = 1;
+    }
+
+    let reused_values = SigHashReusedValuesUnsync::new();
+    let requests = (0..signable_tx.tx.inputs.len())
+        .map(|i| SignatureRequest {
+            input_index: i,
+            public_key: schnorr_key.public_key(),
+            sig_hash: calc_schnorr_signature_hash_with_fork_id(&signable_tx.as_verifiable(), i, SIG_HASH_ALL, &reused_values, fork_id),
+            hash_type: SIG_HASH_ALL,
+        })
+        .collect_vec();
+    let signatures = KeypairSigner::new(vec![schnorr_key]).sign_sync(&requests).unwrap();
+    apply_signatures(&mut signable_tx, &requests, &signatures).unwrap();
     signable_tx
 }
 
@@ -114,7 +267,7 @@ pub fn sign_with_multiple(mut mutable_tx: SignableTransaction, privkeys: Vec<[u8
             let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
             let sig: [u8; 64] = *schnorr_key.sign_schnorr(msg).as_ref();
             // This represents OP_DATA_65 <SIGNATURE+SIGHASH_TYPE> (since signature length is 64 bytes and SIGHASH_TYPE is one byte)
-            mutable_tx.tx.inputs[i].signature_script = std::iter::once(65u8).chain(sig).chain([SIG_HASH_ALL.to_u8()]).collect();
+            mutable_tx.tx.set_signature_script(i, std::iter::once(65u8).chain(sig).chain([SIG_HASH_ALL.to_u8()]).collect());
         }
     }
     mutable_tx
@@ -123,29 +276,17 @@ pub fn sign_with_multiple(mut mutable_tx: SignableTransaction, privkeys: Vec<[u8
 /// TODO (aspect) - merge this with `v1` fn above or refactor wallet core to use the script engine.
 /// Sign a transaction using schnorr
 #[allow(clippy::result_large_err)]
-pub fn sign_with_multiple_v2(mut mutable_tx: SignableTransaction, privkeys: &[[u8; 32]]) -> Signed {
-    let mut map = BTreeMap::new();
-    for privkey in privkeys {
-        let schnorr_key = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, privkey).unwrap();
-        let schnorr_public_key = schnorr_key.public_key().x_only_public_key().0;
-        let script_pub_key_script = once(0x20).chain(schnorr_public_key.serialize().into_iter()).chain(once(0xac)).collect_vec();
-        map.insert(script_pub_key_script, schnorr_key);
-    }
+pub fn sign_with_multiple_v2(mutable_tx: SignableTransaction, privkeys: &[[u8; 32]]) -> Signed {
+    sign_with_multiple_v2_with_fork_id(mutable_tx, privkeys, None)
+}
 
-    let reused_values = SigHashReusedValuesUnsync::new();
-    let mut additional_signatures_required = false;
-    for i in 0..mutable_tx.tx.inputs.len() {
-        let script = mutable_tx.entries[i].as_ref().unwrap().script_public_key.script();
-        if let Some(schnorr_key) = map.get(script) {
-            let sig_hash = calc_schnorr_signature_hash(&mutable_tx.as_verifiable(), i, SIG_HASH_ALL, &reused_values);
-            let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
-            let sig: [u8; 64] = *schnorr_key.sign_schnorr(msg).as_ref();
-            // This represents OP_DATA_65 <SIGNATURE+SIGHASH_TYPE> (since signature length is 64 bytes and SIGHASH_TYPE is one byte)
-            mutable_tx.tx.inputs[i].signature_script = std::iter::once(65u8).chain(sig).chain([SIG_HASH_ALL.to_u8()]).collect();
-        } else {
-            additional_signatures_required = true;
-        }
-    }
+/// Sign a transaction using schnorr for a network with the given sighash fork id (see `Params::sighash_fork_id`)
+#[allow(clippy::result_large_err)]
+pub fn sign_with_multiple_v2_with_fork_id(mut mutable_tx: SignableTransaction, privkeys: &[[u8; 32]], fork_id: Option<u8>) -> Signed {
+    let signer = KeypairSigner::try_from_private_keys(privkeys).unwrap();
+    let (requests, additional_signatures_required) = signature_requests(&mutable_tx, &signer.public_keys(), fork_id);
+    let signatures = signer.sign_sync(&requests).unwrap();
+    apply_signatures(&mut mutable_tx, &requests, &signatures).unwrap();
     if additional_signatures_required {
         Signed::Partially(mutable_tx)
     } else {
@@ -155,9 +296,20 @@ pub fn sign_with_multiple_v2(mut mutable_tx: SignableTransaction, privkeys: &[[u
 
 /// Sign a transaction input with a sighash_type using schnorr
 pub fn sign_input(tx: &impl VerifiableTransaction, input_index: usize, private_key: &[u8; 32], hash_type: SigHashType) -> Vec<u8> {
+    sign_input_with_fork_id(tx, input_index, private_key, hash_type, None)
+}
+
+/// Sign a transaction input with a sighash_type using schnorr for a network with the given sighash fork id
+pub fn sign_input_with_fork_id(
+    tx: &impl VerifiableTransaction,
+    input_index: usize,
+    private_key: &[u8; 32],
+    hash_type: SigHashType,
+    fork_id: Option<u8>,
+) -> Vec<u8> {
     let reused_values = SigHashReusedValuesUnsync::new();
 
-    let hash = calc_schnorr_signature_hash(tx, input_index, hash_type, &reused_values);
+    let hash = calc_schnorr_signature_hash_with_fork_id(tx, input_index, hash_type, &reused_values, fork_id);
     let msg = secp256k1::Message::from_digest_slice(hash.as_bytes().as_slice()).unwrap();
     let schnorr_key = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, private_key).unwrap();
     let sig: [u8; 64] = *schnorr_key.sign_schnorr(msg).as_ref();
@@ -167,6 +319,11 @@ pub fn sign_input(tx: &impl VerifiableTransaction, input_index: usize, private_k
 }
 
 pub fn verify(tx: &impl VerifiableTransaction) -> Result<(), Error> {
+    verify_with_fork_id(tx, None)
+}
+
+/// Verify the P2PK input signatures of a transaction signed for a network with the given sighash fork id
+pub fn verify_with_fork_id(tx: &impl VerifiableTransaction, fork_id: Option<u8>) -> Result<(), Error> {
     let reused_values = SigHashReusedValuesUnsync::new();
     for (i, (input, entry)) in tx.populated_inputs().enumerate() {
         if input.signature_script.is_empty() {
@@ -175,7 +332,7 @@ pub fn verify(tx: &impl VerifiableTransaction) -> Result<(), Error> {
         let pk = &entry.script_public_key.script()[1..33];
         let pk = secp256k1::XOnlyPublicKey::from_slice(pk)?;
         let sig = secp256k1::schnorr::Signature::from_slice(&input.signature_script[1..65])?;
-        let sig_hash = calc_schnorr_signature_hash(tx, i, SIG_HASH_ALL, &reused_values);
+        let sig_hash = calc_schnorr_signature_hash_with_fork_id(tx, i, SIG_HASH_ALL, &reused_values, fork_id);
         let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice())?;
         sig.verify(&msg, &pk)?;
     }
@@ -259,4 +416,86 @@ mod tests {
 
         assert!(verify(&signed_tx.as_verifiable()).is_ok());
     }
+
+    /// Mimics an external device: holds the keys out of reach of the caller and only answers signature requests.
+    struct DeviceSigner(KeypairSigner);
+
+    #[async_trait]
+    impl Signer for DeviceSigner {
+        fn public_keys(&self) -> Vec<secp256k1::PublicKey> {
+            self.0.public_keys()
+        }
+
+        async fn sign(&self, requests: Vec<SignatureRequest>) -> Result<Vec<secp256k1::schnorr::Signature>, Error> {
+            self.0.sign_sync(&requests)
+        }
+    }
+
+    #[test]
+    fn test_sign_with_signer() {
+        use futures_util::FutureExt;
+
+        let secp = Secp256k1::new();
+        let (secret_key, public_key) = secp.generate_keypair(&mut rand::thread_rng());
+        let (secret_key2, public_key2) = secp.generate_keypair(&mut rand::thread_rng());
+        let p2pk = |public_key: secp256k1::PublicKey| {
+            let script = once(0x20).chain(public_key.x_only_public_key().0.serialize()).chain(once(0xac)).collect_vec();
+            ScriptPublicKey::new(0, ScriptVec::from_slice(&script))
+        };
+
+        let prev_tx_id = TransactionId::from_str("880eb9819a31821d9d2399e2f35e2433b72637e393d71ecc9b8d0250f49153c3").unwrap();
+        let inputs = (0..3)
+            .map(|index| TransactionInput {
+                previous_outpoint: TransactionOutpoint { transaction_id: prev_tx_id, index },
+                signature_script: vec![],
+                sequence: 0,
+                sig_op_count: 1,
+            })
+            .collect();
+        let outputs = vec![TransactionOutput { value: 300, script_public_key: p2pk(public_key) }];
+        let unsigned_tx = Transaction::new(0, inputs, outputs, 0, SubnetworkId::default(), 0, vec![]);
+        let entries = [public_key, public_key2, public_key]
+            .into_iter()
+            .map(|key| UtxoEntry { amount: 100, script_public_key: p2pk(key), block_daa_score: 0, is_coinbase: false })
+            .collect();
+        let signable_tx = SignableTransaction::with_entries(unsigned_tx, entries);
+
+        let first = DeviceSigner(KeypairSigner::try_from_private_keys(&[secret_key.secret_bytes()]).unwrap());
+        let partially_signed =
+            sign_with_signer(signable_tx, &first, None).now_or_never().unwrap().unwrap().partially_signed().unwrap();
+        assert!(!partially_signed.tx.inputs[0].signature_script.is_empty());
+        assert!(partially_signed.tx.inputs[1].signature_script.is_empty());
+        assert!(!partially_signed.tx.inputs[2].signature_script.is_empty());
+
+        let second = DeviceSigner(KeypairSigner::try_from_private_keys(&[secret_key2.secret_bytes()]).unwrap());
+        // The second signer only covers input 1, so from its view the transaction remains partially signed
+        let signed = sign_with_signer(partially_signed, &second, None).now_or_never().unwrap().unwrap().partially_signed().unwrap();
+        assert!(verify(&signed.as_verifiable()).is_ok());
+    }
+
+    #[test]
+    fn test_sign_with_fork_id() {
+        let secp = Secp256k1::new();
+        let (secret_key, public_key) = secp.generate_keypair(&mut rand::thread_rng());
+        let script = once(0x20).chain(public_key.x_only_public_key().0.serialize()).chain(once(0xac)).collect_vec();
+        let script_public_key = ScriptPublicKey::new(0, ScriptVec::from_slice(&script));
+
+        let prev_tx_id = TransactionId::from_str("880eb9819a31821d9d2399e2f35e2433b72637e393d71ecc9b8d0250f49153c3").unwrap();
+        let input = TransactionInput {
+            previous_outpoint: TransactionOutpoint { transaction_id: prev_tx_id, index: 0 },
+            signature_script: vec![],
+            sequence: 0,
+            sig_op_count: 1,
+        };
+        let output = TransactionOutput { value: 100, script_public_key: script_public_key.clone() };
+        let unsigned_tx = Transaction::new(0, vec![input], vec![output], 0, SubnetworkId::default(), 0, vec![]);
+        let entry = UtxoEntry { amount: 200, script_public_key, block_daa_score: 0, is_coinbase: false };
+        let signable_tx = SignableTransaction::with_entries(unsigned_tx, vec![entry]);
+
+        let signed = sign_with_multiple_v2_with_fork_id(signable_tx, &[secret_key.secret_bytes()], Some(7)).fully_signed().unwrap();
+        assert!(verify_with_fork_id(&signed.as_verifiable(), Some(7)).is_ok());
+        // The signature does not replay on the parent network nor on a network with a different fork id
+        assert!(verify(&signed.as_verifiable()).is_err());
+        assert!(verify_with_fork_id(&signed.as_verifiable(), Some(8)).is_err());
+    }
 }
@@ -0,0 +1,234 @@
+//!
+//! File format of UTXO set snapshots, allowing a fresh node to bootstrap from a file exported
+//! by a synced node instead of downloading the pruning point UTXO set from peers.
+//!
+//! A snapshot consists of a fixed prefix (magic and version), followed by the borsh-encoded
+//! [`SnapshotMetadata`] and by the pruning point UTXO set encoded as a sequence of non-empty chunks.
+//! The sequence is terminated by an empty chunk followed by the overall number of UTXO entries.
+//!
+//! The proof is validated by the importing node as if received from a peer, and the UTXO set is verified
+//! against the UTXO commitment of the pruning point header. Note however that the blue work claimed for the
+//! proof is taken as is from the trusted block headers of the file and is not recomputed.
+//!
+
+use crate::{
+    block::Block,
+    errors::snapshot::{SnapshotError, SnapshotResult},
+    header::Header,
+    pruning::{PruningPointProof, PruningPointsList},
+    trusted::{ExternalGhostdagData, TrustedBlock},
+    tx::{Transaction, TransactionOutpoint, UtxoEntry},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_hashes::Hash;
+use std::{
+    io::{Read, Write},
+    sync::Arc,
+};
+
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"KASUTXOS";
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// A block of the pruning point anticone (or of its recent past) along with its GHOSTDAG data
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SnapshotTrustedBlock {
+    pub header: Arc<Header>,
+    pub transactions: Arc<Vec<Transaction>>,
+    pub ghostdag: ExternalGhostdagData,
+}
+
+impl From<TrustedBlock> for SnapshotTrustedBlock {
+    fn from(tb: TrustedBlock) -> Self {
+        Self { header: tb.block.header, transactions: tb.block.transactions, ghostdag: tb.ghostdag }
+    }
+}
+
+impl From<SnapshotTrustedBlock> for TrustedBlock {
+    fn from(tb: SnapshotTrustedBlock) -> Self {
+        TrustedBlock::new(Block::from_arcs(tb.header, tb.transactions), tb.ghostdag)
+    }
+}
+
+/// Everything a node requires in order to bootstrap from the snapshot pruning point, except for the UTXO set itself
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SnapshotMetadata {
+    /// The network id (in its string representation) of the exporting node
+    pub network: String,
+    pub pruning_point: Hash,
+    pub pruning_point_proof: PruningPointProof,
+    /// All past pruning points, from genesis up to (and including) the snapshot pruning point
+    pub pruning_points: PruningPointsList,
+    /// The trusted sub-DAG in the anticone and in the recent past of the pruning point, sorted topologically
+    pub trusted_blocks: Vec<SnapshotTrustedBlock>,
+}
+
+impl SnapshotMetadata {
+    /// Verifies the structural consistency of the metadata. Consensus validity is verified by the importing node
+    fn validate_structure(&self) -> SnapshotResult<()> {
+        if self.pruning_point_proof.first().and_then(|level| level.last()).map(|header| header.hash) != Some(self.pruning_point) {
+            return Err(SnapshotError::Malformed("the proof pruning point is not the snapshot pruning point"));
+        }
+        if self.pruning_points.last().map(|header| header.hash) != Some(self.pruning_point) {
+            return Err(SnapshotError::Malformed("the last pruning point is not the snapshot pruning point"));
+        }
+        if !self.trusted_blocks.iter().any(|tb| tb.header.hash == self.pruning_point) {
+            return Err(SnapshotError::Malformed("the trusted blocks do not include the snapshot pruning point"));
+        }
+        Ok(())
+    }
+}
+
+/// Writes a snapshot in a streaming manner, so that the UTXO set need not be held in memory
+pub struct SnapshotWriter<W: Write> {
+    writer: W,
+    utxo_count: u64,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    pub fn new(mut writer: W, metadata: &SnapshotMetadata) -> SnapshotResult<Self> {
+        metadata.validate_structure()?;
+        writer.write_all(&SNAPSHOT_MAGIC)?;
+        writer.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+        metadata.serialize(&mut writer)?;
+        Ok(Self { writer, utxo_count: 0 })
+    }
+
+    pub fn write_utxo_chunk(&mut self, chunk: &[(TransactionOutpoint, UtxoEntry)]) -> SnapshotResult<()> {
+        // An empty chunk marks the end of the UTXO set
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        chunk.serialize(&mut self.writer)?;
+        self.utxo_count += chunk.len() as u64;
+        Ok(())
+    }
+
+    /// Writes the UTXO set trailer and returns the underlying writer (flushed)
+    pub fn finish(mut self) -> SnapshotResult<W> {
+        Vec::<(TransactionOutpoint, UtxoEntry)>::new().serialize(&mut self.writer)?;
+        self.utxo_count.serialize(&mut self.writer)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    pub fn utxo_count(&self) -> u64 {
+        self.utxo_count
+    }
+}
+
+/// Reads a snapshot written by [`SnapshotWriter`]. The UTXO set is read chunk by chunk
+pub struct SnapshotReader<R: Read> {
+    reader: R,
+    utxo_count: u64,
+    done: bool,
+}
+
+impl<R: Read> SnapshotReader<R> {
+    /// Reads the snapshot prefix and metadata, leaving the reader positioned at the beginning of the UTXO set
+    pub fn new(mut reader: R) -> SnapshotResult<(Self, SnapshotMetadata)> {
+        let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidMagic);
+        }
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version, SNAPSHOT_VERSION));
+        }
+        let metadata = SnapshotMetadata::deserialize_reader(&mut reader)?;
+        metadata.validate_structure()?;
+        Ok((Self { reader, utxo_count: 0, done: false }, metadata))
+    }
+
+    /// Returns the next chunk of the UTXO set or `None` once the whole set was read (and its size verified)
+    pub fn next_utxo_chunk(&mut self) -> SnapshotResult<Option<Vec<(TransactionOutpoint, UtxoEntry)>>> {
+        if self.done {
+            return Ok(None);
+        }
+        let chunk = Vec::<(TransactionOutpoint, UtxoEntry)>::deserialize_reader(&mut self.reader)?;
+        if chunk.is_empty() {
+            self.done = true;
+            let declared = u64::deserialize_reader(&mut self.reader)?;
+            if declared != self.utxo_count {
+                return Err(SnapshotError::UtxoCountMismatch(self.utxo_count, declared));
+            }
+            return Ok(None);
+        }
+        self.utxo_count += chunk.len() as u64;
+        Ok(Some(chunk))
+    }
+
+    /// The number of UTXO entries read so far
+    pub fn utxo_count(&self) -> u64 {
+        self.utxo_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tx::ScriptPublicKey, BlockHashMap};
+
+    fn metadata() -> SnapshotMetadata {
+        let pruning_point = Arc::new(Header::from_precomputed_hash(7.into(), vec![1.into()]));
+        let genesis = Arc::new(Header::from_precomputed_hash(1.into(), vec![]));
+        let ghostdag = ExternalGhostdagData {
+            blue_score: 1,
+            blue_work: 2.into(),
+            selected_parent: 1.into(),
+            mergeset_blues: vec![1.into()],
+            mergeset_reds: vec![],
+            blues_anticone_sizes: BlockHashMap::from_iter([(1.into(), 0)]),
+        };
+        SnapshotMetadata {
+            network: "testnet-11".to_string(),
+            pruning_point: pruning_point.hash,
+            pruning_point_proof: vec![vec![genesis.clone(), pruning_point.clone()]],
+            pruning_points: vec![genesis, pruning_point.clone()],
+            trusted_blocks: vec![SnapshotTrustedBlock { header: pruning_point, transactions: Default::default(), ghostdag }],
+        }
+    }
+
+    fn utxo(index: u32) -> (TransactionOutpoint, UtxoEntry) {
+        (
+            TransactionOutpoint::new(3.into(), index),
+            UtxoEntry::new(index as u64 * 100, ScriptPublicKey::from_vec(0, vec![1, 2]), 5, false),
+        )
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let chunks = [(0..3).map(utxo).collect::<Vec<_>>(), vec![], (3..5).map(utxo).collect()];
+        let mut writer = SnapshotWriter::new(Vec::new(), &metadata()).unwrap();
+        for chunk in chunks.iter() {
+            writer.write_utxo_chunk(chunk).unwrap();
+        }
+        assert_eq!(writer.utxo_count(), 5);
+        let bytes = writer.finish().unwrap();
+
+        let (mut reader, read_metadata) = SnapshotReader::new(bytes.as_slice()).unwrap();
+        assert_eq!(read_metadata.network, "testnet-11");
+        assert_eq!(read_metadata.pruning_point, 7.into());
+        assert_eq!(read_metadata.trusted_blocks[0].ghostdag.blues_anticone_sizes.get(&1.into()), Some(&0));
+        let mut utxos = Vec::new();
+        while let Some(chunk) = reader.next_utxo_chunk().unwrap() {
+            utxos.extend(chunk);
+        }
+        assert_eq!(utxos, (0..5).map(utxo).collect::<Vec<_>>());
+        assert!(reader.next_utxo_chunk().unwrap().is_none());
+
+        // A truncated file fails rather than silently importing a partial UTXO set
+        let (mut reader, _) = SnapshotReader::new(&bytes[..bytes.len() - 12]).unwrap();
+        assert!(std::iter::from_fn(|| reader.next_utxo_chunk().transpose()).any(|res| res.is_err()));
+
+        let mut corrupted = bytes.clone();
+        corrupted[0] ^= 0xff;
+        assert!(matches!(SnapshotReader::new(corrupted.as_slice()), Err(SnapshotError::InvalidMagic)));
+
+        let mut inconsistent = metadata();
+        inconsistent.pruning_points.pop();
+        assert!(matches!(SnapshotWriter::new(Vec::new(), &inconsistent), Err(SnapshotError::Malformed(_))));
+    }
+}
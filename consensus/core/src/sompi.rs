@@ -0,0 +1,144 @@
+//!
+//! A typed monetary amount denominated in sompi, the smallest unit of KAS.
+//!
+
+use crate::constants::{MAX_SOMPI, SOMPI_PER_KASPA};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An amount of sompi. Arithmetic is only exposed through checked operations so that
+/// overflows surface as `None` rather than silently wrapping.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct Sompi(pub u64);
+
+impl Sompi {
+    pub const ZERO: Sompi = Sompi(0);
+
+    /// The maximum amount allowed by consensus, see [`MAX_SOMPI`]
+    pub const MAX: Sompi = Sompi(MAX_SOMPI);
+
+    pub const fn new(sompi: u64) -> Self {
+        Self(sompi)
+    }
+
+    /// Converts a whole number of KAS to sompi, returning `None` on overflow
+    pub const fn from_kaspa(kaspa: u64) -> Option<Self> {
+        match kaspa.checked_mul(SOMPI_PER_KASPA) {
+            Some(sompi) => Some(Self(sompi)),
+            None => None,
+        }
+    }
+
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns whether the amount does not exceed [`Sompi::MAX`]
+    pub const fn is_valid(self) -> bool {
+        self.0 <= MAX_SOMPI
+    }
+
+    pub fn checked_add(self, rhs: impl Into<Sompi>) -> Option<Self> {
+        self.0.checked_add(rhs.into().0).map(Self)
+    }
+
+    pub fn checked_sub(self, rhs: impl Into<Sompi>) -> Option<Self> {
+        self.0.checked_sub(rhs.into().0).map(Self)
+    }
+
+    pub fn checked_mul(self, rhs: u64) -> Option<Self> {
+        self.0.checked_mul(rhs).map(Self)
+    }
+
+    /// Sums the given amounts, returning `None` if the total overflows
+    pub fn checked_sum<I: IntoIterator<Item = T>, T: Into<Sompi>>(iter: I) -> Option<Self> {
+        iter.into_iter().try_fold(Self::ZERO, |total, amount| total.checked_add(amount))
+    }
+}
+
+impl From<u64> for Sompi {
+    fn from(sompi: u64) -> Self {
+        Self(sompi)
+    }
+}
+
+impl From<Sompi> for u64 {
+    fn from(sompi: Sompi) -> Self {
+        sompi.0
+    }
+}
+
+impl PartialEq<u64> for Sompi {
+    fn eq(&self, other: &u64) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialOrd<u64> for Sompi {
+    fn partial_cmp(&self, other: &u64) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
+/// Formats the amount in KAS with up to 8 decimal digits, omitting trailing zeros, e.g. `1.5 KAS`
+impl fmt::Display for Sompi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kaspa = self.0 / SOMPI_PER_KASPA;
+        let fraction = self.0 % SOMPI_PER_KASPA;
+        if fraction == 0 {
+            write!(f, "{kaspa} KAS")
+        } else {
+            let fraction = format!("{fraction:08}");
+            write!(f, "{kaspa}.{} KAS", fraction.trim_end_matches('0'))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sompi_checked_arithmetic() {
+        let a = Sompi(5);
+        assert_eq!(a.checked_add(7u64), Some(Sompi(12)));
+        assert_eq!(a.checked_sub(Sompi(5)), Some(Sompi::ZERO));
+        assert_eq!(a.checked_sub(6u64), None);
+        assert_eq!(a.checked_mul(3), Some(Sompi(15)));
+        assert_eq!(Sompi(u64::MAX).checked_add(1u64), None);
+        assert_eq!(Sompi(u64::MAX / 2 + 1).checked_mul(2), None);
+        assert_eq!(Sompi::checked_sum([1u64, 2, 3]), Some(Sompi(6)));
+        assert_eq!(Sompi::checked_sum([u64::MAX, 1]), None);
+        assert_eq!(Sompi::from_kaspa(2), Some(Sompi(2 * SOMPI_PER_KASPA)));
+        assert_eq!(Sompi::from_kaspa(u64::MAX), None);
+        assert!(Sompi::MAX.is_valid() && !Sompi(MAX_SOMPI + 1).is_valid());
+    }
+
+    #[test]
+    fn test_sompi_display() {
+        assert_eq!(Sompi::ZERO.to_string(), "0 KAS");
+        assert_eq!(Sompi(SOMPI_PER_KASPA).to_string(), "1 KAS");
+        assert_eq!(Sompi(150_000_000).to_string(), "1.5 KAS");
+        assert_eq!(Sompi(1).to_string(), "0.00000001 KAS");
+        assert_eq!(Sompi(12_345_678_900).to_string(), "123.456789 KAS");
+    }
+
+    #[test]
+    fn test_sompi_serde() {
+        let json = serde_json::to_string(&Sompi(42)).unwrap();
+        assert_eq!(json, "42");
+        assert_eq!(serde_json::from_str::<Sompi>(&json).unwrap(), Sompi(42));
+        let bytes = borsh::to_vec(&Sompi(42)).unwrap();
+        assert_eq!(bytes, borsh::to_vec(&42u64).unwrap());
+        assert_eq!(Sompi::try_from_slice(&bytes).unwrap(), Sompi(42));
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{block::Block, header::Header, BlockHashMap, BlueWorkType, KType};
+use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_hashes::Hash;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Represents semi-trusted externally provided Ghostdag data (by a network peer)
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct ExternalGhostdagData {
     pub blue_score: u64,
     pub blue_work: BlueWorkType,
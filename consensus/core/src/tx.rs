@@ -9,6 +9,7 @@
 mod script_public_key;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_hashes::Hash;
 use kaspa_utils::hex::ToHex;
 use kaspa_utils::mem_size::MemSizeEstimator;
 use kaspa_utils::{serde_bytes, serde_bytes_fixed_ref};
@@ -19,6 +20,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering::SeqCst;
+use std::sync::OnceLock;
 use std::{
     fmt::Display,
     ops::Range,
@@ -161,6 +163,23 @@ impl BorshSerialize for TransactionMass {
     }
 }
 
+/// Lazily computed transaction hashes, see [`Transaction::hash`]. Derived data only, hence ignored by equality
+#[derive(Debug, Default, Clone)]
+struct TransactionHashes {
+    /// The hash without the mass field, which is also the hash of a transaction with zero mass
+    without_mass: OnceLock<Hash>,
+    /// The hash committing to the mass field along with the (nonzero) mass it was computed for
+    with_mass: OnceLock<(u64, Hash)>,
+}
+
+impl Eq for TransactionHashes {}
+
+impl PartialEq for TransactionHashes {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
 /// Represents a Kaspa transaction
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
@@ -181,6 +200,11 @@ pub struct Transaction {
     // Always use the corresponding self.id() instead of accessing this field directly
     #[serde(with = "serde_bytes_fixed_ref")]
     id: TransactionId,
+
+    // Caches the transaction hashes, cleared along with recomputing the ID. Always use self.hash() instead
+    #[serde(skip)]
+    #[borsh(skip)]
+    hashes: TransactionHashes,
 }
 
 impl Transaction {
@@ -207,7 +231,18 @@ impl Transaction {
         gas: u64,
         payload: Vec<u8>,
     ) -> Self {
-        Self { version, inputs, outputs, lock_time, subnetwork_id, gas, payload, mass: Default::default(), id: Default::default() }
+        Self {
+            version,
+            inputs,
+            outputs,
+            lock_time,
+            subnetwork_id,
+            gas,
+            payload,
+            mass: Default::default(),
+            id: Default::default(),
+            hashes: Default::default(),
+        }
     }
 }
 
@@ -220,9 +255,11 @@ impl Transaction {
         self.subnetwork_id == subnets::SUBNETWORK_ID_COINBASE
     }
 
-    /// Recompute and finalize the tx id based on updated tx fields
+    /// Recompute and finalize the tx id based on updated tx fields. Also drops the cached tx hashes, so this must be
+    /// called following any mutation of the public tx fields (see [`Self::set_signature_script`] for signing)
     pub fn finalize(&mut self) {
         self.id = hashing::tx::id(self);
+        self.hashes = Default::default();
     }
 
     /// Returns the transaction ID
@@ -230,6 +267,28 @@ impl Transaction {
         self.id
     }
 
+    /// Returns the transaction hash, computing it on first use. Note that this is different than the transaction ID.
+    /// See [`hashing::tx::hash`] for `include_mass_field`.
+    ///
+    /// The cached hash for one nonzero mass is kept, so hashing again after a mass change is correct but uncached
+    pub fn hash(&self, include_mass_field: bool) -> Hash {
+        let mass = if include_mass_field { self.mass() } else { 0 };
+        if mass == 0 {
+            return *self.hashes.without_mass.get_or_init(|| hashing::tx::hash_with_mass(self, 0));
+        }
+        match *self.hashes.with_mass.get_or_init(|| (mass, hashing::tx::hash_with_mass(self, mass))) {
+            (cached_mass, hash) if cached_mass == mass => hash,
+            _ => hashing::tx::hash_with_mass(self, mass),
+        }
+    }
+
+    /// Sets the signature script of the input at `input_index`, as done when signing. Drops the cached tx hashes
+    /// which commit to signature scripts, whereas the tx ID (which does not) remains valid
+    pub fn set_signature_script(&mut self, input_index: usize, signature_script: Vec<u8>) {
+        self.inputs[input_index].signature_script = signature_script;
+        self.hashes = Default::default();
+    }
+
     /// Set the mass field of this transaction. The mass field is expected depending on hard-forks which are currently
     /// activated only on some testnets. The field has no effect on tx ID so no need to finalize following this call.
     pub fn set_mass(&self, mass: u64) {
@@ -705,6 +764,35 @@ mod tests {
         assert_eq!(spk, spk2);
     }
 
+    #[test]
+    fn test_transaction_hash_cache() {
+        let mut tx = test_transaction();
+        let uncached = |tx: &Transaction| [hashing::tx::hash(tx, false), hashing::tx::hash(tx, true)];
+        let cached = |tx: &Transaction| [tx.hash(false), tx.hash(true)];
+        assert_eq!(cached(&tx), uncached(&tx));
+
+        // A mass change is committed to even though the hash with the previous mass is cached
+        tx.set_mass(1000);
+        assert_ne!(tx.hash(true), tx.hash(false));
+        tx.set_mass(2000);
+        assert_eq!(cached(&tx), uncached(&tx));
+
+        // Signing and finalizing following a mutation drop the cached hashes
+        let hashes = cached(&tx);
+        tx.set_signature_script(0, vec![1, 2, 3]);
+        assert_eq!(cached(&tx), uncached(&tx));
+        assert_ne!(cached(&tx), hashes);
+        tx.payload.clear();
+        tx.finalize();
+        assert_eq!(cached(&tx), uncached(&tx));
+
+        // The cache is not serialized and does not affect equality
+        let bin = borsh::to_vec(&tx).unwrap();
+        let tx2: Transaction = BorshDeserialize::try_from_slice(&bin).unwrap();
+        assert_eq!(tx, tx2);
+        assert_eq!(cached(&tx2), uncached(&tx));
+    }
+
     // use wasm_bindgen_test::wasm_bindgen_test;
     // #[wasm_bindgen_test]
     // pub fn test_wasm_serde_spk_constructor() {
@@ -95,23 +95,22 @@ impl UtxoCollectionExtensions for UtxoCollection {
 // Functions for UTXO diff algebra with daa score dimension considerations
 //
 
-/// Calculates the intersection and subtraction between two utxo collections.
-/// The function returns with the following outcome:
+/// Cancels out the intersection of two utxo collections while collecting the remainder, without
+/// materializing the intersection. The function returns with the following outcome:
 ///
-/// `result    = result ∪ (this ∩ other)`
+/// `other     = other ∖ this`
 ///
 /// `remainder = remainder ∪ (this ∖ other)`
 ///
 /// where the set operators demand equality also on the DAA score dimension
-pub(super) fn intersection_with_remainder_having_daa_score_in_place(
+pub(super) fn cancellation_with_remainder_having_daa_score_in_place(
     this: &UtxoCollection,
-    other: &UtxoCollection,
-    result: &mut UtxoCollection,
+    other: &mut UtxoCollection,
     remainder: &mut UtxoCollection,
 ) {
     for (outpoint, entry) in this.iter() {
         if other.contains_with_daa_score(outpoint, entry.block_daa_score) {
-            result.insert(*outpoint, entry.clone());
+            other.remove(outpoint);
         } else {
             remainder.insert(*outpoint, entry.clone());
         }
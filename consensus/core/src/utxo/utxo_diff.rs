@@ -2,11 +2,17 @@ use super::{
     utxo_collection::*,
     utxo_error::{UtxoAlgebraError, UtxoResult},
 };
-use crate::tx::{TransactionOutpoint, UtxoEntry, VerifiableTransaction};
+use crate::tx::{TransactionOutpoint, UtxoEntry, VerifiableTransaction, SCRIPT_VECTOR_SIZE};
 use kaspa_utils::mem_size::MemSizeEstimator;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Entry::Vacant;
 
+/// Collections with a smaller capacity are never shrunk, since reallocating them costs more than the memory it saves
+const MIN_SHRINK_CAPACITY: usize = 4096;
+
+/// A collection is considered sparse if its capacity exceeds its length by this factor
+const SPARSE_CAPACITY_FACTOR: usize = 4;
+
 pub trait ImmutableUtxoDiff {
     fn added(&self) -> &UtxoCollection;
     fn removed(&self) -> &UtxoCollection;
@@ -20,7 +26,14 @@ pub struct UtxoDiff {
 
 impl MemSizeEstimator for UtxoDiff {
     fn estimate_mem_bytes(&self) -> usize {
-        size_of::<Self>() + (self.add.len() + self.remove.len()) * (size_of::<TransactionOutpoint>() + size_of::<UtxoEntry>())
+        // Scripts exceeding the inline capacity of the script vector are allocated on the heap
+        let scripts_heap_bytes = |collection: &UtxoCollection| -> usize {
+            collection.values().map(|entry| entry.script_public_key.script().len()).filter(|&len| len > SCRIPT_VECTOR_SIZE).sum()
+        };
+        size_of::<Self>()
+            + (self.add.len() + self.remove.len()) * (size_of::<TransactionOutpoint>() + size_of::<UtxoEntry>())
+            + scripts_heap_bytes(&self.add)
+            + scripts_heap_bytes(&self.remove)
     }
 }
 
@@ -68,6 +81,20 @@ impl UtxoDiff {
         Self { add, remove }
     }
 
+    pub fn with_capacity(add_capacity: usize, remove_capacity: usize) -> Self {
+        Self::new(UtxoCollection::with_capacity(add_capacity), UtxoCollection::with_capacity(remove_capacity))
+    }
+
+    /// Releases the unused capacity of the collections if it is large and mostly vacant, as is the case after
+    /// composing many diffs which cancel each other out
+    pub fn shrink_if_sparse(&mut self) {
+        for collection in [&mut self.add, &mut self.remove] {
+            if collection.capacity() > MIN_SHRINK_CAPACITY && collection.capacity() > SPARSE_CAPACITY_FACTOR * collection.len() {
+                collection.shrink_to_fit();
+            }
+        }
+    }
+
     pub fn as_reversed(&self) -> impl ImmutableUtxoDiff + '_ {
         ReversedUtxoDiff::new(self)
     }
@@ -77,9 +104,12 @@ impl UtxoDiff {
     }
 
     pub fn with_diff(&self, other: &impl ImmutableUtxoDiff) -> UtxoResult<UtxoDiff> {
-        let mut clone = self.clone();
-        clone.with_diff_in_place(other)?;
-        Ok(clone)
+        // Pre-size the result for both diffs rather than growing a clone of this diff while composing
+        let mut result = Self::with_capacity(self.add.len() + other.added().len(), self.remove.len() + other.removed().len());
+        result.add.add_collection(&self.add);
+        result.remove.add_collection(&self.remove);
+        result.with_diff_in_place(other)?;
+        Ok(result)
     }
 
     /// Applies the provided diff to this diff in-place. This is equal to if the
@@ -100,19 +130,15 @@ impl UtxoDiff {
             return Err(UtxoAlgebraError::DuplicateAddPoint(offending_outpoint));
         }
 
-        let mut intersection = UtxoCollection::new();
+        // Reserve for the case where nothing cancels out, so that composing large diffs does not rehash repeatedly
+        self.remove.reserve(other.removed().len());
+        self.add.reserve(other.added().len());
 
-        // If does not exist neither in `add` nor in `remove` - add to `remove`
-        intersection_with_remainder_having_daa_score_in_place(other.removed(), &self.add, &mut intersection, &mut self.remove);
-        // If already exists in `add` with the same DAA score - remove from `add`
-        self.add.remove_collection(&intersection);
+        // If already exists in `add` with the same DAA score - remove from `add`, otherwise add to `remove`
+        cancellation_with_remainder_having_daa_score_in_place(other.removed(), &mut self.add, &mut self.remove);
 
-        intersection.clear();
-
-        // If does not exist neither in `add` nor in `remove`, or exists in `remove' with different DAA score - add to 'add'
-        intersection_with_remainder_having_daa_score_in_place(other.added(), &self.remove, &mut intersection, &mut self.add);
-        // If already exists in `remove` with the same DAA score - remove from `remove`
-        self.remove.remove_collection(&intersection);
+        // If already exists in `remove` with the same DAA score - remove from `remove`, otherwise add to `add`
+        cancellation_with_remainder_having_daa_score_in_place(other.added(), &mut self.remove, &mut self.add);
 
         Ok(())
     }
@@ -567,4 +593,46 @@ mod tests {
         drop(utxo_entry1);
         drop(utxo_entry2);
     }
+
+    #[test]
+    fn test_utxo_diff_mem_bytes() {
+        let outpoint = |index| TransactionOutpoint::new(TransactionId::from_u64_word(1), index);
+        let entry = |script_len: usize| UtxoEntry::new(10, ScriptPublicKey::from_vec(0, vec![0; script_len]), 0, false);
+        let base = size_of::<UtxoDiff>() + size_of::<TransactionOutpoint>() + size_of::<UtxoEntry>();
+
+        let mut diff = UtxoDiff::default();
+        diff.add.insert(outpoint(0), entry(SCRIPT_VECTOR_SIZE));
+        assert_eq!(diff.estimate_mem_bytes(), base);
+
+        diff.remove.insert(outpoint(1), entry(100));
+        assert_eq!(diff.estimate_mem_bytes(), base + size_of::<TransactionOutpoint>() + size_of::<UtxoEntry>() + 100);
+    }
+
+    #[test]
+    fn test_large_diff_composition() {
+        let outpoint = |index| TransactionOutpoint::new(TransactionId::from_u64_word(index), 0);
+        let entry = |daa_score| UtxoEntry::new(10, ScriptPublicKey::default(), daa_score, false);
+        let diff =
+            |range: std::ops::Range<u64>, daa_score| -> UtxoCollection { range.map(|i| (outpoint(i), entry(daa_score))).collect() };
+
+        // Creates outputs 0..10000 and spends outputs 20000..30000 of the base
+        let mut composed = UtxoDiff::new(diff(0..10_000, 1), diff(20_000..30_000, 0));
+        // Spends most outputs created by the first diff and creates a new output
+        let second = UtxoDiff::new(diff(40_000..40_001, 2), diff(0..9_990, 1));
+        assert_eq!(composed.with_diff(&second).unwrap(), {
+            composed.with_diff_in_place(&second).unwrap();
+            composed.clone()
+        });
+        assert_eq!(
+            composed,
+            UtxoDiff::new(diff(9_990..10_000, 1).into_iter().chain(diff(40_000..40_001, 2)).collect(), diff(20_000..30_000, 0))
+        );
+
+        // Reverting both diffs cancels out entirely, after which the vacant capacity is released
+        composed.with_diff_in_place(&UtxoDiff::new(diff(20_000..30_000, 0), composed.add.clone())).unwrap();
+        assert_eq!(composed, UtxoDiff::default());
+        assert!(composed.add.capacity() > MIN_SHRINK_CAPACITY);
+        composed.shrink_if_sparse();
+        assert!(composed.add.capacity() <= MIN_SHRINK_CAPACITY && composed.remove.capacity() <= MIN_SHRINK_CAPACITY);
+    }
 }
@@ -1,5 +1,5 @@
 use derive_more::Display;
-use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, utxo::utxo_diff::UtxoDiff};
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, tx::TransactionId, utxo::utxo_diff::UtxoDiff};
 use kaspa_hashes::Hash;
 use kaspa_notify::{
     events::EventType,
@@ -42,6 +42,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "TransactionsEvicted notification: {} transactions", "_0.transaction_ids.len()")]
+    TransactionsEvicted(TransactionsEvictedNotification),
 }
 }
 
@@ -181,3 +184,15 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 
 #[derive(Debug, Clone)]
 pub struct NewBlockTemplateNotification {}
+
+/// Transactions evicted from the mempool without being accepted, e.g., since they expired
+#[derive(Debug, Clone)]
+pub struct TransactionsEvictedNotification {
+    pub transaction_ids: Arc<Vec<TransactionId>>,
+}
+
+impl TransactionsEvictedNotification {
+    pub fn new(transaction_ids: Arc<Vec<TransactionId>>) -> Self {
+        Self { transaction_ids }
+    }
+}
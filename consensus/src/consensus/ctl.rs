@@ -13,6 +13,7 @@ pub struct Ctl {
     consensus_db_ref: Weak<DB>,
     consensus_db_path: PathBuf,
     consensus: Arc<Consensus>,
+    is_read_only: bool,
 }
 
 impl Ctl {
@@ -20,15 +21,20 @@ impl Ctl {
         management_store: Arc<RwLock<MultiConsensusManagementStore>>,
         consensus_db: Arc<DB>,
         consensus: Arc<Consensus>,
+        is_read_only: bool,
     ) -> Self {
         let consensus_db_path = consensus_db.path().to_owned();
         let consensus_db_ref = Arc::downgrade(&consensus_db);
-        Self { management_store, consensus_db_ref, consensus_db_path, consensus }
+        Self { management_store, consensus_db_ref, consensus_db_path, consensus, is_read_only }
     }
 }
 
 impl ConsensusCtl for Ctl {
     fn start(&self) -> Vec<JoinHandle<()>> {
+        // A read-only consensus is meant for queries only, so the processors writing to the DB are never spawned
+        if self.is_read_only {
+            return vec![];
+        }
         self.consensus.run_processors()
     }
 
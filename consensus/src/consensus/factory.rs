@@ -9,17 +9,27 @@ use kaspa_consensusmanager::{ConsensusFactory, ConsensusInstance, DynConsensusCt
 use kaspa_core::{debug, time::unix_now, warn};
 use kaspa_database::{
     prelude::{
-        BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DirectDbWriter, StoreError, StoreResult, StoreResultExtensions, DB,
+        BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DbAccessMode, DirectDbWriter, StoreError, StoreResult,
+        StoreResultExtensions, DB,
     },
     registry::DatabaseStorePrefixes,
 };
 
 use kaspa_txscript::caches::TxScriptCacheCounters;
-use kaspa_utils::mem_size::MemSizeEstimator;
+use kaspa_utils::{
+    mem_pressure::{Shrinkable, ShrinkableRegistry},
+    mem_size::MemSizeEstimator,
+};
 use parking_lot::RwLock;
 use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error::Error, fs, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Weak},
+};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConsensusEntry {
@@ -95,8 +105,8 @@ impl MultiConsensusManagementStore {
         if self.metadata.read().unwrap_option().is_none() {
             let mut batch = WriteBatch::default();
             let metadata = MultiConsensusMetadata::default();
-            self.metadata.write(BatchDbWriter::new(&mut batch), &metadata).unwrap();
-            self.db.write(batch).unwrap();
+            self.metadata.write(BatchDbWriter::new(&mut batch), &metadata).unwrap_or_halt();
+            self.db.write_batch(batch).unwrap_or_halt();
         }
     }
 
@@ -123,6 +133,12 @@ impl MultiConsensusManagementStore {
         }
     }
 
+    /// The active consensus entry, if one exists. Unlike [`Self::active_consensus_entry`], never writes to the DB
+    pub fn existing_active_consensus_entry(&self) -> StoreResult<Option<ConsensusEntry>> {
+        let metadata = self.metadata.read()?;
+        metadata.current_consensus_key.map(|key| self.entries.read(key.into())).transpose()
+    }
+
     // This function assumes metadata is already set
     pub fn staging_consensus_entry(&mut self) -> Option<ConsensusEntry> {
         let metadata = self.metadata.read().unwrap();
@@ -143,7 +159,7 @@ impl MultiConsensusManagementStore {
             data.current_consensus_key = Some(key);
             data
         })?;
-        self.db.write(batch)?;
+        self.db.write_batch(batch)?;
         Ok(())
     }
 
@@ -158,7 +174,7 @@ impl MultiConsensusManagementStore {
         let mut batch = WriteBatch::default();
         self.metadata.write(BatchDbWriter::new(&mut batch), &metadata)?;
         self.entries.write(BatchDbWriter::new(&mut batch), new_key.into(), new_entry.clone())?;
-        self.db.write(batch)?;
+        self.db.write_batch(batch)?;
 
         Ok(new_entry)
     }
@@ -215,7 +231,7 @@ impl MultiConsensusManagementStore {
         if metadata.is_archival_node != is_archival_node {
             metadata.is_archival_node = is_archival_node;
             let mut batch = WriteBatch::default();
-            self.metadata.write(BatchDbWriter::new(&mut batch), &metadata).unwrap();
+            self.metadata.write(BatchDbWriter::new(&mut batch), &metadata).unwrap_or_halt();
         }
     }
 
@@ -254,6 +270,9 @@ pub struct Factory {
     counters: Arc<ProcessingCounters>,
     tx_script_cache_counters: Arc<TxScriptCacheCounters>,
     fd_budget: i32,
+    db_access_mode: DbAccessMode,
+    /// Registry the caches of the created consensus instances are registered to, for shrinking them under memory pressure
+    cache_registry: Arc<ShrinkableRegistry>,
 }
 
 impl Factory {
@@ -266,6 +285,8 @@ impl Factory {
         counters: Arc<ProcessingCounters>,
         tx_script_cache_counters: Arc<TxScriptCacheCounters>,
         fd_budget: i32,
+        db_access_mode: DbAccessMode,
+        cache_registry: Arc<ShrinkableRegistry>,
     ) -> Self {
         assert!(fd_budget > 0, "fd_budget has to be positive");
         let mut config = config.clone();
@@ -273,7 +294,9 @@ impl Factory {
         set_genesis_utxo_commitment_from_config(&mut config);
         config.process_genesis = false;
         let management_store = Arc::new(RwLock::new(MultiConsensusManagementStore::new(management_db)));
-        management_store.write().set_is_archival_node(config.is_archival);
+        if !db_access_mode.is_read_only() {
+            management_store.write().set_is_archival_node(config.is_archival);
+        }
         let factory = Self {
             management_store,
             config,
@@ -283,10 +306,32 @@ impl Factory {
             counters,
             tx_script_cache_counters,
             fd_budget,
+            db_access_mode,
+            cache_registry,
         };
         factory.delete_inactive_consensus_entries();
         factory
     }
+
+    /// Opens the DB of the consensus entry found at `directory_name` using the factory access mode.
+    /// Secondary instances keep their info logs under a same-named sub directory of the secondary path
+    fn open_consensus_db(&self, directory_name: &str) -> Arc<DB> {
+        let access_mode = match &self.db_access_mode {
+            DbAccessMode::Secondary(secondary_root) => DbAccessMode::Secondary(secondary_root.join(directory_name)),
+            access_mode => access_mode.clone(),
+        };
+        kaspa_database::prelude::ConnBuilder::default()
+            .with_db_path(self.db_root_dir.join(directory_name))
+            .with_parallelism(self.db_parallelism)
+            .with_files_limit(self.fd_budget / 2) // active and staging consensuses should have equal budgets
+            .with_access_mode(access_mode)
+            .build()
+            .unwrap()
+    }
+
+    fn register_caches(&self, name: &str, consensus: &Consensus) {
+        self.cache_registry.register(name, Arc::downgrade(&consensus.storage) as Weak<dyn Shrinkable>);
+    }
 }
 
 impl ConsensusFactory for Factory {
@@ -295,26 +340,30 @@ impl ConsensusFactory for Factory {
 
         let mut config = self.config.clone();
         let mut is_new_consensus = false;
-        let entry = match self.management_store.write().active_consensus_entry().unwrap() {
-            ConsensusEntryType::Existing(entry) => {
-                config.process_genesis = false;
-                entry
-            }
-            ConsensusEntryType::New(entry) => {
-                // Configure to process genesis only if this is a brand new consensus
-                config.process_genesis = true;
-                is_new_consensus = true;
-                entry
+        let entry = if self.db_access_mode.is_read_only() {
+            // A read-only consensus can only be opened over existing data. Note that since writes are rejected by
+            // the DB, such a consensus is meant for queries only and should not be used for processing
+            self.management_store
+                .read()
+                .existing_active_consensus_entry()
+                .unwrap()
+                .expect("no active consensus to open in read-only mode")
+        } else {
+            match self.management_store.write().active_consensus_entry().unwrap() {
+                ConsensusEntryType::Existing(entry) => {
+                    config.process_genesis = false;
+                    entry
+                }
+                ConsensusEntryType::New(entry) => {
+                    // Configure to process genesis only if this is a brand new consensus
+                    config.process_genesis = true;
+                    is_new_consensus = true;
+                    entry
+                }
             }
         };
 
-        let dir = self.db_root_dir.join(entry.directory_name.clone());
-        let db = kaspa_database::prelude::ConnBuilder::default()
-            .with_db_path(dir)
-            .with_parallelism(self.db_parallelism)
-            .with_files_limit(self.fd_budget / 2) // active and staging consensuses should have equal budgets
-            .build()
-            .unwrap();
+        let db = self.open_consensus_db(&entry.directory_name);
 
         let session_lock = SessionLock::new();
         let consensus = Arc::new(Consensus::new(
@@ -332,23 +381,22 @@ impl ConsensusFactory for Factory {
         if is_new_consensus {
             #[cfg(feature = "devnet-prealloc")]
             set_initial_utxo_set(&self.config.initial_utxo_set, consensus.clone(), self.config.params.genesis.hash);
-            self.management_store.write().save_new_active_consensus(entry).unwrap();
+            self.management_store.write().save_new_active_consensus(entry).unwrap_or_halt();
         }
+        self.register_caches("consensus caches", &consensus);
 
-        (ConsensusInstance::new(session_lock, consensus.clone()), Arc::new(Ctl::new(self.management_store.clone(), db, consensus)))
+        (
+            ConsensusInstance::new(session_lock, consensus.clone()),
+            Arc::new(Ctl::new(self.management_store.clone(), db, consensus, self.db_access_mode.is_read_only())),
+        )
     }
 
     fn new_staging_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
         assert!(!self.notification_root.is_closed());
+        assert!(!self.db_access_mode.is_read_only(), "staging consensus cannot be created in read-only mode");
 
-        let entry = self.management_store.write().new_staging_consensus_entry().unwrap();
-        let dir = self.db_root_dir.join(entry.directory_name);
-        let db = kaspa_database::prelude::ConnBuilder::default()
-            .with_db_path(dir)
-            .with_parallelism(self.db_parallelism)
-            .with_files_limit(self.fd_budget / 2) // active and staging consensuses should have equal budgets
-            .build()
-            .unwrap();
+        let entry = self.management_store.write().new_staging_consensus_entry().unwrap_or_halt();
+        let db = self.open_consensus_db(&entry.directory_name);
 
         let session_lock = SessionLock::new();
         let consensus = Arc::new(Consensus::new(
@@ -360,8 +408,12 @@ impl ConsensusFactory for Factory {
             self.tx_script_cache_counters.clone(),
             entry.creation_timestamp,
         ));
+        self.register_caches("staging consensus caches", &consensus);
 
-        (ConsensusInstance::new(session_lock, consensus.clone()), Arc::new(Ctl::new(self.management_store.clone(), db, consensus)))
+        (
+            ConsensusInstance::new(session_lock, consensus.clone()),
+            Arc::new(Ctl::new(self.management_store.clone(), db, consensus, self.db_access_mode.is_read_only())),
+        )
     }
 
     fn close(&self) {
@@ -370,6 +422,10 @@ impl ConsensusFactory for Factory {
     }
 
     fn delete_inactive_consensus_entries(&self) {
+        if self.db_access_mode.is_read_only() {
+            return;
+        }
+
         // Staging entry is deleted also by archival nodes since it represents non-final data
         self.delete_staging_entry();
 
@@ -403,6 +459,10 @@ impl ConsensusFactory for Factory {
     }
 
     fn delete_staging_entry(&self) {
+        if self.db_access_mode.is_read_only() {
+            return;
+        }
+
         let mut write_guard = self.management_store.write();
         if let Some(entry) = write_guard.staging_consensus_entry() {
             let dir = self.db_root_dir.join(entry.directory_name.clone());
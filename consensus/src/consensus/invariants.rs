@@ -0,0 +1,172 @@
+//!
+//! Startup integrity checks of the consensus storage, verifying invariants which might be broken
+//! by a crash (or by a bug) in the middle of a multi-batch DB update.
+//!
+
+use crate::model::stores::{
+    ghostdag::GhostdagStoreReader, headers::HeaderStoreReader, headers_selected_tip::HeadersSelectedTipStoreReader,
+    past_pruning_points::PastPruningPointsStoreReader, pruning::PruningStoreReader, reachability::ReachabilityStoreReader,
+    relations::RelationsStoreReader,
+};
+use kaspa_consensus_core::{blockhash::ORIGIN, blockstatus::BlockStatus};
+use kaspa_database::prelude::StoreResultExtensions;
+use kaspa_hashes::Hash;
+use thiserror::Error;
+
+use super::storage::ConsensusStorage;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InvariantError {
+    #[error("the headers selected tip is missing")]
+    MissingHeadersSelectedTip,
+
+    #[error("the header of headers selected tip {0} is missing")]
+    MissingHeadersSelectedTipHeader(Hash),
+
+    #[error("the pruning point info is missing")]
+    MissingPruningPoint,
+
+    #[error("past pruning point #{0} is missing (current pruning point index is {1})")]
+    MissingPastPruningPoint(u64, u64),
+
+    #[error("the header of past pruning point #{0} ({1}) is missing")]
+    MissingPastPruningPointHeader(u64, Hash),
+
+    #[error("past pruning point #0 is {0} rather than genesis {1}")]
+    FirstPastPruningPointNotGenesis(Hash, Hash),
+
+    #[error("past pruning point #{0} is {1} while the current pruning point is {2}")]
+    LastPastPruningPointMismatch(u64, Hash, Hash),
+
+    #[error("past pruning point #{0} ({1}) points at {2} which is not an earlier past pruning point")]
+    DiscontinuousPastPruningPoints(u64, Hash, Hash),
+
+    #[error("failed reading the block statuses: {0}")]
+    StatusIteration(String),
+
+    #[error("the header of block {0} with status {1:?} is missing")]
+    MissingHeader(Hash, BlockStatus),
+
+    #[error("the GHOSTDAG data of block {0} with status {1:?} is missing")]
+    MissingGhostdagData(Hash, BlockStatus),
+
+    #[error("the relations of block {0} with status {1:?} are missing")]
+    MissingRelations(Hash, BlockStatus),
+
+    #[error("the reachability data of block {0} with status {1:?} is missing")]
+    MissingReachabilityData(Hash, BlockStatus),
+}
+
+pub type InvariantResult<T> = std::result::Result<T, InvariantError>;
+
+/// Verifies the headers selected tip, the past pruning points chain and the DAG data coverage of all
+/// stored block statuses. Returns the first invariant found to be violated
+pub fn check_storage_invariants(storage: &ConsensusStorage, genesis: Hash) -> InvariantResult<()> {
+    check_headers_selected_tip(storage)?;
+    check_past_pruning_points(storage, genesis)?;
+    check_statuses_coverage(storage)
+}
+
+fn check_headers_selected_tip(storage: &ConsensusStorage) -> InvariantResult<()> {
+    let hst = storage.headers_selected_tip_store.read().get().unwrap_option().ok_or(InvariantError::MissingHeadersSelectedTip)?;
+    // The tip is origin only until the first header is processed (e.g., in a staging consensus)
+    if hst.hash != ORIGIN && !storage.headers_store.has(hst.hash).unwrap() {
+        return Err(InvariantError::MissingHeadersSelectedTipHeader(hst.hash));
+    }
+    Ok(())
+}
+
+fn check_past_pruning_points(storage: &ConsensusStorage, genesis: Hash) -> InvariantResult<()> {
+    let info = storage.pruning_point_store.read().get().unwrap_option().ok_or(InvariantError::MissingPruningPoint)?;
+    let mut past_pruning_points = Vec::with_capacity(info.index as usize + 1);
+    for index in 0..=info.index {
+        let pp = storage
+            .past_pruning_points_store
+            .get(index)
+            .unwrap_option()
+            .ok_or(InvariantError::MissingPastPruningPoint(index, info.index))?;
+        if index == 0 {
+            if pp != genesis {
+                return Err(InvariantError::FirstPastPruningPointNotGenesis(pp, genesis));
+            }
+            // The genesis header is not stored by a consensus which was not (yet) synced from genesis or from a proof
+            past_pruning_points.push(pp);
+            continue;
+        }
+        let header =
+            storage.headers_store.get_header(pp).unwrap_option().ok_or(InvariantError::MissingPastPruningPointHeader(index, pp))?;
+        // Each pruning point header points at the pruning point from its POV, which must be an earlier one
+        if !past_pruning_points.contains(&header.pruning_point) {
+            return Err(InvariantError::DiscontinuousPastPruningPoints(index, pp, header.pruning_point));
+        }
+        past_pruning_points.push(pp);
+    }
+    let last = *past_pruning_points.last().expect("at least genesis");
+    if last != info.pruning_point {
+        return Err(InvariantError::LastPastPruningPointMismatch(info.index, last, info.pruning_point));
+    }
+    Ok(())
+}
+
+fn check_statuses_coverage(storage: &ConsensusStorage) -> InvariantResult<()> {
+    let statuses_read = storage.statuses_store.read();
+    let relations_read = storage.relations_stores.read();
+    let reachability_read = storage.reachability_store.read();
+    for res in statuses_read.iterator() {
+        let (hash, status) = res.map_err(|err| InvariantError::StatusIteration(err.to_string()))?;
+        if status.has_block_header() && !storage.headers_store.has(hash).unwrap() {
+            return Err(InvariantError::MissingHeader(hash, status));
+        }
+        // Pruning keeps the headers of proof blocks below the pruning point without their level-0 DAG data,
+        // so full coverage is only expected for blocks with bodies
+        if !status.has_block_body() {
+            continue;
+        }
+        if !storage.ghostdag_store.has(hash).unwrap() {
+            return Err(InvariantError::MissingGhostdagData(hash, status));
+        }
+        if !relations_read[0].has(hash).unwrap() {
+            return Err(InvariantError::MissingRelations(hash, status));
+        }
+        if !reachability_read.has(hash).unwrap() {
+            return Err(InvariantError::MissingReachabilityData(hash, status));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consensus::test_consensus::TestConsensus, model::stores::ghostdag::GhostdagStore};
+    use kaspa_consensus_core::config::{params::MAINNET_PARAMS, ConfigBuilder};
+
+    #[tokio::test]
+    async fn test_storage_invariants() {
+        let config = ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().build();
+        let consensus = TestConsensus::new(&config);
+        let wait_handles = consensus.init();
+
+        let genesis = config.genesis.hash;
+        let mut parent = genesis;
+        for i in 1..=5u64 {
+            consensus.add_utxo_valid_block_with_parents(i.into(), vec![parent], vec![]).await.unwrap();
+            parent = i.into();
+        }
+        let storage = consensus.consensus_clone();
+        assert_eq!(check_storage_invariants(&storage, genesis), Ok(()));
+        assert_eq!(
+            check_storage_invariants(&storage, 1.into()),
+            Err(InvariantError::FirstPastPruningPointNotGenesis(genesis, 1.into()))
+        );
+
+        // Simulate a crash which lost the DAG data of a fully processed block
+        storage.ghostdag_store.delete(3.into()).unwrap();
+        assert!(matches!(
+            check_storage_invariants(&storage, genesis),
+            Err(InvariantError::MissingGhostdagData(hash, _)) if hash == 3.into()
+        ));
+
+        consensus.shutdown(wait_handles);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod cache_policy_builder;
 pub mod ctl;
 pub mod factory;
+pub mod invariants;
 pub mod services;
 pub mod storage;
 pub mod test_consensus;
@@ -16,6 +17,7 @@ use crate::{
         stores::{
             acceptance_data::AcceptanceDataStoreReader,
             block_transactions::BlockTransactionsStoreReader,
+            chain_scores::{ChainBlockScores, ChainScoresStoreReader, SCORE_BUCKET_SIZE},
             ghostdag::{GhostdagData, GhostdagStoreReader},
             headers::{CompactHeaderData, HeaderStoreReader},
             headers_selected_tip::HeadersSelectedTipStoreReader,
@@ -32,6 +34,7 @@ use crate::{
         body_processor::BlockBodyProcessor,
         deps_manager::{BlockProcessingMessage, BlockResultSender, BlockTask, VirtualStateProcessingMessage},
         header_processor::HeaderProcessor,
+        priority::VirtualPriorityLane,
         pruning_processor::processor::{PruningProcessingMessage, PruningProcessor},
         virtual_processor::{errors::PruningImportResult, VirtualStateProcessor},
         ProcessingCounters,
@@ -67,7 +70,7 @@ use kaspa_consensus_core::{
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
-    BlockHashSet, BlueWorkType, ChainPath, HashMapCustomHasher,
+    BlockHashSet, BlueWorkType, ChainPath, ChainScore, HashMapCustomHasher,
 };
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 
@@ -77,10 +80,12 @@ use crossbeam_channel::{
 use itertools::Itertools;
 use kaspa_consensusmanager::{SessionLock, SessionReadGuard};
 
+use kaspa_core::info;
 use kaspa_database::prelude::StoreResultExtensions;
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
 use kaspa_txscript::caches::TxScriptCacheCounters;
+use kaspa_utils::alloc_tracking::{tag_current_thread, AllocSubsystem};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use std::{
@@ -94,6 +99,7 @@ use std::{
 use std::{
     sync::atomic::AtomicBool,
     thread::{self, JoinHandle},
+    time::Instant,
 };
 use tokio::sync::oneshot;
 
@@ -202,11 +208,20 @@ impl Consensus {
         // Thread-pools
         //
 
-        // Pool for header and body processors
-        let block_processors_pool = Arc::new(
+        // Dedicated pools for the header and body processors, so that each can be sized independently
+        let header_processor_pool = Arc::new(
             rayon::ThreadPoolBuilder::new()
-                .num_threads(perf_params.block_processors_num_threads)
-                .thread_name(|i| format!("block-pool-{i}"))
+                .num_threads(perf_params.header_processor_num_threads)
+                .thread_name(|i| format!("header-pool-{i}"))
+                .start_handler(|_| tag_current_thread(AllocSubsystem::Consensus))
+                .build()
+                .unwrap(),
+        );
+        let body_processor_pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(perf_params.body_processor_num_threads)
+                .thread_name(|i| format!("body-pool-{i}"))
+                .start_handler(|_| tag_current_thread(AllocSubsystem::Consensus))
                 .build()
                 .unwrap(),
         );
@@ -217,10 +232,14 @@ impl Consensus {
             rayon::ThreadPoolBuilder::new()
                 .num_threads(perf_params.virtual_processor_num_threads)
                 .thread_name(|i| format!("virtual-pool-{i}"))
+                .start_handler(|_| tag_current_thread(AllocSubsystem::Consensus))
                 .build()
                 .unwrap(),
         );
 
+        // Gives virtual resolution precedence over header processing when the two compete for CPU
+        let virtual_priority_lane = Arc::new(VirtualPriorityLane::new(perf_params.virtual_priority_header_concurrency));
+
         //
         // Pipeline processors
         //
@@ -228,8 +247,10 @@ impl Consensus {
         let header_processor = Arc::new(HeaderProcessor::new(
             receiver,
             body_sender,
-            block_processors_pool.clone(),
+            header_processor_pool,
+            virtual_priority_lane.clone(),
             params,
+            perf_params,
             db.clone(),
             &storage,
             &services,
@@ -240,7 +261,7 @@ impl Consensus {
         let body_processor = Arc::new(BlockBodyProcessor::new(
             body_receiver,
             virtual_sender,
-            block_processors_pool,
+            body_processor_pool,
             params,
             db.clone(),
             &storage,
@@ -255,7 +276,9 @@ impl Consensus {
             pruning_sender,
             pruning_receiver.clone(),
             virtual_pool,
+            virtual_priority_lane,
             params,
+            perf_params,
             db.clone(),
             &storage,
             &services,
@@ -286,6 +309,14 @@ impl Consensus {
             virtual_processor.process_genesis();
         }
 
+        if config.enable_sanity_checks {
+            let start = Instant::now();
+            if let Err(err) = invariants::check_storage_invariants(&storage, config.genesis.hash) {
+                panic!("Consensus storage invariant violated: {err}");
+            }
+            info!("Consensus storage invariants were verified in {:.2}s (sanity test)", start.elapsed().as_secs_f64());
+        }
+
         Self {
             db,
             block_sender: sender,
@@ -311,11 +342,21 @@ impl Consensus {
         let virtual_processor = self.virtual_processor.clone();
         let pruning_processor = self.pruning_processor.clone();
 
+        // The processor threads and pools are dedicated to consensus, so all their allocations are attributed to it
+        let spawn = |name: &str, worker: Box<dyn FnOnce() + Send>| {
+            thread::Builder::new()
+                .name(name.to_string())
+                .spawn(move || {
+                    tag_current_thread(AllocSubsystem::Consensus);
+                    worker()
+                })
+                .unwrap()
+        };
         vec![
-            thread::Builder::new().name("header-processor".to_string()).spawn(move || header_processor.worker()).unwrap(),
-            thread::Builder::new().name("body-processor".to_string()).spawn(move || body_processor.worker()).unwrap(),
-            thread::Builder::new().name("virtual-processor".to_string()).spawn(move || virtual_processor.worker()).unwrap(),
-            thread::Builder::new().name("pruning-processor".to_string()).spawn(move || pruning_processor.worker()).unwrap(),
+            spawn("header-processor", Box::new(move || header_processor.worker())),
+            spawn("body-processor", Box::new(move || body_processor.worker())),
+            spawn("virtual-processor", Box::new(move || virtual_processor.worker())),
+            spawn("pruning-processor", Box::new(move || pruning_processor.worker())),
         ]
     }
 
@@ -451,6 +492,10 @@ impl ConsensusApi for Consensus {
         unimplemented!("unsupported at the API level until KIP9 is finalized")
     }
 
+    fn calc_block_subsidy(&self, daa_score: u64) -> u64 {
+        self.services.coinbase_manager.calc_block_subsidy(daa_score)
+    }
+
     fn get_stats(&self) -> ConsensusStats {
         // This method is designed to return stats asap and not depend on locks which
         // might take time to acquire
@@ -687,6 +732,38 @@ impl ConsensusApi for Consensus {
         sample_headers
     }
 
+    fn get_chain_block_by_score(&self, score: ChainScore, value: u64) -> ConsensusResult<Hash> {
+        // We need consistency between the selected chain, chain scores and header store reads
+        let _guard = self.pruning_lock.blocking_read();
+
+        // The chain scores store is only updated under the selected chain write lock
+        let sc_read = self.storage.selected_chain_store.read();
+        let chain_scores_read = self.storage.chain_scores_store.read();
+        let score_at = |index: u64| {
+            let hash = sc_read.get_by_index(index).expect("store lock is acquired");
+            ChainBlockScores::from(&self.storage.headers_store.get_compact_header_data(hash).unwrap()).get(score)
+        };
+
+        let tip_index = sc_read.get_tip().unwrap().0;
+        if value > score_at(tip_index) {
+            return Err(ConsensusError::ChainScoreAboveSink(value));
+        }
+
+        // Start from the last chain block below the bucket of `value`, or from the lowest indexed block if the bucket is
+        // below the indexed range. Following, walk up the chain over at most a single bucket
+        let mut index = match chain_scores_read.get_bucket_start(score, value / SCORE_BUCKET_SIZE).unwrap_option() {
+            Some(bucket_start) => bucket_start - 1,
+            None => chain_scores_read.get_base_index().unwrap(),
+        };
+        if score_at(index) > value {
+            return Err(ConsensusError::ChainScoreBelowRetention(value));
+        }
+        while index < tip_index && score_at(index + 1) <= value {
+            index += 1;
+        }
+        Ok(sc_read.get_by_index(index).unwrap())
+    }
+
     fn get_virtual_parents(&self) -> BlockHashSet {
         self.lkg_virtual_state.load().parents.iter().copied().collect()
     }
@@ -1043,4 +1120,13 @@ impl ConsensusApi for Consensus {
     fn finality_point(&self) -> Hash {
         self.virtual_processor.virtual_finality_point(&self.lkg_virtual_state.load().ghostdag_data, self.pruning_point())
     }
+
+    fn flush_storage(&self) -> ConsensusResult<()> {
+        self.db.flush_wal(true).and_then(|_| self.db.flush()).map_err(|err| ConsensusError::StorageError(err.to_string()))
+    }
+
+    fn compact_storage(&self) -> ConsensusResult<()> {
+        self.db.compact_range::<&[u8], &[u8]>(None, None);
+        Ok(())
+    }
 }
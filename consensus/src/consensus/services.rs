@@ -147,6 +147,9 @@ impl ConsensusServices {
             mass_calculator.clone(),
             params.storage_mass_activation,
             params.kip10_activation,
+            params.payload_activation,
+            params.sighash_fork_id,
+            params.sighash_fork_activation,
         );
 
         let pruning_point_manager = PruningPointManager::new(
@@ -4,6 +4,7 @@ use crate::{
         acceptance_data::DbAcceptanceDataStore,
         block_transactions::DbBlockTransactionsStore,
         block_window_cache::BlockWindowCacheStore,
+        chain_scores::DbChainScoresStore,
         daa::DbDaaStore,
         depth::DbDepthStore,
         ghostdag::{CompactGhostdagData, DbGhostdagStore},
@@ -22,14 +23,21 @@ use crate::{
         virtual_state::{LkgVirtualState, VirtualStores},
         DB,
     },
-    processes::{ghostdag::ordering::SortableBlock, reachability::inquirer as reachability, relations},
+    processes::{
+        ghostdag::ordering::SortableBlock,
+        mass::{TxComputeMassCache, TX_COMPUTE_MASS_CACHE_SIZE},
+        reachability::inquirer as reachability,
+        relations,
+    },
 };
 
 use super::cache_policy_builder::CachePolicyBuilder as PolicyBuilder;
 use itertools::Itertools;
-use kaspa_consensus_core::{blockstatus::BlockStatus, BlockHashSet};
+use kaspa_consensus_core::{blockstatus::BlockStatus, config::constants::perf::BASELINE_CACHE_MEMORY_BUDGET, BlockHashSet};
+use kaspa_core::debug;
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
+use kaspa_utils::mem_pressure::Shrinkable;
 use parking_lot::RwLock;
 use std::{ops::DerefMut, sync::Arc};
 
@@ -48,6 +56,7 @@ pub struct ConsensusStorage {
     pub pruning_utxoset_stores: Arc<RwLock<PruningUtxosetStores>>,
     pub virtual_stores: Arc<RwLock<VirtualStores>>,
     pub selected_chain_store: Arc<RwLock<DbSelectedChainStore>>,
+    pub chain_scores_store: Arc<RwLock<DbChainScoresStore>>,
 
     // Append-only stores
     pub ghostdag_store: Arc<DbGhostdagStore>,
@@ -66,6 +75,9 @@ pub struct ConsensusStorage {
     pub block_window_cache_for_difficulty: Arc<BlockWindowCacheStore>,
     pub block_window_cache_for_past_median_time: Arc<BlockWindowCacheStore>,
 
+    /// Compute masses of transactions shared between mempool validation and block body validation
+    pub tx_compute_mass_cache: TxComputeMassCache,
+
     // "Last Known Good" caches
     /// The "last known good" virtual state. To be used by any logic which does not want to wait
     /// for a possible virtual state write to complete but can rather settle with the last known state
@@ -74,18 +86,20 @@ pub struct ConsensusStorage {
 
 impl ConsensusStorage {
     pub fn new(db: Arc<DB>, config: Arc<Config>) -> Arc<Self> {
-        let scale_factor = config.ram_scale;
-        let scaled = |s| (s as f64 * scale_factor) as usize;
-
         let params = &config.params;
         let perf_params = &config.perf;
 
+        // The budgets below are the shares of the baseline memory budget, so they are scaled to fit the configured budget
+        let scale_factor = config.ram_scale * perf_params.cache_memory_budget as f64 / BASELINE_CACHE_MEMORY_BUDGET as f64;
+        let scaled = |s| (s as f64 * scale_factor) as usize;
+
         // Lower and upper bounds
         let pruning_depth = params.pruning_depth as usize;
         let pruning_size_for_caches = (params.pruning_depth + params.finality_depth) as usize; // Upper bound for any block/header related data
         let level_lower_bound = 2 * params.pruning_proof_m as usize; // Number of items lower bound for level-related caches
 
-        // Budgets in bytes. All byte budgets overall sum up to ~1GB of memory (which obviously takes more low level alloc space)
+        // Budgets in bytes. All byte budgets overall sum up to ~1GB of memory (which obviously takes more low level alloc space),
+        // i.e., to the baseline cache memory budget
         let daa_excluded_budget = scaled(30_000_000);
         let statuses_budget = scaled(30_000_000);
         let reachability_data_budget = scaled(20_000_000);
@@ -96,6 +110,15 @@ impl ConsensusStorage {
         let children_budget = scaled(5_000_000); // x 3 for reachability and levels
         let ghostdag_budget = scaled(80_000_000); // x 2 for levels
         let headers_budget = scaled(80_000_000);
+
+        // Per-store overrides of the budgets above. Relations budgets keep their parents/children proportion
+        let overrides = &perf_params.cache_budget_overrides;
+        let (parents_budget, children_budget) = match overrides.relations {
+            Some(relations_budget) => (relations_budget * 8 / 9, relations_budget / 9),
+            None => (parents_budget, children_budget),
+        };
+        let ghostdag_budget = overrides.ghostdag.unwrap_or(ghostdag_budget);
+        let headers_budget = overrides.headers.unwrap_or(headers_budget);
         let transactions_budget = scaled(40_000_000);
         let utxo_diffs_budget = scaled(40_000_000);
         let block_window_budget = scaled(200_000_000); // x 2 for difficulty and median time
@@ -108,7 +131,8 @@ impl ConsensusStorage {
         let ghostdag_compact_bytes = size_of::<Hash>() + size_of::<CompactGhostdagData>();
         let headers_compact_bytes = size_of::<Hash>() + size_of::<CompactHeaderData>();
         let difficulty_window_bytes = params.difficulty_window_size(0) * size_of::<SortableBlock>();
-        let median_window_bytes = params.past_median_time_window_size(0) * size_of::<SortableBlock>();
+        // Past median time windows also keep the sorted timestamps of their blocks
+        let median_window_bytes = params.past_median_time_window_size(0) * (size_of::<SortableBlock>() + size_of::<u64>());
 
         // Cache policy builders
         let daa_excluded_builder =
@@ -163,8 +187,6 @@ impl ConsensusStorage {
         let acceptance_data_builder = PolicyBuilder::new().bytes_budget(acceptance_data_budget).tracked_bytes();
         let past_pruning_points_builder = PolicyBuilder::new().max_items(1024).untracked();
 
-        // TODO: consider tracking UtxoDiff byte sizes more accurately including the exact size of ScriptPublicKey
-
         // Headers
         let statuses_store = Arc::new(RwLock::new(DbStatusesStore::new(db.clone(), statuses_builder.build())));
         let relations_stores = Arc::new(RwLock::new(
@@ -202,6 +224,7 @@ impl ConsensusStorage {
         let headers_store = Arc::new(DbHeadersStore::new(db.clone(), headers_builder.build(), headers_compact_builder.build()));
         let depth_store = Arc::new(DbDepthStore::new(db.clone(), header_data_builder.build()));
         let selected_chain_store = Arc::new(RwLock::new(DbSelectedChainStore::new(db.clone(), header_data_builder.build())));
+        let chain_scores_store = Arc::new(RwLock::new(DbChainScoresStore::new(db.clone(), header_data_builder.build())));
 
         // Pruning
         let pruning_point_store = Arc::new(RwLock::new(DbPruningStore::new(db.clone())));
@@ -222,6 +245,9 @@ impl ConsensusStorage {
         let block_window_cache_for_difficulty = Arc::new(BlockWindowCacheStore::new(difficulty_window_builder.build()));
         let block_window_cache_for_past_median_time = Arc::new(BlockWindowCacheStore::new(median_window_builder.build()));
 
+        // Tx masses
+        let tx_compute_mass_cache = TxComputeMassCache::new(TX_COMPUTE_MASS_CACHE_SIZE);
+
         // Virtual stores
         let lkg_virtual_state = LkgVirtualState::default();
         let virtual_stores =
@@ -246,6 +272,7 @@ impl ConsensusStorage {
             pruning_utxoset_stores,
             virtual_stores,
             selected_chain_store,
+            chain_scores_store,
             acceptance_data_store,
             past_pruning_points_store,
             daa_excluded_store,
@@ -254,7 +281,23 @@ impl ConsensusStorage {
             utxo_multisets_store,
             block_window_cache_for_difficulty,
             block_window_cache_for_past_median_time,
+            tx_compute_mass_cache,
             lkg_virtual_state,
         })
     }
 }
+
+impl Shrinkable for ConsensusStorage {
+    /// Shrinks the block window caches, the block relations (parents and children set) caches and the tx mass cache
+    fn shrink(&self, fraction: f64) -> usize {
+        let window_evicted =
+            self.block_window_cache_for_difficulty.shrink(fraction) + self.block_window_cache_for_past_median_time.shrink(fraction);
+        let relations_evicted = self.relations_stores.read().iter().map(|store| store.shrink_caches(fraction)).sum::<usize>()
+            + self.reachability_relations_store.read().shrink_caches(fraction);
+        let masses_evicted = self.tx_compute_mass_cache.shrink(fraction);
+        debug!(
+            "Shrank consensus caches: {window_evicted} block windows, {relations_evicted} block relations and {masses_evicted} tx masses evicted"
+        );
+        window_evicted + relations_evicted + masses_evicted
+    }
+}
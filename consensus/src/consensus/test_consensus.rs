@@ -32,7 +32,7 @@ use crate::{
     pipeline::{body_processor::BlockBodyProcessor, virtual_processor::VirtualStateProcessor, ProcessingCounters},
     test_helpers::header_from_precomputed_hash,
 };
-use kaspa_database::create_temp_db;
+use kaspa_database::create_memory_db;
 use kaspa_database::prelude::ConnBuilder;
 use std::future::Future;
 use std::{sync::Arc, thread::JoinHandle};
@@ -64,9 +64,9 @@ impl TestConsensus {
         Self { params: config.params.clone(), consensus, block_builder, db_lifetime: Default::default() }
     }
 
-    /// Creates a test consensus instance based on `config` with a temp DB and the provided `notification_sender`
+    /// Creates a test consensus instance based on `config` with an in-memory DB and the provided `notification_sender`
     pub fn with_notifier(config: &Config, notification_sender: Sender<Notification>, context: SubscriptionContext) -> Self {
-        let (db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (db_lifetime, db) = create_memory_db!(ConnBuilder::default().with_files_limit(10));
         let notification_root = Arc::new(ConsensusNotificationRoot::with_context(notification_sender, context));
         let counters = Default::default();
         let tx_script_cache_counters = Default::default();
@@ -84,9 +84,9 @@ impl TestConsensus {
         Self { consensus, block_builder, params: config.params.clone(), db_lifetime }
     }
 
-    /// Creates a test consensus instance based on `config` with a temp DB and no notifier
+    /// Creates a test consensus instance based on `config` with an in-memory DB and no notifier
     pub fn new(config: &Config) -> Self {
-        let (db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (db_lifetime, db) = create_memory_db!(ConnBuilder::default().with_files_limit(10));
         let (dummy_notification_sender, _) = async_channel::unbounded();
         let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
         let counters = Default::default();
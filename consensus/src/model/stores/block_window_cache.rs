@@ -16,21 +16,58 @@ pub enum WindowOrigin {
     Sampled,
 }
 
+/// The timestamps of the blocks of a window in ascending order. Kept along past median time windows so that the window
+/// of a block can be derived from the one of its selected parent by inserting and removing the few timestamps entering
+/// and leaving it, rather than fetching and sorting all timestamps anew
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SortedTimestamps(Vec<u64>);
+
+impl SortedTimestamps {
+    pub fn from_unsorted(mut timestamps: Vec<u64>) -> Self {
+        timestamps.sort_unstable(); // This is deterministic because we sort u64
+        Self(timestamps)
+    }
+
+    pub fn insert(&mut self, timestamp: u64) {
+        let index = self.0.partition_point(|&t| t < timestamp);
+        self.0.insert(index, timestamp);
+    }
+
+    /// Removes a single occurrence of `timestamp`, which is expected to exist
+    pub fn remove(&mut self, timestamp: u64) {
+        let index = self.0.partition_point(|&t| t < timestamp);
+        assert_eq!(self.0.get(index), Some(&timestamp), "removed timestamp is expected to exist");
+        self.0.remove(index);
+    }
+
+    pub fn as_slice(&self) -> &[u64] {
+        &self.0
+    }
+}
+
 #[derive(Clone)]
 pub struct BlockWindowHeap {
     pub blocks: BinaryHeap<Reverse<SortableBlock>>,
     origin: WindowOrigin,
+    /// The timestamps of `blocks`, if tracked. Whoever modifies `blocks` is responsible for keeping these in sync
+    timestamps: Option<SortedTimestamps>,
 }
 
-impl MemSizeEstimator for BlockWindowHeap {}
+impl MemSizeEstimator for BlockWindowHeap {
+    fn estimate_mem_bytes(&self) -> usize {
+        size_of::<Self>()
+            + self.blocks.capacity() * size_of::<Reverse<SortableBlock>>()
+            + self.timestamps.as_ref().map_or(0, |timestamps| timestamps.0.capacity() * size_of::<u64>())
+    }
+}
 
 impl BlockWindowHeap {
     pub fn new(origin: WindowOrigin) -> Self {
-        Self { blocks: Default::default(), origin }
+        Self { blocks: Default::default(), origin, timestamps: None }
     }
 
     pub fn with_capacity(origin: WindowOrigin, capacity: usize) -> Self {
-        Self { blocks: BinaryHeap::with_capacity(capacity), origin }
+        Self { blocks: BinaryHeap::with_capacity(capacity), origin, timestamps: None }
     }
 
     #[inline]
@@ -38,6 +75,19 @@ impl BlockWindowHeap {
     pub fn origin(&self) -> WindowOrigin {
         self.origin
     }
+
+    pub fn timestamps(&self) -> Option<&SortedTimestamps> {
+        self.timestamps.as_ref()
+    }
+
+    pub fn timestamps_mut(&mut self) -> Option<&mut SortedTimestamps> {
+        self.timestamps.as_mut()
+    }
+
+    pub fn set_timestamps(&mut self, timestamps: Option<SortedTimestamps>) {
+        debug_assert!(timestamps.as_ref().map_or(true, |timestamps| timestamps.0.len() == self.blocks.len()));
+        self.timestamps = timestamps;
+    }
 }
 
 impl Deref for BlockWindowHeap {
@@ -0,0 +1,226 @@
+use kaspa_consensus_core::ChainScore;
+use kaspa_database::prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DbWriter, StoreResult, DB};
+use kaspa_database::registry::DatabaseStorePrefixes;
+use rocksdb::WriteBatch;
+use std::sync::Arc;
+
+use super::{headers::CompactHeaderData, U64Key};
+
+/// The score range covered by a single entry of the sparse chain score indexes. A lookup walks the
+/// selected chain over at most one range, hence this bounds the lookup cost
+pub const SCORE_BUCKET_SIZE: u64 = 256;
+
+/// The DAA and blue scores of a selected chain block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChainBlockScores {
+    pub daa_score: u64,
+    pub blue_score: u64,
+}
+
+impl ChainBlockScores {
+    pub fn get(&self, score: ChainScore) -> u64 {
+        match score {
+            ChainScore::DaaScore => self.daa_score,
+            ChainScore::BlueScore => self.blue_score,
+        }
+    }
+}
+
+impl From<&CompactHeaderData> for ChainBlockScores {
+    fn from(header: &CompactHeaderData) -> Self {
+        Self { daa_score: header.daa_score, blue_score: header.blue_score }
+    }
+}
+
+/// Reader API for `ChainScoresStore`.
+///
+/// The indexes map each score bucket `b` to the selected chain index of the first chain block whose score is
+/// at least `b * SCORE_BUCKET_SIZE`. Only buckets above the score of the base chain block (the pruning point
+/// on pruned nodes) are kept. The indexes are updated along with the selected chain store, so readers must
+/// hold the selected chain read lock for consistency
+pub trait ChainScoresStoreReader {
+    fn get_bucket_start(&self, score: ChainScore, bucket: u64) -> StoreResult<u64>;
+    /// Returns the chain index of the lowest block covered by the indexes
+    fn get_base_index(&self) -> StoreResult<u64>;
+}
+
+pub trait ChainScoresStore: ChainScoresStoreReader {
+    /// Updates the indexes with a selected chain reorg from `split` to the chain blocks `added` along with their
+    /// chain indexes. `removed_tip` is the chain tip prior to the reorg, or `split` if no blocks were removed
+    fn apply_changes(
+        &mut self,
+        batch: &mut WriteBatch,
+        split: ChainBlockScores,
+        removed_tip: ChainBlockScores,
+        added: &[(u64, ChainBlockScores)],
+    ) -> StoreResult<()>;
+    fn prune_below_pruning_point(
+        &mut self,
+        writer: impl DbWriter,
+        pruning_point_index: u64,
+        pruning_point: ChainBlockScores,
+    ) -> StoreResult<()>;
+    fn set_base_index(&mut self, batch: &mut WriteBatch, base_index: u64) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `ChainScoresStore` trait, with concurrent readers support.
+#[derive(Clone)]
+pub struct DbChainScoresStore {
+    db: Arc<DB>,
+    access_index_by_daa_score: CachedDbAccess<U64Key, u64>,
+    access_index_by_blue_score: CachedDbAccess<U64Key, u64>,
+    access_base_index: CachedDbItem<u64>,
+}
+
+impl DbChainScoresStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access_index_by_daa_score: CachedDbAccess::new(
+                db.clone(),
+                cache_policy,
+                DatabaseStorePrefixes::ChainIndexByDaaScore.into(),
+            ),
+            access_index_by_blue_score: CachedDbAccess::new(
+                db.clone(),
+                cache_policy,
+                DatabaseStorePrefixes::ChainIndexByBlueScore.into(),
+            ),
+            access_base_index: CachedDbItem::new(db, DatabaseStorePrefixes::ChainScoresBaseIndex.into()),
+        }
+    }
+
+    pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
+        Self::new(Arc::clone(&self.db), cache_policy)
+    }
+
+    fn access(&self, score: ChainScore) -> &CachedDbAccess<U64Key, u64> {
+        match score {
+            ChainScore::DaaScore => &self.access_index_by_daa_score,
+            ChainScore::BlueScore => &self.access_index_by_blue_score,
+        }
+    }
+}
+
+impl ChainScoresStoreReader for DbChainScoresStore {
+    fn get_bucket_start(&self, score: ChainScore, bucket: u64) -> StoreResult<u64> {
+        self.access(score).read(bucket.into())
+    }
+
+    fn get_base_index(&self) -> StoreResult<u64> {
+        self.access_base_index.read()
+    }
+}
+
+impl ChainScoresStore for DbChainScoresStore {
+    fn apply_changes(
+        &mut self,
+        batch: &mut WriteBatch,
+        split: ChainBlockScores,
+        removed_tip: ChainBlockScores,
+        added: &[(u64, ChainBlockScores)],
+    ) -> StoreResult<()> {
+        for score in [ChainScore::DaaScore, ChainScore::BlueScore] {
+            let access = self.access(score);
+            let split_bucket = split.get(score) / SCORE_BUCKET_SIZE;
+            for bucket in split_bucket + 1..=removed_tip.get(score) / SCORE_BUCKET_SIZE {
+                access.delete(BatchDbWriter::new(batch), bucket.into())?;
+            }
+            let mut prev_bucket = split_bucket;
+            for &(index, scores) in added {
+                let bucket = scores.get(score) / SCORE_BUCKET_SIZE;
+                // Scores strictly increase along the chain, so the block starts all buckets it skipped to
+                for skipped in prev_bucket + 1..=bucket {
+                    access.write(BatchDbWriter::new(batch), skipped.into(), index)?;
+                }
+                prev_bucket = bucket;
+            }
+        }
+        Ok(())
+    }
+
+    fn prune_below_pruning_point(
+        &mut self,
+        mut writer: impl DbWriter,
+        pruning_point_index: u64,
+        pruning_point: ChainBlockScores,
+    ) -> StoreResult<()> {
+        for score in [ChainScore::DaaScore, ChainScore::BlueScore] {
+            let access = self.access(score);
+            // Buckets above the pruning point bucket start above the pruning point
+            let mut bucket = pruning_point.get(score) / SCORE_BUCKET_SIZE;
+            while access.has(bucket.into())? {
+                access.delete(&mut writer, bucket.into())?;
+                if bucket == 0 {
+                    break;
+                }
+                bucket -= 1; // A missing bucket signals that data below this point has already been pruned
+            }
+        }
+        self.access_base_index.write(writer, &pruning_point_index)
+    }
+
+    fn set_base_index(&mut self, batch: &mut WriteBatch, base_index: u64) -> StoreResult<()> {
+        self.access_base_index.write(BatchDbWriter::new(batch), &base_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{
+        create_memory_db,
+        prelude::{DirectDbWriter, StoreResultExtensions},
+    };
+
+    fn scores(daa_score: u64) -> ChainBlockScores {
+        ChainBlockScores { daa_score, blue_score: daa_score / 2 }
+    }
+
+    #[test]
+    fn test_chain_scores_store() {
+        let (lt, db) = create_memory_db!(kaspa_database::prelude::ConnBuilder::default().with_files_limit(10));
+        let mut store = DbChainScoresStore::new(db.clone(), CachePolicy::Count(16));
+        let s = SCORE_BUCKET_SIZE;
+        let bucket_start = |store: &DbChainScoresStore, score, bucket| store.get_bucket_start(score, bucket).unwrap_option();
+
+        // Chain indexes 1..=3 with DAA scores s - 1, 3s + 1 and 3s + 2 on top of a base block with score 0
+        let mut batch = WriteBatch::default();
+        store.set_base_index(&mut batch, 0).unwrap();
+        store
+            .apply_changes(&mut batch, scores(0), scores(0), &[(1, scores(s - 1)), (2, scores(3 * s + 1)), (3, scores(3 * s + 2))])
+            .unwrap();
+        db.write_batch(batch).unwrap();
+        assert_eq!(bucket_start(&store, ChainScore::DaaScore, 0), None);
+        for bucket in 1..=3 {
+            assert_eq!(bucket_start(&store, ChainScore::DaaScore, bucket), Some(2));
+        }
+        assert_eq!(bucket_start(&store, ChainScore::DaaScore, 4), None);
+        assert_eq!(bucket_start(&store, ChainScore::BlueScore, 1), Some(2));
+        assert_eq!(bucket_start(&store, ChainScore::BlueScore, 2), None);
+
+        // Reorg chain indexes 2..=3 to a single block with score 2s
+        let mut batch = WriteBatch::default();
+        store.apply_changes(&mut batch, scores(s - 1), scores(3 * s + 2), &[(2, scores(2 * s))]).unwrap();
+        db.write_batch(batch).unwrap();
+        assert_eq!(bucket_start(&store, ChainScore::DaaScore, 1), Some(2));
+        assert_eq!(bucket_start(&store, ChainScore::DaaScore, 2), Some(2));
+        assert_eq!(bucket_start(&store, ChainScore::DaaScore, 3), None);
+        assert_eq!(bucket_start(&store, ChainScore::BlueScore, 1), Some(2));
+
+        // Prune below chain index 1
+        store.prune_below_pruning_point(DirectDbWriter::new(&db), 1, scores(s - 1)).unwrap();
+        assert_eq!(store.get_base_index().unwrap(), 1);
+        assert_eq!(bucket_start(&store, ChainScore::DaaScore, 1), Some(2));
+
+        // Prune below chain index 2, which also removes the buckets started by it
+        store.prune_below_pruning_point(DirectDbWriter::new(&db), 2, scores(2 * s)).unwrap();
+        assert_eq!(store.get_base_index().unwrap(), 2);
+        assert_eq!(bucket_start(&store, ChainScore::DaaScore, 1), None);
+        assert_eq!(bucket_start(&store, ChainScore::DaaScore, 2), None);
+        assert_eq!(bucket_start(&store, ChainScore::BlueScore, 1), None);
+        drop(store);
+        drop(db);
+        drop(lt)
+    }
+}
@@ -57,9 +57,28 @@ impl DbChildrenStore {
         self.access.delete_bucket(&mut writer, parent)
     }
 
+    pub(crate) fn children_count(&self, parent: Hash) -> Result<usize, StoreError> {
+        self.access.bucket_len(parent)
+    }
+
+    /// Returns whether all children of `parent` are in `children`, reading them from the cache if possible and
+    /// otherwise from the DB without caching them
+    pub(crate) fn children_subset_of(&self, parent: Hash, children: &BlockHashSet) -> Result<bool, StoreError> {
+        self.access.bucket_all(parent, |child| children.contains(child))
+    }
+
+    /// See [`CachedDbSetAccess::delete_bucket_files`]
+    pub(crate) fn delete_children_files(&self, parent: Hash) -> Result<(), StoreError> {
+        self.access.delete_bucket_files(parent)
+    }
+
     pub(crate) fn prefix(&self) -> &[u8] {
         self.access.prefix()
     }
+
+    pub(crate) fn shrink_cache(&self, fraction: f64) -> usize {
+        self.access.shrink_cache(fraction)
+    }
 }
 
 impl ChildrenStoreReader for DbChildrenStore {
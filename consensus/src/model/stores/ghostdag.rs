@@ -11,6 +11,7 @@ use kaspa_hashes::Hash;
 use itertools::EitherOrBoth::{Both, Left, Right};
 use itertools::Itertools;
 use kaspa_utils::mem_size::MemSizeEstimator;
+use parking_lot::RwLock;
 use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
 use std::iter::once;
@@ -375,7 +376,7 @@ impl GhostdagStore for DbGhostdagStore {
         let mut batch = WriteBatch::default();
         self.access.write(BatchDbWriter::new(&mut batch), hash, data.clone())?;
         self.compact_access.write(BatchDbWriter::new(&mut batch), hash, data.to_compact())?;
-        self.db.write(batch)?;
+        self.db.write_batch(batch)?;
         Ok(())
     }
 
@@ -383,11 +384,89 @@ impl GhostdagStore for DbGhostdagStore {
         let mut batch = WriteBatch::default();
         self.compact_access.delete(BatchDbWriter::new(&mut batch), hash)?;
         self.access.delete(BatchDbWriter::new(&mut batch), hash)?;
-        self.db.write(batch)?;
+        self.db.write_batch(batch)?;
         Ok(())
     }
 }
 
+/// Buffers GHOSTDAG insertions on top of a DB store so that they can be committed to a batch along with
+/// other staged data. Reads are served from the staged data first and fall back to the underlying store.
+/// Uses a lock for interior mutability since `insert` is non-mutable (see `GhostdagStore`), so that the store can
+/// be shared with services via `Arc`.
+pub struct StagingGhostdagStore<'a> {
+    store: &'a DbGhostdagStore,
+    insertions: RwLock<BlockHashMap<Arc<GhostdagData>>>,
+}
+
+impl<'a> StagingGhostdagStore<'a> {
+    pub fn new(store: &'a DbGhostdagStore) -> Self {
+        Self { store, insertions: Default::default() }
+    }
+
+    pub fn commit(self, batch: &mut WriteBatch) -> Result<(), StoreError> {
+        for (hash, data) in self.insertions.into_inner() {
+            self.store.insert_batch(batch, hash, &data)?;
+        }
+        Ok(())
+    }
+
+    fn staged(&self, hash: Hash) -> Option<Arc<GhostdagData>> {
+        self.insertions.read().get(&hash).cloned()
+    }
+}
+
+impl GhostdagStore for StagingGhostdagStore<'_> {
+    fn insert(&self, hash: Hash, data: Arc<GhostdagData>) -> Result<(), StoreError> {
+        if self.has(hash)? {
+            return Err(StoreError::HashAlreadyExists(hash));
+        }
+        self.insertions.write().insert(hash, data);
+        Ok(())
+    }
+
+    fn delete(&self, _hash: Hash) -> Result<(), StoreError> {
+        unimplemented!("staging GHOSTDAG data only supports insertions")
+    }
+}
+
+impl GhostdagStoreReader for StagingGhostdagStore<'_> {
+    fn get_blue_score(&self, hash: Hash) -> Result<u64, StoreError> {
+        self.staged(hash).map_or_else(|| self.store.get_blue_score(hash), |data| Ok(data.blue_score))
+    }
+
+    fn get_blue_work(&self, hash: Hash) -> Result<BlueWorkType, StoreError> {
+        self.staged(hash).map_or_else(|| self.store.get_blue_work(hash), |data| Ok(data.blue_work))
+    }
+
+    fn get_selected_parent(&self, hash: Hash) -> Result<Hash, StoreError> {
+        self.staged(hash).map_or_else(|| self.store.get_selected_parent(hash), |data| Ok(data.selected_parent))
+    }
+
+    fn get_mergeset_blues(&self, hash: Hash) -> Result<BlockHashes, StoreError> {
+        self.staged(hash).map_or_else(|| self.store.get_mergeset_blues(hash), |data| Ok(data.mergeset_blues.clone()))
+    }
+
+    fn get_mergeset_reds(&self, hash: Hash) -> Result<BlockHashes, StoreError> {
+        self.staged(hash).map_or_else(|| self.store.get_mergeset_reds(hash), |data| Ok(data.mergeset_reds.clone()))
+    }
+
+    fn get_blues_anticone_sizes(&self, hash: Hash) -> Result<HashKTypeMap, StoreError> {
+        self.staged(hash).map_or_else(|| self.store.get_blues_anticone_sizes(hash), |data| Ok(data.blues_anticone_sizes.clone()))
+    }
+
+    fn get_data(&self, hash: Hash) -> Result<Arc<GhostdagData>, StoreError> {
+        self.staged(hash).map_or_else(|| self.store.get_data(hash), Ok)
+    }
+
+    fn get_compact_data(&self, hash: Hash) -> Result<CompactGhostdagData, StoreError> {
+        self.staged(hash).map_or_else(|| self.store.get_compact_data(hash), |data| Ok(data.to_compact()))
+    }
+
+    fn has(&self, hash: Hash) -> Result<bool, StoreError> {
+        Ok(self.insertions.read().contains_key(&hash) || self.store.has(hash)?)
+    }
+}
+
 /// An in-memory implementation of `GhostdagStore` trait to be used for tests.
 /// Uses `RefCell` for interior mutability in order to workaround `insert`
 /// being non-mutable.
@@ -160,7 +160,7 @@ impl HeaderStore for DbHeadersStore {
         let mut batch = WriteBatch::default();
         self.compact_headers_access.write(BatchDbWriter::new(&mut batch), hash, header.as_ref().into())?;
         self.headers_access.write(BatchDbWriter::new(&mut batch), hash, HeaderWithBlockLevel { header, block_level })?;
-        self.db.write(batch)?;
+        self.db.write_batch(batch)?;
         Ok(())
     }
 
@@ -168,7 +168,7 @@ impl HeaderStore for DbHeadersStore {
         let mut batch = WriteBatch::default();
         self.compact_headers_access.delete(BatchDbWriter::new(&mut batch), hash)?;
         self.headers_access.delete(BatchDbWriter::new(&mut batch), hash)?;
-        self.db.write(batch)?;
+        self.db.write_batch(batch)?;
         Ok(())
     }
 }
@@ -1,6 +1,7 @@
 pub mod acceptance_data;
 pub mod block_transactions;
 pub mod block_window_cache;
+pub mod chain_scores;
 pub mod children;
 pub mod daa;
 pub mod depth;
@@ -218,7 +218,7 @@ impl ReachabilityStore for DbReachabilityStore {
         let mut batch = WriteBatch::default();
         self.access.write(BatchDbWriter::new(&mut batch), origin, data)?;
         self.reindex_root.write(BatchDbWriter::new(&mut batch), &origin)?;
-        self.db.write(batch)?;
+        self.db.write_batch(batch)?;
 
         Ok(())
     }
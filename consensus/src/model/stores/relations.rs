@@ -26,6 +26,25 @@ pub trait RelationsStoreReader {
     fn counts(&self) -> Result<(usize, usize), StoreError>;
 }
 
+/// Allows passing a borrowed (e.g., staging) store to services which own their store reader
+impl<T: RelationsStoreReader> RelationsStoreReader for &T {
+    fn get_parents(&self, hash: Hash) -> Result<BlockHashes, StoreError> {
+        (*self).get_parents(hash)
+    }
+
+    fn get_children(&self, hash: Hash) -> StoreResult<ReadLock<BlockHashSet>> {
+        (*self).get_children(hash)
+    }
+
+    fn has(&self, hash: Hash) -> Result<bool, StoreError> {
+        (*self).has(hash)
+    }
+
+    fn counts(&self) -> Result<(usize, usize), StoreError> {
+        (*self).counts()
+    }
+}
+
 /// Low-level write API for `RelationsStore`
 pub trait RelationsStore: RelationsStoreReader {
     type DefaultWriter: DirectWriter;
@@ -65,9 +84,45 @@ impl DbRelationsStore {
         }
     }
 
-    pub(crate) fn delete_children(&self, writer: impl DbWriter, parent: Hash) -> Result<(), StoreError> {
+    /// Replaces `replaced_parent` with `replace_with` in the parents of `hash`, and moves `hash` from the children of
+    /// `replaced_parent` to the children of `replace_with`. Cached children entries are mutated only if present, so
+    /// with a batch writer the store must not be read by others until the batch is committed
+    pub fn replace_parent(
+        &mut self,
+        mut writer: impl DbWriter,
+        hash: Hash,
+        replaced_parent: Hash,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError> {
+        let mut parents = (*self.get_parents(hash)?).clone();
+        let replaced_index =
+            parents.iter().copied().position(|h| h == replaced_parent).expect("callers must ensure replaced is a parent");
+        parents.swap_remove(replaced_index);
+        parents.extend(replace_with.iter().copied().filter(|parent| !parents.contains(parent)).collect_vec());
+        self.parents_access.write(&mut writer, hash, BlockHashes::new(parents))?;
+
+        self.children_store.delete_child(&mut writer, replaced_parent, hash)?;
+        for parent in replace_with.iter().copied() {
+            self.children_store.insert_child(&mut writer, parent, hash)?;
+        }
+        Ok(())
+    }
+
+    /// Deletes all the children of `parent`, along with their cached entry. See [`Self::replace_parent`] regarding batch writers
+    pub fn delete_children(&self, writer: impl DbWriter, parent: Hash) -> Result<(), StoreError> {
         self.children_store.delete_children(writer, parent)
     }
+
+    /// Drops the DB files holding only children of `parent`. Should be called only once the deletion of all its
+    /// children was committed, see [`StagingRelationsStore::commit`]
+    pub fn delete_children_files(&self, parent: Hash) -> Result<(), StoreError> {
+        self.children_store.delete_children_files(parent)
+    }
+
+    /// Evicts about `fraction` of the cached parents and children entries and returns the number of evicted entries
+    pub(crate) fn shrink_caches(&self, fraction: f64) -> usize {
+        self.parents_access.shrink_cache(fraction) + self.children_store.shrink_cache(fraction)
+    }
 }
 
 impl RelationsStoreReader for DbRelationsStore {
@@ -189,7 +244,19 @@ impl<'a> StagingRelationsStore<'a> {
         }
     }
 
-    pub fn commit(&mut self, batch: &mut WriteBatch) -> Result<(), StoreError> {
+    /// Checks whether `children_to_delete` covers all persisted children of `parent` while no new children were staged for it,
+    /// in which case the children bucket can be deleted as a whole. Note that `children_to_delete` might include children which
+    /// were staged and never persisted, so the persisted children are checked individually (in a single pass) rather than counted
+    fn deletes_all_children(&self, parent: Hash, children_to_delete: &BlockHashSet) -> Result<bool, StoreError> {
+        if self.children_insertions.get(&parent).is_some_and(|children| !children.is_empty()) {
+            return Ok(false);
+        }
+        self.store.children_store.children_subset_of(parent, children_to_delete)
+    }
+
+    /// Writes the staged changes to `batch` and returns the parents whose children were deleted as a whole, whose files
+    /// can then be dropped via [`DbRelationsStore::delete_children_files`] once the batch is written
+    pub fn commit(&mut self, batch: &mut WriteBatch) -> Result<Vec<Hash>, StoreError> {
         for (k, v) in self.parents_overrides.iter() {
             self.store.parents_access.write(BatchDbWriter::new(batch), *k, (*v).clone())?
         }
@@ -208,18 +275,26 @@ impl<'a> StagingRelationsStore<'a> {
         self.store.parents_access.delete_many(BatchDbWriter::new(batch), &mut self.entry_deletions.iter().copied())?;
 
         // For deleted entries, delete all children
+        let mut deleted_buckets = Vec::with_capacity(self.entry_deletions.len());
         for parent in self.entry_deletions.iter().copied() {
             self.store.delete_children(BatchDbWriter::new(batch), parent)?;
+            deleted_buckets.push(parent);
         }
 
         // Delete only the requested children
         for (parent, children_to_delete) in self.children_deletions.iter() {
+            if self.deletes_all_children(*parent, children_to_delete)? {
+                // Avoid a tombstone per child when the whole bucket goes away, which is common when pruning
+                self.store.delete_children(BatchDbWriter::new(batch), *parent)?;
+                deleted_buckets.push(*parent);
+                continue;
+            }
             for child in children_to_delete {
                 self.store.delete_child(BatchDbWriter::new(batch), *parent, *child)?;
             }
         }
 
-        Ok(())
+        Ok(deleted_buckets)
     }
 
     fn check_not_in_entry_deletions(&self, hash: Hash) -> Result<(), StoreError> {
@@ -409,7 +484,7 @@ impl RelationsStore for MemoryRelationsStore {
 mod tests {
     use super::*;
     use crate::processes::relations::RelationsStoreExtensions;
-    use kaspa_database::create_temp_db;
+    use kaspa_database::create_memory_db;
     use kaspa_utils::mem_size::MemMode;
 
     #[test]
@@ -419,7 +494,7 @@ mod tests {
 
     #[test]
     fn test_db_relations_store() {
-        let (lt, db) = create_temp_db!(kaspa_database::prelude::ConnBuilder::default().with_files_limit(10));
+        let (lt, db) = create_memory_db!(kaspa_database::prelude::ConnBuilder::default().with_files_limit(10));
         test_relations_store(DbRelationsStore::new(
             db,
             0,
@@ -429,6 +504,144 @@ mod tests {
         drop(lt)
     }
 
+    #[test]
+    fn test_staging_children_deletions() {
+        let (lt, db) = create_memory_db!(kaspa_database::prelude::ConnBuilder::default().with_files_limit(10));
+        let mut store = DbRelationsStore::new(db.clone(), 0, CachePolicy::Count(4), CachePolicy::Count(4));
+        for (i, vec) in [(1, vec![]), (2, vec![1]), (3, vec![1]), (4, vec![1, 2])] {
+            store.insert(Hash::from_u64_word(i), BlockHashes::new(vec.into_iter().map(Hash::from_u64_word).collect())).unwrap();
+        }
+        let children_of = |store: &DbRelationsStore, i: u64| -> BlockHashSet {
+            store.get_children(Hash::from_u64_word(i)).unwrap().read().iter().copied().collect()
+        };
+
+        // Delete all children of 1 and only some children of 2
+        let mut staging = StagingRelationsStore::new(&mut store);
+        for (parent, child) in [(1, 2), (1, 3), (1, 4), (2, 4)] {
+            staging.delete_child(MemoryWriter, Hash::from_u64_word(parent), Hash::from_u64_word(child)).unwrap();
+        }
+        let mut batch = WriteBatch::default();
+        staging.commit(&mut batch).unwrap();
+        db.write_batch(batch).unwrap();
+        assert!(children_of(&store, 1).is_empty());
+        assert!(children_of(&store, 2).is_empty());
+        assert_eq!(store.children_store.children_count(Hash::from_u64_word(1)).unwrap(), 0);
+
+        // Children staged for insertion must survive the deletion of all persisted children
+        store.insert_child(DirectDbWriter::new(&db), Hash::from_u64_word(3), Hash::from_u64_word(4)).unwrap();
+        let mut staging = StagingRelationsStore::new(&mut store);
+        staging.delete_child(MemoryWriter, Hash::from_u64_word(3), Hash::from_u64_word(4)).unwrap();
+        staging.insert_child(MemoryWriter, Hash::from_u64_word(3), Hash::from_u64_word(2)).unwrap();
+        let mut batch = WriteBatch::default();
+        staging.commit(&mut batch).unwrap();
+        db.write_batch(batch).unwrap();
+        assert_eq!(children_of(&store, 3), BlockHashSet::from_iter([Hash::from_u64_word(2)]));
+        drop(store);
+        drop(db);
+        drop(lt)
+    }
+
+    #[test]
+    fn test_concurrent_children_mutations() {
+        const PARENTS: u64 = 3;
+        const CHILDREN_PER_THREAD: u64 = 300;
+
+        let (lt, db) = create_memory_db!(kaspa_database::prelude::ConnBuilder::default().with_files_limit(10));
+        let mut store = DbRelationsStore::new(db.clone(), 0, CachePolicy::Count(2), CachePolicy::Count(2));
+        for parent in 0..PARENTS {
+            store.insert(Hash::from_u64_word(parent), BlockHashes::new(vec![])).unwrap();
+        }
+        // Child words are offset so they do not collide with parent words
+        let child = |i: u64| Hash::from_u64_word(PARENTS + i);
+
+        std::thread::scope(|scope| {
+            for thread in 0..2 {
+                let mut store = store.clone();
+                let db = db.clone();
+                scope.spawn(move || {
+                    for i in thread * CHILDREN_PER_THREAD..(thread + 1) * CHILDREN_PER_THREAD {
+                        let parent = Hash::from_u64_word(i % PARENTS);
+                        store.insert_child(DirectDbWriter::new(&db), parent, child(i)).unwrap();
+                        if i % 2 == 0 {
+                            store.delete_child(DirectDbWriter::new(&db), parent, child(i)).unwrap();
+                        }
+                    }
+                });
+            }
+            for _ in 0..2 {
+                let store = store.clone();
+                scope.spawn(move || {
+                    for i in 0..2 * CHILDREN_PER_THREAD {
+                        assert!(
+                            store.get_children(Hash::from_u64_word(i % PARENTS)).unwrap().read().len() <= CHILDREN_PER_THREAD as usize
+                        );
+                    }
+                });
+            }
+        });
+
+        for parent in 0..PARENTS {
+            let expected: BlockHashSet =
+                (0..2 * CHILDREN_PER_THREAD).filter(|i| i % PARENTS == parent && i % 2 == 1).map(child).collect();
+            assert_eq!(store.children_store.children_count(Hash::from_u64_word(parent)).unwrap(), expected.len());
+            assert_eq!(*store.get_children(Hash::from_u64_word(parent)).unwrap().read(), expected);
+        }
+        drop(store);
+        drop(db);
+        drop(lt)
+    }
+
+    #[test]
+    fn test_replace_parent_and_delete_children() {
+        const CHILDREN: u64 = 200;
+
+        let (lt, db) = create_memory_db!(kaspa_database::prelude::ConnBuilder::default().with_files_limit(10));
+        let mut store = DbRelationsStore::new(db.clone(), 0, CachePolicy::Count(2), CachePolicy::Count(2));
+        let (old_parent, new_parent) = (Hash::from_u64_word(1), Hash::from_u64_word(2));
+        store.insert(old_parent, BlockHashes::new(vec![])).unwrap();
+        store.insert(new_parent, BlockHashes::new(vec![])).unwrap();
+        // Child words are offset so they do not collide with parent words
+        let child = |i: u64| Hash::from_u64_word(3 + i);
+        for i in 0..CHILDREN {
+            store.insert(child(i), BlockHashes::new(vec![old_parent])).unwrap();
+        }
+
+        std::thread::scope(|scope| {
+            let mut writer_store = store.clone();
+            let db = db.clone();
+            scope.spawn(move || {
+                for i in 0..CHILDREN {
+                    writer_store.replace_parent(DirectDbWriter::new(&db), child(i), old_parent, &[new_parent]).unwrap();
+                }
+            });
+            for _ in 0..2 {
+                let store = store.clone();
+                scope.spawn(move || {
+                    for _ in 0..CHILDREN {
+                        let old_children = store.get_children(old_parent).unwrap().read().len();
+                        let new_children = store.get_children(new_parent).unwrap().read().len();
+                        assert!(old_children <= CHILDREN as usize && new_children <= CHILDREN as usize);
+                    }
+                });
+            }
+        });
+
+        let expected: BlockHashSet = (0..CHILDREN).map(child).collect();
+        assert!(store.get_children(old_parent).unwrap().read().is_empty());
+        assert_eq!(*store.get_children(new_parent).unwrap().read(), expected);
+        for i in 0..CHILDREN {
+            assert_eq!(store.get_parents(child(i)).unwrap().as_slice(), [new_parent]);
+        }
+
+        // Deleting the children of a cached entry removes the entry from the cache as well
+        store.delete_children(DirectDbWriter::new(&db), new_parent).unwrap();
+        assert!(store.get_children(new_parent).unwrap().read().is_empty());
+        assert_eq!(store.children_store.children_count(new_parent).unwrap(), 0);
+        drop(store);
+        drop(db);
+        drop(lt)
+    }
+
     fn test_relations_store<T: RelationsStore + ChildrenStore>(mut store: T) {
         let parents = [(1, vec![]), (2, vec![1]), (3, vec![1]), (4, vec![2, 3]), (5, vec![1, 4])];
         for (i, vec) in parents.iter().cloned() {
@@ -2,12 +2,12 @@ use kaspa_consensus_core::{blockstatus::BlockStatus, BlockHasher};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rocksdb::WriteBatch;
-use std::sync::Arc;
+use std::{error::Error, sync::Arc};
 
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use kaspa_database::prelude::{CachePolicy, DB};
 use kaspa_database::prelude::{StoreError, StoreResult};
-use kaspa_hashes::Hash;
+use kaspa_hashes::{Hash, HASH_SIZE};
 
 /// Reader API for `StatusesStore`.
 pub trait StatusesStoreReader {
@@ -46,6 +46,14 @@ impl DbStatusesStore {
     pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
         self.access.delete(BatchDbWriter::new(batch), hash)
     }
+
+    /// Iterates over all stored statuses directly from the DB (bypassing the cache)
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(Hash, BlockStatus), Box<dyn Error>>> + '_ {
+        self.access.iterator().map(|iter_result| {
+            let (key_bytes, status) = iter_result?;
+            Ok((Hash::from_bytes(<[u8; HASH_SIZE]>::try_from(key_bytes.as_ref())?), status))
+        })
+    }
 }
 
 pub trait StatusesStoreBatchExtensions {
@@ -99,11 +99,11 @@ impl TipsStore for DbTipsStore {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+    use kaspa_database::{create_memory_db, prelude::ConnBuilder};
 
     #[test]
     fn test_update_tips() {
-        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (_lifetime, db) = create_memory_db!(ConnBuilder::default().with_files_limit(10));
         let mut store = DbTipsStore::new(db.clone());
         store.add_tip(1.into(), &[]).unwrap();
         store.add_tip(3.into(), &[]).unwrap();
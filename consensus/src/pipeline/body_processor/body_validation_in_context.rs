@@ -134,6 +134,7 @@ mod tests {
         {
             let mut block = consensus.build_block_with_parents_and_transactions(2.into(), vec![3.into()], vec![]);
             block.transactions[0].payload[8..16].copy_from_slice(&(5_u64).to_le_bytes());
+            block.transactions[0].finalize();
             block.header.hash_merkle_root = calc_hash_merkle_root(block.transactions.iter());
 
             assert_match!(
@@ -149,6 +150,7 @@ mod tests {
         {
             let mut block = consensus.build_block_with_parents_and_transactions(4.into(), vec![3.into()], vec![]);
             block.transactions[0].payload[0..8].copy_from_slice(&(100_u64).to_le_bytes());
+            block.transactions[0].finalize();
             block.header.hash_merkle_root = calc_hash_merkle_root(block.transactions.iter());
 
             assert_match!(
@@ -160,6 +162,7 @@ mod tests {
         {
             let mut block = consensus.build_block_with_parents_and_transactions(5.into(), vec![3.into()], vec![]);
             block.transactions[0].payload = vec![];
+            block.transactions[0].finalize();
             block.header.hash_merkle_root = calc_hash_merkle_root(block.transactions.iter());
 
             assert_match!(
@@ -174,6 +177,7 @@ mod tests {
             // The block DAA score is 2, so the subsidy should be calculated according to the deflationary stage.
             let mut block = consensus.build_block_with_parents_and_transactions(7.into(), vec![6.into()], vec![]);
             block.transactions[0].payload[8..16].copy_from_slice(&(5_u64).to_le_bytes());
+            block.transactions[0].finalize();
             block.header.hash_merkle_root = calc_hash_merkle_root(block.transactions.iter());
             assert_match!(consensus.validate_and_insert_block(block.to_immutable()).virtual_state_task.await, Err(RuleError::WrongSubsidy(expected,_)) if expected == 44000000000);
         }
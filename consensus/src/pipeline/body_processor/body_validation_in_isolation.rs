@@ -1,18 +1,25 @@
 use std::{collections::HashSet, sync::Arc};
 
 use super::BlockBodyProcessor;
-use crate::errors::{BlockProcessResult, RuleError};
-use kaspa_consensus_core::{block::Block, merkle::calc_hash_merkle_root, tx::TransactionOutpoint};
+use crate::{
+    errors::{BlockProcessResult, RuleError},
+    processes::merkle::hash_merkle_leaves,
+};
+use kaspa_consensus_core::{block::Block, tx::TransactionOutpoint};
+use kaspa_hashes::Hash;
+use kaspa_merkle::calc_merkle_root;
 
 impl BlockBodyProcessor {
     pub fn validate_body_in_isolation(self: &Arc<Self>, block: &Block) -> BlockProcessResult<u64> {
         let storage_mass_activated = self.storage_mass_activation.is_active(block.header.daa_score);
 
         Self::check_has_transactions(block)?;
-        Self::check_hash_merkle_root(block, storage_mass_activated)?;
+        // The leaves are the tx hashes committed to by the block, which also key the tx compute mass cache
+        let leaves = hash_merkle_leaves(&block.transactions, storage_mass_activated);
+        Self::check_hash_merkle_root(block, &leaves)?;
         Self::check_only_one_coinbase(block)?;
         self.check_transactions_in_isolation(block)?;
-        let mass = self.check_block_mass(block, storage_mass_activated)?;
+        let mass = self.check_block_mass(block, &leaves, storage_mass_activated)?;
         self.check_duplicate_transactions(block)?;
         self.check_block_double_spends(block)?;
         self.check_no_chained_transactions(block)?;
@@ -28,8 +35,8 @@ impl BlockBodyProcessor {
         Ok(())
     }
 
-    fn check_hash_merkle_root(block: &Block, storage_mass_activated: bool) -> BlockProcessResult<()> {
-        let calculated = calc_hash_merkle_root(block.transactions.iter(), storage_mass_activated);
+    fn check_hash_merkle_root(block: &Block, leaves: &[Hash]) -> BlockProcessResult<()> {
+        let calculated = calc_merkle_root(leaves.iter().copied());
         if calculated != block.header.hash_merkle_root {
             return Err(RuleError::BadMerkleRoot(block.header.hash_merkle_root, calculated));
         }
@@ -53,16 +60,21 @@ impl BlockBodyProcessor {
             if let Err(e) = self.transaction_validator.validate_tx_in_isolation(tx) {
                 return Err(RuleError::TxInIsolationValidationFailed(tx.id(), e));
             }
+            // The payload activation depends on the header DAA score only, so it is checked here in order to apply
+            // to trusted blocks as well
+            if let Err(e) = self.transaction_validator.check_transaction_payload(tx, block.header.daa_score) {
+                return Err(RuleError::TxInIsolationValidationFailed(tx.id(), e));
+            }
         }
         Ok(())
     }
 
-    fn check_block_mass(self: &Arc<Self>, block: &Block, storage_mass_activated: bool) -> BlockProcessResult<u64> {
+    fn check_block_mass(self: &Arc<Self>, block: &Block, leaves: &[Hash], storage_mass_activated: bool) -> BlockProcessResult<u64> {
         let mut total_mass: u64 = 0;
         if storage_mass_activated {
-            for tx in block.transactions.iter() {
+            for (tx, &tx_hash) in block.transactions.iter().zip(leaves) {
                 // This is only the compute part of the mass, the storage part cannot be computed here
-                let calculated_tx_compute_mass = self.mass_calculator.calc_tx_compute_mass(tx);
+                let calculated_tx_compute_mass = self.tx_compute_mass_cache.get_or_calc(&self.mass_calculator, tx, tx_hash);
                 let committed_contextual_mass = tx.mass();
                 // We only check the lower-bound here, a precise check of the mass commitment
                 // is done when validating the tx in context
@@ -72,15 +84,15 @@ impl BlockBodyProcessor {
                 // Sum over the committed masses
                 total_mass = total_mass.saturating_add(committed_contextual_mass);
                 if total_mass > self.max_block_mass {
-                    return Err(RuleError::ExceedsMassLimit(self.max_block_mass));
+                    return Err(RuleError::ExceedsMassLimit(self.max_block_mass, total_mass));
                 }
             }
         } else {
-            for tx in block.transactions.iter() {
-                let calculated_tx_mass = self.mass_calculator.calc_tx_compute_mass(tx);
+            for (tx, &tx_hash) in block.transactions.iter().zip(leaves) {
+                let calculated_tx_mass = self.tx_compute_mass_cache.get_or_calc(&self.mass_calculator, tx, tx_hash);
                 total_mass = total_mass.saturating_add(calculated_tx_mass);
                 if total_mass > self.max_block_mass {
-                    return Err(RuleError::ExceedsMassLimit(self.max_block_mass));
+                    return Err(RuleError::ExceedsMassLimit(self.max_block_mass, total_mass));
                 }
             }
         }
@@ -136,6 +148,7 @@ mod tests {
     use kaspa_consensus_core::{
         api::{BlockValidationFutures, ConsensusApi},
         block::MutableBlock,
+        errors::tx::TxRuleError,
         header::Header,
         merkle::calc_hash_merkle_root as calc_hash_merkle_root_with_options,
         subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
@@ -415,7 +428,7 @@ mod tests {
         txs[1].inputs[0].sig_op_count = 255;
         txs[1].inputs[1].sig_op_count = 255;
         block.header.hash_merkle_root = calc_hash_merkle_root(txs.iter());
-        assert_match!(body_processor.validate_body_in_isolation(&block.to_immutable()), Err(RuleError::ExceedsMassLimit(_)));
+        assert_match!(body_processor.validate_body_in_isolation(&block.to_immutable()), Err(RuleError::ExceedsMassLimit(..)));
 
         let mut block = example_block.clone();
         let txs = &mut block.transactions;
@@ -450,6 +463,16 @@ mod tests {
             Err(RuleError::TxInIsolationValidationFailed(_, _))
         );
 
+        // Payloads are not activated on mainnet
+        let mut block = example_block.clone();
+        let txs = &mut block.transactions;
+        txs[1].payload = vec![1];
+        block.header.hash_merkle_root = calc_hash_merkle_root(txs.iter());
+        assert_match!(
+            body_processor.validate_body_in_isolation(&block.to_immutable()),
+            Err(RuleError::TxInIsolationValidationFailed(_, TxRuleError::NonCoinbaseTxHasPayload))
+        );
+
         let mut block = example_block;
         let txs = &mut block.transactions;
         txs[3].inputs[0].previous_outpoint = TransactionOutpoint { transaction_id: txs[2].id(), index: 0 };
@@ -467,6 +490,7 @@ mod tests {
 
         let mut block = consensus.build_block_with_parents_and_transactions(1.into(), vec![config.genesis.hash], vec![]);
         block.transactions[0].version += 1;
+        block.transactions[0].finalize();
 
         let BlockValidationFutures { block_task, virtual_state_task } =
             consensus.validate_and_insert_block(block.clone().to_immutable());
@@ -19,9 +19,9 @@ use crate::{
     },
     pipeline::{
         deps_manager::{BlockProcessingMessage, BlockTaskDependencyManager, TaskId, VirtualStateProcessingMessage},
-        ProcessingCounters,
+        ProcessingCounters, ProcessingStage,
     },
-    processes::{coinbase::CoinbaseManager, transaction_validator::TransactionValidator},
+    processes::{coinbase::CoinbaseManager, mass::TxComputeMassCache, transaction_validator::TransactionValidator},
 };
 use crossbeam_channel::{Receiver, Sender};
 use kaspa_consensus_core::{
@@ -39,12 +39,16 @@ use kaspa_consensus_notify::{
     root::ConsensusNotificationRoot,
 };
 use kaspa_consensusmanager::SessionLock;
+use kaspa_database::prelude::StoreResultExtensions;
 use kaspa_hashes::Hash;
 use kaspa_notify::notifier::Notify;
 use parking_lot::RwLock;
 use rayon::ThreadPool;
 use rocksdb::WriteBatch;
-use std::sync::{atomic::Ordering, Arc};
+use std::{
+    sync::{atomic::Ordering, Arc},
+    time::Instant,
+};
 
 pub struct BlockBodyProcessor {
     // Channels
@@ -68,6 +72,7 @@ pub struct BlockBodyProcessor {
     pub(super) block_transactions_store: Arc<DbBlockTransactionsStore>,
     pub(super) body_tips_store: Arc<RwLock<DbTipsStore>>,
     pub(super) block_window_cache_for_past_median_time: Arc<BlockWindowCacheStore>,
+    pub(super) tx_compute_mass_cache: TxComputeMassCache,
 
     // Managers and services
     pub(super) reachability_service: MTReachabilityService<DbReachabilityStore>,
@@ -123,6 +128,7 @@ impl BlockBodyProcessor {
             block_transactions_store: storage.block_transactions_store.clone(),
             body_tips_store: storage.body_tips_store.clone(),
             block_window_cache_for_past_median_time: storage.block_window_cache_for_past_median_time.clone(),
+            tx_compute_mass_cache: storage.tx_compute_mass_cache.clone(),
 
             reachability_service: services.reachability_service.clone(),
             coinbase_manager: services.coinbase_manager.clone(),
@@ -144,10 +150,7 @@ impl BlockBodyProcessor {
                 BlockProcessingMessage::Exit => break,
                 BlockProcessingMessage::Process(task, block_result_transmitter, virtual_result_transmitter) => {
                     if let Some(task_id) = self.task_manager.register(task, block_result_transmitter, virtual_result_transmitter) {
-                        let processor = self.clone();
-                        self.thread_pool.spawn(move || {
-                            processor.queue_block(task_id);
-                        });
+                        self.spawn_queue_block(task_id);
                     }
                 }
             };
@@ -160,6 +163,15 @@ impl BlockBodyProcessor {
         self.sender.send(VirtualStateProcessingMessage::Exit).unwrap();
     }
 
+    fn spawn_queue_block(self: &Arc<BlockBodyProcessor>, task_id: TaskId) {
+        let processor = self.clone();
+        self.counters.body_queue_len.fetch_add(1, Ordering::Relaxed);
+        self.thread_pool.spawn(move || {
+            processor.counters.body_queue_len.fetch_sub(1, Ordering::Relaxed);
+            processor.queue_block(task_id);
+        });
+    }
+
     fn queue_block(self: &Arc<BlockBodyProcessor>, task_id: TaskId) {
         if let Some(task) = self.task_manager.try_begin(task_id) {
             let res = self.process_body(task.block(), task.is_trusted());
@@ -170,13 +182,13 @@ impl BlockBodyProcessor {
                     // We don't care if receivers were dropped
                     let _ = virtual_state_result_transmitter.send(res.clone());
                 } else {
+                    self.counters.virtual_queue_len.fetch_add(1, Ordering::Relaxed);
                     self.sender.send(VirtualStateProcessingMessage::Process(task, virtual_state_result_transmitter)).unwrap();
                 }
             });
 
             for dep in dependent_tasks {
-                let processor = self.clone();
-                self.thread_pool.spawn(move || processor.queue_block(dep));
+                self.spawn_queue_block(dep);
             }
         }
     }
@@ -191,7 +203,10 @@ impl BlockBodyProcessor {
             _ => panic!("unexpected block status {status:?}"),
         }
 
-        let mass = match self.validate_body(block, is_trusted) {
+        let start = Instant::now();
+        let res = self.validate_body(block, is_trusted);
+        self.counters.record_latency(ProcessingStage::BodyValidation, start.elapsed());
+        let mass = match res {
             Ok(mass) => mass,
             Err(e) => {
                 // We mark invalid blocks with status StatusInvalid except in the
@@ -244,7 +259,9 @@ impl BlockBodyProcessor {
         let statuses_write_guard =
             self.statuses_store.set_batch(&mut batch, hash, BlockStatus::StatusUTXOPendingVerification).unwrap();
 
-        self.db.write(batch).unwrap();
+        let start = Instant::now();
+        self.db.write_batch(batch).unwrap_or_halt();
+        self.counters.record_latency(ProcessingStage::Commit, start.elapsed());
 
         // Calling the drops explicitly after the batch is written in order to avoid possible errors.
         drop(statuses_write_guard);
@@ -256,7 +273,7 @@ impl BlockBodyProcessor {
         let mut batch = WriteBatch::default();
         let mut body_tips_write_guard = self.body_tips_store.write();
         body_tips_write_guard.init_batch(&mut batch, &[]).unwrap();
-        self.db.write(batch).unwrap();
+        self.db.write_batch(batch).unwrap_or_halt();
         drop(body_tips_write_guard);
 
         // Write the genesis body
@@ -96,7 +96,7 @@ impl HeaderProcessor {
                 kosherizing_blues = Some(self.depth_manager.kosherizing_blues(ghostdag_data, merge_depth_root).collect());
             }
             if !self.reachability_service.is_dag_ancestor_of_any(red, &mut kosherizing_blues.as_ref().unwrap().iter().copied()) {
-                return Err(RuleError::ViolatingBoundedMergeDepth);
+                return Err(RuleError::ViolatingBoundedMergeDepth(red, merge_depth_root));
             }
         }
 
@@ -104,7 +104,7 @@ impl HeaderProcessor {
         if passed || self.skip_proof_of_work {
             Ok(calc_level_from_pow(pow, self.max_block_level))
         } else {
-            Err(RuleError::InvalidPoW)
+            Err(RuleError::InvalidPoW(header.hash, header.bits))
         }
     }
 }
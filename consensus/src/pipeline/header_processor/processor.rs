@@ -24,15 +24,22 @@ use crate::{
         },
     },
     params::Params,
-    pipeline::deps_manager::{BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, TaskId},
-    processes::{ghostdag::ordering::SortableBlock, reachability::inquirer as reachability, relations::RelationsStoreExtensions},
+    pipeline::{
+        deps_manager::{BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, TaskId},
+        priority::VirtualPriorityLane,
+    },
+    processes::{
+        ghostdag::ordering::SortableBlock,
+        reachability::{inquirer as reachability, ReindexParams},
+        relations::RelationsStoreExtensions,
+    },
 };
 use crossbeam_channel::{Receiver, Sender};
 use itertools::Itertools;
 use kaspa_consensus_core::{
     blockhash::{BlockHashes, ORIGIN},
     blockstatus::BlockStatus::{self, StatusHeaderOnly, StatusInvalid},
-    config::genesis::GenesisBlock,
+    config::{constants::perf::PerfParams, genesis::GenesisBlock},
     header::Header,
     BlockHashSet, BlockLevel,
 };
@@ -43,9 +50,12 @@ use kaspa_utils::vec::VecExtensions;
 use parking_lot::RwLock;
 use rayon::ThreadPool;
 use rocksdb::WriteBatch;
-use std::sync::{atomic::Ordering, Arc};
+use std::{
+    sync::{atomic::Ordering, Arc},
+    time::Instant,
+};
 
-use super::super::ProcessingCounters;
+use super::super::{ProcessingCounters, ProcessingStage};
 
 pub struct HeaderProcessingContext {
     pub hash: Hash,
@@ -110,6 +120,7 @@ pub struct HeaderProcessor {
 
     // Thread pool
     pub(super) thread_pool: Arc<ThreadPool>,
+    priority_lane: Arc<VirtualPriorityLane>,
 
     // Config
     pub(super) genesis: GenesisBlock,
@@ -119,6 +130,7 @@ pub struct HeaderProcessor {
     pub(super) mergeset_size_limit: u64,
     pub(super) skip_proof_of_work: bool,
     pub(super) max_block_level: BlockLevel,
+    reindex_params: ReindexParams,
 
     // DB
     db: Arc<DB>,
@@ -157,11 +169,14 @@ pub struct HeaderProcessor {
 }
 
 impl HeaderProcessor {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         receiver: Receiver<BlockProcessingMessage>,
         body_sender: Sender<BlockProcessingMessage>,
         thread_pool: Arc<ThreadPool>,
+        priority_lane: Arc<VirtualPriorityLane>,
         params: &Params,
+        perf_params: &PerfParams,
         db: Arc<DB>,
         storage: &Arc<ConsensusStorage>,
         services: &Arc<ConsensusServices>,
@@ -172,6 +187,7 @@ impl HeaderProcessor {
             receiver,
             body_sender,
             thread_pool,
+            priority_lane,
             genesis: params.genesis.clone(),
             db,
 
@@ -198,6 +214,7 @@ impl HeaderProcessor {
 
             task_manager: BlockTaskDependencyManager::new(),
             pruning_lock,
+            reindex_params: ReindexParams::from_perf_params(perf_params).with_counters(counters.clone()),
             counters,
             // TODO (HF): make sure to also pass `new_timestamp_deviation_tolerance` and use according to HF activation score
             timestamp_deviation_tolerance: params.timestamp_deviation_tolerance(0),
@@ -218,10 +235,7 @@ impl HeaderProcessor {
                 BlockProcessingMessage::Process(task, block_result_transmitter, virtual_state_result_transmitter) => {
                     if let Some(task_id) = self.task_manager.register(task, block_result_transmitter, virtual_state_result_transmitter)
                     {
-                        let processor = self.clone();
-                        self.thread_pool.spawn(move || {
-                            processor.queue_block(task_id);
-                        });
+                        self.spawn_queue_block(task_id);
                     }
                 }
             };
@@ -234,9 +248,22 @@ impl HeaderProcessor {
         self.body_sender.send(BlockProcessingMessage::Exit).unwrap();
     }
 
+    fn spawn_queue_block(self: &Arc<HeaderProcessor>, task_id: TaskId) {
+        let processor = self.clone();
+        self.counters.header_queue_len.fetch_add(1, Ordering::Relaxed);
+        self.thread_pool.spawn(move || {
+            processor.counters.header_queue_len.fetch_sub(1, Ordering::Relaxed);
+            processor.queue_block(task_id);
+        });
+    }
+
     fn queue_block(self: &Arc<HeaderProcessor>, task_id: TaskId) {
         if let Some(task) = self.task_manager.try_begin(task_id) {
-            let res = self.process_header(&task);
+            let res = {
+                // Yield to virtual resolution if it is in progress and enough headers are already being processed
+                let _lane_guard = self.priority_lane.enter_header();
+                self.process_header(&task)
+            };
 
             let dependent_tasks = self.task_manager.end(
                 task,
@@ -256,8 +283,7 @@ impl HeaderProcessor {
             );
 
             for dep in dependent_tasks {
-                let processor = self.clone();
-                self.thread_pool.spawn(move || processor.queue_block(dep));
+                self.spawn_queue_block(dep);
             }
         }
     }
@@ -276,7 +302,9 @@ impl HeaderProcessor {
         // Validate the header depending on task type
         match task {
             BlockTask::Ordinary { .. } => {
+                let start = Instant::now();
                 let ctx = self.validate_header(header)?;
+                self.counters.record_latency(ProcessingStage::HeaderValidation, start.elapsed());
                 self.commit_header(ctx, header);
             }
             BlockTask::Trusted { .. } => {
@@ -300,7 +328,7 @@ impl HeaderProcessor {
         self.ghostdag(&mut ctx);
         self.pre_pow_validation(&mut ctx, header)?;
         if let Err(e) = self.post_pow_validation(&mut ctx, header) {
-            self.statuses_store.write().set(ctx.hash, StatusInvalid).unwrap();
+            self.statuses_store.write().set(ctx.hash, StatusInvalid).unwrap_or_halt();
             return Err(e);
         }
         Ok(ctx)
@@ -365,7 +393,7 @@ impl HeaderProcessor {
         //
         // Append-only stores: these require no lock and hence done first in order to reduce locking time
         //
-        self.ghostdag_store.insert_batch(&mut batch, ctx.hash, ghostdag_data).unwrap();
+        self.ghostdag_store.insert_batch(&mut batch, ctx.hash, ghostdag_data).unwrap_or_halt();
 
         if let Some(window) = ctx.block_window_for_difficulty {
             self.block_window_cache_for_difficulty.insert(ctx.hash, window);
@@ -374,9 +402,11 @@ impl HeaderProcessor {
             self.block_window_cache_for_past_median_time.insert(ctx.hash, window);
         }
 
-        self.daa_excluded_store.insert_batch(&mut batch, ctx.hash, Arc::new(ctx.mergeset_non_daa.unwrap())).unwrap();
+        self.daa_excluded_store.insert_batch(&mut batch, ctx.hash, Arc::new(ctx.mergeset_non_daa.unwrap())).unwrap_or_halt();
         self.headers_store.insert_batch(&mut batch, ctx.hash, ctx.header, ctx.block_level).unwrap();
-        self.depth_store.insert_batch(&mut batch, ctx.hash, ctx.merge_depth_root.unwrap(), ctx.finality_point.unwrap()).unwrap();
+        self.depth_store
+            .insert_batch(&mut batch, ctx.hash, ctx.merge_depth_root.unwrap(), ctx.finality_point.unwrap())
+            .unwrap_or_halt();
 
         //
         // Reachability and header chain stores
@@ -389,7 +419,8 @@ impl HeaderProcessor {
         let mut staging = StagingReachabilityStore::new(self.reachability_store.upgradable_read());
         let selected_parent = ghostdag_data.selected_parent;
         let mut reachability_mergeset = ghostdag_data.unordered_mergeset_without_selected_parent();
-        reachability::add_block(&mut staging, ctx.hash, selected_parent, &mut reachability_mergeset).unwrap();
+        reachability::add_block_with_params(&mut staging, ctx.hash, selected_parent, &mut reachability_mergeset, &self.reindex_params)
+            .unwrap();
 
         // Non-append only stores need to use write locks.
         // Note we need to keep the lock write guards until the batch is written.
@@ -399,7 +430,7 @@ impl HeaderProcessor {
             && reachability::is_chain_ancestor_of(&staging, pp, ctx.hash).unwrap()
         {
             // Hint reachability about the new tip.
-            reachability::hint_virtual_selected_parent(&mut staging, ctx.hash).unwrap();
+            reachability::hint_virtual_selected_parent_with_params(&mut staging, ctx.hash, &self.reindex_params).unwrap();
             hst_write.set_batch(&mut batch, SortableBlock::new(ctx.hash, header.blue_work)).unwrap();
         }
 
@@ -411,22 +442,24 @@ impl HeaderProcessor {
 
         let mut relations_write = self.relations_stores.write();
         ctx.known_parents.into_iter().enumerate().for_each(|(level, parents_by_level)| {
-            relations_write[level].insert_batch(&mut batch, header.hash, parents_by_level).unwrap();
+            relations_write[level].insert_batch(&mut batch, header.hash, parents_by_level).unwrap_or_halt();
         });
 
         // Write reachability relations. These relations are only needed during header pruning
         let mut reachability_relations_write = self.reachability_relations_store.write();
-        reachability_relations_write.insert_batch(&mut batch, ctx.hash, reachability_parents).unwrap();
+        reachability_relations_write.insert_batch(&mut batch, ctx.hash, reachability_parents).unwrap_or_halt();
 
-        let statuses_write = self.statuses_store.set_batch(&mut batch, ctx.hash, StatusHeaderOnly).unwrap();
+        let statuses_write = self.statuses_store.set_batch(&mut batch, ctx.hash, StatusHeaderOnly).unwrap_or_halt();
 
         // Write reachability data. Only at this brief moment the reachability store is locked for reads.
         // We take special care for this since reachability read queries are used throughout the system frequently.
         // Note we hold the lock until the batch is written
-        let reachability_write = staging.commit(&mut batch).unwrap();
+        let reachability_write = staging.commit(&mut batch).unwrap_or_halt();
 
         // Flush the batch to the DB
-        self.db.write(batch).unwrap();
+        let start = Instant::now();
+        self.db.write_batch(batch).unwrap_or_halt();
+        self.counters.record_latency(ProcessingStage::Commit, start.elapsed());
 
         // Calling the drops explicitly after the batch is written in order to avoid possible errors.
         drop(reachability_write);
@@ -451,10 +484,10 @@ impl HeaderProcessor {
             relations_write[level].insert_batch(&mut batch, ctx.hash, parents_by_level).unwrap_or_exists();
         });
 
-        let statuses_write = self.statuses_store.set_batch(&mut batch, ctx.hash, StatusHeaderOnly).unwrap();
+        let statuses_write = self.statuses_store.set_batch(&mut batch, ctx.hash, StatusHeaderOnly).unwrap_or_halt();
 
         // Flush the batch to the DB
-        self.db.write(batch).unwrap();
+        self.db.write_batch(batch).unwrap_or_halt();
 
         // Calling the drops explicitly after the batch is written in order to avoid possible errors.
         drop(statuses_write);
@@ -466,7 +499,7 @@ impl HeaderProcessor {
         let mut batch = WriteBatch::default();
         let mut hst_write = self.headers_selected_tip_store.write();
         hst_write.set_batch(&mut batch, SortableBlock::new(self.genesis.hash, 0.into())).unwrap();
-        self.db.write(batch).unwrap();
+        self.db.write_batch(batch).unwrap_or_halt();
         drop(hst_write);
 
         // Write the genesis header
@@ -502,7 +535,7 @@ impl HeaderProcessor {
             .for_each(|level| relations_write[level as usize].insert_batch(&mut batch, ORIGIN, BlockHashes::new(vec![])).unwrap());
         let mut hst_write = self.headers_selected_tip_store.write();
         hst_write.set_batch(&mut batch, SortableBlock::new(ORIGIN, 0.into())).unwrap();
-        self.db.write(batch).unwrap();
+        self.db.write_batch(batch).unwrap_or_halt();
         drop(hst_write);
         drop(relations_write);
     }
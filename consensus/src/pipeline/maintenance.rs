@@ -0,0 +1,222 @@
+use super::{ProcessingCounters, ProcessingCountersSnapshot};
+use kaspa_consensus_core::errors::consensus::ConsensusResult;
+use kaspa_consensusmanager::ConsensusManager;
+use kaspa_core::{
+    info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, warn,
+};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const MAINTENANCE: &str = "storage-maintenance";
+
+#[derive(Debug, Clone)]
+pub struct StorageMaintenanceConfig {
+    /// The interval at which processing activity is sampled
+    pub check_interval: Duration,
+
+    /// The max number of blocks processed during a check interval for it to be considered quiet
+    pub quiet_blocks_threshold: u64,
+
+    /// The number of consecutive quiet check intervals required before any maintenance is performed
+    pub quiet_checks: u32,
+
+    /// The min time between consecutive flushes of DB memtables and WAL
+    pub flush_interval: Duration,
+
+    /// The min time between consecutive manual compactions of the whole DB. `None` disables compactions
+    pub compaction_interval: Option<Duration>,
+}
+
+impl StorageMaintenanceConfig {
+    /// Builds a config for a network with the given `bps`, where a check interval is considered quiet if the
+    /// number of processed blocks does not exceed the expected number of blocks by more than 50%
+    pub fn new(bps: u64, compaction_interval: Option<Duration>) -> Self {
+        let check_interval = Duration::from_secs(10);
+        Self {
+            check_interval,
+            quiet_blocks_threshold: bps * check_interval.as_secs() * 3 / 2,
+            quiet_checks: 3,
+            flush_interval: Duration::from_secs(600),
+            compaction_interval,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MaintenanceTask {
+    Flush,
+    Compact,
+}
+
+/// Decides when to perform maintenance based on the processing activity sampled at each check interval
+struct MaintenanceSchedule {
+    config: StorageMaintenanceConfig,
+    quiet_streak: u32,
+    last_flush: Instant,
+    last_compaction: Instant,
+}
+
+impl MaintenanceSchedule {
+    fn new(config: StorageMaintenanceConfig, now: Instant) -> Self {
+        Self { config, quiet_streak: 0, last_flush: now, last_compaction: now }
+    }
+
+    /// Registers the number of blocks processed during the last check interval and returns the maintenance task
+    /// to perform now, if any. A compaction is preferred over a flush if both are due
+    fn next_task(&mut self, processed_blocks: u64, now: Instant) -> Option<MaintenanceTask> {
+        if processed_blocks > self.config.quiet_blocks_threshold {
+            self.quiet_streak = 0;
+            return None;
+        }
+        self.quiet_streak += 1;
+        if self.quiet_streak < self.config.quiet_checks {
+            return None;
+        }
+        let task = if self.config.compaction_interval.is_some_and(|interval| now.duration_since(self.last_compaction) >= interval) {
+            self.last_compaction = now;
+            MaintenanceTask::Compact
+        } else if now.duration_since(self.last_flush) >= self.config.flush_interval {
+            MaintenanceTask::Flush
+        } else {
+            return None;
+        };
+        // A compaction flushes the memtables as well
+        self.last_flush = now;
+        // Require a fresh quiet window before the next task
+        self.quiet_streak = 0;
+        Some(task)
+    }
+}
+
+/// Flushes and compacts the active consensus DB during quiet periods, so that RocksDB flushes and compactions
+/// are less likely to be triggered by the engine itself during bursts of block processing.
+///
+/// Note that an ongoing compaction cannot be interrupted, so node shutdown is delayed until it completes
+pub struct StorageMaintenanceScheduler {
+    consensus_manager: Arc<ConsensusManager>,
+    counters: Arc<ProcessingCounters>,
+    tick_service: Arc<TickService>,
+    config: StorageMaintenanceConfig,
+}
+
+impl StorageMaintenanceScheduler {
+    pub fn new(
+        consensus_manager: Arc<ConsensusManager>,
+        counters: Arc<ProcessingCounters>,
+        tick_service: Arc<TickService>,
+        config: StorageMaintenanceConfig,
+    ) -> Self {
+        Self { consensus_manager, counters, tick_service, config }
+    }
+
+    fn processed_blocks(delta: &ProcessingCountersSnapshot) -> u64 {
+        // Headers and bodies are processed separately during IBD, so either one indicates activity
+        delta.header_counts.max(delta.body_counts)
+    }
+
+    async fn run_task(&self, task: MaintenanceTask) -> ConsensusResult<()> {
+        let session = self.consensus_manager.consensus().unguarded_session();
+        match task {
+            MaintenanceTask::Flush => session.spawn_blocking(|c| c.flush_storage()).await,
+            MaintenanceTask::Compact => session.spawn_blocking(|c| c.flush_storage().and_then(|_| c.compact_storage())).await,
+        }
+    }
+
+    pub async fn worker(self: &Arc<StorageMaintenanceScheduler>) {
+        let mut schedule = MaintenanceSchedule::new(self.config.clone(), Instant::now());
+        let mut last_snapshot = self.counters.snapshot();
+        loop {
+            if let TickReason::Shutdown = self.tick_service.tick(self.config.check_interval).await {
+                break;
+            }
+
+            let snapshot = self.counters.snapshot();
+            let processed_blocks = Self::processed_blocks(&(&snapshot - &last_snapshot));
+            last_snapshot = snapshot;
+
+            let Some(task) = schedule.next_task(processed_blocks, Instant::now()) else {
+                continue;
+            };
+            let start = Instant::now();
+            match self.run_task(task).await {
+                Ok(()) if task == MaintenanceTask::Compact => {
+                    info!("Compacted the consensus DB during a quiet period in {:.2}s", start.elapsed().as_secs_f64())
+                }
+                Ok(()) => trace!("Flushed the consensus DB in {:.2}s", start.elapsed().as_secs_f64()),
+                Err(err) => warn!("Consensus DB maintenance ({:?}) failed: {}", task, err),
+            }
+            // Blocks processed while the task was running do not belong to the next check interval
+            last_snapshot = self.counters.snapshot();
+        }
+
+        trace!("{} thread exiting", MAINTENANCE);
+    }
+}
+
+impl AsyncService for StorageMaintenanceScheduler {
+    fn ident(self: Arc<Self>) -> &'static str {
+        MAINTENANCE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", MAINTENANCE);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", MAINTENANCE);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maintenance_schedule() {
+        let start = Instant::now();
+        let config = StorageMaintenanceConfig::new(10, Some(Duration::from_secs(3600)));
+        let threshold = config.quiet_blocks_threshold;
+        let mut schedule = MaintenanceSchedule::new(config, start);
+
+        // Nothing is due right after startup
+        let mut now = start;
+        for _ in 0..5 {
+            now += Duration::from_secs(10);
+            assert_eq!(schedule.next_task(0, now), None);
+        }
+
+        // A flush is due, but only after enough quiet intervals following a burst
+        now = start + Duration::from_secs(600);
+        assert_eq!(schedule.next_task(threshold + 1, now), None);
+        assert_eq!(schedule.next_task(threshold, now), None);
+        assert_eq!(schedule.next_task(threshold, now), None);
+        assert_eq!(schedule.next_task(threshold, now), Some(MaintenanceTask::Flush));
+
+        // Compaction takes precedence once due, and resets the flush interval as well
+        now = start + Duration::from_secs(3600);
+        assert_eq!(schedule.next_task(0, now), None);
+        assert_eq!(schedule.next_task(0, now), None);
+        assert_eq!(schedule.next_task(0, now), Some(MaintenanceTask::Compact));
+        for _ in 0..3 {
+            assert_eq!(schedule.next_task(0, now + Duration::from_secs(599)), None);
+        }
+        assert_eq!(schedule.next_task(0, now + Duration::from_secs(600)), Some(MaintenanceTask::Flush));
+    }
+}
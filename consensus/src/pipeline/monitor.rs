@@ -1,6 +1,6 @@
-use super::ProcessingCounters;
+use super::{ProcessingCounters, ProcessingStage};
 use kaspa_core::{
-    info,
+    debug, info,
     task::{
         service::{AsyncService, AsyncServiceFuture},
         tick::{TickReason, TickService},
@@ -14,17 +14,34 @@ use std::{
 
 const MONITOR: &str = "consensus-monitor";
 
+/// Node-level figures which are outside the scope of consensus, but are
+/// logged along with processing stats for an at-a-glance view of node health
+pub trait NodeStatusProvider: Send + Sync {
+    /// A recent sample of the number of transactions in the mempool
+    fn mempool_size(&self) -> u64;
+
+    /// The number of currently connected peers
+    fn peer_count(&self) -> usize;
+}
+
 pub struct ConsensusMonitor {
     // Counters
     counters: Arc<ProcessingCounters>,
 
+    // Node status
+    node_status: Option<Arc<dyn NodeStatusProvider>>,
+
     // Tick service
     tick_service: Arc<TickService>,
 }
 
 impl ConsensusMonitor {
     pub fn new(counters: Arc<ProcessingCounters>, tick_service: Arc<TickService>) -> ConsensusMonitor {
-        ConsensusMonitor { counters, tick_service }
+        ConsensusMonitor { counters, node_status: None, tick_service }
+    }
+
+    pub fn with_node_status(self, node_status: Arc<dyn NodeStatusProvider>) -> Self {
+        Self { node_status: Some(node_status), ..self }
     }
 
     pub async fn worker(self: &Arc<ConsensusMonitor>) {
@@ -48,9 +65,14 @@ impl ConsensusMonitor {
             // Subtract the snapshots
             let delta = &snapshot - &last_snapshot;
             let now = Instant::now();
+            let node_status = self
+                .node_status
+                .as_ref()
+                .map(|status| format!("; {} mempool txs; {} peers", status.mempool_size(), status.peer_count()))
+                .unwrap_or_default();
 
             info!(
-                "Processed {} blocks and {} headers in the last {:.2}s ({} transactions; {} UTXO-validated blocks; {:.2} parents; {:.2} mergeset; {:.2} TPB; {:.1} mass)", 
+                "Processed {} blocks and {} headers in the last {:.2}s ({} transactions; {} UTXO-validated blocks; {:.2} parents; {:.2} mergeset; {:.2} TPB; {:.1} mass{})", 
                 delta.body_counts,
                 delta.header_counts,
                 (now - last_log_time).as_secs_f64(),
@@ -60,8 +82,41 @@ impl ConsensusMonitor {
                 if delta.header_counts != 0 { delta.mergeset_counts as f64 / delta.header_counts as f64 } else { 0f64 },
                 if delta.body_counts != 0 { delta.txs_counts as f64 / delta.body_counts as f64 } else{ 0f64 },
                 if delta.body_counts != 0 { delta.mass_counts as f64 / delta.body_counts as f64 } else{ 0f64 },
+                node_status,
             );
 
+            if delta.reindex_counts > 0 {
+                debug!(
+                    "Performed {} reachability reindexes in the last {:.2}s (avg subtree size {:.1}; overall {:.2}ms)",
+                    delta.reindex_counts,
+                    (now - last_log_time).as_secs_f64(),
+                    delta.reindex_subtree_sizes as f64 / delta.reindex_counts as f64,
+                    delta.reindex_micros as f64 / 1000.0,
+                );
+            }
+
+            let stage_latencies = ProcessingStage::ALL
+                .into_iter()
+                .filter_map(|stage| {
+                    let histogram = delta.stage_latency(stage);
+                    (histogram.count() > 0).then(|| {
+                        let [p50, p95, p99] = [0.5, 0.95, 0.99].map(|q| histogram.quantile_micros(q) as f64 / 1000.0);
+                        format!("{} {:.2}/{:.2}/{:.2}", stage.name(), p50, p95, p99)
+                    })
+                })
+                .collect::<Vec<_>>();
+            if !stage_latencies.is_empty() {
+                // Latencies are bucketed by powers of two, hence the figures are upper bounds
+                debug!("Processing stage latencies in ms (p50/p95/p99): {}", stage_latencies.join("; "));
+            }
+
+            if delta.header_queue_len + delta.body_queue_len + delta.virtual_queue_len > 0 {
+                debug!(
+                    "Processing queues: {} headers, {} bodies, {} awaiting virtual",
+                    delta.header_queue_len, delta.body_queue_len, delta.virtual_queue_len
+                );
+            }
+
             if delta.chain_disqualified_counts > 0 {
                 warn!(
                     "Consensus detected UTXO-invalid blocks which are disqualified from the virtual selected chain (possibly due to inheritance): {} disqualified vs. {} valid chain blocks",
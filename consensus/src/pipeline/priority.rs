@@ -0,0 +1,130 @@
+use parking_lot::{Condvar, Mutex};
+use std::time::Duration;
+
+/// Upper bound on the time a header task is held back in favor of virtual resolution. This guarantees
+/// progress of header processing even if virtual resolution is unexpectedly long
+const MAX_HEADER_BACKOFF: Duration = Duration::from_millis(50);
+
+#[derive(Default)]
+struct LaneState {
+    virtual_resolving: bool,
+    active_headers: usize,
+}
+
+/// A priority lane giving virtual resolution precedence over header processing. While virtual is
+/// being resolved, at most `header_concurrency` header tasks are allowed to run concurrently so that
+/// a flood of headers cannot starve the virtual processor of CPU. The lane is a no-op when
+/// `header_concurrency` is 0
+pub struct VirtualPriorityLane {
+    header_concurrency: usize,
+    state: Mutex<LaneState>,
+    signal: Condvar,
+}
+
+impl VirtualPriorityLane {
+    pub fn new(header_concurrency: usize) -> Self {
+        Self { header_concurrency, state: Mutex::new(LaneState::default()), signal: Condvar::new() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.header_concurrency > 0
+    }
+
+    /// To be called by header workers before processing a header. Blocks (for a bounded period) while
+    /// virtual is being resolved and the header concurrency quota is exhausted
+    pub fn enter_header(&self) -> HeaderLaneGuard<'_> {
+        if !self.is_enabled() {
+            return HeaderLaneGuard { lane: self };
+        }
+        let mut state = self.state.lock();
+        while state.virtual_resolving && state.active_headers >= self.header_concurrency {
+            if self.signal.wait_for(&mut state, MAX_HEADER_BACKOFF).timed_out() {
+                break;
+            }
+        }
+        state.active_headers += 1;
+        HeaderLaneGuard { lane: self }
+    }
+
+    /// To be called by the virtual processor for the duration of virtual resolution
+    pub fn enter_virtual(&self) -> VirtualLaneGuard<'_> {
+        if self.is_enabled() {
+            self.state.lock().virtual_resolving = true;
+        }
+        VirtualLaneGuard { lane: self }
+    }
+}
+
+pub struct HeaderLaneGuard<'a> {
+    lane: &'a VirtualPriorityLane,
+}
+
+impl Drop for HeaderLaneGuard<'_> {
+    fn drop(&mut self) {
+        if self.lane.is_enabled() {
+            self.lane.state.lock().active_headers -= 1;
+            self.lane.signal.notify_one();
+        }
+    }
+}
+
+pub struct VirtualLaneGuard<'a> {
+    lane: &'a VirtualPriorityLane,
+}
+
+impl Drop for VirtualLaneGuard<'_> {
+    fn drop(&mut self) {
+        if self.lane.is_enabled() {
+            self.lane.state.lock().virtual_resolving = false;
+            self.lane.signal.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    #[test]
+    fn test_header_concurrency_is_bounded_while_resolving_virtual() {
+        let lane = Arc::new(VirtualPriorityLane::new(1));
+        let virtual_guard = lane.enter_virtual();
+
+        // The first header enters immediately, the second one has to wait for it (or for virtual)
+        let first = lane.enter_header();
+        let entered = Arc::new(AtomicUsize::new(0));
+        let handle = {
+            let (lane, entered) = (lane.clone(), entered.clone());
+            thread::spawn(move || {
+                let _guard = lane.enter_header();
+                entered.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+        thread::sleep(MAX_HEADER_BACKOFF / 5);
+        assert_eq!(entered.load(Ordering::SeqCst), 0);
+
+        drop(first);
+        handle.join().unwrap();
+        assert_eq!(entered.load(Ordering::SeqCst), 1);
+        drop(virtual_guard);
+
+        // With virtual idle, headers are not limited
+        let _guards = (0..4).map(|_| lane.enter_header()).collect::<Vec<_>>();
+        assert_eq!(lane.state.lock().active_headers, 4);
+    }
+
+    #[test]
+    fn test_disabled_lane_never_blocks() {
+        let lane = VirtualPriorityLane::new(0);
+        let _virtual_guard = lane.enter_virtual();
+        let _guards = (0..4).map(|_| lane.enter_header()).collect::<Vec<_>>();
+        assert!(!lane.state.lock().virtual_resolving);
+    }
+}
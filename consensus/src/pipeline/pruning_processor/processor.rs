@@ -8,13 +8,14 @@ use crate::{
     model::{
         services::reachability::{MTReachabilityService, ReachabilityService},
         stores::{
+            chain_scores::{ChainBlockScores, ChainScoresStore},
             ghostdag::{CompactGhostdagData, GhostdagStoreReader},
             headers::HeaderStoreReader,
             past_pruning_points::PastPruningPointsStoreReader,
             pruning::{PruningStore, PruningStoreReader},
             reachability::{DbReachabilityStore, ReachabilityStoreReader, StagingReachabilityStore},
             relations::StagingRelationsStore,
-            selected_chain::SelectedChainStore,
+            selected_chain::{SelectedChainStore, SelectedChainStoreReader},
             statuses::StatusesStoreReader,
             tips::{TipsStore, TipsStoreReader},
             utxo_diffs::UtxoDiffsStoreReader,
@@ -33,7 +34,7 @@ use kaspa_consensus_core::{
     trusted::ExternalGhostdagData,
     BlockHashMap, BlockHashSet, BlockLevel,
 };
-use kaspa_consensusmanager::SessionLock;
+use kaspa_consensusmanager::{PruningPointDeferral, SessionLock};
 use kaspa_core::{debug, info, warn};
 use kaspa_database::prelude::{BatchDbWriter, MemoryWriter, StoreResultExtensions, DB};
 use kaspa_hashes::Hash;
@@ -51,6 +52,10 @@ use std::{
     time::{Duration, Instant},
 };
 
+/// The maximal total duration pruning point movements are delayed for readers holding the pruning point
+/// (see [`kaspa_consensusmanager::PruningPointGuard`]). The budget is replenished once all readers released the pruning point
+const PRUNING_POINT_HOLDERS_GRACE_PERIOD: Duration = Duration::from_secs(300);
+
 pub enum PruningProcessingMessage {
     Exit,
     Process { sink_ghostdag_data: CompactGhostdagData },
@@ -165,6 +170,16 @@ impl PruningProcessor {
     }
 
     fn advance_pruning_point_and_candidate_if_possible(&self, sink_ghostdag_data: CompactGhostdagData) {
+        // Give long readers of pruning point data (e.g., peers syncing the pruning point UTXO set from us) a chance
+        // to complete before the pruning point moves underneath them
+        if self.pruning_lock.pruning_point_holders() > 0
+            && self.is_pruning_point_movement_pending(sink_ghostdag_data)
+            && !self.defer_pruning_point_movement()
+        {
+            info!("Interrupted while waiting for pruning point holders: Process is exiting");
+            return;
+        }
+
         let pruning_point_read = self.pruning_point_store.upgradable_read();
         let current_pruning_info = pruning_point_read.get().unwrap();
         let (new_pruning_points, new_candidate) = self.pruning_point_manager.next_pruning_points_and_candidate_by_ghostdag_data(
@@ -179,12 +194,14 @@ impl PruningProcessor {
             let mut batch = WriteBatch::default();
             let mut pruning_point_write = RwLockUpgradableReadGuard::upgrade(pruning_point_read);
             for (i, past_pp) in new_pruning_points.iter().copied().enumerate() {
-                self.past_pruning_points_store.insert_batch(&mut batch, current_pruning_info.index + i as u64 + 1, past_pp).unwrap();
+                self.past_pruning_points_store
+                    .insert_batch(&mut batch, current_pruning_info.index + i as u64 + 1, past_pp)
+                    .unwrap_or_halt();
             }
             let new_pp_index = current_pruning_info.index + new_pruning_points.len() as u64;
             let new_pruning_point = *new_pruning_points.last().unwrap();
-            pruning_point_write.set_batch(&mut batch, new_pruning_point, new_candidate, new_pp_index).unwrap();
-            self.db.write(batch).unwrap();
+            pruning_point_write.set_batch(&mut batch, new_pruning_point, new_candidate, new_pp_index).unwrap_or_halt();
+            self.db.write_batch(batch).unwrap_or_halt();
             drop(pruning_point_write);
 
             // Inform the user
@@ -201,7 +218,37 @@ impl PruningProcessor {
             self.prune(new_pruning_point);
         } else if new_candidate != current_pruning_info.candidate {
             let mut pruning_point_write = RwLockUpgradableReadGuard::upgrade(pruning_point_read);
-            pruning_point_write.set(current_pruning_info.pruning_point, new_candidate, current_pruning_info.index).unwrap();
+            pruning_point_write.set(current_pruning_info.pruning_point, new_candidate, current_pruning_info.index).unwrap_or_halt();
+        }
+    }
+
+    fn is_pruning_point_movement_pending(&self, sink_ghostdag_data: CompactGhostdagData) -> bool {
+        let current_pruning_info = self.pruning_point_store.read().get().unwrap();
+        let (new_pruning_points, _) = self.pruning_point_manager.next_pruning_points_and_candidate_by_ghostdag_data(
+            sink_ghostdag_data,
+            None,
+            current_pruning_info.candidate,
+            current_pruning_info.pruning_point,
+        );
+        !new_pruning_points.is_empty()
+    }
+
+    /// Waits for pruning point holders to release their guards, up to the remaining [`PRUNING_POINT_HOLDERS_GRACE_PERIOD`]
+    /// budget. Returns false if consensus is exiting
+    fn defer_pruning_point_movement(&self) -> bool {
+        info!("Delaying pruning point movement until {} pruning point holder(s) complete", self.pruning_lock.pruning_point_holders());
+        match self.pruning_lock.defer_pruning_point_movement(PRUNING_POINT_HOLDERS_GRACE_PERIOD, Duration::from_secs(1), || {
+            self.is_consensus_exiting.load(Ordering::Relaxed)
+        }) {
+            PruningPointDeferral::Released => true,
+            PruningPointDeferral::Expired => {
+                warn!(
+                    "Pruning point holders did not complete within the {} seconds deferral budget, moving the pruning point regardless",
+                    PRUNING_POINT_HOLDERS_GRACE_PERIOD.as_secs()
+                );
+                true
+            }
+            PruningPointDeferral::Exiting => false,
         }
     }
 
@@ -215,7 +262,7 @@ impl PruningProcessor {
             let mut batch = WriteBatch::default();
             pruning_utxoset_write.utxo_set.write_diff_batch(&mut batch, utxo_diff.as_ref()).unwrap();
             pruning_utxoset_write.set_utxoset_position(&mut batch, chain_block).unwrap();
-            self.db.write(batch).unwrap();
+            self.db.write_batch(batch).unwrap_or_halt();
         }
         drop(pruning_utxoset_write);
 
@@ -277,28 +324,23 @@ impl PruningProcessor {
 
         info!("Starting Header and Block pruning...");
 
-        {
-            let mut counter = 0;
-            let mut batch = WriteBatch::default();
-            // At this point keep_relations only holds level-0 relations which is the correct filtering criteria for primary GHOSTDAG
-            for kept in keep_relations.keys().copied() {
-                let Some(ghostdag) = self.ghostdag_store.get_data(kept).unwrap_option() else {
-                    continue;
-                };
-                if ghostdag.unordered_mergeset().any(|h| !keep_relations.contains_key(&h)) {
-                    let mut mutable_ghostdag: ExternalGhostdagData = ghostdag.as_ref().into();
-                    mutable_ghostdag.mergeset_blues.retain(|h| keep_relations.contains_key(h));
-                    mutable_ghostdag.mergeset_reds.retain(|h| keep_relations.contains_key(h));
-                    mutable_ghostdag.blues_anticone_sizes.retain(|k, _| keep_relations.contains_key(k));
-                    if !keep_relations.contains_key(&mutable_ghostdag.selected_parent) {
-                        mutable_ghostdag.selected_parent = ORIGIN;
-                    }
-                    counter += 1;
-                    self.ghostdag_store.update_batch(&mut batch, kept, &Arc::new(mutable_ghostdag.into())).unwrap();
+        // At this point keep_relations only holds level-0 relations which is the correct filtering criteria for primary GHOSTDAG.
+        // The filtered data is staged and committed below along with the pruned tips and selected chain
+        let mut filtered_ghostdag = Vec::new();
+        for kept in keep_relations.keys().copied() {
+            let Some(ghostdag) = self.ghostdag_store.get_data(kept).unwrap_option() else {
+                continue;
+            };
+            if ghostdag.unordered_mergeset().any(|h| !keep_relations.contains_key(&h)) {
+                let mut mutable_ghostdag: ExternalGhostdagData = ghostdag.as_ref().into();
+                mutable_ghostdag.mergeset_blues.retain(|h| keep_relations.contains_key(h));
+                mutable_ghostdag.mergeset_reds.retain(|h| keep_relations.contains_key(h));
+                mutable_ghostdag.blues_anticone_sizes.retain(|k, _| keep_relations.contains_key(k));
+                if !keep_relations.contains_key(&mutable_ghostdag.selected_parent) {
+                    mutable_ghostdag.selected_parent = ORIGIN;
                 }
+                filtered_ghostdag.push((kept, Arc::new(mutable_ghostdag.into())));
             }
-            self.db.write(batch).unwrap();
-            info!("Header and Block pruning: updated ghostdag data for {} blocks", counter);
         }
 
         // No need to hold the prune guard while we continue populating keep_relations
@@ -341,8 +383,11 @@ impl PruningProcessor {
         let mut reachability_read = self.reachability_store.upgradable_read();
 
         {
-            // Start with a batch for pruning body tips and selected chain stores
+            // Start with a batch for filtering GHOSTDAG data and pruning body tips and selected chain stores
             let mut batch = WriteBatch::default();
+            for (kept, ghostdag) in filtered_ghostdag.iter() {
+                self.ghostdag_store.update_batch(&mut batch, *kept, ghostdag).unwrap_or_halt();
+            }
 
             // Prune tips which can no longer be merged by virtual.
             // By the prunality proof, any tip which isn't in future(pruning_point) will never be merged
@@ -366,14 +411,22 @@ impl PruningProcessor {
                 )
             }
 
-            // Prune the selected chain index below the pruning point
+            // Prune the selected chain index and the sparse chain score indexes below the pruning point
             let mut selected_chain_write = self.selected_chain_store.write();
+            let mut chain_scores_write = self.chain_scores_store.write();
+            let pruning_point_index = selected_chain_write.get_by_hash(new_pruning_point).unwrap();
+            let pruning_point_scores = ChainBlockScores::from(&self.headers_store.get_compact_header_data(new_pruning_point).unwrap());
+            chain_scores_write
+                .prune_below_pruning_point(BatchDbWriter::new(&mut batch), pruning_point_index, pruning_point_scores)
+                .unwrap();
             selected_chain_write.prune_below_pruning_point(BatchDbWriter::new(&mut batch), new_pruning_point).unwrap();
 
             // Flush the batch to the DB
-            self.db.write(batch).unwrap();
+            self.db.write_batch(batch).unwrap_or_halt();
+            info!("Header and Block pruning: updated ghostdag data for {} blocks", filtered_ghostdag.len());
 
             // Calling the drops explicitly after the batch is written in order to avoid possible errors.
+            drop(chain_scores_write);
             drop(selected_chain_write);
             drop(tips_write);
         }
@@ -420,11 +473,13 @@ impl PruningProcessor {
                 let mut staging_relations = StagingRelationsStore::new(&mut reachability_relations_write);
                 let mut staging_reachability = StagingReachabilityStore::new(reachability_read);
                 let mut statuses_write = self.statuses_store.write();
+                // Level relations whose children buckets are deleted as a whole, see `StagingRelationsStore::commit`
+                let mut deleted_level_children = Vec::new();
 
                 // Prune data related to block bodies and UTXO state
-                self.utxo_multisets_store.delete_batch(&mut batch, current).unwrap();
-                self.utxo_diffs_store.delete_batch(&mut batch, current).unwrap();
-                self.acceptance_data_store.delete_batch(&mut batch, current).unwrap();
+                self.utxo_multisets_store.delete_batch(&mut batch, current).unwrap_or_halt();
+                self.utxo_diffs_store.delete_batch(&mut batch, current).unwrap_or_halt();
+                self.acceptance_data_store.delete_batch(&mut batch, current).unwrap_or_halt();
                 self.block_transactions_store.delete_batch(&mut batch, current).unwrap();
 
                 if let Some(&affiliated_proof_level) = keep_relations.get(&current) {
@@ -433,7 +488,7 @@ impl PruningProcessor {
                         // status. This is important since some proof headers might not have their status set
                         // and we would like to preserve this semantic (having a valid status implies that
                         // other parts of the code assume the existence of GD data etc.)
-                        statuses_write.set_batch(&mut batch, current, StatusHeaderOnly).unwrap();
+                        statuses_write.set_batch(&mut batch, current, StatusHeaderOnly).unwrap_or_halt();
                     }
 
                     // Delete level-x relations for blocks which only belong to higher-than-x proof levels.
@@ -441,7 +496,8 @@ impl PruningProcessor {
                     for lower_level in 0..affiliated_proof_level as usize {
                         let mut staging_level_relations = StagingRelationsStore::new(&mut level_relations_write[lower_level]);
                         relations::delete_level_relations(MemoryWriter, &mut staging_level_relations, current).unwrap_option();
-                        staging_level_relations.commit(&mut batch).unwrap();
+                        let deleted = staging_level_relations.commit(&mut batch).unwrap_or_halt();
+                        deleted_level_children.extend(deleted.into_iter().map(|parent| (lower_level, parent)));
 
                         if lower_level == 0 {
                             self.ghostdag_store.delete_batch(&mut batch, current).unwrap_option();
@@ -463,28 +519,41 @@ impl PruningProcessor {
                     (0..=block_level as usize).for_each(|level| {
                         let mut staging_level_relations = StagingRelationsStore::new(&mut level_relations_write[level]);
                         relations::delete_level_relations(MemoryWriter, &mut staging_level_relations, current).unwrap_option();
-                        staging_level_relations.commit(&mut batch).unwrap();
+                        let deleted = staging_level_relations.commit(&mut batch).unwrap_or_halt();
+                        deleted_level_children.extend(deleted.into_iter().map(|parent| (level, parent)));
                     });
 
                     self.ghostdag_store.delete_batch(&mut batch, current).unwrap_option();
 
                     // Remove additional header related data
-                    self.daa_excluded_store.delete_batch(&mut batch, current).unwrap();
-                    self.depth_store.delete_batch(&mut batch, current).unwrap();
+                    self.daa_excluded_store.delete_batch(&mut batch, current).unwrap_or_halt();
+                    self.depth_store.delete_batch(&mut batch, current).unwrap_or_halt();
                     // Remove status completely
-                    statuses_write.delete_batch(&mut batch, current).unwrap();
+                    statuses_write.delete_batch(&mut batch, current).unwrap_or_halt();
 
                     if !keep_headers.contains(&current) {
                         // Prune the actual headers
-                        self.headers_store.delete_batch(&mut batch, current).unwrap();
+                        self.headers_store.delete_batch(&mut batch, current).unwrap_or_halt();
                     }
                 }
 
-                let reachability_write = staging_reachability.commit(&mut batch).unwrap();
-                staging_relations.commit(&mut batch).unwrap();
+                let reachability_write = staging_reachability.commit(&mut batch).unwrap_or_halt();
+                let deleted_reachability_children = staging_relations.commit(&mut batch).unwrap_or_halt();
 
                 // Flush the batch to the DB
-                self.db.write(batch).unwrap();
+                self.db.write_batch(batch).unwrap_or_halt();
+
+                // Now that the deletions are committed, reclaim the space of whole children buckets without waiting for
+                // compaction. This is an optimization only, so failures are not fatal
+                let deleted_children = deleted_level_children
+                    .into_iter()
+                    .map(|(level, parent)| (&level_relations_write[level], parent))
+                    .chain(deleted_reachability_children.into_iter().map(|parent| (&*reachability_relations_write, parent)));
+                for (store, parent) in deleted_children {
+                    if let Err(err) = store.delete_children_files(parent) {
+                        warn!("Failed deleting the DB files of the pruned children of {}: {}", parent, err);
+                    }
+                }
 
                 // Calling the drops explicitly after the batch is written in order to avoid possible errors.
                 drop(reachability_write);
@@ -518,7 +587,7 @@ impl PruningProcessor {
             let mut pruning_point_write = self.pruning_point_store.write();
             let mut batch = WriteBatch::default();
             pruning_point_write.set_history_root(&mut batch, new_pruning_point).unwrap();
-            self.db.write(batch).unwrap();
+            self.db.write_batch(batch).unwrap_or_halt();
             drop(pruning_point_write);
         }
     }
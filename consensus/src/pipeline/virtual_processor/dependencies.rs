@@ -0,0 +1,105 @@
+use kaspa_consensus_core::tx::{Transaction, TransactionId, TransactionOutpoint};
+use std::collections::HashSet;
+
+/// Marks the mergeset transactions whose UTXO validation depends on preceding merged blocks.
+///
+/// The transactions of a merged block are validated against the selected parent UTXO view composed with the diff of the
+/// merged blocks preceding it in consensus order. A transaction depends on these blocks if it spends an output created by
+/// one of their transactions, or an outpoint also spent by one of them. Any other transaction sees the exact same UTXO
+/// entries in the selected parent view, hence it can be validated concurrently with the transactions of all merged blocks.
+/// Body validation rejects chained transactions, so there are no dependencies between the transactions of a single block.
+pub(super) struct MergesetDependencies {
+    /// Per merged block, whether each of its transactions depends on preceding blocks. Coinbase transactions are never
+    /// validated and are marked as independent
+    dependent: Vec<Vec<bool>>,
+}
+
+impl MergesetDependencies {
+    /// Builds the dependencies of the transactions of `blocks`, given in consensus order with the selected parent first.
+    /// The selected parent coinbase is expected to precede all blocks since it is applied before them
+    pub fn new<'a>(selected_parent_coinbase_id: TransactionId, blocks: impl IntoIterator<Item = &'a [Transaction]>) -> Self {
+        let mut created_by: HashSet<TransactionId> = HashSet::from([selected_parent_coinbase_id]);
+        let mut spent: HashSet<TransactionOutpoint> = HashSet::new();
+        let mut dependent = Vec::new();
+        for txs in blocks {
+            dependent.push(
+                txs.iter()
+                    .enumerate()
+                    .map(|(i, tx)| {
+                        i > 0
+                            && tx.inputs.iter().any(|input| {
+                                created_by.contains(&input.previous_outpoint.transaction_id)
+                                    || spent.contains(&input.previous_outpoint)
+                            })
+                    })
+                    .collect(),
+            );
+            created_by.extend(txs.iter().map(|tx| tx.id()));
+            spent.extend(txs.iter().flat_map(|tx| tx.inputs.iter().map(|input| input.previous_outpoint)));
+        }
+        Self { dependent }
+    }
+
+    pub fn is_dependent(&self, block: usize, tx: usize) -> bool {
+        self.dependent[block][tx]
+    }
+
+    /// Returns the positions of all non-coinbase transactions with no dependencies as (block index, transaction index) pairs
+    pub fn independent(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.dependent.iter().enumerate().flat_map(|(block, txs)| {
+            txs.iter().enumerate().skip(1).filter(|(_, &dependent)| !dependent).map(move |(tx, _)| (block, tx))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, TransactionInput, TransactionOutput},
+    };
+
+    fn tx(inputs: &[TransactionOutpoint], payload: u8) -> Transaction {
+        Transaction::new(
+            0,
+            inputs.iter().map(|&outpoint| TransactionInput::new(outpoint, vec![], 0, 0)).collect(),
+            vec![TransactionOutput::new(1, ScriptPublicKey::default())],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![payload],
+        )
+    }
+
+    #[test]
+    fn test_mergeset_dependencies() {
+        let external = |i: u64| TransactionOutpoint::new(TransactionId::from_u64_word(1000 + i), 0);
+        let coinbase = |payload| tx(&[], payload);
+
+        let selected_parent_coinbase = coinbase(0);
+        let a = tx(&[external(0)], 1);
+        let b = tx(&[TransactionOutpoint::new(selected_parent_coinbase.id(), 0)], 2);
+        let selected_parent = vec![selected_parent_coinbase.clone(), a.clone(), b];
+
+        let spends_a = tx(&[TransactionOutpoint::new(a.id(), 0)], 3);
+        let double_spends_a_input = tx(&[external(0)], 4);
+        let unrelated = tx(&[external(1)], 5);
+        let merged = vec![coinbase(1), spends_a, double_spends_a_input, unrelated];
+
+        // Spends the same outpoint as an earlier transaction of its own block, which is not a dependency
+        let same_block_spender = tx(&[external(2)], 6);
+        let later = vec![coinbase(2), tx(&[external(2)], 7), same_block_spender];
+
+        let dependencies = MergesetDependencies::new(
+            selected_parent_coinbase.id(),
+            [selected_parent.as_slice(), merged.as_slice(), later.as_slice()],
+        );
+        assert!(!dependencies.is_dependent(0, 1));
+        assert!(dependencies.is_dependent(0, 2));
+        assert!(dependencies.is_dependent(1, 1));
+        assert!(dependencies.is_dependent(1, 2));
+        assert!(!dependencies.is_dependent(1, 3));
+        assert_eq!(dependencies.independent().collect::<Vec<_>>(), vec![(0, 1), (1, 3), (2, 1), (2, 2)]);
+    }
+}
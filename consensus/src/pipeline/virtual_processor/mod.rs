@@ -1,3 +1,4 @@
+mod dependencies;
 pub mod errors;
 mod processor;
 mod utxo_validation;
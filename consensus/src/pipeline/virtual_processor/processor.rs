@@ -17,6 +17,7 @@ use crate::{
             acceptance_data::{AcceptanceDataStoreReader, DbAcceptanceDataStore},
             block_transactions::{BlockTransactionsStoreReader, DbBlockTransactionsStore},
             block_window_cache::BlockWindowCacheStore,
+            chain_scores::{ChainBlockScores, ChainScoresStore, ChainScoresStoreReader, DbChainScoresStore},
             daa::DbDaaStore,
             depth::{DbDepthStore, DepthStoreReader},
             ghostdag::{DbGhostdagStore, GhostdagData, GhostdagStoreReader},
@@ -26,7 +27,7 @@ use crate::{
             pruning_utxoset::PruningUtxosetStores,
             reachability::DbReachabilityStore,
             relations::{DbRelationsStore, RelationsStoreReader},
-            selected_chain::{DbSelectedChainStore, SelectedChainStore},
+            selected_chain::{DbSelectedChainStore, SelectedChainStore, SelectedChainStoreReader},
             statuses::{DbStatusesStore, StatusesStore, StatusesStoreBatchExtensions, StatusesStoreReader},
             tips::{DbTipsStore, TipsStoreReader},
             utxo_diffs::{DbUtxoDiffsStore, UtxoDiffsStoreReader},
@@ -37,12 +38,14 @@ use crate::{
     },
     params::Params,
     pipeline::{
-        deps_manager::VirtualStateProcessingMessage, pruning_processor::processor::PruningProcessingMessage,
-        virtual_processor::utxo_validation::UtxoProcessingContext, ProcessingCounters,
+        deps_manager::VirtualStateProcessingMessage, priority::VirtualPriorityLane,
+        pruning_processor::processor::PruningProcessingMessage, virtual_processor::utxo_validation::UtxoProcessingContext,
+        ProcessingCounters, ProcessingStage,
     },
     processes::{
         coinbase::CoinbaseManager,
         ghostdag::ordering::SortableBlock,
+        mass::TxComputeMassCache,
         transaction_validator::{errors::TxResult, transaction_validator_populated::TxValidationFlags, TransactionValidator},
         window::WindowManager,
     },
@@ -53,7 +56,11 @@ use kaspa_consensus_core::{
     block::{BlockTemplate, MutableBlock, TemplateBuildMode, TemplateTransactionSelector},
     blockstatus::BlockStatus::{StatusDisqualifiedFromChain, StatusUTXOValid},
     coinbase::MinerData,
-    config::{genesis::GenesisBlock, params::ForkActivation},
+    config::{
+        constants::perf::PerfParams,
+        genesis::GenesisBlock,
+        params::{AssumeValid, ForkActivation},
+    },
     header::Header,
     merkle::calc_hash_merkle_root,
     pruning::PruningPointsList,
@@ -96,6 +103,7 @@ use std::{
     collections::{BinaryHeap, HashMap, VecDeque},
     ops::Deref,
     sync::{atomic::Ordering, Arc},
+    time::Instant,
 };
 
 pub struct VirtualStateProcessor {
@@ -106,6 +114,7 @@ pub struct VirtualStateProcessor {
 
     // Thread pool
     pub(super) thread_pool: Arc<ThreadPool>,
+    priority_lane: Arc<VirtualPriorityLane>,
 
     // DB
     db: Arc<DB>,
@@ -115,6 +124,8 @@ pub struct VirtualStateProcessor {
     pub(super) max_block_parents: u8,
     pub(super) mergeset_size_limit: u64,
     pub(super) pruning_depth: u64,
+    virtual_resolve_chunk_size: usize,
+    assume_valid: Option<AssumeValid>,
 
     // Stores
     pub(super) statuses_store: Arc<RwLock<DbStatusesStore>>,
@@ -127,6 +138,7 @@ pub struct VirtualStateProcessor {
     pub(super) body_tips_store: Arc<RwLock<DbTipsStore>>,
     pub(super) depth_store: Arc<DbDepthStore>,
     pub(super) selected_chain_store: Arc<RwLock<DbSelectedChainStore>>,
+    pub(super) chain_scores_store: Arc<RwLock<DbChainScoresStore>>,
 
     // Utxo-related stores
     pub(super) utxo_diffs_store: Arc<DbUtxoDiffsStore>,
@@ -155,6 +167,9 @@ pub struct VirtualStateProcessor {
     pub(super) block_window_cache_for_difficulty: Arc<BlockWindowCacheStore>,
     pub(super) block_window_cache_for_past_median_time: Arc<BlockWindowCacheStore>,
 
+    // Compute masses of validated mempool transactions, reused by block body validation
+    pub(super) tx_compute_mass_cache: TxComputeMassCache,
+
     // Pruning lock
     pruning_lock: SessionLock,
 
@@ -175,7 +190,9 @@ impl VirtualStateProcessor {
         pruning_sender: CrossbeamSender<PruningProcessingMessage>,
         pruning_receiver: CrossbeamReceiver<PruningProcessingMessage>,
         thread_pool: Arc<ThreadPool>,
+        priority_lane: Arc<VirtualPriorityLane>,
         params: &Params,
+        perf_params: &PerfParams,
         db: Arc<DB>,
         storage: &Arc<ConsensusStorage>,
         services: &Arc<ConsensusServices>,
@@ -188,11 +205,14 @@ impl VirtualStateProcessor {
             pruning_sender,
             pruning_receiver,
             thread_pool,
+            priority_lane,
 
             genesis: params.genesis.clone(),
             max_block_parents: params.max_block_parents,
             mergeset_size_limit: params.mergeset_size_limit,
             pruning_depth: params.pruning_depth,
+            virtual_resolve_chunk_size: perf_params.virtual_resolve_chunk_size,
+            assume_valid: params.assume_valid,
 
             db,
             statuses_store: storage.statuses_store.clone(),
@@ -205,6 +225,7 @@ impl VirtualStateProcessor {
             body_tips_store: storage.body_tips_store.clone(),
             depth_store: storage.depth_store.clone(),
             selected_chain_store: storage.selected_chain_store.clone(),
+            chain_scores_store: storage.chain_scores_store.clone(),
             utxo_diffs_store: storage.utxo_diffs_store.clone(),
             utxo_multisets_store: storage.utxo_multisets_store.clone(),
             acceptance_data_store: storage.acceptance_data_store.clone(),
@@ -214,6 +235,7 @@ impl VirtualStateProcessor {
 
             block_window_cache_for_difficulty: storage.block_window_cache_for_difficulty.clone(),
             block_window_cache_for_past_median_time: storage.block_window_cache_for_past_median_time.clone(),
+            tx_compute_mass_cache: storage.tx_compute_mass_cache.clone(),
 
             ghostdag_manager: services.ghostdag_manager.clone(),
             reachability_service: services.reachability_service.clone(),
@@ -245,8 +267,14 @@ impl VirtualStateProcessor {
 
             let messages: Vec<VirtualStateProcessingMessage> = std::iter::once(msg).chain(self.receiver.try_iter()).collect();
             trace!("virtual processor received {} tasks", messages.len());
+            let process_count = messages.iter().filter(|msg| msg.is_processing_message()).count();
+            self.counters.virtual_queue_len.fetch_sub(process_count as u64, Ordering::Relaxed);
 
-            self.resolve_virtual();
+            {
+                // Hold back excess header processing while resolving
+                let _lane_guard = self.priority_lane.enter_virtual();
+                self.resolve_virtual();
+            }
 
             let statuses_read = self.statuses_store.read();
             for msg in messages {
@@ -265,31 +293,15 @@ impl VirtualStateProcessor {
     }
 
     fn resolve_virtual(self: &Arc<Self>) {
+        // Advance virtual along the heaviest chain in bounded chunks first, so that the accumulated
+        // UTXO diff held in memory never spans more than `virtual_resolve_chunk_size` chain blocks
+        while self.resolve_virtual_chunk() {}
+
         let pruning_point = self.pruning_point_store.read().pruning_point().unwrap();
         let virtual_read = self.virtual_stores.upgradable_read();
         let prev_state = virtual_read.state.get().unwrap();
         let finality_point = self.virtual_finality_point(&prev_state.ghostdag_data, pruning_point);
-
-        // PRUNE SAFETY: in order to avoid locking the prune lock throughout virtual resolving we make sure
-        // to only process blocks in the future of the finality point (F) which are never pruned (since finality depth << pruning depth).
-        // This is justified since:
-        //      1. Tips which are not in the future of F definitely don't have F on their chain
-        //         hence cannot become the next sink (due to finality violation).
-        //      2. Such tips cannot be merged by virtual since they are violating the merge depth
-        //         bound (merge depth <= finality depth).
-        // (both claims are true by induction for any block in their past as well)
-        let prune_guard = self.pruning_lock.blocking_read();
-        let tips = self
-            .body_tips_store
-            .read()
-            .get()
-            .unwrap()
-            .read()
-            .iter()
-            .copied()
-            .filter(|&h| self.reachability_service.is_dag_ancestor_of(finality_point, h))
-            .collect_vec();
-        drop(prune_guard);
+        let tips = self.virtual_tip_candidates(finality_point);
         let prev_sink = prev_state.ghostdag_data.selected_parent;
         let mut accumulated_diff = prev_state.utxo_diff.clone().to_reversed();
 
@@ -315,7 +327,115 @@ impl VirtualStateProcessor {
             )
             .expect("all possible rule errors are unexpected here");
 
-        let compact_sink_ghostdag_data = if let Some(sink_ghostdag_data) = Lazy::get(&sink_ghostdag_data) {
+        self.notification_root
+            .notify(Notification::NewBlockTemplate(NewBlockTemplateNotification {}))
+            .expect("expecting an open unbounded channel");
+        self.publish_virtual_state_change(new_sink, Lazy::get(&sink_ghostdag_data), &new_virtual_state, accumulated_diff, chain_path);
+    }
+
+    /// Attempts to advance virtual by a single chunk of `virtual_resolve_chunk_size` chain blocks towards the heaviest
+    /// tip, committing the resulting intermediate virtual state (pointing at the chunk's top chain block) to the DB.
+    ///
+    /// Returns `false` if the remaining chain path is short enough to be resolved at once, or if a UTXO-disqualified
+    /// block was encountered along the way. In both cases resolution should proceed with the full sink search.
+    fn resolve_virtual_chunk(&self) -> bool {
+        let chunk_size = self.virtual_resolve_chunk_size;
+        if chunk_size == 0 {
+            return false;
+        }
+
+        let pruning_point = self.pruning_point_store.read().pruning_point().unwrap();
+        let virtual_read = self.virtual_stores.upgradable_read();
+        let prev_state = virtual_read.state.get().unwrap();
+        let finality_point = self.virtual_finality_point(&prev_state.ghostdag_data, pruning_point);
+        let tips = self.virtual_tip_candidates(finality_point);
+        if tips.is_empty() {
+            return false;
+        }
+
+        // Chains which do not agree with the finality point are left for the sink search algorithm to handle
+        let heaviest_tip = self.ghostdag_manager.find_selected_parent(tips);
+        if !self.reachability_service.is_chain_ancestor_of(finality_point, heaviest_tip) {
+            return false;
+        }
+
+        let prev_sink = prev_state.ghostdag_data.selected_parent;
+        let mut chain_path = self.dag_traversal_manager.calculate_chain_path(prev_sink, heaviest_tip, Some(chunk_size + 1));
+        if chain_path.added.len() <= chunk_size {
+            return false;
+        }
+        chain_path.added.truncate(chunk_size);
+        let chunk_sink = *chain_path.added.last().unwrap();
+
+        let mut accumulated_diff = prev_state.utxo_diff.clone().to_reversed();
+        if self.calculate_utxo_state_relatively(&virtual_read, &mut accumulated_diff, prev_sink, chunk_sink) != chunk_sink {
+            // The chunk's chain contains a disqualified block, so the heaviest tip is no longer a sink candidate
+            return false;
+        }
+
+        let virtual_parents = vec![chunk_sink];
+        let virtual_ghostdag_data = self.ghostdag_manager.ghostdag(&virtual_parents);
+        let sink_multiset = self.utxo_multisets_store.get(chunk_sink).unwrap();
+        let sink_ghostdag_data = Lazy::new(|| self.ghostdag_store.get_data(chunk_sink).unwrap());
+        self.cache_sink_windows(chunk_sink, prev_sink, &sink_ghostdag_data);
+
+        let new_virtual_state = self
+            .calculate_and_commit_virtual_state(
+                virtual_read,
+                virtual_parents,
+                virtual_ghostdag_data,
+                sink_multiset,
+                &mut accumulated_diff,
+                &chain_path,
+            )
+            .expect("all possible rule errors are unexpected here");
+
+        debug!(
+            "VIRTUAL PROCESSOR, flushed an intermediate virtual state at chain block {} (DAA score {})",
+            chunk_sink, new_virtual_state.daa_score
+        );
+        self.publish_virtual_state_change(
+            chunk_sink,
+            Lazy::get(&sink_ghostdag_data),
+            &new_virtual_state,
+            accumulated_diff,
+            chain_path,
+        );
+        true
+    }
+
+    /// Returns the body tips which are in the future of `finality_point` and can thus be considered by virtual resolution
+    fn virtual_tip_candidates(&self, finality_point: Hash) -> Vec<Hash> {
+        // PRUNE SAFETY: in order to avoid locking the prune lock throughout virtual resolving we make sure
+        // to only process blocks in the future of the finality point (F) which are never pruned (since finality depth << pruning depth).
+        // This is justified since:
+        //      1. Tips which are not in the future of F definitely don't have F on their chain
+        //         hence cannot become the next sink (due to finality violation).
+        //      2. Such tips cannot be merged by virtual since they are violating the merge depth
+        //         bound (merge depth <= finality depth).
+        // (both claims are true by induction for any block in their past as well)
+        let _prune_guard = self.pruning_lock.blocking_read();
+        self.body_tips_store
+            .read()
+            .get()
+            .unwrap()
+            .read()
+            .iter()
+            .copied()
+            .filter(|&h| self.reachability_service.is_dag_ancestor_of(finality_point, h))
+            .collect_vec()
+    }
+
+    /// Updates the pruning processor and emits the notifications following a committed virtual state change
+    fn publish_virtual_state_change(
+        &self,
+        new_sink: Hash,
+        sink_ghostdag_data: Option<&Arc<GhostdagData>>,
+        new_virtual_state: &VirtualState,
+        accumulated_diff: UtxoDiff,
+        chain_path: ChainPath,
+    ) {
+        let compact_sink_ghostdag_data = if let Some(sink_ghostdag_data) = sink_ghostdag_data {
             // If we had to retrieve the full data, we convert it to compact
             sink_ghostdag_data.to_compact()
         } else {
@@ -332,9 +452,6 @@ impl VirtualStateProcessor {
         // Emit notifications
         let accumulated_diff = Arc::new(accumulated_diff);
         let virtual_parents = Arc::new(new_virtual_state.parents.clone());
-        self.notification_root
-            .notify(Notification::NewBlockTemplate(NewBlockTemplateNotification {}))
-            .expect("expecting an open unbounded channel");
         self.notification_root
             .notify(Notification::UtxosChanged(UtxosChangedNotification::new(accumulated_diff, virtual_parents)))
             .expect("expecting an open unbounded channel");
@@ -407,7 +524,7 @@ impl VirtualStateProcessor {
         for (selected_parent, current) in self.reachability_service.forward_chain_iterator(split_point, to, true).tuple_windows() {
             if selected_parent != diff_point {
                 // This indicates that the selected parent is disqualified, propagate up and continue
-                self.statuses_store.write().set(current, StatusDisqualifiedFromChain).unwrap();
+                self.statuses_store.write().set(current, StatusDisqualifiedFromChain).unwrap_or_halt();
                 chain_disqualified_counter += 1;
                 continue;
             }
@@ -430,14 +547,26 @@ impl VirtualStateProcessor {
                     let selected_parent_multiset_hash = self.utxo_multisets_store.get(selected_parent).unwrap();
                     let selected_parent_utxo_view = (&stores.utxo_set).compose(&*diff);
 
-                    let mut ctx = UtxoProcessingContext::new(mergeset_data.into(), selected_parent_multiset_hash);
+                    let mut ctx = UtxoProcessingContext::new(mergeset_data.clone().into(), selected_parent_multiset_hash.clone());
+                    ctx.assumed_valid = self.is_assumed_valid(current);
 
+                    let start = Instant::now();
                     self.calculate_utxo_state(&mut ctx, &selected_parent_utxo_view, pov_daa_score);
-                    let res = self.verify_expected_utxo_state(&mut ctx, &selected_parent_utxo_view, &header);
+                    let mut res = self.verify_expected_utxo_state(&mut ctx, &selected_parent_utxo_view, &header);
+
+                    if res.is_err() && ctx.assumed_valid {
+                        // Skipping script checks might have led to accepting a mergeset transaction with invalid scripts,
+                        // so the block is fully validated before it is disqualified
+                        warn!("Assumed valid block {} failed UTXO validation, validating it fully", current);
+                        ctx = UtxoProcessingContext::new(mergeset_data.into(), selected_parent_multiset_hash);
+                        self.calculate_utxo_state(&mut ctx, &selected_parent_utxo_view, pov_daa_score);
+                        res = self.verify_expected_utxo_state(&mut ctx, &selected_parent_utxo_view, &header);
+                    }
+                    self.counters.record_latency(ProcessingStage::UtxoResolution, start.elapsed());
 
                     if let Err(rule_error) = res {
                         info!("Block {} is disqualified from virtual chain: {}", current, rule_error);
-                        self.statuses_store.write().set(current, StatusDisqualifiedFromChain).unwrap();
+                        self.statuses_store.write().set(current, StatusDisqualifiedFromChain).unwrap_or_halt();
                         chain_disqualified_counter += 1;
                     } else {
                         debug!("VIRTUAL PROCESSOR, UTXO validated for {current}");
@@ -460,17 +589,33 @@ impl VirtualStateProcessor {
         if chain_disqualified_counter > 0 {
             self.counters.chain_disqualified_counts.fetch_add(chain_disqualified_counter, Ordering::Relaxed);
         }
+        // Deep reorgs grow the accumulated diff with entries which later cancel out, so release the memory they held
+        diff.shrink_if_sparse();
 
         diff_point
     }
 
+    /// Returns whether `block` is strictly in the selected chain past of the assume-valid checkpoint. The checkpoint is
+    /// recognized only once its header was processed (with the expected DAA score)
+    fn is_assumed_valid(&self, block: Hash) -> bool {
+        let Some(assume_valid) = self.assume_valid else {
+            return false;
+        };
+        block != assume_valid.hash
+            && self.reachability_service.has_reachability_data(assume_valid.hash)
+            && self.headers_store.get_daa_score(assume_valid.hash).is_ok_and(|daa_score| daa_score == assume_valid.daa_score)
+            && self.reachability_service.is_chain_ancestor_of(block, assume_valid.hash)
+    }
+
     fn commit_utxo_state(&self, current: Hash, mergeset_diff: UtxoDiff, multiset: MuHash, acceptance_data: AcceptanceData) {
         let mut batch = WriteBatch::default();
-        self.utxo_diffs_store.insert_batch(&mut batch, current, Arc::new(mergeset_diff)).unwrap();
-        self.utxo_multisets_store.insert_batch(&mut batch, current, multiset).unwrap();
-        self.acceptance_data_store.insert_batch(&mut batch, current, Arc::new(acceptance_data)).unwrap();
-        let write_guard = self.statuses_store.set_batch(&mut batch, current, StatusUTXOValid).unwrap();
-        self.db.write(batch).unwrap();
+        self.utxo_diffs_store.insert_batch(&mut batch, current, Arc::new(mergeset_diff)).unwrap_or_halt();
+        self.utxo_multisets_store.insert_batch(&mut batch, current, multiset).unwrap_or_halt();
+        self.acceptance_data_store.insert_batch(&mut batch, current, Arc::new(acceptance_data)).unwrap_or_halt();
+        let write_guard = self.statuses_store.set_batch(&mut batch, current, StatusUTXOValid).unwrap_or_halt();
+        let start = Instant::now();
+        self.db.write_batch(batch).unwrap_or_halt();
+        self.counters.record_latency(ProcessingStage::Commit, start.elapsed());
         // Calling the drops explicitly after the batch is written in order to avoid possible errors.
         drop(write_guard);
     }
@@ -542,24 +687,77 @@ impl VirtualStateProcessor {
         let mut batch = WriteBatch::default();
         let mut virtual_write = RwLockUpgradableReadGuard::upgrade(virtual_read);
         let mut selected_chain_write = self.selected_chain_store.write();
+        let mut chain_scores_write = self.chain_scores_store.write();
 
         // Apply the accumulated diff to the virtual UTXO set
         virtual_write.utxo_set.write_diff_batch(&mut batch, accumulated_diff).unwrap();
 
         // Update virtual state
-        virtual_write.state.set_batch(&mut batch, new_virtual_state).unwrap();
+        virtual_write.state.set_batch(&mut batch, new_virtual_state).unwrap_or_halt();
+
+        // Update the sparse chain score indexes. Must precede the selected chain update since the split point is read from it
+        self.update_chain_scores(&mut batch, &mut chain_scores_write, &selected_chain_write, chain_path);
 
         // Update the virtual selected chain
         selected_chain_write.apply_changes(&mut batch, chain_path).unwrap();
 
         // Flush the batch changes
-        self.db.write(batch).unwrap();
+        let start = Instant::now();
+        self.db.write_batch(batch).unwrap_or_halt();
+        self.counters.record_latency(ProcessingStage::Commit, start.elapsed());
 
         // Calling the drops explicitly after the batch is written in order to avoid possible errors.
         drop(virtual_write);
+        drop(chain_scores_write);
         drop(selected_chain_write);
     }
 
+    /// Updates the sparse chain score indexes with the changes of `chain_path`, given the selected chain prior to the changes
+    fn update_chain_scores(
+        &self,
+        batch: &mut WriteBatch,
+        chain_scores_write: &mut DbChainScoresStore,
+        selected_chain: &DbSelectedChainStore,
+        chain_path: &ChainPath,
+    ) {
+        if chain_path.added.is_empty() && chain_path.removed.is_empty() {
+            return;
+        }
+        let scores = |hash| ChainBlockScores::from(&self.headers_store.get_compact_header_data(hash).unwrap());
+        let (tip_index, tip) = selected_chain.get_tip().unwrap();
+        let split_index = tip_index - chain_path.removed.len() as u64;
+        let split = selected_chain.get_by_index(split_index).unwrap();
+        let added = chain_path.added.iter().enumerate().map(|(i, &hash)| (split_index + 1 + i as u64, scores(hash))).collect_vec();
+        chain_scores_write.apply_changes(batch, scores(split), scores(tip), &added).unwrap();
+    }
+
+    /// Builds the sparse chain score indexes from the pruning point up if they are missing, which is the
+    /// case for databases created before the indexes were introduced
+    fn init_chain_scores(&self) {
+        let selected_chain_read = self.selected_chain_store.read();
+        let mut chain_scores_write = self.chain_scores_store.write();
+        if chain_scores_write.get_base_index().unwrap_option().is_some() {
+            return;
+        }
+        // An empty selected chain is initialized along with the indexes, see `process_genesis`
+        let Some((tip_index, _)) = selected_chain_read.get_tip().unwrap_option() else {
+            return;
+        };
+        let pruning_point = self.pruning_point_store.read().pruning_point().unwrap();
+        let base_index = selected_chain_read.get_by_hash(pruning_point).unwrap_option().unwrap_or(tip_index);
+        let scores = |index| {
+            ChainBlockScores::from(
+                &self.headers_store.get_compact_header_data(selected_chain_read.get_by_index(index).unwrap()).unwrap(),
+            )
+        };
+        let base = scores(base_index);
+        let added = (base_index + 1..=tip_index).map(|index| (index, scores(index))).collect_vec();
+        let mut batch = WriteBatch::default();
+        chain_scores_write.apply_changes(&mut batch, base, base, &added).unwrap();
+        chain_scores_write.set_base_index(&mut batch, base_index).unwrap_or_halt();
+        self.db.write_batch(batch).unwrap_or_halt();
+    }
+
     /// Caches the DAA and Median time windows of the sink block (if needed). Following, virtual's window calculations will
     /// naturally hit the cache finding the sink's windows and building upon them.
     fn cache_sink_windows(&self, new_sink: Hash, prev_sink: Hash, sink_ghostdag_data: &impl Deref<Target = Arc<GhostdagData>>) {
@@ -807,6 +1005,7 @@ impl VirtualStateProcessor {
         args: &TransactionValidationArgs,
     ) -> TxResult<()> {
         self.transaction_validator.validate_tx_in_isolation(&mutable_tx.tx)?;
+        self.transaction_validator.check_transaction_payload(&mutable_tx.tx, virtual_daa_score)?;
         self.transaction_validator.utxo_free_tx_validation(&mutable_tx.tx, virtual_daa_score, virtual_past_median_time)?;
         self.validate_mempool_transaction_in_utxo_context(mutable_tx, virtual_utxo_view, virtual_daa_score, args)?;
         Ok(())
@@ -896,6 +1095,7 @@ impl VirtualStateProcessor {
         // No need to validate the transaction in isolation since we rely on the mining manager to submit transactions
         // which were previously validated through `validate_mempool_transaction_and_populate`, hence we only perform
         // in-context validations
+        self.transaction_validator.check_transaction_payload(tx, virtual_state.daa_score)?;
         self.transaction_validator.utxo_free_tx_validation(tx, virtual_state.daa_score, virtual_state.past_median_time)?;
         let ValidatedTransaction { calculated_fee, .. } =
             self.validate_transaction_in_utxo_context(tx, utxo_view, virtual_state.daa_score, TxValidationFlags::Full)?;
@@ -1066,13 +1266,14 @@ impl VirtualStateProcessor {
             let mut pruning_utxoset_write = self.pruning_utxoset_stores.write();
             let mut batch = WriteBatch::default();
             self.past_pruning_points_store.insert_batch(&mut batch, 0, self.genesis.hash).unwrap_or_exists();
-            pruning_point_write.set_batch(&mut batch, self.genesis.hash, self.genesis.hash, 0).unwrap();
+            pruning_point_write.set_batch(&mut batch, self.genesis.hash, self.genesis.hash, 0).unwrap_or_halt();
             pruning_point_write.set_history_root(&mut batch, self.genesis.hash).unwrap();
             pruning_utxoset_write.set_utxoset_position(&mut batch, self.genesis.hash).unwrap();
-            self.db.write(batch).unwrap();
+            self.db.write_batch(batch).unwrap_or_halt();
             drop(pruning_point_write);
             drop(pruning_utxoset_write);
         }
+        self.init_chain_scores();
     }
 
     /// Initializes UTXO state of genesis and points virtual at genesis.
@@ -1085,7 +1286,8 @@ impl VirtualStateProcessor {
         let mut batch = WriteBatch::default();
         let mut selected_chain_write = self.selected_chain_store.write();
         selected_chain_write.init_with_pruning_point(&mut batch, self.genesis.hash).unwrap();
-        self.db.write(batch).unwrap();
+        self.chain_scores_store.write().set_base_index(&mut batch, 0).unwrap_or_halt();
+        self.db.write_batch(batch).unwrap_or_halt();
         drop(selected_chain_write);
 
         // Init virtual state
@@ -1118,7 +1320,7 @@ impl VirtualStateProcessor {
             let mut batch = WriteBatch::default();
             let mut pruning_utxoset_write = self.pruning_utxoset_stores.write();
             pruning_utxoset_write.set_utxoset_position(&mut batch, new_pruning_point).unwrap();
-            self.db.write(batch).unwrap();
+            self.db.write_batch(batch).unwrap_or_halt();
             drop(pruning_utxoset_write);
         }
 
@@ -1152,10 +1354,10 @@ impl VirtualStateProcessor {
             // Submit partial UTXO state for the pruning point.
             // Note we only have and need the multiset; acceptance data and utxo-diff are irrelevant.
             let mut batch = WriteBatch::default();
-            self.utxo_multisets_store.set_batch(&mut batch, new_pruning_point, imported_utxo_multiset.clone()).unwrap();
+            self.utxo_multisets_store.set_batch(&mut batch, new_pruning_point, imported_utxo_multiset.clone()).unwrap_or_halt();
 
-            let statuses_write = self.statuses_store.set_batch(&mut batch, new_pruning_point, StatusUTXOValid).unwrap();
-            self.db.write(batch).unwrap();
+            let statuses_write = self.statuses_store.set_batch(&mut batch, new_pruning_point, StatusUTXOValid).unwrap_or_halt();
+            self.db.write_batch(batch).unwrap_or_halt();
             drop(statuses_write);
         }
 
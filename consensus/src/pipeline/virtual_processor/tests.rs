@@ -1,15 +1,31 @@
-use crate::{consensus::test_consensus::TestConsensus, model::services::reachability::ReachabilityService};
+use crate::{
+    consensus::test_consensus::TestConsensus,
+    model::{services::reachability::ReachabilityService, stores::virtual_state::VirtualStateStoreReader},
+};
 use kaspa_consensus_core::{
     api::ConsensusApi,
     block::{Block, BlockTemplate, MutableBlock, TemplateBuildMode, TemplateTransactionSelector},
     blockhash,
     blockstatus::BlockStatus,
     coinbase::MinerData,
-    config::{params::MAINNET_PARAMS, ConfigBuilder},
-    tx::{ScriptPublicKey, ScriptVec, Transaction},
+    config::{
+        params::{AssumeValid, ForkActivation, DEVNET_PARAMS, MAINNET_PARAMS},
+        ConfigBuilder,
+    },
+    constants::SOMPI_PER_KASPA,
+    header::Header,
+    muhash::MuHashExtensions,
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
     BlockHashSet,
 };
 use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use kaspa_txscript::{
+    opcodes::codes::{Op0, OpTxInputSpk},
+    pay_to_script_hash_script,
+    script_builder::ScriptBuilder,
+};
 use std::{collections::VecDeque, thread::JoinHandle};
 
 struct OnetimeTxSelector {
@@ -296,6 +312,182 @@ async fn double_search_disqualified_test() {
     ctx.assert_tips_num(1);
 }
 
+#[tokio::test]
+async fn chunked_virtual_resolve_test() {
+    let new_consensus = |chunk_size: usize| {
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .apply_args(|cfg| cfg.perf.virtual_resolve_chunk_size = chunk_size)
+            .build();
+        TestConsensus::new(&config)
+    };
+
+    // Build a chain of 10 blocks along with a heavier competing chain of 25 blocks over genesis. Once the competing chain
+    // becomes heavier, virtual is resolved along a chain path of 11 blocks, which spans several chunks
+    let source = new_consensus(0);
+    let wait_handles = source.init();
+    let genesis = source.params().genesis.hash;
+    let mut blocks = Vec::new();
+    for (first, len) in [(1u64, 10u64), (101, 25)] {
+        let mut parent = genesis;
+        for hash in (first..first + len).map(Hash::from_u64_word) {
+            let block = source.build_utxo_valid_block_with_parents(hash, vec![parent], new_miner_data(), vec![]);
+            source.validate_and_insert_block(block.clone().to_immutable()).virtual_state_task.await.unwrap();
+            blocks.push(block);
+            parent = hash;
+        }
+    }
+    source.shutdown(wait_handles);
+
+    let resolve = |chunk_size: usize| {
+        let blocks = blocks.clone();
+        async move {
+            let consensus = new_consensus(chunk_size);
+            let wait_handles = consensus.init();
+            for block in blocks {
+                consensus.validate_and_insert_block(block.to_immutable()).virtual_state_task.await.unwrap();
+            }
+            let sink = consensus.get_sink();
+            let virtual_stores = consensus.virtual_stores();
+            let virtual_read = virtual_stores.read();
+            let state = virtual_read.state.get().unwrap();
+            let utxo_set = virtual_read.utxo_set.iterator().map(|res| res.unwrap()).collect::<Vec<_>>();
+            let snapshot = (
+                sink,
+                state.parents.clone(),
+                state.ghostdag_data.selected_parent,
+                state.ghostdag_data.blue_work,
+                state.daa_score,
+                state.bits,
+                state.past_median_time,
+                state.multiset.clone().finalize(),
+                state.utxo_diff.clone(),
+                state.accepted_tx_ids.clone(),
+                utxo_set,
+            );
+            drop(virtual_read);
+            consensus.shutdown(wait_handles);
+            snapshot
+        }
+    };
+
+    let expected = resolve(0).await;
+    assert_eq!(expected.0, Hash::from_u64_word(125));
+    for chunk_size in [2, 3] {
+        assert_eq!(resolve(chunk_size).await, expected, "chunk size {chunk_size}");
+    }
+}
+
+/// A UTXO locked by a P2SH script using a KIP-10 introspection opcode, hence spendable only where KIP-10 is active.
+/// Returns the UTXO along with a transaction spending it
+fn kip10_utxo_and_spending_tx() -> ((TransactionOutpoint, UtxoEntry), Transaction) {
+    let redeem_script = ScriptBuilder::new().add_op(Op0).unwrap().add_op(OpTxInputSpk).unwrap().drain();
+    let spk = pay_to_script_hash_script(&redeem_script);
+    let utxo = (
+        TransactionOutpoint::new(1.into(), 0),
+        UtxoEntry { amount: SOMPI_PER_KASPA, script_public_key: spk.clone(), block_daa_score: 0, is_coinbase: false },
+    );
+    let mut tx = Transaction::new(
+        0,
+        vec![TransactionInput::new(utxo.0, ScriptBuilder::new().add_data(&redeem_script).unwrap().drain(), 0, 0)],
+        vec![TransactionOutput::new(utxo.1.amount - 5000, spk)],
+        0,
+        SUBNETWORK_ID_NATIVE,
+        0,
+        vec![],
+    );
+    tx.finalize();
+    (utxo, tx)
+}
+
+/// Returns a test consensus whose genesis UTXO set holds the UTXO of [`kip10_utxo_and_spending_tx`]
+fn new_kip10_utxo_consensus(kip10_active: bool, assume_valid: Option<AssumeValid>) -> TestConsensus {
+    let (utxo, _) = kip10_utxo_and_spending_tx();
+    let config = ConfigBuilder::new(DEVNET_PARAMS)
+        .skip_proof_of_work()
+        .apply_args(|cfg| {
+            let mut genesis_multiset = MuHash::new();
+            genesis_multiset.add_utxo(&utxo.0, &utxo.1);
+            cfg.params.genesis.utxo_commitment = genesis_multiset.finalize();
+            let genesis_header: Header = (&cfg.params.genesis).into();
+            cfg.params.genesis.hash = genesis_header.hash;
+        })
+        .edit_consensus_params(|p| {
+            p.kip10_activation = if kip10_active { ForkActivation::always() } else { ForkActivation::never() };
+            p.assume_valid = assume_valid;
+        })
+        .build();
+    let consensus = TestConsensus::new(&config);
+    let mut genesis_multiset = MuHash::new();
+    consensus.append_imported_pruning_point_utxos(&[utxo], &mut genesis_multiset);
+    consensus.import_pruning_point_utxo_set(config.genesis.hash, genesis_multiset).unwrap();
+    consensus
+}
+
+/// Feeds `blocks` to `consensus` as a syncing node would, i.e., all headers first and then all bodies
+async fn sync_headers_first(consensus: &TestConsensus, blocks: &[MutableBlock]) {
+    for block in blocks.iter() {
+        consensus.validate_and_insert_block(MutableBlock::from_header(block.header.clone()).to_immutable()).block_task.await.unwrap();
+    }
+    for block in blocks.iter() {
+        consensus.validate_and_insert_block(block.clone().to_immutable()).virtual_state_task.await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn assume_valid_test() {
+    // Build a chain whose block 2 spends the KIP-10 UTXO, along with a side block over genesis, in a consensus where
+    // KIP-10 is active. Block 3 accepts the spend, so in consensus instances where KIP-10 is inactive the spend is valid
+    // only if scripts are not verified for block 3
+    let source = new_kip10_utxo_consensus(true, None);
+    let wait_handles = source.init();
+    let (_, spending_tx) = kip10_utxo_and_spending_tx();
+    let genesis = source.params().genesis.hash;
+    let mut blocks = Vec::new();
+    for i in 1..=5u64 {
+        let parent = if i == 1 { genesis } else { (i - 1).into() };
+        let txs = if i == 2 { vec![spending_tx.clone()] } else { vec![] };
+        let block = source.build_utxo_valid_block_with_parents(i.into(), vec![parent], new_miner_data(), txs);
+        let status = source.validate_and_insert_block(block.clone().to_immutable()).virtual_state_task.await.unwrap();
+        assert_eq!(status, BlockStatus::StatusUTXOValid);
+        blocks.push(block);
+    }
+    let side_block = source.build_utxo_valid_block_with_parents(100.into(), vec![genesis], new_miner_data(), vec![]);
+    source.validate_and_insert_block(side_block.clone().to_immutable()).virtual_state_task.await.unwrap();
+    blocks.push(side_block.clone());
+    source.shutdown(wait_handles);
+    let checkpoint = |block: &MutableBlock| Some(AssumeValid::new(block.header.hash, block.header.daa_score));
+
+    // Scripts of the selected chain past of the checkpoint are skipped, so the spend is accepted
+    let consensus = new_kip10_utxo_consensus(false, checkpoint(&blocks[3]));
+    let wait_handles = consensus.init();
+    sync_headers_first(&consensus, &blocks).await;
+    assert_eq!(consensus.get_sink(), 5.into());
+    assert_eq!(consensus.get_block_status(3.into()), Some(BlockStatus::StatusUTXOValid));
+    assert!(consensus
+        .get_block_acceptance_data(3.into())
+        .unwrap()
+        .iter()
+        .any(|mergeset| mergeset.accepted_transactions.iter().any(|entry| entry.transaction_id == spending_tx.id())));
+    consensus.shutdown(wait_handles);
+
+    // Scripts are fully verified when the checkpoint is not in the selected chain future of the block, so the block
+    // accepting the spend is disqualified along with its chain, leaving the side block as the only valid tip
+    let consensus = new_kip10_utxo_consensus(false, checkpoint(&side_block));
+    let wait_handles = consensus.init();
+    sync_headers_first(&consensus, &blocks).await;
+    assert_eq!(consensus.get_block_status(3.into()), Some(BlockStatus::StatusDisqualifiedFromChain));
+    assert_eq!(consensus.get_sink(), side_block.header.hash);
+    consensus.shutdown(wait_handles);
+
+    // Same without any checkpoint
+    let consensus = new_kip10_utxo_consensus(false, None);
+    let wait_handles = consensus.init();
+    sync_headers_first(&consensus, &blocks).await;
+    assert_eq!(consensus.get_block_status(3.into()), Some(BlockStatus::StatusDisqualifiedFromChain));
+    consensus.shutdown(wait_handles);
+}
+
 fn new_miner_data() -> MinerData {
     let secp = secp256k1::Secp256k1::new();
     let mut rng = rand::thread_rng();
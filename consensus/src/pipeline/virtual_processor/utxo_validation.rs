@@ -1,4 +1,4 @@
-use super::VirtualStateProcessor;
+use super::{dependencies::MergesetDependencies, VirtualStateProcessor};
 use crate::{
     errors::{
         BlockProcessResult,
@@ -19,6 +19,7 @@ use kaspa_consensus_core::{
     header::Header,
     mass::Kip9Version,
     muhash::MuHashExtensions,
+    sompi::Sompi,
     tx::{MutableTransaction, PopulatedTransaction, Transaction, TransactionId, ValidatedTransaction, VerifiableTransaction},
     utxo::{
         utxo_diff::UtxoDiff,
@@ -31,9 +32,10 @@ use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
 use kaspa_utils::refs::Refs;
 
+use itertools::Itertools;
 use rayon::prelude::*;
 use smallvec::{smallvec, SmallVec};
-use std::{iter::once, ops::Deref};
+use std::{iter::once, ops::Deref, sync::Arc};
 
 /// A context for processing the UTXO state of a block with respect to its selected parent.
 /// Note this can also be the virtual block.
@@ -44,6 +46,9 @@ pub(super) struct UtxoProcessingContext<'a> {
     pub accepted_tx_ids: Vec<TransactionId>,
     pub mergeset_acceptance_data: Vec<MergesetBlockAcceptanceData>,
     pub mergeset_rewards: BlockHashMap<BlockRewardData>,
+    /// Indicates that the block is in the selected chain past of the assume-valid checkpoint, in which case
+    /// transaction scripts are not verified
+    pub assumed_valid: bool,
 }
 
 impl<'a> UtxoProcessingContext<'a> {
@@ -56,6 +61,7 @@ impl<'a> UtxoProcessingContext<'a> {
             accepted_tx_ids: Vec::with_capacity(1), // We expect at least the selected parent coinbase tx
             mergeset_rewards: BlockHashMap::with_capacity(mergeset_size),
             mergeset_acceptance_data: Vec::with_capacity(mergeset_size),
+            assumed_valid: false,
         }
     }
 
@@ -80,33 +86,51 @@ impl VirtualStateProcessor {
         let validated_coinbase_id = validated_coinbase.id();
         ctx.accepted_tx_ids.push(validated_coinbase_id);
 
-        for (i, (merged_block, txs)) in once((ctx.selected_parent(), selected_parent_transactions))
+        let mergeset = once((ctx.selected_parent(), selected_parent_transactions))
             .chain(
                 ctx.ghostdag_data
                     .consensus_ordered_mergeset_without_selected_parent(self.ghostdag_store.deref())
                     .map(|b| (b, self.block_transactions_store.get(b).unwrap())),
             )
-            .enumerate()
-        {
+            .collect_vec();
+
+        // Transactions which do not depend on preceding merged blocks are validated in a single concurrent pass over
+        // the whole mergeset, so that the parallelism is not bounded by the size of each merged block
+        let dependencies = MergesetDependencies::new(validated_coinbase_id, mergeset.iter().map(|(_, txs)| txs.as_slice()));
+        let mut prevalidated = self.validate_independent_mergeset_transactions(
+            &mergeset,
+            &dependencies,
+            selected_parent_utxo_view,
+            pov_daa_score,
+            ctx.assumed_valid,
+        );
+
+        for (i, (merged_block, txs)) in mergeset.iter().enumerate() {
+            let merged_block = *merged_block;
+
             // Create a composed UTXO view from the selected parent UTXO view + the mergeset UTXO diff
             let composed_view = selected_parent_utxo_view.compose(&ctx.mergeset_diff);
 
             // The first block in the mergeset is always the selected parent
             let is_selected_parent = i == 0;
 
-            // No need to fully validate selected parent transactions since selected parent txs were already validated
-            // as part of selected parent UTXO state verification with the exact same UTXO context.
-            let validation_flags = if is_selected_parent { TxValidationFlags::SkipScriptChecks } else { TxValidationFlags::Full };
-            let (validated_transactions, inner_multiset) =
-                self.validate_transactions_with_muhash_in_parallel(&txs, &composed_view, pov_daa_score, validation_flags);
+            let validation_flags = Self::mergeset_validation_flags(is_selected_parent, ctx.assumed_valid);
+            let (validated_transactions, inner_multiset) = self.validate_transactions_with_muhash_in_parallel(
+                txs,
+                std::mem::take(&mut prevalidated[i]),
+                &composed_view,
+                pov_daa_score,
+                validation_flags,
+            );
 
             ctx.multiset_hash.combine(&inner_multiset);
 
-            let mut block_fee = 0u64;
+            let mut block_fee = Sompi::ZERO;
             for (validated_tx, _) in validated_transactions.iter() {
                 ctx.mergeset_diff.add_transaction(validated_tx, pov_daa_score).unwrap();
                 ctx.accepted_tx_ids.push(validated_tx.id());
-                block_fee += validated_tx.calculated_fee;
+                // Accepted transactions spend distinct UTXOs, hence their fees sum to no more than the total supply
+                block_fee = block_fee.checked_add(validated_tx.calculated_fee).expect("block fees are bounded by the total supply");
             }
 
             if is_selected_parent {
@@ -134,7 +158,7 @@ impl VirtualStateProcessor {
             let coinbase_data = self.coinbase_manager.deserialize_coinbase_payload(&txs[0].payload).unwrap();
             ctx.mergeset_rewards.insert(
                 merged_block,
-                BlockRewardData::new(coinbase_data.subsidy, block_fee, coinbase_data.miner_data.script_public_key),
+                BlockRewardData::new(coinbase_data.subsidy, block_fee.as_u64(), coinbase_data.miner_data.script_public_key),
             );
         }
 
@@ -143,6 +167,50 @@ impl VirtualStateProcessor {
         ctx.accepted_tx_ids.sort();
     }
 
+    fn mergeset_validation_flags(is_selected_parent: bool, assumed_valid: bool) -> TxValidationFlags {
+        // No need to fully validate selected parent transactions since selected parent txs were already validated
+        // as part of selected parent UTXO state verification with the exact same UTXO context.
+        if is_selected_parent || assumed_valid {
+            TxValidationFlags::SkipScriptChecks
+        } else {
+            TxValidationFlags::Full
+        }
+    }
+
+    /// Validates all mergeset transactions which do not depend on preceding merged blocks against the selected parent
+    /// UTXO view, where they see the same UTXO entries as in the context of their merged block. Returns the results per
+    /// merged block, indexed by transaction, where transactions which were not validated have no result
+    fn validate_independent_mergeset_transactions<'a, V: UtxoView + Sync>(
+        &self,
+        mergeset: &'a [(Hash, Arc<Vec<Transaction>>)],
+        dependencies: &MergesetDependencies,
+        selected_parent_utxo_view: &V,
+        pov_daa_score: u64,
+        assumed_valid: bool,
+    ) -> Vec<Vec<Option<TxResult<ValidatedTransaction<'a>>>>> {
+        let independent = dependencies.independent().collect_vec();
+        let results: Vec<_> = self.thread_pool.install(|| {
+            independent
+                .into_par_iter()
+                .map(|(block, i)| {
+                    let flags = Self::mergeset_validation_flags(block == 0, assumed_valid);
+                    let res = self.validate_transaction_in_utxo_context(
+                        &mergeset[block].1[i],
+                        selected_parent_utxo_view,
+                        pov_daa_score,
+                        flags,
+                    );
+                    (block, i, res)
+                })
+                .collect()
+        });
+        let mut prevalidated = mergeset.iter().map(|(_, txs)| (0..txs.len()).map(|_| None).collect_vec()).collect_vec();
+        for (block, i, res) in results {
+            prevalidated[block][i] = Some(res);
+        }
+        prevalidated
+    }
+
     /// Verify that the current block fully respects its own UTXO view. We define a block as
     /// UTXO valid if all the following conditions hold:
     ///     1. The block header includes the expected `utxo_commitment`.
@@ -181,8 +249,9 @@ impl VirtualStateProcessor {
 
         // Verify all transactions are valid in context
         let current_utxo_view = selected_parent_utxo_view.compose(&ctx.mergeset_diff);
+        let validation_flags = if ctx.assumed_valid { TxValidationFlags::SkipScriptChecks } else { TxValidationFlags::Full };
         let validated_transactions =
-            self.validate_transactions_in_parallel(&txs, &current_utxo_view, header.daa_score, TxValidationFlags::Full);
+            self.validate_transactions_in_parallel(&txs, &current_utxo_view, header.daa_score, validation_flags);
         if validated_transactions.len() < txs.len() - 1 {
             // Some non-coinbase transactions are invalid
             return Err(InvalidTransactionsInUtxoContext(txs.len() - 1 - validated_transactions.len(), txs.len() - 1));
@@ -234,21 +303,25 @@ impl VirtualStateProcessor {
     }
 
     /// Same as validate_transactions_in_parallel except during the iteration this will also
-    /// calculate the muhash in parallel for valid transactions
+    /// calculate the muhash in parallel for valid transactions. Transactions with a result in
+    /// `prevalidated` are not validated again
     pub(crate) fn validate_transactions_with_muhash_in_parallel<'a, V: UtxoView + Sync>(
         &self,
         txs: &'a Vec<Transaction>,
+        prevalidated: Vec<Option<TxResult<ValidatedTransaction<'a>>>>,
         utxo_view: &V,
         pov_daa_score: u64,
         flags: TxValidationFlags,
     ) -> (SmallVec<[(ValidatedTransaction<'a>, u32); 2]>, MuHash) {
+        debug_assert_eq!(txs.len(), prevalidated.len());
         self.thread_pool.install(|| {
             txs
                 .par_iter() // We can do this in parallel without complications since block body validation already ensured
                             // that all txs within each block are independent
+                .zip(prevalidated)
                 .enumerate()
                 .skip(1) // Skip the coinbase tx.
-                .filter_map(|(i, tx)| self.validate_transaction_in_utxo_context(tx, &utxo_view, pov_daa_score, flags).ok().map(|vtx| {
+                .filter_map(|(i, (tx, res))| res.unwrap_or_else(|| self.validate_transaction_in_utxo_context(tx, &utxo_view, pov_daa_score, flags)).ok().map(|vtx| {
                     let mh = MuHash::from_transaction(&vtx, pov_daa_score);
                     (smallvec![(vtx, i as u32)], mh)
                 }
@@ -342,6 +415,12 @@ impl VirtualStateProcessor {
         // Set the inner mass field
         mutable_tx.tx.set_mass(contextual_mass);
 
+        // The tx is now in the form it would be included in blocks, so its compute mass can be reused by body validation
+        if let Some(compute_mass) = mutable_tx.calculated_compute_mass {
+            let storage_mass_activated = self.storage_mass_activation.is_active(pov_daa_score);
+            self.tx_compute_mass_cache.insert(&mutable_tx.tx, storage_mass_activated, compute_mass);
+        }
+
         // At this point we know all UTXO entries are populated, so we can safely pass the tx as verifiable
         let mass_and_feerate_threshold = args.feerate_threshold.map(|threshold| (contextual_mass, threshold));
         let calculated_fee = self.transaction_validator.validate_populated_transaction_and_get_fee(
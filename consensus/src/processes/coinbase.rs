@@ -1,6 +1,8 @@
 use kaspa_consensus_core::{
     coinbase::*,
+    emission::EmissionSchedule,
     errors::coinbase::{CoinbaseError, CoinbaseResult},
+    sompi::Sompi,
     subnets,
     tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionOutput},
     BlockHashMap, BlockHashSet,
@@ -17,26 +19,12 @@ const LENGTH_OF_SCRIPT_PUB_KEY_LENGTH: usize = size_of::<u8>();
 const MIN_PAYLOAD_LENGTH: usize =
     LENGTH_OF_BLUE_SCORE + LENGTH_OF_SUBSIDY + LENGTH_OF_SCRIPT_PUB_KEY_VERSION + LENGTH_OF_SCRIPT_PUB_KEY_LENGTH;
 
-// We define a year as 365.25 days and a month as 365.25 / 12 = 30.4375
-// SECONDS_PER_MONTH = 30.4375 * 24 * 60 * 60
-const SECONDS_PER_MONTH: u64 = 2629800;
-
-pub const SUBSIDY_BY_MONTH_TABLE_SIZE: usize = 426;
-pub type SubsidyByMonthTable = [u64; SUBSIDY_BY_MONTH_TABLE_SIZE];
-
 #[derive(Clone)]
 pub struct CoinbaseManager {
     coinbase_payload_script_public_key_max_len: u8,
     max_coinbase_payload_len: usize,
-    deflationary_phase_daa_score: u64,
-    pre_deflationary_phase_base_subsidy: u64,
     target_time_per_block: u64,
-
-    /// Precomputed number of blocks per month
-    blocks_per_month: u64,
-
-    /// Precomputed subsidy by month table
-    subsidy_by_month_table: SubsidyByMonthTable,
+    emission_schedule: EmissionSchedule,
 }
 
 /// Struct used to streamline payload parsing
@@ -65,23 +53,9 @@ impl CoinbaseManager {
         pre_deflationary_phase_base_subsidy: u64,
         target_time_per_block: u64,
     ) -> Self {
-        assert!(1000 % target_time_per_block == 0);
-        let bps = 1000 / target_time_per_block;
-        let blocks_per_month = SECONDS_PER_MONTH * bps;
-
-        // Precomputed subsidy by month table for the actual block per second rate
-        // Here values are rounded up so that we keep the same number of rewarding months as in the original 1 BPS table.
-        // In a 10 BPS network, the induced increase in total rewards is 51 KAS (see tests::calc_high_bps_total_rewards_delta())
-        let subsidy_by_month_table: SubsidyByMonthTable = core::array::from_fn(|i| (SUBSIDY_BY_MONTH_TABLE[i] + bps - 1) / bps);
-        Self {
-            coinbase_payload_script_public_key_max_len,
-            max_coinbase_payload_len,
-            deflationary_phase_daa_score,
-            pre_deflationary_phase_base_subsidy,
-            target_time_per_block,
-            blocks_per_month,
-            subsidy_by_month_table,
-        }
+        let emission_schedule =
+            EmissionSchedule::new(deflationary_phase_daa_score, pre_deflationary_phase_base_subsidy, target_time_per_block);
+        Self { coinbase_payload_script_public_key_max_len, max_coinbase_payload_len, target_time_per_block, emission_schedule }
     }
 
     #[cfg(test)]
@@ -104,21 +78,22 @@ impl CoinbaseManager {
         // Note that combinatorically it is nearly impossible for a blue block to be non-DAA
         for blue in ghostdag_data.mergeset_blues.iter().filter(|h| !mergeset_non_daa.contains(h)) {
             let reward_data = mergeset_rewards.get(blue).unwrap();
-            if reward_data.subsidy + reward_data.total_fees > 0 {
-                outputs
-                    .push(TransactionOutput::new(reward_data.subsidy + reward_data.total_fees, reward_data.script_public_key.clone()));
+            let reward = reward_data.total_reward().ok_or(CoinbaseError::RewardOverflow)?;
+            if !reward.is_zero() {
+                outputs.push(TransactionOutput::new(reward.as_u64(), reward_data.script_public_key.clone()));
             }
         }
 
         // Collect all rewards from mergeset reds ∩ DAA window and create a
         // single output rewarding all to the current block (the "merging" block)
-        let mut red_reward = 0u64;
+        let mut red_reward = Sompi::ZERO;
         for red in ghostdag_data.mergeset_reds.iter().filter(|h| !mergeset_non_daa.contains(h)) {
             let reward_data = mergeset_rewards.get(red).unwrap();
-            red_reward += reward_data.subsidy + reward_data.total_fees;
+            let reward = reward_data.total_reward().ok_or(CoinbaseError::RewardOverflow)?;
+            red_reward = red_reward.checked_add(reward).ok_or(CoinbaseError::RewardOverflow)?;
         }
-        if red_reward > 0 {
-            outputs.push(TransactionOutput::new(red_reward, miner_data.script_public_key.clone()));
+        if !red_reward.is_zero() {
+            outputs.push(TransactionOutput::new(red_reward.as_u64(), miner_data.script_public_key.clone()));
         }
 
         // Build the current block's payload
@@ -127,7 +102,7 @@ impl CoinbaseManager {
 
         Ok(CoinbaseTransactionTemplate {
             tx: Transaction::new(constants::TX_VERSION, vec![], outputs, 0, subnets::SUBNETWORK_ID_COINBASE, 0, payload),
-            has_red_reward: red_reward > 0,
+            has_red_reward: !red_reward.is_zero(),
         })
     }
 
@@ -210,27 +185,24 @@ impl CoinbaseManager {
     }
 
     pub fn calc_block_subsidy(&self, daa_score: u64) -> u64 {
-        if daa_score < self.deflationary_phase_daa_score {
-            return self.pre_deflationary_phase_base_subsidy;
-        }
+        self.emission_schedule.block_subsidy(daa_score)
+    }
 
-        let months_since_deflationary_phase_started =
-            ((daa_score - self.deflationary_phase_daa_score) / self.blocks_per_month) as usize;
-        if months_since_deflationary_phase_started >= self.subsidy_by_month_table.len() {
-            *(self.subsidy_by_month_table).last().unwrap()
-        } else {
-            self.subsidy_by_month_table[months_since_deflationary_phase_started]
-        }
+    pub fn emission_schedule(&self) -> &EmissionSchedule {
+        &self.emission_schedule
     }
 
     #[cfg(test)]
     pub fn legacy_calc_block_subsidy(&self, daa_score: u64) -> u64 {
-        if daa_score < self.deflationary_phase_daa_score {
-            return self.pre_deflationary_phase_base_subsidy;
+        use kaspa_consensus_core::emission::{SECONDS_PER_MONTH, SUBSIDY_BY_MONTH_TABLE};
+
+        let deflationary_phase_daa_score = self.emission_schedule.deflationary_phase_daa_score();
+        if daa_score < deflationary_phase_daa_score {
+            return self.emission_schedule.pre_deflationary_phase_base_subsidy();
         }
 
         // Note that this calculation implicitly assumes that block per second = 1 (by assuming daa score diff is in second units).
-        let months_since_deflationary_phase_started = (daa_score - self.deflationary_phase_daa_score) / SECONDS_PER_MONTH;
+        let months_since_deflationary_phase_started = (daa_score - deflationary_phase_daa_score) / SECONDS_PER_MONTH;
         assert!(months_since_deflationary_phase_started <= usize::MAX as u64);
         let months_since_deflationary_phase_started: usize = months_since_deflationary_phase_started as usize;
         if months_since_deflationary_phase_started >= SUBSIDY_BY_MONTH_TABLE.len() {
@@ -241,33 +213,6 @@ impl CoinbaseManager {
     }
 }
 
-/*
-    This table was pre-calculated by calling `calcDeflationaryPeriodBlockSubsidyFloatCalc` (in kaspad-go) for all months until reaching 0 subsidy.
-    To regenerate this table, run `TestBuildSubsidyTable` in coinbasemanager_test.go (note the `deflationaryPhaseBaseSubsidy` therein).
-    These values apply to 1 block per second.
-*/
-#[rustfmt::skip]
-const SUBSIDY_BY_MONTH_TABLE: [u64; 426] = [
-	44000000000, 41530469757, 39199543598, 36999442271, 34922823143, 32962755691, 31112698372, 29366476791, 27718263097, 26162556530, 24694165062, 23308188075, 22000000000, 20765234878, 19599771799, 18499721135, 17461411571, 16481377845, 15556349186, 14683238395, 13859131548, 13081278265, 12347082531, 11654094037, 11000000000,
-	10382617439, 9799885899, 9249860567, 8730705785, 8240688922, 7778174593, 7341619197, 6929565774, 6540639132, 6173541265, 5827047018, 5500000000, 5191308719, 4899942949, 4624930283, 4365352892, 4120344461, 3889087296, 3670809598, 3464782887, 3270319566, 3086770632, 2913523509, 2750000000, 2595654359,
-	2449971474, 2312465141, 2182676446, 2060172230, 1944543648, 1835404799, 1732391443, 1635159783, 1543385316, 1456761754, 1375000000, 1297827179, 1224985737, 1156232570, 1091338223, 1030086115, 972271824, 917702399, 866195721, 817579891, 771692658, 728380877, 687500000, 648913589, 612492868,
-	578116285, 545669111, 515043057, 486135912, 458851199, 433097860, 408789945, 385846329, 364190438, 343750000, 324456794, 306246434, 289058142, 272834555, 257521528, 243067956, 229425599, 216548930, 204394972, 192923164, 182095219, 171875000, 162228397, 153123217, 144529071,
-	136417277, 128760764, 121533978, 114712799, 108274465, 102197486, 96461582, 91047609, 85937500, 81114198, 76561608, 72264535, 68208638, 64380382, 60766989, 57356399, 54137232, 51098743, 48230791, 45523804, 42968750, 40557099, 38280804, 36132267, 34104319,
-	32190191, 30383494, 28678199, 27068616, 25549371, 24115395, 22761902, 21484375, 20278549, 19140402, 18066133, 17052159, 16095095, 15191747, 14339099, 13534308, 12774685, 12057697, 11380951, 10742187, 10139274, 9570201, 9033066, 8526079, 8047547,
-	7595873, 7169549, 6767154, 6387342, 6028848, 5690475, 5371093, 5069637, 4785100, 4516533, 4263039, 4023773, 3797936, 3584774, 3383577, 3193671, 3014424, 2845237, 2685546, 2534818, 2392550, 2258266, 2131519, 2011886, 1898968,
-	1792387, 1691788, 1596835, 1507212, 1422618, 1342773, 1267409, 1196275, 1129133, 1065759, 1005943, 949484, 896193, 845894, 798417, 753606, 711309, 671386, 633704, 598137, 564566, 532879, 502971, 474742, 448096,
-	422947, 399208, 376803, 355654, 335693, 316852, 299068, 282283, 266439, 251485, 237371, 224048, 211473, 199604, 188401, 177827, 167846, 158426, 149534, 141141, 133219, 125742, 118685, 112024, 105736,
-	99802, 94200, 88913, 83923, 79213, 74767, 70570, 66609, 62871, 59342, 56012, 52868, 49901, 47100, 44456, 41961, 39606, 37383, 35285, 33304, 31435, 29671, 28006, 26434, 24950,
-	23550, 22228, 20980, 19803, 18691, 17642, 16652, 15717, 14835, 14003, 13217, 12475, 11775, 11114, 10490, 9901, 9345, 8821, 8326, 7858, 7417, 7001, 6608, 6237, 5887,
-	5557, 5245, 4950, 4672, 4410, 4163, 3929, 3708, 3500, 3304, 3118, 2943, 2778, 2622, 2475, 2336, 2205, 2081, 1964, 1854, 1750, 1652, 1559, 1471, 1389,
-	1311, 1237, 1168, 1102, 1040, 982, 927, 875, 826, 779, 735, 694, 655, 618, 584, 551, 520, 491, 463, 437, 413, 389, 367, 347, 327,
-	309, 292, 275, 260, 245, 231, 218, 206, 194, 183, 173, 163, 154, 146, 137, 130, 122, 115, 109, 103, 97, 91, 86, 81, 77,
-	73, 68, 65, 61, 57, 54, 51, 48, 45, 43, 40, 38, 36, 34, 32, 30, 28, 27, 25, 24, 22, 21, 20, 19, 18,
-	17, 16, 15, 14, 13, 12, 12, 11, 10, 10, 9, 9, 8, 8, 7, 7, 6, 6, 6, 5, 5, 5, 4, 4, 4,
-	4, 3, 3, 3, 3, 3, 2, 2, 2, 2, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
-	0,
-];
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,6 +220,7 @@ mod tests {
     use kaspa_consensus_core::{
         config::params::{Params, TESTNET11_PARAMS},
         constants::SOMPI_PER_KASPA,
+        emission::SUBSIDY_BY_MONTH_TABLE,
         network::NetworkId,
         tx::scriptvec,
     };
@@ -284,7 +230,9 @@ mod tests {
         const SECONDS_PER_MONTH: u64 = 2629800;
 
         let legacy_cbm = create_legacy_manager();
-        let pre_deflationary_rewards = legacy_cbm.pre_deflationary_phase_base_subsidy * legacy_cbm.deflationary_phase_daa_score;
+        let legacy_emission = legacy_cbm.emission_schedule();
+        let pre_deflationary_rewards =
+            legacy_emission.pre_deflationary_phase_base_subsidy() * legacy_emission.deflationary_phase_daa_score();
         let total_rewards: u64 = pre_deflationary_rewards + SUBSIDY_BY_MONTH_TABLE.iter().map(|x| x * SECONDS_PER_MONTH).sum::<u64>();
         let testnet_11_bps = TESTNET11_PARAMS.bps();
         let total_high_bps_rewards_rounded_up: u64 = pre_deflationary_rewards
@@ -294,8 +242,9 @@ mod tests {
                 .sum::<u64>();
 
         let cbm = create_manager(&TESTNET11_PARAMS);
+        let emission = cbm.emission_schedule();
         let total_high_bps_rewards: u64 =
-            pre_deflationary_rewards + cbm.subsidy_by_month_table.iter().map(|x| x * cbm.blocks_per_month).sum::<u64>();
+            pre_deflationary_rewards + emission.subsidy_by_month_table().iter().map(|x| x * emission.blocks_per_month()).sum::<u64>();
         assert_eq!(total_high_bps_rewards_rounded_up, total_high_bps_rewards, "subsidy adjusted to bps must be rounded up");
 
         let delta = total_high_bps_rewards as i64 - total_rewards as i64;
@@ -308,13 +257,13 @@ mod tests {
     #[test]
     fn subsidy_by_month_table_test() {
         let cbm = create_legacy_manager();
-        cbm.subsidy_by_month_table.iter().enumerate().for_each(|(i, x)| {
+        cbm.emission_schedule().subsidy_by_month_table().iter().enumerate().for_each(|(i, x)| {
             assert_eq!(SUBSIDY_BY_MONTH_TABLE[i], *x, "for 1 BPS, const table and precomputed values must match");
         });
 
         for network_id in NetworkId::iter() {
             let cbm = create_manager(&network_id.into());
-            cbm.subsidy_by_month_table.iter().enumerate().for_each(|(i, x)| {
+            cbm.emission_schedule().subsidy_by_month_table().iter().enumerate().for_each(|(i, x)| {
                 assert_eq!(
                     (SUBSIDY_BY_MONTH_TABLE[i] + cbm.bps() - 1) / cbm.bps(),
                     *x,
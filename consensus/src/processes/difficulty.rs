@@ -5,16 +5,12 @@ use crate::model::stores::{
 };
 use kaspa_consensus_core::{
     config::params::MIN_DIFFICULTY_WINDOW_LEN,
+    difficulty::{DifficultyParams, DifficultySample},
     errors::difficulty::{DifficultyError, DifficultyResult},
     BlockHashSet, BlueWorkType, MAX_WORK_LEVEL,
 };
-use kaspa_math::{Uint256, Uint320};
-use std::{
-    cmp::{max, Ordering},
-    iter::once_with,
-    ops::Deref,
-    sync::Arc,
-};
+use kaspa_math::Uint256;
+use std::{cmp::Ordering, iter::once_with, ops::Deref, sync::Arc};
 
 use super::ghostdag::ordering::SortableBlock;
 use itertools::Itertools;
@@ -39,6 +35,16 @@ trait DifficultyManagerExtension {
             .collect()
     }
 
+    /// Returns the difficulty samples of `window` with the lowest block first, so that it is the block excluded from the
+    /// average target in case of timestamp ties (see [`DifficultyParams::calc_difficulty_bits`])
+    fn get_difficulty_samples(&self, window: &BlockWindowHeap) -> Vec<DifficultySample> {
+        let mut difficulty_blocks = self.get_difficulty_blocks(window);
+        if let Some(min_index) = difficulty_blocks.iter().position_min() {
+            difficulty_blocks.swap(0, min_index);
+        }
+        difficulty_blocks.into_iter().map(|block| DifficultySample { timestamp: block.timestamp, bits: block.bits }).collect()
+    }
+
     fn internal_estimate_network_hashes_per_second(&self, window: &BlockWindowHeap) -> DifficultyResult<u64> {
         // TODO: perhaps move this const
         const MIN_WINDOW_SIZE: usize = 1000;
@@ -79,11 +85,7 @@ trait DifficultyManagerExtension {
 #[derive(Clone)]
 pub struct FullDifficultyManager<T: HeaderStoreReader> {
     headers_store: Arc<T>,
-    genesis_bits: u32,
-    max_difficulty_target: Uint320,
-    difficulty_window_size: usize,
-    min_difficulty_window_len: usize,
-    target_time_per_block: u64,
+    difficulty_params: DifficultyParams,
 }
 
 impl<T: HeaderStoreReader> FullDifficultyManager<T> {
@@ -96,14 +98,16 @@ impl<T: HeaderStoreReader> FullDifficultyManager<T> {
         target_time_per_block: u64,
     ) -> Self {
         Self::check_min_difficulty_window_len(difficulty_window_size, min_difficulty_window_len);
-        Self {
-            headers_store,
-            genesis_bits,
-            max_difficulty_target: max_difficulty_target.into(),
-            difficulty_window_size,
-            min_difficulty_window_len,
+        let difficulty_params = DifficultyParams {
             target_time_per_block,
-        }
+            // Full windows hold every block
+            sample_rate: 1,
+            window_size: difficulty_window_size,
+            min_window_len: min_difficulty_window_len,
+            max_difficulty_target,
+            genesis_bits,
+        };
+        Self { headers_store, difficulty_params }
     }
 
     pub fn calc_daa_score_and_mergeset_non_daa_blocks<'a>(
@@ -129,28 +133,7 @@ impl<T: HeaderStoreReader> FullDifficultyManager<T> {
     }
 
     pub fn calculate_difficulty_bits(&self, window: &BlockWindowHeap) -> u32 {
-        let mut difficulty_blocks = self.get_difficulty_blocks(window);
-
-        // Until there are enough blocks for a valid calculation the difficulty should remain constant.
-        if difficulty_blocks.len() < self.min_difficulty_window_len {
-            return self.genesis_bits;
-        }
-
-        let (min_ts_index, max_ts_index) = difficulty_blocks.iter().position_minmax().into_option().unwrap();
-
-        let min_ts = difficulty_blocks[min_ts_index].timestamp;
-        let max_ts = difficulty_blocks[max_ts_index].timestamp;
-
-        // We remove the minimal block because we want the average target for the internal window.
-        difficulty_blocks.swap_remove(min_ts_index);
-
-        // We need Uint320 to avoid overflow when summing and multiplying by the window size.
-        let difficulty_blocks_len = difficulty_blocks.len() as u64;
-        let targets_sum: Uint320 =
-            difficulty_blocks.into_iter().map(|diff_block| Uint320::from(Uint256::from_compact_target_bits(diff_block.bits))).sum();
-        let average_target = targets_sum / (difficulty_blocks_len);
-        let new_target = average_target * max(max_ts - min_ts, 1) / (self.target_time_per_block * difficulty_blocks_len);
-        Uint256::try_from(new_target.min(self.max_difficulty_target)).expect("max target < Uint256::MAX").compact_target_bits()
+        self.difficulty_params.calc_difficulty_bits(&self.get_difficulty_samples(window))
     }
 
     pub fn estimate_network_hashes_per_second(&self, window: &BlockWindowHeap) -> DifficultyResult<u64> {
@@ -169,12 +152,7 @@ impl<T: HeaderStoreReader> DifficultyManagerExtension for FullDifficultyManager<
 #[derive(Clone)]
 pub struct SampledDifficultyManager<T: HeaderStoreReader> {
     headers_store: Arc<T>,
-    genesis_bits: u32,
-    max_difficulty_target: Uint320,
-    difficulty_window_size: usize,
-    min_difficulty_window_len: usize,
-    difficulty_sample_rate: u64,
-    target_time_per_block: u64,
+    difficulty_params: DifficultyParams,
 }
 
 impl<T: HeaderStoreReader> SampledDifficultyManager<T> {
@@ -188,21 +166,21 @@ impl<T: HeaderStoreReader> SampledDifficultyManager<T> {
         target_time_per_block: u64,
     ) -> Self {
         Self::check_min_difficulty_window_len(difficulty_window_size, min_difficulty_window_len);
-        Self {
-            headers_store,
-            genesis_bits,
-            max_difficulty_target: max_difficulty_target.into(),
-            difficulty_window_size,
-            min_difficulty_window_len,
-            difficulty_sample_rate,
+        let difficulty_params = DifficultyParams {
             target_time_per_block,
-        }
+            sample_rate: difficulty_sample_rate,
+            window_size: difficulty_window_size,
+            min_window_len: min_difficulty_window_len,
+            max_difficulty_target,
+            genesis_bits,
+        };
+        Self { headers_store, difficulty_params }
     }
 
     #[inline]
     #[must_use]
     pub fn difficulty_full_window_size(&self) -> u64 {
-        self.difficulty_window_size as u64 * self.difficulty_sample_rate
+        self.difficulty_params.window_size as u64 * self.difficulty_params.sample_rate
     }
 
     /// Returns the DAA window lowest accepted blue score
@@ -231,32 +209,7 @@ impl<T: HeaderStoreReader> SampledDifficultyManager<T> {
     }
 
     pub fn calculate_difficulty_bits(&self, window: &BlockWindowHeap) -> u32 {
-        // Note: this fn is duplicated (almost, see `* self.difficulty_sample_rate`) in Full and Sampled structs
-        // so some alternate calculation can be investigated here.
-        let mut difficulty_blocks = self.get_difficulty_blocks(window);
-
-        // Until there are enough blocks for a valid calculation the difficulty should remain constant.
-        if difficulty_blocks.len() < self.min_difficulty_window_len {
-            return self.genesis_bits;
-        }
-
-        let (min_ts_index, max_ts_index) = difficulty_blocks.iter().position_minmax().into_option().unwrap();
-
-        let min_ts = difficulty_blocks[min_ts_index].timestamp;
-        let max_ts = difficulty_blocks[max_ts_index].timestamp;
-
-        // We remove the minimal block because we want the average target for the internal window.
-        difficulty_blocks.swap_remove(min_ts_index);
-
-        // We need Uint320 to avoid overflow when summing and multiplying by the window size.
-        let difficulty_blocks_len = difficulty_blocks.len() as u64;
-        let targets_sum: Uint320 =
-            difficulty_blocks.into_iter().map(|diff_block| Uint320::from(Uint256::from_compact_target_bits(diff_block.bits))).sum();
-        let average_target = targets_sum / difficulty_blocks_len;
-        let measured_duration = max(max_ts - min_ts, 1);
-        let expected_duration = self.target_time_per_block * self.difficulty_sample_rate * difficulty_blocks_len; // This does differ from FullDifficultyManager version
-        let new_target = average_target * measured_duration / expected_duration;
-        Uint256::try_from(new_target.min(self.max_difficulty_target)).expect("max target < Uint256::MAX").compact_target_bits()
+        self.difficulty_params.calc_difficulty_bits(&self.get_difficulty_samples(window))
     }
 
     pub fn estimate_network_hashes_per_second(&self, window: &BlockWindowHeap) -> DifficultyResult<u64> {
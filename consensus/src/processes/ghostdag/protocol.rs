@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{ops::Deref, sync::Arc};
 
 use kaspa_consensus_core::{
     blockhash::{self, BlockHashExtensions, BlockHashes},
@@ -132,9 +132,11 @@ impl<T: GhostdagStoreReader, S: RelationsStoreReader, U: ReachabilityService, V:
         let mut new_block_data = GhostdagData::new_with_selected_parent(selected_parent, self.k);
         // Get the mergeset in consensus-agreed topological order (topological here means forward in time from blocks to children)
         let ordered_mergeset = self.ordered_mergeset_without_selected_parent(selected_parent, parents);
+        // Chain data shared by the coloring of all candidates
+        let mut chain_cache = ChainColoringCache::new(selected_parent);
 
         for blue_candidate in ordered_mergeset.iter().cloned() {
-            let coloring = self.check_blue_candidate(&new_block_data, blue_candidate);
+            let coloring = self.check_blue_candidate(&new_block_data, blue_candidate, &mut chain_cache);
 
             if let ColoringOutput::Blue(blue_anticone_size, blues_anticone_sizes) = coloring {
                 // No k-cluster violation found, we can now set the candidate block as blue
@@ -172,6 +174,7 @@ impl<T: GhostdagStoreReader, S: RelationsStoreReader, U: ReachabilityService, V:
         blue_candidate: Hash,
         candidate_blues_anticone_sizes: &mut BlockHashMap<KType>,
         candidate_blue_anticone_size: &mut KType,
+        chain_cache: &mut ChainColoringCache,
     ) -> ColoringState {
         // If blue_candidate is in the future of chain_block, it means
         // that all remaining blues are in the past of chain_block and thus
@@ -195,15 +198,17 @@ impl<T: GhostdagStoreReader, S: RelationsStoreReader, U: ReachabilityService, V:
                 continue;
             }
 
-            candidate_blues_anticone_sizes.insert(block, self.blue_anticone_size(block, new_block_data));
-
+            // Checked before looking up the blue anticone size of the block, which is the more costly check
             *candidate_blue_anticone_size += 1;
             if *candidate_blue_anticone_size > self.k {
                 // k-cluster violation: The candidate's blue anticone exceeded k
                 return ColoringState::Red;
             }
 
-            if *candidate_blues_anticone_sizes.get(&block).unwrap() == self.k {
+            let block_blue_anticone_size = self.blue_anticone_size(block, new_block_data, chain_cache);
+            candidate_blues_anticone_sizes.insert(block, block_blue_anticone_size);
+
+            if block_blue_anticone_size == self.k {
                 // k-cluster violation: A block in candidate's blue anticone already
                 // has k blue blocks in its own anticone
                 return ColoringState::Red;
@@ -211,32 +216,45 @@ impl<T: GhostdagStoreReader, S: RelationsStoreReader, U: ReachabilityService, V:
 
             // This is a sanity check that validates that a blue
             // block's blue anticone is not already larger than K.
-            assert!(*candidate_blues_anticone_sizes.get(&block).unwrap() <= self.k, "found blue anticone larger than K");
+            assert!(block_blue_anticone_size <= self.k, "found blue anticone larger than K");
         }
 
         ColoringState::Pending
     }
 
-    /// Returns the blue anticone size of `block` from the worldview of `context`.
-    /// Expects `block` to be in the blue set of `context`
-    fn blue_anticone_size(&self, block: Hash, context: &GhostdagData) -> KType {
-        let mut current_blues_anticone_sizes = HashKTypeMap::clone(&context.blues_anticone_sizes);
-        let mut current_selected_parent = context.selected_parent;
-        loop {
-            if let Some(size) = current_blues_anticone_sizes.get(&block) {
-                return *size;
-            }
+    /// Returns the blue anticone size of `block` from the worldview of `context`, whose selected parent chain
+    /// is the one of `chain_cache`. Expects `block` to be in the blue set of `context`
+    fn blue_anticone_size(&self, block: Hash, context: &GhostdagData, chain_cache: &mut ChainColoringCache) -> KType {
+        if let Some(size) = context.blues_anticone_sizes.get(&block) {
+            return *size;
+        }
+        // Sizes found along the selected parent chain do not depend on the blues added to the context, hence
+        // they are looked up once per mergeset and reused by all following candidates
+        if let Some(size) = chain_cache.blues_anticone_sizes.get(&block) {
+            return *size;
+        }
 
-            if current_selected_parent == self.genesis_hash || current_selected_parent == blockhash::ORIGIN {
+        let mut chain_index = 0;
+        loop {
+            let chain_block = chain_cache.chain_hash(chain_index, self.ghostdag_store.deref());
+            if chain_block == self.genesis_hash || chain_block == blockhash::ORIGIN {
                 panic!("block {block} is not in blue set of the given context");
             }
 
-            current_blues_anticone_sizes = self.ghostdag_store.get_blues_anticone_sizes(current_selected_parent).unwrap();
-            current_selected_parent = self.ghostdag_store.get_selected_parent(current_selected_parent).unwrap();
+            if let Some(&size) = chain_cache.chain_data(chain_index, self.ghostdag_store.deref()).blues_anticone_sizes.get(&block) {
+                chain_cache.blues_anticone_sizes.insert(block, size);
+                return size;
+            }
+            chain_index += 1;
         }
     }
 
-    fn check_blue_candidate(&self, new_block_data: &GhostdagData, blue_candidate: Hash) -> ColoringOutput {
+    fn check_blue_candidate(
+        &self,
+        new_block_data: &GhostdagData,
+        blue_candidate: Hash,
+        chain_cache: &mut ChainColoringCache,
+    ) -> ColoringOutput {
         // The maximum length of new_block_data.mergeset_blues can be K+1 because
         // it contains the selected parent.
         if new_block_data.mergeset_blues.len() as KType == self.k + 1 {
@@ -250,6 +268,7 @@ impl<T: GhostdagStoreReader, S: RelationsStoreReader, U: ReachabilityService, V:
         // of blue_candidate to be over K.
         let mut chain_block = ChainBlock { hash: None, data: new_block_data.into() };
         let mut candidate_blue_anticone_size: KType = 0;
+        let mut chain_index = 0;
 
         loop {
             let state = self.check_blue_candidate_with_chain_block(
@@ -258,6 +277,7 @@ impl<T: GhostdagStoreReader, S: RelationsStoreReader, U: ReachabilityService, V:
                 blue_candidate,
                 &mut candidate_blues_anticone_sizes,
                 &mut candidate_blue_anticone_size,
+                chain_cache,
             );
 
             match state {
@@ -267,10 +287,43 @@ impl<T: GhostdagStoreReader, S: RelationsStoreReader, U: ReachabilityService, V:
             }
 
             chain_block = ChainBlock {
-                hash: Some(chain_block.data.selected_parent),
-                data: self.ghostdag_store.get_data(chain_block.data.selected_parent).unwrap().into(),
-            }
+                hash: Some(chain_cache.chain_hash(chain_index, self.ghostdag_store.deref())),
+                data: chain_cache.chain_data(chain_index, self.ghostdag_store.deref()).into(),
+            };
+            chain_index += 1;
+        }
+    }
+}
+
+/// The selected parent chain of a new block, loaded once while coloring its mergeset rather than per candidate
+struct ChainColoringCache {
+    selected_parent: Hash,
+    /// The GHOSTDAG data of the chain blocks loaded so far, starting with the selected parent
+    chain: Vec<Arc<GhostdagData>>,
+    /// The blue anticone sizes of blocks in the blue set of the selected parent, as found along the chain
+    blues_anticone_sizes: BlockHashMap<KType>,
+}
+
+impl ChainColoringCache {
+    fn new(selected_parent: Hash) -> Self {
+        Self { selected_parent, chain: Vec::new(), blues_anticone_sizes: BlockHashMap::new() }
+    }
+
+    /// Returns the hash of the chain block at `index`, where the selected parent is at index 0
+    fn chain_hash(&mut self, index: usize, ghostdag_store: &impl GhostdagStoreReader) -> Hash {
+        match index {
+            0 => self.selected_parent,
+            _ => self.chain_data(index - 1, ghostdag_store).selected_parent,
+        }
+    }
+
+    /// Returns the GHOSTDAG data of the chain block at `index`, loading the chain up to it if needed
+    fn chain_data(&mut self, index: usize, ghostdag_store: &impl GhostdagStoreReader) -> Arc<GhostdagData> {
+        while self.chain.len() <= index {
+            let hash = self.chain_hash(self.chain.len(), ghostdag_store);
+            self.chain.push(ghostdag_store.get_data(hash).unwrap());
         }
+        self.chain[index].clone()
     }
 }
 
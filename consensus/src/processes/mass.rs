@@ -0,0 +1,48 @@
+use kaspa_consensus_core::{mass::MassCalculator, tx::Transaction};
+use kaspa_database::prelude::{Cache, CachePolicy};
+use kaspa_hashes::Hash;
+
+/// The number of transaction compute masses kept by [`TxComputeMassCache`]
+pub const TX_COMPUTE_MASS_CACHE_SIZE: usize = 50_000;
+
+/// Caches transaction compute masses by the transaction hash blocks commit to in their hash merkle root.
+/// The hash covers all the fields the compute mass depends on (signature scripts included), so a cached
+/// mass is valid for any transaction with the same hash.
+///
+/// Masses are recorded when mempool transactions are validated in UTXO context (at which point their mass
+/// field is final) and when validating block bodies, so blocks built from the mempool or sharing transactions
+/// with previously validated parallel blocks avoid recomputing them
+#[derive(Clone)]
+pub struct TxComputeMassCache {
+    cache: Cache<Hash, u64>,
+}
+
+impl TxComputeMassCache {
+    pub fn new(size: usize) -> Self {
+        Self { cache: Cache::new(CachePolicy::Count(size)) }
+    }
+
+    /// Returns the compute mass of `tx` whose committed hash is `tx_hash`, calculating and caching it if missing
+    pub fn get_or_calc(&self, mass_calculator: &MassCalculator, tx: &Transaction, tx_hash: Hash) -> u64 {
+        // Coinbase txs are unique to their block and have no compute mass, so there is no point in caching them
+        if tx.is_coinbase() {
+            return 0;
+        }
+        if let Some(mass) = self.cache.get(&tx_hash) {
+            return mass;
+        }
+        let mass = mass_calculator.calc_tx_compute_mass(tx);
+        self.cache.insert(tx_hash, mass);
+        mass
+    }
+
+    /// Records the compute mass of a transaction whose mass field is final, keyed by the hash a block
+    /// would commit to given whether the storage mass hardfork is active
+    pub fn insert(&self, tx: &Transaction, storage_mass_activated: bool, compute_mass: u64) {
+        self.cache.insert(tx.hash(storage_mass_activated), compute_mass);
+    }
+
+    pub fn shrink(&self, fraction: f64) -> usize {
+        self.cache.shrink(fraction)
+    }
+}
@@ -0,0 +1,49 @@
+use kaspa_consensus_core::tx::Transaction;
+use kaspa_hashes::Hash;
+use rayon::prelude::*;
+
+/// The number of transactions from which merkle leaves are hashed in parallel. Below it, the overhead of
+/// distributing the work outweighs the gain
+pub const PARALLEL_MERKLE_LEAVES_THRESHOLD: usize = 64;
+
+/// Hashes the leaves of the hash merkle root of `txs`, i.e., the transaction hashes, in parallel for large transaction
+/// lists. When called from a thread of a rayon pool the work is distributed over that pool. The resulting hashes are
+/// also cached within the transactions
+pub fn hash_merkle_leaves(txs: &[Transaction], include_mass_field: bool) -> Vec<Hash> {
+    if txs.len() < PARALLEL_MERKLE_LEAVES_THRESHOLD {
+        txs.iter().map(|tx| tx.hash(include_mass_field)).collect()
+    } else {
+        txs.par_iter().map(|tx| tx.hash(include_mass_field)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        hashing,
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, TransactionInput, TransactionOutpoint, TransactionOutput},
+    };
+
+    #[test]
+    fn test_parallel_merkle_leaves() {
+        for count in [1, PARALLEL_MERKLE_LEAVES_THRESHOLD - 1, PARALLEL_MERKLE_LEAVES_THRESHOLD, 1000] {
+            let txs = (0..count as u64)
+                .map(|i| {
+                    let input = TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(i), 0), vec![1; 66], 0, 1);
+                    let output = TransactionOutput::new(i + 1, ScriptPublicKey::from_vec(0, vec![2; 34]));
+                    let tx = Transaction::new(0, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+                    tx.set_mass(1000 + i);
+                    tx
+                })
+                .collect::<Vec<_>>();
+            for include_mass_field in [false, true] {
+                let expected = txs.iter().map(|tx| hashing::tx::hash(tx, include_mass_field)).collect::<Vec<_>>();
+                assert_eq!(hash_merkle_leaves(&txs, include_mass_field), expected);
+                // The second call reads the hashes cached by the first one
+                assert_eq!(hash_merkle_leaves(&txs, include_mass_field), expected);
+            }
+        }
+    }
+}
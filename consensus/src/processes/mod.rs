@@ -2,6 +2,8 @@ pub mod block_depth;
 pub mod coinbase;
 pub mod difficulty;
 pub mod ghostdag;
+pub mod mass;
+pub mod merkle;
 pub mod parents_builder;
 pub mod past_median_time;
 pub mod pruning;
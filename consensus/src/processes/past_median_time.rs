@@ -1,6 +1,28 @@
-use crate::model::stores::{block_window_cache::BlockWindowHeap, headers::HeaderStoreReader};
+use crate::model::stores::{
+    block_window_cache::{BlockWindowHeap, SortedTimestamps},
+    headers::HeaderStoreReader,
+};
 use kaspa_consensus_core::errors::block::RuleError;
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
+
+/// Returns the sorted timestamps of the window blocks, fetching and sorting them only if the window does not track them
+fn sorted_window_timestamps<'a>(headers_store: &impl HeaderStoreReader, window: &'a BlockWindowHeap) -> Cow<'a, SortedTimestamps> {
+    match window.timestamps() {
+        Some(timestamps) => Cow::Borrowed(timestamps),
+        None => Cow::Owned(SortedTimestamps::from_unsorted(
+            window.iter().map(|item| headers_store.get_timestamp(item.0.hash).unwrap()).collect(),
+        )),
+    }
+}
+
+/// Makes `window` track the timestamps of its blocks, unless it already does since it was derived from a tracking window.
+/// Windows cached with their timestamps let the windows of their descendants be derived incrementally
+pub fn track_window_timestamps(headers_store: &impl HeaderStoreReader, window: &mut Arc<BlockWindowHeap>) {
+    if window.timestamps().is_none() {
+        let timestamps = sorted_window_timestamps(headers_store, window).into_owned();
+        Arc::make_mut(window).set_timestamps(Some(timestamps));
+    }
+}
 
 /// A past median manager conforming to the legacy golang implementation
 /// based on full, hence un-sampled, windows
@@ -20,9 +42,8 @@ impl<T: HeaderStoreReader> FullPastMedianTimeManager<T> {
             return Ok(self.genesis_timestamp);
         }
 
-        let mut window_timestamps: Vec<u64> =
-            window.iter().map(|item| self.headers_store.get_timestamp(item.0.hash).unwrap()).collect();
-        window_timestamps.sort_unstable(); // This is deterministic because we sort u64
+        let window_timestamps = sorted_window_timestamps(self.headers_store.as_ref(), window);
+        let window_timestamps = window_timestamps.as_slice();
         Ok(window_timestamps[window_timestamps.len() / 2])
     }
 }
@@ -49,9 +70,8 @@ impl<T: HeaderStoreReader> SampledPastMedianTimeManager<T> {
             return Ok(self.genesis_timestamp);
         }
 
-        let mut window_timestamps: Vec<u64> =
-            window.iter().map(|item| self.headers_store.get_timestamp(item.0.hash).unwrap()).collect();
-        window_timestamps.sort_unstable(); // This is deterministic because we sort u64
+        let window_timestamps = sorted_window_timestamps(self.headers_store.as_ref(), window);
+        let window_timestamps = window_timestamps.as_slice();
         let avg_frame_size = window_timestamps.len().min(AVERAGE_FRAME_SIZE);
         // Define the slice so that the average is the highest among the 2 possible solutions in case of an even frame size
         let ending_index = (window_timestamps.len() + avg_frame_size + 1) / 2;
@@ -61,3 +81,108 @@ impl<T: HeaderStoreReader> SampledPastMedianTimeManager<T> {
         Ok(timestamp)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        consensus::test_consensus::TestConsensus, model::stores::ghostdag::GhostdagStoreReader, processes::window::WindowManager,
+    };
+    use kaspa_consensus_core::{
+        api::ConsensusApi,
+        block::MutableBlock,
+        config::{
+            params::{ForkActivation, MAINNET_PARAMS},
+            ConfigBuilder,
+        },
+    };
+    use kaspa_hashes::Hash;
+    use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+
+    /// Builds a random DAG and checks that the incrementally derived window timestamps, and hence past median times,
+    /// are equal to those calculated naively by fetching and sorting the timestamps of each window
+    async fn check_incremental_past_median_time(sampled: bool) {
+        // Mainnet's max block level keeps all blocks at level 0, so headers built from direct parents only are valid
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| {
+                p.sampling_activation = if sampled { ForkActivation::always() } else { ForkActivation::never() };
+                // Small windows which are quickly filled and slide through the DAG
+                p.legacy_timestamp_deviation_tolerance = 16;
+                p.past_median_time_sampled_window_size = 31;
+                p.past_median_time_sample_rate = 2;
+            })
+            .build();
+        let consensus = TestConsensus::new(&config);
+        let wait_handles = consensus.init();
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        let mut blocks = vec![config.genesis.hash];
+        let mut tips = vec![config.genesis.hash];
+        for i in 1..=400u64 {
+            // Either widen the DAG by pointing at a recent block, or merge a few of the tips
+            let parents = if rng.gen_bool(0.2) {
+                vec![blocks[blocks.len() - rng.gen_range(1..=blocks.len().min(20))]]
+            } else {
+                let count = rng.gen_range(1..=3);
+                tips.choose_multiple(&mut rng, count).copied().collect()
+            };
+            let hash = Hash::from_u64_word(i);
+            let mut header = consensus.build_header_with_parents(hash, parents.clone());
+            // Random timestamps above the past median time, so that window timestamps are not ordered by blue work
+            header.timestamp += rng.gen_range(0..5000);
+            consensus.validate_and_insert_block(MutableBlock::from_header(header).to_immutable()).virtual_state_task.await.unwrap();
+            tips.retain(|tip| !parents.contains(tip));
+            tips.push(hash);
+            blocks.push(hash);
+
+            let ghostdag_data = consensus.ghostdag_store().get_data(hash).unwrap();
+            let (past_median_time, window) = consensus.window_manager().calc_past_median_time(&ghostdag_data).unwrap();
+            let mut untracked_window = (*window).clone();
+            untracked_window.set_timestamps(None);
+            let naive_timestamps = sorted_window_timestamps(consensus.headers_store().as_ref(), &untracked_window).into_owned();
+            assert_eq!(window.timestamps(), Some(&naive_timestamps), "window timestamps of block {i} diverged");
+
+            let naive_past_median_time = if sampled {
+                SampledPastMedianTimeManager::new(consensus.headers_store(), config.genesis.timestamp)
+                    .calc_past_median_time(&untracked_window)
+            } else {
+                FullPastMedianTimeManager::new(consensus.headers_store(), config.genesis.timestamp)
+                    .calc_past_median_time(&untracked_window)
+            };
+            assert_eq!(past_median_time, naive_past_median_time.unwrap(), "past median time of block {i} diverged");
+        }
+
+        consensus.shutdown(wait_handles);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_full_past_median_time() {
+        check_incremental_past_median_time(false).await;
+    }
+
+    #[tokio::test]
+    async fn test_incremental_sampled_past_median_time() {
+        check_incremental_past_median_time(true).await;
+    }
+
+    #[test]
+    fn test_sorted_timestamps() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let mut naive: Vec<u64> = (0..100).map(|_| rng.gen_range(0..50)).collect();
+        let mut timestamps = SortedTimestamps::from_unsorted(naive.clone());
+        for _ in 0..1000 {
+            if rng.gen_bool(0.5) {
+                let timestamp = rng.gen_range(0..50);
+                timestamps.insert(timestamp);
+                naive.push(timestamp);
+            } else if !naive.is_empty() {
+                let timestamp = naive.swap_remove(rng.gen_range(0..naive.len()));
+                timestamps.remove(timestamp);
+            }
+            let mut sorted = naive.clone();
+            sorted.sort_unstable();
+            assert_eq!(timestamps.as_slice(), sorted.as_slice());
+        }
+    }
+}
@@ -14,6 +14,7 @@ use kaspa_consensus_core::{
     BlockHashMap, BlockHashSet, BlockLevel, HashMapCustomHasher,
 };
 use kaspa_core::{debug, trace};
+use kaspa_database::prelude::StoreResultExtensions;
 use kaspa_hashes::Hash;
 use kaspa_pow::calc_block_level;
 use kaspa_utils::{binary_heap::BinaryHeapExtensions, vec::VecExtensions};
@@ -23,16 +24,17 @@ use crate::{
     model::{
         services::reachability::ReachabilityService,
         stores::{
-            ghostdag::{GhostdagData, GhostdagStore},
+            chain_scores::ChainScoresStore,
+            ghostdag::{GhostdagData, GhostdagStore, StagingGhostdagStore},
             headers::HeaderStore,
             reachability::StagingReachabilityStore,
-            relations::StagingRelationsStore,
+            relations::{RelationsStoreReader, StagingRelationsStore},
             selected_chain::SelectedChainStore,
-            virtual_state::{VirtualState, VirtualStateStore},
+            virtual_state::VirtualState,
         },
     },
     processes::{
-        ghostdag::{mergeset::unordered_mergeset_without_selected_parent, ordering::SortableBlock},
+        ghostdag::{mergeset::unordered_mergeset_without_selected_parent, ordering::SortableBlock, protocol::GhostdagManager},
         reachability::inquirer as reachability,
         relations::RelationsStoreExtensions,
     },
@@ -81,8 +83,18 @@ impl PruningProofManager {
             }
         }
 
+        // Relations of all levels, level 0 GHOSTDAG data and the initial virtual state are staged and committed in a
+        // single batch, so a failure never leaves a partially applied proof behind
+        let mut relations_write = self.relations_stores.write();
+        let mut virtual_write = self.virtual_stores.write();
+        let mut body_tips_write = self.body_tips_store.write();
+        let mut headers_selected_tip_write = self.headers_selected_tip_store.write();
+        let mut selected_chain_write = self.selected_chain_store.write();
+        let mut chain_scores_write = self.chain_scores_store.write();
+        let mut batch = WriteBatch::default();
         for (level, headers) in proof.iter().enumerate() {
             trace!("Applying level {} from the pruning point proof", level);
+            let mut staging_relations = StagingRelationsStore::new(&mut relations_write[level]);
             let mut level_ancestors: HashSet<Hash> = HashSet::new();
             level_ancestors.insert(ORIGIN);
 
@@ -97,13 +109,27 @@ impl PruningProofManager {
                         .push_if_empty(ORIGIN),
                 );
 
-                self.relations_stores.write()[level].insert(header.hash, parents.clone()).unwrap();
+                staging_relations.insert(header.hash, parents).unwrap_or_halt();
+                level_ancestors.insert(header.hash);
+            }
 
-                if level == 0 {
+            if level == 0 {
+                // GHOSTDAG is computed over the staged level 0 relations and the GHOSTDAG data staged for preceding headers
+                let staging_ghostdag = Arc::new(StagingGhostdagStore::new(&self.ghostdag_store));
+                let ghostdag_manager = GhostdagManager::new(
+                    self.genesis_hash,
+                    self.ghostdag_k,
+                    staging_ghostdag.clone(),
+                    &staging_relations,
+                    self.headers_store.clone(),
+                    self.reachability_service.clone(),
+                );
+                for header in headers.iter() {
                     let gd = if let Some(gd) = trusted_gd_map.get(&header.hash) {
                         gd.clone()
                     } else {
-                        let calculated_gd = self.ghostdag_manager.ghostdag(&parents);
+                        let parents = staging_relations.get_parents(header.hash).unwrap();
+                        let calculated_gd = ghostdag_manager.ghostdag(&parents);
                         // Override the ghostdag data with the real blue score and blue work
                         GhostdagData {
                             blue_score: header.blue_score,
@@ -114,30 +140,39 @@ impl PruningProofManager {
                             blues_anticone_sizes: calculated_gd.blues_anticone_sizes,
                         }
                     };
-                    self.ghostdag_store.insert(header.hash, Arc::new(gd)).unwrap();
+                    staging_ghostdag.insert(header.hash, Arc::new(gd)).unwrap_or_halt();
                 }
 
-                level_ancestors.insert(header.hash);
+                let virtual_parents = vec![pruning_point];
+                let virtual_state = Arc::new(VirtualState {
+                    parents: virtual_parents.clone(),
+                    ghostdag_data: ghostdag_manager.ghostdag(&virtual_parents),
+                    ..VirtualState::default()
+                });
+                virtual_write.state.set_batch(&mut batch, virtual_state).unwrap_or_halt();
+                body_tips_write.init_batch(&mut batch, &virtual_parents).unwrap();
+
+                drop(ghostdag_manager);
+                Arc::into_inner(staging_ghostdag).unwrap().commit(&mut batch).unwrap_or_halt();
             }
-        }
-
-        let virtual_parents = vec![pruning_point];
-        let virtual_state = Arc::new(VirtualState {
-            parents: virtual_parents.clone(),
-            ghostdag_data: self.ghostdag_manager.ghostdag(&virtual_parents),
-            ..VirtualState::default()
-        });
-        self.virtual_stores.write().state.set(virtual_state).unwrap();
 
-        let mut batch = WriteBatch::default();
-        self.body_tips_store.write().init_batch(&mut batch, &virtual_parents).unwrap();
-        self.headers_selected_tip_store
-            .write()
+            staging_relations.commit(&mut batch).unwrap_or_halt();
+        }
+        headers_selected_tip_write
             .set_batch(&mut batch, SortableBlock { hash: pruning_point, blue_work: pruning_point_header.blue_work })
             .unwrap();
-        self.selected_chain_store.write().init_with_pruning_point(&mut batch, pruning_point).unwrap();
-        self.depth_store.insert_batch(&mut batch, pruning_point, ORIGIN, ORIGIN).unwrap();
-        self.db.write(batch).unwrap();
+        selected_chain_write.init_with_pruning_point(&mut batch, pruning_point).unwrap();
+        chain_scores_write.set_base_index(&mut batch, 0).unwrap_or_halt();
+        self.depth_store.insert_batch(&mut batch, pruning_point, ORIGIN, ORIGIN).unwrap_or_halt();
+        self.db.write_batch(batch).unwrap_or_halt();
+
+        // Calling the drops explicitly after the batch is written in order to avoid possible errors.
+        drop(chain_scores_write);
+        drop(selected_chain_write);
+        drop(headers_selected_tip_write);
+        drop(body_tips_write);
+        drop(virtual_write);
+        drop(relations_write);
 
         Ok(())
     }
@@ -212,7 +247,7 @@ impl PruningProofManager {
             let mut staging_reachability_relations = StagingRelationsStore::new(&mut reachability_relations_write);
 
             // Stage
-            staging_reachability_relations.insert(hash, reachability_parents_hashes.clone()).unwrap();
+            staging_reachability_relations.insert(hash, reachability_parents_hashes.clone()).unwrap_or_halt();
             let mergeset = unordered_mergeset_without_selected_parent(
                 &staging_reachability_relations,
                 &staging_reachability,
@@ -222,11 +257,11 @@ impl PruningProofManager {
             reachability::add_block(&mut staging_reachability, hash, selected_parent, &mut mergeset.iter().copied()).unwrap();
 
             // Commit
-            let reachability_write = staging_reachability.commit(&mut batch).unwrap();
-            staging_reachability_relations.commit(&mut batch).unwrap();
+            let reachability_write = staging_reachability.commit(&mut batch).unwrap_or_halt();
+            staging_reachability_relations.commit(&mut batch).unwrap_or_halt();
 
             // Write
-            self.db.write(batch).unwrap();
+            self.db.write_batch(batch).unwrap_or_halt();
 
             // Drop
             drop(reachability_write);
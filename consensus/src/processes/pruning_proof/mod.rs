@@ -37,6 +37,7 @@ use crate::{
     model::{
         services::{reachability::MTReachabilityService, relations::MTRelationsService},
         stores::{
+            chain_scores::DbChainScoresStore,
             depth::DbDepthStore,
             ghostdag::{DbGhostdagStore, GhostdagStoreReader},
             headers::{DbHeadersStore, HeaderStore, HeaderStoreReader},
@@ -110,6 +111,7 @@ pub struct PruningProofManager {
     headers_selected_tip_store: Arc<RwLock<DbHeadersSelectedTipStore>>,
     depth_store: Arc<DbDepthStore>,
     selected_chain_store: Arc<RwLock<DbSelectedChainStore>>,
+    chain_scores_store: Arc<RwLock<DbChainScoresStore>>,
 
     ghostdag_manager: DbGhostdagManager,
     traversal_manager: DbDagTraversalManager,
@@ -159,6 +161,7 @@ impl PruningProofManager {
             body_tips_store: storage.body_tips_store.clone(),
             headers_selected_tip_store: storage.headers_selected_tip_store.clone(),
             selected_chain_store: storage.selected_chain_store.clone(),
+            chain_scores_store: storage.chain_scores_store.clone(),
             depth_store: storage.depth_store.clone(),
 
             traversal_manager,
@@ -185,7 +188,7 @@ impl PruningProofManager {
 
     pub fn import_pruning_points(&self, pruning_points: &[Arc<Header>]) {
         for (i, header) in pruning_points.iter().enumerate() {
-            self.past_pruning_points_store.set(i as u64, header.hash).unwrap();
+            self.past_pruning_points_store.set(i as u64, header.hash).unwrap_or_halt();
 
             if self.headers_store.has(header.hash).unwrap() {
                 continue;
@@ -200,9 +203,11 @@ impl PruningProofManager {
 
         let mut pruning_point_write = self.pruning_point_store.write();
         let mut batch = WriteBatch::default();
-        pruning_point_write.set_batch(&mut batch, new_pruning_point, new_pruning_point, (pruning_points.len() - 1) as u64).unwrap();
+        pruning_point_write
+            .set_batch(&mut batch, new_pruning_point, new_pruning_point, (pruning_points.len() - 1) as u64)
+            .unwrap_or_halt();
         pruning_point_write.set_history_root(&mut batch, new_pruning_point).unwrap();
-        self.db.write(batch).unwrap();
+        self.db.write_batch(batch).unwrap_or_halt();
         drop(pruning_point_write);
     }
 
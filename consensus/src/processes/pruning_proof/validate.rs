@@ -216,10 +216,10 @@ impl PruningProofManager {
                 let level = level as usize;
                 reachability::init(reachability_stores[level].write().deref_mut()).unwrap();
                 relations_stores[level].insert_batch(&mut batch, ORIGIN, BlockHashes::new(vec![])).unwrap();
-                ghostdag_stores[level].insert(ORIGIN, ghostdag_managers[level].origin_ghostdag_data()).unwrap();
+                ghostdag_stores[level].insert(ORIGIN, ghostdag_managers[level].origin_ghostdag_data()).unwrap_or_halt();
             }
 
-            db.write(batch).unwrap();
+            db.write_batch(batch).unwrap_or_halt();
         }
 
         Ok(TempProofContext { db_lifetime, headers_store, ghostdag_stores, relations_stores, reachability_stores, ghostdag_managers })
@@ -282,9 +282,9 @@ impl PruningProofManager {
                     return Err(PruningImportError::PruningProofDuplicateHeaderAtLevel(header.hash, level));
                 }
 
-                relations_stores[level_idx].insert(header.hash, parents.clone()).unwrap();
+                relations_stores[level_idx].insert(header.hash, parents.clone()).unwrap_or_halt();
                 let ghostdag_data = Arc::new(ghostdag_managers[level_idx].ghostdag(&parents));
-                ghostdag_stores[level_idx].insert(header.hash, ghostdag_data.clone()).unwrap();
+                ghostdag_stores[level_idx].insert(header.hash, ghostdag_data.clone()).unwrap_or_halt();
                 selected_tip = Some(match selected_tip {
                     Some(tip) => ghostdag_managers[level_idx].find_selected_parent([tip, header.hash]),
                     None => header.hash,
@@ -27,24 +27,18 @@ pub fn add_block(
     selected_parent: Hash,
     mergeset_iterator: HashIterator,
 ) -> Result<()> {
-    add_block_with_params(store, new_block, selected_parent, mergeset_iterator, None, None)
+    add_block_with_params(store, new_block, selected_parent, mergeset_iterator, &ReindexParams::default())
 }
 
-fn add_block_with_params(
+/// Same as [`add_block`], using the provided reindex `params`
+pub fn add_block_with_params(
     store: &mut (impl ReachabilityStore + ?Sized),
     new_block: Hash,
     selected_parent: Hash,
     mergeset_iterator: HashIterator,
-    reindex_depth: Option<u64>,
-    reindex_slack: Option<u64>,
+    params: &ReindexParams,
 ) -> Result<()> {
-    add_tree_block(
-        store,
-        new_block,
-        selected_parent,
-        reindex_depth.unwrap_or(crate::constants::perf::DEFAULT_REINDEX_DEPTH),
-        reindex_slack.unwrap_or(crate::constants::perf::DEFAULT_REINDEX_SLACK),
-    )?;
+    add_tree_block(store, new_block, selected_parent, params)?;
     add_dag_block(store, new_block, mergeset_iterator)?;
     Ok(())
 }
@@ -148,12 +142,16 @@ fn insert_to_future_covering_set(store: &mut (impl ReachabilityStore + ?Sized),
 /// as moving the reindex point. The consensus runtime is expected to call this function
 /// for a new header selected tip which is `header only` / `pending UTXO verification`, or for a completely resolved `sink`.
 pub fn hint_virtual_selected_parent(store: &mut (impl ReachabilityStore + ?Sized), hint: Hash) -> Result<()> {
-    try_advancing_reindex_root(
-        store,
-        hint,
-        crate::constants::perf::DEFAULT_REINDEX_DEPTH,
-        crate::constants::perf::DEFAULT_REINDEX_SLACK,
-    )
+    hint_virtual_selected_parent_with_params(store, hint, &ReindexParams::default())
+}
+
+/// Same as [`hint_virtual_selected_parent`], using the provided reindex `params`
+pub fn hint_virtual_selected_parent_with_params(
+    store: &mut (impl ReachabilityStore + ?Sized),
+    hint: Hash,
+    params: &ReindexParams,
+) -> Result<()> {
+    try_advancing_reindex_root(store, hint, params)
 }
 
 /// Checks if the `this` block is a strict chain ancestor of the `queried` block (aka `this ∈ chain(queried)`).
@@ -268,7 +266,7 @@ mod tests {
     use itertools::Itertools;
     use kaspa_consensus_core::blockhash::ORIGIN;
     use kaspa_database::prelude::ConnBuilder;
-    use kaspa_database::{create_temp_db, prelude::CachePolicy};
+    use kaspa_database::{create_memory_db, prelude::CachePolicy};
     use parking_lot::RwLock;
     use rand::seq::IteratorRandom;
     use rocksdb::WriteBatch;
@@ -386,7 +384,7 @@ mod tests {
     /// Runs a DAG test-case with full verification using the staging store mechanism.
     /// Note: runtime is quadratic in the number of blocks so should be used with mildly small DAGs (~50)
     fn run_dag_test_case_with_staging(test: &DagTestCase) {
-        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (_lifetime, db) = create_memory_db!(ConnBuilder::default().with_files_limit(10));
         let cache_policy = CachePolicy::Count(test.blocks.len() / 3);
         let reachability = RwLock::new(DbReachabilityStore::new(db.clone(), cache_policy, cache_policy));
         let mut relations = DbRelationsStore::with_prefix(db.clone(), &[], CachePolicy::Empty, CachePolicy::Empty);
@@ -407,7 +405,7 @@ mod tests {
                 let mut batch = WriteBatch::default();
                 let reachability_write = staging_reachability.commit(&mut batch).unwrap();
                 staging_relations.commit(&mut batch).unwrap();
-                db.write(batch).unwrap();
+                db.write_batch(batch).unwrap();
                 drop(reachability_write);
             }
         }
@@ -460,7 +458,7 @@ mod tests {
                 {
                     let reachability_write = staging_reachability.commit(&mut batch).unwrap();
                     staging_relations.commit(&mut batch).unwrap();
-                    db.write(batch).unwrap();
+                    db.write_batch(batch).unwrap();
                     drop(reachability_write);
                 }
 
@@ -520,7 +518,7 @@ mod tests {
             run_dag_test_case(&mut relations, &mut reachability, &test);
 
             // Run with direct DB stores
-            let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let (_lifetime, db) = create_memory_db!(ConnBuilder::default().with_files_limit(10));
             let cache_policy = CachePolicy::Count(test.blocks.len() / 3);
             let mut reachability = DbReachabilityStore::new(db.clone(), cache_policy, cache_policy);
             let mut relations = DbRelationsStore::new(db, 0, cache_policy, cache_policy);
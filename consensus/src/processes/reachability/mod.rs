@@ -5,7 +5,12 @@ mod reindex;
 pub mod tests;
 mod tree;
 
+use crate::{constants::perf::PerfParams, pipeline::ProcessingCounters};
 use kaspa_database::prelude::StoreError;
+use std::{
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -31,6 +36,45 @@ impl ReachabilityError {
 
 pub type Result<T> = std::result::Result<T, ReachabilityError>;
 
+/// Tuning params of reindex operations along with optional counters for collecting reindex telemetry
+#[derive(Clone)]
+pub struct ReindexParams {
+    /// The depth below the selected tip at which the reindex root is maintained
+    pub depth: u64,
+    /// The height difference required for the reindex root to switch chains on a reorg
+    pub slack: u64,
+    counters: Option<Arc<ProcessingCounters>>,
+}
+
+impl ReindexParams {
+    pub fn new(depth: u64, slack: u64) -> Self {
+        Self { depth, slack, counters: None }
+    }
+
+    pub fn from_perf_params(perf_params: &PerfParams) -> Self {
+        Self::new(perf_params.reindex_depth, perf_params.reindex_slack)
+    }
+
+    pub fn with_counters(self, counters: Arc<ProcessingCounters>) -> Self {
+        Self { counters: Some(counters), ..self }
+    }
+
+    /// Records a reindex operation which traversed `subtree_size` blocks in `elapsed` time
+    fn record(&self, subtree_size: u64, elapsed: Duration) {
+        if let Some(counters) = self.counters.as_ref() {
+            counters.reindex_counts.fetch_add(1, Ordering::Relaxed);
+            counters.reindex_subtree_sizes.fetch_add(subtree_size, Ordering::Relaxed);
+            counters.reindex_micros.fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Default for ReindexParams {
+    fn default() -> Self {
+        Self::new(crate::constants::perf::DEFAULT_REINDEX_DEPTH, crate::constants::perf::DEFAULT_REINDEX_SLACK)
+    }
+}
+
 pub trait ReachabilityResultExtensions<T> {
     /// Unwraps the error into `None` if the internal error is `StoreError::KeyNotFound` or panics otherwise
     fn unwrap_option(self) -> Option<T>;
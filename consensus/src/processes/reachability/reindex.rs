@@ -18,6 +18,11 @@ impl<'a, T: ReachabilityStore + ?Sized> ReindexOperationContext<'a, T> {
         Self { store, subtree_sizes: BlockHashMap::new(), depth, slack }
     }
 
+    /// The number of blocks whose subtree sizes were computed during this operation
+    pub(super) fn traversed_blocks(&self) -> u64 {
+        self.subtree_sizes.len() as u64
+    }
+
     /// Traverses the reachability subtree that's defined by the new child
     /// block and reallocates reachability interval space
     /// such that another reindexing is unlikely to occur shortly
@@ -1,7 +1,7 @@
 //!
 //! Test utils for reachability
 //!
-use super::{inquirer::*, tree::*};
+use super::{inquirer::*, tree::*, ReindexParams};
 use crate::{
     model::stores::{
         children::ChildrenStore,
@@ -83,8 +83,9 @@ impl<'a, T: ReachabilityStore + ?Sized> TreeBuilder<'a, T> {
     }
 
     pub fn add_block(&mut self, hash: Hash, parent: Hash) -> &mut Self {
-        add_tree_block(self.store, hash, parent, self.reindex_depth, self.reindex_slack).unwrap();
-        try_advancing_reindex_root(self.store, hash, self.reindex_depth, self.reindex_slack).unwrap();
+        let params = ReindexParams::new(self.reindex_depth, self.reindex_slack);
+        add_tree_block(self.store, hash, parent, &params).unwrap();
+        try_advancing_reindex_root(self.store, hash, &params).unwrap();
         self
     }
 
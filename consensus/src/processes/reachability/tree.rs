@@ -4,6 +4,7 @@
 use super::{extensions::ReachabilityStoreIntervalExtensions, inquirer::*, reindex::ReindexOperationContext, *};
 use crate::model::stores::reachability::ReachabilityStore;
 use kaspa_hashes::Hash;
+use std::time::Instant;
 
 /// Adds `new_block` as a child of `parent` in the tree structure. If this block
 /// has no remaining interval to allocate, a reindexing is triggered. When a reindexing
@@ -12,8 +13,7 @@ pub fn add_tree_block(
     store: &mut (impl ReachabilityStore + ?Sized),
     new_block: Hash,
     parent: Hash,
-    reindex_depth: u64,
-    reindex_slack: u64,
+    params: &ReindexParams,
 ) -> Result<()> {
     // Get the remaining interval capacity
     let remaining = store.interval_remaining_after(parent)?;
@@ -26,10 +26,12 @@ pub fn add_tree_block(
         //       which comes exactly at the end of current capacity
         store.insert(new_block, parent, remaining, parent_height + 1)?;
 
-        // Start a reindex operation (TODO: add timing)
+        // Start a reindex operation
+        let start = Instant::now();
         let reindex_root = store.get_reindex_root()?;
-        let mut ctx = ReindexOperationContext::new(store, reindex_depth, reindex_slack);
+        let mut ctx = ReindexOperationContext::new(store, params.depth, params.slack);
         ctx.reindex_intervals(new_block, reindex_root)?;
+        params.record(ctx.traversed_blocks(), start.elapsed());
     } else {
         let allocated = remaining.split_half().0;
         store.insert(new_block, parent, allocated, parent_height + 1)?;
@@ -109,17 +111,12 @@ pub fn find_next_reindex_root(
 /// of the ordering protocol. That is, it enjoys from the fact that all future blocks are
 /// expected to elect the root subtree (by converging to the agreement to have it on the
 /// selected chain). See also the reachability algorithms overview (TODO)
-pub fn try_advancing_reindex_root(
-    store: &mut (impl ReachabilityStore + ?Sized),
-    hint: Hash,
-    reindex_depth: u64,
-    reindex_slack: u64,
-) -> Result<()> {
+pub fn try_advancing_reindex_root(store: &mut (impl ReachabilityStore + ?Sized), hint: Hash, params: &ReindexParams) -> Result<()> {
     // Get current root from the store
     let current = store.get_reindex_root()?;
 
     // Find the possible new root
-    let (mut ancestor, next) = find_next_reindex_root(store, current, hint, reindex_depth, reindex_slack)?;
+    let (mut ancestor, next) = find_next_reindex_root(store, current, hint, params.depth, params.slack)?;
 
     // No update to root, return
     if current == next {
@@ -131,8 +128,10 @@ pub fn try_advancing_reindex_root(
     // }
     while ancestor != next {
         let child = get_next_chain_ancestor_unchecked(store, next, ancestor)?;
-        let mut ctx = ReindexOperationContext::new(store, reindex_depth, reindex_slack);
+        let start = Instant::now();
+        let mut ctx = ReindexOperationContext::new(store, params.depth, params.slack);
         ctx.concentrate_interval(ancestor, child, child == next)?;
+        params.record(ctx.traversed_blocks(), start.elapsed());
         ancestor = child;
     }
 
@@ -154,12 +154,12 @@ mod tests {
     use crate::model::stores::relations::{DbRelationsStore, RelationsStoreReader, StagingRelationsStore};
     use kaspa_core::assert_match;
     use kaspa_database::prelude::{CachePolicy, ConnBuilder};
-    use kaspa_database::{create_temp_db, prelude::MemoryWriter};
+    use kaspa_database::{create_memory_db, prelude::MemoryWriter};
     use std::sync::Arc;
 
     #[test]
     fn test_delete_level_relations_zero_cache() {
-        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (_lifetime, db) = create_memory_db!(ConnBuilder::default().with_files_limit(10));
         let mut relations = DbRelationsStore::new(db.clone(), 0, CachePolicy::Empty, CachePolicy::Empty);
         relations.insert(ORIGIN, Default::default()).unwrap();
         relations.insert(1.into(), Arc::new(vec![ORIGIN])).unwrap();
@@ -185,7 +185,7 @@ mod tests {
         let mut staging_relations = StagingRelationsStore::new(&mut relations);
         delete_level_relations(MemoryWriter, &mut staging_relations, 1.into()).unwrap();
         staging_relations.commit(&mut batch).unwrap();
-        db.write(batch).unwrap();
+        db.write_batch(batch).unwrap();
 
         assert_match!(relations.get_parents(1.into()), Err(StoreError::KeyNotFound(_)));
         assert_match!(relations.get_children(1.into()).unwrap_err(), StoreError::KeyNotFound(_));
@@ -205,7 +205,7 @@ mod tests {
         let mut staging_relations = StagingRelationsStore::new(&mut relations);
         delete_level_relations(MemoryWriter, &mut staging_relations, 2.into()).unwrap();
         staging_relations.commit(&mut batch).unwrap();
-        db.write(batch).unwrap();
+        db.write_batch(batch).unwrap();
 
         assert_match!(relations.get_parents(2.into()), Err(StoreError::KeyNotFound(_)));
         assert_match!(relations.get_children(2.into()), Err(StoreError::KeyNotFound(_)));
@@ -30,6 +30,12 @@ pub struct TransactionValidator {
     storage_mass_activation: ForkActivation,
     /// KIP-10 hardfork DAA score
     kip10_activation: ForkActivation,
+    /// DAA score from which native transactions may carry a payload
+    payload_activation: ForkActivation,
+    /// Fork id folded into signature hashes
+    sighash_fork_id: Option<u8>,
+    /// DAA score from which the fork id is folded into signature hashes
+    sighash_fork_activation: ForkActivation,
 }
 
 impl TransactionValidator {
@@ -46,6 +52,9 @@ impl TransactionValidator {
         mass_calculator: MassCalculator,
         storage_mass_activation: ForkActivation,
         kip10_activation: ForkActivation,
+        payload_activation: ForkActivation,
+        sighash_fork_id: Option<u8>,
+        sighash_fork_activation: ForkActivation,
     ) -> Self {
         Self {
             max_tx_inputs,
@@ -59,6 +68,9 @@ impl TransactionValidator {
             mass_calculator,
             storage_mass_activation,
             kip10_activation,
+            payload_activation,
+            sighash_fork_id,
+            sighash_fork_activation,
         }
     }
 
@@ -84,6 +96,9 @@ impl TransactionValidator {
             mass_calculator: MassCalculator::new(0, 0, 0, 0),
             storage_mass_activation: ForkActivation::never(),
             kip10_activation: ForkActivation::never(),
+            payload_activation: ForkActivation::never(),
+            sighash_fork_id: None,
+            sighash_fork_activation: ForkActivation::never(),
         }
     }
 }
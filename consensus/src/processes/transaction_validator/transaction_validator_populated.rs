@@ -1,7 +1,8 @@
-use crate::constants::{MAX_SOMPI, SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK};
+use crate::constants::{SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK};
 use kaspa_consensus_core::{
     hashing::sighash::{SigHashReusedValuesSync, SigHashReusedValuesUnsync},
     mass::Kip9Version,
+    sompi::Sompi,
     tx::{TransactionInput, VerifiableTransaction},
 };
 use kaspa_core::warn;
@@ -43,7 +44,7 @@ impl TransactionValidator {
         self.check_transaction_coinbase_maturity(tx, pov_daa_score)?;
         let total_in = self.check_transaction_input_amounts(tx)?;
         let total_out = Self::check_transaction_output_values(tx, total_in)?;
-        let fee = total_in - total_out;
+        let fee = total_in.checked_sub(total_out).expect("outputs do not exceed inputs").as_u64();
         if flags != TxValidationFlags::SkipMassCheck && self.storage_mass_activation.is_active(pov_daa_score) {
             // Storage mass hardfork was activated
             self.check_mass_commitment(tx)?;
@@ -98,16 +99,11 @@ impl TransactionValidator {
         Ok(())
     }
 
-    fn check_transaction_input_amounts(&self, tx: &impl VerifiableTransaction) -> TxResult<u64> {
-        let mut total: u64 = 0;
+    fn check_transaction_input_amounts(&self, tx: &impl VerifiableTransaction) -> TxResult<Sompi> {
+        let mut total = Sompi::ZERO;
         for (_, entry) in tx.populated_inputs() {
-            if let Some(new_total) = total.checked_add(entry.amount) {
-                total = new_total
-            } else {
-                return Err(TxRuleError::InputAmountOverflow);
-            }
-
-            if total > MAX_SOMPI {
+            total = total.checked_add(entry.amount).ok_or(TxRuleError::InputAmountOverflow)?;
+            if !total.is_valid() {
                 return Err(TxRuleError::InputAmountTooHigh);
             }
         }
@@ -115,11 +111,11 @@ impl TransactionValidator {
         Ok(total)
     }
 
-    fn check_transaction_output_values(tx: &impl VerifiableTransaction, total_in: u64) -> TxResult<u64> {
-        // There's no need to check for overflow here because it was already checked by check_transaction_output_value_ranges
-        let total_out: u64 = tx.outputs().iter().map(|out| out.value).sum();
+    fn check_transaction_output_values(tx: &impl VerifiableTransaction, total_in: Sompi) -> TxResult<Sompi> {
+        // Overflow was already ruled out by check_transaction_output_value_ranges, but is still checked for robustness
+        let total_out = Sompi::checked_sum(tx.outputs().iter().map(|out| out.value)).ok_or(TxRuleError::OutputsValueOverflow)?;
         if total_in < total_out {
-            return Err(TxRuleError::SpendTooHigh(total_out, total_in));
+            return Err(TxRuleError::SpendTooHigh(total_out.as_u64(), total_in.as_u64()));
         }
 
         Ok(total_out)
@@ -173,7 +169,8 @@ impl TransactionValidator {
     }
 
     pub fn check_scripts(&self, tx: &(impl VerifiableTransaction + Sync), pov_daa_score: u64) -> TxResult<()> {
-        check_scripts(&self.sig_cache, tx, self.kip10_activation.is_active(pov_daa_score))
+        let sighash_fork_id = self.sighash_fork_id.filter(|_| self.sighash_fork_activation.is_active(pov_daa_score));
+        check_scripts(&self.sig_cache, tx, self.kip10_activation.is_active(pov_daa_score), sighash_fork_id)
     }
 }
 
@@ -181,11 +178,12 @@ pub fn check_scripts(
     sig_cache: &Cache<SigCacheKey, bool>,
     tx: &(impl VerifiableTransaction + Sync),
     kip10_enabled: bool,
+    sighash_fork_id: Option<u8>,
 ) -> TxResult<()> {
     if tx.inputs().len() > CHECK_SCRIPTS_PARALLELISM_THRESHOLD {
-        check_scripts_par_iter(sig_cache, tx, kip10_enabled)
+        check_scripts_par_iter(sig_cache, tx, kip10_enabled, sighash_fork_id)
     } else {
-        check_scripts_sequential(sig_cache, tx, kip10_enabled)
+        check_scripts_sequential(sig_cache, tx, kip10_enabled, sighash_fork_id)
     }
 }
 
@@ -193,10 +191,12 @@ pub fn check_scripts_sequential(
     sig_cache: &Cache<SigCacheKey, bool>,
     tx: &impl VerifiableTransaction,
     kip10_enabled: bool,
+    sighash_fork_id: Option<u8>,
 ) -> TxResult<()> {
     let reused_values = SigHashReusedValuesUnsync::new();
     for (i, (input, entry)) in tx.populated_inputs().enumerate() {
         TxScriptEngine::from_transaction_input(tx, input, i, entry, &reused_values, sig_cache, kip10_enabled)
+            .with_sighash_fork_id(sighash_fork_id)
             .execute()
             .map_err(|err| map_script_err(err, input))?;
     }
@@ -207,11 +207,13 @@ pub fn check_scripts_par_iter(
     sig_cache: &Cache<SigCacheKey, bool>,
     tx: &(impl VerifiableTransaction + Sync),
     kip10_enabled: bool,
+    sighash_fork_id: Option<u8>,
 ) -> TxResult<()> {
     let reused_values = SigHashReusedValuesSync::new();
     (0..tx.inputs().len()).into_par_iter().try_for_each(|idx| {
         let (input, utxo) = tx.populated_input(idx);
         TxScriptEngine::from_transaction_input(tx, input, idx, utxo, &reused_values, sig_cache, kip10_enabled)
+            .with_sighash_fork_id(sighash_fork_id)
             .execute()
             .map_err(|err| map_script_err(err, input))
     })
@@ -222,8 +224,9 @@ pub fn check_scripts_par_iter_pool(
     tx: &(impl VerifiableTransaction + Sync),
     pool: &ThreadPool,
     kip10_enabled: bool,
+    sighash_fork_id: Option<u8>,
 ) -> TxResult<()> {
-    pool.install(|| check_scripts_par_iter(sig_cache, tx, kip10_enabled))
+    pool.install(|| check_scripts_par_iter(sig_cache, tx, kip10_enabled, sighash_fork_id))
 }
 
 fn map_script_err(script_err: TxScriptError, input: &TransactionInput) -> TxRuleError {
@@ -240,7 +243,8 @@ mod tests {
     use super::CHECK_SCRIPTS_PARALLELISM_THRESHOLD;
     use core::str::FromStr;
     use itertools::Itertools;
-    use kaspa_consensus_core::sign::sign;
+    use kaspa_consensus_core::config::params::ForkActivation;
+    use kaspa_consensus_core::sign::{sign, sign_with_fork_id};
     use kaspa_consensus_core::subnets::SubnetworkId;
     use kaspa_consensus_core::tx::{MutableTransaction, PopulatedTransaction, ScriptVec, TransactionId, UtxoEntry};
     use kaspa_consensus_core::tx::{ScriptPublicKey, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput};
@@ -825,4 +829,66 @@ mod tests {
         assert_eq!(tv.check_scripts(&populated_tx, u64::MAX), Ok(()));
         assert_eq!(TransactionValidator::check_sig_op_counts(&populated_tx), Ok(()));
     }
+
+    #[test]
+    fn test_sighash_fork_activation() {
+        const FORK_ID: u8 = 7;
+        const ACTIVATION_DAA_SCORE: u64 = 1000;
+
+        let params = MAINNET_PARAMS.clone();
+        let mut tv = TransactionValidator::new_for_tests(
+            params.max_tx_inputs,
+            params.max_tx_outputs,
+            params.max_signature_script_len,
+            params.max_script_public_key_len,
+            params.ghostdag_k,
+            params.coinbase_payload_script_public_key_max_len,
+            params.coinbase_maturity,
+            Default::default(),
+        );
+        tv.sighash_fork_id = Some(FORK_ID);
+        tv.sighash_fork_activation = ForkActivation::new(ACTIVATION_DAA_SCORE);
+
+        let secp = Secp256k1::new();
+        let (secret_key, public_key) = secp.generate_keypair(&mut rand::thread_rng());
+        let (public_key, _) = public_key.x_only_public_key();
+        let script_pub_key = once(0x20).chain(public_key.serialize()).chain(once(0xac)).collect_vec();
+        let script_public_key = ScriptPublicKey::new(0, ScriptVec::from_slice(&script_pub_key));
+
+        let prev_tx_id = TransactionId::from_str("880eb9819a31821d9d2399e2f35e2433b72637e393d71ecc9b8d0250f49153c3").unwrap();
+        let unsigned_tx = Transaction::new(
+            0,
+            vec![TransactionInput {
+                previous_outpoint: TransactionOutpoint { transaction_id: prev_tx_id, index: 0 },
+                signature_script: vec![],
+                sequence: 0,
+                sig_op_count: 0,
+            }],
+            vec![TransactionOutput { value: 100, script_public_key: script_public_key.clone() }],
+            0,
+            SubnetworkId::from_bytes([0; 20]),
+            0,
+            vec![],
+        );
+        let entries = vec![UtxoEntry { amount: 200, script_public_key, block_daa_score: 0, is_coinbase: false }];
+        let schnorr_key = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &secret_key.secret_bytes()).unwrap();
+
+        // A signature without the fork id is valid only before activation
+        let signed_tx = sign(MutableTransaction::with_entries(unsigned_tx.clone(), entries.clone()), schnorr_key);
+        let populated_tx = signed_tx.as_verifiable();
+        assert_eq!(tv.check_scripts(&populated_tx, ACTIVATION_DAA_SCORE - 1), Ok(()));
+        assert_eq!(
+            tv.check_scripts(&populated_tx, ACTIVATION_DAA_SCORE),
+            Err(TxRuleError::SignatureInvalid(TxScriptError::EvalFalse))
+        );
+
+        // A signature committing to the fork id is valid only after activation
+        let signed_tx = sign_with_fork_id(MutableTransaction::with_entries(unsigned_tx, entries), schnorr_key, Some(FORK_ID));
+        let populated_tx = signed_tx.as_verifiable();
+        assert_eq!(
+            tv.check_scripts(&populated_tx, ACTIVATION_DAA_SCORE - 1),
+            Err(TxRuleError::SignatureInvalid(TxScriptError::EvalFalse))
+        );
+        assert_eq!(tv.check_scripts(&populated_tx, ACTIVATION_DAA_SCORE), Ok(()));
+    }
 }
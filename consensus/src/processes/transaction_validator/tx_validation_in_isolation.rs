@@ -1,5 +1,5 @@
-use crate::constants::{MAX_SOMPI, TX_VERSION};
-use kaspa_consensus_core::tx::Transaction;
+use crate::constants::TX_VERSION;
+use kaspa_consensus_core::{sompi::Sompi, tx::Transaction};
 use std::collections::HashSet;
 
 use super::{
@@ -16,7 +16,6 @@ impl TransactionValidator {
         check_transaction_output_value_ranges(tx)?;
         check_duplicate_transaction_inputs(tx)?;
         check_gas(tx)?;
-        check_transaction_payload(tx)?;
         check_transaction_subnetwork(tx)?;
         check_transaction_version(tx)
     }
@@ -107,14 +106,6 @@ fn check_gas(tx: &Transaction) -> TxResult<()> {
     Ok(())
 }
 
-fn check_transaction_payload(tx: &Transaction) -> TxResult<()> {
-    // This should be revised if subnetworks are activated (along with other validations that weren't copied from kaspad)
-    if !tx.is_coinbase() && !tx.payload.is_empty() {
-        return Err(TxRuleError::NonCoinbaseTxHasPayload);
-    }
-    Ok(())
-}
-
 fn check_transaction_version(tx: &Transaction) -> TxResult<()> {
     if tx.version != TX_VERSION {
         return Err(TxRuleError::UnknownTxVersion(tx.version));
@@ -123,23 +114,19 @@ fn check_transaction_version(tx: &Transaction) -> TxResult<()> {
 }
 
 fn check_transaction_output_value_ranges(tx: &Transaction) -> TxResult<()> {
-    let mut total: u64 = 0;
+    let mut total = Sompi::ZERO;
     for (i, output) in tx.outputs.iter().enumerate() {
-        if output.value == 0 {
+        let value = Sompi::from(output.value);
+        if value.is_zero() {
             return Err(TxRuleError::TxOutZero(i));
         }
 
-        if output.value > MAX_SOMPI {
+        if !value.is_valid() {
             return Err(TxRuleError::TxOutTooHigh(i));
         }
 
-        if let Some(new_total) = total.checked_add(output.value) {
-            total = new_total
-        } else {
-            return Err(TxRuleError::OutputsValueOverflow);
-        }
-
-        if total > MAX_SOMPI {
+        total = total.checked_add(value).ok_or(TxRuleError::OutputsValueOverflow)?;
+        if !total.is_valid() {
             return Err(TxRuleError::TotalTxOutTooHigh);
         }
     }
@@ -302,10 +289,6 @@ mod tests {
         tx.gas = 1;
         assert_match!(tv.validate_tx_in_isolation(&tx), Err(TxRuleError::TxHasGas));
 
-        let mut tx = valid_tx.clone();
-        tx.payload = vec![0];
-        assert_match!(tv.validate_tx_in_isolation(&tx), Err(TxRuleError::NonCoinbaseTxHasPayload));
-
         let mut tx = valid_tx;
         tx.version = TX_VERSION + 1;
         assert_match!(tv.validate_tx_in_isolation(&tx), Err(TxRuleError::UnknownTxVersion(_)));
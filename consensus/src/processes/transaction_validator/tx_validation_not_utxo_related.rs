@@ -12,6 +12,15 @@ impl TransactionValidator {
         self.check_tx_is_finalized(tx, ctx_daa_score, ctx_block_time)
     }
 
+    /// Non-coinbase transactions may only carry a payload once payloads are activated. Block transactions are checked
+    /// against the DAA score of the block header as part of body validation in isolation
+    pub fn check_transaction_payload(&self, tx: &Transaction, ctx_daa_score: u64) -> TxResult<()> {
+        if !tx.is_coinbase() && !tx.payload.is_empty() && !self.payload_activation.is_active(ctx_daa_score) {
+            return Err(TxRuleError::NonCoinbaseTxHasPayload);
+        }
+        Ok(())
+    }
+
     fn check_tx_is_finalized(&self, tx: &Transaction, ctx_daa_score: u64, ctx_block_time: u64) -> TxResult<()> {
         // Lock time of zero means the transaction is finalized.
         if tx.lock_time == 0 {
@@ -39,3 +48,38 @@ impl TransactionValidator {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{params::MAINNET_PARAMS, processes::transaction_validator::TransactionValidator};
+    use kaspa_consensus_core::{
+        config::params::ForkActivation,
+        subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
+    };
+    use kaspa_core::assert_match;
+
+    #[test]
+    fn check_transaction_payload_test() {
+        let params = MAINNET_PARAMS.clone();
+        let mut tv = TransactionValidator::new_for_tests(
+            params.max_tx_inputs,
+            params.max_tx_outputs,
+            params.max_signature_script_len,
+            params.max_script_public_key_len,
+            params.ghostdag_k,
+            params.coinbase_payload_script_public_key_max_len,
+            params.coinbase_maturity,
+            Default::default(),
+        );
+        let coinbase = Transaction::new(0, vec![], vec![], 0, SUBNETWORK_ID_COINBASE, 0, vec![1, 2, 3]);
+        let native = Transaction::new(0, vec![], vec![], 0, SUBNETWORK_ID_NATIVE, 0, vec![1, 2, 3]);
+
+        tv.check_transaction_payload(&coinbase, 0).unwrap();
+        assert_match!(tv.check_transaction_payload(&native, 0), Err(TxRuleError::NonCoinbaseTxHasPayload));
+
+        tv.payload_activation = ForkActivation::new(100);
+        assert_match!(tv.check_transaction_payload(&native, 99), Err(TxRuleError::NonCoinbaseTxHasPayload));
+        tv.check_transaction_payload(&native, 100).unwrap();
+    }
+}
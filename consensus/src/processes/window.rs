@@ -26,7 +26,7 @@ use std::{
 
 use super::{
     difficulty::{FullDifficultyManager, SampledDifficultyManager},
-    past_median_time::{FullPastMedianTimeManager, SampledPastMedianTimeManager},
+    past_median_time::{track_window_timestamps, FullPastMedianTimeManager, SampledPastMedianTimeManager},
 };
 
 #[derive(Clone, Copy)]
@@ -66,6 +66,7 @@ pub trait WindowManager {
 pub struct FullWindowManager<T: GhostdagStoreReader, U: BlockWindowCacheReader, V: HeaderStoreReader> {
     genesis_hash: Hash,
     ghostdag_store: Arc<T>,
+    headers_store: Arc<V>,
     block_window_cache_for_difficulty: Arc<U>,
     block_window_cache_for_past_median_time: Arc<U>,
     difficulty_window_size: usize,
@@ -95,10 +96,11 @@ impl<T: GhostdagStoreReader, U: BlockWindowCacheReader, V: HeaderStoreReader> Fu
             min_difficulty_window_len,
             target_time_per_block,
         );
-        let past_median_time_manager = FullPastMedianTimeManager::new(headers_store, genesis.timestamp);
+        let past_median_time_manager = FullPastMedianTimeManager::new(headers_store.clone(), genesis.timestamp);
         Self {
             genesis_hash: genesis.hash,
             ghostdag_store,
+            headers_store,
             block_window_cache_for_difficulty,
             block_window_cache_for_past_median_time,
             difficulty_window_size,
@@ -135,7 +137,7 @@ impl<T: GhostdagStoreReader, U: BlockWindowCacheReader, V: HeaderStoreReader> Fu
                         );
                     }
 
-                    return Ok(Arc::new(window_heap.binary_heap));
+                    return Ok(Arc::new(window_heap.into_window(self.headers_store.deref())));
                 }
             }
         }
@@ -215,7 +217,8 @@ impl<T: GhostdagStoreReader, U: BlockWindowCacheReader, V: HeaderStoreReader> Wi
     }
 
     fn calc_past_median_time(&self, ghostdag_data: &GhostdagData) -> Result<(u64, Arc<BlockWindowHeap>), RuleError> {
-        let window = self.block_window(ghostdag_data, WindowType::SampledMedianTimeWindow)?;
+        let mut window = self.block_window(ghostdag_data, WindowType::SampledMedianTimeWindow)?;
+        track_window_timestamps(self.headers_store.deref(), &mut window);
         let past_median_time = self.past_median_time_manager.calc_past_median_time(&window)?;
         Ok((past_median_time, window))
     }
@@ -450,7 +453,7 @@ impl<T: GhostdagStoreReader, U: BlockWindowCacheReader, V: HeaderStoreReader, W:
                 // We pass a Lazy heap as an optimization to avoid cloning the selected parent heap in cases where the mergeset contains no samples
                 self.push_mergeset(&mut heap, sample_rate, ghostdag_data, selected_parent_blue_work, mergeset_non_daa_inserter);
                 if let Ok(heap) = Lazy::into_value(heap) {
-                    Arc::new(heap.binary_heap)
+                    Arc::new(heap.into_window(self.headers_store.deref()))
                 } else {
                     selected_parent_window.clone()
                 }
@@ -528,7 +531,8 @@ impl<T: GhostdagStoreReader, U: BlockWindowCacheReader, V: HeaderStoreReader, W:
     }
 
     fn calc_past_median_time(&self, ghostdag_data: &GhostdagData) -> Result<(u64, Arc<BlockWindowHeap>), RuleError> {
-        let window = self.block_window(ghostdag_data, WindowType::SampledMedianTimeWindow)?;
+        let mut window = self.block_window(ghostdag_data, WindowType::SampledMedianTimeWindow)?;
+        track_window_timestamps(self.headers_store.deref(), &mut window);
         let past_median_time = self.past_median_time_manager.calc_past_median_time(&window)?;
         Ok((past_median_time, window))
     }
@@ -687,6 +691,14 @@ impl<T: GhostdagStoreReader, U: BlockWindowCacheReader, V: HeaderStoreReader, W:
 struct BoundedSizeBlockHeap {
     binary_heap: BlockWindowHeap,
     size_bound: usize,
+    /// The blocks entering and leaving the heap, recorded if the heap tracks timestamps which must be updated accordingly
+    changes: Option<WindowChanges>,
+}
+
+#[derive(Default)]
+struct WindowChanges {
+    entered: Vec<Hash>,
+    left: Vec<Hash>,
 }
 
 impl BoundedSizeBlockHeap {
@@ -695,7 +707,8 @@ impl BoundedSizeBlockHeap {
     }
 
     fn from_binary_heap(size_bound: usize, binary_heap: BlockWindowHeap) -> Self {
-        Self { size_bound, binary_heap }
+        let changes = binary_heap.timestamps().map(|_| WindowChanges::default());
+        Self { size_bound, binary_heap, changes }
     }
 
     fn reached_size_bound(&self) -> bool {
@@ -721,14 +734,27 @@ impl BoundedSizeBlockHeap {
                     return false; // Heap is full and the suggested block is greater than the max
                 }
             }
-            self.binary_heap.pop(); // Remove the max block (because it's reverse, it'll be the block with the least blue work)
+            // Remove the max block (because it's reverse, it'll be the block with the least blue work)
+            let removed = self.binary_heap.pop().unwrap();
+            if let Some(changes) = self.changes.as_mut() {
+                match changes.entered.iter().position(|&entered| entered == removed.0.hash) {
+                    Some(index) => {
+                        changes.entered.swap_remove(index);
+                    }
+                    None => changes.left.push(removed.0.hash),
+                }
+            }
         }
         self.binary_heap.push(r_sortable_block);
+        if let Some(changes) = self.changes.as_mut() {
+            changes.entered.push(hash);
+        }
         true
     }
 
     // This method is intended to be used to merge the ancestor heap with the current heap.
     fn merge_ancestor_heap(&mut self, ancestor_heap: &mut BlockWindowHeap) {
+        debug_assert!(self.changes.is_none(), "merging is only expected into heaps built from scratch");
         self.binary_heap.blocks.append(&mut ancestor_heap.blocks);
         // Below we saturate for cases where ancestor may be close to, the origin, or genesis.
         // Note: this is a no-op if overflow_amount is 0, i.e. because of the saturating sub, the sum of the two heaps is less or equal to the size bound.
@@ -736,4 +762,18 @@ impl BoundedSizeBlockHeap {
             self.binary_heap.blocks.pop();
         }
     }
+
+    /// Returns the window, updating the timestamps it inherited from the ancestor window (if tracked) with the timestamps
+    /// of the blocks which entered and left it
+    fn into_window(mut self, headers_store: &impl HeaderStoreReader) -> BlockWindowHeap {
+        if let (Some(changes), Some(timestamps)) = (self.changes, self.binary_heap.timestamps_mut()) {
+            for hash in changes.left {
+                timestamps.remove(headers_store.get_timestamp(hash).unwrap());
+            }
+            for hash in changes.entered {
+                timestamps.insert(headers_store.get_timestamp(hash).unwrap());
+            }
+        }
+        self.binary_heap
+    }
 }
@@ -1,25 +1,129 @@
 use kaspa_hashes::{Hash, HasherBase, MerkleBranchHash, ZERO_HASH};
 
 pub fn calc_merkle_root(hashes: impl ExactSizeIterator<Item = Hash>) -> Hash {
-    if hashes.len() == 0 {
-        return ZERO_HASH;
-    }
-    let next_pot = hashes.len().next_power_of_two();
-    let vec_len = 2 * next_pot - 1;
-    let mut merkles = vec![None; vec_len];
-    for (i, hash) in hashes.enumerate() {
-        merkles[i] = Some(hash);
-    }
-    let mut offset = next_pot;
-    for i in (0..vec_len - 1).step_by(2) {
-        if merkles[i].is_none() {
-            merkles[offset] = None;
+    hashes.collect::<MerkleRootBuilder>().root()
+}
+
+/// Incrementally computes a merkle root over appended leaf hashes, holding only a single pending
+/// subtree root per tree level.
+///
+/// The tree is padded to the next power of two: a node with a missing right child is hashed with
+/// [`ZERO_HASH`] as its right child, and a node without children is omitted. The root of an empty tree is
+/// [`ZERO_HASH`].
+#[derive(Clone, Debug, Default)]
+pub struct MerkleRootBuilder {
+    /// `levels[i]` holds the root of a complete subtree of `2^i` leaves still waiting for its right sibling
+    levels: Vec<Option<Hash>>,
+    len: usize,
+}
+
+impl MerkleRootBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a leaf hash to the tree
+    pub fn push(&mut self, hash: Hash) {
+        let mut carry = hash;
+        let mut level = 0;
+        while let Some(left) = self.levels.get_mut(level).and_then(Option::take) {
+            carry = merkle_hash(left, carry);
+            level += 1;
+        }
+        if level == self.levels.len() {
+            self.levels.push(Some(carry));
         } else {
-            merkles[offset] = Some(merkle_hash(merkles[i].unwrap(), merkles[i + 1].unwrap_or(ZERO_HASH)));
+            self.levels[level] = Some(carry);
         }
-        offset += 1
+        self.len += 1;
+    }
+
+    /// The number of leaves appended so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the merkle root of the leaves appended so far. More leaves may be appended afterwards
+    pub fn root(&self) -> Hash {
+        if self.len == 0 {
+            return ZERO_HASH;
+        }
+        let height = self.len.next_power_of_two().trailing_zeros() as usize;
+        // Fold the pending subtrees from the bottom up, `carry` being the rightmost partial node of the current level
+        let mut carry: Option<Hash> = None;
+        for level in 0..height {
+            carry = match (self.level(level), carry) {
+                (Some(left), right) => Some(merkle_hash(left, right.unwrap_or(ZERO_HASH))),
+                (None, Some(left)) => Some(merkle_hash(left, ZERO_HASH)),
+                (None, None) => None,
+            };
+        }
+        // The leaf count is a power of two iff the whole tree is a single complete subtree
+        carry.or(self.level(height)).unwrap()
+    }
+
+    fn level(&self, level: usize) -> Option<Hash> {
+        self.levels.get(level).copied().flatten()
     }
-    merkles.last().unwrap().unwrap()
+}
+
+impl Extend<Hash> for MerkleRootBuilder {
+    fn extend<T: IntoIterator<Item = Hash>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|hash| self.push(hash));
+    }
+}
+
+impl FromIterator<Hash> for MerkleRootBuilder {
+    fn from_iter<T: IntoIterator<Item = Hash>>(iter: T) -> Self {
+        let mut builder = Self::new();
+        builder.extend(iter);
+        builder
+    }
+}
+
+/// A proof of inclusion of a leaf in a merkle tree built by [`calc_merkle_root`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// The position of the leaf in the tree
+    pub index: usize,
+    /// The sibling of each node on the path from the leaf to the root, bottom up. A missing right sibling is [`ZERO_HASH`]
+    pub siblings: Vec<Hash>,
+}
+
+/// Builds a proof of inclusion of the `index`'th leaf, or returns `None` if there is no such leaf
+pub fn build_merkle_proof(hashes: impl ExactSizeIterator<Item = Hash>, index: usize) -> Option<MerkleProof> {
+    if index >= hashes.len() {
+        return None;
+    }
+    let mut level = hashes.collect::<Vec<_>>();
+    let mut position = index;
+    let mut siblings = Vec::new();
+    while level.len() > 1 {
+        siblings.push(level.get(position ^ 1).copied().unwrap_or(ZERO_HASH));
+        level = level.chunks(2).map(|pair| merkle_hash(pair[0], pair.get(1).copied().unwrap_or(ZERO_HASH))).collect();
+        position >>= 1;
+    }
+    Some(MerkleProof { index, siblings })
+}
+
+/// Verifies that `proof` proves the inclusion of `leaf` in the merkle tree with root `root`
+pub fn verify_merkle_proof(root: Hash, leaf: Hash, proof: &MerkleProof) -> bool {
+    // The index must fit the height of the tree
+    if proof.siblings.len() < usize::BITS as usize && proof.index >> proof.siblings.len() != 0 {
+        return false;
+    }
+    let computed = proof.siblings.iter().enumerate().fold(leaf, |node, (level, &sibling)| {
+        if (proof.index >> level) & 1 == 0 {
+            merkle_hash(node, sibling)
+        } else {
+            merkle_hash(sibling, node)
+        }
+    });
+    computed == root
 }
 
 fn merkle_hash(left: Hash, right: Hash) -> Hash {
@@ -27,3 +131,87 @@ fn merkle_hash(left: Hash, right: Hash) -> Hash {
     hasher.update(left).update(right);
     hasher.finalize()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The former implementation, which materializes the whole padded tree
+    #[allow(clippy::explicit_counter_loop)]
+    fn calc_merkle_root_full_tree(hashes: impl ExactSizeIterator<Item = Hash>) -> Hash {
+        if hashes.len() == 0 {
+            return ZERO_HASH;
+        }
+        let next_pot = hashes.len().next_power_of_two();
+        let vec_len = 2 * next_pot - 1;
+        let mut merkles = vec![None; vec_len];
+        for (i, hash) in hashes.enumerate() {
+            merkles[i] = Some(hash);
+        }
+        let mut offset = next_pot;
+        for i in (0..vec_len - 1).step_by(2) {
+            if merkles[i].is_none() {
+                merkles[offset] = None;
+            } else {
+                merkles[offset] = Some(merkle_hash(merkles[i].unwrap(), merkles[i + 1].unwrap_or(ZERO_HASH)));
+            }
+            offset += 1
+        }
+        merkles.last().unwrap().unwrap()
+    }
+
+    fn leaves(count: usize) -> impl ExactSizeIterator<Item = Hash> {
+        (0..count as u64)
+            .map(|i| Hash::from_u64_word(i.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ 0xdead_beef))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_merkle_root_matches_full_tree() {
+        for count in (0..=260).chain([511, 512, 513, 1023, 1024, 1025, 4097]) {
+            assert_eq!(calc_merkle_root(leaves(count)), calc_merkle_root_full_tree(leaves(count)), "leaf count {count}");
+        }
+    }
+
+    #[test]
+    fn test_merkle_root_builder_incremental() {
+        let mut builder = MerkleRootBuilder::new();
+        assert!(builder.is_empty());
+        assert_eq!(builder.root(), ZERO_HASH);
+        for (i, hash) in leaves(130).enumerate() {
+            builder.push(hash);
+            assert_eq!(builder.len(), i + 1);
+            // Querying the root must not affect subsequent appends
+            assert_eq!(builder.root(), calc_merkle_root_full_tree(leaves(i + 1)), "leaf count {}", i + 1);
+        }
+        let mut extended = builder.clone();
+        extended.extend(leaves(140).skip(130));
+        assert_eq!(extended.root(), calc_merkle_root_full_tree(leaves(140)));
+        assert_eq!(builder.root(), calc_merkle_root_full_tree(leaves(130)));
+    }
+
+    #[test]
+    fn test_merkle_proof() {
+        assert_eq!(build_merkle_proof(leaves(0), 0), None);
+        for count in [1, 2, 3, 4, 5, 7, 8, 9, 33] {
+            let root = calc_merkle_root(leaves(count));
+            for (index, leaf) in leaves(count).enumerate() {
+                let proof = build_merkle_proof(leaves(count), index).unwrap();
+                assert_eq!(proof.siblings.len(), count.next_power_of_two().trailing_zeros() as usize);
+                assert!(verify_merkle_proof(root, leaf, &proof), "leaf {index} of {count}");
+
+                // Wrong leaf, wrong position or tampered siblings must fail
+                assert!(!verify_merkle_proof(root, ZERO_HASH, &proof));
+                if count > 1 {
+                    assert!(!verify_merkle_proof(root, leaf, &MerkleProof { index: index ^ 1, ..proof.clone() }));
+                    let mut tampered = proof.clone();
+                    tampered.siblings[0] = Hash::from_u64_word(u64::MAX);
+                    assert!(!verify_merkle_proof(root, leaf, &tampered));
+                }
+                assert!(!verify_merkle_proof(root, leaf, &MerkleProof { index: index + (1 << proof.siblings.len()), ..proof }));
+            }
+            assert_eq!(build_merkle_proof(leaves(count), count), None);
+        }
+    }
+}
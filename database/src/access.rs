@@ -1,4 +1,4 @@
-use crate::{cache::CachePolicy, db::DB, errors::StoreError};
+use crate::{cache::CachePolicy, db::DB, errors::StoreError, policy::retry_transient};
 
 use super::prelude::{Cache, DbKey, DbWriter};
 use kaspa_utils::mem_size::MemSizeEstimator;
@@ -43,7 +43,11 @@ where
     where
         TKey: Clone + AsRef<[u8]>,
     {
-        Ok(self.cache.contains_key(&key) || self.db.get_pinned(DbKey::new(&self.prefix, key))?.is_some())
+        if self.cache.contains_key(&key) {
+            return Ok(true);
+        }
+        let db_key = DbKey::new(&self.prefix, key);
+        Ok(retry_transient(|| self.db.get_pinned(&db_key)).map_err(|err| StoreError::DbKeyError(db_key.clone(), err))?.is_some())
     }
 
     pub fn read(&self, key: TKey) -> Result<TData, StoreError>
@@ -55,7 +59,9 @@ where
             Ok(data)
         } else {
             let db_key = DbKey::new(&self.prefix, key.clone());
-            if let Some(slice) = self.db.get_pinned(&db_key)? {
+            if let Some(slice) =
+                retry_transient(|| self.db.get_pinned(&db_key)).map_err(|err| StoreError::DbKeyError(db_key.clone(), err))?
+            {
                 let data: TData = bincode::deserialize(&slice)?;
                 self.cache.insert(key, data.clone());
                 Ok(data)
@@ -91,7 +97,8 @@ where
     {
         let bin_data = bincode::serialize(&data)?;
         self.cache.insert(key.clone(), data);
-        writer.put(DbKey::new(&self.prefix, key), bin_data)?;
+        let db_key = DbKey::new(&self.prefix, key);
+        writer.put(&db_key, bin_data).map_err(|err| StoreError::DbKeyError(db_key, err))?;
         Ok(())
     }
 
@@ -137,7 +144,8 @@ where
         TKey: Clone + AsRef<[u8]>,
     {
         self.cache.remove(&key);
-        writer.delete(DbKey::new(&self.prefix, key))?;
+        let db_key = DbKey::new(&self.prefix, key);
+        writer.delete(&db_key).map_err(|err| StoreError::DbKeyError(db_key, err))?;
         Ok(())
     }
 
@@ -213,6 +221,11 @@ where
     pub fn prefix(&self) -> &[u8] {
         &self.prefix
     }
+
+    /// Evicts about `fraction` of the cached items (see [`Cache::shrink`])
+    pub fn shrink_cache(&self, fraction: f64) -> usize {
+        self.cache.shrink(fraction)
+    }
 }
 
 #[cfg(test)]
@@ -240,7 +253,7 @@ mod tests {
         let mut batch = WriteBatch::default();
         access.delete_all(BatchDbWriter::new(&mut batch)).unwrap();
         assert_eq!(16, access.iterator().count());
-        db.write(batch).unwrap();
+        db.write_batch(batch).unwrap();
         assert_eq!(0, access.iterator().count());
     }
 }
@@ -1,5 +1,8 @@
 use indexmap::IndexMap;
-use kaspa_utils::mem_size::{MemMode, MemSizeEstimator};
+use kaspa_utils::{
+    mem_pressure::Shrinkable,
+    mem_size::{MemMode, MemSizeEstimator},
+};
 use parking_lot::RwLock;
 use rand::Rng;
 use std::{collections::hash_map::RandomState, hash::BuildHasher, sync::Arc};
@@ -99,6 +102,20 @@ where
         }
     }
 
+    /// Evicts `fraction` of the items at random (rounded up), keeping at least `min_items` items in tracked mode
+    fn shrink(&mut self, policy: &CachePolicyInner, fraction: f64) -> usize {
+        let len = self.map.len();
+        let evicted = ((len as f64 * fraction).ceil() as usize).min(len.saturating_sub(policy.min_items));
+        for _ in 0..evicted {
+            if let Some((_, v)) = self.map.swap_remove_index(rand::thread_rng().gen_range(0..self.map.len())) {
+                if policy.tracked {
+                    self.tracked_size -= v.estimate_size(policy.mem_mode);
+                }
+            }
+        }
+        evicted
+    }
+
     fn remove(&mut self, policy: &CachePolicyInner, key: &TKey) -> Option<TData> {
         match self.map.swap_remove(key) {
             Some(data) => {
@@ -161,6 +178,23 @@ where
         self.inner.write().insert(&self.policy, key, data);
     }
 
+    /// Returns the cached data of `key` if it exists, otherwise inserts `data` if `should_insert` (which is evaluated
+    /// under the cache lock) holds, in which case `data` is returned
+    pub fn get_or_insert_if(&self, key: TKey, data: TData, should_insert: impl FnOnce() -> bool) -> Option<TData> {
+        if self.policy.max_size == 0 {
+            return None;
+        }
+        let mut inner = self.inner.write();
+        if let Some(existing) = inner.map.get(&key) {
+            return Some(existing.clone());
+        }
+        if !should_insert() {
+            return None;
+        }
+        inner.insert(&self.policy, key, data.clone());
+        Some(data)
+    }
+
     pub fn insert_many(&self, iter: &mut impl Iterator<Item = (TKey, TData)>) {
         if self.policy.max_size == 0 {
             return;
@@ -198,6 +232,14 @@ where
         }
     }
 
+    /// Evicts about `fraction` of the cached items at random and returns the number of evicted items
+    pub fn shrink(&self, fraction: f64) -> usize {
+        if self.policy.max_size == 0 {
+            return 0;
+        }
+        self.inner.write().shrink(&self.policy, fraction.clamp(0.0, 1.0))
+    }
+
     pub fn remove_all(&self) {
         if self.policy.max_size == 0 {
             return;
@@ -209,3 +251,14 @@ where
         }
     }
 }
+
+impl<TKey, TData, S> Shrinkable for Cache<TKey, TData, S>
+where
+    TKey: Clone + std::hash::Hash + Eq + Send + Sync,
+    TData: Clone + Send + Sync + MemSizeEstimator,
+    S: BuildHasher + Default + Send + Sync,
+{
+    fn shrink(&self, fraction: f64) -> usize {
+        Cache::shrink(self, fraction)
+    }
+}
@@ -1,8 +1,9 @@
-use rocksdb::{DBWithThreadMode, MultiThreaded};
+use crate::{errors::StoreResult, policy::retry_transient};
+use rocksdb::{DBWithThreadMode, MultiThreaded, WriteBatch};
 use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
 
-pub use conn_builder::ConnBuilder;
+pub use conn_builder::{ConnBuilder, DbAccessMode};
 use kaspa_utils::fd_budget::FDGuard;
 
 mod conn_builder;
@@ -17,6 +18,18 @@ impl DB {
     pub fn new(inner: DBWithThreadMode<MultiThreaded>, fd_guard: FDGuard) -> Self {
         Self { inner, _fd_guard: fd_guard }
     }
+
+    /// Commits `batch` atomically, retrying transient failures according to the database failure policy.
+    /// Callers are expected to halt on the returned error via [`crate::prelude::StoreResultExtensions::unwrap_or_halt`]
+    pub fn write_batch(&self, batch: WriteBatch) -> StoreResult<()> {
+        #[cfg(feature = "fault-injection")]
+        {
+            crate::fault_injection::inject_latency();
+            crate::fault_injection::inject_failure(&self.inner)?;
+        }
+        // Each attempt consumes the batch it writes, hence the batch is copied per attempt
+        Ok(retry_transient(|| self.inner.write(WriteBatch::from_data(batch.data())))?)
+    }
 }
 
 impl DerefMut for DB {
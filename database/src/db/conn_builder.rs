@@ -1,10 +1,32 @@
-use crate::db::DB;
+use crate::{db::DB, errors::StoreError, policy::halt_on_store_error};
 use rocksdb::{DBWithThreadMode, MultiThreaded};
 use std::{path::PathBuf, sync::Arc};
 
 #[derive(Debug)]
 pub struct Unspecified;
 
+/// The way a connection accesses the underlying RocksDB instance
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DbAccessMode {
+    /// Full read-write access. Only a single process can hold such a connection at a time
+    #[default]
+    ReadWrite,
+    /// Read-only access to a view of the DB as of the time it was opened. Does not take the DB lock,
+    /// hence can be used while another process holds a read-write connection
+    ReadOnly,
+    /// Read-only access as a RocksDB secondary instance, which can follow the writes of the primary
+    /// instance by calling `try_catch_up_with_primary`. The path is used for the instance's own info logs.
+    /// Note that RocksDB requires secondary instances to keep all files open, so the files limit only
+    /// affects the FD budget accounting
+    Secondary(PathBuf),
+}
+
+impl DbAccessMode {
+    pub fn is_read_only(&self) -> bool {
+        !matches!(self, DbAccessMode::ReadWrite)
+    }
+}
+
 #[derive(Debug)]
 pub struct ConnBuilder<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> {
     db_path: Path,
@@ -13,6 +35,8 @@ pub struct ConnBuilder<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> {
     files_limit: FDLimit,
     mem_budget: usize,
     stats_period: StatsPeriod,
+    access_mode: DbAccessMode,
+    in_memory: bool,
 }
 
 impl Default for ConnBuilder<Unspecified, false, Unspecified, Unspecified> {
@@ -24,6 +48,8 @@ impl Default for ConnBuilder<Unspecified, false, Unspecified, Unspecified> {
             mem_budget: 64 * 1024 * 1024,
             stats_period: Unspecified,
             files_limit: Unspecified,
+            access_mode: DbAccessMode::ReadWrite,
+            in_memory: false,
         }
     }
 }
@@ -37,6 +63,8 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
             parallelism: self.parallelism,
             mem_budget: self.mem_budget,
             stats_period: self.stats_period,
+            access_mode: self.access_mode,
+            in_memory: self.in_memory,
         }
     }
     pub fn with_create_if_missing(self, create_if_missing: bool) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
@@ -48,6 +76,16 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
     pub fn with_mem_budget(self, mem_budget: impl Into<usize>) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
         ConnBuilder { mem_budget: mem_budget.into(), ..self }
     }
+    /// Sets the access mode of the connection. Read-only modes never create a missing DB
+    pub fn with_access_mode(self, access_mode: DbAccessMode) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
+        ConnBuilder { access_mode, ..self }
+    }
+    /// Backs the DB by RocksDB's in-memory environment instead of the file system. The DB path then only serves
+    /// as a name (the empty directory RocksDB creates for it is removed once opened), and all data is lost once
+    /// the DB is dropped
+    pub fn with_in_memory(self, in_memory: bool) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
+        ConnBuilder { in_memory, ..self }
+    }
     pub fn with_files_limit(self, files_limit: impl Into<i32>) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, i32> {
         ConnBuilder {
             db_path: self.db_path,
@@ -56,6 +94,8 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
             parallelism: self.parallelism,
             mem_budget: self.mem_budget,
             stats_period: self.stats_period,
+            access_mode: self.access_mode,
+            in_memory: self.in_memory,
         }
     }
 }
@@ -69,6 +109,8 @@ impl<Path, FDLimit> ConnBuilder<Path, false, Unspecified, FDLimit> {
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
             stats_period: self.stats_period,
+            access_mode: self.access_mode,
+            in_memory: self.in_memory,
         }
     }
 }
@@ -82,6 +124,8 @@ impl<Path, StatsPeriod, FDLimit> ConnBuilder<Path, true, StatsPeriod, FDLimit> {
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
             stats_period: Unspecified,
+            access_mode: self.access_mode,
+            in_memory: self.in_memory,
         }
     }
     pub fn with_stats_period(self, stats_period: impl Into<u32>) -> ConnBuilder<Path, true, u32, FDLimit> {
@@ -92,6 +136,8 @@ impl<Path, StatsPeriod, FDLimit> ConnBuilder<Path, true, StatsPeriod, FDLimit> {
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
             stats_period: stats_period.into(),
+            access_mode: self.access_mode,
+            in_memory: self.in_memory,
         }
     }
 }
@@ -104,17 +150,53 @@ macro_rules! default_opts {
         }
 
         opts.optimize_level_style_compaction($self.mem_budget);
+        if $self.in_memory {
+            opts.set_env(&rocksdb::Env::mem_env().unwrap());
+        }
         let guard = kaspa_utils::fd_budget::acquire_guard($self.files_limit)?;
-        opts.set_max_open_files($self.files_limit);
-        opts.create_if_missing($self.create_if_missing);
+        match $self.access_mode {
+            DbAccessMode::ReadWrite => {
+                opts.set_max_open_files($self.files_limit);
+                opts.create_if_missing($self.create_if_missing);
+            }
+            DbAccessMode::ReadOnly => {
+                opts.set_max_open_files($self.files_limit);
+            }
+            DbAccessMode::Secondary(_) => {
+                opts.set_max_open_files(-1);
+            }
+        }
         Ok((opts, guard))
     }};
 }
 
+fn open_db(
+    opts: &rocksdb::Options,
+    db_path: &std::path::Path,
+    access_mode: &DbAccessMode,
+    in_memory: bool,
+) -> DBWithThreadMode<MultiThreaded> {
+    let path = db_path.to_str().unwrap();
+    let db = match access_mode {
+        DbAccessMode::ReadWrite => <DBWithThreadMode<MultiThreaded>>::open(opts, path),
+        DbAccessMode::ReadOnly => <DBWithThreadMode<MultiThreaded>>::open_for_read_only(opts, path, false),
+        DbAccessMode::Secondary(secondary_path) => {
+            <DBWithThreadMode<MultiThreaded>>::open_as_secondary(opts, db_path, secondary_path.as_path())
+        }
+    }
+    // A corrupted or full database is reported along with the action required from the operator
+    .unwrap_or_else(|err| halt_on_store_error(StoreError::DbError(err)));
+    if in_memory {
+        // The in-memory environment never writes to the DB directory, so it is empty and can be removed right away
+        let _ = std::fs::remove_dir(db_path);
+    }
+    db
+}
+
 impl ConnBuilder<PathBuf, false, Unspecified, i32> {
     pub fn build(self) -> Result<Arc<DB>, kaspa_utils::fd_budget::Error> {
         let (opts, guard) = default_opts!(self)?;
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
+        let db = Arc::new(DB::new(open_db(&opts, &self.db_path, &self.access_mode, self.in_memory), guard));
         Ok(db)
     }
 }
@@ -123,7 +205,7 @@ impl ConnBuilder<PathBuf, true, Unspecified, i32> {
     pub fn build(self) -> Result<Arc<DB>, kaspa_utils::fd_budget::Error> {
         let (mut opts, guard) = default_opts!(self)?;
         opts.enable_statistics();
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
+        let db = Arc::new(DB::new(open_db(&opts, &self.db_path, &self.access_mode, self.in_memory), guard));
         Ok(db)
     }
 }
@@ -134,7 +216,51 @@ impl ConnBuilder<PathBuf, true, u32, i32> {
         opts.enable_statistics();
         opts.set_report_bg_io_stats(true);
         opts.set_stats_dump_period_sec(self.stats_period);
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
+        let db = Arc::new(DB::new(open_db(&opts, &self.db_path, &self.access_mode, self.in_memory), guard));
         Ok(db)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_memory_db, create_temp_db, utils::get_kaspa_tempdir};
+
+    #[test]
+    fn test_in_memory() {
+        let (_lifetime, db) = create_memory_db!(ConnBuilder::default().with_files_limit(10));
+        db.put(b"key", b"value").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"key").unwrap().unwrap(), b"value");
+        assert!(!db.path().exists());
+    }
+
+    #[test]
+    fn test_read_only_access_modes() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let path = db.path().to_owned();
+        db.put(b"key", b"value").unwrap();
+        db.flush().unwrap();
+
+        let read_only = ConnBuilder::default()
+            .with_db_path(path.clone())
+            .with_files_limit(10)
+            .with_access_mode(DbAccessMode::ReadOnly)
+            .build()
+            .unwrap();
+        assert_eq!(read_only.get(b"key").unwrap().unwrap(), b"value");
+        assert!(read_only.put(b"key", b"other").is_err());
+
+        let secondary_dir = get_kaspa_tempdir();
+        let secondary = ConnBuilder::default()
+            .with_db_path(path)
+            .with_files_limit(10)
+            .with_access_mode(DbAccessMode::Secondary(secondary_dir.path().to_owned()))
+            .build()
+            .unwrap();
+        db.put(b"key2", b"value2").unwrap();
+        secondary.try_catch_up_with_primary().unwrap();
+        assert_eq!(secondary.get(b"key2").unwrap().unwrap(), b"value2");
+        assert!(secondary.put(b"key", b"other").is_err());
+    }
+}
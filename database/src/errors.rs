@@ -1,4 +1,4 @@
-use crate::prelude::DbKey;
+use crate::{policy::halt_on_store_error, policy::DbFailureKind, prelude::DbKey};
 use kaspa_hashes::Hash;
 use thiserror::Error;
 
@@ -21,15 +21,42 @@ pub enum StoreError {
     #[error("rocksdb error {0}")]
     DbError(#[from] rocksdb::Error),
 
+    /// A RocksDB error along with the key of the failed operation
+    #[error("rocksdb error {1}")]
+    DbKeyError(DbKey, rocksdb::Error),
+
     #[error("bincode error {0}")]
     DeserializationError(#[from] Box<bincode::ErrorKind>),
 }
 
+impl StoreError {
+    /// Classifies the underlying database failure, if any
+    pub fn failure_kind(&self) -> Option<DbFailureKind> {
+        match self {
+            Self::DbError(err) | Self::DbKeyError(_, err) => Some(DbFailureKind::of(err)),
+            // Persisted data which cannot be decoded is damaged
+            Self::DeserializationError(_) => Some(DbFailureKind::Corruption),
+            _ => None,
+        }
+    }
+
+    /// The key involved in the failed operation, if known
+    pub fn db_key(&self) -> Option<&DbKey> {
+        match self {
+            Self::KeyNotFound(key) | Self::DbKeyError(key, _) => Some(key),
+            _ => None,
+        }
+    }
+}
+
 pub type StoreResult<T> = std::result::Result<T, StoreError>;
 
 pub trait StoreResultExtensions<T> {
     /// Unwrap or assert that the error is key not fund in which case `None` is returned
     fn unwrap_option(self) -> Option<T>;
+
+    /// Unwrap or halt the node according to the database failure policy, see [`halt_on_store_error`]
+    fn unwrap_or_halt(self) -> T;
 }
 
 impl<T> StoreResultExtensions<T> for StoreResult<T> {
@@ -37,9 +64,13 @@ impl<T> StoreResultExtensions<T> for StoreResult<T> {
         match self {
             Ok(value) => Some(value),
             Err(StoreError::KeyNotFound(_)) => None,
-            Err(err) => panic!("Unexpected store error: {err:?}"),
+            Err(err) => halt_on_store_error(err),
         }
     }
+
+    fn unwrap_or_halt(self) -> T {
+        self.unwrap_or_else(|err| halt_on_store_error(err))
+    }
 }
 
 pub trait StoreResultEmptyTuple {
@@ -52,7 +83,7 @@ impl StoreResultEmptyTuple for StoreResult<()> {
         match self {
             Ok(_) => (),
             Err(StoreError::KeyAlreadyExists(_)) | Err(StoreError::HashAlreadyExists(_)) => (),
-            Err(err) => panic!("Unexpected store error: {err:?}"),
+            Err(err) => halt_on_store_error(err),
         }
     }
 }
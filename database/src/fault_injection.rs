@@ -0,0 +1,68 @@
+//!
+//! Fault injection for testing the node under a misbehaving database (enabled by the `fault-injection` feature).
+//!
+//! Once enabled, DB write operations (direct writes as well as batch commits) randomly stall and fail with the
+//! configured probabilities. Two kinds of failures are injected:
+//! - transient failures, which are absorbed by the retry policy of [`crate::policy`] and thus exercise the retry
+//!   paths and the resulting stalls
+//! - non-transient failures, which reach the caller and thus exercise the fatal failure handling. Since RocksDB
+//!   errors cannot be constructed directly, these are genuine RocksDB errors obtained by submitting an invalid write
+//!
+//! Note that the configuration is process-wide, i.e., it applies to all databases opened by the process.
+//!
+
+use parking_lot::RwLock;
+use rand::Rng;
+use rocksdb::{DBWithThreadMode, MultiThreaded, WriteBatch, WriteOptions};
+use std::{thread, time::Duration};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DbFaultConfig {
+    /// The probability of an operation attempt to fail with a transient error
+    pub error_probability: f64,
+    /// The probability of an operation to fail with a non-transient error which is returned to the caller
+    pub failure_probability: f64,
+    /// The probability of an operation to be delayed by up to `max_latency`
+    pub latency_probability: f64,
+    pub max_latency: Duration,
+}
+
+static CONFIG: RwLock<Option<DbFaultConfig>> = parking_lot::const_rwlock(None);
+
+/// Starts injecting faults into DB operations according to `config`
+pub fn enable(config: DbFaultConfig) {
+    *CONFIG.write() = Some(config);
+}
+
+/// Stops injecting faults into DB operations
+pub fn disable() {
+    *CONFIG.write() = None;
+}
+
+/// Blocks the current thread for a random duration with the configured probability
+pub(crate) fn inject_latency() {
+    let Some(config) = *CONFIG.read() else {
+        return;
+    };
+    let mut rng = rand::thread_rng();
+    if !config.max_latency.is_zero() && rng.gen_bool(config.latency_probability.clamp(0.0, 1.0)) {
+        thread::sleep(rng.gen_range(Duration::ZERO..=config.max_latency));
+    }
+}
+
+/// Returns whether the current operation attempt should fail with a transient error
+pub(crate) fn inject_transient_failure() -> bool {
+    CONFIG.read().is_some_and(|config| rand::thread_rng().gen_bool(config.error_probability.clamp(0.0, 1.0)))
+}
+
+/// Fails the current operation with a non-transient error with the configured probability
+pub(crate) fn inject_failure(db: &DBWithThreadMode<MultiThreaded>) -> Result<(), rocksdb::Error> {
+    if !CONFIG.read().is_some_and(|config| rand::thread_rng().gen_bool(config.failure_probability.clamp(0.0, 1.0))) {
+        return Ok(());
+    }
+    // RocksDB rejects synced writes which skip the WAL before applying anything, so this fails without side effects
+    let mut opts = WriteOptions::default();
+    opts.set_sync(true);
+    opts.disable_wal(true);
+    db.write_opt(WriteBatch::default(), &opts)
+}
@@ -1,6 +1,7 @@
 use crate::{
     db::DB,
     errors::StoreError,
+    policy::retry_transient,
     prelude::{DbSetAccess, ReadLock},
 };
 
@@ -26,6 +27,10 @@ impl<T> CachedDbItem<T> {
         Self { db, key, cached_item: Arc::new(RwLock::new(None)) }
     }
 
+    fn db_error(&self, err: rocksdb::Error) -> StoreError {
+        StoreError::DbKeyError(DbKey::prefix_only(&self.key), err)
+    }
+
     pub fn read(&self) -> Result<T, StoreError>
     where
         T: Clone + DeserializeOwned,
@@ -33,7 +38,7 @@ impl<T> CachedDbItem<T> {
         if let Some(item) = self.cached_item.read().clone() {
             return Ok(item);
         }
-        if let Some(slice) = self.db.get_pinned(&self.key)? {
+        if let Some(slice) = retry_transient(|| self.db.get_pinned(&self.key)).map_err(|err| self.db_error(err))? {
             let item: T = bincode::deserialize(&slice)?;
             *self.cached_item.write() = Some(item.clone());
             Ok(item)
@@ -67,7 +72,7 @@ where {
         let mut guard = self.cached_item.write();
         let mut item = if let Some(item) = guard.take() {
             item
-        } else if let Some(slice) = self.db.get_pinned(&self.key)? {
+        } else if let Some(slice) = retry_transient(|| self.db.get_pinned(&self.key)).map_err(|err| self.db_error(err))? {
             let item: T = bincode::deserialize(&slice)?;
             item
         } else {
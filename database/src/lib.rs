@@ -6,6 +6,11 @@ mod item;
 mod key;
 mod writer;
 
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
+pub mod migration;
+pub mod policy;
+
 pub mod registry;
 mod set_access;
 pub mod utils;
@@ -17,8 +22,9 @@ pub mod prelude {
     pub use super::cache::{Cache, CachePolicy};
     pub use super::item::{CachedDbItem, CachedDbSetItem};
     pub use super::key::DbKey;
+    pub use super::policy::{halt_on_store_error, retry_transient, DbFailureKind};
     pub use super::set_access::{CachedDbSetAccess, DbSetAccess, ReadLock};
     pub use super::writer::{BatchDbWriter, DbWriter, DirectDbWriter, DirectWriter, MemoryWriter};
-    pub use db::{delete_db, ConnBuilder, DB};
+    pub use db::{delete_db, ConnBuilder, DbAccessMode, DB};
     pub use errors::{StoreError, StoreResult, StoreResultEmptyTuple, StoreResultExtensions};
 }
@@ -0,0 +1,364 @@
+//! Store schema versioning and in-place migrations.
+//!
+//! Every store which changed its persisted layout records a schema version under [`DatabaseStorePrefixes::SchemaVersions`].
+//! Stores without such a record are considered to be at version 0. On startup, a [`MigrationRunner`] is fed with all
+//! known [`Migration`]s of a DB and brings each store up to its latest version, either by transforming its entries in
+//! place or by clearing the store so that its owner rebuilds it from scratch.
+
+use crate::prelude::{BatchDbWriter, CachedDbItem, DbWriter, StoreError, StoreResult, StoreResultExtensions, DB};
+use crate::registry::DatabaseStorePrefixes;
+use itertools::Itertools;
+use rocksdb::{checkpoint::Checkpoint, WriteBatch};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// The persisted schema version of a single store
+#[derive(Clone)]
+pub struct StoreSchemaVersion {
+    access: CachedDbItem<u32>,
+}
+
+impl StoreSchemaVersion {
+    pub fn new(db: Arc<DB>, store: DatabaseStorePrefixes) -> Self {
+        Self { access: CachedDbItem::new(db, DatabaseStorePrefixes::SchemaVersions.into_iter().chain(store).collect()) }
+    }
+
+    /// Returns the recorded version, or 0 if the store has no version record
+    pub fn get(&self) -> StoreResult<u32> {
+        Ok(self.access.read().unwrap_option().unwrap_or_default())
+    }
+
+    pub fn set(&mut self, writer: impl DbWriter, version: u32) -> StoreResult<()> {
+        self.access.write(writer, &version)
+    }
+}
+
+/// How a migration brings a store to its new version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationAction {
+    /// Entries are rewritten in place by [`Migration::transform`]
+    Transform,
+    /// All store entries are deleted and the store owner is expected to rebuild it (e.g., an index resyncing from consensus)
+    Rebuild,
+}
+
+impl Display for MigrationAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationAction::Transform => write!(f, "transform"),
+            MigrationAction::Rebuild => write!(f, "rebuild"),
+        }
+    }
+}
+
+/// A single schema upgrade of a store, from `version() - 1` to `version()`
+pub trait Migration: Send + Sync {
+    fn store(&self) -> DatabaseStorePrefixes;
+
+    /// The schema version of the store once this migration is applied
+    fn version(&self) -> u32;
+
+    fn description(&self) -> &'static str;
+
+    fn action(&self) -> MigrationAction {
+        MigrationAction::Transform
+    }
+
+    /// Writes the transformed entries of the store to `batch` and returns their number. The batch is committed
+    /// atomically along with the new version record. Only called for [`MigrationAction::Transform`] migrations.
+    fn transform(&self, _db: &Arc<DB>, _batch: &mut WriteBatch) -> StoreResult<usize> {
+        Ok(0)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    #[error(transparent)]
+    StoreError(#[from] StoreError),
+
+    #[error("store {0:?} is at schema version {1} which is newer than the latest supported version {2}")]
+    UnsupportedVersion(DatabaseStorePrefixes, u32, u32),
+
+    #[error("no migration of store {0:?} to schema version {1} is known")]
+    MissingMigration(DatabaseStorePrefixes, u32),
+
+    #[error("failed creating a DB backup at {0}: {1}")]
+    BackupError(PathBuf, rocksdb::Error),
+}
+
+pub type MigrationResult<T> = std::result::Result<T, MigrationError>;
+
+/// A migration which was (or in dry-run mode, would be) applied
+#[derive(Debug, Clone)]
+pub struct MigrationRecord {
+    pub store: DatabaseStorePrefixes,
+    pub version: u32,
+    pub description: &'static str,
+    pub action: MigrationAction,
+    /// Number of transformed entries, always 0 for rebuilds and in dry-run mode
+    pub entries: usize,
+}
+
+impl Display for MigrationRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} v{} ({}): {}", self.store, self.version, self.action, self.description)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+    pub records: Vec<MigrationRecord>,
+    pub dry_run: bool,
+    /// The checkpoint created before applying the migrations, if any
+    pub backup_path: Option<PathBuf>,
+}
+
+impl MigrationReport {
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the stores which were cleared and must be rebuilt by their owners
+    pub fn rebuilt_stores(&self) -> impl Iterator<Item = DatabaseStorePrefixes> + '_ {
+        self.records.iter().filter(|record| record.action == MigrationAction::Rebuild).map(|record| record.store)
+    }
+}
+
+/// Applies all pending [`Migration`]s of a DB. Migrations of each store are applied in version order, each along
+/// with its version record in a single atomic write, so an interrupted run resumes from the last applied migration.
+pub struct MigrationRunner {
+    db: Arc<DB>,
+    migrations: Vec<Box<dyn Migration>>,
+    dry_run: bool,
+    backup_path: Option<PathBuf>,
+}
+
+impl MigrationRunner {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db, migrations: Vec::new(), dry_run: false, backup_path: None }
+    }
+
+    pub fn with_migration(mut self, migration: Box<dyn Migration>) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    pub fn with_migrations(mut self, migrations: impl IntoIterator<Item = Box<dyn Migration>>) -> Self {
+        self.migrations.extend(migrations);
+        self
+    }
+
+    /// Only reports the pending migrations without applying them
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Creates a checkpoint of the DB at `path` before applying any migration. The path must not exist.
+    pub fn with_backup_path(mut self, path: Option<PathBuf>) -> Self {
+        self.backup_path = path;
+        self
+    }
+
+    /// Returns the pending migrations in the order they will be applied
+    pub fn pending(&self) -> MigrationResult<Vec<&dyn Migration>> {
+        let by_store =
+            self.migrations.iter().map(|migration| migration.as_ref()).into_group_map_by(|migration| migration.store() as u8);
+        let mut pending = Vec::new();
+        for (_, mut migrations) in by_store.into_iter().sorted_by_key(|(store, _)| *store) {
+            migrations.sort_by_key(|migration| migration.version());
+            let store = migrations[0].store();
+            let latest = migrations.last().unwrap().version();
+            let current = StoreSchemaVersion::new(self.db.clone(), store).get()?;
+            if current > latest {
+                return Err(MigrationError::UnsupportedVersion(store, current, latest));
+            }
+            let by_version: HashMap<u32, &dyn Migration> =
+                migrations.into_iter().map(|migration| (migration.version(), migration)).collect();
+            for version in current + 1..=latest {
+                pending.push(*by_version.get(&version).ok_or(MigrationError::MissingMigration(store, version))?);
+            }
+        }
+        Ok(pending)
+    }
+
+    pub fn run(&self) -> MigrationResult<MigrationReport> {
+        let pending = self.pending()?;
+        let mut report = MigrationReport { dry_run: self.dry_run, ..Default::default() };
+        if pending.is_empty() {
+            return Ok(report);
+        }
+        if self.dry_run {
+            report.records = pending.into_iter().map(|migration| Self::record(migration, 0)).collect();
+            return Ok(report);
+        }
+        if let Some(path) = self.backup_path.as_ref() {
+            self.backup(path)?;
+            report.backup_path = Some(path.clone());
+        }
+        for migration in pending {
+            let entries = self.apply(migration)?;
+            report.records.push(Self::record(migration, entries));
+        }
+        Ok(report)
+    }
+
+    fn apply(&self, migration: &dyn Migration) -> MigrationResult<usize> {
+        let mut batch = WriteBatch::default();
+        let entries = match migration.action() {
+            MigrationAction::Transform => migration.transform(&self.db, &mut batch)?,
+            MigrationAction::Rebuild => {
+                let prefix = migration.store() as u8;
+                batch.delete_range([prefix], [prefix + 1]);
+                0
+            }
+        };
+        StoreSchemaVersion::new(self.db.clone(), migration.store()).set(BatchDbWriter::new(&mut batch), migration.version())?;
+        self.db.write_batch(batch)?;
+        Ok(entries)
+    }
+
+    fn backup(&self, path: &Path) -> MigrationResult<()> {
+        Checkpoint::new(&**self.db)
+            .and_then(|checkpoint| checkpoint.create_checkpoint(path))
+            .map_err(|err| MigrationError::BackupError(path.to_path_buf(), err))
+    }
+
+    fn record(migration: &dyn Migration, entries: usize) -> MigrationRecord {
+        MigrationRecord {
+            store: migration.store(),
+            version: migration.version(),
+            description: migration.description(),
+            action: migration.action(),
+            entries,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_temp_db, prelude::ConnBuilder};
+
+    const STORE: DatabaseStorePrefixes = DatabaseStorePrefixes::UtxoIndex;
+
+    /// Doubles all values of the test store
+    struct DoubleValues;
+
+    impl Migration for DoubleValues {
+        fn store(&self) -> DatabaseStorePrefixes {
+            STORE
+        }
+
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn description(&self) -> &'static str {
+            "double all values"
+        }
+
+        fn transform(&self, db: &Arc<DB>, batch: &mut WriteBatch) -> StoreResult<usize> {
+            let mut count = 0;
+            for (key, value) in entries(db) {
+                batch.put(key, [value[0] * 2]);
+                count += 1;
+            }
+            Ok(count)
+        }
+    }
+
+    struct ClearStore;
+
+    impl Migration for ClearStore {
+        fn store(&self) -> DatabaseStorePrefixes {
+            STORE
+        }
+
+        fn version(&self) -> u32 {
+            2
+        }
+
+        fn description(&self) -> &'static str {
+            "rebuild the store"
+        }
+
+        fn action(&self) -> MigrationAction {
+            MigrationAction::Rebuild
+        }
+    }
+
+    /// A raw DB entry, i.e., a key and a value
+    type Entry = (Box<[u8]>, Box<[u8]>);
+
+    fn entries(db: &DB) -> Vec<Entry> {
+        db.prefix_iterator([STORE as u8]).map(|res| res.unwrap()).take_while(|(key, _)| key[0] == STORE as u8).collect()
+    }
+
+    fn values(db: &DB) -> Vec<u8> {
+        entries(db).into_iter().map(|(_, value)| value[0]).collect()
+    }
+
+    #[test]
+    fn test_migration_runner() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        db.put([STORE as u8, 1], [1]).unwrap();
+        db.put([STORE as u8, 2], [2]).unwrap();
+        // Read the version through a fresh item every time, since the item caches the value it read
+        let version = || StoreSchemaVersion::new(db.clone(), STORE).get().unwrap();
+
+        // Dry run leaves the store untouched
+        let report = MigrationRunner::new(db.clone()).with_migration(Box::new(DoubleValues)).with_dry_run(true).run().unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.records.len(), 1);
+        assert_eq!((values(&db), version()), (vec![1, 2], 0));
+
+        let report = MigrationRunner::new(db.clone()).with_migration(Box::new(DoubleValues)).run().unwrap();
+        assert_eq!(report.records.iter().map(|record| (record.version, record.entries)).collect_vec(), vec![(1, 2)]);
+        assert_eq!((values(&db), version()), (vec![2, 4], 1));
+
+        // Already applied migrations are skipped and a newer one rebuilds the store
+        let runner =
+            MigrationRunner::new(db.clone()).with_migrations([Box::new(ClearStore) as Box<dyn Migration>, Box::new(DoubleValues)]);
+        let report = runner.run().unwrap();
+        assert_eq!(report.rebuilt_stores().count(), 1);
+        assert_eq!((values(&db), version()), (vec![], 2));
+        assert!(runner.run().unwrap().is_empty());
+
+        // A store newer than the known migrations is rejected
+        assert!(matches!(
+            MigrationRunner::new(db.clone()).with_migration(Box::new(DoubleValues)).run(),
+            Err(MigrationError::UnsupportedVersion(_, 2, 1))
+        ));
+    }
+
+    #[test]
+    fn test_missing_migration() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        assert!(matches!(
+            MigrationRunner::new(db).with_migration(Box::new(ClearStore)).run(),
+            Err(MigrationError::MissingMigration(_, 1))
+        ));
+    }
+
+    #[test]
+    fn test_backup() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let backup_dir = tempfile::tempdir().unwrap();
+        let backup_path = backup_dir.path().join("backup");
+        db.put([STORE as u8, 1], [1]).unwrap();
+        let report = MigrationRunner::new(db.clone())
+            .with_migration(Box::new(DoubleValues))
+            .with_backup_path(Some(backup_path.clone()))
+            .run()
+            .unwrap();
+        assert_eq!(report.backup_path, Some(backup_path.clone()));
+        assert!(backup_path.join("CURRENT").exists());
+        assert_eq!(values(&db), vec![2]);
+    }
+}
@@ -0,0 +1,122 @@
+//!
+//! Policy for handling database failures.
+//!
+//! RocksDB errors are classified into failure kinds which trigger different behaviors:
+//! - transient failures (e.g., a busy resource or a timed out operation) are retried with an exponential backoff
+//! - corruption and out-of-space failures halt the node with an actionable message
+//! - any other unexpected failure halts the node with the store name and key involved
+//!
+
+use crate::errors::StoreError;
+use std::{fmt::Display, thread, time::Duration};
+
+/// The maximal number of retries of an operation failing with a transient error
+pub const MAX_TRANSIENT_RETRIES: u32 = 5;
+
+/// The delay before the first retry, doubled on each following retry
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbFailureKind {
+    /// Persisted data is damaged and cannot be trusted anymore
+    Corruption,
+    /// The disk holding the database is full
+    OutOfSpace,
+    /// The operation might succeed if retried
+    Transient,
+    Other,
+}
+
+impl DbFailureKind {
+    pub fn of(err: &rocksdb::Error) -> Self {
+        Self::from_message(err.as_ref())
+    }
+
+    /// Classifies a RocksDB status message. RocksDB reports all failure kinds as a message prefixed with the status
+    /// code, where out-of-space failures are I/O errors with a matching sub-code or OS error description
+    fn from_message(message: &str) -> Self {
+        match message.split(':').next().unwrap_or_default() {
+            "Corruption" => Self::Corruption,
+            "IO error" if message.contains("No space left on device") || message.contains("NoSpace") => Self::OutOfSpace,
+            "Resource busy" | "Operation timed out" | "Operation failed. Try again." | "Result incomplete" => Self::Transient,
+            _ => Self::Other,
+        }
+    }
+
+    /// Guidance printed to the operator when the node halts on a failure of this kind
+    fn advice(&self) -> Option<&'static str> {
+        match self {
+            Self::Corruption => {
+                Some("The database is corrupted. Restart the node with --reset-db in order to resync it from scratch.")
+            }
+            Self::OutOfSpace => Some("The disk holding the database is out of space. Free some disk space and restart the node."),
+            Self::Transient | Self::Other => None,
+        }
+    }
+}
+
+impl Display for DbFailureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Corruption => f.write_str("corruption"),
+            Self::OutOfSpace => f.write_str("out of space"),
+            Self::Transient => f.write_str("transient"),
+            Self::Other => f.write_str("other"),
+        }
+    }
+}
+
+/// Runs `op`, retrying it with an exponential backoff as long as it fails with a transient error, up to
+/// [`MAX_TRANSIENT_RETRIES`] times. Note that this blocks the current thread while waiting
+pub fn retry_transient<T>(mut op: impl FnMut() -> Result<T, rocksdb::Error>) -> Result<T, rocksdb::Error> {
+    let mut backoff = INITIAL_RETRY_BACKOFF;
+    let mut retries = 0;
+    loop {
+        #[cfg(feature = "fault-injection")]
+        if retries < MAX_TRANSIENT_RETRIES && crate::fault_injection::inject_transient_failure() {
+            thread::sleep(backoff);
+            backoff *= 2;
+            retries += 1;
+            continue;
+        }
+        match op() {
+            Err(err) if retries < MAX_TRANSIENT_RETRIES && DbFailureKind::of(&err) == DbFailureKind::Transient => {
+                thread::sleep(backoff);
+                backoff *= 2;
+                retries += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Halts the node on an unexpected store error. The panic message includes the failure kind, the store name and
+/// key involved (when known) and, for unrecoverable failures, the action expected from the operator
+pub fn halt_on_store_error(err: StoreError) -> ! {
+    // Note that the key display starts with the store name when the store prefix is known
+    let location = err.db_key().map(|key| format!(" at key {key}")).unwrap_or_default();
+    if let Some(kind) = err.failure_kind() {
+        if let Some(advice) = kind.advice() {
+            panic!("Fatal store error ({kind}){location}: {err}. {advice}");
+        }
+    }
+    panic!("Unexpected store error{location}: {err:?}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_classification() {
+        assert_eq!(DbFailureKind::from_message("Corruption: block checksum mismatch"), DbFailureKind::Corruption);
+        assert_eq!(
+            DbFailureKind::from_message("IO error: No space left on device: While appending to file: 000123.log"),
+            DbFailureKind::OutOfSpace
+        );
+        assert_eq!(DbFailureKind::from_message("IO error: While open a file for random read: 000123.sst"), DbFailureKind::Other);
+        assert_eq!(DbFailureKind::from_message("Resource busy: "), DbFailureKind::Transient);
+        assert_eq!(DbFailureKind::from_message("Operation failed. Try again.: "), DbFailureKind::Transient);
+        assert_eq!(DbFailureKind::from_message("Invalid argument: Column family not found"), DbFailureKind::Other);
+    }
+}
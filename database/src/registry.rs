@@ -41,6 +41,11 @@ pub enum DatabaseStorePrefixes {
     ReachabilityTreeChildren = 30,
     ReachabilityFutureCoveringSet = 31,
 
+    // ---- Sparse selected chain score indexes ----
+    ChainIndexByDaaScore = 32,
+    ChainIndexByBlueScore = 33,
+    ChainScoresBaseIndex = 34,
+
     // ---- Ghostdag Proof
     TempGhostdag = 40,
     TempGhostdagCompact = 41,
@@ -48,15 +53,21 @@ pub enum DatabaseStorePrefixes {
     // ---- Metadata ----
     MultiConsensusMetadata = 124,
     ConsensusEntries = 125,
+    SchemaVersions = 126,
 
     // ---- Components ----
     Addresses = 128,
     BannedAddresses = 129,
+    PeerStats = 130,
+    NodeIdentity = 131,
+    LocalTransactions = 132,
 
     // ---- Indexes ----
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    AcceptanceIndex = 195,
+    AcceptanceIndexSink = 196,
 
     // ---- Separator ----
     /// Reserved as a separator
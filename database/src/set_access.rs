@@ -9,7 +9,10 @@ use std::{
     fmt::Debug,
     hash::BuildHasher,
     marker::PhantomData,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
 /// A read-only lock. Essentially a wrapper to [`parking_lot::RwLock`] which allows only reading.
@@ -45,6 +48,9 @@ where
 
     // Cache
     cache: Cache<TKey, Arc<RwLock<HashSet<TData, W>>>, S>,
+
+    // Counts DB mutations once they are applied. Used for detecting mutations which occurred while a set entry was read from the DB
+    mutations: Arc<AtomicU64>,
 }
 
 impl<TKey, TData, S, W> CachedDbSetAccess<TKey, TData, S, W>
@@ -55,7 +61,7 @@ where
     W: BuildHasher + Default + Send + Sync,
 {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy, prefix: Vec<u8>) -> Self {
-        Self { inner: DbSetAccess::new(db, prefix), cache: Cache::new(cache_policy) }
+        Self { inner: DbSetAccess::new(db, prefix), cache: Cache::new(cache_policy), mutations: Default::default() }
     }
 
     pub fn read_from_cache(&self, key: TKey) -> Option<ReadLock<HashSet<TData, W>>> {
@@ -63,14 +69,19 @@ where
     }
 
     /// Returns the set entry wrapped with a read-write lock. If the entry is not cached then it is read from the DB and cached.
+    ///
+    /// Cached entries are mutated only while holding the cache lock and only if present (see [`Self::write`]). An entry read
+    /// from the DB is cached only if no mutation happened since the read began, since otherwise the read might have missed
+    /// a mutation whose cache update already took place
     fn read_locked_entry(&self, key: TKey) -> Result<Arc<RwLock<HashSet<TData, W>>>, StoreError> {
         if let Some(data) = self.cache.get(&key) {
             Ok(data)
         } else {
+            let mutations = self.mutations.load(Ordering::SeqCst);
             let data: HashSet<TData, _> = self.inner.bucket_iterator(key.clone()).collect::<Result<_, _>>()?;
             let data = Arc::new(RwLock::new(data));
-            self.cache.insert(key, data.clone());
-            Ok(data)
+            let should_insert = || self.mutations.load(Ordering::SeqCst) == mutations;
+            Ok(self.cache.get_or_insert_if(key, data.clone(), should_insert).unwrap_or(data))
         }
     }
 
@@ -78,31 +89,79 @@ where
         Ok(ReadLock::new(self.read_locked_entry(key)?))
     }
 
+    /// Writes the item and then adds it to the cached set entry if the entry exists in the cache.
+    ///
+    /// In the case of a batch writer the DB is mutated only once the batch is committed, which is unknown here, so the mutation
+    /// is not counted. Callers must hence prevent concurrent reads of the entry until the batch is committed
     pub fn write(&self, writer: impl DbWriter, key: TKey, data: TData) -> Result<(), StoreError> {
-        // We cache the new item only if the set entry already exists in the cache
-        self.cache.update_if_entry_exists(key.clone(), |locked_entry| {
+        let is_batch = writer.is_batch();
+        self.inner.write(writer, key.clone(), data.clone())?;
+        self.count_applied_mutation(is_batch);
+        self.cache.update_if_entry_exists(key, |locked_entry| {
             locked_entry.write().insert(data.clone());
         });
-        self.inner.write(writer, key, data)
+        Ok(())
     }
 
+    /// Deletes the set entry and removes it from the cache. See [`Self::write`] regarding batch writers
     pub fn delete_bucket(&self, writer: impl DbWriter, key: TKey) -> Result<(), StoreError> {
+        let is_batch = writer.is_batch();
+        self.inner.delete_bucket(writer, key.clone())?;
+        self.count_applied_mutation(is_batch);
         self.cache.remove(&key);
-        self.inner.delete_bucket(writer, key)
+        Ok(())
     }
 
+    /// Deletes the item and then removes it from the cached set entry if the entry exists in the cache. See [`Self::write`]
+    /// regarding batch writers
     pub fn delete(&self, writer: impl DbWriter, key: TKey, data: TData) -> Result<(), StoreError> {
-        // We remove the item from cache only if the full set entry already exists in the cache
-        self.cache.update_if_entry_exists(key.clone(), |locked_entry| {
+        let is_batch = writer.is_batch();
+        self.inner.delete(writer, key.clone(), data.clone())?;
+        self.count_applied_mutation(is_batch);
+        self.cache.update_if_entry_exists(key, |locked_entry| {
             locked_entry.write().remove(&data);
         });
-        self.inner.delete(writer, key, data)?;
         Ok(())
     }
 
+    fn count_applied_mutation(&self, is_batch: bool) {
+        if !is_batch {
+            self.mutations.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// See [`DbSetAccess::delete_bucket_files`]
+    pub fn delete_bucket_files(&self, key: TKey) -> Result<(), StoreError> {
+        self.inner.delete_bucket_files(key)
+    }
+
+    /// Returns the number of items in the set entry. Uses the cached entry if it exists, otherwise counts the DB keys
+    /// of the bucket without deserializing them nor populating the cache
+    pub fn bucket_len(&self, key: TKey) -> Result<usize, StoreError> {
+        match self.cache.get(&key) {
+            Some(locked_entry) => Ok(locked_entry.read().len()),
+            None => self.inner.bucket_len(key),
+        }
+    }
+
+    /// Returns whether all items of the set entry satisfy `predicate`. Uses the cached entry if it exists, otherwise
+    /// iterates the DB keys of the bucket (stopping at the first mismatch) without populating the cache
+    pub fn bucket_all(&self, key: TKey, predicate: impl FnMut(&TData) -> bool) -> Result<bool, StoreError> {
+        match self.cache.get(&key) {
+            Some(locked_entry) => Ok(locked_entry.read().iter().all(predicate)),
+            None => self.inner.bucket_all(key, predicate),
+        }
+    }
+
     pub fn prefix(&self) -> &[u8] {
         self.inner.prefix()
     }
+
+    /// Evicts about `fraction` of the cached set entries. Evicting is always safe since entries are cached only
+    /// while being consistent with the DB
+    pub fn shrink_cache(&self, fraction: f64) -> usize {
+        self.cache.shrink(fraction)
+    }
 }
 
 /// A concurrent DB store for typed **set** access *without* caching.
@@ -139,13 +198,54 @@ where
         Ok(DbKey::new_with_bucket(&self.prefix, key, bin_data))
     }
 
-    pub fn delete_bucket(&self, mut writer: impl DbWriter, key: TKey) -> Result<(), StoreError> {
-        let db_key = DbKey::new_with_bucket(&self.prefix, &key, []);
+    fn bucket_bounds(&self, key: &TKey) -> (Vec<u8>, Vec<u8>) {
+        let db_key = DbKey::new_with_bucket(&self.prefix, key, []);
         let (from, to) = rocksdb::PrefixRange(db_key.as_ref()).into_bounds();
-        writer.delete_range(from.unwrap(), to.unwrap())?;
+        (from.unwrap(), to.unwrap())
+    }
+
+    /// Deletes all items of the bucket using a single range tombstone
+    pub fn delete_bucket(&self, mut writer: impl DbWriter, key: TKey) -> Result<(), StoreError> {
+        let (from, to) = self.bucket_bounds(&key);
+        writer.delete_range(from, to)?;
+        Ok(())
+    }
+
+    /// Immediately drops SST files whose keys all belong to the bucket, reclaiming the space of large buckets without
+    /// waiting for compaction. This bypasses any writer, so it is not atomic with other writes and should only be
+    /// called for buckets whose deletion was already committed
+    pub fn delete_bucket_files(&self, key: TKey) -> Result<(), StoreError> {
+        let (from, to) = self.bucket_bounds(&key);
+        self.db.delete_file_in_range(from, to)?;
         Ok(())
     }
 
+    /// Counts the items of the bucket by iterating its DB keys
+    pub fn bucket_len(&self, key: TKey) -> Result<usize, StoreError> {
+        let (from, to) = self.bucket_bounds(&key);
+        let mut read_opts = ReadOptions::default();
+        read_opts.set_iterate_range(from..to);
+        let mut db_iterator = self.db.raw_iterator_opt(read_opts);
+        db_iterator.seek_to_first();
+        let mut count = 0;
+        while db_iterator.valid() {
+            count += 1;
+            db_iterator.next();
+        }
+        db_iterator.status()?;
+        Ok(count)
+    }
+
+    /// Returns whether all items of the bucket satisfy `predicate`, stopping at the first item which does not
+    pub fn bucket_all(&self, key: TKey, mut predicate: impl FnMut(&TData) -> bool) -> Result<bool, StoreError> {
+        for data in self.bucket_iterator(key) {
+            if !predicate(&data?) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     pub fn delete(&self, mut writer: impl DbWriter, key: TKey, data: TData) -> Result<(), StoreError> {
         writer.delete(self.get_db_key(&key, &data)?)?;
         Ok(())
@@ -221,7 +321,80 @@ mod tests {
 
         let mut batch = WriteBatch::default();
         access.delete_bucket(BatchDbWriter::new(&mut batch), 6.into()).unwrap();
-        db.write(batch).unwrap();
+        db.write_batch(batch).unwrap();
         assert_eq!(0, access.bucket_iterator(6.into()).count());
+
+        assert_eq!(2, access.bucket_len(7.into()).unwrap());
+        access.delete_bucket(DirectDbWriter::new(&db), 7.into()).unwrap();
+        access.delete_bucket_files(7.into()).unwrap();
+        assert_eq!(0, access.bucket_len(7.into()).unwrap());
+        assert_eq!(2, access.bucket_len(8.into()).unwrap());
+    }
+
+    #[test]
+    fn test_cached_bucket_len() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let access = CachedDbSetAccess::<Hash, u64>::new(db.clone(), CachePolicy::Count(4), vec![1, 2]);
+
+        for j in 0..3 {
+            access.write(DirectDbWriter::new(&db), 1.into(), j).unwrap();
+        }
+        assert_eq!(3, access.bucket_len(1.into()).unwrap());
+        assert!(access.bucket_all(1.into(), |&j| j < 3).unwrap());
+        assert!(!access.bucket_all(1.into(), |&j| j > 0).unwrap());
+        assert!(access.read_from_cache(1.into()).is_none());
+
+        // Populate the cache and make sure the cached entry is used and kept up to date
+        access.read(1.into()).unwrap();
+        access.delete(DirectDbWriter::new(&db), 1.into(), 0).unwrap();
+        assert_eq!(2, access.bucket_len(1.into()).unwrap());
+        assert!(access.bucket_all(1.into(), |&j| j > 0).unwrap());
+        assert_eq!(0, access.bucket_len(2.into()).unwrap());
+        assert!(access.bucket_all(2.into(), |_| false).unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_cache_consistency() {
+        const BUCKETS: u64 = 4;
+        const ITEMS_PER_WRITER: u64 = 500;
+        const WRITERS: u64 = 2;
+
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        // A tiny cache forces frequent evictions and reloads of set entries from the DB
+        let access = CachedDbSetAccess::<Hash, u64>::new(db.clone(), CachePolicy::Count(2), vec![1, 2]);
+
+        std::thread::scope(|scope| {
+            for writer in 0..WRITERS {
+                let (access, db) = (&access, &db);
+                scope.spawn(move || {
+                    for item in writer * ITEMS_PER_WRITER..(writer + 1) * ITEMS_PER_WRITER {
+                        access.write(DirectDbWriter::new(db), (item % BUCKETS).into(), item).unwrap();
+                        if item % 3 == 0 {
+                            access.delete(DirectDbWriter::new(db), (item % BUCKETS).into(), item).unwrap();
+                        }
+                    }
+                });
+            }
+            for _ in 0..4 {
+                let access = &access;
+                scope.spawn(move || {
+                    for i in 0..WRITERS * ITEMS_PER_WRITER {
+                        let bucket = access.read((i % BUCKETS).into()).unwrap();
+                        assert!(bucket.read().iter().all(|&item| item % BUCKETS == i % BUCKETS));
+                    }
+                });
+            }
+        });
+
+        for bucket in 0..BUCKETS {
+            let expected: HashSet<u64> =
+                (0..WRITERS * ITEMS_PER_WRITER).filter(|item| item % BUCKETS == bucket && item % 3 != 0).collect();
+            let db_items: HashSet<u64> = access.inner.bucket_iterator(bucket.into()).map(Result::unwrap).collect();
+            assert_eq!(db_items, expected);
+            // Read twice to make sure the entry is checked also if it was cached by a reader thread
+            for _ in 0..2 {
+                assert_eq!(*access.read(bucket.into()).unwrap().read(), expected);
+            }
+        }
     }
 }
@@ -1,5 +1,11 @@
 use crate::prelude::DB;
-use std::sync::Weak;
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Weak,
+    },
+};
 use tempfile::TempDir;
 
 #[derive(Default)]
@@ -49,6 +55,14 @@ pub fn get_kaspa_tempdir() -> TempDir {
     db_tempdir
 }
 
+/// Returns a path under `<OS SPECIFIC TEMP DIR>/rusty-kaspa` which is unique within the process, for naming
+/// an in-memory DB (see `ConnBuilder::with_in_memory`)
+pub fn get_memory_db_path() -> PathBuf {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join("rusty-kaspa").join(format!("memory-db-{}-{}", std::process::id(), id))
+}
+
 /// Creates a DB within a temp directory under `<OS SPECIFIC TEMP DIR>/kaspa-rust`
 /// Callers must keep the `TempDbLifetime` guard for as long as they wish the DB to exist.
 #[macro_export]
@@ -61,6 +75,16 @@ macro_rules! create_temp_db {
     }};
 }
 
+/// Creates a DB backed by memory only, for tests and simulations which do not need the data to outlive the process.
+/// Callers must keep the `TempDbLifetime` guard for as long as they wish the DB to exist.
+#[macro_export]
+macro_rules! create_memory_db {
+    ($conn_builder: expr) => {{
+        let db = $conn_builder.with_db_path($crate::utils::get_memory_db_path()).with_in_memory(true).build().unwrap();
+        ($crate::utils::DbLifetime::without_destroy(std::sync::Arc::downgrade(&db)), db)
+    }};
+}
+
 /// Creates a DB within the provided directory path.
 /// Callers must keep the `TempDbLifetime` guard for as long as they wish the DB instance to exist.
 #[macro_export]
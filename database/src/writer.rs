@@ -1,7 +1,7 @@
 use kaspa_utils::refs::Refs;
 use rocksdb::WriteBatch;
 
-use crate::prelude::DB;
+use crate::{policy::retry_transient, prelude::DB};
 
 /// Abstraction over direct/batched DB writing
 pub trait DbWriter {
@@ -13,6 +13,11 @@ pub trait DbWriter {
     fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), rocksdb::Error>
     where
         K: AsRef<[u8]>;
+
+    /// Whether the writes are applied to the DB only once a batch is committed, rather than when they are made
+    fn is_batch(&self) -> bool {
+        false
+    }
 }
 
 /// A trait which is intentionally not implemented for the batch writer.
@@ -39,20 +44,34 @@ impl DbWriter for DirectDbWriter<'_> {
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
     {
-        self.db.put(key, value)
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::inject_latency();
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::inject_failure(&self.db)?;
+        retry_transient(|| self.db.put(key.as_ref(), value.as_ref()))
     }
 
     fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), rocksdb::Error> {
-        self.db.delete(key)
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::inject_latency();
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::inject_failure(&self.db)?;
+        retry_transient(|| self.db.delete(key.as_ref()))
     }
 
     fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), rocksdb::Error>
     where
         K: AsRef<[u8]>,
     {
-        let mut batch = WriteBatch::default();
-        batch.delete_range(from, to);
-        self.db.write(batch)
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::inject_latency();
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::inject_failure(&self.db)?;
+        retry_transient(|| {
+            let mut batch = WriteBatch::default();
+            batch.delete_range(from.as_ref(), to.as_ref());
+            self.db.write(batch)
+        })
     }
 }
 
@@ -74,11 +93,15 @@ impl DbWriter for BatchDbWriter<'_> {
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
     {
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::inject_latency();
         self.batch.put(key, value);
         Ok(())
     }
 
     fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), rocksdb::Error> {
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::inject_latency();
         self.batch.delete(key);
         Ok(())
     }
@@ -87,9 +110,15 @@ impl DbWriter for BatchDbWriter<'_> {
     where
         K: AsRef<[u8]>,
     {
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::inject_latency();
         self.batch.delete_range(from, to);
         Ok(())
     }
+
+    fn is_batch(&self) -> bool {
+        true
+    }
 }
 
 impl<T: DbWriter> DbWriter for &mut T {
@@ -114,6 +143,11 @@ impl<T: DbWriter> DbWriter for &mut T {
     {
         (*self).delete_range(from, to)
     }
+
+    #[inline]
+    fn is_batch(&self) -> bool {
+        (**self).is_batch()
+    }
 }
 
 impl<T: DirectWriter> DirectWriter for &mut T {}
@@ -0,0 +1,49 @@
+use kaspa_database::{
+    create_temp_db,
+    fault_injection::{self, DbFaultConfig},
+    prelude::{ConnBuilder, DbFailureKind, DbWriter, DirectDbWriter, StoreError},
+};
+use rocksdb::WriteBatch;
+use std::time::Duration;
+
+#[test]
+fn test_injected_faults() {
+    let (lt, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+    let config =
+        DbFaultConfig { error_probability: 0.0, failure_probability: 1.0, latency_probability: 0.0, max_latency: Duration::ZERO };
+    fault_injection::enable(config);
+
+    // Direct writes
+    let err = DirectDbWriter::new(&db).put(b"key", b"value").unwrap_err();
+    assert_eq!(DbFailureKind::of(&err), DbFailureKind::Other);
+    assert!(DirectDbWriter::new(&db).delete(b"key").is_err());
+    assert!(DirectDbWriter::new(&db).delete_range(b"a", b"z").is_err());
+
+    // Batch commits
+    let mut batch = WriteBatch::default();
+    batch.put(b"key", b"value");
+    match db.write_batch(batch) {
+        Err(err @ StoreError::DbError(_)) => assert_eq!(err.failure_kind(), Some(DbFailureKind::Other)),
+        res => panic!("expected the injected failure to reach the caller, got {res:?}"),
+    }
+
+    // Failed operations have no side effects and operations succeed once faults are disabled
+    fault_injection::disable();
+    assert!(db.get(b"key").unwrap().is_none());
+    let mut batch = WriteBatch::default();
+    batch.put(b"key", b"value");
+    db.write_batch(batch).unwrap();
+    assert_eq!(db.get(b"key").unwrap().as_deref(), Some(&b"value"[..]));
+
+    // Transient faults are retried, so operations succeed as long as the retries are not exhausted, which never
+    // happens here since injection stops once the maximal number of retries is reached
+    let config =
+        DbFaultConfig { error_probability: 1.0, failure_probability: 0.0, latency_probability: 0.0, max_latency: Duration::ZERO };
+    fault_injection::enable(config);
+    DirectDbWriter::new(&db).put(b"key", b"other").unwrap();
+    fault_injection::disable();
+    assert_eq!(db.get(b"key").unwrap().as_deref(), Some(&b"other"[..]));
+
+    drop(db);
+    drop(lt);
+}
@@ -0,0 +1,16 @@
+#![no_main]
+
+use borsh::BorshDeserialize;
+use kaspa_consensus_core::{header::Header, tx::Transaction};
+use libfuzzer_sys::fuzz_target;
+
+// Borsh is used for persisting consensus data as well as for the wRPC borsh encoding, hence decoding
+// must never panic and any successfully decoded value must re-encode to the exact same bytes
+fuzz_target!(|data: &[u8]| {
+    if let Ok(tx) = Transaction::try_from_slice(data) {
+        assert_eq!(borsh::to_vec(&tx).unwrap(), data);
+    }
+    if let Ok(header) = Header::try_from_slice(data) {
+        assert_eq!(borsh::to_vec(&header).unwrap(), data);
+    }
+});
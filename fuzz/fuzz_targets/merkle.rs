@@ -0,0 +1,12 @@
+#![no_main]
+
+use kaspa_hashes::Hash;
+use kaspa_merkle::calc_merkle_root;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let hashes = data.chunks_exact(32).map(|chunk| Hash::from_slice(chunk)).collect::<Vec<_>>();
+    let root = calc_merkle_root(hashes.iter().copied());
+    // The root is a pure function of the leaves
+    assert_eq!(root, calc_merkle_root(hashes.into_iter()));
+});
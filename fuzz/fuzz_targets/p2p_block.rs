@@ -0,0 +1,21 @@
+#![no_main]
+
+use kaspa_consensus_core::{block::Block, merkle::calc_hash_merkle_root};
+use kaspa_p2p_lib::pb::BlockMessage;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(proto) = BlockMessage::decode(data) else {
+        return;
+    };
+    let Ok(block) = Block::try_from(proto) else {
+        return;
+    };
+    let reconverted = Block::try_from(BlockMessage::from(&block)).unwrap();
+    assert_eq!(block.hash(), reconverted.hash());
+    assert_eq!(block.transactions, reconverted.transactions);
+    // Merkle root computation must never panic regardless of the (unvalidated) transaction contents
+    let _ = calc_hash_merkle_root(block.transactions.iter(), false);
+    let _ = calc_hash_merkle_root(block.transactions.iter(), true);
+});
@@ -0,0 +1,18 @@
+#![no_main]
+
+use kaspa_consensus_core::header::Header;
+use kaspa_p2p_lib::pb::BlockHeader;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(proto) = BlockHeader::decode(data) else {
+        return;
+    };
+    let Ok(header) = Header::try_from(proto) else {
+        return;
+    };
+    // A successfully converted header must survive a full roundtrip through the wire format
+    let reconverted = Header::try_from(BlockHeader::decode(BlockHeader::from(&header).encode_to_vec().as_slice()).unwrap()).unwrap();
+    assert_eq!(header.hash, reconverted.hash);
+});
@@ -0,0 +1,18 @@
+#![no_main]
+
+use kaspa_consensus_core::tx::Transaction;
+use kaspa_p2p_lib::pb::TransactionMessage;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(proto) = TransactionMessage::decode(data) else {
+        return;
+    };
+    let Ok(tx) = Transaction::try_from(proto) else {
+        return;
+    };
+    let reconverted = Transaction::try_from(TransactionMessage::from(&tx)).unwrap();
+    assert_eq!(tx, reconverted);
+    assert_eq!(tx.id(), reconverted.id());
+});
@@ -0,0 +1,32 @@
+#![no_main]
+
+use kaspa_consensus_core::{
+    hashing::sighash::SigHashReusedValuesUnsync,
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{PopulatedTransaction, ScriptPublicKey, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
+};
+use kaspa_txscript::{caches::Cache, TxScriptEngine};
+use libfuzzer_sys::fuzz_target;
+
+// The first byte selects the script public key version and the second byte the length of the signature
+// script, the rest of the input is split between the signature script and the script public key
+fuzz_target!(|data: &[u8]| {
+    let [version, sig_len, rest @ ..] = data else {
+        return;
+    };
+    let (signature_script, script) = rest.split_at((*sig_len as usize).min(rest.len()));
+
+    let input = TransactionInput::new(TransactionOutpoint::new(Default::default(), 0), signature_script.to_vec(), u64::MAX, 4);
+    let output = TransactionOutput::new(1_000_000_000, ScriptPublicKey::from_vec(*version as u16, script.to_vec()));
+    let tx = Transaction::new(1, vec![input.clone()], vec![output.clone()], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+    let utxo_entry = UtxoEntry::new(output.value, output.script_public_key, 0, false);
+    let populated_tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
+
+    let sig_cache = Cache::new(100);
+    let reused_values = SigHashReusedValuesUnsync::new();
+    for kip10_enabled in [false, true] {
+        let mut vm =
+            TxScriptEngine::from_transaction_input(&populated_tx, &input, 0, &utxo_entry, &reused_values, &sig_cache, kip10_enabled);
+        let _ = vm.execute();
+    }
+});
@@ -0,0 +1,111 @@
+//! Generates initial corpus seeds for all fuzz targets. The seeds are derived from the genesis blocks of all
+//! networks along with a few typical transactions and scripts, so that fuzzing starts from inputs which already
+//! pass the protobuf decoding and conversion stages.
+//!
+//! Usage (from the `fuzz` directory): `cargo run --bin generate_corpus [corpus-dir]`
+
+use kaspa_consensus_core::{
+    block::Block,
+    config::genesis::{GenesisBlock, DEVNET_GENESIS, GENESIS, SIMNET_GENESIS, TESTNET11_GENESIS, TESTNET_GENESIS},
+    header::Header,
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{ScriptPublicKey, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput},
+};
+use kaspa_p2p_lib::pb::{BlockHeader, BlockMessage, TransactionMessage};
+use kaspa_txscript::{
+    multisig_redeem_script,
+    opcodes::codes::{OpCheckSig, OpTrue},
+    pay_to_script_hash_script,
+    script_builder::ScriptBuilder,
+};
+use prost::Message;
+use std::{fs, path::Path};
+
+const GENESIS_BLOCKS: [GenesisBlock; 5] = [GENESIS, TESTNET_GENESIS, TESTNET11_GENESIS, SIMNET_GENESIS, DEVNET_GENESIS];
+
+fn write_seeds(corpus_dir: &Path, target: &str, seeds: impl IntoIterator<Item = Vec<u8>>) {
+    let dir = corpus_dir.join(target);
+    fs::create_dir_all(&dir).unwrap();
+    let mut count = 0;
+    for (i, seed) in seeds.into_iter().enumerate() {
+        fs::write(dir.join(format!("seed-{i:03}")), seed).unwrap();
+        count += 1;
+    }
+    println!("{target}: {count} seeds");
+}
+
+fn sample_transaction(parent: &Transaction) -> Transaction {
+    let inputs = (0..3).map(|i| TransactionInput::new(TransactionOutpoint::new(parent.id(), i), vec![i as u8; 66], 0, 1)).collect();
+    let outputs = (0..2)
+        .map(|i| {
+            TransactionOutput::new(1000 * (i + 1), ScriptPublicKey::from_vec(0, [vec![0x20], vec![i as u8; 32], vec![0xac]].concat()))
+        })
+        .collect();
+    Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![])
+}
+
+fn sample_block(genesis: &GenesisBlock, parent: &Header) -> Block {
+    let coinbase = genesis.build_genesis_transactions().remove(0);
+    let tx = sample_transaction(&coinbase);
+    let header = Header::new_finalized(
+        1,
+        vec![vec![parent.hash], vec![parent.hash]],
+        parent.hash_merkle_root,
+        Default::default(),
+        parent.utxo_commitment,
+        parent.timestamp + 1000,
+        parent.bits,
+        parent.nonce + 1,
+        parent.daa_score + 1,
+        1000.into(),
+        1,
+        parent.hash,
+    );
+    Block::new(header, vec![coinbase, tx])
+}
+
+fn main() {
+    let corpus_dir = std::env::args().nth(1).unwrap_or_else(|| "corpus".to_string());
+    let corpus_dir = Path::new(&corpus_dir);
+
+    let genesis_headers = GENESIS_BLOCKS.iter().map(Header::from).collect::<Vec<_>>();
+    let blocks = GENESIS_BLOCKS
+        .iter()
+        .zip(genesis_headers.iter())
+        .flat_map(|(genesis, header)| [Block::from(genesis), sample_block(genesis, header)])
+        .collect::<Vec<_>>();
+    let txs = blocks.iter().flat_map(|block| block.transactions.iter().cloned()).collect::<Vec<_>>();
+
+    write_seeds(corpus_dir, "p2p_header", blocks.iter().map(|block| BlockHeader::from(block.header.as_ref()).encode_to_vec()));
+    write_seeds(corpus_dir, "p2p_block", blocks.iter().map(|block| BlockMessage::from(block).encode_to_vec()));
+    write_seeds(corpus_dir, "p2p_transaction", txs.iter().map(|tx| TransactionMessage::from(tx).encode_to_vec()));
+    write_seeds(
+        corpus_dir,
+        "borsh_serialization",
+        txs.iter()
+            .map(|tx| borsh::to_vec(tx).unwrap())
+            .chain(blocks.iter().map(|block| borsh::to_vec(block.header.as_ref()).unwrap())),
+    );
+    write_seeds(
+        corpus_dir,
+        "merkle",
+        blocks.iter().map(|block| block.transactions.iter().flat_map(|tx| tx.id().as_bytes()).collect::<Vec<u8>>()),
+    );
+
+    // Script seeds follow the layout expected by the txscript target: [spk version, signature script length, signature script, script]
+    let redeem_script = multisig_redeem_script([[1u8; 32], [2u8; 32], [3u8; 32]].iter(), 2).unwrap();
+    let p2sh_signature_script = ScriptBuilder::new().add_data(&[0u8; 65]).unwrap().add_data(&redeem_script).unwrap().drain();
+    let p2pk_script = ScriptBuilder::new().add_data(&[1u8; 32]).unwrap().add_op(OpCheckSig).unwrap().drain();
+    let scripts = [
+        (vec![], vec![OpTrue]),
+        (ScriptBuilder::new().add_data(&[0u8; 65]).unwrap().drain(), p2pk_script),
+        (p2sh_signature_script, pay_to_script_hash_script(&redeem_script).script().to_vec()),
+    ];
+    write_seeds(
+        corpus_dir,
+        "txscript",
+        scripts
+            .into_iter()
+            .map(|(signature_script, script)| [vec![0, signature_script.len() as u8], signature_script, script].concat()),
+    );
+}
@@ -0,0 +1,84 @@
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use kaspa_consensusmanager::spawn_blocking;
+use kaspa_database::prelude::StoreResult;
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{errors::AcceptanceIndexResult, model::TransactionAcceptance};
+
+/// Acceptance index API targeted at retrieval calls.
+pub trait AcceptanceIndexApi: Send + Sync + Debug {
+    /// Retrieve the accepting chain block of each transaction, or `None` for transactions not accepted by the
+    /// selected chain.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_transactions_acceptance(&self, transaction_ids: &[TransactionId]) -> StoreResult<Vec<Option<TransactionAcceptance>>>;
+
+    /// Retrieve the sink the acceptance index is synced with.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_sink(&self) -> StoreResult<Hash>;
+
+    /// Checks if the acceptance index's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> AcceptanceIndexResult<bool>;
+
+    /// Update the acceptance index with the chain blocks removed from and added to the selected chain.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AcceptanceIndexResult<()>;
+
+    /// Resync the acceptance index from the consensus db
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> AcceptanceIndexResult<()>;
+}
+
+/// Async proxy for the acceptance index
+#[derive(Debug, Clone)]
+pub struct AcceptanceIndexProxy {
+    inner: Arc<RwLock<dyn AcceptanceIndexApi>>,
+}
+
+impl AcceptanceIndexProxy {
+    pub fn new(inner: Arc<RwLock<dyn AcceptanceIndexApi>>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get_transactions_acceptance(
+        self,
+        transaction_ids: Vec<TransactionId>,
+    ) -> StoreResult<Vec<Option<TransactionAcceptance>>> {
+        spawn_blocking(move || self.inner.read().get_transactions_acceptance(&transaction_ids)).await.unwrap()
+    }
+
+    pub async fn update(
+        self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AcceptanceIndexResult<()> {
+        spawn_blocking(move || {
+            self.inner.write().update(removed_chain_block_hashes, added_chain_block_hashes, added_chain_blocks_acceptance_data)
+        })
+        .await
+        .unwrap()
+    }
+
+    pub async fn is_synced(self) -> AcceptanceIndexResult<bool> {
+        spawn_blocking(move || self.inner.read().is_synced()).await.unwrap()
+    }
+
+    pub async fn resync(self) -> AcceptanceIndexResult<()> {
+        spawn_blocking(move || self.inner.write().resync()).await.unwrap()
+    }
+}
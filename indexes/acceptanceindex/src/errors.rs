@@ -0,0 +1,22 @@
+use std::io;
+use thiserror::Error;
+
+use crate::IDENT;
+use kaspa_consensus_core::errors::consensus::ConsensusError;
+use kaspa_database::prelude::StoreError;
+
+/// Errors originating from the [`AcceptanceIndex`](crate::AcceptanceIndex).
+#[derive(Error, Debug)]
+pub enum AcceptanceIndexError {
+    #[error("[{IDENT}]: {0}")]
+    StoreAccessError(#[from] StoreError),
+
+    #[error("[{IDENT}]: {0}")]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error("[{IDENT}]: {0}")]
+    DBResetError(#[from] io::Error),
+}
+
+/// Results originating from the [`AcceptanceIndex`](crate::AcceptanceIndex).
+pub type AcceptanceIndexResult<T> = Result<T, AcceptanceIndexError>;
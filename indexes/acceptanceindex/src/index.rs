@@ -0,0 +1,270 @@
+use crate::{
+    api::AcceptanceIndexApi,
+    errors::{AcceptanceIndexError, AcceptanceIndexResult},
+    model::TransactionAcceptance,
+    stores::store_manager::Store,
+    IDENT,
+};
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use kaspa_consensusmanager::{ConsensusManager, ConsensusResetHandler, ConsensusSessionBlocking};
+use kaspa_core::{info, trace};
+use kaspa_database::prelude::{StoreError, StoreResult, DB};
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+const RESYNC_CHUNK_SIZE: usize = 1024; // Number of chain blocks committed at once while resyncing.
+
+/// AcceptanceIndex maps every transaction accepted by the selected chain to its accepting chain block, following
+/// the virtual chain changes reported by consensus.
+///
+/// Notes:
+/// 1) The AcceptanceIndex struct by itself is not thread safe, only correct usage of the supplied RwLock via `new` makes it so.
+/// 2) A resync indexes the chain from the pruning point up, so transactions accepted below the pruning point are only
+///    kept until the next resync.
+pub struct AcceptanceIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl AcceptanceIndex {
+    /// Creates a new [`AcceptanceIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> AcceptanceIndexResult<Arc<RwLock<Self>>> {
+        let mut acceptance_index = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !acceptance_index.is_synced()? {
+            acceptance_index.resync()?;
+        }
+        let acceptance_index = Arc::new(RwLock::new(acceptance_index));
+        consensus_manager
+            .register_consensus_reset_handler(Arc::new(AcceptanceIndexConsensusResetHandler::new(Arc::downgrade(&acceptance_index))));
+        Ok(acceptance_index)
+    }
+
+    /// Collects the acceptance entries of the transactions accepted by the given chain blocks
+    fn acceptance_entries(
+        session: &ConsensusSessionBlocking,
+        chain_block_hashes: &[Hash],
+        acceptance_data: &[Arc<AcceptanceData>],
+    ) -> AcceptanceIndexResult<Vec<(TransactionId, TransactionAcceptance)>> {
+        let mut entries = Vec::new();
+        for (&accepting_block_hash, acceptance_data) in chain_block_hashes.iter().zip(acceptance_data.iter()) {
+            let acceptance = TransactionAcceptance {
+                accepting_block_hash,
+                accepting_blue_score: session.get_header(accepting_block_hash)?.blue_score,
+            };
+            entries.extend(
+                acceptance_data
+                    .iter()
+                    .flat_map(|mergeset_block| mergeset_block.accepted_transactions.iter())
+                    .map(|accepted_tx| (accepted_tx.transaction_id, acceptance)),
+            );
+        }
+        Ok(entries)
+    }
+}
+
+impl AcceptanceIndexApi for AcceptanceIndex {
+    /// Retrieve the accepting chain block of each transaction from the acceptance index db.
+    fn get_transactions_acceptance(&self, transaction_ids: &[TransactionId]) -> StoreResult<Vec<Option<TransactionAcceptance>>> {
+        trace!("[{0}] retrieving the acceptance of {1} transactions", IDENT, transaction_ids.len());
+
+        transaction_ids.iter().map(|&transaction_id| self.store.get_transaction_acceptance(transaction_id)).collect()
+    }
+
+    /// Retrieve the stored sink of the acceptance index.
+    fn get_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Checks to see if the [AcceptanceIndex] is sync'd. This is done via comparing the acceptance index committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the acceptance index and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> AcceptanceIndexResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(sink) => {
+                let res = sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                // Means the acceptance index sink database is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(err) => Err(AcceptanceIndexError::StoreAccessError(err)),
+        }
+    }
+
+    /// Updates the [AcceptanceIndex] via the virtual chain changes supplied:
+    /// 1) Removes the transactions accepted by the removed chain blocks.
+    /// 2) Adds the transactions accepted by the added chain blocks and commits the new sink.
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AcceptanceIndexResult<()> {
+        trace!("[{0}] updating...", IDENT);
+        trace!("[{0}] removing {1} chain blocks", IDENT, removed_chain_block_hashes.len());
+        trace!("[{0}] adding {1} chain blocks", IDENT, added_chain_block_hashes.len());
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        // Only remove transactions still indexed as accepted by a removed block
+        let mut to_remove = Vec::new();
+        for &removed_block_hash in removed_chain_block_hashes.iter() {
+            let acceptance_data = session.get_block_acceptance_data(removed_block_hash)?;
+            for accepted_tx in acceptance_data.iter().flat_map(|mergeset_block| mergeset_block.accepted_transactions.iter()) {
+                if self
+                    .store
+                    .get_transaction_acceptance(accepted_tx.transaction_id)?
+                    .is_some_and(|acceptance| acceptance.accepting_block_hash == removed_block_hash)
+                {
+                    to_remove.push(accepted_tx.transaction_id);
+                }
+            }
+        }
+
+        // The notification carries no acceptance data if consensus was not asked to include it
+        let added_chain_blocks_acceptance_data = if added_chain_blocks_acceptance_data.len() == added_chain_block_hashes.len() {
+            added_chain_blocks_acceptance_data
+        } else {
+            Arc::new(session.get_blocks_acceptance_data(&added_chain_block_hashes, None)?)
+        };
+        let to_add = Self::acceptance_entries(&session, &added_chain_block_hashes, &added_chain_blocks_acceptance_data)?;
+
+        self.store.update(&to_remove, &to_add, added_chain_block_hashes.last().copied())?;
+        Ok(())
+    }
+
+    /// Deletes and reinstates the acceptance index database, syncing it from scratch via the consensus database.
+    ///
+    /// **Notes:**
+    /// 1) The selected chain is indexed from the current pruning point up to the sink.
+    /// 2) resyncing while consensus notifies of virtual chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> AcceptanceIndexResult<()> {
+        info!("Resyncing the acceptance index...");
+
+        self.store.delete_all()?;
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let pruning_point = session.pruning_point();
+        let chain_path = session.get_virtual_chain_from_block(pruning_point, None)?;
+        for chain_block_hashes in chain_path.added.chunks(RESYNC_CHUNK_SIZE) {
+            let acceptance_data = session.get_blocks_acceptance_data(chain_block_hashes, None)?;
+            let to_add = Self::acceptance_entries(&session, chain_block_hashes, &acceptance_data)?;
+            trace!("[{0}] resyncing with batch of {1} accepted transactions from consensus db", IDENT, to_add.len());
+            self.store.update(&[], &to_add, None)?;
+        }
+
+        // Commit the sink last so that an interrupted resync is detected as out of sync
+        let sink = chain_path.added.last().copied().unwrap_or(pruning_point);
+        trace!("[{0}] committing sink {1} from consensus db", IDENT, sink);
+        self.store.update(&[], &[], Some(sink))?;
+
+        Ok(())
+    }
+}
+
+impl Debug for AcceptanceIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AcceptanceIndex").finish()
+    }
+}
+
+struct AcceptanceIndexConsensusResetHandler {
+    acceptance_index: Weak<RwLock<AcceptanceIndex>>,
+}
+
+impl AcceptanceIndexConsensusResetHandler {
+    fn new(acceptance_index: Weak<RwLock<AcceptanceIndex>>) -> Self {
+        Self { acceptance_index }
+    }
+}
+
+impl ConsensusResetHandler for AcceptanceIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(acceptance_index) = self.acceptance_index.upgrade() {
+            acceptance_index.write().resync().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::AcceptanceIndexApi, AcceptanceIndex};
+    use kaspa_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::MAINNET_PARAMS};
+    use kaspa_consensus_core::api::ConsensusApi;
+    use kaspa_consensusmanager::ConsensusManager;
+    use kaspa_database::create_temp_db;
+    use kaspa_database::prelude::ConnBuilder;
+    use kaspa_hashes::Hash;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_acceptance_index() {
+        kaspa_core::log::try_init_logger("INFO");
+
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| {
+                p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+            })
+            .build();
+        let tc = TestConsensus::new(&config);
+        let wait_handles = tc.init();
+
+        // Build a chain of blocks, each accepting the coinbase transaction of its selected parent
+        let mut parent = config.genesis.hash;
+        for i in 1..6u64 {
+            let hash: Hash = i.into();
+            futures::executor::block_on(tc.add_utxo_valid_block_with_parents(hash, vec![parent], vec![])).unwrap();
+            parent = hash;
+        }
+
+        let (_acceptance_index_db_lifetime, acceptance_index_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let acceptance_index = AcceptanceIndex::new(consensus_manager, acceptance_index_db).unwrap();
+        assert!(acceptance_index.read().is_synced().unwrap());
+        assert_eq!(acceptance_index.read().get_sink().unwrap(), tc.get_sink());
+
+        // The resync indexes all transactions accepted by the selected chain
+        let chain = tc.get_virtual_chain_from_block(tc.pruning_point(), None).unwrap().added;
+        let mut accepted_transaction_ids = Vec::new();
+        for &chain_block_hash in chain.iter() {
+            let blue_score = tc.get_header(chain_block_hash).unwrap().blue_score;
+            let transaction_ids = tc
+                .get_block_acceptance_data(chain_block_hash)
+                .unwrap()
+                .iter()
+                .flat_map(|mergeset_block| mergeset_block.accepted_transactions.iter().map(|tx| tx.transaction_id))
+                .collect::<Vec<_>>();
+            for acceptance in acceptance_index.read().get_transactions_acceptance(&transaction_ids).unwrap() {
+                let acceptance = acceptance.expect("accepted transactions are indexed");
+                assert_eq!(acceptance.accepting_block_hash, chain_block_hash);
+                assert_eq!(acceptance.accepting_blue_score, blue_score);
+            }
+            accepted_transaction_ids.extend(transaction_ids);
+        }
+        assert!(!accepted_transaction_ids.is_empty());
+        assert_eq!(acceptance_index.read().get_transactions_acceptance(&[Hash::from_u64_word(u64::MAX)]).unwrap(), vec![None]);
+
+        // Removing the chain blocks removes the transactions they accepted
+        acceptance_index.write().update(Arc::new(chain), Arc::new(vec![]), Arc::new(vec![])).unwrap();
+        assert!(acceptance_index.read().get_transactions_acceptance(&accepted_transaction_ids).unwrap().iter().all(Option::is_none));
+
+        tc.shutdown(wait_handles);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod api;
+pub mod errors;
+mod index;
+pub mod model;
+mod stores;
+
+pub use crate::index::AcceptanceIndex; //we expose this separately to initiate the index.
+
+const IDENT: &str = "acceptanceindex";
@@ -0,0 +1,12 @@
+use kaspa_hashes::Hash;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+
+/// The chain block accepting a transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionAcceptance {
+    pub accepting_block_hash: Hash,
+    pub accepting_blue_score: u64,
+}
+
+impl MemSizeEstimator for TransactionAcceptance {}
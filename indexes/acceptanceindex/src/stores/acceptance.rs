@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use rocksdb::WriteBatch;
+
+use crate::model::TransactionAcceptance;
+
+/// Reader API for `TransactionAcceptanceStore`.
+pub trait TransactionAcceptanceStoreReader {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TransactionAcceptance>>;
+}
+
+pub trait TransactionAcceptanceStore: TransactionAcceptanceStoreReader {
+    fn write_batch(&mut self, batch: &mut WriteBatch, entries: &[(TransactionId, TransactionAcceptance)]) -> StoreResult<()>;
+    fn delete_batch(&mut self, batch: &mut WriteBatch, transaction_ids: &[TransactionId]) -> StoreResult<()>;
+
+    /// Removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TransactionAcceptanceStore` trait
+#[derive(Clone)]
+pub struct DbTransactionAcceptanceStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, TransactionAcceptance>,
+}
+
+impl DbTransactionAcceptanceStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AcceptanceIndex.into()) }
+    }
+}
+
+impl TransactionAcceptanceStoreReader for DbTransactionAcceptanceStore {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TransactionAcceptance>> {
+        match self.access.read(transaction_id) {
+            Ok(acceptance) => Ok(Some(acceptance)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl TransactionAcceptanceStore for DbTransactionAcceptanceStore {
+    fn write_batch(&mut self, batch: &mut WriteBatch, entries: &[(TransactionId, TransactionAcceptance)]) -> StoreResult<()> {
+        let mut writer = BatchDbWriter::new(batch);
+        for (transaction_id, acceptance) in entries.iter() {
+            self.access.write(&mut writer, *transaction_id, *acceptance)?;
+        }
+        Ok(())
+    }
+
+    fn delete_batch(&mut self, batch: &mut WriteBatch, transaction_ids: &[TransactionId]) -> StoreResult<()> {
+        let mut writer = BatchDbWriter::new(batch);
+        for transaction_id in transaction_ids.iter() {
+            self.access.delete(&mut writer, *transaction_id)?;
+        }
+        Ok(())
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
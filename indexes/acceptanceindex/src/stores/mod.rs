@@ -0,0 +1,3 @@
+mod acceptance;
+mod sink;
+pub mod store_manager;
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachedDbItem, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+
+/// Reader API for `AcceptanceIndexSinkStore`.
+pub trait AcceptanceIndexSinkStoreReader {
+    fn get(&self) -> StoreResult<Hash>;
+}
+
+pub trait AcceptanceIndexSinkStore: AcceptanceIndexSinkStoreReader {
+    fn set_batch(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()>;
+    fn remove(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `AcceptanceIndexSinkStore` trait
+#[derive(Clone)]
+pub struct DbAcceptanceIndexSinkStore {
+    db: Arc<DB>,
+    access: CachedDbItem<Hash>,
+}
+
+impl DbAcceptanceIndexSinkStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db, DatabaseStorePrefixes::AcceptanceIndexSink.into()) }
+    }
+}
+
+impl AcceptanceIndexSinkStoreReader for DbAcceptanceIndexSinkStore {
+    fn get(&self) -> StoreResult<Hash> {
+        self.access.read()
+    }
+}
+
+impl AcceptanceIndexSinkStore for DbAcceptanceIndexSinkStore {
+    fn set_batch(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), &sink)
+    }
+
+    fn remove(&mut self) -> StoreResult<()> {
+        self.access.remove(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_core::trace;
+use kaspa_database::prelude::{CachePolicy, StoreResult, DB};
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+
+use crate::{
+    model::TransactionAcceptance,
+    stores::{
+        acceptance::{DbTransactionAcceptanceStore, TransactionAcceptanceStore, TransactionAcceptanceStoreReader},
+        sink::{AcceptanceIndexSinkStore, AcceptanceIndexSinkStoreReader, DbAcceptanceIndexSinkStore},
+    },
+    IDENT,
+};
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    acceptance_store: DbTransactionAcceptanceStore,
+    sink_store: DbAcceptanceIndexSinkStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            acceptance_store: DbTransactionAcceptanceStore::new(db.clone(), CachePolicy::Empty),
+            sink_store: DbAcceptanceIndexSinkStore::new(db),
+        }
+    }
+
+    pub fn get_transaction_acceptance(&self, transaction_id: TransactionId) -> StoreResult<Option<TransactionAcceptance>> {
+        self.acceptance_store.get(transaction_id)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    /// Atomically removes the `to_remove` entries, then adds the `to_add` entries and sets the sink, if any
+    pub fn update(
+        &mut self,
+        to_remove: &[TransactionId],
+        to_add: &[(TransactionId, TransactionAcceptance)],
+        sink: Option<Hash>,
+    ) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        self.acceptance_store.delete_batch(&mut batch, to_remove)?;
+        self.acceptance_store.write_batch(&mut batch, to_add)?;
+        if let Some(sink) = sink {
+            self.sink_store.set_batch(&mut batch, sink)?;
+        }
+        self.db.write_batch(batch)?;
+        Ok(())
+    }
+
+    /// Resets the acceptance index database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        trace!("[{0}] attempting to clear acceptance index database...", IDENT);
+
+        // Clear all
+        self.sink_store.remove()?;
+        self.acceptance_store.delete_all()?;
+
+        trace!("[{0}] clearing acceptance index database - success!", IDENT);
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use kaspa_acceptanceindex::errors::AcceptanceIndexError;
 use kaspa_notify::events::EventType;
 use kaspa_utxoindex::errors::UtxoIndexError;
 use thiserror::Error;
@@ -7,6 +8,9 @@ pub enum IndexError {
     #[error("{0}")]
     UtxoIndexError(#[from] UtxoIndexError),
 
+    #[error("{0}")]
+    AcceptanceIndexError(#[from] AcceptanceIndexError),
+
     #[error("event type {0:?} is not supported")]
     NotSupported(EventType),
 }
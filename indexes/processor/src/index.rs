@@ -0,0 +1,121 @@
+use crate::errors::IndexResult;
+use async_trait::async_trait;
+use kaspa_acceptanceindex::api::AcceptanceIndexProxy;
+use kaspa_consensus_notify::notification as consensus_notification;
+use kaspa_index_core::notification::UtxosChangedNotification;
+use kaspa_utxoindex::api::UtxoIndexProxy;
+use std::{fmt::Debug, sync::Arc};
+
+/// An index maintained by the index [`Processor`](crate::processor::Processor) from consensus notifications.
+///
+/// The processor drives all registered indexes uniformly, in registration order. An error raised by an index
+/// is logged and does not prevent the other indexes from processing the same notification.
+#[async_trait]
+pub trait Index: Send + Sync + Debug {
+    /// A short name identifying the index in logs
+    fn ident(&self) -> &'static str;
+
+    /// Applies the virtual UTXO diff carried by a consensus `UtxosChanged` notification.
+    ///
+    /// An index tracking UTXOs returns the resulting changes, which get relayed as an index `UtxosChanged`
+    /// notification. Other indexes return `None`.
+    async fn handle_utxos_changed(
+        &self,
+        notification: &consensus_notification::UtxosChangedNotification,
+    ) -> IndexResult<Option<UtxosChangedNotification>>;
+
+    /// Returns whether the index follows the virtual chain, in which case consensus `VirtualChainChanged`
+    /// notifications are submitted to [`Index::handle_virtual_chain_changed`]
+    fn tracks_virtual_chain(&self) -> bool {
+        false
+    }
+
+    /// Applies the selected chain changes carried by a consensus `VirtualChainChanged` notification
+    async fn handle_virtual_chain_changed(
+        &self,
+        _notification: &consensus_notification::VirtualChainChangedNotification,
+    ) -> IndexResult<()> {
+        Ok(())
+    }
+
+    /// Called when consensus overrides its UTXO set with the UTXO set of a new pruning point
+    async fn handle_pruning_point_utxo_set_override(&self) -> IndexResult<()> {
+        Ok(())
+    }
+
+    /// Returns whether the index is in sync with the consensus database
+    async fn is_synced(&self) -> IndexResult<bool>;
+
+    /// Rebuilds the index from the consensus database
+    async fn resync(&self) -> IndexResult<()>;
+}
+
+pub type DynIndex = Arc<dyn Index>;
+
+#[async_trait]
+impl Index for UtxoIndexProxy {
+    fn ident(&self) -> &'static str {
+        "utxoindex"
+    }
+
+    async fn handle_utxos_changed(
+        &self,
+        notification: &consensus_notification::UtxosChangedNotification,
+    ) -> IndexResult<Option<UtxosChangedNotification>> {
+        let changes = self.clone().update(notification.accumulated_utxo_diff.clone(), notification.virtual_parents.clone()).await?;
+        Ok(Some(changes.into()))
+    }
+
+    // The UTXO index gets reset along with consensus, see `UtxoIndexConsensusResetHandler`, so it has
+    // nothing to do on a pruning point UTXO set override
+
+    async fn is_synced(&self) -> IndexResult<bool> {
+        Ok(self.clone().is_synced().await?)
+    }
+
+    async fn resync(&self) -> IndexResult<()> {
+        Ok(self.clone().resync().await?)
+    }
+}
+
+#[async_trait]
+impl Index for AcceptanceIndexProxy {
+    fn ident(&self) -> &'static str {
+        "acceptanceindex"
+    }
+
+    async fn handle_utxos_changed(
+        &self,
+        _notification: &consensus_notification::UtxosChangedNotification,
+    ) -> IndexResult<Option<UtxosChangedNotification>> {
+        Ok(None)
+    }
+
+    fn tracks_virtual_chain(&self) -> bool {
+        true
+    }
+
+    async fn handle_virtual_chain_changed(
+        &self,
+        notification: &consensus_notification::VirtualChainChangedNotification,
+    ) -> IndexResult<()> {
+        Ok(self
+            .clone()
+            .update(
+                notification.removed_chain_block_hashes.clone(),
+                notification.added_chain_block_hashes.clone(),
+                notification.added_chain_blocks_acceptance_data.clone(),
+            )
+            .await?)
+    }
+
+    // Like the UTXO index, the acceptance index gets reset along with consensus
+
+    async fn is_synced(&self) -> IndexResult<bool> {
+        Ok(self.clone().is_synced().await?)
+    }
+
+    async fn resync(&self) -> IndexResult<()> {
+        Ok(self.clone().resync().await?)
+    }
+}
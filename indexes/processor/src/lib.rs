@@ -1,4 +1,5 @@
 pub mod errors;
+pub mod index;
 pub mod processor;
 pub mod service;
 
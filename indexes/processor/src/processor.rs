@@ -1,10 +1,11 @@
 use crate::{
     errors::{IndexError, IndexResult},
+    index::DynIndex,
     IDENT,
 };
 use async_trait::async_trait;
 use kaspa_consensus_notify::{notification as consensus_notification, notification::Notification as ConsensusNotification};
-use kaspa_core::{debug, trace};
+use kaspa_core::{debug, trace, warn};
 use kaspa_index_core::notification::{Notification, PruningPointUtxoSetOverrideNotification, UtxosChangedNotification};
 use kaspa_notify::{
     collector::{Collector, CollectorNotificationReceiver},
@@ -14,21 +15,20 @@ use kaspa_notify::{
     notifier::DynNotify,
 };
 use kaspa_utils::triggers::SingleTrigger;
-use kaspa_utxoindex::api::UtxoIndexProxy;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
 };
 
-/// Processor processes incoming consensus UtxosChanged and PruningPointUtxoSetOverride
-/// notifications submitting them to a UtxoIndex.
+/// Processor processes incoming consensus UtxosChanged, VirtualChainChanged and PruningPointUtxoSetOverride
+/// notifications submitting them to the registered indexes.
 ///
 /// It also acts as a [`Collector`], converting the incoming consensus notifications
 /// into their pending local versions and relaying them to a local notifier.
 #[derive(Debug)]
 pub struct Processor {
-    /// An optional UTXO indexer
-    utxoindex: Option<UtxoIndexProxy>,
+    /// The registered indexes, in processing order
+    indexes: Vec<DynIndex>,
 
     recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
 
@@ -39,13 +39,8 @@ pub struct Processor {
 }
 
 impl Processor {
-    pub fn new(utxoindex: Option<UtxoIndexProxy>, recv_channel: CollectorNotificationReceiver<ConsensusNotification>) -> Self {
-        Self {
-            utxoindex,
-            recv_channel,
-            collect_shutdown: Arc::new(SingleTrigger::new()),
-            is_started: Arc::new(AtomicBool::new(false)),
-        }
+    pub fn new(indexes: Vec<DynIndex>, recv_channel: CollectorNotificationReceiver<ConsensusNotification>) -> Self {
+        Self { indexes, recv_channel, collect_shutdown: Arc::new(SingleTrigger::new()), is_started: Arc::new(AtomicBool::new(false)) }
     }
 
     fn spawn_collecting_task(self: Arc<Self>, notifier: DynNotify<Notification>) {
@@ -58,12 +53,13 @@ impl Processor {
 
             while let Ok(notification) = self.recv_channel.recv().await {
                 match self.process_notification(notification).await {
-                    Ok(notification) => match notifier.notify(notification) {
+                    Ok(Some(notification)) => match notifier.notify(notification) {
                         Ok(_) => (),
                         Err(err) => {
                             trace!("[Index processor] notification sender error: {err:?}");
                         }
                     },
+                    Ok(None) => (),
                     Err(err) => {
                         trace!("[Index processor] error while processing a consensus notification: {err:?}");
                     }
@@ -76,13 +72,20 @@ impl Processor {
         });
     }
 
-    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Notification> {
+    /// Processes a consensus notification, returning the local notification to relay, if any
+    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Option<Notification>> {
         match notification {
             ConsensusNotification::UtxosChanged(utxos_changed) => {
-                Ok(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?))
+                Ok(Some(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?)))
             }
             ConsensusNotification::PruningPointUtxoSetOverride(_) => {
-                Ok(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {}))
+                self.process_pruning_point_utxo_set_override().await;
+                Ok(Some(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {})))
+            }
+            ConsensusNotification::VirtualChainChanged(virtual_chain_changed) => {
+                // Virtual chain changes only feed the indexes and are not relayed
+                self.process_virtual_chain_changed(virtual_chain_changed).await;
+                Ok(None)
             }
             _ => Err(IndexError::NotSupported(notification.event_type())),
         }
@@ -93,17 +96,46 @@ impl Processor {
         notification: consensus_notification::UtxosChangedNotification,
     ) -> IndexResult<UtxosChangedNotification> {
         trace!("[{IDENT}]: processing {:?}", notification);
-        if let Some(utxoindex) = self.utxoindex.clone() {
-            let converted_notification: UtxosChangedNotification =
-                utxoindex.update(notification.accumulated_utxo_diff.clone(), notification.virtual_parents).await?.into();
+        // The first index reporting UTXO changes provides the relayed notification, while the
+        // error of a failing index is only returned if no other index reported changes
+        let mut result = Err(IndexError::NotSupported(EventType::UtxosChanged));
+        for index in self.indexes.iter() {
+            match index.handle_utxos_changed(&notification).await {
+                Ok(Some(converted_notification)) if result.is_err() => result = Ok(converted_notification),
+                Ok(_) => {}
+                Err(err) => {
+                    warn!("[{IDENT}]: {} failed processing a UtxosChanged notification: {err}", index.ident());
+                    if result.is_err() {
+                        result = Err(err);
+                    }
+                }
+            }
+        }
+        if let Ok(ref converted_notification) = result {
             debug!(
                 "IDXPRC, Creating UtxosChanged notifications with {} added and {} removed utxos",
                 converted_notification.added.len(),
                 converted_notification.removed.len()
             );
-            return Ok(converted_notification);
-        };
-        Err(IndexError::NotSupported(EventType::UtxosChanged))
+        }
+        result
+    }
+
+    async fn process_virtual_chain_changed(self: &Arc<Self>, notification: consensus_notification::VirtualChainChangedNotification) {
+        trace!("[{IDENT}]: processing {:?}", notification);
+        for index in self.indexes.iter().filter(|index| index.tracks_virtual_chain()) {
+            if let Err(err) = index.handle_virtual_chain_changed(&notification).await {
+                warn!("[{IDENT}]: {} failed processing a VirtualChainChanged notification: {err}", index.ident());
+            }
+        }
+    }
+
+    async fn process_pruning_point_utxo_set_override(self: &Arc<Self>) {
+        for index in self.indexes.iter() {
+            if let Err(err) = index.handle_pruning_point_utxo_set_override().await {
+                warn!("[{IDENT}]: {} failed processing a PruningPointUtxoSetOverride notification: {err}", index.ident());
+            }
+        }
     }
 
     async fn join_collecting_task(&self) -> Result<()> {
@@ -128,6 +160,7 @@ impl Collector<Notification> for Processor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::index::Index;
     use async_channel::{unbounded, Receiver, Sender};
     use kaspa_consensus::{config::Config, consensus::test_consensus::TestConsensus, params::DEVNET_PARAMS, test_helpers::*};
     use kaspa_consensus_core::utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff};
@@ -136,7 +169,7 @@ mod tests {
     use kaspa_database::prelude::ConnBuilder;
     use kaspa_database::utils::DbLifetime;
     use kaspa_notify::notifier::test_helpers::NotifyMock;
-    use kaspa_utxoindex::UtxoIndex;
+    use kaspa_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
     use rand::{rngs::SmallRng, SeedableRng};
     use std::sync::Arc;
 
@@ -151,16 +184,52 @@ mod tests {
         utxoindex_db_lifetime: DbLifetime,
     }
 
+    /// An index failing on every notification
+    #[derive(Debug)]
+    struct FailingIndex;
+
+    #[async_trait]
+    impl Index for FailingIndex {
+        fn ident(&self) -> &'static str {
+            "failing-index"
+        }
+
+        async fn handle_utxos_changed(
+            &self,
+            _notification: &consensus_notification::UtxosChangedNotification,
+        ) -> IndexResult<Option<UtxosChangedNotification>> {
+            Err(IndexError::NotSupported(EventType::UtxosChanged))
+        }
+
+        async fn handle_pruning_point_utxo_set_override(&self) -> IndexResult<()> {
+            Err(IndexError::NotSupported(EventType::PruningPointUtxoSetOverride))
+        }
+
+        async fn is_synced(&self) -> IndexResult<bool> {
+            Ok(true)
+        }
+
+        async fn resync(&self) -> IndexResult<()> {
+            Ok(())
+        }
+    }
+
     impl NotifyPipeline {
         fn new() -> Self {
+            Self::with_failing_index(false)
+        }
+
+        /// Builds the pipeline, registering a failing index ahead of the UTXO index if `failing_index` is set
+        fn with_failing_index(failing_index: bool) -> Self {
             let (consensus_sender, consensus_receiver) = unbounded();
             let (utxoindex_db_lifetime, utxoindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
             let config = Arc::new(Config::new(DEVNET_PARAMS));
             let tc = TestConsensus::new(&config);
             tc.init();
             let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
-            let utxoindex = Some(UtxoIndexProxy::new(UtxoIndex::new(consensus_manager, utxoindex_db).unwrap()));
-            let processor = Arc::new(Processor::new(utxoindex, consensus_receiver));
+            let utxoindex: DynIndex = Arc::new(UtxoIndexProxy::new(UtxoIndex::new(consensus_manager, utxoindex_db).unwrap()));
+            let indexes = if failing_index { vec![Arc::new(FailingIndex) as DynIndex, utxoindex] } else { vec![utxoindex] };
+            let processor = Arc::new(Processor::new(indexes, consensus_receiver));
             let (processor_sender, processor_receiver) = unbounded();
             let notifier = Arc::new(NotifyMock::new(processor_sender));
             processor.clone().start(notifier);
@@ -230,6 +299,44 @@ mod tests {
         pipeline.processor.clone().join().await.expect("stopping the processor must succeed");
     }
 
+    #[tokio::test]
+    async fn test_failing_index_isolation() {
+        let pipeline = NotifyPipeline::with_failing_index(true);
+        let rng = &mut SmallRng::seed_from_u64(42);
+
+        let mut to_add_collection = UtxoCollection::new();
+        to_add_collection.insert(generate_random_outpoint(rng), generate_random_utxo(rng));
+        let test_notification = consensus_notification::UtxosChangedNotification::new(
+            Arc::new(UtxoDiff { add: to_add_collection, remove: UtxoCollection::new() }),
+            Arc::new(generate_random_hashes(rng, 2)),
+        );
+
+        // The failing index does not prevent the UTXO index from processing the notification
+        pipeline.consensus_sender.send(ConsensusNotification::UtxosChanged(test_notification)).await.expect("expected send");
+        match pipeline.processor_receiver.recv().await.expect("receives a notification") {
+            Notification::UtxosChanged(utxo_changed_notification) => {
+                assert_eq!(utxo_changed_notification.added.values().map(|x| x.len()).sum::<usize>(), 1);
+            }
+            unexpected_notification => panic!("Unexpected notification: {unexpected_notification:?}"),
+        }
+
+        // Neither does it prevent relaying a pruning point UTXO set override
+        pipeline
+            .consensus_sender
+            .send(ConsensusNotification::PruningPointUtxoSetOverride(
+                consensus_notification::PruningPointUtxoSetOverrideNotification {},
+            ))
+            .await
+            .expect("expected send");
+        match pipeline.processor_receiver.recv().await.expect("expected recv") {
+            Notification::PruningPointUtxoSetOverride(_) => (),
+            unexpected_notification => panic!("Unexpected notification: {unexpected_notification:?}"),
+        }
+        assert!(pipeline.processor_receiver.is_empty(), "the notification receiver should be empty");
+        pipeline.consensus_sender.close();
+        pipeline.processor.clone().join().await.expect("stopping the processor must succeed");
+    }
+
     #[tokio::test]
     async fn test_pruning_point_utxo_set_override_notification() {
         let pipeline = NotifyPipeline::new();
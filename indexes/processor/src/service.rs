@@ -1,4 +1,4 @@
-use crate::{processor::Processor, IDENT};
+use crate::{index::DynIndex, processor::Processor, IDENT};
 use kaspa_consensus_notify::{
     connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification, notifier::ConsensusNotifier,
 };
@@ -11,7 +11,7 @@ use kaspa_notify::{
     connection::ChannelType,
     events::{EventSwitches, EventType},
     listener::ListenerLifespan,
-    scope::{PruningPointUtxoSetOverrideScope, UtxosChangedScope},
+    scope::{PruningPointUtxoSetOverrideScope, UtxosChangedScope, VirtualChainChangedScope},
     subscription::{context::SubscriptionContext, MutationPolicies, UtxosChangedMutationPolicy},
 };
 use kaspa_utils::{channel::Channel, triggers::SingleTrigger};
@@ -22,6 +22,8 @@ const INDEX_SERVICE: &str = IDENT;
 
 pub struct IndexService {
     utxoindex: Option<UtxoIndexProxy>,
+    /// All registered indexes, including the UTXO index if any
+    indexes: Vec<DynIndex>,
     notifier: Arc<IndexNotifier>,
     shutdown: SingleTrigger,
 }
@@ -32,6 +34,18 @@ impl IndexService {
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
     ) -> Self {
+        Self::with_indexes(consensus_notifier, subscription_context, utxoindex, vec![])
+    }
+
+    /// Creates the service with `indexes` registered after the optional UTXO index
+    pub fn with_indexes(
+        consensus_notifier: &Arc<ConsensusNotifier>,
+        subscription_context: SubscriptionContext,
+        utxoindex: Option<UtxoIndexProxy>,
+        indexes: Vec<DynIndex>,
+    ) -> Self {
+        let indexes = utxoindex.clone().map(|x| Arc::new(x) as DynIndex).into_iter().chain(indexes).collect::<Vec<_>>();
+
         // This notifier UTXOs subscription granularity to consensus notifier
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::Wildcard);
 
@@ -45,7 +59,7 @@ impl IndexService {
         // Prepare the index-processor notifier
         // No subscriber is defined here because the subscription are manually created during the construction and never changed after that.
         let events: EventSwitches = [EventType::UtxosChanged, EventType::PruningPointUtxoSetOverride].as_ref().into();
-        let collector = Arc::new(Processor::new(utxoindex.clone(), consensus_notify_channel.receiver()));
+        let collector = Arc::new(Processor::new(indexes.clone(), consensus_notify_channel.receiver()));
         let notifier = Arc::new(IndexNotifier::new(INDEX_SERVICE, events, vec![collector], vec![], subscription_context, 1, policies));
 
         // Manually subscribe to index-processor related event types
@@ -55,8 +69,13 @@ impl IndexService {
         consensus_notifier
             .try_start_notify(consensus_notify_listener_id, PruningPointUtxoSetOverrideScope::default().into())
             .expect("the subscription always succeeds");
+        if indexes.iter().any(|index| index.tracks_virtual_chain()) {
+            consensus_notifier
+                .try_start_notify(consensus_notify_listener_id, VirtualChainChangedScope::new(true).into())
+                .expect("the subscription always succeeds");
+        }
 
-        Self { utxoindex, notifier, shutdown: SingleTrigger::default() }
+        Self { utxoindex, indexes, notifier, shutdown: SingleTrigger::default() }
     }
 
     pub fn notifier(&self) -> Arc<IndexNotifier> {
@@ -66,6 +85,27 @@ impl IndexService {
     pub fn utxoindex(&self) -> Option<UtxoIndexProxy> {
         self.utxoindex.clone()
     }
+
+    pub fn indexes(&self) -> &[DynIndex] {
+        &self.indexes
+    }
+
+    /// Resyncs every registered index found out of sync with consensus. A failing index is
+    /// reported and skipped so that it does not prevent the other indexes from resyncing.
+    ///
+    /// Note: resyncing while consensus notifies of UTXO changes may corrupt the indexes
+    pub async fn resync_indexes(&self) {
+        for index in self.indexes.iter() {
+            let result = match index.is_synced().await {
+                Ok(true) => Ok(()),
+                Ok(false) => index.resync().await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = result {
+                warn!("{}: failed resyncing {}: {}", INDEX_SERVICE, index.ident(), err);
+            }
+        }
+    }
 }
 
 impl AsyncService for IndexService {
@@ -84,4 +84,12 @@ impl UtxoIndexProxy {
     pub async fn update(self, utxo_diff: Arc<UtxoDiff>, tips: Arc<Vec<Hash>>) -> UtxoIndexResult<UtxoChanges> {
         spawn_blocking(move || self.inner.write().update(utxo_diff, tips)).await.unwrap()
     }
+
+    pub async fn is_synced(self) -> UtxoIndexResult<bool> {
+        spawn_blocking(move || self.inner.read().is_synced()).await.unwrap()
+    }
+
+    pub async fn resync(self) -> UtxoIndexResult<()> {
+        spawn_blocking(move || self.inner.write().resync()).await.unwrap()
+    }
 }
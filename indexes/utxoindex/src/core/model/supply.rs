@@ -1,4 +1,20 @@
+use kaspa_consensus_core::{
+    hashing::sighash::SigHashReusedValuesUnsync,
+    tx::{PopulatedTransaction, UtxoEntry},
+};
+use kaspa_txscript::is_unspendable;
+
 /// Type for circulating supply
 pub type CirculatingSupply = u64;
 /// Type for circulating supply difference
 pub type CirculatingSupplyDiff = i64; // As i64 since circulating supply diff can go negative.
+
+/// Returns the amount `utxo_entry` contributes to the circulating supply. Outputs locked by a provably
+/// unspendable script are burned and do not count towards it
+pub fn circulating_amount(utxo_entry: &UtxoEntry) -> CirculatingSupply {
+    if is_unspendable::<PopulatedTransaction, SigHashReusedValuesUnsync>(utxo_entry.script_public_key.script()) {
+        0
+    } else {
+        utxo_entry.amount
+    }
+}
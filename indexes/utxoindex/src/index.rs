@@ -214,7 +214,12 @@ impl ConsensusResetHandler for UtxoIndexConsensusResetHandler {
 
 #[cfg(test)]
 mod tests {
-    use crate::{api::UtxoIndexApi, model::CirculatingSupply, testutils::virtual_change_emulator::VirtualChangeEmulator, UtxoIndex};
+    use crate::{
+        api::UtxoIndexApi,
+        model::{circulating_amount, CirculatingSupply},
+        testutils::virtual_change_emulator::VirtualChangeEmulator,
+        UtxoIndex,
+    };
     use kaspa_consensus::{
         config::Config,
         consensus::test_consensus::TestConsensus,
@@ -282,7 +287,7 @@ mod tests {
         let mut consensus_supply: CirculatingSupply = 0;
         let consensus_utxo_set_size = consensus_utxos.len();
         for (tx_outpoint, utxo_entry) in consensus_utxos.into_iter() {
-            consensus_supply += utxo_entry.amount;
+            consensus_supply += circulating_amount(&utxo_entry);
             let indexed_utxos = utxoindex
                 .read()
                 .get_utxos_by_script_public_keys(HashSet::from_iter(vec![utxo_entry.script_public_key.clone()]))
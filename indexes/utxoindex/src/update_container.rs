@@ -6,7 +6,7 @@ use kaspa_consensus_core::{
 use kaspa_hashes::Hash;
 use kaspa_utils::hashmap::NestedHashMapExtensions;
 
-use crate::model::{CirculatingSupplyDiff, CompactUtxoEntry, UtxoChanges, UtxoSetByScriptPublicKey};
+use crate::model::{circulating_amount, CirculatingSupplyDiff, CompactUtxoEntry, UtxoChanges, UtxoSetByScriptPublicKey};
 
 /// A struct holding all changes to the utxoindex with on-the-fly conversions and processing.
 pub struct UtxoIndexChanges {
@@ -33,7 +33,7 @@ impl UtxoIndexChanges {
             if to_remove.remove(&transaction_outpoint).is_some() {
                 continue;
             }; // We try and remove from `utxo_diff.remove`, if we do, discard utxo.
-            self.supply_change += utxo_entry.amount as CirculatingSupplyDiff; // TODO: Using `virtual_state.mergeset_rewards` might be a better way to extract this.
+            self.supply_change += circulating_amount(&utxo_entry) as CirculatingSupplyDiff; // TODO: Using `virtual_state.mergeset_rewards` might be a better way to extract this.
 
             self.utxo_changes.added.insert_into_nested(
                 utxo_entry.script_public_key,
@@ -43,7 +43,7 @@ impl UtxoIndexChanges {
         }
 
         for (transaction_outpoint, utxo_entry) in to_remove.into_iter() {
-            self.supply_change -= utxo_entry.amount as CirculatingSupplyDiff; // TODO: Using `virtual_state.mergeset_rewards` might be a better way to extract this.
+            self.supply_change -= circulating_amount(&utxo_entry) as CirculatingSupplyDiff; // TODO: Using `virtual_state.mergeset_rewards` might be a better way to extract this.
 
             self.utxo_changes.removed.insert_into_nested(
                 utxo_entry.script_public_key,
@@ -58,7 +58,7 @@ impl UtxoIndexChanges {
     /// Note: This is meant to be used when resyncing.
     pub fn add_utxos_from_vector(&mut self, utxo_vector: Vec<(TransactionOutpoint, UtxoEntry)>) {
         for (transaction_outpoint, utxo_entry) in utxo_vector.into_iter() {
-            self.supply_change += utxo_entry.amount as CirculatingSupplyDiff;
+            self.supply_change += circulating_amount(&utxo_entry) as CirculatingSupplyDiff;
 
             self.utxo_changes.added.insert_into_nested(
                 utxo_entry.script_public_key,
@@ -1,10 +1,17 @@
 use clap::{arg, Arg, ArgAction, Command};
+use kaspa_addresses::Address;
 use kaspa_consensus_core::{
-    config::Config,
+    config::{
+        constants::perf::{CacheBudgetOverrides, CacheSizePolicy, CACHE_SIZE_POLICIES},
+        params::{ForkActivation, Params, ParamsPreset, PARAMS_PRESETS},
+        Config,
+    },
+    errors::config::{ConfigError, ConfigResult},
     network::{NetworkId, NetworkType},
 };
 use kaspa_core::kaspad_env::version;
 use kaspa_notify::address::tracker::Tracker;
+use kaspa_rpc_core::api::access::RpcAccessConfig;
 use kaspa_utils::networking::ContextualNetAddress;
 use kaspa_wrpc_server::address::WrpcNetAddress;
 use serde::Deserialize;
@@ -12,8 +19,6 @@ use serde_with::{serde_as, DisplayFromStr};
 use std::{ffi::OsString, fs};
 use toml::from_str;
 
-#[cfg(feature = "devnet-prealloc")]
-use kaspa_addresses::Address;
 #[cfg(feature = "devnet-prealloc")]
 use kaspa_consensus_core::tx::{TransactionOutpoint, UtxoEntry};
 #[cfg(feature = "devnet-prealloc")]
@@ -39,6 +44,14 @@ pub struct Args {
     #[serde(rename = "unsaferpc")]
     pub unsafe_rpc: bool,
     pub wrpc_verbose: bool,
+    pub rpc_whitelist: Vec<String>,
+    pub rpc_rate_limits: Vec<String>,
+    pub rpc_max_concurrent_requests: Option<usize>,
+    pub wrpc_max_connections: Option<usize>,
+    pub wrpc_max_connections_per_ip: Option<usize>,
+    pub wrpc_keepalive_interval: u64,
+    pub wrpc_idle_timeout: u64,
+    pub wrpc_write_timeout: u64,
     #[serde(rename = "loglevel")]
     pub log_level: String,
     pub async_threads: usize,
@@ -48,14 +61,24 @@ pub struct Args {
     #[serde(rename = "addpeer")]
     #[serde_as(as = "Vec<DisplayFromStr>")]
     pub add_peers: Vec<ContextualNetAddress>,
+    #[serde(rename = "anchorpeer")]
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub anchor_peers: Vec<ContextualNetAddress>,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub listen: Option<ContextualNetAddress>,
+    pub proxy: Option<String>,
     #[serde(rename = "uacomment")]
     pub user_agent_comments: Vec<String>,
     pub utxoindex: bool,
+    pub acceptanceindex: bool,
     pub reset_db: bool,
+    pub migrate_dry_run: bool,
+    pub migrate_backup_dir: Option<String>,
+    pub print_emission: bool,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
+    #[serde(rename = "minoutgroups")]
+    pub min_outbound_groups: usize,
     #[serde(rename = "maxinpeers")]
     pub inbound_limit: usize,
     #[serde(rename = "rpcmaxclients")]
@@ -76,6 +99,11 @@ pub struct Args {
     pub perf_metrics: bool,
     pub perf_metrics_interval_sec: u64,
     pub block_template_cache_lifetime: Option<u64>,
+    #[serde(rename = "miningaddr")]
+    pub mining_address: Option<String>,
+    pub coinbase_extra_data: Option<String>,
+    pub accept_non_native_subnetworks: bool,
+    pub max_standard_payload_len: usize,
 
     #[cfg(feature = "devnet-prealloc")]
     pub num_prealloc_utxos: Option<u64>,
@@ -90,6 +118,57 @@ pub struct Args {
     #[serde(rename = "nogrpc")]
     pub disable_grpc: bool,
     pub ram_scale: f64,
+    /// Resident memory budget of the node in megabytes, past which the caches are shrunk
+    pub memory_budget: Option<u64>,
+    /// Sizing policy of the consensus caches (small, default, large or custom)
+    pub cache_policy: Option<String>,
+    /// Overall memory budget of the consensus caches in megabytes (custom cache policy only)
+    pub cache_total_mb: Option<u64>,
+    /// Memory budget of the GHOSTDAG data caches in megabytes (custom cache policy only)
+    pub cache_ghostdag_mb: Option<u64>,
+    /// Memory budget of the block relations caches in megabytes (custom cache policy only)
+    pub cache_relations_mb: Option<u64>,
+    /// Memory budget of the block headers cache in megabytes (custom cache policy only)
+    pub cache_headers_mb: Option<u64>,
+    pub disable_db_maintenance: bool,
+    pub db_compaction_interval: u64,
+
+    pub preset: Option<String>,
+    pub override_bps: Option<u64>,
+    pub override_max_block_parents: Option<u8>,
+    pub override_mergeset_size_limit: Option<u64>,
+    pub override_sighash_fork_id: Option<u8>,
+    pub override_sighash_fork_activation: Option<u64>,
+    pub override_pruning_finality_windows: Option<u64>,
+    pub override_payload_activation: Option<u64>,
+
+    #[serde(rename = "p2ptrace")]
+    pub p2p_trace_file: Option<String>,
+    /// Handshake version/verack stage timeout in seconds (the ready stage is allowed twice as long)
+    pub handshake_timeout: Option<u64>,
+    /// Timeout in seconds for P2P flow responses, e.g., the round-trip of an IBD headers request
+    pub p2p_response_timeout: Option<u64>,
+    /// Overall P2P upload rate limit in megabits per second
+    pub max_upload_mbps: Option<f64>,
+    /// P2P upload rate limit to each single peer in megabits per second
+    pub max_peer_upload_mbps: Option<f64>,
+    /// Export the pruning point UTXO set snapshot to this file and exit
+    pub export_snapshot: Option<String>,
+    /// Bootstrap a fresh node from the UTXO set snapshot in this file
+    pub import_snapshot: Option<String>,
+    /// Assume-valid checkpoint (`<block hash>:<DAA score>`). No network ships a default checkpoint, so script
+    /// validation is skipped during sync only when this is set
+    pub assume_valid: Option<String>,
+    /// Fully validate all blocks during sync, ignoring any assume-valid checkpoint
+    pub no_assume_valid: bool,
+    /// Expiry window in seconds of relayed mempool transactions
+    pub mempool_expiry: Option<u64>,
+    /// Expiry window in seconds of locally submitted mempool transactions (zero means these never expire)
+    pub mempool_local_expiry: Option<u64>,
+    /// wRPC (Borsh) URL of a reference node to periodically cross-check the consensus state against
+    pub divergence_reference: Option<String>,
+    /// Interval in seconds between cross-checks against the reference node
+    pub divergence_check_interval: u64,
 }
 
 impl Default for Args {
@@ -102,8 +181,13 @@ impl Default for Args {
             unsafe_rpc: false,
             async_threads: num_cpus::get(),
             utxoindex: false,
+            acceptanceindex: false,
             reset_db: false,
+            migrate_dry_run: false,
+            print_emission: false,
+            migrate_backup_dir: None,
             outbound_target: 8,
+            min_outbound_groups: 4,
             inbound_limit: 128,
             rpc_max_clients: 128,
             max_tracked_addresses: 0,
@@ -118,16 +202,30 @@ impl Default for Args {
             logdir: None,
             rpclisten: None,
             wrpc_verbose: false,
+            rpc_whitelist: vec![],
+            rpc_rate_limits: vec![],
+            rpc_max_concurrent_requests: None,
+            wrpc_max_connections: None,
+            wrpc_max_connections_per_ip: None,
+            wrpc_keepalive_interval: 30,
+            wrpc_idle_timeout: 0,
+            wrpc_write_timeout: 60,
             log_level: "INFO".into(),
             connect_peers: vec![],
             add_peers: vec![],
+            anchor_peers: vec![],
             listen: None,
+            proxy: None,
             user_agent_comments: vec![],
             yes: false,
             perf_metrics: false,
             perf_metrics_interval_sec: 10,
             externalip: None,
             block_template_cache_lifetime: None,
+            mining_address: None,
+            coinbase_extra_data: None,
+            accept_non_native_subnetworks: false,
+            max_standard_payload_len: 0,
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: None,
@@ -140,6 +238,37 @@ impl Default for Args {
             disable_dns_seeding: false,
             disable_grpc: false,
             ram_scale: 1.0,
+            memory_budget: None,
+            cache_policy: None,
+            cache_total_mb: None,
+            cache_ghostdag_mb: None,
+            cache_relations_mb: None,
+            cache_headers_mb: None,
+            disable_db_maintenance: false,
+            db_compaction_interval: 24,
+
+            preset: None,
+            override_bps: None,
+            override_max_block_parents: None,
+            override_mergeset_size_limit: None,
+            override_sighash_fork_id: None,
+            override_sighash_fork_activation: None,
+            override_pruning_finality_windows: None,
+            override_payload_activation: None,
+
+            p2p_trace_file: None,
+            handshake_timeout: None,
+            p2p_response_timeout: None,
+            max_upload_mbps: None,
+            max_peer_upload_mbps: None,
+            export_snapshot: None,
+            import_snapshot: None,
+            assume_valid: None,
+            no_assume_valid: false,
+            mempool_expiry: None,
+            mempool_local_expiry: None,
+            divergence_reference: None,
+            divergence_check_interval: 60,
         }
     }
 }
@@ -156,9 +285,16 @@ impl Args {
         config.enable_sanity_checks = true;
         config.user_agent_comments.clone_from(&self.user_agent_comments);
         config.block_template_cache_lifetime = self.block_template_cache_lifetime;
+        // The mining address is validated against the network via `validate_args`
+        config.mining_address = self.mining_address.as_deref().map(|address| Address::try_from(address).unwrap());
+        config.coinbase_extra_data = self.coinbase_extra_data.as_ref().map(|data| data.as_bytes().to_vec()).unwrap_or_default();
         config.p2p_listen_address = self.listen.unwrap_or(ContextualNetAddress::unspecified());
         config.externalip = self.externalip.map(|v| v.normalize(config.default_p2p_port()));
         config.ram_scale = self.ram_scale;
+        // The cache policy is validated via `validate_args`
+        if let Some(policy) = self.cache_policy.as_deref().and_then(CacheSizePolicy::from_name) {
+            config.perf.apply_cache_size_policy(policy, self.cache_budget_overrides());
+        }
 
         #[cfg(feature = "devnet-prealloc")]
         if let Some(num_prealloc_utxos) = self.num_prealloc_utxos {
@@ -166,6 +302,99 @@ impl Args {
         }
     }
 
+    /// Indicates whether any of the (non-mainnet) consensus param overrides was set
+    /// Builds the access policy configuration of the wRPC endpoints, or `None` if they are unrestricted
+    pub fn rpc_access_config(&self) -> ConfigResult<Option<RpcAccessConfig>> {
+        let mut config = RpcAccessConfig::default();
+        for entry in self.rpc_whitelist.iter() {
+            if !config.add_whitelist_entry(entry) {
+                return Err(ConfigError::InvalidRpcWhitelistEntry(entry.clone()));
+            }
+        }
+        for entry in self.rpc_rate_limits.iter() {
+            if !config.add_rate_limit(entry) {
+                return Err(ConfigError::InvalidRpcRateLimit(entry.clone()));
+            }
+        }
+        if self.rpc_max_concurrent_requests == Some(0) {
+            return Err(ConfigError::RpcMaxConcurrentRequestsTooLow);
+        }
+        config.max_concurrent_requests = self.rpc_max_concurrent_requests;
+        Ok((!config.is_unrestricted()).then_some(config))
+    }
+
+    /// The per-store cache budgets in bytes
+    pub fn cache_budget_overrides(&self) -> CacheBudgetOverrides {
+        let bytes = |mb: Option<u64>| mb.map(|mb| mb as usize * 1_000_000);
+        CacheBudgetOverrides {
+            total: bytes(self.cache_total_mb),
+            ghostdag: bytes(self.cache_ghostdag_mb),
+            relations: bytes(self.cache_relations_mb),
+            headers: bytes(self.cache_headers_mb),
+        }
+    }
+
+    pub fn has_consensus_overrides(&self) -> bool {
+        self.preset.is_some()
+            || self.override_bps.is_some()
+            || self.override_max_block_parents.is_some()
+            || self.override_mergeset_size_limit.is_some()
+            || self.override_sighash_fork_id.is_some()
+            || self.override_sighash_fork_activation.is_some()
+            || self.override_pruning_finality_windows.is_some()
+            || self.override_payload_activation.is_some()
+    }
+
+    /// Applies the consensus param overrides along with the assume-valid checkpoint, failing on the first invalid value.
+    /// The preset is applied first, followed by BPS which resets all BPS-dependent params, including max block parents,
+    /// mergeset size limit and pruning depth which can then be overridden explicitly.
+    pub fn apply_consensus_overrides(&self, params: &mut Params) -> ConfigResult<()> {
+        if self.has_consensus_overrides() && params.net.is_mainnet() {
+            return Err(ConfigError::ConsensusOverridesOnMainnet);
+        }
+        if let Some(name) = self.preset.as_deref() {
+            let preset = ParamsPreset::from_name(name).ok_or_else(|| ConfigError::UnknownPreset(name.to_owned()))?;
+            if preset.fast_test && params.net.network_type != NetworkType::Simnet {
+                return Err(ConfigError::PresetRequiresSimnet(name.to_owned()));
+            }
+            preset.apply(params);
+        }
+        if let Some(bps) = self.override_bps {
+            if !params.set_bps(bps) {
+                return Err(ConfigError::UnsupportedOverrideBps(bps));
+            }
+        }
+        if let Some(max_block_parents) = self.override_max_block_parents {
+            params.max_block_parents = max_block_parents;
+        }
+        if let Some(mergeset_size_limit) = self.override_mergeset_size_limit {
+            params.mergeset_size_limit = mergeset_size_limit;
+        }
+        params.validate_dag_width()?;
+        if let Some(sighash_fork_id) = self.override_sighash_fork_id {
+            params.sighash_fork_id = Some(sighash_fork_id);
+            params.sighash_fork_activation = ForkActivation::always();
+        }
+        if let Some(sighash_fork_activation) = self.override_sighash_fork_activation {
+            params.sighash_fork_activation = ForkActivation::new(sighash_fork_activation);
+        }
+        if let Some(finality_windows) = self.override_pruning_finality_windows {
+            params.set_pruning_depth_in_finality_windows(finality_windows)?;
+        }
+        if let Some(payload_activation) = self.override_payload_activation {
+            params.payload_activation = ForkActivation::new(payload_activation);
+        }
+        if let Some(assume_valid) = self.assume_valid.as_deref() {
+            if self.no_assume_valid {
+                return Err(ConfigError::MixedAssumeValidAndNoAssumeValid);
+            }
+            params.assume_valid = Some(assume_valid.parse()?);
+        } else if self.no_assume_valid {
+            params.assume_valid = None;
+        }
+        Ok(())
+    }
+
     #[cfg(feature = "devnet-prealloc")]
     pub fn generate_prealloc_utxos(&self, num_prealloc_utxos: u64) -> kaspa_consensus_core::utxo::utxo_collection::UtxoCollection {
         let addr = Address::try_from(&self.prealloc_address.as_ref().unwrap()[..]).unwrap();
@@ -251,6 +480,71 @@ pub fn cli() -> Command {
                 .help("Interface:port to listen for wRPC JSON connections (default port: 18110, testnet: 18210)."),
         )
         .arg(arg!(--unsaferpc "Enable RPC commands which affect the state of the node"))
+        .arg(
+            Arg::new("rpc-whitelist")
+                .long("rpc-whitelist")
+                .value_name("METHODS")
+                .action(ArgAction::Append)
+                .value_delimiter(',')
+                .require_equals(true)
+                .help("Serve only the given methods on the wRPC endpoints, where the `public` profile stands for all read-only methods which reveal nothing about the node operator (e.g. --rpc-whitelist=public,submitTransaction)."),
+        )
+        .arg(
+            Arg::new("rpc-rate-limit")
+                .long("rpc-rate-limit")
+                .value_name("METHOD:RATE")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .help("Max number of calls per second of a method by a single wRPC connection, where the method `*` sets the limit of all methods with no specific one."),
+        )
+        .arg(
+            Arg::new("rpc-max-concurrent-requests")
+                .long("rpc-max-concurrent-requests")
+                .value_name("COUNT")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Max number of requests processed concurrently over all wRPC connections, beyond which requests are rejected."),
+        )
+        .arg(
+            Arg::new("wrpc-max-connections")
+                .long("wrpc-max-connections")
+                .value_name("COUNT")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Max number of simultaneous connections to each wRPC endpoint (default: unlimited)."),
+        )
+        .arg(
+            Arg::new("wrpc-max-connections-per-ip")
+                .long("wrpc-max-connections-per-ip")
+                .value_name("COUNT")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Max number of simultaneous connections from a single IP address to each wRPC endpoint (default: unlimited)."),
+        )
+        .arg(
+            Arg::new("wrpc-keepalive-interval")
+                .long("wrpc-keepalive-interval")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Interval of the pings sent to wRPC connections, letting dead peers be detected (default: 30, 0 disables)."),
+        )
+        .arg(
+            Arg::new("wrpc-idle-timeout")
+                .long("wrpc-idle-timeout")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Close wRPC connections subscribed to no notifications and issuing no request for the given time (default: 0, i.e., never)."),
+        )
+        .arg(
+            Arg::new("wrpc-write-timeout")
+                .long("wrpc-write-timeout")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Drop wRPC connections whose written data remains unacknowledged for the given time, Linux only (default: 60, 0 uses the system default)."),
+        )
         .arg(
             Arg::new("connect-peers")
                 .long("connect")
@@ -269,13 +563,29 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(ContextualNetAddress))
                 .help("Add peers to connect with at startup."),
         )
+        .arg(
+            Arg::new("anchor-peers")
+                .long("anchorpeer")
+                .value_name("IP[:PORT]")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Pin an anchor peer, which is always reconnected first and kept connected as an outbound peer."),
+        )
         .arg(
             Arg::new("listen")
                 .long("listen")
                 .value_name("IP[:PORT]")
                 .require_equals(true)
                 .value_parser(clap::value_parser!(ContextualNetAddress))
-                .help("Add an interface:port to listen for connections (default all interfaces port: 16111, testnet: 16211)."),
+                .help("Add an interface:port to listen for connections (default all IPv6 and IPv4 interfaces port: 16111, testnet: 16211). An unspecified IP listens on both families."),
+        )
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .value_name("[USER:PASS@]HOST:PORT")
+                .require_equals(true)
+                .help("Connect to outbound P2P peers through a SOCKS5 proxy (e.g. 127.0.0.1:9050 for Tor). Disables DNS seeding."),
         )
         .arg(
             Arg::new("outpeers")
@@ -285,6 +595,14 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(usize))
                 .help("Target number of outbound peers (default: 8)."),
         )
+        .arg(
+            Arg::new("minoutgroups")
+                .long("minoutgroups")
+                .value_name("minoutgroups")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Minimal number of distinct network groups (/16 IPv4 or /64 IPv6 prefixes) of outbound peers, capped by --outpeers (default: 4)."),
+        )
         .arg(
             Arg::new("maxinpeers")
                 .long("maxinpeers")
@@ -302,6 +620,16 @@ pub fn cli() -> Command {
                 .help("Max number of RPC clients for standard connections (default: 128)."),
         )
         .arg(arg!(--"reset-db" "Reset database before starting node. It's needed when switching between subnetworks."))
+        .arg(arg!(--"migrate-dry-run" "Print the pending database schema migrations and exit without applying them."))
+        .arg(arg!(--"print-emission" "Print the block subsidy emission schedule of the selected network and exit."))
+        .arg(
+            Arg::new("migrate-backup-dir")
+                .long("migrate-backup-dir")
+                .value_name("DIR")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Create a checkpoint of each database in DIR before applying pending schema migrations."),
+        )
         .arg(arg!(--"enable-unsynced-mining" "Allow the node to accept blocks from RPC while not synced (this flag is mainly used for testing)"))
         .arg(
             Arg::new("enable-mainnet-mining")
@@ -311,6 +639,7 @@ pub fn cli() -> Command {
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
         .arg(arg!(--utxoindex "Enable the UTXO index"))
+        .arg(arg!(--acceptanceindex "Enable the transaction acceptance index"))
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -361,6 +690,15 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
         .arg(arg!(--"disable-upnp" "Disable upnp"))
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers"))
         .arg(arg!(--"nogrpc" "Disable gRPC server"))
+        .arg(arg!(--"disable-db-maintenance" "Disable flushing and compacting the database during quiet periods of block processing"))
+        .arg(
+            Arg::new("db-compaction-interval")
+                .long("db-compaction-interval")
+                .value_name("HOURS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Min interval in hours between manual database compactions performed during quiet periods (default: 24). Set to 0 to disable compactions."),
+        )
         .arg(
             Arg::new("ram-scale")
                 .long("ram-scale")
@@ -369,6 +707,241 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .help("Apply a scale factor to memory allocation bounds. Nodes with limited RAM (~4-8GB) should set this to ~0.3-0.5 respectively. Nodes with
 a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance especially for syncing peers faster"),
         )
+        .arg(
+            Arg::new("memory-budget")
+                .long("memory-budget")
+                .value_name("MB")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Resident memory budget of the node in megabytes. Once exceeded, the block window, block relations and mempool caches are shrunk proportionally, preventing out-of-memory kills on small machines (e.g., set to ~3000 on a 4GB machine)."),
+        )
+        .arg(
+            Arg::new("cache-policy")
+                .long("cache-policy")
+                .value_name("POLICY")
+                .require_equals(true)
+                .value_parser(CACHE_SIZE_POLICIES.iter().map(|policy| policy.name()).collect::<Vec<_>>())
+                .help("Sizing policy of the consensus caches: small (half the default sizes), default, large (twice the default sizes) or custom (default sizes with the --cache-*-mb budgets). Applied on top of --ram-scale."),
+        )
+        .arg(
+            Arg::new("cache-total-mb")
+                .long("cache-total-mb")
+                .value_name("MB")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Overall memory budget of the consensus caches in megabytes, split across the stores in fixed proportions and scaled by --ram-scale (requires --cache-policy=custom)."),
+        )
+        .arg(
+            Arg::new("cache-ghostdag-mb")
+                .long("cache-ghostdag-mb")
+                .value_name("MB")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Memory budget of the GHOSTDAG data caches in megabytes (requires --cache-policy=custom)."),
+        )
+        .arg(
+            Arg::new("cache-relations-mb")
+                .long("cache-relations-mb")
+                .value_name("MB")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Memory budget of the block relations caches in megabytes (requires --cache-policy=custom)."),
+        )
+        .arg(
+            Arg::new("cache-headers-mb")
+                .long("cache-headers-mb")
+                .value_name("MB")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Memory budget of the block headers cache in megabytes (requires --cache-policy=custom)."),
+        )
+        .arg(
+            Arg::new("preset")
+                .long("preset")
+                .value_name("PRESET")
+                .require_equals(true)
+                .value_parser(PARAMS_PRESETS.iter().map(|preset| preset.name).collect::<Vec<_>>())
+                .help("Apply a named consensus params preset, e.g., a TN11-like 10 BPS network, or a 1 BPS network with tiny maturity, windows and depths for fast tests (simnet only). Perf params (cache sizes etc.) are tuned accordingly (non-mainnet only)."),
+        )
+        .arg(
+            Arg::new("override-bps")
+                .long("override-bps")
+                .value_name("BPS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Override the network blocks per second along with all BPS-dependent consensus params (non-mainnet only)."),
+        )
+        .arg(
+            Arg::new("override-max-block-parents")
+                .long("override-max-block-parents")
+                .value_name("MAX_BLOCK_PARENTS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u8))
+                .help("Override the max number of direct block parents (non-mainnet only)."),
+        )
+        .arg(
+            Arg::new("override-mergeset-size-limit")
+                .long("override-mergeset-size-limit")
+                .value_name("MERGESET_SIZE_LIMIT")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Override the mergeset size limit (non-mainnet only)."),
+        )
+        .arg(
+            Arg::new("override-sighash-fork-id")
+                .long("override-sighash-fork-id")
+                .value_name("FORK_ID")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u8))
+                .help("Fold a fork id byte into signature hashes, providing replay protection against the parent network (non-mainnet only)."),
+        )
+        .arg(
+            Arg::new("override-sighash-fork-activation")
+                .long("override-sighash-fork-activation")
+                .value_name("DAA_SCORE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Fold the sighash fork id into signature hashes only from the given DAA score on, keeping transactions signed before the fork valid. Defaults to activating from genesis (non-mainnet only)."),
+        )
+        .arg(
+            Arg::new("override-pruning-finality-windows")
+                .long("override-pruning-finality-windows")
+                .value_name("FINALITY_WINDOWS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Override the pruning depth to keep the given number of finality windows of history, which cannot be below the minimal safe depth. Useful for explorer devnets which want a longer history without archival storage. This is a consensus param, so all nodes of the network must use the same value (non-mainnet only)."),
+        )
+        .arg(
+            Arg::new("override-payload-activation")
+                .long("override-payload-activation")
+                .value_name("DAA_SCORE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Allow native non-coinbase transactions to carry a payload from the given DAA score on (non-mainnet only)."),
+        )
+        .arg(
+            Arg::new("miningaddr")
+                .long("miningaddr")
+                .value_name("ADDRESS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Default payout address for block templates requested without a pay address."),
+        )
+        .arg(
+            Arg::new("coinbase-extra-data")
+                .long("coinbase-extra-data")
+                .value_name("DATA")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Extra data (e.g., a pool identifier) to append to the coinbase payload of all block templates."),
+        )
+        .arg(
+            Arg::new("max-standard-payload-len")
+                .long("max-standard-payload-len")
+                .value_name("BYTES")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Max payload length of transactions accepted to the mempool and relayed (default: 0, i.e., no payloads). Payloads are only valid once activated by consensus."),
+        )
+        .arg(arg!(--"accept-non-native-subnetworks" "Accept and relay mempool transactions of non-native subnetworks (these remain subject to the consensus subnetwork rules)"))
+        .arg(
+            Arg::new("p2ptrace")
+                .long("p2ptrace")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Record all incoming P2P messages of this session to FILE for later replay (debugging)."),
+        )
+        .arg(
+            Arg::new("handshake-timeout")
+                .long("handshake-timeout")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Timeout of each P2P handshake stage; the final ready stage is allowed twice as long (default: 4)."),
+        )
+        .arg(
+            Arg::new("p2p-response-timeout")
+                .long("p2p-response-timeout")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Timeout for P2P peers to respond to requests, e.g., IBD headers and blocks (default: 120)."),
+        )
+        .arg(
+            Arg::new("max-upload-mbps")
+                .long("max-upload-mbps")
+                .value_name("MBPS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Limit the overall P2P upload rate (in megabits per second). When saturated, block and transaction relay is prioritized over serving syncing peers."),
+        )
+        .arg(
+            Arg::new("max-peer-upload-mbps")
+                .long("max-peer-upload-mbps")
+                .value_name("MBPS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Limit the P2P upload rate to each single peer (in megabits per second)."),
+        )
+        .arg(
+            Arg::new("export-snapshot")
+                .long("export-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Export the pruning point UTXO set and its headers proof to FILE and exit (the node must not be running)."),
+        )
+        .arg(
+            Arg::new("import-snapshot")
+                .long("import-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .conflicts_with("export-snapshot")
+                .help("Bootstrap a fresh node from a UTXO set snapshot FILE instead of downloading the UTXO set from peers."),
+        )
+        .arg(
+            Arg::new("assume-valid")
+                .long("assume-valid")
+                .value_name("HASH:DAA_SCORE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Skip script validation of blocks in the selected chain past of this checkpoint during sync. No network ships a default checkpoint, so sync fully validates all blocks unless this is set."),
+        )
+        .arg(arg!(--"no-assume-valid" "Fully validate all blocks during sync, ignoring any assume-valid checkpoint."))
+        .arg(
+            Arg::new("mempool-expiry")
+                .long("mempool-expiry")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Evict relayed transactions which were not accepted within this period from the mempool (default: 86400)."),
+        )
+        .arg(
+            Arg::new("mempool-local-expiry")
+                .long("mempool-local-expiry")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Evict transactions submitted via RPC which were not accepted within this period from the mempool, 0 to never evict them (default: 604800)."),
+        )
+        .arg(
+            Arg::new("divergence-reference")
+                .long("divergence-reference")
+                .value_name("URL")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Periodically cross-check the selected tip, pruning point and UTXO commitment against the reference node at this wRPC (Borsh) URL, logging loud errors on divergence (debugging)."),
+        )
+        .arg(
+            Arg::new("divergence-check-interval")
+                .long("divergence-check-interval")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Interval in seconds between cross-checks against the --divergence-reference node (default: 60)."),
+        )
         ;
 
     #[cfg(feature = "devnet-prealloc")]
@@ -422,15 +995,22 @@ impl Args {
             async_threads: arg_match_unwrap_or::<usize>(&m, "async_threads", defaults.async_threads),
             connect_peers: arg_match_many_unwrap_or::<ContextualNetAddress>(&m, "connect-peers", defaults.connect_peers),
             add_peers: arg_match_many_unwrap_or::<ContextualNetAddress>(&m, "add-peers", defaults.add_peers),
+            anchor_peers: arg_match_many_unwrap_or::<ContextualNetAddress>(&m, "anchor-peers", defaults.anchor_peers),
             listen: m.get_one::<ContextualNetAddress>("listen").cloned().or(defaults.listen),
+            proxy: m.get_one::<String>("proxy").cloned().or(defaults.proxy),
             outbound_target: arg_match_unwrap_or::<usize>(&m, "outpeers", defaults.outbound_target),
+            min_outbound_groups: arg_match_unwrap_or::<usize>(&m, "minoutgroups", defaults.min_outbound_groups),
             inbound_limit: arg_match_unwrap_or::<usize>(&m, "maxinpeers", defaults.inbound_limit),
             rpc_max_clients: arg_match_unwrap_or::<usize>(&m, "rpcmaxclients", defaults.rpc_max_clients),
             max_tracked_addresses: arg_match_unwrap_or::<usize>(&m, "max-tracked-addresses", defaults.max_tracked_addresses),
             reset_db: arg_match_unwrap_or::<bool>(&m, "reset-db", defaults.reset_db),
+            migrate_dry_run: arg_match_unwrap_or::<bool>(&m, "migrate-dry-run", defaults.migrate_dry_run),
+            migrate_backup_dir: m.get_one::<String>("migrate-backup-dir").cloned().or(defaults.migrate_backup_dir),
+            print_emission: arg_match_unwrap_or::<bool>(&m, "print-emission", defaults.print_emission),
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            acceptanceindex: arg_match_unwrap_or::<bool>(&m, "acceptanceindex", defaults.acceptanceindex),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
@@ -439,6 +1019,20 @@ impl Args {
             sanity: arg_match_unwrap_or::<bool>(&m, "sanity", defaults.sanity),
             yes: arg_match_unwrap_or::<bool>(&m, "yes", defaults.yes),
             user_agent_comments: arg_match_many_unwrap_or::<String>(&m, "user_agent_comments", defaults.user_agent_comments),
+            rpc_whitelist: arg_match_many_unwrap_or::<String>(&m, "rpc-whitelist", defaults.rpc_whitelist),
+            rpc_rate_limits: arg_match_many_unwrap_or::<String>(&m, "rpc-rate-limit", defaults.rpc_rate_limits),
+            rpc_max_concurrent_requests: m
+                .get_one::<usize>("rpc-max-concurrent-requests")
+                .cloned()
+                .or(defaults.rpc_max_concurrent_requests),
+            wrpc_max_connections: m.get_one::<usize>("wrpc-max-connections").cloned().or(defaults.wrpc_max_connections),
+            wrpc_max_connections_per_ip: m
+                .get_one::<usize>("wrpc-max-connections-per-ip")
+                .cloned()
+                .or(defaults.wrpc_max_connections_per_ip),
+            wrpc_keepalive_interval: arg_match_unwrap_or::<u64>(&m, "wrpc-keepalive-interval", defaults.wrpc_keepalive_interval),
+            wrpc_idle_timeout: arg_match_unwrap_or::<u64>(&m, "wrpc-idle-timeout", defaults.wrpc_idle_timeout),
+            wrpc_write_timeout: arg_match_unwrap_or::<u64>(&m, "wrpc-write-timeout", defaults.wrpc_write_timeout),
             externalip: m.get_one::<ContextualNetAddress>("externalip").cloned(),
             perf_metrics: arg_match_unwrap_or::<bool>(&m, "perf-metrics", defaults.perf_metrics),
             perf_metrics_interval_sec: arg_match_unwrap_or::<u64>(&m, "perf-metrics-interval-sec", defaults.perf_metrics_interval_sec),
@@ -448,6 +1042,58 @@ impl Args {
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
+            memory_budget: m.get_one::<u64>("memory-budget").cloned().or(defaults.memory_budget),
+            cache_policy: m.get_one::<String>("cache-policy").cloned().or(defaults.cache_policy),
+            cache_total_mb: m.get_one::<u64>("cache-total-mb").cloned().or(defaults.cache_total_mb),
+            cache_ghostdag_mb: m.get_one::<u64>("cache-ghostdag-mb").cloned().or(defaults.cache_ghostdag_mb),
+            cache_relations_mb: m.get_one::<u64>("cache-relations-mb").cloned().or(defaults.cache_relations_mb),
+            cache_headers_mb: m.get_one::<u64>("cache-headers-mb").cloned().or(defaults.cache_headers_mb),
+            disable_db_maintenance: arg_match_unwrap_or::<bool>(&m, "disable-db-maintenance", defaults.disable_db_maintenance),
+            db_compaction_interval: arg_match_unwrap_or::<u64>(&m, "db-compaction-interval", defaults.db_compaction_interval),
+            preset: m.get_one::<String>("preset").cloned().or(defaults.preset),
+            override_bps: m.get_one::<u64>("override-bps").cloned().or(defaults.override_bps),
+            override_max_block_parents: m.get_one::<u8>("override-max-block-parents").cloned().or(defaults.override_max_block_parents),
+            override_mergeset_size_limit: m
+                .get_one::<u64>("override-mergeset-size-limit")
+                .cloned()
+                .or(defaults.override_mergeset_size_limit),
+            override_sighash_fork_id: m.get_one::<u8>("override-sighash-fork-id").cloned().or(defaults.override_sighash_fork_id),
+            override_sighash_fork_activation: m
+                .get_one::<u64>("override-sighash-fork-activation")
+                .cloned()
+                .or(defaults.override_sighash_fork_activation),
+            override_pruning_finality_windows: m
+                .get_one::<u64>("override-pruning-finality-windows")
+                .cloned()
+                .or(defaults.override_pruning_finality_windows),
+            override_payload_activation: m
+                .get_one::<u64>("override-payload-activation")
+                .cloned()
+                .or(defaults.override_payload_activation),
+            p2p_trace_file: m.get_one::<String>("p2ptrace").cloned().or(defaults.p2p_trace_file),
+            handshake_timeout: m.get_one::<u64>("handshake-timeout").cloned().or(defaults.handshake_timeout),
+            p2p_response_timeout: m.get_one::<u64>("p2p-response-timeout").cloned().or(defaults.p2p_response_timeout),
+            max_upload_mbps: m.get_one::<f64>("max-upload-mbps").cloned().or(defaults.max_upload_mbps),
+            max_peer_upload_mbps: m.get_one::<f64>("max-peer-upload-mbps").cloned().or(defaults.max_peer_upload_mbps),
+            export_snapshot: m.get_one::<String>("export-snapshot").cloned().or(defaults.export_snapshot),
+            import_snapshot: m.get_one::<String>("import-snapshot").cloned().or(defaults.import_snapshot),
+            assume_valid: m.get_one::<String>("assume-valid").cloned().or(defaults.assume_valid),
+            no_assume_valid: arg_match_unwrap_or::<bool>(&m, "no-assume-valid", defaults.no_assume_valid),
+            mempool_expiry: m.get_one::<u64>("mempool-expiry").cloned().or(defaults.mempool_expiry),
+            mempool_local_expiry: m.get_one::<u64>("mempool-local-expiry").cloned().or(defaults.mempool_local_expiry),
+            divergence_reference: m.get_one::<String>("divergence-reference").cloned().or(defaults.divergence_reference),
+            divergence_check_interval: arg_match_unwrap_or::<u64>(&m, "divergence-check-interval", defaults.divergence_check_interval),
+            mining_address: m.get_one::<String>("miningaddr").cloned().or(defaults.mining_address),
+            coinbase_extra_data: m.get_one::<String>("coinbase-extra-data").cloned().or(defaults.coinbase_extra_data),
+            accept_non_native_subnetworks: arg_match_unwrap_or::<bool>(
+                &m,
+                "accept-non-native-subnetworks",
+                defaults.accept_non_native_subnetworks,
+            ),
+            max_standard_payload_len: m
+                .get_one::<usize>("max-standard-payload-len")
+                .cloned()
+                .unwrap_or(defaults.max_standard_payload_len),
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -549,6 +1195,7 @@ fn arg_match_many_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatch
       --maxutxocachesize=                   Max size of loaded UTXO into ram from the disk in bytes (default:
                                             5000000000)
       --utxoindex                           Enable the UTXO index
+      --acceptanceindex                     Enable the transaction acceptance index
       --archival                            Run as an archival node: don't delete old block data when moving the
                                             pruning point (Warning: heavy disk usage)'
       --protocol-version=                   Use non default p2p protocol version (default: 5)
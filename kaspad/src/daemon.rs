@@ -1,22 +1,36 @@
-use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
+use std::{
+    fs,
+    path::PathBuf,
+    process::exit,
+    sync::{Arc, Weak},
+    time::Duration,
+};
 
 use async_channel::unbounded;
+use kaspa_acceptanceindex::{api::AcceptanceIndexProxy, AcceptanceIndex};
+use kaspa_addresses::{Address, Prefix};
 use kaspa_consensus_core::{
-    config::ConfigBuilder,
+    config::{constants::perf::CacheSizePolicy, params::Params, ConfigBuilder},
+    constants::SOMPI_PER_KASPA,
+    emission::EmissionSchedule,
     errors::config::{ConfigError, ConfigResult},
 };
 use kaspa_consensus_notify::{root::ConsensusNotificationRoot, service::NotifyService};
-use kaspa_core::{core::Core, debug, info, trace};
-use kaspa_core::{kaspad_env::version, task::tick::TickService};
+use kaspa_core::{core::Core, debug, error, info, service::Service, trace};
+use kaspa_core::{kaspad_env::version, task::tick::TickService, time::unix_now};
 use kaspa_database::{
-    prelude::{CachePolicy, DbWriter, DirectDbWriter},
+    migration::MigrationRunner,
+    prelude::{CachePolicy, DbAccessMode, DbWriter, DirectDbWriter},
     registry::DatabaseStorePrefixes,
 };
 use kaspa_grpc_server::service::GrpcService;
 use kaspa_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
+use kaspa_rpc_core::api::access::RpcAccessPolicy;
 use kaspa_rpc_service::service::RpcCoreService;
 use kaspa_txscript::caches::TxScriptCacheCounters;
+use kaspa_utils::fs_lock::FsLock;
 use kaspa_utils::git;
+use kaspa_utils::mem_pressure::{Shrinkable, ShrinkableRegistry};
 use kaspa_utils::networking::ContextualNetAddress;
 use kaspa_utils::sysinfo::SystemInfo;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
@@ -24,21 +38,36 @@ use kaspa_utils_tower::counters::TowerConnectionCounters;
 use kaspa_addressmanager::AddressManager;
 use kaspa_consensus::{consensus::factory::Factory as ConsensusFactory, pipeline::ProcessingCounters};
 use kaspa_consensus::{
-    consensus::factory::MultiConsensusManagementStore, model::stores::headers::DbHeadersStore, pipeline::monitor::ConsensusMonitor,
+    consensus::factory::MultiConsensusManagementStore,
+    model::stores::headers::DbHeadersStore,
+    pipeline::{
+        maintenance::{StorageMaintenanceConfig, StorageMaintenanceScheduler},
+        monitor::{ConsensusMonitor, NodeStatusProvider},
+    },
 };
 use kaspa_consensusmanager::ConsensusManager;
 use kaspa_core::task::runtime::AsyncRuntime;
-use kaspa_index_processor::service::IndexService;
+use kaspa_index_processor::{index::DynIndex, service::IndexService};
 use kaspa_mining::{
     manager::{MiningManager, MiningManagerProxy},
+    mempool::config::{
+        Config as MiningConfig, DEFAULT_HIGH_PRIORITY_TRANSACTION_EXPIRE_INTERVAL_SECONDS, DEFAULT_TRANSACTION_EXPIRE_INTERVAL_SECONDS,
+    },
+    model::tx_query::TransactionQuery,
     monitor::MiningMonitor,
     MiningCounters,
 };
 use kaspa_p2p_flows::{flow_context::FlowContext, service::P2pService};
+use kaspa_p2p_lib::{MessageTraceRecorder, P2pTimeouts, Socks5Proxy, UploadLimits};
 
 use itertools::Itertools;
-use kaspa_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
+use kaspa_perf_monitor::{
+    builder::Builder as PerfMonitorBuilder,
+    counters::CountersSnapshot,
+    watchdog::{MemoryWatchdog, DEFAULT_SAMPLE_INTERVAL as WATCHDOG_SAMPLE_INTERVAL},
+};
 use kaspa_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
+use kaspa_wrpc_client::KaspaRpcClient;
 use kaspa_wrpc_server::service::{Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding, WrpcService};
 
 /// Desired soft FD limit that needs to be configured
@@ -50,14 +79,22 @@ pub const DESIRED_DAEMON_SOFT_FD_LIMIT: u64 = 8 * 1024;
 /// this value may impact the database performance).
 pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 
-use crate::args::Args;
+use crate::{
+    args::Args,
+    divergence::DivergenceMonitor,
+    identity::load_or_create_node_identity,
+    local_transactions::LocalTransactionsService,
+    snapshot::{export_snapshot, import_snapshot},
+};
 
 const DEFAULT_DATA_DIR: &str = "datadir";
 const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
+const ACCEPTANCEINDEX_DB: &str = "acceptanceindex";
 const META_DB: &str = "meta";
 const META_DB_FILE_LIMIT: i32 = 5;
 const DEFAULT_LOG_DIR: &str = "logs";
+const DATA_DIR_LOCK_FILE: &str = ".lock";
 
 fn get_home_dir() -> PathBuf {
     #[cfg(target_os = "windows")]
@@ -89,6 +126,9 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if !args.connect_peers.is_empty() && !args.add_peers.is_empty() {
         return Err(ConfigError::MixedConnectAndAddPeers);
     }
+    if !args.connect_peers.is_empty() && !args.anchor_peers.is_empty() {
+        return Err(ConfigError::MixedConnectAndAnchorPeers);
+    }
     if args.logdir.is_some() && args.no_log_files {
         return Err(ConfigError::MixedLogDirAndNoLogFiles);
     }
@@ -98,12 +138,64 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.ram_scale > 10.0 {
         return Err(ConfigError::RamScaleTooHigh);
     }
+    let cache_policy = match args.cache_policy.as_deref() {
+        Some(name) => CacheSizePolicy::from_name(name).ok_or_else(|| ConfigError::UnknownCachePolicy(name.to_owned()))?,
+        None => CacheSizePolicy::default(),
+    };
+    if cache_policy != CacheSizePolicy::Custom && !args.cache_budget_overrides().is_empty() {
+        return Err(ConfigError::CacheBudgetsRequireCustomPolicy);
+    }
+    if args.max_upload_mbps.is_some_and(|mbps| !(mbps > 0.0 && mbps.is_finite())) {
+        return Err(ConfigError::InvalidUploadLimit("max-upload-mbps"));
+    }
+    if args.max_peer_upload_mbps.is_some_and(|mbps| !(mbps > 0.0 && mbps.is_finite())) {
+        return Err(ConfigError::InvalidUploadLimit("max-peer-upload-mbps"));
+    }
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    consensus_params(args)?;
+    validate_mining_args(args)?;
+    args.rpc_access_config()?;
+    if args.wrpc_max_connections == Some(0) || args.wrpc_max_connections_per_ip == Some(0) {
+        return Err(ConfigError::WrpcMaxConnectionsTooLow);
+    }
+    if let Some(proxy) = args.proxy.as_deref() {
+        proxy.parse::<Socks5Proxy>().map_err(ConfigError::InvalidProxy)?;
+    }
     Ok(())
 }
 
+/// Coinbase payload bytes which cannot be used by the configured extra data: the fixed payload fields (blue score,
+/// subsidy, script version and script length) along with the longest standard script public key a miner can be paid to
+const COINBASE_PAYLOAD_RESERVED_LEN: usize = 8 + 8 + 2 + 1 + 35;
+
+fn validate_mining_args(args: &Args) -> ConfigResult<()> {
+    let network = args.network();
+    if let Some(mining_address) = args.mining_address.as_ref() {
+        match Address::try_from(mining_address.as_str()) {
+            Ok(address) if address.prefix == Prefix::from(network) => {}
+            _ => return Err(ConfigError::InvalidMiningAddress(mining_address.clone(), network.to_string())),
+        }
+    }
+    if let Some(coinbase_extra_data) = args.coinbase_extra_data.as_ref() {
+        // The node version and a '/' separator always prefix the extra data (see the GetBlockTemplate RPC)
+        let params: Params = network.into();
+        let max_len = params.max_coinbase_payload_len.saturating_sub(COINBASE_PAYLOAD_RESERVED_LEN + version().len() + 1);
+        if coinbase_extra_data.len() > max_len {
+            return Err(ConfigError::CoinbaseExtraDataTooLong(max_len));
+        }
+    }
+    Ok(())
+}
+
+/// Builds the consensus params of the selected network with the overrides of `args` applied
+pub fn consensus_params(args: &Args) -> ConfigResult<Params> {
+    let mut params: Params = args.network().into();
+    args.apply_consensus_overrides(&mut params)?;
+    Ok(params)
+}
+
 fn get_user_approval_or_exit(message: &str, approve: bool) {
     if approve {
         return;
@@ -128,6 +220,67 @@ fn get_user_approval_or_exit(message: &str, approve: bool) {
     }
 }
 
+/// Applies the pending schema migrations of the DB named `db_name` according to the migration related [`Args`].
+/// Exits the process on failure since stores cannot be used with an outdated schema.
+fn apply_db_migrations(args: &Args, db_name: &str, runner: MigrationRunner) {
+    let backup_path = args.migrate_backup_dir.as_ref().map(|dir| PathBuf::from(dir).join(format!("{}-{}", db_name, unix_now())));
+    match runner.with_dry_run(args.migrate_dry_run).with_backup_path(backup_path).run() {
+        Ok(report) => {
+            if let Some(path) = report.backup_path.as_ref() {
+                info!("Created a backup of the {} database at {}", db_name, path.display());
+            }
+            for record in report.records.iter() {
+                match report.dry_run {
+                    true => info!("Pending {} database migration: {}", db_name, record),
+                    false => info!("Applied {} database migration: {} ({} entries)", db_name, record, record.entries),
+                }
+            }
+            if report.dry_run && report.is_empty() {
+                info!("No pending {} database migrations", db_name);
+            }
+        }
+        Err(err) => {
+            error!("Failed migrating the {} database: {}", db_name, err);
+            exit(1);
+        }
+    }
+}
+
+/// Reports the mempool size and peer count of the P2P flow context to the consensus monitor
+struct FlowContextStatus(Arc<FlowContext>);
+
+impl NodeStatusProvider for FlowContextStatus {
+    fn mempool_size(&self) -> u64 {
+        self.0.mining_manager().transaction_count_sample(TransactionQuery::TransactionsOnly)
+    }
+
+    fn peer_count(&self) -> usize {
+        self.0.hub().active_peers_len()
+    }
+}
+
+/// Holds the exclusive lock over the network application directory for the lifetime of the node.
+/// The lock is released when the core drops its services on shutdown.
+struct DataDirLock {
+    _lock: FsLock,
+}
+
+impl DataDirLock {
+    const IDENT: &'static str = "data-dir-lock";
+}
+
+impl Service for DataDirLock {
+    fn ident(self: Arc<Self>) -> &'static str {
+        Self::IDENT
+    }
+
+    fn start(self: Arc<Self>, _core: Arc<Core>) -> Vec<std::thread::JoinHandle<()>> {
+        vec![]
+    }
+
+    fn stop(self: Arc<Self>) {}
+}
+
 /// Runtime configuration struct for the application.
 #[derive(Default)]
 pub struct Runtime {
@@ -162,6 +315,40 @@ pub fn get_log_dir(args: &Args) -> Option<String> {
     log_dir
 }
 
+/// Prints the block subsidy emission schedule of the network selected by `args`
+/// (taking consensus overrides such as `--override-bps` into account).
+pub fn print_emission(args: &Args) {
+    let params = match validate_args(args).and_then(|_| consensus_params(args)) {
+        Ok(params) => params,
+        Err(err) => {
+            println!("{}", err);
+            exit(1);
+        }
+    };
+    let schedule = EmissionSchedule::from(&params);
+    let kas = |sompi: u64| format!("{}.{:08}", sompi / SOMPI_PER_KASPA, sompi % SOMPI_PER_KASPA);
+
+    println!("Emission schedule of {} ({} BPS)", params.net, params.bps());
+    println!(
+        "{:>6} {:>16} {:>16} {:>22} {:>26}",
+        "month", "start DAA score", "end DAA score", "subsidy per block", "cumulative emission"
+    );
+    let mut cumulative = 0;
+    for (index, period) in schedule.periods().enumerate() {
+        cumulative += period.emission();
+        // the pre-deflationary phase is listed as month 0
+        println!(
+            "{:>6} {:>16} {:>16} {:>22} {:>26}",
+            index,
+            period.start_daa_score,
+            period.end_daa_score,
+            kas(period.subsidy),
+            kas(cumulative)
+        );
+    }
+    println!("Total emission: {} KAS (subsidy ends at DAA score {})", kas(cumulative), schedule.emission_end_daa_score());
+}
+
 impl Runtime {
     pub fn from_args(args: &Args) -> Self {
         let log_dir = get_log_dir(args);
@@ -220,17 +407,24 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     } else {
         0
     };
+    let acceptance_files_limit = if args.acceptanceindex {
+        let acceptance_files_limit = fd_remaining * 5 / 100;
+        fd_remaining -= acceptance_files_limit;
+        acceptance_files_limit
+    } else {
+        0
+    };
     // Make sure args forms a valid set of properties
-    if let Err(err) = validate_args(args) {
-        println!("{}", err);
-        exit(1);
-    }
+    let params = match validate_args(args).and_then(|_| consensus_params(args)) {
+        Ok(params) => params,
+        Err(err) => {
+            println!("{}", err);
+            exit(1);
+        }
+    };
 
     let config = Arc::new(
-        ConfigBuilder::new(network.into())
-            .adjust_perf_params_to_consensus_params()
-            .apply_args(|config| args.apply_to_config(config))
-            .build(),
+        ConfigBuilder::new(params).adjust_perf_params_to_consensus_params().apply_args(|config| args.apply_to_config(config)).build(),
     );
 
     // TODO: Validate `config` forms a valid set of properties
@@ -244,6 +438,7 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     assert!(!db_dir.to_str().unwrap().is_empty());
     info!("Application directory: {}", app_dir.display());
     info!("Data directory: {}", db_dir.display());
+    info!("Consensus cache policy: {}", config.perf.cache_size_policy);
     match runtime.log_dir.as_ref() {
         Some(s) => {
             info!("Logs directory: {}", s);
@@ -253,8 +448,21 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
         }
     }
 
+    // Prevent a concurrent kaspad process from opening (and corrupting) the same databases.
+    // The lock file lives outside the data directory so that it survives a DB reset.
+    let network_dir = app_dir.join(network.to_prefixed());
+    fs::create_dir_all(network_dir.as_path()).unwrap();
+    let data_dir_lock = match FsLock::try_acquire(network_dir.join(DATA_DIR_LOCK_FILE)) {
+        Ok(lock) => Arc::new(DataDirLock { _lock: lock }),
+        Err(err) => {
+            println!("{err}; is another kaspad instance running with the same --appdir and network?");
+            exit(1);
+        }
+    };
+
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
+    let acceptanceindex_db_dir = db_dir.join(ACCEPTANCEINDEX_DB);
     let meta_db_dir = db_dir.join(META_DB);
 
     let mut is_db_reset_needed = args.reset_db;
@@ -274,6 +482,10 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
+    if args.acceptanceindex {
+        info!("Acceptance index Data directory {}", acceptanceindex_db_dir.display());
+        fs::create_dir_all(acceptanceindex_db_dir.as_path()).unwrap();
+    }
 
     // DB used for addresses store and for multi-consensus management
     let mut meta_db = kaspa_database::prelude::ConnBuilder::default()
@@ -438,6 +650,9 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         if args.utxoindex {
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
         }
+        if args.acceptanceindex {
+            fs::create_dir_all(acceptanceindex_db_dir.as_path()).unwrap();
+        }
 
         // Reopen the DB
         meta_db = kaspa_database::prelude::ConnBuilder::default()
@@ -451,12 +666,20 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         get_user_approval_or_exit("--archival is set to false although the node was previously archival. Proceeding may delete archived data. Do you confirm? (y/n)", args.yes);
     }
 
+    apply_db_migrations(args, META_DB, MigrationRunner::new(meta_db.clone()).with_migrations(kaspa_addressmanager::migrations()));
+    if args.migrate_dry_run {
+        exit(0);
+    }
+
     let connect_peers = args.connect_peers.iter().map(|x| x.normalize(config.default_p2p_port())).collect::<Vec<_>>();
     let add_peers = args.add_peers.iter().map(|x| x.normalize(config.default_p2p_port())).collect();
+    let anchor_peers = args.anchor_peers.iter().map(|x| x.normalize(config.default_p2p_port())).collect();
     let p2p_server_addr = args.listen.unwrap_or(ContextualNetAddress::unspecified()).normalize(config.default_p2p_port());
     // connect_peers means no DNS seeding and no outbound peers
     let outbound_target = if connect_peers.is_empty() { args.outbound_target } else { 0 };
-    let dns_seeders = if connect_peers.is_empty() && !args.disable_dns_seeding { config.dns_seeders } else { &[] };
+    // Seeders are resolved locally, so they are skipped when a proxy is used to avoid DNS leaks
+    let dns_seeders =
+        if connect_peers.is_empty() && !args.disable_dns_seeding && args.proxy.is_none() { config.dns_seeders } else { &[] };
 
     let grpc_server_addr = args.rpclisten.unwrap_or(ContextualNetAddress::loopback()).normalize(config.default_rpc_port());
 
@@ -477,6 +700,9 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     let p2p_tower_counters = Arc::new(TowerConnectionCounters::default());
     let grpc_tower_counters = Arc::new(TowerConnectionCounters::default());
 
+    // Caches which are shrunk by the memory watchdog when the node exceeds its memory budget
+    let cache_registry = Arc::new(ShrinkableRegistry::new());
+
     // Use `num_cpus` background threads for the consensus database as recommended by rocksdb
     let consensus_db_parallelism = num_cpus::get();
     let consensus_factory = Arc::new(ConsensusFactory::new(
@@ -488,9 +714,30 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         processing_counters.clone(),
         tx_script_cache_counters.clone(),
         fd_remaining,
+        DbAccessMode::ReadWrite,
+        cache_registry.clone(),
     ));
+
+    if let Some(path) = args.export_snapshot.as_ref() {
+        match export_snapshot(consensus_factory.as_ref(), &config, path) {
+            Ok((pruning_point, utxo_count)) => {
+                info!("Exported the UTXO set snapshot of pruning point {} ({} UTXO entries) to {}", pruning_point, utxo_count, path);
+                exit(0);
+            }
+            Err(err) => {
+                error!("Failed exporting the UTXO set snapshot: {}", err);
+                exit(1);
+            }
+        }
+    }
+    if let Some(path) = args.import_snapshot.as_ref() {
+        if let Err(err) = import_snapshot(consensus_factory.as_ref(), &config, path) {
+            error!("Failed importing the UTXO set snapshot: {}", err);
+            exit(1);
+        }
+    }
+
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
-    let consensus_monitor = Arc::new(ConsensusMonitor::new(processing_counters.clone(), tick_service.clone()));
 
     let perf_monitor_builder = PerfMonitorBuilder::new()
         .with_fetch_interval(Duration::from_secs(args.perf_metrics_interval_sec))
@@ -507,36 +754,93 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         Arc::new(perf_monitor_builder.build())
     };
 
+    let memory_watchdog = args.memory_budget.map(|memory_budget| {
+        info!("Memory budget: {} MB", memory_budget);
+        Arc::new(MemoryWatchdog::new(
+            tick_service.clone(),
+            WATCHDOG_SAMPLE_INTERVAL,
+            memory_budget * 1_000_000,
+            cache_registry.clone(),
+        ))
+    });
+
     let system_info = SystemInfo::default();
 
     let notify_service = Arc::new(NotifyService::new(notification_root.clone(), notification_recv, subscription_context.clone()));
-    let index_service: Option<Arc<IndexService>> = if args.utxoindex {
+    let utxoindex = args.utxoindex.then(|| {
         // Use only a single thread for none-consensus databases
         let utxoindex_db = kaspa_database::prelude::ConnBuilder::default()
             .with_db_path(utxoindex_db_dir)
             .with_files_limit(utxo_files_limit)
             .build()
             .unwrap();
-        let utxoindex = UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap());
-        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), Some(utxoindex)));
-        Some(index_service)
+        UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap())
+    });
+    let acceptance_index = args.acceptanceindex.then(|| {
+        let acceptance_index_db = kaspa_database::prelude::ConnBuilder::default()
+            .with_db_path(acceptanceindex_db_dir)
+            .with_files_limit(acceptance_files_limit)
+            .build()
+            .unwrap();
+        AcceptanceIndexProxy::new(AcceptanceIndex::new(consensus_manager.clone(), acceptance_index_db).unwrap())
+    });
+    let index_service: Option<Arc<IndexService>> = if utxoindex.is_some() || acceptance_index.is_some() {
+        let indexes = acceptance_index.clone().map(|x| Arc::new(x) as DynIndex).into_iter().collect();
+        Some(Arc::new(IndexService::with_indexes(&notify_service.notifier(), subscription_context.clone(), utxoindex, indexes)))
     } else {
         None
     };
 
-    let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db, tick_service.clone());
-
-    let mining_manager = MiningManagerProxy::new(Arc::new(MiningManager::new_with_extended_config(
-        config.target_time_per_block,
-        false,
-        config.max_block_mass,
-        config.ram_scale,
-        config.block_template_cache_lifetime,
-        mining_counters.clone(),
-    )));
+    let node_identity = Arc::new(load_or_create_node_identity(meta_db.clone()).unwrap());
+    info!("Node identity: {}", node_identity.public_key());
+    // The meta DB migrations were applied above, so a failure here means the DB is unusable
+    let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db.clone(), tick_service.clone())
+        .unwrap_or_else(|err| {
+            error!("Failed initializing the address manager: {}", err);
+            exit(1);
+        });
+
+    let mining_config = MiningConfig::build_default(config.target_time_per_block, false, config.max_block_mass)
+        .apply_ram_scale(config.ram_scale)
+        .with_coinbase_extra_data(config.coinbase_extra_data.clone())
+        .with_non_native_subnetworks(args.accept_non_native_subnetworks)
+        .with_maximum_standard_payload_len(args.max_standard_payload_len)
+        .with_transaction_expiry(
+            args.mempool_expiry.unwrap_or(DEFAULT_TRANSACTION_EXPIRE_INTERVAL_SECONDS),
+            args.mempool_local_expiry.unwrap_or(DEFAULT_HIGH_PRIORITY_TRANSACTION_EXPIRE_INTERVAL_SECONDS),
+        );
+    let mining_manager =
+        Arc::new(MiningManager::with_config(mining_config, config.block_template_cache_lifetime, mining_counters.clone()));
+    cache_registry.register("mempool", Arc::downgrade(&mining_manager) as Weak<dyn Shrinkable>);
+    let mining_manager = MiningManagerProxy::new(mining_manager);
     let mining_monitor =
         Arc::new(MiningMonitor::new(mining_manager.clone(), mining_counters, tx_script_cache_counters.clone(), tick_service.clone()));
 
+    let p2p_trace_recorder = args.p2p_trace_file.as_ref().map(|file| {
+        info!("Recording incoming P2P messages to {}", file);
+        Arc::new(MessageTraceRecorder::create(file).unwrap_or_else(|err| panic!("failed creating P2P trace file {file}: {err}")))
+    });
+    let mut p2p_timeouts = P2pTimeouts::default();
+    if let Some(timeout) = args.handshake_timeout {
+        p2p_timeouts = p2p_timeouts.with_handshake_timeout(Duration::from_secs(timeout));
+    }
+    if let Some(timeout) = args.p2p_response_timeout {
+        p2p_timeouts = p2p_timeouts.with_response_timeout(Duration::from_secs(timeout));
+    }
+    let mut p2p_upload_limits = UploadLimits::default();
+    if let Some(mbps) = args.max_upload_mbps {
+        info!("Limiting P2P upload rate to {} Mbps", mbps);
+        p2p_upload_limits = p2p_upload_limits.with_global_mbps(mbps);
+    }
+    if let Some(mbps) = args.max_peer_upload_mbps {
+        info!("Limiting P2P upload rate to {} Mbps per peer", mbps);
+        p2p_upload_limits = p2p_upload_limits.with_per_peer_mbps(mbps);
+    }
+    // The proxy is validated via `validate_args`
+    let p2p_outbound_proxy = args.proxy.as_deref().map(|proxy| proxy.parse::<Socks5Proxy>().unwrap());
+    if let Some(proxy) = p2p_outbound_proxy.as_ref() {
+        info!("Connecting to outbound P2P peers through SOCKS5 proxy {}, DNS seeding is disabled", proxy);
+    }
     let flow_context = Arc::new(FlowContext::new(
         consensus_manager.clone(),
         address_manager,
@@ -544,13 +848,34 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         mining_manager.clone(),
         tick_service.clone(),
         notification_root,
+        p2p_trace_recorder,
+        p2p_timeouts,
+        p2p_upload_limits,
+        p2p_outbound_proxy,
+        node_identity,
     ));
+    let local_transactions_service = Arc::new(LocalTransactionsService::new(meta_db, flow_context.clone(), tick_service.clone()));
+    let consensus_monitor = Arc::new(
+        ConsensusMonitor::new(processing_counters.clone(), tick_service.clone())
+            .with_node_status(Arc::new(FlowContextStatus(flow_context.clone()))),
+    );
+    let storage_maintenance_scheduler = (!args.disable_db_maintenance).then(|| {
+        let compaction_interval = (args.db_compaction_interval > 0).then(|| Duration::from_secs(args.db_compaction_interval * 3600));
+        Arc::new(StorageMaintenanceScheduler::new(
+            consensus_manager.clone(),
+            processing_counters.clone(),
+            tick_service.clone(),
+            StorageMaintenanceConfig::new(config.bps(), compaction_interval),
+        ))
+    });
     let p2p_service = Arc::new(P2pService::new(
         flow_context.clone(),
         connect_peers,
         add_peers,
+        anchor_peers,
         p2p_server_addr,
         outbound_target,
+        args.min_outbound_groups,
         args.inbound_limit,
         dns_seeders,
         config.default_p2p_port(),
@@ -560,11 +885,13 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     let rpc_core_service = Arc::new(RpcCoreService::new(
         consensus_manager.clone(),
         notify_service.notifier(),
-        index_service.as_ref().map(|x| x.notifier()),
+        // The index notifier only relays UTXO index notifications
+        index_service.as_ref().filter(|x| x.utxoindex().is_some()).map(|x| x.notifier()),
         mining_manager,
         flow_context,
         subscription_context,
-        index_service.as_ref().map(|x| x.utxoindex().unwrap()),
+        index_service.as_ref().and_then(|x| x.utxoindex()),
+        acceptance_index,
         config.clone(),
         core.clone(),
         processing_counters,
@@ -575,6 +902,18 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         grpc_tower_counters.clone(),
         system_info,
     ));
+    let divergence_monitor = args.divergence_reference.as_ref().map(|url| {
+        let reference = KaspaRpcClient::new(WrpcEncoding::Borsh, Some(url.as_str()), None, Some(network), None)
+            .unwrap_or_else(|err| panic!("invalid divergence reference node URL {url}: {err}"));
+        Arc::new(DivergenceMonitor::new(
+            rpc_core_service.clone(),
+            reference,
+            network,
+            config.bps(),
+            Duration::from_secs(args.divergence_check_interval),
+            tick_service.clone(),
+        ))
+    });
     let grpc_service_broadcasters: usize = 3; // TODO: add a command line argument or derive from other arg/config/host-related fields
     let grpc_service = if !args.disable_grpc {
         Some(Arc::new(GrpcService::new(
@@ -605,10 +944,24 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     }
     async_runtime.register(p2p_service);
     async_runtime.register(consensus_monitor);
+    if let Some(storage_maintenance_scheduler) = storage_maintenance_scheduler {
+        async_runtime.register(storage_maintenance_scheduler);
+    }
     async_runtime.register(mining_monitor);
+    async_runtime.register(local_transactions_service);
     async_runtime.register(perf_monitor);
+    if let Some(memory_watchdog) = memory_watchdog {
+        async_runtime.register(memory_watchdog);
+    }
+    if let Some(divergence_monitor) = divergence_monitor {
+        async_runtime.register(divergence_monitor);
+    }
     let wrpc_service_tasks: usize = 2; // num_cpus::get() / 2;
-                                       // Register wRPC servers based on command line arguments
+
+    // A single policy applies to both wRPC servers so the concurrency cap is shared among them.
+    // The access configuration is validated via `validate_args`
+    let wrpc_access_policy = args.rpc_access_config().unwrap().map(|config| Arc::new(RpcAccessPolicy::new(config)));
+    // Register wRPC servers based on command line arguments
     [
         (args.rpclisten_borsh.clone(), WrpcEncoding::Borsh, wrpc_borsh_counters),
         (args.rpclisten_json.clone(), WrpcEncoding::SerdeJson, wrpc_json_counters),
@@ -624,6 +977,12 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
                 WrpcServerOptions {
                     listen_address: listen_address.to_address(&network.network_type, &encoding).to_string(), // TODO: use a normalized ContextualNetAddress instead of a String
                     verbose: args.wrpc_verbose,
+                    access_policy: wrpc_access_policy.clone(),
+                    max_connections: args.wrpc_max_connections,
+                    max_connections_per_ip: args.wrpc_max_connections_per_ip,
+                    keepalive_interval: (args.wrpc_keepalive_interval > 0).then(|| Duration::from_secs(args.wrpc_keepalive_interval)),
+                    idle_timeout: (args.wrpc_idle_timeout > 0).then(|| Duration::from_secs(args.wrpc_idle_timeout)),
+                    write_timeout: (args.wrpc_write_timeout > 0).then(|| Duration::from_secs(args.wrpc_write_timeout)),
                     ..WrpcServerOptions::default()
                 },
             ))
@@ -634,6 +993,8 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     // Consensus must start first in order to init genesis in stores
     core.bind(consensus_manager);
     core.bind(async_runtime);
+    // Bound last so the lock is released only after all other services were dropped
+    core.bind(data_dir_lock);
 
     (core, rpc_core_service)
 }
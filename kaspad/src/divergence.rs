@@ -0,0 +1,252 @@
+//!
+//! Cross-checks of the local consensus state against a reference node, meant for networks running mixed node
+//! implementations where a silent consensus split must be caught as early as possible.
+//!
+//! Every check compares the selected tip, the pruning point and the UTXO commitment of the two nodes. Since both
+//! nodes keep processing blocks between the queries, a mismatch is only reported when it cannot be explained by
+//! propagation delays or ordinary reorgs: a block is expected to be on the selected chain of the other node only
+//! once that node has it at a settled depth below its sink, and the virtual UTXO commitments are only compared
+//! when both nodes report the exact same virtual parents.
+//!
+
+use kaspa_addresses::{Address, Prefix, Version};
+use kaspa_consensus_core::network::NetworkId;
+use kaspa_core::{
+    debug, error, info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, warn,
+};
+use kaspa_rpc_core::{
+    api::rpc::{DynRpcService, RpcApi},
+    RpcHash, RpcResult,
+};
+use kaspa_wrpc_client::{
+    prelude::{ConnectOptions, ConnectStrategy},
+    KaspaRpcClient,
+};
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+const DIVERGENCE_MONITOR: &str = "divergence-monitor";
+
+/// The time it takes a block to become settled on the selected chain, in seconds. Chain disagreements over blocks
+/// above this depth are attributed to ordinary reorgs and propagation delays
+const SETTLED_DEPTH_SECONDS: u64 = 60;
+
+#[derive(Debug)]
+enum Divergence {
+    /// A block settled on the selected chain of one node is not on the selected chain of the other
+    SelectedChain {
+        block: RpcHash,
+        chain_of: &'static str,
+        off_chain_of: &'static str,
+    },
+    PruningPoint {
+        local: RpcHash,
+        reference: RpcHash,
+    },
+    UtxoCommitment {
+        virtual_parents: Vec<RpcHash>,
+        local: RpcHash,
+        reference: RpcHash,
+    },
+}
+
+pub struct DivergenceMonitor {
+    local: DynRpcService,
+    reference: KaspaRpcClient,
+    network_id: NetworkId,
+    interval: Duration,
+    settled_depth: u64,
+    tick_service: Arc<TickService>,
+}
+
+impl DivergenceMonitor {
+    pub fn new(
+        local: DynRpcService,
+        reference: KaspaRpcClient,
+        network_id: NetworkId,
+        bps: u64,
+        interval: Duration,
+        tick_service: Arc<TickService>,
+    ) -> Self {
+        Self { local, reference, network_id, interval, settled_depth: bps * SETTLED_DEPTH_SECONDS, tick_service }
+    }
+
+    fn reference_url(&self) -> String {
+        self.reference.url().unwrap_or_default()
+    }
+
+    pub async fn worker(self: &Arc<DivergenceMonitor>) {
+        let options = ConnectOptions { block_async_connect: false, strategy: ConnectStrategy::Retry, ..Default::default() };
+        if let Err(err) = self.reference.connect(Some(options)).await {
+            error!("[{DIVERGENCE_MONITOR}] failed connecting to the reference node {}: {err}", self.reference_url());
+            return;
+        }
+        info!("[{DIVERGENCE_MONITOR}] cross-checking the consensus state against the reference node {}", self.reference_url());
+
+        let mut diverged = false;
+        loop {
+            if let TickReason::Shutdown = self.tick_service.tick(self.interval).await {
+                // Let the system print final logs before exiting
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                break;
+            }
+            if !self.reference.is_connected() {
+                debug!("[{DIVERGENCE_MONITOR}] reference node {} is not connected, skipping the check", self.reference_url());
+                continue;
+            }
+            match self.check().await {
+                Ok(divergences) if divergences.is_empty() => {
+                    if diverged {
+                        info!("[{DIVERGENCE_MONITOR}] the consensus state agrees with the reference node again");
+                    }
+                    diverged = false;
+                }
+                Ok(divergences) => {
+                    for divergence in divergences {
+                        self.alert(divergence);
+                    }
+                    diverged = true;
+                }
+                Err(err) => debug!("[{DIVERGENCE_MONITOR}] cross-check with the reference node was inconclusive: {err}"),
+            }
+        }
+
+        trace!("[{DIVERGENCE_MONITOR}] worker exiting");
+        let _ = self.reference.disconnect().await;
+    }
+
+    async fn check(&self) -> RpcResult<Vec<Divergence>> {
+        let local = self.local.get_block_dag_info().await?;
+        let reference = self.reference.get_block_dag_info().await?;
+        if reference.network != self.network_id {
+            warn!(
+                "[{DIVERGENCE_MONITOR}] reference node {} is on network {}, skipping the check",
+                self.reference_url(),
+                reference.network
+            );
+            return Ok(vec![]);
+        }
+
+        let mut divergences = vec![];
+
+        // Selected tip: each sink must be on the selected chain of the other node once settled there
+        if local.sink != reference.sink {
+            divergences.extend(self.check_settled_on_chain(local.sink, "local", &self.reference, "reference").await?);
+            divergences.extend(self.check_settled_on_chain(reference.sink, "reference", self.local.as_ref(), "local").await?);
+        }
+
+        // Pruning point: a pruning point lagging behind the other node is fine as long as it is on the same chain
+        if local.pruning_point_hash != reference.pruning_point_hash {
+            let local_pp_off_chain =
+                self.check_settled_on_chain(local.pruning_point_hash, "local", &self.reference, "reference").await?.is_some();
+            let reference_pp_off_chain =
+                self.check_settled_on_chain(reference.pruning_point_hash, "reference", self.local.as_ref(), "local").await?.is_some();
+            if local_pp_off_chain || reference_pp_off_chain {
+                divergences
+                    .push(Divergence::PruningPoint { local: local.pruning_point_hash, reference: reference.pruning_point_hash });
+            }
+        }
+
+        // UTXO commitment: block templates over the same virtual parents commit to the same virtual UTXO set
+        let virtual_parents: HashSet<_> = local.virtual_parent_hashes.iter().copied().collect();
+        if virtual_parents == reference.virtual_parent_hashes.iter().copied().collect() {
+            divergences.extend(self.check_utxo_commitment(&virtual_parents).await?);
+        }
+
+        Ok(divergences)
+    }
+
+    /// Checks that `block`, a selected chain block of the `chain_of` node, is also on the selected chain of `node` in case it is
+    /// settled there. Blocks unknown to `node` or not yet settled are not reported
+    async fn check_settled_on_chain(
+        &self,
+        block: RpcHash,
+        chain_of: &'static str,
+        node: &(impl RpcApi + ?Sized),
+        off_chain_of: &'static str,
+    ) -> RpcResult<Option<Divergence>> {
+        let Ok(rpc_block) = node.get_block(block, false).await else {
+            return Ok(None);
+        };
+        let Some(verbose_data) = rpc_block.verbose_data else {
+            return Ok(None);
+        };
+        if verbose_data.is_chain_block || verbose_data.is_header_only {
+            return Ok(None);
+        }
+        let sink_blue_score = node.get_sink_blue_score().await?;
+        if sink_blue_score < rpc_block.header.blue_score + self.settled_depth {
+            return Ok(None);
+        }
+        Ok(Some(Divergence::SelectedChain { block, chain_of, off_chain_of }))
+    }
+
+    async fn check_utxo_commitment(&self, virtual_parents: &HashSet<RpcHash>) -> RpcResult<Option<Divergence>> {
+        // The pay address does not affect the commitment, so any address of the network will do
+        let pay_address = Address::new(Prefix::from(self.network_id), Version::PubKey, &[0; 32]);
+        let local = self.local.get_block_template(pay_address.clone(), vec![]).await?.block.header;
+        let reference = self.reference.get_block_template(pay_address, vec![]).await?.block.header;
+
+        // The virtual might have advanced on either node since the DAG info was queried
+        let is_current = |parents_by_level: &[Vec<RpcHash>]| {
+            parents_by_level.first().is_some_and(|parents| {
+                parents.len() == virtual_parents.len() && parents.iter().all(|parent| virtual_parents.contains(parent))
+            })
+        };
+        if !is_current(&local.parents_by_level) || !is_current(&reference.parents_by_level) {
+            return Ok(None);
+        }
+        if local.utxo_commitment == reference.utxo_commitment {
+            return Ok(None);
+        }
+        Ok(Some(Divergence::UtxoCommitment {
+            virtual_parents: virtual_parents.iter().copied().collect(),
+            local: local.utxo_commitment,
+            reference: reference.utxo_commitment,
+        }))
+    }
+
+    fn alert(&self, divergence: Divergence) {
+        let url = self.reference_url();
+        match divergence {
+            Divergence::SelectedChain { block, chain_of, off_chain_of } => error!(
+                "[{DIVERGENCE_MONITOR}] CONSENSUS DIVERGENCE from reference node {url}: block {block} is on the {chain_of} selected chain but settled off the {off_chain_of} selected chain"
+            ),
+            Divergence::PruningPoint { local, reference } => error!(
+                "[{DIVERGENCE_MONITOR}] CONSENSUS DIVERGENCE from reference node {url}: pruning points {local} (local) and {reference} (reference) are on different selected chains"
+            ),
+            Divergence::UtxoCommitment { virtual_parents, local, reference } => error!(
+                "[{DIVERGENCE_MONITOR}] CONSENSUS DIVERGENCE from reference node {url}: UTXO commitment {local} (local) differs from {reference} (reference) over the same virtual parents {virtual_parents:?}"
+            ),
+        }
+    }
+}
+
+// service trait implementation for DivergenceMonitor
+impl AsyncService for DivergenceMonitor {
+    fn ident(self: Arc<Self>) -> &'static str {
+        DIVERGENCE_MONITOR
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", DIVERGENCE_MONITOR);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", DIVERGENCE_MONITOR);
+            Ok(())
+        })
+    }
+}
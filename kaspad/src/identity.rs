@@ -0,0 +1,26 @@
+//!
+//! Persistence of the node identity keypair in the meta database, keeping the node identifiable by its peers
+//! across restarts and IP changes.
+//!
+
+use kaspa_core::warn;
+use kaspa_database::{
+    prelude::{CachedDbItem, DirectDbWriter, StoreResult, StoreResultExtensions, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_p2p_lib::NodeIdentity;
+use std::sync::Arc;
+
+/// Loads the node identity from the meta database, generating and storing a new one on first run
+pub fn load_or_create_node_identity(meta_db: Arc<DB>) -> StoreResult<NodeIdentity> {
+    let mut access: CachedDbItem<[u8; 32]> = CachedDbItem::new(meta_db.clone(), DatabaseStorePrefixes::NodeIdentity.into());
+    if let Some(secret) = access.read().unwrap_option() {
+        match NodeIdentity::from_secret_bytes(&secret) {
+            Ok(identity) => return Ok(identity),
+            Err(err) => warn!("The stored node identity is invalid ({err}), generating a new one"),
+        }
+    }
+    let identity = NodeIdentity::generate();
+    access.write(DirectDbWriter::new(&meta_db), &identity.secret_bytes())?;
+    Ok(identity)
+}
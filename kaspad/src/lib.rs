@@ -1,2 +1,6 @@
 pub mod args;
 pub mod daemon;
+pub mod divergence;
+pub mod identity;
+pub mod local_transactions;
+pub mod snapshot;
@@ -0,0 +1,197 @@
+//!
+//! Persistence of the transactions submitted to this node, keeping them in the mempool across restarts.
+//!
+//! The mempool holds locally-submitted (high-priority) transactions, rebroadcasting them to peers until they are
+//! accepted into a block or expired. This service mirrors them in the meta database and, on startup, resubmits the
+//! stored ones which did not expire in the meantime. Resubmission waits for the node to be nearly synced, so that the
+//! transactions are validated against a recent virtual state.
+//!
+
+use kaspa_consensus_core::tx::{Transaction, TransactionId};
+use kaspa_core::{
+    debug, info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, warn,
+};
+use kaspa_database::{
+    prelude::{CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_mining::{
+    mempool::tx::Orphan,
+    model::{local_tx::LocalTransaction, topological_sort::TopologicalSort},
+};
+use kaspa_p2p_flows::flow_context::FlowContext;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+const LOCAL_TRANSACTIONS_SERVICE: &str = "local-transactions-service";
+
+/// The interval at which the stored transactions are synced with the mempool
+const SYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct StoredLocalTransaction {
+    transaction: Transaction,
+    /// The DAA score past which the transaction expires, `None` if it never does
+    expire_daa_score: Option<u64>,
+    is_orphan: bool,
+}
+
+impl MemSizeEstimator for StoredLocalTransaction {}
+
+pub struct LocalTransactionsService {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, StoredLocalTransaction>,
+    /// The orphan status of the stored transactions, by id
+    stored: Mutex<HashMap<TransactionId, bool>>,
+    flow_context: Arc<FlowContext>,
+    tick_service: Arc<TickService>,
+}
+
+impl LocalTransactionsService {
+    pub fn new(meta_db: Arc<DB>, flow_context: Arc<FlowContext>, tick_service: Arc<TickService>) -> Self {
+        let access = CachedDbAccess::new(meta_db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::LocalTransactions.into());
+        Self { db: meta_db, access, stored: Default::default(), flow_context, tick_service }
+    }
+
+    pub async fn worker(self: &Arc<LocalTransactionsService>) {
+        let transactions = self.load();
+        *self.stored.lock() = transactions.iter().map(|x| (x.transaction.id(), x.is_orphan)).collect();
+
+        // Wait for the node to be nearly synced before restoring the stored transactions, meanwhile only storing new ones
+        if !transactions.is_empty() {
+            loop {
+                if self.flow_context.consensus().unguarded_session().async_is_nearly_synced().await {
+                    break;
+                }
+                if let TickReason::Shutdown = self.tick_service.tick(SYNC_INTERVAL).await {
+                    self.sync(false).await;
+                    return;
+                }
+                self.sync(false).await;
+            }
+            self.restore(transactions).await;
+        }
+
+        loop {
+            if let TickReason::Shutdown = self.tick_service.tick(SYNC_INTERVAL).await {
+                break;
+            }
+            self.sync(true).await;
+        }
+        self.sync(true).await;
+        trace!("[{LOCAL_TRANSACTIONS_SERVICE}] worker exiting");
+    }
+
+    fn load(&self) -> Vec<StoredLocalTransaction> {
+        self.access
+            .iterator()
+            .filter_map(|result| match result {
+                Ok((_, transaction)) => Some(transaction),
+                Err(err) => {
+                    warn!("[{LOCAL_TRANSACTIONS_SERVICE}] skipping an unreadable stored transaction: {err}");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Resubmits the stored transactions which did not expire to the mempool, parents first
+    async fn restore(&self, transactions: Vec<StoredLocalTransaction>) {
+        let session = self.flow_context.consensus().unguarded_session();
+        let virtual_daa_score = session.get_virtual_daa_score();
+        let (expired, transactions): (Vec<_>, Vec<_>) = transactions
+            .into_iter()
+            .partition(|x| x.expire_daa_score.is_some_and(|expire_daa_score| expire_daa_score < virtual_daa_score));
+        let orphans: HashMap<_, _> = transactions.iter().map(|x| (x.transaction.id(), x.is_orphan)).collect();
+        let transactions = transactions.into_iter().map(|x| x.transaction).collect::<Vec<_>>().topological_sort();
+
+        let mut restored = 0;
+        for transaction in transactions {
+            let transaction_id = transaction.id();
+            let orphan = if orphans[&transaction_id] { Orphan::Allowed } else { Orphan::Forbidden };
+            match self.flow_context.submit_rpc_transaction(&session, transaction, orphan).await {
+                Ok(()) => restored += 1,
+                Err(err) => debug!("[{LOCAL_TRANSACTIONS_SERVICE}] stored transaction {transaction_id} was not restored: {err}"),
+            }
+        }
+        info!(
+            "[{LOCAL_TRANSACTIONS_SERVICE}] restored {restored} of {} stored local transactions to the mempool ({} expired)",
+            orphans.len(),
+            expired.len()
+        );
+
+        // Transactions which were not restored are dropped by the next sync
+        self.sync(true).await;
+    }
+
+    /// Stores the local transactions of the mempool which are new or changed, and, if `remove` is set, removes the stored
+    /// transactions which left the mempool
+    async fn sync(&self, remove: bool) {
+        let local_transactions = self.flow_context.mining_manager().clone().get_local_transactions().await;
+        if let Err(err) = self.sync_with(local_transactions, remove) {
+            warn!("[{LOCAL_TRANSACTIONS_SERVICE}] failed storing the local transactions: {err}");
+        }
+    }
+
+    fn sync_with(&self, local_transactions: Vec<LocalTransaction>, remove: bool) -> StoreResult<()> {
+        let mut stored = self.stored.lock();
+        let mut current = HashSet::with_capacity(local_transactions.len());
+        for local in local_transactions {
+            let transaction_id = local.transaction.id();
+            current.insert(transaction_id);
+            if stored.get(&transaction_id) != Some(&local.is_orphan) {
+                let transaction = StoredLocalTransaction {
+                    transaction: (*local.transaction.tx).clone(),
+                    expire_daa_score: local.expire_daa_score,
+                    is_orphan: local.is_orphan,
+                };
+                self.access.write(DirectDbWriter::new(&self.db), transaction_id, transaction)?;
+                stored.insert(transaction_id, local.is_orphan);
+            }
+        }
+        if remove {
+            let removed = stored.keys().filter(|transaction_id| !current.contains(*transaction_id)).copied().collect::<Vec<_>>();
+            for transaction_id in removed {
+                self.access.delete(DirectDbWriter::new(&self.db), transaction_id)?;
+                stored.remove(&transaction_id);
+            }
+        }
+        Ok(())
+    }
+}
+
+// service trait implementation for LocalTransactionsService
+impl AsyncService for LocalTransactionsService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        LOCAL_TRANSACTIONS_SERVICE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", LOCAL_TRANSACTIONS_SERVICE);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", LOCAL_TRANSACTIONS_SERVICE);
+            Ok(())
+        })
+    }
+}
@@ -9,7 +9,7 @@ use kaspa_core::{info, signals::Signals};
 use kaspa_utils::fd_budget;
 use kaspad_lib::{
     args::parse_args,
-    daemon::{create_core, DESIRED_DAEMON_SOFT_FD_LIMIT, MINIMUM_DAEMON_SOFT_FD_LIMIT},
+    daemon::{create_core, print_emission, DESIRED_DAEMON_SOFT_FD_LIMIT, MINIMUM_DAEMON_SOFT_FD_LIMIT},
 };
 
 #[cfg(feature = "heap")]
@@ -24,6 +24,11 @@ pub fn main() {
 
     let args = parse_args();
 
+    if args.print_emission {
+        print_emission(&args);
+        return;
+    }
+
     match fd_budget::try_set_fd_limit(DESIRED_DAEMON_SOFT_FD_LIMIT) {
         Ok(limit) => {
             if limit < MINIMUM_DAEMON_SOFT_FD_LIMIT {
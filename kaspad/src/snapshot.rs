@@ -0,0 +1,197 @@
+//!
+//! Offline export and import of UTXO set snapshots (see [`kaspa_consensus_core::snapshot`]).
+//!
+//! Both operations run before the consensus manager is created, directly over consensus instances
+//! obtained from the consensus factory. The import is performed over a staging consensus which is
+//! committed only once the UTXO set was verified against the pruning point UTXO commitment.
+//!
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    time::{Duration, Instant},
+};
+
+use kaspa_consensus_core::{
+    config::Config,
+    errors::{consensus::ConsensusError, pruning::PruningImportError, snapshot::SnapshotError},
+    pruning::PruningProofMetadata,
+    snapshot::{SnapshotMetadata, SnapshotReader, SnapshotTrustedBlock, SnapshotWriter},
+    trusted::{TrustedBlock, TrustedGhostdagData, TrustedHeader},
+};
+use kaspa_consensusmanager::{ConsensusFactory, ConsensusInstance};
+use kaspa_core::info;
+use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    convert::model::trusted::{TrustedDataEntry, TrustedDataPackage},
+};
+use thiserror::Error;
+use tokio::runtime::Runtime as TokioRuntime;
+
+/// The number of UTXO entries per snapshot chunk
+const SNAPSHOT_UTXO_CHUNK_SIZE: usize = 10_000;
+
+#[derive(Error, Debug)]
+pub enum SnapshotBootstrapError {
+    #[error(transparent)]
+    Snapshot(#[from] SnapshotError),
+
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+
+    #[error(transparent)]
+    PruningImport(#[from] PruningImportError),
+
+    #[error("failed building the trusted sub-DAG: {0}")]
+    TrustedData(#[from] ProtocolError),
+
+    #[error("the pruning point is genesis so there is no UTXO set to export")]
+    GenesisPruningPoint,
+
+    #[error("the node already synced beyond genesis (headers selected tip {0}), snapshots can only be imported by fresh nodes")]
+    NodeNotFresh(Hash),
+}
+
+pub type SnapshotBootstrapResult<T> = std::result::Result<T, SnapshotBootstrapError>;
+
+fn new_tokio_runtime() -> SnapshotBootstrapResult<TokioRuntime> {
+    Ok(tokio::runtime::Builder::new_current_thread().enable_all().build().map_err(SnapshotError::from)?)
+}
+
+/// Exports the pruning point UTXO set, proof and trusted data of the active consensus to `path`.
+/// Returns the snapshot pruning point and the number of exported UTXO entries
+pub fn export_snapshot(factory: &dyn ConsensusFactory, config: &Config, path: &str) -> SnapshotBootstrapResult<(Hash, u64)> {
+    let runtime = new_tokio_runtime()?;
+    let (consensus, _ctl) = factory.new_active_consensus();
+    let session = runtime.block_on(consensus.session_blocking());
+
+    let pruning_point = session.pruning_point();
+    if pruning_point == config.genesis.hash {
+        return Err(SnapshotBootstrapError::GenesisPruningPoint);
+    }
+
+    let trusted_data = session.get_pruning_point_anticone_and_trusted_data()?;
+    let entries = trusted_data
+        .anticone
+        .iter()
+        .map(|&hash| Ok(TrustedDataEntry::new(session.get_block(hash)?, vec![], vec![])))
+        .collect::<Result<Vec<_>, ConsensusError>>()?;
+    let package = TrustedDataPackage::new(
+        trusted_data.daa_window_blocks.iter().map(|th| TrustedHeader::new(th.header.clone(), th.ghostdag.clone())).collect(),
+        trusted_data.ghostdag_blocks.iter().map(|tg| TrustedGhostdagData::new(tg.hash, tg.ghostdag.clone())).collect(),
+    );
+    let metadata = SnapshotMetadata {
+        network: config.params.net.to_string(),
+        pruning_point,
+        pruning_point_proof: session.get_pruning_point_proof().as_ref().clone(),
+        pruning_points: session.pruning_point_headers(),
+        trusted_blocks: package.build_trusted_subdag(entries)?.into_iter().map(SnapshotTrustedBlock::from).collect(),
+    };
+
+    // Write to a temporary file first so that an interrupted export never leaves a seemingly valid snapshot behind
+    let temp_path = format!("{path}.partial");
+    let mut writer = SnapshotWriter::new(BufWriter::new(File::create(&temp_path).map_err(SnapshotError::from)?), &metadata)?;
+    let mut from_outpoint = None;
+    loop {
+        let chunk =
+            session.get_pruning_point_utxos(pruning_point, from_outpoint, SNAPSHOT_UTXO_CHUNK_SIZE, from_outpoint.is_some())?;
+        writer.write_utxo_chunk(&chunk)?;
+        if chunk.len() < SNAPSHOT_UTXO_CHUNK_SIZE {
+            break;
+        }
+        from_outpoint = Some(chunk.last().expect("not empty by prev condition").0);
+    }
+    let utxo_count = writer.utxo_count();
+    writer.finish()?;
+    fs::rename(&temp_path, path).map_err(SnapshotError::from)?;
+    Ok((pruning_point, utxo_count))
+}
+
+/// Bootstraps a fresh node from the snapshot at `path`. The snapshot is validated as if it were received from a
+/// peer during IBD with headers proof and is committed as the active consensus only if fully valid.
+/// Returns the snapshot pruning point
+pub fn import_snapshot(factory: &dyn ConsensusFactory, config: &Config, path: &str) -> SnapshotBootstrapResult<Hash> {
+    let runtime = new_tokio_runtime()?;
+    let file = File::open(path).map_err(SnapshotError::from)?;
+    let (mut reader, metadata) = SnapshotReader::new(BufReader::new(file))?;
+    let network = config.params.net.to_string();
+    if metadata.network != network {
+        return Err(SnapshotError::NetworkMismatch(metadata.network, network).into());
+    }
+
+    let (consensus, ctl) = factory.new_active_consensus();
+    {
+        let session = runtime.block_on(consensus.session_blocking());
+        if session.pruning_point() == metadata.pruning_point {
+            info!("The node was already bootstrapped from snapshot pruning point {}, skipping the import", metadata.pruning_point);
+            return Ok(metadata.pruning_point);
+        }
+        let headers_selected_tip = session.get_headers_selected_tip();
+        if headers_selected_tip != config.genesis.hash {
+            return Err(SnapshotBootstrapError::NodeNotFresh(headers_selected_tip));
+        }
+
+        // The proof is validated in the context of current consensus, claiming the work of the heaviest trusted block.
+        // This work is taken from the file as is, similarly to the work claimed by a relay block during IBD
+        let blue_work = metadata.trusted_blocks.iter().map(|tb| tb.header.blue_work).max().unwrap_or_default();
+        session.validate_pruning_proof(&metadata.pruning_point_proof, &PruningProofMetadata::new(blue_work))?;
+    }
+
+    info!("Importing the UTXO set snapshot of pruning point {} from {}", metadata.pruning_point, path);
+    let (staging, staging_ctl) = factory.new_staging_consensus();
+    let handles = staging_ctl.start();
+    let res = import_into_staging(&runtime, &staging, metadata, &mut reader);
+
+    if res.is_ok() {
+        staging_ctl.make_active();
+    }
+    staging_ctl.stop();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    drop((staging, staging_ctl, consensus, ctl));
+    match res {
+        Ok(pruning_point) => {
+            factory.delete_inactive_consensus_entries();
+            info!("Imported {} UTXO entries, the node is bootstrapped from pruning point {}", reader.utxo_count(), pruning_point);
+            Ok(pruning_point)
+        }
+        Err(err) => {
+            factory.delete_staging_entry();
+            Err(err)
+        }
+    }
+}
+
+fn import_into_staging(
+    runtime: &TokioRuntime,
+    staging: &ConsensusInstance,
+    metadata: SnapshotMetadata,
+    reader: &mut SnapshotReader<BufReader<File>>,
+) -> SnapshotBootstrapResult<Hash> {
+    let session = runtime.block_on(staging.session_blocking());
+    let pruning_point = metadata.pruning_point;
+    let trusted_set = metadata.trusted_blocks.into_iter().map(TrustedBlock::from).collect::<Vec<_>>();
+    session.apply_pruning_proof(metadata.pruning_point_proof, &trusted_set)?;
+    session.import_pruning_points(metadata.pruning_points);
+
+    info!("Processing {} trusted blocks", trusted_set.len());
+    for tb in trusted_set {
+        runtime.block_on(session.validate_and_insert_trusted_block(tb).virtual_state_task).map_err(PruningImportError::from)?;
+    }
+
+    let mut multiset = MuHash::new();
+    let mut last_log = Instant::now();
+    while let Some(chunk) = reader.next_utxo_chunk()? {
+        session.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+        if last_log.elapsed() > Duration::from_secs(10) {
+            info!("Imported {} UTXO entries", reader.utxo_count());
+            last_log = Instant::now();
+        }
+    }
+    // Verifies the imported set against the UTXO commitment of the pruning point header
+    session.import_pruning_point_utxo_set(pruning_point, multiset)?;
+    Ok(pruning_point)
+}
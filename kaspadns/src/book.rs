@@ -0,0 +1,217 @@
+use kaspa_core::{info, time::unix_now, warn};
+use kaspa_utils::networking::NetAddress;
+use rand::seq::IteratorRandom;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    net::IpAddr,
+    path::{Path, PathBuf},
+};
+
+/// The maximum number of tracked addresses
+const MAX_NODES: usize = 50_000;
+/// Reachable nodes are re-tested after this period
+const GOOD_RECRAWL_PERIOD: u64 = 30 * 60 * 1000; // 30 minutes
+/// The base retry period of unreachable nodes, multiplied by the number of consecutive failures
+const BAD_RETRY_PERIOD: u64 = 60 * 60 * 1000; // 1 hour
+/// The maximum retry period of unreachable nodes
+const MAX_RETRY_PERIOD: u64 = 24 * 60 * 60 * 1000; // 24 hours
+/// Nodes which were reachable during this period are served to DNS clients
+const GOOD_PERIOD: u64 = 60 * 60 * 1000; // 1 hour
+/// Nodes which were not reachable during this period are dropped after [`MAX_FAILURES`] consecutive failures
+const STALE_PERIOD: u64 = 3 * 24 * 60 * 60 * 1000; // 3 days
+const MAX_FAILURES: u32 = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Node {
+    pub address: NetAddress,
+    /// Unix time (milliseconds) of the last connection attempt, 0 if never attempted
+    pub last_attempt: u64,
+    /// Unix time (milliseconds) of the last successful crawl, 0 if never reached
+    pub last_success: u64,
+    /// The number of consecutive failed connection attempts
+    pub failures: u32,
+    pub protocol_version: u32,
+    pub user_agent: String,
+}
+
+impl Node {
+    fn new(address: NetAddress) -> Self {
+        Self { address, last_attempt: 0, last_success: 0, failures: 0, protocol_version: 0, user_agent: String::new() }
+    }
+
+    fn is_due(&self, now: u64) -> bool {
+        let period =
+            if self.failures == 0 { GOOD_RECRAWL_PERIOD } else { (BAD_RETRY_PERIOD * self.failures as u64).min(MAX_RETRY_PERIOD) };
+        self.last_attempt == 0 || now.saturating_sub(self.last_attempt) >= period
+    }
+
+    pub fn is_good(&self, now: u64) -> bool {
+        self.failures == 0 && self.last_success > 0 && now.saturating_sub(self.last_success) <= GOOD_PERIOD
+    }
+
+    fn is_stale(&self, now: u64) -> bool {
+        self.failures >= MAX_FAILURES && now.saturating_sub(self.last_success) > STALE_PERIOD
+    }
+}
+
+/// The set of known node addresses along with their reachability test results, optionally persisted to a file
+pub struct AddressBook {
+    nodes: HashMap<NetAddress, Node>,
+    path: Option<PathBuf>,
+}
+
+impl AddressBook {
+    pub fn new() -> Self {
+        Self { nodes: HashMap::new(), path: None }
+    }
+
+    /// Loads the book persisted at `path`, or starts an empty one if the file does not exist or is corrupted.
+    /// The book will be saved to `path`
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let nodes: Vec<Node> = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|err| {
+                warn!("Ignoring corrupted address book {}: {}", path.display(), err);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        info!("Loaded {} addresses from {}", nodes.len(), path.display());
+        Self { nodes: nodes.into_iter().map(|node| (node.address, node)).collect(), path: Some(path) }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        let nodes = self.nodes.values().collect::<Vec<_>>();
+        // Write to a temporary file first so a crash does not leave a partially written book behind
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec(&nodes)?)?;
+        fs::rename(tmp_path, path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Adds previously unknown addresses. Returns the number of added addresses
+    pub fn add_addresses(&mut self, addresses: impl IntoIterator<Item = NetAddress>, allow_non_routable: bool) -> usize {
+        let mut added = 0;
+        for address in addresses {
+            if self.nodes.len() >= MAX_NODES {
+                break;
+            }
+            if address.port == 0 || !(allow_non_routable || address.ip.is_publicly_routable()) {
+                continue;
+            }
+            if let std::collections::hash_map::Entry::Vacant(entry) = self.nodes.entry(address) {
+                entry.insert(Node::new(address));
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Returns up to `limit` addresses due for a reachability test, preferring the ones tested least recently,
+    /// and marks them as attempted
+    pub fn take_due(&mut self, limit: usize) -> Vec<NetAddress> {
+        let now = unix_now();
+        let mut due =
+            self.nodes.values().filter(|node| node.is_due(now)).map(|node| (node.last_attempt, node.address)).collect::<Vec<_>>();
+        due.sort_unstable_by_key(|(last_attempt, _)| *last_attempt);
+        due.truncate(limit);
+        for (_, address) in due.iter() {
+            self.nodes.get_mut(address).unwrap().last_attempt = now;
+        }
+        due.into_iter().map(|(_, address)| address).collect()
+    }
+
+    pub fn mark_success(&mut self, address: NetAddress, protocol_version: u32, user_agent: String) {
+        let node = self.nodes.entry(address).or_insert_with(|| Node::new(address));
+        node.last_success = unix_now();
+        node.failures = 0;
+        node.protocol_version = protocol_version;
+        node.user_agent = user_agent;
+    }
+
+    pub fn mark_failure(&mut self, address: NetAddress) {
+        if let Some(node) = self.nodes.get_mut(&address) {
+            node.failures += 1;
+        }
+    }
+
+    /// Removes nodes which repeatedly failed and were not reachable for a long period. Returns the number of removed nodes
+    pub fn prune(&mut self) -> usize {
+        let now = unix_now();
+        let before = self.nodes.len();
+        self.nodes.retain(|_, node| !node.is_stale(now));
+        before - self.nodes.len()
+    }
+
+    /// Returns the number of nodes currently considered good
+    pub fn good_count(&self) -> usize {
+        let now = unix_now();
+        self.nodes.values().filter(|node| node.is_good(now)).count()
+    }
+
+    /// Returns up to `max` random IPs of good nodes listening on `port` with at least `min_protocol_version`,
+    /// either IPv4 or IPv6 ones according to `ipv6`
+    pub fn random_good_ips(&self, port: u16, min_protocol_version: u32, ipv6: bool, max: usize) -> Vec<IpAddr> {
+        let now = unix_now();
+        self.nodes
+            .values()
+            .filter(|node| node.address.port == port && node.protocol_version >= min_protocol_version && node.is_good(now))
+            .map(|node| node.address.ip.0)
+            .filter(|ip| ip.is_ipv6() == ipv6)
+            .choose_multiple(&mut rand::thread_rng(), max)
+    }
+}
+
+impl Default for AddressBook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn address(s: &str) -> NetAddress {
+        NetAddress::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_crawl_scheduling() {
+        let mut book = AddressBook::new();
+        assert_eq!(book.add_addresses([address("1.1.1.1:16111"), address("[2001:db8::1]:16111")], true), 2);
+        assert_eq!(book.add_addresses([address("1.1.1.1:16111"), address("10.0.0.1:16111")], false), 0);
+
+        // All new nodes are due, and are not due again until their retry period passes
+        assert_eq!(book.take_due(10).len(), 2);
+        assert!(book.take_due(10).is_empty());
+
+        book.mark_success(address("1.1.1.1:16111"), 6, "/kaspad:0.15.3/".to_string());
+        book.mark_failure(address("[2001:db8::1]:16111"));
+        assert_eq!(book.good_count(), 1);
+        assert_eq!(book.random_good_ips(16111, 5, false, 8), vec![IpAddr::from_str("1.1.1.1").unwrap()]);
+        assert!(book.random_good_ips(16111, 5, true, 8).is_empty());
+        assert!(book.random_good_ips(16111, 7, false, 8).is_empty());
+        assert!(book.random_good_ips(16110, 5, false, 8).is_empty());
+
+        // Simulate a long period of failures
+        let node = book.nodes.get_mut(&address("[2001:db8::1]:16111")).unwrap();
+        node.failures = MAX_FAILURES;
+        assert!(node.is_due(node.last_attempt + MAX_RETRY_PERIOD));
+        assert_eq!(book.prune(), 1);
+        assert_eq!(book.len(), 1);
+    }
+}
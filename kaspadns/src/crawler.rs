@@ -0,0 +1,142 @@
+use crate::book::AddressBook;
+use async_trait::async_trait;
+use futures::StreamExt;
+use kaspa_core::{debug, info, kaspad_env::version, warn};
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    convert::model::version::Version,
+    dequeue_with_timeout, make_message,
+    pb::{kaspad_message::Payload, RequestAddressesMessage},
+    Adaptor, ConnectionInitializer, Hub, KaspadHandshake, KaspadMessagePayloadType, Router,
+};
+use kaspa_utils::networking::{ContextualNetAddress, IpAddress, NetAddress};
+use parking_lot::Mutex;
+use std::{sync::Arc, time::Duration};
+use tokio::{net::lookup_host, sync::watch, time::MissedTickBehavior};
+use uuid::Uuid;
+
+/// The protocol version advertised to crawled nodes
+const PROTOCOL_VERSION: u32 = 6;
+
+/// The time a crawled node is given to respond with its known addresses
+const ADDRESSES_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The interval between consecutive crawl rounds
+const CRAWL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Performs the handshake with crawled nodes, requests their known addresses and records the results in the address book
+struct CrawlInitializer {
+    book: Arc<Mutex<AddressBook>>,
+    network_name: String,
+    node_id: Uuid,
+    allow_non_routable: bool,
+}
+
+#[async_trait]
+impl ConnectionInitializer for CrawlInitializer {
+    async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+        let mut handshake = KaspadHandshake::new(&router);
+        let mut addresses_route = router.subscribe(vec![KaspadMessagePayloadType::Addresses]);
+        // Messages which nodes send unsolicited right after the handshake are accepted and ignored, so that the
+        // connection is not closed by the router before the addresses arrive
+        let _ignored_route = router.subscribe(vec![
+            KaspadMessagePayloadType::RequestAddresses,
+            KaspadMessagePayloadType::Ping,
+            KaspadMessagePayloadType::InvRelayBlock,
+            KaspadMessagePayloadType::InvTransactions,
+        ]);
+        router.start();
+
+        let mut self_version = Version::new(None, self.node_id.into(), self.network_name.clone(), None, PROTOCOL_VERSION);
+        self_version.add_user_agent("kaspadns", version(), &[]);
+        let peer_version: Version = handshake.handshake(self_version.into()).await?.try_into()?;
+        if peer_version.network != self.network_name {
+            return Err(ProtocolError::WrongNetwork(self.network_name.clone(), peer_version.network));
+        }
+        handshake.exchange_ready_messages().await?;
+
+        router
+            .enqueue(make_message!(
+                Payload::RequestAddresses,
+                RequestAddressesMessage { include_all_subnetworks: false, subnetwork_id: None }
+            ))
+            .await?;
+        let msg = dequeue_with_timeout!(addresses_route, Payload::Addresses, ADDRESSES_TIMEOUT)?;
+        let address_list: Vec<(IpAddress, u16)> = msg.try_into()?;
+
+        let mut book = self.book.lock();
+        book.mark_success(router.net_address().into(), peer_version.protocol_version, peer_version.user_agent);
+        let added = book.add_addresses(address_list.into_iter().map(|(ip, port)| NetAddress::new(ip, port)), self.allow_non_routable);
+        debug!("Crawled {}: received {} new addresses", router, added);
+        Ok(())
+    }
+}
+
+/// Crawls the network by repeatedly testing the reachability of known addresses, learning new addresses on the way
+pub struct Crawler {
+    adaptor: Arc<Adaptor>,
+    book: Arc<Mutex<AddressBook>>,
+    max_concurrent: usize,
+}
+
+impl Crawler {
+    pub fn new(book: Arc<Mutex<AddressBook>>, network_name: String, max_concurrent: usize, allow_non_routable: bool) -> Self {
+        let initializer = Arc::new(CrawlInitializer { book: book.clone(), network_name, node_id: Uuid::new_v4(), allow_non_routable });
+        let adaptor = Adaptor::client_only(Hub::new(), initializer, Default::default());
+        Self { adaptor, book, max_concurrent }
+    }
+
+    /// Adds the addresses of `hosts`, each being either an IP with an optional port, or a host name (e.g. a DNS seeder)
+    /// to resolve. Addresses without an explicit port are assumed to use `default_port`
+    pub async fn bootstrap(&self, hosts: &[String], default_port: u16) {
+        for host in hosts {
+            let addresses = match host.parse::<ContextualNetAddress>() {
+                Ok(address) => vec![address.normalize(default_port)],
+                Err(_) => match lookup_host((host.as_str(), default_port)).await {
+                    Ok(addresses) => addresses.map(NetAddress::from).collect(),
+                    Err(err) => {
+                        warn!("Failed resolving {}: {}", host, err);
+                        continue;
+                    }
+                },
+            };
+            let added = self.book.lock().add_addresses(addresses, true);
+            info!("Bootstrapped {} addresses from {}", added, host);
+        }
+    }
+
+    async fn crawl(&self, address: NetAddress) {
+        match self.adaptor.connect_peer(address.to_string()).await {
+            Ok(peer_key) => self.adaptor.terminate(peer_key).await,
+            Err(err) => {
+                debug!("Failed crawling {}: {}", address, err);
+                self.book.lock().mark_failure(address);
+            }
+        }
+    }
+
+    /// Runs crawl rounds until `shutdown` is signaled, persisting the address book after each round
+    pub async fn run(self: Arc<Self>, mut shutdown: watch::Receiver<bool>) {
+        let mut interval = tokio::time::interval(CRAWL_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.changed() => break,
+            }
+
+            let due = self.book.lock().take_due(self.max_concurrent * 4);
+            if !due.is_empty() {
+                let count = due.len();
+                futures::stream::iter(due).for_each_concurrent(self.max_concurrent, |address| self.crawl(address)).await;
+                let mut book = self.book.lock();
+                let pruned = book.prune();
+                info!("Crawled {} addresses; {} good of {} known addresses ({} pruned)", count, book.good_count(), book.len(), pruned);
+                if let Err(err) = book.save() {
+                    warn!("Failed saving the address book: {}", err);
+                }
+            }
+        }
+        self.adaptor.close().await;
+    }
+}
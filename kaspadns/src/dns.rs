@@ -0,0 +1,270 @@
+//!
+//! Minimal DNS wire format support (RFC 1035), covering what a seeder needs:
+//! parsing single-question queries and building authoritative responses with
+//! A, AAAA and NS records.
+//!
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+use thiserror::Error;
+
+pub const HEADER_LEN: usize = 12;
+/// The maximum size of a DNS message over UDP without EDNS
+pub const MAX_UDP_MESSAGE_LEN: usize = 512;
+
+const MAX_LABEL_LEN: usize = 63;
+const MAX_NAME_LEN: usize = 255;
+
+const FLAG_RESPONSE: u16 = 1 << 15;
+const FLAG_AUTHORITATIVE: u16 = 1 << 10;
+const FLAG_TRUNCATED: u16 = 1 << 9;
+const FLAG_RECURSION_DESIRED: u16 = 1 << 8;
+const OPCODE_MASK: u16 = 0xf << 11;
+
+/// A pointer to the question name, which always directly follows the header
+const QUESTION_NAME_POINTER: [u8; 2] = [0xc0, HEADER_LEN as u8];
+
+pub const TYPE_A: u16 = 1;
+pub const TYPE_NS: u16 = 2;
+pub const TYPE_AAAA: u16 = 28;
+pub const CLASS_IN: u16 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ResponseCode {
+    NoError = 0,
+    FormatError = 1,
+    NotImplemented = 4,
+    Refused = 5,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DnsError {
+    #[error("message is too short")]
+    Truncated,
+
+    #[error("message is not a query")]
+    NotAQuery,
+
+    #[error("unsupported opcode {0}")]
+    UnsupportedOpcode(u16),
+
+    #[error("expected a single question but got {0}")]
+    QuestionCount(u16),
+
+    #[error("malformed question name")]
+    MalformedName,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Question {
+    /// The queried name in lowercase, without the trailing dot
+    pub name: String,
+    pub qtype: u16,
+    pub qclass: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub id: u16,
+    pub recursion_desired: bool,
+    pub question: Question,
+    /// The question section as received, echoed back in responses
+    raw_question: Vec<u8>,
+}
+
+/// Resource record data of answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordData {
+    A(Ipv4Addr),
+    Aaaa(Ipv6Addr),
+    Ns(String),
+}
+
+impl RecordData {
+    fn rtype(&self) -> u16 {
+        match self {
+            RecordData::A(_) => TYPE_A,
+            RecordData::Aaaa(_) => TYPE_AAAA,
+            RecordData::Ns(_) => TYPE_NS,
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            RecordData::A(ip) => ip.octets().to_vec(),
+            RecordData::Aaaa(ip) => ip.octets().to_vec(),
+            RecordData::Ns(name) => encode_name(name),
+        }
+    }
+}
+
+fn read_u16(buf: &[u8], offset: usize) -> Result<u16, DnsError> {
+    buf.get(offset..offset + 2).map(|b| u16::from_be_bytes([b[0], b[1]])).ok_or(DnsError::Truncated)
+}
+
+/// Parses a query holding exactly one question. Compression pointers are not expected within questions
+/// and are rejected
+pub fn parse_query(buf: &[u8]) -> Result<Query, DnsError> {
+    if buf.len() < HEADER_LEN {
+        return Err(DnsError::Truncated);
+    }
+    let id = read_u16(buf, 0)?;
+    let flags = read_u16(buf, 2)?;
+    if flags & FLAG_RESPONSE != 0 {
+        return Err(DnsError::NotAQuery);
+    }
+    let opcode = (flags & OPCODE_MASK) >> 11;
+    if opcode != 0 {
+        return Err(DnsError::UnsupportedOpcode(opcode));
+    }
+    let question_count = read_u16(buf, 4)?;
+    if question_count != 1 {
+        return Err(DnsError::QuestionCount(question_count));
+    }
+
+    let mut offset = HEADER_LEN;
+    let mut labels = Vec::new();
+    loop {
+        let len = *buf.get(offset).ok_or(DnsError::Truncated)? as usize;
+        offset += 1;
+        if len == 0 {
+            break;
+        }
+        if len > MAX_LABEL_LEN {
+            return Err(DnsError::MalformedName);
+        }
+        let label = buf.get(offset..offset + len).ok_or(DnsError::Truncated)?;
+        labels.push(String::from_utf8_lossy(label).to_ascii_lowercase());
+        offset += len;
+        if offset - HEADER_LEN > MAX_NAME_LEN {
+            return Err(DnsError::MalformedName);
+        }
+    }
+    let qtype = read_u16(buf, offset)?;
+    let qclass = read_u16(buf, offset + 2)?;
+    offset += 4;
+
+    Ok(Query {
+        id,
+        recursion_desired: flags & FLAG_RECURSION_DESIRED != 0,
+        question: Question { name: labels.join("."), qtype, qclass },
+        raw_question: buf[HEADER_LEN..offset].to_vec(),
+    })
+}
+
+/// Encodes `name` as a sequence of labels. Labels exceeding the maximum length are truncated
+pub fn encode_name(name: &str) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(name.len() + 2);
+    for label in name.trim_end_matches('.').split('.').filter(|label| !label.is_empty()) {
+        let label = &label.as_bytes()[..label.len().min(MAX_LABEL_LEN)];
+        encoded.push(label.len() as u8);
+        encoded.extend_from_slice(label);
+    }
+    encoded.push(0);
+    encoded
+}
+
+/// Builds an authoritative response to `query` with `answers` (all owned by the question name). Answers which do
+/// not fit within a UDP message are dropped and the response is marked as truncated
+pub fn build_response(query: &Query, code: ResponseCode, answers: &[RecordData], ttl: u32) -> Vec<u8> {
+    let mut flags = FLAG_RESPONSE | FLAG_AUTHORITATIVE | code as u16;
+    if query.recursion_desired {
+        flags |= FLAG_RECURSION_DESIRED;
+    }
+
+    let mut records = Vec::new();
+    let mut answer_count: u16 = 0;
+    for answer in answers {
+        let data = answer.encode();
+        let mut record = Vec::with_capacity(12 + data.len());
+        record.extend_from_slice(&QUESTION_NAME_POINTER);
+        record.extend_from_slice(&answer.rtype().to_be_bytes());
+        record.extend_from_slice(&CLASS_IN.to_be_bytes());
+        record.extend_from_slice(&ttl.to_be_bytes());
+        record.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        record.extend_from_slice(&data);
+        if HEADER_LEN + query.raw_question.len() + records.len() + record.len() > MAX_UDP_MESSAGE_LEN {
+            flags |= FLAG_TRUNCATED;
+            break;
+        }
+        records.extend_from_slice(&record);
+        answer_count += 1;
+    }
+
+    let mut response = Vec::with_capacity(HEADER_LEN + query.raw_question.len() + records.len());
+    response.extend_from_slice(&query.id.to_be_bytes());
+    response.extend_from_slice(&flags.to_be_bytes());
+    response.extend_from_slice(&1u16.to_be_bytes()); // Question count
+    response.extend_from_slice(&answer_count.to_be_bytes());
+    response.extend_from_slice(&0u16.to_be_bytes()); // Authority count
+    response.extend_from_slice(&0u16.to_be_bytes()); // Additional count
+    response.extend_from_slice(&query.raw_question);
+    response.extend_from_slice(&records);
+    response
+}
+
+/// Builds a header-only response with `code` to a message which could not be parsed as a query
+pub fn build_error_response(id: u16, code: ResponseCode) -> Vec<u8> {
+    let mut response = Vec::with_capacity(HEADER_LEN);
+    response.extend_from_slice(&id.to_be_bytes());
+    response.extend_from_slice(&(FLAG_RESPONSE | code as u16).to_be_bytes());
+    response.extend_from_slice(&[0; 8]);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_query(id: u16, name: &str, qtype: u16) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&id.to_be_bytes());
+        buf.extend_from_slice(&FLAG_RECURSION_DESIRED.to_be_bytes());
+        buf.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]);
+        buf.extend_from_slice(&encode_name(name));
+        buf.extend_from_slice(&qtype.to_be_bytes());
+        buf.extend_from_slice(&CLASS_IN.to_be_bytes());
+        buf
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = parse_query(&build_query(7, "Seed.Example.COM.", TYPE_AAAA)).unwrap();
+        assert_eq!(query.id, 7);
+        assert!(query.recursion_desired);
+        assert_eq!(query.question, Question { name: "seed.example.com".to_string(), qtype: TYPE_AAAA, qclass: CLASS_IN });
+
+        let mut buf = build_query(7, "seed.example.com", TYPE_A);
+        assert_eq!(parse_query(&buf[..buf.len() - 1]), Err(DnsError::Truncated));
+        buf[2] |= 0x80;
+        assert_eq!(parse_query(&buf), Err(DnsError::NotAQuery));
+        buf[2] = 0;
+        buf[5] = 2;
+        assert_eq!(parse_query(&buf), Err(DnsError::QuestionCount(2)));
+    }
+
+    #[test]
+    fn test_build_response() {
+        let request = build_query(42, "seed.example.com", TYPE_A);
+        let query = parse_query(&request).unwrap();
+        let answers = [RecordData::A(Ipv4Addr::new(1, 2, 3, 4)), RecordData::A(Ipv4Addr::new(5, 6, 7, 8))];
+        let response = build_response(&query, ResponseCode::NoError, &answers, 30);
+
+        assert_eq!(read_u16(&response, 0).unwrap(), 42);
+        let flags = read_u16(&response, 2).unwrap();
+        assert_eq!(flags & (FLAG_RESPONSE | FLAG_AUTHORITATIVE), FLAG_RESPONSE | FLAG_AUTHORITATIVE);
+        assert_eq!(flags & FLAG_TRUNCATED, 0);
+        assert_eq!(read_u16(&response, 6).unwrap(), 2);
+        // The question is echoed, followed by two records of 16 bytes each
+        assert_eq!(&response[HEADER_LEN..request.len()], &request[HEADER_LEN..]);
+        assert_eq!(response.len(), request.len() + 2 * 16);
+        assert_eq!(&response[response.len() - 4..], &[5, 6, 7, 8]);
+
+        // Answers beyond the UDP message size are dropped
+        let answers = vec![RecordData::Aaaa(Ipv6Addr::LOCALHOST); 32];
+        let response = build_response(&query, ResponseCode::NoError, &answers, 30);
+        assert!(response.len() <= MAX_UDP_MESSAGE_LEN);
+        assert_ne!(read_u16(&response, 2).unwrap() & FLAG_TRUNCATED, 0);
+        assert_eq!(read_u16(&response, 6).unwrap() as usize, (MAX_UDP_MESSAGE_LEN - request.len()) / 28);
+    }
+}
@@ -0,0 +1,129 @@
+use clap::Parser;
+use kaspa_consensus_core::{
+    config::params::Params,
+    network::{NetworkId, NetworkType},
+};
+use kaspa_core::{info, warn};
+use parking_lot::Mutex;
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+use tokio::sync::watch;
+
+mod book;
+mod crawler;
+mod dns;
+mod server;
+
+use book::AddressBook;
+use crawler::Crawler;
+use server::DnsServer;
+
+/// Kaspa DNS seeder: crawls the P2P network and serves the addresses of reachable nodes over DNS
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The host name served by this seeder (e.g. seed.example.com)
+    #[arg(long)]
+    host: String,
+
+    /// The host name of this seeder's nameserver, returned for NS queries
+    #[arg(long)]
+    nameserver: Option<String>,
+
+    /// The address of the DNS UDP listener
+    #[arg(long, default_value = "0.0.0.0:5354")]
+    listen: SocketAddr,
+
+    /// Addresses (IP with an optional port) or host names of nodes to start crawling from. Defaults to the DNS seeders of
+    /// the network if the address book is empty
+    #[arg(long = "peer")]
+    peers: Vec<String>,
+
+    /// Use the test network
+    #[arg(long, default_value_t = false)]
+    testnet: bool,
+
+    /// Testnet network suffix number
+    #[arg(long = "netsuffix", default_value_t = 10)]
+    testnet_suffix: u32,
+
+    /// Directory to store the address book in
+    #[arg(long)]
+    appdir: Option<PathBuf>,
+
+    /// The maximum number of nodes crawled concurrently
+    #[arg(long, default_value_t = 32)]
+    max_concurrent: usize,
+
+    /// Only nodes advertising at least this protocol version are served
+    #[arg(long, default_value_t = 5)]
+    min_protocol_version: u32,
+
+    /// Also crawl addresses which are not publicly routable (useful for testing on local networks)
+    #[arg(long, default_value_t = false)]
+    allow_non_routable: bool,
+
+    /// Logging level for all subsystems {off, error, warn, info, debug, trace}
+    ///  -- You may also specify `<subsystem>=<level>,<subsystem2>=<level>,...` to set the log level for individual subsystems
+    #[arg(long = "loglevel", default_value = "info")]
+    log_level: String,
+}
+
+fn default_app_dir() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    let home = std::env::var_os("USERPROFILE");
+    #[cfg(not(target_os = "windows"))]
+    let home = std::env::var_os("HOME");
+    PathBuf::from(home.unwrap_or_default()).join(".kaspadns")
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    kaspa_core::log::init_logger(None, &args.log_level);
+
+    let network_id = if args.testnet {
+        NetworkId::with_suffix(NetworkType::Testnet, args.testnet_suffix)
+    } else {
+        NetworkId::new(NetworkType::Mainnet)
+    };
+    let params: Params = network_id.into();
+    let app_dir = args.appdir.clone().unwrap_or_else(default_app_dir).join(network_id.to_prefixed());
+    std::fs::create_dir_all(&app_dir).unwrap_or_else(|err| panic!("failed creating {}: {err}", app_dir.display()));
+
+    let book = Arc::new(Mutex::new(AddressBook::load(app_dir.join("peers.json"))));
+    let crawler = Arc::new(Crawler::new(book.clone(), params.network_name(), args.max_concurrent, args.allow_non_routable));
+    let bootstrap_hosts = if !args.peers.is_empty() {
+        args.peers.clone()
+    } else if book.lock().is_empty() {
+        params.dns_seeders.iter().filter(|&&seeder| seeder != args.host).map(|seeder| seeder.to_string()).collect()
+    } else {
+        vec![]
+    };
+    crawler.bootstrap(&bootstrap_hosts, params.default_p2p_port()).await;
+    if book.lock().is_empty() {
+        warn!("No addresses to crawl, use --peer to specify initial nodes");
+    }
+
+    let server = DnsServer::bind(
+        args.listen,
+        book.clone(),
+        &args.host,
+        args.nameserver.clone(),
+        params.default_p2p_port(),
+        args.min_protocol_version,
+    )
+    .await
+    .unwrap_or_else(|err| panic!("failed binding the DNS listener on {}: {err}", args.listen));
+
+    let (shutdown_sender, shutdown_receiver) = watch::channel(false);
+    let crawler_task = tokio::spawn(crawler.run(shutdown_receiver.clone()));
+    let server_task = tokio::spawn(server.run(shutdown_receiver));
+
+    tokio::signal::ctrl_c().await.expect("failed listening for the shutdown signal");
+    info!("Shutting down...");
+    let _ = shutdown_sender.send(true);
+    let _ = tokio::join!(crawler_task, server_task);
+    if let Err(err) = book.lock().save() {
+        warn!("Failed saving the address book: {}", err);
+    }
+}
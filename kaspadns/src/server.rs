@@ -0,0 +1,114 @@
+use crate::{
+    book::AddressBook,
+    dns::{self, DnsError, Query, RecordData, ResponseCode, CLASS_IN, TYPE_A, TYPE_AAAA, TYPE_NS},
+};
+use kaspa_core::{debug, info, warn};
+use parking_lot::Mutex;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+use tokio::{net::UdpSocket, sync::watch};
+
+/// The maximum number of addresses returned in a single response
+const MAX_ANSWERS: usize = 8;
+
+/// The TTL of served records, in seconds
+const RECORD_TTL: u32 = 30;
+
+/// Serves A/AAAA queries for the seeder host name with random good node addresses
+pub struct DnsServer {
+    socket: UdpSocket,
+    book: Arc<Mutex<AddressBook>>,
+    /// The host name served, in lowercase and without the trailing dot
+    host: String,
+    nameserver: Option<String>,
+    default_port: u16,
+    min_protocol_version: u32,
+}
+
+impl DnsServer {
+    pub async fn bind(
+        listen: SocketAddr,
+        book: Arc<Mutex<AddressBook>>,
+        host: &str,
+        nameserver: Option<String>,
+        default_port: u16,
+        min_protocol_version: u32,
+    ) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(listen).await?;
+        info!("DNS server listening on {} for {}", socket.local_addr()?, host);
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        Ok(Self { socket, book, host, nameserver, default_port, min_protocol_version })
+    }
+
+    fn answers(&self, query: &Query) -> (ResponseCode, Vec<RecordData>) {
+        let question = &query.question;
+        if question.name != self.host {
+            return (ResponseCode::Refused, vec![]);
+        }
+        if question.qclass != CLASS_IN {
+            return (ResponseCode::NoError, vec![]);
+        }
+        let answers = match question.qtype {
+            TYPE_A | TYPE_AAAA => {
+                let ipv6 = question.qtype == TYPE_AAAA;
+                self.book
+                    .lock()
+                    .random_good_ips(self.default_port, self.min_protocol_version, ipv6, MAX_ANSWERS)
+                    .into_iter()
+                    .map(|ip| match ip {
+                        IpAddr::V4(ip) => RecordData::A(ip),
+                        IpAddr::V6(ip) => RecordData::Aaaa(ip),
+                    })
+                    .collect()
+            }
+            TYPE_NS => self.nameserver.iter().map(|nameserver| RecordData::Ns(nameserver.clone())).collect(),
+            _ => vec![],
+        };
+        (ResponseCode::NoError, answers)
+    }
+
+    fn handle(&self, request: &[u8]) -> Option<Vec<u8>> {
+        match dns::parse_query(request) {
+            Ok(query) => {
+                let (code, answers) = self.answers(&query);
+                debug!("DNS query {:?}: {:?} with {} answers", query.question, code, answers.len());
+                Some(dns::build_response(&query, code, &answers, RECORD_TTL))
+            }
+            // Responses and garbage are silently dropped
+            Err(DnsError::NotAQuery) | Err(DnsError::Truncated) => None,
+            Err(err) => {
+                debug!("Malformed DNS query: {}", err);
+                let code = if matches!(err, DnsError::UnsupportedOpcode(_)) {
+                    ResponseCode::NotImplemented
+                } else {
+                    ResponseCode::FormatError
+                };
+                Some(dns::build_error_response(u16::from_be_bytes([request[0], request[1]]), code))
+            }
+        }
+    }
+
+    /// Serves queries until `shutdown` is signaled
+    pub async fn run(self, mut shutdown: watch::Receiver<bool>) {
+        let mut buf = [0u8; dns::MAX_UDP_MESSAGE_LEN];
+        loop {
+            let (len, from) = tokio::select! {
+                res = self.socket.recv_from(&mut buf) => match res {
+                    Ok(res) => res,
+                    Err(err) => {
+                        warn!("DNS server receive error: {}", err);
+                        continue;
+                    }
+                },
+                _ = shutdown.changed() => break,
+            };
+            if let Some(response) = self.handle(&buf[..len]) {
+                if let Err(err) = self.socket.send_to(&response, from).await {
+                    debug!("Failed sending DNS response to {}: {}", from, err);
+                }
+            }
+        }
+    }
+}
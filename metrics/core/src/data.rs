@@ -72,6 +72,12 @@ impl MetricGroup {
                 Metric::NodeP2pBytesTxPerSecond,
                 Metric::NodeP2pBytesRx,
                 Metric::NodeP2pBytesRxPerSecond,
+                Metric::NodeP2pMessagesTx,
+                Metric::NodeP2pMessagesTxPerSecond,
+                Metric::NodeP2pMessagesRx,
+                Metric::NodeP2pMessagesRxPerSecond,
+                Metric::NodeP2pKnownBlocksHitRate,
+                Metric::NodeP2pKnownTransactionsHitRate,
                 Metric::NodeGrpcUserBytesTx,
                 Metric::NodeGrpcUserBytesTxPerSecond,
                 Metric::NodeGrpcUserBytesRx,
@@ -154,6 +160,12 @@ impl From<Metric> for MetricGroup {
             | Metric::NodeJsonBytesRxPerSecond
             | Metric::NodeP2pBytesTxPerSecond
             | Metric::NodeP2pBytesRxPerSecond
+            | Metric::NodeP2pMessagesTx
+            | Metric::NodeP2pMessagesRx
+            | Metric::NodeP2pMessagesTxPerSecond
+            | Metric::NodeP2pMessagesRxPerSecond
+            | Metric::NodeP2pKnownBlocksHitRate
+            | Metric::NodeP2pKnownTransactionsHitRate
             | Metric::NodeGrpcUserBytesTxPerSecond
             | Metric::NodeGrpcUserBytesRxPerSecond
             | Metric::NodeTotalBytesRxPerSecond
@@ -215,6 +227,14 @@ pub enum Metric {
     NodeP2pBytesTxPerSecond,
     NodeP2pBytesRxPerSecond,
 
+    NodeP2pMessagesTx,
+    NodeP2pMessagesRx,
+    NodeP2pMessagesTxPerSecond,
+    NodeP2pMessagesRxPerSecond,
+
+    NodeP2pKnownBlocksHitRate,
+    NodeP2pKnownTransactionsHitRate,
+
     NodeBorshBytesTx,
     NodeBorshBytesRx,
     NodeBorshBytesTxPerSecond,
@@ -321,6 +341,8 @@ impl Metric {
             Metric::NodeJsonBytesRx => as_data_size(f, si),
             Metric::NodeP2pBytesTx => as_data_size(f, si),
             Metric::NodeP2pBytesRx => as_data_size(f, si),
+            Metric::NodeP2pMessagesTx => f.trunc().separated_string(),
+            Metric::NodeP2pMessagesRx => f.trunc().separated_string(),
             Metric::NodeGrpcUserBytesTx => as_data_size(f, si),
             Metric::NodeGrpcUserBytesRx => as_data_size(f, si),
             Metric::NodeTotalBytesTx => as_data_size(f, si),
@@ -332,6 +354,10 @@ impl Metric {
             Metric::NodeJsonBytesRxPerSecond => format!("{}/s", as_kb(f, si, short)),
             Metric::NodeP2pBytesTxPerSecond => format!("{}/s", as_kb(f, si, short)),
             Metric::NodeP2pBytesRxPerSecond => format!("{}/s", as_kb(f, si, short)),
+            Metric::NodeP2pMessagesTxPerSecond => format!("{}/s", f.trunc().separated_string()),
+            Metric::NodeP2pMessagesRxPerSecond => format!("{}/s", f.trunc().separated_string()),
+            Metric::NodeP2pKnownBlocksHitRate => as_percentage(f),
+            Metric::NodeP2pKnownTransactionsHitRate => as_percentage(f),
             Metric::NodeGrpcUserBytesTxPerSecond => format!("{}/s", as_kb(f, si, short)),
             Metric::NodeGrpcUserBytesRxPerSecond => format!("{}/s", as_kb(f, si, short)),
             Metric::NodeTotalBytesTxPerSecond => format!("{}/s", as_kb(f, si, short)),
@@ -385,6 +411,8 @@ impl Metric {
             Metric::NodeJsonBytesRx => ("wRPC JSON Rx", "Json Rx"),
             Metric::NodeP2pBytesTx => ("p2p Tx", "p2p Tx"),
             Metric::NodeP2pBytesRx => ("p2p Rx", "p2p Rx"),
+            Metric::NodeP2pMessagesTx => ("p2p Messages Tx", "p2p Msg Tx"),
+            Metric::NodeP2pMessagesRx => ("p2p Messages Rx", "p2p Msg Rx"),
             Metric::NodeGrpcUserBytesTx => ("gRPC Tx", "gRPC Tx"),
             Metric::NodeGrpcUserBytesRx => ("gRPC Rx", "gRPC Rx"),
             Metric::NodeTotalBytesTx => ("Total Tx", "Total Tx"),
@@ -396,6 +424,10 @@ impl Metric {
             Metric::NodeJsonBytesRxPerSecond => ("wRPC JSON Rx/s", "JSON Rx/s"),
             Metric::NodeP2pBytesTxPerSecond => ("p2p Tx/s", "p2p Tx/s"),
             Metric::NodeP2pBytesRxPerSecond => ("p2p Rx/s", "p2p Rx/s"),
+            Metric::NodeP2pMessagesTxPerSecond => ("p2p Messages Tx/s", "p2p Msg Tx/s"),
+            Metric::NodeP2pMessagesRxPerSecond => ("p2p Messages Rx/s", "p2p Msg Rx/s"),
+            Metric::NodeP2pKnownBlocksHitRate => ("p2p Known Blocks Hit Rate", "p2p Blk Hits"),
+            Metric::NodeP2pKnownTransactionsHitRate => ("p2p Known Transactions Hit Rate", "p2p Tx Hits"),
             Metric::NodeGrpcUserBytesTxPerSecond => ("gRPC Tx/s", "gRPC Tx/s"),
             Metric::NodeGrpcUserBytesRxPerSecond => ("gRPC Rx/s", "gRPC Rx/s"),
             Metric::NodeTotalBytesTxPerSecond => ("Total Tx/s", "Total Tx/s"),
@@ -454,6 +486,12 @@ pub struct MetricsData {
     pub node_json_bytes_rx: u64,
     pub node_p2p_bytes_tx: u64,
     pub node_p2p_bytes_rx: u64,
+    pub node_p2p_messages_tx: u64,
+    pub node_p2p_messages_rx: u64,
+    pub node_p2p_known_blocks_hits: u64,
+    pub node_p2p_known_blocks_misses: u64,
+    pub node_p2p_known_transactions_hits: u64,
+    pub node_p2p_known_transactions_misses: u64,
     pub node_grpc_user_bytes_tx: u64,
     pub node_grpc_user_bytes_rx: u64,
     pub node_total_bytes_tx: u64,
@@ -495,6 +533,7 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
             process_metrics,
             storage_metrics,
             custom_metrics: _,
+            allocation_metrics: _,
         } = response; //rpc.get_metrics(true, true, true, true, true, false).await?;
 
         let consensus_metrics = consensus_metrics.ok_or(Error::MissingData("Consensus Metrics"))?;
@@ -537,6 +576,12 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
             node_json_bytes_rx: bandwidth_metrics.json_bytes_rx,
             node_p2p_bytes_tx: bandwidth_metrics.p2p_bytes_tx,
             node_p2p_bytes_rx: bandwidth_metrics.p2p_bytes_rx,
+            node_p2p_messages_tx: bandwidth_metrics.p2p_messages_tx,
+            node_p2p_messages_rx: bandwidth_metrics.p2p_messages_rx,
+            node_p2p_known_blocks_hits: bandwidth_metrics.p2p_known_blocks_hits,
+            node_p2p_known_blocks_misses: bandwidth_metrics.p2p_known_blocks_misses,
+            node_p2p_known_transactions_hits: bandwidth_metrics.p2p_known_transactions_hits,
+            node_p2p_known_transactions_misses: bandwidth_metrics.p2p_known_transactions_misses,
             node_grpc_user_bytes_tx: bandwidth_metrics.grpc_bytes_tx,
             node_grpc_user_bytes_rx: bandwidth_metrics.grpc_bytes_rx,
 
@@ -597,6 +642,8 @@ pub struct MetricsSnapshot {
     pub node_json_bytes_rx: f64,
     pub node_p2p_bytes_tx: f64,
     pub node_p2p_bytes_rx: f64,
+    pub node_p2p_messages_tx: f64,
+    pub node_p2p_messages_rx: f64,
     pub node_grpc_user_bytes_tx: f64,
     pub node_grpc_user_bytes_rx: f64,
     pub node_total_bytes_tx: f64,
@@ -608,6 +655,11 @@ pub struct MetricsSnapshot {
     pub node_json_bytes_rx_per_second: f64,
     pub node_p2p_bytes_tx_per_second: f64,
     pub node_p2p_bytes_rx_per_second: f64,
+    pub node_p2p_messages_tx_per_second: f64,
+    pub node_p2p_messages_rx_per_second: f64,
+    /// Percentage of relayed inventory skipped during the snapshot interval since peers already knew it
+    pub node_p2p_known_blocks_hit_rate: f64,
+    pub node_p2p_known_transactions_hit_rate: f64,
     pub node_grpc_user_bytes_tx_per_second: f64,
     pub node_grpc_user_bytes_rx_per_second: f64,
     pub node_total_bytes_tx_per_second: f64,
@@ -663,6 +715,8 @@ impl MetricsSnapshot {
             Metric::NodeJsonBytesRx => self.node_json_bytes_rx,
             Metric::NodeP2pBytesTx => self.node_p2p_bytes_tx,
             Metric::NodeP2pBytesRx => self.node_p2p_bytes_rx,
+            Metric::NodeP2pMessagesTx => self.node_p2p_messages_tx,
+            Metric::NodeP2pMessagesRx => self.node_p2p_messages_rx,
             Metric::NodeGrpcUserBytesTx => self.node_grpc_user_bytes_tx,
             Metric::NodeGrpcUserBytesRx => self.node_grpc_user_bytes_rx,
             Metric::NodeTotalBytesTx => self.node_total_bytes_tx,
@@ -674,6 +728,10 @@ impl MetricsSnapshot {
             Metric::NodeJsonBytesRxPerSecond => self.node_json_bytes_rx_per_second,
             Metric::NodeP2pBytesTxPerSecond => self.node_p2p_bytes_tx_per_second,
             Metric::NodeP2pBytesRxPerSecond => self.node_p2p_bytes_rx_per_second,
+            Metric::NodeP2pMessagesTxPerSecond => self.node_p2p_messages_tx_per_second,
+            Metric::NodeP2pMessagesRxPerSecond => self.node_p2p_messages_rx_per_second,
+            Metric::NodeP2pKnownBlocksHitRate => self.node_p2p_known_blocks_hit_rate,
+            Metric::NodeP2pKnownTransactionsHitRate => self.node_p2p_known_transactions_hit_rate,
             Metric::NodeGrpcUserBytesTxPerSecond => self.node_grpc_user_bytes_tx_per_second,
             Metric::NodeGrpcUserBytesRxPerSecond => self.node_grpc_user_bytes_rx_per_second,
             Metric::NodeTotalBytesTxPerSecond => self.node_total_bytes_tx_per_second,
@@ -714,6 +772,18 @@ fn per_sec(a: u64, b: u64, duration_millis: f64) -> f64 {
     b.checked_sub(a).unwrap_or_default() as f64 * 1000. / duration_millis
 }
 
+/// Percentage of hits out of the lookups made between the two (hits, misses) samples, NaN if there were none
+#[inline(always)]
+fn hit_rate((hits_a, misses_a): (u64, u64), (hits_b, misses_b): (u64, u64)) -> f64 {
+    let hits = hits_b.saturating_sub(hits_a) as f64;
+    let misses = misses_b.saturating_sub(misses_a) as f64;
+    if hits + misses == 0. {
+        f64::NAN
+    } else {
+        hits * 100. / (hits + misses)
+    }
+}
+
 impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
     fn from((a, b): (&MetricsData, &MetricsData)) -> Self {
         let duration_millis = b.unixtime_millis - a.unixtime_millis;
@@ -726,6 +796,16 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
         let node_json_bytes_rx_per_second = per_sec(a.node_json_bytes_rx, b.node_json_bytes_rx, duration_millis);
         let node_p2p_bytes_tx_per_second = per_sec(a.node_p2p_bytes_tx, b.node_p2p_bytes_tx, duration_millis);
         let node_p2p_bytes_rx_per_second = per_sec(a.node_p2p_bytes_rx, b.node_p2p_bytes_rx, duration_millis);
+        let node_p2p_messages_tx_per_second = per_sec(a.node_p2p_messages_tx, b.node_p2p_messages_tx, duration_millis);
+        let node_p2p_messages_rx_per_second = per_sec(a.node_p2p_messages_rx, b.node_p2p_messages_rx, duration_millis);
+        let node_p2p_known_blocks_hit_rate = hit_rate(
+            (a.node_p2p_known_blocks_hits, a.node_p2p_known_blocks_misses),
+            (b.node_p2p_known_blocks_hits, b.node_p2p_known_blocks_misses),
+        );
+        let node_p2p_known_transactions_hit_rate = hit_rate(
+            (a.node_p2p_known_transactions_hits, a.node_p2p_known_transactions_misses),
+            (b.node_p2p_known_transactions_hits, b.node_p2p_known_transactions_misses),
+        );
         let node_grpc_user_bytes_tx_per_second = per_sec(a.node_grpc_user_bytes_tx, b.node_grpc_user_bytes_tx, duration_millis);
         let node_grpc_user_bytes_rx_per_second = per_sec(a.node_grpc_user_bytes_rx, b.node_grpc_user_bytes_rx, duration_millis);
         let node_total_bytes_tx_per_second = per_sec(a.node_total_bytes_tx, b.node_total_bytes_tx, duration_millis);
@@ -760,6 +840,8 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
             node_json_bytes_rx: b.node_json_bytes_rx as f64,
             node_p2p_bytes_tx: b.node_p2p_bytes_tx as f64,
             node_p2p_bytes_rx: b.node_p2p_bytes_rx as f64,
+            node_p2p_messages_tx: b.node_p2p_messages_tx as f64,
+            node_p2p_messages_rx: b.node_p2p_messages_rx as f64,
             node_grpc_user_bytes_tx: b.node_grpc_user_bytes_tx as f64,
             node_grpc_user_bytes_rx: b.node_grpc_user_bytes_rx as f64,
             node_total_bytes_tx: b.node_total_bytes_tx as f64,
@@ -771,6 +853,10 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
             node_json_bytes_rx_per_second,
             node_p2p_bytes_tx_per_second,
             node_p2p_bytes_rx_per_second,
+            node_p2p_messages_tx_per_second,
+            node_p2p_messages_rx_per_second,
+            node_p2p_known_blocks_hit_rate,
+            node_p2p_known_transactions_hit_rate,
             node_grpc_user_bytes_tx_per_second,
             node_grpc_user_bytes_rx_per_second,
             node_total_bytes_tx_per_second,
@@ -885,6 +971,15 @@ pub fn format_as_float(f: f64, short: bool) -> String {
     }
 }
 
+/// Format supplied value as a percentage with 2 decimal places, or `---` if it is not available (NaN).
+pub fn as_percentage(f: f64) -> String {
+    if f.is_nan() {
+        "---".to_string()
+    } else {
+        format!("{:1.2}%", f)
+    }
+}
+
 /// Format supplied value as a float with 2 decimal places.
 fn format_with_precision(f: f64) -> String {
     if f.fract() < 0.01 {
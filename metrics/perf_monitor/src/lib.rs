@@ -21,6 +21,7 @@ use crate::{counters::Counters, error::Error};
 pub mod builder;
 pub mod counters;
 pub mod error;
+pub mod watchdog;
 
 pub const SERVICE_NAME: &str = "perf-monitor";
 
@@ -0,0 +1,118 @@
+//!
+//! A watchdog keeping the resident memory of the process within a budget.
+//!
+//! The watchdog periodically samples the resident set size (RSS) of the process and, whenever it exceeds the configured
+//! budget, asks the registered caches (see [`ShrinkableRegistry`]) to evict a share of their items proportional to the
+//! excess. Note that the allocator does not necessarily return freed memory to the OS right away, so the RSS might keep
+//! exceeding the budget for a few samples, in which case the caches are shrunk further.
+//!
+
+use crate::error::Error;
+use kaspa_core::{
+    error,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, warn,
+};
+use kaspa_utils::mem_pressure::ShrinkableRegistry;
+use std::{sync::Arc, time::Duration};
+use workflow_perf_monitor::mem::{get_process_memory_info, ProcessMemoryInfo};
+
+pub const WATCHDOG_SERVICE_NAME: &str = "memory-watchdog";
+
+/// The default interval between two samples of the resident set size
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Bounds of the share of cached items evicted at once
+const MIN_SHRINK_FRACTION: f64 = 0.05;
+const MAX_SHRINK_FRACTION: f64 = 0.5;
+
+pub struct MemoryWatchdog {
+    tick_service: Arc<TickService>,
+    sample_interval: Duration,
+    /// Memory budget in bytes
+    memory_budget: u64,
+    registry: Arc<ShrinkableRegistry>,
+}
+
+impl MemoryWatchdog {
+    pub fn new(
+        tick_service: Arc<TickService>,
+        sample_interval: Duration,
+        memory_budget: u64,
+        registry: Arc<ShrinkableRegistry>,
+    ) -> Self {
+        Self { tick_service, sample_interval, memory_budget, registry }
+    }
+
+    /// Returns the share of cached items to evict when the resident set size is `resident_set_size`, or `None` if it is
+    /// within the budget. The share is the relative excess over the budget, bounded to reasonable limits
+    pub fn shrink_fraction(&self, resident_set_size: u64) -> Option<f64> {
+        (resident_set_size > self.memory_budget).then(|| {
+            let excess = (resident_set_size - self.memory_budget) as f64 / self.memory_budget.max(1) as f64;
+            excess.clamp(MIN_SHRINK_FRACTION, MAX_SHRINK_FRACTION)
+        })
+    }
+
+    pub async fn worker(&self) -> Result<(), Error> {
+        while let TickReason::Wakeup = self.tick_service.tick(self.sample_interval).await {
+            let ProcessMemoryInfo { resident_set_size, .. } = get_process_memory_info()?;
+            if let Some(fraction) = self.shrink_fraction(resident_set_size) {
+                let evicted = self.registry.shrink(fraction);
+                warn!(
+                    "[{WATCHDOG_SERVICE_NAME}] resident memory of {} MB exceeds the budget of {} MB, evicted {:.0}% of the cached items: {}",
+                    resident_set_size / 1_000_000,
+                    self.memory_budget / 1_000_000,
+                    fraction * 100.0,
+                    evicted.iter().map(|(name, count)| format!("{count} from {name}")).collect::<Vec<_>>().join(", ")
+                );
+            }
+        }
+        trace!("{WATCHDOG_SERVICE_NAME} worker exiting");
+        Ok(())
+    }
+}
+
+// service trait implementation for MemoryWatchdog
+impl AsyncService for MemoryWatchdog {
+    fn ident(self: Arc<Self>) -> &'static str {
+        WATCHDOG_SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await.unwrap_or_else(|e| {
+                error!("worker error: {e:?}");
+            });
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", WATCHDOG_SERVICE_NAME);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", WATCHDOG_SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrink_fraction() {
+        let watchdog = MemoryWatchdog::new(Arc::new(TickService::new()), DEFAULT_SAMPLE_INTERVAL, 1_000, Default::default());
+        assert_eq!(watchdog.shrink_fraction(900), None);
+        assert_eq!(watchdog.shrink_fraction(1_000), None);
+        assert_eq!(watchdog.shrink_fraction(1_001), Some(MIN_SHRINK_FRACTION));
+        assert_eq!(watchdog.shrink_fraction(1_200), Some(0.2));
+        assert_eq!(watchdog.shrink_fraction(4_000), Some(MAX_SHRINK_FRACTION));
+    }
+}
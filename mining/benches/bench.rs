@@ -225,6 +225,15 @@ pub fn bench_mempool_selectors(c: &mut Criterion) {
             });
         }
 
+        group.bench_function(format!("greedy selector ({})", len), |b| {
+            b.iter(|| {
+                black_box({
+                    let mut selector = frontier.build_greedy_selector(&Policy::new(500_000));
+                    selector.select_transactions().iter().map(|k| k.gas).sum::<u64>()
+                })
+            })
+        });
+
         group.bench_function(format!("dynamic selector ({})", len), |b| {
             b.iter(|| {
                 black_box({
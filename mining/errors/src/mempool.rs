@@ -1,5 +1,6 @@
 use kaspa_consensus_core::{
     errors::tx::TxRuleError,
+    subnets::SubnetworkId,
     tx::{TransactionId, TransactionOutpoint},
 };
 use thiserror::Error;
@@ -132,6 +133,12 @@ pub enum NonStandardError {
 
     #[error("transaction input #{1} has {2} signature operations which is more than the allowed max amount of {3}")]
     RejectSignatureCount(TransactionId, usize, u64, u8),
+
+    #[error("transaction belongs to the non-native subnetwork {1}")]
+    RejectSubnetwork(TransactionId, SubnetworkId),
+
+    #[error("transaction payload of {1} bytes is larger than max allowed size of {2}")]
+    RejectPayloadSize(TransactionId, usize, usize),
 }
 
 impl NonStandardError {
@@ -147,6 +154,8 @@ impl NonStandardError {
             NonStandardError::RejectInputScriptClass(id, _) => id,
             NonStandardError::RejectInsufficientFee(id, _, _) => id,
             NonStandardError::RejectSignatureCount(id, _, _, _) => id,
+            NonStandardError::RejectSubnetwork(id, _) => id,
+            NonStandardError::RejectPayloadSize(id, _, _) => id,
         }
     }
 }
@@ -7,11 +7,24 @@ use kaspa_consensus_core::{
 };
 use kaspa_core::time::{unix_now, Stopwatch};
 
-pub(crate) struct BlockTemplateBuilder {}
+pub(crate) struct BlockTemplateBuilder {
+    /// Node-configured extra data appended to the coinbase payload extra data requested by the miner
+    coinbase_extra_data: Vec<u8>,
+}
 
 impl BlockTemplateBuilder {
-    pub(crate) fn new() -> Self {
-        Self {}
+    pub(crate) fn new(coinbase_extra_data: Vec<u8>) -> Self {
+        Self { coinbase_extra_data }
+    }
+
+    /// Returns the miner data actually used for building the coinbase, i.e., the requested miner data
+    /// with the configured coinbase extra data appended
+    pub(crate) fn miner_data(&self, miner_data: &MinerData) -> MinerData {
+        if self.coinbase_extra_data.is_empty() {
+            return miner_data.clone();
+        }
+        let extra_data = miner_data.extra_data.iter().chain(self.coinbase_extra_data.iter()).copied().collect();
+        MinerData::new(miner_data.script_public_key.clone(), extra_data)
     }
 
     /// BuildBlockTemplate creates a block template for a miner to consume
@@ -104,6 +117,8 @@ impl BlockTemplateBuilder {
             // The last output is always the coinbase red blocks reward
             coinbase_tx.outputs.last_mut().unwrap().script_public_key = new_miner_data.script_public_key.clone();
         }
+        // Recompute the coinbase ID (which commits to the payload) and drop its cached hashes
+        coinbase_tx.finalize();
         // Update the hash merkle root according to the modified transactions
         block_template.block.header.hash_merkle_root =
             consensus.calc_transaction_hash_merkle_root(&block_template.block.transactions, block_template.block.header.daa_score);
@@ -9,10 +9,11 @@ use crate::{
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction, validate_mempool_transactions_in_parallel,
         },
-        tx::{Orphan, Priority, RbfPolicy},
+        tx::{Orphan, Priority, RbfPolicy, TransactionPackage},
         Mempool,
     },
     model::{
+        local_tx::LocalTransaction,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         topological_sort::IntoIterTopologically,
         tx_insert::TransactionInsertion,
@@ -29,11 +30,16 @@ use kaspa_consensus_core::{
     block::{BlockTemplate, TemplateBuildMode, TemplateTransactionSelector},
     coinbase::MinerData,
     errors::{block::RuleError as BlockRuleError, tx::TxRuleError},
+    subnets::SubnetworkId,
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutput},
 };
 use kaspa_consensusmanager::{spawn_blocking, ConsensusProxy};
 use kaspa_core::{debug, error, info, time::Stopwatch, warn};
 use kaspa_mining_errors::{manager::MiningManagerError, mempool::RuleError};
+use kaspa_utils::{
+    alloc_tracking::{AllocScope, AllocSubsystem},
+    mem_pressure::Shrinkable,
+};
 use parking_lot::RwLock;
 use std::sync::Arc;
 use tokio::sync::mpsc::UnboundedSender;
@@ -63,14 +69,16 @@ impl MiningManager {
         max_block_mass: u64,
         ram_scale: f64,
         cache_lifetime: Option<u64>,
+        coinbase_extra_data: Vec<u8>,
         counters: Arc<MiningCounters>,
     ) -> Self {
-        let config =
-            Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass).apply_ram_scale(ram_scale);
+        let config = Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass)
+            .apply_ram_scale(ram_scale)
+            .with_coinbase_extra_data(coinbase_extra_data);
         Self::with_config(config, cache_lifetime, counters)
     }
 
-    pub(crate) fn with_config(config: Config, cache_lifetime: Option<u64>, counters: Arc<MiningCounters>) -> Self {
+    pub fn with_config(config: Config, cache_lifetime: Option<u64>, counters: Arc<MiningCounters>) -> Self {
         let config = Arc::new(config);
         let mempool = RwLock::new(Mempool::new(config.clone(), counters.clone()));
         let block_template_cache = BlockTemplateCache::new(cache_lifetime);
@@ -78,6 +86,8 @@ impl MiningManager {
     }
 
     pub fn get_block_template(&self, consensus: &dyn ConsensusApi, miner_data: &MinerData) -> MiningManagerResult<BlockTemplate> {
+        let block_template_builder = self.block_template_builder();
+        let miner_data = &block_template_builder.miner_data(miner_data);
         let virtual_state_approx_id = consensus.get_virtual_state_approx_id();
         let mut cache_lock = self.block_template_cache.lock(virtual_state_approx_id);
         let immutable_template = cache_lock.get_immutable_cached_template();
@@ -108,7 +118,6 @@ impl MiningManager {
             attempts += 1;
 
             let selector = self.build_selector();
-            let block_template_builder = BlockTemplateBuilder::new();
             let build_mode = if attempts < self.config.maximum_build_block_template_attempts {
                 TemplateBuildMode::Standard
             } else {
@@ -261,9 +270,8 @@ impl MiningManager {
         self.block_template_cache.clear();
     }
 
-    #[cfg(test)]
     pub(crate) fn block_template_builder(&self) -> BlockTemplateBuilder {
-        BlockTemplateBuilder::new()
+        BlockTemplateBuilder::new(self.config.coinbase_extra_data.clone())
     }
 
     /// validate_and_insert_transaction validates the given transaction, and
@@ -285,6 +293,7 @@ impl MiningManager {
         orphan: Orphan,
         rbf_policy: RbfPolicy,
     ) -> MiningManagerResult<TransactionInsertion> {
+        let _scope = AllocScope::enter(AllocSubsystem::Mempool);
         self.validate_and_insert_mutable_transaction(consensus, MutableTransaction::from_tx(transaction), priority, orphan, rbf_policy)
     }
 
@@ -409,6 +418,7 @@ impl MiningManager {
         orphan: Orphan,
         rbf_policy: RbfPolicy,
     ) -> Vec<MiningManagerResult<Arc<Transaction>>> {
+        let _scope = AllocScope::enter(AllocSubsystem::Mempool);
         const TRANSACTION_CHUNK_SIZE: usize = 250;
 
         // The capacity used here may be exceeded since accepted transactions may unorphan other transactions.
@@ -533,6 +543,11 @@ impl MiningManager {
         self.mempool.read().get_transaction(transaction_id, query)
     }
 
+    /// Returns the in-mempool ancestors and descendants statistics of a transaction held by the transaction pool.
+    pub fn get_transaction_package(&self, transaction_id: &TransactionId) -> Option<TransactionPackage> {
+        self.mempool.read().get_transaction_package(transaction_id)
+    }
+
     /// Returns whether the mempool holds this transaction in any form.
     pub fn has_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> bool {
         self.mempool.read().has_transaction(transaction_id, query)
@@ -578,12 +593,18 @@ impl MiningManager {
         self.mempool.read().transaction_count(query)
     }
 
+    /// Returns the transactions submitted to this node which are still pending acceptance in the mempool.
+    pub fn get_local_transactions(&self) -> Vec<LocalTransaction> {
+        self.mempool.read().get_local_transactions()
+    }
+
     pub fn handle_new_block_transactions(
         &self,
         consensus: &dyn ConsensusApi,
         block_daa_score: u64,
         block_transactions: &[Transaction],
     ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
+        let _scope = AllocScope::enter(AllocSubsystem::Mempool);
         // TODO: should use tx acceptance data to verify that new block txs are actually accepted into virtual state.
         // TODO: avoid returning a result from this function (and the underlying function). Any possible error is a
         // problem of the internal implementation and unrelated to the caller
@@ -597,40 +618,48 @@ impl MiningManager {
         Ok(accepted_transactions)
     }
 
-    pub fn expire_low_priority_transactions(&self, consensus: &dyn ConsensusApi) {
+    /// Removes the expired transactions from the orphan pool and from the mempool, along with their redeemers.
+    /// Returns the ids of all the evicted transactions.
+    pub fn expire_transactions(&self, consensus: &dyn ConsensusApi) -> Vec<TransactionId> {
+        let _scope = AllocScope::enter(AllocSubsystem::Mempool);
         // very fine-grained write locks on mempool
-        debug!("<> Expiring low priority transactions...");
+        debug!("<> Expiring transactions...");
 
         // orphan pool
-        if let Err(err) = self.mempool.write().expire_orphan_low_priority_transactions(consensus) {
-            warn!("Failed to expire transactions from orphan pool: {}", err);
-        }
+        let mut evicted_transactions = match self.mempool.write().expire_orphan_transactions(consensus) {
+            Ok(expired_orphans) => expired_orphans,
+            Err(err) => {
+                warn!("Failed to expire transactions from orphan pool: {}", err);
+                vec![]
+            }
+        };
 
         // accepted transaction cache
         self.mempool.write().expire_accepted_transactions(consensus);
 
         // mempool
-        let expired_low_priority_transactions = self.mempool.write().collect_expired_low_priority_transactions(consensus);
-        for chunk in &expired_low_priority_transactions.iter().chunks(24) {
+        let expired_transactions = self.mempool.write().collect_expired_transactions(consensus);
+        for chunk in &expired_transactions.iter().chunks(24) {
             let mut mempool = self.mempool.write();
-            chunk.into_iter().for_each(|tx| {
-                if let Err(err) = mempool.remove_transaction(tx, true, TxRemovalReason::Muted, "") {
-                    warn!("Failed to remove transaction {} from mempool: {}", tx, err);
-                }
+            chunk.into_iter().for_each(|tx| match mempool.remove_transaction(tx, true, TxRemovalReason::Muted, "") {
+                Ok(removed) => evicted_transactions.extend(removed),
+                Err(err) => warn!("Failed to remove transaction {} from mempool: {}", tx, err),
             });
         }
-        match expired_low_priority_transactions.len() {
+        match expired_transactions.len() {
             0 => {}
-            1 => debug!("Removed transaction ({}) {}", TxRemovalReason::Expired, expired_low_priority_transactions[0]),
-            n => debug!("Removed {} transactions ({}): {}...", n, TxRemovalReason::Expired, expired_low_priority_transactions[0]),
+            1 => debug!("Removed transaction ({}) {}", TxRemovalReason::Expired, expired_transactions[0]),
+            n => debug!("Removed {} transactions ({}): {}...", n, TxRemovalReason::Expired, expired_transactions[0]),
         }
+        evicted_transactions
     }
 
     pub fn revalidate_high_priority_transactions(
         &self,
         consensus: &dyn ConsensusApi,
-        transaction_ids_sender: UnboundedSender<Vec<TransactionId>>,
+        transaction_ids_sender: UnboundedSender<Vec<(TransactionId, SubnetworkId)>>,
     ) {
+        let _scope = AllocScope::enter(AllocSubsystem::Mempool);
         const TRANSACTION_CHUNK_SIZE: usize = 1000;
 
         // read lock on mempool
@@ -719,6 +748,7 @@ impl MiningManager {
                 let transaction_id = transaction.id();
                 match validation_result {
                     Ok(()) => {
+                        let subnetwork_id = transaction.tx.subnetwork_id.clone();
                         // Only consider transactions still being in the mempool since during the validation some might have been removed.
                         if mempool.update_revalidated_transaction(transaction) {
                             // A following transaction should not remove this one from the pool since we process in a topological order.
@@ -727,7 +757,7 @@ impl MiningManager {
                             // high-priority transactions, we might wrongfully return as valid the id of a removed transaction.
                             // However, as only consequence, said transaction would then be advertised to registered peers and not be
                             // provided upon request.
-                            valid_ids.push(transaction_id);
+                            valid_ids.push((transaction_id, subnetwork_id));
                             valid += 1;
                         } else {
                             other += 1;
@@ -836,6 +866,19 @@ impl MiningManager {
     pub(crate) fn get_estimated_size(&self) -> usize {
         self.mempool.read().get_estimated_size()
     }
+
+    #[cfg(test)]
+    pub(crate) fn update_revalidated_transaction(&self, transaction: MutableTransaction) -> bool {
+        self.mempool.write().update_revalidated_transaction(transaction)
+    }
+}
+
+impl Shrinkable for MiningManager {
+    /// Evicts the lowest fee rate low-priority transactions from the mempool
+    fn shrink(&self, fraction: f64) -> usize {
+        let _scope = AllocScope::enter(AllocSubsystem::Mempool);
+        self.mempool.write().shrink(fraction).len()
+    }
 }
 
 /// Async proxy for the mining manager
@@ -922,14 +965,14 @@ impl MiningManagerProxy {
             .await
     }
 
-    pub async fn expire_low_priority_transactions(self, consensus: &ConsensusProxy) {
-        consensus.clone().spawn_blocking(move |c| self.inner.expire_low_priority_transactions(c)).await;
+    pub async fn expire_transactions(self, consensus: &ConsensusProxy) -> Vec<TransactionId> {
+        consensus.clone().spawn_blocking(move |c| self.inner.expire_transactions(c)).await
     }
 
     pub async fn revalidate_high_priority_transactions(
         self,
         consensus: &ConsensusProxy,
-        transaction_ids_sender: UnboundedSender<Vec<TransactionId>>,
+        transaction_ids_sender: UnboundedSender<Vec<(TransactionId, SubnetworkId)>>,
     ) {
         consensus.clone().spawn_blocking(move |c| self.inner.revalidate_high_priority_transactions(c, transaction_ids_sender)).await;
     }
@@ -941,6 +984,11 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.get_transaction(&transaction_id, query)).await.unwrap()
     }
 
+    /// Returns the in-mempool ancestors and descendants statistics of a transaction held by the transaction pool.
+    pub async fn get_transaction_package(self, transaction_id: TransactionId) -> Option<TransactionPackage> {
+        spawn_blocking(move || self.inner.get_transaction_package(&transaction_id)).await.unwrap()
+    }
+
     /// Returns whether the mempool holds this transaction in any form.
     pub async fn has_transaction(self, transaction_id: TransactionId, query: TransactionQuery) -> bool {
         spawn_blocking(move || self.inner.has_transaction(&transaction_id, query)).await.unwrap()
@@ -954,6 +1002,11 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.get_all_transactions(query)).await.unwrap()
     }
 
+    /// Returns the transactions submitted to this node which are still pending acceptance in the mempool.
+    pub async fn get_local_transactions(self) -> Vec<LocalTransaction> {
+        spawn_blocking(move || self.inner.get_local_transactions()).await.unwrap()
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
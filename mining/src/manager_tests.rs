@@ -5,10 +5,10 @@ mod tests {
         errors::{MiningManagerError, MiningManagerResult},
         manager::MiningManager,
         mempool::{
-            config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
+            config::{Config, TransactionSelectionStrategy, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
             errors::RuleError,
             model::frontier::selectors::TakeAllSelector,
-            tx::{Orphan, Priority, RbfPolicy},
+            tx::{Orphan, PackageStats, Priority, RbfPolicy},
         },
         model::{tx_insert::TransactionInsertion, tx_query::TransactionQuery},
         testutils::consensus_mock::ConsensusMock,
@@ -35,7 +35,7 @@ mod tests {
         pay_to_address_script, pay_to_script_hash_signature_script,
         test_helpers::{create_transaction, create_transaction_with_change, op_true_script},
     };
-    use kaspa_utils::mem_size::MemSizeEstimator;
+    use kaspa_utils::{mem_pressure::Shrinkable, mem_size::MemSizeEstimator};
     use std::{iter::once, sync::Arc};
     use tokio::sync::mpsc::{error::TryRecvError, unbounded_channel};
 
@@ -994,6 +994,80 @@ mod tests {
         }
     }
 
+    /// test_expire_transactions verifies that transactions and orphans are evicted from the mempool once their expiry window,
+    /// which depends on their priority, has passed, and that high-priority transactions never expire if their window is zero.
+    /// It also verifies that the high-priority transactions are reported as local ones until they expire.
+    #[test]
+    fn test_expire_transactions() {
+        const EXPIRE_INTERVAL: u64 = 10;
+        for high_priority_expire_interval in [2 * EXPIRE_INTERVAL, 0] {
+            let consensus = Arc::new(ConsensusMock::new());
+            let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+            config.transaction_expire_interval_daa_score = EXPIRE_INTERVAL;
+            config.orphan_expire_interval_daa_score = EXPIRE_INTERVAL;
+            config.high_priority_transaction_expire_interval_daa_score = high_priority_expire_interval;
+            // Scan on every call
+            config.transaction_expire_scan_interval_daa_score = 0;
+            config.transaction_expire_scan_interval_milliseconds = 0;
+            config.orphan_expire_scan_interval_daa_score = 0;
+            let counters = Arc::new(MiningCounters::default());
+            let mining_manager = MiningManager::with_config(config, None, counters);
+
+            // Insert a low and a high priority transaction into the mempool and a low and a high priority orphan
+            let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, 4);
+            let inserted = [
+                (&parent_txs[0], Priority::Low),
+                (&parent_txs[1], Priority::High),
+                (&child_txs[2], Priority::Low),
+                (&child_txs[3], Priority::High),
+            ];
+            for (tx, priority) in inserted {
+                mining_manager
+                    .validate_and_insert_transaction(consensus.as_ref(), tx.clone(), priority, Orphan::Allowed, RbfPolicy::Forbidden)
+                    .unwrap();
+            }
+            assert!(mining_manager.has_transaction(&child_txs[2].id(), TransactionQuery::OrphansOnly));
+
+            // Only the high priority transactions are reported as local ones, along with the DAA score they expire at
+            let local_transactions = mining_manager.get_local_transactions();
+            assert_eq!(
+                local_transactions.iter().map(|x| (x.transaction.id(), x.is_orphan)).sorted().collect_vec(),
+                [(parent_txs[1].id(), false), (child_txs[3].id(), true)].into_iter().sorted().collect_vec()
+            );
+            for local_transaction in local_transactions {
+                let expected = (high_priority_expire_interval > 0)
+                    .then_some(local_transaction.added_at_daa_score + high_priority_expire_interval);
+                assert_eq!(local_transaction.expire_daa_score, expected);
+            }
+
+            consensus.set_virtual_daa_score(EXPIRE_INTERVAL);
+            assert!(mining_manager.expire_transactions(consensus.as_ref()).is_empty(), "no transaction should expire yet");
+
+            consensus.set_virtual_daa_score(EXPIRE_INTERVAL + 1);
+            let evicted = mining_manager.expire_transactions(consensus.as_ref());
+            assert_eq!(
+                evicted.into_iter().sorted().collect_vec(),
+                [parent_txs[0].id(), child_txs[2].id()].into_iter().sorted().collect_vec()
+            );
+            assert!(!mining_manager.has_transaction(&parent_txs[0].id(), TransactionQuery::All));
+            assert!(!mining_manager.has_transaction(&child_txs[2].id(), TransactionQuery::All));
+
+            consensus.set_virtual_daa_score(10 * EXPIRE_INTERVAL);
+            let evicted = mining_manager.expire_transactions(consensus.as_ref());
+            if high_priority_expire_interval > 0 {
+                assert_eq!(
+                    evicted.into_iter().sorted().collect_vec(),
+                    [parent_txs[1].id(), child_txs[3].id()].into_iter().sorted().collect_vec()
+                );
+                assert!(mining_manager.get_local_transactions().is_empty());
+            } else {
+                assert!(evicted.is_empty(), "high priority transactions should never expire");
+                assert!(mining_manager.has_transaction(&parent_txs[1].id(), TransactionQuery::TransactionsOnly));
+                assert!(mining_manager.has_transaction(&child_txs[3].id(), TransactionQuery::OrphansOnly));
+            }
+        }
+    }
+
     /// test_revalidate_high_priority_transactions verifies that a transaction spending an output of a transaction initially
     /// accepted by the consensus is later removed from the mempool when the funding transaction gets invalidated in consensus
     /// by a reorg.
@@ -1038,7 +1112,7 @@ mod tests {
         );
         let valid_txs = result.unwrap();
         assert_eq!(1, valid_txs.len(), "the revalidated transaction count is wrong: expected: {}, got: {}", 1, valid_txs.len());
-        assert_eq!(spending_tx.id(), valid_txs[0], "the revalidated transaction is not the right one");
+        assert_eq!(spending_tx.id(), valid_txs[0].0, "the revalidated transaction is not the right one");
 
         // Simulate: Mine 2 more blocks on top of tip1, to re-org out child_tx_1, thus making spending_tx invalid
         consensus.add_transaction(child_tx_1, 1);
@@ -1133,6 +1207,8 @@ mod tests {
         let tx_size = txs[0].mempool_estimated_bytes();
         let size_limit = TX_COUNT * tx_size;
         config.mempool_size_limit = size_limit;
+        // The heavy transactions below are inflated with payloads
+        config.maximum_standard_payload_len = size_limit;
         let mining_manager = MiningManager::with_config(config, None, counters);
 
         for tx in txs {
@@ -1174,6 +1250,315 @@ mod tests {
         assert!(validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), too_big_tx.clone()).is_err());
     }
 
+    // test_shrink verifies that shrinking the mempool under memory pressure evicts the requested share of the
+    // transactions, lowest fee rates first.
+    #[test]
+    fn test_shrink() {
+        const TX_COUNT: usize = 10;
+        let txs = (0..TX_COUNT)
+            .map(|i| {
+                let mut tx = create_transaction_with_utxo_entry(i as u32, 0);
+                tx.calculated_fee = Some(DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE * (i as u64 + 1));
+                tx
+            })
+            .collect_vec();
+
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters.clone());
+        for tx in txs.iter() {
+            validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), tx.clone()).unwrap();
+        }
+
+        assert_eq!(Shrinkable::shrink(&mining_manager, 0.3), 3);
+        for (i, tx) in txs.iter().enumerate() {
+            assert_eq!(
+                mining_manager.has_transaction(&tx.id(), TransactionQuery::TransactionsOnly),
+                i >= 3,
+                "only the 3 lowest paying transactions should be evicted"
+            );
+        }
+        assert_eq!(counters.snapshot().tx_evicted_counts, 3);
+
+        assert_eq!(Shrinkable::shrink(&mining_manager, 1.0), TX_COUNT - 3);
+        assert_eq!(mining_manager.transaction_count(TransactionQuery::TransactionsOnly), 0);
+    }
+
+    // test_transaction_packages verifies that the in-mempool ancestors and descendants of transactions are tracked
+    // along with their cumulative fees and masses, both on insertion and on removal.
+    #[test]
+    fn test_transaction_packages() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        // Build a tree of depth 2 rooted at a transaction spending a funding transaction known by the consensus
+        let funding_txs = create_and_add_funding_transactions(&consensus, 1);
+        let root = create_funded_transaction(once(&funding_txs[0]), vec![0], Some(100 * SOMPI_PER_KASPA), 3_000);
+        let tree = create_children_tree(&root, 2);
+        let (children, grandchildren) = tree.split_at(2);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&root).chain(tree.iter()),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+
+        let fee_and_mass = |txs: &[&Transaction]| {
+            txs.iter().fold((0, 0), |(fee, mass), tx| {
+                let mtx = mining_manager.get_transaction(&tx.id(), TransactionQuery::TransactionsOnly).unwrap();
+                (fee + mtx.calculated_fee.unwrap(), mass + mtx.tx.mass())
+            })
+        };
+        let assert_stats = |stats: PackageStats, txs: &[&Transaction], message: &str| {
+            let (fee, mass) = fee_and_mass(txs);
+            assert_eq!(stats, PackageStats { count: txs.len() as u64, fee, mass }, "{message}");
+        };
+
+        let root_package = mining_manager.get_transaction_package(&root.id()).unwrap();
+        assert_stats(root_package.ancestors, &[], "the root has no ancestors in the mempool");
+        assert_stats(root_package.descendants, &tree.iter().collect_vec(), "all the tree transactions descend from the root");
+        for (i, child) in children.iter().enumerate() {
+            let package = mining_manager.get_transaction_package(&child.id()).unwrap();
+            assert_stats(package.ancestors, &[&root], "the root is the only ancestor of a child");
+            assert_stats(package.descendants, &grandchildren[i * 2..i * 2 + 2].iter().collect_vec(), "a child has 2 descendants");
+        }
+        for (i, grandchild) in grandchildren.iter().enumerate() {
+            let package = mining_manager.get_transaction_package(&grandchild.id()).unwrap();
+            assert_stats(package.ancestors, &[&root, &children[i / 2]], "a grandchild descends from its parent and the root");
+            assert_stats(package.descendants, &[], "a grandchild has no descendants");
+        }
+
+        // Accepting the root in a block removes it from the packages of its descendants
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(once(&root)));
+        assert!(result.is_ok(), "the handling of a block accepted by the consensus should succeed but returned {result:?}");
+        assert!(mining_manager.get_transaction_package(&root.id()).is_none());
+        for (i, grandchild) in grandchildren.iter().enumerate() {
+            let package = mining_manager.get_transaction_package(&grandchild.id()).unwrap();
+            assert_stats(package.ancestors, &[&children[i / 2]], "the root was accepted");
+        }
+
+        // A block double spending a child removes it along with its redeemers and leaves the other branch untouched
+        let double_spend = create_funded_transaction(once(&root), vec![0], None, 5_000);
+        let result =
+            mining_manager.handle_new_block_transactions(consensus.as_ref(), 3, &build_block_transactions(once(&double_spend)));
+        assert!(result.is_ok(), "the handling of a block accepted by the consensus should succeed but returned {result:?}");
+        assert!(mining_manager.get_transaction_package(&children[0].id()).is_none());
+        for grandchild in grandchildren[..2].iter() {
+            assert!(mining_manager.get_transaction_package(&grandchild.id()).is_none());
+        }
+        let package = mining_manager.get_transaction_package(&children[1].id()).unwrap();
+        assert_stats(package.ancestors, &[], "the remaining child has no ancestor left in the mempool");
+        assert_stats(package.descendants, &grandchildren[2..].iter().collect_vec(), "the remaining child kept its descendants");
+
+        // Orphans have no package
+        let orphan = create_child_and_parent_txs_and_add_parent_to_consensus(&consensus);
+        let orphan_child = create_transaction(&orphan, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            orphan_child.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "the mempool should accept an orphan transaction");
+        assert!(mining_manager.get_transaction(&orphan_child.id(), TransactionQuery::OrphansOnly).is_some());
+        assert!(mining_manager.get_transaction_package(&orphan_child.id()).is_none());
+    }
+
+    // test_transaction_package_updates verifies that the packages of the relatives of a transaction are updated when the
+    // transaction is removed along with its redeemers while having in-mempool ancestors, and when it is revalidated.
+    #[test]
+    fn test_transaction_package_updates() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let funding_txs = create_and_add_funding_transactions(&consensus, 1);
+        let root = create_funded_transaction(once(&funding_txs[0]), vec![0], Some(100 * SOMPI_PER_KASPA), 3_000);
+        let tree = create_children_tree(&root, 2);
+        let (children, grandchildren) = tree.split_at(2);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&root).chain(tree.iter()),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+
+        let fee_and_mass = |txs: &[&Transaction]| {
+            txs.iter().fold((0, 0), |(fee, mass), tx| {
+                let mtx = mining_manager.get_transaction(&tx.id(), TransactionQuery::TransactionsOnly).unwrap();
+                (fee + mtx.calculated_fee.unwrap(), mass + mtx.tx.mass())
+            })
+        };
+        let assert_stats = |stats: PackageStats, txs: &[&Transaction], message: &str| {
+            let (fee, mass) = fee_and_mass(txs);
+            assert_eq!(stats, PackageStats { count: txs.len() as u64, fee, mass }, "{message}");
+        };
+
+        // A block double spending a child while the root is still in the mempool removes the child along with its redeemers
+        let double_spend = create_funded_transaction(once(&root), vec![0], None, 5_000);
+        let result =
+            mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(once(&double_spend)));
+        assert!(result.is_ok(), "the handling of a block accepted by the consensus should succeed but returned {result:?}");
+        let root_package = mining_manager.get_transaction_package(&root.id()).unwrap();
+        let remaining = [&children[1], &grandchildren[2], &grandchildren[3]];
+        assert_stats(root_package.descendants, &remaining, "the root keeps the remaining branch only");
+
+        // Revalidating a transaction with a different fee updates the packages of its relatives
+        let mut mtx = mining_manager.get_transaction(&children[1].id(), TransactionQuery::TransactionsOnly).unwrap();
+        mtx.calculated_fee = Some(mtx.calculated_fee.unwrap() + 1_000);
+        assert!(mining_manager.update_revalidated_transaction(mtx));
+        let root_package = mining_manager.get_transaction_package(&root.id()).unwrap();
+        assert_stats(root_package.descendants, &remaining, "the root descendants include the revalidated fee");
+        for grandchild in grandchildren[2..].iter() {
+            let package = mining_manager.get_transaction_package(&grandchild.id()).unwrap();
+            assert_stats(package.ancestors, &[&root, &children[1]], "the grandchild ancestors include the revalidated fee");
+        }
+    }
+
+    // test_evict_with_cpfp verifies that a high paying child protects its low paying parent from being evicted
+    // (child pays for parent) and that eviction considers whole packages.
+    #[test]
+    fn test_evict_with_cpfp() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let funding_txs = create_and_add_funding_transactions(&consensus, 3);
+        let parent = create_transaction(&funding_txs[0], DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let child = create_transaction(&parent, 10 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let standalone = create_transaction(&funding_txs[1], 2 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let incoming = create_transaction(&funding_txs[2], 3 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let initial_txs = [&parent, &child, &standalone];
+
+        // Measure the mempool size once filled with the initial transactions
+        let size_limit = {
+            let counters = Arc::new(MiningCounters::default());
+            let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+            let txs = initial_txs.iter().copied();
+            validate_and_insert_transactions(
+                &mining_manager,
+                consensus.as_ref(),
+                txs,
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            mining_manager.get_estimated_size()
+        };
+
+        let counters = Arc::new(MiningCounters::default());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        config.mempool_size_limit = size_limit;
+        let mining_manager = MiningManager::with_config(config, None, counters);
+        let txs = initial_txs.iter().copied();
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            txs,
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+
+        // The parent has the lowest feerate of all, however the feerate of its package is the highest
+        let parent_package = mining_manager.get_transaction_package(&parent.id()).unwrap();
+        assert_eq!(parent_package.descendants.count, 1);
+
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&incoming),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(mining_manager.get_estimated_size() <= size_limit);
+        assert!(mining_manager.has_transaction(&parent.id(), TransactionQuery::TransactionsOnly), "the parent should be kept");
+        assert!(mining_manager.has_transaction(&child.id(), TransactionQuery::TransactionsOnly), "the child should be kept");
+        assert!(
+            !mining_manager.has_transaction(&standalone.id(), TransactionQuery::TransactionsOnly),
+            "the standalone transaction should be evicted"
+        );
+        assert!(mining_manager.has_transaction(&incoming.id(), TransactionQuery::TransactionsOnly));
+    }
+
+    // test_high_priority_reserved_mass verifies that, for all selection strategies, high-priority transactions are selected
+    // into the reserved block mass ahead of higher paying low-priority transactions.
+    #[test]
+    fn test_high_priority_reserved_mass() {
+        const TX_COUNT: u32 = 20;
+        for strategy in [TransactionSelectionStrategy::Sampling, TransactionSelectionStrategy::Greedy] {
+            let consensus = Arc::new(ConsensusMock::new());
+            let counters = Arc::new(MiningCounters::default());
+            let txs = (0..=TX_COUNT).map(|i| create_transaction_with_utxo_entry(i, 0)).collect_vec();
+            let tx_mass = transaction_estimated_serialized_size(&txs[0].tx);
+
+            // A block fits 5 transactions, one of which is reserved for high-priority transactions
+            let config =
+                Config::build_default(TARGET_TIME_PER_BLOCK, false, 5 * tx_mass).with_transaction_selection(strategy, tx_mass);
+            let mining_manager = MiningManager::with_config(config, None, counters);
+            let (high_priority_tx, low_priority_txs) = txs.split_first().unwrap();
+            for mut tx in low_priority_txs.iter().cloned() {
+                tx.calculated_fee = Some(10 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+                let result = mining_manager.validate_and_insert_mutable_transaction(
+                    consensus.as_ref(),
+                    tx,
+                    Priority::Low,
+                    Orphan::Forbidden,
+                    RbfPolicy::Forbidden,
+                );
+                assert!(result.is_ok(), "the mempool should accept a valid transaction");
+            }
+            let result = mining_manager.validate_and_insert_mutable_transaction(
+                consensus.as_ref(),
+                high_priority_tx.clone(),
+                Priority::High,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept a valid transaction");
+
+            let template = mining_manager.get_block_template(consensus.as_ref(), &get_miner_data(Prefix::Testnet)).unwrap();
+            let transactions = &template.block.transactions[1..];
+            assert_eq!(transactions.len(), 5, "the template should be full ({strategy:?})");
+            assert!(
+                contained_by(high_priority_tx.id(), transactions),
+                "the high-priority transaction should be selected into the reserved mass ({strategy:?})"
+            );
+        }
+    }
+
+    // test_coinbase_extra_data verifies that the configured coinbase extra data is appended to the miner-provided extra data
+    // of both built and modified (i.e., derived from the cache) block templates.
+    #[test]
+    fn test_coinbase_extra_data() {
+        const POOL_ID: &[u8] = b"/pool-id";
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS).with_coinbase_extra_data(POOL_ID.to_vec());
+        let mining_manager = MiningManager::with_config(config, None, counters);
+
+        let miner_data = get_miner_data(Prefix::Testnet);
+        let template = mining_manager.get_block_template(consensus.as_ref(), &miner_data).unwrap();
+        let expected_extra_data = miner_data.extra_data.iter().chain(POOL_ID).copied().collect_vec();
+        assert_eq!(template.miner_data.extra_data, expected_extra_data, "the built template should include the coinbase extra data");
+
+        // A repeated request with the same miner data should be served as is from the cache
+        let cached_template = mining_manager.get_block_template(consensus.as_ref(), &miner_data).unwrap();
+        assert_eq!(cached_template.block.header.hash, template.block.header.hash, "the cached template should be reused");
+
+        let other_miner_data = MinerData::new(miner_data.script_public_key.clone(), b"miner".to_vec());
+        let modified_template = mining_manager.get_block_template(consensus.as_ref(), &other_miner_data).unwrap();
+        assert_eq!(
+            modified_template.miner_data.extra_data,
+            b"miner".iter().chain(POOL_ID).copied().collect_vec(),
+            "the modified template should include the coinbase extra data"
+        );
+    }
+
     fn validate_and_insert_mutable_transaction(
         mining_manager: &MiningManager,
         consensus: &dyn ConsensusApi,
@@ -69,6 +69,16 @@ impl Mempool {
             ));
         }
 
+        // Payloads are bounded by the configured standard length, regardless of whether consensus accepts them.
+        // Coinbase transactions never make it to the mempool so all payloads here are non-coinbase ones
+        if transaction.tx.payload.len() > self.config.maximum_standard_payload_len {
+            return Err(NonStandardError::RejectPayloadSize(
+                transaction_id,
+                transaction.tx.payload.len(),
+                self.config.maximum_standard_payload_len,
+            ));
+        }
+
         for (i, input) in transaction.tx.inputs.iter().enumerate() {
             // Each transaction input signature script must not exceed the
             // maximum size allowed for a standard transaction.
@@ -104,6 +114,15 @@ impl Mempool {
         Ok(())
     }
 
+    /// Rejects transactions of non-native subnetworks unless the mempool is configured to accept them. This gate applies
+    /// regardless of `accept_non_standard`, since accepting such transactions is a separate opt-in
+    pub(crate) fn check_transaction_subnetwork(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
+        if !self.config.accept_non_native_subnetworks && !transaction.tx.subnetwork_id.is_native() {
+            return Err(NonStandardError::RejectSubnetwork(transaction.id(), transaction.tx.subnetwork_id.clone()));
+        }
+        Ok(())
+    }
+
     /// is_transaction_output_dust returns whether or not the passed transaction output
     /// amount is considered dust or not based on the configured minimum transaction
     /// relay fee.
@@ -241,7 +260,7 @@ mod tests {
         config::params::Params,
         constants::{MAX_TX_IN_SEQUENCE_NUM, SOMPI_PER_KASPA, TX_VERSION},
         network::NetworkType,
-        subnets::SUBNETWORK_ID_NATIVE,
+        subnets::{SubnetworkId, SUBNETWORK_ID_NATIVE},
         tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput},
     };
     use kaspa_txscript::{
@@ -513,6 +532,22 @@ mod tests {
                 ),
                 is_standard: false,
             },
+            Test {
+                name: "Transaction with a payload",
+                mtx: new_mtx(
+                    Transaction::new(
+                        TX_VERSION,
+                        vec![dummy_tx_input.clone()],
+                        vec![dummy_tx_out.clone()],
+                        0,
+                        SUBNETWORK_ID_NATIVE,
+                        0,
+                        vec![0u8; 32],
+                    ),
+                    1000,
+                ),
+                is_standard: false,
+            },
             Test {
                 name: "Dust output",
                 mtx: new_mtx(
@@ -581,4 +616,21 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_check_transaction_subnetwork() {
+        let params: Params = NetworkType::Mainnet.into();
+        let tx =
+            |subnetwork_id| MutableTransaction::from_tx(Transaction::new(TX_VERSION, vec![], vec![], 0, subnetwork_id, 0, vec![]));
+        let native = tx(SUBNETWORK_ID_NATIVE);
+        let non_native = tx(SubnetworkId::from_byte(10));
+
+        for accept_non_native_subnetworks in [false, true] {
+            let config = Config::build_default(params.target_time_per_block, false, params.max_block_mass)
+                .with_non_native_subnetworks(accept_non_native_subnetworks);
+            let mempool = Mempool::new(Arc::new(config), Arc::new(MiningCounters::default()));
+            assert!(mempool.check_transaction_subnetwork(&native).is_ok());
+            assert_eq!(mempool.check_transaction_subnetwork(&non_native).is_ok(), accept_non_native_subnetworks);
+        }
+    }
 }
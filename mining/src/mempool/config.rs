@@ -4,7 +4,8 @@ pub(crate) const DEFAULT_MAXIMUM_TRANSACTION_COUNT: usize = 1_000_000;
 pub(crate) const DEFAULT_MEMPOOL_SIZE_LIMIT: usize = 1_000_000_000;
 pub(crate) const DEFAULT_MAXIMUM_BUILD_BLOCK_TEMPLATE_ATTEMPTS: u64 = 5;
 
-pub(crate) const DEFAULT_TRANSACTION_EXPIRE_INTERVAL_SECONDS: u64 = 24 * 60 * 60;
+pub const DEFAULT_TRANSACTION_EXPIRE_INTERVAL_SECONDS: u64 = 24 * 60 * 60;
+pub const DEFAULT_HIGH_PRIORITY_TRANSACTION_EXPIRE_INTERVAL_SECONDS: u64 = 7 * 24 * 60 * 60;
 pub(crate) const DEFAULT_TRANSACTION_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 60;
 pub(crate) const DEFAULT_ACCEPTED_TRANSACTION_EXPIRE_INTERVAL_SECONDS: u64 = 120;
 pub(crate) const DEFAULT_ACCEPTED_TRANSACTION_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 10;
@@ -25,12 +26,27 @@ pub(crate) const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
 pub(crate) const DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 pub(crate) const DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 
+/// The strategy by which block template transactions are selected out of the ready transactions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransactionSelectionStrategy {
+    /// Randomized selection where the probability of a transaction being selected grows with its feerate.
+    /// This gives every transaction a fair chance of inclusion and keeps templates of distinct miners diverse.
+    #[default]
+    Sampling,
+
+    /// Deterministic selection by descending feerate. Transactions which do not fit the remaining block mass
+    /// are skipped in favor of subsequent lighter ones (greedy knapsack).
+    Greedy,
+}
+
 #[derive(Clone, Debug)]
 pub struct Config {
     pub maximum_transaction_count: usize,
     pub mempool_size_limit: usize,
     pub maximum_build_block_template_attempts: u64,
     pub transaction_expire_interval_daa_score: u64,
+    /// Expiry window of high-priority (i.e., locally submitted) transactions. Zero means these never expire
+    pub high_priority_transaction_expire_interval_daa_score: u64,
     pub transaction_expire_scan_interval_daa_score: u64,
     pub transaction_expire_scan_interval_milliseconds: u64,
     pub accepted_transaction_expire_interval_daa_score: u64,
@@ -46,6 +62,18 @@ pub struct Config {
     pub minimum_standard_transaction_version: u16,
     pub maximum_standard_transaction_version: u16,
     pub network_blocks_per_second: u64,
+    pub transaction_selection_strategy: TransactionSelectionStrategy,
+    /// Block mass reserved for high-priority (i.e., locally submitted) transactions, which are then selected
+    /// ahead of all others regardless of the selection strategy. Zero disables the reservation.
+    pub high_priority_reserved_mass: u64,
+    /// Extra data appended to the miner-provided extra data of every block template coinbase (e.g., a pool identifier)
+    pub coinbase_extra_data: Vec<u8>,
+    /// Whether transactions of non-native subnetworks are accepted, in which case they are still subject to the
+    /// subnetwork rules of consensus
+    pub accept_non_native_subnetworks: bool,
+    /// The maximum payload length of standard non-coinbase transactions. Zero renders all such payloads non-standard,
+    /// even once consensus accepts them
+    pub maximum_standard_payload_len: usize,
 }
 
 impl Config {
@@ -55,6 +83,7 @@ impl Config {
         mempool_size_limit: usize,
         maximum_build_block_template_attempts: u64,
         transaction_expire_interval_daa_score: u64,
+        high_priority_transaction_expire_interval_daa_score: u64,
         transaction_expire_scan_interval_daa_score: u64,
         transaction_expire_scan_interval_milliseconds: u64,
         accepted_transaction_expire_interval_daa_score: u64,
@@ -70,12 +99,15 @@ impl Config {
         minimum_standard_transaction_version: u16,
         maximum_standard_transaction_version: u16,
         network_blocks_per_second: u64,
+        transaction_selection_strategy: TransactionSelectionStrategy,
+        high_priority_reserved_mass: u64,
     ) -> Self {
         Self {
             maximum_transaction_count,
             mempool_size_limit,
             maximum_build_block_template_attempts,
             transaction_expire_interval_daa_score,
+            high_priority_transaction_expire_interval_daa_score,
             transaction_expire_scan_interval_daa_score,
             transaction_expire_scan_interval_milliseconds,
             accepted_transaction_expire_interval_daa_score,
@@ -91,6 +123,11 @@ impl Config {
             minimum_standard_transaction_version,
             maximum_standard_transaction_version,
             network_blocks_per_second,
+            transaction_selection_strategy,
+            high_priority_reserved_mass,
+            coinbase_extra_data: Vec::new(),
+            accept_non_native_subnetworks: false,
+            maximum_standard_payload_len: 0,
         }
     }
 
@@ -102,6 +139,8 @@ impl Config {
             mempool_size_limit: DEFAULT_MEMPOOL_SIZE_LIMIT,
             maximum_build_block_template_attempts: DEFAULT_MAXIMUM_BUILD_BLOCK_TEMPLATE_ATTEMPTS,
             transaction_expire_interval_daa_score: DEFAULT_TRANSACTION_EXPIRE_INTERVAL_SECONDS * 1000 / target_milliseconds_per_block,
+            high_priority_transaction_expire_interval_daa_score: DEFAULT_HIGH_PRIORITY_TRANSACTION_EXPIRE_INTERVAL_SECONDS * 1000
+                / target_milliseconds_per_block,
             transaction_expire_scan_interval_daa_score: DEFAULT_TRANSACTION_EXPIRE_SCAN_INTERVAL_SECONDS * 1000
                 / target_milliseconds_per_block,
             transaction_expire_scan_interval_milliseconds: DEFAULT_TRANSACTION_EXPIRE_SCAN_INTERVAL_SECONDS * 1000,
@@ -120,6 +159,11 @@ impl Config {
             minimum_standard_transaction_version: DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION,
             maximum_standard_transaction_version: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION,
             network_blocks_per_second: 1000 / target_milliseconds_per_block,
+            transaction_selection_strategy: TransactionSelectionStrategy::Sampling,
+            high_priority_reserved_mass: 0,
+            coinbase_extra_data: Vec::new(),
+            accept_non_native_subnetworks: false,
+            maximum_standard_payload_len: 0,
         }
     }
 
@@ -130,6 +174,41 @@ impl Config {
         self
     }
 
+    pub fn with_transaction_selection(
+        mut self,
+        transaction_selection_strategy: TransactionSelectionStrategy,
+        high_priority_reserved_mass: u64,
+    ) -> Self {
+        self.transaction_selection_strategy = transaction_selection_strategy;
+        // The reservation can never exceed the block mass
+        self.high_priority_reserved_mass = high_priority_reserved_mass.min(self.maximum_mass_per_block);
+        self
+    }
+
+    /// Sets the expiry windows (in seconds) of low-priority (i.e., relayed) and high-priority (i.e., locally submitted)
+    /// transactions. A zero high-priority window means locally submitted transactions never expire
+    pub fn with_transaction_expiry(mut self, expire_interval_seconds: u64, high_priority_expire_interval_seconds: u64) -> Self {
+        let blocks_per_second = self.network_blocks_per_second.max(1);
+        self.transaction_expire_interval_daa_score = expire_interval_seconds * blocks_per_second;
+        self.high_priority_transaction_expire_interval_daa_score = high_priority_expire_interval_seconds * blocks_per_second;
+        self
+    }
+
+    pub fn with_coinbase_extra_data(mut self, coinbase_extra_data: Vec<u8>) -> Self {
+        self.coinbase_extra_data = coinbase_extra_data;
+        self
+    }
+
+    pub fn with_non_native_subnetworks(mut self, accept_non_native_subnetworks: bool) -> Self {
+        self.accept_non_native_subnetworks = accept_non_native_subnetworks;
+        self
+    }
+
+    pub fn with_maximum_standard_payload_len(mut self, maximum_standard_payload_len: usize) -> Self {
+        self.maximum_standard_payload_len = maximum_standard_payload_len;
+        self
+    }
+
     /// Returns the minimum standard fee/mass ratio currently required by the mempool
     pub(crate) fn minimum_feerate(&self) -> f64 {
         // The parameter minimum_relay_transaction_fee is in sompi/kg units so divide by 1000 to get sompi/gram
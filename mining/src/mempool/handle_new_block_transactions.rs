@@ -54,16 +54,16 @@ impl Mempool {
         Ok(unorphaned_transactions)
     }
 
-    pub(crate) fn expire_orphan_low_priority_transactions(&mut self, consensus: &dyn ConsensusApi) -> RuleResult<()> {
-        self.orphan_pool.expire_low_priority_transactions(consensus.get_virtual_daa_score())
+    pub(crate) fn expire_orphan_transactions(&mut self, consensus: &dyn ConsensusApi) -> RuleResult<Vec<TransactionId>> {
+        self.orphan_pool.expire_transactions(consensus.get_virtual_daa_score())
     }
 
     pub(crate) fn expire_accepted_transactions(&mut self, consensus: &dyn ConsensusApi) {
         self.accepted_transactions.expire(consensus.get_virtual_daa_score());
     }
 
-    pub(crate) fn collect_expired_low_priority_transactions(&mut self, consensus: &dyn ConsensusApi) -> Vec<TransactionId> {
-        self.transaction_pool.collect_expired_low_priority_transactions(consensus.get_virtual_daa_score())
+    pub(crate) fn collect_expired_transactions(&mut self, consensus: &dyn ConsensusApi) -> Vec<TransactionId> {
+        self.transaction_pool.collect_expired_transactions(consensus.get_virtual_daa_score())
     }
 
     fn remove_double_spends(&mut self, transaction: &Transaction) -> RuleResult<()> {
@@ -75,6 +75,7 @@ impl Mempool {
         }
         transactions_to_remove.iter().try_for_each(|x| {
             self.remove_transaction(x, true, TxRemovalReason::DoubleSpend, format!(" favouring {}", transaction.id()).as_str())
+                .map(|_| ())
         })
     }
 }
@@ -1,6 +1,7 @@
 use crate::{
     feerate::{FeerateEstimator, FeerateEstimatorArgs},
     model::{
+        local_tx::LocalTransaction,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         tx_query::TransactionQuery,
     },
@@ -9,8 +10,11 @@ use crate::{
 
 use self::{
     config::Config,
-    model::{accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
-    tx::Priority,
+    model::{
+        accepted_transactions::AcceptedTransactions, map::MempoolTransactionCollection, orphan_pool::OrphanPool, pool::Pool,
+        transactions_pool::TransactionsPool,
+    },
+    tx::{Priority, TransactionPackage},
 };
 use kaspa_consensus_core::{
     block::TemplateTransactionSelector,
@@ -72,6 +76,11 @@ impl Mempool {
         transaction.map(|x| x.mtx.clone())
     }
 
+    /// Returns the package of a transaction held by the transaction pool. Orphans have no package.
+    pub(crate) fn get_transaction_package(&self, transaction_id: &TransactionId) -> Option<TransactionPackage> {
+        self.transaction_pool.get(transaction_id).map(|x| x.package)
+    }
+
     pub(crate) fn has_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> bool {
         (query.include_transaction_pool() && self.transaction_pool.has(transaction_id))
             || (query.include_orphan_pool() && self.orphan_pool.has(transaction_id))
@@ -83,6 +92,25 @@ impl Mempool {
         (transactions, orphans)
     }
 
+    /// Returns the transactions submitted to this node, i.e., of high priority, from both pools
+    pub(crate) fn get_local_transactions(&self) -> Vec<LocalTransaction> {
+        let expire_interval = self.config.high_priority_transaction_expire_interval_daa_score;
+        let local_transactions = |pool: &MempoolTransactionCollection, is_orphan: bool| {
+            pool.values()
+                .filter(|x| x.priority == Priority::High)
+                .map(|x| LocalTransaction {
+                    transaction: x.mtx.clone(),
+                    added_at_daa_score: x.added_at_daa_score,
+                    expire_daa_score: (expire_interval > 0).then_some(x.added_at_daa_score + expire_interval),
+                    is_orphan,
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut transactions = local_transactions(self.transaction_pool.all(), false);
+        transactions.extend(local_transactions(self.orphan_pool.all(), true));
+        transactions
+    }
+
     pub(crate) fn get_all_transaction_ids(&self, query: TransactionQuery) -> (Vec<TransactionId>, Vec<TransactionId>) {
         let transactions = if query.include_transaction_pool() { self.transaction_pool.get_all_transaction_ids() } else { vec![] };
         let orphans = if query.include_orphan_pool() { self.orphan_pool.get_all_transaction_ids() } else { vec![] };
@@ -223,4 +251,38 @@ pub mod tx {
         /// If conditions are not met, leaves the mempool unchanged and fails with a double spend or a tx fee/mass too low error.
         Mandatory,
     }
+
+    /// Cumulative count, fee and mass of a set of transactions in the mempool
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct PackageStats {
+        pub count: u64,
+        pub fee: u64,
+        pub mass: u64,
+    }
+
+    impl PackageStats {
+        pub fn add(&mut self, fee: u64, mass: u64) {
+            self.count += 1;
+            self.fee += fee;
+            self.mass += mass;
+        }
+
+        /// Subtracts a transaction from the stats. Saturates at zero, so that inconsistent stats never underflow
+        pub fn sub(&mut self, fee: u64, mass: u64) {
+            self.count = self.count.saturating_sub(1);
+            self.fee = self.fee.saturating_sub(fee);
+            self.mass = self.mass.saturating_sub(mass);
+        }
+    }
+
+    /// The in-mempool ancestors and descendants of a transaction, both excluding the transaction itself.
+    ///
+    /// Since chained transactions cannot be mined in the same block, a transaction is a block template
+    /// candidate only once all its ancestors are accepted. The fees of its descendants are however taken
+    /// into account when ranking it, so a high feerate child can pay for its low feerate parent (CPFP).
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct TransactionPackage {
+        pub ancestors: PackageStats,
+        pub descendants: PackageStats,
+    }
 }
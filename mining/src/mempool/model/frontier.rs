@@ -1,15 +1,19 @@
 use crate::{
     feerate::{FeerateEstimator, FeerateEstimatorArgs},
+    mempool::config::TransactionSelectionStrategy,
     model::candidate_tx::CandidateTransaction,
     Policy, RebalancingWeightedTransactionSelector,
 };
 
 use feerate_key::FeerateTransactionKey;
-use kaspa_consensus_core::{block::TemplateTransactionSelector, tx::Transaction};
+use kaspa_consensus_core::{
+    block::TemplateTransactionSelector,
+    tx::{Transaction, TransactionId},
+};
 use kaspa_core::trace;
 use rand::{distributions::Uniform, prelude::Distribution, Rng};
 use search_tree::SearchTree;
-use selectors::{SequenceSelector, SequenceSelectorInput, TakeAllSelector};
+use selectors::{SequenceSelector, SequenceSelectorInput, SequenceSelectorTransaction, TakeAllSelector};
 use std::{collections::HashSet, iter::FusedIterator, sync::Arc};
 
 pub(crate) mod feerate_key;
@@ -25,6 +29,11 @@ const COLLISION_FACTOR: u64 = 4;
 /// hard limit in order to allow the SequenceSelector to compensate for consensus rejections.
 const MASS_LIMIT_FACTOR: f64 = 1.2;
 
+/// The greedy selector is provided with the highest feerate transactions up to twice the block mass limit.
+/// The surplus allows filling the block after consensus rejections and packing lighter transactions into
+/// the gap left by transactions which do not fit (knapsack)
+const GREEDY_MASS_LIMIT_FACTOR: u64 = 2;
+
 /// A rough estimation for the average transaction mass. The usage is a non-important edge case
 /// hence we just throw this here (as oppose to performing an accurate estimation)
 const TYPICAL_TX_MASS: f64 = 2000.0;
@@ -106,6 +115,20 @@ impl Frontier {
     ///           current samples. And if the space is highly biased, the repeated elimination of top items and the prefix weight computation
     ///           will readjust it.
     pub fn sample_inplace<R>(&self, rng: &mut R, policy: &Policy, _collisions: &mut u64) -> SequenceSelectorInput
+    where
+        R: Rng + ?Sized,
+    {
+        self.sample_inplace_excluding(rng, policy, &HashSet::new(), _collisions)
+    }
+
+    /// Same as [`Self::sample_inplace`] while never sampling the `excluded` frontier transactions
+    fn sample_inplace_excluding<R>(
+        &self,
+        rng: &mut R,
+        policy: &Policy,
+        excluded: &HashSet<TransactionId>,
+        _collisions: &mut u64,
+    ) -> SequenceSelectorInput
     where
         R: Rng + ?Sized,
     {
@@ -120,7 +143,8 @@ impl Frontier {
         let mut distr = Uniform::new(0f64, self.total_weight());
         let mut down_iter = self.search_tree.descending_iter();
         let mut top = down_iter.next().unwrap();
-        let mut cache = HashSet::new();
+        // Excluded transactions are considered as already sampled
+        let mut cache = excluded.clone();
         let mut sequence = SequenceSelectorInput::default();
         let mut total_selected_mass: u64 = 0;
         let mut collisions = 0;
@@ -175,19 +199,80 @@ impl Frontier {
     /// full transaction selection in less than 150 µs even if the frontier has 1M entries (!!). See mining/benches
     /// for more details.  
     pub fn build_selector(&self, policy: &Policy) -> Box<dyn TemplateTransactionSelector> {
-        if self.total_mass <= policy.max_block_mass {
-            Box::new(TakeAllSelector::new(self.search_tree.ascending_iter().map(|k| k.tx.clone()).collect()))
-        } else if self.total_mass > policy.max_block_mass * COLLISION_FACTOR {
+        self.build_sampling_selector(policy, &HashSet::new(), 0)
+    }
+
+    fn build_sampling_selector(
+        &self,
+        policy: &Policy,
+        excluded: &HashSet<TransactionId>,
+        excluded_mass: u64,
+    ) -> Box<dyn TemplateTransactionSelector> {
+        let total_mass = self.total_mass - excluded_mass;
+        if total_mass <= policy.max_block_mass {
+            Box::new(TakeAllSelector::new(self.ascending_iter().filter(|tx| !excluded.contains(&tx.id())).cloned().collect()))
+        } else if total_mass > policy.max_block_mass * COLLISION_FACTOR {
             let mut rng = rand::thread_rng();
-            Box::new(SequenceSelector::new(self.sample_inplace(&mut rng, policy, &mut 0), policy.clone()))
+            Box::new(SequenceSelector::new(self.sample_inplace_excluding(&mut rng, policy, excluded, &mut 0), policy.clone()))
         } else {
             Box::new(RebalancingWeightedTransactionSelector::new(
                 policy.clone(),
-                self.search_tree.ascending_iter().cloned().map(CandidateTransaction::from_key).collect(),
+                self.search_tree
+                    .ascending_iter()
+                    .filter(|key| !excluded.contains(&key.tx.id()))
+                    .cloned()
+                    .map(CandidateTransaction::from_key)
+                    .collect(),
             ))
         }
     }
 
+    /// Builds a transaction selector according to `strategy`. See [`TransactionSelectionStrategy`] for details.
+    ///
+    /// The `excluded` frontier transactions, whose total mass is `excluded_mass`, are never provided to the selector.
+    /// This allows selecting them by other means (e.g. into a reserved block mass) without the selector accounting
+    /// for their mass a second time
+    pub fn build_selector_with_strategy(
+        &self,
+        strategy: TransactionSelectionStrategy,
+        policy: &Policy,
+        excluded: &HashSet<TransactionId>,
+        excluded_mass: u64,
+    ) -> Box<dyn TemplateTransactionSelector> {
+        match strategy {
+            TransactionSelectionStrategy::Sampling => self.build_sampling_selector(policy, excluded, excluded_mass),
+            TransactionSelectionStrategy::Greedy => self.build_greedy_selector_excluding(policy, excluded, excluded_mass),
+        }
+    }
+
+    /// Builds a deterministic selector which tries the transactions by descending feerate order
+    pub fn build_greedy_selector(&self, policy: &Policy) -> Box<dyn TemplateTransactionSelector> {
+        self.build_greedy_selector_excluding(policy, &HashSet::new(), 0)
+    }
+
+    fn build_greedy_selector_excluding(
+        &self,
+        policy: &Policy,
+        excluded: &HashSet<TransactionId>,
+        excluded_mass: u64,
+    ) -> Box<dyn TemplateTransactionSelector> {
+        let descending = self.search_tree.descending_iter().filter(|key| !excluded.contains(&key.tx.id()));
+        if self.total_mass - excluded_mass <= policy.max_block_mass {
+            return Box::new(TakeAllSelector::new(descending.map(|k| k.tx.clone()).collect()));
+        }
+        let mass_limit = policy.max_block_mass.saturating_mul(GREEDY_MASS_LIMIT_FACTOR);
+        let mut total_mass = 0u64;
+        let sequence = descending
+            .take_while(|key| {
+                let within_limit = total_mass < mass_limit;
+                total_mass = total_mass.saturating_add(key.mass);
+                within_limit
+            })
+            .map(|key| SequenceSelectorTransaction::new(key.tx.clone(), key.mass))
+            .collect();
+        Box::new(SequenceSelector::new(sequence, policy.clone()))
+    }
+
     /// Exposed for benchmarking purposes
     pub fn build_selector_sample_inplace(&self, _collisions: &mut u64) -> Box<dyn TemplateTransactionSelector> {
         let mut rng = rand::thread_rng();
@@ -323,6 +408,61 @@ mod tests {
         selector.select_transactions().iter().map(|k| k.gas).sum::<u64>();
     }
 
+    #[test]
+    pub fn test_greedy_selection() {
+        let policy = Policy::new(500_000);
+        let mut frontier = Frontier::default();
+        let lights = (0..1000u64).map(|i| build_feerate_key(i + 1, 1650, i)).collect_vec();
+        // A heavy transaction with the highest feerate, followed by one which cannot fit besides it
+        let heavy = build_feerate_key(1000 * 400_000, 400_000, 1000);
+        let too_heavy = build_feerate_key(900 * 450_000, 450_000, 1001);
+        for key in lights.iter().chain([&heavy, &too_heavy]).cloned() {
+            frontier.insert(key).then_some(()).unwrap();
+        }
+
+        let selected = frontier.build_greedy_selector(&policy).select_transactions().iter().map(|tx| tx.id()).collect_vec();
+        // The selection is deterministic
+        assert_eq!(selected, frontier.build_greedy_selector(&policy).select_transactions().iter().map(|tx| tx.id()).collect_vec());
+
+        // The heavy tx is selected first, the too heavy one is skipped and the remaining mass is filled by the best light txs
+        let light_count = (500_000 - 400_000) / 1650;
+        assert_eq!(selected[0], heavy.tx.id());
+        assert!(!selected.contains(&too_heavy.tx.id()));
+        assert_eq!(selected.len() as u64, 1 + light_count);
+        let expected = lights.iter().rev().take(light_count as usize).map(|key| key.tx.id()).collect::<HashSet<_>>();
+        assert_eq!(selected[1..].iter().copied().collect::<HashSet<_>>(), expected);
+
+        // A frontier which fits into a block is fully selected
+        let mut selector = frontier.build_greedy_selector(&Policy::new(frontier.total_mass()));
+        assert_eq!(selector.select_transactions().len(), frontier.len());
+    }
+
+    #[test]
+    pub fn test_selection_with_exclusions() {
+        let mut frontier = Frontier::default();
+        let keys = (0..2000u64).map(|i| build_feerate_key(i + 1, 1650, i)).collect_vec();
+        for key in keys.iter().cloned() {
+            frontier.insert(key).then_some(()).unwrap();
+        }
+        // Exclude the highest feerate transactions, which are the most likely to be selected
+        let excluded = keys.iter().rev().take(100).map(|key| key.tx.id()).collect::<HashSet<_>>();
+        let excluded_mass = 100 * 1650;
+
+        // Cover in-place sampling, rebalancing and take-all selections by varying the block mass
+        for max_block_mass in [500_000, frontier.total_mass() / 2, frontier.total_mass()] {
+            let policy = Policy::new(max_block_mass);
+            for strategy in [TransactionSelectionStrategy::Sampling, TransactionSelectionStrategy::Greedy] {
+                let selected =
+                    frontier.build_selector_with_strategy(strategy, &policy, &excluded, excluded_mass).select_transactions();
+                assert!(!selected.is_empty(), "{strategy:?} selected no transaction for a block mass of {max_block_mass}");
+                assert!(
+                    selected.iter().all(|tx| !excluded.contains(&tx.id())),
+                    "{strategy:?} selected an excluded transaction for a block mass of {max_block_mass}"
+                );
+            }
+        }
+    }
+
     #[test]
     pub fn test_total_mass_tracking() {
         let mut rng = thread_rng();
@@ -454,6 +594,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_feerate_estimator_with_packages() {
+        let mut rng = thread_rng();
+        let cap = 2000;
+        let mass: u64 = 1650;
+        let keys = (0..cap).map(|i| build_feerate_key(rng.gen_range(1650..3300), mass, i)).collect_vec();
+
+        // Every 10th transaction is ranked by the feerate of a package including a high paying descendant (CPFP)
+        let package_keys = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| match i % 10 {
+                0 => FeerateTransactionKey::with_feerate(key.fee, key.mass, key.feerate() * 100.0, key.tx.clone()),
+                _ => key.clone(),
+            })
+            .collect_vec();
+
+        let mut frontier = Frontier::default();
+        let mut package_frontier = Frontier::default();
+        for (key, package_key) in keys.into_iter().zip(package_keys) {
+            frontier.insert(key).then_some(()).unwrap();
+            package_frontier.insert(package_key).then_some(()).unwrap();
+        }
+
+        // Packages affect the ranking only
+        let top = package_frontier.search_tree.descending_iter().next().unwrap();
+        assert!(top.feerate() > top.fee as f64 / top.mass as f64);
+
+        // The estimator only accounts for the own fee and mass of each transaction, so estimations are unaffected
+        assert_eq!(frontier.total_mass(), package_frontier.total_mass());
+        let eps = frontier.total_weight() * 1e-9;
+        assert!((frontier.total_weight() - package_frontier.total_weight()).abs() <= eps);
+        let args = || FeerateEstimatorArgs { network_blocks_per_second: 1, maximum_mass_per_block: 500_000 };
+        let estimations = frontier.build_feerate_estimator(args()).calc_estimations(1.0).ordered_buckets();
+        let package_estimations = package_frontier.build_feerate_estimator(args()).calc_estimations(1.0).ordered_buckets();
+        for (bucket, package_bucket) in estimations.iter().zip(package_estimations.iter()) {
+            assert!((bucket.feerate - package_bucket.feerate).abs() <= bucket.feerate * 1e-9);
+            assert!((bucket.estimated_seconds - package_bucket.estimated_seconds).abs() <= bucket.estimated_seconds * 1e-9);
+        }
+    }
+
     #[test]
     fn test_feerate_estimator_with_low_mass_outliers() {
         const MIN_FEERATE: f64 = 1.0;
@@ -6,6 +6,7 @@ use std::sync::Arc;
 pub struct FeerateTransactionKey {
     pub fee: u64,
     pub mass: u64,
+    feerate: f64,
     weight: f64,
     pub tx: Arc<Transaction>,
 }
@@ -20,14 +21,23 @@ impl PartialEq for FeerateTransactionKey {
 
 impl FeerateTransactionKey {
     pub fn new(fee: u64, mass: u64, tx: Arc<Transaction>) -> Self {
+        Self::with_feerate(fee, mass, fee as f64 / mass as f64, tx)
+    }
+
+    /// Creates a key ranked by `feerate` rather than by the `fee / mass` ratio of the transaction itself.
+    /// This is used for ranking a transaction along with its in-mempool descendants (CPFP). The weight
+    /// remains that of the transaction itself, see below
+    pub fn with_feerate(fee: u64, mass: u64, feerate: f64, tx: Arc<Transaction>) -> Self {
         // NOTE: any change to the way this weight is calculated (such as scaling by some factor)
         // requires a reversed update to total_weight in `Frontier::build_feerate_estimator`. This
-        // is because the math methods in FeeEstimator assume this specific weight function.
-        Self { fee, mass, weight: (fee as f64 / mass as f64).powi(ALPHA), tx }
+        // is because the math methods in FeeEstimator assume this specific weight function, i.e.,
+        // the weight of the transaction's own fee and mass, which are also the ones accounted for
+        // by the estimator and the sampling selector (regardless of the ranking feerate).
+        Self { fee, mass, feerate, weight: (fee as f64 / mass as f64).powi(ALPHA), tx }
     }
 
     pub fn feerate(&self) -> f64 {
-        self.fee as f64 / self.mass as f64
+        self.feerate
     }
 
     pub fn weight(&self) -> f64 {
@@ -50,15 +60,14 @@ impl PartialOrd for FeerateTransactionKey {
 
 impl Ord for FeerateTransactionKey {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // Our first priority is the feerate.
-        // The weight function is monotonic in feerate so we prefer using it
-        // since it is cached
-        match self.weight().total_cmp(&other.weight()) {
+        // Our first priority is the (ranking) feerate. Note that the weight is not necessarily
+        // monotonic in it, since a ranking feerate might be raised by descendants
+        match self.feerate().total_cmp(&other.feerate()) {
             core::cmp::Ordering::Equal => {}
             ord => return ord,
         }
 
-        // If feerates (and thus weights) are equal, prefer the higher fee in absolute value
+        // If feerates are equal, prefer the higher fee in absolute value
         match self.fee.cmp(&other.fee) {
             core::cmp::Ordering::Equal => {}
             ord => return ord,
@@ -66,7 +75,8 @@ impl Ord for FeerateTransactionKey {
 
         //
         // At this point we don't compare the mass fields since if both feerate
-        // and fee are equal, mass must be equal as well
+        // and fee are equal, mass must be equal as well (unless the feerate was
+        // raised by descendants, in which case the order is arbitrary anyway)
         //
 
         // Finally, we compare transaction ids in order to allow multiple transactions with
@@ -80,7 +90,7 @@ impl From<&MempoolTransaction> for FeerateTransactionKey {
         let mass = tx.mtx.tx.mass();
         let fee = tx.mtx.calculated_fee.expect("fee is expected to be populated");
         assert_ne!(mass, 0, "mass field is expected to be set when inserting to the mempool");
-        Self::new(fee, mass, tx.mtx.tx.clone())
+        Self::with_feerate(fee, mass, tx.effective_fee_rate(), tx.mtx.tx.clone())
     }
 }
 
@@ -4,7 +4,7 @@ use kaspa_consensus_core::{
     tx::{Transaction, TransactionId},
 };
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     sync::Arc,
 };
 
@@ -160,3 +160,44 @@ impl TemplateTransactionSelector for TakeAllSelector {
         true
     }
 }
+
+/// A selector which places a set of reserved transactions (typically high-priority local submissions) at the
+/// head of the template and delegates the selection of the remaining block mass to an inner selector.
+///
+/// The inner selector is expected to be built with a policy whose mass limit excludes the reserved mass, and
+/// over a frontier excluding the reserved transactions.
+pub struct ReservedMassSelector {
+    reserved: Vec<Arc<Transaction>>,
+    reserved_ids: HashSet<TransactionId>,
+    inner: Box<dyn TemplateTransactionSelector>,
+}
+
+impl ReservedMassSelector {
+    pub fn new(
+        reserved: Vec<Arc<Transaction>>,
+        reserved_ids: HashSet<TransactionId>,
+        inner: Box<dyn TemplateTransactionSelector>,
+    ) -> Self {
+        Self { reserved, reserved_ids, inner }
+    }
+}
+
+impl TemplateTransactionSelector for ReservedMassSelector {
+    fn select_transactions(&mut self) -> Vec<Transaction> {
+        // Reserved transactions are drained on the first call so that subsequent calls only return inner selections
+        let mut transactions = self.reserved.drain(..).map(|tx| tx.as_ref().clone()).collect::<Vec<_>>();
+        transactions.extend(self.inner.select_transactions());
+        transactions
+    }
+
+    fn reject_selection(&mut self, tx_id: TransactionId) {
+        // The mass of rejected reserved transactions is not reused since the inner selector is unaware of it
+        if !self.reserved_ids.contains(&tx_id) {
+            self.inner.reject_selection(tx_id);
+        }
+    }
+
+    fn is_successful(&self) -> bool {
+        self.inner.is_successful()
+    }
+}
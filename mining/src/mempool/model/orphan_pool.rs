@@ -264,33 +264,36 @@ impl OrphanPool {
         &mut self.chained_orphans
     }
 
-    pub(crate) fn expire_low_priority_transactions(&mut self, virtual_daa_score: u64) -> RuleResult<()> {
+    /// Removes the expired orphans and returns their ids
+    pub(crate) fn expire_transactions(&mut self, virtual_daa_score: u64) -> RuleResult<Vec<TransactionId>> {
         if virtual_daa_score < self.last_expire_scan + self.config.orphan_expire_scan_interval_daa_score {
-            return Ok(());
+            return Ok(vec![]);
         }
 
-        // Never expire high priority transactions
-        // Remove all transactions whose `added_at_daa_score` is older then TransactionExpireIntervalDAAScore
-        let expired_low_priority_transactions: Vec<TransactionId> = self
+        // Remove all low priority transactions whose `added_at_daa_score` is older than the orphan expire interval,
+        // and all high priority transactions older than the high priority expire interval (if set)
+        let expired_transactions: Vec<TransactionId> = self
             .all_orphans
             .values()
             .filter_map(|x| {
-                if (x.priority == Priority::Low)
-                    && virtual_daa_score > x.added_at_daa_score + self.config.orphan_expire_interval_daa_score
-                {
-                    Some(x.id())
-                } else {
-                    None
-                }
+                let expired = match x.priority {
+                    Priority::Low => virtual_daa_score > x.added_at_daa_score + self.config.orphan_expire_interval_daa_score,
+                    Priority::High => {
+                        self.config.high_priority_transaction_expire_interval_daa_score > 0
+                            && virtual_daa_score
+                                > x.added_at_daa_score + self.config.high_priority_transaction_expire_interval_daa_score
+                    }
+                };
+                expired.then_some(x.id())
             })
             .collect();
 
-        for transaction_id in expired_low_priority_transactions.iter() {
+        for transaction_id in expired_transactions.iter() {
             self.remove_orphan(transaction_id, false, TxRemovalReason::Expired, "")?;
         }
 
         self.last_expire_scan = virtual_daa_score;
-        Ok(())
+        Ok(expired_transactions)
     }
 }
 
@@ -9,14 +9,15 @@ use crate::{
             tx::{DoubleSpend, MempoolTransaction},
             utxo_set::MempoolUtxoSet,
         },
-        tx::Priority,
+        tx::{PackageStats, Priority, TransactionPackage},
     },
     model::{topological_index::TopologicalIndex, TransactionIdSet},
     Policy,
 };
+use itertools::Itertools;
 use kaspa_consensus_core::{
     block::TemplateTransactionSelector,
-    tx::{MutableTransaction, TransactionId, TransactionOutpoint},
+    tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint},
 };
 use kaspa_core::{debug, time::unix_now, trace};
 use std::{
@@ -25,7 +26,7 @@ use std::{
     sync::Arc,
 };
 
-use super::frontier::Frontier;
+use super::frontier::{selectors::ReservedMassSelector, Frontier};
 
 /// Pool of transactions to be included in a block template
 ///
@@ -67,6 +68,9 @@ pub(crate) struct TransactionsPool {
     /// Transactions with no parents in the mempool -- ready to be inserted into a block template
     ready_transactions: Frontier,
 
+    /// The subset of ready transactions having a high priority
+    high_priority_ready_transactions: TransactionIdSet,
+
     last_expire_scan_daa_score: u64,
 
     /// last expire scan time in milliseconds
@@ -87,6 +91,7 @@ impl TransactionsPool {
             parent_transactions: TransactionsEdges::default(),
             chained_transactions: TransactionsEdges::default(),
             ready_transactions: Default::default(),
+            high_priority_ready_transactions: Default::default(),
             last_expire_scan_daa_score: 0,
             last_expire_scan_time: unix_now(),
             utxo_set: MempoolUtxoSet::new(),
@@ -120,9 +125,23 @@ impl TransactionsPool {
         // The transactions chained to the added transaction cannot be stored
         // here yet since, by definition, they would have been orphans.
         let parents = self.get_parent_transaction_ids_in_pool(&transaction.mtx);
+
+        // Register the transaction in the packages of its ancestors. As stated above, the transaction
+        // has no descendants yet.
+        let mut transaction = transaction;
+        let ancestors = self.get_ancestor_ids_in_pool(parents.iter().copied());
+        transaction.package = TransactionPackage { ancestors: self.get_package_stats(ancestors.iter()), ..Default::default() };
+        let (fee, mass) = (transaction.mtx.calculated_fee.unwrap(), transaction.mtx.tx.mass());
+        for ancestor_id in ancestors.iter() {
+            self.update_package(ancestor_id, |package| package.descendants.add(fee, mass));
+        }
+
         self.parent_transactions.insert(id, parents.clone());
         if parents.is_empty() {
             self.ready_transactions.insert((&transaction).into());
+            if transaction.priority == Priority::High {
+                self.high_priority_ready_transactions.insert(id);
+            }
         }
         for parent_id in parents {
             let entry = self.chained_transactions.entry(parent_id).or_default();
@@ -138,6 +157,10 @@ impl TransactionsPool {
 
     /// Fully removes the transaction from all relational sets, as well as from the UTXO set
     pub(crate) fn remove_transaction(&mut self, transaction_id: &TransactionId) -> RuleResult<MempoolTransaction> {
+        // Collect the relatives whose packages include the removed transaction before the relations are removed
+        let ancestors = self.get_ancestor_ids_in_pool(self.parent_transactions.get(transaction_id).into_iter().flatten().copied());
+        let descendants = self.get_redeemer_ids_in_pool(transaction_id);
+
         // Remove all bijective parent/chained relations
         if let Some(parents) = self.parent_transactions.get(transaction_id) {
             for parent in parents.iter() {
@@ -153,6 +176,9 @@ impl TransactionsPool {
                     if parents.is_empty() {
                         let tx = self.all_transactions.get(chain).unwrap();
                         self.ready_transactions.insert(tx.into());
+                        if tx.priority == Priority::High {
+                            self.high_priority_ready_transactions.insert(*chain);
+                        }
                     }
                 }
             }
@@ -164,6 +190,8 @@ impl TransactionsPool {
         let removed_tx = self.all_transactions.remove(transaction_id).ok_or(RuleError::RejectMissingTransaction(*transaction_id))?;
 
         self.ready_transactions.remove(&(&removed_tx).into());
+        self.high_priority_ready_transactions.remove(transaction_id);
+        self.update_packages_after_removal(&removed_tx, &ancestors, &descendants);
 
         // TODO: consider using `self.parent_transactions.get(transaction_id)`
         // The tradeoff to consider is whether it might be possible that a parent tx exists in the pool
@@ -182,16 +210,135 @@ impl TransactionsPool {
         Ok(removed_tx)
     }
 
+    /// Returns the ids of all in-pool transactions being directly or indirectly parents of the transactions
+    /// with ids `parent_ids`, `parent_ids` included.
+    ///
+    /// NOTE: like [`Pool::get_redeemer_ids_in_pool`], this operation's complexity might become linear in the size
+    /// of the mempool if the mempool contains deeply chained transactions
+    fn get_ancestor_ids_in_pool(&self, parent_ids: impl Iterator<Item = TransactionId>) -> TransactionIdSet {
+        let mut ancestors = TransactionIdSet::new();
+        let mut queue = parent_ids.collect::<Vec<_>>();
+        while let Some(id) = queue.pop() {
+            if self.has(&id) && ancestors.insert(id) {
+                queue.extend(self.parent_transactions.get(&id).into_iter().flatten().copied());
+            }
+        }
+        ancestors
+    }
+
+    /// Returns the ids of the transaction and of all its in-pool redeemers, ordered such that each transaction follows
+    /// all its redeemers. Removing transactions in this order keeps every removed transaction free of in-pool redeemers,
+    /// so that the packages of the remaining ancestors are updated incrementally
+    pub(crate) fn get_removal_order_with_redeemers(&self, transaction_id: &TransactionId) -> Vec<TransactionId> {
+        // Depth-first post-order traversal over the redeemer relations
+        let mut order = vec![];
+        let mut visited = TransactionIdSet::new();
+        let mut stack = vec![(*transaction_id, false)];
+        while let Some((id, is_expanded)) = stack.pop() {
+            if is_expanded {
+                order.push(id);
+            } else if self.has(&id) && visited.insert(id) {
+                stack.push((id, true));
+                stack.extend(self.chained_transactions.get(&id).into_iter().flatten().map(|redeemer_id| (*redeemer_id, false)));
+            }
+        }
+        order
+    }
+
+    fn get_package_stats<'a>(&self, transaction_ids: impl Iterator<Item = &'a TransactionId>) -> PackageStats {
+        let mut stats = PackageStats::default();
+        for tx in transaction_ids.filter_map(|id| self.all_transactions.get(id)) {
+            stats.add(tx.mtx.calculated_fee.unwrap(), tx.mtx.tx.mass());
+        }
+        stats
+    }
+
+    /// Applies `update` to the package of a transaction, keeping its ranking in the ready transactions frontier consistent
+    fn update_package(&mut self, transaction_id: &TransactionId, update: impl FnOnce(&mut TransactionPackage)) {
+        let Some(tx) = self.all_transactions.get_mut(transaction_id) else {
+            return;
+        };
+        // The frontier key depends on the descendants so it must be removed prior to the update and reinserted after
+        let is_ready = self.ready_transactions.remove(&(&*tx).into());
+        update(&mut tx.package);
+        if is_ready {
+            self.ready_transactions.insert((&*tx).into());
+        }
+    }
+
+    fn update_packages_after_removal(
+        &mut self,
+        removed_tx: &MempoolTransaction,
+        ancestors: &TransactionIdSet,
+        descendants: &[TransactionId],
+    ) {
+        if ancestors.is_empty() || descendants.is_empty() {
+            // Removing the transaction does not disconnect any ancestor from any descendant, so only the
+            // removed transaction itself has to be subtracted from the packages of its relatives
+            let (fee, mass) = (removed_tx.mtx.calculated_fee.unwrap(), removed_tx.mtx.tx.mass());
+            for ancestor_id in ancestors.iter() {
+                self.update_package(ancestor_id, |package| package.descendants.sub(fee, mass));
+            }
+            for descendant_id in descendants.iter() {
+                self.update_package(descendant_id, |package| package.ancestors.sub(fee, mass));
+            }
+        } else {
+            // Some descendants of the ancestors might have been reachable only through the removed transaction, hence the
+            // packages of all relatives are rebuilt from scratch. Note that the mempool removes redeemers prior to the
+            // transactions they redeem (see `get_removal_order_with_redeemers`), so this is only reached when a transaction
+            // having both in-pool ancestors and redeemers is removed alone, which a consistent consensus never requires
+            for ancestor_id in ancestors.iter() {
+                let stats = self.get_package_stats(self.get_redeemer_ids_in_pool(ancestor_id).iter());
+                self.update_package(ancestor_id, |package| package.descendants = stats);
+            }
+            for descendant_id in descendants.iter() {
+                let parents = self.parent_transactions.get(descendant_id).into_iter().flatten().copied();
+                let stats = self.get_package_stats(self.get_ancestor_ids_in_pool(parents).iter());
+                self.update_package(descendant_id, |package| package.ancestors = stats);
+            }
+        }
+    }
+
     pub(crate) fn update_revalidated_transaction(&mut self, transaction: MutableTransaction) -> bool {
-        if let Some(tx) = self.all_transactions.get_mut(&transaction.id()) {
-            // Make sure to update the overall estimated size since the updated transaction might have a different size
-            self.estimated_size -= tx.mtx.mempool_estimated_bytes();
-            tx.mtx = transaction;
-            self.estimated_size += tx.mtx.mempool_estimated_bytes();
-            true
+        let transaction_id = transaction.id();
+        let Some(tx) = self.all_transactions.get_mut(&transaction_id) else {
+            return false;
+        };
+        let previous = (tx.mtx.calculated_fee.unwrap(), tx.mtx.tx.mass());
+        // The frontier key depends on the fee and mass so it must be removed prior to the update and reinserted after
+        self.ready_transactions.remove(&(&*tx).into());
+        // Make sure to update the overall estimated size since the updated transaction might have a different size
+        self.estimated_size -= tx.mtx.mempool_estimated_bytes();
+        tx.mtx = transaction;
+        self.estimated_size += tx.mtx.mempool_estimated_bytes();
+        let current = (tx.mtx.calculated_fee.unwrap(), tx.mtx.tx.mass());
+        // Refresh the frontier and high-priority memberships from the current relations so that the reserved block
+        // mass never refers to a transaction which is not ready
+        let is_ready = self.parent_transactions.get(&transaction_id).map_or(true, |parents| parents.is_empty());
+        if is_ready {
+            self.ready_transactions.insert((&*tx).into());
+            if tx.priority == Priority::High {
+                self.high_priority_ready_transactions.insert(transaction_id);
+            }
         } else {
-            false
+            self.high_priority_ready_transactions.remove(&transaction_id);
+        }
+
+        // The packages of all relatives include the fee and mass of the transaction
+        if current != previous {
+            let replace = |stats: &mut PackageStats| {
+                stats.sub(previous.0, previous.1);
+                stats.add(current.0, current.1);
+            };
+            let parents = self.parent_transactions.get(&transaction_id).into_iter().flatten().copied();
+            for ancestor_id in self.get_ancestor_ids_in_pool(parents).iter() {
+                self.update_package(ancestor_id, |package| replace(&mut package.descendants));
+            }
+            for descendant_id in self.get_redeemer_ids_in_pool(&transaction_id).iter() {
+                self.update_package(descendant_id, |package| replace(&mut package.ancestors));
+            }
         }
+        true
     }
 
     pub(crate) fn ready_transaction_count(&self) -> usize {
@@ -202,9 +349,43 @@ impl TransactionsPool {
         self.ready_transactions.total_mass()
     }
 
-    /// Dynamically builds a transaction selector based on the specific state of the ready transactions frontier
+    /// Builds a transaction selector following the configured selection strategy. High-priority transactions are
+    /// selected first into the reserved block mass if such a reservation is configured
     pub(crate) fn build_selector(&self) -> Box<dyn TemplateTransactionSelector> {
-        self.ready_transactions.build_selector(&Policy::new(self.config.maximum_mass_per_block))
+        let strategy = self.config.transaction_selection_strategy;
+        let (reserved, reserved_mass) = self.select_reserved_transactions();
+        let reserved_ids = reserved.iter().map(|tx| tx.id()).collect();
+        let policy = Policy::new(self.config.maximum_mass_per_block - reserved_mass);
+        // Reserved transactions are excluded from the frontier selection so that their mass is accounted for only once
+        let selector = self.ready_transactions.build_selector_with_strategy(strategy, &policy, &reserved_ids, reserved_mass);
+        if reserved.is_empty() {
+            selector
+        } else {
+            Box::new(ReservedMassSelector::new(reserved, reserved_ids, selector))
+        }
+    }
+
+    /// Selects ready high-priority transactions by descending feerate into the reserved block mass
+    fn select_reserved_transactions(&self) -> (Vec<Arc<Transaction>>, u64) {
+        let capacity = self.config.high_priority_reserved_mass;
+        if capacity == 0 || self.high_priority_ready_transactions.is_empty() {
+            return (vec![], 0);
+        }
+        let candidates = self
+            .high_priority_ready_transactions
+            .iter()
+            .map(|id| self.all_transactions.get(id).unwrap())
+            .sorted_by(|a, b| b.effective_fee_rate().total_cmp(&a.effective_fee_rate()));
+        let mut reserved = vec![];
+        let mut reserved_mass = 0;
+        for tx in candidates {
+            let mass = tx.mtx.tx.mass();
+            if reserved_mass + mass <= capacity {
+                reserved_mass += mass;
+                reserved.push(tx.mtx.tx.clone());
+            }
+        }
+        (reserved, reserved_mass)
     }
 
     /// Builds a feerate estimator based on internal state of the ready transactions frontier
@@ -213,9 +394,9 @@ impl TransactionsPool {
     }
 
     /// Returns the exceeding low-priority transactions having the lowest fee rates in order
-    /// to make room for `transaction`. The returned transactions
-    /// are guaranteed to be unchained (no successor in mempool) and to not be parent of
-    /// `transaction`.
+    /// to make room for `transaction`. The returned transactions are guaranteed to be ready
+    /// (no parent in mempool) and to not be ancestors of `transaction`. Each of them is expected
+    /// to be removed along with all its redeemers, so the whole package is accounted for.
     ///
     /// An error is returned if the mempool is filled with high priority transactions, or
     /// there are not enough lower feerate transactions that can be removed to accommodate `transaction`
@@ -234,6 +415,7 @@ impl TransactionsPool {
         // Returns a vector of transactions to be removed (the caller has to actually remove)
         let feerate_threshold = transaction.calculated_feerate().unwrap();
         let mut txs_to_remove = Vec::with_capacity(1); // Normally we expect a single removal
+        let mut selection = TransactionIdSet::new();
         let mut selection_overall_size = 0;
         for tx in self
             .ready_transactions
@@ -247,17 +429,23 @@ impl TransactionsPool {
                 continue;
             }
 
-            // We are iterating ready txs by ascending feerate so the pending tx has lower feerate than all remaining txs
-            if tx.fee_rate() > feerate_threshold {
+            // We are iterating ready txs by ascending effective feerate so the pending tx has lower feerate than all remaining
+            // txs. Note that the feerate of the evicted package (tx + redeemers) is bounded by the effective feerate of tx.
+            if tx.effective_fee_rate() > feerate_threshold {
                 let err = RuleError::RejectMempoolIsFull;
                 debug!("Transaction {} with feerate {} has been rejected: {}", transaction.id(), feerate_threshold, err);
                 return Err(err);
             }
 
             txs_to_remove.push(tx.id());
-            selection_overall_size += tx.mtx.mempool_estimated_bytes();
+            // Redeemers might be shared by several selected packages so they are accounted for only once
+            for redeemer_id in redeemers {
+                if selection.insert(redeemer_id) {
+                    selection_overall_size += self.all_transactions.get(&redeemer_id).unwrap().mtx.mempool_estimated_bytes();
+                }
+            }
 
-            if self.len() + 1 - txs_to_remove.len() <= self.config.maximum_transaction_count
+            if self.len() + 1 - selection.len() <= self.config.maximum_transaction_count
                 && self.estimated_size + transaction_size - selection_overall_size <= self.config.mempool_size_limit
             {
                 return Ok(txs_to_remove);
@@ -277,6 +465,30 @@ impl TransactionsPool {
         Err(RuleError::RejectMempoolIsFull)
     }
 
+    /// Returns the low-priority ready transactions having the lowest fee rates which, along with their redeemers, make up
+    /// at least `count` transactions (or all low-priority ready transactions if there are not enough of them)
+    pub(crate) fn lowest_feerate_transactions(&self, count: usize) -> Vec<TransactionId> {
+        let mut selected = Vec::new();
+        let mut selection = TransactionIdSet::new();
+        for tx in self
+            .ready_transactions
+            .ascending_iter()
+            .map(|tx| self.all_transactions.get(&tx.id()).unwrap())
+            .filter(|mtx| mtx.priority == Priority::Low)
+        {
+            if selection.len() >= count {
+                break;
+            }
+            if selection.contains(&tx.id()) {
+                continue;
+            }
+            selected.push(tx.id());
+            selection.insert(tx.id());
+            selection.extend(self.get_redeemer_ids_in_pool(&tx.id()));
+        }
+        selected
+    }
+
     pub(crate) fn get_estimated_size(&self) -> usize {
         self.estimated_size
     }
@@ -312,7 +524,7 @@ impl TransactionsPool {
         }
     }
 
-    pub(crate) fn collect_expired_low_priority_transactions(&mut self, virtual_daa_score: u64) -> Vec<TransactionId> {
+    pub(crate) fn collect_expired_transactions(&mut self, virtual_daa_score: u64) -> Vec<TransactionId> {
         let now = unix_now();
         if virtual_daa_score < self.last_expire_scan_daa_score + self.config.transaction_expire_scan_interval_daa_score
             || now < self.last_expire_scan_time + self.config.transaction_expire_scan_interval_milliseconds
@@ -323,14 +535,16 @@ impl TransactionsPool {
         self.last_expire_scan_daa_score = virtual_daa_score;
         self.last_expire_scan_time = now;
 
-        // Never expire high priority transactions
-        // Remove all transactions whose added_at_daa_score is older then transaction_expire_interval_daa_score
+        // Remove all transactions whose added_at_daa_score is older than the expire interval of their priority.
+        // High priority transactions never expire if their expire interval is zero
         self.all_transactions
             .values()
             .filter_map(|x| {
-                if (x.priority == Priority::Low)
-                    && virtual_daa_score > x.added_at_daa_score + self.config.transaction_expire_interval_daa_score
-                {
+                let expire_interval = match x.priority {
+                    Priority::Low => self.config.transaction_expire_interval_daa_score,
+                    Priority::High => self.config.high_priority_transaction_expire_interval_daa_score,
+                };
+                if (x.priority == Priority::Low || expire_interval > 0) && virtual_daa_score > x.added_at_daa_score + expire_interval {
                     Some(x.id())
                 } else {
                     None
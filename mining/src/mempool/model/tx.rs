@@ -1,4 +1,4 @@
-use crate::mempool::tx::{Priority, RbfPolicy};
+use crate::mempool::tx::{Priority, RbfPolicy, TransactionPackage};
 use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint};
 use kaspa_mining_errors::mempool::RuleError;
 use std::{
@@ -10,12 +10,14 @@ pub(crate) struct MempoolTransaction {
     pub(crate) mtx: MutableTransaction,
     pub(crate) priority: Priority,
     pub(crate) added_at_daa_score: u64,
+    /// In-mempool ancestors and descendants of this transaction. Maintained by the transactions pool only
+    pub(crate) package: TransactionPackage,
 }
 
 impl MempoolTransaction {
     pub(crate) fn new(mtx: MutableTransaction, priority: Priority, added_at_daa_score: u64) -> Self {
         assert_eq!(mtx.tx.inputs.len(), mtx.entries.len());
-        Self { mtx, priority, added_at_daa_score }
+        Self { mtx, priority, added_at_daa_score, package: Default::default() }
     }
 
     pub(crate) fn id(&self) -> TransactionId {
@@ -27,6 +29,20 @@ impl MempoolTransaction {
         assert!(contextual_mass > 0, "expected to be called for validated txs only");
         self.mtx.calculated_fee.unwrap() as f64 / contextual_mass as f64
     }
+
+    /// Returns the feerate of this transaction along with all its in-mempool descendants
+    pub(crate) fn descendants_fee_rate(&self) -> f64 {
+        let contextual_mass = self.mtx.tx.mass();
+        assert!(contextual_mass > 0, "expected to be called for validated txs only");
+        let fee = self.mtx.calculated_fee.unwrap() + self.package.descendants.fee;
+        fee as f64 / (contextual_mass + self.package.descendants.mass) as f64
+    }
+
+    /// Returns the feerate by which this transaction is ranked among ready transactions, i.e., its own feerate
+    /// possibly raised by the fees of its descendants (CPFP)
+    pub(crate) fn effective_fee_rate(&self) -> f64 {
+        self.fee_rate().max(self.descendants_fee_rate())
+    }
 }
 
 impl RbfPolicy {
@@ -85,6 +101,7 @@ pub(crate) enum TxRemovalReason {
     InvalidInBlockTemplate,
     RevalidationWithMissingOutpoints,
     ReplacedByFee,
+    MemoryPressure,
 }
 
 impl TxRemovalReason {
@@ -99,6 +116,7 @@ impl TxRemovalReason {
             TxRemovalReason::InvalidInBlockTemplate => "invalid in block template",
             TxRemovalReason::RevalidationWithMissingOutpoints => "revalidation with missing outpoints",
             TxRemovalReason::ReplacedByFee => "replaced by fee",
+            TxRemovalReason::MemoryPressure => "memory pressure",
         }
     }
 
@@ -1,26 +1,33 @@
 use crate::mempool::{
     errors::RuleResult,
     model::{pool::Pool, tx::TxRemovalReason},
+    tx::Priority,
     Mempool,
 };
 use kaspa_consensus_core::tx::TransactionId;
-use kaspa_core::debug;
+use kaspa_core::{debug, warn};
 use kaspa_utils::iter::IterExtensions;
+use std::sync::atomic::Ordering;
 
 impl Mempool {
+    /// Removes the transaction (and its redeemers if `remove_redeemers` is set) and returns the ids of all the removed
+    /// transactions, including orphans depending on them
     pub(crate) fn remove_transaction(
         &mut self,
         transaction_id: &TransactionId,
         remove_redeemers: bool,
         reason: TxRemovalReason,
         extra_info: &str,
-    ) -> RuleResult<()> {
+    ) -> RuleResult<Vec<TransactionId>> {
         if self.orphan_pool.has(transaction_id) {
-            return self.orphan_pool.remove_orphan(transaction_id, true, reason, extra_info).map(|_| ());
+            return self
+                .orphan_pool
+                .remove_orphan(transaction_id, true, reason, extra_info)
+                .map(|removed| removed.into_iter().map(|x| x.id()).collect());
         }
 
         if !self.transaction_pool.has(transaction_id) {
-            return Ok(());
+            return Ok(vec![]);
         }
 
         let mut removed_transactions = vec![*transaction_id];
@@ -29,8 +36,13 @@ impl Mempool {
             removed_transactions.extend(self.transaction_pool.get_redeemer_ids_in_pool(transaction_id));
         }
 
+        // Redeemers are removed prior to the transactions they redeem, see `get_removal_order_with_redeemers`
+        let removal_order = match remove_redeemers {
+            true => self.transaction_pool.get_removal_order_with_redeemers(transaction_id),
+            false => vec![*transaction_id],
+        };
         let mut removed_orphans: Vec<TransactionId> = vec![];
-        for tx_id in removed_transactions.iter() {
+        for tx_id in removal_order.iter() {
             // Remove the tx from the transaction pool and the UTXO set (handled within the pool)
             let tx = self.transaction_pool.remove_transaction(tx_id)?;
             // Update/remove descendent orphan txs (depending on `remove_redeemers`)
@@ -65,6 +77,25 @@ impl Mempool {
             },
         }
 
-        Ok(())
+        Ok(removed_transactions)
+    }
+
+    /// Evicts about `fraction` of the low-priority transactions, lowest fee rates first, along with their redeemers in
+    /// order to relieve memory pressure. Returns the ids of all the removed transactions
+    pub(crate) fn shrink(&mut self, fraction: f64) -> Vec<TransactionId> {
+        let low_priority_count = self.transaction_pool.all_transaction_ids_with_priority(Priority::Low).len();
+        let count = (low_priority_count as f64 * fraction.clamp(0.0, 1.0)).ceil() as usize;
+        let mut removed_transactions = vec![];
+        for transaction_id in self.transaction_pool.lowest_feerate_transactions(count) {
+            match self.remove_transaction(&transaction_id, true, TxRemovalReason::Muted, "") {
+                Ok(removed) => removed_transactions.extend(removed),
+                Err(err) => warn!("Failed to remove transaction {} from mempool: {}", transaction_id, err),
+            }
+        }
+        self.counters.tx_evicted_counts.fetch_add(removed_transactions.len() as u64, Ordering::Relaxed);
+        if !removed_transactions.is_empty() {
+            debug!("Removed {} transactions ({})", removed_transactions.len(), TxRemovalReason::MemoryPressure);
+        }
+        removed_transactions
     }
 }
@@ -145,6 +145,7 @@ impl Mempool {
             return Err(RuleError::RejectDuplicate(transaction_id));
         }
 
+        self.check_transaction_subnetwork(transaction)?;
         if !self.config.accept_non_standard {
             self.check_transaction_standard_in_isolation(transaction)?;
         }
@@ -0,0 +1,12 @@
+use kaspa_consensus_core::tx::MutableTransaction;
+
+/// A transaction submitted to this node (i.e., of high priority), held by the mempool until accepted into a
+/// block or expired
+#[derive(Debug, Clone)]
+pub struct LocalTransaction {
+    pub transaction: MutableTransaction,
+    pub added_at_daa_score: u64,
+    /// The DAA score past which the transaction expires, `None` if it never does
+    pub expire_daa_score: Option<u64>,
+    pub is_orphan: bool,
+}
@@ -2,6 +2,7 @@ use kaspa_consensus_core::tx::TransactionId;
 use std::collections::HashSet;
 
 pub mod candidate_tx;
+pub mod local_tx;
 pub mod owner_txs;
 pub mod topological_index;
 pub mod topological_sort;
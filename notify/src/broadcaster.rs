@@ -1,6 +1,6 @@
 extern crate derive_more;
 use crate::{
-    connection::Connection,
+    connection::{Connection, OverflowPolicy},
     error::Result,
     events::{EventArray, EventType},
     listener::ListenerId,
@@ -76,6 +76,10 @@ where
         result
     }
 
+    fn contains(&self, id: &ListenerId) -> bool {
+        self.0.values().any(|encoding_set| encoding_set.values().any(|connection_set| connection_set.contains_key(id)))
+    }
+
     // fn len(&self) -> usize {
     //     self.0.values().map(|encodings| encodings.values().map(|connections| connections.len()).count()).count()
     // }
@@ -87,6 +91,60 @@ impl<C: Connection> Default for Plan<C> {
     }
 }
 
+/// Bounded queue of the messages sent to a listener connection, drained by a dedicated task
+struct Outbox<N, C>
+where
+    N: Notification,
+    C: Connection<Notification = N>,
+{
+    sender: Sender<C::Message>,
+    capacity: usize,
+    encoding: C::Encoding,
+    /// Number of notifications dropped since the last queued one
+    dropped: u64,
+}
+
+impl<N, C> Outbox<N, C>
+where
+    N: Notification,
+    C: Connection<Notification = N>,
+{
+    fn new(connection: C, capacity: usize) -> Self {
+        // An extra slot is reserved for the gap marker
+        let (sender, receiver) = async_channel::bounded::<C::Message>(capacity + 1);
+        let encoding = connection.encoding();
+        workflow_core::task::spawn(async move {
+            while let Ok(message) = receiver.recv().await {
+                if connection.send(message).await.is_err() && connection.is_closed() {
+                    break;
+                }
+            }
+            receiver.close();
+        });
+        Self { sender, capacity, encoding, dropped: 0 }
+    }
+
+    /// Queues `message` or drops it if the queue is full, in which case a gap marker is queued ahead of the next message.
+    ///
+    /// Returns `false` if the connection is closed.
+    fn push(&mut self, message: C::Message) -> bool {
+        if self.sender.is_closed() {
+            return false;
+        }
+        if self.sender.len() >= self.capacity {
+            self.dropped += 1;
+            return true;
+        }
+        if self.dropped > 0 {
+            if let Some(gap) = N::notifications_gap(self.dropped) {
+                let _ = self.sender.try_send(C::into_message(&gap, &self.encoding));
+            }
+            self.dropped = 0;
+        }
+        self.sender.try_send(message).is_ok()
+    }
+}
+
 #[derive(Clone, Debug)]
 enum Ctl<C>
 where
@@ -151,6 +209,8 @@ where
         workflow_core::task::spawn(async move {
             // Broadcasting plan by event type
             let mut plan = EventArray::<Plan<C>>::default();
+            // Outboxes of the listeners with a bounded overflow policy
+            let mut outboxes: HashMap<ListenerId, Outbox<N, C>> = HashMap::new();
             // Create a store for closed connections to be removed from the plan
             let mut purge: Vec<ListenerId> = Vec::new();
             loop {
@@ -160,11 +220,17 @@ where
                             match ctl {
                                 Ctl::Register(subscription, id, connection) => {
                                     let event_type = subscription.event_type();
+                                    if let OverflowPolicy::Bounded(capacity) = connection.overflow_policy() {
+                                        outboxes.entry(id).or_insert_with(|| Outbox::new(connection.clone(), capacity));
+                                    }
                                     plan[event_type].insert(subscription.broadcasting(&context), id, connection);
                                     debug!("[{}] insert {} subscription, count = {}, capacity = {}", self, event_type, plan[event_type].len(), plan[event_type].capacity());
                                 },
                                 Ctl::Unregister(event_type, id) => {
                                     plan[event_type].remove(&id);
+                                    if !plan.iter().any(|plan| plan.contains(&id)) {
+                                        outboxes.remove(&id);
+                                    }
                                     debug!("[{}] remove {} subscription, count = {}, capacity = {}", self, event_type, plan[event_type].len(), plan[event_type].capacity());
                                 },
                             }
@@ -184,7 +250,17 @@ where
                                         // ... by message encoding
                                         let message = C::into_message(&applied_notification, encoding);
                                         for (id, connection) in connection_set.iter() {
-                                            // ... to listeners connections
+                                            // ... to listeners outboxes
+                                            if let Some(outbox) = outboxes.get_mut(id) {
+                                                if !outbox.push(message.clone()) {
+                                                    trace!("[{}] could not queue a notification for listener {id} because its connection is closed - removing it", self);
+                                                    purge.push(*id);
+                                                } else if outbox.dropped == 1 {
+                                                    debug!("[{}] outbox of listener {id} is full, dropping notifications", self);
+                                                }
+                                                continue;
+                                            }
+                                            // ... or directly to listeners connections
                                             match connection.send(message.clone()).await {
                                                 Ok(_) => {
                                                     trace!("[{}] sent notification {notification} to listener {id}", self);
@@ -201,7 +277,12 @@ where
                                 }
                             }
                             // Remove closed connections
-                            purge.drain(..).for_each(|id| { plan[event].remove(&id); });
+                            purge.drain(..).for_each(|id| {
+                                plan[event].remove(&id);
+                                if !plan.iter().any(|plan| plan.contains(&id)) {
+                                    outboxes.remove(&id);
+                                }
+                            });
 
                         } else {
                             break;
@@ -264,7 +345,8 @@ mod tests {
         notifier::test_helpers::{
             overall_test_steps, utxos_changed_test_steps, virtual_chain_changed_test_steps, Step, TestConnection, SYNC_MAX_DELAY,
         },
-        subscription::context::SubscriptionContext,
+        scope::{BlockAddedScope, Scope},
+        subscription::{context::SubscriptionContext, Command, Mutation},
     };
     use async_channel::{unbounded, Sender};
     use tokio::time::timeout;
@@ -455,4 +537,58 @@ mod tests {
         let mut test = Test::new("UtxosChanged broadcast", 3, utxos_changed_test_steps(0));
         test.run().await;
     }
+
+    #[tokio::test]
+    async fn test_overflow() {
+        kaspa_core::log::try_init_logger("trace,kaspa_notify=trace");
+        const CAPACITY: usize = 2;
+        let subscription_context = SubscriptionContext::new();
+        let (sync_sender, sync_receiver) = unbounded();
+        let (notification_sender, notification_receiver) = unbounded();
+        let broadcaster =
+            Arc::new(TestBroadcaster::new("test", 0, subscription_context.clone(), notification_receiver, Some(sync_sender)));
+        broadcaster.start();
+
+        // The listener consumes its channel (of capacity 1) only when explicitly requested
+        let (sender, receiver) = async_channel::bounded(1);
+        let connection =
+            TestConnection::new("test", sender, ChannelType::Closable).with_overflow_policy(OverflowPolicy::Bounded(CAPACITY));
+        let mut listener = Listener::new(0, connection);
+        let mutation = Mutation::new(Command::Start, Scope::BlockAdded(BlockAddedScope {}));
+        listener.mutate(mutation, Default::default(), &subscription_context).unwrap();
+        broadcaster.register(listener.subscriptions[EventType::BlockAdded].clone(), 0, listener.connection()).unwrap();
+        timeout(SYNC_MAX_DELAY, sync_receiver.recv()).await.unwrap().unwrap();
+
+        let block_added = |data| TestNotification::BlockAdded(BlockAddedNotification { data });
+        let send = |data| {
+            let notification_sender = notification_sender.clone();
+            let sync_receiver = sync_receiver.clone();
+            async move {
+                notification_sender.send(block_added(data)).await.unwrap();
+                timeout(SYNC_MAX_DELAY, sync_receiver.recv()).await.unwrap().unwrap();
+            }
+        };
+
+        // Flood the listener: one notification sits in its channel, one is held by the outbox task
+        // and the outbox queues CAPACITY more, all the others are dropped
+        const FLOOD: u64 = 10;
+        for data in 0..FLOOD {
+            send(data).await;
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let delivered = 2 + CAPACITY as u64;
+        for data in 0..delivered {
+            assert_eq!(timeout(SYNC_MAX_DELAY, receiver.recv()).await.unwrap().unwrap(), block_added(data));
+        }
+        assert!(timeout(std::time::Duration::from_millis(100), receiver.recv()).await.is_err());
+
+        // The next notification is preceded by a gap marker reporting the dropped ones
+        send(FLOOD).await;
+        let gap = TestNotification::NotificationsGap(NotificationsGapNotification { dropped: FLOOD - delivered });
+        assert_eq!(timeout(SYNC_MAX_DELAY, receiver.recv()).await.unwrap().unwrap(), gap);
+        assert_eq!(timeout(SYNC_MAX_DELAY, receiver.recv()).await.unwrap().unwrap(), block_added(FLOOD));
+
+        notification_sender.close();
+        assert!(broadcaster.join().await.is_ok(), "broadcaster failed to stop");
+    }
 }
@@ -16,6 +16,24 @@ pub trait Connection: Clone + Display + Debug + Send + Sync + 'static {
     async fn send(&self, message: Self::Message) -> Result<(), Self::Error>;
     fn close(&self) -> bool;
     fn is_closed(&self) -> bool;
+
+    /// Policy applied when the connection does not consume the notifications as fast as they are produced
+    fn overflow_policy(&self) -> OverflowPolicy {
+        OverflowPolicy::Unbounded
+    }
+}
+
+/// Defines how notifications are queued for a listener connection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Notifications are sent directly to the connection, waiting for it as long as needed
+    #[default]
+    Unbounded,
+
+    /// Up to `capacity` notifications are queued for the connection. Notifications overflowing the queue are
+    /// dropped and the next queued notification is preceded by a single `NotificationsGap` notification
+    /// informing the client it missed some and must re-query the state it tracks.
+    Bounded(usize),
 }
 
 #[derive(Clone, Debug)]
@@ -32,6 +50,7 @@ where
     name: &'static str,
     sender: Sender<N>,
     channel_type: ChannelType,
+    overflow_policy: OverflowPolicy,
 }
 
 impl<N> ChannelConnection<N>
@@ -39,7 +58,11 @@ where
     N: Notification,
 {
     pub fn new(name: &'static str, sender: Sender<N>, channel_type: ChannelType) -> Self {
-        Self { name, sender, channel_type }
+        Self { name, sender, channel_type, overflow_policy: OverflowPolicy::Unbounded }
+    }
+
+    pub fn with_overflow_policy(self, overflow_policy: OverflowPolicy) -> Self {
+        Self { overflow_policy, ..self }
     }
 
     /// Close the connection, ignoring the channel type
@@ -98,4 +121,8 @@ where
     fn is_closed(&self) -> bool {
         self.sender.is_closed()
     }
+
+    fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
 }
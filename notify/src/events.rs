@@ -51,10 +51,14 @@ event_type_enum! {
         VirtualDaaScoreChanged,
         PruningPointUtxoSetOverride,
         NewBlockTemplate,
+        TransactionsEvicted,
+        /// Marks a gap in the notifications delivered to a listener whose queue overflowed. Gap markers are
+        /// sent to bounded listeners regardless of their subscriptions.
+        NotificationsGap,
     }
 }
 
-pub const EVENT_COUNT: usize = 9;
+pub const EVENT_COUNT: usize = 11;
 
 impl FromStr for EventType {
     type Err = Error;
@@ -70,6 +74,8 @@ impl FromStr for EventType {
             "virtual-daa-score-changed" => Ok(EventType::VirtualDaaScoreChanged),
             "pruning-point-utxo-set-override" => Ok(EventType::PruningPointUtxoSetOverride),
             "new-block-template" => Ok(EventType::NewBlockTemplate),
+            "transactions-evicted" => Ok(EventType::TransactionsEvicted),
+            "notifications-gap" => Ok(EventType::NotificationsGap),
             _ => Err(Error::InvalidEventType(s.to_string())),
         }
     }
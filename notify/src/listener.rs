@@ -73,6 +73,11 @@ where
         self.subscriptions[event_type].mutate(mutation, policies, context)
     }
 
+    /// Returns the number of active subscriptions
+    pub fn active_subscriptions(&self) -> usize {
+        self.subscriptions.iter().filter(|subscription| subscription.active()).count()
+    }
+
     pub fn close(&self) {
         if !self.is_closed() {
             self.connection.close();
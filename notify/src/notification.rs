@@ -35,6 +35,12 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
     }
 
     fn event_type(&self) -> EventType;
+
+    /// Builds a notification marking a gap of `dropped` notifications in the flow of a listener, if supported
+    /// by the notification type. Without support, overflowing notifications are dropped silently.
+    fn notifications_gap(_dropped: u64) -> Option<Self> {
+        None
+    }
 }
 
 #[macro_export]
@@ -101,6 +107,11 @@ pub mod test_helpers {
         pub addresses: Arc<Vec<Address>>,
     }
 
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct NotificationsGapNotification {
+        pub dropped: u64,
+    }
+
     full_featured! {
     #[derive(Clone, Debug, Display, PartialEq, Eq)]
     pub enum TestNotification {
@@ -110,6 +121,8 @@ pub mod test_helpers {
         VirtualChainChanged(VirtualChainChangedNotification),
         #[display(fmt = "UtxosChanged #{}", "_0.data")]
         UtxosChanged(UtxosChangedNotification),
+        #[display(fmt = "NotificationsGap of {}", "_0.dropped")]
+        NotificationsGap(NotificationsGapNotification),
     }
     }
 
@@ -181,6 +194,10 @@ pub mod test_helpers {
         fn event_type(&self) -> EventType {
             self.into()
         }
+
+        fn notifications_gap(dropped: u64) -> Option<Self> {
+            Some(TestNotification::NotificationsGap(NotificationsGapNotification { dropped }))
+        }
     }
 
     /// A trait to help tests match notification received and expected thanks to some predefined data
@@ -215,12 +232,22 @@ pub mod test_helpers {
             &mut self.data
         }
     }
+    impl Data for NotificationsGapNotification {
+        fn data(&self) -> u64 {
+            self.dropped
+        }
+
+        fn data_mut(&mut self) -> &mut u64 {
+            &mut self.dropped
+        }
+    }
     impl Data for TestNotification {
         fn data(&self) -> u64 {
             match self {
                 TestNotification::BlockAdded(n) => n.data(),
                 TestNotification::VirtualChainChanged(n) => n.data(),
                 TestNotification::UtxosChanged(n) => n.data(),
+                TestNotification::NotificationsGap(n) => n.data(),
             }
         }
 
@@ -229,6 +256,7 @@ pub mod test_helpers {
                 TestNotification::BlockAdded(n) => n.data_mut(),
                 TestNotification::VirtualChainChanged(n) => n.data_mut(),
                 TestNotification::UtxosChanged(n) => n.data_mut(),
+                TestNotification::NotificationsGap(n) => n.data_mut(),
             }
         }
     }
@@ -21,7 +21,7 @@ use async_trait::async_trait;
 use core::fmt::Debug;
 use futures::future::join_all;
 use itertools::Itertools;
-use kaspa_core::{debug, trace};
+use kaspa_core::{debug, trace, warn};
 use parking_lot::Mutex;
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -105,6 +105,26 @@ pub type DynNotify<N> = Arc<dyn Notify<N>>;
 /// A notifier is built with a specific set of enabled event types. It is however possible to manually subscribe
 /// to a disabled scope and thus have a custom-made collector of the notifier receive notifications of this disabled scope,
 /// allowing some handling of the notification into the collector before it gets dropped by the notifier.
+/// The subscriptions of a listener
+#[derive(Clone, Debug)]
+pub struct ListenerSubscriptions {
+    pub id: ListenerId,
+    /// Display of the listener connection
+    pub connection: String,
+    /// Number of event types the listener is subscribed to
+    pub subscriptions: usize,
+}
+
+/// Inspection and cleanup of the listeners of a notifier, independently of its notification and connection types
+pub trait ListenerAudit: Send + Sync + Debug {
+    /// Returns the subscriptions of every registered listener
+    fn listener_subscriptions(&self) -> Vec<ListenerSubscriptions>;
+
+    /// Unregisters the listeners whose connection is closed, logging them as leaked since their owner should have
+    /// unregistered them. Returns the number of leaked listeners
+    fn audit_listeners(&self) -> usize;
+}
+
 #[derive(Debug)]
 pub struct Notifier<N, C>
 where
@@ -209,6 +229,20 @@ where
     }
 }
 
+impl<N, C> ListenerAudit for Notifier<N, C>
+where
+    N: Notification,
+    C: Connection<Notification = N>,
+{
+    fn listener_subscriptions(&self) -> Vec<ListenerSubscriptions> {
+        self.inner.listener_subscriptions()
+    }
+
+    fn audit_listeners(&self) -> usize {
+        self.inner.audit_listeners()
+    }
+}
+
 #[async_trait]
 impl<N, C> SubscriptionManager for Notifier<N, C>
 where
@@ -385,6 +419,33 @@ where
         Ok(())
     }
 
+    fn listener_subscriptions(&self) -> Vec<ListenerSubscriptions> {
+        self.listeners
+            .lock()
+            .iter()
+            .map(|(id, listener)| ListenerSubscriptions {
+                id: *id,
+                connection: listener.connection().to_string(),
+                subscriptions: listener.active_subscriptions(),
+            })
+            .collect()
+    }
+
+    fn audit_listeners(self: &Arc<Self>) -> usize {
+        let leaked = self
+            .listeners
+            .lock()
+            .iter()
+            .filter(|(_, listener)| listener.is_closed())
+            .map(|(id, listener)| (*id, listener.connection()))
+            .collect_vec();
+        leaked.iter().for_each(|(id, connection)| {
+            warn!("[Notifier {}] listener {id} of closed connection {connection} was leaked, unregistering it", self.name);
+            let _ = self.unregister_listener(*id);
+        });
+        leaked.len()
+    }
+
     pub fn execute_subscribe_command(&self, id: ListenerId, scope: Scope, command: Command) -> Result<()> {
         let event = scope.event_type();
         if self.enabled_events[event] {
@@ -828,6 +889,7 @@ mod tests {
         converter::ConverterFrom,
         events::EVENT_TYPE_ARRAY,
         notification::test_helpers::*,
+        scope::BlockAddedScope,
         subscriber::test_helpers::{SubscriptionManagerMock, SubscriptionMessage},
     };
     use async_channel::{unbounded, Receiver, Sender};
@@ -1004,4 +1066,27 @@ mod tests {
         let test = Test::new("UtxosChanged broadcast", 3, utxos_changed_test_steps(SUBSCRIPTION_MANAGER_ID));
         test.run().await;
     }
+
+    #[tokio::test]
+    async fn test_listener_audit() {
+        kaspa_core::log::try_init_logger("info,kaspa_notify=trace");
+        let mut test = Test::new("Listener audit", 2, vec![]);
+        for &listener in test.listeners.iter() {
+            test.notifier.try_start_notify(listener, BlockAddedScope {}.into()).unwrap();
+        }
+        let subscriptions = test.notifier.listener_subscriptions();
+        assert_eq!(subscriptions.len(), 2);
+        assert!(subscriptions.iter().all(|x| x.subscriptions == 1));
+
+        // Nothing leaks while the connections are open
+        assert_eq!(test.notifier.audit_listeners(), 0);
+
+        // Dropping the receiving end closes the first connection without unregistering its listener
+        drop(test.notification_receivers.remove(0));
+        assert_eq!(test.notifier.audit_listeners(), 1);
+        let subscriptions = test.notifier.listener_subscriptions();
+        assert_eq!(subscriptions.len(), 1);
+        assert_eq!(subscriptions[0].id, test.listeners[1]);
+        assert_eq!(test.notifier.audit_listeners(), 0);
+    }
 }
@@ -1,27 +1,34 @@
 use crate::flowcontext::{
+    block_submissions::BlockSubmissions,
+    ibd_progress::{IbdPhase, IbdProgress},
+    inventory::{InventoryScheduler, INVENTORY_FLUSH_INTERVAL},
     orphans::{OrphanBlocksPool, OrphanOutput},
-    process_queue::ProcessQueue,
-    transactions::TransactionsSpread,
+    submission_queue::{
+        BlockSubmissionQueue, SubmissionPriority, SubmissionQueueSnapshot, BLOCK_SUBMISSION_CAPACITY, RELAY_RESERVED_CAPACITY,
+    },
+    sync_peers::{SyncPeerSelector, SYNC_PEER_SELECTION_WINDOW},
+    transactions::{TransactionsSpread, BROADCAST_INTERVAL},
 };
 use crate::{v5, v6};
 use async_trait::async_trait;
 use futures::future::join_all;
-use kaspa_addressmanager::AddressManager;
+use kaspa_addressmanager::{AddressManager, PeerStats};
 use kaspa_connectionmanager::ConnectionManager;
 use kaspa_consensus_core::api::{BlockValidationFuture, BlockValidationFutures};
 use kaspa_consensus_core::block::Block;
 use kaspa_consensus_core::config::Config;
 use kaspa_consensus_core::errors::block::RuleError;
+use kaspa_consensus_core::subnets::SubnetworkId;
 use kaspa_consensus_core::tx::{Transaction, TransactionId};
 use kaspa_consensus_notify::{
-    notification::{Notification, PruningPointUtxoSetOverrideNotification},
+    notification::{Notification, PruningPointUtxoSetOverrideNotification, TransactionsEvictedNotification},
     root::ConsensusNotificationRoot,
 };
 use kaspa_consensusmanager::{BlockProcessingBatch, ConsensusInstance, ConsensusManager, ConsensusProxy};
 use kaspa_core::{
     debug, info,
     kaspad_env::{name, version},
-    task::tick::TickService,
+    task::tick::{TickReason, TickService},
 };
 use kaspa_core::{time::unix_now, warn};
 use kaspa_hashes::Hash;
@@ -29,11 +36,9 @@ use kaspa_mining::mempool::tx::{Orphan, Priority};
 use kaspa_mining::{manager::MiningManagerProxy, mempool::tx::RbfPolicy};
 use kaspa_notify::notifier::Notify;
 use kaspa_p2p_lib::{
-    common::ProtocolError,
-    convert::model::version::Version,
-    make_message,
-    pb::{kaspad_message::Payload, InvRelayBlockMessage},
-    ConnectionInitializer, Hub, KaspadHandshake, PeerKey, PeerProperties, Router,
+    common::ProtocolError, convert::model::version::Version, ConnectionInitializer, Hub, KaspadHandshake, KnownInventoryCounters,
+    KnownInventorySizes, MessageTraceRecorder, NodeIdentity, P2pMessageCounters, P2pTimeoutCounters, P2pTimeouts, PeerKey,
+    PeerProperties, Router, Socks5Proxy, TimeoutKind, UploadLimits,
 };
 use kaspa_utils::iter::IterExtensions;
 use kaspa_utils::networking::PeerId;
@@ -210,15 +215,22 @@ impl BlockEventLogger {
 
 pub struct FlowContextInner {
     pub node_id: PeerId,
+    /// The persistent identity key of the node, proven to peers during the handshake
+    pub node_identity: Arc<NodeIdentity>,
     pub consensus_manager: Arc<ConsensusManager>,
     pub config: Arc<Config>,
     hub: Hub,
     orphans_pool: AsyncRwLock<OrphanBlocksPool>,
     shared_block_requests: Arc<Mutex<HashMap<Hash, RequestScopeMetadata>>>,
+    block_submissions: BlockSubmissions,
+    block_submission_queue: BlockSubmissionQueue,
+    inventory: Arc<InventoryScheduler>,
     transactions_spread: AsyncRwLock<TransactionsSpread>,
     shared_transaction_requests: Arc<Mutex<HashMap<TransactionId, RequestScopeMetadata>>>,
     is_ibd_running: Arc<AtomicBool>,
     ibd_metadata: Arc<RwLock<Option<IbdMetadata>>>,
+    ibd_progress: RwLock<Option<IbdProgress>>,
+    sync_peer_selector: Mutex<SyncPeerSelector>,
     pub address_manager: Arc<Mutex<AddressManager>>,
     connection_manager: RwLock<Option<Arc<ConnectionManager>>>,
     mining_manager: MiningManagerProxy,
@@ -231,6 +243,9 @@ pub struct FlowContextInner {
     // Orphan parameters
     orphan_resolution_range: u32,
     max_orphans: usize,
+
+    // Handshake stage and flow response timeouts
+    p2p_timeouts: P2pTimeouts,
 }
 
 #[derive(Clone)]
@@ -303,24 +318,45 @@ impl FlowContext {
         mining_manager: MiningManagerProxy,
         tick_service: Arc<TickService>,
         notification_root: Arc<ConsensusNotificationRoot>,
+        p2p_trace_recorder: Option<Arc<MessageTraceRecorder>>,
+        p2p_timeouts: P2pTimeouts,
+        p2p_upload_limits: UploadLimits,
+        p2p_outbound_proxy: Option<Socks5Proxy>,
+        node_identity: Arc<NodeIdentity>,
     ) -> Self {
-        let hub = Hub::new();
+        let mut hub = p2p_trace_recorder
+            .map_or_else(Hub::new, Hub::with_trace_recorder)
+            .with_upload_limits(p2p_upload_limits)
+            .with_known_inventory_sizes(KnownInventorySizes {
+                blocks: config.perf.p2p_known_blocks_cache_size,
+                transactions: config.perf.p2p_known_transactions_cache_size,
+            });
+        if let Some(proxy) = p2p_outbound_proxy {
+            hub = hub.with_outbound_proxy(proxy);
+        }
 
         let orphan_resolution_range = BASELINE_ORPHAN_RESOLUTION_RANGE + (config.bps() as f64).log2().ceil() as u32;
 
         // The maximum amount of orphans allowed in the orphans pool. This number is an approximation
         // of how many orphans there can possibly be on average bounded by an upper bound.
         let max_orphans = (2u64.pow(orphan_resolution_range) as usize * config.ghostdag_k as usize).min(MAX_ORPHANS_UPPER_BOUND);
+        let inventory = Arc::new(InventoryScheduler::new(hub.clone()));
         Self {
             inner: Arc::new(FlowContextInner {
                 node_id: Uuid::new_v4().into(),
+                node_identity,
                 consensus_manager,
                 orphans_pool: AsyncRwLock::new(OrphanBlocksPool::new(max_orphans)),
                 shared_block_requests: Arc::new(Mutex::new(HashMap::new())),
-                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone())),
+                block_submissions: BlockSubmissions::new(),
+                block_submission_queue: BlockSubmissionQueue::new(BLOCK_SUBMISSION_CAPACITY, RELAY_RESERVED_CAPACITY),
+                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(inventory.clone())),
+                inventory,
                 shared_transaction_requests: Arc::new(Mutex::new(HashMap::new())),
                 is_ibd_running: Default::default(),
                 ibd_metadata: Default::default(),
+                ibd_progress: Default::default(),
+                sync_peer_selector: Default::default(),
                 hub,
                 address_manager,
                 connection_manager: Default::default(),
@@ -330,6 +366,7 @@ impl FlowContext {
                 block_event_logger: if config.bps() > 1 { Some(BlockEventLogger::new(config.bps() as usize)) } else { None },
                 orphan_resolution_range,
                 max_orphans,
+                p2p_timeouts,
                 config,
             }),
         }
@@ -347,10 +384,16 @@ impl FlowContext {
         self.max_orphans
     }
 
+    pub fn p2p_timeouts(&self) -> &P2pTimeouts {
+        &self.p2p_timeouts
+    }
+
     pub fn start_async_services(&self) {
         if let Some(logger) = self.block_event_logger.as_ref() {
             logger.start();
         }
+        self.start_transactions_broadcast_task();
+        self.start_inventory_flush_task();
     }
 
     pub fn set_connection_manager(&self, connection_manager: Arc<ConnectionManager>) {
@@ -373,6 +416,32 @@ impl FlowContext {
         &self.hub
     }
 
+    /// P2P message counters per message type and direction, aggregated over all peers
+    pub fn p2p_message_counters(&self) -> Arc<P2pMessageCounters> {
+        self.hub.message_counters()
+    }
+
+    /// Counters of timeouts expired by peers, per timeout kind
+    pub fn p2p_timeout_counters(&self) -> Arc<P2pTimeoutCounters> {
+        self.hub.timeout_counters()
+    }
+
+    /// Counters of known inventory lookups made before relaying blocks and transactions, aggregated over all peers
+    pub fn p2p_inventory_counters(&self) -> Arc<KnownInventoryCounters> {
+        self.hub.inventory_counters()
+    }
+
+    /// Penalizes a peer which did not respond within the `kind` timeout by marking a connection failure on its
+    /// address, lowering its priority for future outbound connections. The caller is expected to disconnect it.
+    ///
+    /// Note: handshake timeouts of outbound peers are already marked as connection failures by the connection manager
+    pub fn penalize_slow_peer(&self, router: &Router, kind: TimeoutKind) {
+        warn!("Peer {} exceeded the {} timeout of {:?}", router, kind, self.p2p_timeouts.get(kind));
+        if router.is_outbound() {
+            self.address_manager.lock().mark_connection_failure(router.net_address().into());
+        }
+    }
+
     pub fn mining_manager(&self) -> &MiningManagerProxy {
         &self.mining_manager
     }
@@ -380,12 +449,48 @@ impl FlowContext {
     pub fn try_set_ibd_running(&self, peer: PeerKey, relay_daa_score: u64) -> Option<IbdRunningGuard> {
         if self.is_ibd_running.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
             self.ibd_metadata.write().replace(IbdMetadata { peer, daa_score: relay_daa_score });
+            self.ibd_progress.write().replace(IbdProgress::new(peer.identity(), Instant::now()));
             Some(IbdRunningGuard { indicator: self.is_ibd_running.clone() })
         } else {
             None
         }
     }
 
+    /// Registers `router` as a sync peer candidate following a relay block with `relay_daa_score` which triggered IBD.
+    /// Competing candidates are awaited for [`SYNC_PEER_SELECTION_WINDOW`], after which returns whether this peer is the
+    /// best one to sync from (by reported DAA score, historical throughput and latency)
+    pub async fn select_sync_peer(&self, router: &Router, relay_daa_score: u64) -> bool {
+        let peer = router.key();
+        self.sync_peer_selector.lock().register_candidate(peer, relay_daa_score, router.last_ping_duration(), Instant::now());
+        if self.is_ibd_running() {
+            return false;
+        }
+        tokio::time::sleep(SYNC_PEER_SELECTION_WINDOW).await;
+        let mut selector = self.sync_peer_selector.lock();
+        selector.retain(|peer| self.hub.has_peer(*peer));
+        if selector.is_best_candidate(&peer, Instant::now()) {
+            selector.start_sync(&peer);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Reports the round-trip of an IBD request of `items` headers or blocks from the sync peer. Returns true if the
+    /// sync peer stalled repeatedly while another candidate is available, in which case the current IBD should be
+    /// aborted (disconnecting the peer) so that IBD is restarted with a different sync peer
+    pub fn report_sync_peer_round_trip(&self, router: &Router, items: usize, elapsed: Duration) -> bool {
+        let peer = router.key();
+        let mut selector = self.sync_peer_selector.lock();
+        if selector.report_round_trip(&peer, items, elapsed, Instant::now()) {
+            warn!("IBD sync peer {} stalled repeatedly, switching to another sync peer", router);
+            selector.withdraw(&peer);
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn is_ibd_running(&self) -> bool {
         self.is_ibd_running.load(Ordering::SeqCst)
     }
@@ -408,6 +513,29 @@ impl FlowContext {
         }
     }
 
+    /// If IBD is running, returns its progress
+    pub fn ibd_progress(&self) -> Option<IbdProgress> {
+        if self.is_ibd_running() {
+            self.ibd_progress.read().clone()
+        } else {
+            None
+        }
+    }
+
+    /// Publishes that the running IBD moved to `phase`, expecting `total` items to be processed (zero if unknown)
+    pub fn set_ibd_phase(&self, phase: IbdPhase, total: u64) {
+        if let Some(progress) = self.ibd_progress.write().as_mut() {
+            progress.enter_phase(phase, total, Instant::now());
+        }
+    }
+
+    /// Publishes that the running IBD processed `processed` more items in its current phase
+    pub fn report_ibd_progress(&self, processed: u64) {
+        if let Some(progress) = self.ibd_progress.write().as_mut() {
+            progress.report(processed);
+        }
+    }
+
     fn try_adding_request_impl(req: Hash, map: &Arc<Mutex<HashMap<Hash, RequestScopeMetadata>>>) -> Option<RequestScope<Hash>> {
         match map.lock().entry(req) {
             Entry::Occupied(mut e) => {
@@ -481,19 +609,53 @@ impl FlowContext {
         self.orphans_pool.write().await.revalidate_orphans(consensus).await
     }
 
+    /// Submits `block` to consensus unless the same block is already being processed following another submission,
+    /// in which case the validation futures of the in-flight submission are returned. The returned flag indicates
+    /// whether the block was actually submitted, i.e., whether the caller is responsible for post-processing it.
+    ///
+    /// Waits for room in the block submission queue, where relay and RPC blocks take precedence over IBD blocks.
+    /// Blocks which are already in flight return immediately without occupying the queue.
+    pub async fn validate_and_insert_block(&self, consensus: &ConsensusProxy, block: Block) -> (BlockValidationFutures, bool) {
+        let hash = block.hash();
+        if let Some(futures) = self.block_submissions.get(&hash) {
+            return (futures, false);
+        }
+        let permit = self.block_submission_queue.acquire(SubmissionPriority::Relay).await;
+        // The block might have been submitted by another caller while waiting for the permit, in which case the
+        // permit is dropped unused
+        self.block_submissions.submit(hash, || permit.attach(consensus.validate_and_insert_block(block)))
+    }
+
+    /// Submits a block downloaded during IBD to consensus, waiting for room in the block submission queue as needed.
+    pub async fn validate_and_insert_ibd_block(&self, consensus: &ConsensusProxy, block: Block) -> BlockValidationFutures {
+        let permit = self.block_submission_queue.acquire(SubmissionPriority::Bulk).await;
+        permit.attach(consensus.validate_and_insert_block(block))
+    }
+
+    /// Returns the current depths of the block submission queue per priority class
+    pub fn block_submission_queue_depths(&self) -> SubmissionQueueSnapshot {
+        self.block_submission_queue.counters().snapshot()
+    }
+
     /// Adds the rpc-submitted block to the DAG and propagates it to peers.
     pub async fn submit_rpc_block(&self, consensus: &ConsensusProxy, block: Block) -> Result<(), ProtocolError> {
         if block.transactions.is_empty() {
             return Err(RuleError::NoTransactions)?;
         }
         let hash = block.hash();
-        let BlockValidationFutures { block_task, virtual_state_task } = consensus.validate_and_insert_block(block.clone());
+        let (BlockValidationFutures { block_task, virtual_state_task }, submitted) =
+            self.validate_and_insert_block(consensus, block.clone()).await;
         if let Err(err) = block_task.await {
             warn!("Validation failed for block {}: {}", hash, err);
             return Err(err)?;
         }
+        if !submitted {
+            // The block is concurrently being processed following its relay, which also takes care of propagating it
+            debug!("Submitted block {} was already being processed", hash);
+            return Ok(());
+        }
         // Broadcast as soon as the block has been validated and inserted into the DAG
-        self.hub.broadcast(make_message!(Payload::InvRelayBlock, InvRelayBlockMessage { hash: Some(hash.into()) })).await;
+        self.inventory.announce_blocks(once(hash));
 
         self.on_new_block(consensus, Default::default(), block, virtual_state_task).await;
         self.log_block_event(BlockLogEvent::Submit(hash));
@@ -531,16 +693,12 @@ impl FlowContext {
         let mut blocks = self.unorphan_blocks(consensus, hash).await;
 
         // Broadcast unorphaned blocks
-        let msgs = blocks
-            .iter()
-            .map(|(b, _)| make_message!(Payload::InvRelayBlock, InvRelayBlockMessage { hash: Some(b.hash().into()) }))
-            .collect();
-        self.hub.broadcast_many(msgs).await;
+        self.inventory.announce_blocks(blocks.iter().map(|(b, _)| b.hash()));
 
         // Process blocks in topological order
         blocks.sort_by(|a, b| a.0.header.blue_work.partial_cmp(&b.0.header.blue_work).unwrap());
-        // Use a ProcessQueue so we get rid of duplicates
-        let mut transactions_to_broadcast = ProcessQueue::new();
+        // Duplicates are filtered out by the broadcast queue
+        let mut transactions_to_broadcast = Vec::new();
         for (block, virtual_state_task) in ancestor_batch.zip().chain(once((block, virtual_state_task))).chain(blocks.into_iter()) {
             // We only care about waiting for virtual to process the block at this point, before proceeding with post-processing
             // actions such as updating the mempool. We know this will not err since `block_task` already completed w/o error
@@ -551,7 +709,7 @@ impl FlowContext {
                 .handle_new_block_transactions(consensus, block.header.daa_score, block.transactions.clone())
                 .await
             {
-                transactions_to_broadcast.enqueue_chunk(txs.into_iter().map(|x| x.id()));
+                transactions_to_broadcast.extend(txs.into_iter().map(|x| (x.id(), x.subnetwork_id.clone())));
             }
         }
 
@@ -564,7 +722,7 @@ impl FlowContext {
         self.broadcast_transactions(transactions_to_broadcast, false).await;
 
         if self.should_run_mempool_scanning_task().await {
-            // Spawn a task executing the removal of expired low priority transactions and, if time has come too,
+            // Spawn a task executing the removal of expired transactions and, if time has come too,
             // the revalidation of high priority transactions.
             //
             // The TransactionSpread member ensures at most one instance of this task is running at any
@@ -574,13 +732,15 @@ impl FlowContext {
             let context = self.clone();
             debug!("<> Starting mempool scanning task #{}...", self.mempool_scanning_job_count().await);
             tokio::spawn(async move {
-                mining_manager.clone().expire_low_priority_transactions(&consensus_clone).await;
+                let evicted_transactions = mining_manager.clone().expire_transactions(&consensus_clone).await;
+                context.on_transactions_evicted_from_mempool(evicted_transactions);
                 if context.should_rebroadcast().await {
                     let (tx, mut rx) = unbounded_channel();
                     tokio::spawn(async move {
                         mining_manager.revalidate_high_priority_transactions(&consensus_clone, tx).await;
                     });
                     while let Some(transactions) = rx.recv().await {
+                        context.inventory.forget_transactions(&transactions.iter().map(|(id, _)| *id).collect::<Vec<_>>());
                         let _ = context
                             .broadcast_transactions(
                                 transactions,
@@ -602,6 +762,17 @@ impl FlowContext {
         let _ = self.notification_root.notify(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {}));
     }
 
+    /// Notifies that transactions were evicted from the mempool without being accepted (e.g., since they expired).
+    pub fn on_transactions_evicted_from_mempool(&self, transaction_ids: Vec<TransactionId>) {
+        if transaction_ids.is_empty() {
+            return;
+        }
+        // Ignore the possible error, see `on_pruning_point_utxoset_override`
+        let _ = self
+            .notification_root
+            .notify(Notification::TransactionsEvicted(TransactionsEvictedNotification::new(Arc::new(transaction_ids))));
+    }
+
     /// Notifies that a transaction has been added to the mempool.
     pub async fn on_transaction_added_to_mempool(&self) {
         // TODO: call a handler function or a predefined registered service
@@ -610,8 +781,8 @@ impl FlowContext {
     /// Adds the rpc-submitted transaction to the mempool and propagates it to peers.
     ///
     /// Transactions submitted through rpc are considered high priority. This definition does not affect the tx selection algorithm
-    /// but only changes how we manage the lifetime of the tx. A high-priority tx does not expire and is repeatedly rebroadcasted to
-    /// peers
+    /// but only changes how we manage the lifetime of the tx. A high-priority tx expires only after the (much longer) local expiry
+    /// window of the mempool, if any, and is repeatedly rebroadcasted to peers
     pub async fn submit_rpc_transaction(
         &self,
         consensus: &ConsensusProxy,
@@ -624,7 +795,7 @@ impl FlowContext {
             .validate_and_insert_transaction(consensus, transaction, Priority::High, orphan, RbfPolicy::Forbidden)
             .await?;
         self.broadcast_transactions(
-            transaction_insertion.accepted.iter().map(|x| x.id()),
+            transaction_insertion.accepted.iter().map(|x| (x.id(), x.subnetwork_id.clone())),
             false, // RPC transactions are considered high priority, so we don't want to throttle them
         )
         .await;
@@ -636,8 +807,8 @@ impl FlowContext {
     /// Returns the removed mempool transaction on successful replace by fee.
     ///
     /// Transactions submitted through rpc are considered high priority. This definition does not affect the tx selection algorithm
-    /// but only changes how we manage the lifetime of the tx. A high-priority tx does not expire and is repeatedly rebroadcasted to
-    /// peers
+    /// but only changes how we manage the lifetime of the tx. A high-priority tx expires only after the (much longer) local expiry
+    /// window of the mempool, if any, and is repeatedly rebroadcasted to peers
     pub async fn submit_rpc_transaction_replacement(
         &self,
         consensus: &ConsensusProxy,
@@ -649,7 +820,7 @@ impl FlowContext {
             .validate_and_insert_transaction(consensus, transaction, Priority::High, Orphan::Forbidden, RbfPolicy::Mandatory)
             .await?;
         self.broadcast_transactions(
-            transaction_insertion.accepted.iter().map(|x| x.id()),
+            transaction_insertion.accepted.iter().map(|x| (x.id(), x.subnetwork_id.clone())),
             false, // RPC transactions are considered high priority, so we don't want to throttle them
         )
         .await;
@@ -678,13 +849,44 @@ impl FlowContext {
         self.transactions_spread.write().await.mempool_scanning_is_done()
     }
 
-    /// Add the given transactions IDs to a set of IDs to broadcast. The IDs will be broadcasted to all peers
-    /// within transaction Inv messages.
+    /// Add the given transactions IDs, along with their subnetworks, to a set of IDs to broadcast. The IDs will be
+    /// broadcasted within transaction Inv messages to all peers not known to have them already and relaying their
+    /// subnetworks.
     ///
-    /// The broadcast itself may happen only during a subsequent call to this function since it is done at most
-    /// after a predefined interval or when the queue length is larger than the Inv message capacity.
-    pub async fn broadcast_transactions<I: IntoIterator<Item = TransactionId>>(&self, transaction_ids: I, should_throttle: bool) {
-        self.transactions_spread.write().await.broadcast_transactions(transaction_ids, should_throttle).await
+    /// The broadcast is batched and happens at most after a predefined interval, either on the next tick of
+    /// the broadcast task or during a subsequent call to this function, or when the queue length is larger
+    /// than the Inv message capacity.
+    pub async fn broadcast_transactions<I: IntoIterator<Item = (TransactionId, SubnetworkId)>>(
+        &self,
+        transactions: I,
+        should_throttle: bool,
+    ) {
+        self.transactions_spread.write().await.broadcast_transactions(transactions, should_throttle).await
+    }
+
+    /// Returns the scheduler of outgoing block and transaction announcements
+    pub fn inventory(&self) -> &InventoryScheduler {
+        &self.inventory
+    }
+
+    /// Spawns a task sending the pending inventory announcements on every inventory flush interval
+    fn start_inventory_flush_task(&self) {
+        let context = self.clone();
+        tokio::spawn(async move {
+            while let TickReason::Wakeup = context.tick_service.tick(INVENTORY_FLUSH_INTERVAL).await {
+                context.inventory.flush().await;
+            }
+        });
+    }
+
+    /// Spawns a task flushing the transaction IDs pending broadcast on every broadcast interval
+    fn start_transactions_broadcast_task(&self) {
+        let context = self.clone();
+        tokio::spawn(async move {
+            while let TickReason::Wakeup = context.tick_service.tick(BROADCAST_INTERVAL).await {
+                context.transactions_spread.write().await.flush().await;
+            }
+        });
     }
 }
 
@@ -692,14 +894,14 @@ impl FlowContext {
 impl ConnectionInitializer for FlowContext {
     async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
         // Build the handshake object and subscribe to handshake messages
-        let mut handshake = KaspadHandshake::new(&router);
+        let mut handshake = KaspadHandshake::with_timeouts(&router, self.p2p_timeouts).with_identity(self.node_identity.clone());
 
         // We start the router receive loop only after we registered to handshake routes
         router.start();
 
         let network_name = self.config.network_name();
 
-        let local_address = self.address_manager.lock().best_local_address();
+        let local_address = self.address_manager.lock().best_local_address(router.net_address().ip().into());
 
         // Build the local version message
         // Subnets are not currently supported
@@ -745,6 +947,7 @@ impl ConnectionInitializer for FlowContext {
             disable_relay_tx: peer_version.disable_relay_tx,
             subnetwork_id: peer_version.subnetwork_id.to_owned(),
             time_offset,
+            identity: peer_version.identity,
         });
         router.set_properties(peer_properties);
 
@@ -762,11 +965,11 @@ impl ConnectionInitializer for FlowContext {
             let mut address_manager = self.address_manager.lock();
 
             if router.is_outbound() {
-                address_manager.add_address(router.net_address().into());
+                address_manager.add_address(router.net_address().into(), None);
             }
 
             if let Some(peer_ip_address) = peer_version.address {
-                address_manager.add_address(peer_ip_address);
+                address_manager.add_address(peer_ip_address, Some(router.net_address().ip().into()));
             }
         }
 
@@ -776,4 +979,20 @@ impl ConnectionInitializer for FlowContext {
 
         Ok(())
     }
+
+    fn on_connection_closed(&self, router: &Arc<Router>) {
+        // Persist the statistics of the connection so they survive restarts
+        let session = router.session_stats();
+        let stats = PeerStats {
+            bytes_sent: session.bytes_sent,
+            bytes_received: session.bytes_received,
+            protocol_version: router.properties().protocol_version,
+            avg_latency: session.avg_latency,
+            latency_samples: session.latency_samples,
+            // Peers feeding us mostly redundant data lose reputation on top of their protocol misbehavior
+            ban_score: session.ban_score.saturating_add(session.redundancy_score()),
+            ..Default::default()
+        };
+        self.address_manager.lock().record_peer_session(router.net_address().ip().into(), stats);
+    }
 }
@@ -21,6 +21,8 @@ where
             let res = self.start().await;
             if let Err(err) = res {
                 if let Some(router) = self.router() {
+                    router.record_timeout(&err);
+                    router.record_misbehavior(&err);
                     router.try_sending_reject_message(&err).await;
                     if router.close().await || !err.is_connection_closed_error() {
                         warn!("{} flow error: {}, disconnecting from peer {}.", self.name(), err, router);
@@ -0,0 +1,157 @@
+use futures::{
+    future::{BoxFuture, Shared},
+    FutureExt,
+};
+use kaspa_consensus_core::api::{BlockValidationFuture, BlockValidationFutures};
+use kaspa_hashes::Hash;
+use parking_lot::Mutex;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+};
+
+type SharedBlockValidationFuture = Shared<BlockValidationFuture>;
+
+struct InFlightBlock {
+    /// Distinguishes between consecutive submissions of the same block
+    id: u64,
+    block_task: SharedBlockValidationFuture,
+    virtual_state_task: SharedBlockValidationFuture,
+}
+
+#[derive(Default)]
+struct Inner {
+    blocks: HashMap<Hash, InFlightBlock>,
+    next_id: u64,
+}
+
+/// Registry of blocks currently being processed by consensus. Concurrent submissions of the same block (e.g., relayed
+/// by several peers or submitted via RPC while being relayed) coalesce into a single consensus processing task whose
+/// results are shared by all submitters.
+///
+/// A block is removed from the registry once it failed validation or once its virtual state processing completed,
+/// after which a new submission reaches consensus again (where it is usually resolved by the block status check).
+#[derive(Clone, Default)]
+pub struct BlockSubmissions {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl BlockSubmissions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the validation futures of the in-flight submission of `hash` if one exists, otherwise submits the block
+    /// using `submit` and registers it. The returned flag indicates whether `submit` was called.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn submit(&self, hash: Hash, submit: impl FnOnce() -> BlockValidationFutures) -> (BlockValidationFutures, bool) {
+        let mut inner = self.inner.lock();
+        if let Some(in_flight) = inner.blocks.get(&hash) {
+            return (Self::futures(in_flight), false);
+        }
+
+        let id = inner.next_id;
+        inner.next_id += 1;
+        let BlockValidationFutures { block_task, virtual_state_task } = submit();
+
+        // Entries are removed from within the shared futures, hence before their output is delivered to any of the
+        // submitters, so that a retry following a failure (e.g., of an orphan block) is never coalesced with it
+        let registry = self.clone();
+        let block_task: BoxFuture<'static, _> = Box::pin(async move {
+            let res = block_task.await;
+            if res.is_err() {
+                registry.remove(hash, id);
+            }
+            res
+        });
+        let registry = self.clone();
+        let virtual_state_task: BoxFuture<'static, _> = Box::pin(async move {
+            let res = virtual_state_task.await;
+            registry.remove(hash, id);
+            res
+        });
+
+        let in_flight = InFlightBlock { id, block_task: block_task.shared(), virtual_state_task: virtual_state_task.shared() };
+        let futures = Self::futures(&in_flight);
+        // Drive the virtual state task so that the entry is removed even if no submitter awaits it
+        tokio::spawn(in_flight.virtual_state_task.clone());
+        inner.blocks.insert(hash, in_flight);
+        (futures, true)
+    }
+
+    /// Returns the validation futures of the in-flight submission of `hash`, if any
+    pub fn get(&self, hash: &Hash) -> Option<BlockValidationFutures> {
+        self.inner.lock().blocks.get(hash).map(Self::futures)
+    }
+
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.inner.lock().blocks.contains_key(hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().blocks.is_empty()
+    }
+
+    fn futures(in_flight: &InFlightBlock) -> BlockValidationFutures {
+        BlockValidationFutures {
+            block_task: Box::pin(in_flight.block_task.clone()),
+            virtual_state_task: Box::pin(in_flight.virtual_state_task.clone()),
+        }
+    }
+
+    fn remove(&self, hash: Hash, id: u64) {
+        if let Entry::Occupied(entry) = self.inner.lock().blocks.entry(hash) {
+            if entry.get().id == id {
+                entry.remove();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flowcontext::testutils::pending_futures;
+    use kaspa_consensus_core::blockstatus::BlockStatus;
+
+    #[tokio::test]
+    async fn test_block_submissions() {
+        let submissions = BlockSubmissions::new();
+        let hash = 1.into();
+
+        // Concurrent submissions coalesce into a single one
+        let (futures, block_sender, virtual_sender) = pending_futures();
+        let (first, submitted) = submissions.submit(hash, || futures);
+        assert!(submitted);
+        let (second, submitted) = submissions.submit(hash, || unreachable!());
+        assert!(!submitted);
+        let third = submissions.get(&hash).unwrap();
+        assert!(submissions.contains(&hash));
+
+        block_sender.send(()).unwrap();
+        assert!(matches!(first.block_task.await, Ok(BlockStatus::StatusUTXOPendingVerification)));
+        assert!(matches!(second.block_task.await, Ok(BlockStatus::StatusUTXOPendingVerification)));
+        assert!(matches!(third.block_task.await, Ok(BlockStatus::StatusUTXOPendingVerification)));
+        // Still in flight until virtual processing completes
+        assert!(submissions.contains(&hash));
+        virtual_sender.send(()).unwrap();
+        assert!(matches!(second.virtual_state_task.await, Ok(BlockStatus::StatusUTXOValid)));
+        assert!(matches!(first.virtual_state_task.await, Ok(BlockStatus::StatusUTXOValid)));
+        assert!(submissions.is_empty());
+        assert!(submissions.get(&hash).is_none());
+
+        // A failed submission is removed before its result is delivered, so a retry reaches consensus again
+        let (futures, block_sender, _virtual_sender) = pending_futures();
+        let (first, _) = submissions.submit(hash, || futures);
+        drop(block_sender);
+        assert!(first.block_task.await.is_err());
+        let (futures, _block_sender, _virtual_sender) = pending_futures();
+        let (_, submitted) = submissions.submit(hash, || futures);
+        assert!(submitted);
+    }
+}
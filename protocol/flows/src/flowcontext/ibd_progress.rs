@@ -0,0 +1,105 @@
+use kaspa_utils::networking::PeerId;
+use std::time::{Duration, Instant};
+
+/// The phases of an IBD, in order of occurrence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IbdPhase {
+    /// Negotiating the chain segment missing from the local DAG with the sync peer
+    Negotiating,
+    /// Downloading and applying the pruning point proof and the trusted blocks (IBD with headers proof only)
+    HeadersProof,
+    /// Downloading block headers
+    Headers,
+    /// Downloading the pruning point UTXO set (IBD with headers proof only)
+    UtxoSet,
+    /// Downloading block bodies
+    Bodies,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IbdPhaseProgress {
+    pub phase: IbdPhase,
+    /// The number of items (headers, blocks or UTXOs) processed so far
+    pub processed: u64,
+    /// The expected number of items, or zero if unknown
+    pub total: u64,
+    started: Instant,
+}
+
+/// Progress of the running IBD, published by the IBD flow
+#[derive(Debug, Clone)]
+pub struct IbdProgress {
+    /// The peer being synced from
+    pub peer: PeerId,
+    /// Progress of every phase entered so far, the last one being the current phase
+    pub phases: Vec<IbdPhaseProgress>,
+}
+
+impl IbdProgress {
+    pub fn new(peer: PeerId, now: Instant) -> Self {
+        Self { peer, phases: vec![IbdPhaseProgress { phase: IbdPhase::Negotiating, processed: 0, total: 0, started: now }] }
+    }
+
+    pub fn current(&self) -> &IbdPhaseProgress {
+        self.phases.last().expect("always holds at least the negotiation phase")
+    }
+
+    /// Moves to `phase` expecting `total` items. The previous phase is considered complete. Entering the current
+    /// phase again (i.e. when syncing bodies in multiple passes) adds `total` to the items expected by it
+    pub fn enter_phase(&mut self, phase: IbdPhase, total: u64, now: Instant) {
+        let current = self.phases.last_mut().expect("always holds at least the negotiation phase");
+        if current.phase == phase {
+            current.total = current.processed.max(current.total) + total;
+            return;
+        }
+        current.total = current.processed;
+        self.phases.push(IbdPhaseProgress { phase, processed: 0, total, started: now });
+    }
+
+    pub fn report(&mut self, processed: u64) {
+        let current = self.phases.last_mut().expect("always holds at least the negotiation phase");
+        current.processed += processed;
+    }
+
+    /// Estimates the remaining time of the current phase by extrapolating its processing rate so far.
+    /// Returns `None` if the amount of items left to process is unknown or nothing was processed yet
+    pub fn eta(&self, now: Instant) -> Option<Duration> {
+        let current = self.current();
+        if current.processed == 0 || current.total == 0 {
+            return None;
+        }
+        let remaining = current.total.saturating_sub(current.processed);
+        Some(now.duration_since(current.started).mul_f64(remaining as f64 / current.processed as f64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_ibd_progress() {
+        let start = Instant::now();
+        let mut progress = IbdProgress::new(Uuid::new_v4().into(), start);
+        assert_eq!(progress.current().phase, IbdPhase::Negotiating);
+        assert_eq!(progress.eta(start), None);
+
+        progress.enter_phase(IbdPhase::Headers, 1000, start);
+        assert_eq!(progress.eta(start), None);
+        progress.report(250);
+        assert_eq!(progress.eta(start + Duration::from_secs(10)), Some(Duration::from_secs(30)));
+
+        // Headers exceeding the estimate complete the phase with the actual count
+        progress.report(800);
+        assert_eq!(progress.eta(start + Duration::from_secs(10)), Some(Duration::ZERO));
+        progress.enter_phase(IbdPhase::Bodies, 100, start + Duration::from_secs(10));
+        assert_eq!((progress.phases[1].processed, progress.phases[1].total), (1050, 1050));
+
+        // A second pass over missing bodies extends the current phase
+        progress.report(100);
+        progress.enter_phase(IbdPhase::Bodies, 20, start + Duration::from_secs(20));
+        assert_eq!(progress.phases.len(), 3);
+        assert_eq!((progress.current().processed, progress.current().total), (100, 120));
+    }
+}
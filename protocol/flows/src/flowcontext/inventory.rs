@@ -0,0 +1,107 @@
+use super::transactions::MAX_INV_PER_TX_INV_MSG;
+use kaspa_consensus_core::{subnets::SubnetworkId, tx::TransactionId};
+use kaspa_core::debug;
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::{
+    make_message,
+    pb::{kaspad_message::Payload, InvRelayBlockMessage, InvTransactionsMessage, KaspadMessage},
+    Hub, PeerKey,
+};
+use parking_lot::Mutex;
+use std::{collections::HashMap, time::Duration};
+
+/// Interval at which pending inventory announcements are sent to peers
+pub(crate) const INVENTORY_FLUSH_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Default)]
+struct PeerInventory {
+    pending_blocks: Vec<Hash>,
+    pending_transactions: Vec<TransactionId>,
+}
+
+impl PeerInventory {
+    fn into_messages(self) -> Vec<KaspadMessage> {
+        let mut msgs = self
+            .pending_blocks
+            .into_iter()
+            .map(|hash| make_message!(Payload::InvRelayBlock, InvRelayBlockMessage { hash: Some(hash.into()) }))
+            .collect::<Vec<_>>();
+        for ids in self.pending_transactions.chunks(MAX_INV_PER_TX_INV_MSG) {
+            msgs.push(make_message!(Payload::InvTransactions, InvTransactionsMessage { ids: ids.iter().map(|x| x.into()).collect() }));
+        }
+        msgs
+    }
+}
+
+/// Schedules outgoing block and transaction announcements. Announcements are batched per peer and sent on
+/// every [`INVENTORY_FLUSH_INTERVAL`] tick, skipping hashes found in the known inventory caches of the peer
+/// router, i.e. hashes the peer announced to us or which we already announced to it.
+pub struct InventoryScheduler {
+    hub: Hub,
+    peers: Mutex<HashMap<PeerKey, PeerInventory>>,
+}
+
+impl InventoryScheduler {
+    pub fn new(hub: Hub) -> Self {
+        Self { hub, peers: Mutex::new(HashMap::new()) }
+    }
+
+    /// Forgets that any peer knows the given transactions so that they get announced again, i.e. for
+    /// rebroadcasting transactions that were not accepted yet
+    pub fn forget_transactions(&self, ids: &[TransactionId]) {
+        for router in self.hub.select_peers(None) {
+            router.forget_known_transactions(ids);
+        }
+    }
+
+    /// Schedules the announcement of the given blocks to all peers, in order
+    pub fn announce_blocks<I: IntoIterator<Item = Hash>>(&self, hashes: I) {
+        let hashes = hashes.into_iter().collect::<Vec<_>>();
+        let routers = self.hub.select_peers(None);
+        let mut peers = self.peers.lock();
+        for router in routers {
+            let unknown = router.filter_unknown_blocks(hashes.iter().copied());
+            if !unknown.is_empty() {
+                peers.entry(router.key()).or_default().pending_blocks.extend(unknown);
+            }
+        }
+    }
+
+    /// Schedules the announcement of the given transactions to all peers, or to `num_peers` randomly selected
+    /// peers if specified. Each peer is only announced the transactions of the subnetworks it relays
+    pub fn announce_transactions(&self, transactions: &[(TransactionId, SubnetworkId)], num_peers: Option<usize>) {
+        let routers = self.hub.select_peers(num_peers);
+        let mut peers = self.peers.lock();
+        for router in routers {
+            let properties = router.properties();
+            let unknown = router.filter_unknown_transactions(
+                transactions.iter().filter(|(_, subnetwork_id)| properties.relays_subnetwork(subnetwork_id)).map(|(id, _)| *id),
+            );
+            if !unknown.is_empty() {
+                peers.entry(router.key()).or_default().pending_transactions.extend(unknown);
+            }
+        }
+    }
+
+    /// Sends all pending announcements and drops those of disconnected peers
+    pub async fn flush(&self) {
+        let routers = self.hub.select_peers(None);
+        let batches = {
+            let mut peers = self.peers.lock();
+            let batches = routers
+                .into_iter()
+                .filter_map(|router| peers.remove(&router.key()).map(|inventory| (router, inventory.into_messages())))
+                .collect::<Vec<_>>();
+            peers.clear();
+            batches
+        };
+        for (router, msgs) in batches {
+            debug!("Inventory announcement: sending {} inv messages to peer {}", msgs.len(), router);
+            for msg in msgs {
+                if router.enqueue(msg).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,10 @@
+pub mod block_submissions;
+pub mod ibd_progress;
+pub mod inventory;
 pub mod orphans;
 pub(crate) mod process_queue;
+pub mod submission_queue;
+pub mod sync_peers;
+#[cfg(test)]
+mod testutils;
 pub mod transactions;
@@ -0,0 +1,193 @@
+use futures::FutureExt;
+use kaspa_consensus_core::api::BlockValidationFutures;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// The maximal number of blocks submitted through the queue whose header or body processing is still pending
+pub const BLOCK_SUBMISSION_CAPACITY: usize = 1024;
+
+/// The part of [`BLOCK_SUBMISSION_CAPACITY`] which bulk submissions cannot occupy
+pub const RELAY_RESERVED_CAPACITY: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionPriority {
+    /// Relayed and RPC submitted blocks, which are few and latency sensitive
+    Relay,
+    /// Blocks downloaded during IBD, which are submitted in large batches
+    Bulk,
+}
+
+#[derive(Default)]
+struct ClassCounters {
+    in_flight: AtomicUsize,
+    waiting: AtomicUsize,
+}
+
+#[derive(Default)]
+pub struct SubmissionQueueCounters {
+    relay: ClassCounters,
+    bulk: ClassCounters,
+}
+
+impl SubmissionQueueCounters {
+    fn class(&self, priority: SubmissionPriority) -> &ClassCounters {
+        match priority {
+            SubmissionPriority::Relay => &self.relay,
+            SubmissionPriority::Bulk => &self.bulk,
+        }
+    }
+
+    pub fn snapshot(&self) -> SubmissionQueueSnapshot {
+        SubmissionQueueSnapshot {
+            relay_in_flight: self.relay.in_flight.load(Ordering::Relaxed),
+            relay_waiting: self.relay.waiting.load(Ordering::Relaxed),
+            bulk_in_flight: self.bulk.in_flight.load(Ordering::Relaxed),
+            bulk_waiting: self.bulk.waiting.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SubmissionQueueSnapshot {
+    /// Relay blocks submitted to consensus and pending header or body processing
+    pub relay_in_flight: usize,
+    /// Relay blocks waiting for a permit to be submitted
+    pub relay_waiting: usize,
+    pub bulk_in_flight: usize,
+    pub bulk_waiting: usize,
+}
+
+/// Bounds the number of blocks submitted to consensus and pending processing, so that submitters wait for an
+/// async permit rather than piling blocks into the unbounded consensus channel.
+///
+/// Bulk submissions may occupy the capacity except for a reserved part, hence relay and RPC submissions always
+/// find room promptly and never starve behind large IBD batches
+#[derive(Clone)]
+pub struct BlockSubmissionQueue {
+    total: Arc<Semaphore>,
+    bulk: Arc<Semaphore>,
+    counters: Arc<SubmissionQueueCounters>,
+}
+
+impl BlockSubmissionQueue {
+    pub fn new(capacity: usize, relay_reserved: usize) -> Self {
+        assert!(relay_reserved < capacity);
+        Self {
+            total: Arc::new(Semaphore::new(capacity)),
+            bulk: Arc::new(Semaphore::new(capacity - relay_reserved)),
+            counters: Default::default(),
+        }
+    }
+
+    pub fn counters(&self) -> Arc<SubmissionQueueCounters> {
+        self.counters.clone()
+    }
+
+    /// Waits for room to submit a block of the given priority
+    pub async fn acquire(&self, priority: SubmissionPriority) -> SubmissionPermit {
+        let class = self.counters.class(priority);
+        // Counts as waiting until a permit is obtained or the acquisition is cancelled
+        let waiting = WaitingGuard::new(class);
+        // The semaphores are never closed
+        let bulk = match priority {
+            SubmissionPriority::Relay => None,
+            SubmissionPriority::Bulk => Some(self.bulk.clone().acquire_owned().await.unwrap()),
+        };
+        let total = self.total.clone().acquire_owned().await.unwrap();
+        drop(waiting);
+        class.in_flight.fetch_add(1, Ordering::Relaxed);
+        SubmissionPermit { _total: total, _bulk: bulk, priority, counters: self.counters.clone() }
+    }
+}
+
+struct WaitingGuard<'a>(&'a ClassCounters);
+
+impl<'a> WaitingGuard<'a> {
+    fn new(class: &'a ClassCounters) -> Self {
+        class.waiting.fetch_add(1, Ordering::Relaxed);
+        Self(class)
+    }
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.waiting.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Room for a single block submission, released on drop
+pub struct SubmissionPermit {
+    _total: OwnedSemaphorePermit,
+    _bulk: Option<OwnedSemaphorePermit>,
+    priority: SubmissionPriority,
+    counters: Arc<SubmissionQueueCounters>,
+}
+
+impl SubmissionPermit {
+    /// Holds the permit until the block task of the submitted `futures` completes, regardless of whether the submitter
+    /// awaits it. Must be called from within a tokio runtime
+    pub fn attach(self, futures: BlockValidationFutures) -> BlockValidationFutures {
+        let block_task = futures.block_task.shared();
+        let release = block_task.clone();
+        tokio::spawn(async move {
+            let _ = release.await;
+            drop(self);
+        });
+        BlockValidationFutures { block_task: Box::pin(block_task), virtual_state_task: futures.virtual_state_task }
+    }
+}
+
+impl Drop for SubmissionPermit {
+    fn drop(&mut self) {
+        self.counters.class(self.priority).in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flowcontext::testutils::pending_futures;
+    use kaspa_consensus_core::blockstatus::BlockStatus;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    #[tokio::test]
+    async fn test_submission_priorities() {
+        let queue = BlockSubmissionQueue::new(3, 1);
+        let counters = queue.counters();
+
+        // Bulk submissions occupy the capacity except for the reserved part
+        let (first, first_sender, _first_virtual_sender) = pending_futures();
+        let first = queue.acquire(SubmissionPriority::Bulk).await.attach(first);
+        let (second, _second_sender, _second_virtual_sender) = pending_futures();
+        let _second = queue.acquire(SubmissionPriority::Bulk).await.attach(second);
+        assert!(timeout(Duration::from_millis(50), queue.acquire(SubmissionPriority::Bulk)).await.is_err());
+
+        // A relay submission still finds room, after which the queue is full
+        let relay = queue.acquire(SubmissionPriority::Relay).await;
+        assert_eq!(
+            counters.snapshot(),
+            SubmissionQueueSnapshot { relay_in_flight: 1, relay_waiting: 0, bulk_in_flight: 2, bulk_waiting: 0 }
+        );
+        let waiting_relay = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.acquire(SubmissionPriority::Relay).await }
+        });
+        tokio::task::yield_now().await;
+        assert_eq!(counters.snapshot().relay_waiting, 1);
+
+        // Completing a block task releases its permit even though the submitter did not await it yet
+        first_sender.send(()).unwrap();
+        let _waiting_relay = waiting_relay.await.unwrap();
+        assert_eq!(
+            counters.snapshot(),
+            SubmissionQueueSnapshot { relay_in_flight: 2, relay_waiting: 0, bulk_in_flight: 1, bulk_waiting: 0 }
+        );
+        assert!(matches!(first.block_task.await, Ok(BlockStatus::StatusUTXOPendingVerification)));
+        drop(relay);
+        assert_eq!(counters.snapshot().relay_in_flight, 1);
+    }
+}
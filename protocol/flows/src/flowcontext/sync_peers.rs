@@ -0,0 +1,197 @@
+use kaspa_p2p_lib::PeerKey;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// The time peers triggering IBD wait for competing candidates before the best one is selected as the sync peer
+pub const SYNC_PEER_SELECTION_WINDOW: Duration = Duration::from_millis(500);
+
+/// A peer which did not trigger IBD for longer than this period is no longer considered a sync peer candidate
+const CANDIDACY_EXPIRY: Duration = Duration::from_secs(30);
+
+/// Candidates whose reported DAA scores are within this distance are considered equally advanced
+const DAA_SCORE_TOLERANCE: u64 = 600;
+
+/// Candidates whose throughputs differ by less than this ratio are considered equally fast
+const THROUGHPUT_TOLERANCE: f64 = 0.2;
+
+/// A chunk round-trip taking longer than this is considered a stall of the sync peer
+pub const SYNC_PEER_STALL_THRESHOLD: Duration = Duration::from_secs(10);
+
+/// The number of stalls during a single IBD after which the sync peer is switched, if a competing candidate exists
+const MAX_SYNC_PEER_STALLS: u32 = 3;
+
+/// Weight of the most recent measurement in the throughput moving average
+const THROUGHPUT_EMA_ALPHA: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SyncPeerStats {
+    /// The highest DAA score of a relay block by which this peer triggered IBD
+    pub reported_daa_score: u64,
+    /// The last ping round-trip of this peer (milliseconds)
+    pub latency: u64,
+    /// Moving average of the IBD throughput (items per second) measured while syncing from this peer
+    pub throughput: Option<f64>,
+    /// The number of stalls observed during the current IBD with this peer
+    pub stalls: u32,
+    /// The last time this peer triggered IBD
+    last_candidacy: Option<Instant>,
+}
+
+impl SyncPeerStats {
+    /// Compares the sync quality of two peers. The reported DAA score is compared first, then the historical
+    /// throughput (if known for both peers) and finally the latency
+    pub fn compare(&self, other: &Self) -> Ordering {
+        if self.reported_daa_score.abs_diff(other.reported_daa_score) > DAA_SCORE_TOLERANCE {
+            return self.reported_daa_score.cmp(&other.reported_daa_score);
+        }
+        if let (Some(a), Some(b)) = (self.throughput, other.throughput) {
+            if (a - b).abs() > THROUGHPUT_TOLERANCE * a.max(b) {
+                return a.total_cmp(&b);
+            }
+        }
+        // Lower latency is better
+        other.latency.cmp(&self.latency)
+    }
+
+    fn is_candidate(&self, now: Instant) -> bool {
+        self.last_candidacy.is_some_and(|time| now.duration_since(time) < CANDIDACY_EXPIRY)
+    }
+}
+
+/// Tracks the quality of peers triggering IBD in order to select the best one as the sync peer, and to
+/// switch away from a sync peer which stalls repeatedly
+#[derive(Debug, Default)]
+pub struct SyncPeerSelector {
+    peers: HashMap<PeerKey, SyncPeerStats>,
+}
+
+impl SyncPeerSelector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stats(&self, peer: &PeerKey) -> Option<SyncPeerStats> {
+        self.peers.get(peer).copied()
+    }
+
+    /// Registers `peer` as a sync peer candidate following a relay block with `daa_score` which triggered IBD
+    pub fn register_candidate(&mut self, peer: PeerKey, daa_score: u64, latency: u64, now: Instant) {
+        let stats = self.peers.entry(peer).or_default();
+        stats.reported_daa_score = stats.reported_daa_score.max(daa_score);
+        stats.latency = latency;
+        stats.last_candidacy = Some(now);
+    }
+
+    /// Returns the best of the current sync peer candidates
+    pub fn best_candidate(&self, now: Instant) -> Option<PeerKey> {
+        self.peers.iter().filter(|(_, stats)| stats.is_candidate(now)).max_by(|(_, a), (_, b)| a.compare(b)).map(|(peer, _)| *peer)
+    }
+
+    /// Returns whether `peer` is a candidate and no other candidate is strictly better
+    pub fn is_best_candidate(&self, peer: &PeerKey, now: Instant) -> bool {
+        let Some(stats) = self.peers.get(peer).filter(|stats| stats.is_candidate(now)) else {
+            return false;
+        };
+        self.peers.iter().filter(|(key, other)| *key != peer && other.is_candidate(now)).all(|(_, other)| other.compare(stats).is_le())
+    }
+
+    /// Resets the stall count of `peer` as it starts an IBD
+    pub fn start_sync(&mut self, peer: &PeerKey) {
+        if let Some(stats) = self.peers.get_mut(peer) {
+            stats.stalls = 0;
+        }
+    }
+
+    /// Reports the round-trip of an IBD request of `items` headers or blocks from the sync peer `peer`.
+    /// Returns true if the peer stalled repeatedly and another sync peer candidate is available, in which
+    /// case the sync peer should be switched
+    pub fn report_round_trip(&mut self, peer: &PeerKey, items: usize, elapsed: Duration, now: Instant) -> bool {
+        let Some(stats) = self.peers.get_mut(peer) else {
+            return false;
+        };
+        let throughput = items as f64 / elapsed.as_secs_f64().max(0.001);
+        stats.throughput = Some(match stats.throughput {
+            Some(average) => THROUGHPUT_EMA_ALPHA * throughput + (1.0 - THROUGHPUT_EMA_ALPHA) * average,
+            None => throughput,
+        });
+        if elapsed < SYNC_PEER_STALL_THRESHOLD {
+            return false;
+        }
+        stats.stalls += 1;
+        if stats.stalls < MAX_SYNC_PEER_STALLS {
+            return false;
+        }
+        let reported_daa_score = stats.reported_daa_score;
+        self.peers.iter().any(|(key, other)| {
+            key != peer && other.is_candidate(now) && other.reported_daa_score + DAA_SCORE_TOLERANCE >= reported_daa_score
+        })
+    }
+
+    /// Removes the candidacy of `peer`, so it is no longer selected until it triggers IBD again
+    pub fn withdraw(&mut self, peer: &PeerKey) {
+        if let Some(stats) = self.peers.get_mut(peer) {
+            stats.last_candidacy = None;
+        }
+    }
+
+    /// Keeps only the peers for which `f` returns true (e.g., the currently connected peers)
+    pub fn retain(&mut self, mut f: impl FnMut(&PeerKey) -> bool) {
+        self.peers.retain(|peer, _| f(peer));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::IpAddr;
+    use uuid::Uuid;
+
+    fn new_peer() -> PeerKey {
+        PeerKey::new(Uuid::new_v4().into(), IpAddr::from([127, 0, 0, 1]).into())
+    }
+
+    #[test]
+    fn test_sync_peer_selection() {
+        let now = Instant::now();
+        let (a, b, c) = (new_peer(), new_peer(), new_peer());
+        let mut selector = SyncPeerSelector::new();
+
+        // A peer reporting a significantly higher DAA score wins regardless of latency
+        selector.register_candidate(a, 10_000, 50, now);
+        selector.register_candidate(b, 20_000, 500, now);
+        assert_eq!(selector.best_candidate(now), Some(b));
+        assert!(!selector.is_best_candidate(&a, now));
+
+        // Within the DAA score tolerance, historical throughput and then latency decide
+        selector.register_candidate(a, 19_900, 50, now);
+        assert!(selector.is_best_candidate(&a, now));
+        selector.report_round_trip(&b, 1000, Duration::from_secs(1), now);
+        selector.report_round_trip(&a, 100, Duration::from_secs(1), now);
+        assert_eq!(selector.best_candidate(now), Some(b));
+
+        // Expired candidates are not considered
+        let later = now + CANDIDACY_EXPIRY;
+        selector.register_candidate(c, 0, 1000, later);
+        assert_eq!(selector.best_candidate(later), Some(c));
+        assert!(selector.is_best_candidate(&c, later));
+
+        // Repeated stalls switch the sync peer only if another candidate exists
+        let mut selector = SyncPeerSelector::new();
+        selector.register_candidate(a, 10_000, 50, now);
+        selector.start_sync(&a);
+        for _ in 0..MAX_SYNC_PEER_STALLS {
+            assert!(!selector.report_round_trip(&a, 99, SYNC_PEER_STALL_THRESHOLD, now));
+        }
+        selector.register_candidate(b, 10_000, 100, now);
+        assert!(selector.report_round_trip(&a, 99, SYNC_PEER_STALL_THRESHOLD, now));
+        assert!(!selector.report_round_trip(&a, 99, Duration::from_millis(100), now));
+        selector.withdraw(&b);
+        assert!(!selector.report_round_trip(&a, 99, SYNC_PEER_STALL_THRESHOLD, now));
+
+        selector.retain(|peer| *peer != a);
+        assert!(selector.stats(&a).is_none());
+    }
+}
@@ -0,0 +1,18 @@
+use kaspa_consensus_core::{api::BlockValidationFutures, blockstatus::BlockStatus, errors::block::RuleError};
+use tokio::sync::oneshot;
+
+/// Returns block validation futures which complete successfully once the respective sender is used, and fail
+/// once it is dropped
+pub(crate) fn pending_futures() -> (BlockValidationFutures, oneshot::Sender<()>, oneshot::Sender<()>) {
+    let (block_sender, block_receiver) = oneshot::channel();
+    let (virtual_sender, virtual_receiver) = oneshot::channel();
+    let futures = BlockValidationFutures {
+        block_task: Box::pin(async move {
+            block_receiver.await.map(|_| BlockStatus::StatusUTXOPendingVerification).map_err(|_| RuleError::NoTransactions)
+        }),
+        virtual_state_task: Box::pin(async move {
+            virtual_receiver.await.map(|_| BlockStatus::StatusUTXOValid).map_err(|_| RuleError::NoTransactions)
+        }),
+    };
+    (futures, block_sender, virtual_sender)
+}
@@ -1,37 +1,45 @@
-use super::process_queue::ProcessQueue;
+use super::{inventory::InventoryScheduler, process_queue::ProcessQueue};
 use itertools::Itertools;
-use kaspa_consensus_core::tx::TransactionId;
+use kaspa_consensus_core::{
+    subnets::{SubnetworkId, SUBNETWORK_ID_NATIVE},
+    tx::TransactionId,
+};
 use kaspa_core::debug;
-use kaspa_p2p_lib::{
-    make_message,
-    pb::{kaspad_message::Payload, InvTransactionsMessage, KaspadMessage},
-    Hub,
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use std::time::{Duration, Instant};
 
 /// Interval between mempool scanning tasks (in seconds)
 const SCANNING_TASK_INTERVAL: u64 = 10;
 const REBROADCAST_FREQUENCY: u64 = 3;
-const BROADCAST_INTERVAL: Duration = Duration::from_millis(500);
+pub(crate) const BROADCAST_INTERVAL: Duration = Duration::from_millis(500);
 pub(crate) const MAX_INV_PER_TX_INV_MSG: usize = 131_072;
 
 pub struct TransactionsSpread {
-    hub: Hub,
+    inventory: Arc<InventoryScheduler>,
     last_scanning_time: Instant,
     scanning_task_running: bool,
     scanning_job_count: u64,
     transaction_ids: ProcessQueue<TransactionId>,
+    /// The subnetworks of the queued transactions which do not belong to the native subnetwork
+    non_native_subnetworks: HashMap<TransactionId, SubnetworkId>,
+    /// Whether all the queued transactions may be announced to a subset of the peers only
+    throttle_queued: bool,
     last_broadcast_time: Instant,
 }
 
 impl TransactionsSpread {
-    pub fn new(hub: Hub) -> Self {
+    pub fn new(inventory: Arc<InventoryScheduler>) -> Self {
         Self {
-            hub,
+            inventory,
             last_scanning_time: Instant::now(),
             scanning_task_running: false,
             scanning_job_count: 0,
             transaction_ids: ProcessQueue::new(),
+            non_native_subnetworks: HashMap::new(),
+            throttle_queued: true,
             last_broadcast_time: Instant::now(),
         }
     }
@@ -69,38 +77,56 @@ impl TransactionsSpread {
         self.scanning_task_running = false;
     }
 
-    /// Add the given transactions IDs to a set of IDs to broadcast. The IDs will be broadcasted to all peers
-    /// within transaction Inv messages.
+    /// Add the given transactions IDs to a set of IDs to broadcast. The IDs will be broadcasted within transaction
+    /// Inv messages to all peers not known to have them already, such as the peer which relayed them to us, and
+    /// whose subnetwork filter accepts the subnetwork of the transaction.
     ///
-    /// The broadcast itself may happen only during a subsequent call to this function since it is done at most
-    /// every `BROADCAST_INTERVAL` milliseconds or when the queue length is larger than the Inv message
-    /// capacity.
+    /// The broadcast is batched: it happens at most every `BROADCAST_INTERVAL` milliseconds or when the queue
+    /// length is larger than the Inv message capacity. Pending IDs are flushed by [`Self::flush`] which is
+    /// called on every broadcast interval tick, so the IDs never wait for a subsequent call to this function.
     ///
     /// _GO-KASPAD: EnqueueTransactionIDsForPropagation_
-    pub async fn broadcast_transactions<I: IntoIterator<Item = TransactionId>>(&mut self, transaction_ids: I, should_throttle: bool) {
-        self.transaction_ids.enqueue_chunk(transaction_ids);
+    pub async fn broadcast_transactions<I: IntoIterator<Item = (TransactionId, SubnetworkId)>>(
+        &mut self,
+        transactions: I,
+        should_throttle: bool,
+    ) {
+        self.transaction_ids.enqueue_chunk(transactions.into_iter().map(|(transaction_id, subnetwork_id)| {
+            if !subnetwork_id.is_native() {
+                self.non_native_subnetworks.insert(transaction_id, subnetwork_id);
+            }
+            transaction_id
+        }));
+        self.throttle_queued &= should_throttle;
 
         let now = Instant::now();
         if now < self.last_broadcast_time + BROADCAST_INTERVAL && self.transaction_ids.len() < MAX_INV_PER_TX_INV_MSG {
             return;
         }
 
+        self.flush().await;
+    }
+
+    /// Hands all the queued transaction IDs, if any, to the inventory scheduler for announcement. The queue
+    /// is throttled only if all its transactions were queued with throttling.
+    pub async fn flush(&mut self) {
+        if self.transaction_ids.is_empty() {
+            return;
+        }
+
+        let should_throttle = std::mem::replace(&mut self.throttle_queued, true);
+        // TODO: Figure out a better number
+        let num_peers = should_throttle.then_some(8);
         while !self.transaction_ids.is_empty() {
-            let ids = self.transaction_ids.dequeue_chunk(MAX_INV_PER_TX_INV_MSG).map(|x| x.into()).collect_vec();
-            debug!("Transaction propagation: broadcasting {} transactions", ids.len());
-            let msg = make_message!(Payload::InvTransactions, InvTransactionsMessage { ids });
-            self.broadcast(msg, should_throttle).await;
+            let transactions = self
+                .transaction_ids
+                .dequeue_chunk(MAX_INV_PER_TX_INV_MSG)
+                .map(|id| (id, self.non_native_subnetworks.remove(&id).unwrap_or(SUBNETWORK_ID_NATIVE)))
+                .collect_vec();
+            debug!("Transaction propagation: broadcasting {} transactions", transactions.len());
+            self.inventory.announce_transactions(&transactions, num_peers);
         }
 
         self.last_broadcast_time = Instant::now();
     }
-
-    async fn broadcast(&self, msg: KaspadMessage, should_throttle: bool) {
-        if should_throttle {
-            // TODO: Figure out a better number
-            self.hub.broadcast_to_some_peers(msg, 8).await
-        } else {
-            self.hub.broadcast(msg).await
-        }
-    }
 }
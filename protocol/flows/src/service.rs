@@ -18,8 +18,10 @@ pub struct P2pService {
     flow_context: Arc<FlowContext>,
     connect_peers: Vec<NetAddress>,
     add_peers: Vec<NetAddress>,
+    anchor_peers: Vec<NetAddress>,
     listen: NetAddress,
     outbound_target: usize,
+    min_outbound_groups: usize,
     inbound_limit: usize,
     dns_seeders: &'static [&'static str],
     default_port: u16,
@@ -32,8 +34,10 @@ impl P2pService {
         flow_context: Arc<FlowContext>,
         connect_peers: Vec<NetAddress>,
         add_peers: Vec<NetAddress>,
+        anchor_peers: Vec<NetAddress>,
         listen: NetAddress,
         outbound_target: usize,
+        min_outbound_groups: usize,
         inbound_limit: usize,
         dns_seeders: &'static [&'static str],
         default_port: u16,
@@ -43,9 +47,11 @@ impl P2pService {
             flow_context,
             connect_peers,
             add_peers,
+            anchor_peers,
             shutdown: SingleTrigger::default(),
             listen,
             outbound_target,
+            min_outbound_groups,
             inbound_limit,
             dns_seeders,
             default_port,
@@ -75,6 +81,8 @@ impl AsyncService for P2pService {
             self.dns_seeders,
             self.default_port,
             self.flow_context.address_manager.clone(),
+            self.anchor_peers.iter().map(|&address| address.into()).collect(),
+            self.min_outbound_groups,
         );
 
         self.flow_context.set_connection_manager(connection_manager.clone());
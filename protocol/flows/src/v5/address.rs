@@ -53,9 +53,10 @@ impl ReceiveAddressesFlow {
         if address_list.len() > MAX_ADDRESSES_RECEIVE {
             return Err(ProtocolError::OtherOwned(format!("address count {} exceeded {}", address_list.len(), MAX_ADDRESSES_RECEIVE)));
         }
+        let source = Some(self.router.net_address().ip().into());
         let mut amgr_lock = self.ctx.address_manager.lock();
         for (ip, port) in address_list {
-            amgr_lock.add_address(NetAddress::new(ip, port))
+            amgr_lock.add_address(NetAddress::new(ip, port), source)
         }
 
         Ok(())
@@ -87,9 +88,19 @@ impl SendAddressesFlow {
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
         loop {
             dequeue!(self.incoming_route, Payload::RequestAddresses)?;
-            let addresses = self.ctx.address_manager.lock().iterate_addresses().collect_vec();
-            let address_list = addresses
-                .choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND)
+            let (local_addresses, addresses) = {
+                let address_manager = self.ctx.address_manager.lock();
+                (address_manager.get_local_addresses(), address_manager.iterate_addresses().collect_vec())
+            };
+            // Our own addresses of all IP families are advertised along with a random sample of the known ones
+            let address_list = local_addresses
+                .iter()
+                .take(MAX_ADDRESSES_SEND)
+                .chain(
+                    addresses
+                        .choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND.saturating_sub(local_addresses.len()))
+                        .filter(|addr| !local_addresses.contains(addr)),
+                )
                 .map(|addr| (addr.ip, addr.port).into())
                 .collect();
             self.router.enqueue(make_message!(Payload::Addresses, AddressesMessage { address_list })).await?;
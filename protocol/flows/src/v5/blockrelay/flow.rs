@@ -9,12 +9,12 @@ use kaspa_core::debug;
 use kaspa_hashes::Hash;
 use kaspa_p2p_lib::{
     common::ProtocolError,
-    dequeue, dequeue_with_timeout, make_message, make_request,
-    pb::{kaspad_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlocksMessage},
-    IncomingRoute, Router, SharedIncomingRoute,
+    dequeue, dequeue_with_timeout, make_request,
+    pb::{kaspad_message::Payload, RequestBlockLocatorMessage, RequestRelayBlocksMessage},
+    IncomingRoute, Router, SharedIncomingRoute, SubmissionOutcome,
 };
 use kaspa_utils::channel::{JobSender, JobTrySendError as TrySendError};
-use std::{collections::VecDeque, sync::Arc};
+use std::{collections::VecDeque, iter::once, sync::Arc};
 
 pub struct RelayInvMessage {
     hash: Hash,
@@ -91,6 +91,10 @@ impl HandleRelayInvsFlow {
         loop {
             // Loop over incoming block inv messages
             let inv = self.invs_route.dequeue().await?;
+            if !inv.is_orphan_root {
+                // The peer announced this block, so there is no need to announce it back
+                self.router.mark_known_blocks(once(inv.hash));
+            }
             let session = self.ctx.consensus().unguarded_session();
 
             match session.async_get_block_status(inv.hash).await {
@@ -150,10 +154,20 @@ impl HandleRelayInvsFlow {
                 continue;
             }
 
-            let BlockValidationFutures { block_task, mut virtual_state_task } = session.validate_and_insert_block(block.clone());
+            let (BlockValidationFutures { block_task, mut virtual_state_task }, submitted) =
+                self.ctx.validate_and_insert_block(&session, block.clone()).await;
 
             let ancestor_batch = match block_task.await {
-                Ok(_) => Default::default(),
+                Ok(_) if !submitted => {
+                    // The block was concurrently submitted by other means (e.g., via RPC) and is post-processed by the submitter
+                    debug!("Relay block {} was already being processed, continuing...", inv.hash);
+                    self.router.record_block_outcome(SubmissionOutcome::Duplicate);
+                    continue;
+                }
+                Ok(_) => {
+                    self.router.record_block_outcome(SubmissionOutcome::Valid);
+                    Default::default()
+                }
                 Err(RuleError::MissingParents(missing_parents)) => {
                     debug!("Block {} is orphan and has missing parents: {:?}", block.hash(), missing_parents);
                     if let Some(mut ancestor_batch) = self.process_orphan(&session, block.clone(), inv.known_within_range).await? {
@@ -161,48 +175,50 @@ impl HandleRelayInvsFlow {
                         let BlockValidationFutures { block_task: block_task_inner, virtual_state_task: virtual_state_task_inner } =
                             session.validate_and_insert_block(block.clone());
                         virtual_state_task = virtual_state_task_inner;
-                        for block_task in ancestor_batch.block_tasks.take().unwrap() {
+                        for (ancestor, block_task) in ancestor_batch.blocks.iter().zip(ancestor_batch.block_tasks.take().unwrap()) {
                             match block_task.await {
                                 Ok(_) => {}
                                 // We disconnect on invalidness even though this is not a direct relay from this peer, because
                                 // current relay is a descendant of this block (i.e. this peer claims all its ancestors are valid)
-                                Err(rule_error) => return Err(rule_error.into()),
+                                Err(rule_error) => {
+                                    self.router.record_block_outcome(SubmissionOutcome::Invalid);
+                                    return Err(ProtocolError::InvalidBlock(ancestor.hash(), Box::new(rule_error)));
+                                }
                             }
                         }
 
                         match block_task_inner.await {
-                            Ok(_) => match ancestor_batch.blocks.len() {
-                                0 => debug!("Retried orphan block {} successfully", block.hash()),
-                                n => {
-                                    self.ctx.log_block_event(BlockLogEvent::Unorphaned(ancestor_batch.blocks[0].hash(), n));
-                                    debug!("Unorphaned {} ancestors and retried orphan block {} successfully", n, block.hash())
+                            Ok(_) => {
+                                self.router.record_block_outcome(SubmissionOutcome::Valid);
+                                match ancestor_batch.blocks.len() {
+                                    0 => debug!("Retried orphan block {} successfully", block.hash()),
+                                    n => {
+                                        self.ctx.log_block_event(BlockLogEvent::Unorphaned(ancestor_batch.blocks[0].hash(), n));
+                                        debug!("Unorphaned {} ancestors and retried orphan block {} successfully", n, block.hash())
+                                    }
                                 }
-                            },
-                            Err(rule_error) => return Err(rule_error.into()),
+                            }
+                            Err(rule_error) => {
+                                self.router.record_block_outcome(SubmissionOutcome::Invalid);
+                                return Err(ProtocolError::InvalidBlock(inv.hash, Box::new(rule_error)));
+                            }
                         }
                         ancestor_batch
                     } else {
                         continue;
                     }
                 }
-                Err(rule_error) => return Err(rule_error.into()),
+                Err(rule_error) => {
+                    self.router.record_block_outcome(SubmissionOutcome::Invalid);
+                    return Err(ProtocolError::InvalidBlock(inv.hash, Box::new(rule_error)));
+                }
             };
 
             // As a policy, we only relay blocks who stand a chance to enter past(virtual).
             // The only mining rule which permanently excludes a block is the merge depth bound
             // (as opposed to "max parents" and "mergeset size limit" rules)
             if broadcast {
-                let msgs = ancestor_batch
-                    .blocks
-                    .iter()
-                    .map(|b| make_message!(Payload::InvRelayBlock, InvRelayBlockMessage { hash: Some(b.hash().into()) }))
-                    .collect();
-                self.ctx.hub().broadcast_many(msgs).await;
-
-                self.ctx
-                    .hub()
-                    .broadcast(make_message!(Payload::InvRelayBlock, InvRelayBlockMessage { hash: Some(inv.hash.into()) }))
-                    .await;
+                self.ctx.inventory().announce_blocks(ancestor_batch.blocks.iter().map(|b| b.hash()).chain(once(inv.hash)));
             }
 
             // We spawn post-processing as a separate task so that this loop
@@ -1,14 +1,19 @@
 use crate::{
     flow_context::FlowContext,
+    flowcontext::ibd_progress::IbdPhase,
     v5::{
         ibd::{HeadersChunkStream, TrustedEntryStream},
         Flow,
     },
 };
-use futures::future::{join_all, select, try_join_all, Either};
+use futures::{
+    future::{join_all, select, try_join_all, BoxFuture, Either},
+    FutureExt, TryFutureExt,
+};
 use kaspa_consensus_core::{
     api::BlockValidationFuture,
     block::Block,
+    blockstatus::BlockStatus,
     header::Header,
     pruning::{PruningPointProof, PruningPointsList, PruningProofMetadata},
     BlockHashSet,
@@ -25,7 +30,7 @@ use kaspa_p2p_lib::{
         kaspad_message::Payload, RequestAntipastMessage, RequestHeadersMessage, RequestIbdBlocksMessage,
         RequestPruningPointAndItsAnticoneMessage, RequestPruningPointProofMessage, RequestPruningPointUtxoSetMessage,
     },
-    IncomingRoute, Router,
+    IncomingRoute, Router, SubmissionOutcome, TimeoutKind,
 };
 use kaspa_utils::channel::JobReceiver;
 use std::{
@@ -64,10 +69,48 @@ pub enum IbdType {
 }
 
 struct QueueChunkOutput {
-    jobs: Vec<BlockValidationFuture>,
+    jobs: Vec<BlockJob>,
     daa_score: u64,
     timestamp: u64,
 }
+
+/// A block processing job whose failure is attributed to the processed block
+type BlockJob = BoxFuture<'static, Result<BlockStatus, ProtocolError>>;
+
+fn block_job(hash: Hash, job: BlockValidationFuture) -> BlockJob {
+    Box::pin(job.map_err(move |err| ProtocolError::InvalidBlock(hash, Box::new(err))))
+}
+
+/// Like [`block_job`], additionally attributing the processing outcome to the peer which submitted the block
+/// (or the header, if `header_only`)
+fn attributed_block_job(router: Arc<Router>, header_only: bool, hash: Hash, job: BlockValidationFuture) -> BlockJob {
+    block_job(
+        hash,
+        Box::pin(job.inspect(move |res| {
+            let outcome = if res.is_ok() { SubmissionOutcome::Valid } else { SubmissionOutcome::Invalid };
+            if header_only {
+                router.record_header_outcome(outcome);
+            } else {
+                router.record_block_outcome(outcome);
+            }
+        })),
+    )
+}
+
+/// Submits the headers of `chunk` for processing, waiting for room in the block submission queue as needed
+async fn header_jobs(ctx: &FlowContext, consensus: &ConsensusProxy, router: &Arc<Router>, chunk: HeadersChunk) -> Vec<BlockJob> {
+    let mut jobs = Vec::with_capacity(chunk.len());
+    for header in chunk {
+        let hash = header.hash;
+        jobs.push(attributed_block_job(
+            router.clone(),
+            true,
+            hash,
+            ctx.validate_and_insert_ibd_block(consensus, Block::from_header_arc(header)).await.virtual_state_task,
+        ));
+    }
+    jobs
+}
 // TODO: define a peer banning strategy
 
 impl IbdFlow {
@@ -77,6 +120,10 @@ impl IbdFlow {
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
         while let Ok(relay_block) = self.relay_receiver.recv().await {
+            if !self.ctx.select_sync_peer(&self.router, relay_block.header.daa_score).await {
+                debug!("Peer {} was not selected as the IBD sync peer for relay block {}", self.router, relay_block.hash());
+                continue;
+            }
             if let Some(_guard) = self.ctx.try_set_ibd_running(self.router.key(), relay_block.header.daa_score) {
                 info!("IBD started with peer {}", self.router);
 
@@ -84,6 +131,9 @@ impl IbdFlow {
                     Ok(_) => info!("IBD with peer {} completed successfully", self.router),
                     Err(e) => {
                         info!("IBD with peer {} completed with error: {}", self.router, e);
+                        if let Some(kind) = e.timeout_kind() {
+                            self.ctx.penalize_slow_peer(&self.router, kind);
+                        }
                         return Err(e);
                     }
                 }
@@ -227,10 +277,12 @@ impl IbdFlow {
     }
 
     async fn sync_and_validate_pruning_proof(&mut self, staging: &ConsensusProxy, relay_block: &Block) -> Result<Hash, ProtocolError> {
+        self.ctx.set_ibd_phase(IbdPhase::HeadersProof, 0);
         self.router.enqueue(make_message!(Payload::RequestPruningPointProof, RequestPruningPointProofMessage {})).await?;
 
-        // Pruning proof generation and communication might take several minutes, so we allow a long 10 minute timeout
-        let msg = dequeue_with_timeout!(self.incoming_route, Payload::PruningPointProof, Duration::from_secs(600))?;
+        // Pruning proof generation and communication might take several minutes, so we allow a long timeout (10 minutes by default)
+        let timeout = self.ctx.p2p_timeouts().pruning_point_proof;
+        let msg = dequeue_with_timeout!(self.incoming_route, Payload::PruningPointProof, timeout, TimeoutKind::PruningPointProof)?;
         let proof: PruningPointProof = msg.try_into()?;
         debug!("received proof with overall {} headers", proof.iter().map(|l| l.len()).sum::<usize>());
 
@@ -343,6 +395,7 @@ impl IbdFlow {
         // TODO: add logs to staging commit process
 
         info!("Starting to process {} trusted blocks", trusted_set.len());
+        self.ctx.set_ibd_phase(IbdPhase::HeadersProof, trusted_set.len() as u64);
         let mut last_time = Instant::now();
         let mut last_index: usize = 0;
         for (i, tb) in trusted_set.into_iter().enumerate() {
@@ -354,7 +407,9 @@ impl IbdFlow {
                 last_index = i;
             }
             // TODO: queue and join in batches
-            staging.validate_and_insert_trusted_block(tb).virtual_state_task.await?;
+            let hash = tb.block.hash();
+            block_job(hash, staging.validate_and_insert_trusted_block(tb).virtual_state_task).await?;
+            self.ctx.report_ibd_progress(1);
         }
         info!("Done processing trusted blocks");
         Ok(proof_pruning_point)
@@ -369,6 +424,8 @@ impl IbdFlow {
     ) -> Result<(), ProtocolError> {
         let highest_shared_header_score = consensus.async_get_header(highest_known_syncer_chain_hash).await?.daa_score;
         let mut progress_reporter = ProgressReporter::new(highest_shared_header_score, relay_block.header.daa_score, "block headers");
+        // The DAA score difference approximates the number of headers to download
+        self.ctx.set_ibd_phase(IbdPhase::Headers, relay_block.header.daa_score.saturating_sub(highest_shared_header_score));
 
         self.router
             .enqueue(make_message!(
@@ -379,30 +436,34 @@ impl IbdFlow {
                 }
             ))
             .await?;
-        let mut chunk_stream = HeadersChunkStream::new(&self.router, &mut self.incoming_route);
+        let mut chunk_stream = HeadersChunkStream::new(&self.router, &mut self.incoming_route, self.ctx.p2p_timeouts().headers);
 
         if let Some(chunk) = chunk_stream.next().await? {
             let (mut prev_daa_score, mut prev_timestamp) = {
                 let last_header = chunk.last().expect("chunk is never empty");
                 (last_header.daa_score, last_header.timestamp)
             };
-            let mut prev_jobs: Vec<BlockValidationFuture> =
-                chunk.into_iter().map(|h| consensus.validate_and_insert_block(Block::from_header_arc(h)).virtual_state_task).collect();
+            let mut prev_jobs = header_jobs(&self.ctx, consensus, &self.router, chunk).await;
 
-            while let Some(chunk) = chunk_stream.next().await? {
+            loop {
+                let request_time = Instant::now();
+                let Some(chunk) = chunk_stream.next().await? else {
+                    break;
+                };
+                if self.ctx.report_sync_peer_round_trip(&self.router, chunk.len(), request_time.elapsed()) {
+                    return Err(ProtocolError::Other("sync peer stalled repeatedly while downloading headers"));
+                }
                 let (current_daa_score, current_timestamp) = {
                     let last_header = chunk.last().expect("chunk is never empty");
                     (last_header.daa_score, last_header.timestamp)
                 };
-                let current_jobs = chunk
-                    .into_iter()
-                    .map(|h| consensus.validate_and_insert_block(Block::from_header_arc(h)).virtual_state_task)
-                    .collect();
+                let current_jobs = header_jobs(&self.ctx, consensus, &self.router, chunk).await;
                 let prev_chunk_len = prev_jobs.len();
                 // Join the previous chunk so that we always concurrently process a chunk and receive another
                 try_join_all(prev_jobs).await?;
                 // Log the progress
                 progress_reporter.report(prev_chunk_len, prev_daa_score, prev_timestamp);
+                self.ctx.report_ibd_progress(prev_chunk_len as u64);
                 prev_daa_score = current_daa_score;
                 prev_timestamp = current_timestamp;
                 prev_jobs = current_jobs;
@@ -411,6 +472,7 @@ impl IbdFlow {
             let prev_chunk_len = prev_jobs.len();
             try_join_all(prev_jobs).await?;
             progress_reporter.report_completion(prev_chunk_len);
+            self.ctx.report_ibd_progress(prev_chunk_len as u64);
         }
 
         self.sync_missing_relay_past_headers(consensus, syncer_virtual_selected_parent, relay_block.hash()).await?;
@@ -443,12 +505,14 @@ impl IbdFlow {
             ))
             .await?;
 
-        let msg = dequeue_with_timeout!(self.incoming_route, Payload::BlockHeaders)?;
+        let timeout = self.ctx.p2p_timeouts().headers;
+        let msg = dequeue_with_timeout!(self.incoming_route, Payload::BlockHeaders, timeout, TimeoutKind::Headers)?;
         let chunk: HeadersChunk = msg.try_into()?;
-        let jobs: Vec<BlockValidationFuture> =
-            chunk.into_iter().map(|h| consensus.validate_and_insert_block(Block::from_header_arc(h)).virtual_state_task).collect();
+        let jobs = header_jobs(&self.ctx, consensus, &self.router, chunk).await;
+        let jobs_len = jobs.len();
         try_join_all(jobs).await?;
-        dequeue_with_timeout!(self.incoming_route, Payload::DoneHeaders)?;
+        self.ctx.report_ibd_progress(jobs_len as u64);
+        dequeue_with_timeout!(self.incoming_route, Payload::DoneHeaders, timeout, TimeoutKind::Headers)?;
 
         if consensus.async_get_block_status(relay_block_hash).await.is_none() {
             // If the relay block has still not been received, the peer is misbehaving
@@ -487,9 +551,11 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
                 RequestPruningPointUtxoSetMessage { pruning_point_hash: Some(pruning_point.into()) }
             ))
             .await?;
+        self.ctx.set_ibd_phase(IbdPhase::UtxoSet, 0);
         let mut chunk_stream = PruningPointUtxosetChunkStream::new(&self.router, &mut self.incoming_route);
         let mut multiset = MuHash::new();
         while let Some(chunk) = chunk_stream.next().await? {
+            self.ctx.report_ibd_progress(chunk.len() as u64);
             multiset = consensus
                 .clone()
                 .spawn_blocking(move |c| {
@@ -521,6 +587,7 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
             }
             Either::Right((hashes_result, _)) => hashes_result,
         }?;
+        self.ctx.set_ibd_phase(IbdPhase::Bodies, hashes.len() as u64);
         if hashes.is_empty() {
             return Ok(());
         }
@@ -534,13 +601,18 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
             self.queue_block_processing_chunk(consensus, iter.next().expect("hashes was non empty")).await?;
 
         for chunk in iter {
+            let request_time = Instant::now();
             let QueueChunkOutput { jobs: current_jobs, daa_score: current_daa_score, timestamp: current_timestamp } =
                 self.queue_block_processing_chunk(consensus, chunk).await?;
+            if self.ctx.report_sync_peer_round_trip(&self.router, chunk.len(), request_time.elapsed()) {
+                return Err(ProtocolError::Other("sync peer stalled repeatedly while downloading blocks"));
+            }
             let prev_chunk_len = prev_jobs.len();
             // Join the previous chunk so that we always concurrently process a chunk and receive another
             try_join_all(prev_jobs).await?;
             // Log the progress
             progress_reporter.report(prev_chunk_len, prev_daa_score, prev_timestamp);
+            self.ctx.report_ibd_progress(prev_chunk_len as u64);
             prev_daa_score = current_daa_score;
             prev_timestamp = current_timestamp;
             prev_jobs = current_jobs;
@@ -549,6 +621,7 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
         let prev_chunk_len = prev_jobs.len();
         try_join_all(prev_jobs).await?;
         progress_reporter.report_completion(prev_chunk_len);
+        self.ctx.report_ibd_progress(prev_chunk_len as u64);
 
         Ok(())
     }
@@ -567,8 +640,9 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
                 RequestIbdBlocksMessage { hashes: chunk.iter().map(|h| h.into()).collect() }
             ))
             .await?;
+        let timeout = self.ctx.p2p_timeouts().ibd_blocks;
         for &expected_hash in chunk {
-            let msg = dequeue_with_timeout!(self.incoming_route, Payload::IbdBlock)?;
+            let msg = dequeue_with_timeout!(self.incoming_route, Payload::IbdBlock, timeout, TimeoutKind::IbdBlocks)?;
             let block: Block = msg.try_into()?;
             if block.hash() != expected_hash {
                 return Err(ProtocolError::OtherOwned(format!("expected block {} but got {}", expected_hash, block.hash())));
@@ -578,7 +652,12 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
             }
             current_daa_score = block.header.daa_score;
             current_timestamp = block.header.timestamp;
-            jobs.push(consensus.validate_and_insert_block(block).virtual_state_task);
+            jobs.push(attributed_block_job(
+                self.router.clone(),
+                false,
+                expected_hash,
+                self.ctx.validate_and_insert_ibd_block(consensus, block).await.virtual_state_task,
+            ));
         }
 
         Ok(QueueChunkOutput { jobs, daa_score: current_daa_score, timestamp: current_timestamp })
@@ -6,9 +6,10 @@ use kaspa_consensusmanager::ConsensusProxy;
 use kaspa_core::{debug, warn};
 use kaspa_hashes::Hash;
 use kaspa_p2p_lib::{
-    common::{ProtocolError, DEFAULT_TIMEOUT},
+    common::ProtocolError,
     dequeue_with_timeout, make_message,
     pb::{kaspad_message::Payload, RequestIbdChainBlockLocatorMessage},
+    TimeoutKind,
 };
 
 pub struct ChainNegotiationOutput {
@@ -32,7 +33,7 @@ impl IbdFlow {
         */
 
         // None hashes indicate that the full chain is queried
-        let mut locator_hashes = self.get_syncer_chain_block_locator(None, None, DEFAULT_TIMEOUT).await?;
+        let mut locator_hashes = self.get_syncer_chain_block_locator(None, None, self.ctx.p2p_timeouts().response).await?;
         if locator_hashes.is_empty() {
             return Err(ProtocolError::Other("Expecting initial syncer chain block locator to contain at least one element"));
         }
@@ -176,7 +177,7 @@ impl IbdFlow {
                 RequestIbdChainBlockLocatorMessage { low_hash: low.map(|h| h.into()), high_hash: high.map(|h| h.into()) }
             ))
             .await?;
-        let msg = dequeue_with_timeout!(self.incoming_route, Payload::IbdChainBlockLocator, timeout)?;
+        let msg = dequeue_with_timeout!(self.incoming_route, Payload::IbdChainBlockLocator, timeout, TimeoutKind::Response)?;
         if msg.block_locator_hashes.len() > 64 {
             return Err(ProtocolError::Other(
                 "Got block locator of size > 64 while expecting
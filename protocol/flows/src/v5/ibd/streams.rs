@@ -16,9 +16,9 @@ use kaspa_p2p_lib::{
         kaspad_message::Payload, RequestNextHeadersMessage, RequestNextPruningPointAndItsAnticoneBlocksMessage,
         RequestNextPruningPointUtxoSetChunkMessage,
     },
-    IncomingRoute, Router,
+    IncomingRoute, Router, TimeoutKind,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::time::timeout;
 
 pub const IBD_BATCH_SIZE: usize = 99;
@@ -88,15 +88,17 @@ pub struct HeadersChunkStream<'a, 'b> {
     router: &'a Router,
     incoming_route: &'b mut IncomingRoute,
     i: usize,
+    /// Max time to wait for each chunk, i.e., the round-trip of each headers request
+    timeout: Duration,
 }
 
 impl<'a, 'b> HeadersChunkStream<'a, 'b> {
-    pub fn new(router: &'a Router, incoming_route: &'b mut IncomingRoute) -> Self {
-        Self { router, incoming_route, i: 0 }
+    pub fn new(router: &'a Router, incoming_route: &'b mut IncomingRoute, timeout: Duration) -> Self {
+        Self { router, incoming_route, i: 0, timeout }
     }
 
     pub async fn next(&mut self) -> Result<Option<HeadersChunk>, ProtocolError> {
-        let res = match timeout(DEFAULT_TIMEOUT, self.incoming_route.recv()).await {
+        let res = match timeout(self.timeout, self.incoming_route.recv()).await {
             Ok(op) => {
                 if let Some(msg) = op {
                     match msg.payload {
@@ -121,7 +123,7 @@ impl<'a, 'b> HeadersChunkStream<'a, 'b> {
                     Err(ProtocolError::ConnectionClosed)
                 }
             }
-            Err(_) => Err(ProtocolError::Timeout(DEFAULT_TIMEOUT)),
+            Err(_) => Err(ProtocolError::StageTimeout(TimeoutKind::Headers, self.timeout)),
         };
 
         // Request the next batch only if the stream is still live
@@ -48,6 +48,9 @@ impl RequestPruningPointUtxoSetFlow {
         let mut chunks_sent = 0;
 
         let consensus = self.ctx.consensus();
+        // Delay pruning point movements while the UTXO set is being served, since the session is released between batches.
+        // This is best effort: when the guard limits are reached, a moved pruning point is reported to the peer as usual
+        let _pruning_point_guard = consensus.try_hold_pruning_point(self.router.identity());
         let mut session = consensus.session().await;
 
         loop {
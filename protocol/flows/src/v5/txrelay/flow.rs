@@ -112,6 +112,7 @@ impl RelayTransactionsFlow {
             if inv.len() > MAX_INV_PER_TX_INV_MSG {
                 return Err(ProtocolError::Other("Number of invs in tx inv message is over the limit"));
             }
+            self.router.mark_known_transactions(inv.iter().copied());
 
             let session = self.ctx.consensus().unguarded_session();
 
@@ -243,7 +244,7 @@ impl RelayTransactionsFlow {
         self.ctx
             .broadcast_transactions(
                 insert_results.into_iter().filter_map(|res| match res {
-                    Ok(x) => Some(x.id()),
+                    Ok(x) => Some((x.id(), x.subnetwork_id.clone())),
                     Err(_) => None,
                 }),
                 should_throttle,
@@ -46,6 +46,8 @@ impl PruningPointAndItsAnticoneRequestsFlow {
             debug!("Got request for pruning point and its anticone");
 
             let consensus = self.ctx.consensus();
+            // Best effort: when the guard limits are reached, the request is served without delaying pruning point movements
+            let _pruning_point_guard = consensus.try_hold_pruning_point(self.router.identity());
             let mut session = consensus.session().await;
 
             let pp_headers = session.async_pruning_point_headers().await;
@@ -0,0 +1,39 @@
+use kaspa_core::{error, info};
+use kaspa_p2p_lib::{replay_trace, MessageTraceReader};
+use std::net::SocketAddr;
+
+const USAGE: &str = "usage: kaspa_p2p_replay <trace-file> <target-address> [recorded-peer-address] [speedup]";
+
+/// Replays the messages recorded from a single peer (by default, the first peer appearing in the trace)
+/// against a fresh node listening at the target address
+#[tokio::main]
+async fn main() {
+    kaspa_core::log::init_logger(None, "info");
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (Some(trace_file), Some(target_address)) = (args.first(), args.get(1)) else {
+        eprintln!("{USAGE}");
+        std::process::exit(1);
+    };
+    let mut peer: Option<SocketAddr> = args.get(2).map(|s| s.parse().expect(USAGE));
+    let speedup: f64 = args.get(3).map(|s| s.parse().expect(USAGE)).unwrap_or(1.0);
+
+    let reader = MessageTraceReader::open(trace_file).expect("failed opening the trace file");
+    let mut records = Vec::new();
+    for record in reader {
+        let record = record.expect("failed reading the trace file");
+        if *peer.get_or_insert(record.peer) == record.peer {
+            records.push(record);
+        }
+    }
+    let Some(peer) = peer else {
+        info!("P2P replay, trace file {} is empty", trace_file);
+        return;
+    };
+
+    info!("P2P replay, sending {} messages recorded from peer {} to {} (speedup: {})", records.len(), peer, target_address, speedup);
+    match replay_trace(records, target_address.clone(), speedup).await {
+        Ok(sent) => info!("P2P replay, sent {} messages", sent),
+        Err(err) => error!("P2P replay failed: {}", err),
+    }
+}
@@ -1,5 +1,10 @@
-use crate::{convert::error::ConversionError, core::peer::PeerKey, KaspadMessagePayloadType};
+use crate::{
+    convert::error::ConversionError,
+    core::{identity::PeerIdentityKey, peer::PeerKey, timeouts::TimeoutKind},
+    KaspadMessagePayloadType,
+};
 use kaspa_consensus_core::errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError};
+use kaspa_hashes::Hash;
 use kaspa_mining_errors::manager::MiningManagerError;
 use std::time::Duration;
 use thiserror::Error;
@@ -12,6 +17,9 @@ pub enum ProtocolError {
     #[error("timeout expired after {0:?}")]
     Timeout(Duration),
 
+    #[error("{0} timeout expired after {1:?}")]
+    StageTimeout(TimeoutKind, Duration),
+
     #[error("P2P protocol version mismatch - local: {0}, remote: {1}")]
     VersionMismatch(u32, u32),
 
@@ -27,6 +35,9 @@ pub enum ProtocolError {
     #[error("{0}")]
     RuleError(#[from] RuleError),
 
+    #[error("block {0} is invalid: {1}")]
+    InvalidBlock(Hash, Box<RuleError>),
+
     #[error("{0}")]
     PruningImportError(#[from] PruningImportError),
 
@@ -67,6 +78,9 @@ pub enum ProtocolError {
     #[error("loopback connection - node is connecting to itself")]
     LoopbackConnection(PeerKey),
 
+    #[error("peer failed to prove its identity {0}")]
+    InvalidPeerIdentity(PeerIdentityKey),
+
     #[error("got reject message: {0}")]
     Rejected(String),
 
@@ -85,6 +99,27 @@ impl ProtocolError {
         matches!(self, Self::ConnectionClosed)
     }
 
+    /// Returns the kind of the expired timeout if this error is a [`ProtocolError::StageTimeout`]
+    pub fn timeout_kind(&self) -> Option<TimeoutKind> {
+        match self {
+            Self::StageTimeout(kind, _) => Some(*kind),
+            _ => None,
+        }
+    }
+
+    /// The misbehavior score charged to the peer which caused this error. Errors which are not
+    /// necessarily the peer's fault (timeouts, network issues, etc.) are not scored
+    pub fn ban_score(&self) -> u32 {
+        match self {
+            Self::MisbehavingPeer(_) | Self::InvalidBlock(_, _) | Self::InvalidPeerIdentity(_) => 100,
+            Self::UnexpectedMessage(_, _)
+            | Self::ConversionError(_)
+            | Self::NoRouteForMessageType(_)
+            | Self::IncomingRouteCapacityReached(_, _) => 10,
+            _ => 0,
+        }
+    }
+
     pub fn can_send_outgoing_message(&self) -> bool {
         !matches!(self, Self::ConnectionClosed | Self::OutgoingRouteCapacityReached(_))
     }
@@ -185,6 +220,8 @@ macro_rules! unwrap_message_with_request_id {
 /// let res = dequeue_with_timeout!(receiver, Payload::Verack) // Uses the default timeout
 /// // or:
 /// let res = dequeue_with_timeout!(receiver, Payload::Verack, Duration::from_secs(30))
+/// // or, reporting the expired timeout as a `ProtocolError::StageTimeout` of the given kind:
+/// let res = dequeue_with_timeout!(receiver, Payload::Verack, Duration::from_secs(30), TimeoutKind::HandshakeVerack)
 /// ```
 #[macro_export]
 macro_rules! dequeue_with_timeout {
@@ -204,6 +241,14 @@ macro_rules! dequeue_with_timeout {
             Err(_) => Err($crate::common::ProtocolError::Timeout($timeout_duration)),
         }
     }};
+    ($receiver:expr, $pattern:path, $timeout_duration:expr, $timeout_kind:expr) => {{
+        match tokio::time::timeout($timeout_duration, $receiver.recv()).await {
+            Ok(op) => {
+                $crate::unwrap_message!(op, $pattern)
+            }
+            Err(_) => Err($crate::common::ProtocolError::StageTimeout($timeout_kind, $timeout_duration)),
+        }
+    }};
 }
 
 /// Macro to indefinitely await a channel `Receiver<pb::KaspadMessage>::recv` call and expect a specific payload type (without a timeout).
@@ -1,6 +1,6 @@
 use super::{error::ConversionError, option::TryIntoOptionEx};
 use crate::pb as protowire;
-use kaspa_consensus_core::{block::Block, tx::Transaction};
+use kaspa_consensus_core::{block::Block, limits::Limit, tx::Transaction};
 
 // ----------------------------------------------------------------------------
 // consensus_core to protowire
@@ -20,6 +20,7 @@ impl TryFrom<protowire::BlockMessage> for Block {
     type Error = ConversionError;
 
     fn try_from(block: protowire::BlockMessage) -> Result<Self, Self::Error> {
+        Limit::BlockTransactions.check(block.transactions.len())?;
         Ok(Self::new(
             block.header.try_into_ex()?,
             block.transactions.into_iter().map(|i| i.try_into()).collect::<Result<Vec<Transaction>, Self::Error>>()?,
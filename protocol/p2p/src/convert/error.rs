@@ -1,4 +1,4 @@
-use kaspa_consensus_core::subnets::SubnetworkConversionError;
+use kaspa_consensus_core::{limits::LimitExceeded, subnets::SubnetworkConversionError};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Error)]
@@ -29,4 +29,7 @@ pub enum ConversionError {
 
     #[error(transparent)]
     SubnetParsingError(#[from] SubnetworkConversionError),
+
+    #[error(transparent)]
+    LimitExceeded(#[from] LimitExceeded),
 }
@@ -1,5 +1,5 @@
 use crate::pb as protowire;
-use kaspa_consensus_core::{header::Header, BlueWorkType};
+use kaspa_consensus_core::{header::Header, limits::Limit, BlueWorkType};
 use kaspa_hashes::Hash;
 
 use super::error::ConversionError;
@@ -42,6 +42,7 @@ impl From<&Vec<Hash>> for protowire::BlockLevelParents {
 impl TryFrom<protowire::BlockHeader> for Header {
     type Error = ConversionError;
     fn try_from(item: protowire::BlockHeader) -> Result<Self, Self::Error> {
+        Limit::ParentLevels.check(item.parents.len())?;
         Ok(Self::new_finalized(
             item.version.try_into()?,
             item.parents.into_iter().map(Vec::<Hash>::try_from).collect::<Result<Vec<Vec<Hash>>, ConversionError>>()?,
@@ -63,6 +64,7 @@ impl TryFrom<protowire::BlockHeader> for Header {
 impl TryFrom<protowire::BlockLevelParents> for Vec<Hash> {
     type Error = ConversionError;
     fn try_from(item: protowire::BlockLevelParents) -> Result<Self, Self::Error> {
+        Limit::ParentsPerLevel.check(item.parent_hashes.len())?;
         item.parent_hashes.into_iter().map(|x| x.try_into()).collect()
     }
 }
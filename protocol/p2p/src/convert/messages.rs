@@ -33,6 +33,8 @@ impl From<Version> for protowire::VersionMessage {
             disable_relay_tx: item.disable_relay_tx,
             subnetwork_id: item.subnetwork_id.map(|x| x.into()),
             network: item.network.clone(),
+            identity_public_key: item.identity.map(|key| key.as_bytes().to_vec()).unwrap_or_default(),
+            identity_nonce: vec![],
         }
     }
 }
@@ -54,6 +56,7 @@ impl TryFrom<protowire::VersionMessage> for Version {
             disable_relay_tx: msg.disable_relay_tx,
             subnetwork_id: if msg.subnetwork_id.is_none() { None } else { Some(msg.subnetwork_id.unwrap().try_into()?) },
             network: msg.network.clone(),
+            identity: if msg.identity_public_key.is_empty() { None } else { Some(msg.identity_public_key.as_slice().try_into()?) },
         })
     }
 }
@@ -5,6 +5,8 @@ use kaspa_core::{
 };
 use kaspa_utils::networking::{NetAddress, PeerId};
 
+use crate::core::identity::PeerIdentityKey;
+
 /// Maximum allowed length for the user agent field in a version message `VersionMessage`.
 pub const MAX_USER_AGENT_LEN: usize = 256;
 
@@ -18,6 +20,8 @@ pub struct Version {
     pub user_agent: String,
     pub disable_relay_tx: bool,
     pub subnetwork_id: Option<SubnetworkId>,
+    /// The identity key of the node, set by the handshake once the node proved owning it
+    pub identity: Option<PeerIdentityKey>,
 }
 
 impl Version {
@@ -38,6 +42,7 @@ impl Version {
             user_agent: format!("/{}:{}/", name(), version()),
             disable_relay_tx: false,
             subnetwork_id,
+            identity: None,
         }
     }
 
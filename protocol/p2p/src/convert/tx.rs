@@ -1,6 +1,7 @@
 use super::{error::ConversionError, option::TryIntoOptionEx};
 use crate::pb as protowire;
 use kaspa_consensus_core::{
+    limits::Limit,
     subnets::SubnetworkId,
     tx::{ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
 };
@@ -96,6 +97,7 @@ impl TryFrom<protowire::ScriptPublicKey> for ScriptPublicKey {
     type Error = ConversionError;
 
     fn try_from(value: protowire::ScriptPublicKey) -> Result<Self, Self::Error> {
+        Limit::ScriptPublicKeyLen.check(value.script.len())?;
         Ok(Self::from_vec(value.version.try_into()?, value.script))
     }
 }
@@ -120,6 +122,7 @@ impl TryFrom<protowire::TransactionInput> for TransactionInput {
     type Error = ConversionError;
 
     fn try_from(value: protowire::TransactionInput) -> Result<Self, Self::Error> {
+        Limit::SignatureScriptLen.check(value.signature_script.len())?;
         Ok(Self::new(value.previous_outpoint.try_into_ex()?, value.signature_script, value.sequence, value.sig_op_count.try_into()?))
     }
 }
@@ -136,6 +139,9 @@ impl TryFrom<protowire::TransactionMessage> for Transaction {
     type Error = ConversionError;
 
     fn try_from(tx: protowire::TransactionMessage) -> Result<Self, Self::Error> {
+        Limit::TransactionInputs.check(tx.inputs.len())?;
+        Limit::TransactionOutputs.check(tx.outputs.len())?;
+        Limit::PayloadLen.check(tx.payload.len())?;
         let transaction = Self::new(
             tx.version.try_into()?,
             tx.inputs.into_iter().map(|i| i.try_into()).collect::<Result<Vec<TransactionInput>, Self::Error>>()?,
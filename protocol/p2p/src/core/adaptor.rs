@@ -17,6 +17,9 @@ use super::peer::PeerKey;
 #[tonic::async_trait]
 pub trait ConnectionInitializer: Sync + Send {
     async fn initialize_connection(&self, new_router: Arc<Router>) -> Result<(), ProtocolError>;
+
+    /// Called once a peer which was successfully initialized is removed from the set of active peers
+    fn on_connection_closed(&self, _router: &Arc<Router>) {}
 }
 
 /// The main object to create for managing a fully-fledged Kaspa P2P peer
@@ -48,7 +51,18 @@ impl Adaptor {
     /// Creates a P2P adaptor with only client-side support. Typical Kaspa nodes should use `Adaptor::bidirectional`
     pub fn client_only(hub: Hub, initializer: Arc<dyn ConnectionInitializer>, counters: Arc<TowerConnectionCounters>) -> Arc<Self> {
         let (hub_sender, hub_receiver) = mpsc_channel(Self::hub_channel_size());
-        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters);
+        let connection_handler = ConnectionHandler::new(
+            hub_sender,
+            initializer.clone(),
+            counters,
+            hub.trace_recorder(),
+            hub.message_counters(),
+            hub.timeout_counters(),
+            hub.upload_throttle(),
+            hub.known_inventory_sizes(),
+            hub.inventory_counters(),
+            hub.outbound_proxy(),
+        );
         let adaptor = Arc::new(Adaptor::new(None, connection_handler, hub));
         adaptor.hub.clone().start_event_loop(hub_receiver, initializer);
         adaptor
@@ -62,7 +76,18 @@ impl Adaptor {
         counters: Arc<TowerConnectionCounters>,
     ) -> Result<Arc<Self>, ConnectionError> {
         let (hub_sender, hub_receiver) = mpsc_channel(Self::hub_channel_size());
-        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters);
+        let connection_handler = ConnectionHandler::new(
+            hub_sender,
+            initializer.clone(),
+            counters,
+            hub.trace_recorder(),
+            hub.message_counters(),
+            hub.timeout_counters(),
+            hub.upload_throttle(),
+            hub.known_inventory_sizes(),
+            hub.inventory_counters(),
+            hub.outbound_proxy(),
+        );
         let server_termination = connection_handler.serve(serve_address)?;
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, hub));
         adaptor.hub.clone().start_event_loop(hub_receiver, initializer);
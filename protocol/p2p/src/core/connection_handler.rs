@@ -1,24 +1,32 @@
 use crate::common::ProtocolError;
+use crate::core::counters::P2pMessageCounters;
 use crate::core::hub::HubEvent;
+use crate::core::inventory::{KnownInventoryCounters, KnownInventorySizes};
+use crate::core::proxy::Socks5Proxy;
+use crate::core::throttle::UploadThrottle;
+use crate::core::timeouts::P2pTimeoutCounters;
+use crate::core::trace::MessageTraceRecorder;
 use crate::pb::{
     p2p_client::P2pClient as ProtoP2pClient, p2p_server::P2p as ProtoP2p, p2p_server::P2pServer as ProtoP2pServer, KaspadMessage,
 };
 use crate::{ConnectionInitializer, Router};
 use futures::FutureExt;
-use kaspa_core::{debug, info};
+use kaspa_core::{debug, info, warn};
 use kaspa_utils::networking::NetAddress;
 use kaspa_utils_tower::{
     counters::TowerConnectionCounters,
     middleware::{BodyExt, CountBytesBody, MapRequestBodyLayer, MapResponseBodyLayer, ServiceBuilder},
 };
-use std::net::ToSocketAddrs;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
-use tokio::sync::mpsc::{channel as mpsc_channel, Sender as MpscSender};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc::{channel as mpsc_channel, Receiver as MpscReceiver, Sender as MpscSender};
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
-use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
 use tokio_stream::StreamExt;
 use tonic::transport::{Error as TonicError, Server as TonicServer};
 use tonic::{Request, Response, Status as TonicStatus, Streaming};
@@ -42,7 +50,9 @@ pub enum ConnectionError {
 }
 
 /// Maximum P2P decoded gRPC message size to send and receive
-const P2P_MAX_MESSAGE_SIZE: usize = 1024 * 1024 * 1024; // 1GB
+pub(crate) const P2P_MAX_MESSAGE_SIZE: usize = 1024 * 1024 * 1024; // 1GB
+
+type OutgoingStream = Pin<Box<dyn futures::Stream<Item = KaspadMessage> + Send + 'static>>;
 
 /// Handles Router creation for both server and client-side new connections
 #[derive(Clone)]
@@ -51,6 +61,15 @@ pub struct ConnectionHandler {
     hub_sender: MpscSender<HubEvent>,
     initializer: Arc<dyn ConnectionInitializer>,
     counters: Arc<TowerConnectionCounters>,
+    /// If set, all incoming messages from all peers are recorded to a trace file
+    trace_recorder: Option<Arc<MessageTraceRecorder>>,
+    message_counters: Arc<P2pMessageCounters>,
+    timeout_counters: Arc<P2pTimeoutCounters>,
+    upload_throttle: UploadThrottle,
+    known_inventory_sizes: KnownInventorySizes,
+    inventory_counters: Arc<KnownInventoryCounters>,
+    /// If set, outbound connections are made through this SOCKS5 proxy
+    outbound_proxy: Option<Socks5Proxy>,
 }
 
 impl ConnectionHandler {
@@ -58,15 +77,49 @@ impl ConnectionHandler {
         hub_sender: MpscSender<HubEvent>,
         initializer: Arc<dyn ConnectionInitializer>,
         counters: Arc<TowerConnectionCounters>,
+        trace_recorder: Option<Arc<MessageTraceRecorder>>,
+        message_counters: Arc<P2pMessageCounters>,
+        timeout_counters: Arc<P2pTimeoutCounters>,
+        upload_throttle: UploadThrottle,
+        known_inventory_sizes: KnownInventorySizes,
+        inventory_counters: Arc<KnownInventoryCounters>,
+        outbound_proxy: Option<Socks5Proxy>,
     ) -> Self {
-        Self { hub_sender, initializer, counters }
+        Self {
+            hub_sender,
+            initializer,
+            counters,
+            trace_recorder,
+            message_counters,
+            timeout_counters,
+            upload_throttle,
+            known_inventory_sizes,
+            inventory_counters,
+            outbound_proxy,
+        }
     }
 
-    /// Launches a P2P server listener loop
+    /// Launches a P2P server listener loop. An unspecified `serve_address` stands for dual-stack listening, i.e. on
+    /// both IPv6 and IPv4, in which case failing to listen on the family other than the one of `serve_address` is not fatal
     pub(crate) fn serve(&self, serve_address: NetAddress) -> Result<OneshotSender<()>, ConnectionError> {
         let (termination_sender, termination_receiver) = oneshot_channel::<()>();
         let connection_handler = self.clone();
-        info!("P2P Server starting on: {}", serve_address);
+
+        let mut listeners = vec![Self::bind(serve_address.into())?];
+        if serve_address.ip.is_unspecified() {
+            let other_family_address = match serve_address.ip.0 {
+                IpAddr::V4(_) => SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), serve_address.port),
+                IpAddr::V6(_) => SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), serve_address.port),
+            };
+            match Self::bind(other_family_address) {
+                Ok(listener) => listeners.push(listener),
+                Err(err) => warn!("P2P Server cannot listen on {}, serving a single IP family: {}", other_family_address, err),
+            }
+        }
+        for listener in listeners.iter() {
+            info!("P2P Server starting on: {}", listener.local_addr()?);
+        }
+        let incoming = futures::stream::select_all(listeners.into_iter().map(TcpListenerStream::new));
 
         let bytes_tx = self.counters.bytes_tx.clone();
         let bytes_rx = self.counters.bytes_rx.clone();
@@ -82,7 +135,7 @@ impl ConnectionHandler {
                 .layer(MapRequestBodyLayer::new(move |body| CountBytesBody::new(body, bytes_rx.clone()).boxed_unsync()))
                 .layer(MapResponseBodyLayer::new(move |body| CountBytesBody::new(body, bytes_tx.clone())))
                 .add_service(proto_server)
-                .serve_with_shutdown(serve_address.into(), termination_receiver.map(drop))
+                .serve_with_incoming_shutdown(incoming, termination_receiver.map(drop))
                 .await;
 
             match serve_result {
@@ -93,19 +146,40 @@ impl ConnectionHandler {
         Ok(termination_sender)
     }
 
+    /// Binds a listener at `address`. IPv6 listeners are restricted to IPv6 so that an IPv4 listener can share their port
+    fn bind(address: SocketAddr) -> std::io::Result<TcpListener> {
+        let socket = Socket::new(Domain::for_address(address), Type::STREAM, Some(Protocol::TCP))?;
+        if address.is_ipv6() {
+            socket.set_only_v6(true)?;
+        }
+        #[cfg(not(windows))]
+        socket.set_reuse_address(true)?;
+        socket.set_nonblocking(true)?;
+        socket.bind(&address.into())?;
+        socket.listen(1024)?;
+        TcpListener::from_std(socket.into())
+    }
+
     /// Connect to a new peer
     pub(crate) async fn connect(&self, peer_address: String) -> Result<Arc<Router>, ConnectionError> {
-        let Some(socket_address) = peer_address.to_socket_addrs()?.next() else {
+        let socket_address = match self.outbound_proxy {
+            // Host names are not resolved locally to avoid DNS leaks, so only IP addresses can be reached through a proxy
+            Some(_) => peer_address.parse::<SocketAddr>().ok(),
+            None => peer_address.to_socket_addrs()?.next(),
+        };
+        let Some(socket_address) = socket_address else {
             return Err(ConnectionError::NoAddress);
         };
         let peer_address = format!("http://{}", peer_address); // Add scheme prefix as required by Tonic
 
-        let channel = tonic::transport::Endpoint::new(peer_address)?
+        let endpoint = tonic::transport::Endpoint::new(peer_address)?
             .timeout(Duration::from_millis(Self::communication_timeout()))
             .connect_timeout(Duration::from_millis(Self::connect_timeout()))
-            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())))
-            .connect()
-            .await?;
+            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())));
+        let channel = match self.outbound_proxy.as_ref() {
+            Some(proxy) => endpoint.connect_with_connector(proxy.connector(Duration::from_millis(Self::keep_alive()))).await?,
+            None => endpoint.connect().await?,
+        };
 
         let channel = ServiceBuilder::new()
             .layer(MapResponseBodyLayer::new(move |body| CountBytesBody::new(body, self.counters.bytes_rx.clone())))
@@ -118,9 +192,21 @@ impl ConnectionHandler {
             .max_decoding_message_size(P2P_MAX_MESSAGE_SIZE);
 
         let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_network_channel_size());
-        let incoming_stream = client.message_stream(ReceiverStream::new(outgoing_receiver)).await?.into_inner();
-
-        let router = Router::new(socket_address, true, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
+        let incoming_stream = client.message_stream(self.outgoing_stream(outgoing_receiver)).await?.into_inner();
+
+        let router = Router::new(
+            socket_address,
+            true,
+            self.hub_sender.clone(),
+            incoming_stream,
+            outgoing_route,
+            self.trace_recorder.clone(),
+            self.message_counters.clone(),
+            self.timeout_counters.clone(),
+            self.known_inventory_sizes,
+            self.inventory_counters.clone(),
+        )
+        .await;
 
         // For outbound peers, we perform the initialization as part of the connect logic
         match self.initializer.initialize_connection(router.clone()).await {
@@ -130,6 +216,7 @@ impl ConnectionHandler {
             }
 
             Err(err) => {
+                router.record_timeout(&err);
                 router.try_sending_reject_message(&err).await;
                 // Ignoring the new router
                 router.close().await;
@@ -175,6 +262,15 @@ impl ConnectionHandler {
         }
     }
 
+    /// Builds the stream of messages sent to the network peer, applying upload rate limits if configured
+    fn outgoing_stream(&self, outgoing_receiver: MpscReceiver<KaspadMessage>) -> OutgoingStream {
+        if self.upload_throttle.is_unlimited() {
+            Box::pin(ReceiverStream::new(outgoing_receiver))
+        } else {
+            Box::pin(self.upload_throttle.throttle(outgoing_receiver))
+        }
+    }
+
     // TODO: revisit the below constants
     fn outgoing_network_channel_size() -> usize {
         // TODO: this number is taken from go-kaspad and should be re-evaluated
@@ -212,12 +308,24 @@ impl ProtoP2p for ConnectionHandler {
         let incoming_stream = request.into_inner();
 
         // Build the router object
-        let router = Router::new(remote_address, false, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
+        let router = Router::new(
+            remote_address,
+            false,
+            self.hub_sender.clone(),
+            incoming_stream,
+            outgoing_route,
+            self.trace_recorder.clone(),
+            self.message_counters.clone(),
+            self.timeout_counters.clone(),
+            self.known_inventory_sizes,
+            self.inventory_counters.clone(),
+        )
+        .await;
 
         // Notify the central Hub about the new peer
         self.hub_sender.send(HubEvent::NewPeer(router)).await.expect("hub receiver should never drop before senders");
 
         // Give tonic a receiver stream (messages sent to it will be forwarded to the network peer)
-        Ok(Response::new(Box::pin(ReceiverStream::new(outgoing_receiver).map(Ok)) as Self::MessageStreamStream))
+        Ok(Response::new(Box::pin(self.outgoing_stream(outgoing_receiver).map(Ok)) as Self::MessageStreamStream))
     }
 }
@@ -0,0 +1,263 @@
+use crate::pb::KaspadMessage;
+use crate::KaspadMessagePayloadType;
+use prost::Message;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+#[derive(Debug, Default)]
+struct MessageTypeCounters {
+    incoming_count: AtomicU64,
+    incoming_bytes: AtomicU64,
+    outgoing_count: AtomicU64,
+    outgoing_bytes: AtomicU64,
+}
+
+/// A snapshot of the traffic of a single P2P message type. Byte counts refer to the
+/// protobuf-encoded message size, i.e., before gRPC framing and compression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageTypeStats {
+    pub message_type: KaspadMessagePayloadType,
+    pub incoming_count: u64,
+    pub incoming_bytes: u64,
+    pub outgoing_count: u64,
+    pub outgoing_bytes: u64,
+}
+
+/// Counters of P2P messages (count and bytes) per message type and direction, aggregated over all peers
+#[derive(Debug)]
+pub struct P2pMessageCounters {
+    counters: [MessageTypeCounters; KaspadMessagePayloadType::COUNT],
+}
+
+impl Default for P2pMessageCounters {
+    fn default() -> Self {
+        Self { counters: std::array::from_fn(|_| Default::default()) }
+    }
+}
+
+impl P2pMessageCounters {
+    pub fn record_incoming(&self, msg: &KaspadMessage) {
+        if let Some(payload) = msg.payload.as_ref() {
+            let counters = &self.counters[KaspadMessagePayloadType::from(payload) as usize];
+            counters.incoming_count.fetch_add(1, Ordering::Relaxed);
+            counters.incoming_bytes.fetch_add(msg.encoded_len() as u64, Ordering::Relaxed);
+        }
+    }
+
+    pub fn record_outgoing(&self, msg: &KaspadMessage) {
+        if let Some(payload) = msg.payload.as_ref() {
+            let counters = &self.counters[KaspadMessagePayloadType::from(payload) as usize];
+            counters.outgoing_count.fetch_add(1, Ordering::Relaxed);
+            counters.outgoing_bytes.fetch_add(msg.encoded_len() as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the stats of all message types which were sent or received at least once
+    pub fn snapshot(&self) -> Vec<MessageTypeStats> {
+        KaspadMessagePayloadType::ALL
+            .into_iter()
+            .zip(self.counters.iter())
+            .map(|(message_type, counters)| MessageTypeStats {
+                message_type,
+                incoming_count: counters.incoming_count.load(Ordering::Relaxed),
+                incoming_bytes: counters.incoming_bytes.load(Ordering::Relaxed),
+                outgoing_count: counters.outgoing_count.load(Ordering::Relaxed),
+                outgoing_bytes: counters.outgoing_bytes.load(Ordering::Relaxed),
+            })
+            .filter(|stats| stats.incoming_count > 0 || stats.outgoing_count > 0)
+            .collect()
+    }
+
+    /// Returns the total number of (incoming, outgoing) messages over all message types
+    pub fn total_counts(&self) -> (u64, u64) {
+        self.counters.iter().fold((0, 0), |(incoming, outgoing), counters| {
+            (incoming + counters.incoming_count.load(Ordering::Relaxed), outgoing + counters.outgoing_count.load(Ordering::Relaxed))
+        })
+    }
+}
+
+/// The outcome of processing a block or a header submitted by a peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    /// Validated and added to the DAG
+    Valid,
+    /// Already known or being processed on behalf of another peer
+    Duplicate,
+    /// Rejected by consensus
+    Invalid,
+}
+
+/// Counts of the blocks or headers submitted by a peer, by processing outcome
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubmissionStats {
+    pub valid: u64,
+    pub duplicate: u64,
+    pub invalid: u64,
+}
+
+impl SubmissionStats {
+    pub fn total(&self) -> u64 {
+        self.valid + self.duplicate + self.invalid
+    }
+}
+
+#[derive(Debug, Default)]
+struct SubmissionCounters {
+    valid: AtomicU64,
+    duplicate: AtomicU64,
+    invalid: AtomicU64,
+}
+
+impl SubmissionCounters {
+    fn record(&self, outcome: SubmissionOutcome) {
+        match outcome {
+            SubmissionOutcome::Valid => &self.valid,
+            SubmissionOutcome::Duplicate => &self.duplicate,
+            SubmissionOutcome::Invalid => &self.invalid,
+        }
+        .fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> SubmissionStats {
+        SubmissionStats {
+            valid: self.valid.load(Ordering::Relaxed),
+            duplicate: self.duplicate.load(Ordering::Relaxed),
+            invalid: self.invalid.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The number of redundant submissions charged with a single misbehavior point, see [`PeerSessionStats::redundancy_score`]
+const REDUNDANT_SUBMISSIONS_PER_POINT: u64 = 100;
+
+/// The maximal misbehavior score charged for redundant submissions within a single connection
+const MAX_REDUNDANCY_SCORE: u32 = 50;
+
+/// A snapshot of the traffic and quality figures of a single peer connection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PeerSessionStats {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    /// Average ping latency in milliseconds
+    pub avg_latency: u64,
+    pub latency_samples: u64,
+    /// Accumulated misbehavior score, see [`crate::common::ProtocolError::ban_score`]
+    pub ban_score: u32,
+    /// Full blocks submitted by the peer, either relayed or during IBD
+    pub blocks: SubmissionStats,
+    /// Headers submitted by the peer during IBD
+    pub headers: SubmissionStats,
+}
+
+impl PeerSessionStats {
+    /// The number of blocks and headers this peer contributed to the DAG
+    pub fn contribution(&self) -> u64 {
+        self.blocks.valid + self.headers.valid
+    }
+
+    /// A misbehavior score for feeding us mostly redundant data, i.e., duplicate submissions in excess of the
+    /// valid ones. Invalid submissions are not accounted for here since they are charged as protocol errors
+    pub fn redundancy_score(&self) -> u32 {
+        let excess = (self.blocks.duplicate + self.headers.duplicate).saturating_sub(self.contribution());
+        (excess / REDUNDANT_SUBMISSIONS_PER_POINT).min(MAX_REDUNDANCY_SCORE as u64) as u32
+    }
+}
+
+/// Traffic and quality counters of a single peer connection
+#[derive(Debug, Default)]
+pub struct PeerSessionCounters {
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    latency_sum: AtomicU64,
+    latency_samples: AtomicU64,
+    ban_score: AtomicU32,
+    blocks: SubmissionCounters,
+    headers: SubmissionCounters,
+}
+
+impl PeerSessionCounters {
+    pub fn record_incoming(&self, msg: &KaspadMessage) {
+        self.bytes_received.fetch_add(msg.encoded_len() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_outgoing(&self, msg: &KaspadMessage) {
+        self.bytes_sent.fetch_add(msg.encoded_len() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_latency(&self, latency: u64) {
+        self.latency_sum.fetch_add(latency, Ordering::Relaxed);
+        self.latency_samples.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn add_ban_score(&self, score: u32) {
+        self.ban_score.fetch_add(score, Ordering::Relaxed);
+    }
+
+    pub fn record_block(&self, outcome: SubmissionOutcome) {
+        self.blocks.record(outcome);
+    }
+
+    pub fn record_header(&self, outcome: SubmissionOutcome) {
+        self.headers.record(outcome);
+    }
+
+    pub fn snapshot(&self) -> PeerSessionStats {
+        let latency_samples = self.latency_samples.load(Ordering::Relaxed);
+        PeerSessionStats {
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            avg_latency: if latency_samples > 0 { self.latency_sum.load(Ordering::Relaxed) / latency_samples } else { 0 },
+            latency_samples,
+            ban_score: self.ban_score.load(Ordering::Relaxed),
+            blocks: self.blocks.snapshot(),
+            headers: self.headers.snapshot(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::{kaspad_message::Payload, PingMessage, PongMessage};
+
+    #[test]
+    fn test_message_counters() {
+        let counters = P2pMessageCounters::default();
+        let ping = KaspadMessage { payload: Some(Payload::Ping(PingMessage { nonce: 7 })), request_id: 0, response_id: 0 };
+        let pong = KaspadMessage { payload: Some(Payload::Pong(PongMessage { nonce: 7 })), request_id: 0, response_id: 0 };
+        counters.record_incoming(&ping);
+        counters.record_incoming(&ping);
+        counters.record_outgoing(&pong);
+        counters.record_incoming(&KaspadMessage { payload: None, request_id: 0, response_id: 0 });
+
+        let snapshot = counters.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        let ping_stats = snapshot.iter().find(|s| s.message_type == KaspadMessagePayloadType::Ping).unwrap();
+        assert_eq!(ping_stats.incoming_count, 2);
+        assert_eq!(ping_stats.incoming_bytes, 2 * ping.encoded_len() as u64);
+        assert_eq!(ping_stats.outgoing_count, 0);
+        let pong_stats = snapshot.iter().find(|s| s.message_type == KaspadMessagePayloadType::Pong).unwrap();
+        assert_eq!((pong_stats.incoming_count, pong_stats.outgoing_count), (0, 1));
+        assert_eq!(pong_stats.outgoing_bytes, pong.encoded_len() as u64);
+        assert_eq!(counters.total_counts(), (2, 1));
+    }
+
+    #[test]
+    fn test_session_submissions() {
+        let counters = PeerSessionCounters::default();
+        counters.record_block(SubmissionOutcome::Valid);
+        counters.record_block(SubmissionOutcome::Invalid);
+        (0..10).for_each(|_| counters.record_header(SubmissionOutcome::Valid));
+        (0..260).for_each(|_| counters.record_header(SubmissionOutcome::Duplicate));
+
+        let stats = counters.snapshot();
+        assert_eq!(stats.blocks, SubmissionStats { valid: 1, duplicate: 0, invalid: 1 });
+        assert_eq!(stats.headers, SubmissionStats { valid: 10, duplicate: 260, invalid: 0 });
+        assert_eq!(stats.contribution(), 11);
+        // 249 duplicates in excess of the valid submissions
+        assert_eq!(stats.redundancy_score(), 2);
+
+        let flooding =
+            PeerSessionStats { blocks: SubmissionStats { duplicate: 1_000_000, ..Default::default() }, ..Default::default() };
+        assert_eq!(flooding.redundancy_score(), MAX_REDUNDANCY_SCORE);
+    }
+}
@@ -0,0 +1,61 @@
+//!
+//! Fault injection for testing the node over an unreliable network (enabled by the `fault-injection` feature).
+//!
+//! Once enabled, messages received from peers are randomly dropped or delayed with the configured probabilities
+//! before being routed to flows. Handshake messages are never dropped, so connections can still be established,
+//! whereas dropped flow messages are expected to be recovered from through the regular timeout and reconnection logic.
+//!
+//! Note that the configuration is process-wide, i.e., it applies to all the peers of all hubs of the process.
+//!
+
+use crate::{pb::KaspadMessage, KaspadMessagePayloadType};
+use parking_lot::RwLock;
+use rand::Rng;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct P2pFaultConfig {
+    /// The probability of an incoming message to be dropped
+    pub drop_probability: f64,
+    /// The probability of an incoming message to be delayed by up to `max_delay`
+    pub delay_probability: f64,
+    pub max_delay: Duration,
+}
+
+/// A fault injected into the processing of an incoming message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InjectedFault {
+    Drop,
+    Delay(Duration),
+}
+
+static CONFIG: RwLock<Option<P2pFaultConfig>> = parking_lot::const_rwlock(None);
+
+/// Starts injecting faults into incoming P2P messages according to `config`
+pub fn enable(config: P2pFaultConfig) {
+    *CONFIG.write() = Some(config);
+}
+
+/// Stops injecting faults into incoming P2P messages
+pub fn disable() {
+    *CONFIG.write() = None;
+}
+
+/// Draws the fault to inject into the processing of `msg`, if any
+pub(crate) fn sample(msg: &KaspadMessage) -> Option<InjectedFault> {
+    let config = (*CONFIG.read())?;
+    let droppable = msg.payload.as_ref().is_some_and(|payload| {
+        !matches!(
+            KaspadMessagePayloadType::from(payload),
+            KaspadMessagePayloadType::Version | KaspadMessagePayloadType::Verack | KaspadMessagePayloadType::Ready
+        )
+    });
+    let mut rng = rand::thread_rng();
+    if droppable && rng.gen_bool(config.drop_probability.clamp(0.0, 1.0)) {
+        Some(InjectedFault::Drop)
+    } else if !config.max_delay.is_zero() && rng.gen_bool(config.delay_probability.clamp(0.0, 1.0)) {
+        Some(InjectedFault::Delay(rng.gen_range(Duration::ZERO..=config.max_delay)))
+    } else {
+        None
+    }
+}
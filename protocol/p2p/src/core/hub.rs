@@ -1,5 +1,6 @@
 use crate::{common::ProtocolError, pb::KaspadMessage, ConnectionInitializer, Peer, Router};
 use kaspa_core::{debug, info, warn};
+use kaspa_utils::networking::PeerId;
 use parking_lot::RwLock;
 use std::{
     collections::{hash_map::Entry::Occupied, HashMap},
@@ -7,7 +8,13 @@ use std::{
 };
 use tokio::sync::mpsc::Receiver as MpscReceiver;
 
+use super::counters::P2pMessageCounters;
+use super::inventory::{KnownInventoryCounters, KnownInventorySizes};
 use super::peer::PeerKey;
+use super::proxy::Socks5Proxy;
+use super::throttle::{UploadLimits, UploadThrottle};
+use super::timeouts::P2pTimeoutCounters;
+use super::trace::MessageTraceRecorder;
 use rand::prelude::IteratorRandom;
 
 #[derive(Debug)]
@@ -23,11 +30,101 @@ pub struct Hub {
     ///
     /// Note: the map key holds the node id and IP to prevent node impersonating.
     pub(crate) peers: Arc<RwLock<HashMap<PeerKey, Arc<Router>>>>,
+
+    /// Optional recorder of all incoming P2P messages, handed to every router created by adaptors using this hub
+    trace_recorder: Option<Arc<MessageTraceRecorder>>,
+
+    /// Message counters shared by all routers created by adaptors using this hub
+    message_counters: Arc<P2pMessageCounters>,
+
+    /// Expired timeout counters shared by all routers created by adaptors using this hub
+    timeout_counters: Arc<P2pTimeoutCounters>,
+
+    /// Upload rate limiting applied to all routers created by adaptors using this hub
+    upload_throttle: UploadThrottle,
+
+    /// Capacities of the known inventory caches of all routers created by adaptors using this hub
+    known_inventory_sizes: KnownInventorySizes,
+
+    /// Known inventory hit/miss counters shared by all routers created by adaptors using this hub
+    inventory_counters: Arc<KnownInventoryCounters>,
+
+    /// Optional SOCKS5 proxy through which all outbound connections of adaptors using this hub are made
+    outbound_proxy: Option<Socks5Proxy>,
 }
 
 impl Hub {
     pub fn new() -> Self {
-        Self { peers: Arc::new(RwLock::new(HashMap::new())) }
+        Self {
+            peers: Arc::new(RwLock::new(HashMap::new())),
+            trace_recorder: None,
+            message_counters: Default::default(),
+            timeout_counters: Default::default(),
+            upload_throttle: Default::default(),
+            known_inventory_sizes: Default::default(),
+            inventory_counters: Default::default(),
+            outbound_proxy: None,
+        }
+    }
+
+    /// Creates a hub whose peers record all their incoming messages using `trace_recorder`
+    pub fn with_trace_recorder(trace_recorder: Arc<MessageTraceRecorder>) -> Self {
+        Self {
+            peers: Arc::new(RwLock::new(HashMap::new())),
+            trace_recorder: Some(trace_recorder),
+            message_counters: Default::default(),
+            timeout_counters: Default::default(),
+            upload_throttle: Default::default(),
+            known_inventory_sizes: Default::default(),
+            inventory_counters: Default::default(),
+            outbound_proxy: None,
+        }
+    }
+
+    /// Limits the upload rate of peers of this hub according to `limits`
+    pub fn with_upload_limits(self, limits: UploadLimits) -> Self {
+        Self { upload_throttle: UploadThrottle::new(limits), ..self }
+    }
+
+    /// Sets the capacities of the per-peer caches of known inventory
+    pub fn with_known_inventory_sizes(self, sizes: KnownInventorySizes) -> Self {
+        Self { known_inventory_sizes: sizes, ..self }
+    }
+
+    /// Routes the outbound connections of peers of this hub through a SOCKS5 proxy
+    pub fn with_outbound_proxy(self, proxy: Socks5Proxy) -> Self {
+        Self { outbound_proxy: Some(proxy), ..self }
+    }
+
+    pub(crate) fn trace_recorder(&self) -> Option<Arc<MessageTraceRecorder>> {
+        self.trace_recorder.clone()
+    }
+
+    /// Per message type traffic counters aggregated over all peers of this hub
+    pub fn message_counters(&self) -> Arc<P2pMessageCounters> {
+        self.message_counters.clone()
+    }
+
+    /// Counters of timeouts expired by peers of this hub, per timeout kind
+    pub fn timeout_counters(&self) -> Arc<P2pTimeoutCounters> {
+        self.timeout_counters.clone()
+    }
+
+    pub(crate) fn upload_throttle(&self) -> UploadThrottle {
+        self.upload_throttle.clone()
+    }
+
+    pub(crate) fn known_inventory_sizes(&self) -> KnownInventorySizes {
+        self.known_inventory_sizes
+    }
+
+    pub(crate) fn outbound_proxy(&self) -> Option<Socks5Proxy> {
+        self.outbound_proxy.clone()
+    }
+
+    /// Counters of known inventory lookups made before relaying inventory to peers of this hub
+    pub fn inventory_counters(&self) -> Arc<KnownInventoryCounters> {
+        self.inventory_counters.clone()
     }
 
     /// Starts a loop for receiving central hub events from all peer routers. This mechanism is used for
@@ -48,6 +145,7 @@ impl Hub {
                                     self.insert_new_router(new_router).await;
                                 }
                                 Err(err) => {
+                                    new_router.record_timeout(&err);
                                     new_router.try_sending_reject_message(&err).await;
                                     // Ignoring the new router
                                     new_router.close().await;
@@ -61,14 +159,19 @@ impl Hub {
                         }
                     }
                     HubEvent::PeerClosing(router) => {
+                        let mut removed = false;
                         if let Occupied(entry) = self.peers.write().entry(router.key()) {
                             // We search for the router by identity, but make sure to delete it only if it's actually the same object.
                             // This is extremely important in cases of duplicate connection rejection etc.
                             if Arc::ptr_eq(entry.get(), &router) {
                                 entry.remove_entry();
+                                removed = true;
                                 debug!("P2P, Hub event loop, removing peer, router-id: {}", router.identity());
                             }
                         }
+                        if removed {
+                            initializer.on_connection_closed(&router);
+                        }
                     }
                 }
             }
@@ -142,6 +245,14 @@ impl Hub {
         }
     }
 
+    /// Returns the routers of all peers, or of `num_peers` randomly selected peers if specified
+    pub fn select_peers(&self, num_peers: Option<usize>) -> Vec<Arc<Router>> {
+        match num_peers {
+            Some(num_peers) => self.select_some_peers(num_peers).collect(),
+            None => self.peers.read().values().cloned().collect(),
+        }
+    }
+
     /// Broadcast a vector of messages to all peers
     pub async fn broadcast_many(&self, msgs: Vec<KaspadMessage>) {
         if msgs.is_empty() {
@@ -177,6 +288,11 @@ impl Hub {
         self.peers.read().values().map(|r| r.as_ref().into()).collect()
     }
 
+    /// Returns the router of the active peer with the given identity, if any
+    pub fn find_peer(&self, identity: PeerId) -> Option<Arc<Router>> {
+        self.peers.read().values().find(|router| router.identity() == identity).cloned()
+    }
+
     /// Returns the number of currently active peers
     pub fn active_peers_len(&self) -> usize {
         self.peers.read().len()
@@ -0,0 +1,159 @@
+use kaspa_hashes::{HasherBase, PeerIdentitySigningHash};
+use kaspa_utils::hex::ToHex;
+use rand::RngCore;
+use secp256k1::{schnorr, Keypair, Message, XOnlyPublicKey, SECP256K1};
+use std::fmt::{Debug, Display};
+
+/// Service flag advertised in version messages by nodes able to prove their identity during the handshake
+pub const SERVICE_NODE_IDENTITY: u64 = 1;
+
+/// The size of the challenge nonces sent in version messages
+pub const IDENTITY_NONCE_SIZE: usize = 32;
+
+/// The public key of a node, identifying it across connections and IP changes
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PeerIdentityKey([u8; 32]);
+
+impl PeerIdentityKey {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for PeerIdentityKey {
+    type Error = std::array::TryFromSliceError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self(value.try_into()?))
+    }
+}
+
+impl Display for PeerIdentityKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.as_slice().to_hex())
+    }
+}
+
+impl Debug for PeerIdentityKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// The handshake context an identity proof is bound to. Binding the nonces of both sides along with both identity keys
+/// prevents a node from relaying the proof of a third node, obtained over a separate connection, as its own
+#[derive(Clone, Copy, Debug)]
+pub struct IdentityProofContext<'a> {
+    /// The challenge nonce sent by the verifier
+    pub challenge: &'a [u8],
+    /// The nonce sent by the prover in its own version message
+    pub prover_nonce: &'a [u8],
+    /// The identity key of the prover
+    pub prover_key: &'a PeerIdentityKey,
+    /// The identity key of the verifier
+    pub verifier_key: &'a PeerIdentityKey,
+}
+
+impl IdentityProofContext<'_> {
+    fn message(&self) -> Message {
+        let mut hasher = PeerIdentitySigningHash::new();
+        for nonce in [self.challenge, self.prover_nonce] {
+            // Nonces are length-prefixed since their length is chosen by the peers
+            hasher.update((nonce.len() as u64).to_le_bytes());
+            hasher.update(nonce);
+        }
+        hasher.update(self.prover_key.as_bytes());
+        hasher.update(self.verifier_key.as_bytes());
+        Message::from_digest(hasher.finalize().as_bytes())
+    }
+}
+
+/// The persistent keypair of the local node, proving its identity to peers by signing their handshake challenges
+pub struct NodeIdentity {
+    keypair: Keypair,
+}
+
+impl NodeIdentity {
+    pub fn generate() -> Self {
+        Self { keypair: Keypair::new(SECP256K1, &mut rand::thread_rng()) }
+    }
+
+    pub fn from_secret_bytes(secret: &[u8; 32]) -> Result<Self, secp256k1::Error> {
+        Ok(Self { keypair: Keypair::from_seckey_slice(SECP256K1, secret)? })
+    }
+
+    pub fn secret_bytes(&self) -> [u8; 32] {
+        self.keypair.secret_bytes()
+    }
+
+    pub fn public_key(&self) -> PeerIdentityKey {
+        PeerIdentityKey(self.keypair.x_only_public_key().0.serialize())
+    }
+
+    /// Proves the identity of the local node within `context`, whose prover key must be the local public key
+    pub fn prove(&self, context: &IdentityProofContext) -> [u8; 64] {
+        debug_assert_eq!(*context.prover_key, self.public_key());
+        SECP256K1.sign_schnorr(&context.message(), &self.keypair).serialize()
+    }
+}
+
+impl Debug for NodeIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeIdentity").field("public_key", &self.public_key()).finish()
+    }
+}
+
+/// Generates a fresh challenge nonce
+pub fn generate_nonce() -> Vec<u8> {
+    let mut nonce = vec![0; IDENTITY_NONCE_SIZE];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    nonce
+}
+
+/// Checks that `signature` proves the identity of the prover of `context`
+pub fn verify_identity_proof(context: &IdentityProofContext, signature: &[u8]) -> bool {
+    let Ok(public_key) = XOnlyPublicKey::from_slice(context.prover_key.as_bytes()) else {
+        return false;
+    };
+    let Ok(signature) = schnorr::Signature::from_slice(signature) else {
+        return false;
+    };
+    SECP256K1.verify_schnorr(&signature, &context.message(), &public_key).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_proof() {
+        let identity = NodeIdentity::generate();
+        let restored = NodeIdentity::from_secret_bytes(&identity.secret_bytes()).unwrap();
+        assert_eq!(identity.public_key(), restored.public_key());
+
+        let (prover_key, verifier_key, other_key) =
+            (identity.public_key(), NodeIdentity::generate().public_key(), NodeIdentity::generate().public_key());
+        let (challenge, prover_nonce) = (generate_nonce(), generate_nonce());
+        let context = IdentityProofContext {
+            challenge: &challenge,
+            prover_nonce: &prover_nonce,
+            prover_key: &prover_key,
+            verifier_key: &verifier_key,
+        };
+        let signature = restored.prove(&context);
+        assert!(verify_identity_proof(&context, &signature));
+        assert!(!verify_identity_proof(&context, &signature[..32]));
+
+        // The proof is bound to each element of the context
+        let other_nonce = generate_nonce();
+        assert!(!verify_identity_proof(&IdentityProofContext { challenge: &other_nonce, ..context }, &signature));
+        assert!(!verify_identity_proof(&IdentityProofContext { prover_nonce: &other_nonce, ..context }, &signature));
+        assert!(!verify_identity_proof(&IdentityProofContext { prover_key: &other_key, ..context }, &signature));
+        assert!(!verify_identity_proof(&IdentityProofContext { verifier_key: &other_key, ..context }, &signature));
+        // Swapping the nonces does not move bytes across the length-prefixed boundary
+        assert!(!verify_identity_proof(
+            &IdentityProofContext { challenge: &prover_nonce, prover_nonce: &challenge, ..context },
+            &signature
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+use kaspa_hashes::Hash;
+use kaspa_utils::lru::LruCache;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Number of block hashes remembered as known by each peer unless configured otherwise
+pub const DEFAULT_KNOWN_BLOCKS_CACHE_SIZE: usize = 1_024;
+
+/// Number of transaction IDs remembered as known by each peer unless configured otherwise
+pub const DEFAULT_KNOWN_TRANSACTIONS_CACHE_SIZE: usize = 8_192;
+
+/// Capacities of the per-peer caches of known inventory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownInventorySizes {
+    pub blocks: usize,
+    pub transactions: usize,
+}
+
+impl Default for KnownInventorySizes {
+    fn default() -> Self {
+        Self { blocks: DEFAULT_KNOWN_BLOCKS_CACHE_SIZE, transactions: DEFAULT_KNOWN_TRANSACTIONS_CACHE_SIZE }
+    }
+}
+
+/// A bounded set of inventory hashes (block hashes or transaction IDs) known to a peer, evicting the least
+/// recently used hash when full
+pub struct KnownInventory {
+    cache: LruCache<Hash, ()>,
+}
+
+impl KnownInventory {
+    /// Creates a set remembering up to `capacity` hashes. A zero capacity disables it
+    pub fn new(capacity: usize) -> Self {
+        Self { cache: LruCache::new(capacity) }
+    }
+
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.cache.contains_key(hash)
+    }
+
+    /// Marks `hash` as known and most recently used. Returns whether it was already known
+    pub fn touch(&mut self, hash: Hash) -> bool {
+        self.cache.insert(hash, ()).is_some()
+    }
+
+    pub fn remove(&mut self, hash: &Hash) {
+        self.cache.remove(hash);
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+/// Counters of known inventory lookups made before relaying inventory to peers, aggregated over all peers.
+/// A hit means the announcement was skipped since the peer already knew the hash
+#[derive(Debug, Default)]
+pub struct KnownInventoryCounters {
+    block_hits: AtomicU64,
+    block_misses: AtomicU64,
+    transaction_hits: AtomicU64,
+    transaction_misses: AtomicU64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KnownInventoryStats {
+    pub block_hits: u64,
+    pub block_misses: u64,
+    pub transaction_hits: u64,
+    pub transaction_misses: u64,
+}
+
+impl KnownInventoryCounters {
+    pub(crate) fn record_blocks(&self, hits: u64, misses: u64) {
+        self.block_hits.fetch_add(hits, Ordering::Relaxed);
+        self.block_misses.fetch_add(misses, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_transactions(&self, hits: u64, misses: u64) {
+        self.transaction_hits.fetch_add(hits, Ordering::Relaxed);
+        self.transaction_misses.fetch_add(misses, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> KnownInventoryStats {
+        KnownInventoryStats {
+            block_hits: self.block_hits.load(Ordering::Relaxed),
+            block_misses: self.block_misses.load(Ordering::Relaxed),
+            transaction_hits: self.transaction_hits.load(Ordering::Relaxed),
+            transaction_misses: self.transaction_misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_inventory_lru() {
+        let mut known = KnownInventory::new(3);
+        assert!(!known.touch(1.into()));
+        assert!(known.touch(1.into()));
+        assert!(!known.touch(2.into()));
+        assert!(!known.touch(3.into()));
+
+        // 1 was used more recently than 2, hence 2 is evicted when reaching capacity
+        known.touch(1.into());
+        assert!(!known.touch(4.into()));
+        assert_eq!(known.len(), 3);
+        assert!(!known.contains(&2.into()));
+        assert!(known.contains(&1.into()));
+
+        // A removed hash is unknown again and does not cause another hash to be evicted early
+        known.remove(&3.into());
+        assert!(!known.contains(&3.into()));
+        assert!(!known.touch(5.into()));
+        assert!(known.contains(&1.into()) && known.contains(&4.into()) && known.contains(&5.into()));
+
+        // Touching a known hash again does not grow the set
+        for _ in 0..10 {
+            assert!(known.touch(5.into()));
+        }
+        assert_eq!(known.len(), 3);
+
+        let mut disabled = KnownInventory::new(0);
+        assert!(!disabled.touch(1.into()));
+        assert!(disabled.is_empty());
+    }
+}
@@ -1,6 +1,15 @@
 pub mod adaptor;
 pub mod connection_handler;
+pub mod counters;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
 pub mod hub;
+pub mod identity;
+pub mod inventory;
 pub mod payload_type;
 pub mod peer;
+pub mod proxy;
 pub mod router;
+pub mod throttle;
+pub mod timeouts;
+pub mod trace;
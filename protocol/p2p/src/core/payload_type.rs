@@ -48,6 +48,58 @@ pub enum KaspadMessagePayloadType {
     RequestNextPruningPointAndItsAnticoneBlocks,
 }
 
+impl KaspadMessagePayloadType {
+    /// The number of distinct payload types
+    pub const COUNT: usize = KaspadMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks as usize + 1;
+
+    /// All payload types, ordered by their discriminant (i.e., `ALL[t as usize] == t`)
+    pub const ALL: [KaspadMessagePayloadType; Self::COUNT] = [
+        KaspadMessagePayloadType::Addresses,
+        KaspadMessagePayloadType::Block,
+        KaspadMessagePayloadType::Transaction,
+        KaspadMessagePayloadType::BlockLocator,
+        KaspadMessagePayloadType::RequestAddresses,
+        KaspadMessagePayloadType::RequestRelayBlocks,
+        KaspadMessagePayloadType::RequestTransactions,
+        KaspadMessagePayloadType::IbdBlock,
+        KaspadMessagePayloadType::InvRelayBlock,
+        KaspadMessagePayloadType::InvTransactions,
+        KaspadMessagePayloadType::Ping,
+        KaspadMessagePayloadType::Pong,
+        KaspadMessagePayloadType::Verack,
+        KaspadMessagePayloadType::Version,
+        KaspadMessagePayloadType::TransactionNotFound,
+        KaspadMessagePayloadType::Reject,
+        KaspadMessagePayloadType::PruningPointUtxoSetChunk,
+        KaspadMessagePayloadType::RequestIbdBlocks,
+        KaspadMessagePayloadType::UnexpectedPruningPoint,
+        KaspadMessagePayloadType::IbdBlockLocator,
+        KaspadMessagePayloadType::IbdBlockLocatorHighestHash,
+        KaspadMessagePayloadType::RequestNextPruningPointUtxoSetChunk,
+        KaspadMessagePayloadType::DonePruningPointUtxoSetChunks,
+        KaspadMessagePayloadType::IbdBlockLocatorHighestHashNotFound,
+        KaspadMessagePayloadType::BlockWithTrustedData,
+        KaspadMessagePayloadType::DoneBlocksWithTrustedData,
+        KaspadMessagePayloadType::RequestPruningPointAndItsAnticone,
+        KaspadMessagePayloadType::BlockHeaders,
+        KaspadMessagePayloadType::RequestNextHeaders,
+        KaspadMessagePayloadType::DoneHeaders,
+        KaspadMessagePayloadType::RequestPruningPointUtxoSet,
+        KaspadMessagePayloadType::RequestHeaders,
+        KaspadMessagePayloadType::RequestBlockLocator,
+        KaspadMessagePayloadType::PruningPoints,
+        KaspadMessagePayloadType::RequestPruningPointProof,
+        KaspadMessagePayloadType::PruningPointProof,
+        KaspadMessagePayloadType::Ready,
+        KaspadMessagePayloadType::BlockWithTrustedDataV4,
+        KaspadMessagePayloadType::TrustedData,
+        KaspadMessagePayloadType::RequestIbdChainBlockLocator,
+        KaspadMessagePayloadType::IbdChainBlockLocator,
+        KaspadMessagePayloadType::RequestAntipast,
+        KaspadMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks,
+    ];
+}
+
 impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
     fn from(payload: &KaspadMessagePayload) -> Self {
         match payload {
@@ -103,3 +155,15 @@ impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_payload_types_ordered() {
+        for (i, msg_type) in KaspadMessagePayloadType::ALL.into_iter().enumerate() {
+            assert_eq!(msg_type as usize, i);
+        }
+    }
+}
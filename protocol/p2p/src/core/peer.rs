@@ -1,3 +1,4 @@
+use crate::core::identity::PeerIdentityKey;
 use kaspa_consensus_core::subnets::SubnetworkId;
 use kaspa_utils::networking::{IpAddress, PeerId};
 use std::{fmt::Display, net::SocketAddr, sync::Arc, time::Instant};
@@ -11,6 +12,22 @@ pub struct PeerProperties {
     pub disable_relay_tx: bool,
     pub subnetwork_id: Option<SubnetworkId>,
     pub time_offset: i64,
+    /// The identity key proven by the peer during the handshake, if any
+    pub identity: Option<PeerIdentityKey>,
+}
+
+impl PeerProperties {
+    /// Returns whether transactions of the given subnetwork should be relayed to the peer. Peers which disabled transaction
+    /// relay receive none, and peers following a specific subnetwork receive only native transactions and those of it
+    pub fn relays_subnetwork(&self, subnetwork_id: &SubnetworkId) -> bool {
+        if self.disable_relay_tx {
+            return false;
+        }
+        match &self.subnetwork_id {
+            Some(peer_subnetwork_id) => subnetwork_id.is_native() || subnetwork_id == peer_subnetwork_id,
+            None => true,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -77,6 +94,10 @@ impl PeerKey {
     pub fn new(identity: PeerId, ip: IpAddress) -> Self {
         Self { identity, ip }
     }
+
+    pub fn identity(&self) -> PeerId {
+        self.identity
+    }
 }
 
 impl From<&Peer> for PeerKey {
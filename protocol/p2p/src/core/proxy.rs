@@ -0,0 +1,276 @@
+//!
+//! SOCKS5 proxy support for outbound P2P connections (RFC 1928, with username/password authentication of RFC 1929).
+//!
+//! Connections are requested from the proxy by IP when the destination is an IP address and by domain name otherwise,
+//! so host names are always resolved by the proxy and never by the local node.
+//!
+
+use hyper_util::rt::TokioIo;
+use socket2::{SockRef, TcpKeepalive};
+use std::{
+    fmt::Display,
+    future::Future,
+    io::{Error, ErrorKind, Result},
+    net::IpAddr,
+    pin::Pin,
+    str::FromStr,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use tonic::{codegen::Service, transport::Uri};
+
+const SOCKS_VERSION: u8 = 5;
+const AUTH_NONE: u8 = 0;
+const AUTH_USERNAME_PASSWORD: u8 = 2;
+const AUTH_NO_ACCEPTABLE_METHOD: u8 = 0xff;
+const USERNAME_PASSWORD_VERSION: u8 = 1;
+const COMMAND_CONNECT: u8 = 1;
+const ADDRESS_IPV4: u8 = 1;
+const ADDRESS_DOMAIN: u8 = 3;
+const ADDRESS_IPV6: u8 = 4;
+
+#[derive(Clone, PartialEq, Eq)]
+struct Credentials {
+    username: String,
+    password: String,
+}
+
+/// A SOCKS5 proxy, parsed from `[USER:PASS@]HOST:PORT`
+#[derive(Clone, PartialEq, Eq)]
+pub struct Socks5Proxy {
+    address: String,
+    credentials: Option<Credentials>,
+}
+
+impl Socks5Proxy {
+    pub fn new(address: String) -> Self {
+        Self { address, credentials: None }
+    }
+
+    pub fn with_credentials(self, username: String, password: String) -> Self {
+        Self { credentials: Some(Credentials { username, password }), ..self }
+    }
+
+    /// Opens a connection to `host:port` through the proxy
+    pub async fn connect(&self, host: &str, port: u16) -> Result<TcpStream> {
+        let mut stream = TcpStream::connect(&self.address).await?;
+        self.negotiate(&mut stream, host, port).await?;
+        Ok(stream)
+    }
+
+    async fn negotiate(&self, stream: &mut TcpStream, host: &str, port: u16) -> Result<()> {
+        // Method selection
+        let method = if self.credentials.is_some() { AUTH_USERNAME_PASSWORD } else { AUTH_NONE };
+        stream.write_all(&[SOCKS_VERSION, 1, method]).await?;
+        let mut reply = [0u8; 2];
+        stream.read_exact(&mut reply).await?;
+        if reply[0] != SOCKS_VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, "the proxy is not a SOCKS5 proxy"));
+        }
+        match (reply[1], self.credentials.as_ref()) {
+            (AUTH_NONE, _) => {}
+            (AUTH_USERNAME_PASSWORD, Some(credentials)) => Self::authenticate(stream, credentials).await?,
+            (AUTH_NO_ACCEPTABLE_METHOD, _) => {
+                return Err(Error::new(ErrorKind::PermissionDenied, "the proxy rejected the authentication method"))
+            }
+            (method, _) => {
+                return Err(Error::new(ErrorKind::InvalidData, format!("the proxy selected an unexpected method {method}")))
+            }
+        }
+
+        // Connect request
+        let mut request = vec![SOCKS_VERSION, COMMAND_CONNECT, 0];
+        match host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+            Ok(IpAddr::V4(ip)) => {
+                request.push(ADDRESS_IPV4);
+                request.extend_from_slice(&ip.octets());
+            }
+            Ok(IpAddr::V6(ip)) => {
+                request.push(ADDRESS_IPV6);
+                request.extend_from_slice(&ip.octets());
+            }
+            Err(_) => {
+                let length = u8::try_from(host.len()).map_err(|_| Error::new(ErrorKind::InvalidInput, "the host name is too long"))?;
+                request.extend_from_slice(&[ADDRESS_DOMAIN, length]);
+                request.extend_from_slice(host.as_bytes());
+            }
+        }
+        request.extend_from_slice(&port.to_be_bytes());
+        stream.write_all(&request).await?;
+
+        // Connect reply, whose bound address is of no use
+        let mut reply = [0u8; 4];
+        stream.read_exact(&mut reply).await?;
+        if reply[1] != 0 {
+            return Err(Error::new(ErrorKind::ConnectionRefused, format!("the proxy failed connecting: {}", reply_message(reply[1]))));
+        }
+        let bound_address_length = match reply[3] {
+            ADDRESS_IPV4 => 4,
+            ADDRESS_IPV6 => 16,
+            ADDRESS_DOMAIN => stream.read_u8().await? as usize,
+            address_type => {
+                return Err(Error::new(ErrorKind::InvalidData, format!("the proxy replied an unknown address type {address_type}")))
+            }
+        };
+        let mut bound_address = vec![0u8; bound_address_length + 2];
+        stream.read_exact(&mut bound_address).await?;
+        Ok(())
+    }
+
+    async fn authenticate(stream: &mut TcpStream, credentials: &Credentials) -> Result<()> {
+        let too_long = |_| Error::new(ErrorKind::InvalidInput, "the proxy credentials are too long");
+        let mut request = vec![USERNAME_PASSWORD_VERSION, u8::try_from(credentials.username.len()).map_err(too_long)?];
+        request.extend_from_slice(credentials.username.as_bytes());
+        request.push(u8::try_from(credentials.password.len()).map_err(too_long)?);
+        request.extend_from_slice(credentials.password.as_bytes());
+        stream.write_all(&request).await?;
+        let mut reply = [0u8; 2];
+        stream.read_exact(&mut reply).await?;
+        if reply[1] != 0 {
+            return Err(Error::new(ErrorKind::PermissionDenied, "the proxy rejected the credentials"));
+        }
+        Ok(())
+    }
+
+    /// A connector of gRPC channels through the proxy
+    pub(crate) fn connector(&self, keep_alive: Duration) -> Socks5Connector {
+        Socks5Connector { proxy: self.clone(), keep_alive }
+    }
+}
+
+fn reply_message(code: u8) -> &'static str {
+    match code {
+        1 => "general failure",
+        2 => "connection not allowed by ruleset",
+        3 => "network unreachable",
+        4 => "host unreachable",
+        5 => "connection refused",
+        6 => "TTL expired",
+        7 => "command not supported",
+        8 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+impl FromStr for Socks5Proxy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (credentials, address) = match s.rsplit_once('@') {
+            Some((credentials, address)) => (Some(credentials), address),
+            None => (None, s),
+        };
+        let Some((host, port)) = address.rsplit_once(':') else {
+            return Err(format!("proxy address {address} has no port"));
+        };
+        if host.is_empty() || port.parse::<u16>().is_err() {
+            return Err(format!("invalid proxy address {address}"));
+        }
+        let proxy = Self::new(address.to_owned());
+        match credentials.map(|credentials| credentials.split_once(':')) {
+            None => Ok(proxy),
+            Some(Some((username, password))) if username.len() <= 255 && password.len() <= 255 => {
+                Ok(proxy.with_credentials(username.to_owned(), password.to_owned()))
+            }
+            Some(_) => Err("proxy credentials must be given as USER:PASS, each of at most 255 bytes".to_owned()),
+        }
+    }
+}
+
+impl Display for Socks5Proxy {
+    /// Displays the proxy address, omitting the credentials
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.address)
+    }
+}
+
+impl std::fmt::Debug for Socks5Proxy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Socks5Proxy").field("address", &self.address).field("authenticated", &self.credentials.is_some()).finish()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Socks5Connector {
+    proxy: Socks5Proxy,
+    keep_alive: Duration,
+}
+
+impl Service<Uri> for Socks5Connector {
+    type Response = TokioIo<TcpStream>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let proxy = self.proxy.clone();
+        let keep_alive = self.keep_alive;
+        Box::pin(async move {
+            let (Some(host), Some(port)) = (uri.host(), uri.port_u16()) else {
+                return Err(Error::new(ErrorKind::InvalidInput, format!("peer address {uri} has no host or port")));
+            };
+            let stream = proxy.connect(host, port).await?;
+            stream.set_nodelay(true)?;
+            SockRef::from(&stream).set_tcp_keepalive(&TcpKeepalive::new().with_time(keep_alive))?;
+            Ok(TokioIo::new(stream))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_proxy_parsing() {
+        assert_eq!("127.0.0.1:9050".parse(), Ok(Socks5Proxy::new("127.0.0.1:9050".to_owned())));
+        assert_eq!(
+            "user:p@ss@localhost:1080".parse(),
+            Ok(Socks5Proxy::new("localhost:1080".to_owned()).with_credentials("user".to_owned(), "p@ss".to_owned()))
+        );
+        assert!("127.0.0.1".parse::<Socks5Proxy>().is_err());
+        assert!("127.0.0.1:port".parse::<Socks5Proxy>().is_err());
+        assert!("user@127.0.0.1:9050".parse::<Socks5Proxy>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_proxy_negotiation() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy =
+            Socks5Proxy::new(listener.local_addr().unwrap().to_string()).with_credentials("user".to_owned(), "pass".to_owned());
+
+        // A minimal proxy expecting a connect request to a domain
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).await.unwrap();
+            assert_eq!(greeting, [SOCKS_VERSION, 1, AUTH_USERNAME_PASSWORD]);
+            stream.write_all(&[SOCKS_VERSION, AUTH_USERNAME_PASSWORD]).await.unwrap();
+            let mut auth = [0u8; 11];
+            stream.read_exact(&mut auth).await.unwrap();
+            assert_eq!(&auth, b"\x01\x04user\x04pass");
+            stream.write_all(&[USERNAME_PASSWORD_VERSION, 0]).await.unwrap();
+            let mut request = vec![0u8; 5 + "seeder.kaspa.org".len() + 2];
+            stream.read_exact(&mut request).await.unwrap();
+            assert_eq!(request[..5], [SOCKS_VERSION, COMMAND_CONNECT, 0, ADDRESS_DOMAIN, 16]);
+            assert_eq!(&request[5..21], b"seeder.kaspa.org");
+            assert_eq!(request[21..], 16111u16.to_be_bytes());
+            stream.write_all(&[SOCKS_VERSION, 0, 0, ADDRESS_IPV4, 10, 0, 0, 1, 0x3e, 0xef]).await.unwrap();
+            stream.write_all(b"ping").await.unwrap();
+        });
+
+        let mut stream = proxy.connect("seeder.kaspa.org", 16111).await.unwrap();
+        let mut payload = [0u8; 4];
+        stream.read_exact(&mut payload).await.unwrap();
+        assert_eq!(&payload, b"ping");
+        server.await.unwrap();
+    }
+}
@@ -4,6 +4,8 @@ use crate::pb::{kaspad_message::Payload as KaspadMessagePayload, KaspadMessage};
 use crate::{common::ProtocolError, KaspadMessagePayloadType};
 use crate::{make_message, Peer};
 use kaspa_core::{debug, error, info, trace, warn};
+use kaspa_hashes::Hash;
+use kaspa_utils::alloc_tracking::{AllocSubsystem, InSubsystemExt};
 use kaspa_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
 use seqlock::SeqLock;
@@ -19,7 +21,11 @@ use tokio::sync::mpsc::{channel as mpsc_channel, Receiver as MpscReceiver, Sende
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
 use tonic::Streaming;
 
+use super::counters::{P2pMessageCounters, PeerSessionCounters, PeerSessionStats, SubmissionOutcome};
+use super::inventory::{KnownInventory, KnownInventoryCounters, KnownInventorySizes};
 use super::peer::{PeerKey, PeerProperties};
+use super::timeouts::{P2pTimeoutCounters, TimeoutKind};
+use super::trace::MessageTraceRecorder;
 
 pub struct IncomingRoute {
     rx: MpscReceiver<KaspadMessage>,
@@ -89,6 +95,22 @@ impl From<KaspadMessagePayloadType> for IncomingRouteOverflowPolicy {
     }
 }
 
+/// A snapshot of the incoming route of a single flow registered on a router
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowState {
+    /// The id of the incoming route
+    pub route_id: u32,
+
+    /// The message types routed to the flow, ordered by discriminant
+    pub message_types: Vec<KaspadMessagePayloadType>,
+
+    /// Number of messages waiting in the route for the flow to process them
+    pub queued: usize,
+
+    /// Capacity of the route. A full route either drops or disconnects, see [`IncomingRouteOverflowPolicy`]
+    pub capacity: usize,
+}
+
 #[derive(Debug, Default)]
 struct RouterMutableState {
     /// Used on router init to signal the router receive loop to start listening
@@ -139,6 +161,24 @@ pub struct Router {
 
     /// Used for managing router mutable state
     mutable_state: Mutex<RouterMutableState>,
+
+    /// Message counters shared by all routers
+    message_counters: Arc<P2pMessageCounters>,
+
+    /// Expired timeout counters shared by all routers
+    timeout_counters: Arc<P2pTimeoutCounters>,
+
+    /// Traffic and quality counters of this specific connection
+    session_counters: PeerSessionCounters,
+
+    /// Block hashes this peer announced or was sent, consulted before relaying blocks to it
+    known_blocks: Mutex<KnownInventory>,
+
+    /// Transaction IDs this peer announced or was sent, consulted before relaying transactions to it
+    known_transactions: Mutex<KnownInventory>,
+
+    /// Known inventory hit/miss counters shared by all routers
+    inventory_counters: Arc<KnownInventoryCounters>,
 }
 
 impl Display for Router {
@@ -179,6 +219,11 @@ impl Router {
         hub_sender: MpscSender<HubEvent>,
         mut incoming_stream: Streaming<KaspadMessage>,
         outgoing_route: MpscSender<KaspadMessage>,
+        trace_recorder: Option<Arc<MessageTraceRecorder>>,
+        message_counters: Arc<P2pMessageCounters>,
+        timeout_counters: Arc<P2pTimeoutCounters>,
+        known_inventory_sizes: KnownInventorySizes,
+        inventory_counters: Arc<KnownInventoryCounters>,
     ) -> Arc<Self> {
         let (start_sender, start_receiver) = oneshot_channel();
         let (shutdown_sender, mut shutdown_receiver) = oneshot_channel();
@@ -193,6 +238,12 @@ impl Router {
             outgoing_route,
             hub_sender,
             mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
+            message_counters,
+            timeout_counters,
+            session_counters: Default::default(),
+            known_blocks: Mutex::new(KnownInventory::new(known_inventory_sizes.blocks)),
+            known_transactions: Mutex::new(KnownInventory::new(known_inventory_sizes.transactions)),
+            inventory_counters,
         });
 
         let router_clone = router.clone();
@@ -209,12 +260,28 @@ impl Router {
                         break;
                     }
 
-                    res = incoming_stream.message() => match res {
+                    // Messages are decoded while polling the stream, so their buffers are attributed to p2p
+                    res = incoming_stream.message().in_subsystem(AllocSubsystem::P2p) => match res {
                         Ok(Some(msg)) => {
                             trace!("P2P msg: {:?}, router-id: {}, peer: {}", message_summary(&msg), router.identity(), router);
+                            router.message_counters.record_incoming(&msg);
+                            router.session_counters.record_incoming(&msg);
+                            if let Some(recorder) = trace_recorder.as_ref() {
+                                recorder.record(router.net_address, &msg);
+                            }
+                            #[cfg(feature = "fault-injection")]
+                            match super::fault_injection::sample(&msg) {
+                                Some(super::fault_injection::InjectedFault::Drop) => {
+                                    trace!("P2P, fault injection dropped msg: {:?}, peer: {}", message_summary(&msg), router);
+                                    continue;
+                                }
+                                Some(super::fault_injection::InjectedFault::Delay(delay)) => tokio::time::sleep(delay).await,
+                                None => {}
+                            }
                             match router.route_to_flow(msg) {
                                 Ok(()) => {},
                                 Err(e) => {
+                                    router.record_misbehavior(&e);
                                     match e {
                                         ProtocolError::IgnorableReject(reason) => debug!("P2P, got reject message: {} from peer: {}", reason, router),
                                         ProtocolError::Rejected(reason) => warn!("P2P, got reject message: {} from peer: {}", reason, router),
@@ -288,6 +355,7 @@ impl Router {
     /// Sets the duration of the last ping
     pub fn set_last_ping_duration(&self, last_ping_duration: u64) {
         self.mutable_state.lock().last_ping_duration = last_ping_duration;
+        self.session_counters.record_latency(last_ping_duration);
     }
 
     pub fn last_ping_duration(&self) -> u64 {
@@ -361,6 +429,29 @@ impl Router {
         incoming_route
     }
 
+    /// Returns the state of the incoming routes of all flows currently registered on this router, ordered by route id
+    pub fn flow_states(&self) -> Vec<FlowState> {
+        let map_by_type = self.routing_map_by_type.read();
+        let mut states = self
+            .routing_map_by_id
+            .read()
+            .iter()
+            .map(|(&route_id, sender)| {
+                let mut message_types =
+                    map_by_type.iter().filter(|(_, s)| s.same_channel(sender)).map(|(&msg_type, _)| msg_type).collect::<Vec<_>>();
+                message_types.sort_by_key(|&msg_type| msg_type as u8);
+                FlowState {
+                    route_id,
+                    message_types,
+                    queued: sender.max_capacity() - sender.capacity(),
+                    capacity: sender.max_capacity(),
+                }
+            })
+            .collect::<Vec<_>>();
+        states.sort_by_key(|state| state.route_id);
+        states
+    }
+
     /// Routes a message coming from the network to the corresponding registered flow
     pub fn route_to_flow(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
         if msg.payload.is_none() {
@@ -402,6 +493,10 @@ impl Router {
     /// Enqueues a locally-originated message to be sent to the network peer
     pub async fn enqueue(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
         assert!(msg.payload.is_some(), "Kaspad P2P message should always have a value");
+        // Counted before sending since the message is moved into the route. A failure to send closes the connection
+        // anyway so the slight over-count is harmless
+        self.message_counters.record_outgoing(&msg);
+        self.session_counters.record_outgoing(&msg);
         match self.outgoing_route.try_send(msg) {
             Ok(_) => Ok(()),
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
@@ -409,6 +504,88 @@ impl Router {
         }
     }
 
+    /// If `err` is an expired protocol stage timeout, counts it in the shared timeout counters and returns its kind
+    pub fn record_timeout(&self, err: &ProtocolError) -> Option<TimeoutKind> {
+        let kind = err.timeout_kind()?;
+        self.timeout_counters.record(kind);
+        Some(kind)
+    }
+
+    /// Charges the misbehavior score of `err` to this peer
+    pub fn record_misbehavior(&self, err: &ProtocolError) {
+        let score = err.ban_score();
+        if score > 0 {
+            self.session_counters.add_ban_score(score);
+        }
+    }
+
+    /// Attributes the processing outcome of a full block submitted by this peer
+    pub fn record_block_outcome(&self, outcome: SubmissionOutcome) {
+        self.session_counters.record_block(outcome);
+    }
+
+    /// Attributes the processing outcome of a header submitted by this peer
+    pub fn record_header_outcome(&self, outcome: SubmissionOutcome) {
+        self.session_counters.record_header(outcome);
+    }
+
+    /// Traffic and quality figures of this connection so far
+    pub fn session_stats(&self) -> PeerSessionStats {
+        self.session_counters.snapshot()
+    }
+
+    /// Records that this peer knows the given blocks, i.e. since it announced them to us
+    pub fn mark_known_blocks<I: IntoIterator<Item = Hash>>(&self, hashes: I) {
+        let mut known = self.known_blocks.lock();
+        hashes.into_iter().for_each(|hash| {
+            known.touch(hash);
+        });
+    }
+
+    /// Records that this peer knows the given transactions
+    pub fn mark_known_transactions<I: IntoIterator<Item = Hash>>(&self, ids: I) {
+        let mut known = self.known_transactions.lock();
+        ids.into_iter().for_each(|id| {
+            known.touch(id);
+        });
+    }
+
+    /// Forgets that this peer knows the given transactions so that they are relayed to it again
+    pub fn forget_known_transactions<'a, I: IntoIterator<Item = &'a Hash>>(&self, ids: I) {
+        let mut known = self.known_transactions.lock();
+        ids.into_iter().for_each(|id| known.remove(id));
+    }
+
+    /// Returns the blocks out of `hashes` which are unknown to this peer and hence should be announced to it, and
+    /// marks them as known
+    pub fn filter_unknown_blocks<I: IntoIterator<Item = Hash>>(&self, hashes: I) -> Vec<Hash> {
+        let unknown = Self::filter_unknown(&mut self.known_blocks.lock(), hashes);
+        self.inventory_counters.record_blocks(unknown.1, unknown.0.len() as u64);
+        unknown.0
+    }
+
+    /// Returns the transactions out of `ids` which are unknown to this peer and hence should be announced to it,
+    /// and marks them as known
+    pub fn filter_unknown_transactions<I: IntoIterator<Item = Hash>>(&self, ids: I) -> Vec<Hash> {
+        let unknown = Self::filter_unknown(&mut self.known_transactions.lock(), ids);
+        self.inventory_counters.record_transactions(unknown.1, unknown.0.len() as u64);
+        unknown.0
+    }
+
+    /// Returns the unknown hashes along with the number of known ones
+    fn filter_unknown<I: IntoIterator<Item = Hash>>(known: &mut KnownInventory, hashes: I) -> (Vec<Hash>, u64) {
+        let mut hits = 0;
+        let unknown = hashes
+            .into_iter()
+            .filter(|&hash| {
+                let was_known = known.touch(hash);
+                hits += was_known as u64;
+                !was_known
+            })
+            .collect();
+        (unknown, hits)
+    }
+
     /// Based on the type of the protocol error, tries sending a reject message before shutting down the connection
     pub async fn try_sending_reject_message(&self, err: &ProtocolError) {
         if err.can_send_outgoing_message() {
@@ -0,0 +1,154 @@
+use crate::{pb::KaspadMessage, KaspadMessagePayloadType};
+use futures::Stream;
+use parking_lot::Mutex;
+use prost::Message;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc::Receiver as MpscReceiver;
+
+/// The fraction of the global bucket capacity which bulk (IBD serving) traffic must leave
+/// untouched, so that block and transaction relay can still go through when the link is saturated
+const BULK_RESERVE_RATIO: f64 = 0.25;
+
+/// Upload rate limits in bytes per second. A `None` value means unlimited
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UploadLimits {
+    /// Limit over the overall upload rate summed across all peers
+    pub global: Option<u64>,
+    /// Limit over the upload rate to each single peer
+    pub per_peer: Option<u64>,
+}
+
+impl UploadLimits {
+    /// Converts megabits per second to bytes per second
+    pub fn mbps_to_bytes_per_sec(mbps: f64) -> u64 {
+        (mbps * 1_000_000.0 / 8.0) as u64
+    }
+
+    pub fn with_global_mbps(self, mbps: f64) -> Self {
+        Self { global: Some(Self::mbps_to_bytes_per_sec(mbps)), ..self }
+    }
+
+    pub fn with_per_peer_mbps(self, mbps: f64) -> Self {
+        Self { per_peer: Some(Self::mbps_to_bytes_per_sec(mbps)), ..self }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.global.is_none() && self.per_peer.is_none()
+    }
+}
+
+/// A token bucket allowing bursts of up to one second worth of traffic. Tokens are consumed eagerly
+/// and the bucket may go into debt, in which case the caller is asked to delay accordingly
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    /// Refill rate in bytes per second
+    rate: f64,
+    capacity: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    pub fn new(bytes_per_sec: u64) -> Self {
+        let rate = bytes_per_sec.max(1) as f64;
+        Self { rate, capacity: rate, state: Mutex::new((rate, Instant::now())) }
+    }
+
+    /// Consumes `bytes` tokens and returns the delay after which sending them respects the rate. The tokens
+    /// are only considered available if `reserve_ratio` of the capacity is left in the bucket afterwards
+    pub fn consume(&self, bytes: usize, reserve_ratio: f64) -> Duration {
+        let mut state = self.state.lock();
+        let (tokens, last) = &mut *state;
+        let now = Instant::now();
+        *tokens = (*tokens + now.duration_since(*last).as_secs_f64() * self.rate).min(self.capacity);
+        *last = now;
+
+        let required = bytes as f64 + reserve_ratio * self.capacity;
+        let delay = if *tokens >= required { Duration::ZERO } else { Duration::from_secs_f64((required - *tokens) / self.rate) };
+        *tokens -= bytes as f64;
+        delay
+    }
+}
+
+/// Upload throttle shared by all routers of a hub
+#[derive(Debug, Clone, Default)]
+pub struct UploadThrottle {
+    global: Option<Arc<TokenBucket>>,
+    per_peer: Option<u64>,
+}
+
+impl UploadThrottle {
+    pub fn new(limits: UploadLimits) -> Self {
+        Self { global: limits.global.map(|rate| Arc::new(TokenBucket::new(rate))), per_peer: limits.per_peer }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.global.is_none() && self.per_peer.is_none()
+    }
+
+    /// Wraps the outgoing route receiver of a single peer with a stream emitting
+    /// messages no faster than the global and per-peer rate limits allow
+    pub(crate) fn throttle(&self, receiver: MpscReceiver<KaspadMessage>) -> impl Stream<Item = KaspadMessage> + Send + 'static {
+        let global = self.global.clone();
+        let peer = self.per_peer.map(TokenBucket::new);
+        futures::stream::unfold((receiver, global, peer), |(mut receiver, global, peer)| async move {
+            let msg = receiver.recv().await?;
+            let len = msg.encoded_len();
+            let mut delay = peer.as_ref().map_or(Duration::ZERO, |peer| peer.consume(len, 0.0));
+            if let Some(global) = global.as_ref() {
+                let reserve_ratio = if is_bulk(&msg) { BULK_RESERVE_RATIO } else { 0.0 };
+                delay = delay.max(global.consume(len, reserve_ratio));
+            }
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            Some((msg, (receiver, global, peer)))
+        })
+    }
+}
+
+/// Indicates whether the message is part of bulk data served to a syncing peer (as opposed to
+/// relay or control messages), in which case it is deprioritized when upload is saturated
+fn is_bulk(msg: &KaspadMessage) -> bool {
+    let Some(payload) = msg.payload.as_ref() else { return false };
+    matches!(
+        KaspadMessagePayloadType::from(payload),
+        KaspadMessagePayloadType::IbdBlock
+            | KaspadMessagePayloadType::BlockHeaders
+            | KaspadMessagePayloadType::BlockWithTrustedData
+            | KaspadMessagePayloadType::BlockWithTrustedDataV4
+            | KaspadMessagePayloadType::TrustedData
+            | KaspadMessagePayloadType::PruningPointUtxoSetChunk
+            | KaspadMessagePayloadType::PruningPointProof
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_delays() {
+        let bucket = TokenBucket::new(1000);
+
+        // The initial burst is served immediately
+        assert_eq!(bucket.consume(600, 0.0), Duration::ZERO);
+
+        // Bulk traffic must leave the reserve intact: 400 left, 300 + 250 required
+        let delay = bucket.consume(300, BULK_RESERVE_RATIO);
+        assert!(delay > Duration::from_millis(140) && delay <= Duration::from_millis(150), "{delay:?}");
+
+        // The bucket is now in debt (100 tokens left), so even priority traffic has to wait
+        let delay = bucket.consume(500, 0.0);
+        assert!(delay > Duration::from_millis(390) && delay <= Duration::from_millis(400), "{delay:?}");
+    }
+
+    #[test]
+    fn test_upload_limits_conversion() {
+        let limits = UploadLimits::default().with_global_mbps(8.0).with_per_peer_mbps(0.8);
+        assert_eq!(limits, UploadLimits { global: Some(1_000_000), per_peer: Some(100_000) });
+        assert!(UploadThrottle::new(UploadLimits::default()).is_unlimited());
+    }
+}
@@ -0,0 +1,155 @@
+use crate::common::DEFAULT_TIMEOUT;
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// The stage of the protocol at which a peer failed to respond in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeoutKind {
+    /// Waiting for the peer's version message
+    HandshakeVersion,
+    /// Waiting for the peer to acknowledge our version message
+    HandshakeVerack,
+    /// Waiting for the peer's ready message
+    HandshakeReady,
+    /// Waiting for a headers chunk during IBD (headers request round-trip)
+    Headers,
+    /// Waiting for a requested block during IBD
+    IbdBlocks,
+    /// Waiting for the pruning point proof during IBD
+    PruningPointProof,
+    /// Waiting for any other flow response
+    Response,
+}
+
+impl TimeoutKind {
+    pub const COUNT: usize = 7;
+
+    pub const ALL: [TimeoutKind; Self::COUNT] = [
+        TimeoutKind::HandshakeVersion,
+        TimeoutKind::HandshakeVerack,
+        TimeoutKind::HandshakeReady,
+        TimeoutKind::Headers,
+        TimeoutKind::IbdBlocks,
+        TimeoutKind::PruningPointProof,
+        TimeoutKind::Response,
+    ];
+}
+
+impl Display for TimeoutKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TimeoutKind::HandshakeVersion => "handshake version",
+            TimeoutKind::HandshakeVerack => "handshake verack",
+            TimeoutKind::HandshakeReady => "handshake ready",
+            TimeoutKind::Headers => "headers response",
+            TimeoutKind::IbdBlocks => "IBD blocks response",
+            TimeoutKind::PruningPointProof => "pruning point proof",
+            TimeoutKind::Response => "response",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Configurable P2P timeouts, one per [`TimeoutKind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct P2pTimeouts {
+    pub handshake_version: Duration,
+    pub handshake_verack: Duration,
+    pub handshake_ready: Duration,
+    pub headers: Duration,
+    pub ibd_blocks: Duration,
+    /// Pruning proof generation and communication might take several minutes, hence this one is much longer by default
+    pub pruning_point_proof: Duration,
+    pub response: Duration,
+}
+
+impl Default for P2pTimeouts {
+    fn default() -> Self {
+        Self {
+            handshake_version: Duration::from_secs(4),
+            handshake_verack: Duration::from_secs(4),
+            handshake_ready: Duration::from_secs(8),
+            headers: DEFAULT_TIMEOUT,
+            ibd_blocks: DEFAULT_TIMEOUT,
+            pruning_point_proof: Duration::from_secs(600),
+            response: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl P2pTimeouts {
+    pub fn get(&self, kind: TimeoutKind) -> Duration {
+        match kind {
+            TimeoutKind::HandshakeVersion => self.handshake_version,
+            TimeoutKind::HandshakeVerack => self.handshake_verack,
+            TimeoutKind::HandshakeReady => self.handshake_ready,
+            TimeoutKind::Headers => self.headers,
+            TimeoutKind::IbdBlocks => self.ibd_blocks,
+            TimeoutKind::PruningPointProof => self.pruning_point_proof,
+            TimeoutKind::Response => self.response,
+        }
+    }
+
+    /// Sets the version and verack stage timeouts to `timeout` and the ready stage timeout to twice as much
+    pub fn with_handshake_timeout(self, timeout: Duration) -> Self {
+        Self { handshake_version: timeout, handshake_verack: timeout, handshake_ready: timeout * 2, ..self }
+    }
+
+    /// Sets the timeouts of all flow responses except for the pruning point proof to `timeout`
+    pub fn with_response_timeout(self, timeout: Duration) -> Self {
+        Self { headers: timeout, ibd_blocks: timeout, response: timeout, ..self }
+    }
+}
+
+/// Counters of expired timeouts per [`TimeoutKind`], aggregated over all peers
+#[derive(Debug, Default)]
+pub struct P2pTimeoutCounters {
+    counters: [AtomicU64; TimeoutKind::COUNT],
+}
+
+impl P2pTimeoutCounters {
+    pub fn record(&self, kind: TimeoutKind) {
+        self.counters[kind as usize].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn count(&self, kind: TimeoutKind) -> u64 {
+        self.counters[kind as usize].load(Ordering::Relaxed)
+    }
+
+    /// Returns the counts of all timeout kinds which expired at least once
+    pub fn snapshot(&self) -> Vec<(TimeoutKind, u64)> {
+        TimeoutKind::ALL.into_iter().map(|kind| (kind, self.count(kind))).filter(|(_, count)| *count > 0).collect()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counters.iter().map(|counter| counter.load(Ordering::Relaxed)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeouts() {
+        for (i, kind) in TimeoutKind::ALL.into_iter().enumerate() {
+            assert_eq!(kind as usize, i);
+        }
+
+        let timeouts =
+            P2pTimeouts::default().with_handshake_timeout(Duration::from_secs(10)).with_response_timeout(Duration::from_secs(30));
+        assert_eq!(timeouts.get(TimeoutKind::HandshakeReady), Duration::from_secs(20));
+        assert_eq!(timeouts.get(TimeoutKind::Headers), Duration::from_secs(30));
+        assert_eq!(timeouts.get(TimeoutKind::PruningPointProof), Duration::from_secs(600));
+
+        let counters = P2pTimeoutCounters::default();
+        counters.record(TimeoutKind::Headers);
+        counters.record(TimeoutKind::Headers);
+        counters.record(TimeoutKind::HandshakeVersion);
+        assert_eq!(counters.snapshot(), vec![(TimeoutKind::HandshakeVersion, 1), (TimeoutKind::Headers, 2)]);
+        assert_eq!(counters.total(), 3);
+    }
+}
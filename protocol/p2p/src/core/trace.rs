@@ -0,0 +1,259 @@
+//! Recording and replaying of P2P message traces.
+//!
+//! A trace file consists of a short header followed by a sequence of records, each holding the time
+//! (relative to the beginning of the recording) at which a message was received, the address of the
+//! peer which sent it and the protobuf-encoded message itself. Traces are meant for debugging consensus
+//! divergences and for feeding captured real-world traffic into a fresh node.
+
+use crate::core::connection_handler::{ConnectionError, P2P_MAX_MESSAGE_SIZE};
+use crate::pb::{p2p_client::P2pClient as ProtoP2pClient, KaspadMessage};
+use crate::{KaspadMessagePayloadType, BLANK_ROUTE_ID};
+use kaspa_core::{debug, warn};
+use prost::Message;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::channel as mpsc_channel;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt;
+
+const TRACE_MAGIC: &[u8; 8] = b"KASPATRC";
+const TRACE_VERSION: u8 = 1;
+
+/// A single recorded incoming P2P message
+#[derive(Debug, Clone)]
+pub struct TraceRecord {
+    /// Milliseconds elapsed since the recording started
+    pub timestamp: u64,
+    /// The peer from which the message was received
+    pub peer: SocketAddr,
+    pub message: KaspadMessage,
+}
+
+/// The number of records which can be queued for the trace writer before new records are dropped
+const TRACE_QUEUE_CAPACITY: usize = 4096;
+
+/// Appends all incoming P2P messages of a session to a trace file. A single recorder is shared by all peer routers.
+/// Records are queued to a dedicated writer thread, so recording never blocks the routers on file I/O
+pub struct MessageTraceRecorder {
+    sender: Option<SyncSender<TraceRecord>>,
+    writer_thread: Option<JoinHandle<()>>,
+    start: Instant,
+    dropped: AtomicU64,
+}
+
+impl MessageTraceRecorder {
+    /// Creates a new trace file at `path`, truncating any existing file
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(TRACE_MAGIC)?;
+        writer.write_all(&[TRACE_VERSION])?;
+        writer.flush()?;
+        let (sender, receiver) = sync_channel(TRACE_QUEUE_CAPACITY);
+        let writer_thread =
+            thread::Builder::new().name("p2p-trace-writer".to_string()).spawn(move || write_records(writer, receiver))?;
+        Ok(Self { sender: Some(sender), writer_thread: Some(writer_thread), start: Instant::now(), dropped: AtomicU64::new(0) })
+    }
+
+    /// Records a message received from `peer`. Records are dropped if the writer cannot keep up, and write failures
+    /// are logged and otherwise ignored, since recording is a debugging aid which should never affect the node itself
+    pub fn record(&self, peer: SocketAddr, message: &KaspadMessage) {
+        let record = TraceRecord { timestamp: self.start.elapsed().as_millis() as u64, peer, message: message.clone() };
+        if let Some(sender) = self.sender.as_ref() {
+            if sender.try_send(record).is_err() {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl Drop for MessageTraceRecorder {
+    fn drop(&mut self) {
+        // Closing the channel lets the writer drain the queued records and flush the file
+        drop(self.sender.take());
+        if let Some(writer_thread) = self.writer_thread.take() {
+            let _ = writer_thread.join();
+        }
+        let dropped = self.dropped.load(Ordering::Relaxed);
+        if dropped > 0 {
+            warn!("P2P trace, {} messages were not recorded since the trace writer could not keep up", dropped);
+        }
+    }
+}
+
+impl std::fmt::Debug for MessageTraceRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageTraceRecorder").field("start", &self.start).finish()
+    }
+}
+
+fn write_records(mut writer: BufWriter<File>, receiver: Receiver<TraceRecord>) {
+    for record in receiver {
+        if let Err(err) = write_record(&mut writer, &record) {
+            warn!("P2P trace, failed recording a message from peer {}: {}", record.peer, err);
+        }
+    }
+    if let Err(err) = writer.flush() {
+        warn!("P2P trace, failed flushing the trace file: {}", err);
+    }
+}
+
+fn write_record<W: Write>(writer: &mut W, record: &TraceRecord) -> io::Result<()> {
+    let peer = record.peer.to_string();
+    let payload = record.message.encode_to_vec();
+    writer.write_all(&record.timestamp.to_le_bytes())?;
+    writer.write_all(&(peer.len() as u16).to_le_bytes())?;
+    writer.write_all(peer.as_bytes())?;
+    writer.write_all(&(payload.len() as u32).to_le_bytes())?;
+    writer.write_all(&payload)
+}
+
+/// Iterates over the records of a trace file created by [`MessageTraceRecorder`]
+pub struct MessageTraceReader<R: Read> {
+    reader: R,
+}
+
+impl MessageTraceReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> MessageTraceReader<R> {
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; TRACE_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if &magic != TRACE_MAGIC {
+            return Err(io::Error::new(ErrorKind::InvalidData, "not a P2P trace file"));
+        }
+        let mut version = [0u8; 1];
+        reader.read_exact(&mut version)?;
+        if version[0] != TRACE_VERSION {
+            return Err(io::Error::new(ErrorKind::InvalidData, format!("unsupported P2P trace version {}", version[0])));
+        }
+        Ok(Self { reader })
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<TraceRecord>> {
+        let mut timestamp = [0u8; 8];
+        match self.reader.read_exact(&mut timestamp) {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+        let mut len = [0u8; 2];
+        self.reader.read_exact(&mut len)?;
+        let mut peer = vec![0u8; u16::from_le_bytes(len) as usize];
+        self.reader.read_exact(&mut peer)?;
+        let peer = String::from_utf8(peer)
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "invalid peer address"))?;
+        let mut len = [0u8; 4];
+        self.reader.read_exact(&mut len)?;
+        let mut payload = vec![0u8; u32::from_le_bytes(len) as usize];
+        self.reader.read_exact(&mut payload)?;
+        let message = KaspadMessage::decode(payload.as_slice()).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+        Ok(Some(TraceRecord { timestamp: u64::from_le_bytes(timestamp), peer, message }))
+    }
+}
+
+impl<R: Read> Iterator for MessageTraceReader<R> {
+    type Item = io::Result<TraceRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// Replays the given records against the node listening at `target_address`, preserving the original relative
+/// timing of the messages divided by `speedup`. Messages are sent over a raw P2P stream, i.e., the caller is
+/// expected to pass a trace which includes the handshake messages of the recorded session. Returns the number
+/// of messages sent.
+pub async fn replay_trace(
+    records: impl IntoIterator<Item = TraceRecord>,
+    target_address: String,
+    speedup: f64,
+) -> Result<usize, ConnectionError> {
+    let channel = tonic::transport::Endpoint::new(format!("http://{}", target_address))?.connect().await?;
+    let mut client = ProtoP2pClient::new(channel)
+        .send_compressed(tonic::codec::CompressionEncoding::Gzip)
+        .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+        .max_decoding_message_size(P2P_MAX_MESSAGE_SIZE);
+
+    let (outgoing_route, outgoing_receiver) = mpsc_channel(256);
+    let mut incoming_stream = client.message_stream(ReceiverStream::new(outgoing_receiver)).await?.into_inner();
+
+    // Drain the responses of the target node so that it never blocks on a full stream
+    let drain_task = tokio::spawn(async move {
+        while let Some(res) = incoming_stream.next().await {
+            match res {
+                Ok(msg) => debug!("P2P replay, received {:?}", msg.payload.as_ref().map(KaspadMessagePayloadType::from)),
+                Err(err) => {
+                    debug!("P2P replay, incoming stream closed: {}", err);
+                    break;
+                }
+            }
+        }
+    });
+
+    let start = Instant::now();
+    let mut sent = 0;
+    for mut record in records {
+        let due = Duration::from_secs_f64(record.timestamp as f64 / 1000.0 / speedup);
+        if let Some(wait) = due.checked_sub(start.elapsed()) {
+            tokio::time::sleep(wait).await;
+        }
+        // Request/response route ids are session specific so they cannot be reused
+        record.message.response_id = BLANK_ROUTE_ID;
+        record.message.request_id = BLANK_ROUTE_ID;
+        if outgoing_route.send(record.message).await.is_err() {
+            break;
+        }
+        sent += 1;
+    }
+
+    drop(outgoing_route);
+    drain_task.abort();
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::{kaspad_message::Payload, PingMessage};
+
+    #[test]
+    fn test_trace_roundtrip() {
+        let path = std::env::temp_dir().join(format!("kaspa-p2p-trace-{}.bin", std::process::id()));
+        let peers: Vec<SocketAddr> = vec!["127.0.0.1:16111".parse().unwrap(), "[::1]:16112".parse().unwrap()];
+        {
+            let recorder = MessageTraceRecorder::create(&path).unwrap();
+            for nonce in 0..10u64 {
+                let message =
+                    KaspadMessage { payload: Some(Payload::Ping(PingMessage { nonce })), request_id: 0, response_id: nonce as u32 };
+                recorder.record(peers[nonce as usize % 2], &message);
+            }
+        }
+
+        let records = MessageTraceReader::open(&path).unwrap().collect::<io::Result<Vec<_>>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(records.len(), 10);
+        for (nonce, record) in records.iter().enumerate() {
+            assert_eq!(record.peer, peers[nonce % 2]);
+            assert_eq!(record.message.response_id, nonce as u32);
+            assert!(matches!(record.message.payload, Some(Payload::Ping(PingMessage { nonce: n })) if n == nonce as u64));
+        }
+        assert!(records.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+    }
+
+    #[test]
+    fn test_trace_rejects_foreign_files() {
+        assert!(MessageTraceReader::new(&b"NOTATRACE"[..]).is_err());
+    }
+}
@@ -101,6 +101,8 @@ fn build_dummy_version_message() -> VersionMessage {
         disable_relay_tx: false,
         subnetwork_id: None,
         network: "kaspa-mainnet".to_string(),
+        identity_public_key: vec![],
+        identity_nonce: vec![],
     }
 }
 
@@ -1,5 +1,7 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
+use crate::core::identity::{self, IdentityProofContext, NodeIdentity, PeerIdentityKey, SERVICE_NODE_IDENTITY};
+use crate::core::timeouts::{P2pTimeouts, TimeoutKind};
 use crate::pb::{kaspad_message::Payload, ReadyMessage, VerackMessage, VersionMessage};
 use crate::{common::ProtocolError, dequeue_with_timeout, make_message};
 use crate::{IncomingRoute, KaspadMessagePayloadType, Router};
@@ -11,26 +13,50 @@ pub struct KaspadHandshake<'a> {
     version_receiver: IncomingRoute,
     verack_receiver: IncomingRoute,
     ready_receiver: IncomingRoute,
+    timeouts: P2pTimeouts,
+    identity: Option<Arc<NodeIdentity>>,
 }
 
 impl<'a> KaspadHandshake<'a> {
     /// Builds the handshake object and subscribes to handshake messages
     pub fn new(router: &'a Router) -> Self {
+        Self::with_timeouts(router, Default::default())
+    }
+
+    /// Builds the handshake object using the handshake stage timeouts of `timeouts`
+    pub fn with_timeouts(router: &'a Router, timeouts: P2pTimeouts) -> Self {
         Self {
             router,
             version_receiver: router.subscribe(vec![KaspadMessagePayloadType::Version]),
             verack_receiver: router.subscribe(vec![KaspadMessagePayloadType::Verack]),
             ready_receiver: router.subscribe(vec![KaspadMessagePayloadType::Ready]),
+            timeouts,
+            identity: None,
         }
     }
 
-    async fn receive_version_flow(router: &Router, version_receiver: &mut IncomingRoute) -> Result<VersionMessage, ProtocolError> {
+    /// Proves the identity of the local node to peers advertising the node identity service, and requires them to prove
+    /// theirs in return. Without a local identity, the identity keys announced by peers are ignored
+    pub fn with_identity(mut self, identity: Arc<NodeIdentity>) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    async fn receive_version_flow(
+        router: &Router,
+        version_receiver: &mut IncomingRoute,
+        identity: Option<&NodeIdentity>,
+        self_nonce: &[u8],
+        timeout: Duration,
+    ) -> Result<VersionMessage, ProtocolError> {
         debug!("starting receive version flow");
 
-        let version_message = dequeue_with_timeout!(version_receiver, Payload::Version, Duration::from_secs(4))?;
+        let version_message = dequeue_with_timeout!(version_receiver, Payload::Version, timeout, TimeoutKind::HandshakeVersion)?;
         debug!("accepted version message: {version_message:?}");
 
-        let verack_message = make_message!(Payload::Verack, VerackMessage {});
+        // Answer the identity challenge of the peer, if any
+        let identity_signature = identity.map(|identity| prove_identity(identity, self_nonce, &version_message)).unwrap_or_default();
+        let verack_message = make_message!(Payload::Verack, VerackMessage { identity_signature });
         router.enqueue(verack_message).await?;
 
         Ok(version_message)
@@ -40,17 +66,18 @@ impl<'a> KaspadHandshake<'a> {
         router: &Router,
         verack_receiver: &mut IncomingRoute,
         version_message: VersionMessage,
-    ) -> Result<(), ProtocolError> {
+        timeout: Duration,
+    ) -> Result<VerackMessage, ProtocolError> {
         debug!("starting send version flow");
 
         debug!("sending version message: {version_message:?}");
         let version_message = make_message!(Payload::Version, version_message);
         router.enqueue(version_message).await?;
 
-        let verack_message = dequeue_with_timeout!(verack_receiver, Payload::Verack, Duration::from_secs(4))?;
+        let verack_message = dequeue_with_timeout!(verack_receiver, Payload::Verack, timeout, TimeoutKind::HandshakeVerack)?;
         debug!("accepted verack_message: {verack_message:?}");
 
-        Ok(())
+        Ok(verack_message)
     }
 
     /// Exchange `Ready` messages with the peer. This is the final step of the handshake protocol and should
@@ -61,20 +88,187 @@ impl<'a> KaspadHandshake<'a> {
         let sent_ready_message = make_message!(Payload::Ready, ReadyMessage {});
         self.router.enqueue(sent_ready_message).await?;
 
-        let recv_ready_message = dequeue_with_timeout!(self.ready_receiver, Payload::Ready, Duration::from_secs(8))?;
+        let recv_ready_message =
+            dequeue_with_timeout!(self.ready_receiver, Payload::Ready, self.timeouts.handshake_ready, TimeoutKind::HandshakeReady)?;
         debug!("accepted ready message: {recv_ready_message:?}");
 
         Ok(())
     }
 
-    /// Performs the handshake with the peer, essentially exchanging version messages
-    pub async fn handshake(&mut self, self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
+    /// Performs the handshake with the peer, essentially exchanging version messages. The identity key of the returned
+    /// peer version message is set only if the peer proved owning it
+    pub async fn handshake(&mut self, mut self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
+        if let Some(identity) = self.identity.as_ref() {
+            self_version_message.services |= SERVICE_NODE_IDENTITY;
+            self_version_message.identity_public_key = identity.public_key().as_bytes().to_vec();
+            self_version_message.identity_nonce = identity::generate_nonce();
+        }
+        let nonce = self_version_message.identity_nonce.clone();
+
         // Run both send and receive flows concurrently -- this is critical in order to avoid a handshake deadlock
         let (send_res, recv_res) = tokio::join!(
-            Self::send_version_flow(self.router, &mut self.verack_receiver, self_version_message),
-            Self::receive_version_flow(self.router, &mut self.version_receiver)
+            Self::send_version_flow(self.router, &mut self.verack_receiver, self_version_message, self.timeouts.handshake_verack),
+            Self::receive_version_flow(
+                self.router,
+                &mut self.version_receiver,
+                self.identity.as_deref(),
+                &nonce,
+                self.timeouts.handshake_version
+            )
         );
-        send_res?;
-        recv_res
+        let verack_message = send_res?;
+        let mut version_message = recv_res?;
+
+        // Authenticate the identity announced by the peer
+        match self.identity.as_deref() {
+            Some(identity) => authenticate_peer_identity(identity, &nonce, &mut version_message, &verack_message)?,
+            None => version_message.identity_public_key.clear(),
+        }
+        Ok(version_message)
+    }
+}
+
+/// Returns the identity key announced in `version_message`, if the peer advertises the node identity service
+fn announced_identity_key(version_message: &VersionMessage) -> Option<Result<PeerIdentityKey, ProtocolError>> {
+    if version_message.services & SERVICE_NODE_IDENTITY == 0 || version_message.identity_public_key.is_empty() {
+        return None;
+    }
+    Some(
+        PeerIdentityKey::try_from(version_message.identity_public_key.as_slice())
+            .map_err(|_| ProtocolError::OtherOwned("peer identity key has an invalid length".to_owned())),
+    )
+}
+
+/// Answers the identity challenge of the peer, binding the proof to the nonces and identity keys of both sides. Returns an
+/// empty proof if the peer did not challenge the local node or did not announce a valid identity key of its own
+fn prove_identity(identity: &NodeIdentity, self_nonce: &[u8], peer_version_message: &VersionMessage) -> Vec<u8> {
+    let Some(Ok(verifier_key)) = announced_identity_key(peer_version_message) else {
+        return vec![];
+    };
+    if peer_version_message.identity_nonce.is_empty() {
+        return vec![];
+    }
+    let prover_key = identity.public_key();
+    let context = IdentityProofContext {
+        challenge: &peer_version_message.identity_nonce,
+        prover_nonce: self_nonce,
+        prover_key: &prover_key,
+        verifier_key: &verifier_key,
+    };
+    identity.prove(&context).to_vec()
+}
+
+/// Verifies the proof of the identity announced by the peer in response to the `self_nonce` challenge. Peers announcing no
+/// identity are accepted as anonymous
+fn authenticate_peer_identity(
+    identity: &NodeIdentity,
+    self_nonce: &[u8],
+    peer_version_message: &mut VersionMessage,
+    verack_message: &VerackMessage,
+) -> Result<(), ProtocolError> {
+    let Some(prover_key) = announced_identity_key(peer_version_message).transpose()? else {
+        peer_version_message.identity_public_key.clear();
+        return Ok(());
+    };
+    let verifier_key = identity.public_key();
+    let context = IdentityProofContext {
+        challenge: self_nonce,
+        prover_nonce: &peer_version_message.identity_nonce,
+        prover_key: &prover_key,
+        verifier_key: &verifier_key,
+    };
+    if !identity::verify_identity_proof(&context, &verack_message.identity_signature) {
+        return Err(ProtocolError::InvalidPeerIdentity(prover_key));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A node taking part in handshakes
+    struct Node {
+        identity: NodeIdentity,
+    }
+
+    impl Node {
+        fn new() -> Self {
+            Self { identity: NodeIdentity::generate() }
+        }
+
+        /// The version message the node sends when initiating a handshake, as built by [`KaspadHandshake::handshake`]
+        fn version_message(&self) -> VersionMessage {
+            VersionMessage {
+                services: SERVICE_NODE_IDENTITY,
+                identity_public_key: self.identity.public_key().as_bytes().to_vec(),
+                identity_nonce: identity::generate_nonce(),
+                ..Default::default()
+            }
+        }
+
+        /// The verack the node answers `peer_version` with, given the version message it sent itself
+        fn verack_message(&self, self_version: &VersionMessage, peer_version: &VersionMessage) -> VerackMessage {
+            VerackMessage { identity_signature: prove_identity(&self.identity, &self_version.identity_nonce, peer_version) }
+        }
+
+        /// Authenticates the peer identity given the exchanged messages, returning the authenticated key if any
+        fn authenticate(
+            &self,
+            self_version: &VersionMessage,
+            mut peer_version: VersionMessage,
+            peer_verack: &VerackMessage,
+        ) -> Result<Option<PeerIdentityKey>, ProtocolError> {
+            authenticate_peer_identity(&self.identity, &self_version.identity_nonce, &mut peer_version, peer_verack)?;
+            announced_identity_key(&peer_version).transpose()
+        }
+    }
+
+    #[test]
+    fn test_identity_handshake() {
+        let (alice, bob) = (Node::new(), Node::new());
+        let (alice_version, bob_version) = (alice.version_message(), bob.version_message());
+        let alice_verack = alice.verack_message(&alice_version, &bob_version);
+        let bob_verack = bob.verack_message(&bob_version, &alice_version);
+
+        assert_eq!(alice.authenticate(&alice_version, bob_version.clone(), &bob_verack).unwrap(), Some(bob.identity.public_key()));
+        assert_eq!(bob.authenticate(&bob_version, alice_version.clone(), &alice_verack).unwrap(), Some(alice.identity.public_key()));
+
+        // An anonymous peer is accepted without any identity
+        let anonymous_version = VersionMessage::default();
+        assert_eq!(alice.authenticate(&alice_version, anonymous_version.clone(), &VerackMessage::default()).unwrap(), None);
+        assert!(alice.verack_message(&alice_version, &anonymous_version).identity_signature.is_empty());
+
+        // A proof from a previous handshake does not replay within a new one
+        let new_alice_version = alice.version_message();
+        assert!(matches!(
+            alice.authenticate(&new_alice_version, bob_version, &bob_verack),
+            Err(ProtocolError::InvalidPeerIdentity(key)) if key == bob.identity.public_key()
+        ));
+    }
+
+    #[test]
+    fn test_identity_relay() {
+        // Mallory, holding an identity of its own, handshakes with both Alice and Bob and attempts to pose as Bob to Alice
+        // by relaying the challenge of Alice to Bob and the proof of Bob back to Alice
+        let (alice, bob, mallory) = (Node::new(), Node::new(), Node::new());
+        let alice_version = alice.version_message();
+
+        // Mallory challenges Bob with the nonce of Alice, announcing its own identity to Bob
+        let mallory_to_bob_version =
+            VersionMessage { identity_nonce: alice_version.identity_nonce.clone(), ..mallory.version_message() };
+        let bob_version = bob.version_message();
+        let bob_verack = bob.verack_message(&bob_version, &mallory_to_bob_version);
+        // Bob proves its identity to Mallory as expected
+        assert_eq!(
+            mallory.authenticate(&mallory_to_bob_version, bob_version.clone(), &bob_verack).unwrap(),
+            Some(bob.identity.public_key())
+        );
+
+        // Mallory then forwards the version and proof of Bob to Alice, but the proof is bound to the identity of Mallory
+        assert!(matches!(
+            alice.authenticate(&alice_version, bob_version, &bob_verack),
+            Err(ProtocolError::InvalidPeerIdentity(key)) if key == bob.identity.public_key()
+        ));
     }
 }
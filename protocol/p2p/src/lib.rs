@@ -12,8 +12,17 @@ mod handshake;
 
 pub use crate::core::adaptor::{Adaptor, ConnectionInitializer};
 pub use crate::core::connection_handler::ConnectionError;
+pub use crate::core::counters::{MessageTypeStats, P2pMessageCounters, PeerSessionStats, SubmissionOutcome, SubmissionStats};
+#[cfg(feature = "fault-injection")]
+pub use crate::core::fault_injection;
 pub use crate::core::hub::Hub;
+pub use crate::core::identity::{NodeIdentity, PeerIdentityKey};
+pub use crate::core::inventory::{KnownInventoryCounters, KnownInventorySizes, KnownInventoryStats};
 pub use crate::core::payload_type::KaspadMessagePayloadType;
 pub use crate::core::peer::{Peer, PeerKey, PeerProperties};
-pub use crate::core::router::{IncomingRoute, Router, SharedIncomingRoute, BLANK_ROUTE_ID};
+pub use crate::core::proxy::Socks5Proxy;
+pub use crate::core::router::{FlowState, IncomingRoute, Router, SharedIncomingRoute, BLANK_ROUTE_ID};
+pub use crate::core::throttle::{UploadLimits, UploadThrottle};
+pub use crate::core::timeouts::{P2pTimeoutCounters, P2pTimeouts, TimeoutKind};
+pub use crate::core::trace::{replay_trace, MessageTraceReader, MessageTraceRecorder, TraceRecord};
 pub use handshake::KaspadHandshake;
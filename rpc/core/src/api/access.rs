@@ -0,0 +1,228 @@
+//!
+//! Access policy of RPC endpoints, meant for operators exposing public endpoints.
+//!
+//! A policy restricts the methods served on an endpoint to a whitelist, limits the rate at which
+//! each connection may call every method and caps the number of requests processed concurrently
+//! over all connections.
+//!
+
+use crate::{api::ops::RpcApiOps, RpcError, RpcResult};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+/// Whitelist entry standing for all methods of [`RpcApiOps::is_public`]
+pub const PUBLIC_PROFILE: &str = "public";
+
+#[derive(Clone, Debug, Default)]
+pub struct RpcAccessConfig {
+    /// Methods served on the endpoint, all of them if `None`
+    pub whitelist: Option<HashSet<RpcApiOps>>,
+    /// Requests per second allowed to a single connection, by method
+    pub rate_limits: HashMap<RpcApiOps, u32>,
+    /// Requests per second allowed to a single connection for methods with no specific limit
+    pub default_rate_limit: Option<u32>,
+    /// Max number of requests processed concurrently over all connections
+    pub max_concurrent_requests: Option<usize>,
+}
+
+impl RpcAccessConfig {
+    /// Adds the methods of a whitelist entry, being either [`PUBLIC_PROFILE`] or a method name.
+    /// Returns `false` if the entry is unknown
+    pub fn add_whitelist_entry(&mut self, entry: &str) -> bool {
+        let ops: Vec<RpcApiOps> = if entry.eq_ignore_ascii_case(PUBLIC_PROFILE) {
+            RpcApiOps::into_iter().filter(RpcApiOps::is_public).collect()
+        } else if let Some(op) = parse_method(entry) {
+            vec![op]
+        } else {
+            return false;
+        };
+        self.whitelist.get_or_insert_with(HashSet::new).extend(ops);
+        true
+    }
+
+    /// Adds a rate limit given as `<method>:<requests per second>`, where the method `*` sets the default
+    /// limit of all methods. Returns `false` if the entry is malformed
+    pub fn add_rate_limit(&mut self, entry: &str) -> bool {
+        let Some((method, rate)) = entry.split_once(':') else {
+            return false;
+        };
+        let Some(rate) = rate.trim().parse::<u32>().ok().filter(|&rate| rate > 0) else {
+            return false;
+        };
+        match method.trim() {
+            "*" => self.default_rate_limit = Some(rate),
+            method => match parse_method(method) {
+                Some(op) => {
+                    self.rate_limits.insert(op, rate);
+                }
+                None => return false,
+            },
+        }
+        true
+    }
+
+    pub fn is_unrestricted(&self) -> bool {
+        self.whitelist.is_none()
+            && self.rate_limits.is_empty()
+            && self.default_rate_limit.is_none()
+            && self.max_concurrent_requests.is_none()
+    }
+
+    fn rate_limit(&self, op: RpcApiOps) -> Option<u32> {
+        self.rate_limits.get(&op).copied().or(self.default_rate_limit)
+    }
+}
+
+/// Parses a method name, either in camel or pascal case (i.e. `getInfo` or `GetInfo`)
+fn parse_method(name: &str) -> Option<RpcApiOps> {
+    RpcApiOps::into_iter().find(|op| op.is_method() && op.as_str().eq_ignore_ascii_case(name))
+}
+
+/// Token bucket holding up to a second worth of requests
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32, now: Instant) -> Self {
+        Self { tokens: rate as f64, last: now }
+    }
+
+    fn try_take(&mut self, rate: u32, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate as f64).min(rate as f64);
+        self.last = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Rate limiting state of a single connection, dropped along with it
+#[derive(Debug, Default)]
+pub struct RpcConnectionLimits {
+    buckets: Mutex<HashMap<RpcApiOps, TokenBucket>>,
+}
+
+/// Access policy shared by all connections of the endpoints it applies to
+#[derive(Debug)]
+pub struct RpcAccessPolicy {
+    config: RpcAccessConfig,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl RpcAccessPolicy {
+    pub fn new(config: RpcAccessConfig) -> Self {
+        Self { config, in_flight: Default::default() }
+    }
+
+    pub fn config(&self) -> &RpcAccessConfig {
+        &self.config
+    }
+
+    /// Admits a request of a connection, returning a permit to be held while the request is processed
+    pub fn acquire(&self, op: RpcApiOps, limits: &RpcConnectionLimits) -> RpcResult<RpcAccessPermit> {
+        self.acquire_at(op, limits, Instant::now())
+    }
+
+    fn acquire_at(&self, op: RpcApiOps, limits: &RpcConnectionLimits, now: Instant) -> RpcResult<RpcAccessPermit> {
+        if self.config.whitelist.as_ref().is_some_and(|whitelist| !whitelist.contains(&op)) {
+            return Err(RpcError::MethodNotAllowed(op));
+        }
+        if let Some(rate) = self.config.rate_limit(op) {
+            let mut buckets = limits.buckets.lock().unwrap();
+            if !buckets.entry(op).or_insert_with(|| TokenBucket::new(rate, now)).try_take(rate, now) {
+                return Err(RpcError::RateLimitExceeded(op, rate));
+            }
+        }
+        let Some(max) = self.config.max_concurrent_requests else {
+            return Ok(RpcAccessPermit { in_flight: None });
+        };
+        self.in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |in_flight| (in_flight < max).then_some(in_flight + 1))
+            .map_err(|_| RpcError::TooManyConcurrentRequests(max))?;
+        Ok(RpcAccessPermit { in_flight: Some(self.in_flight.clone()) })
+    }
+}
+
+/// Admission of a request, counted against the concurrency cap until dropped
+#[derive(Debug)]
+pub struct RpcAccessPermit {
+    in_flight: Option<Arc<AtomicUsize>>,
+}
+
+impl Drop for RpcAccessPermit {
+    fn drop(&mut self) {
+        if let Some(in_flight) = self.in_flight.as_ref() {
+            in_flight.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_access_config_parsing() {
+        let mut config = RpcAccessConfig::default();
+        assert!(config.is_unrestricted());
+        assert!(config.add_whitelist_entry("public"));
+        assert!(config.add_whitelist_entry("submitTransaction"));
+        assert!(!config.add_whitelist_entry("getEverything"));
+        // Notification ops are not methods
+        assert!(!config.add_whitelist_entry("BlockAddedNotification"));
+        let whitelist = config.whitelist.as_ref().unwrap();
+        assert!(whitelist.contains(&RpcApiOps::GetBlockDagInfo));
+        assert!(whitelist.contains(&RpcApiOps::SubmitTransaction));
+        assert!(!whitelist.contains(&RpcApiOps::Shutdown));
+        assert!(!whitelist.contains(&RpcApiOps::GetConnectedPeerInfo));
+
+        assert!(config.add_rate_limit("GetUtxosByAddresses:5"));
+        assert!(config.add_rate_limit("*:100"));
+        assert!(!config.add_rate_limit("getInfo"));
+        assert!(!config.add_rate_limit("getInfo:0"));
+        assert!(!config.add_rate_limit("getEverything:1"));
+        assert_eq!(config.rate_limit(RpcApiOps::GetUtxosByAddresses), Some(5));
+        assert_eq!(config.rate_limit(RpcApiOps::GetInfo), Some(100));
+    }
+
+    #[test]
+    fn test_access_policy() {
+        let mut config = RpcAccessConfig::default();
+        config.add_whitelist_entry("public");
+        config.add_rate_limit("getInfo:2");
+        config.max_concurrent_requests = Some(3);
+        let policy = RpcAccessPolicy::new(config);
+        let (first, second) = (RpcConnectionLimits::default(), RpcConnectionLimits::default());
+        let now = Instant::now();
+
+        assert!(matches!(policy.acquire_at(RpcApiOps::Shutdown, &first, now), Err(RpcError::MethodNotAllowed(_))));
+
+        // Rate limits apply to each connection separately and refill over time
+        let a = policy.acquire_at(RpcApiOps::GetInfo, &first, now).unwrap();
+        let b = policy.acquire_at(RpcApiOps::GetInfo, &first, now).unwrap();
+        assert!(matches!(policy.acquire_at(RpcApiOps::GetInfo, &first, now), Err(RpcError::RateLimitExceeded(_, 2))));
+        let c = policy.acquire_at(RpcApiOps::GetInfo, &second, now).unwrap();
+
+        // The concurrency cap applies to all connections
+        assert!(matches!(policy.acquire_at(RpcApiOps::GetBlockCount, &second, now), Err(RpcError::TooManyConcurrentRequests(3))));
+        drop((a, b, c));
+        let later = now + Duration::from_millis(500);
+        let _d = policy.acquire_at(RpcApiOps::GetInfo, &first, later).unwrap();
+        assert!(policy.acquire_at(RpcApiOps::GetInfo, &first, later).is_err());
+        let _e = policy.acquire_at(RpcApiOps::GetBlockCount, &second, later).unwrap();
+    }
+}
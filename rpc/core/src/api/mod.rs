@@ -2,6 +2,7 @@
 //!  API module for the RPC server. Implements core RPC primitives.
 //!
 
+pub mod access;
 pub mod connection;
 pub mod ctl;
 pub mod notifications;
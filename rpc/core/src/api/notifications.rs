@@ -48,6 +48,12 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "TransactionsEvicted notification: {} transactions", "_0.transaction_ids.len()")]
+    TransactionsEvicted(TransactionsEvictedNotification),
+
+    #[display(fmt = "NotificationsGap notification: {} notifications dropped", "_0.dropped")]
+    NotificationsGap(NotificationsGapNotification),
 }
 }
 
@@ -64,6 +70,8 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::TransactionsEvicted(v) => to_value(&v),
+            Notification::NotificationsGap(v) => to_value(&v),
         }
     }
 }
@@ -115,6 +123,10 @@ impl NotificationTrait for Notification {
     fn event_type(&self) -> EventType {
         self.into()
     }
+
+    fn notifications_gap(dropped: u64) -> Option<Self> {
+        Some(Notification::NotificationsGap(NotificationsGapNotification { dropped }))
+    }
 }
 
 impl Serializer for Notification {
@@ -157,6 +169,14 @@ impl Serializer for Notification {
                 store!(u16, &8, writer)?;
                 serialize!(NewBlockTemplateNotification, notification, writer)?;
             }
+            Notification::TransactionsEvicted(notification) => {
+                store!(u16, &9, writer)?;
+                serialize!(TransactionsEvictedNotification, notification, writer)?;
+            }
+            Notification::NotificationsGap(notification) => {
+                store!(u16, &10, writer)?;
+                serialize!(NotificationsGapNotification, notification, writer)?;
+            }
         }
         Ok(())
     }
@@ -202,6 +222,14 @@ impl Deserializer for Notification {
                 let notification = deserialize!(NewBlockTemplateNotification, reader)?;
                 Ok(Notification::NewBlockTemplate(notification))
             }
+            9 => {
+                let notification = deserialize!(TransactionsEvictedNotification, reader)?;
+                Ok(Notification::TransactionsEvicted(notification))
+            }
+            10 => {
+                let notification = deserialize!(NotificationsGapNotification, reader)?;
+                Ok(Notification::NotificationsGap(notification))
+            }
             _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid variant")),
         }
     }
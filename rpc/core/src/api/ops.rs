@@ -40,6 +40,7 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged = 16,
     NotifyVirtualChainChanged = 17,
     NotifySinkBlueScoreChanged = 18,
+    NotifyTransactionsEvicted = 19,
 
     // Notification ops required by wRPC
 
@@ -54,6 +55,8 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification = 66,
     PruningPointUtxoSetOverrideNotification = 67,
     NewBlockTemplateNotification = 68,
+    TransactionsEvictedNotification = 69,
+    NotificationsGapNotification = 70,
 
     // RPC methods
     /// Ping the node to check if connection is alive
@@ -120,7 +123,7 @@ pub enum RpcApiOps {
     Unban = 140,
     /// Get generic node information
     GetInfo = 141,
-    //
+    /// Estimate the network hashrate (hashes per second) over a window of blocks ending at a given block (or virtual)
     EstimateNetworkHashesPerSecond = 142,
     /// Get a list of mempool entries that belong to a specific address
     GetMempoolEntriesByAddresses = 143,
@@ -136,6 +139,30 @@ pub enum RpcApiOps {
     GetFeeEstimateExperimental = 148,
     /// Block color determination by iterating DAG.
     GetCurrentBlockColor = 149,
+    /// Get P2P message traffic statistics per message type
+    GetP2pStats = 150,
+    /// Get block processing counters along with processing rates since the previous call
+    GetProcessingStats = 151,
+    /// Get the state of the P2P flows of all connected peers, or of a specific peer by its id
+    GetPeerFlowStates = 152,
+    /// Disconnect a specific peer by its id, without banning it
+    DisconnectPeer = 153,
+    /// Mark a specific peer by its id as protected from eviction, or remove its protection
+    SetPeerProtected = 154,
+    /// Get the chain block accepting each of the given transactions (requires the acceptance index)
+    GetTransactionAcceptanceData = 155,
+    /// Get the selected chain block at which the chain reached a given DAA score or blue score
+    GetChainBlockByScore = 156,
+    /// Get a block in its hex-encoded consensus serialization
+    GetBlockSerialized = 157,
+    /// Submit a block given in its hex-encoded consensus serialization
+    SubmitBlockSerialized = 158,
+    /// Get a block or mempool transaction in its hex-encoded consensus serialization
+    GetTransactionSerialized = 159,
+    /// Submit a transaction given in its hex-encoded consensus serialization
+    SubmitTransactionSerialized = 160,
+    /// Get the transactions submitted to this node which are pending acceptance
+    GetLocalTransactions = 161,
 }
 
 impl RpcApiOps {
@@ -151,10 +178,54 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyTransactionsEvicted
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
     }
+
+    /// Whether the op is an RPC method, as opposed to connection control, subscription and notification ops
+    pub fn is_method(&self) -> bool {
+        *self as u32 >= RpcApiOps::Ping as u32
+    }
+
+    /// Whether the method is read-only and reveals nothing about the node operator, its peers or its host,
+    /// hence is safe to serve on public endpoints
+    pub fn is_public(&self) -> bool {
+        matches!(
+            self,
+            RpcApiOps::Ping
+                | RpcApiOps::GetServerInfo
+                | RpcApiOps::GetSyncStatus
+                | RpcApiOps::GetCurrentNetwork
+                | RpcApiOps::GetSink
+                | RpcApiOps::GetMempoolEntry
+                | RpcApiOps::GetMempoolEntries
+                | RpcApiOps::GetBlock
+                | RpcApiOps::GetSubnetwork
+                | RpcApiOps::GetVirtualChainFromBlock
+                | RpcApiOps::GetBlocks
+                | RpcApiOps::GetBlockCount
+                | RpcApiOps::GetBlockDagInfo
+                | RpcApiOps::GetHeaders
+                | RpcApiOps::GetUtxosByAddresses
+                | RpcApiOps::GetBalanceByAddress
+                | RpcApiOps::GetBalancesByAddresses
+                | RpcApiOps::GetSinkBlueScore
+                | RpcApiOps::GetInfo
+                | RpcApiOps::EstimateNetworkHashesPerSecond
+                | RpcApiOps::GetMempoolEntriesByAddresses
+                | RpcApiOps::GetCoinSupply
+                | RpcApiOps::GetDaaScoreTimestampEstimate
+                | RpcApiOps::GetFeeEstimate
+                | RpcApiOps::GetFeeEstimateExperimental
+                | RpcApiOps::GetCurrentBlockColor
+                | RpcApiOps::GetTransactionAcceptanceData
+                | RpcApiOps::GetChainBlockByScore
+                | RpcApiOps::GetBlockSerialized
+                | RpcApiOps::GetTransactionSerialized
+        )
+    }
 }
 
 impl From<RpcApiOps> for u32 {
@@ -177,6 +248,8 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::TransactionsEvicted => RpcApiOps::TransactionsEvictedNotification,
+            EventType::NotificationsGap => RpcApiOps::NotificationsGapNotification,
         }
     }
 }
@@ -71,6 +71,7 @@ pub trait RpcApi: Sync + Send + AnySync {
                 consensus_metrics,
                 storage_metrics,
                 custom_metrics,
+                allocation_metrics: false,
             },
         )
         .await
@@ -140,7 +141,7 @@ pub trait RpcApi: Sync + Send + AnySync {
 
     /// Requests the list of known kaspad addresses in the current network (mainnet, testnet, etc.)
     async fn get_peer_addresses(&self) -> RpcResult<GetPeerAddressesResponse> {
-        self.get_peer_addresses_call(None, GetPeerAddressesRequest {}).await
+        self.get_peer_addresses_call(None, GetPeerAddressesRequest { verbose: false }).await
     }
     async fn get_peer_addresses_call(
         &self,
@@ -175,7 +176,7 @@ pub trait RpcApi: Sync + Send + AnySync {
     /// Requests information about all the transactions currently in the mempool.
     async fn get_mempool_entries(&self, include_orphan_pool: bool, filter_transaction_pool: bool) -> RpcResult<Vec<RpcMempoolEntry>> {
         Ok(self
-            .get_mempool_entries_call(None, GetMempoolEntriesRequest::new(include_orphan_pool, filter_transaction_pool))
+            .get_mempool_entries_call(None, GetMempoolEntriesRequest::new(include_orphan_pool, filter_transaction_pool, None))
             .await?
             .mempool_entries)
     }
@@ -419,7 +420,8 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetMempoolEntriesByAddressesRequest,
     ) -> RpcResult<GetMempoolEntriesByAddressesResponse>;
 
-    ///
+    /// Requests the maximal and circulating coin supply. The circulating supply excludes burned outputs, i.e., outputs locked by
+    /// provably unspendable scripts. It is read from the UTXO index if enabled, or otherwise periodically computed from the UTXO set.
     async fn get_coin_supply(&self) -> RpcResult<GetCoinSupplyResponse> {
         self.get_coin_supply_call(None, GetCoinSupplyRequest {}).await
     }
@@ -469,6 +471,150 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetCurrentBlockColorRequest,
     ) -> RpcResult<GetCurrentBlockColorResponse>;
 
+    /// Get P2P message counts and bytes per message type and direction, aggregated over all peers.
+    async fn get_p2p_stats(&self) -> RpcResult<GetP2pStatsResponse> {
+        self.get_p2p_stats_call(None, GetP2pStatsRequest {}).await
+    }
+    async fn get_p2p_stats_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetP2pStatsRequest,
+    ) -> RpcResult<GetP2pStatsResponse>;
+
+    /// Get the node's block processing counters, along with the processing rates measured since the previous call.
+    async fn get_processing_stats(&self) -> RpcResult<GetProcessingStatsResponse> {
+        self.get_processing_stats_call(None, GetProcessingStatsRequest {}).await
+    }
+    async fn get_processing_stats_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetProcessingStatsRequest,
+    ) -> RpcResult<GetProcessingStatsResponse>;
+
+    /// Get the state of the P2P flows of all connected peers, or only of the peer with the given id.
+    async fn get_peer_flow_states(&self, peer_id: Option<RpcNodeId>) -> RpcResult<GetPeerFlowStatesResponse> {
+        self.get_peer_flow_states_call(None, GetPeerFlowStatesRequest { peer_id }).await
+    }
+    async fn get_peer_flow_states_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetPeerFlowStatesRequest,
+    ) -> RpcResult<GetPeerFlowStatesResponse>;
+
+    /// Disconnect the peer with the given id without banning it.
+    async fn disconnect_peer(&self, peer_id: RpcNodeId) -> RpcResult<()> {
+        self.disconnect_peer_call(None, DisconnectPeerRequest { peer_id }).await?;
+        Ok(())
+    }
+    async fn disconnect_peer_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: DisconnectPeerRequest,
+    ) -> RpcResult<DisconnectPeerResponse>;
+
+    /// Mark the peer with the given id as protected from eviction, or remove its protection.
+    async fn set_peer_protected(&self, peer_id: RpcNodeId, is_protected: bool) -> RpcResult<()> {
+        self.set_peer_protected_call(None, SetPeerProtectedRequest { peer_id, is_protected }).await?;
+        Ok(())
+    }
+    async fn set_peer_protected_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: SetPeerProtectedRequest,
+    ) -> RpcResult<SetPeerProtectedResponse>;
+
+    /// Get the accepting chain block hash and blue score of each of the given transactions accepted by the selected chain.
+    /// Transactions not accepted by the selected chain are omitted.
+    ///
+    /// Requires the node to run with the `--acceptanceindex` argument.
+    async fn get_transaction_acceptance_data(
+        &self,
+        transaction_ids: Vec<RpcTransactionId>,
+    ) -> RpcResult<Vec<RpcTransactionAcceptance>> {
+        Ok(self.get_transaction_acceptance_data_call(None, GetTransactionAcceptanceDataRequest { transaction_ids }).await?.acceptances)
+    }
+    async fn get_transaction_acceptance_data_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionAcceptanceDataRequest,
+    ) -> RpcResult<GetTransactionAcceptanceDataResponse>;
+
+    /// Get the selected chain block with the highest DAA score (or blue score, if `by_blue_score` is set) not exceeding `score`,
+    /// i.e., the chain block which was the sink when the chain reached `score`.
+    ///
+    /// Only chain blocks from the pruning point up to the sink can be looked up.
+    async fn get_chain_block_by_score(&self, score: u64, by_blue_score: bool) -> RpcResult<GetChainBlockByScoreResponse> {
+        self.get_chain_block_by_score_call(None, GetChainBlockByScoreRequest { score, by_blue_score }).await
+    }
+    async fn get_chain_block_by_score_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetChainBlockByScoreRequest,
+    ) -> RpcResult<GetChainBlockByScoreResponse>;
+
+    /// Get the block with the given hash in its consensus (hashing preimage) serialization, hex-encoded.
+    async fn get_block_serialized(&self, hash: RpcHash) -> RpcResult<String> {
+        Ok(self.get_block_serialized_call(None, GetBlockSerializedRequest { hash }).await?.block_hex)
+    }
+    async fn get_block_serialized_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetBlockSerializedRequest,
+    ) -> RpcResult<GetBlockSerializedResponse>;
+
+    /// Submit a block given in its consensus (hashing preimage) serialization, hex-encoded.
+    async fn submit_block_serialized(
+        &self,
+        block_hex: String,
+        allow_non_daa_blocks: bool,
+    ) -> RpcResult<SubmitBlockSerializedResponse> {
+        self.submit_block_serialized_call(None, SubmitBlockSerializedRequest { block_hex, allow_non_daa_blocks }).await
+    }
+    async fn submit_block_serialized_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: SubmitBlockSerializedRequest,
+    ) -> RpcResult<SubmitBlockSerializedResponse>;
+
+    /// Get a transaction in its consensus (hashing preimage) serialization, hex-encoded.
+    ///
+    /// The transaction is looked up in the block `block_hash` if provided, otherwise in the mempool (including orphans).
+    async fn get_transaction_serialized(&self, transaction_id: RpcTransactionId, block_hash: Option<RpcHash>) -> RpcResult<String> {
+        Ok(self
+            .get_transaction_serialized_call(None, GetTransactionSerializedRequest { transaction_id, block_hash })
+            .await?
+            .transaction_hex)
+    }
+    async fn get_transaction_serialized_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionSerializedRequest,
+    ) -> RpcResult<GetTransactionSerializedResponse>;
+
+    /// Submit a transaction given in its consensus (hashing preimage) serialization, hex-encoded.
+    async fn submit_transaction_serialized(&self, transaction_hex: String, allow_orphan: bool) -> RpcResult<RpcTransactionId> {
+        Ok(self
+            .submit_transaction_serialized_call(None, SubmitTransactionSerializedRequest { transaction_hex, allow_orphan })
+            .await?
+            .transaction_id)
+    }
+    async fn submit_transaction_serialized_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: SubmitTransactionSerializedRequest,
+    ) -> RpcResult<SubmitTransactionSerializedResponse>;
+
+    /// Get the transactions submitted to this node which are still pending acceptance. These are kept in the mempool,
+    /// across restarts too, and periodically rebroadcast until accepted into a block or expired.
+    async fn get_local_transactions(&self) -> RpcResult<Vec<RpcLocalTransaction>> {
+        Ok(self.get_local_transactions_call(None, GetLocalTransactionsRequest {}).await?.transactions)
+    }
+    async fn get_local_transactions_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetLocalTransactionsRequest,
+    ) -> RpcResult<GetLocalTransactionsResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
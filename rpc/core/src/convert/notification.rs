@@ -3,7 +3,8 @@
 use crate::{
     convert::utxo::utxo_set_into_rpc, BlockAddedNotification, FinalityConflictNotification, FinalityConflictResolvedNotification,
     NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification, RpcAcceptedTransactionIds,
-    SinkBlueScoreChangedNotification, UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
+    SinkBlueScoreChangedNotification, TransactionsEvictedNotification, UtxosChangedNotification, VirtualChainChangedNotification,
+    VirtualDaaScoreChangedNotification,
 };
 use kaspa_consensus_notify::notification as consensus_notify;
 use kaspa_index_core::notification as index_notify;
@@ -31,6 +32,7 @@ impl From<&consensus_notify::Notification> for Notification {
             consensus_notify::Notification::VirtualDaaScoreChanged(msg) => Notification::VirtualDaaScoreChanged(msg.into()),
             consensus_notify::Notification::PruningPointUtxoSetOverride(msg) => Notification::PruningPointUtxoSetOverride(msg.into()),
             consensus_notify::Notification::NewBlockTemplate(msg) => Notification::NewBlockTemplate(msg.into()),
+            consensus_notify::Notification::TransactionsEvicted(msg) => Notification::TransactionsEvicted(msg.into()),
         }
     }
 }
@@ -112,6 +114,12 @@ impl From<&consensus_notify::NewBlockTemplateNotification> for NewBlockTemplateN
     }
 }
 
+impl From<&consensus_notify::TransactionsEvictedNotification> for TransactionsEvictedNotification {
+    fn from(item: &consensus_notify::TransactionsEvictedNotification) -> Self {
+        Self { transaction_ids: item.transaction_ids.clone() }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // index to rpc_core
 // ----------------------------------------------------------------------------
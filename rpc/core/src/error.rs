@@ -2,13 +2,17 @@
 //! [`RpcError`] enum used by RPC primitives.
 //!
 
-use kaspa_consensus_core::{subnets::SubnetworkConversionError, tx::TransactionId};
+use kaspa_addresses::Prefix;
+use kaspa_consensus_core::{limits::LimitExceeded, subnets::SubnetworkConversionError, tx::TransactionId};
 use kaspa_utils::networking::IpAddress;
 use std::{net::AddrParseError, num::TryFromIntError};
 use thiserror::Error;
 use workflow_core::channel::ChannelError;
 
-use crate::{api::ctl::RpcState, RpcHash, RpcTransactionId, SubmitBlockRejectReason};
+use crate::{
+    api::{ctl::RpcState, ops::RpcApiOps},
+    RpcHash, RpcNodeId, RpcTransactionId, SubmitBlockRejectReason,
+};
 
 #[derive(Clone, Debug, Error)]
 pub enum RpcError {
@@ -48,6 +52,9 @@ pub enum RpcError {
     #[error("Coinbase payload is above max length ({0}). Try to shorten the extra data.")]
     CoinbasePayloadLengthAboveMax(usize),
 
+    #[error("No pay address was provided and the node has no configured mining address (--miningaddr)")]
+    MissingPayAddress,
+
     #[error("Rejected transaction {0}: {1}")]
     RejectedTransaction(RpcTransactionId, String),
 
@@ -63,6 +70,9 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --acceptanceindex argument.")]
+    NoAcceptanceIndex,
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
@@ -75,12 +85,36 @@ pub enum RpcError {
     #[error("Method unavailable in safe mode. Run the node with --unsaferpc argument.")]
     UnavailableInSafeMode,
 
+    #[error("Method {0:?} is not allowed on this RPC endpoint.")]
+    MethodNotAllowed(RpcApiOps),
+
+    #[error("Rate limit of {1} {0:?} requests per second exceeded.")]
+    RateLimitExceeded(RpcApiOps, u32),
+
+    #[error("RPC server is busy, {0} requests are already being processed.")]
+    TooManyConcurrentRequests(usize),
+
+    #[error("Peer {0} is not connected.")]
+    PeerNotFound(RpcNodeId),
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
     #[error("IP {0} is not registered as banned.")]
     IpIsNotBanned(IpAddress),
 
+    #[error("Invalid prefix length {1} for IP {0}.")]
+    InvalidIpPrefixLength(IpAddress, u8),
+
+    #[error("Cannot ban IP range {0} because it contains some permanent connection.")]
+    IpRangeHasPermanentConnection(String),
+
+    #[error("IP range {0} is not registered as banned.")]
+    IpRangeIsNotBanned(String),
+
+    #[error("Cannot ban IP range {0} because it is too broad.")]
+    IpRangeTooBroad(String),
+
     #[error("Block {0} doesn't have any merger block.")]
     MergerNotFound(RpcHash),
 
@@ -90,6 +124,9 @@ pub enum RpcError {
     #[error(transparent)]
     AddressError(#[from] kaspa_addresses::AddressError),
 
+    #[error("address {address} belongs to the {found} network while the node is on the {expected} network")]
+    AddressNetworkMismatch { address: String, expected: Prefix, found: Prefix },
+
     #[error(transparent)]
     NetworkTypeError(#[from] kaspa_consensus_core::network::NetworkTypeError),
 
@@ -105,6 +142,9 @@ pub enum RpcError {
     #[error(transparent)]
     ConsensusError(#[from] kaspa_consensus_core::errors::consensus::ConsensusError),
 
+    #[error("Consensus serialization error: {0}")]
+    CodecError(#[from] kaspa_consensus_core::errors::codec::CodecError),
+
     #[error(transparent)]
     ScriptClassError(#[from] kaspa_txscript::script_class::Error),
 
@@ -126,6 +166,9 @@ pub enum RpcError {
     #[error(transparent)]
     SubnetParsingError(#[from] SubnetworkConversionError),
 
+    #[error(transparent)]
+    LimitExceeded(#[from] LimitExceeded),
+
     #[error(transparent)]
     WasmError(#[from] workflow_wasm::error::Error),
 
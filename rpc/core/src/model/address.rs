@@ -1,9 +1,23 @@
-use crate::{RpcTransactionOutpoint, RpcUtxoEntry};
+use crate::{RpcError, RpcResult, RpcTransactionOutpoint, RpcUtxoEntry};
+use kaspa_addresses::Prefix;
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
 pub type RpcAddress = kaspa_addresses::Address;
 
+/// Verifies that `address` belongs to the network identified by `prefix`.
+pub fn validate_address_network(address: &RpcAddress, prefix: Prefix) -> RpcResult<()> {
+    if address.prefix != prefix {
+        return Err(RpcError::AddressNetworkMismatch { address: address.to_string(), expected: prefix, found: address.prefix });
+    }
+    Ok(())
+}
+
+/// Verifies that all `addresses` belong to the network identified by `prefix`, failing on the first address which does not.
+pub fn validate_addresses_network<'a>(addresses: impl IntoIterator<Item = &'a RpcAddress>, prefix: Prefix) -> RpcResult<()> {
+    addresses.into_iter().try_for_each(|address| validate_address_network(address, prefix))
+}
+
 /// Represents a UTXO entry of an address returned by the `GetUtxosByAddresses` RPC.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -58,3 +72,20 @@ impl Deserializer for RpcBalancesByAddressesEntry {
         Ok(Self { address, balance })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::Version;
+
+    #[test]
+    fn test_validate_addresses_network() {
+        let mainnet = RpcAddress::new(Prefix::Mainnet, Version::PubKey, &[0u8; 32]);
+        let testnet = RpcAddress::new(Prefix::Testnet, Version::PubKey, &[0u8; 32]);
+        assert!(validate_address_network(&mainnet, Prefix::Mainnet).is_ok());
+        assert!(validate_addresses_network([&mainnet, &testnet], Prefix::Testnet).is_err_and(|err| matches!(
+            err,
+            RpcError::AddressNetworkMismatch { expected: Prefix::Testnet, found: Prefix::Mainnet, .. }
+        )));
+    }
+}
@@ -1,5 +1,6 @@
 use super::RpcAddress;
 use super::RpcTransaction;
+use super::RpcTransactionId;
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
@@ -33,6 +34,75 @@ impl Deserializer for RpcMempoolEntry {
     }
 }
 
+/// Cumulative statistics of the in-mempool ancestors and descendants of a transaction (excluding the transaction itself)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionPackage {
+    pub ancestor_count: u64,
+    pub ancestor_fees: u64,
+    pub ancestor_mass: u64,
+    pub descendant_count: u64,
+    pub descendant_fees: u64,
+    pub descendant_mass: u64,
+}
+
+impl Serializer for RpcTransactionPackage {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(u64, &self.ancestor_count, writer)?;
+        store!(u64, &self.ancestor_fees, writer)?;
+        store!(u64, &self.ancestor_mass, writer)?;
+        store!(u64, &self.descendant_count, writer)?;
+        store!(u64, &self.descendant_fees, writer)?;
+        store!(u64, &self.descendant_mass, writer)
+    }
+}
+
+impl Deserializer for RpcTransactionPackage {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let ancestor_count = load!(u64, reader)?;
+        let ancestor_fees = load!(u64, reader)?;
+        let ancestor_mass = load!(u64, reader)?;
+        let descendant_count = load!(u64, reader)?;
+        let descendant_fees = load!(u64, reader)?;
+        let descendant_mass = load!(u64, reader)?;
+        Ok(Self { ancestor_count, ancestor_fees, ancestor_mass, descendant_count, descendant_fees, descendant_mass })
+    }
+}
+
+/// A transaction submitted to this node, kept in the mempool and periodically rebroadcast until accepted into a block or expired
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLocalTransaction {
+    pub transaction_id: RpcTransactionId,
+    pub added_at_daa_score: u64,
+    /// The DAA score past which the transaction expires, `None` if it never does
+    pub expire_daa_score: Option<u64>,
+    pub is_orphan: bool,
+}
+
+impl Serializer for RpcLocalTransaction {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(u64, &self.added_at_daa_score, writer)?;
+        store!(Option<u64>, &self.expire_daa_score, writer)?;
+        store!(bool, &self.is_orphan, writer)
+    }
+}
+
+impl Deserializer for RpcLocalTransaction {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let added_at_daa_score = load!(u64, reader)?;
+        let expire_daa_score = load!(Option<u64>, reader)?;
+        let is_orphan = load!(bool, reader)?;
+        Ok(Self { transaction_id, added_at_daa_score, expire_daa_score, is_orphan })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RpcMempoolEntryByAddress {
     pub address: RpcAddress,
@@ -79,6 +149,21 @@ cfg_if::cfg_if! {
                 transaction : ITransaction;
                 isOrphan : boolean;
             }
+
+            /**
+             * Cumulative statistics of the in-mempool ancestors and descendants
+             * of a transaction (excluding the transaction itself).
+             * 
+             * @category Node RPC
+             */
+            export interface ITransactionPackage {
+                ancestorCount : bigint;
+                ancestorFees : bigint;
+                ancestorMass : bigint;
+                descendantCount : bigint;
+                descendantFees : bigint;
+                descendantMass : bigint;
+            }
         "#;
     }
 }
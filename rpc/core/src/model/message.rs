@@ -75,7 +75,7 @@ impl Display for SubmitBlockRejectReason {
     }
 }
 
-#[derive(Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "lowercase")]
 #[serde(tag = "type", content = "reason")]
 #[borsh(use_discriminant = true)]
@@ -93,22 +93,27 @@ impl SubmitBlockReport {
 #[serde(rename_all = "camelCase")]
 pub struct SubmitBlockResponse {
     pub report: SubmitBlockReport,
+    /// Details of the rejection, e.g., the violated consensus rule along with the offending values
+    #[serde(default)]
+    pub error: Option<String>,
 }
 
 impl Serializer for SubmitBlockResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(SubmitBlockReport, &self.report, writer)?;
+        store!(Option<String>, &self.error, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for SubmitBlockResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let report = load!(SubmitBlockReport, reader)?;
+        let error = if payload_version > 1 { load!(Option<String>, reader)? } else { None };
 
-        Ok(Self { report })
+        Ok(Self { report, error })
     }
 }
 
@@ -119,21 +124,27 @@ impl Deserializer for SubmitBlockResponse {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetBlockTemplateRequest {
-    /// Which kaspa address should the coinbase block reward transaction pay into
-    pub pay_address: RpcAddress,
+    /// Which kaspa address should the coinbase block reward transaction pay into.
+    /// If missing, the node falls back to its configured mining address (`--miningaddr`)
+    pub pay_address: Option<RpcAddress>,
     // TODO: replace with hex serialization
     pub extra_data: RpcExtraData,
 }
 impl GetBlockTemplateRequest {
     pub fn new(pay_address: RpcAddress, extra_data: RpcExtraData) -> Self {
-        Self { pay_address, extra_data }
+        Self { pay_address: Some(pay_address), extra_data }
+    }
+
+    /// Requests a block template paying to the mining address configured by the node
+    pub fn with_default_pay_address(extra_data: RpcExtraData) -> Self {
+        Self { pay_address: None, extra_data }
     }
 }
 
 impl Serializer for GetBlockTemplateRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
-        store!(RpcAddress, &self.pay_address, writer)?;
+        store!(u16, &2, writer)?;
+        store!(Option<RpcAddress>, &self.pay_address, writer)?;
         store!(RpcExtraData, &self.extra_data, writer)?;
 
         Ok(())
@@ -142,8 +153,8 @@ impl Serializer for GetBlockTemplateRequest {
 
 impl Deserializer for GetBlockTemplateRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        let pay_address = load!(RpcAddress, reader)?;
+        let payload_version = load!(u16, reader)?;
+        let pay_address = if payload_version > 1 { load!(Option<RpcAddress>, reader)? } else { Some(load!(RpcAddress, reader)?) };
         let extra_data = load!(RpcExtraData, reader)?;
 
         Ok(Self { pay_address, extra_data })
@@ -159,14 +170,27 @@ pub struct GetBlockTemplateResponse {
     /// Callers are discouraged (but not forbidden) from solving blocks when kaspad is not synced.
     /// That is because when kaspad isn't in sync with the rest of the network there's a high
     /// chance the block will never be accepted, thus the solving effort would have been wasted.
+    /// Note that unless kaspad runs with `--enable-unsynced-mining`, blocks submitted while it
+    /// is not synced are rejected.
     pub is_synced: bool,
+    /// The reward earned by the block once merged: its subsidy plus the fees of its transactions.
+    /// Zero if reported by a node which predates this field.
+    pub block_reward: u64,
+    /// The subsidy part of `block_reward`. Zero if reported by a node which predates this field.
+    pub block_subsidy: u64,
+    /// The virtual DAA score the template was built on, which is also the DAA score of the block.
+    /// Zero if reported by a node which predates this field.
+    pub virtual_daa_score: u64,
 }
 
 impl Serializer for GetBlockTemplateResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         serialize!(RpcRawBlock, &self.block, writer)?;
         store!(bool, &self.is_synced, writer)?;
+        store!(u64, &self.block_reward, writer)?;
+        store!(u64, &self.block_subsidy, writer)?;
+        store!(u64, &self.virtual_daa_score, writer)?;
 
         Ok(())
     }
@@ -174,11 +198,13 @@ impl Serializer for GetBlockTemplateResponse {
 
 impl Deserializer for GetBlockTemplateResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let block = deserialize!(RpcRawBlock, reader)?;
         let is_synced = load!(bool, reader)?;
+        let (block_reward, block_subsidy, virtual_daa_score) =
+            if payload_version > 1 { (load!(u64, reader)?, load!(u64, reader)?, load!(u64, reader)?) } else { (0, 0, 0) };
 
-        Ok(Self { block, is_synced })
+        Ok(Self { block, is_synced, block_reward, block_subsidy, virtual_daa_score })
     }
 }
 
@@ -271,11 +297,19 @@ pub struct GetInfoResponse {
     pub is_synced: bool,
     pub has_notify_command: bool,
     pub has_message_id: bool,
+    /// The network the node is running on. `None` if reported by a node which predates this field
+    pub network_id: Option<RpcNetworkId>,
+    /// Whether RPC commands affecting the state of the node are enabled
+    pub unsafe_rpc: bool,
+    /// The sizing policy of the consensus caches (small, default, large or custom). `None` if reported by a node
+    /// which predates this field
+    #[serde(default)]
+    pub cache_policy: Option<String>,
 }
 
 impl Serializer for GetInfoResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
         store!(String, &self.p2p_id, writer)?;
         store!(u64, &self.mempool_size, writer)?;
         store!(String, &self.server_version, writer)?;
@@ -283,6 +317,9 @@ impl Serializer for GetInfoResponse {
         store!(bool, &self.is_synced, writer)?;
         store!(bool, &self.has_notify_command, writer)?;
         store!(bool, &self.has_message_id, writer)?;
+        store!(Option<RpcNetworkId>, &self.network_id, writer)?;
+        store!(bool, &self.unsafe_rpc, writer)?;
+        store!(Option<String>, &self.cache_policy, writer)?;
 
         Ok(())
     }
@@ -290,7 +327,7 @@ impl Serializer for GetInfoResponse {
 
 impl Deserializer for GetInfoResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let p2p_id = load!(String, reader)?;
         let mempool_size = load!(u64, reader)?;
         let server_version = load!(String, reader)?;
@@ -298,8 +335,22 @@ impl Deserializer for GetInfoResponse {
         let is_synced = load!(bool, reader)?;
         let has_notify_command = load!(bool, reader)?;
         let has_message_id = load!(bool, reader)?;
+        let (network_id, unsafe_rpc) =
+            if payload_version > 1 { (load!(Option<RpcNetworkId>, reader)?, load!(bool, reader)?) } else { (None, false) };
+        let cache_policy = if payload_version > 2 { load!(Option<String>, reader)? } else { None };
 
-        Ok(Self { p2p_id, mempool_size, server_version, is_utxo_indexed, is_synced, has_notify_command, has_message_id })
+        Ok(Self {
+            p2p_id,
+            mempool_size,
+            server_version,
+            is_utxo_indexed,
+            is_synced,
+            has_notify_command,
+            has_message_id,
+            network_id,
+            unsafe_rpc,
+            cache_policy,
+        })
     }
 }
 
@@ -325,45 +376,61 @@ impl Deserializer for GetCurrentNetworkRequest {
 #[serde(rename_all = "camelCase")]
 pub struct GetCurrentNetworkResponse {
     pub network: RpcNetworkType,
+    /// The full network name, including the suffix of testnets. `None` if reported by a node which predates this field
+    pub network_id: Option<RpcNetworkId>,
 }
 
 impl GetCurrentNetworkResponse {
-    pub fn new(network: RpcNetworkType) -> Self {
-        Self { network }
+    pub fn new(network: RpcNetworkType, network_id: Option<RpcNetworkId>) -> Self {
+        Self { network, network_id }
     }
 }
 
 impl Serializer for GetCurrentNetworkResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcNetworkType, &self.network, writer)?;
+        store!(Option<RpcNetworkId>, &self.network_id, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetCurrentNetworkResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let network = load!(RpcNetworkType, reader)?;
-        Ok(Self { network })
+        let network_id = if payload_version > 1 { load!(Option<RpcNetworkId>, reader)? } else { None };
+        Ok(Self { network, network_id })
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct GetPeerAddressesRequest {}
+pub struct GetPeerAddressesRequest {
+    /// Also return the persisted statistics of previously connected peers
+    #[serde(default)]
+    pub verbose: bool,
+}
+
+impl GetPeerAddressesRequest {
+    pub fn new(verbose: bool) -> Self {
+        Self { verbose }
+    }
+}
 
 impl Serializer for GetPeerAddressesRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
+        store!(bool, &self.verbose, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetPeerAddressesRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        Ok(Self {})
+        let payload_version = load!(u16, reader)?;
+        let verbose = if payload_version > 1 { load!(bool, reader)? } else { false };
+        Ok(Self { verbose })
     }
 }
 
@@ -372,29 +439,34 @@ impl Deserializer for GetPeerAddressesRequest {
 pub struct GetPeerAddressesResponse {
     pub known_addresses: Vec<RpcPeerAddress>,
     pub banned_addresses: Vec<RpcIpAddress>,
+    /// Persisted statistics of previously connected peers, only populated in verbose mode
+    #[serde(default)]
+    pub peer_stats: Vec<RpcPeerStats>,
 }
 
 impl GetPeerAddressesResponse {
-    pub fn new(known_addresses: Vec<RpcPeerAddress>, banned_addresses: Vec<RpcIpAddress>) -> Self {
-        Self { known_addresses, banned_addresses }
+    pub fn new(known_addresses: Vec<RpcPeerAddress>, banned_addresses: Vec<RpcIpAddress>, peer_stats: Vec<RpcPeerStats>) -> Self {
+        Self { known_addresses, banned_addresses, peer_stats }
     }
 }
 
 impl Serializer for GetPeerAddressesResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Vec<RpcPeerAddress>, &self.known_addresses, writer)?;
         store!(Vec<RpcIpAddress>, &self.banned_addresses, writer)?;
+        serialize!(Vec<RpcPeerStats>, &self.peer_stats, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetPeerAddressesResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let known_addresses = load!(Vec<RpcPeerAddress>, reader)?;
         let banned_addresses = load!(Vec<RpcIpAddress>, reader)?;
-        Ok(Self { known_addresses, banned_addresses })
+        let peer_stats = if payload_version > 1 { deserialize!(Vec<RpcPeerStats>, reader)? } else { vec![] };
+        Ok(Self { known_addresses, banned_addresses, peer_stats })
     }
 }
 
@@ -485,27 +557,31 @@ impl Deserializer for GetMempoolEntryRequest {
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntryResponse {
     pub mempool_entry: RpcMempoolEntry,
+    /// In-mempool ancestors and descendants of the transaction. Orphans have no package.
+    pub package: Option<RpcTransactionPackage>,
 }
 
 impl GetMempoolEntryResponse {
-    pub fn new(mempool_entry: RpcMempoolEntry) -> Self {
-        Self { mempool_entry }
+    pub fn new(mempool_entry: RpcMempoolEntry, package: Option<RpcTransactionPackage>) -> Self {
+        Self { mempool_entry, package }
     }
 }
 
 impl Serializer for GetMempoolEntryResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         serialize!(RpcMempoolEntry, &self.mempool_entry, writer)?;
+        serialize!(Option<RpcTransactionPackage>, &self.package, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetMempoolEntryResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let mempool_entry = deserialize!(RpcMempoolEntry, reader)?;
-        Ok(Self { mempool_entry })
+        let package = if payload_version > 1 { deserialize!(Option<RpcTransactionPackage>, reader)? } else { None };
+        Ok(Self { mempool_entry, package })
     }
 }
 
@@ -515,19 +591,22 @@ pub struct GetMempoolEntriesRequest {
     pub include_orphan_pool: bool,
     // TODO: replace with `include_transaction_pool`
     pub filter_transaction_pool: bool,
+    /// If set, only the entries of transactions belonging to this subnetwork are returned
+    pub subnetwork_id: Option<RpcSubnetworkId>,
 }
 
 impl GetMempoolEntriesRequest {
-    pub fn new(include_orphan_pool: bool, filter_transaction_pool: bool) -> Self {
-        Self { include_orphan_pool, filter_transaction_pool }
+    pub fn new(include_orphan_pool: bool, filter_transaction_pool: bool, subnetwork_id: Option<RpcSubnetworkId>) -> Self {
+        Self { include_orphan_pool, filter_transaction_pool, subnetwork_id }
     }
 }
 
 impl Serializer for GetMempoolEntriesRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(bool, &self.include_orphan_pool, writer)?;
         store!(bool, &self.filter_transaction_pool, writer)?;
+        store!(Option<RpcSubnetworkId>, &self.subnetwork_id, writer)?;
 
         Ok(())
     }
@@ -535,11 +614,12 @@ impl Serializer for GetMempoolEntriesRequest {
 
 impl Deserializer for GetMempoolEntriesRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let include_orphan_pool = load!(bool, reader)?;
         let filter_transaction_pool = load!(bool, reader)?;
+        let subnetwork_id = if payload_version > 1 { load!(Option<RpcSubnetworkId>, reader)? } else { None };
 
-        Ok(Self { include_orphan_pool, filter_transaction_pool })
+        Ok(Self { include_orphan_pool, filter_transaction_pool, subnetwork_id })
     }
 }
 
@@ -1506,18 +1586,32 @@ impl Deserializer for GetUtxosByAddressesResponse {
 #[serde(rename_all = "camelCase")]
 pub struct BanRequest {
     pub ip: RpcIpAddress,
+    /// When set, the whole CIDR range `ip/prefix_length` is banned instead of the single IP
+    pub prefix_length: Option<u8>,
+    /// Ban duration in seconds, the node default (24 hours) is used when unset
+    pub duration: Option<u64>,
+    /// Bans the IP (or range) permanently, overriding `duration`
+    #[serde(default)]
+    pub is_permanent: bool,
 }
 
 impl BanRequest {
     pub fn new(ip: RpcIpAddress) -> Self {
-        Self { ip }
+        Self { ip, prefix_length: None, duration: None, is_permanent: false }
+    }
+
+    pub fn new_range(ip: RpcIpAddress, prefix_length: Option<u8>, duration: Option<u64>, is_permanent: bool) -> Self {
+        Self { ip, prefix_length, duration, is_permanent }
     }
 }
 
 impl Serializer for BanRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcIpAddress, &self.ip, writer)?;
+        store!(Option<u8>, &self.prefix_length, writer)?;
+        store!(Option<u64>, &self.duration, writer)?;
+        store!(bool, &self.is_permanent, writer)?;
 
         Ok(())
     }
@@ -1525,10 +1619,15 @@ impl Serializer for BanRequest {
 
 impl Deserializer for BanRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let ip = load!(RpcIpAddress, reader)?;
+        let (prefix_length, duration, is_permanent) = if payload_version > 1 {
+            (load!(Option<u8>, reader)?, load!(Option<u64>, reader)?, load!(bool, reader)?)
+        } else {
+            (None, None, false)
+        };
 
-        Ok(Self { ip })
+        Ok(Self { ip, prefix_length, duration, is_permanent })
     }
 }
 
@@ -1554,18 +1653,25 @@ impl Deserializer for BanResponse {
 #[serde(rename_all = "camelCase")]
 pub struct UnbanRequest {
     pub ip: RpcIpAddress,
+    /// When set, lifts the ban of the CIDR range `ip/prefix_length` instead of the single IP ban
+    pub prefix_length: Option<u8>,
 }
 
 impl UnbanRequest {
     pub fn new(ip: RpcIpAddress) -> Self {
-        Self { ip }
+        Self { ip, prefix_length: None }
+    }
+
+    pub fn new_range(ip: RpcIpAddress, prefix_length: Option<u8>) -> Self {
+        Self { ip, prefix_length }
     }
 }
 
 impl Serializer for UnbanRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcIpAddress, &self.ip, writer)?;
+        store!(Option<u8>, &self.prefix_length, writer)?;
 
         Ok(())
     }
@@ -1573,10 +1679,11 @@ impl Serializer for UnbanRequest {
 
 impl Deserializer for UnbanRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let ip = load!(RpcIpAddress, reader)?;
+        let prefix_length = if payload_version > 1 { load!(Option<u8>, reader)? } else { None };
 
-        Ok(Self { ip })
+        Ok(Self { ip, prefix_length })
     }
 }
 
@@ -1956,17 +2063,21 @@ pub struct GetMetricsRequest {
     pub consensus_metrics: bool,
     pub storage_metrics: bool,
     pub custom_metrics: bool,
+    /// Per subsystem allocation counters, available only on nodes built with the `alloc-tracking` feature
+    #[serde(default)]
+    pub allocation_metrics: bool,
 }
 
 impl Serializer for GetMetricsRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(bool, &self.process_metrics, writer)?;
         store!(bool, &self.connection_metrics, writer)?;
         store!(bool, &self.bandwidth_metrics, writer)?;
         store!(bool, &self.consensus_metrics, writer)?;
         store!(bool, &self.storage_metrics, writer)?;
         store!(bool, &self.custom_metrics, writer)?;
+        store!(bool, &self.allocation_metrics, writer)?;
 
         Ok(())
     }
@@ -1974,15 +2085,24 @@ impl Serializer for GetMetricsRequest {
 
 impl Deserializer for GetMetricsRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let process_metrics = load!(bool, reader)?;
         let connection_metrics = load!(bool, reader)?;
         let bandwidth_metrics = load!(bool, reader)?;
         let consensus_metrics = load!(bool, reader)?;
         let storage_metrics = load!(bool, reader)?;
         let custom_metrics = load!(bool, reader)?;
+        let allocation_metrics = if payload_version > 1 { load!(bool, reader)? } else { false };
 
-        Ok(Self { process_metrics, connection_metrics, bandwidth_metrics, consensus_metrics, storage_metrics, custom_metrics })
+        Ok(Self {
+            process_metrics,
+            connection_metrics,
+            bandwidth_metrics,
+            consensus_metrics,
+            storage_metrics,
+            custom_metrics,
+            allocation_metrics,
+        })
     }
 }
 
@@ -2055,11 +2175,18 @@ pub struct ConnectionMetrics {
     pub json_handshake_failures: u64,
 
     pub active_peers: u32,
+
+    /// Notification subscriptions of every RPC connection registered as a notification listener
+    #[serde(default)]
+    pub connection_subscriptions: Vec<RpcConnectionSubscriptions>,
+    /// Number of notification listeners of closed connections found and unregistered by the periodic audit
+    #[serde(default)]
+    pub leaked_listeners: u64,
 }
 
 impl Serializer for ConnectionMetrics {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(u32, &self.borsh_live_connections, writer)?;
         store!(u64, &self.borsh_connection_attempts, writer)?;
         store!(u64, &self.borsh_handshake_failures, writer)?;
@@ -2067,6 +2194,8 @@ impl Serializer for ConnectionMetrics {
         store!(u64, &self.json_connection_attempts, writer)?;
         store!(u64, &self.json_handshake_failures, writer)?;
         store!(u32, &self.active_peers, writer)?;
+        serialize!(Vec<RpcConnectionSubscriptions>, &self.connection_subscriptions, writer)?;
+        store!(u64, &self.leaked_listeners, writer)?;
 
         Ok(())
     }
@@ -2074,7 +2203,7 @@ impl Serializer for ConnectionMetrics {
 
 impl Deserializer for ConnectionMetrics {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let borsh_live_connections = load!(u32, reader)?;
         let borsh_connection_attempts = load!(u64, reader)?;
         let borsh_handshake_failures = load!(u64, reader)?;
@@ -2082,6 +2211,11 @@ impl Deserializer for ConnectionMetrics {
         let json_connection_attempts = load!(u64, reader)?;
         let json_handshake_failures = load!(u64, reader)?;
         let active_peers = load!(u32, reader)?;
+        let (connection_subscriptions, leaked_listeners) = if payload_version > 1 {
+            (deserialize!(Vec<RpcConnectionSubscriptions>, reader)?, load!(u64, reader)?)
+        } else {
+            (vec![], 0)
+        };
 
         Ok(Self {
             borsh_live_connections,
@@ -2091,10 +2225,48 @@ impl Deserializer for ConnectionMetrics {
             json_connection_attempts,
             json_handshake_failures,
             active_peers,
+            connection_subscriptions,
+            leaked_listeners,
         })
     }
 }
 
+/// The notification subscriptions of an RPC connection
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcConnectionSubscriptions {
+    /// The RPC server of the connection: `grpc`, `wrpc-borsh` or `wrpc-json`
+    pub protocol: String,
+    pub connection: String,
+    pub listener_id: u64,
+    /// Number of event types the connection is subscribed to
+    pub subscriptions: u32,
+}
+
+impl Serializer for RpcConnectionSubscriptions {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.protocol, writer)?;
+        store!(String, &self.connection, writer)?;
+        store!(u64, &self.listener_id, writer)?;
+        store!(u32, &self.subscriptions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcConnectionSubscriptions {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let protocol = load!(String, reader)?;
+        let connection = load!(String, reader)?;
+        let listener_id = load!(u64, reader)?;
+        let subscriptions = load!(u32, reader)?;
+
+        Ok(Self { protocol, connection, listener_id, subscriptions })
+    }
+}
+
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BandwidthMetrics {
@@ -2106,11 +2278,24 @@ pub struct BandwidthMetrics {
     pub p2p_bytes_rx: u64,
     pub grpc_bytes_tx: u64,
     pub grpc_bytes_rx: u64,
+    /// Number of P2P messages sent and received, over all message types
+    pub p2p_messages_tx: u64,
+    pub p2p_messages_rx: u64,
+    /// Number of block and transaction announcements skipped since the peer already knew them (hits)
+    /// or sent since it did not (misses), over all peers
+    #[serde(default)]
+    pub p2p_known_blocks_hits: u64,
+    #[serde(default)]
+    pub p2p_known_blocks_misses: u64,
+    #[serde(default)]
+    pub p2p_known_transactions_hits: u64,
+    #[serde(default)]
+    pub p2p_known_transactions_misses: u64,
 }
 
 impl Serializer for BandwidthMetrics {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
         store!(u64, &self.borsh_bytes_tx, writer)?;
         store!(u64, &self.borsh_bytes_rx, writer)?;
         store!(u64, &self.json_bytes_tx, writer)?;
@@ -2119,6 +2304,12 @@ impl Serializer for BandwidthMetrics {
         store!(u64, &self.p2p_bytes_rx, writer)?;
         store!(u64, &self.grpc_bytes_tx, writer)?;
         store!(u64, &self.grpc_bytes_rx, writer)?;
+        store!(u64, &self.p2p_messages_tx, writer)?;
+        store!(u64, &self.p2p_messages_rx, writer)?;
+        store!(u64, &self.p2p_known_blocks_hits, writer)?;
+        store!(u64, &self.p2p_known_blocks_misses, writer)?;
+        store!(u64, &self.p2p_known_transactions_hits, writer)?;
+        store!(u64, &self.p2p_known_transactions_misses, writer)?;
 
         Ok(())
     }
@@ -2126,7 +2317,7 @@ impl Serializer for BandwidthMetrics {
 
 impl Deserializer for BandwidthMetrics {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let borsh_bytes_tx = load!(u64, reader)?;
         let borsh_bytes_rx = load!(u64, reader)?;
         let json_bytes_tx = load!(u64, reader)?;
@@ -2135,6 +2326,13 @@ impl Deserializer for BandwidthMetrics {
         let p2p_bytes_rx = load!(u64, reader)?;
         let grpc_bytes_tx = load!(u64, reader)?;
         let grpc_bytes_rx = load!(u64, reader)?;
+        let (p2p_messages_tx, p2p_messages_rx) = if payload_version > 1 { (load!(u64, reader)?, load!(u64, reader)?) } else { (0, 0) };
+        let (p2p_known_blocks_hits, p2p_known_blocks_misses, p2p_known_transactions_hits, p2p_known_transactions_misses) =
+            if payload_version > 2 {
+                (load!(u64, reader)?, load!(u64, reader)?, load!(u64, reader)?, load!(u64, reader)?)
+            } else {
+                (0, 0, 0, 0)
+            };
 
         Ok(Self {
             borsh_bytes_tx,
@@ -2145,10 +2343,54 @@ impl Deserializer for BandwidthMetrics {
             p2p_bytes_rx,
             grpc_bytes_tx,
             grpc_bytes_rx,
+            p2p_messages_tx,
+            p2p_messages_rx,
+            p2p_known_blocks_hits,
+            p2p_known_blocks_misses,
+            p2p_known_transactions_hits,
+            p2p_known_transactions_misses,
         })
     }
 }
 
+/// Latency percentiles of a consensus processing stage since node startup. Latencies are bucketed by powers of two,
+/// hence the percentiles are upper bounds
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingStageLatencyMetrics {
+    pub stage: String,
+    pub sample_count: u64,
+    pub p50_micros: u64,
+    pub p95_micros: u64,
+    pub p99_micros: u64,
+}
+
+impl Serializer for ProcessingStageLatencyMetrics {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.stage, writer)?;
+        store!(u64, &self.sample_count, writer)?;
+        store!(u64, &self.p50_micros, writer)?;
+        store!(u64, &self.p95_micros, writer)?;
+        store!(u64, &self.p99_micros, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for ProcessingStageLatencyMetrics {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let stage = load!(String, reader)?;
+        let sample_count = load!(u64, reader)?;
+        let p50_micros = load!(u64, reader)?;
+        let p95_micros = load!(u64, reader)?;
+        let p99_micros = load!(u64, reader)?;
+
+        Ok(Self { stage, sample_count, p50_micros, p95_micros, p99_micros })
+    }
+}
+
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConsensusMetrics {
@@ -2169,11 +2411,23 @@ pub struct ConsensusMetrics {
     pub network_past_median_time: u64,
     pub network_virtual_parent_hashes_count: u32,
     pub network_virtual_daa_score: u64,
+
+    #[serde(default)]
+    pub node_relay_block_submissions_in_flight: u32,
+    #[serde(default)]
+    pub node_relay_block_submissions_waiting: u32,
+    #[serde(default)]
+    pub node_ibd_block_submissions_in_flight: u32,
+    #[serde(default)]
+    pub node_ibd_block_submissions_waiting: u32,
+
+    #[serde(default)]
+    pub node_stage_latencies: Vec<ProcessingStageLatencyMetrics>,
 }
 
 impl Serializer for ConsensusMetrics {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
         store!(u64, &self.node_blocks_submitted_count, writer)?;
         store!(u64, &self.node_headers_processed_count, writer)?;
         store!(u64, &self.node_dependencies_processed_count, writer)?;
@@ -2189,6 +2443,11 @@ impl Serializer for ConsensusMetrics {
         store!(u64, &self.network_past_median_time, writer)?;
         store!(u32, &self.network_virtual_parent_hashes_count, writer)?;
         store!(u64, &self.network_virtual_daa_score, writer)?;
+        store!(u32, &self.node_relay_block_submissions_in_flight, writer)?;
+        store!(u32, &self.node_relay_block_submissions_waiting, writer)?;
+        store!(u32, &self.node_ibd_block_submissions_in_flight, writer)?;
+        store!(u32, &self.node_ibd_block_submissions_waiting, writer)?;
+        serialize!(Vec<ProcessingStageLatencyMetrics>, &self.node_stage_latencies, writer)?;
 
         Ok(())
     }
@@ -2196,7 +2455,7 @@ impl Serializer for ConsensusMetrics {
 
 impl Deserializer for ConsensusMetrics {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let node_blocks_submitted_count = load!(u64, reader)?;
         let node_headers_processed_count = load!(u64, reader)?;
         let node_dependencies_processed_count = load!(u64, reader)?;
@@ -2212,6 +2471,18 @@ impl Deserializer for ConsensusMetrics {
         let network_past_median_time = load!(u64, reader)?;
         let network_virtual_parent_hashes_count = load!(u32, reader)?;
         let network_virtual_daa_score = load!(u64, reader)?;
+        let (
+            node_relay_block_submissions_in_flight,
+            node_relay_block_submissions_waiting,
+            node_ibd_block_submissions_in_flight,
+            node_ibd_block_submissions_waiting,
+        ) = if payload_version > 1 {
+            (load!(u32, reader)?, load!(u32, reader)?, load!(u32, reader)?, load!(u32, reader)?)
+        } else {
+            (0, 0, 0, 0)
+        };
+        let node_stage_latencies =
+            if payload_version > 2 { deserialize!(Vec<ProcessingStageLatencyMetrics>, reader)? } else { vec![] };
 
         Ok(Self {
             node_blocks_submitted_count,
@@ -2229,6 +2500,11 @@ impl Deserializer for ConsensusMetrics {
             network_past_median_time,
             network_virtual_parent_hashes_count,
             network_virtual_daa_score,
+            node_relay_block_submissions_in_flight,
+            node_relay_block_submissions_waiting,
+            node_ibd_block_submissions_in_flight,
+            node_ibd_block_submissions_waiting,
+            node_stage_latencies,
         })
     }
 }
@@ -2257,6 +2533,71 @@ impl Deserializer for StorageMetrics {
     }
 }
 
+/// The allocation counters of a single subsystem, see `kaspa_utils::alloc_tracking`
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemAllocationMetrics {
+    pub name: String,
+    /// Bytes allocated by the subsystem and not yet freed
+    pub live_bytes: u64,
+    pub allocated_bytes: u64,
+    pub freed_bytes: u64,
+    pub allocation_count: u64,
+    pub deallocation_count: u64,
+}
+
+impl Serializer for SubsystemAllocationMetrics {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.name, writer)?;
+        store!(u64, &self.live_bytes, writer)?;
+        store!(u64, &self.allocated_bytes, writer)?;
+        store!(u64, &self.freed_bytes, writer)?;
+        store!(u64, &self.allocation_count, writer)?;
+        store!(u64, &self.deallocation_count, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for SubsystemAllocationMetrics {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let name = load!(String, reader)?;
+        let live_bytes = load!(u64, reader)?;
+        let allocated_bytes = load!(u64, reader)?;
+        let freed_bytes = load!(u64, reader)?;
+        let allocation_count = load!(u64, reader)?;
+        let deallocation_count = load!(u64, reader)?;
+
+        Ok(Self { name, live_bytes, allocated_bytes, freed_bytes, allocation_count, deallocation_count })
+    }
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationMetrics {
+    pub subsystems: Vec<SubsystemAllocationMetrics>,
+}
+
+impl Serializer for AllocationMetrics {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<SubsystemAllocationMetrics>, &self.subsystems, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for AllocationMetrics {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let subsystems = deserialize!(Vec<SubsystemAllocationMetrics>, reader)?;
+
+        Ok(Self { subsystems })
+    }
+}
+
 // TODO: Custom metrics dictionary
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum CustomMetricValue {
@@ -2290,6 +2631,9 @@ pub struct GetMetricsResponse {
     pub storage_metrics: Option<StorageMetrics>,
     // TODO: this is currently a placeholder
     pub custom_metrics: Option<HashMap<String, CustomMetricValue>>,
+    /// `None` unless requested from a node built with the `alloc-tracking` feature
+    #[serde(default)]
+    pub allocation_metrics: Option<AllocationMetrics>,
 }
 
 impl GetMetricsResponse {
@@ -2301,6 +2645,7 @@ impl GetMetricsResponse {
         consensus_metrics: Option<ConsensusMetrics>,
         storage_metrics: Option<StorageMetrics>,
         custom_metrics: Option<HashMap<String, CustomMetricValue>>,
+        allocation_metrics: Option<AllocationMetrics>,
     ) -> Self {
         Self {
             process_metrics,
@@ -2310,13 +2655,14 @@ impl GetMetricsResponse {
             storage_metrics,
             server_time,
             custom_metrics,
+            allocation_metrics,
         }
     }
 }
 
 impl Serializer for GetMetricsResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(u64, &self.server_time, writer)?;
         serialize!(Option<ProcessMetrics>, &self.process_metrics, writer)?;
         serialize!(Option<ConnectionMetrics>, &self.connection_metrics, writer)?;
@@ -2324,6 +2670,7 @@ impl Serializer for GetMetricsResponse {
         serialize!(Option<ConsensusMetrics>, &self.consensus_metrics, writer)?;
         serialize!(Option<StorageMetrics>, &self.storage_metrics, writer)?;
         serialize!(Option<HashMap<String, CustomMetricValue>>, &self.custom_metrics, writer)?;
+        serialize!(Option<AllocationMetrics>, &self.allocation_metrics, writer)?;
 
         Ok(())
     }
@@ -2331,7 +2678,7 @@ impl Serializer for GetMetricsResponse {
 
 impl Deserializer for GetMetricsResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let server_time = load!(u64, reader)?;
         let process_metrics = deserialize!(Option<ProcessMetrics>, reader)?;
         let connection_metrics = deserialize!(Option<ConnectionMetrics>, reader)?;
@@ -2339,6 +2686,7 @@ impl Deserializer for GetMetricsResponse {
         let consensus_metrics = deserialize!(Option<ConsensusMetrics>, reader)?;
         let storage_metrics = deserialize!(Option<StorageMetrics>, reader)?;
         let custom_metrics = deserialize!(Option<HashMap<String, CustomMetricValue>>, reader)?;
+        let allocation_metrics = if payload_version > 1 { deserialize!(Option<AllocationMetrics>, reader)? } else { None };
 
         Ok(Self {
             server_time,
@@ -2348,6 +2696,7 @@ impl Deserializer for GetMetricsResponse {
             consensus_metrics,
             storage_metrics,
             custom_metrics,
+            allocation_metrics,
         })
     }
 }
@@ -2451,21 +2800,26 @@ impl Deserializer for GetSyncStatusRequest {
 #[serde(rename_all = "camelCase")]
 pub struct GetSyncStatusResponse {
     pub is_synced: bool,
+    /// Detailed progress of the running IBD
+    #[serde(default)]
+    pub progress: RpcSyncProgress,
 }
 
 impl Serializer for GetSyncStatusResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(bool, &self.is_synced, writer)?;
+        serialize!(RpcSyncProgress, &self.progress, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetSyncStatusResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let is_synced = load!(bool, reader)?;
-        Ok(Self { is_synced })
+        let progress = if payload_version > 1 { deserialize!(RpcSyncProgress, reader)? } else { Default::default() };
+        Ok(Self { is_synced, progress })
     }
 }
 
@@ -2666,147 +3020,776 @@ impl Deserializer for GetCurrentBlockColorResponse {
     }
 }
 
-// ----------------------------------------------------------------------------
-// Subscriptions & notifications
-// ----------------------------------------------------------------------------
-
-// ~~~~~~~~~~~~~~~~~~~~~~
-// BlockAddedNotification
-
-/// NotifyBlockAddedRequest registers this connection for blockAdded notifications.
-///
-/// See: BlockAddedNotification
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyBlockAddedRequest {
-    pub command: Command,
-}
-impl NotifyBlockAddedRequest {
-    pub fn new(command: Command) -> Self {
-        Self { command }
-    }
-}
+pub struct GetP2pStatsRequest {}
 
-impl Serializer for NotifyBlockAddedRequest {
+impl Serializer for GetP2pStatsRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
-        store!(Command, &self.command, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for NotifyBlockAddedRequest {
+impl Deserializer for GetP2pStatsRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        let command = load!(Command, reader)?;
-        Ok(Self { command })
+        Ok(Self {})
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyBlockAddedResponse {}
+pub struct GetP2pStatsResponse {
+    /// Traffic statistics of all P2P message types which were sent or received at least once
+    pub message_stats: Vec<RpcP2pMessageStats>,
+    /// The connected peers which contributed the most blocks and headers during their current connection
+    #[serde(default)]
+    pub top_peers: Vec<RpcPeerContribution>,
+}
 
-impl Serializer for NotifyBlockAddedResponse {
+impl Serializer for GetP2pStatsResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
+        serialize!(Vec<RpcP2pMessageStats>, &self.message_stats, writer)?;
+        serialize!(Vec<RpcPeerContribution>, &self.top_peers, writer)?;
+
         Ok(())
     }
 }
 
-impl Deserializer for NotifyBlockAddedResponse {
+impl Deserializer for GetP2pStatsResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        Ok(Self {})
+        let payload_version = load!(u16, reader)?;
+        let message_stats = deserialize!(Vec<RpcP2pMessageStats>, reader)?;
+        let top_peers = if payload_version > 1 { deserialize!(Vec<RpcPeerContribution>, reader)? } else { vec![] };
+
+        Ok(Self { message_stats, top_peers })
     }
 }
 
-/// BlockAddedNotification is sent whenever a blocks has been added (NOT accepted)
-/// into the DAG.
-///
-/// See: NotifyBlockAddedRequest
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct BlockAddedNotification {
-    pub block: Arc<RpcBlock>,
-}
+pub struct GetProcessingStatsRequest {}
 
-impl Serializer for BlockAddedNotification {
+impl Serializer for GetProcessingStatsRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
-        serialize!(RpcBlock, &self.block, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for BlockAddedNotification {
+impl Deserializer for GetProcessingStatsRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        let block = deserialize!(RpcBlock, reader)?;
-        Ok(Self { block: block.into() })
+        Ok(Self {})
     }
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// VirtualChainChangedNotification
-
-// NotifyVirtualChainChangedRequest registers this connection for
-// virtualDaaScoreChanged notifications.
-//
-// See: VirtualChainChangedNotification
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyVirtualChainChangedRequest {
-    pub include_accepted_transaction_ids: bool,
-    pub command: Command,
-}
-
-impl NotifyVirtualChainChangedRequest {
-    pub fn new(include_accepted_transaction_ids: bool, command: Command) -> Self {
-        Self { include_accepted_transaction_ids, command }
-    }
-}
-
-impl Serializer for NotifyVirtualChainChangedRequest {
+pub struct GetProcessingStatsResponse {
+    // Counters accumulated since node startup
+    pub blocks_submitted_count: u64,
+    pub headers_processed_count: u64,
+    pub dependencies_processed_count: u64,
+    pub bodies_processed_count: u64,
+    pub transactions_processed_count: u64,
+    pub chain_blocks_processed_count: u64,
+    pub chain_disqualified_count: u64,
+    pub mass_processed_count: u64,
+
+    /// Length of the interval the rates below were measured over, which is
+    /// the time elapsed since the previous call (or since node startup)
+    pub interval_millis: u64,
+    // Per-second processing rates over the interval
+    pub headers_per_second: f64,
+    pub bodies_per_second: f64,
+    pub transactions_per_second: f64,
+    pub dependencies_per_second: f64,
+}
+
+impl Serializer for GetProcessingStatsResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
-        store!(bool, &self.include_accepted_transaction_ids, writer)?;
-        store!(Command, &self.command, writer)?;
+        store!(u64, &self.blocks_submitted_count, writer)?;
+        store!(u64, &self.headers_processed_count, writer)?;
+        store!(u64, &self.dependencies_processed_count, writer)?;
+        store!(u64, &self.bodies_processed_count, writer)?;
+        store!(u64, &self.transactions_processed_count, writer)?;
+        store!(u64, &self.chain_blocks_processed_count, writer)?;
+        store!(u64, &self.chain_disqualified_count, writer)?;
+        store!(u64, &self.mass_processed_count, writer)?;
+        store!(u64, &self.interval_millis, writer)?;
+        store!(f64, &self.headers_per_second, writer)?;
+        store!(f64, &self.bodies_per_second, writer)?;
+        store!(f64, &self.transactions_per_second, writer)?;
+        store!(f64, &self.dependencies_per_second, writer)?;
+
         Ok(())
     }
 }
 
-impl Deserializer for NotifyVirtualChainChangedRequest {
+impl Deserializer for GetProcessingStatsResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        let include_accepted_transaction_ids = load!(bool, reader)?;
-        let command = load!(Command, reader)?;
-        Ok(Self { include_accepted_transaction_ids, command })
+        let blocks_submitted_count = load!(u64, reader)?;
+        let headers_processed_count = load!(u64, reader)?;
+        let dependencies_processed_count = load!(u64, reader)?;
+        let bodies_processed_count = load!(u64, reader)?;
+        let transactions_processed_count = load!(u64, reader)?;
+        let chain_blocks_processed_count = load!(u64, reader)?;
+        let chain_disqualified_count = load!(u64, reader)?;
+        let mass_processed_count = load!(u64, reader)?;
+        let interval_millis = load!(u64, reader)?;
+        let headers_per_second = load!(f64, reader)?;
+        let bodies_per_second = load!(f64, reader)?;
+        let transactions_per_second = load!(f64, reader)?;
+        let dependencies_per_second = load!(f64, reader)?;
+
+        Ok(Self {
+            blocks_submitted_count,
+            headers_processed_count,
+            dependencies_processed_count,
+            bodies_processed_count,
+            transactions_processed_count,
+            chain_blocks_processed_count,
+            chain_disqualified_count,
+            mass_processed_count,
+            interval_millis,
+            headers_per_second,
+            bodies_per_second,
+            transactions_per_second,
+            dependencies_per_second,
+        })
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyVirtualChainChangedResponse {}
+pub struct GetPeerFlowStatesRequest {
+    /// When set, only the flows of the peer with this id are returned
+    pub peer_id: Option<RpcNodeId>,
+}
 
-impl Serializer for NotifyVirtualChainChangedResponse {
+impl Serializer for GetPeerFlowStatesRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
+        store!(Option<RpcNodeId>, &self.peer_id, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for NotifyVirtualChainChangedResponse {
+impl Deserializer for GetPeerFlowStatesRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        Ok(Self {})
+        let peer_id = load!(Option<RpcNodeId>, reader)?;
+        Ok(Self { peer_id })
     }
 }
 
-// VirtualChainChangedNotification is sent whenever the DAG's selected parent
-// chain had changed.
-//
-// See: NotifyVirtualChainChangedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPeerFlowStatesResponse {
+    pub peers: Vec<RpcPeerFlowStates>,
+}
+
+impl Serializer for GetPeerFlowStatesResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcPeerFlowStates>, &self.peers, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetPeerFlowStatesResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let peers = deserialize!(Vec<RpcPeerFlowStates>, reader)?;
+        Ok(Self { peers })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisconnectPeerRequest {
+    pub peer_id: RpcNodeId,
+}
+
+impl Serializer for DisconnectPeerRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcNodeId, &self.peer_id, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for DisconnectPeerRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let peer_id = load!(RpcNodeId, reader)?;
+        Ok(Self { peer_id })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisconnectPeerResponse {}
+
+impl Serializer for DisconnectPeerResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for DisconnectPeerResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPeerProtectedRequest {
+    pub peer_id: RpcNodeId,
+    pub is_protected: bool,
+}
+
+impl Serializer for SetPeerProtectedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcNodeId, &self.peer_id, writer)?;
+        store!(bool, &self.is_protected, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for SetPeerProtectedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let peer_id = load!(RpcNodeId, reader)?;
+        let is_protected = load!(bool, reader)?;
+        Ok(Self { peer_id, is_protected })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPeerProtectedResponse {}
+
+impl Serializer for SetPeerProtectedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for SetPeerProtectedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionAcceptanceDataRequest {
+    pub transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl Serializer for GetTransactionAcceptanceDataRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionAcceptanceDataRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        Ok(Self { transaction_ids })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionAcceptanceDataResponse {
+    /// The acceptance of the requested transactions accepted by the selected chain, in request order
+    pub acceptances: Vec<RpcTransactionAcceptance>,
+}
+
+impl Serializer for GetTransactionAcceptanceDataResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionAcceptance>, &self.acceptances, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionAcceptanceDataResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let acceptances = load!(Vec<RpcTransactionAcceptance>, reader)?;
+        Ok(Self { acceptances })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChainBlockByScoreRequest {
+    pub score: u64,
+    /// Look up by blue score rather than by DAA score
+    pub by_blue_score: bool,
+}
+
+impl Serializer for GetChainBlockByScoreRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.score, writer)?;
+        store!(bool, &self.by_blue_score, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetChainBlockByScoreRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let score = load!(u64, reader)?;
+        let by_blue_score = load!(bool, reader)?;
+        Ok(Self { score, by_blue_score })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChainBlockByScoreResponse {
+    pub block_hash: RpcHash,
+    pub daa_score: u64,
+    pub blue_score: u64,
+}
+
+impl Serializer for GetChainBlockByScoreResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcHash, &self.block_hash, writer)?;
+        store!(u64, &self.daa_score, writer)?;
+        store!(u64, &self.blue_score, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetChainBlockByScoreResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let block_hash = load!(RpcHash, reader)?;
+        let daa_score = load!(u64, reader)?;
+        let blue_score = load!(u64, reader)?;
+        Ok(Self { block_hash, daa_score, blue_score })
+    }
+}
+
+/// GetBlockSerializedRequest requests a block in its consensus (hashing preimage) serialization, hex-encoded.
+///
+/// See: [`kaspa_consensus_core::codec`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockSerializedRequest {
+    pub hash: RpcHash,
+}
+
+impl Serializer for GetBlockSerializedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcHash, &self.hash, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetBlockSerializedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let hash = load!(RpcHash, reader)?;
+        Ok(Self { hash })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockSerializedResponse {
+    pub block_hex: String,
+}
+
+impl Serializer for GetBlockSerializedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.block_hex, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetBlockSerializedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let block_hex = load!(String, reader)?;
+        Ok(Self { block_hex })
+    }
+}
+
+/// SubmitBlockSerializedRequest requests to submit a block given in its consensus (hashing preimage) serialization, hex-encoded.
+///
+/// See: [`SubmitBlockRequest`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitBlockSerializedRequest {
+    pub block_hex: String,
+    #[serde(alias = "allowNonDAABlocks")]
+    pub allow_non_daa_blocks: bool,
+}
+
+impl Serializer for SubmitBlockSerializedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.block_hex, writer)?;
+        store!(bool, &self.allow_non_daa_blocks, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for SubmitBlockSerializedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let block_hex = load!(String, reader)?;
+        let allow_non_daa_blocks = load!(bool, reader)?;
+        Ok(Self { block_hex, allow_non_daa_blocks })
+    }
+}
+
+/// See: [`SubmitBlockResponse`]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitBlockSerializedResponse {
+    pub report: SubmitBlockReport,
+    /// Details of the rejection, e.g., the violated consensus rule along with the offending values
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl From<SubmitBlockResponse> for SubmitBlockSerializedResponse {
+    fn from(response: SubmitBlockResponse) -> Self {
+        Self { report: response.report, error: response.error }
+    }
+}
+
+impl From<SubmitBlockSerializedResponse> for SubmitBlockResponse {
+    fn from(response: SubmitBlockSerializedResponse) -> Self {
+        Self { report: response.report, error: response.error }
+    }
+}
+
+impl Serializer for SubmitBlockSerializedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(SubmitBlockReport, &self.report, writer)?;
+        store!(Option<String>, &self.error, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for SubmitBlockSerializedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let report = load!(SubmitBlockReport, reader)?;
+        let error = load!(Option<String>, reader)?;
+        Ok(Self { report, error })
+    }
+}
+
+/// GetTransactionSerializedRequest requests a transaction in its consensus (hashing preimage) serialization, hex-encoded.
+///
+/// The transaction is looked up in the given block if any, otherwise in the mempool.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionSerializedRequest {
+    pub transaction_id: RpcTransactionId,
+    pub block_hash: Option<RpcHash>,
+}
+
+impl Serializer for GetTransactionSerializedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(Option<RpcHash>, &self.block_hash, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionSerializedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let block_hash = load!(Option<RpcHash>, reader)?;
+        Ok(Self { transaction_id, block_hash })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionSerializedResponse {
+    pub transaction_hex: String,
+}
+
+impl Serializer for GetTransactionSerializedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.transaction_hex, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionSerializedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_hex = load!(String, reader)?;
+        Ok(Self { transaction_hex })
+    }
+}
+
+/// SubmitTransactionSerializedRequest requests to submit a transaction given in its consensus (hashing preimage) serialization, hex-encoded.
+///
+/// See: [`SubmitTransactionRequest`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionSerializedRequest {
+    pub transaction_hex: String,
+    pub allow_orphan: bool,
+}
+
+impl Serializer for SubmitTransactionSerializedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.transaction_hex, writer)?;
+        store!(bool, &self.allow_orphan, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for SubmitTransactionSerializedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_hex = load!(String, reader)?;
+        let allow_orphan = load!(bool, reader)?;
+        Ok(Self { transaction_hex, allow_orphan })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionSerializedResponse {
+    pub transaction_id: RpcTransactionId,
+}
+
+impl Serializer for SubmitTransactionSerializedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for SubmitTransactionSerializedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        Ok(Self { transaction_id })
+    }
+}
+
+/// GetLocalTransactionsRequest requests the transactions submitted to this node which are still pending acceptance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLocalTransactionsRequest {}
+
+impl Serializer for GetLocalTransactionsRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetLocalTransactionsRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLocalTransactionsResponse {
+    pub transactions: Vec<RpcLocalTransaction>,
+}
+
+impl Serializer for GetLocalTransactionsResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcLocalTransaction>, &self.transactions, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetLocalTransactionsResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transactions = deserialize!(Vec<RpcLocalTransaction>, reader)?;
+        Ok(Self { transactions })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Subscriptions & notifications
+// ----------------------------------------------------------------------------
+
+// ~~~~~~~~~~~~~~~~~~~~~~
+// BlockAddedNotification
+
+/// NotifyBlockAddedRequest registers this connection for blockAdded notifications.
+///
+/// See: BlockAddedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyBlockAddedRequest {
+    pub command: Command,
+}
+impl NotifyBlockAddedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+impl Serializer for NotifyBlockAddedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyBlockAddedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyBlockAddedResponse {}
+
+impl Serializer for NotifyBlockAddedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyBlockAddedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// BlockAddedNotification is sent whenever a blocks has been added (NOT accepted)
+/// into the DAG.
+///
+/// See: NotifyBlockAddedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockAddedNotification {
+    pub block: Arc<RpcBlock>,
+}
+
+impl Serializer for BlockAddedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcBlock, &self.block, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for BlockAddedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let block = deserialize!(RpcBlock, reader)?;
+        Ok(Self { block: block.into() })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// VirtualChainChangedNotification
+
+// NotifyVirtualChainChangedRequest registers this connection for
+// virtualDaaScoreChanged notifications.
+//
+// See: VirtualChainChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyVirtualChainChangedRequest {
+    pub include_accepted_transaction_ids: bool,
+    pub command: Command,
+}
+
+impl NotifyVirtualChainChangedRequest {
+    pub fn new(include_accepted_transaction_ids: bool, command: Command) -> Self {
+        Self { include_accepted_transaction_ids, command }
+    }
+}
+
+impl Serializer for NotifyVirtualChainChangedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(bool, &self.include_accepted_transaction_ids, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyVirtualChainChangedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let include_accepted_transaction_ids = load!(bool, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { include_accepted_transaction_ids, command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyVirtualChainChangedResponse {}
+
+impl Serializer for NotifyVirtualChainChangedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyVirtualChainChangedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+// VirtualChainChangedNotification is sent whenever the DAG's selected parent
+// chain had changed.
+//
+// See: NotifyVirtualChainChangedRequest
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VirtualChainChangedNotification {
@@ -3398,6 +4381,111 @@ impl Deserializer for NewBlockTemplateNotification {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TransactionsEvictedNotification
+
+/// NotifyTransactionsEvictedRequest registers this connection for transactionsEvicted notifications.
+///
+/// See: TransactionsEvictedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyTransactionsEvictedRequest {
+    pub command: Command,
+}
+impl NotifyTransactionsEvictedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+impl Serializer for NotifyTransactionsEvictedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyTransactionsEvictedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyTransactionsEvictedResponse {}
+
+impl Serializer for NotifyTransactionsEvictedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyTransactionsEvictedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// TransactionsEvictedNotification is sent whenever transactions are evicted from the mempool
+/// without being accepted, e.g., since they were not accepted within the mempool expiry window.
+///
+/// See: NotifyTransactionsEvictedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionsEvictedNotification {
+    pub transaction_ids: Arc<Vec<RpcTransactionId>>,
+}
+
+impl Serializer for TransactionsEvictedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for TransactionsEvictedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        Ok(Self { transaction_ids: transaction_ids.into() })
+    }
+}
+
+/// NotificationsGapNotification is sent to a client not consuming its notifications fast enough
+/// whenever some had to be dropped. It precedes the next notification delivered after the gap.
+///
+/// Clients receiving it must assume they missed notifications of any subscribed type and re-query
+/// the state they track (e.g. UTXO balances or the virtual chain).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationsGapNotification {
+    /// Number of notifications dropped
+    pub dropped: u64,
+}
+
+impl Serializer for NotificationsGapNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.dropped, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotificationsGapNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let dropped = load!(u64, reader)?;
+        Ok(Self { dropped })
+    }
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
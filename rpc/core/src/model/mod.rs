@@ -14,6 +14,7 @@ pub mod network;
 pub mod peer;
 pub mod script_class;
 pub mod subnets;
+pub mod sync;
 mod tests;
 pub mod tx;
 
@@ -29,4 +30,5 @@ pub use message::*;
 pub use network::*;
 pub use peer::*;
 pub use subnets::*;
+pub use sync::*;
 pub use tx::*;
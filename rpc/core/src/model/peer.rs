@@ -1,6 +1,7 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_utils::networking::{ContextualNetAddress, IpAddress, NetAddress, PeerId};
 use serde::{Deserialize, Serialize};
+use workflow_serializer::prelude::*;
 
 pub type RpcNodeId = PeerId;
 pub type RpcIpAddress = IpAddress;
@@ -20,4 +21,213 @@ pub struct RpcPeerInfo {
     pub advertised_protocol_version: u32,
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
+    /// Hex encoded identity public key proven by the peer during the handshake, if any
+    pub identity: Option<String>,
+}
+
+/// P2P traffic statistics of a single message type, aggregated over all peers since node startup.
+/// Byte counts refer to the encoded message size before transport compression
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcP2pMessageStats {
+    pub message_type: String,
+    pub incoming_count: u64,
+    pub incoming_bytes: u64,
+    pub outgoing_count: u64,
+    pub outgoing_bytes: u64,
+}
+
+impl Serializer for RpcP2pMessageStats {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(String, &self.message_type, writer)?;
+        store!(u64, &self.incoming_count, writer)?;
+        store!(u64, &self.incoming_bytes, writer)?;
+        store!(u64, &self.outgoing_count, writer)?;
+        store!(u64, &self.outgoing_bytes, writer)
+    }
+}
+
+impl Deserializer for RpcP2pMessageStats {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let message_type = load!(String, reader)?;
+        let incoming_count = load!(u64, reader)?;
+        let incoming_bytes = load!(u64, reader)?;
+        let outgoing_count = load!(u64, reader)?;
+        let outgoing_bytes = load!(u64, reader)?;
+        Ok(Self { message_type, incoming_count, incoming_bytes, outgoing_count, outgoing_bytes })
+    }
+}
+
+/// Blocks and headers submitted by a connected peer during its current connection, by processing outcome,
+/// along with the traffic exchanged with it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPeerContribution {
+    pub id: RpcNodeId,
+    pub address: RpcPeerAddress,
+    pub valid_blocks: u64,
+    /// Blocks which were already known or concurrently processed
+    pub duplicate_blocks: u64,
+    pub invalid_blocks: u64,
+    pub valid_headers: u64,
+    pub duplicate_headers: u64,
+    pub invalid_headers: u64,
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+}
+
+impl Serializer for RpcPeerContribution {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcNodeId, &self.id, writer)?;
+        store!(RpcPeerAddress, &self.address, writer)?;
+        store!(u64, &self.valid_blocks, writer)?;
+        store!(u64, &self.duplicate_blocks, writer)?;
+        store!(u64, &self.invalid_blocks, writer)?;
+        store!(u64, &self.valid_headers, writer)?;
+        store!(u64, &self.duplicate_headers, writer)?;
+        store!(u64, &self.invalid_headers, writer)?;
+        store!(u64, &self.bytes_received, writer)?;
+        store!(u64, &self.bytes_sent, writer)
+    }
+}
+
+impl Deserializer for RpcPeerContribution {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let id = load!(RpcNodeId, reader)?;
+        let address = load!(RpcPeerAddress, reader)?;
+        let valid_blocks = load!(u64, reader)?;
+        let duplicate_blocks = load!(u64, reader)?;
+        let invalid_blocks = load!(u64, reader)?;
+        let valid_headers = load!(u64, reader)?;
+        let duplicate_headers = load!(u64, reader)?;
+        let invalid_headers = load!(u64, reader)?;
+        let bytes_received = load!(u64, reader)?;
+        let bytes_sent = load!(u64, reader)?;
+        Ok(Self {
+            id,
+            address,
+            valid_blocks,
+            duplicate_blocks,
+            invalid_blocks,
+            valid_headers,
+            duplicate_headers,
+            invalid_headers,
+            bytes_received,
+            bytes_sent,
+        })
+    }
+}
+
+/// Statistics of a peer IP accumulated over all past connections and persisted across node restarts
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPeerStats {
+    pub ip: RpcIpAddress,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    /// Unix time (milliseconds) at which a connection to the peer last ended
+    pub last_seen: u64,
+    pub protocol_version: u32,
+    /// Average ping latency in milliseconds
+    pub avg_latency: u64,
+    pub ban_score: u32,
+    pub sessions: u64,
+}
+
+impl Serializer for RpcPeerStats {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcIpAddress, &self.ip, writer)?;
+        store!(u64, &self.bytes_sent, writer)?;
+        store!(u64, &self.bytes_received, writer)?;
+        store!(u64, &self.last_seen, writer)?;
+        store!(u32, &self.protocol_version, writer)?;
+        store!(u64, &self.avg_latency, writer)?;
+        store!(u32, &self.ban_score, writer)?;
+        store!(u64, &self.sessions, writer)
+    }
+}
+
+impl Deserializer for RpcPeerStats {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let ip = load!(RpcIpAddress, reader)?;
+        let bytes_sent = load!(u64, reader)?;
+        let bytes_received = load!(u64, reader)?;
+        let last_seen = load!(u64, reader)?;
+        let protocol_version = load!(u32, reader)?;
+        let avg_latency = load!(u64, reader)?;
+        let ban_score = load!(u32, reader)?;
+        let sessions = load!(u64, reader)?;
+        Ok(Self { ip, bytes_sent, bytes_received, last_seen, protocol_version, avg_latency, ban_score, sessions })
+    }
+}
+
+/// State of the incoming route of a single P2P flow of a peer
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFlowState {
+    /// The P2P message types routed to the flow
+    pub message_types: Vec<String>,
+    /// Number of messages waiting for the flow to process them
+    pub queued: u64,
+    pub capacity: u64,
+}
+
+impl Serializer for RpcFlowState {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(Vec<String>, &self.message_types, writer)?;
+        store!(u64, &self.queued, writer)?;
+        store!(u64, &self.capacity, writer)
+    }
+}
+
+impl Deserializer for RpcFlowState {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let message_types = load!(Vec<String>, reader)?;
+        let queued = load!(u64, reader)?;
+        let capacity = load!(u64, reader)?;
+        Ok(Self { message_types, queued, capacity })
+    }
+}
+
+/// The P2P flows of a connected peer along with its operator controls
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPeerFlowStates {
+    pub id: RpcNodeId,
+    pub address: RpcPeerAddress,
+    pub is_outbound: bool,
+    /// Whether the peer is protected from eviction
+    pub is_protected: bool,
+    pub flows: Vec<RpcFlowState>,
+}
+
+impl Serializer for RpcPeerFlowStates {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcNodeId, &self.id, writer)?;
+        store!(RpcPeerAddress, &self.address, writer)?;
+        store!(bool, &self.is_outbound, writer)?;
+        store!(bool, &self.is_protected, writer)?;
+        serialize!(Vec<RpcFlowState>, &self.flows, writer)
+    }
+}
+
+impl Deserializer for RpcPeerFlowStates {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let id = load!(RpcNodeId, reader)?;
+        let address = load!(RpcPeerAddress, reader)?;
+        let is_outbound = load!(bool, reader)?;
+        let is_protected = load!(bool, reader)?;
+        let flows = deserialize!(Vec<RpcFlowState>, reader)?;
+        Ok(Self { id, address, is_outbound, is_protected, flows })
+    }
 }
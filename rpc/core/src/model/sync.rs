@@ -0,0 +1,119 @@
+use crate::RpcNodeId;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use workflow_serializer::prelude::*;
+
+/// The phases of the initial block download (IBD), in order of occurrence
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "kebab-case")]
+#[borsh(use_discriminant = true)]
+pub enum RpcSyncPhase {
+    /// Negotiating the missing chain segment with the sync peer
+    Negotiating = 0,
+    /// Downloading and applying the pruning point proof and its trusted blocks
+    HeadersProof,
+    /// Downloading block headers
+    Headers,
+    /// Downloading the pruning point UTXO set
+    UtxoSet,
+    /// Downloading block bodies
+    Bodies,
+    /// No IBD is running
+    #[default]
+    Done,
+}
+
+impl std::fmt::Display for RpcSyncPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RpcSyncPhase::Negotiating => "negotiating",
+            RpcSyncPhase::HeadersProof => "headers-proof",
+            RpcSyncPhase::Headers => "headers",
+            RpcSyncPhase::UtxoSet => "utxo-set",
+            RpcSyncPhase::Bodies => "bodies",
+            RpcSyncPhase::Done => "done",
+        };
+        f.write_str(s)
+    }
+}
+
+impl std::str::FromStr for RpcSyncPhase {
+    type Err = crate::RpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "negotiating" => Ok(RpcSyncPhase::Negotiating),
+            "headers-proof" => Ok(RpcSyncPhase::HeadersProof),
+            "headers" => Ok(RpcSyncPhase::Headers),
+            "utxo-set" => Ok(RpcSyncPhase::UtxoSet),
+            "bodies" => Ok(RpcSyncPhase::Bodies),
+            "done" => Ok(RpcSyncPhase::Done),
+            _ => Err(crate::RpcError::General(format!("invalid sync phase: {s}"))),
+        }
+    }
+}
+
+/// Progress of a single IBD phase
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSyncPhaseProgress {
+    pub phase: RpcSyncPhase,
+    /// The number of items (headers, blocks or UTXOs) processed so far
+    pub processed: u64,
+    /// The expected number of items, or zero if unknown. Header counts are estimated by DAA score
+    pub total: u64,
+}
+
+impl Serializer for RpcSyncPhaseProgress {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcSyncPhase, &self.phase, writer)?;
+        store!(u64, &self.processed, writer)?;
+        store!(u64, &self.total, writer)
+    }
+}
+
+impl Deserializer for RpcSyncPhaseProgress {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let phase = load!(RpcSyncPhase, reader)?;
+        let processed = load!(u64, reader)?;
+        let total = load!(u64, reader)?;
+        Ok(Self { phase, processed, total })
+    }
+}
+
+/// Detailed progress of the node synchronization
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSyncProgress {
+    /// The current IBD phase, [`RpcSyncPhase::Done`] if no IBD is running
+    pub phase: RpcSyncPhase,
+    /// Progress of every phase entered by the running IBD, the last one being the current phase
+    pub phases: Vec<RpcSyncPhaseProgress>,
+    /// The peer the running IBD syncs from
+    pub sync_peer_id: Option<RpcNodeId>,
+    /// Estimated remaining time of the current phase, in milliseconds
+    pub eta: Option<u64>,
+}
+
+impl Serializer for RpcSyncProgress {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcSyncPhase, &self.phase, writer)?;
+        serialize!(Vec<RpcSyncPhaseProgress>, &self.phases, writer)?;
+        store!(Option<RpcNodeId>, &self.sync_peer_id, writer)?;
+        store!(Option<u64>, &self.eta, writer)
+    }
+}
+
+impl Deserializer for RpcSyncProgress {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let phase = load!(RpcSyncPhase, reader)?;
+        let phases = deserialize!(Vec<RpcSyncPhaseProgress>, reader)?;
+        let sync_peer_id = load!(Option<RpcNodeId>, reader)?;
+        let eta = load!(Option<u64>, reader)?;
+        Ok(Self { phase, phases, sync_peer_id, eta })
+    }
+}
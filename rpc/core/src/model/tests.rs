@@ -241,6 +241,7 @@ mod mockery {
                 compute_mass: mock(),
                 block_hash: mock(),
                 block_time: mock(),
+                payload_hex: "deadbeef".to_string(),
             }
         }
     }
@@ -303,6 +304,77 @@ mod mockery {
                 advertised_protocol_version: mock(),
                 time_connected: mock(),
                 is_ibd_peer: mock(),
+                identity: Some("4d3c".to_string()),
+            }
+        }
+    }
+
+    impl Mock for RpcP2pMessageStats {
+        fn mock() -> Self {
+            RpcP2pMessageStats {
+                message_type: "Ping".to_string(),
+                incoming_count: mock(),
+                incoming_bytes: mock(),
+                outgoing_count: mock(),
+                outgoing_bytes: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcPeerContribution {
+        fn mock() -> Self {
+            RpcPeerContribution {
+                id: mock(),
+                address: mock(),
+                valid_blocks: mock(),
+                duplicate_blocks: mock(),
+                invalid_blocks: mock(),
+                valid_headers: mock(),
+                duplicate_headers: mock(),
+                invalid_headers: mock(),
+                bytes_received: mock(),
+                bytes_sent: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcFlowState {
+        fn mock() -> Self {
+            RpcFlowState { message_types: vec!["Ping".to_string()], queued: mock(), capacity: mock() }
+        }
+    }
+
+    impl Mock for RpcPeerFlowStates {
+        fn mock() -> Self {
+            RpcPeerFlowStates { id: mock(), address: mock(), is_outbound: mock(), is_protected: mock(), flows: mock() }
+        }
+    }
+
+    impl Mock for RpcPeerStats {
+        fn mock() -> Self {
+            RpcPeerStats {
+                ip: mock(),
+                bytes_sent: mock(),
+                bytes_received: mock(),
+                last_seen: mock(),
+                protocol_version: mock(),
+                avg_latency: mock(),
+                ban_score: mock(),
+                sessions: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcSyncProgress {
+        fn mock() -> Self {
+            RpcSyncProgress {
+                phase: RpcSyncPhase::Headers,
+                phases: vec![
+                    RpcSyncPhaseProgress { phase: RpcSyncPhase::Negotiating, processed: 0, total: 0 },
+                    RpcSyncPhaseProgress { phase: RpcSyncPhase::Headers, processed: mock(), total: mock() },
+                ],
+                sync_peer_id: mock(),
+                eta: mock(),
             }
         }
     }
@@ -371,6 +443,19 @@ mod mockery {
                 json_connection_attempts: mock(),
                 json_handshake_failures: mock(),
                 active_peers: mock(),
+                connection_subscriptions: mock(),
+                leaked_listeners: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcConnectionSubscriptions {
+        fn mock() -> Self {
+            RpcConnectionSubscriptions {
+                protocol: "grpc".to_string(),
+                connection: "127.0.0.1:16110".to_string(),
+                listener_id: mock(),
+                subscriptions: mock(),
             }
         }
     }
@@ -386,6 +471,12 @@ mod mockery {
                 p2p_bytes_rx: mock(),
                 grpc_bytes_tx: mock(),
                 grpc_bytes_rx: mock(),
+                p2p_messages_tx: mock(),
+                p2p_messages_rx: mock(),
+                p2p_known_blocks_hits: mock(),
+                p2p_known_blocks_misses: mock(),
+                p2p_known_transactions_hits: mock(),
+                p2p_known_transactions_misses: mock(),
             }
         }
     }
@@ -408,6 +499,17 @@ mod mockery {
                 network_past_median_time: mock(),
                 network_virtual_parent_hashes_count: mock(),
                 network_virtual_daa_score: mock(),
+                node_relay_block_submissions_in_flight: mock(),
+                node_relay_block_submissions_waiting: mock(),
+                node_ibd_block_submissions_in_flight: mock(),
+                node_ibd_block_submissions_waiting: mock(),
+                node_stage_latencies: vec![ProcessingStageLatencyMetrics {
+                    stage: "commit".to_string(),
+                    sample_count: mock(),
+                    p50_micros: mock(),
+                    p95_micros: mock(),
+                    p99_micros: mock(),
+                }],
             }
         }
     }
@@ -418,6 +520,21 @@ mod mockery {
         }
     }
 
+    impl Mock for AllocationMetrics {
+        fn mock() -> Self {
+            AllocationMetrics {
+                subsystems: vec![SubsystemAllocationMetrics {
+                    name: "mempool".to_string(),
+                    live_bytes: mock(),
+                    allocated_bytes: mock(),
+                    freed_bytes: mock(),
+                    allocation_count: mock(),
+                    deallocation_count: mock(),
+                }],
+            }
+        }
+    }
+
     // --------------------------------------------
     // implementations for all the rpc request
     // and response data structures.
@@ -432,7 +549,7 @@ mod mockery {
 
     impl Mock for SubmitBlockResponse {
         fn mock() -> Self {
-            SubmitBlockResponse { report: SubmitBlockReport::Success }
+            SubmitBlockResponse { report: SubmitBlockReport::Success, error: None }
         }
     }
 
@@ -440,7 +557,7 @@ mod mockery {
 
     impl Mock for GetBlockTemplateRequest {
         fn mock() -> Self {
-            GetBlockTemplateRequest { pay_address: mock(), extra_data: vec![4, 2] }
+            GetBlockTemplateRequest { pay_address: Some(mock()), extra_data: vec![4, 2] }
         }
     }
 
@@ -448,7 +565,13 @@ mod mockery {
 
     impl Mock for GetBlockTemplateResponse {
         fn mock() -> Self {
-            GetBlockTemplateResponse { block: mock(), is_synced: true }
+            GetBlockTemplateResponse {
+                block: mock(),
+                is_synced: true,
+                block_reward: mock(),
+                block_subsidy: mock(),
+                virtual_daa_score: mock(),
+            }
         }
     }
 
@@ -488,6 +611,9 @@ mod mockery {
                 is_synced: false,
                 has_notify_command: true,
                 has_message_id: false,
+                network_id: Some(RpcNetworkId::with_suffix(NetworkType::Testnet, 10)),
+                unsafe_rpc: true,
+                cache_policy: Some("default".to_string()),
             }
         }
     }
@@ -504,7 +630,7 @@ mod mockery {
 
     impl Mock for GetCurrentNetworkResponse {
         fn mock() -> Self {
-            GetCurrentNetworkResponse { network: NetworkType::Mainnet }
+            GetCurrentNetworkResponse { network: NetworkType::Mainnet, network_id: Some(RpcNetworkId::new(NetworkType::Mainnet)) }
         }
     }
 
@@ -512,7 +638,7 @@ mod mockery {
 
     impl Mock for GetPeerAddressesRequest {
         fn mock() -> Self {
-            GetPeerAddressesRequest {}
+            GetPeerAddressesRequest { verbose: true }
         }
     }
 
@@ -520,7 +646,7 @@ mod mockery {
 
     impl Mock for GetPeerAddressesResponse {
         fn mock() -> Self {
-            GetPeerAddressesResponse { known_addresses: mock(), banned_addresses: mock() }
+            GetPeerAddressesResponse { known_addresses: mock(), banned_addresses: mock(), peer_stats: mock() }
         }
     }
 
@@ -552,7 +678,17 @@ mod mockery {
 
     impl Mock for GetMempoolEntryResponse {
         fn mock() -> Self {
-            GetMempoolEntryResponse { mempool_entry: RpcMempoolEntry { fee: mock(), transaction: mock(), is_orphan: false } }
+            GetMempoolEntryResponse {
+                mempool_entry: RpcMempoolEntry { fee: mock(), transaction: mock(), is_orphan: false },
+                package: Some(RpcTransactionPackage {
+                    ancestor_count: mock(),
+                    ancestor_fees: mock(),
+                    ancestor_mass: mock(),
+                    descendant_count: mock(),
+                    descendant_fees: mock(),
+                    descendant_mass: mock(),
+                }),
+            }
         }
     }
 
@@ -560,7 +696,7 @@ mod mockery {
 
     impl Mock for GetMempoolEntriesRequest {
         fn mock() -> Self {
-            GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: false }
+            GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: false, subnetwork_id: Some(mock()) }
         }
     }
 
@@ -843,7 +979,7 @@ mod mockery {
 
     impl Mock for BanRequest {
         fn mock() -> Self {
-            BanRequest { ip: mock() }
+            BanRequest { ip: mock(), prefix_length: mock(), duration: mock(), is_permanent: true }
         }
     }
 
@@ -859,7 +995,7 @@ mod mockery {
 
     impl Mock for UnbanRequest {
         fn mock() -> Self {
-            UnbanRequest { ip: mock() }
+            UnbanRequest { ip: mock(), prefix_length: mock() }
         }
     }
 
@@ -986,6 +1122,7 @@ mod mockery {
                 consensus_metrics: true,
                 storage_metrics: true,
                 custom_metrics: false,
+                allocation_metrics: true,
             }
         }
     }
@@ -1002,6 +1139,7 @@ mod mockery {
                 consensus_metrics: mock(),
                 storage_metrics: mock(),
                 custom_metrics: None,
+                allocation_metrics: mock(),
             }
         }
     }
@@ -1042,12 +1180,233 @@ mod mockery {
 
     impl Mock for GetSyncStatusResponse {
         fn mock() -> Self {
-            GetSyncStatusResponse { is_synced: true }
+            GetSyncStatusResponse { is_synced: true, progress: mock() }
         }
     }
 
     test!(GetSyncStatusResponse);
 
+    impl Mock for GetP2pStatsRequest {
+        fn mock() -> Self {
+            GetP2pStatsRequest {}
+        }
+    }
+
+    test!(GetP2pStatsRequest);
+
+    impl Mock for GetP2pStatsResponse {
+        fn mock() -> Self {
+            GetP2pStatsResponse { message_stats: mock(), top_peers: mock() }
+        }
+    }
+
+    test!(GetP2pStatsResponse);
+
+    impl Mock for GetProcessingStatsRequest {
+        fn mock() -> Self {
+            GetProcessingStatsRequest {}
+        }
+    }
+
+    test!(GetProcessingStatsRequest);
+
+    impl Mock for GetProcessingStatsResponse {
+        fn mock() -> Self {
+            GetProcessingStatsResponse {
+                blocks_submitted_count: mock(),
+                headers_processed_count: mock(),
+                dependencies_processed_count: mock(),
+                bodies_processed_count: mock(),
+                transactions_processed_count: mock(),
+                chain_blocks_processed_count: mock(),
+                chain_disqualified_count: mock(),
+                mass_processed_count: mock(),
+                interval_millis: mock(),
+                headers_per_second: mock(),
+                bodies_per_second: mock(),
+                transactions_per_second: mock(),
+                dependencies_per_second: mock(),
+            }
+        }
+    }
+
+    test!(GetProcessingStatsResponse);
+
+    impl Mock for GetPeerFlowStatesRequest {
+        fn mock() -> Self {
+            GetPeerFlowStatesRequest { peer_id: mock() }
+        }
+    }
+
+    test!(GetPeerFlowStatesRequest);
+
+    impl Mock for GetPeerFlowStatesResponse {
+        fn mock() -> Self {
+            GetPeerFlowStatesResponse { peers: mock() }
+        }
+    }
+
+    test!(GetPeerFlowStatesResponse);
+
+    impl Mock for DisconnectPeerRequest {
+        fn mock() -> Self {
+            DisconnectPeerRequest { peer_id: mock() }
+        }
+    }
+
+    test!(DisconnectPeerRequest);
+
+    impl Mock for DisconnectPeerResponse {
+        fn mock() -> Self {
+            DisconnectPeerResponse {}
+        }
+    }
+
+    test!(DisconnectPeerResponse);
+
+    impl Mock for SetPeerProtectedRequest {
+        fn mock() -> Self {
+            SetPeerProtectedRequest { peer_id: mock(), is_protected: mock() }
+        }
+    }
+
+    test!(SetPeerProtectedRequest);
+
+    impl Mock for SetPeerProtectedResponse {
+        fn mock() -> Self {
+            SetPeerProtectedResponse {}
+        }
+    }
+
+    test!(SetPeerProtectedResponse);
+
+    impl Mock for RpcTransactionAcceptance {
+        fn mock() -> Self {
+            RpcTransactionAcceptance { transaction_id: mock(), accepting_block_hash: mock(), accepting_blue_score: mock() }
+        }
+    }
+
+    impl Mock for GetTransactionAcceptanceDataRequest {
+        fn mock() -> Self {
+            GetTransactionAcceptanceDataRequest { transaction_ids: mock() }
+        }
+    }
+
+    test!(GetTransactionAcceptanceDataRequest);
+
+    impl Mock for GetTransactionAcceptanceDataResponse {
+        fn mock() -> Self {
+            GetTransactionAcceptanceDataResponse { acceptances: mock() }
+        }
+    }
+
+    test!(GetTransactionAcceptanceDataResponse);
+
+    impl Mock for GetChainBlockByScoreRequest {
+        fn mock() -> Self {
+            GetChainBlockByScoreRequest { score: mock(), by_blue_score: true }
+        }
+    }
+
+    test!(GetChainBlockByScoreRequest);
+
+    impl Mock for GetChainBlockByScoreResponse {
+        fn mock() -> Self {
+            GetChainBlockByScoreResponse { block_hash: mock(), daa_score: mock(), blue_score: mock() }
+        }
+    }
+
+    test!(GetChainBlockByScoreResponse);
+
+    impl Mock for GetBlockSerializedRequest {
+        fn mock() -> Self {
+            GetBlockSerializedRequest { hash: mock() }
+        }
+    }
+
+    test!(GetBlockSerializedRequest);
+
+    impl Mock for GetBlockSerializedResponse {
+        fn mock() -> Self {
+            GetBlockSerializedResponse { block_hex: "deadbeef".to_string() }
+        }
+    }
+
+    test!(GetBlockSerializedResponse);
+
+    impl Mock for SubmitBlockSerializedRequest {
+        fn mock() -> Self {
+            SubmitBlockSerializedRequest { block_hex: "deadbeef".to_string(), allow_non_daa_blocks: true }
+        }
+    }
+
+    test!(SubmitBlockSerializedRequest);
+
+    impl Mock for SubmitBlockSerializedResponse {
+        fn mock() -> Self {
+            SubmitBlockSerializedResponse {
+                report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid),
+                error: Some("bad merkle root".to_string()),
+            }
+        }
+    }
+
+    test!(SubmitBlockSerializedResponse);
+
+    impl Mock for GetTransactionSerializedRequest {
+        fn mock() -> Self {
+            GetTransactionSerializedRequest { transaction_id: mock(), block_hash: mock() }
+        }
+    }
+
+    test!(GetTransactionSerializedRequest);
+
+    impl Mock for GetTransactionSerializedResponse {
+        fn mock() -> Self {
+            GetTransactionSerializedResponse { transaction_hex: "deadbeef".to_string() }
+        }
+    }
+
+    test!(GetTransactionSerializedResponse);
+
+    impl Mock for SubmitTransactionSerializedRequest {
+        fn mock() -> Self {
+            SubmitTransactionSerializedRequest { transaction_hex: "deadbeef".to_string(), allow_orphan: false }
+        }
+    }
+
+    test!(SubmitTransactionSerializedRequest);
+
+    impl Mock for SubmitTransactionSerializedResponse {
+        fn mock() -> Self {
+            SubmitTransactionSerializedResponse { transaction_id: mock() }
+        }
+    }
+
+    test!(SubmitTransactionSerializedResponse);
+
+    impl Mock for RpcLocalTransaction {
+        fn mock() -> Self {
+            RpcLocalTransaction { transaction_id: mock(), added_at_daa_score: mock(), expire_daa_score: mock(), is_orphan: mock() }
+        }
+    }
+
+    impl Mock for GetLocalTransactionsRequest {
+        fn mock() -> Self {
+            GetLocalTransactionsRequest {}
+        }
+    }
+
+    test!(GetLocalTransactionsRequest);
+
+    impl Mock for GetLocalTransactionsResponse {
+        fn mock() -> Self {
+            GetLocalTransactionsResponse { transactions: mock() }
+        }
+    }
+
+    test!(GetLocalTransactionsResponse);
+
     impl Mock for GetDaaScoreTimestampEstimateRequest {
         fn mock() -> Self {
             GetDaaScoreTimestampEstimateRequest { daa_scores: mock() }
@@ -1284,6 +1643,38 @@ mod mockery {
 
     test!(NewBlockTemplateNotification);
 
+    impl Mock for NotifyTransactionsEvictedRequest {
+        fn mock() -> Self {
+            NotifyTransactionsEvictedRequest { command: Command::Start }
+        }
+    }
+
+    test!(NotifyTransactionsEvictedRequest);
+
+    impl Mock for NotifyTransactionsEvictedResponse {
+        fn mock() -> Self {
+            NotifyTransactionsEvictedResponse {}
+        }
+    }
+
+    test!(NotifyTransactionsEvictedResponse);
+
+    impl Mock for TransactionsEvictedNotification {
+        fn mock() -> Self {
+            TransactionsEvictedNotification { transaction_ids: mock() }
+        }
+    }
+
+    test!(TransactionsEvictedNotification);
+
+    impl Mock for NotificationsGapNotification {
+        fn mock() -> Self {
+            NotificationsGapNotification { dropped: mock() }
+        }
+    }
+
+    test!(NotificationsGapNotification);
+
     impl Mock for SubscribeResponse {
         fn mock() -> Self {
             SubscribeResponse::new(mock())
@@ -363,16 +363,19 @@ pub struct RpcTransactionVerboseData {
     pub compute_mass: u64,
     pub block_hash: RpcHash,
     pub block_time: u64,
+    /// The transaction payload, hex encoded
+    pub payload_hex: String,
 }
 
 impl Serializer for RpcTransactionVerboseData {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u8, &1, writer)?;
+        store!(u8, &2, writer)?;
         store!(RpcTransactionId, &self.transaction_id, writer)?;
         store!(RpcHash, &self.hash, writer)?;
         store!(u64, &self.compute_mass, writer)?;
         store!(RpcHash, &self.block_hash, writer)?;
         store!(u64, &self.block_time, writer)?;
+        store!(String, &self.payload_hex, writer)?;
 
         Ok(())
     }
@@ -380,14 +383,15 @@ impl Serializer for RpcTransactionVerboseData {
 
 impl Deserializer for RpcTransactionVerboseData {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u8, reader)?;
+        let version = load!(u8, reader)?;
         let transaction_id = load!(RpcTransactionId, reader)?;
         let hash = load!(RpcHash, reader)?;
         let compute_mass = load!(u64, reader)?;
         let block_hash = load!(RpcHash, reader)?;
         let block_time = load!(u64, reader)?;
+        let payload_hex = if version > 1 { load!(String, reader)? } else { String::new() };
 
-        Ok(Self { transaction_id, hash, compute_mass, block_hash, block_time })
+        Ok(Self { transaction_id, hash, compute_mass, block_hash, block_time, payload_hex })
     }
 }
 
@@ -398,3 +402,12 @@ pub struct RpcAcceptedTransactionIds {
     pub accepting_block_hash: RpcHash,
     pub accepted_transaction_ids: Vec<RpcTransactionId>,
 }
+
+/// Represents the chain block accepting a transaction
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionAcceptance {
+    pub transaction_id: RpcTransactionId,
+    pub accepting_block_hash: RpcHash,
+    pub accepting_blue_score: u64,
+}
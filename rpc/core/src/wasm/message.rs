@@ -247,6 +247,9 @@ declare! {
         hasNotifyCommand : boolean;
         /** GRPC ONLY */
         hasMessageId : boolean;
+        networkId? : string;
+        unsafeRpc : boolean;
+        cachePolicy? : string;
     }
     "#,
 }
@@ -263,7 +266,12 @@ declare! {
     /**
      * @category Node RPC
      */
-    export interface IGetPeerAddressesRequest { }
+    export interface IGetPeerAddressesRequest {
+        /**
+         * Also return the persisted statistics of previously connected peers
+         */
+        verbose? : boolean;
+    }
     "#,
 }
 
@@ -501,11 +509,43 @@ try_from! ( args: IGetSyncStatusRequest, GetSyncStatusRequest, {
 declare! {
     IGetSyncStatusResponse,
     r#"
+    /**
+     * Phase of the initial block download (IBD), `done` if no IBD is running.
+     *
+     * @category Node RPC
+     */
+    export type SyncPhase = "negotiating" | "headers-proof" | "headers" | "utxo-set" | "bodies" | "done";
+
+    /**
+     * Progress of a single IBD phase. A zero `total` denotes an unknown amount of items.
+     *
+     * @category Node RPC
+     */
+    export interface ISyncPhaseProgress {
+        phase : SyncPhase;
+        processed : bigint;
+        total : bigint;
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface ISyncProgress {
+        phase : SyncPhase;
+        phases : ISyncPhaseProgress[];
+        syncPeerId? : string;
+        /**
+         * Estimated remaining time of the current phase, in milliseconds
+         */
+        eta? : bigint;
+    }
+
     /**
      * @category Node RPC
      */
     export interface IGetSyncStatusResponse {
         isSynced : boolean;
+        progress : ISyncProgress;
     }
     "#,
 }
@@ -514,6 +554,119 @@ try_from! ( args: GetSyncStatusResponse, IGetSyncStatusResponse, {
     Ok(to_value(&args)?.into())
 });
 
+// ---
+
+declare! {
+    IGetP2pStatsRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetP2pStatsRequest { }
+    "#,
+}
+
+try_from! ( args: IGetP2pStatsRequest, GetP2pStatsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetP2pStatsResponse,
+    r#"
+    /**
+     * Traffic of a single P2P message type, aggregated over all peers.
+     * Byte counts refer to the protobuf-encoded message size.
+     *
+     * @category Node RPC
+     */
+    export interface IP2pMessageStats {
+        messageType : string;
+        incomingCount : bigint;
+        incomingBytes : bigint;
+        outgoingCount : bigint;
+        outgoingBytes : bigint;
+    }
+
+    /**
+     * Blocks and headers submitted by a connected peer during its current connection,
+     * by processing outcome, along with the traffic exchanged with it.
+     *
+     * @category Node RPC
+     */
+    export interface IPeerContribution {
+        id : string;
+        address : INetworkAddress;
+        validBlocks : bigint;
+        duplicateBlocks : bigint;
+        invalidBlocks : bigint;
+        validHeaders : bigint;
+        duplicateHeaders : bigint;
+        invalidHeaders : bigint;
+        bytesReceived : bigint;
+        bytesSent : bigint;
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface IGetP2pStatsResponse {
+        messageStats : IP2pMessageStats[];
+        topPeers : IPeerContribution[];
+    }
+    "#,
+}
+
+try_from! ( args: GetP2pStatsResponse, IGetP2pStatsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetProcessingStatsRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetProcessingStatsRequest { }
+    "#,
+}
+
+try_from! ( args: IGetProcessingStatsRequest, GetProcessingStatsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetProcessingStatsResponse,
+    r#"
+    /**
+     * Processing counters are accumulated since node startup, while rates
+     * are measured over `intervalMillis` since the previous call.
+     *
+     * @category Node RPC
+     */
+    export interface IGetProcessingStatsResponse {
+        blocksSubmittedCount : bigint;
+        headersProcessedCount : bigint;
+        dependenciesProcessedCount : bigint;
+        bodiesProcessedCount : bigint;
+        transactionsProcessedCount : bigint;
+        chainBlocksProcessedCount : bigint;
+        chainDisqualifiedCount : bigint;
+        massProcessedCount : bigint;
+        intervalMillis : bigint;
+        headersPerSecond : number;
+        bodiesPerSecond : number;
+        transactionsPerSecond : number;
+        dependenciesPerSecond : number;
+    }
+    "#,
+}
+
+try_from! ( args: GetProcessingStatsResponse, IGetProcessingStatsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
 /*
     Interfaces for methods with arguments
 */
@@ -567,6 +720,18 @@ declare! {
          * IPv4 or IPv6 address to ban.
          */
         ip : string;
+        /**
+         * Optional CIDR prefix length. When set, the whole range `ip/prefixLength` is banned.
+         */
+        prefixLength? : number;
+        /**
+         * Optional ban duration in seconds. The node default (24 hours) is used when not set.
+         */
+        duration? : bigint;
+        /**
+         * Bans the address (or range) permanently, overriding `duration`.
+         */
+        isPermanent? : boolean;
     }
     "#,
 }
@@ -593,6 +758,435 @@ try_from! ( args: BanResponse, IBanResponse, {
 
 // ---
 
+declare! {
+    IDisconnectPeerRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IDisconnectPeerRequest {
+        /**
+         * Id of the connected peer to disconnect from.
+         */
+        peerId : string;
+    }
+    "#,
+}
+
+try_from! ( args: IDisconnectPeerRequest, DisconnectPeerRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IDisconnectPeerResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IDisconnectPeerResponse { }
+    "#,
+}
+
+try_from! ( args: DisconnectPeerResponse, IDisconnectPeerResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetPeerFlowStatesRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetPeerFlowStatesRequest {
+        /**
+         * Optional peer id. When set, only the flows of this peer are returned.
+         */
+        peerId? : string;
+    }
+    "#,
+}
+
+try_from! ( args: IGetPeerFlowStatesRequest, GetPeerFlowStatesRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetPeerFlowStatesResponse,
+    r#"
+    /**
+     * State of the incoming route of a single P2P flow of a peer.
+     *
+     * @category Node RPC
+     */
+    export interface IFlowState {
+        messageTypes : string[];
+        queued : bigint;
+        capacity : bigint;
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface IPeerFlowStates {
+        id : string;
+        address : INetworkAddress;
+        isOutbound : boolean;
+        isProtected : boolean;
+        flows : IFlowState[];
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface IGetPeerFlowStatesResponse {
+        peers : IPeerFlowStates[];
+    }
+    "#,
+}
+
+try_from! ( args: GetPeerFlowStatesResponse, IGetPeerFlowStatesResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ISetPeerProtectedRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ISetPeerProtectedRequest {
+        /**
+         * Id of the connected peer.
+         */
+        peerId : string;
+        /**
+         * Protects the peer from eviction when `true`, removes its protection otherwise.
+         */
+        isProtected : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: ISetPeerProtectedRequest, SetPeerProtectedRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    ISetPeerProtectedResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ISetPeerProtectedResponse { }
+    "#,
+}
+
+try_from! ( args: SetPeerProtectedResponse, ISetPeerProtectedResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionAcceptanceDataRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetTransactionAcceptanceDataRequest {
+        /**
+         * Ids of the transactions to look up.
+         */
+        transactionIds : HexString[];
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionAcceptanceDataRequest, GetTransactionAcceptanceDataRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionAcceptanceDataResponse,
+    r#"
+    /**
+     * Chain block accepting a transaction.
+     *
+     * @category Node RPC
+     */
+    export interface ITransactionAcceptance {
+        transactionId : HexString;
+        acceptingBlockHash : HexString;
+        acceptingBlueScore : bigint;
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface IGetTransactionAcceptanceDataResponse {
+        /**
+         * Acceptance of the requested transactions accepted by the selected chain, in request order.
+         */
+        acceptances : ITransactionAcceptance[];
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionAcceptanceDataResponse, IGetTransactionAcceptanceDataResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetChainBlockByScoreRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetChainBlockByScoreRequest {
+        score : bigint;
+        /**
+         * Look up by blue score rather than by DAA score.
+         */
+        byBlueScore : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetChainBlockByScoreRequest, GetChainBlockByScoreRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetChainBlockByScoreResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetChainBlockByScoreResponse {
+        blockHash : HexString;
+        daaScore : bigint;
+        blueScore : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetChainBlockByScoreResponse, IGetChainBlockByScoreResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBlockSerializedRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetBlockSerializedRequest {
+        hash : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetBlockSerializedRequest, GetBlockSerializedRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBlockSerializedResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetBlockSerializedResponse {
+        /**
+         * The block in its consensus (hashing preimage) serialization.
+         */
+        blockHex : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: GetBlockSerializedResponse, IGetBlockSerializedResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ISubmitBlockSerializedRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ISubmitBlockSerializedRequest {
+        /**
+         * The block in its consensus (hashing preimage) serialization.
+         */
+        blockHex : HexString;
+        allowNonDaaBlocks : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: ISubmitBlockSerializedRequest, SubmitBlockSerializedRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    ISubmitBlockSerializedResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ISubmitBlockSerializedResponse {
+        report : ISubmitBlockReport;
+        /**
+         * Details of the rejection, if available.
+         */
+        error? : string;
+    }
+    "#,
+}
+
+try_from! ( args: SubmitBlockSerializedResponse, ISubmitBlockSerializedResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionSerializedRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetTransactionSerializedRequest {
+        transactionId : HexString;
+        /**
+         * The block containing the transaction. If omitted, the transaction is looked up in the mempool.
+         */
+        blockHash? : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionSerializedRequest, GetTransactionSerializedRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionSerializedResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetTransactionSerializedResponse {
+        /**
+         * The transaction in its consensus (hashing preimage) serialization.
+         */
+        transactionHex : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionSerializedResponse, IGetTransactionSerializedResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ISubmitTransactionSerializedRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionSerializedRequest {
+        /**
+         * The transaction in its consensus (hashing preimage) serialization.
+         */
+        transactionHex : HexString;
+        allowOrphan : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: ISubmitTransactionSerializedRequest, SubmitTransactionSerializedRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    ISubmitTransactionSerializedResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionSerializedResponse {
+        transactionId : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: SubmitTransactionSerializedResponse, ISubmitTransactionSerializedResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetLocalTransactionsRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetLocalTransactionsRequest { }
+    "#,
+}
+
+try_from! ( args: IGetLocalTransactionsRequest, GetLocalTransactionsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetLocalTransactionsResponse,
+    r#"
+    /**
+     * A transaction submitted to the node, pending acceptance.
+     *
+     * @category Node RPC
+     */
+    export interface ILocalTransaction {
+        transactionId : HexString;
+        addedAtDaaScore : bigint;
+        /**
+         * The DAA score past which the transaction expires, absent if it never does.
+         */
+        expireDaaScore? : bigint;
+        isOrphan : boolean;
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface IGetLocalTransactionsResponse {
+        transactions : ILocalTransaction[];
+    }
+    "#,
+}
+
+try_from! ( args: GetLocalTransactionsResponse, IGetLocalTransactionsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IEstimateNetworkHashesPerSecondRequest,
     r#"
@@ -814,7 +1408,10 @@ declare! {
      * @category Node RPC
      */
     export interface IGetBlockTemplateRequest {
-        payAddress : Address | string;
+        /**
+         * If omitted, the block template pays to the mining address configured by the node (`--miningaddr`).
+         */
+        payAddress? : Address | string;
         /**
          * `extraData` can contain a user-supplied plain text or a byte array represented by `Uint8array`.
          */
@@ -824,7 +1421,7 @@ declare! {
 }
 
 try_from! ( args: IGetBlockTemplateRequest, GetBlockTemplateRequest, {
-    let pay_address = args.cast_into::<Address>("payAddress")?;
+    let pay_address = args.try_get_value("payAddress")?.map(Address::try_owned_from).transpose()?;
     let extra_data = if let Some(extra_data) = args.try_get_value("extraData")? {
         if let Some(text) = extra_data.as_string() {
             text.into_bytes()
@@ -850,6 +1447,13 @@ declare! {
      */
     export interface IGetBlockTemplateResponse {
         block : IRawBlock;
+        isSynced : boolean;
+        /**
+         * Block subsidy plus the fees of the block transactions (in sompi).
+         */
+        blockReward : bigint;
+        blockSubsidy : bigint;
+        virtualDaaScore : bigint;
     }
     "#,
 }
@@ -962,6 +1566,7 @@ declare! {
      */
     export interface IGetCurrentNetworkResponse {
         network : string;
+        networkId? : string;
     }
     "#,
 }
@@ -1023,6 +1628,7 @@ declare! {
     export interface IGetMempoolEntriesRequest {
         includeOrphanPool? : boolean;
         filterTransactionPool? : boolean;
+        subnetworkId? : HexString;
     }
     "#,
 }
@@ -1121,6 +1727,7 @@ declare! {
      */
     export interface IGetMempoolEntryResponse {
         mempoolEntry : IMempoolEntry;
+        package? : ITransactionPackage;
     }
     "#,
 }
@@ -1356,6 +1963,10 @@ declare! {
      */
     export interface ISubmitBlockResponse {
         report : ISubmitBlockReport;
+        /**
+         * Details of the rejection, if available.
+         */
+        error? : string;
     }
     "#,
 }
@@ -1498,6 +2109,10 @@ declare! {
          * IPv4 or IPv6 address to unban.
          */
         ip : string;
+        /**
+         * Optional CIDR prefix length. When set, the ban of the range `ip/prefixLength` is lifted.
+         */
+        prefixLength? : number;
     }
     "#,
 }
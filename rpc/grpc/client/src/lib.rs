@@ -276,6 +276,18 @@ impl RpcApi for GrpcClient {
     route!(get_fee_estimate_call, GetFeeEstimate);
     route!(get_fee_estimate_experimental_call, GetFeeEstimateExperimental);
     route!(get_current_block_color_call, GetCurrentBlockColor);
+    route!(get_p2p_stats_call, GetP2pStats);
+    route!(get_processing_stats_call, GetProcessingStats);
+    route!(get_peer_flow_states_call, GetPeerFlowStates);
+    route!(disconnect_peer_call, DisconnectPeer);
+    route!(set_peer_protected_call, SetPeerProtected);
+    route!(get_transaction_acceptance_data_call, GetTransactionAcceptanceData);
+    route!(get_chain_block_by_score_call, GetChainBlockByScore);
+    route!(get_block_serialized_call, GetBlockSerialized);
+    route!(submit_block_serialized_call, SubmitBlockSerialized);
+    route!(get_transaction_serialized_call, GetTransactionSerialized);
+    route!(submit_transaction_serialized_call, SubmitTransactionSerialized);
+    route!(get_local_transactions_call, GetLocalTransactions);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -914,16 +926,18 @@ impl Inner {
 
     /// Start sending notifications of some type to the client.
     async fn start_notify_to_client(&self, scope: Scope) -> RpcResult<()> {
-        let request = kaspad_request::Payload::from_notification_type(&scope, Command::Start);
-        self.call((&request).into(), request).await?;
+        if let Some(request) = kaspad_request::Payload::from_notification_type(&scope, Command::Start) {
+            self.call((&request).into(), request).await?;
+        }
         Ok(())
     }
 
     /// Stop sending notifications of some type to the client.
     async fn stop_notify_to_client(&self, scope: Scope) -> RpcResult<()> {
         if self.handle_stop_notify() {
-            let request = kaspad_request::Payload::from_notification_type(&scope, Command::Stop);
-            self.call((&request).into(), request).await?;
+            if let Some(request) = kaspad_request::Payload::from_notification_type(&scope, Command::Stop) {
+                self.call((&request).into(), request).await?;
+            }
         }
         Ok(())
     }
@@ -1,20 +1,27 @@
+use std::{env, path::PathBuf};
+
 fn main() {
     let protowire_files = &["./proto/messages.proto", "./proto/rpc.proto"];
+    let service_files = &["./proto/health.proto", "./proto/reflection.proto"];
     let dirs = &["./proto"];
 
+    // The descriptors of all compiled files are served by the reflection service
+    let descriptor_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("descriptors.bin");
+
     tonic_build::configure()
         .build_server(true)
         .build_client(true)
+        .file_descriptor_set_path(descriptor_path)
 
         // In case we want protowire.rs to be explicitly integrated in the crate code,
         // uncomment this line and reflect the change in src/lib.rs
         //.out_dir("./src")
 
-        .compile_protos(&protowire_files[0..1], dirs)
+        .compile_protos(&[protowire_files[0], service_files[0], service_files[1]], dirs)
         .unwrap_or_else(|e| panic!("protobuf compile error: {e}"));
 
     // recompile protobufs only if any of the proto files changes.
-    for file in protowire_files {
+    for file in protowire_files.iter().chain(service_files) {
         println!("cargo:rerun-if-changed={file}");
     }
 }
@@ -1,5 +1,6 @@
 use crate::protowire;
 use crate::{from, try_from};
+use kaspa_consensus_core::limits::Limit;
 use kaspa_rpc_core::{RpcError, RpcHash};
 use std::str::FromStr;
 
@@ -43,6 +44,7 @@ from!(item: &kaspa_rpc_core::RpcBlockVerboseData, protowire::RpcBlockVerboseData
 // ----------------------------------------------------------------------------
 
 try_from!(item: &protowire::RpcBlock, kaspa_rpc_core::RpcBlock, {
+    Limit::BlockTransactions.check(item.transactions.len())?;
     Self {
         header: item
             .header
@@ -55,6 +57,7 @@ try_from!(item: &protowire::RpcBlock, kaspa_rpc_core::RpcBlock, {
 });
 
 try_from!(item: &protowire::RpcBlock, kaspa_rpc_core::RpcRawBlock, {
+    Limit::BlockTransactions.check(item.transactions.len())?;
     Self {
         header: item
             .header
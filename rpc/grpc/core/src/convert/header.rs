@@ -1,6 +1,6 @@
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_consensus_core::header::Header;
+use kaspa_consensus_core::{header::Header, limits::Limit};
 use kaspa_rpc_core::{FromRpcHex, RpcError, RpcHash, RpcResult, ToRpcHex};
 use std::str::FromStr;
 
@@ -49,6 +49,7 @@ from!(item: &Vec<RpcHash>, protowire::RpcBlockLevelParents, { Self { parent_hash
 // ----------------------------------------------------------------------------
 
 try_from!(item: &protowire::RpcBlockHeader, kaspa_rpc_core::RpcHeader, {
+    Limit::ParentLevels.check(item.parents.len())?;
     // We re-hash the block to remain as most trustless as possible
     let header = Header::new_finalized(
         item.version.try_into()?,
@@ -69,6 +70,7 @@ try_from!(item: &protowire::RpcBlockHeader, kaspa_rpc_core::RpcHeader, {
 });
 
 try_from!(item: &protowire::RpcBlockHeader, kaspa_rpc_core::RpcRawHeader, {
+    Limit::ParentLevels.check(item.parents.len())?;
     Self {
         version: item.version.try_into()?,
         parents_by_level: item.parents.iter().map(Vec::<RpcHash>::try_from).collect::<RpcResult<Vec<Vec<RpcHash>>>>()?,
@@ -86,6 +88,7 @@ try_from!(item: &protowire::RpcBlockHeader, kaspa_rpc_core::RpcRawHeader, {
 });
 
 try_from!(item: &protowire::RpcBlockLevelParents, Vec<RpcHash>, {
+    Limit::ParentsPerLevel.check(item.parent_hashes.len())?;
     item.parent_hashes.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?
 });
 
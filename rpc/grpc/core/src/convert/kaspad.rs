@@ -63,6 +63,18 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(GetFeeEstimate);
     impl_into_kaspad_request!(GetFeeEstimateExperimental);
     impl_into_kaspad_request!(GetCurrentBlockColor);
+    impl_into_kaspad_request!(GetP2pStats);
+    impl_into_kaspad_request!(GetProcessingStats);
+    impl_into_kaspad_request!(GetPeerFlowStates);
+    impl_into_kaspad_request!(DisconnectPeer);
+    impl_into_kaspad_request!(SetPeerProtected);
+    impl_into_kaspad_request!(GetTransactionAcceptanceData);
+    impl_into_kaspad_request!(GetChainBlockByScore);
+    impl_into_kaspad_request!(GetBlockSerialized);
+    impl_into_kaspad_request!(SubmitBlockSerialized);
+    impl_into_kaspad_request!(GetTransactionSerialized);
+    impl_into_kaspad_request!(SubmitTransactionSerialized);
+    impl_into_kaspad_request!(GetLocalTransactions);
 
     impl_into_kaspad_request!(NotifyBlockAdded);
     impl_into_kaspad_request!(NotifyNewBlockTemplate);
@@ -72,6 +84,7 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_request!(NotifyVirtualChainChanged);
     impl_into_kaspad_request!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_request!(NotifyTransactionsEvicted);
 
     macro_rules! impl_into_kaspad_request {
         ($name:tt) => {
@@ -200,6 +213,18 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_response!(GetFeeEstimate);
     impl_into_kaspad_response!(GetFeeEstimateExperimental);
     impl_into_kaspad_response!(GetCurrentBlockColor);
+    impl_into_kaspad_response!(GetP2pStats);
+    impl_into_kaspad_response!(GetProcessingStats);
+    impl_into_kaspad_response!(GetPeerFlowStates);
+    impl_into_kaspad_response!(DisconnectPeer);
+    impl_into_kaspad_response!(SetPeerProtected);
+    impl_into_kaspad_response!(GetTransactionAcceptanceData);
+    impl_into_kaspad_response!(GetChainBlockByScore);
+    impl_into_kaspad_response!(GetBlockSerialized);
+    impl_into_kaspad_response!(SubmitBlockSerialized);
+    impl_into_kaspad_response!(GetTransactionSerialized);
+    impl_into_kaspad_response!(SubmitTransactionSerialized);
+    impl_into_kaspad_response!(GetLocalTransactions);
 
     impl_into_kaspad_notify_response!(NotifyBlockAdded);
     impl_into_kaspad_notify_response!(NotifyNewBlockTemplate);
@@ -209,6 +234,7 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_notify_response!(NotifyVirtualChainChanged);
     impl_into_kaspad_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_notify_response!(NotifyTransactionsEvicted);
 
     impl_into_kaspad_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
     impl_into_kaspad_notify_response!(NotifyPruningPointUtxoSetOverride, StopNotifyingPruningPointUtxoSetOverride);
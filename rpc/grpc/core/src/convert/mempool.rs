@@ -1,6 +1,7 @@
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::RpcError;
+use kaspa_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -10,6 +11,26 @@ from!(item: &kaspa_rpc_core::RpcMempoolEntry, protowire::RpcMempoolEntry, {
     Self { fee: item.fee, transaction: Some((&item.transaction).into()), is_orphan: item.is_orphan }
 });
 
+from!(item: &kaspa_rpc_core::RpcTransactionPackage, protowire::RpcTransactionPackage, {
+    Self {
+        ancestor_count: item.ancestor_count,
+        ancestor_fees: item.ancestor_fees,
+        ancestor_mass: item.ancestor_mass,
+        descendant_count: item.descendant_count,
+        descendant_fees: item.descendant_fees,
+        descendant_mass: item.descendant_mass,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcLocalTransaction, protowire::RpcLocalTransaction, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        added_at_daa_score: item.added_at_daa_score,
+        expire_daa_score: item.expire_daa_score.unwrap_or_default(),
+        is_orphan: item.is_orphan,
+    }
+});
+
 from!(item: &kaspa_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntryByAddress, {
     Self {
         address: (&item.address).into(),
@@ -33,6 +54,26 @@ try_from!(item: &protowire::RpcMempoolEntry, kaspa_rpc_core::RpcMempoolEntry, {
     )
 });
 
+try_from!(item: &protowire::RpcTransactionPackage, kaspa_rpc_core::RpcTransactionPackage, {
+    Self {
+        ancestor_count: item.ancestor_count,
+        ancestor_fees: item.ancestor_fees,
+        ancestor_mass: item.ancestor_mass,
+        descendant_count: item.descendant_count,
+        descendant_fees: item.descendant_fees,
+        descendant_mass: item.descendant_mass,
+    }
+});
+
+try_from!(item: &protowire::RpcLocalTransaction, kaspa_rpc_core::RpcLocalTransaction, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        added_at_daa_score: item.added_at_daa_score,
+        expire_daa_score: (item.expire_daa_score != 0).then_some(item.expire_daa_score),
+        is_orphan: item.is_orphan,
+    }
+});
+
 try_from!(item: &protowire::RpcMempoolEntryByAddress, kaspa_rpc_core::RpcMempoolEntryByAddress, {
     Self::new(
         item.address.as_str().try_into()?,
@@ -23,7 +23,7 @@ use kaspa_consensus_core::network::NetworkId;
 use kaspa_core::debug;
 use kaspa_notify::subscription::Command;
 use kaspa_rpc_core::{
-    RpcContextualPeerAddress, RpcError, RpcExtraData, RpcHash, RpcIpAddress, RpcNetworkType, RpcPeerAddress, RpcResult,
+    RpcContextualPeerAddress, RpcError, RpcExtraData, RpcHash, RpcIpAddress, RpcNetworkType, RpcNodeId, RpcPeerAddress, RpcResult,
     SubmitBlockRejectReason, SubmitBlockReport,
 };
 use kaspa_utils::hex::*;
@@ -147,19 +147,30 @@ from!(item: &kaspa_rpc_core::SubmitBlockRequest, protowire::SubmitBlockRequestMe
 from!(item: RpcResult<&kaspa_rpc_core::SubmitBlockResponse>, protowire::SubmitBlockResponseMessage, {
     let error: Option<protowire::RpcError> = match item.report {
         kaspa_rpc_core::SubmitBlockReport::Success => None,
-        kaspa_rpc_core::SubmitBlockReport::Reject(reason) => Some(RpcError::SubmitBlockError(reason).into())
+        kaspa_rpc_core::SubmitBlockReport::Reject(reason) => match item.error.as_ref() {
+            // The rejection details are appended to the error message, which is the only protowire field able to carry them
+            Some(details) => Some(protowire::RpcError { message: format!("{}: {}", RpcError::SubmitBlockError(reason), details) }),
+            None => Some(RpcError::SubmitBlockError(reason).into()),
+        },
     };
     Self { reject_reason: RejectReason::from(&item.report) as i32, error }
 });
 
 from!(item: &kaspa_rpc_core::GetBlockTemplateRequest, protowire::GetBlockTemplateRequestMessage, {
     Self {
-        pay_address: (&item.pay_address).into(),
+        pay_address: item.pay_address.as_ref().map(|address| address.into()).unwrap_or_default(),
         extra_data: String::from_utf8(item.extra_data.clone()).expect("extra data has to be valid UTF-8"),
     }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetBlockTemplateResponse>, protowire::GetBlockTemplateResponseMessage, {
-    Self { block: Some((&item.block).into()), is_synced: item.is_synced, error: None }
+    Self {
+        block: Some((&item.block).into()),
+        is_synced: item.is_synced,
+        block_reward: item.block_reward,
+        block_subsidy: item.block_subsidy,
+        virtual_daa_score: item.virtual_daa_score,
+        error: None,
+    }
 });
 
 from!(item: &kaspa_rpc_core::GetBlockRequest, protowire::GetBlockRequestMessage, {
@@ -184,6 +195,9 @@ from!(item: RpcResult<&kaspa_rpc_core::GetInfoResponse>, protowire::GetInfoRespo
         is_synced: item.is_synced,
         has_notify_command: item.has_notify_command,
         has_message_id: item.has_message_id,
+        network_id: item.network_id.map(|network_id| network_id.to_string()).unwrap_or_default(),
+        unsafe_rpc: item.unsafe_rpc,
+        cache_policy: item.cache_policy.clone().unwrap_or_default(),
         error: None,
     }
 });
@@ -193,18 +207,28 @@ from!(item: &kaspa_rpc_core::NotifyNewBlockTemplateRequest, protowire::NotifyNew
 });
 from!(RpcResult<&kaspa_rpc_core::NotifyNewBlockTemplateResponse>, protowire::NotifyNewBlockTemplateResponseMessage);
 
+from!(item: &kaspa_rpc_core::NotifyTransactionsEvictedRequest, protowire::NotifyTransactionsEvictedRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyTransactionsEvictedResponse>, protowire::NotifyTransactionsEvictedResponseMessage);
+
 // ~~~
 
 from!(&kaspa_rpc_core::GetCurrentNetworkRequest, protowire::GetCurrentNetworkRequestMessage);
 from!(item: RpcResult<&kaspa_rpc_core::GetCurrentNetworkResponse>, protowire::GetCurrentNetworkResponseMessage, {
-    Self { current_network: item.network.to_string(), error: None }
+    Self {
+        current_network: item.network.to_string(),
+        network_id: item.network_id.map(|network_id| network_id.to_string()).unwrap_or_default(),
+        error: None,
+    }
 });
 
-from!(&kaspa_rpc_core::GetPeerAddressesRequest, protowire::GetPeerAddressesRequestMessage);
+from!(item: &kaspa_rpc_core::GetPeerAddressesRequest, protowire::GetPeerAddressesRequestMessage, { Self { verbose: item.verbose } });
 from!(item: RpcResult<&kaspa_rpc_core::GetPeerAddressesResponse>, protowire::GetPeerAddressesResponseMessage, {
     Self {
         addresses: item.known_addresses.iter().map(|x| x.into()).collect(),
         banned_addresses: item.banned_addresses.iter().map(|x| x.into()).collect(),
+        peer_stats: item.peer_stats.iter().map(|x| x.into()).collect(),
         error: None,
     }
 });
@@ -222,11 +246,15 @@ from!(item: &kaspa_rpc_core::GetMempoolEntryRequest, protowire::GetMempoolEntryR
     }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetMempoolEntryResponse>, protowire::GetMempoolEntryResponseMessage, {
-    Self { entry: Some((&item.mempool_entry).into()), error: None }
+    Self { entry: Some((&item.mempool_entry).into()), package: item.package.as_ref().map(|x| x.into()), error: None }
 });
 
 from!(item: &kaspa_rpc_core::GetMempoolEntriesRequest, protowire::GetMempoolEntriesRequestMessage, {
-    Self { include_orphan_pool: item.include_orphan_pool, filter_transaction_pool: item.filter_transaction_pool }
+    Self {
+        include_orphan_pool: item.include_orphan_pool,
+        filter_transaction_pool: item.filter_transaction_pool,
+        subnetwork_id: item.subnetwork_id.as_ref().map(|subnetwork_id| subnetwork_id.to_string()).unwrap_or_default(),
+    }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetMempoolEntriesResponse>, protowire::GetMempoolEntriesResponseMessage, {
     Self { entries: item.mempool_entries.iter().map(|x| x.into()).collect(), error: None }
@@ -360,10 +388,19 @@ from!(item: RpcResult<&kaspa_rpc_core::GetSinkBlueScoreResponse>, protowire::Get
     Self { blue_score: item.blue_score, error: None }
 });
 
-from!(item: &kaspa_rpc_core::BanRequest, protowire::BanRequestMessage, { Self { ip: item.ip.to_string() } });
+from!(item: &kaspa_rpc_core::BanRequest, protowire::BanRequestMessage, {
+    Self {
+        ip: item.ip.to_string(),
+        prefix_length: item.prefix_length.unwrap_or_default().into(),
+        duration: item.duration.unwrap_or_default(),
+        is_permanent: item.is_permanent,
+    }
+});
 from!(_item: RpcResult<&kaspa_rpc_core::BanResponse>, protowire::BanResponseMessage, { Self { error: None } });
 
-from!(item: &kaspa_rpc_core::UnbanRequest, protowire::UnbanRequestMessage, { Self { ip: item.ip.to_string() } });
+from!(item: &kaspa_rpc_core::UnbanRequest, protowire::UnbanRequestMessage, {
+    Self { ip: item.ip.to_string(), prefix_length: item.prefix_length.unwrap_or_default().into() }
+});
 from!(_item: RpcResult<&kaspa_rpc_core::UnbanResponse>, protowire::UnbanResponseMessage, { Self { error: None } });
 
 from!(item: &kaspa_rpc_core::EstimateNetworkHashesPerSecondRequest, protowire::EstimateNetworkHashesPerSecondRequestMessage, {
@@ -430,6 +467,105 @@ from!(item: RpcResult<&kaspa_rpc_core::GetCurrentBlockColorResponse>, protowire:
     Self { blue: item.blue, error: None }
 });
 
+from!(&kaspa_rpc_core::GetP2pStatsRequest, protowire::GetP2pStatsRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetP2pStatsResponse>, protowire::GetP2pStatsResponseMessage, {
+    Self {
+        message_stats: item.message_stats.iter().map(|x| x.into()).collect(),
+        top_peers: item.top_peers.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
+from!(&kaspa_rpc_core::GetProcessingStatsRequest, protowire::GetProcessingStatsRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetProcessingStatsResponse>, protowire::GetProcessingStatsResponseMessage, {
+    Self {
+        blocks_submitted_count: item.blocks_submitted_count,
+        headers_processed_count: item.headers_processed_count,
+        dependencies_processed_count: item.dependencies_processed_count,
+        bodies_processed_count: item.bodies_processed_count,
+        transactions_processed_count: item.transactions_processed_count,
+        chain_blocks_processed_count: item.chain_blocks_processed_count,
+        chain_disqualified_count: item.chain_disqualified_count,
+        mass_processed_count: item.mass_processed_count,
+        interval_millis: item.interval_millis,
+        headers_per_second: item.headers_per_second,
+        bodies_per_second: item.bodies_per_second,
+        transactions_per_second: item.transactions_per_second,
+        dependencies_per_second: item.dependencies_per_second,
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::GetPeerFlowStatesRequest, protowire::GetPeerFlowStatesRequestMessage, {
+    Self { peer_id: item.peer_id.map(|id| id.to_string()).unwrap_or_default() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetPeerFlowStatesResponse>, protowire::GetPeerFlowStatesResponseMessage, {
+    Self { peers: item.peers.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::DisconnectPeerRequest, protowire::DisconnectPeerRequestMessage, {
+    Self { peer_id: item.peer_id.to_string() }
+});
+from!(_item: RpcResult<&kaspa_rpc_core::DisconnectPeerResponse>, protowire::DisconnectPeerResponseMessage, { Self { error: None } });
+
+from!(item: &kaspa_rpc_core::SetPeerProtectedRequest, protowire::SetPeerProtectedRequestMessage, {
+    Self { peer_id: item.peer_id.to_string(), is_protected: item.is_protected }
+});
+from!(_item: RpcResult<&kaspa_rpc_core::SetPeerProtectedResponse>, protowire::SetPeerProtectedResponseMessage, { Self { error: None } });
+
+from!(item: &kaspa_rpc_core::GetTransactionAcceptanceDataRequest, protowire::GetTransactionAcceptanceDataRequestMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionAcceptanceDataResponse>, protowire::GetTransactionAcceptanceDataResponseMessage, {
+    Self { acceptances: item.acceptances.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetChainBlockByScoreRequest, protowire::GetChainBlockByScoreRequestMessage, {
+    Self { score: item.score, by_blue_score: item.by_blue_score }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetChainBlockByScoreResponse>, protowire::GetChainBlockByScoreResponseMessage, {
+    Self { block_hash: item.block_hash.to_string(), daa_score: item.daa_score, blue_score: item.blue_score, error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetBlockSerializedRequest, protowire::GetBlockSerializedRequestMessage, {
+    Self { hash: item.hash.to_string() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetBlockSerializedResponse>, protowire::GetBlockSerializedResponseMessage, {
+    Self { block_hex: item.block_hex.clone(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::SubmitBlockSerializedRequest, protowire::SubmitBlockSerializedRequestMessage, {
+    Self { block_hex: item.block_hex.clone(), allow_non_daa_blocks: item.allow_non_daa_blocks }
+});
+// Like SubmitBlockResponseMessage, of which the conversion is reused, this message breaks the general conversion convention
+from!(item: RpcResult<&kaspa_rpc_core::SubmitBlockSerializedResponse>, protowire::SubmitBlockSerializedResponseMessage, {
+    let response = kaspa_rpc_core::SubmitBlockResponse { report: item.report.clone(), error: item.error.clone() };
+    let message = protowire::SubmitBlockResponseMessage::from(Ok(&response));
+    Self { reject_reason: message.reject_reason, error: message.error }
+});
+
+from!(item: &kaspa_rpc_core::GetTransactionSerializedRequest, protowire::GetTransactionSerializedRequestMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        block_hash: item.block_hash.map_or(Default::default(), |x| x.to_string()),
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionSerializedResponse>, protowire::GetTransactionSerializedResponseMessage, {
+    Self { transaction_hex: item.transaction_hex.clone(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::SubmitTransactionSerializedRequest, protowire::SubmitTransactionSerializedRequestMessage, {
+    Self { transaction_hex: item.transaction_hex.clone(), allow_orphan: item.allow_orphan }
+});
+from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionSerializedResponse>, protowire::SubmitTransactionSerializedResponseMessage, {
+    Self { transaction_id: item.transaction_id.to_string(), error: None }
+});
+
+from!(&kaspa_rpc_core::GetLocalTransactionsRequest, protowire::GetLocalTransactionsRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetLocalTransactionsResponse>, protowire::GetLocalTransactionsResponseMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect(), error: None }
+});
+
 from!(&kaspa_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&kaspa_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -441,6 +577,7 @@ from!(item: &kaspa_rpc_core::GetMetricsRequest, protowire::GetMetricsRequestMess
         consensus_metrics: item.consensus_metrics,
         storage_metrics: item.storage_metrics,
         custom_metrics: item.custom_metrics,
+        allocation_metrics: item.allocation_metrics,
     }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetMetricsResponse>, protowire::GetMetricsResponseMessage, {
@@ -453,6 +590,7 @@ from!(item: RpcResult<&kaspa_rpc_core::GetMetricsResponse>, protowire::GetMetric
         storage_metrics: item.storage_metrics.as_ref().map(|x| x.into()),
         // TODO
         // custom_metrics : None,
+        allocation_metrics: item.allocation_metrics.as_ref().map(|x| x.into()),
         error: None,
     }
 });
@@ -503,6 +641,7 @@ from!(&kaspa_rpc_core::GetSyncStatusRequest, protowire::GetSyncStatusRequestMess
 from!(item: RpcResult<&kaspa_rpc_core::GetSyncStatusResponse>, protowire::GetSyncStatusResponseMessage, {
     Self {
         is_synced: item.is_synced,
+        progress: Some((&item.progress).into()),
         error: None,
     }
 });
@@ -584,21 +723,31 @@ impl TryFrom<&protowire::SubmitBlockResponseMessage> for kaspa_rpc_core::SubmitB
             match report {
                 SubmitBlockReport::Success => {
                     if err.message == RpcError::SubmitBlockError(SubmitBlockRejectReason::RouteIsFull).to_string() {
-                        Ok(Self { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull) })
+                        Ok(Self { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull), error: None })
                     } else {
                         Err(err.into())
                     }
                 }
-                SubmitBlockReport::Reject(_) => Ok(Self { report }),
+                SubmitBlockReport::Reject(reason) => {
+                    let error = err
+                        .message
+                        .strip_prefix(&RpcError::SubmitBlockError(reason).to_string())
+                        .and_then(|details| details.strip_prefix(": "))
+                        .map(|details| details.to_string());
+                    Ok(Self { report, error })
+                }
             }
         } else {
-            Ok(Self { report })
+            Ok(Self { report, error: None })
         }
     }
 }
 
 try_from!(item: &protowire::GetBlockTemplateRequestMessage, kaspa_rpc_core::GetBlockTemplateRequest, {
-    Self { pay_address: item.pay_address.clone().try_into()?, extra_data: RpcExtraData::from_iter(item.extra_data.bytes()) }
+    Self {
+        pay_address: if item.pay_address.is_empty() { None } else { Some(item.pay_address.clone().try_into()?) },
+        extra_data: RpcExtraData::from_iter(item.extra_data.bytes()),
+    }
 });
 try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<kaspa_rpc_core::GetBlockTemplateResponse>, {
     Self {
@@ -608,6 +757,9 @@ try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<kaspa_rpc
             .ok_or_else(|| RpcError::MissingRpcFieldError("GetBlockTemplateResponseMessage".to_string(), "block".to_string()))?
             .try_into()?,
         is_synced: item.is_synced,
+        block_reward: item.block_reward,
+        block_subsidy: item.block_subsidy,
+        virtual_daa_score: item.virtual_daa_score,
     }
 });
 
@@ -639,6 +791,9 @@ try_from!(item: &protowire::GetInfoResponseMessage, RpcResult<kaspa_rpc_core::Ge
         is_synced: item.is_synced,
         has_notify_command: item.has_notify_command,
         has_message_id: item.has_message_id,
+        network_id: if item.network_id.is_empty() { None } else { Some(NetworkId::from_str(&item.network_id)?) },
+        unsafe_rpc: item.unsafe_rpc,
+        cache_policy: if item.cache_policy.is_empty() { None } else { Some(item.cache_policy.clone()) },
     }
 });
 
@@ -647,6 +802,11 @@ try_from!(item: &protowire::NotifyNewBlockTemplateRequestMessage, kaspa_rpc_core
 });
 try_from!(&protowire::NotifyNewBlockTemplateResponseMessage, RpcResult<kaspa_rpc_core::NotifyNewBlockTemplateResponse>);
 
+try_from!(item: &protowire::NotifyTransactionsEvictedRequestMessage, kaspa_rpc_core::NotifyTransactionsEvictedRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(&protowire::NotifyTransactionsEvictedResponseMessage, RpcResult<kaspa_rpc_core::NotifyTransactionsEvictedResponse>);
+
 // ~~~
 
 try_from!(&protowire::GetCurrentNetworkRequestMessage, kaspa_rpc_core::GetCurrentNetworkRequest);
@@ -654,14 +814,20 @@ try_from!(item: &protowire::GetCurrentNetworkResponseMessage, RpcResult<kaspa_rp
     // Note that current_network is first converted to lowercase because the golang implementation
     // returns a "human readable" version with a capital first letter while the rusty version
     // is fully lowercase.
-    Self { network: RpcNetworkType::from_str(&item.current_network.to_lowercase())? }
+    Self {
+        network: RpcNetworkType::from_str(&item.current_network.to_lowercase())?,
+        network_id: if item.network_id.is_empty() { None } else { Some(NetworkId::from_str(&item.network_id)?) },
+    }
 });
 
-try_from!(&protowire::GetPeerAddressesRequestMessage, kaspa_rpc_core::GetPeerAddressesRequest);
+try_from!(item: &protowire::GetPeerAddressesRequestMessage, kaspa_rpc_core::GetPeerAddressesRequest, {
+    Self { verbose: item.verbose }
+});
 try_from!(item: &protowire::GetPeerAddressesResponseMessage, RpcResult<kaspa_rpc_core::GetPeerAddressesResponse>, {
     Self {
         known_addresses: item.addresses.iter().map(RpcPeerAddress::try_from).collect::<Result<Vec<_>, _>>()?,
         banned_addresses: item.banned_addresses.iter().map(RpcIpAddress::try_from).collect::<Result<Vec<_>, _>>()?,
+        peer_stats: item.peer_stats.iter().map(kaspa_rpc_core::RpcPeerStats::try_from).collect::<Result<Vec<_>, _>>()?,
     }
 });
 
@@ -684,11 +850,20 @@ try_from!(item: &protowire::GetMempoolEntryResponseMessage, RpcResult<kaspa_rpc_
             .as_ref()
             .ok_or_else(|| RpcError::MissingRpcFieldError("GetMempoolEntryResponseMessage".to_string(), "entry".to_string()))?
             .try_into()?,
+        package: item.package.as_ref().map(|x| x.try_into()).transpose()?,
     }
 });
 
 try_from!(item: &protowire::GetMempoolEntriesRequestMessage, kaspa_rpc_core::GetMempoolEntriesRequest, {
-    Self { include_orphan_pool: item.include_orphan_pool, filter_transaction_pool: item.filter_transaction_pool }
+    Self {
+        include_orphan_pool: item.include_orphan_pool,
+        filter_transaction_pool: item.filter_transaction_pool,
+        subnetwork_id: if item.subnetwork_id.is_empty() {
+            None
+        } else {
+            Some(kaspa_rpc_core::RpcSubnetworkId::from_str(&item.subnetwork_id)?)
+        },
+    }
 });
 try_from!(item: &protowire::GetMempoolEntriesResponseMessage, RpcResult<kaspa_rpc_core::GetMempoolEntriesResponse>, {
     Self { mempool_entries: item.entries.iter().map(kaspa_rpc_core::RpcMempoolEntry::try_from).collect::<Result<Vec<_>, _>>()? }
@@ -837,10 +1012,19 @@ try_from!(item: &protowire::GetSinkBlueScoreResponseMessage, RpcResult<kaspa_rpc
     Self { blue_score: item.blue_score }
 });
 
-try_from!(item: &protowire::BanRequestMessage, kaspa_rpc_core::BanRequest, { Self { ip: RpcIpAddress::from_str(&item.ip)? } });
+try_from!(item: &protowire::BanRequestMessage, kaspa_rpc_core::BanRequest, {
+    Self {
+        ip: RpcIpAddress::from_str(&item.ip)?,
+        prefix_length: (item.prefix_length > 0).then(|| u8::try_from(item.prefix_length)).transpose()?,
+        duration: (item.duration > 0).then_some(item.duration),
+        is_permanent: item.is_permanent,
+    }
+});
 try_from!(&protowire::BanResponseMessage, RpcResult<kaspa_rpc_core::BanResponse>);
 
-try_from!(item: &protowire::UnbanRequestMessage, kaspa_rpc_core::UnbanRequest, { Self { ip: RpcIpAddress::from_str(&item.ip)? } });
+try_from!(item: &protowire::UnbanRequestMessage, kaspa_rpc_core::UnbanRequest, {
+    Self { ip: RpcIpAddress::from_str(&item.ip)?, prefix_length: (item.prefix_length > 0).then(|| u8::try_from(item.prefix_length)).transpose()? }
+});
 try_from!(&protowire::UnbanResponseMessage, RpcResult<kaspa_rpc_core::UnbanResponse>);
 
 try_from!(item: &protowire::EstimateNetworkHashesPerSecondRequestMessage, kaspa_rpc_core::EstimateNetworkHashesPerSecondRequest, {
@@ -917,6 +1101,115 @@ try_from!(item: &protowire::GetCurrentBlockColorResponseMessage, RpcResult<kaspa
     }
 });
 
+try_from!(&protowire::GetP2pStatsRequestMessage, kaspa_rpc_core::GetP2pStatsRequest);
+try_from!(item: &protowire::GetP2pStatsResponseMessage, RpcResult<kaspa_rpc_core::GetP2pStatsResponse>, {
+    Self {
+        message_stats: item
+            .message_stats
+            .iter()
+            .map(kaspa_rpc_core::RpcP2pMessageStats::try_from)
+            .collect::<Result<Vec<_>, _>>()?,
+        top_peers: item.top_peers.iter().map(kaspa_rpc_core::RpcPeerContribution::try_from).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(&protowire::GetProcessingStatsRequestMessage, kaspa_rpc_core::GetProcessingStatsRequest);
+try_from!(item: &protowire::GetProcessingStatsResponseMessage, RpcResult<kaspa_rpc_core::GetProcessingStatsResponse>, {
+    Self {
+        blocks_submitted_count: item.blocks_submitted_count,
+        headers_processed_count: item.headers_processed_count,
+        dependencies_processed_count: item.dependencies_processed_count,
+        bodies_processed_count: item.bodies_processed_count,
+        transactions_processed_count: item.transactions_processed_count,
+        chain_blocks_processed_count: item.chain_blocks_processed_count,
+        chain_disqualified_count: item.chain_disqualified_count,
+        mass_processed_count: item.mass_processed_count,
+        interval_millis: item.interval_millis,
+        headers_per_second: item.headers_per_second,
+        bodies_per_second: item.bodies_per_second,
+        transactions_per_second: item.transactions_per_second,
+        dependencies_per_second: item.dependencies_per_second,
+    }
+});
+
+try_from!(item: &protowire::GetPeerFlowStatesRequestMessage, kaspa_rpc_core::GetPeerFlowStatesRequest, {
+    Self { peer_id: (!item.peer_id.is_empty()).then(|| RpcNodeId::from_str(&item.peer_id)).transpose()? }
+});
+try_from!(item: &protowire::GetPeerFlowStatesResponseMessage, RpcResult<kaspa_rpc_core::GetPeerFlowStatesResponse>, {
+    Self { peers: item.peers.iter().map(kaspa_rpc_core::RpcPeerFlowStates::try_from).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::DisconnectPeerRequestMessage, kaspa_rpc_core::DisconnectPeerRequest, {
+    Self { peer_id: RpcNodeId::from_str(&item.peer_id)? }
+});
+try_from!(&protowire::DisconnectPeerResponseMessage, RpcResult<kaspa_rpc_core::DisconnectPeerResponse>);
+
+try_from!(item: &protowire::SetPeerProtectedRequestMessage, kaspa_rpc_core::SetPeerProtectedRequest, {
+    Self { peer_id: RpcNodeId::from_str(&item.peer_id)?, is_protected: item.is_protected }
+});
+try_from!(&protowire::SetPeerProtectedResponseMessage, RpcResult<kaspa_rpc_core::SetPeerProtectedResponse>);
+
+try_from!(item: &protowire::GetTransactionAcceptanceDataRequestMessage, kaspa_rpc_core::GetTransactionAcceptanceDataRequest, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::GetTransactionAcceptanceDataResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionAcceptanceDataResponse>, {
+    Self {
+        acceptances: item
+            .acceptances
+            .iter()
+            .map(kaspa_rpc_core::RpcTransactionAcceptance::try_from)
+            .collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::GetChainBlockByScoreRequestMessage, kaspa_rpc_core::GetChainBlockByScoreRequest, {
+    Self { score: item.score, by_blue_score: item.by_blue_score }
+});
+try_from!(item: &protowire::GetChainBlockByScoreResponseMessage, RpcResult<kaspa_rpc_core::GetChainBlockByScoreResponse>, {
+    Self { block_hash: RpcHash::from_str(&item.block_hash)?, daa_score: item.daa_score, blue_score: item.blue_score }
+});
+
+try_from!(item: &protowire::GetBlockSerializedRequestMessage, kaspa_rpc_core::GetBlockSerializedRequest, {
+    Self { hash: RpcHash::from_str(&item.hash)? }
+});
+try_from!(item: &protowire::GetBlockSerializedResponseMessage, RpcResult<kaspa_rpc_core::GetBlockSerializedResponse>, {
+    Self { block_hex: item.block_hex.clone() }
+});
+
+try_from!(item: &protowire::SubmitBlockSerializedRequestMessage, kaspa_rpc_core::SubmitBlockSerializedRequest, {
+    Self { block_hex: item.block_hex.clone(), allow_non_daa_blocks: item.allow_non_daa_blocks }
+});
+impl TryFrom<&protowire::SubmitBlockSerializedResponseMessage> for kaspa_rpc_core::SubmitBlockSerializedResponse {
+    type Error = RpcError;
+    // Like SubmitBlockResponseMessage, of which the conversion is reused, this message breaks the general conversion convention
+    fn try_from(item: &protowire::SubmitBlockSerializedResponseMessage) -> RpcResult<Self> {
+        let message = protowire::SubmitBlockResponseMessage { reject_reason: item.reject_reason, error: item.error.clone() };
+        Ok(kaspa_rpc_core::SubmitBlockResponse::try_from(&message)?.into())
+    }
+}
+
+try_from!(item: &protowire::GetTransactionSerializedRequestMessage, kaspa_rpc_core::GetTransactionSerializedRequest, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        block_hash: if item.block_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.block_hash)?) },
+    }
+});
+try_from!(item: &protowire::GetTransactionSerializedResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionSerializedResponse>, {
+    Self { transaction_hex: item.transaction_hex.clone() }
+});
+
+try_from!(item: &protowire::SubmitTransactionSerializedRequestMessage, kaspa_rpc_core::SubmitTransactionSerializedRequest, {
+    Self { transaction_hex: item.transaction_hex.clone(), allow_orphan: item.allow_orphan }
+});
+try_from!(item: &protowire::SubmitTransactionSerializedResponseMessage, RpcResult<kaspa_rpc_core::SubmitTransactionSerializedResponse>, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+
+try_from!(&protowire::GetLocalTransactionsRequestMessage, kaspa_rpc_core::GetLocalTransactionsRequest);
+try_from!(item: &protowire::GetLocalTransactionsResponseMessage, RpcResult<kaspa_rpc_core::GetLocalTransactionsResponse>, {
+    Self { transactions: item.transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
 try_from!(&protowire::PingRequestMessage, kaspa_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<kaspa_rpc_core::PingResponse>);
 
@@ -928,6 +1221,7 @@ try_from!(item: &protowire::GetMetricsRequestMessage, kaspa_rpc_core::GetMetrics
         consensus_metrics: item.consensus_metrics,
         storage_metrics: item.storage_metrics,
         custom_metrics : item.custom_metrics,
+        allocation_metrics: item.allocation_metrics,
     }
 });
 try_from!(item: &protowire::GetMetricsResponseMessage, RpcResult<kaspa_rpc_core::GetMetricsResponse>, {
@@ -940,6 +1234,7 @@ try_from!(item: &protowire::GetMetricsResponseMessage, RpcResult<kaspa_rpc_core:
         storage_metrics: item.storage_metrics.as_ref().map(|x| x.try_into()).transpose()?,
         // TODO
         custom_metrics: None,
+        allocation_metrics: item.allocation_metrics.as_ref().map(|x| x.try_into()).transpose()?,
     }
 });
 
@@ -984,6 +1279,7 @@ try_from!(&protowire::GetSyncStatusRequestMessage, kaspa_rpc_core::GetSyncStatus
 try_from!(item: &protowire::GetSyncStatusResponseMessage, RpcResult<kaspa_rpc_core::GetSyncStatusResponse>, {
     Self {
         is_synced: item.is_synced,
+        progress: item.progress.as_ref().map(kaspa_rpc_core::RpcSyncProgress::try_from).transpose()?.unwrap_or_default(),
     }
 });
 
@@ -1069,11 +1365,11 @@ mod tests {
         }
         let tests = vec![
             Test::new(
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Success }),
+                Ok(SubmitBlockResponse { report: SubmitBlockReport::Success, error: None }),
                 SubmitBlockResponseMessage { reject_reason: RejectReason::None as i32, error: None },
             ),
             Test::new(
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) }),
+                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid), error: None }),
                 SubmitBlockResponseMessage {
                     reject_reason: RejectReason::BlockInvalid as i32,
                     error: Some(protowire::RpcError {
@@ -1082,7 +1378,22 @@ mod tests {
                 },
             ),
             Test::new(
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::IsInIBD) }),
+                Ok(SubmitBlockResponse {
+                    report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid),
+                    error: Some("Block rejected. Reason: expected header blue work 2 but got 3".to_string()),
+                }),
+                SubmitBlockResponseMessage {
+                    reject_reason: RejectReason::BlockInvalid as i32,
+                    error: Some(protowire::RpcError {
+                        message: format!(
+                            "{}: Block rejected. Reason: expected header blue work 2 but got 3",
+                            RpcError::SubmitBlockError(SubmitBlockRejectReason::BlockInvalid)
+                        ),
+                    }),
+                },
+            ),
+            Test::new(
+                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::IsInIBD), error: None }),
                 SubmitBlockResponseMessage {
                     reject_reason: RejectReason::IsInIbd as i32,
                     error: Some(protowire::RpcError {
@@ -1091,7 +1402,7 @@ mod tests {
                 },
             ),
             Test::new(
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull) }),
+                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull), error: None }),
                 SubmitBlockResponseMessage {
                     reject_reason: RejectReason::None as i32, // This rpc core reject reason has no matching protowire variant
                     error: Some(protowire::RpcError {
@@ -1113,6 +1424,7 @@ mod tests {
                 Ok(ref cnv_response) => {
                     let Ok(ref response) = test.rpc_core else { panic!() };
                     assert_eq!(cnv_response.report, response.report);
+                    assert_eq!(cnv_response.error, response.error);
                 }
                 Err(ref cnv_err) => {
                     let Err(ref err) = test.rpc_core else { panic!() };
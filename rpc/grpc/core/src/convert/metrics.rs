@@ -37,6 +37,17 @@ from!(item: &kaspa_rpc_core::ConnectionMetrics, protowire::ConnectionMetrics, {
         json_connection_attempts: item.json_connection_attempts,
         json_handshake_failures: item.json_handshake_failures,
         active_peers: item.active_peers,
+        connection_subscriptions: item.connection_subscriptions.iter().map(|x| x.into()).collect(),
+        leaked_listeners: item.leaked_listeners,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcConnectionSubscriptions, protowire::RpcConnectionSubscriptions, {
+    Self {
+        protocol: item.protocol.clone(),
+        connection: item.connection.clone(),
+        listener_id: item.listener_id,
+        subscriptions: item.subscriptions,
     }
 });
 
@@ -50,6 +61,12 @@ from!(item: &kaspa_rpc_core::BandwidthMetrics, protowire::BandwidthMetrics, {
         grpc_p2p_bytes_rx: item.p2p_bytes_rx,
         grpc_user_bytes_tx: item.grpc_bytes_tx,
         grpc_user_bytes_rx: item.grpc_bytes_rx,
+        p2p_messages_tx: item.p2p_messages_tx,
+        p2p_messages_rx: item.p2p_messages_rx,
+        p2p_known_blocks_hits: item.p2p_known_blocks_hits,
+        p2p_known_blocks_misses: item.p2p_known_blocks_misses,
+        p2p_known_transactions_hits: item.p2p_known_transactions_hits,
+        p2p_known_transactions_misses: item.p2p_known_transactions_misses,
     }
 });
 
@@ -71,6 +88,23 @@ from!(item: &kaspa_rpc_core::ConsensusMetrics, protowire::ConsensusMetrics, {
         past_median_time: item.network_past_median_time,
         virtual_parent_hashes_count: item.network_virtual_parent_hashes_count,
         virtual_daa_score: item.network_virtual_daa_score,
+
+        relay_block_submissions_in_flight: item.node_relay_block_submissions_in_flight,
+        relay_block_submissions_waiting: item.node_relay_block_submissions_waiting,
+        ibd_block_submissions_in_flight: item.node_ibd_block_submissions_in_flight,
+        ibd_block_submissions_waiting: item.node_ibd_block_submissions_waiting,
+
+        stage_latencies: item.node_stage_latencies.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::ProcessingStageLatencyMetrics, protowire::ProcessingStageLatencyMetrics, {
+    Self {
+        stage: item.stage.clone(),
+        sample_count: item.sample_count,
+        p50_micros: item.p50_micros,
+        p95_micros: item.p95_micros,
+        p99_micros: item.p99_micros,
     }
 });
 
@@ -80,6 +114,23 @@ from!(item: &kaspa_rpc_core::StorageMetrics, protowire::StorageMetrics, {
     }
 });
 
+from!(item: &kaspa_rpc_core::SubsystemAllocationMetrics, protowire::SubsystemAllocationMetrics, {
+    Self {
+        name: item.name.clone(),
+        live_bytes: item.live_bytes,
+        allocated_bytes: item.allocated_bytes,
+        freed_bytes: item.freed_bytes,
+        allocation_count: item.allocation_count,
+        deallocation_count: item.deallocation_count,
+    }
+});
+
+from!(item: &kaspa_rpc_core::AllocationMetrics, protowire::AllocationMetrics, {
+    Self {
+        subsystems: item.subsystems.iter().map(|x| x.into()).collect(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -111,6 +162,17 @@ try_from!(item: &protowire::ConnectionMetrics, kaspa_rpc_core::ConnectionMetrics
         json_connection_attempts: item.json_connection_attempts,
         json_handshake_failures: item.json_handshake_failures,
         active_peers: item.active_peers,
+        connection_subscriptions: item.connection_subscriptions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        leaked_listeners: item.leaked_listeners,
+    }
+});
+
+try_from!(item: &protowire::RpcConnectionSubscriptions, kaspa_rpc_core::RpcConnectionSubscriptions, {
+    Self {
+        protocol: item.protocol.clone(),
+        connection: item.connection.clone(),
+        listener_id: item.listener_id,
+        subscriptions: item.subscriptions,
     }
 });
 
@@ -124,6 +186,12 @@ try_from!(item: &protowire::BandwidthMetrics, kaspa_rpc_core::BandwidthMetrics,
         p2p_bytes_rx: item.grpc_p2p_bytes_rx,
         grpc_bytes_tx: item.grpc_user_bytes_tx,
         grpc_bytes_rx: item.grpc_user_bytes_rx,
+        p2p_messages_tx: item.p2p_messages_tx,
+        p2p_messages_rx: item.p2p_messages_rx,
+        p2p_known_blocks_hits: item.p2p_known_blocks_hits,
+        p2p_known_blocks_misses: item.p2p_known_blocks_misses,
+        p2p_known_transactions_hits: item.p2p_known_transactions_hits,
+        p2p_known_transactions_misses: item.p2p_known_transactions_misses,
     }
 });
 
@@ -145,6 +213,23 @@ try_from!(item: &protowire::ConsensusMetrics, kaspa_rpc_core::ConsensusMetrics,
         network_past_median_time: item.past_median_time,
         network_virtual_parent_hashes_count: item.virtual_parent_hashes_count,
         network_virtual_daa_score: item.virtual_daa_score,
+
+        node_relay_block_submissions_in_flight: item.relay_block_submissions_in_flight,
+        node_relay_block_submissions_waiting: item.relay_block_submissions_waiting,
+        node_ibd_block_submissions_in_flight: item.ibd_block_submissions_in_flight,
+        node_ibd_block_submissions_waiting: item.ibd_block_submissions_waiting,
+
+        node_stage_latencies: item.stage_latencies.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::ProcessingStageLatencyMetrics, kaspa_rpc_core::ProcessingStageLatencyMetrics, {
+    Self {
+        stage: item.stage.clone(),
+        sample_count: item.sample_count,
+        p50_micros: item.p50_micros,
+        p95_micros: item.p95_micros,
+        p99_micros: item.p99_micros,
     }
 });
 
@@ -153,3 +238,20 @@ try_from!(item: &protowire::StorageMetrics, kaspa_rpc_core::StorageMetrics, {
         storage_size_bytes: item.storage_size_bytes,
     }
 });
+
+try_from!(item: &protowire::SubsystemAllocationMetrics, kaspa_rpc_core::SubsystemAllocationMetrics, {
+    Self {
+        name: item.name.clone(),
+        live_bytes: item.live_bytes,
+        allocated_bytes: item.allocated_bytes,
+        freed_bytes: item.freed_bytes,
+        allocation_count: item.allocation_count,
+        deallocation_count: item.deallocation_count,
+    }
+});
+
+try_from!(item: &protowire::AllocationMetrics, kaspa_rpc_core::AllocationMetrics, {
+    Self {
+        subsystems: item.subsystems.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
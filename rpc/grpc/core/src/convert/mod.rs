@@ -9,4 +9,5 @@ pub mod message;
 pub mod metrics;
 pub mod notification;
 pub mod peer;
+pub mod sync;
 pub mod tx;
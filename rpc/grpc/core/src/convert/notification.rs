@@ -2,16 +2,17 @@ use crate::protowire::{
     kaspad_response::Payload, BlockAddedNotificationMessage, KaspadResponse, NewBlockTemplateNotificationMessage, RpcNotifyCommand,
 };
 use crate::protowire::{
-    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifyPruningPointUtxoSetOverrideResponseMessage, NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage,
-    PruningPointUtxoSetOverrideNotificationMessage, SinkBlueScoreChangedNotificationMessage,
-    StopNotifyingPruningPointUtxoSetOverrideRequestMessage, StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
-    StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage,
+    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotificationsGapNotificationMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifyPruningPointUtxoSetOverrideResponseMessage,
+    NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage, PruningPointUtxoSetOverrideNotificationMessage,
+    SinkBlueScoreChangedNotificationMessage, StopNotifyingPruningPointUtxoSetOverrideRequestMessage,
+    StopNotifyingPruningPointUtxoSetOverrideResponseMessage, StopNotifyingUtxosChangedRequestMessage,
+    StopNotifyingUtxosChangedResponseMessage, TransactionsEvictedNotificationMessage, UtxosChangedNotificationMessage,
     VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
 };
 use crate::{from, try_from};
 use kaspa_notify::subscription::Command;
-use kaspa_rpc_core::{Notification, RpcError, RpcHash};
+use kaspa_rpc_core::{Notification, RpcError, RpcHash, RpcTransactionId};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -34,6 +35,8 @@ from!(item: &kaspa_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::TransactionsEvicted(ref notification) => Payload::TransactionsEvictedNotification(notification.into()),
+        Notification::NotificationsGap(ref notification) => Payload::NotificationsGapNotification(notification.into()),
     }
 });
 
@@ -41,6 +44,12 @@ from!(item: &kaspa_rpc_core::BlockAddedNotification, BlockAddedNotificationMessa
 
 from!(&kaspa_rpc_core::NewBlockTemplateNotification, NewBlockTemplateNotificationMessage);
 
+from!(item: &kaspa_rpc_core::TransactionsEvictedNotification, TransactionsEvictedNotificationMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect() }
+});
+
+from!(item: &kaspa_rpc_core::NotificationsGapNotification, NotificationsGapNotificationMessage, { Self { dropped: item.dropped } });
+
 from!(item: &kaspa_rpc_core::VirtualChainChangedNotification, VirtualChainChangedNotificationMessage, {
     Self {
         removed_chain_block_hashes: item.removed_chain_block_hashes.iter().map(|x| x.to_string()).collect(),
@@ -117,6 +126,8 @@ try_from!(item: &Payload, kaspa_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::TransactionsEvictedNotification(ref notification) => Notification::TransactionsEvicted(notification.try_into()?),
+        Payload::NotificationsGapNotification(ref notification) => Notification::NotificationsGap(notification.try_into()?),
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -134,6 +145,16 @@ try_from!(item: &BlockAddedNotificationMessage, kaspa_rpc_core::BlockAddedNotifi
 
 try_from!(&NewBlockTemplateNotificationMessage, kaspa_rpc_core::NewBlockTemplateNotification);
 
+try_from!(item: &TransactionsEvictedNotificationMessage, kaspa_rpc_core::TransactionsEvictedNotification, {
+    Self {
+        transaction_ids: Arc::new(item.transaction_ids.iter().map(|x| RpcTransactionId::from_str(x)).collect::<Result<Vec<_>, _>>()?),
+    }
+});
+
+try_from!(item: &NotificationsGapNotificationMessage, kaspa_rpc_core::NotificationsGapNotification, {
+    Self { dropped: item.dropped }
+});
+
 try_from!(item: &VirtualChainChangedNotificationMessage, kaspa_rpc_core::VirtualChainChangedNotification, {
     Self {
         removed_chain_block_hashes: Arc::new(
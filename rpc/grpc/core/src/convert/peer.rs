@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::{RpcError, RpcNodeId, RpcPeerAddress};
+use kaspa_rpc_core::{RpcError, RpcIpAddress, RpcNodeId, RpcPeerAddress};
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -19,6 +19,59 @@ from!(item: &kaspa_rpc_core::RpcPeerInfo, protowire::GetConnectedPeerInfoMessage
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as i64,
         is_ibd_peer: item.is_ibd_peer,
+        identity: item.identity.clone().unwrap_or_default(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcP2pMessageStats, protowire::RpcP2pMessageStats, {
+    Self {
+        message_type: item.message_type.clone(),
+        incoming_count: item.incoming_count,
+        incoming_bytes: item.incoming_bytes,
+        outgoing_count: item.outgoing_count,
+        outgoing_bytes: item.outgoing_bytes,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcPeerContribution, protowire::RpcPeerContribution, {
+    Self {
+        id: item.id.to_string(),
+        address: item.address.to_string(),
+        valid_blocks: item.valid_blocks,
+        duplicate_blocks: item.duplicate_blocks,
+        invalid_blocks: item.invalid_blocks,
+        valid_headers: item.valid_headers,
+        duplicate_headers: item.duplicate_headers,
+        invalid_headers: item.invalid_headers,
+        bytes_received: item.bytes_received,
+        bytes_sent: item.bytes_sent,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcPeerStats, protowire::RpcPeerStats, {
+    Self {
+        ip: item.ip.to_string(),
+        bytes_sent: item.bytes_sent,
+        bytes_received: item.bytes_received,
+        last_seen: item.last_seen,
+        protocol_version: item.protocol_version,
+        avg_latency: item.avg_latency,
+        ban_score: item.ban_score,
+        sessions: item.sessions,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcFlowState, protowire::RpcFlowState, {
+    Self { message_types: item.message_types.clone(), queued: item.queued, capacity: item.capacity }
+});
+
+from!(item: &kaspa_rpc_core::RpcPeerFlowStates, protowire::RpcPeerFlowStates, {
+    Self {
+        id: item.id.to_string(),
+        address: item.address.to_string(),
+        is_outbound: item.is_outbound,
+        is_protected: item.is_protected,
+        flows: item.flows.iter().map(|x| x.into()).collect(),
     }
 });
 
@@ -40,6 +93,59 @@ try_from!(item: &protowire::GetConnectedPeerInfoMessage, kaspa_rpc_core::RpcPeer
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as u64,
         is_ibd_peer: item.is_ibd_peer,
+        identity: (!item.identity.is_empty()).then(|| item.identity.clone()),
+    }
+});
+
+try_from!(item: &protowire::RpcP2pMessageStats, kaspa_rpc_core::RpcP2pMessageStats, {
+    Self {
+        message_type: item.message_type.clone(),
+        incoming_count: item.incoming_count,
+        incoming_bytes: item.incoming_bytes,
+        outgoing_count: item.outgoing_count,
+        outgoing_bytes: item.outgoing_bytes,
+    }
+});
+
+try_from!(item: &protowire::RpcPeerContribution, kaspa_rpc_core::RpcPeerContribution, {
+    Self {
+        id: RpcNodeId::from_str(&item.id)?,
+        address: RpcPeerAddress::from_str(&item.address)?,
+        valid_blocks: item.valid_blocks,
+        duplicate_blocks: item.duplicate_blocks,
+        invalid_blocks: item.invalid_blocks,
+        valid_headers: item.valid_headers,
+        duplicate_headers: item.duplicate_headers,
+        invalid_headers: item.invalid_headers,
+        bytes_received: item.bytes_received,
+        bytes_sent: item.bytes_sent,
+    }
+});
+
+try_from!(item: &protowire::RpcPeerStats, kaspa_rpc_core::RpcPeerStats, {
+    Self {
+        ip: RpcIpAddress::from_str(&item.ip)?,
+        bytes_sent: item.bytes_sent,
+        bytes_received: item.bytes_received,
+        last_seen: item.last_seen,
+        protocol_version: item.protocol_version,
+        avg_latency: item.avg_latency,
+        ban_score: item.ban_score,
+        sessions: item.sessions,
+    }
+});
+
+try_from!(item: &protowire::RpcFlowState, kaspa_rpc_core::RpcFlowState, {
+    Self { message_types: item.message_types.clone(), queued: item.queued, capacity: item.capacity }
+});
+
+try_from!(item: &protowire::RpcPeerFlowStates, kaspa_rpc_core::RpcPeerFlowStates, {
+    Self {
+        id: RpcNodeId::from_str(&item.id)?,
+        address: RpcPeerAddress::from_str(&item.address)?,
+        is_outbound: item.is_outbound,
+        is_protected: item.is_protected,
+        flows: item.flows.iter().map(kaspa_rpc_core::RpcFlowState::try_from).collect::<Result<Vec<_>, _>>()?,
     }
 });
 
@@ -0,0 +1,40 @@
+use std::str::FromStr;
+
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcNodeId, RpcSyncPhase};
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcSyncPhaseProgress, protowire::RpcSyncPhaseProgress, {
+    Self { phase: item.phase.to_string(), processed: item.processed, total: item.total }
+});
+
+from!(item: &kaspa_rpc_core::RpcSyncProgress, protowire::RpcSyncProgress, {
+    Self {
+        phase: item.phase.to_string(),
+        phases: item.phases.iter().map(|x| x.into()).collect(),
+        sync_peer_id: item.sync_peer_id.map(|id| id.to_string()).unwrap_or_default(),
+        eta: item.eta.unwrap_or_default(),
+        has_eta: item.eta.is_some(),
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcSyncPhaseProgress, kaspa_rpc_core::RpcSyncPhaseProgress, {
+    Self { phase: RpcSyncPhase::from_str(&item.phase)?, processed: item.processed, total: item.total }
+});
+
+try_from!(item: &protowire::RpcSyncProgress, kaspa_rpc_core::RpcSyncProgress, {
+    Self {
+        phase: RpcSyncPhase::from_str(&item.phase)?,
+        phases: item.phases.iter().map(kaspa_rpc_core::RpcSyncPhaseProgress::try_from).collect::<Result<Vec<_>, _>>()?,
+        sync_peer_id: (!item.sync_peer_id.is_empty()).then(|| RpcNodeId::from_str(&item.sync_peer_id)).transpose()?,
+        eta: item.has_eta.then_some(item.eta),
+    }
+});
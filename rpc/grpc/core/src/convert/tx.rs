@@ -1,5 +1,6 @@
 use crate::protowire;
 use crate::{from, try_from};
+use kaspa_consensus_core::limits::Limit;
 use kaspa_rpc_core::{FromRpcHex, RpcError, RpcHash, RpcResult, RpcScriptVec, ToRpcHex};
 use std::str::FromStr;
 
@@ -63,6 +64,7 @@ from!(item: &kaspa_rpc_core::RpcTransactionVerboseData, protowire::RpcTransactio
         compute_mass: item.compute_mass,
         block_hash: item.block_hash.to_string(),
         block_time: item.block_time,
+        payload_hex: item.payload_hex.clone(),
     }
 });
 
@@ -82,6 +84,14 @@ from!(item: &kaspa_rpc_core::RpcAcceptedTransactionIds, protowire::RpcAcceptedTr
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcTransactionAcceptance, protowire::RpcTransactionAcceptance, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_blue_score: item.accepting_blue_score,
+    }
+});
+
 from!(item: &kaspa_rpc_core::RpcUtxosByAddressesEntry, protowire::RpcUtxosByAddressesEntry, {
     Self {
         address: item.address.as_ref().map_or("".to_string(), |x| x.into()),
@@ -95,6 +105,10 @@ from!(item: &kaspa_rpc_core::RpcUtxosByAddressesEntry, protowire::RpcUtxosByAddr
 // ----------------------------------------------------------------------------
 
 try_from!(item: &protowire::RpcTransaction, kaspa_rpc_core::RpcTransaction, {
+    Limit::TransactionInputs.check(item.inputs.len())?;
+    Limit::TransactionOutputs.check(item.outputs.len())?;
+    // Binary fields are hex encoded, hence decode to half their length
+    Limit::PayloadLen.check(item.payload.len() / 2)?;
     Self {
         version: item.version.try_into()?,
         inputs: item
@@ -117,6 +131,7 @@ try_from!(item: &protowire::RpcTransaction, kaspa_rpc_core::RpcTransaction, {
 });
 
 try_from!(item: &protowire::RpcTransactionInput, kaspa_rpc_core::RpcTransactionInput, {
+    Limit::SignatureScriptLen.check(item.signature_script.len() / 2)?;
     Self {
         previous_outpoint: item
             .previous_outpoint
@@ -160,6 +175,7 @@ try_from!(item: &protowire::RpcUtxoEntry, kaspa_rpc_core::RpcUtxoEntry, {
 });
 
 try_from!(item: &protowire::RpcScriptPublicKey, kaspa_rpc_core::RpcScriptPublicKey, {
+    Limit::ScriptPublicKeyLen.check(item.script_public_key.len() / 2)?;
     Self::new(u16::try_from(item.version)?, RpcScriptVec::from_rpc_hex(item.script_public_key.as_str())?)
 });
 
@@ -170,6 +186,7 @@ try_from!(item: &protowire::RpcTransactionVerboseData, kaspa_rpc_core::RpcTransa
         compute_mass: item.compute_mass,
         block_hash: RpcHash::from_str(&item.block_hash)?,
         block_time: item.block_time,
+        payload_hex: item.payload_hex.clone(),
     }
 });
 
@@ -189,6 +206,14 @@ try_from!(item: &protowire::RpcAcceptedTransactionIds, kaspa_rpc_core::RpcAccept
     }
 });
 
+try_from!(item: &protowire::RpcTransactionAcceptance, kaspa_rpc_core::RpcTransactionAcceptance, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_blue_score: item.accepting_blue_score,
+    }
+});
+
 try_from!(item: &protowire::RpcUtxosByAddressesEntry, kaspa_rpc_core::RpcUtxosByAddressesEntry, {
     let address = if item.address.is_empty() { None } else { Some(item.address.as_str().try_into()?) };
     Self {
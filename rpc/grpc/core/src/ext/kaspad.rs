@@ -3,13 +3,14 @@ use kaspa_notify::{scope::Scope, subscription::Command};
 use crate::protowire::{
     kaspad_request, kaspad_response, KaspadRequest, KaspadResponse, NotifyBlockAddedRequestMessage,
     NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage,
-    NotifyVirtualDaaScoreChangedRequestMessage,
+    NotifySinkBlueScoreChangedRequestMessage, NotifyTransactionsEvictedRequestMessage, NotifyUtxosChangedRequestMessage,
+    NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
 };
 
 impl KaspadRequest {
-    pub fn from_notification_type(scope: &Scope, command: Command) -> Self {
-        KaspadRequest { id: 0, payload: Some(kaspad_request::Payload::from_notification_type(scope, command)) }
+    /// Builds the subscription request of `scope`, if the scope requires one
+    pub fn from_notification_type(scope: &Scope, command: Command) -> Option<Self> {
+        kaspad_request::Payload::from_notification_type(scope, command).map(|payload| KaspadRequest { id: 0, payload: Some(payload) })
     }
 
     pub fn is_subscription(&self) -> bool {
@@ -18,8 +19,11 @@ impl KaspadRequest {
 }
 
 impl kaspad_request::Payload {
-    pub fn from_notification_type(scope: &Scope, command: Command) -> Self {
-        match scope {
+    /// Builds the subscription request of `scope`, if the scope requires one.
+    ///
+    /// Notifications gaps are delivered regardless of subscriptions, so their scope has no request.
+    pub fn from_notification_type(scope: &Scope, command: Command) -> Option<Self> {
+        let payload = match scope {
             Scope::BlockAdded(_) => {
                 kaspad_request::Payload::NotifyBlockAddedRequest(NotifyBlockAddedRequestMessage { command: command.into() })
             }
@@ -64,7 +68,14 @@ impl kaspad_request::Payload {
                     command: command.into(),
                 })
             }
-        }
+            Scope::TransactionsEvicted(_) => {
+                kaspad_request::Payload::NotifyTransactionsEvictedRequest(NotifyTransactionsEvictedRequestMessage {
+                    command: command.into(),
+                })
+            }
+            Scope::NotificationsGap(_) => return None,
+        };
+        Some(payload)
     }
 
     pub fn is_subscription(&self) -> bool {
@@ -79,6 +90,7 @@ impl kaspad_request::Payload {
                 | Payload::NotifyVirtualDaaScoreChangedRequest(_)
                 | Payload::NotifyPruningPointUtxoSetOverrideRequest(_)
                 | Payload::NotifyNewBlockTemplateRequest(_)
+                | Payload::NotifyTransactionsEvictedRequest(_)
                 | Payload::StopNotifyingUtxosChangedRequest(_)
                 | Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_)
         )
@@ -108,6 +120,8 @@ impl kaspad_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::TransactionsEvictedNotification(_) => true,
+            Payload::NotificationsGapNotification(_) => true,
             _ => false,
         }
     }
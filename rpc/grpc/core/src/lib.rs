@@ -10,3 +10,16 @@ pub const RPC_MAX_MESSAGE_SIZE: usize = 1024 * 1024 * 1024; // 1GB
 pub mod protowire {
     tonic::include_proto!("protowire");
 }
+
+/// The gRPC health checking protocol
+pub mod health {
+    tonic::include_proto!("grpc.health.v1");
+}
+
+/// The gRPC server reflection protocol
+pub mod reflection {
+    tonic::include_proto!("grpc.reflection.v1alpha");
+}
+
+/// Encoded `FileDescriptorSet` of all the protobuf files of the crate, served by the reflection service
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("descriptors");
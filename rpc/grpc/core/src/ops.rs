@@ -87,6 +87,18 @@ pub enum KaspadPayloadOps {
     GetFeeEstimate,
     GetFeeEstimateExperimental,
     GetCurrentBlockColor,
+    GetP2pStats,
+    GetProcessingStats,
+    GetPeerFlowStates,
+    DisconnectPeer,
+    SetPeerProtected,
+    GetTransactionAcceptanceData,
+    GetChainBlockByScore,
+    GetBlockSerialized,
+    SubmitBlockSerialized,
+    GetTransactionSerialized,
+    SubmitTransactionSerialized,
+    GetLocalTransactions,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -97,6 +109,7 @@ pub enum KaspadPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyTransactionsEvicted,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
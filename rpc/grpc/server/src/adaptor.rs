@@ -1,4 +1,4 @@
-use crate::{connection_handler::ConnectionHandler, manager::Manager};
+use crate::{connection::Connection, connection_handler::ConnectionHandler, manager::Manager};
 use kaspa_core::debug;
 use kaspa_notify::{notifier::Notifier, subscription::context::SubscriptionContext};
 use kaspa_rpc_core::{api::rpc::DynRpcService, notify::connection::ChannelConnection, Notification, RpcResult};
@@ -66,6 +66,11 @@ impl Adaptor {
         self.connection_handler.start()
     }
 
+    /// The notifier of which every client connection subscribing to notifications is a listener
+    pub fn notifier(&self) -> Arc<Notifier<Notification, Connection>> {
+        self.connection_handler.notifier()
+    }
+
     /// Terminates all connections and cleans up any additional async resources
     pub async fn stop(&self) -> RpcResult<()> {
         debug!("GRPC, Stopping the adaptor");
@@ -16,7 +16,7 @@ use kaspa_grpc_core::{
     protowire::{KaspadRequest, KaspadResponse},
 };
 use kaspa_notify::{
-    connection::Connection as ConnectionT,
+    connection::{Connection as ConnectionT, OverflowPolicy},
     error::Error as NotificationError,
     listener::{ListenerId, ListenerLifespan},
     notifier::Notifier,
@@ -275,15 +275,15 @@ impl Connection {
                     }
                 }
             }
+            // Mark as closed, preventing any further registration as a notification listener
+            connection.close();
+
             // Unregister from notifier
             connection.unregister_listener();
 
             // Drop all routes, triggering the drop of all handlers
             router.unsubscribe_all();
 
-            // Mark as closed
-            connection.close();
-
             // Send a close notification to the central Manager
             connection
                 .inner
@@ -319,8 +319,10 @@ impl Connection {
     }
 
     pub fn get_or_register_listener_id(&self) -> GrpcServerResult<ListenerId> {
+        // The closed state is checked under the lock so that no listener gets registered past its unregistration on close
+        let mut mutable_state = self.inner.mutable_state.lock();
         match self.is_closed() {
-            false => Ok(*self.inner.mutable_state.lock().listener_id.get_or_insert_with(|| {
+            false => Ok(*mutable_state.listener_id.get_or_insert_with(|| {
                 let listener_id =
                     self.inner.server_context.notifier.as_ref().register_new_listener(self.clone(), ListenerLifespan::Dynamic);
                 debug!("GRPC, Connection {} registered as notification listener {}", self, listener_id);
@@ -342,6 +344,11 @@ impl Connection {
         256
     }
 
+    /// Maximum number of notifications queued for the client before some get dropped
+    pub fn notification_queue_size() -> usize {
+        1024
+    }
+
     /// Enqueues a response to be sent to the client
     pub async fn enqueue(&self, response: KaspadResponse) -> GrpcServerResult<()> {
         assert!(response.payload.is_some(), "Kaspad gRPC message should always have a value");
@@ -356,6 +363,14 @@ impl Connection {
             }
         }
     }
+
+    /// Enqueues a notification to be sent to the client, waiting for the outgoing route to have room.
+    ///
+    /// Unlike responses, notifications flooding a slow client do not disconnect it: they are bounded
+    /// upstream by the notifier according to the connection overflow policy.
+    async fn enqueue_notification(&self, response: KaspadResponse) -> GrpcServerResult<()> {
+        self.inner.outgoing_route.send(response).await.map_err(|_| GrpcServerError::ConnectionClosed)
+    }
 }
 
 fn match_for_io_error(err_status: &tonic::Status) -> Option<&std::io::Error> {
@@ -418,7 +433,7 @@ impl ConnectionT for Connection {
 
     async fn send(&self, message: Self::Message) -> Result<(), Self::Error> {
         match !self.is_closed() {
-            true => self.enqueue((*message).clone()).await,
+            true => self.enqueue_notification((*message).clone()).await,
             false => Err(NotificationError::ConnectionClosed.into()),
         }
     }
@@ -446,4 +461,8 @@ impl ConnectionT for Connection {
     fn is_closed(&self) -> bool {
         self.inner.is_closed.load(Ordering::SeqCst)
     }
+
+    fn overflow_policy(&self) -> OverflowPolicy {
+        OverflowPolicy::Bounded(Self::notification_queue_size())
+    }
 }
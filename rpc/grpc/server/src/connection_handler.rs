@@ -1,16 +1,20 @@
 use crate::{
     collector::{GrpcServiceCollector, GrpcServiceConverter},
     connection::Connection,
+    health::HealthService,
     manager::{ManagerEvent, RegistrationRequest},
+    reflection::ReflectionService,
     request_handler::{factory::Factory, interface::Interface},
 };
 use futures::{FutureExt, Stream};
 use kaspa_core::{debug, info, warn};
 use kaspa_grpc_core::{
+    health::health_server::HealthServer,
     protowire::{
         rpc_server::{Rpc, RpcServer},
         KaspadRequest, KaspadResponse,
     },
+    reflection::server_reflection_server::ServerReflectionServer,
     RPC_MAX_MESSAGE_SIZE,
 };
 use kaspa_notify::{
@@ -131,6 +135,10 @@ impl ConnectionHandler {
         let bytes_tx = self.counters.bytes_tx.clone();
         let bytes_rx = self.counters.bytes_rx.clone();
 
+        // Load balancers and debugging tools can probe the node health and introspect the served services
+        let health_server = HealthServer::new(HealthService::new(self.server_context.core_service.clone(), self.running.clone()));
+        let reflection_server = ServerReflectionServer::new(ReflectionService::new());
+
         // Spawn server task
         let server_handle = tokio::spawn(async move {
             let protowire_server = RpcServer::new(connection_handler)
@@ -147,6 +155,8 @@ impl ConnectionHandler {
                 .layer(MapRequestBodyLayer::new(move |body| CountBytesBody::new(body, bytes_rx.clone()).boxed_unsync()))
                 .layer(MapResponseBodyLayer::new(move |body| CountBytesBody::new(body, bytes_tx.clone())))
                 .add_service(protowire_server)
+                .add_service(health_server)
+                .add_service(reflection_server)
                 .serve_with_shutdown(
                     serve_address.into(),
                     signal_receiver.map(|_| {
@@ -188,7 +198,7 @@ impl ConnectionHandler {
     }
 
     #[inline(always)]
-    fn notifier(&self) -> Arc<Notifier<Notification, Connection>> {
+    pub(crate) fn notifier(&self) -> Arc<Notifier<Notification, Connection>> {
         self.server_context.notifier.clone()
     }
 
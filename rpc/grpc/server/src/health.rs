@@ -0,0 +1,77 @@
+use futures::Stream;
+use kaspa_grpc_core::health::{health_check_response::ServingStatus, health_server::Health, HealthCheckRequest, HealthCheckResponse};
+use kaspa_rpc_core::api::rpc::DynRpcService;
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tonic::{Request, Response, Status};
+
+/// The services reported by the health service, the empty name standing for the server as a whole
+const HEALTH_CHECKED_SERVICES: [&str; 2] = ["", "protowire.RPC"];
+
+/// Interval at which watched statuses are refreshed
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A gRPC health service compatible with `grpc-health-probe`, reporting the node as serving only once it is synced
+#[derive(Clone)]
+pub struct HealthService {
+    core_service: DynRpcService,
+    running: Arc<AtomicBool>,
+}
+
+impl HealthService {
+    pub fn new(core_service: DynRpcService, running: Arc<AtomicBool>) -> Self {
+        Self { core_service, running }
+    }
+
+    async fn status(&self, service: &str) -> ServingStatus {
+        if !HEALTH_CHECKED_SERVICES.contains(&service) {
+            return ServingStatus::ServiceUnknown;
+        }
+        if self.running.load(Ordering::SeqCst) && self.core_service.get_sync_status().await.unwrap_or_default() {
+            ServingStatus::Serving
+        } else {
+            ServingStatus::NotServing
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Health for HealthService {
+    type WatchStream = Pin<Box<dyn Stream<Item = Result<HealthCheckResponse, Status>> + Send + 'static>>;
+
+    async fn check(&self, request: Request<HealthCheckRequest>) -> Result<Response<HealthCheckResponse>, Status> {
+        let service = request.into_inner().service;
+        match self.status(&service).await {
+            ServingStatus::ServiceUnknown => Err(Status::not_found(format!("unknown service {service}"))),
+            status => Ok(Response::new(HealthCheckResponse { status: status as i32 })),
+        }
+    }
+
+    /// Sends the current status and then every status change, until the server stops
+    async fn watch(&self, request: Request<HealthCheckRequest>) -> Result<Response<Self::WatchStream>, Status> {
+        let service = request.into_inner().service;
+        let health = self.clone();
+        let stream = async_stream::stream! {
+            let mut interval = tokio::time::interval(WATCH_INTERVAL);
+            let mut last = None;
+            loop {
+                interval.tick().await;
+                let status = health.status(&service).await;
+                if last != Some(status) {
+                    yield Ok::<_, Status>(HealthCheckResponse { status: status as i32 });
+                    last = Some(status);
+                }
+                if !health.running.load(Ordering::SeqCst) {
+                    break;
+                }
+            }
+        };
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
@@ -3,7 +3,9 @@ pub mod collector;
 pub mod connection;
 pub mod connection_handler;
 pub mod error;
+pub mod health;
 pub mod manager;
+pub mod reflection;
 pub mod request_handler;
 pub mod service;
 
@@ -0,0 +1,206 @@
+use futures::Stream;
+use kaspa_grpc_core::{
+    reflection::{
+        server_reflection_request::MessageRequest, server_reflection_response::MessageResponse,
+        server_reflection_server::ServerReflection, ErrorResponse, ExtensionNumberResponse, FileDescriptorResponse,
+        ListServiceResponse, ServerReflectionRequest, ServerReflectionResponse, ServiceResponse,
+    },
+    FILE_DESCRIPTOR_SET,
+};
+use prost::Message;
+use prost_types::{DescriptorProto, FileDescriptorProto, FileDescriptorSet};
+use std::{
+    collections::{HashMap, HashSet},
+    pin::Pin,
+    sync::Arc,
+};
+use tonic::{Code, Request, Response, Status, Streaming};
+
+/// A gRPC server reflection service, letting tools such as `grpcurl` introspect the served services without the
+/// protobuf files at hand
+#[derive(Clone)]
+pub struct ReflectionService {
+    index: Arc<DescriptorIndex>,
+}
+
+impl ReflectionService {
+    pub fn new() -> Self {
+        Self { index: Arc::new(DescriptorIndex::new(FILE_DESCRIPTOR_SET)) }
+    }
+}
+
+impl Default for ReflectionService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tonic::async_trait]
+impl ServerReflection for ReflectionService {
+    type ServerReflectionInfoStream = Pin<Box<dyn Stream<Item = Result<ServerReflectionResponse, Status>> + Send + 'static>>;
+
+    async fn server_reflection_info(
+        &self,
+        request: Request<Streaming<ServerReflectionRequest>>,
+    ) -> Result<Response<Self::ServerReflectionInfoStream>, Status> {
+        let mut requests = request.into_inner();
+        let index = self.index.clone();
+        let stream = async_stream::try_stream! {
+            while let Some(request) = requests.message().await? {
+                yield index.respond(request);
+            }
+        };
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// The files of a descriptor set, indexed by the symbols they declare
+struct DescriptorIndex {
+    files: HashMap<String, FileDescriptorProto>,
+    /// Fully-qualified symbol name to the name of the file declaring it
+    symbols: HashMap<String, String>,
+    message_types: HashSet<String>,
+    services: Vec<String>,
+}
+
+impl DescriptorIndex {
+    fn new(encoded_set: &[u8]) -> Self {
+        let set = FileDescriptorSet::decode(encoded_set).expect("the compiled descriptor set is valid");
+        let mut index = Self { files: HashMap::new(), symbols: HashMap::new(), message_types: HashSet::new(), services: vec![] };
+        for file in set.file {
+            let file_name = file.name().to_owned();
+            let package = file.package();
+            for service in file.service.iter() {
+                let service_name = qualify(package, service.name());
+                for method in service.method.iter() {
+                    index.symbols.insert(qualify(&service_name, method.name()), file_name.clone());
+                }
+                index.symbols.insert(service_name.clone(), file_name.clone());
+                index.services.push(service_name);
+            }
+            for message in file.message_type.iter() {
+                index.add_message(package, message, &file_name);
+            }
+            for enumeration in file.enum_type.iter() {
+                index.symbols.insert(qualify(package, enumeration.name()), file_name.clone());
+            }
+            index.files.insert(file_name, file);
+        }
+        index
+    }
+
+    fn add_message(&mut self, scope: &str, message: &DescriptorProto, file_name: &str) {
+        let name = qualify(scope, message.name());
+        for nested in message.nested_type.iter() {
+            self.add_message(&name, nested, file_name);
+        }
+        for enumeration in message.enum_type.iter() {
+            self.symbols.insert(qualify(&name, enumeration.name()), file_name.to_owned());
+        }
+        self.symbols.insert(name.clone(), file_name.to_owned());
+        self.message_types.insert(name);
+    }
+
+    fn respond(&self, request: ServerReflectionRequest) -> ServerReflectionResponse {
+        let message_response = match request.message_request.as_ref() {
+            Some(MessageRequest::FileByFilename(file_name)) => self.file_response(file_name),
+            Some(MessageRequest::FileContainingSymbol(symbol)) => match self.symbols.get(symbol) {
+                Some(file_name) => self.file_response(file_name),
+                None => error_response(Code::NotFound, format!("unknown symbol {symbol}")),
+            },
+            // The served protobuf files declare no extensions
+            Some(MessageRequest::FileContainingExtension(extension)) => error_response(
+                Code::NotFound,
+                format!("unknown extension {} of {}", extension.extension_number, extension.containing_type),
+            ),
+            Some(MessageRequest::AllExtensionNumbersOfType(message_type)) if self.message_types.contains(message_type) => {
+                MessageResponse::AllExtensionNumbersResponse(ExtensionNumberResponse {
+                    base_type_name: message_type.clone(),
+                    extension_number: vec![],
+                })
+            }
+            Some(MessageRequest::AllExtensionNumbersOfType(message_type)) => {
+                error_response(Code::NotFound, format!("unknown message type {message_type}"))
+            }
+            Some(MessageRequest::ListServices(_)) => MessageResponse::ListServicesResponse(ListServiceResponse {
+                service: self.services.iter().map(|name| ServiceResponse { name: name.clone() }).collect(),
+            }),
+            None => error_response(Code::InvalidArgument, "the request is empty".to_owned()),
+        };
+        ServerReflectionResponse {
+            valid_host: request.host.clone(),
+            original_request: Some(request),
+            message_response: Some(message_response),
+        }
+    }
+
+    /// Responds with a file along with all its transitive dependencies
+    fn file_response(&self, file_name: &str) -> MessageResponse {
+        if !self.files.contains_key(file_name) {
+            return error_response(Code::NotFound, format!("unknown file {file_name}"));
+        }
+        let mut visited = HashSet::new();
+        let mut pending = vec![file_name];
+        let mut file_descriptor_proto = vec![];
+        while let Some(file_name) = pending.pop() {
+            if !visited.insert(file_name) {
+                continue;
+            }
+            let Some(file) = self.files.get(file_name) else {
+                continue;
+            };
+            file_descriptor_proto.push(file.encode_to_vec());
+            pending.extend(file.dependency.iter().map(String::as_str));
+        }
+        MessageResponse::FileDescriptorResponse(FileDescriptorResponse { file_descriptor_proto })
+    }
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{scope}.{name}")
+    }
+}
+
+fn error_response(code: Code, error_message: String) -> MessageResponse {
+    MessageResponse::ErrorResponse(ErrorResponse { error_code: code as i32, error_message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(message_request: MessageRequest) -> ServerReflectionRequest {
+        ServerReflectionRequest { host: String::new(), message_request: Some(message_request) }
+    }
+
+    #[test]
+    fn test_descriptor_index() {
+        let index = DescriptorIndex::new(FILE_DESCRIPTOR_SET);
+        for service in ["protowire.RPC", "grpc.health.v1.Health", "grpc.reflection.v1alpha.ServerReflection"] {
+            assert!(index.services.iter().any(|name| name == service), "{service} should be listed");
+        }
+        assert_eq!(index.symbols.get("protowire.RPC.MessageStream").map(String::as_str), Some("messages.proto"));
+        assert_eq!(index.symbols.get("protowire.GetInfoRequestMessage").map(String::as_str), Some("rpc.proto"));
+        assert_eq!(index.symbols.get("grpc.health.v1.HealthCheckResponse.ServingStatus").map(String::as_str), Some("health.proto"));
+
+        // Files come along with their dependencies
+        match index.respond(request(MessageRequest::FileContainingSymbol("protowire.RPC".to_owned()))).message_response {
+            Some(MessageResponse::FileDescriptorResponse(response)) => {
+                let names: HashSet<_> = response
+                    .file_descriptor_proto
+                    .iter()
+                    .map(|encoded| FileDescriptorProto::decode(encoded.as_slice()).unwrap().name().to_owned())
+                    .collect();
+                assert_eq!(names, HashSet::from(["messages.proto".to_owned(), "rpc.proto".to_owned()]));
+            }
+            response => panic!("unexpected response {response:?}"),
+        }
+        match index.respond(request(MessageRequest::FileContainingSymbol("protowire.Unknown".to_owned()))).message_response {
+            Some(MessageResponse::ErrorResponse(response)) => assert_eq!(response.error_code, Code::NotFound as i32),
+            response => panic!("unexpected response {response:?}"),
+        }
+    }
+}
@@ -14,7 +14,7 @@ use crate::{
 use kaspa_grpc_core::protowire::{kaspad_request::Payload, *};
 use kaspa_grpc_core::{ops::KaspadPayloadOps, protowire::NotifyFinalityConflictResponseMessage};
 use kaspa_notify::{scope::FinalityConflictResolvedScope, subscriber::SubscriptionManager};
-use kaspa_rpc_core::{SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse};
+use kaspa_rpc_core::{SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse, SubmitBlockSerializedResponse};
 use kaspa_rpc_macros::build_grpc_server_interface;
 
 pub struct Factory {}
@@ -81,6 +81,18 @@ impl Factory {
                 GetFeeEstimate,
                 GetFeeEstimateExperimental,
                 GetCurrentBlockColor,
+                GetP2pStats,
+                GetProcessingStats,
+                GetPeerFlowStates,
+                DisconnectPeer,
+                SetPeerProtected,
+                GetTransactionAcceptanceData,
+                GetChainBlockByScore,
+                GetBlockSerialized,
+                SubmitBlockSerialized,
+                GetTransactionSerialized,
+                SubmitTransactionSerialized,
+                GetLocalTransactions,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -89,6 +101,7 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifyTransactionsEvicted,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
@@ -142,7 +155,20 @@ impl Factory {
             network_bps,
             10.max(network_bps * 2),
             KaspadRoutingPolicy::DropIfFull(Arc::new(Box::new(|_: &KaspadRequest| {
-                Ok(Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull) }).into())
+                Ok(Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull), error: None })
+                    .into())
+            }))),
+        );
+        interface.set_method_properties(
+            KaspadPayloadOps::SubmitBlockSerialized,
+            network_bps,
+            10.max(network_bps * 2),
+            KaspadRoutingPolicy::DropIfFull(Arc::new(Box::new(|_: &KaspadRequest| {
+                Ok(Ok(SubmitBlockSerializedResponse {
+                    report: SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull),
+                    error: None,
+                })
+                .into())
             }))),
         );
 
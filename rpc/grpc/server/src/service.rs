@@ -11,6 +11,9 @@ use kaspa_utils_tower::counters::TowerConnectionCounters;
 use std::sync::Arc;
 use triggered::Listener;
 
+/// The protocol under which the client connections are reported by `GetMetrics`
+const GRPC_PROTOCOL: &str = "grpc";
+
 pub struct GrpcService {
     net_address: NetAddress,
     config: Arc<Config>,
@@ -72,6 +75,7 @@ impl AsyncService for GrpcService {
             self.broadcasters,
             self.counters.clone(),
         );
+        self.core_service.register_connection_notifier(GRPC_PROTOCOL, grpc_adaptor.notifier());
 
         // Signal the server was started
         self.started.trigger.trigger();
@@ -75,6 +75,9 @@ impl RpcApi for RpcCoreMock {
             is_synced: false,
             has_notify_command: true,
             has_message_id: true,
+            network_id: None,
+            unsafe_rpc: false,
+            cache_policy: None,
         })
     }
 
@@ -122,6 +125,102 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_p2p_stats_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetP2pStatsRequest,
+    ) -> RpcResult<GetP2pStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_processing_stats_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetProcessingStatsRequest,
+    ) -> RpcResult<GetProcessingStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_peer_flow_states_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetPeerFlowStatesRequest,
+    ) -> RpcResult<GetPeerFlowStatesResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn disconnect_peer_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: DisconnectPeerRequest,
+    ) -> RpcResult<DisconnectPeerResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn set_peer_protected_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SetPeerProtectedRequest,
+    ) -> RpcResult<SetPeerProtectedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_acceptance_data_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionAcceptanceDataRequest,
+    ) -> RpcResult<GetTransactionAcceptanceDataResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_chain_block_by_score_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetChainBlockByScoreRequest,
+    ) -> RpcResult<GetChainBlockByScoreResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_serialized_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetBlockSerializedRequest,
+    ) -> RpcResult<GetBlockSerializedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn submit_block_serialized_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SubmitBlockSerializedRequest,
+    ) -> RpcResult<SubmitBlockSerializedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_serialized_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionSerializedRequest,
+    ) -> RpcResult<GetTransactionSerializedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn submit_transaction_serialized_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SubmitTransactionSerializedRequest,
+    ) -> RpcResult<SubmitTransactionSerializedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_local_transactions_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetLocalTransactionsRequest,
+    ) -> RpcResult<GetLocalTransactionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn get_current_network_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -1,8 +1,14 @@
-use convert_case::{Case, Casing};
+use convert_case::{Boundary, Case, Casing};
 use proc_macro2::{Ident, Span};
 use quote::ToTokens;
 use syn::{Attribute, Error, Expr, ExprArray, Result};
 
+/// Converts a method name to `case`, keeping digits attached to their word
+/// (i.e. `GetP2pStats` becomes `get_p2p_stats` rather than `get_p_2_p_stats`)
+pub fn to_case(name: &str, case: Case) -> String {
+    name.from_case(Case::Pascal).without_boundaries(&Boundary::digits()).to_case(case)
+}
+
 #[derive(Debug)]
 pub struct Handler {
     pub name: String,
@@ -36,10 +42,11 @@ impl Handler {
             _ => (handler.to_token_stream().to_string(), vec![]),
         };
         //let name = handler.to_token_stream().to_string();
-        let fn_call = Ident::new(&format!("{}_call", name.to_case(Case::Snake)), Span::call_site());
-        let fn_with_suffix = fn_suffix.map(|suffix| Ident::new(&format!("{}_{suffix}", name.to_case(Case::Snake)), Span::call_site()));
-        let fn_no_suffix = Ident::new(&name.to_case(Case::Snake), Span::call_site());
-        let fn_camel = Ident::new(&name.to_case(Case::Camel), Span::call_site());
+        let fn_call = Ident::new(&format!("{}_call", to_case(&name, Case::Snake)), Span::call_site());
+        let fn_with_suffix =
+            fn_suffix.map(|suffix| Ident::new(&format!("{}_{suffix}", to_case(&name, Case::Snake)), Span::call_site()));
+        let fn_no_suffix = Ident::new(&to_case(&name, Case::Snake), Span::call_site());
+        let fn_camel = Ident::new(&to_case(&name, Case::Camel), Span::call_site());
         let request_type = Ident::new(&format!("{name}Request"), Span::call_site());
         let response_type = Ident::new(&format!("{name}Response"), Span::call_site());
         let typename = Ident::new(&name.to_string(), Span::call_site());
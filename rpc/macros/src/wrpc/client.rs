@@ -71,7 +71,7 @@ impl ToTokens for RpcTable {
                         //let request = request;
                         let __ret: RpcResult<Serializable<#response_type>> = {
                             let resp: ClientResult<Serializable<#response_type>> = __self.inner.rpc_client.call(#rpc_api_ops::#handler, Serializable(request)).await;
-                            Ok(resp.map_err(|e| kaspa_rpc_core::error::RpcError::RpcSubsystem(e.to_string()))?)
+                            Ok(resp.map_err(crate::error::rpc_call_error)?)
                         };
                         #[allow(unreachable_code)]
                         __ret.map(Serializable::into_inner)
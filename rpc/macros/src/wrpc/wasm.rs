@@ -243,7 +243,7 @@ impl ToTokens for TsInterface {
         let Handler { name, typename, ts_custom_section_ident, .. } = handler;
 
         let declaration = if name.ends_with("Request") {
-            let method = (&name.trim_end_matches("Request")[1..]).to_case(Case::Camel);
+            let method = to_case(&name.trim_end_matches("Request")[1..], Case::Camel);
             insert_typedoc(
                 declaration,
                 &format!(
@@ -253,7 +253,7 @@ impl ToTokens for TsInterface {
                 ),
             )
         } else if name.ends_with("Response") {
-            let method = (&name.trim_end_matches("Response")[1..]).to_case(Case::Camel);
+            let method = to_case(&name.trim_end_matches("Response")[1..], Case::Camel);
             insert_typedoc(
                 declaration,
                 &format!(
@@ -3,44 +3,39 @@ use kaspa_addresses::Address;
 use kaspa_consensus_core::{
     block::Block,
     config::Config,
-    hashing::tx::hash,
     header::Header,
-    tx::{MutableTransaction, Transaction, TransactionId, TransactionInput, TransactionOutput},
+    tx::{MutableTransaction, Transaction, TransactionId},
     ChainPath,
 };
 use kaspa_consensus_notify::notification::{self as consensus_notify, Notification as ConsensusNotification};
 use kaspa_consensusmanager::{ConsensusManager, ConsensusProxy};
-use kaspa_math::Uint256;
 use kaspa_mining::model::{owner_txs::OwnerTransactions, TransactionIdSet};
 use kaspa_notify::converter::Converter;
 use kaspa_rpc_core::{
-    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcHash, RpcMempoolEntry,
-    RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput, RpcTransactionOutputVerboseData,
-    RpcTransactionVerboseData,
+    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcMempoolEntry, RpcMempoolEntryByAddress, RpcResult,
+    RpcTransaction,
 };
-use kaspa_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
 use std::{collections::HashMap, fmt::Debug, sync::Arc};
 
+use super::verbose::{VerboseDataBuilder, DEFAULT_BLOCK_VERBOSE_DATA_CACHE_BYTES};
+
 /// Conversion of consensus_core to rpc_core structures
 pub struct ConsensusConverter {
     consensus_manager: Arc<ConsensusManager>,
     config: Arc<Config>,
+    verbose_data_builder: VerboseDataBuilder,
 }
 
 impl ConsensusConverter {
     pub fn new(consensus_manager: Arc<ConsensusManager>, config: Arc<Config>) -> Self {
-        Self { consensus_manager, config }
+        let verbose_data_builder = VerboseDataBuilder::new(config.clone(), DEFAULT_BLOCK_VERBOSE_DATA_CACHE_BYTES);
+        Self { consensus_manager, config, verbose_data_builder }
     }
 
     /// Returns the proof-of-work difficulty as a multiple of the minimum difficulty using
     /// the passed bits field from the header of a block.
     pub fn get_difficulty_ratio(&self, bits: u32) -> f64 {
-        // The minimum difficulty is the max possible proof-of-work limit bits
-        // converted back to a number. Note this is not the same as the proof of
-        // work limit directly because the block difficulty is encoded in a block
-        // with the compact form which loses precision.
-        let target = Uint256::from_compact_target_bits(bits);
-        self.config.max_difficulty_target_f64 / target.as_f64()
+        self.verbose_data_builder.difficulty_ratio(bits)
     }
 
     /// Converts a consensus [`Block`] into an [`RpcBlock`], optionally including transaction verbose data.
@@ -53,35 +48,7 @@ impl ConsensusConverter {
         include_transactions: bool,
         include_transaction_verbose_data: bool,
     ) -> RpcResult<RpcBlock> {
-        let hash = block.hash();
-        let ghostdag_data = consensus.async_get_ghostdag_data(hash).await?;
-        let block_status = consensus.async_get_block_status(hash).await.unwrap();
-        let children = consensus.async_get_block_children(hash).await.unwrap_or_default();
-        let is_chain_block = consensus.async_is_chain_block(hash).await?;
-        let verbose_data = Some(RpcBlockVerboseData {
-            hash,
-            difficulty: self.get_difficulty_ratio(block.header.bits),
-            selected_parent_hash: ghostdag_data.selected_parent,
-            transaction_ids: block.transactions.iter().map(|x| x.id()).collect(),
-            is_header_only: block_status.is_header_only(),
-            blue_score: ghostdag_data.blue_score,
-            children_hashes: children,
-            merge_set_blues_hashes: ghostdag_data.mergeset_blues,
-            merge_set_reds_hashes: ghostdag_data.mergeset_reds,
-            is_chain_block,
-        });
-
-        let transactions = if include_transactions {
-            block
-                .transactions
-                .iter()
-                .map(|x| self.get_transaction(consensus, x, Some(&block.header), include_transaction_verbose_data))
-                .collect::<Vec<_>>()
-        } else {
-            vec![]
-        };
-
-        Ok(RpcBlock { header: block.header.as_ref().into(), transactions, verbose_data })
+        self.verbose_data_builder.block(consensus, block, include_transactions, include_transaction_verbose_data).await
     }
 
     pub fn get_mempool_entry(&self, consensus: &ConsensusProxy, transaction: &MutableTransaction) -> RpcMempoolEntry {
@@ -113,7 +80,7 @@ impl ConsensusConverter {
 
     /// Converts a consensus [`Transaction`] into an [`RpcTransaction`], optionally including verbose data.
     ///
-    /// _GO-KASPAD: PopulateTransactionWithVerboseData
+    /// _GO-KASPAD: PopulateTransactionWithVerboseData_
     pub fn get_transaction(
         &self,
         consensus: &ConsensusProxy,
@@ -121,41 +88,7 @@ impl ConsensusConverter {
         header: Option<&Header>,
         include_verbose_data: bool,
     ) -> RpcTransaction {
-        if include_verbose_data {
-            let verbose_data = Some(RpcTransactionVerboseData {
-                transaction_id: transaction.id(),
-                hash: hash(transaction, false),
-                compute_mass: consensus.calculate_transaction_compute_mass(transaction),
-                // TODO: make block_hash an option
-                block_hash: header.map_or_else(RpcHash::default, |x| x.hash),
-                block_time: header.map_or(0, |x| x.timestamp),
-            });
-            RpcTransaction {
-                version: transaction.version,
-                inputs: transaction.inputs.iter().map(|x| self.get_transaction_input(x)).collect(),
-                outputs: transaction.outputs.iter().map(|x| self.get_transaction_output(x)).collect(),
-                lock_time: transaction.lock_time,
-                subnetwork_id: transaction.subnetwork_id.clone(),
-                gas: transaction.gas,
-                payload: transaction.payload.clone(),
-                mass: transaction.mass(),
-                verbose_data,
-            }
-        } else {
-            transaction.into()
-        }
-    }
-
-    fn get_transaction_input(&self, input: &TransactionInput) -> RpcTransactionInput {
-        input.into()
-    }
-
-    fn get_transaction_output(&self, output: &TransactionOutput) -> RpcTransactionOutput {
-        let script_public_key_type = ScriptClass::from_script(&output.script_public_key);
-        let address = extract_script_pub_key_address(&output.script_public_key, self.config.prefix()).ok();
-        let verbose_data =
-            address.map(|address| RpcTransactionOutputVerboseData { script_public_key_type, script_public_key_address: address });
-        RpcTransactionOutput { value: output.value, script_public_key: output.script_public_key.clone(), verbose_data }
+        self.verbose_data_builder.transaction(consensus, transaction, header, include_verbose_data)
     }
 
     pub async fn get_virtual_chain_accepted_transaction_ids(
@@ -0,0 +1,34 @@
+use kaspa_mining::{mempool::tx::TransactionPackage, model::local_tx::LocalTransaction};
+use kaspa_rpc_core::{RpcLocalTransaction, RpcTransactionPackage};
+
+pub trait TransactionPackageConverter {
+    fn into_rpc(self) -> RpcTransactionPackage;
+}
+
+impl TransactionPackageConverter for TransactionPackage {
+    fn into_rpc(self) -> RpcTransactionPackage {
+        RpcTransactionPackage {
+            ancestor_count: self.ancestors.count,
+            ancestor_fees: self.ancestors.fee,
+            ancestor_mass: self.ancestors.mass,
+            descendant_count: self.descendants.count,
+            descendant_fees: self.descendants.fee,
+            descendant_mass: self.descendants.mass,
+        }
+    }
+}
+
+pub trait LocalTransactionConverter {
+    fn into_rpc(self) -> RpcLocalTransaction;
+}
+
+impl LocalTransactionConverter for LocalTransaction {
+    fn into_rpc(self) -> RpcLocalTransaction {
+        RpcLocalTransaction {
+            transaction_id: self.transaction.id(),
+            added_at_daa_score: self.added_at_daa_score,
+            expire_daa_score: self.expire_daa_score,
+            is_orphan: self.is_orphan,
+        }
+    }
+}
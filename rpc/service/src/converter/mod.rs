@@ -1,4 +1,6 @@
 pub mod consensus;
 pub mod feerate_estimate;
 pub mod index;
+pub mod mempool;
 pub mod protocol;
+pub mod verbose;
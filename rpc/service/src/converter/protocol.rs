@@ -1,8 +1,11 @@
-use std::sync::Arc;
+use std::{cmp::Reverse, sync::Arc, time::Instant};
 
-use kaspa_p2p_flows::flow_context::FlowContext;
-use kaspa_p2p_lib::{Peer, PeerKey};
-use kaspa_rpc_core::RpcPeerInfo;
+use kaspa_p2p_flows::{flow_context::FlowContext, flowcontext::ibd_progress::IbdPhase};
+use kaspa_p2p_lib::{FlowState, MessageTypeStats, Peer, PeerKey, Router};
+use kaspa_rpc_core::{
+    RpcFlowState, RpcP2pMessageStats, RpcPeerContribution, RpcPeerFlowStates, RpcPeerInfo, RpcSyncPhase, RpcSyncPhaseProgress,
+    RpcSyncProgress,
+};
 
 pub struct ProtocolConverter {
     flow_context: Arc<FlowContext>,
@@ -25,6 +28,7 @@ impl ProtocolConverter {
             user_agent: properties.user_agent.clone(),
             advertised_protocol_version: properties.advertised_protocol_version,
             time_connected: peer.time_connected(),
+            identity: properties.identity.map(|key| key.to_string()),
         }
     }
 
@@ -32,4 +36,89 @@ impl ProtocolConverter {
         let ibd_peer_key = self.flow_context.ibd_peer_key();
         peers.iter().map(|x| self.get_peer_info(x, &ibd_peer_key)).collect()
     }
+
+    pub fn get_p2p_message_stats(&self) -> Vec<RpcP2pMessageStats> {
+        self.flow_context.p2p_message_counters().snapshot().iter().map(Self::get_message_type_stats).collect()
+    }
+
+    /// Returns the `count` connected peers which contributed the most blocks and headers during their current connection
+    pub fn get_top_peer_contributions(&self, count: usize) -> Vec<RpcPeerContribution> {
+        let mut peers =
+            self.flow_context.hub().select_peers(None).into_iter().map(|router| (router.session_stats(), router)).collect::<Vec<_>>();
+        peers.sort_by_key(|(stats, _)| Reverse((stats.contribution(), stats.bytes_received)));
+        peers
+            .into_iter()
+            .take(count)
+            .map(|(stats, router)| RpcPeerContribution {
+                id: router.identity(),
+                address: router.net_address().into(),
+                valid_blocks: stats.blocks.valid,
+                duplicate_blocks: stats.blocks.duplicate,
+                invalid_blocks: stats.blocks.invalid,
+                valid_headers: stats.headers.valid,
+                duplicate_headers: stats.headers.duplicate,
+                invalid_headers: stats.headers.invalid,
+                bytes_received: stats.bytes_received,
+                bytes_sent: stats.bytes_sent,
+            })
+            .collect()
+    }
+
+    fn get_message_type_stats(stats: &MessageTypeStats) -> RpcP2pMessageStats {
+        RpcP2pMessageStats {
+            message_type: format!("{:?}", stats.message_type),
+            incoming_count: stats.incoming_count,
+            incoming_bytes: stats.incoming_bytes,
+            outgoing_count: stats.outgoing_count,
+            outgoing_bytes: stats.outgoing_bytes,
+        }
+    }
+
+    pub fn get_peers_flow_states(&self, routers: &[Arc<Router>]) -> Vec<RpcPeerFlowStates> {
+        let connection_manager = self.flow_context.connection_manager();
+        routers
+            .iter()
+            .map(|router| RpcPeerFlowStates {
+                id: router.identity(),
+                address: router.net_address().into(),
+                is_outbound: router.is_outbound(),
+                is_protected: connection_manager.as_ref().is_some_and(|cm| cm.is_protected(router.identity())),
+                flows: router.flow_states().iter().map(Self::get_flow_state).collect(),
+            })
+            .collect()
+    }
+
+    fn get_flow_state(state: &FlowState) -> RpcFlowState {
+        RpcFlowState {
+            message_types: state.message_types.iter().map(|msg_type| format!("{:?}", msg_type)).collect(),
+            queued: state.queued as u64,
+            capacity: state.capacity as u64,
+        }
+    }
+
+    pub fn get_sync_progress(&self) -> RpcSyncProgress {
+        let Some(progress) = self.flow_context.ibd_progress() else {
+            return RpcSyncProgress::default();
+        };
+        RpcSyncProgress {
+            phase: Self::get_sync_phase(progress.current().phase),
+            phases: progress
+                .phases
+                .iter()
+                .map(|x| RpcSyncPhaseProgress { phase: Self::get_sync_phase(x.phase), processed: x.processed, total: x.total })
+                .collect(),
+            sync_peer_id: Some(progress.peer),
+            eta: progress.eta(Instant::now()).map(|eta| eta.as_millis() as u64),
+        }
+    }
+
+    fn get_sync_phase(phase: IbdPhase) -> RpcSyncPhase {
+        match phase {
+            IbdPhase::Negotiating => RpcSyncPhase::Negotiating,
+            IbdPhase::HeadersProof => RpcSyncPhase::HeadersProof,
+            IbdPhase::Headers => RpcSyncPhase::Headers,
+            IbdPhase::UtxoSet => RpcSyncPhase::UtxoSet,
+            IbdPhase::Bodies => RpcSyncPhase::Bodies,
+        }
+    }
 }
@@ -0,0 +1,274 @@
+use kaspa_consensus_core::{
+    block::Block,
+    config::Config,
+    hashing::tx::hash,
+    header::Header,
+    tx::{Transaction, TransactionOutput},
+};
+use kaspa_consensusmanager::ConsensusProxy;
+use kaspa_hashes::Hash;
+use kaspa_math::Uint256;
+use kaspa_rpc_core::{
+    RpcBlock, RpcBlockVerboseData, RpcHash, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput,
+    RpcTransactionOutputVerboseData, RpcTransactionVerboseData,
+};
+use kaspa_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
+use kaspa_utils::{hex::ToHex, lru::LruCache, mem_size::MemSizeEstimator};
+use parking_lot::Mutex;
+use std::{mem::size_of, sync::Arc};
+
+/// Default memory budget in bytes of the block verbose data cache
+pub const DEFAULT_BLOCK_VERBOSE_DATA_CACHE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Identifies the DAG state from which the DAG-dependent fields of block verbose data (children, chain block status)
+/// were derived. The virtual DAA score advances with every block added to the virtual and the headers selected tip
+/// with the headers preceding their blocks, so cached fields are refreshed only once either of them changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DagStateMarker {
+    virtual_daa_score: u64,
+    headers_selected_tip: Hash,
+}
+
+impl DagStateMarker {
+    async fn from_consensus(consensus: &ConsensusProxy) -> Self {
+        Self {
+            virtual_daa_score: consensus.get_virtual_daa_score(),
+            headers_selected_tip: consensus.async_get_headers_selected_tip().await,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CachedBlockVerboseData {
+    verbose_data: RpcBlockVerboseData,
+    /// Block transactions along with their verbose data. Populated on the first request including them
+    transactions: Option<Arc<Vec<RpcTransaction>>>,
+    /// The DAG state the DAG-dependent fields of `verbose_data` are up to date with
+    marker: DagStateMarker,
+}
+
+impl MemSizeEstimator for CachedBlockVerboseData {
+    fn estimate_mem_bytes(&self) -> usize {
+        let verbose_data = &self.verbose_data;
+        let hashes = verbose_data.transaction_ids.len()
+            + verbose_data.children_hashes.len()
+            + verbose_data.merge_set_blues_hashes.len()
+            + verbose_data.merge_set_reds_hashes.len();
+        let transactions = self.transactions.as_ref().map_or(0, |transactions| {
+            transactions
+                .iter()
+                .map(|tx| {
+                    size_of::<RpcTransaction>()
+                        + tx.payload.len()
+                        + tx.verbose_data.as_ref().map_or(0, |verbose_data| verbose_data.payload_hex.len())
+                        + tx.inputs.iter().map(|input| size_of::<RpcTransactionInput>() + input.signature_script.len()).sum::<usize>()
+                        + tx.outputs.len() * size_of::<RpcTransactionOutput>()
+                })
+                .sum()
+        });
+        size_of::<Self>() + hashes * size_of::<Hash>() + transactions
+    }
+}
+
+/// Builds the verbose data attached to blocks and transactions returned by RPC (transaction ids, masses,
+/// addresses, GHOSTDAG data, children, chain block status). Block verbose data is cached in an LRU bounded by its
+/// estimated byte size since the same blocks are typically requested repeatedly by different clients.
+pub struct VerboseDataBuilder {
+    config: Arc<Config>,
+    block_cache: Mutex<LruCache<Hash, CachedBlockVerboseData>>,
+}
+
+impl VerboseDataBuilder {
+    pub fn new(config: Arc<Config>, block_cache_bytes: usize) -> Self {
+        Self { config, block_cache: Mutex::new(LruCache::new(block_cache_bytes)) }
+    }
+
+    /// Returns the proof-of-work difficulty as a multiple of the minimum difficulty using
+    /// the passed bits field from the header of a block.
+    pub fn difficulty_ratio(&self, bits: u32) -> f64 {
+        // The minimum difficulty is the max possible proof-of-work limit bits
+        // converted back to a number. Note this is not the same as the proof of
+        // work limit directly because the block difficulty is encoded in a block
+        // with the compact form which loses precision.
+        let target = Uint256::from_compact_target_bits(bits);
+        self.config.max_difficulty_target_f64 / target.as_f64()
+    }
+
+    /// Converts a consensus [`Block`] into an [`RpcBlock`] with verbose data, optionally including transactions
+    pub async fn block(
+        &self,
+        consensus: &ConsensusProxy,
+        block: &Block,
+        include_transactions: bool,
+        include_transaction_verbose_data: bool,
+    ) -> RpcResult<RpcBlock> {
+        let hash = block.hash();
+        // The marker is captured before querying so that cached fields are never older than the marker stored with them
+        let marker = DagStateMarker::from_consensus(consensus).await;
+        let cached = self.block_cache.lock().get(&hash).cloned();
+        let mut is_cache_outdated = true;
+
+        let mut entry = match cached {
+            Some(entry) if entry.marker == marker => {
+                is_cache_outdated = false;
+                entry
+            }
+            Some(mut entry) => {
+                self.update_dag_dependent_verbose_data(consensus, &mut entry.verbose_data).await?;
+                entry.marker = marker;
+                entry
+            }
+            None => {
+                CachedBlockVerboseData { verbose_data: self.block_verbose_data(consensus, block).await?, transactions: None, marker }
+            }
+        };
+        if include_transactions && include_transaction_verbose_data && entry.transactions.is_none() {
+            entry.transactions = Some(Arc::new(
+                block.transactions.iter().map(|tx| self.transaction(consensus, tx, Some(&block.header), true)).collect(),
+            ));
+            is_cache_outdated = true;
+        }
+        // The transactions of header-only blocks are not known yet, so such blocks are not cached. Blocks with a body
+        // remain so, hence the header-only status of cached blocks is never refreshed
+        if is_cache_outdated && !entry.verbose_data.is_header_only {
+            let weight = entry.estimate_mem_bytes();
+            self.block_cache.lock().insert_weighted(hash, entry.clone(), weight);
+        }
+        let CachedBlockVerboseData { verbose_data, transactions: verbose_transactions, .. } = entry;
+
+        let transactions = match (include_transactions, verbose_transactions) {
+            (false, _) => vec![],
+            (true, Some(verbose_transactions)) if include_transaction_verbose_data => verbose_transactions.as_ref().clone(),
+            (true, _) => block.transactions.iter().map(RpcTransaction::from).collect(),
+        };
+
+        Ok(RpcBlock { header: block.header.as_ref().into(), transactions, verbose_data: Some(verbose_data) })
+    }
+
+    /// _GO-KASPAD: PopulateBlockWithVerboseData_
+    async fn block_verbose_data(&self, consensus: &ConsensusProxy, block: &Block) -> RpcResult<RpcBlockVerboseData> {
+        let hash = block.hash();
+        let ghostdag_data = consensus.async_get_ghostdag_data(hash).await?;
+        let mut verbose_data = RpcBlockVerboseData {
+            hash,
+            difficulty: self.difficulty_ratio(block.header.bits),
+            selected_parent_hash: ghostdag_data.selected_parent,
+            transaction_ids: block.transactions.iter().map(|x| x.id()).collect(),
+            is_header_only: consensus.async_get_block_status(hash).await.unwrap().is_header_only(),
+            blue_score: ghostdag_data.blue_score,
+            children_hashes: vec![],
+            merge_set_blues_hashes: ghostdag_data.mergeset_blues,
+            merge_set_reds_hashes: ghostdag_data.mergeset_reds,
+            is_chain_block: false,
+        };
+        self.update_dag_dependent_verbose_data(consensus, &mut verbose_data).await?;
+        Ok(verbose_data)
+    }
+
+    /// Sets the block verbose data fields which change as the DAG grows: a block gains children and it might enter
+    /// or leave the selected chain
+    async fn update_dag_dependent_verbose_data(
+        &self,
+        consensus: &ConsensusProxy,
+        verbose_data: &mut RpcBlockVerboseData,
+    ) -> RpcResult<()> {
+        let hash = verbose_data.hash;
+        verbose_data.children_hashes = consensus.async_get_block_children(hash).await.unwrap_or_default();
+        verbose_data.is_chain_block = consensus.async_is_chain_block(hash).await?;
+        Ok(())
+    }
+
+    /// Converts a consensus [`Transaction`] into an [`RpcTransaction`], optionally including verbose data.
+    ///
+    /// _GO-KASPAD: PopulateTransactionWithVerboseData_
+    pub fn transaction(
+        &self,
+        consensus: &ConsensusProxy,
+        transaction: &Transaction,
+        header: Option<&Header>,
+        include_verbose_data: bool,
+    ) -> RpcTransaction {
+        if include_verbose_data {
+            let verbose_data = Some(RpcTransactionVerboseData {
+                transaction_id: transaction.id(),
+                hash: hash(transaction, false),
+                compute_mass: consensus.calculate_transaction_compute_mass(transaction),
+                // TODO: make block_hash an option
+                block_hash: header.map_or_else(RpcHash::default, |x| x.hash),
+                block_time: header.map_or(0, |x| x.timestamp),
+                payload_hex: transaction.payload.to_hex(),
+            });
+            RpcTransaction {
+                version: transaction.version,
+                inputs: transaction.inputs.iter().map(|x| x.into()).collect(),
+                outputs: transaction.outputs.iter().map(|x| self.transaction_output(x)).collect(),
+                lock_time: transaction.lock_time,
+                subnetwork_id: transaction.subnetwork_id.clone(),
+                gas: transaction.gas,
+                payload: transaction.payload.clone(),
+                mass: transaction.mass(),
+                verbose_data,
+            }
+        } else {
+            transaction.into()
+        }
+    }
+
+    fn transaction_output(&self, output: &TransactionOutput) -> RpcTransactionOutput {
+        let script_public_key_type = ScriptClass::from_script(&output.script_public_key);
+        let address = extract_script_pub_key_address(&output.script_public_key, self.config.prefix()).ok();
+        let verbose_data =
+            address.map(|address| RpcTransactionOutputVerboseData { script_public_key_type, script_public_key_address: address });
+        RpcTransactionOutput { value: output.value, script_public_key: output.script_public_key.clone(), verbose_data }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+
+    fn cached_block(hash: Hash, payload_len: usize) -> CachedBlockVerboseData {
+        let verbose_data = RpcBlockVerboseData {
+            hash,
+            difficulty: 1.0,
+            selected_parent_hash: Hash::default(),
+            transaction_ids: vec![],
+            is_header_only: false,
+            blue_score: 0,
+            children_hashes: vec![],
+            merge_set_blues_hashes: vec![],
+            merge_set_reds_hashes: vec![],
+            is_chain_block: false,
+        };
+        let transaction = RpcTransaction {
+            version: 0,
+            inputs: vec![],
+            outputs: vec![],
+            lock_time: 0,
+            subnetwork_id: SUBNETWORK_ID_NATIVE,
+            gas: 0,
+            payload: vec![0; payload_len],
+            mass: 0,
+            verbose_data: None,
+        };
+        let marker = DagStateMarker { virtual_daa_score: 0, headers_selected_tip: Hash::default() };
+        CachedBlockVerboseData { verbose_data, transactions: Some(Arc::new(vec![transaction])), marker }
+    }
+
+    #[test]
+    fn test_block_verbose_data_cache_byte_bound() {
+        let entries = (0..3).map(|i| cached_block(Hash::from_u64_word(i), 100_000)).collect::<Vec<_>>();
+        let weight = entries[0].estimate_mem_bytes();
+        assert!(weight > 100_000 && weight > cached_block(Hash::default(), 0).estimate_mem_bytes());
+
+        // The cache fits two of the blocks, so inserting a third evicts the least recently used one
+        let mut cache = LruCache::new(weight * 5 / 2);
+        for entry in entries.iter() {
+            cache.insert_weighted(entry.verbose_data.hash, entry.clone(), entry.estimate_mem_bytes());
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains_key(&entries[0].verbose_data.hash));
+        assert!(cache.weight() <= cache.capacity());
+    }
+}
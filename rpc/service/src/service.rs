@@ -2,24 +2,30 @@
 
 use super::collector::{CollectorFromConsensus, CollectorFromIndex};
 use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter};
+use crate::converter::mempool::{LocalTransactionConverter, TransactionPackageConverter};
 use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
 use crate::service::NetworkType::{Mainnet, Testnet};
 use async_trait::async_trait;
-use kaspa_consensus_core::api::counters::ProcessingCounters;
+use ipnet::IpNet;
+use kaspa_acceptanceindex::api::AcceptanceIndexProxy;
+use kaspa_addressmanager::{is_ban_range_too_broad, DEFAULT_BAN_DURATION};
+use kaspa_consensus_core::api::counters::{ProcessingCounters, ProcessingCountersSnapshot, ProcessingStage};
 use kaspa_consensus_core::errors::block::RuleError;
 use kaspa_consensus_core::{
     block::Block,
+    codec,
     coinbase::MinerData,
     config::Config,
     constants::MAX_SOMPI,
     network::NetworkType,
     tx::{Transaction, COINBASE_TRANSACTION_INDEX},
+    ChainScore,
 };
 use kaspa_consensus_notify::{
     notifier::ConsensusNotifier,
     {connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification},
 };
-use kaspa_consensusmanager::ConsensusManager;
+use kaspa_consensusmanager::{ConsensusManager, ConsensusProxy};
 use kaspa_core::time::unix_now;
 use kaspa_core::{
     core::Core,
@@ -46,7 +52,7 @@ use kaspa_notify::{
     connection::ChannelType,
     events::{EventSwitches, EventType, EVENT_TYPE_ARRAY},
     listener::ListenerId,
-    notifier::Notifier,
+    notifier::{ListenerAudit, Notifier},
     scope::Scope,
     subscriber::{Subscriber, SubscriptionManager},
 };
@@ -64,19 +70,24 @@ use kaspa_rpc_core::{
     Notification, RpcError, RpcResult,
 };
 use kaspa_txscript::{extract_script_pub_key_address, pay_to_address_script};
+use kaspa_utils::alloc_tracking::{self, AllocSubsystem};
 use kaspa_utils::expiring_cache::ExpiringCache;
 use kaspa_utils::sysinfo::SystemInfo;
 use kaspa_utils::{channel::Channel, triggers::SingleTrigger};
 use kaspa_utils_tower::counters::TowerConnectionCounters;
-use kaspa_utxoindex::api::UtxoIndexProxy;
-use std::time::Duration;
+use kaspa_utxoindex::{api::UtxoIndexProxy, model::circulating_amount};
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
 use std::{
     collections::HashMap,
     iter::once,
-    sync::{atomic::Ordering, Arc},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     vec,
 };
-use tokio::join;
+use tokio::{join, time::MissedTickBehavior};
 use workflow_rpc::server::WebSocketCounters as WrpcServerCounters;
 
 /// A service implementing the Rpc API at kaspa_rpc_core level.
@@ -102,12 +113,15 @@ pub struct RpcCoreService {
     mining_manager: MiningManagerProxy,
     flow_context: Arc<FlowContext>,
     utxoindex: Option<UtxoIndexProxy>,
+    acceptance_index: Option<AcceptanceIndexProxy>,
     config: Arc<Config>,
     consensus_converter: Arc<ConsensusConverter>,
     index_converter: Arc<IndexConverter>,
     protocol_converter: Arc<ProtocolConverter>,
     core: Arc<Core>,
     processing_counters: Arc<ProcessingCounters>,
+    /// The processing counters snapshot taken by the most recent `GetProcessingStats` call and its time
+    processing_stats_checkpoint: Mutex<(ProcessingCountersSnapshot, Instant)>,
     wrpc_borsh_counters: Arc<WrpcServerCounters>,
     wrpc_json_counters: Arc<WrpcServerCounters>,
     shutdown: SingleTrigger,
@@ -118,10 +132,22 @@ pub struct RpcCoreService {
     system_info: SystemInfo,
     fee_estimate_cache: ExpiringCache<RpcFeeEstimate>,
     fee_estimate_verbose_cache: ExpiringCache<kaspa_mining::errors::MiningManagerResult<GetFeeEstimateExperimentalResponse>>,
+    /// The circulating supply computed from the consensus UTXO set when the UTXO index is disabled
+    circulating_supply_cache: ExpiringCache<u64>,
+    /// The notifiers of the RPC servers, whose listeners are the client connections, by protocol
+    connection_notifiers: Mutex<Vec<(&'static str, Arc<dyn ListenerAudit>)>>,
+    /// Number of leaked notification listeners found by the listener audit
+    leaked_listeners: AtomicU64,
 }
 
 const RPC_CORE: &str = "rpc-core";
 
+/// The interval at which notifier listeners of closed connections are searched for
+const LISTENER_AUDIT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The number of top contributing peers reported by GetP2pStats
+const TOP_CONTRIBUTING_PEERS: usize = 10;
+
 impl RpcCoreService {
     pub const IDENT: &'static str = "rpc-core-service";
 
@@ -134,6 +160,7 @@ impl RpcCoreService {
         flow_context: Arc<FlowContext>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        acceptance_index: Option<AcceptanceIndexProxy>,
         config: Arc<Config>,
         core: Arc<Core>,
         processing_counters: Arc<ProcessingCounters>,
@@ -161,6 +188,8 @@ impl RpcCoreService {
         let mut consensus_events: EventSwitches = EVENT_TYPE_ARRAY[..].into();
         consensus_events[EventType::UtxosChanged] = false;
         consensus_events[EventType::PruningPointUtxoSetOverride] = index_notifier.is_none();
+        // Gaps are produced by the notifier itself and require no subscription upstream
+        consensus_events[EventType::NotificationsGap] = false;
         let consensus_converter = Arc::new(ConsensusConverter::new(consensus_manager.clone(), config.clone()));
         let consensus_collector = Arc::new(CollectorFromConsensus::new(
             "rpc-core <= consensus",
@@ -205,11 +234,13 @@ impl RpcCoreService {
             mining_manager,
             flow_context,
             utxoindex,
+            acceptance_index,
             config,
             consensus_converter,
             index_converter,
             protocol_converter,
             core,
+            processing_stats_checkpoint: Mutex::new((processing_counters.snapshot(), Instant::now())),
             processing_counters,
             wrpc_borsh_counters,
             wrpc_json_counters,
@@ -221,11 +252,63 @@ impl RpcCoreService {
             system_info,
             fee_estimate_cache: ExpiringCache::new(Duration::from_millis(500), Duration::from_millis(1000)),
             fee_estimate_verbose_cache: ExpiringCache::new(Duration::from_millis(500), Duration::from_millis(1000)),
+            circulating_supply_cache: ExpiringCache::new(Duration::from_secs(60), Duration::from_secs(600)),
+            connection_notifiers: Default::default(),
+            leaked_listeners: Default::default(),
         }
     }
 
-    pub fn start_impl(&self) {
+    pub fn start_impl(self: &Arc<Self>) {
         self.notifier().start();
+        self.clone().spawn_listener_audit_task();
+    }
+
+    /// Registers the notifier of an RPC server, so that its listeners get audited and their subscriptions reported by `GetMetrics`
+    pub fn register_connection_notifier(&self, protocol: &'static str, notifier: Arc<dyn ListenerAudit>) {
+        self.connection_notifiers.lock().push((protocol, notifier));
+    }
+
+    /// Periodically unregisters the notifier listeners left behind by closed connections
+    fn spawn_listener_audit_task(self: Arc<Self>) {
+        let shutdown_signal = self.shutdown.listener.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(LISTENER_AUDIT_INTERVAL);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            ticker.tick().await;
+            tokio::pin!(shutdown_signal);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => self.audit_listeners(),
+                    _ = &mut shutdown_signal => break,
+                }
+            }
+            trace!("{} listener audit task exiting", Self::IDENT);
+        });
+    }
+
+    fn audit_listeners(&self) {
+        let connection_notifiers = self.connection_notifiers.lock().clone();
+        let leaked = self.notifier.audit_listeners()
+            + connection_notifiers.iter().map(|(_, notifier)| notifier.audit_listeners()).sum::<usize>();
+        if leaked > 0 {
+            self.leaked_listeners.fetch_add(leaked as u64, Ordering::Relaxed);
+        }
+    }
+
+    fn connection_subscriptions(&self) -> Vec<RpcConnectionSubscriptions> {
+        let connection_notifiers = self.connection_notifiers.lock().clone();
+        connection_notifiers
+            .iter()
+            .flat_map(|(protocol, notifier)| {
+                notifier.listener_subscriptions().into_iter().map(|listener| RpcConnectionSubscriptions {
+                    protocol: protocol.to_string(),
+                    connection: listener.connection,
+                    listener_id: listener.id,
+                    subscriptions: listener.subscriptions as u32,
+                })
+            })
+            .collect()
     }
 
     pub async fn join(&self) -> RpcResult<()> {
@@ -269,6 +352,22 @@ impl RpcCoreService {
             .unwrap_or_default()
     }
 
+    /// Sums the circulating supply over the consensus virtual UTXO set. The set is read in chunks while the virtual
+    /// might advance, so the result is an approximation of the supply around the time of the scan
+    async fn scan_circulating_supply(session: &ConsensusProxy) -> u64 {
+        const SCAN_CHUNK_SIZE: usize = 2048;
+        let mut circulating_supply = 0;
+        let mut from_outpoint = None;
+        loop {
+            let chunk = session.async_get_virtual_utxos(from_outpoint, SCAN_CHUNK_SIZE, from_outpoint.is_some()).await;
+            circulating_supply += chunk.iter().map(|(_, utxo_entry)| circulating_amount(utxo_entry)).sum::<u64>();
+            if chunk.len() < SCAN_CHUNK_SIZE {
+                break circulating_supply;
+            }
+            from_outpoint = chunk.last().map(|(outpoint, _)| *outpoint);
+        }
+    }
+
     fn has_sufficient_peer_connectivity(&self) -> bool {
         // Other network types can be used in an isolated environment without peers
         !matches!(self.flow_context.config.net.network_type, Mainnet | Testnet) || self.flow_context.hub().has_peers()
@@ -284,6 +383,14 @@ impl RpcCoreService {
             (false, false) => Ok(TransactionQuery::TransactionsOnly),
         }
     }
+
+    /// Returns the IP range targeted by a ban or unban request, i.e. the single IP unless a prefix length is specified
+    fn requested_ip_range(ip: RpcIpAddress, prefix_length: Option<u8>) -> RpcResult<IpNet> {
+        match prefix_length {
+            Some(prefix_length) => IpNet::new(ip.0, prefix_length).map_err(|_| RpcError::InvalidIpPrefixLength(ip, prefix_length)),
+            None => Ok(IpNet::from(ip.0)),
+        }
+    }
 }
 
 #[async_trait]
@@ -295,19 +402,19 @@ impl RpcApi for RpcCoreService {
     ) -> RpcResult<SubmitBlockResponse> {
         let session = self.consensus_manager.consensus().unguarded_session();
 
-        // TODO: consider adding an error field to SubmitBlockReport to document both the report and error fields
         let is_synced: bool = self.has_sufficient_peer_connectivity() && session.async_is_nearly_synced().await;
 
         if !self.config.enable_unsynced_mining && !is_synced {
-            // error = "Block not submitted - node is not synced"
-            return Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::IsInIBD) });
+            return Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::IsInIBD), error: None });
         }
 
         let try_block: RpcResult<Block> = request.block.try_into();
         if let Err(err) = &try_block {
             trace!("incoming SubmitBlockRequest with block conversion error: {}", err);
-            // error = format!("Could not parse block: {0}", err)
-            return Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) });
+            return Ok(SubmitBlockResponse {
+                report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid),
+                error: Some(format!("Could not parse block: {0}", err)),
+            });
         }
         let block = try_block?;
         let hash = block.hash();
@@ -320,14 +427,19 @@ impl RpcApi for RpcCoreService {
             let daa_window_block_duration = self.config.daa_window_duration_in_blocks(virtual_daa_score);
             if virtual_daa_score > daa_window_block_duration && block.header.daa_score < virtual_daa_score - daa_window_block_duration
             {
-                // error = format!("Block rejected. Reason: block DAA score {0} is too far behind virtual's DAA score {1}", block.header.daa_score, virtual_daa_score)
-                return Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) });
+                return Ok(SubmitBlockResponse {
+                    report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid),
+                    error: Some(format!(
+                        "Block rejected. Reason: block DAA score {0} is too far behind virtual's DAA score {1}",
+                        block.header.daa_score, virtual_daa_score
+                    )),
+                });
             }
         }
 
         trace!("incoming SubmitBlockRequest for block {}", hash);
         match self.flow_context.submit_rpc_block(&session, block.clone()).await {
-            Ok(_) => Ok(SubmitBlockResponse { report: SubmitBlockReport::Success }),
+            Ok(_) => Ok(SubmitBlockResponse { report: SubmitBlockReport::Success, error: None }),
             Err(ProtocolError::RuleError(RuleError::BadMerkleRoot(h1, h2))) => {
                 warn!(
                     "The RPC submitted block triggered a {} error: {}. 
@@ -338,14 +450,20 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 if self.config.net.is_mainnet() {
                     warn!("Printing the full block for debug purposes:\n{:?}", block);
                 }
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) })
+                Ok(SubmitBlockResponse {
+                    report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid),
+                    error: Some(format!("Block rejected. Reason: {}", RuleError::BadMerkleRoot(h1, h2))),
+                })
             }
             Err(err) => {
                 warn!(
-                    "The RPC submitted block triggered an error: {}\nPrinting the full header for debug purposes:\n{:?}",
-                    err, block
+                    "The RPC submitted block {} triggered an error: {}\nPrinting the full header for debug purposes:\n{:?}",
+                    hash, err, block
                 );
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) })
+                Ok(SubmitBlockResponse {
+                    report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid),
+                    error: Some(format!("Block rejected. Reason: {}", err)),
+                })
             }
         }
     }
@@ -361,13 +479,14 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             return Err(RpcError::General("Mining on mainnet is not supported for initial Rust versions".to_owned()));
         }
 
+        // Fall back to the node mining address if the request does not specify a pay address
+        let pay_address = request.pay_address.as_ref().or(self.config.mining_address.as_ref()).ok_or(RpcError::MissingPayAddress)?;
+
         // Make sure the pay address prefix matches the config network type
-        if request.pay_address.prefix != self.config.prefix() {
-            return Err(kaspa_addresses::AddressError::InvalidPrefix(request.pay_address.prefix.to_string()))?;
-        }
+        validate_address_network(pay_address, self.config.prefix())?;
 
         // Build block template
-        let script_public_key = kaspa_txscript::pay_to_address_script(&request.pay_address);
+        let script_public_key = kaspa_txscript::pay_to_address_script(pay_address);
         let extra_data = version().as_bytes().iter().chain(once(&(b'/'))).chain(&request.extra_data).cloned().collect::<Vec<_>>();
         let miner_data: MinerData = MinerData::new(script_public_key, extra_data);
         let session = self.consensus_manager.consensus().unguarded_session();
@@ -380,9 +499,15 @@ NOTE: This error usually indicates an RPC conversion error between the node and
 
         let is_nearly_synced =
             self.config.is_nearly_synced(block_template.selected_parent_timestamp, block_template.selected_parent_daa_score);
+        let virtual_daa_score = block_template.block.header.daa_score;
+        let block_subsidy = session.calc_block_subsidy(virtual_daa_score);
+        let block_reward = block_subsidy + block_template.calculated_fees.iter().sum::<u64>();
         Ok(GetBlockTemplateResponse {
             block: block_template.block.into(),
             is_synced: self.has_sufficient_peer_connectivity() && is_nearly_synced,
+            block_reward,
+            block_subsidy,
+            virtual_daa_score,
         })
     }
 
@@ -474,6 +599,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             is_synced: self.has_sufficient_peer_connectivity() && is_nearly_synced,
             has_notify_command: true,
             has_message_id: true,
+            network_id: Some(self.config.net),
+            unsafe_rpc: self.config.unsafe_rpc,
+            cache_policy: Some(self.config.perf.cache_size_policy.to_string()),
         })
     }
 
@@ -486,8 +614,12 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         let Some(transaction) = self.mining_manager.clone().get_transaction(request.transaction_id, query).await else {
             return Err(RpcError::TransactionNotFound(request.transaction_id));
         };
+        let package = self.mining_manager.clone().get_transaction_package(request.transaction_id).await;
         let session = self.consensus_manager.consensus().unguarded_session();
-        Ok(GetMempoolEntryResponse::new(self.consensus_converter.get_mempool_entry(&session, &transaction)))
+        Ok(GetMempoolEntryResponse::new(
+            self.consensus_converter.get_mempool_entry(&session, &transaction),
+            package.map(TransactionPackageConverter::into_rpc),
+        ))
     }
 
     async fn get_mempool_entries_call(
@@ -501,6 +633,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         let mempool_entries = transactions
             .iter()
             .chain(orphans.iter())
+            .filter(|transaction| {
+                request.subnetwork_id.as_ref().map_or(true, |subnetwork_id| transaction.tx.subnetwork_id == *subnetwork_id)
+            })
             .map(|transaction| self.consensus_converter.get_mempool_entry(&session, transaction))
             .collect();
         Ok(GetMempoolEntriesResponse::new(mempool_entries))
@@ -511,6 +646,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         request: GetMempoolEntriesByAddressesRequest,
     ) -> RpcResult<GetMempoolEntriesByAddressesResponse> {
+        validate_addresses_network(&request.addresses, self.config.prefix())?;
         let query = self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
         let session = self.consensus_manager.consensus().unguarded_session();
         let script_public_keys = request.addresses.iter().map(pay_to_address_script).collect();
@@ -579,7 +715,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         _: GetCurrentNetworkRequest,
     ) -> RpcResult<GetCurrentNetworkResponse> {
-        Ok(GetCurrentNetworkResponse::new(*self.config.net))
+        Ok(GetCurrentNetworkResponse::new(*self.config.net, Some(self.config.net)))
     }
 
     async fn get_subnetwork_call(
@@ -647,6 +783,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         if !self.config.utxoindex {
             return Err(RpcError::NoUtxoIndex);
         }
+        validate_addresses_network(&request.addresses, self.config.prefix())?;
         // TODO: discuss if the entry order is part of the method requirements
         //       (the current impl does not retain an entry order matching the request addresses order)
         let entry_map = self.get_utxo_set_by_script_public_key(request.addresses.iter()).await;
@@ -661,6 +798,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         if !self.config.utxoindex {
             return Err(RpcError::NoUtxoIndex);
         }
+        validate_address_network(&request.address, self.config.prefix())?;
         let entry_map = self.get_balance_by_script_public_key(once(&request.address)).await;
         let balance = entry_map.values().sum();
         Ok(GetBalanceByAddressResponse::new(balance))
@@ -674,6 +812,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         if !self.config.utxoindex {
             return Err(RpcError::NoUtxoIndex);
         }
+        validate_addresses_network(&request.addresses, self.config.prefix())?;
         let entry_map = self.get_balance_by_script_public_key(request.addresses.iter()).await;
         let entries = request
             .addresses
@@ -692,11 +831,14 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         _: GetCoinSupplyRequest,
     ) -> RpcResult<GetCoinSupplyResponse> {
-        if !self.config.utxoindex {
-            return Err(RpcError::NoUtxoIndex);
-        }
-        let circulating_sompi =
-            self.utxoindex.clone().unwrap().get_circulating_supply().await.map_err(|e| RpcError::General(e.to_string()))?;
+        let circulating_sompi = match self.utxoindex.clone() {
+            Some(utxoindex) => utxoindex.get_circulating_supply().await.map_err(|e| RpcError::General(e.to_string()))?,
+            None => {
+                // Without the UTXO index, fall back to summing the consensus virtual UTXO set, which is too slow to do per request
+                let session = self.consensus_manager.consensus().unguarded_session();
+                self.circulating_supply_cache.get(async move { Self::scan_circulating_supply(&session).await }).await
+            }
+        };
         Ok(GetCoinSupplyResponse::new(MAX_SOMPI, circulating_sompi))
     }
 
@@ -877,10 +1019,28 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     async fn get_peer_addresses_call(
         &self,
         _connection: Option<&DynRpcConnection>,
-        _: GetPeerAddressesRequest,
+        request: GetPeerAddressesRequest,
     ) -> RpcResult<GetPeerAddressesResponse> {
         let address_manager = self.flow_context.address_manager.lock();
-        Ok(GetPeerAddressesResponse::new(address_manager.get_all_addresses(), address_manager.get_all_banned_addresses()))
+        let peer_stats = if request.verbose {
+            address_manager
+                .get_all_peer_stats()
+                .into_iter()
+                .map(|(ip, stats)| RpcPeerStats {
+                    ip,
+                    bytes_sent: stats.bytes_sent,
+                    bytes_received: stats.bytes_received,
+                    last_seen: stats.last_seen,
+                    protocol_version: stats.protocol_version,
+                    avg_latency: stats.avg_latency,
+                    ban_score: stats.ban_score,
+                    sessions: stats.sessions,
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        Ok(GetPeerAddressesResponse::new(address_manager.get_all_addresses(), address_manager.get_all_banned_addresses(), peer_stats))
     }
 
     async fn ban_call(&self, _connection: Option<&DynRpcConnection>, request: BanRequest) -> RpcResult<BanResponse> {
@@ -889,11 +1049,21 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             return Err(RpcError::UnavailableInSafeMode);
         }
         if let Some(connection_manager) = self.flow_context.connection_manager() {
-            let ip = request.ip.into();
-            if connection_manager.ip_has_permanent_connection(ip).await {
-                return Err(RpcError::IpHasPermanentConnection(request.ip));
+            let range = Self::requested_ip_range(request.ip, request.prefix_length)?;
+            if is_ban_range_too_broad(range) {
+                return Err(RpcError::IpRangeTooBroad(range.to_string()));
+            }
+            if connection_manager.range_has_permanent_connection(range).await {
+                return Err(match request.prefix_length {
+                    Some(_) => RpcError::IpRangeHasPermanentConnection(range.to_string()),
+                    None => RpcError::IpHasPermanentConnection(request.ip),
+                });
             }
-            connection_manager.ban(ip).await;
+            let duration = match request.is_permanent {
+                true => None,
+                false => Some(request.duration.map_or(DEFAULT_BAN_DURATION, Duration::from_secs)),
+            };
+            connection_manager.ban_range(range, duration).await;
         } else {
             return Err(RpcError::NoConnectionManager);
         }
@@ -905,11 +1075,12 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             warn!("Unban RPC command called while node in safe RPC mode -- ignoring.");
             return Err(RpcError::UnavailableInSafeMode);
         }
-        let mut address_manager = self.flow_context.address_manager.lock();
-        if address_manager.is_banned(request.ip) {
-            address_manager.unban(request.ip)
-        } else {
-            return Err(RpcError::IpIsNotBanned(request.ip));
+        let range = Self::requested_ip_range(request.ip, request.prefix_length)?;
+        if !self.flow_context.address_manager.lock().unban_range(range) {
+            return Err(match request.prefix_length {
+                Some(_) => RpcError::IpRangeIsNotBanned(range.to_string()),
+                None => RpcError::IpIsNotBanned(request.ip),
+            });
         }
         Ok(UnbanResponse {})
     }
@@ -924,6 +1095,214 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetConnectedPeerInfoResponse::new(peer_info))
     }
 
+    async fn get_p2p_stats_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _: GetP2pStatsRequest,
+    ) -> RpcResult<GetP2pStatsResponse> {
+        Ok(GetP2pStatsResponse {
+            message_stats: self.protocol_converter.get_p2p_message_stats(),
+            top_peers: self.protocol_converter.get_top_peer_contributions(TOP_CONTRIBUTING_PEERS),
+        })
+    }
+
+    async fn get_processing_stats_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _: GetProcessingStatsRequest,
+    ) -> RpcResult<GetProcessingStatsResponse> {
+        let snapshot = self.processing_counters.snapshot();
+        let now = Instant::now();
+        let mut checkpoint = self.processing_stats_checkpoint.lock();
+        let delta = &snapshot - &checkpoint.0;
+        let interval = now.duration_since(checkpoint.1);
+        let rate = |count: u64| if interval.is_zero() { 0f64 } else { count as f64 / interval.as_secs_f64() };
+
+        let response = GetProcessingStatsResponse {
+            blocks_submitted_count: snapshot.blocks_submitted,
+            headers_processed_count: snapshot.header_counts,
+            dependencies_processed_count: snapshot.dep_counts,
+            bodies_processed_count: snapshot.body_counts,
+            transactions_processed_count: snapshot.txs_counts,
+            chain_blocks_processed_count: snapshot.chain_block_counts,
+            chain_disqualified_count: snapshot.chain_disqualified_counts,
+            mass_processed_count: snapshot.mass_counts,
+            interval_millis: interval.as_millis() as u64,
+            headers_per_second: rate(delta.header_counts),
+            bodies_per_second: rate(delta.body_counts),
+            transactions_per_second: rate(delta.txs_counts),
+            dependencies_per_second: rate(delta.dep_counts),
+        };
+        *checkpoint = (snapshot, now);
+        Ok(response)
+    }
+
+    async fn get_peer_flow_states_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetPeerFlowStatesRequest,
+    ) -> RpcResult<GetPeerFlowStatesResponse> {
+        let routers = match request.peer_id {
+            Some(peer_id) => vec![self.flow_context.hub().find_peer(peer_id).ok_or(RpcError::PeerNotFound(peer_id))?],
+            None => self.flow_context.hub().select_peers(None),
+        };
+        Ok(GetPeerFlowStatesResponse { peers: self.protocol_converter.get_peers_flow_states(&routers) })
+    }
+
+    async fn disconnect_peer_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: DisconnectPeerRequest,
+    ) -> RpcResult<DisconnectPeerResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("DisconnectPeer RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let Some(connection_manager) = self.flow_context.connection_manager() else {
+            return Err(RpcError::NoConnectionManager);
+        };
+        if !connection_manager.disconnect(request.peer_id).await {
+            return Err(RpcError::PeerNotFound(request.peer_id));
+        }
+        Ok(DisconnectPeerResponse {})
+    }
+
+    async fn set_peer_protected_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: SetPeerProtectedRequest,
+    ) -> RpcResult<SetPeerProtectedResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("SetPeerProtected RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let Some(connection_manager) = self.flow_context.connection_manager() else {
+            return Err(RpcError::NoConnectionManager);
+        };
+        // Protection may always be removed, while only a connected peer may be protected
+        if request.is_protected && self.flow_context.hub().find_peer(request.peer_id).is_none() {
+            return Err(RpcError::PeerNotFound(request.peer_id));
+        }
+        connection_manager.set_protected(request.peer_id, request.is_protected);
+        Ok(SetPeerProtectedResponse {})
+    }
+
+    async fn get_transaction_acceptance_data_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionAcceptanceDataRequest,
+    ) -> RpcResult<GetTransactionAcceptanceDataResponse> {
+        let Some(acceptance_index) = self.acceptance_index.clone() else {
+            return Err(RpcError::NoAcceptanceIndex);
+        };
+        let transaction_ids = request.transaction_ids;
+        let acceptances = acceptance_index
+            .get_transactions_acceptance(transaction_ids.clone())
+            .await
+            .map_err(|e| RpcError::General(e.to_string()))?
+            .into_iter()
+            .zip(transaction_ids)
+            .filter_map(|(acceptance, transaction_id)| {
+                acceptance.map(|acceptance| RpcTransactionAcceptance {
+                    transaction_id,
+                    accepting_block_hash: acceptance.accepting_block_hash,
+                    accepting_blue_score: acceptance.accepting_blue_score,
+                })
+            })
+            .collect();
+        Ok(GetTransactionAcceptanceDataResponse { acceptances })
+    }
+
+    async fn get_chain_block_by_score_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetChainBlockByScoreRequest,
+    ) -> RpcResult<GetChainBlockByScoreResponse> {
+        let score = if request.by_blue_score { ChainScore::BlueScore } else { ChainScore::DaaScore };
+        let session = self.consensus_manager.consensus().session().await;
+        let block_hash = session.async_get_chain_block_by_score(score, request.score).await?;
+        let header = session.async_get_header(block_hash).await?;
+        Ok(GetChainBlockByScoreResponse { block_hash, daa_score: header.daa_score, blue_score: header.blue_score })
+    }
+
+    async fn get_block_serialized_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetBlockSerializedRequest,
+    ) -> RpcResult<GetBlockSerializedResponse> {
+        let session = self.consensus_manager.consensus().session().await;
+        let block = session.async_get_block(request.hash).await?;
+        Ok(GetBlockSerializedResponse { block_hex: codec::encode_block(&block).to_rpc_hex() })
+    }
+
+    async fn submit_block_serialized_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: SubmitBlockSerializedRequest,
+    ) -> RpcResult<SubmitBlockSerializedResponse> {
+        let try_block: RpcResult<Block> = Vec::<u8>::from_rpc_hex(&request.block_hex)
+            .map_err(RpcError::from)
+            .and_then(|bytes| codec::decode_block(&bytes).map_err(RpcError::from));
+        let block = match try_block {
+            Ok(block) => block,
+            Err(err) => {
+                trace!("incoming SubmitBlockSerializedRequest with block decoding error: {}", err);
+                return Ok(SubmitBlockSerializedResponse {
+                    report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid),
+                    error: Some(format!("Could not parse block: {0}", err)),
+                });
+            }
+        };
+        let request = SubmitBlockRequest::new((&block).into(), request.allow_non_daa_blocks);
+        Ok(self.submit_block_call(connection, request).await?.into())
+    }
+
+    async fn get_transaction_serialized_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionSerializedRequest,
+    ) -> RpcResult<GetTransactionSerializedResponse> {
+        let transaction_hex = match request.block_hash {
+            Some(block_hash) => {
+                let session = self.consensus_manager.consensus().session().await;
+                let block = session.async_get_block(block_hash).await?;
+                block
+                    .transactions
+                    .iter()
+                    .find(|tx| tx.id() == request.transaction_id)
+                    .map(|tx| codec::encode_transaction(tx).to_rpc_hex())
+            }
+            None => self
+                .mining_manager
+                .clone()
+                .get_transaction(request.transaction_id, TransactionQuery::All)
+                .await
+                .map(|transaction| codec::encode_transaction(&transaction.tx).to_rpc_hex()),
+        };
+        let transaction_hex = transaction_hex.ok_or(RpcError::TransactionNotFound(request.transaction_id))?;
+        Ok(GetTransactionSerializedResponse { transaction_hex })
+    }
+
+    async fn submit_transaction_serialized_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: SubmitTransactionSerializedRequest,
+    ) -> RpcResult<SubmitTransactionSerializedResponse> {
+        let transaction = codec::decode_transaction(&Vec::<u8>::from_rpc_hex(&request.transaction_hex)?)?;
+        let request = SubmitTransactionRequest::new((&transaction).into(), request.allow_orphan);
+        let transaction_id = self.submit_transaction_call(connection, request).await?.transaction_id;
+        Ok(SubmitTransactionSerializedResponse { transaction_id })
+    }
+
+    async fn get_local_transactions_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetLocalTransactionsRequest,
+    ) -> RpcResult<GetLocalTransactionsResponse> {
+        let transactions = self.mining_manager.clone().get_local_transactions().await;
+        Ok(GetLocalTransactionsResponse { transactions: transactions.into_iter().map(|tx| tx.into_rpc()).collect() })
+    }
+
     async fn shutdown_call(&self, _connection: Option<&DynRpcConnection>, _: ShutdownRequest) -> RpcResult<ShutdownResponse> {
         if !self.config.unsafe_rpc {
             warn!("Shutdown RPC command called while node in safe RPC mode -- ignoring.");
@@ -1009,22 +1388,36 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             json_handshake_failures: self.wrpc_json_counters.handshake_failures.load(Ordering::Relaxed) as u64,
 
             active_peers: self.flow_context.hub().active_peers_len() as u32,
+
+            connection_subscriptions: self.connection_subscriptions(),
+            leaked_listeners: self.leaked_listeners.load(Ordering::Relaxed),
         });
 
-        let bandwidth_metrics = req.bandwidth_metrics.then(|| BandwidthMetrics {
-            borsh_bytes_tx: self.wrpc_borsh_counters.tx_bytes.load(Ordering::Relaxed) as u64,
-            borsh_bytes_rx: self.wrpc_borsh_counters.rx_bytes.load(Ordering::Relaxed) as u64,
-            json_bytes_tx: self.wrpc_json_counters.tx_bytes.load(Ordering::Relaxed) as u64,
-            json_bytes_rx: self.wrpc_json_counters.rx_bytes.load(Ordering::Relaxed) as u64,
-            p2p_bytes_tx: self.p2p_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
-            p2p_bytes_rx: self.p2p_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
-            grpc_bytes_tx: self.grpc_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
-            grpc_bytes_rx: self.grpc_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
+        let bandwidth_metrics = req.bandwidth_metrics.then(|| {
+            let (p2p_messages_rx, p2p_messages_tx) = self.flow_context.p2p_message_counters().total_counts();
+            let inventory_stats = self.flow_context.p2p_inventory_counters().snapshot();
+            BandwidthMetrics {
+                borsh_bytes_tx: self.wrpc_borsh_counters.tx_bytes.load(Ordering::Relaxed) as u64,
+                borsh_bytes_rx: self.wrpc_borsh_counters.rx_bytes.load(Ordering::Relaxed) as u64,
+                json_bytes_tx: self.wrpc_json_counters.tx_bytes.load(Ordering::Relaxed) as u64,
+                json_bytes_rx: self.wrpc_json_counters.rx_bytes.load(Ordering::Relaxed) as u64,
+                p2p_bytes_tx: self.p2p_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
+                p2p_bytes_rx: self.p2p_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
+                grpc_bytes_tx: self.grpc_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
+                grpc_bytes_rx: self.grpc_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
+                p2p_messages_tx,
+                p2p_messages_rx,
+                p2p_known_blocks_hits: inventory_stats.block_hits,
+                p2p_known_blocks_misses: inventory_stats.block_misses,
+                p2p_known_transactions_hits: inventory_stats.transaction_hits,
+                p2p_known_transactions_misses: inventory_stats.transaction_misses,
+            }
         });
 
         let consensus_metrics = if req.consensus_metrics {
             let consensus_stats = self.consensus_manager.consensus().unguarded_session().async_get_stats().await;
             let processing_counters = self.processing_counters.snapshot();
+            let submission_queue_depths = self.flow_context.block_submission_queue_depths();
 
             Some(ConsensusMetrics {
                 node_blocks_submitted_count: processing_counters.blocks_submitted,
@@ -1044,6 +1437,25 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 network_past_median_time: consensus_stats.virtual_stats.past_median_time,
                 network_virtual_parent_hashes_count: consensus_stats.virtual_stats.num_parents,
                 network_virtual_daa_score: consensus_stats.virtual_stats.daa_score,
+                // ---
+                node_relay_block_submissions_in_flight: submission_queue_depths.relay_in_flight as u32,
+                node_relay_block_submissions_waiting: submission_queue_depths.relay_waiting as u32,
+                node_ibd_block_submissions_in_flight: submission_queue_depths.bulk_in_flight as u32,
+                node_ibd_block_submissions_waiting: submission_queue_depths.bulk_waiting as u32,
+                // ---
+                node_stage_latencies: ProcessingStage::ALL
+                    .into_iter()
+                    .map(|stage| {
+                        let histogram = processing_counters.stage_latency(stage);
+                        ProcessingStageLatencyMetrics {
+                            stage: stage.name().to_string(),
+                            sample_count: histogram.count(),
+                            p50_micros: histogram.quantile_micros(0.5),
+                            p95_micros: histogram.quantile_micros(0.95),
+                            p99_micros: histogram.quantile_micros(0.99),
+                        }
+                    })
+                    .collect(),
             })
         } else {
             None
@@ -1053,6 +1465,23 @@ NOTE: This error usually indicates an RPC conversion error between the node and
 
         let custom_metrics: Option<HashMap<String, CustomMetricValue>> = None;
 
+        let allocation_metrics = (req.allocation_metrics && alloc_tracking::is_enabled()).then(|| AllocationMetrics {
+            subsystems: AllocSubsystem::ALL
+                .into_iter()
+                .map(|subsystem| {
+                    let stats = alloc_tracking::stats(subsystem);
+                    SubsystemAllocationMetrics {
+                        name: subsystem.name().to_string(),
+                        live_bytes: stats.live_bytes(),
+                        allocated_bytes: stats.allocated_bytes,
+                        freed_bytes: stats.freed_bytes,
+                        allocation_count: stats.allocations,
+                        deallocation_count: stats.deallocations,
+                    }
+                })
+                .collect(),
+        });
+
         let server_time = unix_now();
 
         let response = GetMetricsResponse {
@@ -1063,6 +1492,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             consensus_metrics,
             storage_metrics,
             custom_metrics,
+            allocation_metrics,
         };
 
         Ok(response)
@@ -1113,7 +1543,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     ) -> RpcResult<GetSyncStatusResponse> {
         let session = self.consensus_manager.consensus().unguarded_session();
         let is_synced: bool = self.has_sufficient_peer_connectivity() && session.async_is_nearly_synced().await;
-        Ok(GetSyncStatusResponse { is_synced })
+        Ok(GetSyncStatusResponse { is_synced, progress: self.protocol_converter.get_sync_progress() })
     }
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1145,6 +1575,11 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 warn!("RPC subscription to blanket UtxosChanged called while node in safe RPC mode -- ignoring.");
                 Err(RpcError::UnavailableInSafeMode)
             }
+            Scope::UtxosChanged(ref utxos_changed_scope) => {
+                validate_addresses_network(&utxos_changed_scope.addresses, self.config.prefix())?;
+                self.notifier.clone().start_notify(id, scope).await?;
+                Ok(())
+            }
             _ => {
                 self.notifier.clone().start_notify(id, scope).await?;
                 Ok(())
@@ -76,6 +76,8 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::TransactionsEvictedNotification,
+            RpcApiOps::NotificationsGapNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -488,6 +490,12 @@ impl KaspaRpcClient {
         Ok(())
     }
 
+    /// Register a new [`NotificationListener`] with its own notification channel.
+    /// Its subscriptions are restored automatically when the client reconnects.
+    pub fn new_listener(&self, name: &'static str) -> NotificationListener {
+        NotificationListener::new(self, name)
+    }
+
     pub fn notification_channel_receiver(&self) -> Receiver<Notification> {
         self.inner.notification_intake_channel.lock().unwrap().receiver.clone()
     }
@@ -567,6 +575,14 @@ impl KaspaRpcClient {
                         if let Ok(msg) = msg {
                             match msg {
                                 WrpcCtl::Connect => {
+                                    // Server-side subscriptions are bound to the WebSocket connection,
+                                    // so re-send the compounded listener subscriptions after a reconnect.
+                                    let notifier = inner.notifier.lock().unwrap().clone();
+                                    if let Some(notifier) = notifier {
+                                        if let Err(err) = notifier.try_renew_subscriptions() {
+                                            log_error!("Error renewing notification subscriptions: {err}");
+                                        }
+                                    }
                                     inner.rpc_ctl.signal_open().await.expect("(KaspaRpcClient) rpc_ctl.signal_open() error");
                                 }
                                 WrpcCtl::Disconnect => {
@@ -615,6 +631,7 @@ impl RpcApi for KaspaRpcClient {
             Ping,
             AddPeer,
             Ban,
+            DisconnectPeer,
             EstimateNetworkHashesPerSecond,
             GetBalanceByAddress,
             GetBalancesByAddresses,
@@ -627,6 +644,9 @@ impl RpcApi for KaspaRpcClient {
             GetCoinSupply,
             GetConnectedPeerInfo,
             GetConnections,
+            GetP2pStats,
+            GetProcessingStats,
+            GetPeerFlowStates,
             GetCurrentNetwork,
             GetDaaScoreTimestampEstimate,
             GetFeeEstimate,
@@ -651,6 +671,14 @@ impl RpcApi for KaspaRpcClient {
             SubmitBlock,
             SubmitTransaction,
             SubmitTransactionReplacement,
+            SetPeerProtected,
+            GetTransactionAcceptanceData,
+            GetChainBlockByScore,
+            GetBlockSerialized,
+            SubmitBlockSerialized,
+            GetTransactionSerialized,
+            SubmitTransactionSerialized,
+            GetLocalTransactions,
             Unban,
         ]
     );
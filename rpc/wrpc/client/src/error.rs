@@ -8,6 +8,7 @@ use workflow_core::sendable::*;
 use workflow_http::error::Error as HttpError;
 use workflow_rpc::client::error::Error as RpcError;
 use workflow_rpc::client::error::WebSocketError;
+use workflow_rpc::error::ServerError;
 use workflow_wasm::error::Error as WasmError;
 use workflow_wasm::printable::*;
 
@@ -116,6 +117,22 @@ impl From<Error> for JsValue {
     }
 }
 
+/// Converts the error of an RPC call into an [`RpcApi`](kaspa_rpc_core::api::rpc::RpcApi) error. Errors reported by the
+/// server carry the server error message as is, like over gRPC, while failures of the call itself are RPC subsystem errors
+pub(crate) fn rpc_call_error(err: RpcError) -> kaspa_rpc_core::error::RpcError {
+    let message = match &err {
+        RpcError::RpcCall(ServerError::Text(message)) => Some(message.clone()),
+        RpcError::JsonServerError(json_err) => {
+            serde_json::to_value(json_err).ok().and_then(|value| value.get("message")?.as_str().map(String::from))
+        }
+        _ => None,
+    };
+    match message {
+        Some(message) => kaspa_rpc_core::error::RpcError::General(message),
+        None => kaspa_rpc_core::error::RpcError::RpcSubsystem(err.to_string()),
+    }
+}
+
 // impl From<workflow_wasm::serde::Error> for Error {
 //     fn from(err: workflow_wasm::serde::Error) -> Self {
 //         Self::ToValue(err.to_string())
@@ -1,6 +1,7 @@
 #![allow(unused_imports)]
 
 pub use crate::client::*;
+pub use crate::listener::NotificationListener;
 pub use crate::resolver::Resolver;
 pub use crate::result::Result;
 pub use async_std::sync::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
@@ -17,6 +17,7 @@
 pub mod client;
 pub mod error;
 mod imports;
+pub mod listener;
 pub mod result;
 pub use imports::{KaspaRpcClient, Resolver, WrpcEncoding};
 pub mod node;
@@ -0,0 +1,76 @@
+//!
+//! Typed notification listener for the [`KaspaRpcClient`].
+//!
+//! [`NotificationListener`] bundles a listener registration with its own
+//! notification channel, allowing subscriptions to be managed and received
+//! without dealing with [`ListenerId`] and [`ChannelConnection`] directly.
+//!
+//! ```ignore
+//! let listener = client.new_listener("my-listener");
+//! listener.subscribe(Scope::VirtualDaaScoreChanged(VirtualDaaScoreChangedScope {})).await?;
+//! while let Ok(notification) = listener.recv().await {
+//!     if let Notification::VirtualDaaScoreChanged(notification) = notification {
+//!         println!("DAA score: {}", notification.virtual_daa_score);
+//!     }
+//! }
+//! ```
+//!
+//! Subscriptions of a listener are tracked by the client and are re-sent to
+//! the node when the underlying WebSocket reconnects, so a listener remains
+//! valid across connection drops. Calling [`KaspaRpcClient::disconnect()`]
+//! discards all listeners; they need to be re-created after `connect()`.
+//!
+
+use crate::error::Error;
+use crate::imports::*;
+use kaspa_notify::connection::ChannelType;
+
+pub struct NotificationListener {
+    client: KaspaRpcClient,
+    id: ListenerId,
+    channel: Channel<Notification>,
+}
+
+impl NotificationListener {
+    pub(crate) fn new(client: &KaspaRpcClient, name: &'static str) -> Self {
+        let channel = Channel::unbounded();
+        let id = client.register_new_listener(ChannelConnection::new(name, channel.sender.clone(), ChannelType::Persistent));
+        Self { client: client.clone(), id, channel }
+    }
+
+    pub fn id(&self) -> ListenerId {
+        self.id
+    }
+
+    /// Start receiving notifications for the given scope.
+    pub async fn subscribe(&self, scope: Scope) -> Result<()> {
+        Ok(self.client.start_notify(self.id, scope).await?)
+    }
+
+    /// Stop receiving notifications for the given scope.
+    pub async fn unsubscribe(&self, scope: Scope) -> Result<()> {
+        Ok(self.client.stop_notify(self.id, scope).await?)
+    }
+
+    /// Wait for the next notification. Fails once the listener has been unregistered.
+    pub async fn recv(&self) -> Result<Notification> {
+        self.channel.receiver.recv().await.map_err(|err| Error::ChannelError(err.to_string()))
+    }
+
+    /// Return the next notification if one is pending.
+    pub fn try_recv(&self) -> Option<Notification> {
+        self.channel.receiver.try_recv().ok()
+    }
+
+    /// Notification receiver, usable as a [`Stream`] or in a `select!` block.
+    pub fn receiver(&self) -> Receiver<Notification> {
+        self.channel.receiver.clone()
+    }
+
+    /// Stop all subscriptions of this listener and close its channel.
+    pub async fn unregister(self) -> Result<()> {
+        self.client.unregister_listener(self.id).await?;
+        self.channel.sender.close();
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 //! Re-exports of the most commonly used types and traits.
 
 pub use crate::client::{ConnectOptions, ConnectStrategy};
+pub use crate::listener::NotificationListener;
 pub use crate::{KaspaRpcClient, Resolver, WrpcEncoding};
 pub use kaspa_consensus_core::network::{NetworkId, NetworkType};
 pub use kaspa_notify::{connection::ChannelType, listener::ListenerId, scope::*};
@@ -75,6 +75,12 @@ async fn main() -> Result<()> {
         listen_address: interface.unwrap_or_else(|| format!("wrpc://127.0.0.1:{proxy_port}")),
         grpc_proxy_address: Some(grpc_proxy_address.unwrap_or_else(|| format!("grpc://127.0.0.1:{kaspad_port}"))),
         verbose,
+        access_policy: None,
+        max_connections: None,
+        max_connections_per_ip: None,
+        keepalive_interval: None,
+        idle_timeout: None,
+        write_timeout: None,
         // ..Options::default()
     });
     log_info!("");
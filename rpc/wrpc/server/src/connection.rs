@@ -1,15 +1,24 @@
+use kaspa_core::time::unix_now;
 use kaspa_grpc_client::{GrpcClient, GrpcClientNotify};
 use kaspa_notify::{
-    connection::Connection as ConnectionT,
+    connection::{Connection as ConnectionT, OverflowPolicy},
     error::{Error as NotifyError, Result as NotifyResult},
     listener::ListenerId,
     notification::Notification as NotificationT,
     notifier::Notify,
 };
-use kaspa_rpc_core::{api::ops::RpcApiOps, notify::mode::NotificationMode, Notification};
+use kaspa_rpc_core::{
+    api::{access::RpcConnectionLimits, ops::RpcApiOps},
+    notify::mode::NotificationMode,
+    Notification,
+};
 use std::{
     fmt::{Debug, Display},
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
 };
 use workflow_log::log_trace;
 use workflow_rpc::{
@@ -51,6 +60,12 @@ struct ConnectionInner {
     pub grpc_client: Option<Arc<GrpcClient>>,
     // not using an atomic in case an Id will change type in the future...
     pub listener_id: Mutex<Option<ListenerId>>,
+    /// Set on disconnection, after which no notification listener can be registered anymore
+    pub disconnected: AtomicBool,
+    pub limits: RpcConnectionLimits,
+    /// Unix time in milliseconds of the last request of the client
+    pub last_activity: AtomicU64,
+    pub subscribed: AtomicBool,
 }
 
 impl ConnectionInner {
@@ -88,7 +103,19 @@ impl Connection {
         assert!(grpc_client.is_none() || grpc_client.as_ref().unwrap().notification_mode() == NotificationMode::Direct);
         // Should a gRPC client be provided, no listener_id is required for subscriptions so the listener id is set to default
         let listener_id = Mutex::new(grpc_client.clone().map(|_| ListenerId::default()));
-        Connection { inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id }) }
+        Connection {
+            inner: Arc::new(ConnectionInner {
+                id,
+                peer: *peer,
+                messenger,
+                grpc_client,
+                listener_id,
+                disconnected: AtomicBool::new(false),
+                limits: Default::default(),
+                last_activity: AtomicU64::new(unix_now()),
+                subscribed: AtomicBool::new(false),
+            }),
+        }
     }
 
     /// Obtain the connection id
@@ -117,14 +144,53 @@ impl Connection {
         *self.inner.listener_id.lock().unwrap()
     }
 
-    pub fn register_notification_listener(&self, listener_id: ListenerId) {
-        self.inner.listener_id.lock().unwrap().replace(listener_id);
+    /// Gets the notification listener id of the connection, registering one with `register` if none is set yet.
+    /// Returns `None` if the connection is disconnected, so a listener never outlives its connection
+    pub fn get_or_register_listener_id(&self, register: impl FnOnce() -> ListenerId) -> Option<ListenerId> {
+        let mut listener_id = self.inner.listener_id.lock().unwrap();
+        if self.inner.disconnected.load(Ordering::Relaxed) {
+            return None;
+        }
+        Some(*listener_id.get_or_insert_with(register))
+    }
+
+    /// Marks the connection as disconnected and takes its notification listener id, if any
+    pub fn unregister_listener_id(&self) -> Option<ListenerId> {
+        let mut listener_id = self.inner.listener_id.lock().unwrap();
+        self.inner.disconnected.store(true, Ordering::Relaxed);
+        listener_id.take()
     }
 
     pub fn peer(&self) -> &SocketAddr {
         &self.inner.peer
     }
 
+    /// Rate limiting state of the connection under the access policy of the server
+    pub fn limits(&self) -> &RpcConnectionLimits {
+        &self.inner.limits
+    }
+
+    /// Records a request of the client
+    pub fn touch(&self) {
+        self.inner.last_activity.store(unix_now(), Ordering::Relaxed);
+    }
+
+    /// Marks the connection as subscribed to notifications, which keeps it from being considered idle
+    pub fn set_subscribed(&self) {
+        self.inner.subscribed.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the client is subscribed to no notifications and issued no request for `timeout`
+    pub fn is_idle(&self, now: u64, timeout: Duration) -> bool {
+        !self.inner.subscribed.load(Ordering::Relaxed)
+            && now.saturating_sub(self.inner.last_activity.load(Ordering::Relaxed)) > timeout.as_millis() as u64
+    }
+
+    /// Maximum number of notifications queued for the client before some get dropped
+    pub fn notification_queue_size() -> usize {
+        1024
+    }
+
     /// Creates a WebSocket [`Message`] that can be posted to the connection ([`Messenger`]) sink
     /// directly.
     pub fn create_serialized_notification_message<Ops, Msg>(encoding: Encoding, op: Ops, msg: Msg) -> WrpcResult<Message>
@@ -179,6 +245,10 @@ impl ConnectionT for Connection {
     fn is_closed(&self) -> bool {
         self.messenger().sink().is_closed()
     }
+
+    fn overflow_policy(&self) -> OverflowPolicy {
+        OverflowPolicy::Bounded(Self::notification_queue_size())
+    }
 }
 
 pub type ConnectionReference = Arc<Connection>;
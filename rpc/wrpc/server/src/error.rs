@@ -1,6 +1,6 @@
 use kaspa_notify::error::Error as NotifyError;
 use kaspa_rpc_core::RpcError;
-use std::sync::PoisonError;
+use std::{net::IpAddr, sync::PoisonError};
 use thiserror::Error;
 use workflow_rpc::server::{error::Error as RpcServerError, WebSocketError};
 
@@ -12,6 +12,12 @@ pub enum Error {
     #[error("WebSocket error: {0}")]
     WebSocketError(#[from] WebSocketError),
 
+    #[error("max number of {0} connections reached")]
+    MaxConnections(usize),
+
+    #[error("max number of {0} connections from {1} reached")]
+    MaxConnectionsPerIp(usize, IpAddr),
+
     #[error("Poison error")]
     PoisonError,
 
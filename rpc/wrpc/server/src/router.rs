@@ -36,6 +36,7 @@ impl Router {
                 Ping,
                 AddPeer,
                 Ban,
+                DisconnectPeer,
                 EstimateNetworkHashesPerSecond,
                 GetBalanceByAddress,
                 GetBalancesByAddresses,
@@ -59,7 +60,10 @@ impl Router {
                 GetMempoolEntry,
                 GetMetrics,
                 GetConnections,
+                GetP2pStats,
                 GetPeerAddresses,
+                GetPeerFlowStates,
+                GetProcessingStats,
                 GetServerInfo,
                 GetSink,
                 GetSinkBlueScore,
@@ -69,6 +73,14 @@ impl Router {
                 GetUtxosByAddresses,
                 GetVirtualChainFromBlock,
                 ResolveFinalityConflict,
+                SetPeerProtected,
+                GetTransactionAcceptanceData,
+                GetChainBlockByScore,
+                GetBlockSerialized,
+                SubmitBlockSerialized,
+                GetTransactionSerialized,
+                SubmitTransactionSerialized,
+                GetLocalTransactions,
                 Shutdown,
                 SubmitBlock,
                 SubmitTransaction,
@@ -1,9 +1,11 @@
 use crate::{
     collector::{WrpcServiceCollector, WrpcServiceConverter},
     connection::Connection,
+    error::Error,
     result::Result,
     service::Options,
 };
+use kaspa_core::time::unix_now;
 use kaspa_grpc_client::GrpcClient;
 use kaspa_notify::{
     connection::ChannelType,
@@ -15,9 +17,13 @@ use kaspa_notify::{
     subscription::{MutationPolicies, UtxosChangedMutationPolicy},
 };
 use kaspa_rpc_core::{
-    api::rpc::{DynRpcService, RpcApi},
+    api::{
+        access::RpcAccessPermit,
+        ops::RpcApiOps,
+        rpc::{DynRpcService, RpcApi},
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection, mode::NotificationMode},
-    Notification, RpcResult,
+    Notification, RpcError, RpcResult,
 };
 use kaspa_rpc_service::service::RpcCoreService;
 use std::{
@@ -86,6 +92,11 @@ impl Server {
                 tasks,
                 policies,
             ));
+            let protocol = match encoding {
+                Encoding::Borsh => "wrpc-borsh",
+                Encoding::SerdeJson => "wrpc-json",
+            };
+            service.register_connection_notifier(protocol, wrpc_notifier.clone());
             Some(RpcCore { service, wrpc_notifier })
         } else {
             None
@@ -109,8 +120,36 @@ impl Server {
         }
     }
 
+    /// Checks whether one more connection from `peer` fits the connection limits
+    fn check_connection_limits(&self, sockets: &HashMap<u64, Connection>, peer: &SocketAddr) -> Result<()> {
+        if let Some(max_connections) = self.inner.options.max_connections {
+            if sockets.len() >= max_connections {
+                return Err(Error::MaxConnections(max_connections));
+            }
+        }
+        if let Some(max_connections_per_ip) = self.inner.options.max_connections_per_ip {
+            if sockets.values().filter(|connection| connection.peer().ip() == peer.ip()).count() >= max_connections_per_ip {
+                return Err(Error::MaxConnectionsPerIp(max_connections_per_ip, peer.ip()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a connection from `peer` can currently be accepted, checked before the WebSocket handshake
+    pub fn accepts(&self, peer: &SocketAddr) -> bool {
+        let sockets = self.inner.sockets.lock().unwrap();
+        match self.check_connection_limits(&sockets, peer) {
+            Ok(()) => true,
+            Err(err) => {
+                log_debug!("WebSocket {peer} rejected: {err}");
+                false
+            }
+        }
+    }
+
     pub async fn connect(&self, peer: &SocketAddr, messenger: Arc<Messenger>) -> Result<Connection> {
         // log_trace!("WebSocket connected: {}", peer);
+        self.check_connection_limits(&*self.inner.sockets.lock()?, peer)?;
         let id = self.inner.next_connection_id.fetch_add(1, Ordering::SeqCst);
 
         let grpc_client = if let Some(grpc_proxy_address) = &self.inner.options.grpc_proxy_address {
@@ -140,14 +179,39 @@ impl Server {
             connection.grpc_client().start(Some(connection.grpc_client_notify_target())).await;
             // log_trace!("gRPC started...");
         }
-        self.inner.sockets.lock()?.insert(id, connection.clone());
+        // Other connections might have been registered meanwhile so the limits are checked again along with the registration
+        let registered = {
+            let mut sockets = self.inner.sockets.lock()?;
+            self.check_connection_limits(&sockets, peer).map(|_| sockets.insert(id, connection.clone()))
+        };
+        if let Err(err) = registered {
+            if self.inner.options.grpc_proxy_address.is_some() {
+                let _ = connection.grpc_client().disconnect().await;
+            }
+            return Err(err);
+        }
         Ok(connection)
     }
 
+    /// Sends a keepalive ping to all connections and closes the idle ones. Dead peers fail to acknowledge
+    /// the pings, so their connections get dropped once the write timeout of the listener expires
+    pub fn check_connections(&self) {
+        let now = unix_now();
+        let sockets = self.inner.sockets.lock().unwrap();
+        for connection in sockets.values() {
+            if self.inner.options.idle_timeout.is_some_and(|timeout| connection.is_idle(now, timeout)) {
+                log_debug!("WebSocket {} is idle, closing", connection.peer());
+                let _ = connection.messenger().close();
+            } else if self.inner.options.keepalive_interval.is_some() {
+                let _ = connection.messenger().send_raw_message(Message::Ping(vec![]));
+            }
+        }
+    }
+
     pub async fn disconnect(&self, connection: Connection) {
         // log_info!("WebSocket disconnected: {}", connection.peer());
         if let Some(rpc_core) = &self.inner.rpc_core {
-            if let Some(listener_id) = connection.listener_id() {
+            if let Some(listener_id) = connection.unregister_listener_id() {
                 rpc_core.wrpc_notifier.unregister_listener(listener_id).unwrap_or_else(|err| {
                     log_error!("WebSocket {} (disconnected) error unregistering the notification listener: {err}", connection.peer());
                 });
@@ -177,19 +241,19 @@ impl Server {
     }
 
     pub async fn start_notify(&self, connection: &Connection, scope: Scope) -> RpcResult<()> {
-        let listener_id = if let Some(listener_id) = connection.listener_id() {
-            listener_id
-        } else {
-            // The only possible case here is a server connected to rpc core.
-            // If the proxy is used, the connection has a gRPC client and the listener id
-            // is always set to Some(ListenerId::default()) by the connection ctor.
-            let notifier =
-                self.notifier().unwrap_or_else(|| panic!("Incorrect use: `server::Server` does not carry an internal notifier"));
-            let listener_id = notifier.register_new_listener(connection.clone(), ListenerLifespan::Dynamic);
-            connection.register_notification_listener(listener_id);
-            listener_id
-        };
+        let listener_id = connection
+            .get_or_register_listener_id(|| {
+                // The only possible case here is a server connected to rpc core.
+                // If the proxy is used, the connection has a gRPC client and the listener id
+                // is always set to Some(ListenerId::default()) by the connection ctor.
+                let notifier =
+                    self.notifier().unwrap_or_else(|| panic!("Incorrect use: `server::Server` does not carry an internal notifier"));
+                notifier.register_new_listener(connection.clone(), ListenerLifespan::Dynamic)
+            })
+            .ok_or_else(|| RpcError::General(format!("WebSocket {} is disconnected", connection.peer())))?;
         workflow_log::log_trace!("notification subscribe[0x{listener_id:x}] {scope:?}");
+        connection.touch();
+        connection.set_subscribed();
         if let Some(rpc_core) = &self.inner.rpc_core {
             rpc_core.wrpc_notifier.clone().try_start_notify(listener_id, scope)?;
         } else {
@@ -216,6 +280,13 @@ impl Server {
         self.inner.options.verbose
     }
 
+    /// Admits a method call of a connection under the access policy of the server, if any. The returned permit
+    /// must be held until the call completes
+    pub fn admit(&self, op: RpcApiOps, connection: &Connection) -> RpcResult<Option<RpcAccessPermit>> {
+        connection.touch();
+        self.inner.options.access_policy.as_ref().map(|policy| policy.acquire(op, connection.limits())).transpose()
+    }
+
     pub async fn join(&self) -> Result<()> {
         if let Some(rpc_core) = &self.inner.rpc_core {
             // Wait for the internal notifier to stop
@@ -5,11 +5,14 @@ use kaspa_core::{
     task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
     trace, warn,
 };
-use kaspa_rpc_core::api::ops::RpcApiOps;
+use kaspa_rpc_core::api::{access::RpcAccessPolicy, ops::RpcApiOps};
 use kaspa_rpc_service::service::RpcCoreService;
 use kaspa_utils::triggers::SingleTrigger;
-use std::sync::Arc;
-use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
+use std::{sync::Arc, time::Duration};
+use tokio::{
+    sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender},
+    time::MissedTickBehavior,
+};
 use workflow_rpc::server::prelude::*;
 pub use workflow_rpc::server::{Encoding as WrpcEncoding, WebSocketConfig, WebSocketCounters};
 
@@ -20,11 +23,33 @@ pub struct Options {
     pub listen_address: String,
     pub grpc_proxy_address: Option<String>,
     pub verbose: bool,
+    /// Restrictions on the methods served, possibly shared with other servers
+    pub access_policy: Option<Arc<RpcAccessPolicy>>,
+    /// Max number of simultaneous connections
+    pub max_connections: Option<usize>,
+    /// Max number of simultaneous connections from a single IP address
+    pub max_connections_per_ip: Option<usize>,
+    /// Interval of the pings sent to all connections, letting dead peers be detected
+    pub keepalive_interval: Option<Duration>,
+    /// Time after which connections subscribed to no notifications and issuing no request are closed
+    pub idle_timeout: Option<Duration>,
+    /// Time after which connections whose written data remains unacknowledged are dropped (Linux only)
+    pub write_timeout: Option<Duration>,
 }
 
 impl Default for Options {
     fn default() -> Self {
-        Options { listen_address: "127.0.0.1:17110".to_owned(), verbose: false, grpc_proxy_address: None }
+        Options {
+            listen_address: "127.0.0.1:17110".to_owned(),
+            verbose: false,
+            grpc_proxy_address: None,
+            access_policy: None,
+            max_connections: None,
+            max_connections_per_ip: None,
+            keepalive_interval: None,
+            idle_timeout: None,
+            write_timeout: None,
+        }
     }
 }
 
@@ -63,6 +88,10 @@ impl KaspaRpcHandler {
 impl RpcHandler for KaspaRpcHandler {
     type Context = Connection;
 
+    fn accept(&self, peer: &SocketAddr) -> bool {
+        self.server.accepts(peer)
+    }
+
     async fn handshake(
         self: Arc<Self>,
         peer: &SocketAddr,
@@ -143,12 +172,34 @@ impl WrpcService {
             service.server.join().await.unwrap_or_else(|err| warn!("wRPC error: `{err}"));
         });
 
+        // Spawn a task pinging the connections and closing the idle ones
+        if let Some(period) = self.options.keepalive_interval.or(self.options.idle_timeout.map(|timeout| timeout / 2)) {
+            let server = self.rpc_handler.server.clone();
+            let shutdown_signal = self.shutdown.listener.clone();
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(period);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                tokio::pin!(shutdown_signal);
+                loop {
+                    tokio::select! {
+                        _ = ticker.tick() => server.check_connections(),
+                        _ = &mut shutdown_signal => break,
+                    }
+                }
+            });
+        }
+
         // Spawn a task running the server
         info!("WRPC Server starting on: {}", listen_address);
         tokio::spawn(async move {
             let config = WebSocketConfig { max_message_size: Some(MAX_WRPC_MESSAGE_SIZE), ..Default::default() };
             match self.server.bind(&listen_address).await {
                 Ok(listener) => {
+                    if let Some(write_timeout) = self.options.write_timeout {
+                        // Accepted sockets inherit the option from the listener
+                        set_write_timeout(&listener, write_timeout)
+                            .unwrap_or_else(|err| warn!("WRPC Server unable to set the write timeout on {listen_address}: {err}"));
+                    }
                     let serve_result = self.server.listen(listener, Some(config)).await;
                     match serve_result {
                         Ok(_) => info!("WRPC Server stopped on: {}", listen_address),
@@ -163,6 +214,16 @@ impl WrpcService {
     }
 }
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn set_write_timeout(listener: &TcpListener, timeout: Duration) -> std::io::Result<()> {
+    socket2::SockRef::from(listener).set_tcp_user_timeout(Some(timeout))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn set_write_timeout(_listener: &TcpListener, _timeout: Duration) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "write timeouts are only supported on Linux"))
+}
+
 const WRPC_SERVER: &str = "wrpc-service";
 
 impl AsyncService for WrpcService {
@@ -907,6 +907,10 @@ build_wrpc_wasm_bindgen_subscriptions!([
     /// New block template notification event is produced when a new block
     /// template is generated for mining in the Kaspa BlockDAG.
     NewBlockTemplate,
+    /// Manage subscription for a transactions evicted notification event.
+    /// Transactions evicted notification event is produced when transactions
+    /// are evicted from the mempool without being accepted (e.g., expired).
+    TransactionsEvicted,
 ]);
 
 // Build RPC method invocation functions. This macro
@@ -976,8 +980,20 @@ build_wrpc_wasm_bindgen_interface!(
         /// Obtains basic information about the synchronization status of the Kaspa node.
         /// Returned information: Syncing status.
         GetSyncStatus,
+        /// Retrieves the P2P traffic of the Kaspa node per message type and direction.
+        /// Returned information: Message counts and bytes, incoming and outgoing.
+        GetP2pStats,
+        /// Retrieves the block processing counters of the Kaspa node.
+        /// Returned information: Processed headers, bodies, transactions and dependencies,
+        /// along with their per-second rates since the previous call.
+        GetProcessingStats,
         /// Feerate estimates
         GetFeeEstimate,
+        /// Retrieves the transactions submitted to the Kaspa node which are
+        /// still pending acceptance and periodically rebroadcast.
+        /// Returned information: Transaction ids, DAA scores at which they were
+        /// added and past which they expire, orphan status.
+        GetLocalTransactions,
         /// Retrieves the current network configuration.
         /// Returned information: Current network configuration.
         GetCurrentNetwork,
@@ -990,6 +1006,9 @@ build_wrpc_wasm_bindgen_interface!(
         /// Bans a peer from connecting to the Kaspa node for a specified duration.
         /// Returned information: None.
         Ban,
+        /// Disconnects a specific peer by its id without banning it.
+        /// Returned information: None.
+        DisconnectPeer,
         /// Estimates the network's current hash rate in hashes per second.
         /// Returned information: Estimated network hashes per second.
         EstimateNetworkHashesPerSecond,
@@ -1005,9 +1024,15 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves multiple blocks from the Kaspa BlockDAG.
         /// Returned information: List of block information.
         GetBlocks,
+        /// Retrieves a specific block in its consensus (hashing preimage) serialization.
+        /// Returned information: Hex-encoded serialized block.
+        GetBlockSerialized,
         /// Generates a new block template for mining.
         /// Returned information: Block template information.
         GetBlockTemplate,
+        /// Retrieves the selected chain block at which the chain reached a given DAA score or blue score.
+        /// Returned information: Chain block hash, DAA score and blue score.
+        GetChainBlockByScore,
         /// Checks if block is blue or not.
         /// Returned information: Block blueness.
         GetCurrentBlockColor,
@@ -1029,9 +1054,18 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves a specific mempool entry by transaction ID.
         /// Returned information: Mempool entry information.
         GetMempoolEntry,
+        /// Retrieves the state of the P2P flows of the connected peers, or of a specific peer.
+        /// Returned information: Message types and queued messages per flow, and peer protection.
+        GetPeerFlowStates,
         /// Retrieves information about a subnetwork in the Kaspa BlockDAG.
         /// Returned information: Subnetwork information.
         GetSubnetwork,
+        /// Retrieves a transaction of a block or of the mempool in its consensus (hashing preimage) serialization.
+        /// Returned information: Hex-encoded serialized transaction.
+        GetTransactionSerialized,
+        /// Retrieves the chain block accepting each of the given transactions.
+        /// Returned information: Accepting block hash and blue score per accepted transaction.
+        GetTransactionAcceptanceData,
         /// Retrieves unspent transaction outputs (UTXOs) associated with
         /// specific addresses.
         /// Returned information: List of UTXOs.
@@ -1042,15 +1076,24 @@ build_wrpc_wasm_bindgen_interface!(
         /// Resolves a finality conflict in the Kaspa BlockDAG.
         /// Returned information: None.
         ResolveFinalityConflict,
+        /// Marks a specific peer by its id as protected from eviction, or removes its protection.
+        /// Returned information: None.
+        SetPeerProtected,
         /// Submits a block to the Kaspa network.
         /// Returned information: None.
         SubmitBlock,
+        /// Submits a block given in its consensus (hashing preimage) serialization to the Kaspa network.
+        /// Returned information: None.
+        SubmitBlockSerialized,
         /// Submits a transaction to the Kaspa network.
         /// Returned information: Submitted Transaction Id.
         SubmitTransaction,
         /// Submits an RBF transaction to the Kaspa network.
         /// Returned information: Submitted Transaction Id, Transaction that was replaced.
         SubmitTransactionReplacement,
+        /// Submits a transaction given in its consensus (hashing preimage) serialization to the Kaspa network.
+        /// Returned information: Submitted Transaction Id.
+        SubmitTransactionSerialized,
         /// Unbans a previously banned peer, allowing it to connect
         /// to the Kaspa node again.
         /// Returned information: None.
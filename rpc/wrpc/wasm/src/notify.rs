@@ -25,6 +25,8 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    TransactionsEvicted = "transactions-evicted",
+    NotificationsGap = "notifications-gap",
 }
 
 /**
@@ -40,7 +42,9 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate
+    | ITransactionsEvicted
+    | INotificationsGap;
 
 /**
  * RPC notification event data map.
@@ -59,6 +63,8 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "transactions-evicted" : ITransactionsEvicted,
+    "notifications-gap" : INotificationsGap,
 }
 
 /**
@@ -84,6 +90,7 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeTransactionsEvicted},
  * 
  * @category Node RPC
  */
@@ -241,3 +248,36 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    ITransactionsEvicted,
+    r#"
+    /**
+     * Transactions evicted notification event is produced when transactions
+     * are evicted from the mempool without being accepted, e.g., since they
+     * expired.
+     * 
+     * @category Node RPC
+     */
+    export interface ITransactionsEvicted {
+        transactionIds: HexString[];
+    }
+    "#,
+}
+
+declare! {
+    INotificationsGap,
+    r#"
+    /**
+     * Notifications gap event is produced when the node had to drop
+     * notifications since the client did not consume them fast enough.
+     * It is delivered regardless of subscriptions, and clients receiving
+     * it should re-query the state they track.
+     * 
+     * @category Node RPC
+     */
+    export interface INotificationsGap {
+        dropped: bigint;
+    }
+    "#,
+}
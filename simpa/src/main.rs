@@ -27,7 +27,7 @@ use kaspa_core::{
     trace, warn,
 };
 use kaspa_database::prelude::ConnBuilder;
-use kaspa_database::{create_temp_db, load_existing_db};
+use kaspa_database::{create_memory_db, create_temp_db, load_existing_db};
 use kaspa_hashes::Hash;
 use kaspa_perf_monitor::{builder::Builder, counters::CountersSnapshot};
 use kaspa_utils::fd_budget;
@@ -56,6 +56,15 @@ struct Args {
     #[arg(short, long, default_value_t = 200)]
     tpb: u64,
 
+    /// Route generated transactions through a real mempool from which block templates are built by the mining
+    /// manager, reporting end-to-end throughput and latency percentiles (in this mode --tpb is ignored)
+    #[arg(long, default_value_t = false)]
+    mempool: bool,
+
+    /// Target number of transactions per second generated network-wide in mempool mode (defaults to tpb * bps)
+    #[arg(long)]
+    tps: Option<f64>,
+
     /// Target simulation time (seconds)
     #[arg(short, long, default_value_t = 600)]
     sim_time: u64,
@@ -64,7 +73,7 @@ struct Args {
     #[arg(short = 'n', long)]
     target_blocks: Option<u64>,
 
-    /// Number of pool-thread threads used by the header and body processors.
+    /// Number of pool-thread threads used by each of the header and body processors.
     /// Defaults to the number of logical CPU cores.
     #[arg(short, long)]
     processors_threads: Option<usize>,
@@ -122,6 +131,10 @@ struct Args {
     rocksdb_files_limit: Option<i32>,
     #[arg(long)]
     rocksdb_mem_budget: Option<usize>,
+
+    /// Keep the simulation DBs in memory rather than in temp directories (does not apply to the output directory DB)
+    #[arg(long, default_value_t = false)]
+    in_memory_db: bool,
 }
 
 #[cfg(feature = "heap")]
@@ -236,17 +249,21 @@ fn main_impl(mut args: Args) {
         (consensus, lifetime)
     } else {
         let until = if args.target_blocks.is_none() { config.genesis.timestamp + args.sim_time * 1000 } else { u64::MAX }; // milliseconds
+        let mempool_tps = args.mempool.then(|| args.tps.unwrap_or(args.tpb as f64 * args.bps));
         let mut sim = KaspaNetworkSimulator::new(args.delay, args.bps, args.target_blocks, config.clone(), args.output_dir);
         let (consensus, handles, lifetime) = sim
             .init(
                 args.miners,
                 args.tpb,
+                mempool_tps,
                 args.rocksdb_stats,
                 args.rocksdb_stats_period_sec,
                 args.rocksdb_files_limit,
                 args.rocksdb_mem_budget,
+                args.in_memory_db,
             )
             .run(until);
+        sim.report_traffic_stats();
         consensus.shutdown(handles);
         (consensus, lifetime)
     };
@@ -257,7 +274,8 @@ fn main_impl(mut args: Args) {
     }
 
     // Benchmark the DAG validation time
-    let (_lifetime2, db2) = create_temp_db!(ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd));
+    let conn_builder2 = ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd);
+    let (_lifetime2, db2) = if args.in_memory_db { create_memory_db!(conn_builder2) } else { create_temp_db!(conn_builder2) };
     let (dummy_notification_sender, _) = unbounded();
     let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
     let consensus2 = Arc::new(Consensus::new(
@@ -335,7 +353,8 @@ fn apply_args_to_consensus_params(args: &Args, params: &mut Params) {
 
 fn apply_args_to_perf_params(args: &Args, perf_params: &mut PerfParams) {
     if let Some(processors_pool_threads) = args.processors_threads {
-        perf_params.block_processors_num_threads = processors_pool_threads;
+        perf_params.header_processor_num_threads = processors_pool_threads;
+        perf_params.body_processor_num_threads = processors_pool_threads;
     }
     if let Some(virtual_pool_threads) = args.virtual_threads {
         perf_params.virtual_processor_num_threads = virtual_pool_threads;
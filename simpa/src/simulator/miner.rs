@@ -14,6 +14,7 @@ use kaspa_consensus_core::tx::{
 };
 use kaspa_consensus_core::utxo::utxo_view::UtxoView;
 use kaspa_core::trace;
+use kaspa_mining::mempool::tx::{Orphan, Priority, RbfPolicy};
 use kaspa_utils::sim::{Environment, Process, Resumption, Suspension};
 use rand::rngs::ThreadRng;
 use rand::Rng;
@@ -23,6 +24,8 @@ use std::cmp::max;
 use std::iter::once;
 use std::sync::Arc;
 
+use super::traffic::{MempoolTraffic, TRAFFIC_TX_FEE};
+
 struct OnetimeTxSelector {
     txs: Option<Vec<Transaction>>,
 }
@@ -77,6 +80,9 @@ pub struct Miner {
 
     // Mass calculator
     mass_calculator: MassCalculator,
+
+    // Mempool traffic mode (if set, transactions are submitted to the mempool and templates are built by the mining manager)
+    traffic: Option<MempoolTraffic>,
 }
 
 impl Miner {
@@ -90,6 +96,7 @@ impl Miner {
         params: &Params,
         target_txs_per_block: u64,
         target_blocks: Option<u64>,
+        traffic: Option<MempoolTraffic>,
     ) -> Self {
         let (schnorr_public_key, _) = pk.x_only_public_key();
         let script_pub_key_script = once(0x20).chain(schnorr_public_key.serialize()).chain(once(0xac)).collect_vec(); // TODO: Use script builder when available to create p2pk properly
@@ -114,25 +121,72 @@ impl Miner {
                 params.mass_per_sig_op,
                 params.storage_mass_parameter,
             ),
+            traffic,
         }
     }
 
     fn build_new_block(&mut self, timestamp: u64) -> Block {
-        let txs = self.build_txs();
         let nonce = self.id;
-        let session = self.consensus.acquire_session();
-        let mut block_template = self
-            .consensus
-            .build_block_template(self.miner_data.clone(), Box::new(OnetimeTxSelector::new(txs)), TemplateBuildMode::Standard)
-            .expect("simulation txs are selected in sync with virtual state and are expected to be valid");
-        drop(session);
+        let mut block_template = if self.traffic.is_some() {
+            self.submit_traffic_txs(timestamp);
+            let session = self.consensus.acquire_session();
+            let block_template = self
+                .traffic
+                .as_ref()
+                .unwrap()
+                .mining_manager
+                .get_block_template(self.consensus.as_ref(), &self.miner_data)
+                .expect("mempool txs are validated against virtual state and are expected to be valid");
+            drop(session);
+            block_template
+        } else {
+            let txs = self.build_txs(self.target_txs_per_block as usize);
+            let session = self.consensus.acquire_session();
+            let block_template = self
+                .consensus
+                .build_block_template(self.miner_data.clone(), Box::new(OnetimeTxSelector::new(txs)), TemplateBuildMode::Standard)
+                .expect("simulation txs are selected in sync with virtual state and are expected to be valid");
+            drop(session);
+            block_template
+        };
         block_template.block.header.timestamp = timestamp; // Use simulation time rather than real time
         block_template.block.header.nonce = nonce;
         block_template.block.header.finalize();
         block_template.block.to_immutable()
     }
 
-    fn build_txs(&mut self) -> Vec<Transaction> {
+    /// Generates the amount of transactions owed by the target TPS and submits them to the mempool
+    fn submit_traffic_txs(&mut self, now: u64) {
+        let count = self.traffic.as_mut().unwrap().txs_to_generate(now);
+        let txs = self.build_txs(count as usize);
+        let traffic = self.traffic.as_mut().unwrap();
+        let session = self.consensus.acquire_session();
+        for tx in txs {
+            let tx_id = tx.id();
+            let result = traffic.mining_manager.validate_and_insert_transaction(
+                self.consensus.as_ref(),
+                tx,
+                Priority::High,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            if let Err(err) = &result {
+                trace!("Simulated transaction {} was rejected by the mempool: {}", tx_id, err);
+            }
+            traffic.on_submitted(tx_id, now, result.is_ok());
+        }
+        drop(session);
+    }
+
+    fn tx_fee(&self) -> u64 {
+        if self.traffic.is_some() {
+            TRAFFIC_TX_FEE
+        } else {
+            1
+        }
+    }
+
+    fn build_txs(&mut self, count: usize) -> Vec<Transaction> {
         let virtual_read = self.consensus.virtual_stores.read();
         let virtual_state = virtual_read.state.get().unwrap();
         let virtual_utxo_view = &virtual_read.utxo_set;
@@ -146,7 +200,7 @@ impl Miner {
                 let unsigned_tx = self.create_unsigned_tx(outpoint, entry.amount, multiple_outputs);
                 Some(MutableTransaction::with_entries(unsigned_tx, vec![entry]))
             })
-            .take(self.target_txs_per_block as usize)
+            .take(count)
             .collect::<Vec<_>>()
             .into_par_iter()
             .map(|mutable_tx| {
@@ -172,7 +226,7 @@ impl Miner {
         virtual_daa_score: u64,
     ) -> Option<UtxoEntry> {
         let entry = utxo_view.get(&outpoint)?;
-        if entry.amount < 2
+        if entry.amount <= self.tx_fee()
             || (entry.is_coinbase && (virtual_daa_score as i64 - entry.block_daa_score as i64) <= self.params.coinbase_maturity as i64)
         {
             return None;
@@ -181,16 +235,17 @@ impl Miner {
     }
 
     fn create_unsigned_tx(&self, outpoint: TransactionOutpoint, input_amount: u64, multiple_outputs: bool) -> Transaction {
+        let fee = self.tx_fee();
         Transaction::new_non_finalized(
             0,
             vec![TransactionInput::new(outpoint, vec![], 0, 0)],
-            if multiple_outputs && input_amount > 4 {
+            if multiple_outputs && input_amount > 4 * fee {
                 vec![
                     TransactionOutput::new(input_amount / 2, self.miner_data.script_public_key.clone()),
-                    TransactionOutput::new(input_amount / 2 - 1, self.miner_data.script_public_key.clone()),
+                    TransactionOutput::new(input_amount / 2 - fee, self.miner_data.script_public_key.clone()),
                 ]
             } else {
-                vec![TransactionOutput::new(input_amount - 1, self.miner_data.script_public_key.clone())]
+                vec![TransactionOutput::new(input_amount - fee, self.miner_data.script_public_key.clone())]
             },
             0,
             SUBNETWORK_ID_NATIVE,
@@ -223,9 +278,20 @@ impl Miner {
         if self.report_progress(env) {
             Suspension::Halt
         } else {
+            let (daa_score, transactions) = (block.header.daa_score, block.transactions.clone());
             let session = self.consensus.acquire_session();
             let status = futures::executor::block_on(self.consensus.validate_and_insert_block(block).virtual_state_task).unwrap();
             assert!(status.is_utxo_valid_or_pending());
+            if let Some(traffic) = self.traffic.as_mut() {
+                // Skip the coinbase transaction
+                for tx in transactions.iter().skip(1) {
+                    traffic.on_included(tx.id(), env.now());
+                }
+                traffic
+                    .mining_manager
+                    .handle_new_block_transactions(self.consensus.as_ref(), daa_score, &transactions)
+                    .expect("mempool is expected to handle new block transactions");
+            }
             drop(session);
             Suspension::Idle
         }
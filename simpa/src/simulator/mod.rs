@@ -1,2 +1,3 @@
 pub mod miner;
 pub mod network;
+pub mod traffic;
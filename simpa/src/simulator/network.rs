@@ -1,17 +1,19 @@
 use async_channel::unbounded;
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_core::time::unix_now;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
 
 use super::miner::Miner;
+use super::traffic::{MempoolTraffic, TrafficStats};
 
 use kaspa_consensus::config::Config;
 use kaspa_consensus::consensus::Consensus;
 use kaspa_consensus_core::block::Block;
 use kaspa_database::prelude::ConnBuilder;
 use kaspa_database::utils::DbLifetime;
-use kaspa_database::{create_permanent_db, create_temp_db};
+use kaspa_database::{create_memory_db, create_permanent_db, create_temp_db};
+use kaspa_mining::{manager::MiningManager, MiningCounters};
 use kaspa_utils::fd_budget;
 use kaspa_utils::sim::Simulation;
 
@@ -28,6 +30,8 @@ pub struct KaspaNetworkSimulator {
     bps: f64,                   // Blocks per second
     target_blocks: Option<u64>, // Target simulation blocks
     output_dir: Option<String>, // Possible permanent output directory
+
+    traffic_stats: Option<Arc<Mutex<TrafficStats>>>, // Mempool traffic stats (if running in mempool mode)
 }
 
 impl KaspaNetworkSimulator {
@@ -39,6 +43,7 @@ impl KaspaNetworkSimulator {
             config,
             target_blocks,
             output_dir,
+            traffic_stats: None,
         }
     }
 
@@ -46,11 +51,14 @@ impl KaspaNetworkSimulator {
         &mut self,
         num_miners: u64,
         target_txs_per_block: u64,
+        mempool_tps: Option<f64>,
         rocksdb_stats: bool,
         rocksdb_stats_period_sec: Option<u32>,
         rocksdb_files_limit: Option<i32>,
         rocksdb_mem_budget: Option<usize>,
+        in_memory_db: bool,
     ) -> &mut Self {
+        self.traffic_stats = mempool_tps.map(|_| Default::default());
         let secp = secp256k1::Secp256k1::new();
         let mut rng = rand::thread_rng();
         for i in 0..num_miners {
@@ -68,6 +76,12 @@ impl KaspaNetworkSimulator {
                 (true, Some(dir), true, None) => create_permanent_db!(dir, builder.enable_stats()),
                 (true, Some(dir), false, _) => create_permanent_db!(dir, builder),
 
+                (_, _, true, Some(rocksdb_stats_period_sec)) if in_memory_db => {
+                    create_memory_db!(builder.enable_stats().with_stats_period(rocksdb_stats_period_sec))
+                }
+                (_, _, true, None) if in_memory_db => create_memory_db!(builder.enable_stats()),
+                (_, _, false, _) if in_memory_db => create_memory_db!(builder),
+
                 (_, _, true, Some(rocksdb_stats_period_sec)) => {
                     create_temp_db!(builder.enable_stats().with_stats_period(rocksdb_stats_period_sec))
                 }
@@ -87,6 +101,16 @@ impl KaspaNetworkSimulator {
                 unix_now(),
             ));
             let handles = consensus.run_processors();
+            let traffic = mempool_tps.map(|tps| {
+                let mining_manager = Arc::new(MiningManager::new(
+                    self.config.target_time_per_block,
+                    false,
+                    self.config.max_block_mass,
+                    Some(0), // Templates must reflect the latest mempool state in simulation time
+                    Arc::new(MiningCounters::default()),
+                ));
+                MempoolTraffic::new(mining_manager, tps / num_miners as f64, self.traffic_stats.clone().unwrap())
+            });
             let (sk, pk) = secp.generate_keypair(&mut rng);
             let miner_process = Box::new(Miner::new(
                 i,
@@ -98,6 +122,7 @@ impl KaspaNetworkSimulator {
                 &self.config,
                 target_txs_per_block,
                 self.target_blocks,
+                traffic,
             ));
             self.simulation.register(i, miner_process);
             self.consensuses.push((consensus, handles, lifetime));
@@ -105,6 +130,13 @@ impl KaspaNetworkSimulator {
         self
     }
 
+    /// Logs the end-to-end throughput and latency stats of the mempool traffic (if running in mempool mode)
+    pub fn report_traffic_stats(&self) {
+        if let Some(stats) = self.traffic_stats.as_ref() {
+            stats.lock().unwrap().report();
+        }
+    }
+
     pub fn run(&mut self, until: u64) -> ConsensusWrapper {
         self.simulation.run(until);
         for (consensus, handles, _) in self.consensuses.drain(1..) {
@@ -0,0 +1,106 @@
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_core::info;
+use kaspa_mining::manager::MiningManager;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// Fee (in sompi) paid by each generated transaction when routed through the mempool.
+/// Chosen to be well above the minimum relay fee of the simple 1-input/2-output transactions we generate
+pub(super) const TRAFFIC_TX_FEE: u64 = 10_000;
+
+/// Aggregated transaction traffic statistics, shared by all simulated miners
+#[derive(Default)]
+pub struct TrafficStats {
+    /// Number of transactions generated and submitted to a mempool
+    submitted: u64,
+    /// Number of submitted transactions which were rejected by the mempool
+    rejected: u64,
+    /// End-to-end latencies (simulation time in milliseconds) from submission until first inclusion in a block
+    latencies: Vec<u64>,
+    /// Simulation times (in milliseconds) of the first submission and of the last inclusion
+    first_submission_time: Option<u64>,
+    last_inclusion_time: u64,
+}
+
+impl TrafficStats {
+    pub fn report(&self) {
+        let included = self.latencies.len() as u64;
+        let duration = self.last_inclusion_time.saturating_sub(self.first_submission_time.unwrap_or_default());
+        let throughput = if duration > 0 { included as f64 * 1000.0 / duration as f64 } else { 0.0 };
+        info!(
+            "[Mempool traffic] submitted: {}, rejected: {}, included: {}, pending: {}, throughput: {:.2} (t/s)",
+            self.submitted,
+            self.rejected,
+            included,
+            self.submitted - self.rejected - included,
+            throughput
+        );
+        if included > 0 {
+            let mut latencies = self.latencies.clone();
+            latencies.sort_unstable();
+            let percentile = |p: usize| latencies[((latencies.len() - 1) * p) / 100] as f64 / 1000.0;
+            info!(
+                "[Mempool traffic] inclusion latency (seconds): p50: {:.2}, p90: {:.2}, p99: {:.2}, max: {:.2}",
+                percentile(50),
+                percentile(90),
+                percentile(99),
+                percentile(100)
+            );
+        }
+    }
+}
+
+/// Transaction traffic of a single miner which is routed through a real mempool (via a mining manager)
+/// rather than being selected directly into the miner's block templates
+pub(super) struct MempoolTraffic {
+    pub(super) mining_manager: Arc<MiningManager>,
+
+    /// Target transactions per second generated by the owning miner
+    tps: f64,
+
+    /// Fractional amount of transactions owed since the last generation
+    owed_txs: f64,
+    last_generation_time: Option<u64>,
+
+    /// Submission times of transactions not yet seen in a block
+    submission_times: HashMap<TransactionId, u64>,
+
+    stats: Arc<Mutex<TrafficStats>>,
+}
+
+impl MempoolTraffic {
+    pub(super) fn new(mining_manager: Arc<MiningManager>, tps: f64, stats: Arc<Mutex<TrafficStats>>) -> Self {
+        Self { mining_manager, tps, owed_txs: 0.0, last_generation_time: None, submission_times: HashMap::new(), stats }
+    }
+
+    /// Returns the number of transactions which should be generated at simulation time `now` in order to meet the target TPS
+    pub(super) fn txs_to_generate(&mut self, now: u64) -> u64 {
+        let elapsed = now - self.last_generation_time.unwrap_or(now);
+        self.last_generation_time = Some(now);
+        self.owed_txs += self.tps * elapsed as f64 / 1000.0;
+        let count = self.owed_txs.floor();
+        self.owed_txs -= count;
+        count as u64
+    }
+
+    pub(super) fn on_submitted(&mut self, tx_id: TransactionId, now: u64, accepted: bool) {
+        let mut stats = self.stats.lock().unwrap();
+        stats.submitted += 1;
+        stats.first_submission_time.get_or_insert(now);
+        if accepted {
+            self.submission_times.insert(tx_id, now);
+        } else {
+            stats.rejected += 1;
+        }
+    }
+
+    pub(super) fn on_included(&mut self, tx_id: TransactionId, now: u64) {
+        if let Some(submission_time) = self.submission_times.remove(&tx_id) {
+            let mut stats = self.stats.lock().unwrap();
+            stats.latencies.push(now - submission_time);
+            stats.last_inclusion_time = stats.last_inclusion_time.max(now);
+        }
+    }
+}
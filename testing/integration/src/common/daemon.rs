@@ -7,6 +7,11 @@ use kaspa_notify::subscription::context::SubscriptionContext;
 use kaspa_rpc_core::notify::mode::NotificationMode;
 use kaspa_rpc_service::service::RpcCoreService;
 use kaspa_utils::triggers::Listener;
+use kaspa_wrpc_client::{
+    prelude::{ConnectOptions, ConnectStrategy},
+    KaspaRpcClient, WrpcEncoding,
+};
+use kaspa_wrpc_server::address::WrpcNetAddress;
 use kaspad_lib::{args::Args, daemon::create_core_with_runtime};
 use parking_lot::RwLock;
 use std::{ops::Deref, sync::Arc, time::Duration};
@@ -14,6 +19,8 @@ use tempfile::TempDir;
 
 use kaspa_grpc_client::ClientPool;
 
+use super::transport::{RpcTransport, TransportClient};
+
 pub struct ClientManager {
     pub args: RwLock<Args>,
 
@@ -26,6 +33,8 @@ pub struct ClientManager {
     // Daemon ports
     pub rpc_port: u16,
     pub p2p_port: u16,
+    pub rpc_borsh_port: Option<u16>,
+    pub rpc_json_port: Option<u16>,
 }
 
 impl ClientManager {
@@ -34,8 +43,14 @@ impl ClientManager {
         let context = SubscriptionContext::with_options(None);
         let rpc_port = args.rpclisten.unwrap().normalize(0).port;
         let p2p_port = args.listen.unwrap().normalize(0).port;
+        let wrpc_port = |address: &Option<WrpcNetAddress>| match address {
+            Some(WrpcNetAddress::Custom(address)) => Some(address.normalize(0).port),
+            _ => None,
+        };
+        let rpc_borsh_port = wrpc_port(&args.rpclisten_borsh);
+        let rpc_json_port = wrpc_port(&args.rpclisten_json);
         let args = RwLock::new(args);
-        Self { args, network, context, rpc_port, p2p_port }
+        Self { args, network, context, rpc_port, p2p_port, rpc_borsh_port, rpc_json_port }
     }
 
     pub async fn new_client(&self) -> GrpcClient {
@@ -53,6 +68,36 @@ impl ClientManager {
         .unwrap()
     }
 
+    /// Connects a wRPC client using `encoding` to the daemon. The daemon must listen on a custom wRPC address
+    /// for this encoding, as set by [`Daemon::fill_args_with_random_ports`]
+    pub async fn new_wrpc_client(&self, encoding: WrpcEncoding) -> KaspaRpcClient {
+        let port = match encoding {
+            WrpcEncoding::Borsh => self.rpc_borsh_port,
+            WrpcEncoding::SerdeJson => self.rpc_json_port,
+        }
+        .unwrap_or_else(|| panic!("the daemon has no custom wRPC {encoding} listening address"));
+        let url = format!("ws://localhost:{port}");
+        let client = KaspaRpcClient::new(encoding, Some(&url), None, Some(self.network), Some(self.context.clone())).unwrap();
+        let options = ConnectOptions {
+            block_async_connect: true,
+            strategy: ConnectStrategy::Fallback,
+            url: Some(url),
+            connect_timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+        client.connect(Some(options)).await.unwrap();
+        client
+    }
+
+    /// Connects a client of the given `transport` to the daemon
+    pub async fn new_transport_client(&self, transport: RpcTransport) -> TransportClient {
+        match transport {
+            RpcTransport::Grpc => TransportClient::Grpc(self.new_client().await),
+            RpcTransport::WrpcBorsh => TransportClient::Wrpc(self.new_wrpc_client(WrpcEncoding::Borsh).await),
+            RpcTransport::WrpcJson => TransportClient::Wrpc(self.new_wrpc_client(WrpcEncoding::SerdeJson).await),
+        }
+    }
+
     pub async fn new_clients(&self, count: usize) -> Vec<GrpcClient> {
         let mut clients = Vec::with_capacity(count);
         for _ in 0..count {
@@ -0,0 +1,177 @@
+//!
+//! Reproducible DAG fixtures for consensus tests.
+//!
+//! A fixture is a deterministic DAG topology generated from [`DagFixtureParams`]. It is fed through the full
+//! consensus pipeline and the resulting GHOSTDAG and virtual state is rendered as a textual snapshot, which is
+//! compared against a golden file under `testdata/dag_fixtures`. Algorithm changes affecting the outcome thus show
+//! up as explicit diffs of the golden files.
+//!
+//! Run the tests with `UPDATE_GOLDEN=1` in order to (re)write the golden files after an intended change.
+//!
+
+use itertools::Itertools;
+use kaspa_consensus::{consensus::test_consensus::TestConsensus, model::stores::ghostdag::GhostdagStoreReader};
+use kaspa_consensus_core::{api::ConsensusApi, coinbase::MinerData, tx::ScriptPublicKey};
+use kaspa_hashes::Hash;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The hash word of the genesis block of fixture DAGs. Fixture blocks are numbered sequentially after it
+pub const FIXTURE_GENESIS: u64 = 1;
+
+/// A chain of blocks forking from the main DAG which is submitted only after the main DAG, so that it causes a
+/// reorg of the virtual selected chain if it accumulates more blue work than the main DAG past the fork
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReorgPattern {
+    /// The layer of the main DAG holding the block the chain forks from
+    pub fork_layer: usize,
+    /// The number of blocks of the chain
+    pub length: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DagFixtureParams {
+    pub seed: u64,
+    /// The maximal number of blocks per layer of the main DAG
+    pub width: usize,
+    /// The number of layers of the main DAG
+    pub depth: usize,
+    /// The maximal number of parents per block
+    pub max_parents: usize,
+    pub reorgs: Vec<ReorgPattern>,
+}
+
+/// A DAG topology listing blocks along with their parents in a topological (submission) order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DagFixture {
+    pub blocks: Vec<(Hash, Vec<Hash>)>,
+}
+
+impl DagFixture {
+    /// Generates the DAG described by `params`. The same parameters always generate the same DAG
+    pub fn generate(params: &DagFixtureParams) -> Self {
+        assert!(params.width > 0 && params.max_parents > 0, "fixture DAGs require a positive width and parent limit");
+        let mut rng = ChaCha8Rng::seed_from_u64(params.seed);
+        let mut next_word = FIXTURE_GENESIS + 1;
+        let mut blocks = Vec::new();
+        let mut layers = vec![vec![Hash::from_u64_word(FIXTURE_GENESIS)]];
+
+        for _ in 0..params.depth {
+            let previous = layers.last().unwrap().clone();
+            let layer_width = rng.gen_range(1..=params.width);
+            let mut layer = Vec::with_capacity(layer_width);
+            for _ in 0..layer_width {
+                let num_parents = rng.gen_range(1..=previous.len().min(params.max_parents));
+                let mut parents = previous.choose_multiple(&mut rng, num_parents).copied().collect::<Vec<_>>();
+                parents.sort();
+                let hash = Hash::from_u64_word(next_word);
+                next_word += 1;
+                blocks.push((hash, parents));
+                layer.push(hash);
+            }
+            layers.push(layer);
+        }
+
+        for reorg in params.reorgs.iter() {
+            let fork_layer = &layers[reorg.fork_layer.min(layers.len() - 1)];
+            let mut tip = *fork_layer.choose(&mut rng).unwrap();
+            for _ in 0..reorg.length {
+                let hash = Hash::from_u64_word(next_word);
+                next_word += 1;
+                blocks.push((hash, vec![tip]));
+                tip = hash;
+            }
+        }
+
+        Self { blocks }
+    }
+
+    /// Feeds the fixture blocks one by one through the full pipeline of `consensus`, which is expected to
+    /// use [`FIXTURE_GENESIS`] as genesis. Blocks are UTXO valid, so that chain blocks are not disqualified
+    pub async fn submit(&self, consensus: &TestConsensus) {
+        let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), vec![]);
+        for (hash, parents) in self.blocks.iter() {
+            let mut block = consensus.build_utxo_valid_block_with_parents(*hash, parents.clone(), miner_data.clone(), vec![]);
+            // Templates are timestamped by the wall clock, so the minimal valid timestamp is used instead for reproducibility
+            block.header.timestamp = consensus.build_header_with_parents(*hash, parents.clone()).timestamp;
+            consensus.validate_and_insert_block(block.to_immutable()).virtual_state_task.await.unwrap();
+        }
+    }
+
+    /// Renders the GHOSTDAG data of all fixture blocks and the resulting DAG tips and virtual selected chain of `consensus`
+    /// after submitting the fixture. Blocks are referred to by their fixture number for readability
+    pub fn snapshot(&self, consensus: &TestConsensus) -> String {
+        let ids = std::iter::once(Hash::from_u64_word(FIXTURE_GENESIS))
+            .chain(self.blocks.iter().map(|(hash, _)| *hash))
+            .enumerate()
+            .map(|(i, hash)| (hash, i as u64 + FIXTURE_GENESIS))
+            .collect::<HashMap<_, _>>();
+        let id = |hash: &Hash| ids[hash];
+        let ids_of = |hashes: &[Hash]| hashes.iter().map(id).sorted().join(",");
+
+        let mut snapshot = String::new();
+        for (hash, parents) in self.blocks.iter() {
+            let data = consensus.ghostdag_store().get_data(*hash).unwrap();
+            writeln!(
+                snapshot,
+                "block {} parents=[{}] selected_parent={} blue_score={} blue_work={:x} blues=[{}] reds=[{}]",
+                id(hash),
+                ids_of(parents),
+                id(&data.selected_parent),
+                data.blue_score,
+                data.blue_work,
+                ids_of(data.mergeset_blues.as_slice()),
+                ids_of(data.mergeset_reds.as_slice()),
+            )
+            .unwrap();
+        }
+
+        // The virtual parents, and hence the virtual DAA score, are not rendered since they are partly picked at random
+        // once the tips exceed the parents limit
+        let chain = consensus.get_virtual_chain_from_block(Hash::from_u64_word(FIXTURE_GENESIS), None).unwrap();
+        writeln!(snapshot, "virtual sink={} tips=[{}]", id(&consensus.get_sink()), ids_of(&consensus.get_tips())).unwrap();
+        writeln!(snapshot, "selected chain={}", chain.added.iter().map(id).join(",")).unwrap();
+        snapshot
+    }
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    // In debug mode the working directory is often the top-level workspace folder
+    let base = if Path::new("testdata").exists() { Path::new("testdata") } else { Path::new("testing/integration/testdata") };
+    base.join("dag_fixtures").join(format!("{name}.golden"))
+}
+
+/// Asserts that `snapshot` matches the golden file `name`. The golden file is (re)written instead if the
+/// `UPDATE_GOLDEN` environment variable is set
+pub fn assert_golden(name: &str, snapshot: &str) {
+    let path = golden_path(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, snapshot).unwrap();
+        return;
+    }
+    let golden = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("missing golden file {} ({err}), run with UPDATE_GOLDEN=1 to create it", path.display()));
+    if golden != snapshot {
+        let diff = golden
+            .lines()
+            .zip(snapshot.lines())
+            .enumerate()
+            .filter(|(_, (expected, actual))| expected != actual)
+            .map(|(i, (expected, actual))| format!("line {}:\n  expected: {expected}\n  actual:   {actual}", i + 1))
+            .collect::<Vec<_>>();
+        panic!(
+            "snapshot differs from golden file {} ({} vs {} lines), first differences:\n{}\nRun with UPDATE_GOLDEN=1 if the change is intended",
+            path.display(),
+            golden.lines().count(),
+            snapshot.lines().count(),
+            diff.into_iter().take(10).collect::<Vec<_>>().join("\n")
+        );
+    }
+}
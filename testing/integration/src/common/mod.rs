@@ -8,7 +8,9 @@ pub mod args;
 pub mod client;
 pub mod client_notify;
 pub mod daemon;
+pub mod dag_fixture;
 pub mod listener;
+pub mod transport;
 pub mod utils;
 
 pub fn open_file(file_path: &Path) -> File {
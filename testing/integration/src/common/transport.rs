@@ -0,0 +1,71 @@
+//!
+//! RPC transports exposed by the daemon, allowing the same test cases to run against every one of them.
+//!
+
+use async_channel::Receiver;
+use kaspa_grpc_client::GrpcClient;
+use kaspa_notify::{connection::ChannelType, scope::Scope};
+use kaspa_rpc_core::{api::rpc::RpcApi, notify::connection::ChannelConnection, Notification};
+use kaspa_wrpc_client::KaspaRpcClient;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RpcTransport {
+    Grpc,
+    WrpcBorsh,
+    WrpcJson,
+}
+
+impl RpcTransport {
+    pub const ALL: [RpcTransport; 3] = [RpcTransport::Grpc, RpcTransport::WrpcBorsh, RpcTransport::WrpcJson];
+}
+
+impl Display for RpcTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpcTransport::Grpc => write!(f, "gRPC"),
+            RpcTransport::WrpcBorsh => write!(f, "wRPC/borsh"),
+            RpcTransport::WrpcJson => write!(f, "wRPC/json"),
+        }
+    }
+}
+
+/// An RPC client connected to the daemon through one of the [`RpcTransport`]s
+#[derive(Clone)]
+pub enum TransportClient {
+    Grpc(GrpcClient),
+    Wrpc(KaspaRpcClient),
+}
+
+impl TransportClient {
+    pub fn rpc_api(&self) -> &dyn RpcApi {
+        match self {
+            TransportClient::Grpc(client) => client,
+            TransportClient::Wrpc(client) => client,
+        }
+    }
+
+    /// Subscribes to the notifications of `scope` and returns the channel receiving them
+    pub async fn subscribe(&self, scope: Scope) -> Receiver<Notification> {
+        match self {
+            TransportClient::Grpc(client) => {
+                // In direct mode, all notifications are sent to the client channel
+                client.start_notify(GrpcClient::DIRECT_MODE_LISTENER_ID, scope).await.unwrap();
+                client.notification_channel_receiver()
+            }
+            TransportClient::Wrpc(client) => {
+                let (sender, receiver) = async_channel::unbounded();
+                let id = client.register_new_listener(ChannelConnection::new("transport client", sender, ChannelType::Closable));
+                client.start_notify(id, scope).await.unwrap();
+                receiver
+            }
+        }
+    }
+
+    pub async fn disconnect(&self) {
+        match self {
+            TransportClient::Grpc(client) => client.disconnect().await.unwrap(),
+            TransportClient::Wrpc(client) => client.disconnect().await.unwrap(),
+        }
+    }
+}
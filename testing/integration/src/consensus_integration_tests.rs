@@ -55,7 +55,7 @@ use kaspa_core::signals::Shutdown;
 use kaspa_core::task::runtime::AsyncRuntime;
 use kaspa_core::{assert_match, info};
 use kaspa_database::create_temp_db;
-use kaspa_database::prelude::{CachePolicy, ConnBuilder};
+use kaspa_database::prelude::{CachePolicy, ConnBuilder, DbAccessMode};
 use kaspa_index_processor::service::IndexService;
 use kaspa_math::Uint256;
 use kaspa_muhash::MuHash;
@@ -836,11 +836,15 @@ impl KaspadGoParams {
             storage_mass_parameter: STORAGE_MASS_PARAMETER,
             storage_mass_activation: ForkActivation::never(),
             kip10_activation: ForkActivation::never(),
+            payload_activation: ForkActivation::never(),
+            sighash_fork_id: None,
+            sighash_fork_activation: ForkActivation::never(),
             deflationary_phase_daa_score: self.DeflationaryPhaseDaaScore,
             pre_deflationary_phase_base_subsidy: self.PreDeflationaryPhaseBaseSubsidy,
             coinbase_maturity: MAINNET_PARAMS.coinbase_maturity,
             skip_proof_of_work: self.SkipProofOfWork,
             max_block_level: self.MaxBlockLevel,
+            assume_valid: None,
             pruning_proof_m: self.PruningProofM,
         }
     }
@@ -1287,13 +1291,13 @@ async fn bounded_merge_depth_test() {
     // The merge depth root belongs to selected_chain, and block_chain_2[1] is red and doesn't have it in its past, and is not in the
     // past of any kosherizing block, so we expect the next block to be rejected.
     match consensus.add_block_with_parents(100.into(), vec![block_chain_2[1], *selected_chain.last().unwrap()]).await {
-        Err(RuleError::ViolatingBoundedMergeDepth) => {}
+        Err(RuleError::ViolatingBoundedMergeDepth(..)) => {}
         res => panic!("Unexpected result: {res:?}"),
     }
 
     // A block that points to tip of both chains will be rejected for similar reasons (since block_chain_2 tip is also red).
     match consensus.add_block_with_parents(101.into(), vec![*block_chain_2.last().unwrap(), *selected_chain.last().unwrap()]).await {
-        Err(RuleError::ViolatingBoundedMergeDepth) => {}
+        Err(RuleError::ViolatingBoundedMergeDepth(..)) => {}
         res => panic!("Unexpected result: {res:?}"),
     }
 
@@ -1323,7 +1327,7 @@ async fn bounded_merge_depth_test() {
 
     // Since kosherizing_hash is now red, we expect this to fail.
     match consensus.add_block_with_parents(1200.into(), vec![kosherizing_hash, *selected_chain.last().unwrap()]).await {
-        Err(RuleError::ViolatingBoundedMergeDepth) => {}
+        Err(RuleError::ViolatingBoundedMergeDepth(..)) => {}
         res => panic!("Unexpected result: {res:?}"),
     }
 
@@ -1747,6 +1751,8 @@ async fn staging_consensus_test() {
         counters,
         tx_script_cache_counters,
         200,
+        DbAccessMode::ReadWrite,
+        Default::default(),
     ));
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
 
@@ -1761,6 +1767,58 @@ async fn staging_consensus_test() {
     core.join(joins);
 }
 
+#[tokio::test]
+async fn read_only_consensus_test() {
+    use kaspa_consensusmanager::ConsensusFactory as _;
+
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(MAINNET_PARAMS).build();
+
+    let db_tempdir = get_kaspa_tempdir();
+    let db_path = db_tempdir.path().to_owned();
+    let consensus_db_dir = db_path.join("consensus");
+    let meta_db_dir = db_path.join("meta");
+
+    let new_factory = |access_mode: DbAccessMode| {
+        let meta_db = kaspa_database::prelude::ConnBuilder::default()
+            .with_db_path(meta_db_dir.clone())
+            .with_files_limit(5)
+            .with_access_mode(access_mode.clone())
+            .build()
+            .unwrap();
+        let (notification_send, _notification_recv) = unbounded();
+        ConsensusFactory::new(
+            meta_db,
+            &config,
+            consensus_db_dir.clone(),
+            4,
+            Arc::new(ConsensusNotificationRoot::new(notification_send)),
+            Arc::new(ProcessingCounters::default()),
+            Arc::new(TxScriptCacheCounters::default()),
+            200,
+            access_mode,
+            Default::default(),
+        )
+    };
+
+    // Create the consensus data, which processes genesis
+    {
+        let factory = new_factory(DbAccessMode::ReadWrite);
+        let (_, ctl) = factory.new_active_consensus();
+        let handles = ctl.start();
+        assert_eq!(handles.len(), 4);
+        ctl.stop();
+        handles.into_iter().for_each(|handle| handle.join().unwrap());
+    }
+
+    // A read-only consensus is opened over the existing data without spawning any processor
+    let factory = new_factory(DbAccessMode::ReadOnly);
+    let (consensus, ctl) = factory.new_active_consensus();
+    assert!(ctl.start().is_empty());
+    assert_eq!(consensus.session().await.async_get_sink().await, config.genesis.hash);
+    ctl.stop();
+}
+
 /// Tests the KIP-10 transaction introspection opcode activation by verifying that:
 /// 1. Transactions using these opcodes are rejected before the activation DAA score
 /// 2. The same transactions are accepted at and after the activation score
@@ -6,7 +6,7 @@ use crate::common::{
 };
 use kaspa_addresses::Address;
 use kaspa_alloc::init_allocator_with_default_settings;
-use kaspa_consensus::params::SIMNET_PARAMS;
+use kaspa_consensus::params::{SIMNET_FAST_TEST_PRESET, SIMNET_PARAMS};
 use kaspa_consensus_core::header::Header;
 use kaspa_consensusmanager::ConsensusManager;
 use kaspa_core::{task::runtime::AsyncRuntime, trace};
@@ -137,11 +137,16 @@ async fn daemon_utxos_propagation_test() {
         enable_unsynced_mining: true,
         disable_upnp: true, // UPnP registration might take some time and is not needed for this test
         utxoindex: true,
+        preset: Some(SIMNET_FAST_TEST_PRESET.name.to_owned()),
         ..Default::default()
     };
     let total_fd_limit = 10;
 
-    let coinbase_maturity = SIMNET_PARAMS.coinbase_maturity;
+    // The fast test preset brings coinbase maturity down to a few blocks
+    let mut params = SIMNET_PARAMS;
+    SIMNET_FAST_TEST_PRESET.apply(&mut params);
+    let coinbase_maturity = params.coinbase_maturity;
+    let base_subsidy = params.pre_deflationary_phase_base_subsidy;
     let mut kaspad1 = Daemon::new_random_with_args(args.clone(), total_fd_limit);
     let mut kaspad2 = Daemon::new_random_with_args(args, total_fd_limit);
     let rpc_client1 = kaspad1.start().await;
@@ -183,7 +188,7 @@ async fn daemon_utxos_propagation_test() {
     // Some dummy non-monitored address
     let blank_address = Address::new(kaspad1.network.into(), kaspa_addresses::Version::PubKey, &[0; 32]);
 
-    // Mine 1000 blocks to daemon #1
+    // Mine `coinbase_maturity` blocks to daemon #1
     let initial_blocks = coinbase_maturity;
     let mut last_block_hash = None;
     for i in 0..initial_blocks {
@@ -214,12 +219,12 @@ async fn daemon_utxos_propagation_test() {
         50,
         20,
         move || {
-            async fn daa_score_reached(client: GrpcClient) -> bool {
+            async fn daa_score_reached(client: GrpcClient, coinbase_maturity: u64) -> bool {
                 let virtual_daa_score = client.get_server_info().await.unwrap().virtual_daa_score;
                 trace!("Virtual DAA score: {}", virtual_daa_score);
-                virtual_daa_score == SIMNET_PARAMS.coinbase_maturity
+                virtual_daa_score == coinbase_maturity
             }
-            Box::pin(daa_score_reached(check_client.clone()))
+            Box::pin(daa_score_reached(check_client.clone(), coinbase_maturity))
         },
         "the nodes did not add and relay all the initial blocks",
     )
@@ -257,16 +262,16 @@ async fn daemon_utxos_propagation_test() {
 
     // Check the balance of the miner address
     let miner_balance = rpc_client2.get_balance_by_address(miner_address.clone()).await.unwrap();
-    assert_eq!(miner_balance, initial_blocks * SIMNET_PARAMS.pre_deflationary_phase_base_subsidy);
+    assert_eq!(miner_balance, initial_blocks * base_subsidy);
     let miner_balance = rpc_client1.get_balance_by_address(miner_address.clone()).await.unwrap();
-    assert_eq!(miner_balance, initial_blocks * SIMNET_PARAMS.pre_deflationary_phase_base_subsidy);
+    assert_eq!(miner_balance, initial_blocks * base_subsidy);
 
     // Get the miner UTXOs
     let utxos = fetch_spendable_utxos(&rpc_client1, miner_address.clone(), coinbase_maturity).await;
     assert_eq!(utxos.len(), EXTRA_BLOCKS - 1);
     for utxo in utxos.iter() {
         assert!(utxo.1.is_coinbase);
-        assert_eq!(utxo.1.amount, SIMNET_PARAMS.pre_deflationary_phase_base_subsidy);
+        assert_eq!(utxo.1.amount, base_subsidy);
         assert_eq!(utxo.1.script_public_key, miner_spk);
     }
 
@@ -277,8 +282,8 @@ async fn daemon_utxos_propagation_test() {
     // Spend some coins
     const NUMBER_INPUTS: u64 = 2;
     const NUMBER_OUTPUTS: u64 = 2;
-    const TX_AMOUNT: u64 = SIMNET_PARAMS.pre_deflationary_phase_base_subsidy * (NUMBER_INPUTS * 5 - 1) / 5;
-    let transaction = generate_tx(miner_schnorr_key, &utxos[0..NUMBER_INPUTS as usize], TX_AMOUNT, NUMBER_OUTPUTS, &user_address);
+    let tx_amount = base_subsidy * (NUMBER_INPUTS * 5 - 1) / 5;
+    let transaction = generate_tx(miner_schnorr_key, &utxos[0..NUMBER_INPUTS as usize], tx_amount, NUMBER_OUTPUTS, &user_address);
     rpc_client1.submit_transaction((&transaction).into(), false).await.unwrap();
 
     let check_client = rpc_client1.clone();
@@ -308,20 +313,17 @@ async fn daemon_utxos_propagation_test() {
         assert!(uc.added.iter().all(|x| x.address.is_some() && *x.address.as_ref().unwrap() == user_address));
         assert_eq!(uc.removed.len() as u64, NUMBER_INPUTS);
         assert_eq!(uc.added.len() as u64, NUMBER_OUTPUTS);
-        assert_eq!(
-            uc.removed.iter().map(|x| x.utxo_entry.amount).sum::<u64>(),
-            SIMNET_PARAMS.pre_deflationary_phase_base_subsidy * NUMBER_INPUTS
-        );
-        assert_eq!(uc.added.iter().map(|x| x.utxo_entry.amount).sum::<u64>(), TX_AMOUNT);
+        assert_eq!(uc.removed.iter().map(|x| x.utxo_entry.amount).sum::<u64>(), base_subsidy * NUMBER_INPUTS);
+        assert_eq!(uc.added.iter().map(|x| x.utxo_entry.amount).sum::<u64>(), tx_amount);
     }
 
     // Check the balance of both miner and user addresses
     for x in clients.iter() {
         let miner_balance = x.get_balance_by_address(miner_address.clone()).await.unwrap();
-        assert_eq!(miner_balance, (initial_blocks - NUMBER_INPUTS) * SIMNET_PARAMS.pre_deflationary_phase_base_subsidy);
+        assert_eq!(miner_balance, (initial_blocks - NUMBER_INPUTS) * base_subsidy);
 
         let user_balance = x.get_balance_by_address(user_address.clone()).await.unwrap();
-        assert_eq!(user_balance, TX_AMOUNT);
+        assert_eq!(user_balance, tx_amount);
     }
 
     // Terminate multi-listener clients
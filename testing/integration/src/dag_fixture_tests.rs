@@ -0,0 +1,59 @@
+use crate::common::dag_fixture::{assert_golden, DagFixture, DagFixtureParams, ReorgPattern, FIXTURE_GENESIS};
+use kaspa_alloc::init_allocator_with_default_settings;
+use kaspa_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::MAINNET_PARAMS};
+use kaspa_consensus_core::api::ConsensusApi;
+use kaspa_hashes::Hash;
+
+#[test]
+fn test_dag_fixture_determinism() {
+    let params =
+        DagFixtureParams { seed: 7, width: 5, depth: 20, max_parents: 4, reorgs: vec![ReorgPattern { fork_layer: 3, length: 10 }] };
+    let fixture = DagFixture::generate(&params);
+    assert_eq!(fixture, DagFixture::generate(&params));
+    assert_ne!(fixture, DagFixture::generate(&DagFixtureParams { seed: 8, ..params.clone() }));
+
+    // Blocks are listed in topological order and respect the parent limit
+    for (i, (_, parents)) in fixture.blocks.iter().enumerate() {
+        assert!(!parents.is_empty() && parents.len() <= params.max_parents);
+        for parent in parents.iter() {
+            assert!(*parent == Hash::from_u64_word(FIXTURE_GENESIS) || fixture.blocks[..i].iter().any(|(hash, _)| hash == parent));
+        }
+    }
+    assert!(fixture.blocks.len() >= params.depth + 10 && fixture.blocks.len() <= params.depth * params.width + 10);
+}
+
+/// Feeds the fixture generated by `params` through a fresh consensus instance and compares the resulting state with
+/// the golden file `name`. Returns the fixture along with the resulting sink
+async fn run_fixture(name: &str, params: DagFixtureParams) -> (DagFixture, Hash) {
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| p.genesis.hash = Hash::from_u64_word(FIXTURE_GENESIS))
+        .build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    let fixture = DagFixture::generate(&params);
+    fixture.submit(&consensus).await;
+    assert_golden(name, &fixture.snapshot(&consensus));
+    let sink = consensus.get_sink();
+
+    consensus.shutdown(wait_handles);
+    (fixture, sink)
+}
+
+#[tokio::test]
+async fn test_dag_fixture_goldens() {
+    init_allocator_with_default_settings();
+
+    run_fixture("chain", DagFixtureParams { seed: 1, width: 1, depth: 30, max_parents: 1, reorgs: vec![] }).await;
+    run_fixture("wide", DagFixtureParams { seed: 2, width: 8, depth: 25, max_parents: 8, reorgs: vec![] }).await;
+
+    // The main DAG tip has a blue score of at most 1 + 15 * 3 = 46, whereas the side chain tip has a blue score of at
+    // least 5 + 60 = 65, so the side chain must take over the virtual selected chain
+    let (fixture, sink) = run_fixture(
+        "reorg",
+        DagFixtureParams { seed: 3, width: 3, depth: 15, max_parents: 3, reorgs: vec![ReorgPattern { fork_layer: 5, length: 60 }] },
+    )
+    .await;
+    assert_eq!(sink, fixture.blocks.last().unwrap().0);
+}
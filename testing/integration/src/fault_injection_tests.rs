@@ -0,0 +1,92 @@
+//!
+//! Convergence tests under injected DB and P2P faults.
+//!
+//! Fault injection is configured process-wide, so these tests should run apart from other daemon tests:
+//! `cargo test --release --package kaspa-testing-integration --lib --features fault-injection -- fault_injection_tests`
+//!
+
+use crate::common::{daemon::Daemon, utils::wait_for};
+use kaspa_addresses::Address;
+use kaspa_alloc::init_allocator_with_default_settings;
+use kaspa_database::fault_injection::{self as db_faults, DbFaultConfig};
+use kaspa_grpc_client::GrpcClient;
+use kaspa_p2p_lib::fault_injection::{self as p2p_faults, P2pFaultConfig};
+use kaspa_rpc_core::api::rpc::RpcApi;
+use kaspad_lib::args::Args;
+use std::time::Duration;
+
+/// Mines `count` blocks on top of the current sink of the node behind `client`
+async fn mine_blocks(client: &GrpcClient, pay_address: &Address, count: usize) {
+    for _ in 0..count {
+        let template = client.get_block_template(pay_address.clone(), vec![]).await.unwrap();
+        client.submit_block(template.block, false).await.unwrap();
+    }
+}
+
+/// `cargo test --release --package kaspa-testing-integration --lib --features fault-injection -- fault_injection_tests::daemon_convergence_under_faults_test`
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn daemon_convergence_under_faults_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    db_faults::enable(DbFaultConfig {
+        error_probability: 0.05,
+        failure_probability: 0.0,
+        latency_probability: 0.1,
+        max_latency: Duration::from_millis(5),
+    });
+    p2p_faults::enable(P2pFaultConfig { drop_probability: 0.02, delay_probability: 0.2, max_delay: Duration::from_millis(50) });
+
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+
+    let mut kaspad1 = Daemon::new_random_with_args(args.clone(), total_fd_limit);
+    let mut kaspad2 = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client1 = kaspad1.start().await;
+    let rpc_client2 = kaspad2.start().await;
+    let pay_address = Address::new(kaspad1.network.into(), kaspa_addresses::Version::PubKey, &[0; 32]);
+
+    // Mine a first batch before connecting the nodes so that daemon #2 has to sync it through IBD
+    mine_blocks(&rpc_client1, &pay_address, 20).await;
+
+    // Connect kaspad2 to kaspad1 as a permanent peer, so it reconnects whenever a dropped message fails the connection
+    rpc_client2.add_peer(format!("127.0.0.1:{}", kaspad1.p2p_port).try_into().unwrap(), true).await.unwrap();
+
+    // Mine a second batch to be relayed while the nodes are connected
+    mine_blocks(&rpc_client1, &pay_address, 20).await;
+
+    let check_client1 = rpc_client1.clone();
+    let check_client2 = rpc_client2.clone();
+    wait_for(
+        500,
+        240,
+        move || {
+            async fn converged(client1: GrpcClient, client2: GrpcClient) -> bool {
+                let dag_info1 = client1.get_block_dag_info().await.unwrap();
+                let dag_info2 = client2.get_block_dag_info().await.unwrap();
+                dag_info1.sink == dag_info2.sink && dag_info1.block_count == dag_info2.block_count
+            }
+            Box::pin(converged(check_client1.clone(), check_client2.clone()))
+        },
+        "the nodes did not converge despite the injected faults",
+    )
+    .await;
+    assert_eq!(rpc_client2.get_block_dag_info().await.unwrap().block_count, 40);
+
+    db_faults::disable();
+    p2p_faults::disable();
+
+    rpc_client1.disconnect().await.unwrap();
+    drop(rpc_client1);
+    kaspad1.shutdown();
+
+    rpc_client2.disconnect().await.unwrap();
+    drop(rpc_client2);
+    kaspad2.shutdown();
+}
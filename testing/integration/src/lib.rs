@@ -12,6 +12,9 @@ pub mod consensus_integration_tests;
 #[cfg(test)]
 pub mod consensus_pipeline_tests;
 
+#[cfg(test)]
+pub mod dag_fixture_tests;
+
 #[cfg(test)]
 pub mod daemon_integration_tests;
 
@@ -25,3 +28,10 @@ pub mod subscribe_benchmarks;
 
 #[cfg(test)]
 pub mod rpc_tests;
+
+#[cfg(test)]
+pub mod rpc_parity_tests;
+
+#[cfg(test)]
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection_tests;
@@ -0,0 +1,289 @@
+//!
+//! Transport parity tests running the same RPC requests against the gRPC, wRPC/borsh and wRPC/json
+//! endpoints of a single daemon and asserting that all transports produce the same results, errors and notifications.
+//!
+
+use crate::common::{daemon::Daemon, transport::RpcTransport, utils::wait_for};
+use kaspa_addresses::{Address, Version};
+use kaspa_alloc::init_allocator_with_default_settings;
+use kaspa_consensus::params::SIMNET_GENESIS;
+use kaspa_consensus_core::{header::Header, subnets::SubnetworkId};
+use kaspa_core::info;
+use kaspa_grpc_client::GrpcClient;
+use kaspa_hashes::Hash;
+use kaspa_notify::scope::BlockAddedScope;
+use kaspa_rpc_core::{api::rpc::RpcApi, Notification, RpcHash, RpcResult};
+use kaspa_utils::fd_budget;
+use kaspad_lib::args::Args;
+use serde::Serialize;
+use serde_json::Value;
+use std::{collections::HashMap, mem::discriminant, time::Duration};
+use tokio::time::timeout;
+
+/// The number of blocks mined before running the cases
+const MINED_BLOCKS: u64 = 10;
+
+/// The maximal delay for receiving a notification
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The data of the tested DAG the cases refer to
+struct CaseContext {
+    pay_address: Address,
+    tip: RpcHash,
+}
+
+/// A request run against every transport
+#[derive(Debug, Clone, Copy)]
+enum ParityCase {
+    GetInfo,
+    GetServerInfo,
+    GetSystemInfo,
+    GetSyncStatus,
+    GetCurrentNetwork,
+    GetBlockCount,
+    GetBlockDagInfo,
+    GetSink,
+    GetSinkBlueScore,
+    GetCoinSupply,
+    GetGenesisBlock,
+    GetTipBlock,
+    GetBlocks,
+    GetVirtualChainFromBlock,
+    GetBalanceByAddress,
+    GetBalancesByAddresses,
+    GetUtxosByAddresses,
+    GetMempoolEntries,
+    GetConnectedPeerInfo,
+    GetPeerAddresses,
+    GetFeeEstimate,
+    EstimateNetworkHashesPerSecond,
+    GetDaaScoreTimestampEstimate,
+    GetCurrentBlockColor,
+    // Requests expected to fail, either as unsupported or as referring to unknown data
+    GetHeaders,
+    GetUnknownBlock,
+    GetUnknownSubnetwork,
+    GetVirtualChainFromUnknownBlock,
+}
+
+impl ParityCase {
+    const ALL: [ParityCase; 28] = [
+        ParityCase::GetInfo,
+        ParityCase::GetServerInfo,
+        ParityCase::GetSystemInfo,
+        ParityCase::GetSyncStatus,
+        ParityCase::GetCurrentNetwork,
+        ParityCase::GetBlockCount,
+        ParityCase::GetBlockDagInfo,
+        ParityCase::GetSink,
+        ParityCase::GetSinkBlueScore,
+        ParityCase::GetCoinSupply,
+        ParityCase::GetGenesisBlock,
+        ParityCase::GetTipBlock,
+        ParityCase::GetBlocks,
+        ParityCase::GetVirtualChainFromBlock,
+        ParityCase::GetBalanceByAddress,
+        ParityCase::GetBalancesByAddresses,
+        ParityCase::GetUtxosByAddresses,
+        ParityCase::GetMempoolEntries,
+        ParityCase::GetConnectedPeerInfo,
+        ParityCase::GetPeerAddresses,
+        ParityCase::GetFeeEstimate,
+        ParityCase::EstimateNetworkHashesPerSecond,
+        ParityCase::GetDaaScoreTimestampEstimate,
+        ParityCase::GetCurrentBlockColor,
+        ParityCase::GetHeaders,
+        ParityCase::GetUnknownBlock,
+        ParityCase::GetUnknownSubnetwork,
+        ParityCase::GetVirtualChainFromUnknownBlock,
+    ];
+
+    fn expects_error(&self) -> bool {
+        matches!(
+            self,
+            ParityCase::GetHeaders
+                | ParityCase::GetUnknownBlock
+                | ParityCase::GetUnknownSubnetwork
+                | ParityCase::GetVirtualChainFromUnknownBlock
+        )
+    }
+
+    /// Runs the request through `rpc` and renders its response as JSON so responses of all transports can be compared
+    async fn run(&self, rpc: &dyn RpcApi, ctx: &CaseContext) -> RpcResult<Value> {
+        fn json<T: Serialize>(response: RpcResult<T>) -> RpcResult<Value> {
+            response.map(|response| serde_json::to_value(response).unwrap())
+        }
+
+        let genesis = SIMNET_GENESIS.hash;
+        let unknown = Hash::from_u64_word(u64::MAX);
+        match self {
+            ParityCase::GetInfo => json(rpc.get_info().await),
+            ParityCase::GetServerInfo => json(rpc.get_server_info().await),
+            ParityCase::GetSystemInfo => json(rpc.get_system_info().await),
+            ParityCase::GetSyncStatus => json(rpc.get_sync_status().await),
+            ParityCase::GetCurrentNetwork => json(rpc.get_current_network().await),
+            ParityCase::GetBlockCount => json(rpc.get_block_count().await),
+            ParityCase::GetBlockDagInfo => json(rpc.get_block_dag_info().await),
+            ParityCase::GetSink => json(rpc.get_sink().await),
+            ParityCase::GetSinkBlueScore => json(rpc.get_sink_blue_score().await),
+            ParityCase::GetCoinSupply => json(rpc.get_coin_supply().await),
+            ParityCase::GetGenesisBlock => json(rpc.get_block(genesis, true).await),
+            ParityCase::GetTipBlock => json(rpc.get_block(ctx.tip, true).await),
+            ParityCase::GetBlocks => json(rpc.get_blocks(Some(genesis), true, true).await),
+            ParityCase::GetVirtualChainFromBlock => json(rpc.get_virtual_chain_from_block(genesis, true).await),
+            ParityCase::GetBalanceByAddress => json(rpc.get_balance_by_address(ctx.pay_address.clone()).await),
+            ParityCase::GetBalancesByAddresses => json(rpc.get_balances_by_addresses(vec![ctx.pay_address.clone()]).await),
+            ParityCase::GetUtxosByAddresses => json(rpc.get_utxos_by_addresses(vec![ctx.pay_address.clone()]).await),
+            ParityCase::GetMempoolEntries => json(rpc.get_mempool_entries(true, false).await),
+            ParityCase::GetConnectedPeerInfo => json(rpc.get_connected_peer_info().await),
+            ParityCase::GetPeerAddresses => json(rpc.get_peer_addresses().await),
+            ParityCase::GetFeeEstimate => json(rpc.get_fee_estimate().await),
+            ParityCase::EstimateNetworkHashesPerSecond => json(rpc.estimate_network_hashes_per_second(1000, None).await),
+            ParityCase::GetDaaScoreTimestampEstimate => json(rpc.get_daa_score_timestamp_estimate(vec![0, MINED_BLOCKS]).await),
+            ParityCase::GetCurrentBlockColor => json(rpc.get_current_block_color(ctx.tip).await),
+            ParityCase::GetHeaders => json(rpc.get_headers(genesis, MINED_BLOCKS, true).await),
+            ParityCase::GetUnknownBlock => json(rpc.get_block(unknown, false).await),
+            ParityCase::GetUnknownSubnetwork => json(rpc.get_subnetwork(SubnetworkId::from_byte(0xfe)).await),
+            ParityCase::GetVirtualChainFromUnknownBlock => json(rpc.get_virtual_chain_from_block(unknown, false).await),
+        }
+    }
+}
+
+/// Mines `count` chained blocks and returns the hash of the last one
+async fn mine_blocks(client: &GrpcClient, pay_address: &Address, count: u64) -> RpcHash {
+    let mut tip = SIMNET_GENESIS.hash;
+    for _ in 0..count {
+        let template = client.get_block_template(pay_address.clone(), vec![]).await.unwrap();
+        tip = Header::from(&template.block.header).hash;
+        client.submit_block(template.block, false).await.unwrap();
+    }
+    tip
+}
+
+/// `cargo test --release --package kaspa-testing-integration --lib -- rpc_parity_tests::transport_parity_test`
+#[tokio::test]
+async fn transport_parity_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let args = Args {
+        simnet: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        enable_unsynced_mining: true,
+        utxoindex: true,
+        unsafe_rpc: true,
+        ..Default::default()
+    };
+    let fd_total_budget = fd_budget::limit();
+    let mut daemon = Daemon::new_random_with_args(args, fd_total_budget);
+    let grpc_client = daemon.start().await;
+
+    let pay_address = Address::new(daemon.network.into(), Version::PubKey, &[0; 32]);
+    let tip = mine_blocks(&grpc_client, &pay_address, MINED_BLOCKS).await;
+
+    // Wait for the virtual state to settle so that all transports observe the same DAG
+    let check_client = grpc_client.clone();
+    wait_for(
+        100,
+        100,
+        move || {
+            async fn settled(client: GrpcClient, tip: RpcHash) -> bool {
+                client.get_sink().await.unwrap().sink == tip
+            }
+            Box::pin(settled(check_client.clone(), tip))
+        },
+        "the mined blocks did not become the sink",
+    )
+    .await;
+    grpc_client.disconnect().await.unwrap();
+
+    // Connect all clients before running any case, so the connection-related data of the responses is the same
+    let mut clients = Vec::with_capacity(RpcTransport::ALL.len());
+    for transport in RpcTransport::ALL {
+        clients.push((transport, daemon.new_transport_client(transport).await));
+    }
+
+    let ctx = CaseContext { pay_address, tip };
+    for case in ParityCase::ALL {
+        info!("Running {case:?} over all transports");
+        let mut results = Vec::with_capacity(clients.len());
+        for (transport, client) in clients.iter() {
+            results.push((*transport, case.run(client.rpc_api(), &ctx).await));
+        }
+
+        if case.expects_error() {
+            // Errors must be of the same kind and carry the same message over all transports
+            let (reference_transport, reference) = &results[0];
+            let reference = reference.as_ref().expect_err(&format!("{case:?} unexpectedly succeeded over {reference_transport}"));
+            for (transport, result) in results.iter().skip(1) {
+                let err = result.as_ref().expect_err(&format!("{case:?} unexpectedly succeeded over {transport}"));
+                assert_eq!(
+                    discriminant(reference),
+                    discriminant(err),
+                    "{case:?} error kinds differ between {reference_transport} and {transport}: {reference:?} vs {err:?}"
+                );
+                assert_eq!(
+                    reference.to_string(),
+                    err.to_string(),
+                    "{case:?} error messages differ between {reference_transport} and {transport}"
+                );
+            }
+            continue;
+        }
+
+        let (reference_transport, reference) = &results[0];
+        let reference = reference.as_ref().unwrap_or_else(|err| panic!("{case:?} failed over {reference_transport}: {err}"));
+        for (transport, result) in results.iter().skip(1) {
+            let result = result.as_ref().unwrap_or_else(|err| panic!("{case:?} failed over {transport}: {err}"));
+            assert_eq!(reference, result, "{case:?} responses differ between {reference_transport} and {transport}");
+        }
+    }
+
+    // Subscribe every client to block-added notifications and mine a block through every transport, so both the
+    // mutating calls and the notifications they trigger are compared
+    let mut receivers = Vec::with_capacity(clients.len());
+    for (_, client) in clients.iter() {
+        receivers.push(client.subscribe(BlockAddedScope {}.into()).await);
+    }
+    let mut mined = Vec::with_capacity(clients.len());
+    for (transport, client) in clients.iter() {
+        let rpc = client.rpc_api();
+        let template = rpc.get_block_template(ctx.pay_address.clone(), vec![]).await.unwrap();
+        mined.push(Header::from(&template.block.header).hash);
+        let response =
+            rpc.submit_block(template.block, false).await.unwrap_or_else(|err| panic!("SubmitBlock failed over {transport}: {err}"));
+        assert!(response.report.is_success(), "SubmitBlock was rejected over {transport}: {response:?}");
+    }
+
+    // Every transport must notify all the mined blocks with the same content
+    let mut reference: Option<(RpcTransport, HashMap<RpcHash, Value>)> = None;
+    for ((transport, _), receiver) in clients.iter().zip(receivers.iter()) {
+        let mut notified = HashMap::with_capacity(mined.len());
+        while !mined.iter().all(|hash| notified.contains_key(hash)) {
+            let notification = timeout(NOTIFICATION_TIMEOUT, receiver.recv())
+                .await
+                .unwrap_or_else(|_| panic!("missing block-added notifications over {transport}"))
+                .unwrap();
+            let Notification::BlockAdded(notification) = notification else {
+                panic!("unexpected notification over {transport}: {notification:?}");
+            };
+            notified.insert(notification.block.header.hash, serde_json::to_value(&*notification.block).unwrap());
+        }
+        match &reference {
+            Some((reference_transport, reference)) => {
+                for hash in mined.iter() {
+                    assert_eq!(
+                        reference[hash], notified[hash],
+                        "block-added notifications of {hash} differ between {reference_transport} and {transport}"
+                    );
+                }
+            }
+            None => reference = Some((*transport, notified)),
+        }
+    }
+
+    for (_, client) in clients {
+        client.disconnect().await;
+    }
+    daemon.shutdown();
+}
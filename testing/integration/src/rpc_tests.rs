@@ -4,7 +4,7 @@ use crate::common::{client_notify::ChannelNotify, daemon::Daemon};
 use futures_util::future::try_join_all;
 use kaspa_addresses::{Address, Prefix, Version};
 use kaspa_consensus::params::SIMNET_GENESIS;
-use kaspa_consensus_core::{constants::MAX_SOMPI, header::Header, subnets::SubnetworkId, tx::Transaction};
+use kaspa_consensus_core::{codec, constants::MAX_SOMPI, header::Header, subnets::SubnetworkId, tx::Transaction};
 use kaspa_core::{assert_match, info};
 use kaspa_grpc_core::ops::KaspadPayloadOps;
 use kaspa_hashes::Hash;
@@ -12,7 +12,7 @@ use kaspa_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
         BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
-        SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
+        SinkBlueScoreChangedScope, TransactionsEvictedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
 };
 use kaspa_rpc_core::{api::rpc::RpcApi, model::*, Notification};
@@ -49,6 +49,7 @@ async fn sanity_test() {
         enable_unsynced_mining: true,
         block_template_cache_lifetime: Some(0),
         utxoindex: true,
+        acceptanceindex: true,
         unsafe_rpc: true,
         ..Default::default()
     };
@@ -103,11 +104,11 @@ async fn sanity_test() {
                     assert!(response.removed_chain_block_hashes.is_empty());
 
                     // Get a block template
-                    let GetBlockTemplateResponse { block, is_synced } = rpc_client
+                    let GetBlockTemplateResponse { block, is_synced, .. } = rpc_client
                         .get_block_template_call(
                             None,
                             GetBlockTemplateRequest {
-                                pay_address: Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]),
+                                pay_address: Some(Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32])),
                                 extra_data: Vec::new(),
                             },
                         )
@@ -191,6 +192,7 @@ async fn sanity_test() {
                 tst!(op, {
                     let response = rpc_client.get_current_network_call(None, GetCurrentNetworkRequest {}).await.unwrap();
                     assert_eq!(response.network, network_id.network_type);
+                    assert_eq!(response.network_id, Some(network_id));
                 })
             }
 
@@ -231,6 +233,9 @@ async fn sanity_test() {
                     assert!(response.is_utxo_indexed);
                     assert!(response.has_message_id);
                     assert!(response.has_notify_command);
+                    assert_eq!(response.network_id, Some(network_id));
+                    assert!(response.unsafe_rpc);
+                    assert_eq!(response.cache_policy.as_deref(), Some("default"));
                 })
             }
 
@@ -273,7 +278,11 @@ async fn sanity_test() {
                     let response = rpc_client
                         .get_mempool_entries_call(
                             None,
-                            GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: false },
+                            GetMempoolEntriesRequest {
+                                include_orphan_pool: true,
+                                filter_transaction_pool: false,
+                                subnetwork_id: None,
+                            },
                         )
                         .await
                         .unwrap();
@@ -289,6 +298,144 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetP2pStats => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_p2p_stats_call(None, GetP2pStatsRequest {}).await.unwrap();
+                    // No peers are connected so no P2P traffic is expected
+                    assert!(response.message_stats.is_empty());
+                    assert!(response.top_peers.is_empty());
+                })
+            }
+
+            KaspadPayloadOps::GetProcessingStats => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_processing_stats_call(None, GetProcessingStatsRequest {}).await.unwrap();
+                    assert!(response.headers_per_second.is_finite() && response.headers_per_second >= 0.0);
+                    assert!(response.transactions_per_second.is_finite() && response.transactions_per_second >= 0.0);
+                })
+            }
+
+            KaspadPayloadOps::GetPeerFlowStates => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // No peers are connected
+                    let response =
+                        rpc_client.get_peer_flow_states_call(None, GetPeerFlowStatesRequest { peer_id: None }).await.unwrap();
+                    assert!(response.peers.is_empty());
+                    let peer_id = RpcNodeId::default();
+                    let result = rpc_client.get_peer_flow_states(Some(peer_id)).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::DisconnectPeer => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let peer_id = RpcNodeId::default();
+                    let result = rpc_client.disconnect_peer(peer_id).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::SetPeerProtected => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Only connected peers may be protected, while removing a protection always succeeds
+                    let peer_id = RpcNodeId::default();
+                    assert!(rpc_client.set_peer_protected(peer_id, true).await.is_err());
+                    rpc_client.set_peer_protected(peer_id, false).await.unwrap();
+                })
+            }
+
+            KaspadPayloadOps::GetTransactionAcceptanceData => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Transactions not accepted by the selected chain are omitted
+                    let transaction_ids = vec![Hash::from_u64_word(u64::MAX)];
+                    let acceptances = rpc_client.get_transaction_acceptance_data(transaction_ids).await.unwrap();
+                    assert!(acceptances.is_empty());
+                })
+            }
+
+            KaspadPayloadOps::GetChainBlockByScore => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let sink = rpc_client.get_sink().await.unwrap().sink;
+                    let sink_blue_score = rpc_client.get_sink_blue_score().await.unwrap();
+                    let response = rpc_client.get_chain_block_by_score(sink_blue_score, true).await.unwrap();
+                    assert_eq!(response.block_hash, sink);
+                    assert_eq!(response.blue_score, sink_blue_score);
+                    let response = rpc_client.get_chain_block_by_score(response.daa_score, false).await.unwrap();
+                    assert_eq!(response.block_hash, sink);
+
+                    // Scores above the sink cannot be looked up
+                    assert!(rpc_client.get_chain_block_by_score(u64::MAX, false).await.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetBlockSerialized => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    assert!(rpc_client.get_block_serialized(0.into()).await.is_err());
+
+                    let block_hex = rpc_client.get_block_serialized(SIMNET_GENESIS.hash).await.unwrap();
+                    let block = codec::decode_block(&Vec::<u8>::from_rpc_hex(&block_hex).unwrap()).unwrap();
+                    assert_eq!(block.hash(), SIMNET_GENESIS.hash);
+                })
+            }
+
+            KaspadPayloadOps::SubmitBlockSerialized => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // A block which cannot be decoded is reported as invalid
+                    let response = rpc_client.submit_block_serialized("deadbeef".to_string(), false).await.unwrap();
+                    assert_eq!(response.report, SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid));
+                    assert!(response.error.is_some());
+                })
+            }
+
+            KaspadPayloadOps::GetTransactionSerialized => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let block_hex = rpc_client.get_block_serialized(SIMNET_GENESIS.hash).await.unwrap();
+                    let block = codec::decode_block(&Vec::<u8>::from_rpc_hex(&block_hex).unwrap()).unwrap();
+                    let coinbase_id = block.transactions[0].id();
+
+                    let transaction_hex = rpc_client.get_transaction_serialized(coinbase_id, Some(SIMNET_GENESIS.hash)).await.unwrap();
+                    let transaction = codec::decode_transaction(&Vec::<u8>::from_rpc_hex(&transaction_hex).unwrap()).unwrap();
+                    assert_eq!(transaction.id(), coinbase_id);
+
+                    // The coinbase transaction is not in the mempool
+                    assert!(rpc_client.get_transaction_serialized(coinbase_id, None).await.is_err());
+                })
+            }
+
+            KaspadPayloadOps::SubmitTransactionSerialized => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Build an erroneous transaction...
+                    let transaction = Transaction::new(0, vec![], vec![], 0, SubnetworkId::default(), 0, vec![]);
+                    let result =
+                        rpc_client.submit_transaction_serialized(codec::encode_transaction(&transaction).to_rpc_hex(), false).await;
+                    // ...that gets rejected by the consensus
+                    assert!(result.is_err());
+
+                    // Malformed data is rejected as well
+                    assert!(rpc_client.submit_transaction_serialized("deadbeef".to_string(), false).await.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetLocalTransactions => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // No transaction was accepted into the mempool
+                    let transactions = rpc_client.get_local_transactions().await.unwrap();
+                    assert!(transactions.is_empty());
+                })
+            }
+
             KaspadPayloadOps::AddPeer => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -298,7 +445,7 @@ async fn sanity_test() {
                     // Add peer only adds the IP to a connection request. It will only be added to known_addresses if it
                     // actually can be connected to. So in this test we can't expect it to be added unless we set up an
                     // actual peer.
-                    let response = rpc_client.get_peer_addresses_call(None, GetPeerAddressesRequest {}).await.unwrap();
+                    let response = rpc_client.get_peer_addresses_call(None, GetPeerAddressesRequest { verbose: false }).await.unwrap();
                     assert!(response.known_addresses.is_empty());
                 })
             }
@@ -310,13 +457,13 @@ async fn sanity_test() {
                     let ip = peer_address.normalize(1).ip;
 
                     let _ = rpc_client.add_peer_call(None, AddPeerRequest { peer_address, is_permanent: false }).await.unwrap();
-                    let _ = rpc_client.ban_call(None, BanRequest { ip }).await.unwrap();
+                    let _ = rpc_client.ban_call(None, BanRequest::new(ip)).await.unwrap();
 
-                    let response = rpc_client.get_peer_addresses_call(None, GetPeerAddressesRequest {}).await.unwrap();
+                    let response = rpc_client.get_peer_addresses_call(None, GetPeerAddressesRequest { verbose: false }).await.unwrap();
                     assert!(response.banned_addresses.contains(&ip));
 
-                    let _ = rpc_client.unban_call(None, UnbanRequest { ip }).await.unwrap();
-                    let response = rpc_client.get_peer_addresses_call(None, GetPeerAddressesRequest {}).await.unwrap();
+                    let _ = rpc_client.unban_call(None, UnbanRequest::new(ip)).await.unwrap();
+                    let response = rpc_client.get_peer_addresses_call(None, GetPeerAddressesRequest { verbose: false }).await.unwrap();
                     assert!(!response.banned_addresses.contains(&ip));
                 })
             }
@@ -520,12 +667,15 @@ async fn sanity_test() {
                                 process_metrics: true,
                                 storage_metrics: true,
                                 custom_metrics: true,
+                                allocation_metrics: true,
                             },
                         )
                         .await
                         .unwrap();
                     assert!(get_metrics_call_response.process_metrics.is_some());
                     assert!(get_metrics_call_response.consensus_metrics.is_some());
+                    // Allocation tracking requires the tracking allocator, which the test daemon does not install
+                    assert!(get_metrics_call_response.allocation_metrics.is_none());
 
                     let get_metrics_call_response = rpc_client
                         .get_metrics_call(
@@ -537,6 +687,7 @@ async fn sanity_test() {
                                 process_metrics: true,
                                 storage_metrics: true,
                                 custom_metrics: true,
+                                allocation_metrics: false,
                             },
                         )
                         .await
@@ -554,6 +705,7 @@ async fn sanity_test() {
                                 process_metrics: false,
                                 storage_metrics: false,
                                 custom_metrics: true,
+                                allocation_metrics: false,
                             },
                         )
                         .await
@@ -571,6 +723,7 @@ async fn sanity_test() {
                                 process_metrics: false,
                                 storage_metrics: false,
                                 custom_metrics: true,
+                                allocation_metrics: false,
                             },
                         )
                         .await
@@ -672,6 +825,14 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::NotifyTransactionsEvicted => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, TransactionsEvictedScope {}.into()).await.unwrap();
+                })
+            }
+
             KaspadPayloadOps::NotifyFinalityConflict => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -41,13 +41,22 @@ enum mi_option_e {
     _mi_option_last,
 }
 
+#[cfg(all(feature = "tracking", not(feature = "heap")))]
+mod tracking;
+
 #[cfg(not(feature = "heap"))]
 use mimalloc::MiMalloc;
-#[cfg(not(feature = "heap"))]
+#[cfg(all(not(feature = "heap"), not(feature = "tracking")))]
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
+#[cfg(all(not(feature = "heap"), feature = "tracking"))]
+#[global_allocator]
+static GLOBAL: tracking::TrackingAllocator<MiMalloc> = tracking::TrackingAllocator(MiMalloc);
 
 pub fn init_allocator_with_default_settings() {
+    #[cfg(all(feature = "tracking", not(feature = "heap")))]
+    kaspa_utils::alloc_tracking::set_enabled();
+
     #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
     #[cfg(not(feature = "heap"))]
     unsafe {
@@ -0,0 +1,65 @@
+use kaspa_utils::alloc_tracking::{current_tag, record_alloc, record_dealloc};
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    mem::size_of,
+    ptr::null_mut,
+};
+
+/// Wraps an allocator and attributes every allocation to the subsystem of the allocating thread.
+///
+/// Each allocation is prefixed with a header holding the subsystem tag in its last byte, so that deallocations
+/// are attributed to the allocating subsystem. The header spans the alignment of the allocation (at least a word),
+/// which keeps the returned pointer aligned
+pub struct TrackingAllocator<A>(pub A);
+
+fn header_size(layout: Layout) -> usize {
+    layout.align().max(size_of::<usize>())
+}
+
+/// Returns the layout of the inner allocation of a `size` bytes allocation, or `None` on overflow
+fn inner_layout(layout: Layout, size: usize) -> Option<Layout> {
+    Layout::from_size_align(size.checked_add(header_size(layout))?, layout.align()).ok()
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let Some(inner) = inner_layout(layout, layout.size()) else {
+            return null_mut();
+        };
+        let base = self.0.alloc(inner);
+        if base.is_null() {
+            return base;
+        }
+        let header = header_size(layout);
+        let tag = current_tag();
+        base.add(header - 1).write(tag);
+        record_alloc(tag, layout.size());
+        base.add(header)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let header = header_size(layout);
+        let base = ptr.sub(header);
+        record_dealloc(base.add(header - 1).read(), layout.size());
+        // The layout was validated when the block was allocated
+        self.0.dealloc(base, Layout::from_size_align_unchecked(layout.size() + header, layout.align()));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let Some(new_inner) = inner_layout(layout, new_size) else {
+            return null_mut();
+        };
+        let header = header_size(layout);
+        let base = ptr.sub(header);
+        // The block keeps its original tag, so it remains attributed to the subsystem which allocated it
+        let tag = base.add(header - 1).read();
+        let new_base =
+            self.0.realloc(base, Layout::from_size_align_unchecked(layout.size() + header, layout.align()), new_inner.size());
+        if new_base.is_null() {
+            return new_base;
+        }
+        record_dealloc(tag, layout.size());
+        record_alloc(tag, new_size);
+        new_base.add(header)
+    }
+}
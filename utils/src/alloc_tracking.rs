@@ -0,0 +1,215 @@
+//!
+//! Attribution of heap allocations to node subsystems.
+//!
+//! Code tags the allocations it makes with a subsystem by entering an [`AllocScope`], tagging a dedicated thread with
+//! [`tag_current_thread`] or polling a future through [`InSubsystemExt::in_subsystem`]. The tracking allocator of
+//! `kaspa-alloc` (behind its `tracking` feature) records every allocation against the subsystem of the allocating code,
+//! and every deallocation against the subsystem the allocation was originally made by, so the counters reflect the memory
+//! held by each subsystem regardless of where it is eventually released. Without the tracking allocator, tagging costs a
+//! single thread-local write and all counters remain zero.
+//!
+
+use std::{
+    cell::Cell,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    task::{Context, Poll},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum AllocSubsystem {
+    /// Allocations made outside of any tagged scope
+    Other = 0,
+    /// Consensus processing, mostly populating the store caches
+    Consensus,
+    /// Mempool insertions, removals and revalidations
+    Mempool,
+    /// Decoding of incoming p2p messages, which are then buffered in the flow queues
+    P2p,
+}
+
+const SUBSYSTEMS_COUNT: usize = 4;
+
+impl AllocSubsystem {
+    pub const ALL: [AllocSubsystem; SUBSYSTEMS_COUNT] =
+        [AllocSubsystem::Other, AllocSubsystem::Consensus, AllocSubsystem::Mempool, AllocSubsystem::P2p];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AllocSubsystem::Other => "other",
+            AllocSubsystem::Consensus => "consensus",
+            AllocSubsystem::Mempool => "mempool",
+            AllocSubsystem::P2p => "p2p",
+        }
+    }
+}
+
+/// A snapshot of the allocation counters of a subsystem
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    pub allocated_bytes: u64,
+    pub freed_bytes: u64,
+    pub allocations: u64,
+    pub deallocations: u64,
+}
+
+impl AllocationStats {
+    /// The number of bytes currently held by the subsystem
+    pub fn live_bytes(&self) -> u64 {
+        self.allocated_bytes.saturating_sub(self.freed_bytes)
+    }
+}
+
+struct Counters {
+    allocated_bytes: AtomicU64,
+    freed_bytes: AtomicU64,
+    allocations: AtomicU64,
+    deallocations: AtomicU64,
+}
+
+impl Counters {
+    const fn new() -> Self {
+        Self {
+            allocated_bytes: AtomicU64::new(0),
+            freed_bytes: AtomicU64::new(0),
+            allocations: AtomicU64::new(0),
+            deallocations: AtomicU64::new(0),
+        }
+    }
+}
+
+static COUNTERS: [Counters; SUBSYSTEMS_COUNT] = [const { Counters::new() }; SUBSYSTEMS_COUNT];
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // Must not allocate on access since it is read from within the allocator
+    static CURRENT: Cell<u8> = const { Cell::new(AllocSubsystem::Other as u8) };
+}
+
+/// Returns the tag of the subsystem the current thread allocates for. Called by the tracking allocator
+pub fn current_tag() -> u8 {
+    CURRENT.try_with(Cell::get).unwrap_or(AllocSubsystem::Other as u8)
+}
+
+/// Records an allocation of `size` bytes for the subsystem tagged `tag`. Called by the tracking allocator
+pub fn record_alloc(tag: u8, size: usize) {
+    let counters = &COUNTERS[tag as usize % SUBSYSTEMS_COUNT];
+    counters.allocated_bytes.fetch_add(size as u64, Ordering::Relaxed);
+    counters.allocations.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Records a deallocation of `size` bytes allocated by the subsystem tagged `tag`. Called by the tracking allocator
+pub fn record_dealloc(tag: u8, size: usize) {
+    let counters = &COUNTERS[tag as usize % SUBSYSTEMS_COUNT];
+    counters.freed_bytes.fetch_add(size as u64, Ordering::Relaxed);
+    counters.deallocations.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Marks the tracking allocator as installed. Called once by `kaspa-alloc` on initialization
+pub fn set_enabled() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns whether the tracking allocator is installed, i.e. whether the counters are meaningful
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn stats(subsystem: AllocSubsystem) -> AllocationStats {
+    let counters = &COUNTERS[subsystem as usize];
+    AllocationStats {
+        allocated_bytes: counters.allocated_bytes.load(Ordering::Relaxed),
+        freed_bytes: counters.freed_bytes.load(Ordering::Relaxed),
+        allocations: counters.allocations.load(Ordering::Relaxed),
+        deallocations: counters.deallocations.load(Ordering::Relaxed),
+    }
+}
+
+/// Tags all subsequent allocations of the current thread with `subsystem`. Meant for threads dedicated to a subsystem
+pub fn tag_current_thread(subsystem: AllocSubsystem) {
+    let _ = CURRENT.try_with(|current| current.set(subsystem as u8));
+}
+
+/// Tags the allocations of the current thread with a subsystem until dropped, restoring the previous tag.
+///
+/// The scope is deliberately `!Send` so it cannot be held across an `.await` point of a `Send` future, where
+/// the thread might switch to polling unrelated tasks. Use [`InSubsystemExt::in_subsystem`] for futures instead
+pub struct AllocScope {
+    prev: u8,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl AllocScope {
+    pub fn enter(subsystem: AllocSubsystem) -> Self {
+        let prev = CURRENT.try_with(|current| current.replace(subsystem as u8)).unwrap_or(AllocSubsystem::Other as u8);
+        Self { prev, _not_send: PhantomData }
+    }
+}
+
+impl Drop for AllocScope {
+    fn drop(&mut self) {
+        let _ = CURRENT.try_with(|current| current.set(self.prev));
+    }
+}
+
+/// A future whose polls are tagged with a subsystem
+pub struct InSubsystem<F> {
+    inner: F,
+    subsystem: AllocSubsystem,
+}
+
+impl<F: Future> Future for InSubsystem<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let _scope = AllocScope::enter(self.subsystem);
+        // SAFETY: `inner` is never moved out of the pinned `InSubsystem`
+        unsafe { self.map_unchecked_mut(|this| &mut this.inner) }.poll(cx)
+    }
+}
+
+pub trait InSubsystemExt: Future + Sized {
+    /// Tags the allocations made while polling this future with `subsystem`
+    fn in_subsystem(self, subsystem: AllocSubsystem) -> InSubsystem<Self> {
+        InSubsystem { inner: self, subsystem }
+    }
+}
+
+impl<F: Future> InSubsystemExt for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alloc_scopes() {
+        assert_eq!(current_tag(), AllocSubsystem::Other as u8);
+        {
+            let _outer = AllocScope::enter(AllocSubsystem::Mempool);
+            assert_eq!(current_tag(), AllocSubsystem::Mempool as u8);
+            {
+                let _inner = AllocScope::enter(AllocSubsystem::P2p);
+                assert_eq!(current_tag(), AllocSubsystem::P2p as u8);
+            }
+            assert_eq!(current_tag(), AllocSubsystem::Mempool as u8);
+        }
+        assert_eq!(current_tag(), AllocSubsystem::Other as u8);
+
+        // Polls are tagged while the scope does not leak out of them
+        let tag = futures_util::FutureExt::now_or_never(async { current_tag() }.in_subsystem(AllocSubsystem::Consensus));
+        assert_eq!(tag, Some(AllocSubsystem::Consensus as u8));
+        assert_eq!(current_tag(), AllocSubsystem::Other as u8);
+
+        // Deallocations are recorded against the allocating subsystem
+        let before = stats(AllocSubsystem::P2p);
+        record_alloc(AllocSubsystem::P2p as u8, 100);
+        record_dealloc(AllocSubsystem::P2p as u8, 40);
+        let after = stats(AllocSubsystem::P2p);
+        assert_eq!(after.allocated_bytes - before.allocated_bytes, 100);
+        assert_eq!(after.freed_bytes - before.freed_bytes, 40);
+        assert_eq!((after.allocations - before.allocations, after.deallocations - before.deallocations), (1, 1));
+    }
+}
@@ -0,0 +1,147 @@
+//!
+//! Exclusive advisory lock over a directory, used to prevent several
+//! processes from operating on the same data directory concurrently.
+//!
+//! The lock is held through a lock file containing the PID of the owning
+//! process. On unix the lock is an `flock(2)` on that file, so it is released
+//! by the OS even if the owning process crashes. On windows the file is opened
+//! without any sharing rights which provides the same guarantee.
+//!
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("lock file {} is held by another process (PID {pid})", .path.display())]
+    LockedByPid { path: PathBuf, pid: u32 },
+
+    #[error("lock file {} is held by another process", .path.display())]
+    Locked { path: PathBuf },
+
+    #[error("unable to lock {}: {source}", .path.display())]
+    Io { path: PathBuf, source: std::io::Error },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// An exclusive lock over a lock file. The lock is released when this value is dropped.
+#[derive(Debug)]
+pub struct FsLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl FsLock {
+    /// Attempts to acquire an exclusive lock over the lock file at `path`, creating it if needed.
+    /// Fails immediately if the lock is held by another process.
+    pub fn try_acquire(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let io_err = |source| Error::Io { path: path.clone(), source };
+
+        let mut file = match open_exclusive(&path) {
+            Ok(file) => file,
+            Err(err) if is_contended(&err) => return Err(locked_error(&path)),
+            Err(err) => return Err(io_err(err)),
+        };
+
+        // Record the PID of the owner so a competing process can report it
+        file.set_len(0).map_err(io_err)?;
+        file.write_all(std::process::id().to_string().as_bytes()).map_err(io_err)?;
+        file.sync_all().map_err(io_err)?;
+
+        Ok(Self { file, path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for FsLock {
+    fn drop(&mut self) {
+        // The file is intentionally left in place: removing it would race with a
+        // process which already opened it and is about to acquire the lock
+        let _ = self.file.set_len(0);
+        #[cfg(unix)]
+        unsafe {
+            use std::os::unix::io::AsRawFd;
+            libc::flock(self.file.as_raw_fd(), libc::LOCK_UN);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn open_exclusive(path: &Path) -> std::io::Result<File> {
+    use std::os::unix::io::AsRawFd;
+    let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(file)
+}
+
+#[cfg(windows)]
+fn open_exclusive(path: &Path) -> std::io::Result<File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    OpenOptions::new().read(true).write(true).create(true).truncate(false).share_mode(0).open(path)
+}
+
+#[cfg(unix)]
+fn is_contended(err: &std::io::Error) -> bool {
+    err.kind() == std::io::ErrorKind::WouldBlock
+}
+
+#[cfg(windows)]
+fn is_contended(err: &std::io::Error) -> bool {
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    err.raw_os_error() == Some(ERROR_SHARING_VIOLATION)
+}
+
+fn locked_error(path: &Path) -> Error {
+    match read_pid(path) {
+        Some(pid) => Error::LockedByPid { path: path.to_path_buf(), pid },
+        None => Error::Locked { path: path.to_path_buf() },
+    }
+}
+
+fn read_pid(path: &Path) -> Option<u32> {
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::Start(0)).ok()?;
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    content.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fs_lock_is_exclusive() {
+        let dir = std::env::temp_dir().join(format!("kaspa-fs-lock-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(".lock");
+
+        let lock = FsLock::try_acquire(&path).unwrap();
+        assert_eq!(read_pid(&path), Some(std::process::id()));
+
+        // A second acquisition (on a distinct open file description) must fail and report the owner
+        match FsLock::try_acquire(&path) {
+            Err(Error::LockedByPid { pid, .. }) => assert_eq!(pid, std::process::id()),
+            #[cfg(windows)]
+            Err(Error::Locked { .. }) => {}
+            other => panic!("unexpected result: {other:?}"),
+        }
+
+        drop(lock);
+        let lock = FsLock::try_acquire(&path).unwrap();
+        drop(lock);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
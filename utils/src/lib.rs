@@ -4,6 +4,7 @@
 //! General purpose utilities and various type extensions used across the Rusty Kaspa codebase.
 //!
 
+pub mod alloc_tracking;
 pub mod any;
 pub mod arc;
 pub mod binary_heap;
@@ -12,6 +13,8 @@ pub mod expiring_cache;
 pub mod hashmap;
 pub mod hex;
 pub mod iter;
+pub mod lru;
+pub mod mem_pressure;
 pub mod mem_size;
 pub mod networking;
 pub mod option;
@@ -203,4 +206,6 @@ pub mod git;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod fd_budget;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod fs_lock;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod sysinfo;
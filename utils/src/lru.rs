@@ -0,0 +1,241 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
+};
+
+/// Marks the absence of a linked entry
+const NIL: usize = usize::MAX;
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    weight: usize,
+    /// The next more recently used entry
+    newer: usize,
+    /// The next less recently used entry
+    older: usize,
+}
+
+/// A least recently used cache bounded by the total weight of its entries. Entries are weighted by the caller,
+/// e.g., by `1` for a cache bounded by the number of entries or by the estimated byte size for a byte-bounded cache.
+///
+/// Entries are kept in a slab and linked by recency of use, so accessing, inserting and evicting are all `O(1)`.
+pub struct LruCache<K, V, S = std::collections::hash_map::RandomState> {
+    capacity: usize,
+    weight: usize,
+    index: HashMap<K, usize, S>,
+    slab: Vec<Option<Entry<K, V>>>,
+    vacant: Vec<usize>,
+    newest: usize,
+    oldest: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// Creates a cache holding entries up to a total weight of `capacity`. A zero capacity disables the cache
+    pub fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, Default::default())
+    }
+}
+
+impl<K: Hash + Eq + Clone, V, S: BuildHasher> LruCache<K, V, S> {
+    pub fn with_hasher(capacity: usize, hasher: S) -> Self {
+        Self {
+            capacity,
+            weight: 0,
+            index: HashMap::with_hasher(hasher),
+            slab: Vec::new(),
+            vacant: Vec::new(),
+            newest: NIL,
+            oldest: NIL,
+        }
+    }
+
+    /// The maximum total weight of the cached entries
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The total weight of the cached entries
+    pub fn weight(&self) -> usize {
+        self.weight
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Returns the value of `key` without marking it as used
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.index.get(key).map(|&slot| &self.entry(slot).value)
+    }
+
+    /// Returns the value of `key`, marking it as the most recently used
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    /// Returns the value of `key` for modification, marking it as the most recently used. Note that the weight of the
+    /// entry is not updated, re-insert the entry if its weight changes
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let slot = *self.index.get(key)?;
+        self.unlink(slot);
+        self.link_newest(slot);
+        Some(&mut self.entry_mut(slot).value)
+    }
+
+    /// Inserts an entry of unit weight. See [`Self::insert_weighted`]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert_weighted(key, value, 1)
+    }
+
+    /// Inserts `value` as the most recently used entry of `key`, evicting the least recently used entries as needed
+    /// to remain within capacity. Returns the value previously cached for `key`, if any. A value weighing more than
+    /// the whole capacity is not cached.
+    pub fn insert_weighted(&mut self, key: K, value: V, weight: usize) -> Option<V> {
+        let previous = self.remove(&key);
+        if weight > self.capacity {
+            return previous;
+        }
+        let entry = Entry { key: key.clone(), value, weight, newer: NIL, older: NIL };
+        let slot = match self.vacant.pop() {
+            Some(slot) => {
+                self.slab[slot] = Some(entry);
+                slot
+            }
+            None => {
+                self.slab.push(Some(entry));
+                self.slab.len() - 1
+            }
+        };
+        self.index.insert(key, slot);
+        self.link_newest(slot);
+        self.weight += weight;
+        // The new entry fits within capacity, so older entries are always available for eviction
+        while self.weight > self.capacity {
+            self.remove_slot(self.oldest);
+        }
+        previous
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let slot = self.index.remove(key)?;
+        Some(self.release(slot).value)
+    }
+
+    fn remove_slot(&mut self, slot: usize) {
+        let entry = self.release(slot);
+        self.index.remove(&entry.key);
+    }
+
+    /// Unlinks and frees the slot of an entry which is no longer indexed
+    fn release(&mut self, slot: usize) -> Entry<K, V> {
+        self.unlink(slot);
+        let entry = self.slab[slot].take().expect("linked slots are occupied");
+        self.vacant.push(slot);
+        self.weight -= entry.weight;
+        entry
+    }
+
+    fn link_newest(&mut self, slot: usize) {
+        let newest = self.newest;
+        let entry = self.entry_mut(slot);
+        entry.newer = NIL;
+        entry.older = newest;
+        match newest {
+            NIL => self.oldest = slot,
+            newest => self.entry_mut(newest).newer = slot,
+        }
+        self.newest = slot;
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let Entry { newer, older, .. } = *self.entry(slot);
+        match newer {
+            NIL => self.newest = older,
+            newer => self.entry_mut(newer).older = older,
+        }
+        match older {
+            NIL => self.oldest = newer,
+            older => self.entry_mut(older).newer = newer,
+        }
+    }
+
+    fn entry(&self, slot: usize) -> &Entry<K, V> {
+        self.slab[slot].as_ref().expect("indexed slots are occupied")
+    }
+
+    fn entry_mut(&mut self, slot: usize) -> &mut Entry<K, V> {
+        self.slab[slot].as_mut().expect("indexed slots are occupied")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_eviction_order() {
+        let mut cache = LruCache::new(3);
+        for key in 1..=3 {
+            assert_eq!(cache.insert(key, key * 10), None);
+        }
+        // 1 was used more recently than 2, hence 2 is evicted when exceeding capacity
+        assert_eq!(cache.get(&1), Some(&10));
+        cache.insert(4, 40);
+        assert_eq!(cache.len(), 3);
+        assert!(!cache.contains_key(&2));
+        assert_eq!(cache.peek(&3), Some(&30));
+
+        // Peeking does not affect the order, so 3 is evicted next
+        cache.insert(5, 50);
+        assert!(!cache.contains_key(&3));
+        assert!([1, 4, 5].iter().all(|key| cache.contains_key(key)));
+
+        // Re-inserting replaces the value and makes the entry the most recently used
+        assert_eq!(cache.insert(1, 11), Some(10));
+        cache.insert(6, 60);
+        assert!(!cache.contains_key(&4));
+        assert_eq!(cache.get(&1), Some(&11));
+
+        // A removed entry frees its slot for reuse without evicting others early
+        let slots = cache.slab.len();
+        assert_eq!(cache.remove(&5), Some(50));
+        cache.insert(7, 70);
+        assert!([1, 6, 7].iter().all(|key| cache.contains_key(key)));
+        assert_eq!(cache.slab.len(), slots);
+    }
+
+    #[test]
+    fn test_lru_weights() {
+        let mut cache = LruCache::new(10);
+        cache.insert_weighted(1, (), 4);
+        cache.insert_weighted(2, (), 4);
+        assert_eq!(cache.weight(), 8);
+
+        // Both older entries are evicted to fit the new one
+        cache.insert_weighted(3, (), 9);
+        assert_eq!((cache.len(), cache.weight()), (1, 9));
+
+        // Re-inserting with a lower weight releases the previous weight
+        cache.insert_weighted(3, (), 2);
+        cache.insert_weighted(4, (), 8);
+        assert_eq!((cache.len(), cache.weight()), (2, 10));
+
+        // An entry heavier than the whole capacity is not cached and replaces any previous value
+        cache.insert_weighted(4, (), 11);
+        assert!(!cache.contains_key(&4));
+        assert_eq!((cache.len(), cache.weight()), (1, 2));
+
+        let mut disabled = LruCache::new(0);
+        disabled.insert(1, ());
+        assert!(disabled.is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+//!
+//! Shrinking of in-memory caches under memory pressure.
+//!
+//! Caches implementing [`Shrinkable`] are registered in a [`ShrinkableRegistry`] which is handed to a memory watchdog.
+//! Once the process exceeds its memory budget, the watchdog asks all registered caches to evict a proportional share of
+//! their items. Caches are registered weakly, so a cache owned by a component which is dropped (e.g., a staging consensus)
+//! simply leaves the registry.
+//!
+
+use parking_lot::Mutex;
+use std::sync::Weak;
+
+/// A cache which can give up part of its items when the process runs low on memory
+pub trait Shrinkable: Send + Sync {
+    /// Evicts about `fraction` (in `[0, 1]`) of the cached items and returns the number of evicted items
+    fn shrink(&self, fraction: f64) -> usize;
+}
+
+#[derive(Default)]
+pub struct ShrinkableRegistry {
+    caches: Mutex<Vec<(String, Weak<dyn Shrinkable>)>>,
+}
+
+impl ShrinkableRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `cache` under `name` for as long as it is alive
+    pub fn register(&self, name: impl Into<String>, cache: Weak<dyn Shrinkable>) {
+        self.caches.lock().push((name.into(), cache));
+    }
+
+    /// Shrinks all the registered caches by `fraction` and returns the number of items evicted from each of them, by name
+    pub fn shrink(&self, fraction: f64) -> Vec<(String, usize)> {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let caches = {
+            let mut caches = self.caches.lock();
+            caches.retain(|(_, cache)| cache.strong_count() > 0);
+            caches.iter().filter_map(|(name, cache)| cache.upgrade().map(|cache| (name.clone(), cache))).collect::<Vec<_>>()
+        };
+        // The registry lock is released while shrinking since caches might register further caches meanwhile
+        caches.into_iter().map(|(name, cache)| (name, cache.shrink(fraction))).collect()
+    }
+
+    /// The number of registered caches which are still alive
+    pub fn len(&self) -> usize {
+        self.caches.lock().iter().filter(|(_, cache)| cache.strong_count() > 0).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    struct TestCache(AtomicUsize);
+
+    impl Shrinkable for TestCache {
+        fn shrink(&self, fraction: f64) -> usize {
+            let len = self.0.load(Ordering::Relaxed);
+            let evicted = (len as f64 * fraction).ceil() as usize;
+            self.0.store(len - evicted, Ordering::Relaxed);
+            evicted
+        }
+    }
+
+    #[test]
+    fn test_shrinkable_registry() {
+        let registry = ShrinkableRegistry::new();
+        let first = Arc::new(TestCache(AtomicUsize::new(100)));
+        let second = Arc::new(TestCache(AtomicUsize::new(10)));
+        registry.register("first", Arc::downgrade(&first) as Weak<dyn Shrinkable>);
+        registry.register("second", Arc::downgrade(&second) as Weak<dyn Shrinkable>);
+        assert_eq!(registry.len(), 2);
+
+        assert_eq!(registry.shrink(0.25), vec![("first".to_string(), 25), ("second".to_string(), 3)]);
+        assert_eq!(first.0.load(Ordering::Relaxed), 75);
+
+        // Dropped caches leave the registry
+        drop(second);
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.shrink(2.0), vec![("first".to_string(), 75)]);
+    }
+}
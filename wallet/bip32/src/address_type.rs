@@ -5,6 +5,7 @@
 use std::fmt;
 
 /// Address type used in HD wallet address derivation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AddressType {
     Receive = 0,
     Change,
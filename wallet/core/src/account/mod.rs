@@ -307,11 +307,24 @@ pub trait Account: AnySync + Send + Sync + 'static {
         payment_secret: Option<Secret>,
         abortable: &Abortable,
         notifier: Option<GenerationNotifier>,
+    ) -> Result<(GeneratorSummary, Vec<kaspa_hashes::Hash>)> {
+        self.sweep_addresses(None, wallet_secret, payment_secret, abortable, notifier).await
+    }
+
+    /// Aggregate UTXOs belonging to `addresses` (or all account UTXOs if `None`) into
+    /// the change address. UTXOs that do not fit into a single transaction are compounded
+    /// over a chain of transactions, each within the maximum transaction mass.
+    async fn sweep_addresses(
+        self: Arc<Self>,
+        addresses: Option<Vec<Address>>,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        abortable: &Abortable,
+        notifier: Option<GenerationNotifier>,
     ) -> Result<(GeneratorSummary, Vec<kaspa_hashes::Hash>)> {
         let keydata = self.prv_key_data(wallet_secret).await?;
         let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
-        let settings =
-            GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), PaymentDestination::Change, Fees::None, None)?;
+        let settings = sweep_settings(self.clone().as_dyn_arc(), addresses)?;
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
         let mut stream = generator.stream();
@@ -329,6 +342,20 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok((generator.summary(), ids))
     }
 
+    /// Dry run of [`Account::sweep_addresses`]. The returned summary reports the number
+    /// of transactions and the total fees the sweep would require; nothing is signed or submitted.
+    async fn estimate_sweep(self: Arc<Self>, addresses: Option<Vec<Address>>, abortable: &Abortable) -> Result<GeneratorSummary> {
+        let settings = sweep_settings(self.as_dyn_arc(), addresses)?;
+        let generator = Generator::try_new(settings, None, Some(abortable))?;
+
+        let mut stream = generator.stream();
+        while let Some(_transaction) = stream.try_next().await? {
+            yield_executor().await;
+        }
+
+        Ok(generator.summary())
+    }
+
     /// Send funds to a [`PaymentDestination`] comprised of one or multiple [`PaymentOutputs`](crate::tx::PaymentOutputs)
     /// or [`PaymentDestination::Change`] variant that will forward funds to the change address.
     async fn send(
@@ -363,23 +390,36 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok((generator.summary(), ids))
     }
 
-    async fn pskb_from_send_generator(
+    /// Create a PSKB of unsigned transactions sending funds to a [`PaymentDestination`]. No key data is involved,
+    /// so watch-only accounts can create PSKBs to be signed offline with [`Account::pskb_sign`] by the holder
+    /// of the private keys, then broadcast with [`Account::pskb_broadcast`].
+    async fn pskb_create(
         self: Arc<Self>,
         destination: PaymentDestination,
         priority_fee_sompi: Fees,
         payload: Option<Vec<u8>>,
-        wallet_secret: Secret,
-        payment_secret: Option<Secret>,
         abortable: &Abortable,
     ) -> Result<Bundle, Error> {
         let settings = GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, priority_fee_sompi, payload)?;
-        let keydata = self.prv_key_data(wallet_secret).await?;
-        let signer = Arc::new(PSKBSigner::new(self.clone().as_dyn_arc(), keydata, payment_secret));
         let generator = Generator::try_new(settings, None, Some(abortable))?;
-        let pskt_generator = PSKTGenerator::new(generator, signer, self.wallet().address_prefix()?);
+        let pskt_generator = PSKTGenerator::new(generator, self.wallet().address_prefix()?);
         bundle_from_pskt_generator(pskt_generator).await
     }
 
+    /// Same as [`Account::pskb_create`], once checked that the secrets unlock the account key data.
+    async fn pskb_from_send_generator(
+        self: Arc<Self>,
+        destination: PaymentDestination,
+        priority_fee_sompi: Fees,
+        payload: Option<Vec<u8>>,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        abortable: &Abortable,
+    ) -> Result<Bundle, Error> {
+        self.prv_key_data(wallet_secret).await?.payload.decrypt(payment_secret.as_ref())?;
+        self.pskb_create(destination, priority_fee_sompi, payload, abortable).await
+    }
+
     async fn pskb_sign(
         self: Arc<Self>,
         bundle: &Bundle,
@@ -503,6 +543,19 @@ pub trait Account: AnySync + Send + Sync + 'static {
 
 downcast_sync!(dyn Account);
 
+/// Generator settings forwarding account UTXOs to the change address, optionally
+/// restricted to UTXOs of the given addresses.
+fn sweep_settings(account: Arc<dyn Account>, addresses: Option<Vec<Address>>) -> Result<GeneratorSettings> {
+    let mut settings = GeneratorSettings::try_new_with_account(account, PaymentDestination::Change, Fees::None, None)?;
+    if let Some(addresses) = addresses {
+        let addresses = addresses.into_iter().collect::<HashSet<_>>();
+        let utxo_iterator = std::mem::replace(&mut settings.utxo_iterator, Box::new(std::iter::empty()));
+        settings.utxo_iterator =
+            Box::new(utxo_iterator.filter(move |utxo| utxo.address().is_some_and(|address| addresses.contains(&address))));
+    }
+    Ok(settings)
+}
+
 /// Account trait used by legacy account types (BIP32 account types with the `'972` derivation path).
 #[async_trait]
 pub trait AsLegacyAccount: Account {
@@ -9,7 +9,7 @@ use crate::tx::PaymentOutputs;
 use futures::stream;
 use kaspa_bip32::{DerivationPath, KeyFingerprint, PrivateKey};
 use kaspa_consensus_client::UtxoEntry as ClientUTXO;
-use kaspa_consensus_core::hashing::sighash::{calc_schnorr_signature_hash, SigHashReusedValuesUnsync};
+use kaspa_consensus_core::hashing::sighash::{calc_schnorr_signature_hash_with_fork_id, SigHashReusedValuesUnsync};
 use kaspa_consensus_core::tx::VerifiableTransaction;
 use kaspa_consensus_core::tx::{TransactionInput, UtxoEntry};
 use kaspa_txscript::extract_script_pub_key_address;
@@ -79,32 +79,31 @@ impl PSKBSigner {
     }
 }
 
+/// Generates unsigned PSKTs from the transactions of a [`Generator`], requiring no key data
 pub struct PSKTGenerator {
     generator: Generator,
-    signer: Arc<PSKBSigner>,
     prefix: Prefix,
 }
 
 impl PSKTGenerator {
-    pub fn new(generator: Generator, signer: Arc<PSKBSigner>, prefix: Prefix) -> Self {
-        Self { generator, signer, prefix }
+    pub fn new(generator: Generator, prefix: Prefix) -> Self {
+        Self { generator, prefix }
     }
 
     pub fn stream(&self) -> impl Stream<Item = Result<PSKT<Signer>, Error>> {
-        PSKTStream::new(self.generator.clone(), self.signer.clone(), self.prefix)
+        PSKTStream::new(self.generator.clone(), self.prefix)
     }
 }
 
 struct PSKTStream {
     generator_stream: Pin<Box<dyn Stream<Item = Result<PendingTransaction, Error>> + Send>>,
-    signer: Arc<PSKBSigner>,
     prefix: Prefix,
 }
 
 impl PSKTStream {
-    fn new(generator: Generator, signer: Arc<PSKBSigner>, prefix: Prefix) -> Self {
+    fn new(generator: Generator, prefix: Prefix) -> Self {
         let generator_stream = generator.stream().map_err(Error::from);
-        Self { generator_stream: Box::pin(generator_stream), signer, prefix }
+        Self { generator_stream: Box::pin(generator_stream), prefix }
     }
 }
 
@@ -115,7 +114,6 @@ impl Stream for PSKTStream {
         let this = self.as_ref();
 
         let _prefix = this.prefix;
-        let _signer = this.signer.clone();
 
         match self.get_mut().generator_stream.as_mut().poll_next(cx) {
             Poll::Ready(Some(Ok(pending_tx))) => {
@@ -133,7 +131,8 @@ fn convert_pending_tx_to_pskt(pending_tx: PendingTransaction) -> Result<PSKT<Sig
     let signable_tx = pending_tx.signable_transaction();
     let verifiable_tx = signable_tx.as_verifiable();
     let populated_inputs: Vec<(&TransactionInput, &UtxoEntry)> = verifiable_tx.populated_inputs().collect();
-    let pskt_inner = Inner::try_from((pending_tx.transaction(), populated_inputs.to_owned()))?;
+    let mut pskt_inner = Inner::try_from((pending_tx.transaction(), populated_inputs.to_owned()))?;
+    pskt_inner.global.sighash_fork_id = pending_tx.generator().network_params().sighash_fork_id();
     Ok(PSKT::<Signer>::from(pskt_inner))
 }
 
@@ -185,6 +184,7 @@ pub async fn pskb_signer_for_address(
 
     for pskt_inner in bundle.iter().cloned() {
         let pskt: PSKT<Signer> = PSKT::from(pskt_inner);
+        let sighash_fork_id = pskt.global.sighash_fork_id;
 
         let sign = |signer_pskt: PSKT<Signer>| {
             signer_pskt
@@ -194,7 +194,13 @@ pub async fn pskb_signer_for_address(
                         .iter()
                         .enumerate()
                         .map(|(idx, _input)| {
-                            let hash = calc_schnorr_signature_hash(&tx.as_verifiable(), idx, sighash[idx], &reused_values);
+                            let hash = calc_schnorr_signature_hash_with_fork_id(
+                                &tx.as_verifiable(),
+                                idx,
+                                sighash[idx],
+                                &reused_values,
+                                sighash_fork_id,
+                            );
                             let msg = secp256k1::Message::from_digest_slice(hash.as_bytes().as_slice()).unwrap();
 
                             // When address represents a locked UTXO, no private key is available.
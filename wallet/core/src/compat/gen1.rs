@@ -1,23 +1,111 @@
 use crate::imports::*;
-use chacha20poly1305::{aead::AeadMut, Key, KeyInit};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, AeadCore, AeadMut, OsRng},
+    Key, KeyInit, XChaCha20Poly1305,
+};
+use workflow_store::fs;
+
+/// The version of the keys files written by the Go kaspawallet
+pub const KEYS_FILE_VERSION: u32 = 1;
+
+/// The number of argon2 threads used by version 1 keys files
+pub const KEYS_FILE_NUM_THREADS: u32 = 8;
+
+const SALT_SIZE: usize = 16;
+
+fn mnemonic_aead(num_threads: u32, salt: &[u8], pass: &[u8]) -> Result<XChaCha20Poly1305> {
+    let params = argon2::ParamsBuilder::new().t_cost(1).m_cost(64 * 1024).p_cost(num_threads).output_len(32).build()?;
+    let mut key = [0u8; 32];
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, Default::default(), params).hash_password_into(pass, salt, &mut key[..])?;
+    let aead = XChaCha20Poly1305::new(Key::from_slice(&key));
+    key.zeroize();
+    Ok(aead)
+}
 
 pub fn decrypt_mnemonic<T: AsRef<[u8]>>(
     num_threads: u32,
     EncryptedMnemonic { cipher, salt }: EncryptedMnemonic<T>,
     pass: &[u8],
 ) -> Result<String> {
-    let params = argon2::ParamsBuilder::new().t_cost(1).m_cost(64 * 1024).p_cost(num_threads).output_len(32).build().unwrap();
-    let mut key = [0u8; 32];
-    argon2::Argon2::new(argon2::Algorithm::Argon2id, Default::default(), params)
-        .hash_password_into(pass, salt.as_ref(), &mut key[..])
-        .unwrap();
-    let mut aead = chacha20poly1305::XChaCha20Poly1305::new(Key::from_slice(&key));
+    let mut aead = mnemonic_aead(num_threads, salt.as_ref(), pass)?;
     let (nonce, ciphertext) = cipher.as_ref().split_at(24);
 
     let decrypted = aead.decrypt(nonce.into(), ciphertext)?;
     Ok(unsafe { String::from_utf8_unchecked(decrypted) })
 }
 
+/// Encrypts a mnemonic the way the Go kaspawallet does, the nonce being prepended to the cipher
+pub fn encrypt_mnemonic(num_threads: u32, mnemonic: &str, pass: &[u8]) -> Result<EncryptedMnemonic<Vec<u8>>> {
+    let mut salt = vec![0u8; SALT_SIZE];
+    OsRng.fill_bytes(&mut salt);
+    let mut aead = mnemonic_aead(num_threads, &salt, pass)?;
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let mut cipher = nonce.to_vec();
+    cipher.extend(aead.encrypt(&nonce, mnemonic.as_bytes())?);
+    Ok(EncryptedMnemonic { cipher, salt })
+}
+
+/// A mnemonic encrypted by the Go kaspawallet, hex-encoded in keys files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeysFileEncryptedMnemonic {
+    #[serde(with = "kaspa_utils::serde_bytes")]
+    pub cipher: Vec<u8>,
+    #[serde(with = "kaspa_utils::serde_bytes")]
+    pub salt: Vec<u8>,
+}
+
+impl KeysFileEncryptedMnemonic {
+    pub fn as_encrypted_mnemonic(&self) -> EncryptedMnemonic<&[u8]> {
+        EncryptedMnemonic { cipher: self.cipher.as_slice(), salt: self.salt.as_slice() }
+    }
+}
+
+impl From<EncryptedMnemonic<Vec<u8>>> for KeysFileEncryptedMnemonic {
+    fn from(EncryptedMnemonic { cipher, salt }: EncryptedMnemonic<Vec<u8>>) -> Self {
+        Self { cipher, salt }
+    }
+}
+
+/// The `keys.json` file of the Go kaspawallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeysFile {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_threads: Option<u8>,
+    pub encrypted_mnemonics: Vec<KeysFileEncryptedMnemonic>,
+    pub public_keys: Vec<String>,
+    pub minimum_signatures: u32,
+    pub cosigner_index: u32,
+    pub last_used_external_index: u32,
+    pub last_used_internal_index: u32,
+    pub ecdsa: bool,
+}
+
+impl KeysFile {
+    pub async fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Ok(fs::read_json(path.as_ref()).await?)
+    }
+
+    pub async fn store(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        Ok(fs::write_json(path.as_ref(), self).await?)
+    }
+
+    /// Whether the file describes a single signature wallet, as opposed to a multisig one
+    pub fn is_single(&self) -> bool {
+        self.encrypted_mnemonics.len() == 1 && self.public_keys.len() == 1
+    }
+
+    /// The number of argon2 threads the mnemonics were encrypted with
+    pub fn num_threads(&self) -> Result<u32> {
+        match self.version {
+            0 => self.num_threads.map(u32::from).ok_or_else(|| Error::Custom("version 0 keys file has no numThreads".to_owned())),
+            _ => Ok(KEYS_FILE_NUM_THREADS),
+        }
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
 mod test {
@@ -130,6 +218,97 @@ mod test {
         );
     }
 
+    #[test]
+    fn encrypt_go_mnemonics_test() {
+        let mnemonic = "dizzy uncover funny time weapon chat volume squirrel comic motion until diamond response remind hurt spider door strategy entire oyster hawk marriage soon fabric";
+        let encrypted = encrypt_mnemonic(KEYS_FILE_NUM_THREADS, mnemonic, b"password").unwrap();
+        assert_eq!(encrypted.salt.len(), SALT_SIZE);
+        assert!(decrypt_mnemonic(
+            KEYS_FILE_NUM_THREADS,
+            EncryptedMnemonic { cipher: &encrypted.cipher, salt: &encrypted.salt },
+            b"wrong"
+        )
+        .is_err());
+        assert_eq!(decrypt_mnemonic(KEYS_FILE_NUM_THREADS, encrypted, b"password").unwrap(), mnemonic);
+    }
+
+    #[test]
+    fn keys_file_json_test() {
+        let json = r#"{"version":1,"encryptedMnemonics":[{"cipher":"2022041df1a5bdcc26445952c53f96518641118bf0f990a01747d631d4607e5b53af3c9f4c07d6e3b84bc766445191b13d1f1fdf7ac96eae9c8859a9add660ac15b938356f936fdf614640d89627d368c57b22cf62844b1e1bcf3feceecbc6bf655df9519d7e3cfede6fe19d87a49e5709211b0b95c8d68781c70c4722bd8e25361492ef38d5cca21664a7f0838e4a1e2994d30c6d4b81d1397169570375ce56608439ae00e84c1f6acdd805f0ee22d4ba7b354c7f7cd4b2d18ce4fd6b8af785f95ed2a69361f318bc","salt":"044f5b890e48af4a7dcd7e7766af9380"}],"publicKeys":["kpub2KUE88roSn5peP1rEZnbRuKYw1fEPbhqBoXVWW7mLfkrLvQBAjUqwx7m1ezeSfqfecv9RUYePuHf99iW51i31WjwWjnzKDCUcTucBSiBbJA"],"minimumSignatures":1,"cosignerIndex":0,"lastUsedExternalIndex":0,"lastUsedInternalIndex":0,"ecdsa":false}"#;
+        let keys_file: KeysFile = serde_json::from_str(json).unwrap();
+        assert!(keys_file.is_single());
+        assert_eq!(keys_file.num_threads().unwrap(), KEYS_FILE_NUM_THREADS);
+        assert_eq!(serde_json::to_string(&keys_file).unwrap(), json);
+
+        let decrypted = decrypt_mnemonic(KEYS_FILE_NUM_THREADS, keys_file.encrypted_mnemonics[0].as_encrypted_mnemonic(), b"");
+        assert!(decrypted.unwrap().starts_with("dizzy uncover"));
+    }
+
+    async fn create_test_wallet() -> Arc<Wallet> {
+        let resident_store = Wallet::resident_store().unwrap();
+        let wallet = Arc::new(Wallet::try_new(resident_store, None, Some(NetworkId::new(NetworkType::Mainnet))).unwrap());
+        wallet
+            .create_wallet(
+                &Secret::new(vec![]),
+                WalletCreateArgs {
+                    title: None,
+                    filename: None,
+                    encryption_kind: EncryptionKind::XChaCha20Poly1305,
+                    user_hint: None,
+                    overwrite_wallet_storage: false,
+                },
+            )
+            .await
+            .unwrap();
+        wallet
+    }
+
+    #[tokio::test]
+    async fn export_golang_keys_file_test() {
+        let wallet = create_test_wallet().await;
+        let wallet_secret = Secret::new(vec![]);
+        let export_secret = Secret::new(b"password".to_vec());
+
+        let file = MultisigWalletFileV1 {
+            encrypted_mnemonics: vec![
+                EncryptedMnemonic {
+                    cipher: hex!("f587dbc539b5303605e7065f4a473caffc91d5992dc0c4ec0b111e5362aa089c6ed034d4165697c13776777fa6a9396b0396515f75fa8fa34d13a3abdbf126bf8575be389177998c77170f3dba80c18d7cb5e223802cd4df51584ea280c08f31a8ecccca31000f4ebd78d584ba95ad2424b57a2945c60a7a36174bf69ecf251c141f01644aeb10268f3321bc2114a24da8ab8983540224e494634889a48f846ceea4238869d1e397f041f5594c53453ea63606a4bb50").as_slice(),
+                    salt: hex!("04fb57493be318c3bb1cddb6dde05e09").as_slice(),
+                },
+                EncryptedMnemonic {
+                    cipher: hex!("2244d1b757e635cec13347d8b6d57c446063b9b72f54c425055eefd983c11cd4d75b0303e47848b5df29991056769c109cad73844fcc4de3d68122fdc09ec31a9e26334cb65141de1fb74718fd44e1d7312eaf975871833026569f06624f02ea79ba189e2db8cbfc4a1ada7fc4801179fb9b838618418043a335e8e01ab9dc8b6b8a1aa963a827a7914bab0815337d3955e5d2a4fc2df738506d5eb537ca7c52c690106bde9d2b686949a2e651099311796df3698499e8606cdbdc9963fc9172b12b").as_slice(),
+                    salt: hex!("60405c5b3a180e4fdebd5a6d5c51bf76").as_slice(),
+                },
+            ],
+            xpublic_keys: vec![
+                "kpub2J937qL9n85s7HrhYyYYdMkzq1kaMiAf9PAcJzRW3jV7NgntNfGGrNgut7ZxcVrJqH42BCT2WyjfnxJh3SBDjLhXHe3UC2RJUu5tcjsViuK",
+                "kpub2Jtuqt6WJWZv3fQUnKhuEaCxbAyzLsFn3UEEaM4g7CXa2LZjQZH4o6tpj83tFaewMEyX56qrAF4Q64uqunVyBayuuRNwjru5DWchDEcq5vz",
+                "kpub2JZg9pofE54nqvkhFRRx18pAMhYDPL2CpYqBx2AkzvsEknCh8V4rtez9ZYeab3HCW1Xsm9f4d6J5dfJVg9NADWN7rtqNft21batcii1SjXy",
+                "kpub2HuRXjAmhs3KwQ9WpHVaiHRjBP37TQUiUGFQBTwp7cdbArCo5s2MT6415nd3ZYaELvNbZ4qTJjCGTavExv514tWftaGQzCK8gQz6BQJNySp",
+                "kpub2KCvcuKVgfy1h7PvCw4xFcdLAPoerVZBG4qTo8vRGH2Qe6p5AgLyRek5CEnuCDkduXHqgwtvaVfYYBS7gQBR1J4XowdvqvPXsHZGA5WyRJF",
+            ],
+            required_signatures: 2,
+            cosigner_index: 1,
+            ecdsa: false,
+        };
+        let mut public_keys = file.xpublic_keys.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+        public_keys.sort_unstable();
+        let account = wallet.import_kaspawallet_golang_multisig_v1(&Secret::new(vec![]), &wallet_secret, file).await.unwrap();
+
+        let keys_file = wallet.export_kaspawallet_golang_keys_file(&wallet_secret, None, &export_secret, account.id()).await.unwrap();
+        assert_eq!(keys_file.version, KEYS_FILE_VERSION);
+        assert_eq!(keys_file.encrypted_mnemonics.len(), 2);
+        assert_eq!(keys_file.public_keys, public_keys);
+        assert_eq!(keys_file.minimum_signatures, 2);
+        assert_eq!(keys_file.cosigner_index, 1);
+
+        // the exported file imports back into the same account
+        let keys_file: KeysFile = serde_json::from_str(&serde_json::to_string(&keys_file).unwrap()).unwrap();
+        let imported =
+            create_test_wallet().await.import_kaspawallet_golang_keys_file(&export_secret, &wallet_secret, &keys_file).await.unwrap();
+        assert_eq!(imported.receive_address().unwrap(), account.receive_address().unwrap());
+    }
+
     #[test]
     fn deser_golang_wallet_test() {
         #[allow(dead_code)]
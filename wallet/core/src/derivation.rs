@@ -14,7 +14,9 @@ use crate::account::AccountKind;
 use crate::error::Error;
 use crate::imports::*;
 use crate::result::Result;
-use kaspa_bip32::{AddressType, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey, Language, Mnemonic, SecretKeyExt};
+use kaspa_bip32::{
+    AddressType, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey, Language, Mnemonic, Prefix as KeyPrefix, SecretKeyExt,
+};
 use kaspa_consensus_core::network::{NetworkType, NetworkTypeT};
 use kaspa_txscript::{
     extract_script_pub_key_address, multisig_redeem_script, multisig_redeem_script_ecdsa, pay_to_script_hash_script,
@@ -455,6 +457,59 @@ pub fn create_multisig_address(
     Ok(address)
 }
 
+/// Parses the extended public keys of a multisig account, whatever their prefixes (`xpub`, `kpub`, ...),
+/// and sorts them in their canonical order
+pub fn parse_multisig_xpub_keys<S: AsRef<str>>(xpub_keys: &[S]) -> Result<Vec<ExtendedPublicKeySecp256k1>> {
+    let mut xpub_keys = xpub_keys
+        .iter()
+        .map(|xpub_key| {
+            let xpub_key = xpub_key.as_ref();
+            ExtendedPublicKeySecp256k1::from_str(xpub_key).map_err(|err| Error::InvalidExtendedPublicKey(xpub_key.to_string(), err))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    sort_multisig_xpub_keys(&mut xpub_keys);
+    Ok(xpub_keys)
+}
+
+/// Sorts the extended public keys of a multisig account in their canonical order, so that all cosigners
+/// agree on the key order, and hence on the addresses, regardless of the order in which the keys were exchanged.
+///
+/// Keys are ordered by their `xpub` serialization, which keeps the order of accounts created before the
+/// keys were normalized.
+pub fn sort_multisig_xpub_keys(xpub_keys: &mut [ExtendedPublicKeySecp256k1]) {
+    xpub_keys.sort_by_cached_key(|xpub_key| xpub_key.to_string(Some(KeyPrefix::XPUB)));
+}
+
+/// Returns the cosigner index of `xpub_key`, that is its position among the canonically sorted `xpub_keys`
+pub fn multisig_cosigner_index(xpub_keys: &[ExtendedPublicKeySecp256k1], xpub_key: &ExtendedPublicKeySecp256k1) -> Option<u8> {
+    let xpub_key = xpub_key.to_string(Some(KeyPrefix::XPUB));
+    let mut xpub_keys = xpub_keys.iter().map(|xpub_key| xpub_key.to_string(Some(KeyPrefix::XPUB))).collect::<Vec<_>>();
+    xpub_keys.sort_unstable();
+    xpub_keys.binary_search(&xpub_key).ok().and_then(|index| u8::try_from(index).ok())
+}
+
+/// Derives the multisig address at `index` of the `cosigner_index` branch, from the extended public keys of all cosigners
+pub fn derive_multisig_address(
+    xpub_keys: &[ExtendedPublicKeySecp256k1],
+    cosigner_index: u8,
+    address_type: AddressType,
+    index: u32,
+    minimum_signatures: usize,
+    prefix: Prefix,
+    ecdsa: bool,
+) -> Result<Address> {
+    let mut xpub_keys = xpub_keys.to_vec();
+    sort_multisig_xpub_keys(&mut xpub_keys);
+    let keys = xpub_keys
+        .into_iter()
+        .map(|xpub_key| {
+            WalletDerivationManager::derive_child_pubkey_manager(xpub_key, address_type, Some(cosigner_index as u32))?
+                .derive_pubkey(index)
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    create_multisig_address(minimum_signatures, keys, prefix, ecdsa)
+}
+
 /// @category Wallet SDK
 #[wasm_bindgen(js_name=createAddress)]
 pub fn create_address_js(
@@ -572,3 +627,78 @@ pub fn build_derivate_paths(
     let change_path = build_derivate_path(account_kind, account_index, cosigner_index, AddressType::Change)?;
     Ok((receive_path, change_path))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use kaspa_bip32::ChildNumber;
+
+    const PERMUTATIONS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+    fn make_cosigner_xpubs() -> Vec<ExtendedPublicKeySecp256k1> {
+        (0..3).map(|index| make_xpub().derive_child(ChildNumber::new(index, false).unwrap()).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_multisig_address_agreement() -> Result<()> {
+        let cosigners = make_cosigner_xpubs();
+        let canonical = parse_multisig_xpub_keys(&cosigners.iter().map(|xpub| xpub.to_string(None)).collect::<Vec<_>>())?;
+
+        for (n, permutation) in PERMUTATIONS.iter().enumerate() {
+            // Each participant may exchange the keys in any order and with any prefix
+            let xpub_keys = permutation
+                .iter()
+                .enumerate()
+                .map(|(i, &cosigner)| {
+                    let prefix = if (n + i) % 2 == 0 { KeyPrefix::KPUB } else { KeyPrefix::XPUB };
+                    cosigners[cosigner].to_string(Some(prefix))
+                })
+                .collect::<Vec<_>>();
+            let xpub_keys = parse_multisig_xpub_keys(&xpub_keys)?;
+            assert_eq!(xpub_keys, canonical);
+
+            for cosigner in cosigners.iter() {
+                assert_eq!(multisig_cosigner_index(&xpub_keys, cosigner), multisig_cosigner_index(&canonical, cosigner));
+            }
+
+            let unsorted = permutation.iter().map(|&cosigner| cosigners[cosigner].clone()).collect::<Vec<_>>();
+            for cosigner_index in 0..3 {
+                for address_type in [AddressType::Receive, AddressType::Change] {
+                    for index in 0..3 {
+                        let address =
+                            derive_multisig_address(&unsorted, cosigner_index, address_type, index, 2, Prefix::Mainnet, false)?;
+                        let expected =
+                            derive_multisig_address(&canonical, cosigner_index, address_type, index, 2, Prefix::Mainnet, false)?;
+                        assert_eq!(address, expected);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multisig_cosigner_index() -> Result<()> {
+        let cosigners = make_cosigner_xpubs();
+        let mut xpub_keys = cosigners.clone();
+        sort_multisig_xpub_keys(&mut xpub_keys);
+
+        let mut indexes = cosigners.iter().map(|cosigner| multisig_cosigner_index(&xpub_keys, cosigner).unwrap()).collect::<Vec<_>>();
+        for (cosigner, &index) in cosigners.iter().zip(indexes.iter()) {
+            assert_eq!(&xpub_keys[index as usize], cosigner);
+        }
+        indexes.sort_unstable();
+        assert_eq!(indexes, vec![0, 1, 2]);
+
+        assert_eq!(multisig_cosigner_index(&xpub_keys[..2], &xpub_keys[2]), None);
+
+        // Distinct cosigner branches lead to distinct addresses
+        let first = derive_multisig_address(&xpub_keys, 0, AddressType::Receive, 0, 2, Prefix::Mainnet, false)?;
+        let second = derive_multisig_address(&xpub_keys, 1, AddressType::Receive, 0, 2, Prefix::Mainnet, false)?;
+        assert_ne!(first, second);
+
+        Ok(())
+    }
+}
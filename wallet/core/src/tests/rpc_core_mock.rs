@@ -25,6 +25,7 @@ pub struct RpcCoreMock {
     ctl: RpcCtl,
     core_notifier: Arc<RpcCoreNotifier>,
     _sync_receiver: Receiver<()>,
+    blocks: Mutex<AHashMap<RpcHash, RpcBlock>>,
 }
 
 impl RpcCoreMock {
@@ -41,7 +42,12 @@ impl RpcCoreMock {
             policies,
             Some(sync_sender),
         ));
-        Self { core_notifier, _sync_receiver: sync_receiver, ctl: RpcCtl::new() }
+        Self { core_notifier, _sync_receiver: sync_receiver, ctl: RpcCtl::new(), blocks: Default::default() }
+    }
+
+    /// Makes `block` available to `get_block` requests
+    pub fn add_block(&self, block: RpcBlock) {
+        self.blocks.lock().unwrap().insert(block.header.hash, block);
     }
 
     pub fn core_notifier(&self) -> Arc<RpcCoreNotifier> {
@@ -92,6 +98,9 @@ impl RpcApi for RpcCoreMock {
             is_synced: false,
             has_notify_command: false,
             has_message_id: false,
+            network_id: None,
+            unsafe_rpc: false,
+            cache_policy: None,
         })
     }
 
@@ -139,6 +148,102 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_p2p_stats_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetP2pStatsRequest,
+    ) -> RpcResult<GetP2pStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_processing_stats_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetProcessingStatsRequest,
+    ) -> RpcResult<GetProcessingStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_peer_flow_states_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetPeerFlowStatesRequest,
+    ) -> RpcResult<GetPeerFlowStatesResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn disconnect_peer_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: DisconnectPeerRequest,
+    ) -> RpcResult<DisconnectPeerResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn set_peer_protected_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SetPeerProtectedRequest,
+    ) -> RpcResult<SetPeerProtectedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_acceptance_data_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionAcceptanceDataRequest,
+    ) -> RpcResult<GetTransactionAcceptanceDataResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_chain_block_by_score_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetChainBlockByScoreRequest,
+    ) -> RpcResult<GetChainBlockByScoreResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_serialized_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetBlockSerializedRequest,
+    ) -> RpcResult<GetBlockSerializedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn submit_block_serialized_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SubmitBlockSerializedRequest,
+    ) -> RpcResult<SubmitBlockSerializedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_serialized_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionSerializedRequest,
+    ) -> RpcResult<GetTransactionSerializedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn submit_transaction_serialized_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SubmitTransactionSerializedRequest,
+    ) -> RpcResult<SubmitTransactionSerializedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_local_transactions_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetLocalTransactionsRequest,
+    ) -> RpcResult<GetLocalTransactionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn get_current_network_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -219,8 +324,9 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
-    async fn get_block_call(&self, _connection: Option<&DynRpcConnection>, _request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
-        Err(RpcError::NotImplemented)
+    async fn get_block_call(&self, _connection: Option<&DynRpcConnection>, request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
+        let block = self.blocks.lock().unwrap().get(&request.hash).cloned();
+        block.map(|block| GetBlockResponse { block }).ok_or_else(|| RpcError::General(format!("block {} not found", request.hash)))
     }
 
     async fn get_subnetwork_call(
@@ -9,7 +9,9 @@ use crate::rpc::DynRpcApi;
 use crate::tx::{DataKind, Generator};
 use crate::utxo::{UtxoContext, UtxoEntryId, UtxoEntryReference};
 use kaspa_consensus_core::hashing::sighash_type::SigHashType;
-use kaspa_consensus_core::sign::{sign_input, sign_with_multiple_v2, Signed};
+use kaspa_consensus_core::sign::{
+    sign_input_with_fork_id, sign_with_multiple_v2_with_fork_id, sign_with_signer, Signed, Signer as TxSigner,
+};
 use kaspa_consensus_core::tx::{SignableTransaction, Transaction, TransactionId};
 use kaspa_rpc_core::{RpcTransaction, RpcTransactionId};
 
@@ -241,9 +243,14 @@ impl PendingTransaction {
         Ok(())
     }
 
+    /// Sighash fork id of the network the transaction is signed for
+    fn sighash_fork_id(&self) -> Option<u8> {
+        self.inner.generator.network_params().sighash_fork_id()
+    }
+
     pub fn try_sign(&self) -> Result<()> {
         let signer = self.inner.generator.signer().as_ref().expect("no signer in tx generator");
-        let signed_tx = signer.try_sign(self.inner.signable_tx.lock()?.clone(), self.addresses())?;
+        let signed_tx = signer.try_sign(self.inner.signable_tx.lock()?.clone(), self.addresses(), self.sighash_fork_id())?;
         *self.inner.signable_tx.lock().unwrap() = signed_tx;
         Ok(())
     }
@@ -252,12 +259,12 @@ impl PendingTransaction {
         let mutable_tx = self.inner.signable_tx.lock()?.clone();
         let verifiable_tx = mutable_tx.as_verifiable();
 
-        Ok(sign_input(&verifiable_tx, input_index, private_key, hash_type))
+        Ok(sign_input_with_fork_id(&verifiable_tx, input_index, private_key, hash_type, self.sighash_fork_id()))
     }
 
     pub fn fill_input(&self, input_index: usize, signature_script: Vec<u8>) -> Result<()> {
         let mut mutable_tx = self.inner.signable_tx.lock()?.clone();
-        mutable_tx.tx.inputs[input_index].signature_script = signature_script;
+        mutable_tx.tx.set_signature_script(input_index, signature_script);
         *self.inner.signable_tx.lock().unwrap() = mutable_tx;
 
         Ok(())
@@ -268,10 +275,10 @@ impl PendingTransaction {
 
         let signature_script = {
             let verifiable_tx = &mutable_tx.as_verifiable();
-            sign_input(verifiable_tx, input_index, private_key, hash_type)
+            sign_input_with_fork_id(verifiable_tx, input_index, private_key, hash_type, self.sighash_fork_id())
         };
 
-        mutable_tx.tx.inputs[input_index].signature_script = signature_script;
+        mutable_tx.tx.set_signature_script(input_index, signature_script);
         *self.inner.signable_tx.lock().unwrap() = mutable_tx;
 
         Ok(())
@@ -279,7 +286,7 @@ impl PendingTransaction {
 
     pub fn try_sign_with_keys(&self, privkeys: &[[u8; 32]], check_fully_signed: Option<bool>) -> Result<()> {
         let mutable_tx = self.inner.signable_tx.lock()?.clone();
-        let signed = sign_with_multiple_v2(mutable_tx, privkeys);
+        let signed = sign_with_multiple_v2_with_fork_id(mutable_tx, privkeys, self.sighash_fork_id());
 
         let signed_tx = match signed {
             Signed::Fully(tx) => tx,
@@ -295,4 +302,17 @@ impl PendingTransaction {
         *self.inner.signable_tx.lock().unwrap() = signed_tx;
         Ok(())
     }
+
+    /// Sign the transaction using an external [`Signer`](kaspa_consensus_core::sign::Signer)
+    /// such as a hardware wallet. Fails if the transaction remains partially signed
+    /// unless `check_fully_signed` is `Some(false)`.
+    pub async fn try_sign_with_signer(&self, signer: &dyn TxSigner, check_fully_signed: Option<bool>) -> Result<()> {
+        let mutable_tx = self.inner.signable_tx.lock()?.clone();
+        let signed = sign_with_signer(mutable_tx, signer, self.sighash_fork_id()).await?;
+
+        let signed_tx = if check_fully_signed.unwrap_or(true) { signed.fully_signed()? } else { signed.unwrap() };
+
+        *self.inner.signable_tx.lock().unwrap() = signed_tx;
+        Ok(())
+    }
 }
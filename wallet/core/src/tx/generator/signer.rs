@@ -4,10 +4,11 @@
 
 use crate::imports::*;
 use kaspa_bip32::PrivateKey;
-use kaspa_consensus_core::{sign::sign_with_multiple_v2, tx::SignableTransaction};
+use kaspa_consensus_core::{sign::sign_with_multiple_v2_with_fork_id, tx::SignableTransaction};
 
 pub trait SignerT: Send + Sync + 'static {
-    fn try_sign(&self, transaction: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction>;
+    /// Signs the inputs spending `addresses`, committing the signatures to the sighash fork id of the network
+    fn try_sign(&self, transaction: SignableTransaction, addresses: &[Address], fork_id: Option<u8>) -> Result<SignableTransaction>;
 }
 
 struct Inner {
@@ -44,13 +45,13 @@ impl Signer {
 }
 
 impl SignerT for Signer {
-    fn try_sign(&self, mutable_tx: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction> {
+    fn try_sign(&self, mutable_tx: SignableTransaction, addresses: &[Address], fork_id: Option<u8>) -> Result<SignableTransaction> {
         self.ingest(addresses)?;
 
         let keys = self.inner.keys.lock().unwrap();
         let mut keys_for_signing = addresses.iter().map(|address| *keys.get(address).unwrap()).collect::<Vec<_>>();
         // TODO - refactor for multisig
-        let signable_tx = sign_with_multiple_v2(mutable_tx, &keys_for_signing).fully_signed()?;
+        let signable_tx = sign_with_multiple_v2_with_fork_id(mutable_tx, &keys_for_signing, fork_id).fully_signed()?;
         keys_for_signing.zeroize();
         Ok(signable_tx)
     }
@@ -74,10 +75,10 @@ impl KeydataSigner {
 }
 
 impl SignerT for KeydataSigner {
-    fn try_sign(&self, mutable_tx: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction> {
+    fn try_sign(&self, mutable_tx: SignableTransaction, addresses: &[Address], fork_id: Option<u8>) -> Result<SignableTransaction> {
         let mut keys_for_signing = addresses.iter().map(|address| *self.inner.keys.get(address).unwrap()).collect::<Vec<_>>();
         // TODO - refactor for multisig
-        let signable_tx = sign_with_multiple_v2(mutable_tx, &keys_for_signing).fully_signed()?;
+        let signable_tx = sign_with_multiple_v2_with_fork_id(mutable_tx, &keys_for_signing, fork_id).fully_signed()?;
         keys_for_signing.zeroize();
         Ok(signable_tx)
     }
@@ -0,0 +1,478 @@
+//!
+//! Implements [`TransactionTracker`], which follows submitted transactions
+//! through their lifecycle (mempool, acceptance by a chain block, confirmation,
+//! eviction or replacement) and reports each change as a [`TrackerEvent`].
+//!
+//! Acceptance is detected using `VirtualChainChanged` notifications, confirmations
+//! are counted in DAA score from the accepting block using `VirtualDaaScoreChanged`
+//! notifications. Transactions that are not yet accepted are periodically looked up
+//! in the node mempool and are reported as evicted if they disappear without being
+//! accepted.
+//!
+
+use crate::imports::*;
+use kaspa_consensus_core::tx::Transaction;
+use kaspa_hashes::Hash;
+use kaspa_notify::{
+    listener::ListenerId,
+    scope::{Scope, VirtualChainChangedScope, VirtualDaaScoreChangedScope},
+};
+use kaspa_rpc_core::{
+    message::VirtualChainChangedNotification,
+    notify::connection::{ChannelConnection, ChannelType},
+    Notification, RpcTransaction,
+};
+use workflow_core::channel::{Channel, DuplexChannel};
+use workflow_core::task::spawn;
+
+/// Default number of DAA score units a transaction needs to be
+/// accepted for before it is reported as [`TrackerEvent::Confirmed`].
+pub const DEFAULT_CONFIRMATION_DEPTH: u64 = 100;
+/// Interval (in DAA score units) between mempool lookups of unaccepted transactions.
+pub const MEMPOOL_CHECK_INTERVAL: u64 = 10;
+/// Number of consecutive failed mempool lookups after which
+/// an unaccepted transaction is reported as evicted.
+const MEMPOOL_CHECK_EVICTION_THRESHOLD: usize = 2;
+
+/// Lifecycle events produced by the [`TransactionTracker`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "kebab-case")]
+pub enum TrackerEvent {
+    /// Transaction has been accepted into the mempool and is now tracked.
+    #[serde(rename_all = "camelCase")]
+    Mempool { id: TransactionId },
+    /// Transaction has been accepted by a block of the virtual selected chain.
+    #[serde(rename_all = "camelCase")]
+    Accepted { id: TransactionId, accepting_block_hash: Hash, accepting_daa_score: u64 },
+    /// The accepting block has been removed from the selected chain
+    /// by a reorg; the transaction is pending acceptance again.
+    #[serde(rename_all = "camelCase")]
+    Unaccepted { id: TransactionId },
+    /// Transaction has reached the configured confirmation depth. Tracking stops.
+    #[serde(rename_all = "camelCase")]
+    Confirmed { id: TransactionId, confirmations: u64 },
+    /// Transaction has left the mempool without being accepted. Tracking stops.
+    #[serde(rename_all = "camelCase")]
+    Evicted { id: TransactionId },
+    /// Transaction has been replaced in the mempool by `replacement`. Tracking
+    /// stops for `id` and continues for `replacement`.
+    #[serde(rename_all = "camelCase")]
+    Replaced { id: TransactionId, replacement: TransactionId },
+}
+
+#[derive(Clone, Copy, Debug)]
+enum TrackedState {
+    Mempool { failed_lookups: usize },
+    Accepted { accepting_block_hash: Hash, accepting_daa_score: u64 },
+}
+
+struct Inner {
+    rpc: Arc<DynRpcApi>,
+    confirmation_depth: u64,
+    transactions: Mutex<AHashMap<TransactionId, TrackedState>>,
+    current_daa_score: AtomicU64,
+    last_mempool_check_daa_score: AtomicU64,
+    listener_id: Mutex<Option<ListenerId>>,
+    notification_channel: Channel<Notification>,
+    task_ctl: DuplexChannel,
+    task_is_running: AtomicBool,
+    multiplexer: Multiplexer<Box<TrackerEvent>>,
+}
+
+/// Tracks the lifecycle of submitted transactions.
+///
+/// Transactions are registered with [`TransactionTracker::submit`],
+/// [`TransactionTracker::submit_replacement`] or [`TransactionTracker::track`]
+/// (for transactions submitted by other means). Events are delivered
+/// via the [`Multiplexer`] returned by [`TransactionTracker::multiplexer`].
+#[derive(Clone)]
+pub struct TransactionTracker {
+    inner: Arc<Inner>,
+}
+
+impl TransactionTracker {
+    /// Create a new tracker reporting transactions as confirmed once they
+    /// have been accepted for `confirmation_depth` DAA score units.
+    pub fn new(rpc: Arc<DynRpcApi>, confirmation_depth: u64) -> Self {
+        let inner = Inner {
+            rpc,
+            confirmation_depth,
+            transactions: Mutex::new(AHashMap::default()),
+            current_daa_score: AtomicU64::new(0),
+            last_mempool_check_daa_score: AtomicU64::new(0),
+            listener_id: Mutex::new(None),
+            notification_channel: Channel::unbounded(),
+            task_ctl: DuplexChannel::oneshot(),
+            task_is_running: AtomicBool::new(false),
+            multiplexer: Multiplexer::new(),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    pub fn multiplexer(&self) -> &Multiplexer<Box<TrackerEvent>> {
+        &self.inner.multiplexer
+    }
+
+    pub fn confirmation_depth(&self) -> u64 {
+        self.inner.confirmation_depth
+    }
+
+    /// Returns `true` if the transaction is currently tracked.
+    pub fn is_tracked(&self, id: &TransactionId) -> bool {
+        self.inner.transactions.lock().unwrap().contains_key(id)
+    }
+
+    /// Ids of all currently tracked transactions.
+    pub fn tracked(&self) -> Vec<TransactionId> {
+        self.inner.transactions.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Submit a transaction to the node and start tracking it.
+    pub async fn submit(&self, transaction: RpcTransaction) -> Result<TransactionId> {
+        let id = self.inner.rpc.submit_transaction(transaction, false).await?;
+        self.track(id)?;
+        Ok(id)
+    }
+
+    /// Submit a transaction replacing a conflicting mempool transaction and
+    /// start tracking it. If the replaced transaction was tracked, a
+    /// [`TrackerEvent::Replaced`] event is produced for it.
+    pub async fn submit_replacement(&self, transaction: RpcTransaction) -> Result<TransactionId> {
+        let response = self.inner.rpc.submit_transaction_replacement(transaction).await?;
+        let replacement = response.transaction_id;
+        let replaced = Transaction::try_from(response.replaced_transaction)?.id();
+        if self.inner.transactions.lock().unwrap().remove(&replaced).is_some() {
+            self.notify(TrackerEvent::Replaced { id: replaced, replacement })?;
+        }
+        self.track(replacement)?;
+        Ok(replacement)
+    }
+
+    /// Start tracking a transaction that has already been submitted to the mempool.
+    pub fn track(&self, id: TransactionId) -> Result<()> {
+        self.inner.transactions.lock().unwrap().insert(id, TrackedState::Mempool { failed_lookups: 0 });
+        self.notify(TrackerEvent::Mempool { id })
+    }
+
+    /// Stop tracking a transaction without producing any events.
+    pub fn untrack(&self, id: &TransactionId) -> bool {
+        self.inner.transactions.lock().unwrap().remove(id).is_some()
+    }
+
+    fn notify(&self, event: TrackerEvent) -> Result<()> {
+        self.inner
+            .multiplexer
+            .try_broadcast(Box::new(event))
+            .map_err(|_| Error::Custom("multiplexer channel error during transaction tracker notify".to_string()))
+    }
+
+    pub async fn handle_virtual_chain_changed(&self, notification: &VirtualChainChangedNotification) -> Result<()> {
+        let mut events = vec![];
+        let accepted = {
+            let mut transactions = self.inner.transactions.lock().unwrap();
+            if transactions.is_empty() {
+                return Ok(());
+            }
+
+            // revert acceptance by blocks that are no longer on the selected chain
+            let removed = notification.removed_chain_block_hashes.iter().collect::<AHashSet<_>>();
+            for (id, state) in transactions.iter_mut() {
+                if let TrackedState::Accepted { accepting_block_hash, .. } = state {
+                    if removed.contains(accepting_block_hash) {
+                        *state = TrackedState::Mempool { failed_lookups: 0 };
+                        events.push(TrackerEvent::Unaccepted { id: *id });
+                    }
+                }
+            }
+
+            notification
+                .accepted_transaction_ids
+                .iter()
+                .filter_map(|accepted| {
+                    let ids = accepted
+                        .accepted_transaction_ids
+                        .iter()
+                        .filter(|id| transactions.contains_key(id))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    (!ids.is_empty()).then_some((accepted.accepting_block_hash, ids))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // confirmations are counted from the DAA score of the accepting block, which
+        // is fetched without holding the lock since chain blocks are accepted rarely
+        let mut accepting_daa_scores = Vec::with_capacity(accepted.len());
+        for (accepting_block_hash, _) in accepted.iter() {
+            let accepting_daa_score = match self.inner.rpc.get_block(*accepting_block_hash, false).await {
+                Ok(block) => block.header.daa_score,
+                Err(err) => {
+                    log_warn!(
+                        "transaction tracker: unable to get accepting block {accepting_block_hash}, using virtual DAA score: {err}"
+                    );
+                    self.inner.current_daa_score.load(Ordering::SeqCst)
+                }
+            };
+            accepting_daa_scores.push(accepting_daa_score);
+        }
+
+        {
+            let mut transactions = self.inner.transactions.lock().unwrap();
+            for ((accepting_block_hash, ids), accepting_daa_score) in accepted.into_iter().zip(accepting_daa_scores) {
+                for id in ids {
+                    // the transaction might have been untracked while the accepting block was fetched
+                    if let Some(state) = transactions.get_mut(&id) {
+                        *state = TrackedState::Accepted { accepting_block_hash, accepting_daa_score };
+                        events.push(TrackerEvent::Accepted { id, accepting_block_hash, accepting_daa_score });
+                    }
+                }
+            }
+        }
+
+        events.into_iter().try_for_each(|event| self.notify(event))
+    }
+
+    pub async fn handle_daa_score_change(&self, current_daa_score: u64) -> Result<()> {
+        self.inner.current_daa_score.store(current_daa_score, Ordering::SeqCst);
+
+        let (confirmed, pending) = {
+            let mut confirmed = vec![];
+            let mut pending = vec![];
+            let confirmation_depth = self.inner.confirmation_depth;
+            self.inner.transactions.lock().unwrap().retain(|id, state| match state {
+                TrackedState::Accepted { accepting_daa_score, .. } => {
+                    let confirmations = current_daa_score.saturating_sub(*accepting_daa_score);
+                    if confirmations >= confirmation_depth {
+                        confirmed.push(TrackerEvent::Confirmed { id: *id, confirmations });
+                        false
+                    } else {
+                        true
+                    }
+                }
+                TrackedState::Mempool { .. } => {
+                    pending.push(*id);
+                    true
+                }
+            });
+            (confirmed, pending)
+        };
+
+        confirmed.into_iter().try_for_each(|event| self.notify(event))?;
+
+        let last_check = self.inner.last_mempool_check_daa_score.load(Ordering::SeqCst);
+        if !pending.is_empty() && current_daa_score >= last_check + MEMPOOL_CHECK_INTERVAL {
+            self.inner.last_mempool_check_daa_score.store(current_daa_score, Ordering::SeqCst);
+            self.check_mempool(pending).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Look up unaccepted transactions in the node mempool. A transaction missing
+    /// from the mempool may simply have been accepted by a block whose chain
+    /// notification has not been received yet, so eviction is only reported
+    /// after several consecutive failed lookups.
+    async fn check_mempool(&self, pending: Vec<TransactionId>) -> Result<()> {
+        for id in pending {
+            let found = self.inner.rpc.get_mempool_entry(id, true, false).await.is_ok();
+
+            let evicted = {
+                let mut transactions = self.inner.transactions.lock().unwrap();
+                match transactions.get_mut(&id) {
+                    Some(TrackedState::Mempool { failed_lookups }) => {
+                        *failed_lookups = if found { 0 } else { *failed_lookups + 1 };
+                        if *failed_lookups >= MEMPOOL_CHECK_EVICTION_THRESHOLD {
+                            transactions.remove(&id);
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                    // accepted or untracked while the lookup was in flight
+                    _ => false,
+                }
+            };
+
+            if evicted {
+                self.notify(TrackerEvent::Evicted { id })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_notification(&self, notification: Notification) -> Result<()> {
+        match notification {
+            Notification::VirtualDaaScoreChanged(notification) => {
+                self.handle_daa_score_change(notification.virtual_daa_score).await?;
+            }
+            Notification::VirtualChainChanged(notification) => {
+                self.handle_virtual_chain_changed(&notification).await?;
+            }
+            _ => {
+                log_warn!("transaction tracker: unexpected notification: {:?}", notification);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn register_notification_listener(&self) -> Result<()> {
+        let listener_id = self.inner.rpc.register_new_listener(ChannelConnection::new(
+            "transaction tracker",
+            self.inner.notification_channel.sender.clone(),
+            ChannelType::Persistent,
+        ));
+        *self.inner.listener_id.lock().unwrap() = Some(listener_id);
+        self.inner.rpc.start_notify(listener_id, Scope::VirtualDaaScoreChanged(VirtualDaaScoreChangedScope {})).await?;
+        self.inner.rpc.start_notify(listener_id, Scope::VirtualChainChanged(VirtualChainChangedScope::new(true))).await?;
+        Ok(())
+    }
+
+    async fn unregister_notification_listener(&self) -> Result<()> {
+        let listener_id = self.inner.listener_id.lock().unwrap().take();
+        if let Some(id) = listener_id {
+            self.inner.rpc.unregister_listener(id).await?;
+        }
+        Ok(())
+    }
+
+    /// Subscribe to node notifications and start processing them.
+    pub async fn start(&self) -> Result<()> {
+        if self.inner.task_is_running.load(Ordering::SeqCst) {
+            return Err(Error::custom("TransactionTracker::start() called while task is already running"));
+        }
+        self.register_notification_listener().await?;
+        self.inner.task_is_running.store(true, Ordering::SeqCst);
+
+        let this = self.clone();
+        let notification_receiver = self.inner.notification_channel.receiver.clone();
+        let task_ctl_receiver = self.inner.task_ctl.request.receiver.clone();
+        let task_ctl_sender = self.inner.task_ctl.response.sender.clone();
+
+        spawn(async move {
+            loop {
+                select_biased! {
+                    notification = notification_receiver.recv().fuse() => {
+                        match notification {
+                            Ok(notification) => {
+                                if let Err(err) = this.handle_notification(notification).await {
+                                    log_error!("transaction tracker: error while handling notification: {err}");
+                                }
+                            }
+                            Err(err) => {
+                                log_error!("transaction tracker: RPC notification channel error: {err}");
+                                break;
+                            }
+                        }
+                    },
+                    _ = task_ctl_receiver.recv().fuse() => {
+                        break;
+                    },
+                }
+            }
+
+            this.unregister_notification_listener().await.unwrap_or_else(|err| log_error!("{err}"));
+            this.inner.task_is_running.store(false, Ordering::SeqCst);
+            task_ctl_sender.send(()).await.unwrap();
+        });
+
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        if self.inner.task_is_running.load(Ordering::SeqCst) {
+            self.inner.task_ctl.signal(()).await.expect("TransactionTracker::stop() `signal` error");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::RpcCoreMock;
+    use kaspa_consensus_core::header::Header;
+    use kaspa_rpc_core::{RpcAcceptedTransactionIds, RpcBlock};
+    use workflow_core::channel::MultiplexerChannel;
+
+    fn chain_changed(removed: &[Hash], accepted: &[(Hash, TransactionId)]) -> VirtualChainChangedNotification {
+        VirtualChainChangedNotification {
+            removed_chain_block_hashes: Arc::new(removed.to_vec()),
+            added_chain_block_hashes: Arc::new(accepted.iter().map(|(hash, _)| *hash).collect()),
+            accepted_transaction_ids: Arc::new(
+                accepted
+                    .iter()
+                    .map(|(hash, id)| RpcAcceptedTransactionIds { accepting_block_hash: *hash, accepted_transaction_ids: vec![*id] })
+                    .collect(),
+            ),
+        }
+    }
+
+    fn drain(channel: &MultiplexerChannel<Box<TrackerEvent>>) -> Vec<TrackerEvent> {
+        std::iter::from_fn(|| channel.receiver.try_recv().ok()).map(|event| *event).collect()
+    }
+
+    fn chain_block(hash: Hash, daa_score: u64) -> RpcBlock {
+        let mut header = Header::from_precomputed_hash(hash, vec![]);
+        header.daa_score = daa_score;
+        RpcBlock { header: header.into(), transactions: vec![], verbose_data: None }
+    }
+
+    #[tokio::test]
+    async fn test_transaction_tracker_lifecycle() -> Result<()> {
+        let rpc = Arc::new(RpcCoreMock::new());
+        let tracker = TransactionTracker::new(rpc.clone(), 10);
+        let events = tracker.multiplexer().channel();
+        let id = TransactionId::from_u64_word(1);
+        let (block_a, block_b) = (Hash::from_u64_word(2), Hash::from_u64_word(3));
+        // the accepting blocks are mined before the virtual DAA score changes are observed
+        rpc.add_block(chain_block(block_a, 98));
+        rpc.add_block(chain_block(block_b, 103));
+
+        tracker.handle_daa_score_change(100).await?;
+        tracker.track(id)?;
+        tracker.handle_virtual_chain_changed(&chain_changed(&[], &[(block_a, id)])).await?;
+        tracker.handle_daa_score_change(105).await?;
+        // reorg moves acceptance of the transaction to another block
+        tracker.handle_virtual_chain_changed(&chain_changed(&[block_a], &[(block_b, id)])).await?;
+        tracker.handle_daa_score_change(112).await?;
+        assert!(tracker.is_tracked(&id));
+        tracker.handle_daa_score_change(113).await?;
+        assert!(!tracker.is_tracked(&id));
+
+        assert_eq!(
+            drain(&events),
+            vec![
+                TrackerEvent::Mempool { id },
+                TrackerEvent::Accepted { id, accepting_block_hash: block_a, accepting_daa_score: 98 },
+                TrackerEvent::Unaccepted { id },
+                TrackerEvent::Accepted { id, accepting_block_hash: block_b, accepting_daa_score: 103 },
+                TrackerEvent::Confirmed { id, confirmations: 10 },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_tracker_eviction() -> Result<()> {
+        // the mock RPC fails all mempool lookups
+        let tracker = TransactionTracker::new(Arc::new(RpcCoreMock::new()), 10);
+        let events = tracker.multiplexer().channel();
+        let id = TransactionId::from_u64_word(1);
+
+        tracker.track(id)?;
+        tracker.handle_daa_score_change(MEMPOOL_CHECK_INTERVAL).await?;
+        assert!(tracker.is_tracked(&id));
+        // lookups are rate limited by DAA score
+        tracker.handle_daa_score_change(MEMPOOL_CHECK_INTERVAL + 1).await?;
+        assert!(tracker.is_tracked(&id));
+        tracker.handle_daa_score_change(MEMPOOL_CHECK_INTERVAL * 2).await?;
+        assert!(!tracker.is_tracked(&id));
+
+        assert_eq!(drain(&events), vec![TrackerEvent::Mempool { id }, TrackerEvent::Evicted { id }]);
+
+        Ok(())
+    }
+}
@@ -13,6 +13,8 @@ pub struct NetworkParams {
     pub user_transaction_maturity_period_daa: AtomicU64,
     pub kip9_version: Kip9Version,
     pub additional_compound_transaction_mass: u64,
+    /// Sighash fork id of the network (see `Params::sighash_fork_id`), committed to by all transaction signatures
+    pub sighash_fork_id: RwLock<Option<u8>>,
 }
 
 impl NetworkParams {
@@ -41,6 +43,11 @@ impl NetworkParams {
         self.additional_compound_transaction_mass
     }
 
+    #[inline]
+    pub fn sighash_fork_id(&self) -> Option<u8> {
+        *self.sighash_fork_id.read().unwrap()
+    }
+
     pub fn set_coinbase_transaction_maturity_period_daa(&self, value: u64) {
         self.coinbase_transaction_maturity_period_daa.store(value, Ordering::Relaxed);
     }
@@ -48,6 +55,10 @@ impl NetworkParams {
     pub fn set_user_transaction_maturity_period_daa(&self, value: u64) {
         self.user_transaction_maturity_period_daa.store(value, Ordering::Relaxed);
     }
+
+    pub fn set_sighash_fork_id(&self, value: Option<u8>) {
+        *self.sighash_fork_id.write().unwrap() = value;
+    }
 }
 
 static MAINNET_NETWORK_PARAMS: LazyLock<NetworkParams> = LazyLock::new(|| NetworkParams {
@@ -56,6 +67,7 @@ static MAINNET_NETWORK_PARAMS: LazyLock<NetworkParams> = LazyLock::new(|| Networ
     user_transaction_maturity_period_daa: AtomicU64::new(10),
     kip9_version: Kip9Version::Beta,
     additional_compound_transaction_mass: 100,
+    sighash_fork_id: RwLock::new(None),
 });
 
 static TESTNET10_NETWORK_PARAMS: LazyLock<NetworkParams> = LazyLock::new(|| NetworkParams {
@@ -64,6 +76,7 @@ static TESTNET10_NETWORK_PARAMS: LazyLock<NetworkParams> = LazyLock::new(|| Netw
     user_transaction_maturity_period_daa: AtomicU64::new(10),
     kip9_version: Kip9Version::Beta,
     additional_compound_transaction_mass: 100,
+    sighash_fork_id: RwLock::new(None),
 });
 
 static TESTNET11_NETWORK_PARAMS: LazyLock<NetworkParams> = LazyLock::new(|| NetworkParams {
@@ -72,6 +85,7 @@ static TESTNET11_NETWORK_PARAMS: LazyLock<NetworkParams> = LazyLock::new(|| Netw
     user_transaction_maturity_period_daa: AtomicU64::new(100),
     kip9_version: Kip9Version::Alpha,
     additional_compound_transaction_mass: 100,
+    sighash_fork_id: RwLock::new(None),
 });
 
 static SIMNET_NETWORK_PARAMS: LazyLock<NetworkParams> = LazyLock::new(|| NetworkParams {
@@ -80,6 +94,7 @@ static SIMNET_NETWORK_PARAMS: LazyLock<NetworkParams> = LazyLock::new(|| Network
     user_transaction_maturity_period_daa: AtomicU64::new(10),
     kip9_version: Kip9Version::Alpha,
     additional_compound_transaction_mass: 0,
+    sighash_fork_id: RwLock::new(None),
 });
 
 static DEVNET_NETWORK_PARAMS: LazyLock<NetworkParams> = LazyLock::new(|| NetworkParams {
@@ -88,6 +103,7 @@ static DEVNET_NETWORK_PARAMS: LazyLock<NetworkParams> = LazyLock::new(|| Network
     user_transaction_maturity_period_daa: AtomicU64::new(10),
     kip9_version: Kip9Version::Beta,
     additional_compound_transaction_mass: 0,
+    sighash_fork_id: RwLock::new(None),
 });
 
 impl NetworkParams {
@@ -130,3 +146,12 @@ pub fn set_user_transaction_maturity_period_daa(network_id: &NetworkId, value: u
     }
     network_params.set_user_transaction_maturity_period_daa(value);
 }
+
+/// Set the sighash fork id of a given network. Must match the `--override-sighash-fork-id`
+/// setting of the node, otherwise the transactions signed by the wallet are rejected.
+pub fn set_sighash_fork_id(network_id: &NetworkId, value: Option<u8>) {
+    if network_id.network_type == NetworkType::Mainnet && value.is_some() {
+        panic!("Sighash fork id can not be set on mainnet");
+    }
+    NetworkParams::from(*network_id).set_sighash_fork_id(value);
+}
@@ -16,7 +16,10 @@ pub use args::*;
 
 use crate::account::ScanNotifier;
 use crate::api::traits::WalletApi;
-use crate::compat::gen1::decrypt_mnemonic;
+use crate::compat::gen1::{
+    decrypt_mnemonic, encrypt_mnemonic, KeysFile, KeysFileEncryptedMnemonic, KEYS_FILE_NUM_THREADS, KEYS_FILE_VERSION,
+};
+use crate::derivation::{multisig_cosigner_index, parse_multisig_xpub_keys, sort_multisig_xpub_keys};
 use crate::error::Error::Custom;
 use crate::factory::try_load_account;
 use crate::imports::*;
@@ -25,7 +28,7 @@ use crate::storage::interface::{OpenArgs, StorageDescriptor};
 use crate::storage::local::interface::LocalStore;
 use crate::storage::local::Storage;
 use crate::wallet::maps::ActiveAccountMap;
-use kaspa_bip32::{ExtendedKey, Language, Mnemonic, Prefix as KeyPrefix, WordCount};
+use kaspa_bip32::{Language, Mnemonic, Prefix as KeyPrefix, WordCount};
 use kaspa_notify::{
     listener::ListenerId,
     scope::{Scope, VirtualDaaScoreChangedScope},
@@ -703,11 +706,12 @@ impl Wallet {
         self: &Arc<Wallet>,
         wallet_secret: &Secret,
         prv_key_data_args: Vec<PrvKeyDataArgs>,
-        mut xpub_keys: Vec<String>,
+        xpub_keys: Vec<String>,
         account_name: Option<String>,
         minimum_signatures: u16,
     ) -> Result<Arc<dyn Account>> {
         let account_store = self.inner.store.clone().as_account_store()?;
+        let mut xpub_keys = parse_multisig_xpub_keys(&xpub_keys)?;
 
         let account: Arc<dyn Account> = if prv_key_data_args.is_not_empty() {
             let mut generated_xpubs = Vec::with_capacity(prv_key_data_args.len());
@@ -722,23 +726,15 @@ impl Wallet {
                     .await?
                     .ok_or_else(|| Error::PrivateKeyNotFound(prv_key_data_id))?;
                 let xpub_key = prv_key_data.create_xpub(payment_secret.as_ref(), MULTISIG_ACCOUNT_KIND.into(), 0).await?; // todo it can be done concurrently
-                generated_xpubs.push(xpub_key.to_string(Some(KeyPrefix::XPUB)));
+                generated_xpubs.push(xpub_key);
                 prv_key_data_ids.push(prv_key_data_id);
             }
 
-            generated_xpubs.sort_unstable();
             xpub_keys.extend_from_slice(generated_xpubs.as_slice());
-            xpub_keys.sort_unstable();
+            sort_multisig_xpub_keys(&mut xpub_keys);
 
             let min_cosigner_index =
-                generated_xpubs.first().and_then(|first_generated| xpub_keys.binary_search(first_generated).ok()).map(|v| v as u8);
-
-            let xpub_keys = xpub_keys
-                .into_iter()
-                .map(|xpub_key| {
-                    ExtendedPublicKeySecp256k1::from_str(&xpub_key).map_err(|err| Error::InvalidExtendedPublicKey(xpub_key, err))
-                })
-                .collect::<Result<Vec<_>>>()?;
+                generated_xpubs.iter().filter_map(|generated| multisig_cosigner_index(&xpub_keys, generated)).min();
 
             Arc::new(
                 multisig::MultiSig::try_new(
@@ -753,13 +749,6 @@ impl Wallet {
                 .await?,
             )
         } else {
-            let xpub_keys = xpub_keys
-                .into_iter()
-                .map(|xpub_key| {
-                    ExtendedPublicKeySecp256k1::from_str(&xpub_key).map_err(|err| Error::InvalidExtendedPublicKey(xpub_key, err))
-                })
-                .collect::<Result<Vec<_>>>()?;
-
             Arc::new(
                 multisig::MultiSig::try_new(self, account_name, Arc::new(xpub_keys), None, None, minimum_signatures, false).await?,
             )
@@ -1441,6 +1430,142 @@ impl Wallet {
         Ok(acc)
     }
 
+    /// Imports the account of a Go kaspawallet `keys.json` file, decrypting its mnemonics with `import_secret`
+    pub async fn import_kaspawallet_golang_keys_file(
+        self: &Arc<Wallet>,
+        import_secret: &Secret,
+        wallet_secret: &Secret,
+        keys_file: &KeysFile,
+    ) -> Result<Arc<dyn Account>> {
+        let num_threads = keys_file.num_threads()?;
+        let mut encrypted_mnemonics =
+            keys_file.encrypted_mnemonics.iter().map(KeysFileEncryptedMnemonic::as_encrypted_mnemonic).collect::<Vec<_>>();
+        let mut xpublic_keys = keys_file.public_keys.iter().map(String::as_str).collect::<Vec<_>>();
+
+        if keys_file.is_single() {
+            let encrypted_mnemonic = encrypted_mnemonics.remove(0);
+            let xpublic_key = xpublic_keys.remove(0);
+            match keys_file.version {
+                0 => {
+                    let file = SingleWalletFileV0 { num_threads, encrypted_mnemonic, xpublic_key, ecdsa: keys_file.ecdsa };
+                    self.import_kaspawallet_golang_single_v0(import_secret, wallet_secret, file).await
+                }
+                KEYS_FILE_VERSION => {
+                    let file = SingleWalletFileV1 { encrypted_mnemonic, xpublic_key, ecdsa: keys_file.ecdsa };
+                    self.import_kaspawallet_golang_single_v1(import_secret, wallet_secret, file).await
+                }
+                version => Err(Error::Custom(format!("unsupported keys file version {version}"))),
+            }
+        } else {
+            let required_signatures = u16::try_from(keys_file.minimum_signatures)
+                .map_err(|_| Error::Custom(format!("invalid minimum signatures {}", keys_file.minimum_signatures)))?;
+            let cosigner_index = u8::try_from(keys_file.cosigner_index)
+                .map_err(|_| Error::Custom(format!("invalid cosigner index {}", keys_file.cosigner_index)))?;
+            match keys_file.version {
+                0 => {
+                    let file = MultisigWalletFileV0 {
+                        num_threads,
+                        encrypted_mnemonics,
+                        xpublic_keys,
+                        required_signatures,
+                        cosigner_index,
+                        ecdsa: keys_file.ecdsa,
+                    };
+                    self.import_kaspawallet_golang_multisig_v0(import_secret, wallet_secret, file).await
+                }
+                KEYS_FILE_VERSION => {
+                    let file = MultisigWalletFileV1 {
+                        encrypted_mnemonics,
+                        xpublic_keys,
+                        required_signatures,
+                        cosigner_index,
+                        ecdsa: keys_file.ecdsa,
+                    };
+                    self.import_kaspawallet_golang_multisig_v1(import_secret, wallet_secret, file).await
+                }
+                version => Err(Error::Custom(format!("unsupported keys file version {version}"))),
+            }
+        }
+    }
+
+    /// Exports an account as a Go kaspawallet `keys.json` file, encrypting its mnemonics with `export_secret`.
+    ///
+    /// Only the accounts the Go kaspawallet is able to derive are supported: the first BIP32 account
+    /// of a mnemonic and multisig accounts whose private keys are all mnemonics.
+    pub async fn export_kaspawallet_golang_keys_file(
+        self: &Arc<Wallet>,
+        wallet_secret: &Secret,
+        payment_secret: Option<&Secret>,
+        export_secret: &Secret,
+        account_id: &AccountId,
+    ) -> Result<KeysFile> {
+        let (account_storage, metadata) =
+            self.inner.store.as_account_store()?.load_single(account_id).await?.ok_or(Error::AccountNotFound(*account_id))?;
+        let prefix = KeyPrefix::from(self.network_id()?);
+
+        let prv_key_data_store = self.inner.store.as_prv_key_data_store()?;
+        let mut mnemonics = vec![];
+        for prv_key_data_id in &account_storage.prv_key_data_ids {
+            let prv_key_data = prv_key_data_store
+                .load_key_data(wallet_secret, &prv_key_data_id)
+                .await?
+                .ok_or(Error::PrivateKeyNotFound(prv_key_data_id))?;
+            let mnemonic = prv_key_data
+                .as_mnemonic(payment_secret)?
+                .ok_or_else(|| Error::Custom(format!("private key {prv_key_data_id} is not a mnemonic")))?;
+            mnemonics.push(mnemonic);
+        }
+
+        let (public_keys, minimum_signatures, cosigner_index, ecdsa) = match account_storage.kind.as_ref() {
+            BIP32_ACCOUNT_KIND => {
+                let bip32::Payload { xpub_keys, account_index, ecdsa } = bip32::Payload::try_load(&account_storage)?;
+                if account_index != 0 || mnemonics.len() != 1 {
+                    return Err(Error::Custom("only the first account of a mnemonic can be exported".to_owned()));
+                }
+                (xpub_keys.iter().map(|xpub_key| xpub_key.to_string(Some(prefix))).collect::<Vec<_>>(), 1, 0, ecdsa)
+            }
+            MULTISIG_ACCOUNT_KIND => {
+                let multisig::Payload { xpub_keys, minimum_signatures, ecdsa, .. } = multisig::Payload::try_load(&account_storage)?;
+                // the Go kaspawallet orders the keys regardless of their prefix
+                let mut public_keys = xpub_keys.iter().map(|xpub_key| xpub_key.to_string(Some(prefix))).collect::<Vec<_>>();
+                public_keys
+                    .sort_unstable_by(|left, right| left.split_at(KeyPrefix::LENGTH).1.cmp(right.split_at(KeyPrefix::LENGTH).1));
+                let mut cosigner_indexes = Vec::with_capacity(mnemonics.len());
+                for mnemonic in mnemonics.iter() {
+                    let prv_key_data =
+                        storage::PrvKeyData::try_new_from_mnemonic(mnemonic.clone(), None, self.store().encryption_kind()?)?;
+                    let xpub_key = prv_key_data.create_xpub(None, MULTISIG_ACCOUNT_KIND.into(), 0).await?.to_string(Some(prefix));
+                    let index = public_keys
+                        .iter()
+                        .position(|public_key| public_key == &xpub_key)
+                        .ok_or_else(|| Error::Custom("private key does not match any account public key".to_owned()))?;
+                    cosigner_indexes.push(index as u32);
+                }
+                (public_keys, minimum_signatures as u32, cosigner_indexes.into_iter().min().unwrap_or_default(), ecdsa)
+            }
+            kind => return Err(Error::Custom(format!("{kind} accounts cannot be exported as keys files"))),
+        };
+
+        let num_threads = KEYS_FILE_NUM_THREADS;
+        let encrypted_mnemonics = mnemonics
+            .iter()
+            .map(|mnemonic| encrypt_mnemonic(num_threads, mnemonic.phrase(), export_secret.as_ref()).map(Into::into))
+            .collect::<Result<Vec<_>>>()?;
+        let indexes = metadata.and_then(|metadata| metadata.indexes.clone()).unwrap_or_default();
+
+        Ok(KeysFile {
+            version: KEYS_FILE_VERSION,
+            num_threads: None,
+            encrypted_mnemonics,
+            public_keys,
+            minimum_signatures,
+            cosigner_index,
+            last_used_external_index: indexes.receive(),
+            last_used_internal_index: indexes.change(),
+            ecdsa,
+        })
+    }
+
     pub async fn import_legacy_keydata(
         self: &Arc<Wallet>,
         import_secret: &Secret,
@@ -1610,15 +1735,7 @@ impl Wallet {
         minimum_signatures: u16,
         additional_xpub_keys: Vec<String>,
     ) -> Result<Arc<dyn Account>> {
-        let mut additional_xpub_keys = additional_xpub_keys
-            .into_iter()
-            .map(|xpub| {
-                ExtendedKey::from_str(&xpub).map(|mut xpub| {
-                    xpub.prefix = KeyPrefix::XPUB;
-                    xpub.to_string()
-                })
-            })
-            .collect::<Result<Vec<_>, kaspa_bip32::Error>>()?;
+        let mut xpub_keys = parse_multisig_xpub_keys(&additional_xpub_keys)?;
 
         let mut generated_xpubs = Vec::with_capacity(mnemonics_secrets.len());
         let mut prv_key_data_ids = Vec::with_capacity(mnemonics_secrets.len());
@@ -1631,25 +1748,15 @@ impl Wallet {
                 return Err(Error::PrivateKeyAlreadyExists(prv_key_data.id));
             }
             let xpub_key = prv_key_data.create_xpub(payment_secret.as_ref(), MULTISIG_ACCOUNT_KIND.into(), 0).await?; // todo it can be done concurrently
-            generated_xpubs.push(xpub_key.to_string(Some(KeyPrefix::XPUB)));
+            generated_xpubs.push(xpub_key);
             prv_key_data_ids.push(prv_key_data.id);
             prv_key_data_store.store(wallet_secret, prv_key_data).await?;
         }
 
-        generated_xpubs.sort_unstable();
-        additional_xpub_keys.extend_from_slice(generated_xpubs.as_slice());
-        let mut xpub_keys = additional_xpub_keys;
-        xpub_keys.sort_unstable();
-
-        let min_cosigner_index =
-            generated_xpubs.first().and_then(|first_generated| xpub_keys.binary_search(first_generated).ok()).map(|v| v as u8);
+        xpub_keys.extend_from_slice(generated_xpubs.as_slice());
+        sort_multisig_xpub_keys(&mut xpub_keys);
 
-        let xpub_keys = xpub_keys
-            .into_iter()
-            .map(|xpub_key| {
-                ExtendedPublicKeySecp256k1::from_str(&xpub_key).map_err(|err| Error::InvalidExtendedPublicKey(xpub_key, err))
-            })
-            .collect::<Result<Vec<_>>>()?;
+        let min_cosigner_index = generated_xpubs.iter().filter_map(|generated| multisig_cosigner_index(&xpub_keys, generated)).min();
 
         let account: Arc<dyn Account> = Arc::new(
             multisig::MultiSig::try_new(
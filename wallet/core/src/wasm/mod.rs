@@ -25,6 +25,7 @@ cfg_if! {
         pub use self::utxo::*;
         pub use self::encryption::*;
         pub use self::cryptobox::*;
+        pub use kaspa_wallet_pskt::wasm::pskt::PSKT;
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::derivation::path::{KaspaDerivationPath, Purpose};
 use crate::derivation::traits::*;
 use crate::imports::*;
 use hmac::Mac;
@@ -194,16 +195,11 @@ impl WalletDerivationManager {
         if is_multisig && cosigner_index.is_none() {
             return Err("cosigner_index is required for multisig path derivation".to_string().into());
         }
-        let purpose = if is_multisig { 45 } else { 44 };
-        let mut path = format!("m/{purpose}'/111111'/{account_index}'");
-        if let Some(cosigner_index) = cosigner_index {
-            path = format!("{path}/{}", cosigner_index)
-        }
-        if let Some(address_type) = address_type {
-            path = format!("{path}/{}", address_type.index());
-        }
-        let path = path.parse::<DerivationPath>()?;
-        Ok(path)
+        let purpose = if is_multisig { Purpose::MultiSig } else { Purpose::SingleSig };
+        let account_index = u32::try_from(account_index)
+            .map_err(|_| Error::InvalidDerivationPath(format!("account index {account_index} is out of range")))?;
+        let path = KaspaDerivationPath::try_new(purpose, account_index, cosigner_index, address_type, None)?;
+        Ok(path.to_derivation_path())
     }
 
     pub fn receive_pubkey_manager(&self) -> &PubkeyDerivationManager {
@@ -4,4 +4,5 @@
 
 pub mod gen0;
 pub mod gen1;
+pub mod path;
 pub mod traits;
@@ -0,0 +1,279 @@
+//!
+//! Typed derivation paths of the Kaspa standard derivation scheme
+//! `m/<purpose>'/111111'/<account>'[/<cosigner>]/<address type>/<address index>`.
+//!
+
+use crate::error::Error;
+use crate::result::Result;
+use kaspa_bip32::{AddressType, ChildNumber, DerivationPath};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// SLIP-0044 coin type registered for Kaspa.
+pub const KASPA_COIN_TYPE: u32 = 111111;
+
+/// Purpose level of a Kaspa derivation path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Purpose {
+    /// BIP-44 single signature accounts (`44'`).
+    SingleSig,
+    /// BIP-45 multisig accounts (`45'`), which carry an additional cosigner level.
+    MultiSig,
+}
+
+impl Purpose {
+    pub fn index(&self) -> u32 {
+        match self {
+            Purpose::SingleSig => 44,
+            Purpose::MultiSig => 45,
+        }
+    }
+}
+
+impl TryFrom<u32> for Purpose {
+    type Error = Error;
+
+    fn try_from(index: u32) -> Result<Self> {
+        match index {
+            44 => Ok(Purpose::SingleSig),
+            45 => Ok(Purpose::MultiSig),
+            _ => Err(Error::InvalidDerivationPath(format!("unsupported purpose {index}' (expected 44' or 45')"))),
+        }
+    }
+}
+
+/// A validated derivation path of the Kaspa standard derivation scheme.
+///
+/// The path may stop at the account level, at the address type level (the
+/// receive or change chain), or address a single key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KaspaDerivationPath {
+    purpose: Purpose,
+    account_index: u32,
+    cosigner_index: Option<u32>,
+    address_type: Option<AddressType>,
+    address_index: Option<u32>,
+}
+
+impl KaspaDerivationPath {
+    /// Account level path `m/44'/111111'/<account_index>'`.
+    pub fn single_sig(account_index: u32) -> Result<Self> {
+        Self::try_new(Purpose::SingleSig, account_index, None, None, None)
+    }
+
+    /// Account level path `m/45'/111111'/<account_index>'/<cosigner_index>`.
+    pub fn multisig(account_index: u32, cosigner_index: u32) -> Result<Self> {
+        Self::try_new(Purpose::MultiSig, account_index, Some(cosigner_index), None, None)
+    }
+
+    pub fn try_new(
+        purpose: Purpose,
+        account_index: u32,
+        cosigner_index: Option<u32>,
+        address_type: Option<AddressType>,
+        address_index: Option<u32>,
+    ) -> Result<Self> {
+        if account_index >= ChildNumber::HARDENED_FLAG {
+            return Err(Error::InvalidDerivationPath(format!("account index {account_index} is out of range")));
+        }
+        match (purpose, cosigner_index) {
+            (Purpose::SingleSig, Some(_)) => {
+                return Err(Error::InvalidDerivationPath("cosigner index is only allowed in multisig paths".to_string()))
+            }
+            (Purpose::MultiSig, None) => {
+                return Err(Error::InvalidDerivationPath("cosigner index is required in multisig paths".to_string()))
+            }
+            (Purpose::MultiSig, Some(index)) if index >= ChildNumber::HARDENED_FLAG => {
+                return Err(Error::InvalidDerivationPath(format!("cosigner index {index} is out of range")))
+            }
+            _ => {}
+        }
+        if let Some(address_index) = address_index {
+            if address_type.is_none() {
+                return Err(Error::InvalidDerivationPath("address index requires an address type".to_string()));
+            }
+            if address_index >= ChildNumber::HARDENED_FLAG {
+                return Err(Error::InvalidDerivationPath(format!("address index {address_index} is out of range")));
+            }
+        }
+
+        Ok(Self { purpose, account_index, cosigner_index, address_type, address_index })
+    }
+
+    pub fn purpose(&self) -> Purpose {
+        self.purpose
+    }
+
+    pub fn account_index(&self) -> u32 {
+        self.account_index
+    }
+
+    pub fn cosigner_index(&self) -> Option<u32> {
+        self.cosigner_index
+    }
+
+    pub fn address_type(&self) -> Option<AddressType> {
+        self.address_type
+    }
+
+    pub fn address_index(&self) -> Option<u32> {
+        self.address_index
+    }
+
+    /// The account level part of this path.
+    pub fn account(&self) -> Self {
+        Self { address_type: None, address_index: None, ..*self }
+    }
+
+    /// The path of the key at `index` in the `address_type` chain of this account.
+    pub fn address(&self, address_type: AddressType, index: u32) -> Result<Self> {
+        Self::try_new(self.purpose, self.account_index, self.cosigner_index, Some(address_type), Some(index))
+    }
+
+    /// Receive key paths of this account for the given index range.
+    pub fn receive_paths(&self, indexes: Range<u32>) -> Result<Vec<Self>> {
+        indexes.map(|index| self.address(AddressType::Receive, index)).collect()
+    }
+
+    /// Change key paths of this account for the given index range.
+    pub fn change_paths(&self, indexes: Range<u32>) -> Result<Vec<Self>> {
+        indexes.map(|index| self.address(AddressType::Change, index)).collect()
+    }
+
+    pub fn to_derivation_path(&self) -> DerivationPath {
+        DerivationPath::from(self)
+    }
+}
+
+impl From<&KaspaDerivationPath> for DerivationPath {
+    fn from(path: &KaspaDerivationPath) -> Self {
+        let hardened = |index| ChildNumber::new(index, true).expect("validated index");
+        let normal = |index| ChildNumber::new(index, false).expect("validated index");
+        let mut derivation_path = DerivationPath::default();
+        derivation_path.push(hardened(path.purpose.index()));
+        derivation_path.push(hardened(KASPA_COIN_TYPE));
+        derivation_path.push(hardened(path.account_index));
+        if let Some(cosigner_index) = path.cosigner_index {
+            derivation_path.push(normal(cosigner_index));
+        }
+        if let Some(address_type) = path.address_type {
+            derivation_path.push(normal(address_type.index()));
+        }
+        if let Some(address_index) = path.address_index {
+            derivation_path.push(normal(address_index));
+        }
+        derivation_path
+    }
+}
+
+impl TryFrom<&DerivationPath> for KaspaDerivationPath {
+    type Error = Error;
+
+    fn try_from(path: &DerivationPath) -> Result<Self> {
+        let invalid = |msg: &str| Error::InvalidDerivationPath(format!("{msg} in `{path}`"));
+        let mut children = path.iter();
+
+        let purpose = match children.next() {
+            Some(child) if child.is_hardened() => Purpose::try_from(child.index())?,
+            _ => return Err(invalid("expected a hardened purpose")),
+        };
+        match children.next() {
+            Some(child) if child.is_hardened() && child.index() == KASPA_COIN_TYPE => {}
+            _ => return Err(invalid("expected coin type 111111'")),
+        }
+        let account_index = match children.next() {
+            Some(child) if child.is_hardened() => child.index(),
+            _ => return Err(invalid("expected a hardened account index")),
+        };
+
+        let mut non_hardened = || match children.next() {
+            Some(child) if child.is_hardened() => Err(invalid("unexpected hardened child")),
+            child => Ok(child.map(|child| child.index())),
+        };
+        let cosigner_index = match purpose {
+            Purpose::SingleSig => None,
+            Purpose::MultiSig => Some(non_hardened()?.ok_or_else(|| invalid("expected a cosigner index"))?),
+        };
+        let address_type = match non_hardened()? {
+            None => None,
+            Some(0) => Some(AddressType::Receive),
+            Some(1) => Some(AddressType::Change),
+            Some(_) => return Err(invalid("address type must be 0 (receive) or 1 (change)")),
+        };
+        let address_index = non_hardened()?;
+        if non_hardened()?.is_some() {
+            return Err(invalid("too many levels"));
+        }
+
+        Self::try_new(purpose, account_index, cosigner_index, address_type, address_index)
+    }
+}
+
+impl FromStr for KaspaDerivationPath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        Self::try_from(&DerivationPath::from_str(path)?)
+    }
+}
+
+impl fmt::Display for KaspaDerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_derivation_path())
+    }
+}
+
+impl Serialize for KaspaDerivationPath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for KaspaDerivationPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        path.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kaspa_derivation_path() {
+        let account = KaspaDerivationPath::single_sig(3).unwrap();
+        assert_eq!(account.to_string(), "m/44'/111111'/3'");
+
+        let receive = account.receive_paths(0..2).unwrap();
+        assert_eq!(receive[1].to_string(), "m/44'/111111'/3'/0/1");
+        assert_eq!(account.change_paths(5..6).unwrap()[0].to_string(), "m/44'/111111'/3'/1/5");
+
+        let multisig = KaspaDerivationPath::multisig(0, 2).unwrap().address(AddressType::Change, 7).unwrap();
+        assert_eq!(multisig.to_string(), "m/45'/111111'/0'/2/1/7");
+
+        for path in ["m/44'/111111'/3'", "m/44'/111111'/3'/0", "m/44'/111111'/3'/1/9", "m/45'/111111'/0'/2/1/7"] {
+            assert_eq!(path.parse::<KaspaDerivationPath>().unwrap().to_string(), path);
+        }
+
+        for path in [
+            "m/44'/972/0'",           // legacy coin type
+            "m/49'/111111'/0'",       // unsupported purpose
+            "m/44'/111111'/0",        // non-hardened account
+            "m/44'/111111'/0'/2/0",   // invalid address type
+            "m/44'/111111'/0'/0/1'",  // hardened address index
+            "m/44'/111111'/0'/0/1/2", // too deep
+            "m/45'/111111'/0'",       // missing cosigner
+        ] {
+            assert!(path.parse::<KaspaDerivationPath>().is_err(), "{path} should be rejected");
+        }
+
+        assert!(KaspaDerivationPath::single_sig(ChildNumber::HARDENED_FLAG).is_err());
+        assert!(account.address(AddressType::Receive, ChildNumber::HARDENED_FLAG).is_err());
+
+        let json = serde_json::to_string(&multisig).unwrap();
+        assert_eq!(serde_json::from_str::<KaspaDerivationPath>(&json).unwrap(), multisig);
+    }
+}
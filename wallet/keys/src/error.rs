@@ -58,6 +58,9 @@ pub enum Error {
     #[error("Invalid PublicKey Array (must be string[] or PrivateKey[])")]
     InvalidPublicKeyArray,
 
+    #[error("Invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+
     #[error(transparent)]
     NetworkId(#[from] kaspa_consensus_core::network::NetworkIdError),
 
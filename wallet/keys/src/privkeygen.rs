@@ -27,6 +27,8 @@ impl PrivateKeyGenerator {
     pub fn new(xprv: &XPrvT, is_multisig: bool, account_index: u64, cosigner_index: Option<u32>) -> Result<PrivateKeyGenerator> {
         let xprv = XPrv::try_cast_from(xprv)?;
         let xprv = xprv.as_ref().inner();
+        // Single-signature paths have no cosigner level, so a cosigner index is only meaningful for multisig
+        let cosigner_index = cosigner_index.filter(|_| is_multisig);
         let receive = xprv.clone().derive_path(&WalletDerivationManager::build_derivate_path(
             is_multisig,
             account_index,
@@ -0,0 +1,133 @@
+use clap::{Parser, Subcommand};
+use std::{fs, path::PathBuf};
+
+/// Kaspa wallet. Starts an interactive session unless a command is given.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    /// Network to operate on (e.g. mainnet, testnet-10)
+    #[arg(long, global = true)]
+    pub network: Option<String>,
+
+    /// wRPC URL of a node running with --utxoindex (defaults to the configured server or a public node)
+    #[arg(long, global = true)]
+    pub rpc: Option<String>,
+
+    /// Name of the wallet file to open (defaults to the last used wallet)
+    #[arg(long, global = true)]
+    pub wallet: Option<String>,
+
+    /// Name or id of the account to operate on (prompts if the wallet contains multiple accounts)
+    #[arg(long, global = true)]
+    pub account: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Wallet operations
+    #[command(subcommand)]
+    Wallet(WalletCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WalletCommand {
+    /// Create a new bip32 wallet
+    Create {
+        /// Name of the wallet file
+        name: Option<String>,
+    },
+    /// Account address operations
+    #[command(subcommand)]
+    Address(AddressCommand),
+    /// Display the balance of the account
+    Balance,
+    /// Send funds to an address
+    Send {
+        /// Destination address
+        address: String,
+        /// Amount in KAS
+        amount: String,
+        /// Priority fee in KAS
+        #[arg(long)]
+        priority_fee: Option<String>,
+    },
+    /// Sign a partially signed transaction bundle, printing the signed bundle
+    Sign {
+        /// File containing the serialized partially signed transaction bundle
+        #[arg(long)]
+        pstx: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AddressCommand {
+    /// Generate a new receive address
+    New,
+}
+
+impl Args {
+    /// Translates the given command into the sequence of interactive wallet commands implementing it
+    pub fn into_commands(self) -> Result<Option<Vec<String>>, String> {
+        let Some(Command::Wallet(command)) = self.command else {
+            return Ok(None);
+        };
+
+        let mut commands = vec![];
+        if let Some(network) = &self.network {
+            commands.push(format!("network {network}"));
+        }
+
+        let connect = format!("connect {}", self.rpc.as_deref().unwrap_or_default());
+        let open = format!("wallet open {}", self.wallet.as_deref().unwrap_or_default());
+        let select = format!("select {}", self.account.as_deref().unwrap_or_default());
+
+        match command {
+            WalletCommand::Create { name } => {
+                commands.push(format!("wallet create {}", name.unwrap_or_default()));
+            }
+            WalletCommand::Address(AddressCommand::New) => {
+                commands.extend([open, select, "address new".to_string()]);
+            }
+            WalletCommand::Balance => {
+                commands.extend([connect, open, select, "balance".to_string()]);
+            }
+            WalletCommand::Send { address, amount, priority_fee } => {
+                let send = format!("send {address} {amount} {}", priority_fee.unwrap_or_default());
+                commands.extend([connect, open, select, send]);
+            }
+            WalletCommand::Sign { pstx } => {
+                let bundle = fs::read_to_string(&pstx).map_err(|err| format!("unable to read '{}': {err}", pstx.display()))?;
+                commands.extend([open, select, format!("pskb sign {}", bundle.trim())]);
+            }
+        }
+
+        Ok(Some(commands.into_iter().map(|command| command.trim().to_string()).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(args: &[&str]) -> Vec<String> {
+        Args::parse_from(std::iter::once("kaspa-wallet").chain(args.iter().copied())).into_commands().unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_wallet_commands() {
+        assert!(Args::parse_from(["kaspa-wallet"]).into_commands().unwrap().is_none());
+        assert_eq!(commands(&["wallet", "create"]), vec!["wallet create"]);
+        assert_eq!(commands(&["wallet", "address", "new", "--wallet", "w1"]), vec!["wallet open w1", "select", "address new"]);
+        assert_eq!(
+            commands(&["--network", "testnet-10", "wallet", "balance", "--rpc", "ws://127.0.0.1:17210", "--account", "acc"]),
+            vec!["network testnet-10", "connect ws://127.0.0.1:17210", "wallet open", "select acc", "balance"]
+        );
+        assert_eq!(
+            commands(&["wallet", "send", "kaspatest:qz", "1.5", "--priority-fee", "0.001"]),
+            vec!["connect", "wallet open", "select", "send kaspatest:qz 1.5 0.001"]
+        );
+    }
+}
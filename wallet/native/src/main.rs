@@ -1,9 +1,24 @@
-use kaspa_cli_lib::{kaspa_cli, TerminalOptions};
+use clap::Parser;
+use kaspa_cli_lib::{kaspa_cli, kaspa_cli_exec, TerminalOptions};
+
+mod args;
 
 #[tokio::main]
 async fn main() {
-    let result = kaspa_cli(TerminalOptions::new().with_prompt("$ "), None).await;
+    let commands = match args::Args::parse().into_commands() {
+        Ok(commands) => commands,
+        Err(err) => {
+            println!("{err}");
+            std::process::exit(1);
+        }
+    };
+
+    let result = match commands {
+        Some(commands) => kaspa_cli_exec(TerminalOptions::new().with_prompt(""), commands).await,
+        None => kaspa_cli(TerminalOptions::new().with_prompt("$ "), None).await,
+    };
     if let Err(err) = result {
         println!("{err}");
+        std::process::exit(1);
     }
 }
@@ -34,6 +34,11 @@ pub struct Global {
     /// A map from xpub to the used key fingerprint and derivation path as defined by BIP 32.
     pub xpubs: BTreeMap<Xpub, KeySource>,
     pub id: Option<TransactionId>,
+    #[builder(setter(strip_option))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The sighash fork id of the network the transaction is built for (see `Params::sighash_fork_id`), committed to by all
+    /// input signatures.
+    pub sighash_fork_id: Option<u8>,
     /// Proprietary key-value pairs for this output.
     pub proprietaries: BTreeMap<String, serde_value::Value>,
     /// Unknown key-value pairs for this output.
@@ -100,6 +105,9 @@ impl Add for Global {
             (Some(v), _) | (_, Some(v)) => Some(v),
             _ => None,
         };
+        if self.sighash_fork_id != rhs.sighash_fork_id {
+            return Err(CombineError::SighashForkIdMismatch { this: self.sighash_fork_id, that: rhs.sighash_fork_id });
+        }
 
         self.proprietaries =
             combine_if_no_conflicts(self.proprietaries, rhs.proprietaries).map_err(CombineError::NotCompatibleProprietary)?;
@@ -120,6 +128,7 @@ impl Default for Global {
             output_count: 0,
             xpubs: Default::default(),
             id: None,
+            sighash_fork_id: None,
             proprietaries: Default::default(),
             unknowns: Default::default(),
         }
@@ -158,6 +167,13 @@ pub enum CombineError {
         /// Into a PSKT with `that` tx id.
         that: TransactionId,
     },
+    #[error("The sighash fork ids are not the same")]
+    SighashForkIdMismatch {
+        /// Attempted to combine a PSKT with `this` sighash fork id.
+        this: Option<u8>,
+        /// Into a PSKT with `that` sighash fork id.
+        that: Option<u8>,
+    },
 
     #[error("combining PSKT, key-source conflict for xpub {0}")]
     /// Xpubs have inconsistent key sources.
@@ -2,8 +2,9 @@
 //! Partially Signed Kaspa Transaction (PSKT)
 //!
 
+use kaspa_addresses::{Address, Prefix, Version as AddressVersion};
 use kaspa_bip32::{secp256k1, DerivationPath, KeyFingerprint};
-use kaspa_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+use kaspa_consensus_core::hashing::sighash::{calc_schnorr_signature_hash_with_fork_id, SigHashReusedValuesUnsync};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::{collections::BTreeMap, fmt::Display, fmt::Formatter, future::Future, marker::PhantomData, ops::Deref};
@@ -13,13 +14,14 @@ pub use crate::global::{Global, GlobalBuilder};
 pub use crate::input::{Input, InputBuilder};
 pub use crate::output::{Output, OutputBuilder};
 pub use crate::role::{Combiner, Constructor, Creator, Extractor, Finalizer, Signer, Updater};
+use kaspa_consensus_core::sign::{KeypairSigner, SignatureRequest, Signer as TxSigner};
 use kaspa_consensus_core::tx::UtxoEntry;
 use kaspa_consensus_core::{
     hashing::sighash_type::SigHashType,
     subnets::SUBNETWORK_ID_NATIVE,
     tx::{MutableTransaction, SignableTransaction, Transaction, TransactionId, TransactionInput, TransactionOutput},
 };
-use kaspa_txscript::{caches::Cache, TxScriptEngine};
+use kaspa_txscript::{caches::Cache, pay_to_address_script, TxScriptEngine};
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -190,6 +192,12 @@ impl PSKT<Creator> {
         self
     }
 
+    /// Sets the sighash fork id of the network the transaction is built for, which all signatures must commit to.
+    pub fn sighash_fork_id(mut self, fork_id: u8) -> Self {
+        self.inner_pskt.global.sighash_fork_id = Some(fork_id);
+        self
+    }
+
     // todo generic const
     /// Sets the inputs modifiable bit in the transaction modifiable flags.
     pub fn inputs_modifiable(mut self) -> Self {
@@ -302,6 +310,82 @@ impl PSKT<Signer> {
         Ok(self)
     }
 
+    /// Builds a signature request for every input each of `public_keys` can spend: inputs locked
+    /// to the key's P2PK script and P2SH inputs whose redeem script references the key.
+    pub fn signature_requests(&self, public_keys: &[secp256k1::PublicKey]) -> Result<Vec<SignatureRequest>, Error> {
+        if self.inputs.iter().any(|input| input.utxo_entry.is_none()) {
+            return Err(Error::MissingUtxoEntry);
+        }
+
+        let unsigned_tx = self.unsigned_tx();
+        let tx = unsigned_tx.as_verifiable();
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let mut requests = vec![];
+        for (idx, input) in self.inputs.iter().enumerate() {
+            let script_public_key = &input.utxo_entry.as_ref().expect("checked above").script_public_key;
+            for public_key in public_keys {
+                let serialized = public_key.x_only_public_key().0.serialize();
+                let spendable = match input.redeem_script.as_ref() {
+                    Some(redeem_script) => redeem_script.windows(serialized.len()).any(|window| window == serialized),
+                    None => {
+                        let address = Address::new(Prefix::Mainnet, AddressVersion::PubKey, &serialized);
+                        pay_to_address_script(&address) == *script_public_key
+                    }
+                };
+                if spendable {
+                    let sig_hash = calc_schnorr_signature_hash_with_fork_id(
+                        &tx,
+                        idx,
+                        input.sighash_type,
+                        &reused_values,
+                        self.global.sighash_fork_id,
+                    );
+                    requests.push(SignatureRequest {
+                        input_index: idx,
+                        public_key: *public_key,
+                        sig_hash,
+                        hash_type: input.sighash_type,
+                    });
+                }
+            }
+        }
+
+        Ok(requests)
+    }
+
+    /// Records the signatures produced for `requests` as partial signatures of the corresponding inputs.
+    pub fn add_signatures(
+        mut self,
+        requests: &[SignatureRequest],
+        signatures: &[secp256k1::schnorr::Signature],
+    ) -> Result<Self, Error> {
+        if requests.len() != signatures.len() {
+            return Err(Error::Custom(format!("Expected {} signatures but received {}", requests.len(), signatures.len())));
+        }
+        for (request, signature) in requests.iter().zip(signatures) {
+            let input = self.inner_pskt.inputs.get_mut(request.input_index).ok_or(Error::OutOfBounds)?;
+            input.partial_sigs.insert(request.public_key, Signature::Schnorr(*signature));
+            input.bip32_derivations.entry(request.public_key).or_insert(None);
+        }
+        Ok(self)
+    }
+
+    /// Signs all inputs spendable by `signer`, which may be backed by in-memory keys or an external device.
+    /// Inputs not matching any of its keys are left untouched.
+    pub async fn sign_with(self, signer: &dyn TxSigner) -> Result<Self, Error> {
+        let requests = self.signature_requests(&signer.public_keys())?;
+        let signatures = signer.sign(requests.clone()).await.map_err(|err| Error::Custom(err.to_string()))?;
+        self.add_signatures(&requests, &signatures)
+    }
+
+    /// Synchronous variant of [`PSKT::sign_with`] using in-memory key pairs.
+    pub fn sign_schnorr(self, keypairs: &[secp256k1::Keypair]) -> Result<Self, Error> {
+        let signer = KeypairSigner::new(keypairs.to_vec());
+        let requests = self.signature_requests(&signer.public_keys())?;
+        let signatures = signer.sign_sync(&requests).map_err(|err| Error::Custom(err.to_string()))?;
+        self.add_signatures(&requests, &signatures)
+    }
+
     pub fn calculate_id(&self) -> TransactionId {
         self.calculate_id_internal()
     }
@@ -415,8 +499,8 @@ impl PSKT<Extractor> {
         let tx = self.unsigned_tx();
         let entries = tx.entries;
         let mut tx = tx.tx;
-        tx.inputs.iter_mut().zip(self.inner_pskt.inputs).try_for_each(|(dest, src)| {
-            dest.signature_script = src.final_script_sig.ok_or(TxNotFinalized {})?;
+        self.inner_pskt.inputs.into_iter().enumerate().try_for_each(|(index, src)| {
+            tx.set_signature_script(index, src.final_script_sig.ok_or(TxNotFinalized {})?);
             Ok(())
         })?;
         Ok(move |mass| {
@@ -426,6 +510,7 @@ impl PSKT<Extractor> {
     }
 
     pub fn extract_tx(self) -> Result<impl FnOnce(u64) -> (Transaction, Vec<Option<UtxoEntry>>), ExtractError> {
+        let sighash_fork_id = self.global.sighash_fork_id;
         let (tx, entries) = self.extract_tx_unchecked()?(0);
 
         let tx = MutableTransaction::with_entries(tx, entries.into_iter().flatten().collect());
@@ -436,7 +521,9 @@ impl PSKT<Extractor> {
             let reused_values = SigHashReusedValuesUnsync::new();
 
             tx.populated_inputs().enumerate().try_for_each(|(idx, (input, entry))| {
-                TxScriptEngine::from_transaction_input(&tx, input, idx, entry, &reused_values, &cache, false).execute()?;
+                TxScriptEngine::from_transaction_input(&tx, input, idx, entry, &reused_values, &cache, false)
+                    .with_sighash_fork_id(sighash_fork_id)
+                    .execute()?;
                 <Result<(), ExtractError>>::Ok(())
             })?;
         }
@@ -485,10 +572,80 @@ pub struct TxNotFinalized {}
 
 #[cfg(test)]
 mod tests {
-
-    // #[test]
-    // fn it_works() {
-    //     let result = add(2, 2);
-    //     assert_eq!(result, 4);
-    // }
+    use super::*;
+    use kaspa_consensus_core::tx::TransactionOutpoint;
+    use kaspa_txscript::opcodes::codes::OpData65;
+    use secp256k1::{rand::thread_rng, Keypair};
+    use std::{iter, str::FromStr};
+
+    fn p2pk_input(owner: &Keypair) -> Input {
+        let address = Address::new(Prefix::Testnet, AddressVersion::PubKey, &owner.x_only_public_key().0.serialize());
+        crate::input::InputBuilder::default()
+            .utxo_entry(UtxoEntry {
+                amount: 1_000_000,
+                script_public_key: pay_to_address_script(&address),
+                block_daa_score: 10,
+                is_coinbase: false,
+            })
+            .previous_outpoint(TransactionOutpoint {
+                transaction_id: TransactionId::from_str("63020db736215f8b1105a9281f7bcbb6473d965ecc45bb2fb5da59bd35e6ff84").unwrap(),
+                index: 0,
+            })
+            .sig_op_count(1)
+            .build()
+            .unwrap()
+    }
+
+    fn finalize_schnorr(signed: PSKT<Signer>) -> PSKT<Finalizer> {
+        signed
+            .finalizer()
+            .finalize_sync(|inner: &Inner| -> Result<Vec<Vec<u8>>, String> {
+                Ok(inner
+                    .inputs
+                    .iter()
+                    .map(|input| {
+                        let (_, signature) = input.partial_sigs.first_key_value().unwrap();
+                        iter::once(OpData65).chain(signature.into_bytes()).chain([input.sighash_type.to_u8()]).collect()
+                    })
+                    .collect())
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sign_schnorr_p2pk() {
+        let owner = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        let stranger = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        let signer = PSKT::<Creator>::default().constructor().input(p2pk_input(&owner)).signer();
+
+        // A key unrelated to the input must not produce a signature.
+        let untouched = signer.clone().sign_schnorr(&[stranger]).unwrap();
+        assert!(untouched.inputs[0].partial_sigs.is_empty());
+
+        let signed = signer.sign_schnorr(&[stranger, owner]).unwrap();
+        assert_eq!(signed.inputs[0].partial_sigs.len(), 1);
+        assert!(signed.inputs[0].partial_sigs.contains_key(&owner.public_key()));
+
+        // The signature must satisfy the script engine once finalized.
+        let finalized = finalize_schnorr(signed);
+        assert!(finalized.extractor().unwrap().extract_tx().is_ok());
+    }
+
+    #[test]
+    fn test_sign_with_sighash_fork_id() {
+        let owner = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        let signer = PSKT::<Creator>::default().sighash_fork_id(7).constructor().input(p2pk_input(&owner)).signer();
+        let finalized = finalize_schnorr(signer.sign_schnorr(&[owner]).unwrap());
+        assert!(finalized.clone().extractor().unwrap().extract_tx().is_ok());
+
+        // The signatures commit to the fork id, so verifying them for a network without it fails
+        let mut stripped = finalized;
+        stripped.inner_pskt.global.sighash_fork_id = None;
+        assert!(matches!(stripped.extractor().unwrap().extract_tx(), Err(ExtractError::TxScriptError(_))));
+
+        // PSKTs built for different networks can not be combined
+        let other = PSKT::<Creator>::default().sighash_fork_id(8).constructor().combiner();
+        let this = PSKT::<Creator>::default().sighash_fork_id(7).constructor().combiner();
+        assert!(matches!(this + other, Err(CombineError::Global(crate::global::CombineError::SighashForkIdMismatch { .. }))));
+    }
 }
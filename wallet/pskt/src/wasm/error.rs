@@ -59,6 +59,6 @@ impl From<String> for Error {
 
 impl From<Error> for JsValue {
     fn from(err: Error) -> Self {
-        JsValue::from_str(&err.to_string())
+        js_sys::Error::new(&err.to_string()).into()
     }
 }
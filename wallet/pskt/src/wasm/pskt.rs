@@ -4,8 +4,11 @@ use kaspa_consensus_core::tx::TransactionId;
 use wasm_bindgen::prelude::*;
 // use js_sys::Object;
 use crate::pskt::Inner;
+use kaspa_bip32::secp256k1;
 use kaspa_consensus_client::{Transaction, TransactionInput, TransactionInputT, TransactionOutput, TransactionOutputT};
+use kaspa_wallet_keys::privatekey::PrivateKey;
 use serde::{Deserialize, Serialize};
+use std::ops::Deref;
 use std::sync::MutexGuard;
 use std::sync::{Arc, Mutex};
 use workflow_wasm::{
@@ -62,6 +65,9 @@ impl From<State> for PSKT {
 extern "C" {
     #[wasm_bindgen(typescript_type = "PSKT | Transaction | string | undefined")]
     pub type CtorT;
+
+    #[wasm_bindgen(typescript_type = "(PrivateKey | HexString | Uint8Array)[]")]
+    pub type PrivateKeyArrayT;
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -75,7 +81,7 @@ impl<T> TryFrom<Payload> for Native<T> {
     fn try_from(value: Payload) -> Result<Self> {
         let Payload { data } = value;
         if data.starts_with("PSKT") {
-            unimplemented!("PSKT binary serialization")
+            Ok(Native::from_hex(&data)?)
         } else {
             Ok(serde_json::from_str(&data).map_err(|err| format!("Invalid JSON: {err}"))?)
         }
@@ -96,7 +102,11 @@ impl TryCastFromJs for PSKT {
     {
         Self::resolve(value, || {
             if let Some(data) = value.as_ref().as_string() {
-                let pskt_inner: Inner = serde_json::from_str(&data).map_err(|_| Error::InvalidPayload)?;
+                let pskt_inner: Inner = if data.starts_with("PSKT") {
+                    Native::<Signer>::from_hex(&data).map_err(|_| Error::InvalidPayload)?.deref().clone()
+                } else {
+                    serde_json::from_str(&data).map_err(|_| Error::InvalidPayload)?
+                };
                 Ok(PSKT::from(State::NoOp(Some(pskt_inner))))
             } else if let Ok(transaction) = Transaction::try_owned_from(value) {
                 let pskt_inner: Inner = transaction.try_into()?;
@@ -116,22 +126,22 @@ impl PSKT {
     }
 
     #[wasm_bindgen(getter, js_name = "role")]
-    pub fn role_getter(&self) -> String {
-        self.state().as_ref().unwrap().display().to_string()
+    pub fn role_getter(&self) -> Result<String> {
+        Ok(self.state().as_ref().ok_or(Error::NotInitialized)?.display().to_string())
     }
 
     #[wasm_bindgen(getter, js_name = "payload")]
-    pub fn payload_getter(&self) -> JsValue {
+    pub fn payload_getter(&self) -> Result<JsValue> {
         let state = self.state();
-        serde_wasm_bindgen::to_value(state.as_ref().unwrap()).unwrap()
+        serde_wasm_bindgen::to_value(state.as_ref().ok_or(Error::NotInitialized)?).map_err(Error::custom)
     }
 
     fn state(&self) -> MutexGuard<Option<State>> {
         self.state.lock().unwrap()
     }
 
-    fn take(&self) -> State {
-        self.state.lock().unwrap().take().unwrap()
+    fn take(&self) -> Result<State> {
+        self.state.lock().unwrap().take().ok_or(Error::NotInitialized)
     }
 
     fn replace(&self, state: State) -> Result<PSKT> {
@@ -139,15 +149,26 @@ impl PSKT {
         Ok(self.clone())
     }
 
+    /// Puts back a state that does not support the requested transition,
+    /// leaving the PSKT usable after the error is reported.
+    fn invalid_state(&self, state: State) -> Error {
+        let err = Error::state(&state);
+        self.state.lock().unwrap().replace(state);
+        err
+    }
+
     /// Change role to `CREATOR`
     /// #[wasm_bindgen(js_name = toCreator)]
     pub fn creator(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::NoOp(inner) => match inner {
                 None => State::Creator(Native::default()),
-                Some(_) => Err(Error::CreateNotAllowed)?,
+                Some(inner) => {
+                    self.replace(State::NoOp(Some(inner)))?;
+                    Err(Error::CreateNotAllowed)?
+                }
             },
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -156,10 +177,10 @@ impl PSKT {
     /// Change role to `CONSTRUCTOR`
     #[wasm_bindgen(js_name = toConstructor)]
     pub fn constructor(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::NoOp(inner) => State::Constructor(inner.ok_or(Error::NotInitialized)?.into()),
             State::Creator(pskt) => State::Constructor(pskt.constructor()),
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -168,10 +189,10 @@ impl PSKT {
     /// Change role to `UPDATER`
     #[wasm_bindgen(js_name = toUpdater)]
     pub fn updater(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::NoOp(inner) => State::Updater(inner.ok_or(Error::NotInitialized)?.into()),
             State::Constructor(constructor) => State::Updater(constructor.updater()),
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -180,12 +201,12 @@ impl PSKT {
     /// Change role to `SIGNER`
     #[wasm_bindgen(js_name = toSigner)]
     pub fn signer(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::NoOp(inner) => State::Signer(inner.ok_or(Error::NotInitialized)?.into()),
             State::Constructor(pskt) => State::Signer(pskt.signer()),
             State::Updater(pskt) => State::Signer(pskt.signer()),
             State::Combiner(pskt) => State::Signer(pskt.signer()),
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -194,12 +215,12 @@ impl PSKT {
     /// Change role to `COMBINER`
     #[wasm_bindgen(js_name = toCombiner)]
     pub fn combiner(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::NoOp(inner) => State::Combiner(inner.ok_or(Error::NotInitialized)?.into()),
             State::Constructor(pskt) => State::Combiner(pskt.combiner()),
             State::Updater(pskt) => State::Combiner(pskt.combiner()),
             State::Signer(pskt) => State::Combiner(pskt.combiner()),
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -208,10 +229,10 @@ impl PSKT {
     /// Change role to `FINALIZER`
     #[wasm_bindgen(js_name = toFinalizer)]
     pub fn finalizer(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::NoOp(inner) => State::Finalizer(inner.ok_or(Error::NotInitialized)?.into()),
             State::Combiner(pskt) => State::Finalizer(pskt.finalizer()),
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -220,10 +241,16 @@ impl PSKT {
     /// Change role to `EXTRACTOR`
     #[wasm_bindgen(js_name = toExtractor)]
     pub fn extractor(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::NoOp(inner) => State::Extractor(inner.ok_or(Error::NotInitialized)?.into()),
-            State::Finalizer(pskt) => State::Extractor(pskt.extractor()?),
-            state => Err(Error::state(state))?,
+            State::Finalizer(pskt) => match pskt.clone().extractor() {
+                Ok(extractor) => State::Extractor(extractor),
+                Err(err) => {
+                    self.replace(State::Finalizer(pskt))?;
+                    Err(err)?
+                }
+            },
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -231,9 +258,9 @@ impl PSKT {
 
     #[wasm_bindgen(js_name = fallbackLockTime)]
     pub fn fallback_lock_time(&self, lock_time: u64) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::Creator(pskt) => State::Creator(pskt.fallback_lock_time(lock_time)),
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -241,9 +268,9 @@ impl PSKT {
 
     #[wasm_bindgen(js_name = inputsModifiable)]
     pub fn inputs_modifiable(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::Creator(pskt) => State::Creator(pskt.inputs_modifiable()),
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -251,9 +278,9 @@ impl PSKT {
 
     #[wasm_bindgen(js_name = outputsModifiable)]
     pub fn outputs_modifiable(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::Creator(pskt) => State::Creator(pskt.outputs_modifiable()),
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -261,9 +288,9 @@ impl PSKT {
 
     #[wasm_bindgen(js_name = noMoreInputs)]
     pub fn no_more_inputs(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::Constructor(pskt) => State::Constructor(pskt.no_more_inputs()),
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -271,29 +298,29 @@ impl PSKT {
 
     #[wasm_bindgen(js_name = noMoreOutputs)]
     pub fn no_more_outputs(&self) -> Result<PSKT> {
-        let state = match self.take() {
+        let state = match self.take()? {
             State::Constructor(pskt) => State::Constructor(pskt.no_more_outputs()),
-            state => Err(Error::state(state))?,
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
     }
 
     pub fn input(&self, input: &TransactionInputT) -> Result<PSKT> {
-        let input = TransactionInput::try_owned_from(input)?;
-        let state = match self.take() {
-            State::Constructor(pskt) => State::Constructor(pskt.input(input.try_into()?)),
-            state => Err(Error::state(state))?,
+        let input = TransactionInput::try_owned_from(input)?.try_into()?;
+        let state = match self.take()? {
+            State::Constructor(pskt) => State::Constructor(pskt.input(input)),
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
     }
 
     pub fn output(&self, output: &TransactionOutputT) -> Result<PSKT> {
-        let output = TransactionOutput::try_owned_from(output)?;
-        let state = match self.take() {
-            State::Constructor(pskt) => State::Constructor(pskt.output(output.try_into()?)),
-            state => Err(Error::state(state))?,
+        let output = TransactionOutput::try_owned_from(output)?.try_into()?;
+        let state = match self.take()? {
+            State::Constructor(pskt) => State::Constructor(pskt.output(output)),
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -301,9 +328,15 @@ impl PSKT {
 
     #[wasm_bindgen(js_name = setSequence)]
     pub fn set_sequence(&self, n: u64, input_index: usize) -> Result<PSKT> {
-        let state = match self.take() {
-            State::Updater(pskt) => State::Updater(pskt.set_sequence(n, input_index)?),
-            state => Err(Error::state(state))?,
+        let state = match self.take()? {
+            State::Updater(pskt) => match pskt.clone().set_sequence(n, input_index) {
+                Ok(updater) => State::Updater(updater),
+                Err(err) => {
+                    self.replace(State::Updater(pskt))?;
+                    Err(err)?
+                }
+            },
+            state => Err(self.invalid_state(state))?,
         };
 
         self.replace(state)
@@ -312,9 +345,80 @@ impl PSKT {
     #[wasm_bindgen(js_name = calculateId)]
     pub fn calculate_id(&self) -> Result<TransactionId> {
         let state = self.state();
-        match state.as_ref().unwrap() {
+        match state.as_ref().ok_or(Error::NotInitialized)? {
             State::Signer(pskt) => Ok(pskt.calculate_id()),
             state => Err(Error::state(state))?,
         }
     }
+
+    /// Sign all inputs spendable by the supplied private keys (requires `SIGNER` role).
+    /// Inputs that none of the keys can spend are left unsigned, allowing the PSKT
+    /// to be passed on to other signers and merged using `combine()`.
+    pub fn sign(&self, private_keys: &PrivateKeyArrayT) -> Result<PSKT> {
+        let private_keys = private_keys.unchecked_ref::<js_sys::Array>();
+        let keypairs = private_keys
+            .iter()
+            .map(|key| {
+                let key = PrivateKey::try_owned_from(&key).map_err(|err| Error::custom(format!("Invalid private key: {err}")))?;
+                secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &key.secret_bytes()).map_err(Error::custom)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let state = match self.take()? {
+            State::Signer(pskt) => match pskt.clone().sign_schnorr(&keypairs) {
+                Ok(signer) => State::Signer(signer),
+                Err(err) => {
+                    self.replace(State::Signer(pskt))?;
+                    Err(err)?
+                }
+            },
+            state => Err(self.invalid_state(state))?,
+        };
+
+        self.replace(state)
+    }
+
+    /// Merge signatures and data of another PSKT of the same transaction into this one
+    /// (requires `COMBINER` role).
+    pub fn combine(&self, other: &PSKT) -> Result<PSKT> {
+        let other = match other.state().as_ref().ok_or(Error::NotInitialized)? {
+            State::NoOp(inner) => inner.clone().ok_or(Error::NotInitialized)?,
+            State::Creator(pskt) => pskt.deref().clone(),
+            State::Constructor(pskt) => pskt.deref().clone(),
+            State::Updater(pskt) => pskt.deref().clone(),
+            State::Signer(pskt) => pskt.deref().clone(),
+            State::Combiner(pskt) => pskt.deref().clone(),
+            State::Finalizer(pskt) => pskt.deref().clone(),
+            State::Extractor(pskt) => pskt.deref().clone(),
+        };
+
+        let state = match self.take()? {
+            State::Combiner(pskt) => match pskt.clone() + Native::<Combiner>::from(other) {
+                Ok(combined) => State::Combiner(combined),
+                Err(err) => {
+                    self.replace(State::Combiner(pskt))?;
+                    Err(Error::custom(err))?
+                }
+            },
+            state => Err(self.invalid_state(state))?,
+        };
+
+        self.replace(state)
+    }
+
+    /// Serialize the PSKT into a `PSKT`-prefixed hex string accepted by the constructor.
+    pub fn serialize(&self) -> Result<String> {
+        let state = self.state();
+        let hex = match state.as_ref().ok_or(Error::NotInitialized)? {
+            State::NoOp(inner) => Native::<Signer>::from(inner.clone().ok_or(Error::NotInitialized)?).to_hex(),
+            State::Creator(pskt) => pskt.to_hex(),
+            State::Constructor(pskt) => pskt.to_hex(),
+            State::Updater(pskt) => pskt.to_hex(),
+            State::Signer(pskt) => pskt.to_hex(),
+            State::Combiner(pskt) => pskt.to_hex(),
+            State::Finalizer(pskt) => pskt.to_hex(),
+            State::Extractor(pskt) => pskt.to_hex(),
+        };
+        hex.map_err(Error::from)
+    }
 }